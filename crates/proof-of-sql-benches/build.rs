@@ -0,0 +1,20 @@
+//! Injects the git commit the benchmarks are built from as `GIT_HASH`, so that timing rows can
+//! record it.
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=GIT_HASH");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    if std::env::var_os("GIT_HASH").is_some() {
+        return;
+    }
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    if let Some(git_hash) = git_hash {
+        println!("cargo:rustc-env=GIT_HASH={}", git_hash.trim());
+    }
+}
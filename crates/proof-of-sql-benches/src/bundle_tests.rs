@@ -0,0 +1,114 @@
+//! Compares a [`VerifiableQueryResultBundle`] of the benchmark queries with separate
+//! [`VerifiableQueryResult`]s of them.
+use super::utils::{
+    benchmark_accessor::BenchmarkAccessor, queries::all_queries,
+    random_util::generate_random_columns,
+};
+use ark_std::test_rng;
+use bumpalo::Bump;
+use datafusion::config::ConfigOptions;
+use proof_of_sql::{
+    base::database::TableRef,
+    proof_primitive::dory::{
+        DynamicDoryCommitment, DynamicDoryEvaluationProof, ProverSetup, PublicParameters,
+        VerifierSetup,
+    },
+    sql::proof::{VerifiableQueryResult, VerifiableQueryResultBundle},
+};
+use proof_of_sql_planner::sql_to_proof_plans;
+use rand::{rngs::StdRng, SeedableRng};
+use sqlparser::dialect::GenericDialect;
+use std::time::{Duration, Instant};
+
+const TABLE_SIZE: usize = 100;
+
+/// Add `offset` to the number of every placeholder, e.g. turn `$1` into `$3` for an offset of 2
+fn offset_placeholders(sql: &str, offset: usize) -> String {
+    let mut result = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        result.push(c);
+        if c == '$' {
+            let mut number = String::new();
+            while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                number.push(digit);
+            }
+            if !number.is_empty() {
+                result.push_str(&(number.parse::<usize>().unwrap() + offset).to_string());
+            }
+        }
+    }
+    result
+}
+
+/// Prove all benchmark queries, each over its own table, once as a bundle and once separately,
+/// and check that the bundle is smaller. The proof sizes and verification times are printed.
+#[test]
+fn a_bundle_of_the_benchmark_queries_is_smaller_than_separate_proofs() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let alloc = Bump::new();
+    let mut accessor: BenchmarkAccessor<'_, DynamicDoryCommitment> = BenchmarkAccessor::default();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    // Each query gets its own table and its own placeholders, since the bundle shares both
+    let mut plans = Vec::new();
+    let mut params = Vec::new();
+    for (index, (_, sql, columns, query_params)) in all_queries().into_iter().enumerate() {
+        let table_name = format!("bench_table_{index}");
+        accessor.insert_table(
+            TableRef::from_names(None, &table_name),
+            &generate_random_columns(&alloc, &mut rng, &columns, TABLE_SIZE),
+            &&prover_setup,
+        );
+        let sql = offset_placeholders(&sql.replace("bench_table", &table_name), params.len());
+        let statements = sqlparser::parser::Parser::parse_sql(&GenericDialect {}, &sql).unwrap();
+        let query_plans =
+            sql_to_proof_plans(&statements, &accessor, &ConfigOptions::default()).unwrap();
+        plans.extend(query_plans);
+        params.extend(query_params);
+    }
+    assert!(plans.len() > 1);
+
+    let bundle = VerifiableQueryResultBundle::<DynamicDoryEvaluationProof>::new(
+        &plans,
+        &accessor,
+        &&prover_setup,
+        &params,
+    )
+    .unwrap();
+    let bundle_proof_bytes = bundle.encoded_size_hint().proof_bytes;
+    let start = Instant::now();
+    let query_results = bundle
+        .verify_bundle(&plans, &accessor, &&verifier_setup, &params)
+        .unwrap();
+    let bundle_verify_time = start.elapsed();
+    assert!(query_results
+        .into_iter()
+        .all(|query_result| query_result.is_ok()));
+
+    let mut separate_proof_bytes = 0;
+    let mut separate_verify_time = Duration::ZERO;
+    for plan in &plans {
+        let res = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+            plan,
+            &accessor,
+            &&prover_setup,
+            &params,
+        )
+        .unwrap();
+        separate_proof_bytes += res.encoded_size_hint().proof_bytes;
+        let start = Instant::now();
+        res.verify(plan, &accessor, &&verifier_setup, &params)
+            .unwrap();
+        separate_verify_time += start.elapsed();
+    }
+
+    println!(
+        "{} plans: bundle proof of {bundle_proof_bytes} bytes verified in {bundle_verify_time:?}, \
+         separate proofs of {separate_proof_bytes} bytes verified in {separate_verify_time:?}",
+        plans.len()
+    );
+    assert!(bundle_proof_bytes < separate_proof_bytes);
+}
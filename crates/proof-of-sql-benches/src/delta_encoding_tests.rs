@@ -0,0 +1,70 @@
+//! Compares committing to a smooth `BigInt` column stored as a [`DeltaEncodedColumn`] with
+//! committing to the plain column.
+use ark_std::test_rng;
+use proof_of_sql::{
+    base::{
+        commitment::{Commitment, CommittableColumn},
+        database::DeltaEncodedColumn,
+    },
+    proof_primitive::dory::{DynamicDoryCommitment, ProverSetup, PublicParameters},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+use std::time::Instant;
+
+const TABLE_SIZE: usize = 1_000;
+
+fn bincode_size(value: &impl Serialize) -> usize {
+    bincode::serde::encode_to_vec(value, bincode::config::standard())
+        .unwrap()
+        .len()
+}
+
+/// Millisecond timestamps of readings taken about once a second, starting in 2023
+fn smooth_timestamps(rng: &mut impl Rng) -> Vec<i64> {
+    (0..TABLE_SIZE)
+        .scan(1_700_000_000_000_i64, |timestamp, _| {
+            *timestamp += rng.gen_range(990..=1_010);
+            Some(*timestamp)
+        })
+        .collect()
+}
+
+/// Commit to smooth data both as deltas and as plain values, and check that the commitments are
+/// equal and so have the same size, while the deltas take up less space with a variable length
+/// encoding. The sizes and commitment times are printed.
+#[test]
+fn a_delta_encoded_column_has_the_same_commitment_as_the_plain_column() {
+    let public_parameters = PublicParameters::test_rand(6, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let mut rng = StdRng::seed_from_u64(42);
+    let values = smooth_timestamps(&mut rng);
+    let deltas = DeltaEncodedColumn::try_from_values(&values).unwrap();
+
+    let start = Instant::now();
+    let delta_commitments = DynamicDoryCommitment::compute_commitments(
+        &[CommittableColumn::DeltaEncoded(&deltas)],
+        0,
+        &&prover_setup,
+    );
+    let delta_commit_time = start.elapsed();
+    let start = Instant::now();
+    let plain_commitments = DynamicDoryCommitment::compute_commitments(
+        &[CommittableColumn::BigInt(&values)],
+        0,
+        &&prover_setup,
+    );
+    let plain_commit_time = start.elapsed();
+    assert_eq!(delta_commitments, plain_commitments);
+
+    let commitment_bytes = bincode_size(&plain_commitments[0]);
+    assert_eq!(bincode_size(&delta_commitments[0]), commitment_bytes);
+    let delta_bytes = bincode_size(&deltas.deltas());
+    let plain_bytes = bincode_size(&values.as_slice());
+    println!(
+        "{TABLE_SIZE} rows: commitment of {commitment_bytes} bytes either way, \
+         deltas of {delta_bytes} bytes committed in {delta_commit_time:?}, \
+         values of {plain_bytes} bytes committed in {plain_commit_time:?}"
+    );
+    assert!(delta_bytes < plain_bytes);
+}
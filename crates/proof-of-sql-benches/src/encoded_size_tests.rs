@@ -0,0 +1,107 @@
+//! Tests that [`VerifiableQueryResult::encoded_size_hint`] matches the actual serialized sizes
+//! for the benchmark queries.
+use super::utils::{
+    benchmark_accessor::BenchmarkAccessor, queries::all_queries,
+    random_util::generate_random_columns,
+};
+use ark_std::test_rng;
+use bumpalo::Bump;
+use datafusion::config::ConfigOptions;
+use nova_snark::{
+    provider::hyperkzg::{CommitmentEngine, CommitmentKey},
+    traits::commitment::CommitmentEngineTrait,
+};
+use proof_of_sql::{
+    base::{commitment::CommitmentEvaluationProof, database::TableRef},
+    proof_primitive::{
+        dory::{
+            DoryEvaluationProof, DoryProverPublicSetup, DynamicDoryEvaluationProof, ProverSetup,
+            PublicParameters,
+        },
+        hyperkzg::{
+            nova_commitment_key_to_hyperkzg_public_setup, HyperKZGCommitmentEvaluationProof,
+            HyperKZGEngine,
+        },
+    },
+    sql::proof::VerifiableQueryResult,
+};
+use proof_of_sql_planner::sql_to_proof_plans;
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+use sqlparser::dialect::GenericDialect;
+
+const TABLE_SIZE: usize = 100;
+
+fn bincode_size(value: &impl Serialize, legacy: bool) -> usize {
+    if legacy {
+        bincode::serde::encode_to_vec(value, bincode::config::legacy())
+    } else {
+        bincode::serde::encode_to_vec(value, bincode::config::standard())
+    }
+    .unwrap()
+    .len()
+}
+
+/// Prove every benchmark query and check that the size hints are exact for bincode's legacy
+/// configuration and upper bounds for its standard configuration.
+fn assert_encoded_size_hints_match_bincode_sizes<CP>(prover_setup: CP::ProverPublicSetup<'_>)
+where
+    CP: CommitmentEvaluationProof + Serialize,
+{
+    let alloc = Bump::new();
+    let mut accessor: BenchmarkAccessor<'_, CP::Commitment> = BenchmarkAccessor::default();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for (query, sql, columns, params) in all_queries() {
+        accessor.insert_table(
+            TableRef::from_names(None, "bench_table"),
+            &generate_random_columns(&alloc, &mut rng, &columns, TABLE_SIZE),
+            &prover_setup,
+        );
+        let statements = sqlparser::parser::Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        let plans = sql_to_proof_plans(&statements, &accessor, &ConfigOptions::default()).unwrap();
+        for plan in plans {
+            let res =
+                VerifiableQueryResult::<CP>::new(&plan, &accessor, &prover_setup, &params).unwrap();
+            let hint = res.encoded_size_hint();
+            assert_eq!(hint.total, hint.proof_bytes + hint.result_bytes);
+
+            assert_eq!(hint.proof_bytes, bincode_size(&res.proof, true), "{query}");
+            assert_eq!(
+                hint.result_bytes,
+                bincode_size(&res.result, true),
+                "{query}"
+            );
+            assert_eq!(hint.total, bincode_size(&res, true), "{query}");
+
+            assert!(
+                hint.proof_bytes >= bincode_size(&res.proof, false),
+                "{query}"
+            );
+            assert!(
+                hint.result_bytes >= bincode_size(&res.result, false),
+                "{query}"
+            );
+            assert!(hint.total >= bincode_size(&res, false), "{query}");
+        }
+    }
+}
+
+#[test]
+fn we_can_hint_the_encoded_sizes_of_the_benchmark_queries_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    assert_encoded_size_hints_match_bincode_sizes::<DoryEvaluationProof>(
+        DoryProverPublicSetup::new(&prover_setup, 5),
+    );
+    assert_encoded_size_hints_match_bincode_sizes::<DynamicDoryEvaluationProof>(&prover_setup);
+}
+
+#[test]
+fn we_can_hint_the_encoded_sizes_of_the_benchmark_queries_with_hyperkzg() {
+    let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 4 * TABLE_SIZE);
+    let prover_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+    assert_encoded_size_hints_match_bincode_sizes::<HyperKZGCommitmentEvaluationProof>(
+        &prover_setup,
+    );
+}
@@ -17,15 +17,27 @@
 //! - `-x` `--silent` - Silence console output (default: `false`)
 //! - `-h` `--write_header` - Write CVS header to console (default: `false`)
 //! - `-c` `--csv_path` - Path to the CSV file for storing timing results (Optional)
+//! - `-j` `--jsonl_path` - Path to the JSON Lines file for storing timing results (Optional)
 //! - `-b` `--blitzar_handle_path` - Path to the Blitzar handle used for `DynamicDory` (Optional)
-//! - `-d` `--dory_public_params_path` - Path to the public parameters used for `DynamicDory` (Optional)
+//! - `-d` `--dory_public_params_path` - Path to the public parameters or prover setup file used for `DynamicDory` (Optional)
 //! - `-p` `--ppot_path` - Path to the Perpetual Powers of Tau file used for `HyperKZG` (Optional)
 //!
 //! # Optional File Path Environment Variables
 //! - `CSV_PATH` - Path to the CSV file for storing timing results
+//! - `JSONL_PATH` - Path to the JSON Lines file for storing timing results
 //! - `BLITZAR_HANDLE_PATH` - Path to the Blitzar handle used for `Dory` and `DynamicDory` commitment schemes
 //! - `DORY_PUBLIC_PARAMS_PATH` - Path to the public parameters used for `Dory` and `DynamicDory` commitment schemes
 //! - `PPOT_PATH` - Path to the Perpetual Powers of Tau file used for `HyperKZG` commitment scheme
+//!
+//! # Setup size
+//! Before proving a query, the number of generators the proof needs is printed next to the number
+//! of generators the loaded setup has, so that the setup can be sized to the table size.
+//!
+//! # Timing rows
+//! Besides the timings, each row records the setup parameters (`nu_sigma`, the setup source, which
+//! is `test-rand` or the file name of the setup, and whether a Blitzar handle was loaded), the crate
+//! version, and the git commit. The first column is the format version, see
+//! `utils::results_io::RESULTS_FORMAT_VERSION`.
 
 use ark_serialize::Validate;
 use ark_std::{rand, test_rng};
@@ -33,25 +45,24 @@ use blitzar::{compute::init_backend, proof::InnerProductProof};
 use bumpalo::Bump;
 use clap::{ArgAction, Parser, ValueEnum};
 use datafusion::config::ConfigOptions;
-use halo2curves::bn256::G2Affine;
 use nova_snark::{
-    provider::{
-        bn256_grumpkin::bn256::Affine,
-        hyperkzg::{CommitmentEngine, CommitmentKey, EvaluationEngine},
-    },
+    provider::hyperkzg::{CommitmentEngine, CommitmentKey, EvaluationEngine},
     traits::{commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait},
 };
 use proof_of_sql::{
-    base::{commitment::CommitmentEvaluationProof, database::TableRef},
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::TableRef,
+    },
     proof_primitive::{
         dory::{
-            DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
-            DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
+            DoryEvaluationProof, DynamicDoryEvaluationProof, ProverSetup, ProverSetupFile,
+            PublicParameters, SharedDorySetup, VerifierSetup,
         },
         hyperkzg::{
             deserialize_flat_compressed_hyperkzg_public_setup_from_reader,
-            nova_commitment_key_to_hyperkzg_public_setup, HyperKZGCommitmentEvaluationProof,
-            HyperKZGEngine,
+            hyperkzg_verifier_key_from_public_setup, nova_commitment_key_to_hyperkzg_public_setup,
+            HyperKZGCommitmentEvaluationProof, HyperKZGEngine,
         },
     },
     sql::proof::VerifiableQueryResult,
@@ -61,13 +72,19 @@ use rand::{rngs::StdRng, SeedableRng};
 use sqlparser::dialect::GenericDialect;
 use std::{path::PathBuf, time::Instant};
 use tracing::{span, Level};
+#[cfg(test)]
+mod bundle_tests;
+#[cfg(test)]
+mod delta_encoding_tests;
+#[cfg(test)]
+mod encoded_size_tests;
 mod utils;
 use utils::{
     benchmark_accessor::BenchmarkAccessor,
     jaeger_setup::{setup_jaeger_tracing, stop_jaeger_tracing},
     queries::{all_queries, get_query, QueryEntry},
     random_util::generate_random_columns,
-    results_io::append_to_csv,
+    results_io::{append_to_csv, append_to_jsonl, header_line, SetupParameters, TimingRow},
 };
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -170,6 +187,10 @@ struct Cli {
     #[arg(short, long, env)]
     csv_path: Option<PathBuf>,
 
+    /// Optional path to the JSON Lines file for storing results
+    #[arg(short, long, env)]
+    jsonl_path: Option<PathBuf>,
+
     /// Optional path to the Blitzar handle used for the `Dory` and `DynamicDory` commitment schemes
     #[arg(short, long, env)]
     blitzar_handle_path: Option<PathBuf>,
@@ -208,6 +229,7 @@ fn bench_by_schema<CP: CommitmentEvaluationProof>(
     schema: &str,
     cli: &Cli,
     queries: &[QueryEntry],
+    setup: &SetupParameters,
     prover_setup: CP::ProverPublicSetup<'_>,
     verifier_setup: CP::VerifierPublicSetup<'_>,
 ) {
@@ -229,6 +251,17 @@ fn bench_by_schema<CP: CommitmentEvaluationProof>(
 
         // Prove and verify the plans
         for plan in plans {
+            if !cli.silence {
+                let required = plan.required_setup_size(&accessor, params).unwrap();
+                match CP::Commitment::num_generators(&prover_setup) {
+                    Some(available) => eprintln!(
+                        "{schema} - {query}: requires {required} generators, setup has {available}"
+                    ),
+                    None => eprintln!(
+                        "{schema} - {query}: requires {required} generators, setup is unbounded"
+                    ),
+                }
+            }
             for i in 0..cli.iterations {
                 let span = span!(
                     Level::DEBUG,
@@ -255,19 +288,24 @@ fn bench_by_schema<CP: CommitmentEvaluationProof>(
 
                 span.exit();
 
+                let row = TimingRow {
+                    commitment_scheme: schema,
+                    query,
+                    table_size: cli.table_size,
+                    generate_proof_ms: generate_proof_elapsed,
+                    verify_proof_ms: verify_elapsed,
+                    iteration: i,
+                    setup,
+                };
+
                 // Append results to CSV file
                 if let Some(csv_path) = &cli.csv_path {
-                    append_to_csv(
-                        csv_path,
-                        &[
-                            schema.to_string(),
-                            (*query).to_string(),
-                            cli.table_size.to_string(),
-                            generate_proof_elapsed.to_string(),
-                            verify_elapsed.to_string(),
-                            i.to_string(),
-                        ],
-                    );
+                    append_to_csv(csv_path, &row);
+                }
+
+                // Append results to JSON Lines file
+                if let Some(jsonl_path) = &cli.jsonl_path {
+                    append_to_jsonl(jsonl_path, &row);
                 }
 
                 // Print results to console
@@ -275,10 +313,7 @@ fn bench_by_schema<CP: CommitmentEvaluationProof>(
                     eprintln!("Number of query results: {num_query_results}");
                     eprintln!("{schema} - generate proof: {generate_proof_elapsed} ms");
                     eprintln!("{schema} - verify proof: {verify_elapsed} ms");
-                    println!(
-                        "{schema},{query},{},{generate_proof_elapsed},{verify_elapsed},{i}",
-                        cli.table_size
-                    );
+                    println!("{}", row.to_csv_line());
                 }
             }
         }
@@ -292,53 +327,105 @@ fn bench_by_schema<CP: CommitmentEvaluationProof>(
 /// * `queries` - A slice of query entries to benchmark.
 #[tracing::instrument(name = "Inner Product Proof", level = "debug", skip_all)]
 fn bench_inner_product_proof(cli: &Cli, queries: &[QueryEntry]) {
-    bench_by_schema::<InnerProductProof>("Inner Product Proof", cli, queries, (), ());
+    bench_by_schema::<InnerProductProof>(
+        "Inner Product Proof",
+        cli,
+        queries,
+        &SetupParameters::none(),
+        (),
+        (),
+    );
+}
+
+/// The Dory public parameters, either serialized or as a memory-mapped prover setup file.
+enum DoryPublicParameters {
+    Deserialized(PublicParameters),
+    Mapped(ProverSetupFile),
 }
 
 /// Loads the Dory public parameters.
 ///
+/// The optional file may either contain serialized public parameters or be a prover setup file,
+/// which is memory-mapped instead.
+///
 /// # Arguments
 /// * `cli` - A reference to the command line interface arguments.
 ///
 /// # Panics
 /// * The optional Dory public parameters file is defined but can't be loaded.
-fn load_dory_public_parameters(cli: &Cli) -> PublicParameters {
+#[tracing::instrument(name = "Dory public parameters", level = "debug", skip_all)]
+fn load_dory_public_parameters(cli: &Cli) -> DoryPublicParameters {
     if let Some(dory_public_params_path) = &cli.dory_public_params_path {
-        PublicParameters::load_from_file(std::path::Path::new(&dory_public_params_path))
-            .expect("Failed to load Dory public parameters")
+        let path = std::path::Path::new(&dory_public_params_path);
+        if ProverSetupFile::is_prover_setup_file(path)
+            .expect("Failed to read Dory public parameters")
+        {
+            DoryPublicParameters::Mapped(
+                ProverSetupFile::open(path).expect("Failed to open Dory prover setup file"),
+            )
+        } else {
+            DoryPublicParameters::Deserialized(
+                PublicParameters::load_from_file(path)
+                    .expect("Failed to load Dory public parameters"),
+            )
+        }
     } else {
-        PublicParameters::test_rand(cli.nu_sigma, &mut test_rng())
+        DoryPublicParameters::Deserialized(PublicParameters::test_rand(
+            cli.nu_sigma,
+            &mut test_rng(),
+        ))
     }
 }
 
+/// Returns the parameters of the Dory setup, as recorded in the timing rows.
+///
+/// # Arguments
+/// * `cli` - A reference to the command line interface arguments.
+fn dory_setup_parameters(cli: &Cli) -> SetupParameters {
+    SetupParameters::new(
+        Some(cli.nu_sigma),
+        cli.dory_public_params_path.as_deref(),
+        cli.blitzar_handle_path.is_some(),
+    )
+}
+
 /// Loads the Dory setup for the given public parameters.
 ///
+/// The setup is shared between the `Dory` and `DynamicDory` schemes, so that the generators are
+/// only held once when benchmarking both.
+///
 /// # Arguments
 /// * `public_parameters` - A reference to the public parameters.
 /// * `cli` - A reference to the command line interface arguments.
 ///
 /// # Panics
 /// * The Blitzar handle path cannot be parsed from the string.
+#[tracing::instrument(name = "Dory setup", level = "debug", skip_all)]
 fn load_dory_setup<'a>(
-    public_parameters: &'a PublicParameters,
-    cli: &'a Cli,
-) -> (ProverSetup<'a>, VerifierSetup) {
-    let (prover_setup, verifier_setup) = if let Some(blitzar_handle_path) = &cli.blitzar_handle_path
-    {
-        let handle =
-            blitzar::compute::MsmHandle::new_from_file(blitzar_handle_path.to_str().unwrap());
-        let prover_setup =
-            ProverSetup::from_public_parameters_and_blitzar_handle(public_parameters, handle);
-        let verifier_setup = VerifierSetup::from(public_parameters);
-
-        (prover_setup, verifier_setup)
-    } else {
-        let prover_setup = ProverSetup::from(public_parameters);
-        let verifier_setup = VerifierSetup::from(public_parameters);
-        (prover_setup, verifier_setup)
-    };
-
-    (prover_setup, verifier_setup)
+    public_parameters: &'a DoryPublicParameters,
+    cli: &Cli,
+) -> SharedDorySetup<'a> {
+    let handle = cli.blitzar_handle_path.as_ref().map(|blitzar_handle_path| {
+        blitzar::compute::MsmHandle::new_from_file(blitzar_handle_path.to_str().unwrap())
+    });
+    match (public_parameters, handle) {
+        (DoryPublicParameters::Deserialized(public_parameters), Some(handle)) => {
+            SharedDorySetup::new(
+                ProverSetup::from_public_parameters_and_blitzar_handle(public_parameters, handle),
+                VerifierSetup::from(public_parameters),
+            )
+        }
+        (DoryPublicParameters::Deserialized(public_parameters), None) => {
+            SharedDorySetup::from(public_parameters)
+        }
+        (DoryPublicParameters::Mapped(file), Some(handle)) => SharedDorySetup::new(
+            file.prover_setup_with_blitzar_handle(handle),
+            file.verifier_setup(),
+        ),
+        (DoryPublicParameters::Mapped(file), None) => {
+            SharedDorySetup::new(file.prover_setup(), file.verifier_setup())
+        }
+    }
 }
 
 /// Benchmarks the `Dory` scheme.
@@ -346,22 +433,16 @@ fn load_dory_setup<'a>(
 /// # Arguments
 /// * `cli` - A reference to the command line interface arguments.
 /// * `queries` - A slice of query entries to benchmark.
+/// * `setup` - The Dory setup, which may be shared with the `DynamicDory` scheme.
 #[tracing::instrument(name = "Dory", level = "debug", skip_all)]
-fn bench_dory(cli: &Cli, queries: &[QueryEntry]) {
-    let span = span!(Level::DEBUG, "setup", sigma = cli.nu_sigma).entered();
-    let public_parameters = load_dory_public_parameters(cli);
-    let (prover_setup, verifier_setup) = load_dory_setup(&public_parameters, cli);
-
-    let prover_public_setup = DoryProverPublicSetup::new(&prover_setup, cli.nu_sigma);
-    let verifier_public_setup = DoryVerifierPublicSetup::new(&verifier_setup, cli.nu_sigma);
-    span.exit();
-
+fn bench_dory(cli: &Cli, queries: &[QueryEntry], setup: &SharedDorySetup) {
     bench_by_schema::<DoryEvaluationProof>(
         "Dory",
         cli,
         queries,
-        prover_public_setup,
-        verifier_public_setup,
+        &dory_setup_parameters(cli),
+        setup.dory_prover_setup(cli.nu_sigma),
+        setup.dory_verifier_setup(cli.nu_sigma),
     );
 }
 
@@ -370,19 +451,16 @@ fn bench_dory(cli: &Cli, queries: &[QueryEntry]) {
 /// # Arguments
 /// * `cli` - A reference to the command line interface arguments.
 /// * `queries` - A slice of query entries to benchmark.
+/// * `setup` - The Dory setup, which may be shared with the `Dory` scheme.
 #[tracing::instrument(name = "Dynamic Dory", level = "debug", skip_all)]
-fn bench_dynamic_dory(cli: &Cli, queries: &[QueryEntry]) {
-    let span = span!(Level::DEBUG, "setup", nu = cli.nu_sigma).entered();
-    let public_parameters = load_dory_public_parameters(cli);
-    let (prover_setup, verifier_setup) = load_dory_setup(&public_parameters, cli);
-    span.exit();
-
+fn bench_dynamic_dory(cli: &Cli, queries: &[QueryEntry], setup: &SharedDorySetup) {
     bench_by_schema::<DynamicDoryEvaluationProof>(
         "Dynamic Dory",
         cli,
         queries,
-        &prover_setup,
-        &verifier_setup,
+        &dory_setup_parameters(cli),
+        setup.dynamic_dory_prover_setup(),
+        setup.dynamic_dory_verifier_setup(),
     );
 }
 
@@ -404,15 +482,7 @@ fn bench_hyperkzg(cli: &Cli, queries: &[QueryEntry]) {
             deserialize_flat_compressed_hyperkzg_public_setup_from_reader(&file, Validate::Yes)
                 .unwrap();
 
-        let ck: CommitmentKey<HyperKZGEngine> = CommitmentKey::new(
-            prover_setup
-                .iter()
-                .map(blitzar::compute::convert_to_halo2_bn256_g1_affine)
-                .collect(),
-            Affine::default(),
-            G2Affine::default(),
-        );
-        let (_, vk) = EvaluationEngine::setup(&ck);
+        let vk = hyperkzg_verifier_key_from_public_setup(&prover_setup);
 
         (prover_setup, vk)
     } else {
@@ -427,6 +497,7 @@ fn bench_hyperkzg(cli: &Cli, queries: &[QueryEntry]) {
         "HyperKZG",
         cli,
         queries,
+        &SetupParameters::new(None, cli.ppot_path.as_deref(), false),
         &prover_setup,
         &vk,
     );
@@ -451,9 +522,7 @@ fn main() {
     let cli = Cli::parse();
 
     if cli.write_header && !cli.silence {
-        println!(
-            "commitment_scheme,query,table_size,generate_proof (ms),verify_proof (ms),iteration"
-        );
+        println!("{}", header_line());
     }
 
     let queries = if cli.query == Query::All {
@@ -466,18 +535,24 @@ fn main() {
     match cli.scheme {
         CommitmentScheme::All => {
             bench_inner_product_proof(&cli, &queries);
-            bench_dory(&cli, &queries);
-            bench_dynamic_dory(&cli, &queries);
+            {
+                let public_parameters = load_dory_public_parameters(&cli);
+                let setup = load_dory_setup(&public_parameters, &cli);
+                bench_dory(&cli, &queries, &setup);
+                bench_dynamic_dory(&cli, &queries, &setup);
+            }
             bench_hyperkzg(&cli, &queries);
         }
         CommitmentScheme::InnerProductProof => {
             bench_inner_product_proof(&cli, &queries);
         }
         CommitmentScheme::Dory => {
-            bench_dory(&cli, &queries);
+            let public_parameters = load_dory_public_parameters(&cli);
+            bench_dory(&cli, &queries, &load_dory_setup(&public_parameters, &cli));
         }
         CommitmentScheme::DynamicDory => {
-            bench_dynamic_dory(&cli, &queries);
+            let public_parameters = load_dory_public_parameters(&cli);
+            bench_dynamic_dory(&cli, &queries, &load_dory_setup(&public_parameters, &cli));
         }
         CommitmentScheme::HyperKZG => {
             bench_hyperkzg(&cli, &queries);
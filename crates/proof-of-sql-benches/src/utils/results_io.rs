@@ -1,5 +1,191 @@
-use csv::{Writer, WriterBuilder};
-use std::{fs::OpenOptions, io::BufWriter, path::Path};
+use csv::{Reader, Writer, WriterBuilder};
+use std::{
+    fs::OpenOptions,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// The version of the format of the timing rows.
+///
+/// It is the first column of the header, so that files written by older versions, whose header
+/// starts with `commitment_scheme`, can still be told apart and parsed. See [`header_version`].
+pub const RESULTS_FORMAT_VERSION: u32 = 2;
+
+/// The columns of the timing rows of [`RESULTS_FORMAT_VERSION`] 1.
+const HEADER_V1: [&str; 6] = [
+    "commitment_scheme",
+    "query",
+    "table_size",
+    "generate_proof (ms)",
+    "verify_proof (ms)",
+    "iteration",
+];
+
+/// The columns of the timing rows of the current [`RESULTS_FORMAT_VERSION`].
+pub const HEADER: [&str; 12] = [
+    "format_version",
+    "commitment_scheme",
+    "query",
+    "table_size",
+    "generate_proof (ms)",
+    "verify_proof (ms)",
+    "iteration",
+    "nu_sigma",
+    "setup_source",
+    "blitzar_handle",
+    "crate_version",
+    "git_hash",
+];
+
+/// The parameters of the setup a benchmark ran with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupParameters {
+    /// `max_nu` or `sigma` of the Dory setups, `None` for schemes that don't use it
+    pub nu_sigma: Option<usize>,
+    /// `test-rand` for generated setups, the file name for setups loaded from a file, or `none`
+    pub setup_source: String,
+    /// Whether a Blitzar handle was loaded from a file
+    pub blitzar_handle: bool,
+}
+
+impl SetupParameters {
+    /// Creates the parameters of a setup that is either generated or loaded from `setup_path`.
+    pub fn new(nu_sigma: Option<usize>, setup_path: Option<&Path>, blitzar_handle: bool) -> Self {
+        let setup_source = setup_path.map_or_else(
+            || "test-rand".to_string(),
+            |path| {
+                path.file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+                    .into_owned()
+            },
+        );
+        Self {
+            nu_sigma,
+            setup_source,
+            blitzar_handle,
+        }
+    }
+
+    /// Creates the parameters of a scheme without a setup.
+    pub fn none() -> Self {
+        Self {
+            nu_sigma: None,
+            setup_source: "none".to_string(),
+            blitzar_handle: false,
+        }
+    }
+}
+
+/// The timings of a single iteration of a benchmark.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingRow<'a> {
+    /// The commitment scheme
+    pub commitment_scheme: &'a str,
+    /// The query
+    pub query: &'a str,
+    /// The number of rows of the queried table
+    pub table_size: usize,
+    /// The time it took to generate the proof, in milliseconds
+    pub generate_proof_ms: u128,
+    /// The time it took to verify the proof, in milliseconds
+    pub verify_proof_ms: u128,
+    /// The iteration
+    pub iteration: usize,
+    /// The setup the benchmark ran with
+    pub setup: &'a SetupParameters,
+}
+
+/// Returns the version of this crate.
+fn crate_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Returns the git commit this binary was built from, injected by the build script.
+fn git_hash() -> &'static str {
+    option_env!("GIT_HASH").unwrap_or("unknown")
+}
+
+impl TimingRow<'_> {
+    /// Returns the values of the row, in the order of [`HEADER`].
+    pub fn to_record(&self) -> [String; HEADER.len()] {
+        [
+            RESULTS_FORMAT_VERSION.to_string(),
+            self.commitment_scheme.to_string(),
+            self.query.to_string(),
+            self.table_size.to_string(),
+            self.generate_proof_ms.to_string(),
+            self.verify_proof_ms.to_string(),
+            self.iteration.to_string(),
+            self.setup
+                .nu_sigma
+                .map(|nu_sigma| nu_sigma.to_string())
+                .unwrap_or_default(),
+            self.setup.setup_source.clone(),
+            self.setup.blitzar_handle.to_string(),
+            crate_version().to_string(),
+            git_hash().to_string(),
+        ]
+    }
+
+    /// Returns the row as a line of CSV, without the trailing newline.
+    ///
+    /// # Panics
+    /// * If the row cannot be written as CSV.
+    pub fn to_csv_line(&self) -> String {
+        csv_line(&self.to_record())
+    }
+
+    /// Returns the row as a line of JSON, without the trailing newline.
+    pub fn to_json_line(&self) -> String {
+        serde_json::json!({
+            "format_version": RESULTS_FORMAT_VERSION,
+            "commitment_scheme": self.commitment_scheme,
+            "query": self.query,
+            "table_size": self.table_size,
+            "generate_proof_ms": self.generate_proof_ms,
+            "verify_proof_ms": self.verify_proof_ms,
+            "iteration": self.iteration,
+            "nu_sigma": self.setup.nu_sigma,
+            "setup_source": self.setup.setup_source,
+            "blitzar_handle": self.setup.blitzar_handle,
+            "crate_version": crate_version(),
+            "git_hash": git_hash(),
+        })
+        .to_string()
+    }
+}
+
+/// Writes a record as a line of CSV, without the trailing newline.
+///
+/// # Panics
+/// * If the record cannot be written as CSV.
+fn csv_line<T: AsRef<[u8]>>(record: &[T]) -> String {
+    let mut writer = WriterBuilder::new().from_writer(Vec::new());
+    writer
+        .write_record(record)
+        .expect("Failed to write row as CSV.");
+    let line = String::from_utf8(writer.into_inner().expect("Failed to flush CSV writer."))
+        .expect("CSV is valid UTF-8.");
+    line.trim_end().to_string()
+}
+
+/// Returns the CSV header line of the current [`RESULTS_FORMAT_VERSION`].
+pub fn header_line() -> String {
+    csv_line(&HEADER)
+}
+
+/// Returns the [`RESULTS_FORMAT_VERSION`] of a CSV header, or `None` if it is not a known header.
+pub fn header_version<T: AsRef<str>>(header: &[T]) -> Option<u32> {
+    let header: Vec<&str> = header.iter().map(AsRef::as_ref).collect();
+    if header == HEADER_V1 {
+        Some(1)
+    } else if header.first() == Some(&HEADER[0]) {
+        (header == HEADER).then_some(RESULTS_FORMAT_VERSION)
+    } else {
+        None
+    }
+}
 
 /// Writes the header to the CSV file.
 ///
@@ -10,26 +196,20 @@ use std::{fs::OpenOptions, io::BufWriter, path::Path};
 /// * If the header cannot be written to the CSV file.
 fn write_csv_header(writer: &mut Writer<BufWriter<std::fs::File>>) {
     writer
-        .write_record([
-            "commitment_scheme",
-            "query",
-            "table_size",
-            "generate_proof (ms)",
-            "verify_proof (ms)",
-            "iteration",
-        ])
+        .write_record(HEADER)
         .expect("Failed to write headers to CSV file.");
 }
 
-/// Appends values to an existing CSV file or creates a new one if it doesn't exist.
+/// Appends a row to an existing CSV file or creates a new one if it doesn't exist.
 ///
 /// # Arguments
 /// * `file_path` - The path to the CSV file.
-/// * `new_row` - A vector of strings to append to the file.
+/// * `row` - The row to append to the file.
 ///
 /// # Panics
 /// * If the file cannot be opened, read, or appended.
-pub fn append_to_csv(file_path: &Path, new_row: &[String]) {
+/// * If the file was written with a different [`RESULTS_FORMAT_VERSION`].
+pub fn append_to_csv(file_path: &Path, row: &TimingRow) {
     // Open the file in append mode or create it if it doesn't exist
     let file = OpenOptions::new()
         .read(true)
@@ -41,6 +221,19 @@ pub fn append_to_csv(file_path: &Path, new_row: &[String]) {
     // Check if the file is empty to determine if we need to write headers
     let is_empty = file.metadata().map(|m| m.len() == 0).unwrap_or(true);
 
+    // Rows of different formats must not be mixed in a file
+    if !is_empty {
+        let header = Reader::from_path(file_path)
+            .and_then(|mut reader| reader.headers().cloned())
+            .expect("Failed to read the header of the CSV file.");
+        let version = header_version(header.iter().collect::<Vec<_>>().as_slice());
+        assert_eq!(
+            version,
+            Some(RESULTS_FORMAT_VERSION),
+            "The CSV file was written in a different format, use a new file."
+        );
+    }
+
     // Create a CSV writer
     let mut writer = WriterBuilder::new().from_writer(BufWriter::new(file));
 
@@ -51,8 +244,135 @@ pub fn append_to_csv(file_path: &Path, new_row: &[String]) {
 
     // Write new row to the CSV file
     writer
-        .write_record(new_row)
+        .write_record(row.to_record())
         .expect("Failed to write row to CSV file.");
 
     writer.flush().expect("Failed to flush CSV writer.");
 }
+
+/// Appends a row to an existing JSON Lines file or creates a new one if it doesn't exist.
+///
+/// Each line carries its `format_version`, so files may mix rows of different versions.
+///
+/// # Arguments
+/// * `file_path` - The path to the JSON Lines file.
+/// * `row` - The row to append to the file.
+///
+/// # Panics
+/// * If the file cannot be opened or appended.
+pub fn append_to_jsonl(file_path: &Path, row: &TimingRow) {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(file_path)
+        .expect("Failed to open or create the JSON Lines file.");
+    writeln!(file, "{}", row.to_json_line()).expect("Failed to write row to JSON Lines file.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dory_setup() -> SetupParameters {
+        SetupParameters::new(
+            Some(11),
+            Some(Path::new("/data/setups/public_parameters_nu_16.bin")),
+            true,
+        )
+    }
+
+    fn row(setup: &SetupParameters) -> TimingRow<'_> {
+        TimingRow {
+            commitment_scheme: "Dynamic Dory",
+            query: "Single Column Filter",
+            table_size: 1000,
+            generate_proof_ms: 52,
+            verify_proof_ms: 7,
+            iteration: 2,
+            setup,
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_source_of_a_setup() {
+        assert_eq!(
+            dory_setup().setup_source,
+            "public_parameters_nu_16.bin".to_string()
+        );
+        assert_eq!(
+            SetupParameters::new(Some(11), None, false).setup_source,
+            "test-rand".to_string()
+        );
+        assert_eq!(SetupParameters::none().setup_source, "none".to_string());
+    }
+
+    #[test]
+    fn we_can_format_a_row_as_csv() {
+        let setup = dory_setup();
+        let record = row(&setup).to_record();
+        assert_eq!(record.len(), HEADER.len());
+        assert_eq!(
+            row(&setup).to_csv_line(),
+            format!(
+                "2,Dynamic Dory,Single Column Filter,1000,52,7,2,11,public_parameters_nu_16.bin,true,{},{}",
+                crate_version(),
+                git_hash()
+            )
+        );
+    }
+
+    #[test]
+    fn we_leave_nu_sigma_empty_and_quote_setup_sources_in_csv() {
+        let setup = SetupParameters {
+            nu_sigma: None,
+            setup_source: "ppot,0080.bin".to_string(),
+            blitzar_handle: false,
+        };
+        assert_eq!(
+            row(&setup).to_csv_line(),
+            format!(
+                "2,Dynamic Dory,Single Column Filter,1000,52,7,2,,\"ppot,0080.bin\",false,{},{}",
+                crate_version(),
+                git_hash()
+            )
+        );
+    }
+
+    #[test]
+    fn we_can_format_a_row_as_json() {
+        let setup = dory_setup();
+        let json: serde_json::Value = serde_json::from_str(&row(&setup).to_json_line()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "format_version": 2,
+                "commitment_scheme": "Dynamic Dory",
+                "query": "Single Column Filter",
+                "table_size": 1000,
+                "generate_proof_ms": 52,
+                "verify_proof_ms": 7,
+                "iteration": 2,
+                "nu_sigma": 11,
+                "setup_source": "public_parameters_nu_16.bin",
+                "blitzar_handle": true,
+                "crate_version": crate_version(),
+                "git_hash": git_hash(),
+            })
+        );
+        assert!(!row(&setup).to_json_line().contains('\n'));
+    }
+
+    #[test]
+    fn we_can_tell_the_version_of_a_header() {
+        assert_eq!(header_version(&HEADER_V1), Some(1));
+        assert_eq!(header_version(&HEADER), Some(RESULTS_FORMAT_VERSION));
+        assert_eq!(header_version(&HEADER[..HEADER.len() - 1]), None);
+        assert_eq!(header_version(&["scheme", "query"]), None);
+        assert_eq!(
+            header_line(),
+            "format_version,commitment_scheme,query,table_size,generate_proof (ms),\
+             verify_proof (ms),iteration,nu_sigma,setup_source,blitzar_handle,crate_version,\
+             git_hash"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::IdentifierValidationError;
 use alloc::string::String;
 use snafu::Snafu;
 
@@ -17,6 +18,12 @@ pub enum ParseError {
         /// The underlying error
         error: String,
     },
+    #[snafu(display("Invalid identifier: {source}"))]
+    /// The identifier violates the configured [`IdentifierLimits`](crate::IdentifierLimits)
+    InvalidIdentifier {
+        /// The underlying validation error
+        source: IdentifierValidationError,
+    },
     #[snafu(display("Unable to parse resource_id"))]
     /// Cannot parse the `resource_id`
     ResourceIdParseError {
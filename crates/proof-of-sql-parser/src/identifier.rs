@@ -1,4 +1,4 @@
-use crate::{sql::IdentifierParser, ParseError, ParseResult};
+use crate::{sql::IdentifierParser, IdentifierLimits, ParseError, ParseResult};
 use alloc::{format, string::ToString};
 use arrayvec::ArrayString;
 use core::{cmp::Ordering, fmt, ops::Deref, str::FromStr};
@@ -38,6 +38,30 @@ impl Identifier {
         Self::from_str(string.as_ref())
     }
 
+    /// Parse an [`Identifier`], first checking it against the given [`IdentifierLimits`].
+    ///
+    /// Identifiers are additionally limited to 64 bytes, the capacity of an [`Identifier`],
+    /// regardless of the configured maximum length.
+    ///
+    /// # Errors
+    /// Returns [`ParseError::InvalidIdentifier`] if the identifier violates `limits`, and
+    /// [`ParseError::IdentifierParseError`] if it otherwise cannot be parsed.
+    pub fn try_new_with_limits<S: AsRef<str>>(
+        string: S,
+        limits: &IdentifierLimits,
+    ) -> ParseResult<Self> {
+        let string = string.as_ref();
+        limits
+            .validate_unquoted(string.trim())
+            .map_err(|source| ParseError::InvalidIdentifier { source })?;
+        let name = IdentifierParser::new()
+            .parse(string)
+            .map_err(|e| ParseError::IdentifierParseError{ error:
+                format!("failed to parse identifier, (you may have used a reserved keyword as an ID, i.e. 'timestamp') {e:?}")})?;
+
+        Ok(Identifier::new(name))
+    }
+
     /// The name of this [Identifier]
     /// It already implements [Deref] to [str], so this method is not necessary for most use cases.
     #[must_use]
@@ -56,12 +80,7 @@ impl FromStr for Identifier {
     type Err = ParseError;
 
     fn from_str(string: &str) -> ParseResult<Self> {
-        let name = IdentifierParser::new()
-            .parse(string)
-            .map_err(|e| ParseError::IdentifierParseError{ error:
-                format!("failed to parse identifier, (you may have used a reserved keyword as an ID, i.e. 'timestamp') {e:?}")})?;
-
-        Ok(Identifier::new(name))
+        Identifier::try_new_with_limits(string, &IdentifierLimits::default())
     }
 }
 crate::impl_serde_from_str!(Identifier);
@@ -111,6 +130,7 @@ impl AsRef<str> for Identifier {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IdentifierValidationError;
     use alloc::{borrow::ToOwned, vec, vec::Vec};
 
     #[test]
@@ -214,6 +234,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn we_get_typed_errors_for_identifiers_violating_limits() {
+        let limits = IdentifierLimits::new(8);
+        assert_eq!(
+            Identifier::try_new_with_limits(" Short_Id ", &limits)
+                .unwrap()
+                .name(),
+            "short_id"
+        );
+        assert_eq!(
+            Identifier::try_new_with_limits("too_long_id", &limits),
+            Err(ParseError::InvalidIdentifier {
+                source: IdentifierValidationError::TooLong {
+                    length: 11,
+                    max_length: 8
+                }
+            })
+        );
+        assert_eq!(
+            Identifier::try_new_with_limits("bad$id", &limits),
+            Err(ParseError::InvalidIdentifier {
+                source: IdentifierValidationError::InvalidCharacter {
+                    character: '$',
+                    position: 3
+                }
+            })
+        );
+        assert_eq!(
+            Identifier::from_str("Select"),
+            Err(ParseError::InvalidIdentifier {
+                source: IdentifierValidationError::ReservedWord {
+                    word: "select".to_owned()
+                }
+            })
+        );
+        assert!(matches!(
+            Identifier::from_str(&"a".repeat(1 << 20)),
+            Err(ParseError::InvalidIdentifier {
+                source: IdentifierValidationError::TooLong { .. }
+            })
+        ));
+        // The capacity of an Identifier still applies when the configured limit is larger
+        assert!(matches!(
+            Identifier::from_str(&"a".repeat(65)),
+            Err(ParseError::IdentifierParseError { .. })
+        ));
+    }
+
     #[test]
     fn serialize_works() {
         let identifier = Identifier::from_str("GOOD_IDENTIFIER").unwrap();
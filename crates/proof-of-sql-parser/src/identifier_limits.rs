@@ -0,0 +1,285 @@
+//! Configurable validation of identifiers supplied by users.
+use alloc::string::String;
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+
+/// The default maximum length of an identifier, in bytes.
+pub const DEFAULT_MAX_IDENTIFIER_LENGTH: usize = 128;
+
+/// Keywords of the SQL grammar, which may not be used as unquoted identifiers.
+const RESERVED_WORDS: [&str; 23] = [
+    "all",
+    "asc",
+    "desc",
+    "as",
+    "and",
+    "from",
+    "not",
+    "or",
+    "select",
+    "where",
+    "order",
+    "by",
+    "limit",
+    "offset",
+    "group",
+    "min",
+    "max",
+    "count",
+    "sum",
+    "true",
+    "false",
+    "timestamp",
+    "to_timestamp",
+];
+
+/// Errors encountered when validating an identifier against [`IdentifierLimits`]
+#[derive(Debug, Snafu, Eq, PartialEq, Clone)]
+pub enum IdentifierValidationError {
+    #[snafu(display("identifier is empty"))]
+    /// The identifier is empty
+    Empty,
+    #[snafu(display("identifier is {length} bytes long, but the limit is {max_length} bytes"))]
+    /// The identifier is longer than allowed
+    TooLong {
+        /// The length of the identifier in bytes
+        length: usize,
+        /// The maximum allowed length in bytes
+        max_length: usize,
+    },
+    #[snafu(display("identifier contains invalid character {character:?} at byte {position}"))]
+    /// The identifier contains a character outside of the allowed character classes
+    InvalidCharacter {
+        /// The offending character
+        character: char,
+        /// The byte offset of the offending character
+        position: usize,
+    },
+    #[snafu(display("'{word}' is a reserved word and can only be used as a quoted identifier"))]
+    /// The unquoted identifier is a reserved word
+    ReservedWord {
+        /// The reserved word, in lower case
+        word: String,
+    },
+}
+
+/// Limits that identifiers supplied by users must respect.
+///
+/// Unquoted identifiers must match the identifier grammar, `[A-Za-z_][A-Za-z0-9_]*`, and must not be
+/// a reserved word. Quoted identifiers may contain any character other than control characters.
+/// Both must be non-empty and no longer than [`IdentifierLimits::max_length`] bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdentifierLimits {
+    max_length: usize,
+}
+
+impl Default for IdentifierLimits {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_IDENTIFIER_LENGTH)
+    }
+}
+
+impl IdentifierLimits {
+    /// Create limits allowing identifiers of up to `max_length` bytes
+    #[must_use]
+    pub const fn new(max_length: usize) -> Self {
+        Self { max_length }
+    }
+
+    /// The maximum length of an identifier, in bytes
+    #[must_use]
+    pub const fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Validate an unquoted identifier.
+    ///
+    /// # Errors
+    /// Returns an [`IdentifierValidationError`] if the identifier is empty, too long,
+    /// contains characters outside of `[A-Za-z0-9_]`, starts with a digit or is a reserved word.
+    pub fn validate_unquoted(&self, name: &str) -> Result<(), IdentifierValidationError> {
+        self.validate_length(name)?;
+        if let Some((position, character)) = name.char_indices().find(|&(position, c)| {
+            !(c.is_ascii_alphabetic() || c == '_' || (position > 0 && c.is_ascii_digit()))
+        }) {
+            return Err(IdentifierValidationError::InvalidCharacter {
+                character,
+                position,
+            });
+        }
+        let lower_case = name.to_ascii_lowercase();
+        if RESERVED_WORDS.contains(&lower_case.as_str()) {
+            return Err(IdentifierValidationError::ReservedWord { word: lower_case });
+        }
+        Ok(())
+    }
+
+    /// Validate a quoted identifier.
+    ///
+    /// # Errors
+    /// Returns an [`IdentifierValidationError`] if the identifier is empty, too long or
+    /// contains control characters.
+    pub fn validate_quoted(&self, name: &str) -> Result<(), IdentifierValidationError> {
+        self.validate_length(name)?;
+        if let Some((position, character)) = name.char_indices().find(|(_, c)| c.is_control()) {
+            return Err(IdentifierValidationError::InvalidCharacter {
+                character,
+                position,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validate an [`Ident`], using the quoted or unquoted rules depending on its quote style.
+    ///
+    /// # Errors
+    /// Returns an [`IdentifierValidationError`] if the identifier violates these limits.
+    pub fn validate_ident(&self, ident: &Ident) -> Result<(), IdentifierValidationError> {
+        match ident.quote_style {
+            Some(_) => self.validate_quoted(&ident.value),
+            None => self.validate_unquoted(&ident.value),
+        }
+    }
+
+    fn validate_length(&self, name: &str) -> Result<(), IdentifierValidationError> {
+        if name.is_empty() {
+            Err(IdentifierValidationError::Empty)
+        } else if name.len() > self.max_length {
+            Err(IdentifierValidationError::TooLong {
+                length: name.len(),
+                max_length: self.max_length,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn we_can_validate_unquoted_identifiers() {
+        let limits = IdentifierLimits::default();
+        assert_eq!(limits.max_length(), DEFAULT_MAX_IDENTIFIER_LENGTH);
+        assert_eq!(limits.validate_unquoted("good_identifier13"), Ok(()));
+        assert_eq!(
+            limits.validate_unquoted("_Can_Start_With_Underscore"),
+            Ok(())
+        );
+        assert_eq!(limits.validate_unquoted(&"a".repeat(128)), Ok(()));
+        assert_eq!(
+            limits.validate_unquoted(""),
+            Err(IdentifierValidationError::Empty)
+        );
+        assert_eq!(
+            limits.validate_unquoted(&"a".repeat(129)),
+            Err(IdentifierValidationError::TooLong {
+                length: 129,
+                max_length: 128
+            })
+        );
+        assert_eq!(
+            limits.validate_unquoted("13ad_identifier"),
+            Err(IdentifierValidationError::InvalidCharacter {
+                character: '1',
+                position: 0
+            })
+        );
+        assert_eq!(
+            limits.validate_unquoted("bad identifier"),
+            Err(IdentifierValidationError::InvalidCharacter {
+                character: ' ',
+                position: 3
+            })
+        );
+        assert_eq!(
+            limits.validate_unquoted("bad_茶"),
+            Err(IdentifierValidationError::InvalidCharacter {
+                character: '茶',
+                position: 4
+            })
+        );
+    }
+
+    #[test]
+    fn we_cannot_use_reserved_words_as_unquoted_identifiers() {
+        let limits = IdentifierLimits::default();
+        for word in RESERVED_WORDS {
+            assert_eq!(
+                limits.validate_unquoted(word),
+                Err(IdentifierValidationError::ReservedWord {
+                    word: word.to_string()
+                })
+            );
+            assert_eq!(limits.validate_quoted(word), Ok(()));
+        }
+        assert_eq!(
+            limits.validate_unquoted("TimeStamp"),
+            Err(IdentifierValidationError::ReservedWord {
+                word: "timestamp".to_string()
+            })
+        );
+        assert_eq!(limits.validate_unquoted("timestamps"), Ok(()));
+    }
+
+    #[test]
+    fn we_can_validate_quoted_identifiers() {
+        let limits = IdentifierLimits::new(8);
+        assert_eq!(limits.validate_quoted("a b.c$茶"), Ok(()));
+        assert_eq!(
+            limits.validate_quoted(""),
+            Err(IdentifierValidationError::Empty)
+        );
+        assert_eq!(
+            limits.validate_quoted("茶茶茶"),
+            Err(IdentifierValidationError::TooLong {
+                length: 9,
+                max_length: 8
+            })
+        );
+        assert_eq!(
+            limits.validate_quoted("a\nb"),
+            Err(IdentifierValidationError::InvalidCharacter {
+                character: '\n',
+                position: 1
+            })
+        );
+    }
+
+    #[test]
+    fn we_can_validate_idents_according_to_their_quote_style() {
+        let limits = IdentifierLimits::default();
+        assert_eq!(limits.validate_ident(&Ident::new("valid")), Ok(()));
+        assert_eq!(
+            limits.validate_ident(&Ident::new("not valid")),
+            Err(IdentifierValidationError::InvalidCharacter {
+                character: ' ',
+                position: 3
+            })
+        );
+        assert_eq!(
+            limits.validate_ident(&Ident::with_quote('"', "now valid")),
+            Ok(())
+        );
+        assert_eq!(
+            limits.validate_ident(&Ident::new("select")),
+            Err(IdentifierValidationError::ReservedWord {
+                word: "select".to_string()
+            })
+        );
+        assert_eq!(
+            limits.validate_ident(&Ident::with_quote('"', "select")),
+            Ok(())
+        );
+        assert_eq!(
+            limits.validate_ident(&Ident::with_quote('"', "a".repeat(1 << 20))),
+            Err(IdentifierValidationError::TooLong {
+                length: 1 << 20,
+                max_length: 128
+            })
+        );
+    }
+}
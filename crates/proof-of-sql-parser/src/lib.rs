@@ -29,6 +29,9 @@ pub(crate) use error::ParseResult;
 pub(crate) mod identifier;
 pub use identifier::Identifier;
 
+pub mod identifier_limits;
+pub use identifier_limits::{IdentifierLimits, IdentifierValidationError};
+
 pub mod resource_id;
 pub use resource_id::ResourceId;
 
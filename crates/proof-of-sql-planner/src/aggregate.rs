@@ -4,7 +4,10 @@ use datafusion::{
     logical_expr::expr::{AggregateFunction, AggregateFunctionDefinition},
     physical_plan,
 };
-use proof_of_sql::{base::database::ColumnType, sql::proof_exprs::DynProofExpr};
+use proof_of_sql::{
+    base::database::ColumnType,
+    sql::proof_exprs::{DynProofExpr, ProofExpr},
+};
 use sqlparser::ast::Ident;
 
 /// An aggregate function we support
@@ -14,15 +17,18 @@ pub enum AggregateFunc {
     Sum,
     /// Count
     Count,
+    /// Count of the distinct values of a column, optionally with a filter
+    CountDistinct,
 }
 
-/// Convert an [`AggregateFunction`] to a [`DynProofExpr`]
+/// Convert an [`AggregateFunction`] to a [`DynProofExpr`] along with the [`DynProofExpr`] of its
+/// `FILTER (WHERE ..)` clause, which only `COUNT(DISTINCT ..)` may have
 ///
 /// TODO: Some moderate changes are necessary once we upgrade `DataFusion` to 46.0.0
 pub(crate) fn aggregate_function_to_proof_expr(
     function: &AggregateFunction,
     schema: &[(Ident, ColumnType)],
-) -> PlannerResult<(AggregateFunc, DynProofExpr)> {
+) -> PlannerResult<(AggregateFunc, DynProofExpr, Option<DynProofExpr>)> {
     match function {
         AggregateFunction {
             distinct: false,
@@ -37,7 +43,51 @@ pub(crate) fn aggregate_function_to_proof_expr(
                 physical_plan::aggregates::AggregateFunction::Count => AggregateFunc::Count,
                 _ => Err(PlannerError::UnsupportedAggregateOperation { op: op.clone() })?,
             };
-            Ok((aggregate_function, expr_to_proof_expr(&args[0], schema)?))
+            Ok((
+                aggregate_function,
+                expr_to_proof_expr(&args[0], schema)?,
+                None,
+            ))
+        }
+        // Only distinct counts of columns are provable, see `GroupByExec`
+        AggregateFunction {
+            distinct: true,
+            filter,
+            order_by: None,
+            args,
+            func_def:
+                AggregateFunctionDefinition::BuiltIn(
+                    physical_plan::aggregates::AggregateFunction::Count,
+                ),
+            ..
+        } if args.len() == 1 => {
+            let expr = expr_to_proof_expr(&args[0], schema)?;
+            let filter = filter
+                .as_deref()
+                .map(|filter| expr_to_proof_expr(filter, schema))
+                .transpose()?;
+            let is_countable = matches!(
+                &expr,
+                DynProofExpr::Column(column_expr) if matches!(
+                    column_expr.data_type(),
+                    ColumnType::Boolean
+                        | ColumnType::Uint8
+                        | ColumnType::TinyInt
+                        | ColumnType::SmallInt
+                        | ColumnType::Int
+                        | ColumnType::BigInt
+                        | ColumnType::TimestampTZ(_, _)
+                )
+            );
+            let is_filter_non_boolean = filter
+                .as_ref()
+                .is_some_and(|filter| filter.data_type() != ColumnType::Boolean);
+            if !is_countable || is_filter_non_boolean {
+                return Err(PlannerError::UnsupportedAggregateFunction {
+                    function: function.clone(),
+                });
+            }
+            Ok((AggregateFunc::CountDistinct, expr, filter))
         }
         _ => Err(PlannerError::UnsupportedAggregateFunction {
             function: function.clone(),
@@ -82,12 +132,92 @@ mod tests {
                         TableRef::from_names(None, "table"),
                         "a".into(),
                         ColumnType::BigInt
-                    ))
+                    )),
+                    None
                 )
             );
         }
     }
 
+    #[test]
+    fn we_can_convert_a_distinct_count_with_or_without_a_filter_to_proof_expr() {
+        let schema: Vec<(Ident, ColumnType)> = vec![
+            ("a".into(), ColumnType::BigInt),
+            ("b".into(), ColumnType::Boolean),
+        ];
+        let column = |name: &str, column_type| {
+            DynProofExpr::new_column(ColumnRef::new(
+                TableRef::from_names(None, "table"),
+                name.into(),
+                column_type,
+            ))
+        };
+        let function = AggregateFunction::new(
+            physical_plan::aggregates::AggregateFunction::Count,
+            vec![df_column("table", "a")],
+            true,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            aggregate_function_to_proof_expr(&function, &schema).unwrap(),
+            (
+                AggregateFunc::CountDistinct,
+                column("a", ColumnType::BigInt),
+                None
+            )
+        );
+        let function = AggregateFunction::new(
+            physical_plan::aggregates::AggregateFunction::Count,
+            vec![df_column("table", "a")],
+            true,
+            Some(Box::new(df_column("table", "b"))),
+            None,
+            None,
+        );
+        assert_eq!(
+            aggregate_function_to_proof_expr(&function, &schema).unwrap(),
+            (
+                AggregateFunc::CountDistinct,
+                column("a", ColumnType::BigInt),
+                Some(column("b", ColumnType::Boolean))
+            )
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_a_distinct_count_of_an_unsupported_expr_or_with_a_non_boolean_filter() {
+        let schema: Vec<(Ident, ColumnType)> = vec![
+            ("a".into(), ColumnType::BigInt),
+            ("c".into(), ColumnType::VarChar),
+        ];
+        for (arg, filter) in [
+            // Not a column
+            (df_column("table", "a") + df_column("table", "a"), None),
+            // A column of an unsupported type
+            (df_column("table", "c"), None),
+            // A non-boolean filter
+            (
+                df_column("table", "a"),
+                Some(Box::new(df_column("table", "a"))),
+            ),
+        ] {
+            let function = AggregateFunction::new(
+                physical_plan::aggregates::AggregateFunction::Count,
+                vec![arg],
+                true,
+                filter,
+                None,
+                None,
+            );
+            assert!(matches!(
+                aggregate_function_to_proof_expr(&function, &schema),
+                Err(PlannerError::UnsupportedAggregateFunction { .. })
+            ));
+        }
+    }
+
     #[test]
     fn we_cannot_convert_an_aggregate_function_to_pair_if_unsupported() {
         let expr = df_column("table", "a");
@@ -141,13 +271,13 @@ mod tests {
 
     #[test]
     fn we_cannot_convert_an_aggregate_function_to_pair_if_unsupported_options() {
-        // No distinct, filter, or order_by
+        // No distinct other than COUNT(DISTINCT ..), filter other than on COUNT(DISTINCT ..), or order_by
 
         // Distinct
         let expr = df_column("table", "a");
         let schema = vec![("a".into(), ColumnType::BigInt)];
         let function = AggregateFunction::new(
-            physical_plan::aggregates::AggregateFunction::Count,
+            physical_plan::aggregates::AggregateFunction::Sum,
             vec![expr.clone()],
             true,
             None,
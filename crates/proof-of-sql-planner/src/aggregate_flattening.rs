@@ -0,0 +1,183 @@
+use super::PlannerResult;
+use alloc::{sync::Arc, vec::Vec};
+use datafusion::{
+    common::{
+        tree_node::{Transformed, TreeNode},
+        Column,
+    },
+    logical_expr::{
+        expr_rewriter::{normalize_col, unnormalize_col},
+        Aggregate, Expr, Filter, LogicalPlan, Projection, SubqueryAlias, TableScan,
+    },
+};
+use indexmap::IndexMap;
+
+/// Flatten every aggregation over a derived table in `plan` into an aggregation directly over
+/// a `TableScan`
+///
+/// `DataFusion` keeps the projections of derived tables such as
+/// `SELECT SUM(y), COUNT(1) FROM (SELECT x AS y FROM t WHERE c)` between the `Aggregate` and the
+/// `TableScan`, which we can't prove. Such a plan is rewritten into the same shape as the
+/// equivalent flat query `SELECT SUM(x) AS "SUM(y)", COUNT(1) FROM t WHERE c`,
+/// which converts to a single GROUP BY proof plan.
+///
+/// Only aggregations whose input consists of subquery aliases, projections and filters over a
+/// `TableScan` without fetch are rewritten. Anything else in between, e.g. a `LIMIT`, an
+/// `ORDER BY` or a window function, changes the semantics of the aggregation and is left alone.
+pub(crate) fn flatten_aggregates(plan: &LogicalPlan) -> PlannerResult<LogicalPlan> {
+    Ok(try_flatten_aggregates(plan)?.unwrap_or_else(|| plan.clone()))
+}
+
+/// Recursively flatten aggregations in `plan`, returning `None` if nothing changed
+fn try_flatten_aggregates(plan: &LogicalPlan) -> PlannerResult<Option<LogicalPlan>> {
+    let inputs = plan.inputs();
+    let flattened_inputs = inputs
+        .iter()
+        .map(|input| try_flatten_aggregates(input))
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let rebuilt_plan = if flattened_inputs.iter().any(Option::is_some) {
+        let new_inputs = inputs
+            .into_iter()
+            .zip(flattened_inputs)
+            .map(|(input, flattened_input)| flattened_input.unwrap_or_else(|| input.clone()))
+            .collect();
+        Some(plan.with_new_exprs(plan.expressions(), new_inputs)?)
+    } else {
+        None
+    };
+    let flattened_aggregate = match rebuilt_plan.as_ref().unwrap_or(plan) {
+        LogicalPlan::Aggregate(aggregate) => flatten_aggregate(aggregate)?,
+        _ => None,
+    };
+    Ok(flattened_aggregate
+        .map(LogicalPlan::Aggregate)
+        .or(rebuilt_plan))
+}
+
+/// Replace the columns in `expr` which are outputs of a projection by the projected expressions
+pub(crate) fn substitute_columns(
+    expr: Expr,
+    replacements: &IndexMap<Column, Expr>,
+) -> PlannerResult<Expr> {
+    Ok(expr
+        .transform_up(&|e| {
+            Ok(match e {
+                Expr::Column(column) => match replacements.get(&column) {
+                    Some(replacement) => Transformed::yes(replacement.clone()),
+                    None => Transformed::no(Expr::Column(column)),
+                },
+                _ => Transformed::no(e),
+            })
+        })?
+        .data)
+}
+
+/// Flatten a single aggregation whose input is a derived table over a `TableScan`
+///
+/// Returns `None` if the input is already a `TableScan` or can't be flattened.
+/// The output schema of the aggregation is preserved so that plans built on top of it stay valid.
+fn flatten_aggregate(aggregate: &Aggregate) -> PlannerResult<Option<Aggregate>> {
+    if matches!(aggregate.input.as_ref(), LogicalPlan::TableScan(_)) {
+        return Ok(None);
+    }
+    let mut group_expr = aggregate.group_expr.clone();
+    let mut aggr_expr = aggregate.aggr_expr.clone();
+    let mut filters: Vec<Expr> = Vec::new();
+    let mut input = aggregate.input.as_ref();
+    // Push the expressions down through the derived table until we hit the `TableScan`
+    let table_scan = loop {
+        match input {
+            LogicalPlan::SubqueryAlias(SubqueryAlias {
+                input: alias_input, ..
+            }) => {
+                let requalify = |e: Expr| normalize_col(unnormalize_col(e), alias_input);
+                group_expr = group_expr
+                    .into_iter()
+                    .map(requalify)
+                    .collect::<Result<_, _>>()?;
+                aggr_expr = aggr_expr
+                    .into_iter()
+                    .map(requalify)
+                    .collect::<Result<_, _>>()?;
+                filters = filters
+                    .into_iter()
+                    .map(requalify)
+                    .collect::<Result<_, _>>()?;
+                input = alias_input;
+            }
+            LogicalPlan::Projection(Projection {
+                expr,
+                input: projection_input,
+                schema,
+                ..
+            }) => {
+                let replacements = schema
+                    .columns()
+                    .into_iter()
+                    .zip(expr.iter().map(|e| e.clone().unalias()))
+                    .collect::<IndexMap<_, _>>();
+                let substitute = |e: Expr| substitute_columns(e, &replacements);
+                group_expr = group_expr
+                    .into_iter()
+                    .map(substitute)
+                    .collect::<PlannerResult<_>>()?;
+                aggr_expr = aggr_expr
+                    .into_iter()
+                    .map(substitute)
+                    .collect::<PlannerResult<_>>()?;
+                filters = filters
+                    .into_iter()
+                    .map(substitute)
+                    .collect::<PlannerResult<_>>()?;
+                input = projection_input;
+            }
+            LogicalPlan::Filter(Filter {
+                predicate,
+                input: filter_input,
+                ..
+            }) => {
+                filters.push(predicate.clone());
+                input = filter_input;
+            }
+            LogicalPlan::TableScan(table_scan @ TableScan { fetch: None, .. }) => break table_scan,
+            _ => return Ok(None),
+        }
+    };
+    // Group columns have to remain columns with the same names since they are part of the output
+    let group_columns_are_preserved =
+        aggregate
+            .group_expr
+            .iter()
+            .zip(group_expr.iter())
+            .all(|(original, flattened)| match (original, flattened) {
+                (Expr::Column(original), Expr::Column(flattened)) => {
+                    original.name == flattened.name
+                }
+                _ => false,
+            });
+    if !group_columns_are_preserved {
+        return Ok(None);
+    }
+    // Keep the names of the aggregate expressions, which later plans refer to
+    let aggr_expr = aggregate
+        .aggr_expr
+        .iter()
+        .zip(aggr_expr)
+        .map(|(original, flattened)| {
+            let name = original.display_name()?;
+            Ok(if flattened.display_name()? == name {
+                flattened
+            } else {
+                flattened.unalias().alias(name)
+            })
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let mut flattened_table_scan = table_scan.clone();
+    flattened_table_scan.filters.extend(filters);
+    Ok(Some(Aggregate::try_new_with_schema(
+        Arc::new(LogicalPlan::TableScan(flattened_table_scan)),
+        group_expr,
+        aggr_expr,
+        Arc::clone(&aggregate.schema),
+    )?))
+}
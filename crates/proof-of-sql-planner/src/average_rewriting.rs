@@ -0,0 +1,269 @@
+use super::{aggregate_flattening::substitute_columns, PlannerResult};
+use alloc::{string::String, sync::Arc, vec, vec::Vec};
+use arrow::datatypes::DataType;
+use datafusion::{
+    common::Column,
+    logical_expr::{
+        expr::{AggregateFunction, AggregateFunctionDefinition},
+        lit, Aggregate, Cast, Expr, LogicalPlan, Projection,
+    },
+    physical_plan::aggregates::AggregateFunction as BuiltInAggregateFunction,
+};
+use indexmap::IndexMap;
+
+/// Rewrite every `AVG` of an aggregation in `plan` into a `SUM` divided by a `COUNT`
+///
+/// We can only prove `SUM`s and `COUNT`s, so
+/// `SELECT x % 10 AS bucket, AVG(y) FROM t GROUP BY x % 10` is rewritten into
+/// `SELECT x % 10 AS bucket, SUM(y) / COUNT(1) AS "AVG(t.y)" FROM t GROUP BY x % 10`.
+/// The aggregation is proven and the division is done in postprocessing. Since it is an integer
+/// division for integer arguments, the average is truncated towards zero.
+///
+/// The `SUM` and `COUNT` already present in an aggregation are reused. Aggregations with an
+/// `AVG(DISTINCT ..)` or an `AVG` with a filter are left alone.
+///
+/// Aggregations with a `COUNT(DISTINCT ..)` are rewritten the same way even without an `AVG`, so
+/// that `SELECT region, COUNT(DISTINCT user_id), SUM(amount) FROM t GROUP BY region` is proven as
+/// `SUM(amount)`, `COUNT(1)` and `COUNT(DISTINCT user_id)`, in the order `GroupByExec` requires.
+pub(crate) fn rewrite_averages(plan: &LogicalPlan) -> PlannerResult<LogicalPlan> {
+    Ok(try_rewrite_averages(plan)?.unwrap_or_else(|| plan.clone()))
+}
+
+/// Recursively rewrite averages in `plan`, returning `None` if nothing changed
+fn try_rewrite_averages(plan: &LogicalPlan) -> PlannerResult<Option<LogicalPlan>> {
+    let inputs = plan.inputs();
+    let rewritten_inputs = inputs
+        .iter()
+        .map(|input| try_rewrite_averages(input))
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let rebuilt_plan = if rewritten_inputs.iter().any(Option::is_some) {
+        let new_inputs = inputs
+            .into_iter()
+            .zip(rewritten_inputs)
+            .map(|(input, rewritten_input)| rewritten_input.unwrap_or_else(|| input.clone()))
+            .collect();
+        Some(plan.with_new_exprs(plan.expressions(), new_inputs)?)
+    } else {
+        None
+    };
+    let rewritten_plan = match rebuilt_plan.as_ref().unwrap_or(plan) {
+        LogicalPlan::Aggregate(aggregate) => rewrite_aggregate(aggregate)?,
+        // The projection added below an existing projection is merged into it, so that the
+        // aggregation can be proven with the projection as postprocessing
+        LogicalPlan::Projection(projection) => match projection.input.as_ref() {
+            LogicalPlan::Projection(inner_projection)
+                if rebuilt_plan.is_some()
+                    && matches!(inner_projection.input.as_ref(), LogicalPlan::Aggregate(_)) =>
+            {
+                Some(merge_projections(projection, inner_projection)?)
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    Ok(rewritten_plan.or(rebuilt_plan))
+}
+
+/// Whether `expr` is an `AVG`
+fn is_average(expr: &Expr) -> bool {
+    match expr {
+        Expr::Alias(alias) => is_average(&alias.expr),
+        Expr::AggregateFunction(AggregateFunction {
+            func_def: AggregateFunctionDefinition::BuiltIn(BuiltInAggregateFunction::Avg),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+/// The argument of an `AVG` we can rewrite
+fn average_argument(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Alias(alias) => average_argument(&alias.expr),
+        Expr::AggregateFunction(AggregateFunction {
+            func_def: AggregateFunctionDefinition::BuiltIn(BuiltInAggregateFunction::Avg),
+            args,
+            distinct: false,
+            filter: None,
+            order_by: None,
+            ..
+        }) if args.len() == 1 => Some(&args[0]),
+        _ => None,
+    }
+}
+
+/// Whether `expr` is a `COUNT` of all rows, which is the case for any non-distinct count without
+/// a filter since columns are not nullable
+fn is_row_count(expr: &Expr) -> bool {
+    match expr {
+        Expr::Alias(alias) => is_row_count(&alias.expr),
+        Expr::AggregateFunction(AggregateFunction {
+            func_def: AggregateFunctionDefinition::BuiltIn(BuiltInAggregateFunction::Count),
+            args,
+            distinct: false,
+            filter: None,
+            order_by: None,
+            ..
+        }) => args.len() == 1,
+        _ => false,
+    }
+}
+
+/// Whether `expr` is a `COUNT(DISTINCT ..)`
+fn is_distinct_count(expr: &Expr) -> bool {
+    match expr {
+        Expr::Alias(alias) => is_distinct_count(&alias.expr),
+        Expr::AggregateFunction(AggregateFunction {
+            func_def: AggregateFunctionDefinition::BuiltIn(BuiltInAggregateFunction::Count),
+            distinct: true,
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+/// Whether the aggregate expressions are ordered as `SUM`s, a `COUNT` and `COUNT(DISTINCT ..)`s
+fn is_in_provable_order(aggr_expr: &[Expr]) -> bool {
+    let num_distinct_counts = aggr_expr
+        .iter()
+        .rev()
+        .take_while(|e| is_distinct_count(e))
+        .count();
+    match aggr_expr[..aggr_expr.len() - num_distinct_counts].split_last() {
+        Some((count, sums)) => {
+            is_row_count(count) && !sums.iter().any(|e| is_row_count(e) || is_distinct_count(e))
+        }
+        None => false,
+    }
+}
+
+/// Replace the `AVG`s of a single aggregation by `SUM`s and a `COUNT`, and divide them in a
+/// projection on top which preserves the output schema of the aggregation
+///
+/// Returns `None` if the aggregation has an `AVG` we can't rewrite, or has no `AVG` and either
+/// no `COUNT(DISTINCT ..)` or its aggregate expressions already in provable order.
+fn rewrite_aggregate(aggregate: &Aggregate) -> PlannerResult<Option<LogicalPlan>> {
+    let averages = aggregate
+        .aggr_expr
+        .iter()
+        .filter(|e| is_average(e))
+        .collect::<Vec<_>>();
+    if !averages.iter().all(|e| average_argument(e).is_some()) {
+        return Ok(None);
+    }
+    if averages.is_empty()
+        && (!aggregate.aggr_expr.iter().any(is_distinct_count)
+            || is_in_provable_order(&aggregate.aggr_expr))
+    {
+        return Ok(None);
+    }
+    // The aggregate expressions have to be ordered as SUMs, COUNT and COUNT(DISTINCT ..)s
+    let mut sums: IndexMap<String, Expr> = IndexMap::new();
+    let mut counts: IndexMap<String, Expr> = IndexMap::new();
+    let mut distinct_counts: Vec<Expr> = Vec::new();
+    // The SUM each average is replaced by, keyed by the name of the average
+    let mut average_sums: IndexMap<String, String> = IndexMap::new();
+    for e in &aggregate.aggr_expr {
+        let name = e.display_name()?;
+        if let Some(argument) = average_argument(e) {
+            // `AVG` of an integer is coerced to `AVG` of a `Float64`, which we don't support
+            let argument = match argument {
+                Expr::Cast(Cast {
+                    expr,
+                    data_type: DataType::Float64,
+                }) => expr.as_ref().clone(),
+                _ => argument.clone(),
+            };
+            let sum = Expr::AggregateFunction(AggregateFunction::new(
+                BuiltInAggregateFunction::Sum,
+                vec![argument],
+                false,
+                None,
+                None,
+                None,
+            ));
+            let sum_name = sum.display_name()?;
+            sums.entry(sum_name.clone()).or_insert(sum);
+            average_sums.insert(name, sum_name);
+        } else if is_row_count(e) {
+            counts.insert(name, e.clone());
+        } else if is_distinct_count(e) {
+            distinct_counts.push(e.clone());
+        } else {
+            sums.entry(name).or_insert_with(|| e.clone());
+        }
+    }
+    let count_name = match counts.keys().next() {
+        Some(count_name) => count_name.clone(),
+        None => {
+            let count = Expr::AggregateFunction(AggregateFunction::new(
+                BuiltInAggregateFunction::Count,
+                vec![lit(1_i64)],
+                false,
+                None,
+                None,
+                None,
+            ));
+            let count_name = count.display_name()?;
+            counts.insert(count_name.clone(), count);
+            count_name
+        }
+    };
+    let aggr_expr = sums
+        .into_values()
+        .chain(counts.into_values())
+        .chain(distinct_counts)
+        .collect();
+    let rewritten_aggregate = Aggregate::try_new(
+        Arc::clone(&aggregate.input),
+        aggregate.group_expr.clone(),
+        aggr_expr,
+    )?;
+    // Restore the output schema of the original aggregation
+    let expr = aggregate
+        .schema
+        .columns()
+        .into_iter()
+        .map(|column| match average_sums.get(&column.name) {
+            Some(sum_name) => (Expr::Column(Column::from_name(sum_name))
+                / Expr::Column(Column::from_name(&count_name)))
+            .alias(&column.name),
+            None => Expr::Column(column),
+        })
+        .collect();
+    Ok(Some(LogicalPlan::Projection(Projection::try_new(
+        expr,
+        Arc::new(LogicalPlan::Aggregate(rewritten_aggregate)),
+    )?)))
+}
+
+/// Merge a projection into the projection below it
+fn merge_projections(
+    projection: &Projection,
+    inner_projection: &Projection,
+) -> PlannerResult<LogicalPlan> {
+    let replacements = inner_projection
+        .schema
+        .columns()
+        .into_iter()
+        .zip(inner_projection.expr.iter().map(|e| e.clone().unalias()))
+        .collect::<IndexMap<_, _>>();
+    // Keep the names of the projected expressions, which are the names of the output columns
+    let expr = projection
+        .expr
+        .iter()
+        .map(|e| {
+            let name = e.display_name()?;
+            let merged = substitute_columns(e.clone(), &replacements)?;
+            Ok(if merged.display_name()? == name {
+                merged
+            } else {
+                merged.unalias().alias(name)
+            })
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    Ok(LogicalPlan::Projection(Projection::try_new(
+        expr,
+        Arc::clone(&inner_projection.input),
+    )?))
+}
@@ -1,5 +1,5 @@
 use super::table_reference_to_table_ref;
-use crate::schema_to_column_fields;
+use crate::{get_udf, schema_to_column_fields, udf_names};
 use alloc::sync::Arc;
 use arrow::datatypes::{Field, Schema};
 use core::any::Any;
@@ -46,8 +46,8 @@ impl<A: SchemaAccessor> ContextProvider for PoSqlContextProvider<A> {
         let column_fields = schema_to_column_fields(schema);
         Ok(Arc::new(PoSqlTableSource::new(column_fields)) as Arc<dyn TableSource>)
     }
-    fn get_function_meta(&self, _name: &str) -> Option<Arc<ScalarUDF>> {
-        None
+    fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        get_udf(name)
     }
     //TODO: add count and sum
     fn get_aggregate_meta(&self, _name: &str) -> Option<Arc<AggregateUDF>> {
@@ -63,7 +63,7 @@ impl<A: SchemaAccessor> ContextProvider for PoSqlContextProvider<A> {
         &self.options
     }
     fn udfs_names(&self) -> Vec<String> {
-        Vec::new()
+        udf_names()
     }
     fn udafs_names(&self) -> Vec<String> {
         Vec::new()
@@ -159,11 +159,29 @@ mod tests {
         // Empty
         let accessor = TestSchemaAccessor::new(indexmap_with_default! {AHasher;});
         let context_provider = PoSqlContextProvider::new(accessor);
-        assert_eq!(context_provider.udfs_names(), Vec::<String>::new());
+        assert_eq!(
+            context_provider.udfs_names(),
+            vec![
+                "if".to_string(),
+                "iif".to_string(),
+                "strpos".to_string(),
+                "instr".to_string(),
+                "round_to".to_string(),
+                "split_part".to_string(),
+                "isqrt".to_string(),
+                "pow_mod".to_string()
+            ]
+        );
         assert_eq!(context_provider.udafs_names(), Vec::<String>::new());
         assert_eq!(context_provider.udwfs_names(), Vec::<String>::new());
         assert_eq!(context_provider.get_variable_type(&[]), None);
         assert_eq!(context_provider.get_function_meta(""), None);
+        assert!(context_provider.get_function_meta("iif").is_some());
+        assert!(context_provider.get_function_meta("strpos").is_some());
+        assert!(context_provider.get_function_meta("round_to").is_some());
+        assert!(context_provider.get_function_meta("split_part").is_some());
+        assert!(context_provider.get_function_meta("isqrt").is_some());
+        assert!(context_provider.get_function_meta("pow_mod").is_some());
         assert_eq!(context_provider.get_aggregate_meta(""), None);
         assert_eq!(context_provider.get_window_meta(""), None);
         assert_eq!(
@@ -186,11 +204,29 @@ mod tests {
             },
         });
         let context_provider = PoSqlContextProvider::new(accessor);
-        assert_eq!(context_provider.udfs_names(), Vec::<String>::new());
+        assert_eq!(
+            context_provider.udfs_names(),
+            vec![
+                "if".to_string(),
+                "iif".to_string(),
+                "strpos".to_string(),
+                "instr".to_string(),
+                "round_to".to_string(),
+                "split_part".to_string(),
+                "isqrt".to_string(),
+                "pow_mod".to_string()
+            ]
+        );
         assert_eq!(context_provider.udafs_names(), Vec::<String>::new());
         assert_eq!(context_provider.udwfs_names(), Vec::<String>::new());
         assert_eq!(context_provider.get_variable_type(&[]), None);
         assert_eq!(context_provider.get_function_meta(""), None);
+        assert!(context_provider.get_function_meta("iif").is_some());
+        assert!(context_provider.get_function_meta("strpos").is_some());
+        assert!(context_provider.get_function_meta("round_to").is_some());
+        assert!(context_provider.get_function_meta("split_part").is_some());
+        assert!(context_provider.get_function_meta("isqrt").is_some());
+        assert!(context_provider.get_function_meta("pow_mod").is_some());
         assert_eq!(context_provider.get_aggregate_meta(""), None);
         assert_eq!(context_provider.get_window_meta(""), None);
         assert_eq!(
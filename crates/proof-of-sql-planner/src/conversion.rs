@@ -1,9 +1,11 @@
 use crate::{
-    logical_plan_to_proof_plan, logical_plan_to_proof_plan_with_postprocessing, PlannerResult,
-    PoSqlContextProvider, ProofPlanWithPostprocessing,
+    flatten_aggregates, logical_plan_to_proof_plan, logical_plan_to_proof_plan_with_postprocessing,
+    rewrite_averages, PlannerError, PlannerResult, PoSqlContextProvider,
+    ProofPlanWithPostprocessing,
 };
 use alloc::{sync::Arc, vec::Vec};
 use datafusion::{
+    common::{DataFusionError, SchemaError},
     config::ConfigOptions,
     logical_expr::LogicalPlan,
     optimizer::{Analyzer, Optimizer, OptimizerContext, OptimizerRule},
@@ -12,38 +14,195 @@ use datafusion::{
 use indexmap::IndexSet;
 use proof_of_sql::{
     base::database::{ParseError, SchemaAccessor, TableRef},
-    sql::proof_plans::DynProofPlan,
+    sql::{proof_plans::DynProofPlan, ExpressionLimits},
+};
+use sqlparser::ast::{
+    visit_expressions, visit_relations, Expr, Ident, Query, Select, SelectItem, SetExpr, Statement,
+    Visit, Visitor,
 };
-use sqlparser::ast::{visit_relations, Statement};
 use std::ops::ControlFlow;
 
 /// Get [`Optimizer`]
 ///
 /// In order to support queries such as `select $1::varchar;` we have to temporarily disable
 /// [`CommonSubexprEliminate`] rule in the optimizer in `DataFusion` 38. Once we upgrade to
-/// `DataFusion` 46 we can remove this function and use `Optimizer::new()` directly.
+/// `DataFusion` 46 we can remove this from this function.
+///
+/// The `SingleDistinctToGroupBy` rule is disabled as well. It rewrites
+/// `SELECT a, COUNT(DISTINCT b), SUM(c) FROM t GROUP BY a` into an aggregation over an
+/// aggregation grouped by `a` and `b`, which we can't prove, while `GroupByExec` proves
+/// `COUNT(DISTINCT b)` directly.
 pub fn optimizer() -> Optimizer {
     // Step 1: Grab the recommended set
     let recommended_rules: Vec<Arc<dyn OptimizerRule + Send + Sync>> = Optimizer::new().rules;
 
-    // Step 2: Filter out [`CommonSubexprEliminate`]
+    // Step 2: Filter out [`CommonSubexprEliminate`] and `SingleDistinctToGroupBy`
     let filtered_rules = recommended_rules
         .into_iter()
-        .filter(|rule| rule.name() != "common_sub_expression_eliminate")
+        .filter(|rule| {
+            !matches!(
+                rule.name(),
+                "common_sub_expression_eliminate" | "single_distinct_aggregation_to_group_by"
+            )
+        })
         .collect::<Vec<_>>();
 
     // Step 3: Build an optimizer with the new list
     Optimizer::with_rules(filtered_rules)
 }
 
+/// Check whether a `Statement` contains a `WITH RECURSIVE` clause anywhere
+fn contains_recursive_cte(statement: &Statement) -> bool {
+    struct RecursiveCteFinder;
+    impl Visitor for RecursiveCteFinder {
+        type Break = ();
+        fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+            if query.with.as_ref().is_some_and(|with| with.recursive) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+    statement.visit(&mut RecursiveCteFinder).is_break()
+}
+
+/// The name `DataFusion` resolves an identifier to
+fn normalized_name(ident: &Ident, enable_ident_normalization: bool) -> String {
+    if enable_ident_normalization && ident.quote_style.is_none() {
+        ident.value.to_lowercase()
+    } else {
+        ident.value.clone()
+    }
+}
+
+/// Find a result alias of a `SELECT` which the `WHERE` clause of the same `SELECT` references
+fn find_result_alias_in_where_clause(
+    statement: &Statement,
+    enable_ident_normalization: bool,
+) -> Option<String> {
+    /// The `SELECT`s of a query body, excluding those of nested queries
+    fn selects(body: &SetExpr) -> Vec<&Select> {
+        match body {
+            SetExpr::Select(select) => vec![select],
+            SetExpr::SetOperation { left, right, .. } => {
+                selects(left).into_iter().chain(selects(right)).collect()
+            }
+            _ => vec![],
+        }
+    }
+    struct ResultAliasFinder {
+        enable_ident_normalization: bool,
+    }
+    impl Visitor for ResultAliasFinder {
+        type Break = String;
+        fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+            for select in selects(&query.body) {
+                let aliases: Vec<String> = select
+                    .projection
+                    .iter()
+                    .filter_map(|item| match item {
+                        SelectItem::ExprWithAlias { alias, .. } => {
+                            Some(normalized_name(alias, self.enable_ident_normalization))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(selection) = &select.selection {
+                    visit_expressions(selection, |expr| match expr {
+                        Expr::Identifier(ident) => {
+                            let name = normalized_name(ident, self.enable_ident_normalization);
+                            if aliases.contains(&name) {
+                                ControlFlow::Break(name)
+                            } else {
+                                ControlFlow::Continue(())
+                            }
+                        }
+                        _ => ControlFlow::Continue(()),
+                    })?;
+                }
+            }
+            ControlFlow::Continue(())
+        }
+    }
+    statement
+        .visit(&mut ResultAliasFinder {
+            enable_ident_normalization,
+        })
+        .break_value()
+}
+
+/// Replace the error of a `WHERE` clause referencing a result alias, which `DataFusion` reports as a
+/// missing column, with a clear one
+///
+/// A column of a queried table takes precedence over a result alias of the same name, in which
+/// case `DataFusion` does not fail on the reference.
+fn explain_result_alias_in_where_clause(
+    error: DataFusionError,
+    statement: &Statement,
+    enable_ident_normalization: bool,
+) -> PlannerError {
+    match (
+        find_result_alias_in_where_clause(statement, enable_ident_normalization),
+        error.find_root(),
+    ) {
+        (
+            Some(alias),
+            DataFusionError::SchemaError(SchemaError::FieldNotFound { field, .. }, _),
+        ) if field.name == alias => PlannerError::ResultAliasInWhereClause { alias },
+        _ => error.into(),
+    }
+}
+
+/// Convert a `Statement` into an optimized `LogicalPlan`
+///
+/// This is steps 2 to 4 of `sql_to_posql_plans`.
+fn statement_to_logical_plan<A: SchemaAccessor>(
+    ast: &Statement,
+    context_provider: &PoSqlContextProvider<A>,
+    config: &ConfigOptions,
+) -> PlannerResult<LogicalPlan> {
+    if contains_recursive_cte(ast) {
+        return Err(PlannerError::RecursiveCteNotSupported);
+    }
+    // 2. Convert the AST into a `LogicalPlan` using `SqlToRel`
+    let enable_ident_normalization = config.sql_parser.enable_ident_normalization;
+    let raw_logical_plan = SqlToRel::new_with_options(
+        context_provider,
+        ParserOptions {
+            parse_float_as_decimal: config.sql_parser.parse_float_as_decimal,
+            enable_ident_normalization,
+        },
+    )
+    .sql_statement_to_plan(ast.clone())
+    .map_err(|error| {
+        explain_result_alias_in_where_clause(error, ast, enable_ident_normalization)
+    })?;
+    // 3. Analyze the `LogicalPlan` using `Analyzer`
+    let analyzer = Analyzer::new();
+    let analyzed_logical_plan = analyzer.execute_and_check(raw_logical_plan, config, |_, _| {})?;
+    // 4. Optimize the `LogicalPlan` using `Optimizer`
+    let optimizer = optimizer();
+    let optimizer_context = OptimizerContext::default();
+    Ok(optimizer.optimize(analyzed_logical_plan, &optimizer_context, |_, _| {})?)
+}
+
 /// Convert a SQL query to a Proof of SQL plan using schema from provided tables
 ///
+/// Non-recursive CTEs are inlined wherever they are referenced.
+/// Recursive CTEs are not supported.
+/// Result aliases cannot be referenced in `WHERE` clauses, since these are evaluated first.
+///
 /// This function does the following
-/// 1. Parse the SQL query into AST using sqlparser
+/// 1. Parse the SQL query into AST using sqlparser, which the caller does with the dialect of
+///    its choice
 /// 2. Convert the AST into a `LogicalPlan` using `SqlToRel`
 /// 3. Analyze the `LogicalPlan` using `Analyzer`
 /// 4. Optimize the `LogicalPlan` using `Optimizer`
-/// 5. Convert the optimized `LogicalPlan` into a Proof of SQL plan
+/// 5. Flatten aggregations over derived tables in the optimized `LogicalPlan`
+/// 6. Rewrite averages in the flattened `LogicalPlan` into sums divided by counts, and order the
+///    aggregate expressions of aggregations with distinct counts
+/// 7. Convert the rewritten `LogicalPlan` into a Proof of SQL plan
 fn sql_to_posql_plans<T, F, A>(
     statements: &[Statement],
     schemas: &A,
@@ -55,61 +214,102 @@ where
     A: SchemaAccessor + Clone,
 {
     let context_provider = PoSqlContextProvider::new(schemas.clone());
-    // 1. Parse the SQL query into AST using sqlparser
+    // 1. The SQL query has been parsed into AST by the caller
     statements
         .iter()
         .map(|ast| -> PlannerResult<T> {
-            // 2. Convert the AST into a `LogicalPlan` using `SqlToRel`
-            let raw_logical_plan = SqlToRel::new_with_options(
-                &context_provider,
-                ParserOptions {
-                    parse_float_as_decimal: config.sql_parser.parse_float_as_decimal,
-                    enable_ident_normalization: config.sql_parser.enable_ident_normalization,
-                },
-            )
-            .sql_statement_to_plan(ast.clone())?;
-            // 3. Analyze the `LogicalPlan` using `Analyzer`
-            let analyzer = Analyzer::new();
-            let analyzed_logical_plan =
-                analyzer.execute_and_check(raw_logical_plan, config, |_, _| {})?;
-            // 4. Optimize the `LogicalPlan` using `Optimizer`
-            let optimizer = optimizer();
-            let optimizer_context = OptimizerContext::default();
-            let optimized_logical_plan =
-                optimizer.optimize(analyzed_logical_plan, &optimizer_context, |_, _| {})?;
-            // 5. Convert the optimized `LogicalPlan` into a Proof of SQL plan
-            planner_converter(&optimized_logical_plan, schemas)
+            let optimized_logical_plan = statement_to_logical_plan(ast, &context_provider, config)?;
+            // 5. Flatten aggregations over derived tables in the optimized `LogicalPlan`
+            let flattened_logical_plan = flatten_aggregates(&optimized_logical_plan)?;
+            // 6. Rewrite averages in the flattened `LogicalPlan` into sums divided by counts
+            let rewritten_logical_plan = rewrite_averages(&flattened_logical_plan)?;
+            // 7. Convert the rewritten `LogicalPlan` into a Proof of SQL plan
+            planner_converter(&rewritten_logical_plan, schemas)
         })
         .collect::<PlannerResult<Vec<_>>>()
 }
 
+/// Convert a SQL query to optimized `LogicalPlan`s using schema from provided tables
+///
+/// This is intended for debugging the conversion of a query. The plans are returned in
+/// statement order, as they are after optimization (step 4 of `sql_to_posql_plans`)
+/// and before they are lowered into Proof of SQL plans.
+pub fn sql_to_logical_plans<A: SchemaAccessor + Clone>(
+    statements: &[Statement],
+    schemas: &A,
+    config: &ConfigOptions,
+) -> PlannerResult<Vec<LogicalPlan>> {
+    let context_provider = PoSqlContextProvider::new(schemas.clone());
+    statements
+        .iter()
+        .map(|ast| statement_to_logical_plan(ast, &context_provider, config))
+        .collect()
+}
+
 /// Convert a SQL query to a `DynProofPlan` using schema from provided tables
 ///
+/// The resulting plans are checked against the default [`ExpressionLimits`].
 /// See `sql_to_posql_plans` for more details
 pub fn sql_to_proof_plans<A: SchemaAccessor + Clone>(
     statements: &[Statement],
     schemas: &A,
     config: &ConfigOptions,
 ) -> PlannerResult<Vec<DynProofPlan>> {
-    sql_to_posql_plans(statements, schemas, config, logical_plan_to_proof_plan)
+    sql_to_proof_plans_with_limits(statements, schemas, config, &ExpressionLimits::default())
+}
+
+/// Convert a SQL query to a `DynProofPlan` using schema from provided tables,
+/// erroring if any resulting plan exceeds `limits`
+///
+/// See `sql_to_posql_plans` for more details
+pub fn sql_to_proof_plans_with_limits<A: SchemaAccessor + Clone>(
+    statements: &[Statement],
+    schemas: &A,
+    config: &ConfigOptions,
+    limits: &ExpressionLimits,
+) -> PlannerResult<Vec<DynProofPlan>> {
+    sql_to_posql_plans(statements, schemas, config, |plan, schemas| {
+        let proof_plan = logical_plan_to_proof_plan(plan, schemas)?;
+        limits.check_plan(&proof_plan)?;
+        Ok(proof_plan)
+    })
 }
 
 /// Convert a SQL query to a `ProofPlanWithPostprocessing` using schema from provided tables
 ///
+/// The resulting plans are checked against the default [`ExpressionLimits`].
 /// See `sql_to_posql_plans` for more details
 pub fn sql_to_proof_plans_with_postprocessing<A: SchemaAccessor + Clone>(
     statements: &[Statement],
     schemas: &A,
     config: &ConfigOptions,
 ) -> PlannerResult<Vec<ProofPlanWithPostprocessing>> {
-    sql_to_posql_plans(
+    sql_to_proof_plans_with_postprocessing_and_limits(
         statements,
         schemas,
         config,
-        logical_plan_to_proof_plan_with_postprocessing,
+        &ExpressionLimits::default(),
     )
 }
 
+/// Convert a SQL query to a `ProofPlanWithPostprocessing` using schema from provided tables,
+/// erroring if any resulting plan exceeds `limits`
+///
+/// See `sql_to_posql_plans` for more details
+pub fn sql_to_proof_plans_with_postprocessing_and_limits<A: SchemaAccessor + Clone>(
+    statements: &[Statement],
+    schemas: &A,
+    config: &ConfigOptions,
+    limits: &ExpressionLimits,
+) -> PlannerResult<Vec<ProofPlanWithPostprocessing>> {
+    sql_to_posql_plans(statements, schemas, config, |plan, schemas| {
+        let plan_with_postprocessing =
+            logical_plan_to_proof_plan_with_postprocessing(plan, schemas)?;
+        limits.check_plan(plan_with_postprocessing.plan())?;
+        Ok(plan_with_postprocessing)
+    })
+}
+
 /// Given a `Statement` retrieves all unique tables in the query
 pub fn get_table_refs_from_statement(
     statement: &Statement,
@@ -131,11 +331,117 @@ pub fn get_table_refs_from_statement(
 
 #[cfg(test)]
 mod tests {
-    use super::get_table_refs_from_statement;
-    use indexmap::IndexSet;
-    use proof_of_sql::base::database::TableRef;
+    use super::{get_table_refs_from_statement, sql_to_logical_plans, sql_to_proof_plans};
+    use crate::{flatten_aggregates, logical_plan_to_proof_plan, PlannerError};
+    use ahash::AHasher;
+    use datafusion::config::ConfigOptions;
+    use indexmap::{indexmap_with_default, IndexSet};
+    use proof_of_sql::base::database::{ColumnType, TableRef, TestSchemaAccessor};
     use sqlparser::{dialect::GenericDialect, parser::Parser};
 
+    fn schemas() -> TestSchemaAccessor {
+        TestSchemaAccessor::new(indexmap_with_default! {AHasher;
+            TableRef::new("namespace", "table") => indexmap_with_default! {AHasher;
+                "a".into() => ColumnType::BigInt,
+                "b".into() => ColumnType::VarChar
+            },
+        })
+    }
+
+    #[test]
+    fn we_can_get_the_logical_plans_that_are_lowered_into_proof_plans() {
+        let statements = Parser::parse_sql(
+            &GenericDialect {},
+            "SELECT a FROM namespace.table WHERE a > 1; SELECT b, COUNT(a) FROM namespace.table GROUP BY b;",
+        )
+        .unwrap();
+        let schemas = schemas();
+        let config = ConfigOptions::default();
+
+        let logical_plans = sql_to_logical_plans(&statements, &schemas, &config).unwrap();
+        assert_eq!(logical_plans.len(), 2);
+        // the plans are in statement order
+        assert!(logical_plans[0].to_string().contains("Filter"));
+        assert!(logical_plans[1].to_string().contains("Aggregate"));
+
+        let proof_plans = sql_to_proof_plans(&statements, &schemas, &config).unwrap();
+        let lowered_plans = logical_plans
+            .iter()
+            .map(|plan| {
+                logical_plan_to_proof_plan(&flatten_aggregates(plan).unwrap(), &schemas).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lowered_plans, proof_plans);
+    }
+
+    #[test]
+    fn we_cannot_get_logical_plans_of_recursive_ctes() {
+        let statements = Parser::parse_sql(
+            &GenericDialect {},
+            "WITH RECURSIVE t AS (SELECT a FROM namespace.table) SELECT a FROM t;",
+        )
+        .unwrap();
+        assert!(matches!(
+            sql_to_logical_plans(&statements, &schemas(), &ConfigOptions::default()),
+            Err(PlannerError::RecursiveCteNotSupported)
+        ));
+    }
+
+    #[test]
+    fn we_cannot_reference_result_aliases_in_where_clauses() {
+        let statements = Parser::parse_sql(
+            &GenericDialect {},
+            "SELECT a + 1 AS y FROM namespace.table WHERE y > 0;",
+        )
+        .unwrap();
+        assert!(matches!(
+            sql_to_logical_plans(&statements, &schemas(), &ConfigOptions::default()),
+            Err(PlannerError::ResultAliasInWhereClause { alias }) if alias == "y"
+        ));
+
+        // Aliases are normalized like any other identifier
+        let statements = Parser::parse_sql(
+            &GenericDialect {},
+            "SELECT a + 1 AS Y FROM namespace.table WHERE y > 0;",
+        )
+        .unwrap();
+        assert!(matches!(
+            sql_to_logical_plans(&statements, &schemas(), &ConfigOptions::default()),
+            Err(PlannerError::ResultAliasInWhereClause { alias }) if alias == "y"
+        ));
+
+        // Unknown columns which are not aliases are still reported by DataFusion
+        let statements = Parser::parse_sql(
+            &GenericDialect {},
+            "SELECT a + 1 AS y FROM namespace.table WHERE z > 0;",
+        )
+        .unwrap();
+        assert!(matches!(
+            sql_to_logical_plans(&statements, &schemas(), &ConfigOptions::default()),
+            Err(PlannerError::DataFusionError { .. })
+        ));
+    }
+
+    #[test]
+    fn a_column_takes_precedence_over_a_result_alias_in_a_where_clause() {
+        let statements = Parser::parse_sql(
+            &GenericDialect {},
+            "SELECT a + 1 AS a FROM namespace.table WHERE a > 1;",
+        )
+        .unwrap();
+        let schemas = schemas();
+        let config = ConfigOptions::default();
+        let expected = Parser::parse_sql(
+            &GenericDialect {},
+            "SELECT a + 1 AS a FROM namespace.table WHERE namespace.table.a > 1;",
+        )
+        .unwrap();
+        assert_eq!(
+            sql_to_proof_plans(&statements, &schemas, &config).unwrap(),
+            sql_to_proof_plans(&expected, &schemas, &config).unwrap()
+        );
+    }
+
     #[test]
     fn we_can_get_table_references() {
         let statement = Parser::parse_sql(
@@ -7,7 +7,10 @@ use datafusion::{
     },
     physical_plan,
 };
-use proof_of_sql::{base::math::decimal::DecimalError, sql::AnalyzeError};
+use proof_of_sql::{
+    base::math::decimal::DecimalError,
+    sql::{AnalyzeError, ExpressionLimitsError},
+};
 use snafu::Snafu;
 use sqlparser::parser::ParserError;
 
@@ -26,6 +29,12 @@ pub enum PlannerError {
         /// Underlying decimal error
         source: DecimalError,
     },
+    /// Returned when a plan exceeds the configured expression limits
+    #[snafu(transparent)]
+    ExpressionLimitsError {
+        /// Underlying expression limits error
+        source: ExpressionLimitsError,
+    },
     /// Returned when sqlparser fails to parse a query
     #[snafu(transparent)]
     SqlParserError {
@@ -101,6 +110,29 @@ pub enum PlannerError {
     /// Returned when catalog is provided since it is not supported
     #[snafu(display("Catalog is not supported"))]
     CatalogNotSupported,
+    /// Returned when a query contains a recursive common table expression
+    #[snafu(display("Recursive common table expressions are not supported"))]
+    RecursiveCteNotSupported,
+    /// Returned when a `GROUP BY GROUPING SETS` has more grouping sets than are supported
+    #[snafu(display(
+        "{num_grouping_sets} grouping sets are not supported, at most {} are",
+        crate::MAX_GROUPING_SETS
+    ))]
+    TooManyGroupingSets {
+        /// The number of grouping sets
+        num_grouping_sets: usize,
+    },
+    /// Returned when a `WHERE` clause references a result alias of its own `SELECT`
+    ///
+    /// As in standard SQL, the `WHERE` clause is evaluated before the results are computed, so
+    /// only columns of the queried tables can be referenced in it.
+    #[snafu(display(
+        "Result alias {alias} cannot be referenced in a WHERE clause, repeat its expression instead"
+    ))]
+    ResultAliasInWhereClause {
+        /// The referenced alias
+        alias: String,
+    },
     /// Returned when error occurs in postprocessing
     #[snafu(transparent)]
     PostprocessingError {
@@ -1,9 +1,10 @@
 use super::{
     column_to_column_ref, placeholder_to_placeholder_expr, scalar_value_to_literal_value,
-    PlannerError, PlannerResult,
+    PlannerError, PlannerResult, IF_FUNCTION_NAME, ISQRT_FUNCTION_NAME, POW_MOD_FUNCTION_NAME,
+    ROUND_TO_FUNCTION_NAME,
 };
 use datafusion::logical_expr::{
-    expr::{Alias, Placeholder},
+    expr::{Alias, Placeholder, ScalarFunction},
     BinaryExpr, Expr, Operator,
 };
 use proof_of_sql::{
@@ -81,11 +82,80 @@ fn binary_expr_to_proof_expr(
             left_proof_expr,
             right_proof_expr,
         )?),
+        Operator::Modulo => Ok(DynProofExpr::try_new_modulo(
+            left_proof_expr,
+            &right_proof_expr,
+        )?),
         // Any other operator is unsupported
         _ => Err(PlannerError::UnsupportedBinaryOperator { op }),
     }
 }
 
+/// Convert an `IF(condition, then, else)` call to [`DynProofExpr`]
+fn if_function_to_proof_expr(
+    scalar_function: &ScalarFunction,
+    schema: &[(Ident, ColumnType)],
+) -> PlannerResult<DynProofExpr> {
+    match scalar_function.args.as_slice() {
+        [condition, then_expr, else_expr] => Ok(DynProofExpr::try_new_if(
+            expr_to_proof_expr(condition, schema)?,
+            expr_to_proof_expr(then_expr, schema)?,
+            expr_to_proof_expr(else_expr, schema)?,
+        )?),
+        _ => Err(PlannerError::UnsupportedLogicalExpression {
+            expr: Expr::ScalarFunction(scalar_function.clone()),
+        }),
+    }
+}
+
+/// Convert a `ROUND_TO(value, step)` call to [`DynProofExpr`]
+fn round_to_function_to_proof_expr(
+    scalar_function: &ScalarFunction,
+    schema: &[(Ident, ColumnType)],
+) -> PlannerResult<DynProofExpr> {
+    match scalar_function.args.as_slice() {
+        [value, step] => Ok(DynProofExpr::try_new_round_to_multiple(
+            expr_to_proof_expr(value, schema)?,
+            &expr_to_proof_expr(step, schema)?,
+        )?),
+        _ => Err(PlannerError::UnsupportedLogicalExpression {
+            expr: Expr::ScalarFunction(scalar_function.clone()),
+        }),
+    }
+}
+
+/// Convert an `ISQRT(value)` call to [`DynProofExpr`]
+fn isqrt_function_to_proof_expr(
+    scalar_function: &ScalarFunction,
+    schema: &[(Ident, ColumnType)],
+) -> PlannerResult<DynProofExpr> {
+    match scalar_function.args.as_slice() {
+        [value] => Ok(DynProofExpr::try_new_isqrt(expr_to_proof_expr(
+            value, schema,
+        )?)?),
+        _ => Err(PlannerError::UnsupportedLogicalExpression {
+            expr: Expr::ScalarFunction(scalar_function.clone()),
+        }),
+    }
+}
+
+/// Convert a `POW_MOD(base, exponent, modulus)` call to [`DynProofExpr`]
+fn pow_mod_function_to_proof_expr(
+    scalar_function: &ScalarFunction,
+    schema: &[(Ident, ColumnType)],
+) -> PlannerResult<DynProofExpr> {
+    match scalar_function.args.as_slice() {
+        [base, exponent, modulus] => Ok(DynProofExpr::try_new_pow_mod(
+            expr_to_proof_expr(base, schema)?,
+            &expr_to_proof_expr(exponent, schema)?,
+            &expr_to_proof_expr(modulus, schema)?,
+        )?),
+        _ => Err(PlannerError::UnsupportedLogicalExpression {
+            expr: Expr::ScalarFunction(scalar_function.clone()),
+        }),
+    }
+}
+
 /// Convert an [`datafusion::expr::Expr`] to [`DynProofExpr`]
 ///
 /// # Panics
@@ -123,10 +193,32 @@ pub fn expr_to_proof_expr(
                             data_type: cast.data_type.clone(),
                         }
                     })?;
+                    // Literals which can be coerced to the type are not cast in the proof
+                    if let DynProofExpr::Literal(literal_expr) = &from_expr {
+                        if let Ok(literal) = literal_expr.value().try_coerce_to(&to_type) {
+                            return Ok(DynProofExpr::new_literal(literal));
+                        }
+                    }
                     Ok(DynProofExpr::try_new_cast(from_expr, to_type)?)
                 }
             }
         }
+        Expr::ScalarFunction(scalar_function) if scalar_function.name() == IF_FUNCTION_NAME => {
+            if_function_to_proof_expr(scalar_function, schema)
+        }
+        Expr::ScalarFunction(scalar_function)
+            if scalar_function.name() == ROUND_TO_FUNCTION_NAME =>
+        {
+            round_to_function_to_proof_expr(scalar_function, schema)
+        }
+        Expr::ScalarFunction(scalar_function) if scalar_function.name() == ISQRT_FUNCTION_NAME => {
+            isqrt_function_to_proof_expr(scalar_function, schema)
+        }
+        Expr::ScalarFunction(scalar_function)
+            if scalar_function.name() == POW_MOD_FUNCTION_NAME =>
+        {
+            pow_mod_function_to_proof_expr(scalar_function, schema)
+        }
         _ => Err(PlannerError::UnsupportedLogicalExpression { expr: expr.clone() }),
     }
 }
@@ -135,7 +227,7 @@ pub fn expr_to_proof_expr(
 mod tests {
     use super::*;
     use crate::df_util::*;
-    use arrow::datatypes::DataType;
+    use arrow::datatypes::{DataType, TimeUnit};
     use core::ops::{Add, Mul, Sub};
     use datafusion::{
         common::ScalarValue,
@@ -144,9 +236,16 @@ mod tests {
             Cast,
         },
     };
-    use proof_of_sql::base::{
-        database::{ColumnRef, ColumnType, LiteralValue, TableRef},
-        math::decimal::Precision,
+    use proof_of_sql::{
+        base::{
+            database::{ColumnRef, ColumnType, LiteralValue, TableRef},
+            math::{
+                decimal::{DecimalError, Precision},
+                i256::I256,
+            },
+            posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+        },
+        sql::{proof_exprs::ProofExpr, AnalyzeError},
     };
 
     #[expect(non_snake_case)]
@@ -516,6 +615,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_convert_arithmetic_binary_expr_with_mixed_integer_and_decimal_operands() {
+        let schema = vec![
+            ("column1".into(), ColumnType::SmallInt),
+            (
+                "column2".into(),
+                ColumnType::Decimal75(Precision::new(25).unwrap(), 5),
+            ),
+        ];
+        let cases = [
+            (
+                df_column("namespace.table_name", "column1")
+                    .add(df_column("namespace.table_name", "column2")),
+                DynProofExpr::try_new_add(COLUMN1_SMALLINT(), COLUMN2_DECIMAL_25_5()).unwrap(),
+                ColumnType::Decimal75(Precision::new(26).unwrap(), 5),
+            ),
+            (
+                df_column("namespace.table_name", "column2")
+                    .sub(df_column("namespace.table_name", "column1")),
+                DynProofExpr::try_new_subtract(COLUMN2_DECIMAL_25_5(), COLUMN1_SMALLINT()).unwrap(),
+                ColumnType::Decimal75(Precision::new(26).unwrap(), 5),
+            ),
+            (
+                df_column("namespace.table_name", "column2")
+                    .mul(df_column("namespace.table_name", "column1")),
+                DynProofExpr::try_new_multiply(COLUMN2_DECIMAL_25_5(), COLUMN1_SMALLINT()).unwrap(),
+                ColumnType::Decimal75(Precision::new(31).unwrap(), 5),
+            ),
+        ];
+        for (expr, expected_proof_expr, expected_type) in cases {
+            let proof_expr = expr_to_proof_expr(&expr, &schema).unwrap();
+            assert_eq!(proof_expr, expected_proof_expr);
+            assert_eq!(proof_expr.data_type(), expected_type);
+        }
+    }
+
+    #[test]
+    fn we_cannot_convert_arithmetic_binary_expr_if_scaling_exceeds_the_maximum_precision() {
+        let schema = vec![
+            (
+                "column1".into(),
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 6),
+            ),
+            (
+                "column3".into(),
+                ColumnType::Decimal75(Precision::new(75).unwrap(), 5),
+            ),
+        ];
+        let expr = df_column("namespace.table_name", "column3")
+            .add(df_column("namespace.table_name", "column1"));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::AnalyzeError {
+                source: AnalyzeError::DecimalConversionError {
+                    source: DecimalError::InvalidPrecision { .. }
+                }
+            })
+        ));
+    }
+
     #[test]
     fn we_can_convert_logical_binary_expr_to_proof_expr() {
         let schema = vec![
@@ -584,6 +743,280 @@ mod tests {
         );
     }
 
+    // If
+    fn df_if(condition: Expr, then_expr: Expr, else_expr: Expr) -> Expr {
+        Expr::ScalarFunction(ScalarFunction::new_udf(
+            crate::get_udf(IF_FUNCTION_NAME).unwrap(),
+            vec![condition, then_expr, else_expr],
+        ))
+    }
+
+    #[test]
+    fn we_can_convert_if_expr_to_proof_expr() {
+        let expr = df_if(
+            df_column("namespace.table_name", "column1"),
+            df_column("namespace.table_name", "column2"),
+            Expr::Literal(ScalarValue::Int64(Some(1))),
+        );
+        let schema = vec![
+            ("column1".into(), ColumnType::Boolean),
+            ("column2".into(), ColumnType::BigInt),
+        ];
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_if(
+                COLUMN1_BOOLEAN(),
+                COLUMN2_BIGINT(),
+                DynProofExpr::new_literal(LiteralValue::BigInt(1))
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_if_expr_to_proof_expr_with_mismatched_branch_types() {
+        let expr = df_if(
+            df_column("namespace.table_name", "column1"),
+            df_column("namespace.table_name", "column2"),
+            Expr::Literal(ScalarValue::Int32(Some(1))),
+        );
+        let schema = vec![
+            ("column1".into(), ColumnType::Boolean),
+            ("column2".into(), ColumnType::BigInt),
+        ];
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::AnalyzeError {
+                source: AnalyzeError::DataTypeMismatch { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_convert_if_expr_to_proof_expr_with_the_wrong_number_of_arguments() {
+        let expr = Expr::ScalarFunction(ScalarFunction::new_udf(
+            crate::get_udf(IF_FUNCTION_NAME).unwrap(),
+            vec![Expr::Literal(ScalarValue::Boolean(Some(true)))],
+        ));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &Vec::new()),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+
+    // RoundTo
+    fn df_round_to(value: Expr, step: Expr) -> Expr {
+        Expr::ScalarFunction(ScalarFunction::new_udf(
+            crate::get_udf(ROUND_TO_FUNCTION_NAME).unwrap(),
+            vec![value, step],
+        ))
+    }
+
+    #[test]
+    fn we_can_convert_round_to_expr_to_proof_expr() {
+        let expr = df_round_to(
+            df_column("namespace.table_name", "column2"),
+            Expr::Literal(ScalarValue::Int64(Some(100))),
+        );
+        let schema = vec![("column2".into(), ColumnType::BigInt)];
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_round_to_multiple(
+                COLUMN2_BIGINT(),
+                &DynProofExpr::new_literal(LiteralValue::BigInt(100))
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_round_to_expr_to_proof_expr_with_an_invalid_step() {
+        let schema = vec![
+            ("column1".into(), ColumnType::BigInt),
+            ("column2".into(), ColumnType::BigInt),
+        ];
+        for step in [
+            Expr::Literal(ScalarValue::Int64(Some(0))),
+            df_column("namespace.table_name", "column1"),
+        ] {
+            let expr = df_round_to(df_column("namespace.table_name", "column2"), step);
+            assert!(matches!(
+                expr_to_proof_expr(&expr, &schema),
+                Err(PlannerError::AnalyzeError {
+                    source: AnalyzeError::InvalidRoundingStep
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_convert_modulo_expr_to_proof_expr() {
+        let expr = df_column("namespace.table_name", "column2")
+            % Expr::Literal(ScalarValue::Int64(Some(10)));
+        let schema = vec![("column2".into(), ColumnType::BigInt)];
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_modulo(
+                COLUMN2_BIGINT(),
+                &DynProofExpr::new_literal(LiteralValue::BigInt(10))
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_modulo_expr_to_proof_expr_with_an_invalid_modulus() {
+        let schema = vec![
+            ("column1".into(), ColumnType::BigInt),
+            ("column2".into(), ColumnType::BigInt),
+        ];
+        for modulus in [
+            Expr::Literal(ScalarValue::Int64(Some(0))),
+            df_column("namespace.table_name", "column1"),
+        ] {
+            let expr = df_column("namespace.table_name", "column2") % modulus;
+            assert!(matches!(
+                expr_to_proof_expr(&expr, &schema),
+                Err(PlannerError::AnalyzeError {
+                    source: AnalyzeError::InvalidModulus
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_cannot_convert_round_to_expr_to_proof_expr_with_the_wrong_number_of_arguments() {
+        let expr = Expr::ScalarFunction(ScalarFunction::new_udf(
+            crate::get_udf(ROUND_TO_FUNCTION_NAME).unwrap(),
+            vec![Expr::Literal(ScalarValue::Int64(Some(1)))],
+        ));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &Vec::new()),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+
+    // ISqrt
+    fn df_isqrt(args: Vec<Expr>) -> Expr {
+        Expr::ScalarFunction(ScalarFunction::new_udf(
+            crate::get_udf(ISQRT_FUNCTION_NAME).unwrap(),
+            args,
+        ))
+    }
+
+    #[test]
+    fn we_can_convert_isqrt_expr_to_proof_expr() {
+        let expr = df_isqrt(vec![df_column("namespace.table_name", "column2")]);
+        let schema = vec![("column2".into(), ColumnType::BigInt)];
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_isqrt(COLUMN2_BIGINT()).unwrap()
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_isqrt_expr_to_proof_expr_with_a_non_integer_argument() {
+        let expr = df_isqrt(vec![df_column("namespace.table_name", "column1")]);
+        let schema = vec![("column1".into(), ColumnType::VarChar)];
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::AnalyzeError {
+                source: AnalyzeError::InvalidDataType { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_convert_isqrt_expr_to_proof_expr_with_the_wrong_number_of_arguments() {
+        let expr = df_isqrt(vec![
+            Expr::Literal(ScalarValue::Int64(Some(1))),
+            Expr::Literal(ScalarValue::Int64(Some(2))),
+        ]);
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &Vec::new()),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+
+    // PowMod
+    fn df_pow_mod(args: Vec<Expr>) -> Expr {
+        Expr::ScalarFunction(ScalarFunction::new_udf(
+            crate::get_udf(POW_MOD_FUNCTION_NAME).unwrap(),
+            args,
+        ))
+    }
+
+    #[test]
+    fn we_can_convert_pow_mod_expr_to_proof_expr() {
+        let expr = df_pow_mod(vec![
+            df_column("namespace.table_name", "column2"),
+            Expr::Literal(ScalarValue::Int64(Some(3))),
+            Expr::Literal(ScalarValue::Int64(Some(7))),
+        ]);
+        let schema = vec![("column2".into(), ColumnType::BigInt)];
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_pow_mod(
+                COLUMN2_BIGINT(),
+                &DynProofExpr::new_literal(LiteralValue::BigInt(3)),
+                &DynProofExpr::new_literal(LiteralValue::BigInt(7))
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_pow_mod_expr_to_proof_expr_with_an_invalid_exponent_or_modulus() {
+        let schema = vec![
+            ("column1".into(), ColumnType::BigInt),
+            ("column2".into(), ColumnType::BigInt),
+        ];
+        for exponent in [
+            Expr::Literal(ScalarValue::Int64(Some(17))),
+            df_column("namespace.table_name", "column1"),
+        ] {
+            let expr = df_pow_mod(vec![
+                df_column("namespace.table_name", "column2"),
+                exponent,
+                Expr::Literal(ScalarValue::Int64(Some(7))),
+            ]);
+            assert!(matches!(
+                expr_to_proof_expr(&expr, &schema),
+                Err(PlannerError::AnalyzeError {
+                    source: AnalyzeError::InvalidExponent { .. }
+                })
+            ));
+        }
+        for modulus in [
+            Expr::Literal(ScalarValue::Int64(Some(0))),
+            df_column("namespace.table_name", "column1"),
+        ] {
+            let expr = df_pow_mod(vec![
+                df_column("namespace.table_name", "column2"),
+                Expr::Literal(ScalarValue::Int64(Some(2))),
+                modulus,
+            ]);
+            assert!(matches!(
+                expr_to_proof_expr(&expr, &schema),
+                Err(PlannerError::AnalyzeError {
+                    source: AnalyzeError::InvalidPowModModulus
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_cannot_convert_pow_mod_expr_to_proof_expr_with_the_wrong_number_of_arguments() {
+        let expr = df_pow_mod(vec![
+            Expr::Literal(ScalarValue::Int64(Some(2))),
+            Expr::Literal(ScalarValue::Int64(Some(3))),
+        ]);
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &Vec::new()),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+
     // Cast
     #[test]
     fn we_can_convert_cast_expr_to_proof_expr() {
@@ -602,6 +1035,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_convert_cast_expr_of_coercible_literal_to_proof_expr() {
+        // Integer to decimal
+        let expr = Expr::Cast(Cast::new(
+            Box::new(Expr::Literal(ScalarValue::Int32(Some(-12)))),
+            DataType::Decimal256(10, 2),
+        ));
+        assert_eq!(
+            expr_to_proof_expr(&expr, &Vec::new()).unwrap(),
+            DynProofExpr::new_literal(LiteralValue::Decimal75(
+                Precision::new(10).unwrap(),
+                2,
+                I256::from(-1200)
+            ))
+        );
+
+        // Timestamp to a timestamp of another unit and time zone
+        let expr = Expr::Cast(Cast::new(
+            Box::new(Expr::Literal(ScalarValue::TimestampSecond(
+                Some(5),
+                Some("+01:00".into()),
+            ))),
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+        ));
+        assert_eq!(
+            expr_to_proof_expr(&expr, &Vec::new()).unwrap(),
+            DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::utc(),
+                5_000
+            ))
+        );
+
+        // A literal which can not be coerced is still cast, which fails for unsupported casts
+        let expr = Expr::Cast(Cast::new(
+            Box::new(Expr::Literal(ScalarValue::Int64(Some(1_000)))),
+            DataType::Int8,
+        ));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &Vec::new()),
+            Err(PlannerError::AnalyzeError {
+                source: AnalyzeError::DataTypeMismatch { .. }
+            })
+        ));
+    }
+
     #[test]
     fn we_cannot_convert_cast_expr_to_proof_expr_when_inner_expr_to_proof_expr_fails() {
         // Unsupported logical expression
@@ -1,7 +1,11 @@
 //! This crate converts a `DataFusion` `LogicalPlan` to a `ProofPlan` and `Postprocessing`
 #![cfg_attr(test, expect(clippy::missing_panics_doc))]
 extern crate alloc;
+mod aggregate_flattening;
+pub(crate) use aggregate_flattening::flatten_aggregates;
 mod aggregate;
+mod average_rewriting;
+pub(crate) use average_rewriting::rewrite_averages;
 pub(crate) use aggregate::{aggregate_function_to_proof_expr, AggregateFunc};
 mod context;
 pub use context::PoSqlContextProvider;
@@ -9,7 +13,9 @@ pub use context::PoSqlContextProvider;
 pub(crate) use context::PoSqlTableSource;
 mod conversion;
 pub use conversion::{
-    get_table_refs_from_statement, sql_to_proof_plans, sql_to_proof_plans_with_postprocessing,
+    get_table_refs_from_statement, sql_to_logical_plans, sql_to_proof_plans,
+    sql_to_proof_plans_with_limits, sql_to_proof_plans_with_postprocessing,
+    sql_to_proof_plans_with_postprocessing_and_limits,
 };
 #[cfg(test)]
 mod df_util;
@@ -20,11 +26,21 @@ pub use error::{PlannerError, PlannerResult};
 mod plan;
 /// Proof of SQL Postprocessing. Used when the last step of the logical plan is an unprovable projection.
 pub mod postprocessing;
-pub use plan::logical_plan_to_proof_plan;
+pub use plan::{logical_plan_to_proof_plan, GROUPING_ID_COLUMN_NAME, MAX_GROUPING_SETS};
 mod proof_plan_with_postprocessing;
 pub use proof_plan_with_postprocessing::{
     logical_plan_to_proof_plan_with_postprocessing, ProofPlanWithPostprocessing,
 };
+mod table_references;
+pub use table_references::{
+    extract_table_and_column_references, extract_table_and_column_references_with_dialect,
+    extract_table_references, extract_table_references_with_dialect,
+};
+mod udf;
+pub(crate) use udf::{
+    get_udf, udf_names, IF_FUNCTION_NAME, ISQRT_FUNCTION_NAME, POW_MOD_FUNCTION_NAME,
+    ROUND_TO_FUNCTION_NAME,
+};
 mod util;
 pub use util::column_fields_to_schema;
 pub(crate) use util::{
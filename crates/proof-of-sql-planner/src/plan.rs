@@ -7,7 +7,10 @@ use alloc::vec::Vec;
 use datafusion::{
     common::{DFSchema, JoinConstraint, JoinType},
     logical_expr::{
-        expr::Alias, Aggregate, Expr, Join, Limit, LogicalPlan, Projection, TableScan, Union,
+        expr::{AggregateFunction, Alias, Sort as SortExpr},
+        expr_rewriter::{normalize_col, unnormalize_col},
+        Aggregate, Expr, Filter, GroupingSet, Join, Limit, LogicalPlan, Projection, Sort,
+        SubqueryAlias, TableScan, Union,
     },
     sql::{sqlparser::ast::Ident, TableReference},
 };
@@ -17,7 +20,7 @@ use proof_of_sql::{
     sql::{
         proof::ProofPlan,
         proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
-        proof_plans::{DynProofPlan, SortMergeJoinExec},
+        proof_plans::{DynProofPlan, GroupByExec, LeftAntiJoinExec, SortMergeJoinExec},
     },
 };
 
@@ -148,6 +151,37 @@ fn projection_to_proof_plan(
     Ok(DynProofPlan::new_projection(aliased_exprs, input_plan))
 }
 
+/// Convert a [`datafusion::logical_plan::LogicalPlan`] to a [`DynProofPlan`] for filters
+/// that were not pushed down into a table scan, e.g. filters over a subquery with a `LIMIT`
+fn filter_to_proof_plan(
+    predicate: &Expr,
+    input: &LogicalPlan,
+    schemas: &impl SchemaAccessor,
+) -> PlannerResult<DynProofPlan> {
+    let input_plan = logical_plan_to_proof_plan(input, schemas)?;
+    let input_schema = try_get_schema_as_vec_from_df_schema(input.schema())?;
+    // A filter keeps every column of its input
+    let aliased_exprs = input
+        .schema()
+        .columns()
+        .into_iter()
+        .map(|column| -> PlannerResult<AliasedDynProofExpr> {
+            let alias = column.name.as_str().into();
+            let proof_expr = expr_to_proof_expr(&Expr::Column(column), &input_schema)?;
+            Ok(AliasedDynProofExpr {
+                expr: proof_expr,
+                alias,
+            })
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let filter_proof_expr = expr_to_proof_expr(predicate, &input_schema)?;
+    Ok(DynProofPlan::new_generalized_filter(
+        aliased_exprs,
+        input_plan,
+        filter_proof_expr,
+    ))
+}
+
 /// Convert a [`datafusion::logical_plan::LogicalPlan`] to a [`DynProofPlan`] for GROUP BYs
 ///
 /// TODO: Improve how we handle GROUP BYs so that all the tech debt is resolved
@@ -161,16 +195,9 @@ fn aggregate_to_proof_plan(
     schemas: &impl SchemaAccessor,
     alias_map: &IndexMap<&str, &str>,
 ) -> PlannerResult<DynProofPlan> {
-    // Check that all of `group_expr` are columns and get their names
-    let group_columns = group_expr
-        .iter()
-        .map(|e| match e {
-            Expr::Column(c) => Ok(c),
-            _ => Err(PlannerError::UnsupportedLogicalPlan {
-                plan: input.clone(),
-            }),
-        })
-        .collect::<PlannerResult<Vec<_>>>()?;
+    if let [Expr::GroupingSet(GroupingSet::GroupingSets(grouping_sets))] = group_expr {
+        return grouping_sets_to_proof_plan(input, grouping_sets, aggr_expr, schemas, alias_map);
+    }
     match input {
         // Only TableScan without fetch is supported
         LogicalPlan::TableScan(TableScan {
@@ -190,15 +217,19 @@ fn aggregate_to_proof_plan(
                 .unwrap_or_else(|| Ok(DynProofExpr::new_literal(LiteralValue::Boolean(true))))?;
             // Aggregate
             // Prove that the ordering of `aggr_expr` is
-            // 1. All group columns according to `group_columns`
+            // 1. All group expressions according to `group_expr`
             // 2. (Optional) All the SUMs
             // 3. COUNT
+            // 4. (Optional) All the COUNT(DISTINCT ..)s, each with an optional FILTER
             if aggr_expr.is_empty() {
                 return Err(PlannerError::UnsupportedLogicalPlan {
                     plan: input.clone(),
                 });
             }
-            let agg_aliased_proof_exprs: Vec<((AggregateFunc, DynProofExpr), Ident)> = aggr_expr
+            let agg_aliased_proof_exprs: Vec<(
+                (AggregateFunc, DynProofExpr, Option<DynProofExpr>),
+                Ident,
+            )> = aggr_expr
                 .iter()
                 .map(|e| match e.clone().unalias() {
                     Expr::AggregateFunction(agg) => {
@@ -219,62 +250,410 @@ fn aggregate_to_proof_plan(
                     }),
                 })
                 .collect::<PlannerResult<Vec<_>>>()?;
-            // Check that the last expression is COUNT and the rest are SUMs
-            let (sum_tuples, count_tuple) =
-                agg_aliased_proof_exprs.split_at(agg_aliased_proof_exprs.len() - 1);
-            let sum_is_compliant = sum_tuples
+            // Check that the trailing COUNT(DISTINCT ..)s follow a COUNT and the rest are SUMs
+            let num_count_distincts = agg_aliased_proof_exprs
                 .iter()
-                .all(|((op, _), _)| matches!(op, AggregateFunc::Sum));
-            let count_is_compliant = count_tuple
+                .rev()
+                .take_while(|((op, _, _), _)| matches!(op, AggregateFunc::CountDistinct))
+                .count();
+            let (non_distinct_tuples, count_distinct_tuples) = agg_aliased_proof_exprs
+                .split_at(agg_aliased_proof_exprs.len() - num_count_distincts);
+            let Some((((count_op, _, _), count_alias), sum_tuples)) =
+                non_distinct_tuples.split_last()
+            else {
+                return Err(PlannerError::UnsupportedLogicalPlan {
+                    plan: input.clone(),
+                });
+            };
+            let sum_is_compliant = sum_tuples
                 .iter()
-                .all(|((op, _), _)| matches!(op, AggregateFunc::Count));
+                .all(|((op, _, _), _)| matches!(op, AggregateFunc::Sum));
+            let count_is_compliant = matches!(count_op, AggregateFunc::Count);
             if !sum_is_compliant || !count_is_compliant {
                 return Err(PlannerError::UnsupportedLogicalPlan {
                     plan: input.clone(),
                 });
             }
-            let count_alias = agg_aliased_proof_exprs
-                .last()
-                .expect("We have already checked that this exists")
-                .1
-                .clone();
-            // `group_by_exprs`
-            let group_by_exprs = group_columns
+            let count_alias = count_alias.clone();
+            // `group_by_exprs`, where group columns keep their names and computed group
+            // expressions such as `x % 10` are output under their aliases
+            let group_by_exprs = group_expr
                 .iter()
-                .map(|column| {
-                    Ok(ColumnExpr::new(column_to_column_ref(
-                        column,
-                        &input_schema,
-                    )?))
+                .map(|e| match e {
+                    Expr::Column(column) => {
+                        let column_expr =
+                            ColumnExpr::new(column_to_column_ref(column, &input_schema)?);
+                        Ok(AliasedDynProofExpr {
+                            alias: column_expr.column_id(),
+                            expr: DynProofExpr::Column(column_expr),
+                        })
+                    }
+                    _ => {
+                        let name_string = e.display_name()?;
+                        let alias = alias_map.get(name_string.as_str()).ok_or_else(|| {
+                            PlannerError::UnsupportedLogicalPlan {
+                                plan: input.clone(),
+                            }
+                        })?;
+                        Ok(AliasedDynProofExpr {
+                            expr: expr_to_proof_expr(e, &input_schema)?,
+                            alias: (*alias).into(),
+                        })
+                    }
                 })
                 .collect::<PlannerResult<Vec<_>>>()?;
             // `sum_expr`
             let sum_expr = sum_tuples
                 .iter()
-                .map(|((_, expr), alias)| AliasedDynProofExpr {
+                .map(|((_, expr, _), alias)| AliasedDynProofExpr {
                     expr: expr.clone(),
                     alias: alias.clone(),
                 })
                 .collect::<Vec<_>>();
-            Ok(DynProofPlan::new_group_by(
-                group_by_exprs,
-                sum_expr,
-                count_alias,
-                table_expr,
-                consolidated_filter_proof_expr,
+            // `count_distinct_exprs` and `count_distinct_filters`, whose columns and types are
+            // checked by `aggregate_function_to_proof_expr`
+            let (count_distinct_exprs, count_distinct_filters) = count_distinct_tuples
+                .iter()
+                .map(|((_, expr, filter), alias)| match expr {
+                    DynProofExpr::Column(column_expr) => {
+                        Ok(((column_expr.clone(), alias.clone()), filter.clone()))
+                    }
+                    _ => Err(PlannerError::UnsupportedLogicalPlan {
+                        plan: input.clone(),
+                    }),
+                })
+                .collect::<PlannerResult<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            Ok(DynProofPlan::GroupBy(
+                GroupByExec::new_with_aliased_group_by_exprs(
+                    group_by_exprs,
+                    sum_expr,
+                    count_alias,
+                    count_distinct_exprs,
+                    count_distinct_filters,
+                    table_expr,
+                    consolidated_filter_proof_expr,
+                ),
             ))
         }
+        // References to CTEs and other aliased subqueries are inlined
+        LogicalPlan::SubqueryAlias(SubqueryAlias {
+            input: alias_input, ..
+        }) => subquery_alias_aggregate_to_proof_plan(
+            alias_input,
+            group_expr,
+            aggr_expr,
+            schemas,
+            alias_map,
+        ),
         _ => Err(PlannerError::UnsupportedLogicalPlan {
             plan: input.clone(),
         }),
     }
 }
 
+/// Convert a GROUP BY over an aliased subquery, e.g. a reference to a CTE, to a [`DynProofPlan`]
+///
+/// The columns of `group_expr` and `aggr_expr` are requalified so that they refer to
+/// `alias_input` instead of the alias.
+fn subquery_alias_aggregate_to_proof_plan(
+    alias_input: &LogicalPlan,
+    group_expr: &[Expr],
+    aggr_expr: &[Expr],
+    schemas: &impl SchemaAccessor,
+    alias_map: &IndexMap<&str, &str>,
+) -> PlannerResult<DynProofPlan> {
+    let requalify = |e: &Expr| normalize_col(unnormalize_col(e.clone()), alias_input);
+    let requalified_group_expr = group_expr
+        .iter()
+        .map(requalify)
+        .collect::<Result<Vec<_>, _>>()?;
+    let requalified_aggr_expr = aggr_expr
+        .iter()
+        .map(requalify)
+        .collect::<Result<Vec<_>, _>>()?;
+    // The aliases of the computed group expressions and the aggregate expressions are looked up
+    // by name, which requalification changes
+    let name_strings = group_expr
+        .iter()
+        .chain(aggr_expr)
+        .map(Expr::display_name)
+        .collect::<Result<Vec<_>, _>>()?;
+    let requalified_name_strings = requalified_group_expr
+        .iter()
+        .chain(&requalified_aggr_expr)
+        .map(Expr::display_name)
+        .collect::<Result<Vec<_>, _>>()?;
+    let requalified_alias_map = name_strings
+        .iter()
+        .zip(requalified_name_strings.iter())
+        .filter_map(|(name_string, requalified_name_string)| {
+            alias_map
+                .get(name_string.as_str())
+                .map(|alias| (requalified_name_string.as_str(), *alias))
+        })
+        .collect::<IndexMap<_, _>>();
+    aggregate_to_proof_plan(
+        alias_input,
+        &requalified_group_expr,
+        &requalified_aggr_expr,
+        schemas,
+        &requalified_alias_map,
+    )
+}
+
+/// The name of the column telling the grouping sets of a `GROUP BY GROUPING SETS` apart
+pub const GROUPING_ID_COLUMN_NAME: &str = "__grouping_id";
+
+/// The maximum number of grouping sets of a `GROUP BY GROUPING SETS`
+///
+/// Each grouping set is proven as a separate `GROUP BY`, so proofs grow with the number of sets.
+pub const MAX_GROUPING_SETS: usize = 8;
+
+/// Convert a `GROUP BY GROUPING SETS` to a [`DynProofPlan`]
+///
+/// Each grouping set is planned as a separate GROUP BY, and the sets are proven together by a
+/// [`GroupingSetsExec`](proof_of_sql::sql::proof_plans::GroupingSetsExec) whose result has a
+/// [`GROUPING_ID_COLUMN_NAME`] column telling the sets apart.
+fn grouping_sets_to_proof_plan(
+    input: &LogicalPlan,
+    grouping_sets: &[Vec<Expr>],
+    aggr_expr: &[Expr],
+    schemas: &impl SchemaAccessor,
+    alias_map: &IndexMap<&str, &str>,
+) -> PlannerResult<DynProofPlan> {
+    if grouping_sets.len() > MAX_GROUPING_SETS {
+        return Err(PlannerError::TooManyGroupingSets {
+            num_grouping_sets: grouping_sets.len(),
+        });
+    }
+    let group_by_plans = grouping_sets
+        .iter()
+        .map(|grouping_set| {
+            match aggregate_to_proof_plan(input, grouping_set, aggr_expr, schemas, alias_map)? {
+                // Grouping sets do not support distinct counts or computed group by expressions
+                DynProofPlan::GroupBy(group_by)
+                    if group_by.count_distinct_exprs().is_empty()
+                        && group_by.group_by_columns().is_some() =>
+                {
+                    Ok(group_by)
+                }
+                _ => Err(PlannerError::UnsupportedLogicalPlan {
+                    plan: input.clone(),
+                }),
+            }
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let first = group_by_plans
+        .first()
+        .ok_or_else(|| PlannerError::UnsupportedLogicalPlan {
+            plan: input.clone(),
+        })?;
+    Ok(DynProofPlan::try_new_grouping_sets(
+        group_by_plans
+            .iter()
+            .map(|group_by| {
+                group_by
+                    .group_by_columns()
+                    .expect("group by expressions were checked to be columns")
+            })
+            .collect(),
+        first.sum_expr().to_vec(),
+        first.count_alias().clone(),
+        first.table().clone(),
+        first.where_clause().clone(),
+        GROUPING_ID_COLUMN_NAME.into(),
+    )?)
+}
+
+/// Convert a `SELECT DISTINCT ON`, which `DataFusion` rewrites to
+/// ```ignore
+/// Projection: FIRST_VALUE(<expr1>) ORDER BY [<keys>, <tiebreaker>] AS <alias1>, ...
+///   Sort: <keys>
+///     Aggregate: groupBy=[<keys>], aggr=[FIRST_VALUE(<expr1>) ORDER BY [<keys>, <tiebreaker>], ...]
+/// ```
+/// to a [`FirstPerGroupExec`](proof_of_sql::sql::proof_plans::FirstPerGroupExec)
+///
+/// The keys must be sorted ascending and followed by a single tiebreaker in the `ORDER BY`.
+/// Since the plan is only proven at the top level of a query, the keys and the tiebreaker have to
+/// be selected.
+#[expect(clippy::too_many_lines)]
+fn distinct_on_to_proof_plan(
+    expr: &[Expr],
+    output_schema: &DFSchema,
+    sort: &Sort,
+    aggregate: &Aggregate,
+    schema_accessor: &impl SchemaAccessor,
+    plan: &LogicalPlan,
+) -> PlannerResult<DynProofPlan> {
+    let unsupported = || PlannerError::UnsupportedLogicalPlan { plan: plan.clone() };
+    let is_ascending_sort_of = |sort_expr: &Expr, key: &Expr| matches!(sort_expr, Expr::Sort(SortExpr { expr, asc: true, .. }) if **expr == *key);
+    let group_expr = &aggregate.group_expr;
+    // The result is sorted by the keys
+    if sort.fetch.is_some()
+        || sort.expr.len() != group_expr.len()
+        || !sort
+            .expr
+            .iter()
+            .all(|sort_expr| matches!(sort_expr, Expr::Sort(SortExpr { asc: true, .. })))
+    {
+        return Err(unsupported());
+    }
+    // Every aggregate is the `FIRST_VALUE` of an expression in the same order
+    let (first_value_args, orders): (Vec<&Expr>, Vec<&Vec<Expr>>) = aggregate
+        .aggr_expr
+        .iter()
+        .map(|aggr_expr| match aggr_expr {
+            Expr::AggregateFunction(AggregateFunction {
+                func_def,
+                args,
+                distinct: false,
+                filter: None,
+                order_by: Some(order_by),
+                ..
+            }) if args.len() == 1 && func_def.name().eq_ignore_ascii_case("first_value") => {
+                Ok((&args[0], order_by))
+            }
+            _ => Err(unsupported()),
+        })
+        .collect::<PlannerResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let order = *orders.first().ok_or_else(unsupported)?;
+    if orders.iter().any(|other_order| *other_order != order) {
+        return Err(unsupported());
+    }
+    // The order is by the keys followed by the tiebreaker
+    let [key_order @ .., Expr::Sort(SortExpr {
+        expr: tiebreaker,
+        asc,
+        ..
+    })] = order.as_slice()
+    else {
+        return Err(unsupported());
+    };
+    if key_order.len() != group_expr.len()
+        || !key_order
+            .iter()
+            .zip(group_expr)
+            .all(|(sort_expr, key)| is_ascending_sort_of(sort_expr, key))
+    {
+        return Err(unsupported());
+    }
+    // The selected expressions in terms of the input of the aggregation
+    let selected_args = expr
+        .iter()
+        .map(|e| {
+            let column = match e {
+                Expr::Column(column) => column,
+                Expr::Alias(Alias { expr, .. }) => match expr.as_ref() {
+                    Expr::Column(column) => column,
+                    _ => return Err(unsupported()),
+                },
+                _ => return Err(unsupported()),
+            };
+            let index = aggregate
+                .schema
+                .index_of_column(column)
+                .map_err(|_| unsupported())?;
+            Ok(if index < group_expr.len() {
+                &group_expr[index]
+            } else {
+                first_value_args[index - group_expr.len()]
+            })
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let position_of = |arg: &Expr| {
+        selected_args
+            .iter()
+            .position(|&selected_arg| selected_arg == arg)
+            .ok_or_else(unsupported)
+    };
+    let key_column_indexes = group_expr
+        .iter()
+        .map(&position_of)
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let tiebreaker_column_index = position_of(tiebreaker)?;
+    let input_schema = try_get_schema_as_vec_from_df_schema(aggregate.input.schema())?;
+    let aliased_exprs = selected_args
+        .iter()
+        .zip(output_schema.fields().into_iter())
+        .map(|(arg, field)| -> PlannerResult<AliasedDynProofExpr> {
+            Ok(AliasedDynProofExpr {
+                expr: expr_to_proof_expr(arg, &input_schema)?,
+                alias: field.name().as_str().into(),
+            })
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let input_plan = DynProofPlan::new_projection(
+        aliased_exprs,
+        logical_plan_to_proof_plan(&aggregate.input, schema_accessor)?,
+    );
+    Ok(DynProofPlan::try_new_first_per_group(
+        input_plan,
+        key_column_indexes,
+        tiebreaker_column_index,
+        !asc,
+    )?)
+}
+
+/// Convert a left anti-join, which is how `NOT EXISTS` and `NOT IN` subqueries are decorrelated,
+/// to a [`LeftAntiJoinExec`]
+///
+/// Only equijoins on a single pair of plain columns of the same type without any additional
+/// filter are supported.
+fn left_anti_join_to_proof_plan(
+    join: &Join,
+    schema_accessor: &impl SchemaAccessor,
+    plan: &LogicalPlan,
+) -> PlannerResult<DynProofPlan> {
+    if join.join_constraint != JoinConstraint::On || join.filter.is_some() || join.on.len() != 1 {
+        return Err(PlannerError::UnsupportedLogicalPlan { plan: plan.clone() });
+    }
+    let left_plan = Box::new(logical_plan_to_proof_plan(&join.left, schema_accessor)?);
+    let right_plan = Box::new(logical_plan_to_proof_plan(&join.right, schema_accessor)?);
+    let left_column_result_fields = left_plan
+        .get_column_result_fields()
+        .into_iter()
+        .map(|c| (c.name(), c.data_type()))
+        .collect::<IndexMap<_, _>>();
+    let right_column_result_fields = right_plan
+        .get_column_result_fields()
+        .into_iter()
+        .map(|c| (c.name(), c.data_type()))
+        .collect::<IndexMap<_, _>>();
+    let (left_indices, right_indices): (Vec<usize>, Vec<usize>) = join
+        .on
+        .iter()
+        .map(|(left_expr, right_expr)| match (left_expr, right_expr) {
+            (Expr::Column(col_a), Expr::Column(col_b)) => left_column_result_fields
+                .get_full(&Ident::new(col_a.name.clone()))
+                .zip(right_column_result_fields.get_full(&Ident::new(col_b.name.clone())))
+                .filter(|((_, _, left_type), (_, _, right_type))| left_type == right_type)
+                .map(|((left_index, _, _), (right_index, _, _))| (left_index, right_index))
+                .ok_or_else(|| PlannerError::UnsupportedLogicalPlan { plan: plan.clone() }),
+            _ => Err(PlannerError::UnsupportedLogicalPlan { plan: plan.clone() }),
+        })
+        .collect::<PlannerResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    Ok(DynProofPlan::LeftAntiJoin(LeftAntiJoinExec::new(
+        left_plan,
+        right_plan,
+        left_indices,
+        right_indices,
+    )))
+}
+
 fn join_to_proof_plan(
     join: &Join,
     schema_accessor: &impl SchemaAccessor,
     plan: &LogicalPlan,
 ) -> PlannerResult<DynProofPlan> {
+    if join.join_type == JoinType::LeftAnti {
+        return left_anti_join_to_proof_plan(join, schema_accessor, plan);
+    }
     if join.join_type != JoinType::Inner || join.join_constraint != JoinConstraint::On {
         return Err(PlannerError::UnsupportedLogicalPlan { plan: plan.clone() });
     }
@@ -377,8 +756,15 @@ pub fn logical_plan_to_proof_plan(
             schema,
             ..
         }) => {
-            let name_strings = group_expr
-                .iter()
+            // The group fields of a `GROUP BY GROUPING SETS` are those of its distinct group columns
+            let group_exprs: IndexSet<&Expr> = match group_expr.as_slice() {
+                [Expr::GroupingSet(GroupingSet::GroupingSets(grouping_sets))] => {
+                    grouping_sets.iter().flatten().collect()
+                }
+                _ => group_expr.iter().collect(),
+            };
+            let name_strings = group_exprs
+                .into_iter()
                 .chain(aggr_expr.iter())
                 .map(Expr::display_name)
                 .collect::<Result<Vec<_>, _>>()?;
@@ -430,9 +816,25 @@ pub fn logical_plan_to_proof_plan(
                         &alias_map,
                     )
                 }
+                // `DISTINCT ON`
+                LogicalPlan::Sort(sort) => match &*sort.input {
+                    LogicalPlan::Aggregate(aggregate) => distinct_on_to_proof_plan(
+                        expr,
+                        schema,
+                        sort,
+                        aggregate,
+                        schema_accessor,
+                        plan,
+                    ),
+                    _ => projection_to_proof_plan(expr, input, schema, schema_accessor),
+                },
                 _ => projection_to_proof_plan(expr, input, schema, schema_accessor),
             }
         }
+        // Filter
+        LogicalPlan::Filter(Filter {
+            predicate, input, ..
+        }) => filter_to_proof_plan(predicate, input, schema_accessor),
         // Limit
         LogicalPlan::Limit(Limit { input, fetch, skip }) => {
             let input_plan = logical_plan_to_proof_plan(input, schema_accessor)?;
@@ -449,6 +851,11 @@ pub fn logical_plan_to_proof_plan(
             Ok(DynProofPlan::new_union(input_plans, column_fields))
         }
         LogicalPlan::Join(join) => join_to_proof_plan(join, schema_accessor, plan),
+        // References to CTEs and other aliased subqueries are inlined
+        LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            logical_plan_to_proof_plan(input, schema_accessor)
+        }
+        LogicalPlan::RecursiveQuery(_) => Err(PlannerError::RecursiveCteNotSupported),
         _ => Err(PlannerError::UnsupportedLogicalPlan { plan: plan.clone() }),
     }
 }
@@ -980,24 +1387,19 @@ mod tests {
         assert_eq!(result, expected);
     }
 
-    // Error case tests
     #[test]
-    fn we_cannot_aggregate_with_non_column_group_expr() {
-        // Setup group expression with a non-column expression
+    fn we_can_aggregate_with_a_computed_group_expr() {
+        // Setup group expression `a % 10`
         let group_expr = vec![Expr::BinaryExpr(BinaryExpr::new(
             Box::new(df_column("table", "a")),
-            Operator::Plus,
-            Box::new(df_column("table", "b")),
+            Operator::Modulo,
+            Box::new(Expr::Literal(ScalarValue::Int64(Some(10)))),
         ))];
 
         // Create the aggregate expressions
         let aggr_expr = vec![
-            Expr::BinaryExpr(BinaryExpr::new(
-                Box::new(df_column("table", "a")),
-                Operator::Plus,
-                Box::new(df_column("table", "b")),
-            )),
-            COUNT_1(),
+            SUM_B(),   // SUM
+            COUNT_1(), // COUNT
         ];
 
         // Create the input plan
@@ -1012,11 +1414,111 @@ mod tests {
             .unwrap(),
         );
         let alias_map = indexmap! {
-            "a+b" => "res",
+            "table.a % Int64(10)" => "bucket",
+            "SUM(table.b)" => "sum_b",
+            "COUNT(Int64(1))" => "count_1",
+        };
+
+        // Test the function
+        let result =
+            aggregate_to_proof_plan(&input_plan, &group_expr, &aggr_expr, &SCHEMAS(), &alias_map)
+                .unwrap();
+
+        // Expected result
+        let expected = DynProofPlan::GroupBy(GroupByExec::new_with_aliased_group_by_exprs(
+            vec![AliasedDynProofExpr {
+                expr: DynProofExpr::try_new_modulo(
+                    DynProofExpr::new_column(ColumnRef::new(
+                        TABLE_REF_TABLE(),
+                        "a".into(),
+                        ColumnType::BigInt,
+                    )),
+                    &DynProofExpr::new_literal(LiteralValue::BigInt(10)),
+                )
+                .unwrap(),
+                alias: "bucket".into(),
+            }],
+            vec![AliasedDynProofExpr {
+                expr: DynProofExpr::new_column(ColumnRef::new(
+                    TABLE_REF_TABLE(),
+                    "b".into(),
+                    ColumnType::Int,
+                )),
+                alias: "sum_b".into(),
+            }],
+            "count_1".into(),
+            vec![],
+            vec![],
+            TableExpr {
+                table_ref: TABLE_REF_TABLE(),
+            },
+            DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+        ));
+
+        assert_eq!(result, expected);
+    }
+
+    // Error case tests
+    #[test]
+    fn we_cannot_aggregate_with_an_unprovable_group_expr() {
+        // Setup group expression with a division, which can't be proven
+        let group_expr = vec![Expr::BinaryExpr(BinaryExpr::new(
+            Box::new(df_column("table", "a")),
+            Operator::Divide,
+            Box::new(df_column("table", "b")),
+        ))];
+
+        // Create the aggregate expressions
+        let aggr_expr = vec![COUNT_1()];
+
+        // Create the input plan
+        let input_plan = LogicalPlan::TableScan(
+            TableScan::try_new(
+                "table",
+                TABLE_SOURCE(),
+                Some(vec![0, 1, 2, 3]),
+                vec![],
+                None,
+            )
+            .unwrap(),
+        );
+        let alias_map = indexmap! {
+            "table.a / table.b" => "res",
             "COUNT(Int64(1))" => "count_1",
         };
 
         // Test the function - should return an error
+        let result =
+            aggregate_to_proof_plan(&input_plan, &group_expr, &aggr_expr, &SCHEMAS(), &alias_map);
+        assert!(matches!(
+            result,
+            Err(PlannerError::UnsupportedBinaryOperator {
+                op: Operator::Divide
+            })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_aggregate_with_a_computed_group_expr_without_an_alias() {
+        let group_expr = vec![Expr::BinaryExpr(BinaryExpr::new(
+            Box::new(df_column("table", "a")),
+            Operator::Modulo,
+            Box::new(Expr::Literal(ScalarValue::Int64(Some(10)))),
+        ))];
+        let aggr_expr = vec![COUNT_1()];
+        let input_plan = LogicalPlan::TableScan(
+            TableScan::try_new(
+                "table",
+                TABLE_SOURCE(),
+                Some(vec![0, 1, 2, 3]),
+                vec![],
+                None,
+            )
+            .unwrap(),
+        );
+        let alias_map = indexmap! {
+            "COUNT(Int64(1))" => "count_1",
+        };
         let result =
             aggregate_to_proof_plan(&input_plan, &group_expr, &aggr_expr, &SCHEMAS(), &alias_map);
         assert!(matches!(
@@ -1649,6 +2151,61 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    // Filter
+    #[test]
+    fn we_can_convert_filter_plan_over_limit_to_proof_plan() {
+        // Filters can not be pushed below a limit so they remain in the plan
+        let plan = LogicalPlan::Filter(
+            Filter::try_new(
+                df_column("table", "a").eq(df_column("table", "b")),
+                Arc::new(LogicalPlan::Limit(Limit {
+                    input: Arc::new(LogicalPlan::TableScan(
+                        TableScan::try_new("table", TABLE_SOURCE(), Some(vec![0, 1]), vec![], None)
+                            .unwrap(),
+                    )),
+                    fetch: None,
+                    skip: 2,
+                })),
+            )
+            .unwrap(),
+        );
+        let schemas = SCHEMAS();
+        let result = logical_plan_to_proof_plan(&plan, &schemas).unwrap();
+        let expected = DynProofPlan::new_generalized_filter(
+            vec![ALIASED_A(), ALIASED_B()],
+            DynProofPlan::new_slice(
+                DynProofPlan::new_projection(
+                    vec![ALIASED_A(), ALIASED_B()],
+                    DynProofPlan::new_table(
+                        TABLE_REF_TABLE(),
+                        vec![
+                            ColumnField::new("a".into(), ColumnType::BigInt),
+                            ColumnField::new("b".into(), ColumnType::Int),
+                            ColumnField::new("c".into(), ColumnType::VarChar),
+                            ColumnField::new("d".into(), ColumnType::Boolean),
+                        ],
+                    ),
+                ),
+                2,
+                None,
+            ),
+            DynProofExpr::try_new_equals(
+                DynProofExpr::new_column(ColumnRef::new(
+                    TABLE_REF_TABLE(),
+                    "a".into(),
+                    ColumnType::BigInt,
+                )),
+                DynProofExpr::new_column(ColumnRef::new(
+                    TABLE_REF_TABLE(),
+                    "b".into(),
+                    ColumnType::Int,
+                )),
+            )
+            .unwrap(),
+        );
+        assert_eq!(result, expected);
+    }
+
     // Union
     #[expect(clippy::too_many_lines)]
     #[test]
@@ -1925,6 +2482,149 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    // Aggregate with grouping sets
+    #[test]
+    fn we_can_convert_agg_plan_with_grouping_sets_to_proof_plan() {
+        let group_expr = vec![Expr::GroupingSet(GroupingSet::GroupingSets(vec![
+            vec![df_column("table", "a")],
+            vec![df_column("table", "c")],
+            vec![],
+        ]))];
+        let aggr_expr = vec![SUM_B(), COUNT_1()];
+        let input_plan = LogicalPlan::TableScan(
+            TableScan::try_new(
+                "table",
+                TABLE_SOURCE(),
+                Some(vec![0, 1, 2, 3]),
+                vec![],
+                None,
+            )
+            .unwrap(),
+        );
+        let agg_plan = LogicalPlan::Aggregate(
+            Aggregate::try_new(Arc::new(input_plan), group_expr, aggr_expr).unwrap(),
+        );
+
+        let result = logical_plan_to_proof_plan(&agg_plan, &SCHEMAS()).unwrap();
+
+        let column = |name: &str, data_type: ColumnType| {
+            ColumnExpr::new(ColumnRef::new(TABLE_REF_TABLE(), name.into(), data_type))
+        };
+        let expected = DynProofPlan::try_new_grouping_sets(
+            vec![
+                vec![column("a", ColumnType::BigInt)],
+                vec![column("c", ColumnType::VarChar)],
+                vec![],
+            ],
+            vec![AliasedDynProofExpr {
+                expr: DynProofExpr::new_column(ColumnRef::new(
+                    TABLE_REF_TABLE(),
+                    "b".into(),
+                    ColumnType::Int,
+                )),
+                alias: "SUM(table.b)".into(),
+            }],
+            "COUNT(Int64(1))".into(),
+            TableExpr {
+                table_ref: TABLE_REF_TABLE(),
+            },
+            DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+            GROUPING_ID_COLUMN_NAME.into(),
+        )
+        .unwrap();
+        assert_eq!(
+            result.get_column_result_fields(),
+            vec![
+                ColumnField::new("a".into(), ColumnType::BigInt),
+                ColumnField::new("c".into(), ColumnType::VarChar),
+                ColumnField::new("SUM(table.b)".into(), ColumnType::Int),
+                ColumnField::new("COUNT(Int64(1))".into(), ColumnType::BigInt),
+                ColumnField::new(GROUPING_ID_COLUMN_NAME.into(), ColumnType::BigInt),
+            ]
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn we_cannot_convert_agg_plan_with_too_many_grouping_sets_to_proof_plan() {
+        let group_expr = vec![Expr::GroupingSet(GroupingSet::GroupingSets(vec![
+            vec![
+                df_column("table", "a")
+            ];
+            MAX_GROUPING_SETS
+                + 1
+        ]))];
+        let input_plan = LogicalPlan::TableScan(
+            TableScan::try_new(
+                "table",
+                TABLE_SOURCE(),
+                Some(vec![0, 1, 2, 3]),
+                vec![],
+                None,
+            )
+            .unwrap(),
+        );
+        let agg_plan = LogicalPlan::Aggregate(
+            Aggregate::try_new(Arc::new(input_plan), group_expr, vec![COUNT_1()]).unwrap(),
+        );
+
+        assert!(matches!(
+            logical_plan_to_proof_plan(&agg_plan, &SCHEMAS()),
+            Err(PlannerError::TooManyGroupingSets {
+                num_grouping_sets: 9
+            })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_convert_a_sorted_agg_plan_which_is_not_a_distinct_on_to_proof_plan() {
+        let input_plan = LogicalPlan::TableScan(
+            TableScan::try_new(
+                "table",
+                TABLE_SOURCE(),
+                Some(vec![0, 1, 2, 3]),
+                vec![],
+                None,
+            )
+            .unwrap(),
+        );
+        let agg_plan = LogicalPlan::Aggregate(
+            Aggregate::try_new(
+                Arc::new(input_plan),
+                vec![df_column("table", "a")],
+                vec![SUM_B()],
+            )
+            .unwrap(),
+        );
+        let sort_plan = LogicalPlan::Sort(Sort {
+            expr: vec![Expr::Sort(SortExpr::new(
+                Box::new(df_column("table", "a")),
+                true,
+                false,
+            ))],
+            input: Arc::new(agg_plan),
+            fetch: None,
+        });
+        let proj_plan = LogicalPlan::Projection(
+            Projection::try_new(
+                vec![
+                    df_column("table", "a"),
+                    Expr::Column(Column::new(
+                        None::<TableReference>,
+                        "SUM(table.b)".to_string(),
+                    )),
+                ],
+                Arc::new(sort_plan),
+            )
+            .unwrap(),
+        );
+
+        assert!(matches!(
+            logical_plan_to_proof_plan(&proj_plan, &SCHEMAS()),
+            Err(PlannerError::UnsupportedLogicalPlan { .. })
+        ));
+    }
+
     #[test]
     fn we_cannot_convert_unsupported_agg_plan_to_proof_plan() {
         // Setup group expression
@@ -0,0 +1,492 @@
+use crate::PlannerResult;
+use alloc::{string::String, vec::Vec};
+use core::ops::ControlFlow;
+use datafusion::sql::TableReference;
+use indexmap::{IndexMap, IndexSet};
+use sqlparser::{
+    ast::{
+        Expr, Ident, ObjectName, Query, SelectItem, SetExpr, Statement, TableFactor, Visit, Visitor,
+    },
+    dialect::{Dialect, GenericDialect},
+    parser::Parser,
+};
+
+/// Normalize an identifier the same way `DataFusion` does by default,
+/// i.e. lowercase it unless it is quoted
+fn normalize_ident(ident: &Ident) -> String {
+    if ident.quote_style.is_some() {
+        ident.value.clone()
+    } else {
+        ident.value.to_lowercase()
+    }
+}
+
+/// Convert an [`ObjectName`] to a [`TableReference`]
+///
+/// Returns `None` if the name has more than three parts.
+fn object_name_to_table_reference(name: &ObjectName) -> Option<TableReference> {
+    match name.0.as_slice() {
+        [table] => Some(TableReference::bare(normalize_ident(table))),
+        [schema, table] => Some(TableReference::partial(
+            normalize_ident(schema),
+            normalize_ident(table),
+        )),
+        [catalog, schema, table] => Some(TableReference::full(
+            normalize_ident(catalog),
+            normalize_ident(schema),
+            normalize_ident(table),
+        )),
+        _ => None,
+    }
+}
+
+/// The outcome of attributing a column identifier to a table within a [`Scope`]
+enum Resolution<'a> {
+    /// The column belongs to this base table
+    Table(&'a TableReference),
+    /// The column refers to something in this scope, but which base table it belongs to
+    /// can not be determined without schemas
+    Undeterminable,
+    /// The column does not refer to anything in this scope, e.g. it is a correlated reference
+    Unresolved,
+}
+
+/// The names visible within a single query
+#[derive(Default)]
+struct Scope {
+    /// Names of the common table expressions defined by the query
+    ctes: Vec<String>,
+    /// Aliases of the output columns of the query
+    output_aliases: Vec<String>,
+    /// Relations in the `FROM` clause, keyed by the name they can be qualified with.
+    /// Base tables map to `Some`; derived tables and common table expressions to `None`.
+    relations: Vec<(Vec<String>, Option<TableReference>)>,
+    /// Possibly qualified column identifiers appearing in the query
+    columns: Vec<Vec<String>>,
+}
+
+impl Scope {
+    /// Attribute a possibly qualified column identifier to a relation of this scope
+    fn resolve(&self, column: &[String]) -> Resolution<'_> {
+        match column.split_last() {
+            Some((name, [])) => {
+                if self.output_aliases.contains(name) {
+                    return Resolution::Undeterminable;
+                }
+                if self.relations.is_empty() {
+                    return Resolution::Unresolved;
+                }
+                let mut base_tables = IndexSet::new();
+                for (_, table) in &self.relations {
+                    match table {
+                        Some(table) => {
+                            base_tables.insert(table);
+                        }
+                        None => return Resolution::Undeterminable,
+                    }
+                }
+                if base_tables.len() == 1 {
+                    Resolution::Table(base_tables[0])
+                } else {
+                    Resolution::Undeterminable
+                }
+            }
+            Some((_, qualifier)) => match self
+                .relations
+                .iter()
+                .find(|(name, _)| !name.is_empty() && name.ends_with(qualifier))
+            {
+                Some((_, Some(table))) => Resolution::Table(table),
+                Some((_, None)) => Resolution::Undeterminable,
+                None => Resolution::Unresolved,
+            },
+            None => Resolution::Undeterminable,
+        }
+    }
+}
+
+/// A [`Visitor`] collecting the base tables and columns referenced by SQL statements
+#[derive(Default)]
+struct ReferenceCollector {
+    /// Referenced base tables in order of first appearance
+    tables: IndexSet<TableReference>,
+    /// Columns attributed to base tables
+    columns: IndexMap<TableReference, IndexSet<String>>,
+    /// Scopes of the statement and queries currently being visited, innermost last
+    scopes: Vec<Scope>,
+}
+
+impl ReferenceCollector {
+    /// Whether `name` refers to a common table expression visible in the current scope
+    fn is_cte(&self, name: &ObjectName) -> bool {
+        match name.0.as_slice() {
+            [ident] => {
+                let name = normalize_ident(ident);
+                self.scopes.iter().any(|scope| scope.ctes.contains(&name))
+            }
+            _ => false,
+        }
+    }
+
+    /// Leave the innermost scope
+    ///
+    /// Its columns are attributed to base tables where possible. Columns which do not refer
+    /// to anything in the scope are passed on to the enclosing scope.
+    fn pop_scope(&mut self) {
+        let Some(scope) = self.scopes.pop() else {
+            return;
+        };
+        for column in &scope.columns {
+            match scope.resolve(column) {
+                Resolution::Table(table) => {
+                    if let Some(name) = column.last() {
+                        self.columns
+                            .entry(table.clone())
+                            .or_default()
+                            .insert(name.clone());
+                    }
+                }
+                Resolution::Unresolved => {
+                    if let Some(parent) = self.scopes.last_mut() {
+                        parent.columns.push(column.clone());
+                    }
+                }
+                Resolution::Undeterminable => {}
+            }
+        }
+    }
+}
+
+impl Visitor for ReferenceCollector {
+    type Break = ();
+
+    fn pre_visit_statement(&mut self, _statement: &Statement) -> ControlFlow<Self::Break> {
+        self.scopes.push(Scope::default());
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_statement(&mut self, _statement: &Statement) -> ControlFlow<Self::Break> {
+        self.pop_scope();
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        let ctes = query
+            .with
+            .iter()
+            .flat_map(|with| &with.cte_tables)
+            .map(|cte| normalize_ident(&cte.alias.name))
+            .collect();
+        let output_aliases = match &*query.body {
+            SetExpr::Select(select) => select
+                .projection
+                .iter()
+                .filter_map(|item| match item {
+                    SelectItem::ExprWithAlias { alias, .. } => Some(normalize_ident(alias)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.scopes.push(Scope {
+            ctes,
+            output_aliases,
+            ..Scope::default()
+        });
+        ControlFlow::Continue(())
+    }
+
+    fn post_visit_query(&mut self, _query: &Query) -> ControlFlow<Self::Break> {
+        self.pop_scope();
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+        if !self.is_cte(relation) {
+            if let Some(table) = object_name_to_table_reference(relation) {
+                self.tables.insert(table);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_table_factor(&mut self, table_factor: &TableFactor) -> ControlFlow<Self::Break> {
+        let relation = match table_factor {
+            TableFactor::Table { name, alias, .. } => {
+                let table = if self.is_cte(name) {
+                    None
+                } else {
+                    object_name_to_table_reference(name)
+                };
+                let qualifier = alias.as_ref().map_or_else(
+                    || name.0.iter().map(normalize_ident).collect(),
+                    |alias| vec![normalize_ident(&alias.name)],
+                );
+                (qualifier, table)
+            }
+            // The tables of a nested join are visited individually
+            TableFactor::NestedJoin { .. } => return ControlFlow::Continue(()),
+            TableFactor::Derived { alias, .. } => (
+                alias
+                    .iter()
+                    .map(|alias| normalize_ident(&alias.name))
+                    .collect(),
+                None,
+            ),
+            _ => (Vec::new(), None),
+        };
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.relations.push(relation);
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_expr(&mut self, expr: &Expr) -> ControlFlow<Self::Break> {
+        let column = match expr {
+            Expr::Identifier(ident) => vec![normalize_ident(ident)],
+            Expr::CompoundIdentifier(idents) => idents.iter().map(normalize_ident).collect(),
+            _ => return ControlFlow::Continue(()),
+        };
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.columns.push(column);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Parse `sql` with `dialect` and collect the tables and columns it references
+fn collect_references(sql: &str, dialect: &dyn Dialect) -> PlannerResult<ReferenceCollector> {
+    let statements = Parser::parse_sql(dialect, sql)?;
+    let mut collector = ReferenceCollector::default();
+    // The collector never breaks, so there is nothing to inspect in the result
+    let _ = statements.visit(&mut collector);
+    Ok(collector)
+}
+
+/// Get the base tables referenced by a SQL string without planning it
+///
+/// Unlike planning, this needs neither a `ContextProvider` nor schemas. Tables referenced in
+/// joins and subqueries are included, while the names of common table expressions are not.
+/// Tables are returned in order of first appearance, and unquoted identifiers are lowercased
+/// the same way `DataFusion` normalizes them.
+///
+/// The SQL is parsed with the [`GenericDialect`].
+/// See [`extract_table_references_with_dialect`] for other dialects.
+pub fn extract_table_references(sql: &str) -> PlannerResult<Vec<TableReference>> {
+    extract_table_references_with_dialect(sql, &GenericDialect {})
+}
+
+/// Get the base tables referenced by a SQL string in the given `sqlparser` dialect without
+/// planning it
+///
+/// The dialect decides e.g. which quotes delimit identifiers, so that
+/// ``SELECT * FROM `Orders` `` only parses with the `MySqlDialect`, and `"Orders"` is a string
+/// rather than a quoted identifier there.
+/// See [`extract_table_references`] for more details.
+pub fn extract_table_references_with_dialect(
+    sql: &str,
+    dialect: &dyn Dialect,
+) -> PlannerResult<Vec<TableReference>> {
+    Ok(collect_references(sql, dialect)?
+        .tables
+        .into_iter()
+        .collect())
+}
+
+/// Get the base tables referenced by a SQL string along with the columns referenced in each
+/// of them, without planning it
+///
+/// Columns are only included where the table they belong to can be determined syntactically,
+/// i.e. when they are qualified with a table name or alias, or when they appear in a query
+/// reading from a single base table. Every table returned by [`extract_table_references`] is a
+/// key of the result, even if none of its columns could be determined.
+///
+/// The SQL is parsed with the [`GenericDialect`].
+/// See [`extract_table_and_column_references_with_dialect`] for other dialects.
+pub fn extract_table_and_column_references(
+    sql: &str,
+) -> PlannerResult<IndexMap<TableReference, IndexSet<String>>> {
+    extract_table_and_column_references_with_dialect(sql, &GenericDialect {})
+}
+
+/// Get the base tables referenced by a SQL string in the given `sqlparser` dialect along with
+/// the columns referenced in each of them, without planning it
+///
+/// See [`extract_table_and_column_references`] and [`extract_table_references_with_dialect`]
+/// for more details.
+pub fn extract_table_and_column_references_with_dialect(
+    sql: &str,
+    dialect: &dyn Dialect,
+) -> PlannerResult<IndexMap<TableReference, IndexSet<String>>> {
+    let ReferenceCollector {
+        tables,
+        mut columns,
+        ..
+    } = collect_references(sql, dialect)?;
+    Ok(tables
+        .into_iter()
+        .map(|table| {
+            let table_columns = columns.swap_remove(&table).unwrap_or_default();
+            (table, table_columns)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlannerError;
+    use alloc::vec;
+    use sqlparser::dialect::{MySqlDialect, PostgreSqlDialect};
+
+    fn columns<const N: usize>(names: [&str; N]) -> IndexSet<String> {
+        names.into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn we_can_extract_table_references_from_joins_and_subqueries() {
+        let sql =
+            "SELECT e.employee_id, e.employee_name, d.department_name, p.project_name, s.salary
+FROM employees e
+JOIN departments d ON e.department_id = d.department_id
+JOIN management.projects p ON e.employee_id = p.employee_id
+JOIN Internal.Salaries s ON e.employee_id = s.employee_id
+WHERE e.department_id IN (
+    SELECT department_id
+    FROM departments
+    WHERE department_name = 'Sales'
+)
+AND p.project_id IN (
+    SELECT project_id
+    FROM project_assignments
+    WHERE employee_id = e.employee_id
+);";
+        assert_eq!(
+            extract_table_references(sql).unwrap(),
+            vec![
+                TableReference::bare("employees"),
+                TableReference::bare("departments"),
+                TableReference::partial("management", "projects"),
+                TableReference::partial("internal", "salaries"),
+                TableReference::bare("project_assignments"),
+            ]
+        );
+        let references = extract_table_and_column_references(sql).unwrap();
+        assert_eq!(
+            references[&TableReference::bare("employees")],
+            columns(["employee_id", "employee_name", "department_id"])
+        );
+        assert_eq!(
+            references[&TableReference::bare("departments")],
+            columns(["department_name", "department_id"])
+        );
+        assert_eq!(
+            references[&TableReference::partial("management", "projects")],
+            columns(["project_name", "employee_id", "project_id"])
+        );
+        assert_eq!(
+            references[&TableReference::partial("internal", "salaries")],
+            columns(["salary", "employee_id"])
+        );
+        assert_eq!(
+            references[&TableReference::bare("project_assignments")],
+            columns(["project_id", "employee_id"])
+        );
+    }
+
+    #[test]
+    fn we_can_exclude_cte_names_from_table_references() {
+        let sql = "WITH recent AS (SELECT id, amount FROM orders WHERE ts > 1)
+SELECT r.amount, c.name FROM recent r JOIN sxt.customers c ON r.id = c.id;";
+        assert_eq!(
+            extract_table_references(sql).unwrap(),
+            vec![
+                TableReference::bare("orders"),
+                TableReference::partial("sxt", "customers"),
+            ]
+        );
+        let references = extract_table_and_column_references(sql).unwrap();
+        assert_eq!(references.len(), 2);
+        assert_eq!(
+            references[&TableReference::bare("orders")],
+            columns(["id", "amount", "ts"])
+        );
+        assert_eq!(
+            references[&TableReference::partial("sxt", "customers")],
+            columns(["name", "id"])
+        );
+    }
+
+    #[test]
+    fn we_can_extract_table_references_from_multiple_statements() {
+        let sql =
+            "SELECT a AS x, b FROM t WHERE b IN (SELECT u.b FROM u WHERE u.c = t.a) ORDER BY x;
+SELECT * FROM \"Quoted\".t;";
+        assert_eq!(
+            extract_table_references(sql).unwrap(),
+            vec![
+                TableReference::bare("t"),
+                TableReference::bare("u"),
+                TableReference::partial("Quoted", "t"),
+            ]
+        );
+        let references = extract_table_and_column_references(sql).unwrap();
+        assert_eq!(references[&TableReference::bare("t")], columns(["a", "b"]));
+        assert_eq!(references[&TableReference::bare("u")], columns(["b", "c"]));
+        assert_eq!(
+            references[&TableReference::partial("Quoted", "t")],
+            columns([])
+        );
+    }
+
+    #[test]
+    fn we_do_not_attribute_ambiguous_columns_to_tables() {
+        let sql = "SELECT a, t.b FROM t JOIN u ON t.id = u.id;
+SELECT c FROM (SELECT c FROM v) AS sub;";
+        let references = extract_table_and_column_references(sql).unwrap();
+        assert_eq!(references[&TableReference::bare("t")], columns(["b", "id"]));
+        assert_eq!(references[&TableReference::bare("u")], columns(["id"]));
+        assert_eq!(references[&TableReference::bare("v")], columns(["c"]));
+    }
+
+    #[test]
+    fn we_can_extract_table_references_in_other_dialects() {
+        let sql = "SELECT `Name`, \"Name\" FROM `Cats` JOIN sxt.`Owners` ON `Cats`.id = owner_id;";
+        assert_eq!(
+            extract_table_references_with_dialect(sql, &MySqlDialect {}).unwrap(),
+            vec![
+                TableReference::bare("Cats"),
+                TableReference::partial("sxt", "Owners"),
+            ]
+        );
+        // `"age"` is a string in MySQL, so only the backtick-quoted `Name` is a column
+        let sql = "SELECT `Name`, \"age\" FROM `Cats`;";
+        let references =
+            extract_table_and_column_references_with_dialect(sql, &MySqlDialect {}).unwrap();
+        assert_eq!(references[&TableReference::bare("Cats")], columns(["Name"]));
+
+        let sql = "SELECT \"Name\", age::bigint FROM \"Cats\";";
+        let references =
+            extract_table_and_column_references_with_dialect(sql, &PostgreSqlDialect {}).unwrap();
+        assert_eq!(
+            references[&TableReference::bare("Cats")],
+            columns(["Name", "age"])
+        );
+    }
+
+    #[test]
+    fn we_cannot_extract_table_references_from_sql_in_another_dialect() {
+        assert!(matches!(
+            extract_table_references_with_dialect("SELECT * FROM `Cats`;", &PostgreSqlDialect {}),
+            Err(PlannerError::SqlParserError { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_extract_table_references_from_invalid_sql() {
+        assert!(matches!(
+            extract_table_references("SELECT * FROM"),
+            Err(PlannerError::SqlParserError { .. })
+        ));
+    }
+}
@@ -0,0 +1,981 @@
+use alloc::sync::Arc;
+use arrow::{
+    array::{Array, BooleanArray, Int32Array, Int64Array, StringArray},
+    compute::{cast_with_options, kernels::zip::zip, CastOptions},
+    datatypes::DataType,
+};
+use core::any::Any;
+use datafusion::{
+    common::{exec_err, plan_err, Result},
+    logical_expr::{ColumnarValue, ScalarUDF, ScalarUDFImpl, Signature, Volatility},
+};
+
+/// Name of the conditional function `IF(condition, then, else)`
+pub(crate) const IF_FUNCTION_NAME: &str = "if";
+
+/// Alias of [`IF_FUNCTION_NAME`]
+const IIF_FUNCTION_NAME: &str = "iif";
+
+/// Name of the substring search function `STRPOS(string, substring)`
+///
+/// `DataFusion` plans `POSITION(substring IN string)` as a call to this function.
+const STRPOS_FUNCTION_NAME: &str = "strpos";
+
+/// Alias of [`STRPOS_FUNCTION_NAME`]
+const INSTR_FUNCTION_NAME: &str = "instr";
+
+/// Name of the function `SPLIT_PART(string, delimiter, n)`, extracting a delimited field
+const SPLIT_PART_FUNCTION_NAME: &str = "split_part";
+
+/// Name of the function `ROUND_TO(value, step)`, rounding an integer to the nearest multiple of a step
+pub(crate) const ROUND_TO_FUNCTION_NAME: &str = "round_to";
+
+/// Name of the function `ISQRT(value)`, the integer square root of a non-negative integer
+pub(crate) const ISQRT_FUNCTION_NAME: &str = "isqrt";
+
+/// Name of the function `POW_MOD(base, exponent, modulus)`, the modular power of an integer
+pub(crate) const POW_MOD_FUNCTION_NAME: &str = "pow_mod";
+
+/// The scalar function `IF(condition, then, else)`, also available as `IIF`
+///
+/// `DataFusion` 38 has no such function built in, so it is registered with the
+/// [`PoSqlContextProvider`](crate::PoSqlContextProvider) and converted to an
+/// `IfExpr` by the planner.
+#[derive(Debug)]
+pub(crate) struct IfUdf {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl IfUdf {
+    /// Create a new `IfUdf`
+    pub(crate) fn new() -> Self {
+        Self {
+            signature: Signature::any(3, Volatility::Immutable),
+            aliases: vec![IIF_FUNCTION_NAME.into()],
+        }
+    }
+}
+
+impl ScalarUDFImpl for IfUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &str {
+        IF_FUNCTION_NAME
+    }
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types {
+            [_, then_type, _] => Ok(then_type.clone()),
+            _ => plan_err!("IF takes exactly three arguments"),
+        }
+    }
+    /// Evaluate the function. This is only used by `DataFusion` to fold constant expressions.
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(args)?;
+        let [condition, then_array, else_array] = arrays.as_slice() else {
+            return exec_err!("IF takes exactly three arguments");
+        };
+        let Some(condition) = condition.as_any().downcast_ref::<BooleanArray>() else {
+            return exec_err!("The condition of IF must be boolean");
+        };
+        Ok(ColumnarValue::Array(zip(
+            condition, then_array, else_array,
+        )?))
+    }
+}
+
+/// 1-based character position of the first occurrence of `substring` in `string`, or 0 if absent
+///
+/// An empty `substring` is found at position 1.
+fn strpos(string: &str, substring: &str) -> i32 {
+    string.find(substring).map_or(0, |byte_index| {
+        i32::try_from(string[..byte_index].chars().count() + 1).unwrap_or(i32::MAX)
+    })
+}
+
+/// The scalar function `STRPOS(string, substring)`, also available as `INSTR` and
+/// through the `POSITION(substring IN string)` syntax
+///
+/// A varchar column is committed to as the blake3 hashes of its values, one scalar per row, so
+/// no commitment binds the bytes of a string and there is no provable `PositionExpr`. This
+/// function is therefore only supported when `DataFusion` can fold it into a constant, i.e. when
+/// both arguments are literals. Calls on columns are rejected by the planner.
+#[derive(Debug)]
+pub(crate) struct StrposUdf {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl StrposUdf {
+    /// Create a new `StrposUdf`
+    pub(crate) fn new() -> Self {
+        Self {
+            signature: Signature::exact(
+                vec![DataType::Utf8, DataType::Utf8],
+                Volatility::Immutable,
+            ),
+            aliases: vec![INSTR_FUNCTION_NAME.into()],
+        }
+    }
+}
+
+impl ScalarUDFImpl for StrposUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &str {
+        STRPOS_FUNCTION_NAME
+    }
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Int32)
+    }
+    /// Evaluate the function. This is only used by `DataFusion` to fold constant expressions.
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(args)?;
+        let [string, substring] = arrays.as_slice() else {
+            return exec_err!("STRPOS takes exactly two arguments");
+        };
+        let (Some(string), Some(substring)) = (
+            string.as_any().downcast_ref::<StringArray>(),
+            substring.as_any().downcast_ref::<StringArray>(),
+        ) else {
+            return exec_err!("The arguments of STRPOS must be strings");
+        };
+        let res: Int32Array = (0..string.len())
+            .map(|i| {
+                (string.is_valid(i) && substring.is_valid(i))
+                    .then(|| strpos(string.value(i), substring.value(i)))
+            })
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(res)))
+    }
+}
+
+/// The `n`-th field of `string` split on `delimiter`, counted from 1
+///
+/// A negative `n` counts from the end, and a field beyond the number of fields is empty.
+/// Returns `None` if `delimiter` is empty or `n` is zero.
+fn split_part<'a>(string: &'a str, delimiter: &str, n: i64) -> Option<&'a str> {
+    if delimiter.is_empty() || n == 0 {
+        return None;
+    }
+    let index = usize::try_from(n.unsigned_abs() - 1).ok();
+    let field = if n > 0 {
+        index.and_then(|index| string.split(delimiter).nth(index))
+    } else {
+        index.and_then(|index| string.rsplit(delimiter).nth(index))
+    };
+    Some(field.unwrap_or_default())
+}
+
+/// The scalar function `SPLIT_PART(string, delimiter, n)`, extracting the `n`-th field of a
+/// delimited string
+///
+/// A varchar column is committed to as the blake3 hashes of its values, one scalar per row, so
+/// no commitment binds the bytes of a string and there is no provable `SplitPartExpr`. Even with
+/// the bytes committed, the result would be a varchar whose hash would have to be recomputed in
+/// the proof. This function is therefore only supported when `DataFusion` can fold it into a
+/// constant, i.e. when all arguments are literals. Calls on columns are rejected by the planner.
+#[derive(Debug)]
+pub(crate) struct SplitPartUdf {
+    signature: Signature,
+}
+
+impl SplitPartUdf {
+    /// Create a new `SplitPartUdf`
+    pub(crate) fn new() -> Self {
+        Self {
+            signature: Signature::exact(
+                vec![DataType::Utf8, DataType::Utf8, DataType::Int64],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for SplitPartUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &str {
+        SPLIT_PART_FUNCTION_NAME
+    }
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+    /// Evaluate the function. This is only used by `DataFusion` to fold constant expressions.
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(args)?;
+        let [string, delimiter, n] = arrays.as_slice() else {
+            return exec_err!("SPLIT_PART takes exactly three arguments");
+        };
+        let (Some(string), Some(delimiter), Some(n)) = (
+            string.as_any().downcast_ref::<StringArray>(),
+            delimiter.as_any().downcast_ref::<StringArray>(),
+            n.as_any().downcast_ref::<Int64Array>(),
+        ) else {
+            return exec_err!("The arguments of SPLIT_PART must be two strings and an integer");
+        };
+        let res = (0..string.len())
+            .map(|i| {
+                if !(string.is_valid(i) && delimiter.is_valid(i) && n.is_valid(i)) {
+                    return Ok(None);
+                }
+                match split_part(string.value(i), delimiter.value(i), n.value(i)) {
+                    Some(field) => Ok(Some(field)),
+                    None => exec_err!(
+                        "SPLIT_PART requires a nonempty delimiter and a nonzero field position"
+                    ),
+                }
+            })
+            .collect::<Result<StringArray>>()?;
+        Ok(ColumnarValue::Array(Arc::new(res)))
+    }
+}
+
+/// The multiple of `step` nearest to `value`, rounding exact midpoints away from zero
+///
+/// Returns `None` if `step` is zero or the result is out of range.
+fn round_to(value: i64, step: i64) -> Option<i64> {
+    let abs_step = i128::from(step).abs();
+    if abs_step == 0 {
+        return None;
+    }
+    let value = i128::from(value);
+    let rounded = value.signum() * ((value.abs() + abs_step / 2) / abs_step) * abs_step;
+    i64::try_from(rounded).ok()
+}
+
+/// The scalar function `ROUND_TO(value, step)`, rounding an integer to the nearest multiple of
+/// `step` with exact midpoints rounded away from zero
+///
+/// The result has the type of `value`. The planner converts this function to a
+/// `RoundToMultipleExpr`, which requires `step` to be a nonzero integer literal.
+#[derive(Debug)]
+pub(crate) struct RoundToUdf {
+    signature: Signature,
+}
+
+impl RoundToUdf {
+    /// Create a new `RoundToUdf`
+    pub(crate) fn new() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RoundToUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &str {
+        ROUND_TO_FUNCTION_NAME
+    }
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types {
+            [value_type, _] => Ok(value_type.clone()),
+            _ => plan_err!("ROUND_TO takes exactly two arguments"),
+        }
+    }
+    /// Evaluate the function. This is only used by `DataFusion` to fold constant expressions.
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(args)?;
+        let [value, step] = arrays.as_slice() else {
+            return exec_err!("ROUND_TO takes exactly two arguments");
+        };
+        if !value.data_type().is_integer() || !step.data_type().is_integer() {
+            return exec_err!("The arguments of ROUND_TO must be integers");
+        }
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let value_array = cast_with_options(value, &DataType::Int64, &options)?;
+        let step_array = cast_with_options(step, &DataType::Int64, &options)?;
+        let (Some(value_array), Some(step_array)) = (
+            value_array.as_any().downcast_ref::<Int64Array>(),
+            step_array.as_any().downcast_ref::<Int64Array>(),
+        ) else {
+            return exec_err!("The arguments of ROUND_TO must be integers");
+        };
+        let res = value_array
+            .iter()
+            .zip(step_array.iter())
+            .map(|pair| match pair {
+                (Some(value), Some(step)) => match round_to(value, step) {
+                    Some(rounded) => Ok(Some(rounded)),
+                    None => {
+                        exec_err!("ROUND_TO({value}, {step}) has a zero step or is out of range")
+                    }
+                },
+                _ => Ok(None),
+            })
+            .collect::<Result<Int64Array>>()?;
+        Ok(ColumnarValue::Array(cast_with_options(
+            &res,
+            value.data_type(),
+            &options,
+        )?))
+    }
+}
+
+/// The integer square root of `value`, i.e. the largest `r` with `r * r <= value`
+///
+/// Returns `None` if `value` is negative.
+fn isqrt(value: i64) -> Option<i64> {
+    u64::try_from(value)
+        .ok()
+        .map(|value| i64::try_from(value.isqrt()).expect("Square roots of i64 fit in i64"))
+}
+
+/// The scalar function `ISQRT(value)`, the integer square root `FLOOR(SQRT(value))` of a
+/// non-negative integer
+///
+/// The result has the type of `value`. The planner converts this function to an `ISqrtExpr`.
+#[derive(Debug)]
+pub(crate) struct ISqrtUdf {
+    signature: Signature,
+}
+
+impl ISqrtUdf {
+    /// Create a new `ISqrtUdf`
+    pub(crate) fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ISqrtUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &str {
+        ISQRT_FUNCTION_NAME
+    }
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types {
+            [value_type] => Ok(value_type.clone()),
+            _ => plan_err!("ISQRT takes exactly one argument"),
+        }
+    }
+    /// Evaluate the function. This is only used by `DataFusion` to fold constant expressions.
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(args)?;
+        let [value] = arrays.as_slice() else {
+            return exec_err!("ISQRT takes exactly one argument");
+        };
+        if !value.data_type().is_integer() {
+            return exec_err!("The argument of ISQRT must be an integer");
+        }
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let value_array = cast_with_options(value, &DataType::Int64, &options)?;
+        let Some(value_array) = value_array.as_any().downcast_ref::<Int64Array>() else {
+            return exec_err!("The argument of ISQRT must be an integer");
+        };
+        let res = value_array
+            .iter()
+            .map(|value| match value {
+                Some(value) => match isqrt(value) {
+                    Some(root) => Ok(Some(root)),
+                    None => exec_err!("ISQRT({value}) has a negative argument"),
+                },
+                None => Ok(None),
+            })
+            .collect::<Result<Int64Array>>()?;
+        Ok(ColumnarValue::Array(cast_with_options(
+            &res,
+            value.data_type(),
+            &options,
+        )?))
+    }
+}
+
+/// `base^exponent mod |modulus|`, as the least non-negative residue
+///
+/// Returns `None` if `exponent` is negative or `modulus` is zero.
+fn pow_mod(base: i64, exponent: i64, modulus: i64) -> Option<i64> {
+    let mut exponent = u64::try_from(exponent).ok()?;
+    let modulus = i128::from(modulus).abs();
+    if modulus == 0 {
+        return None;
+    }
+    // Residues are below 2^63, so their products fit in an i128
+    let mut base = i128::from(base).rem_euclid(modulus);
+    let mut res = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            res = res * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    Some(i64::try_from(res).expect("Residues modulo an i64 fit in i64"))
+}
+
+/// The scalar function `POW_MOD(base, exponent, modulus)`, raising an integer to a power modulo
+/// `modulus`
+///
+/// The result is a `BIGINT` in `[0, |modulus|)`. The planner converts this function to a
+/// `PowModExpr`, which requires `exponent` to be an integer literal between 0 and 16 and
+/// `modulus` to be a nonzero integer literal.
+#[derive(Debug)]
+pub(crate) struct PowModUdf {
+    signature: Signature,
+}
+
+impl PowModUdf {
+    /// Create a new `PowModUdf`
+    pub(crate) fn new() -> Self {
+        Self {
+            signature: Signature::any(3, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for PowModUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &str {
+        POW_MOD_FUNCTION_NAME
+    }
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match arg_types {
+            [_, _, _] => Ok(DataType::Int64),
+            _ => plan_err!("POW_MOD takes exactly three arguments"),
+        }
+    }
+    /// Evaluate the function. This is only used by `DataFusion` to fold constant expressions.
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let arrays = ColumnarValue::values_to_arrays(args)?;
+        let [base, exponent, modulus] = arrays.as_slice() else {
+            return exec_err!("POW_MOD takes exactly three arguments");
+        };
+        if !base.data_type().is_integer()
+            || !exponent.data_type().is_integer()
+            || !modulus.data_type().is_integer()
+        {
+            return exec_err!("The arguments of POW_MOD must be integers");
+        }
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let base_array = cast_with_options(base, &DataType::Int64, &options)?;
+        let exponent_array = cast_with_options(exponent, &DataType::Int64, &options)?;
+        let modulus_array = cast_with_options(modulus, &DataType::Int64, &options)?;
+        let (Some(base_array), Some(exponent_array), Some(modulus_array)) = (
+            base_array.as_any().downcast_ref::<Int64Array>(),
+            exponent_array.as_any().downcast_ref::<Int64Array>(),
+            modulus_array.as_any().downcast_ref::<Int64Array>(),
+        ) else {
+            return exec_err!("The arguments of POW_MOD must be integers");
+        };
+        let res = base_array
+            .iter()
+            .zip(exponent_array.iter().zip(modulus_array.iter()))
+            .map(|triple| match triple {
+                (Some(base), (Some(exponent), Some(modulus))) => {
+                    match pow_mod(base, exponent, modulus) {
+                        Some(power) => Ok(Some(power)),
+                        None => exec_err!(
+                            "POW_MOD({base}, {exponent}, {modulus}) has a negative exponent or a \
+                             zero modulus"
+                        ),
+                    }
+                }
+                _ => Ok(None),
+            })
+            .collect::<Result<Int64Array>>()?;
+        Ok(ColumnarValue::Array(Arc::new(res)))
+    }
+}
+
+/// Get the Proof of SQL specific scalar function with the given name, if any
+pub(crate) fn get_udf(name: &str) -> Option<Arc<ScalarUDF>> {
+    match name.to_lowercase().as_str() {
+        IF_FUNCTION_NAME | IIF_FUNCTION_NAME => {
+            Some(Arc::new(ScalarUDF::new_from_impl(IfUdf::new())))
+        }
+        STRPOS_FUNCTION_NAME | INSTR_FUNCTION_NAME => {
+            Some(Arc::new(ScalarUDF::new_from_impl(StrposUdf::new())))
+        }
+        ROUND_TO_FUNCTION_NAME => Some(Arc::new(ScalarUDF::new_from_impl(RoundToUdf::new()))),
+        SPLIT_PART_FUNCTION_NAME => Some(Arc::new(ScalarUDF::new_from_impl(SplitPartUdf::new()))),
+        ISQRT_FUNCTION_NAME => Some(Arc::new(ScalarUDF::new_from_impl(ISqrtUdf::new()))),
+        POW_MOD_FUNCTION_NAME => Some(Arc::new(ScalarUDF::new_from_impl(PowModUdf::new()))),
+        _ => None,
+    }
+}
+
+/// Names of all Proof of SQL specific scalar functions, including aliases
+pub(crate) fn udf_names() -> Vec<String> {
+    vec![
+        IF_FUNCTION_NAME.into(),
+        IIF_FUNCTION_NAME.into(),
+        STRPOS_FUNCTION_NAME.into(),
+        INSTR_FUNCTION_NAME.into(),
+        ROUND_TO_FUNCTION_NAME.into(),
+        SPLIT_PART_FUNCTION_NAME.into(),
+        ISQRT_FUNCTION_NAME.into(),
+        POW_MOD_FUNCTION_NAME.into(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int64Array};
+    use datafusion::common::ScalarValue;
+
+    #[test]
+    fn we_can_get_the_if_udf() {
+        for name in ["if", "IF", "iif", "IIF"] {
+            let udf = get_udf(name).unwrap();
+            assert_eq!(udf.name(), IF_FUNCTION_NAME);
+        }
+        assert!(get_udf("").is_none());
+        assert!(get_udf("ifnull").is_none());
+        assert_eq!(
+            udf_names(),
+            vec![
+                "if".to_string(),
+                "iif".to_string(),
+                "strpos".to_string(),
+                "instr".to_string(),
+                "round_to".to_string(),
+                "split_part".to_string(),
+                "isqrt".to_string(),
+                "pow_mod".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_strpos_udf() {
+        for name in ["strpos", "STRPOS", "instr", "Instr"] {
+            let udf = get_udf(name).unwrap();
+            assert_eq!(udf.name(), STRPOS_FUNCTION_NAME);
+        }
+        assert_eq!(
+            StrposUdf::new()
+                .return_type(&[DataType::Utf8, DataType::Utf8])
+                .unwrap(),
+            DataType::Int32
+        );
+    }
+
+    #[test]
+    fn we_can_find_the_position_of_a_substring() {
+        assert_eq!(strpos("abcabc", "c"), 3);
+        assert_eq!(strpos("abcabc", "abc"), 1);
+        assert_eq!(strpos("abcabc", "x"), 0);
+        assert_eq!(strpos("abcabc", ""), 1);
+        assert_eq!(strpos("", ""), 1);
+        assert_eq!(strpos("", "a"), 0);
+        // Overlapping matches give the first one
+        assert_eq!(strpos("aaaa", "aa"), 1);
+        assert_eq!(strpos("xaaaa", "aaa"), 2);
+        // Positions are counted in characters rather than bytes
+        assert_eq!(strpos("héllo", "l"), 3);
+        assert_eq!(strpos("ünïcödé", "cö"), 4);
+    }
+
+    #[test]
+    fn we_can_invoke_the_strpos_udf() {
+        let udf = StrposUdf::new();
+        let string: ArrayRef = Arc::new(StringArray::from(vec!["Chloe", "Margaret", "Katy"]));
+        let substring: ArrayRef = Arc::new(StringArray::from(vec!["o", "x", ""]));
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Array(string),
+                ColumnarValue::Array(substring),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(Int32Array::from(vec![3, 0, 1]));
+        assert_eq!(&res, &expected);
+
+        // Scalars are broadcast and nulls propagate
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some("Margaret".to_string()))),
+                ColumnarValue::Scalar(ScalarValue::Utf8(None)),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(Int32Array::from(vec![None]));
+        assert_eq!(&res, &expected);
+
+        // Non-string arguments are rejected
+        assert!(udf
+            .invoke(&[
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some("a".to_string()))),
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn we_can_get_the_split_part_udf() {
+        for name in ["split_part", "SPLIT_PART", "Split_Part"] {
+            let udf = get_udf(name).unwrap();
+            assert_eq!(udf.name(), SPLIT_PART_FUNCTION_NAME);
+        }
+        assert_eq!(
+            SplitPartUdf::new()
+                .return_type(&[DataType::Utf8, DataType::Utf8, DataType::Int64])
+                .unwrap(),
+            DataType::Utf8
+        );
+    }
+
+    #[test]
+    fn we_can_split_a_delimited_string() {
+        assert_eq!(split_part("a,b,c", ",", 1), Some("a"));
+        assert_eq!(split_part("a,b,c", ",", 3), Some("c"));
+        // Fields beyond the number of fields are empty
+        assert_eq!(split_part("a,b,c", ",", 4), Some(""));
+        assert_eq!(split_part("a,b,c", ",", i64::MAX), Some(""));
+        // Negative positions count from the end
+        assert_eq!(split_part("a,b,c", ",", -1), Some("c"));
+        assert_eq!(split_part("a,b,c", ",", -3), Some("a"));
+        assert_eq!(split_part("a,b,c", ",", -4), Some(""));
+        assert_eq!(split_part("a,b,c", ",", i64::MIN), Some(""));
+        // A missing delimiter gives the whole string as the only field
+        assert_eq!(split_part("abc", ",", 1), Some("abc"));
+        assert_eq!(split_part("abc", ",", 2), Some(""));
+        // Empty fields and multi-character delimiters
+        assert_eq!(split_part("a,,c", ",", 2), Some(""));
+        assert_eq!(split_part(",a", ",", 1), Some(""));
+        assert_eq!(split_part("", ",", 1), Some(""));
+        assert_eq!(split_part("a--b--c", "--", 2), Some("b"));
+        assert_eq!(split_part("ünï|cödé", "|", 2), Some("cödé"));
+        // An empty delimiter or a zero position is invalid
+        assert_eq!(split_part("a,b", "", 1), None);
+        assert_eq!(split_part("a,b", ",", 0), None);
+    }
+
+    #[test]
+    fn we_can_invoke_the_split_part_udf() {
+        let udf = SplitPartUdf::new();
+        let string: ArrayRef = Arc::new(StringArray::from(vec!["a.b.c", "Margaret", "x.y"]));
+        let delimiter: ArrayRef = Arc::new(StringArray::from(vec![".", ".", "."]));
+        let n: ArrayRef = Arc::new(Int64Array::from(vec![2, 1, 3]));
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Array(string),
+                ColumnarValue::Array(delimiter),
+                ColumnarValue::Array(n),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(StringArray::from(vec!["b", "Margaret", ""]));
+        assert_eq!(&res, &expected);
+
+        // Scalars are broadcast and nulls propagate
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Scalar(ScalarValue::Utf8(Some("a.b".to_string()))),
+                ColumnarValue::Scalar(ScalarValue::Utf8(None)),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(StringArray::from(vec![None::<&str>]));
+        assert_eq!(&res, &expected);
+
+        // An empty delimiter, a zero position and mistyped arguments are rejected
+        for args in [
+            [
+                ScalarValue::Utf8(Some("a.b".to_string())),
+                ScalarValue::Utf8(Some(String::new())),
+                ScalarValue::Int64(Some(1)),
+            ],
+            [
+                ScalarValue::Utf8(Some("a.b".to_string())),
+                ScalarValue::Utf8(Some(".".to_string())),
+                ScalarValue::Int64(Some(0)),
+            ],
+            [
+                ScalarValue::Int64(Some(1)),
+                ScalarValue::Utf8(Some(".".to_string())),
+                ScalarValue::Int64(Some(1)),
+            ],
+        ] {
+            assert!(udf.invoke(&args.map(ColumnarValue::Scalar)).is_err());
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_return_type_of_the_if_udf() {
+        let udf = IfUdf::new();
+        assert_eq!(
+            udf.return_type(&[DataType::Boolean, DataType::Int64, DataType::Int64])
+                .unwrap(),
+            DataType::Int64
+        );
+        assert!(udf.return_type(&[DataType::Boolean]).is_err());
+    }
+
+    #[test]
+    fn we_can_invoke_the_if_udf() {
+        let udf = IfUdf::new();
+        let condition: ArrayRef = Arc::new(BooleanArray::from(vec![true, false, true]));
+        let then_array: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3]));
+        let else_array: ArrayRef = Arc::new(Int64Array::from(vec![4, 5, 6]));
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Array(condition),
+                ColumnarValue::Array(then_array),
+                ColumnarValue::Array(else_array),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(Int64Array::from(vec![1, 5, 3]));
+        assert_eq!(&res, &expected);
+
+        // Scalars are broadcast
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Scalar(ScalarValue::Boolean(Some(false))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(2))),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(Int64Array::from(vec![2]));
+        assert_eq!(&res, &expected);
+
+        // Non-boolean conditions are rejected
+        assert!(udf
+            .invoke(&[
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(2))),
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn we_can_get_the_round_to_udf() {
+        for name in ["round_to", "ROUND_TO", "Round_To"] {
+            let udf = get_udf(name).unwrap();
+            assert_eq!(udf.name(), ROUND_TO_FUNCTION_NAME);
+        }
+        let udf = RoundToUdf::new();
+        assert_eq!(
+            udf.return_type(&[DataType::Int16, DataType::Int64])
+                .unwrap(),
+            DataType::Int16
+        );
+        assert!(udf.return_type(&[DataType::Int16]).is_err());
+    }
+
+    #[test]
+    fn we_can_round_to_the_nearest_multiple() {
+        assert_eq!(round_to(149, 100), Some(100));
+        assert_eq!(round_to(150, 100), Some(200));
+        assert_eq!(round_to(-150, 100), Some(-200));
+        assert_eq!(round_to(-149, 100), Some(-100));
+        assert_eq!(round_to(150, -100), Some(200));
+        assert_eq!(round_to(5, 3), Some(6));
+        assert_eq!(round_to(-4, 3), Some(-3));
+        assert_eq!(round_to(0, 7), Some(0));
+        assert_eq!(round_to(1, 0), None);
+        assert_eq!(round_to(i64::MAX, 10), None);
+        assert_eq!(round_to(i64::MIN, i64::MIN), Some(i64::MIN));
+    }
+
+    #[test]
+    fn we_can_invoke_the_round_to_udf() {
+        let udf = RoundToUdf::new();
+        let value: ArrayRef = Arc::new(Int32Array::from(vec![Some(14), Some(-15), None]));
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Array(value),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(10))),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(Int32Array::from(vec![Some(10), Some(-20), None]));
+        assert_eq!(&res, &expected);
+
+        // Zero steps, results out of range and non-integer arguments are rejected
+        for (value, step) in [
+            (ScalarValue::Int64(Some(1)), ScalarValue::Int64(Some(0))),
+            (ScalarValue::Int8(Some(125)), ScalarValue::Int64(Some(10))),
+            (
+                ScalarValue::Utf8(Some("a".to_string())),
+                ScalarValue::Int64(Some(10)),
+            ),
+            (
+                ScalarValue::Int64(Some(1)),
+                ScalarValue::Boolean(Some(true)),
+            ),
+        ] {
+            assert!(udf
+                .invoke(&[ColumnarValue::Scalar(value), ColumnarValue::Scalar(step)])
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_isqrt_udf() {
+        for name in ["isqrt", "ISQRT", "ISqrt"] {
+            let udf = get_udf(name).unwrap();
+            assert_eq!(udf.name(), ISQRT_FUNCTION_NAME);
+        }
+        let udf = ISqrtUdf::new();
+        assert_eq!(
+            udf.return_type(&[DataType::Int16]).unwrap(),
+            DataType::Int16
+        );
+        assert!(udf
+            .return_type(&[DataType::Int16, DataType::Int16])
+            .is_err());
+    }
+
+    #[test]
+    fn we_can_take_integer_square_roots() {
+        assert_eq!(isqrt(0), Some(0));
+        assert_eq!(isqrt(1), Some(1));
+        assert_eq!(isqrt(15), Some(3));
+        assert_eq!(isqrt(16), Some(4));
+        assert_eq!(isqrt(17), Some(4));
+        assert_eq!(isqrt(i64::MAX), Some(3_037_000_499));
+        assert_eq!(isqrt(-1), None);
+        assert_eq!(isqrt(i64::MIN), None);
+    }
+
+    #[test]
+    fn we_can_invoke_the_isqrt_udf() {
+        let udf = ISqrtUdf::new();
+        let value: ArrayRef = Arc::new(Int32Array::from(vec![Some(0), Some(99), Some(100), None]));
+        let ColumnarValue::Array(res) = udf.invoke(&[ColumnarValue::Array(value)]).unwrap() else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(Int32Array::from(vec![Some(0), Some(9), Some(10), None]));
+        assert_eq!(&res, &expected);
+
+        // Negative and non-integer arguments are rejected
+        for value in [
+            ScalarValue::Int64(Some(-1)),
+            ScalarValue::Utf8(Some("a".to_string())),
+        ] {
+            assert!(udf.invoke(&[ColumnarValue::Scalar(value)]).is_err());
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_pow_mod_udf() {
+        for name in ["pow_mod", "POW_MOD", "Pow_Mod"] {
+            let udf = get_udf(name).unwrap();
+            assert_eq!(udf.name(), POW_MOD_FUNCTION_NAME);
+        }
+        let udf = PowModUdf::new();
+        assert_eq!(
+            udf.return_type(&[DataType::Int16, DataType::Int64, DataType::Int32])
+                .unwrap(),
+            DataType::Int64
+        );
+        assert!(udf
+            .return_type(&[DataType::Int16, DataType::Int16])
+            .is_err());
+    }
+
+    #[test]
+    fn we_can_take_modular_powers() {
+        assert_eq!(pow_mod(3, 4, 7), Some(4));
+        assert_eq!(pow_mod(-3, 3, 7), Some(1));
+        assert_eq!(pow_mod(-7, 1, 3), Some(2));
+        assert_eq!(pow_mod(2, 10, -1000), Some(24));
+        assert_eq!(pow_mod(5, 0, 7), Some(1));
+        assert_eq!(pow_mod(5, 0, 1), Some(0));
+        assert_eq!(pow_mod(2, 100, 1_000_000_007), Some(976_371_285));
+        assert_eq!(pow_mod(i64::MIN, 3, i64::MIN), Some(0));
+        assert_eq!(pow_mod(-1, 3, i64::MIN), Some(i64::MAX));
+        assert_eq!(pow_mod(2, -1, 7), None);
+        assert_eq!(pow_mod(2, 3, 0), None);
+    }
+
+    #[test]
+    fn we_can_invoke_the_pow_mod_udf() {
+        let udf = PowModUdf::new();
+        let base: ArrayRef = Arc::new(Int32Array::from(vec![Some(3), Some(-3), None]));
+        let ColumnarValue::Array(res) = udf
+            .invoke(&[
+                ColumnarValue::Array(base),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(3))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(7))),
+            ])
+            .unwrap()
+        else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(Int64Array::from(vec![Some(6), Some(1), None]));
+        assert_eq!(&res, &expected);
+
+        // Negative exponents, zero moduli and non-integer arguments are rejected
+        for (exponent, modulus) in [
+            (ScalarValue::Int64(Some(-1)), ScalarValue::Int64(Some(7))),
+            (ScalarValue::Int64(Some(2)), ScalarValue::Int64(Some(0))),
+            (
+                ScalarValue::Utf8(Some("a".to_string())),
+                ScalarValue::Int64(Some(7)),
+            ),
+        ] {
+            assert!(udf
+                .invoke(&[
+                    ColumnarValue::Scalar(ScalarValue::Int64(Some(2))),
+                    ColumnarValue::Scalar(exponent),
+                    ColumnarValue::Scalar(modulus),
+                ])
+                .is_err());
+        }
+    }
+}
@@ -14,6 +14,7 @@ use proof_of_sql::{
     sql::proof_exprs::DynProofExpr,
 };
 use sqlparser::ast::Ident;
+use std::sync::Arc;
 
 /// Parse a placeholder string of the form "$1", "$2", etc. into a `usize`.
 fn parse_placeholder_id(s: &str) -> Option<usize> {
@@ -60,6 +61,14 @@ pub(crate) fn table_reference_to_table_ref(table: &TableReference) -> PlannerRes
 ///
 /// TODO: add other types supported in `PoSQL`
 pub(crate) fn scalar_value_to_literal_value(value: ScalarValue) -> PlannerResult<LiteralValue> {
+    let data_type = value.data_type();
+    let timestamp = |unit, timezone: &Option<Arc<str>>, time| {
+        PoSQLTimeZone::try_from(timezone)
+            .map(|timezone| LiteralValue::TimeStampTZ(unit, timezone, time))
+            .map_err(|_| PlannerError::UnsupportedDataType {
+                data_type: data_type.clone(),
+            })
+    };
     match value {
         ScalarValue::Boolean(Some(v)) => Ok(LiteralValue::Boolean(v)),
         ScalarValue::Int8(Some(v)) => Ok(LiteralValue::TinyInt(v)),
@@ -69,26 +78,16 @@ pub(crate) fn scalar_value_to_literal_value(value: ScalarValue) -> PlannerResult
         ScalarValue::UInt8(Some(v)) => Ok(LiteralValue::Uint8(v)),
         ScalarValue::Utf8(Some(v)) => Ok(LiteralValue::VarChar(v)),
         ScalarValue::Binary(Some(v)) => Ok(LiteralValue::VarBinary(v)),
-        ScalarValue::TimestampSecond(Some(v), None) => Ok(LiteralValue::TimeStampTZ(
-            PoSQLTimeUnit::Second,
-            PoSQLTimeZone::utc(),
-            v,
-        )),
-        ScalarValue::TimestampMillisecond(Some(v), None) => Ok(LiteralValue::TimeStampTZ(
-            PoSQLTimeUnit::Millisecond,
-            PoSQLTimeZone::utc(),
-            v,
-        )),
-        ScalarValue::TimestampMicrosecond(Some(v), None) => Ok(LiteralValue::TimeStampTZ(
-            PoSQLTimeUnit::Microsecond,
-            PoSQLTimeZone::utc(),
-            v,
-        )),
-        ScalarValue::TimestampNanosecond(Some(v), None) => Ok(LiteralValue::TimeStampTZ(
-            PoSQLTimeUnit::Nanosecond,
-            PoSQLTimeZone::utc(),
-            v,
-        )),
+        ScalarValue::TimestampSecond(Some(v), tz) => timestamp(PoSQLTimeUnit::Second, &tz, v),
+        ScalarValue::TimestampMillisecond(Some(v), tz) => {
+            timestamp(PoSQLTimeUnit::Millisecond, &tz, v)
+        }
+        ScalarValue::TimestampMicrosecond(Some(v), tz) => {
+            timestamp(PoSQLTimeUnit::Microsecond, &tz, v)
+        }
+        ScalarValue::TimestampNanosecond(Some(v), tz) => {
+            timestamp(PoSQLTimeUnit::Nanosecond, &tz, v)
+        }
         ScalarValue::Decimal128(Some(v), precision, scale) => Ok(LiteralValue::Decimal75(
             Precision::new(precision)?,
             scale,
@@ -99,9 +98,7 @@ pub(crate) fn scalar_value_to_literal_value(value: ScalarValue) -> PlannerResult
             scale,
             v.into(),
         )),
-        _ => Err(PlannerError::UnsupportedDataType {
-            data_type: value.data_type().clone(),
-        }),
+        _ => Err(PlannerError::UnsupportedDataType { data_type }),
     }
 }
 
@@ -365,6 +362,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_convert_scalar_value_to_literal_value_for_timestamps_with_time_zones() {
+        let value = ScalarValue::TimestampSecond(Some(1_741_236_192_i64), Some("+01:00".into()));
+        assert_eq!(
+            scalar_value_to_literal_value(value).unwrap(),
+            LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::new(3600),
+                1_741_236_192_i64
+            )
+        );
+
+        let value =
+            ScalarValue::TimestampMillisecond(Some(1_741_236_192_004_i64), Some("-05:30".into()));
+        assert_eq!(
+            scalar_value_to_literal_value(value).unwrap(),
+            LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::new(-19_800),
+                1_741_236_192_004_i64
+            )
+        );
+
+        let value = ScalarValue::TimestampNanosecond(Some(1), Some("UTC".into()));
+        assert_eq!(
+            scalar_value_to_literal_value(value).unwrap(),
+            LiteralValue::TimeStampTZ(PoSQLTimeUnit::Nanosecond, PoSQLTimeZone::utc(), 1)
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_scalar_value_to_literal_value_for_timestamps_with_invalid_time_zones() {
+        let value = ScalarValue::TimestampMicrosecond(Some(1), Some("America/New_York".into()));
+        assert!(matches!(
+            scalar_value_to_literal_value(value),
+            Err(PlannerError::UnsupportedDataType { .. })
+        ));
+    }
+
     #[expect(clippy::cast_sign_loss)]
     #[test]
     fn we_can_convert_scalar_value_to_literal_value_for_decimals() {
@@ -0,0 +1,532 @@
+//! In this file we compare the verified results of random queries over random tables
+//! with the results of executing the same queries in `DataFusion`.
+//!
+//! These tests are slow, so they only run with the `differential-tests` feature.
+#![cfg(feature = "differential-tests")]
+use arrow::{compute::concat_batches, record_batch::RecordBatch};
+use core::fmt;
+use datafusion::{
+    config::ConfigOptions,
+    datasource::MemTable,
+    prelude::{SessionConfig, SessionContext},
+};
+use proof_of_sql::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, OwnedColumn, OwnedTable, OwnedTableTestAccessor, TableRef,
+        },
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+        scalar::Scalar,
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::proof::VerifiableQueryResult,
+};
+use proof_of_sql_planner::sql_to_proof_plans;
+use proptest::prelude::*;
+use sqlparser::{dialect::GenericDialect, parser::Parser};
+use std::sync::Arc;
+
+const TABLE_NAME: &str = "t";
+const INT_COLUMNS: &[&str] = &["a", "b"];
+const OTHER_COLUMNS: &[&str] = &["d", "e", "f"];
+const VARCHARS: &[&str] = &["", "x", "y", "xy"];
+const GROUP_BY_KEYS: &[&str] = &["a", "b", "d"];
+const DISTINCT_ON_KEYS: &[&str] = &["a", "d"];
+const TIEBREAKERS: &[&str] = &["b", "f"];
+
+/// A row of the table `t`
+///
+/// `e` is a `DECIMAL(10, 2)` in hundredths and `f` is a `TIMESTAMP` in seconds
+#[derive(Debug, Clone)]
+struct Row {
+    a: i64,
+    b: i64,
+    c: bool,
+    d: &'static str,
+    e: i64,
+    f: i64,
+}
+
+fn row() -> impl Strategy<Value = Row> {
+    (
+        -10_i64..=10,
+        -10_i64..=10,
+        any::<bool>(),
+        prop::sample::select(VARCHARS),
+        -1000_i64..=1000,
+        0_i64..5,
+    )
+        .prop_map(|(a, b, c, d, e, f)| Row { a, b, c, d, e, f })
+}
+
+fn owned_table_of_rows(rows: &[Row]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("a", rows.iter().map(|row| row.a)),
+        bigint("b", rows.iter().map(|row| row.b)),
+        boolean("c", rows.iter().map(|row| row.c)),
+        varchar("d", rows.iter().map(|row| row.d)),
+        decimal75("e", 10, 2, rows.iter().map(|row| row.e)),
+        timestamptz(
+            "f",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            rows.iter().map(|row| row.f),
+        ),
+    ])
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ArithmeticOp {
+    Add,
+    Subtract,
+    Multiply,
+}
+
+impl fmt::Display for ArithmeticOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ArithmeticOp::Add => "+",
+            ArithmeticOp::Subtract => "-",
+            ArithmeticOp::Multiply => "*",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareOp {
+    Equal,
+    LessThan,
+    GreaterThan,
+    LessThanOrEqual,
+    GreaterThanOrEqual,
+}
+
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CompareOp::Equal => "=",
+            CompareOp::LessThan => "<",
+            CompareOp::GreaterThan => ">",
+            CompareOp::LessThanOrEqual => "<=",
+            CompareOp::GreaterThanOrEqual => ">=",
+        })
+    }
+}
+
+/// A `BIGINT` expression, which is kept shallow so that it can't overflow
+#[derive(Debug, Clone)]
+enum IntExpr {
+    Column(&'static str),
+    Literal(i64),
+    Binary(Box<IntExpr>, ArithmeticOp, Box<IntExpr>),
+}
+
+impl fmt::Display for IntExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntExpr::Column(name) => f.write_str(name),
+            IntExpr::Literal(value) if *value < 0 => write!(f, "({value})"),
+            IntExpr::Literal(value) => write!(f, "{value}"),
+            IntExpr::Binary(lhs, op, rhs) => write!(f, "({lhs} {op} {rhs})"),
+        }
+    }
+}
+
+/// A `BOOLEAN` expression
+#[derive(Debug, Clone)]
+enum BoolExpr {
+    Column,
+    Literal(bool),
+    Compare(IntExpr, CompareOp, IntExpr),
+    /// Compare the decimal column `e` with an integer
+    DecimalCompare(CompareOp, IntExpr),
+    /// Compare the varchar column `d` with a literal
+    VarCharEquals(&'static str),
+    Not(Box<BoolExpr>),
+    And(Box<BoolExpr>, Box<BoolExpr>),
+    Or(Box<BoolExpr>, Box<BoolExpr>),
+}
+
+impl fmt::Display for BoolExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoolExpr::Column => f.write_str("c"),
+            BoolExpr::Literal(value) => write!(f, "{value}"),
+            BoolExpr::Compare(lhs, op, rhs) => write!(f, "({lhs} {op} {rhs})"),
+            BoolExpr::DecimalCompare(op, rhs) => write!(f, "(e {op} {rhs})"),
+            BoolExpr::VarCharEquals(value) => write!(f, "(d = '{value}')"),
+            BoolExpr::Not(expr) => write!(f, "(NOT {expr})"),
+            BoolExpr::And(lhs, rhs) => write!(f, "({lhs} AND {rhs})"),
+            BoolExpr::Or(lhs, rhs) => write!(f, "({lhs} OR {rhs})"),
+        }
+    }
+}
+
+/// An expression of a `SELECT` list
+#[derive(Debug, Clone)]
+enum SelectExpr {
+    Int(IntExpr),
+    Bool(BoolExpr),
+    /// One of the varchar, decimal or timestamp columns
+    Column(&'static str),
+    /// Add an integer to the decimal column `e`
+    DecimalAdd(IntExpr),
+}
+
+impl fmt::Display for SelectExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectExpr::Int(expr) => write!(f, "{expr}"),
+            SelectExpr::Bool(expr) => write!(f, "{expr}"),
+            SelectExpr::Column(name) => f.write_str(name),
+            SelectExpr::DecimalAdd(rhs) => write!(f, "(e + {rhs})"),
+        }
+    }
+}
+
+/// A query over the table `t`
+#[derive(Clone)]
+enum Query {
+    Filter {
+        exprs: Vec<SelectExpr>,
+        predicate: Option<BoolExpr>,
+    },
+    GroupBy {
+        keys: Vec<&'static str>,
+        sums: Vec<IntExpr>,
+        sum_decimal: bool,
+        predicate: Option<BoolExpr>,
+    },
+    DistinctOn {
+        keys: Vec<&'static str>,
+        tiebreaker: &'static str,
+        descending: bool,
+        predicate: Option<BoolExpr>,
+    },
+}
+
+impl Query {
+    /// Whether the order of the rows of the result is defined by the query
+    fn is_ordered(&self) -> bool {
+        matches!(self, Query::DistinctOn { .. })
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_where = |f: &mut fmt::Formatter<'_>, predicate: &Option<BoolExpr>| {
+            predicate
+                .as_ref()
+                .map_or(Ok(()), |predicate| write!(f, " WHERE {predicate}"))
+        };
+        match self {
+            Query::Filter { exprs, predicate } => {
+                let exprs: Vec<String> = exprs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, expr)| format!("{expr} AS c{i}"))
+                    .collect();
+                write!(f, "SELECT {} FROM {TABLE_NAME}", exprs.join(", "))?;
+                write_where(f, predicate)
+            }
+            Query::GroupBy {
+                keys,
+                sums,
+                sum_decimal,
+                predicate,
+            } => {
+                let keys = keys.join(", ");
+                let sums: Vec<String> = sums
+                    .iter()
+                    .enumerate()
+                    .map(|(i, expr)| format!("SUM({expr}) AS s{i}, "))
+                    .collect();
+                let sum_decimal = if *sum_decimal { "SUM(e) AS se, " } else { "" };
+                write!(
+                    f,
+                    "SELECT {keys}, {}{sum_decimal}COUNT(1) AS n FROM {TABLE_NAME}",
+                    sums.concat()
+                )?;
+                write_where(f, predicate)?;
+                write!(f, " GROUP BY {keys}")
+            }
+            Query::DistinctOn {
+                keys,
+                tiebreaker,
+                descending,
+                predicate,
+            } => {
+                let keys = keys.join(", ");
+                write!(
+                    f,
+                    "SELECT DISTINCT ON ({keys}) {keys}, {tiebreaker} FROM {TABLE_NAME}"
+                )?;
+                write_where(f, predicate)?;
+                write!(f, " ORDER BY {keys}, {tiebreaker}")?;
+                if *descending {
+                    f.write_str(" DESC")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Failing cases are shrunk and reported as the SQL of the query
+impl fmt::Debug for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+fn arithmetic_op() -> impl Strategy<Value = ArithmeticOp> {
+    prop_oneof![
+        Just(ArithmeticOp::Add),
+        Just(ArithmeticOp::Subtract),
+        Just(ArithmeticOp::Multiply),
+    ]
+}
+
+fn compare_op() -> impl Strategy<Value = CompareOp> {
+    prop_oneof![
+        Just(CompareOp::Equal),
+        Just(CompareOp::LessThan),
+        Just(CompareOp::GreaterThan),
+        Just(CompareOp::LessThanOrEqual),
+        Just(CompareOp::GreaterThanOrEqual),
+    ]
+}
+
+fn int_expr() -> impl Strategy<Value = IntExpr> {
+    let leaf = prop_oneof![
+        prop::sample::select(INT_COLUMNS).prop_map(IntExpr::Column),
+        (-5_i64..=5).prop_map(IntExpr::Literal),
+    ];
+    leaf.prop_recursive(2, 4, 2, |inner| {
+        (inner.clone(), arithmetic_op(), inner)
+            .prop_map(|(lhs, op, rhs)| IntExpr::Binary(Box::new(lhs), op, Box::new(rhs)))
+    })
+}
+
+fn bool_expr() -> impl Strategy<Value = BoolExpr> {
+    let leaf = prop_oneof![
+        Just(BoolExpr::Column),
+        any::<bool>().prop_map(BoolExpr::Literal),
+        (int_expr(), compare_op(), int_expr())
+            .prop_map(|(lhs, op, rhs)| BoolExpr::Compare(lhs, op, rhs)),
+        (compare_op(), int_expr()).prop_map(|(op, rhs)| BoolExpr::DecimalCompare(op, rhs)),
+        prop::sample::select(VARCHARS).prop_map(BoolExpr::VarCharEquals),
+    ];
+    leaf.prop_recursive(2, 4, 2, |inner| {
+        prop_oneof![
+            inner.clone().prop_map(|expr| BoolExpr::Not(Box::new(expr))),
+            (inner.clone(), inner.clone())
+                .prop_map(|(lhs, rhs)| BoolExpr::And(Box::new(lhs), Box::new(rhs))),
+            (inner.clone(), inner)
+                .prop_map(|(lhs, rhs)| BoolExpr::Or(Box::new(lhs), Box::new(rhs))),
+        ]
+    })
+}
+
+fn select_expr() -> impl Strategy<Value = SelectExpr> {
+    prop_oneof![
+        int_expr().prop_map(SelectExpr::Int),
+        bool_expr().prop_map(SelectExpr::Bool),
+        prop::sample::select(OTHER_COLUMNS).prop_map(SelectExpr::Column),
+        int_expr().prop_map(SelectExpr::DecimalAdd),
+    ]
+}
+
+fn query() -> impl Strategy<Value = Query> {
+    let predicate = || prop::option::of(bool_expr());
+    prop_oneof![
+        (prop::collection::vec(select_expr(), 1..=3), predicate())
+            .prop_map(|(exprs, predicate)| Query::Filter { exprs, predicate }),
+        (
+            prop::sample::subsequence(GROUP_BY_KEYS, 1..=GROUP_BY_KEYS.len()),
+            prop::collection::vec(int_expr(), 0..=2),
+            any::<bool>(),
+            predicate(),
+        )
+            .prop_map(|(keys, sums, sum_decimal, predicate)| Query::GroupBy {
+                keys,
+                sums,
+                sum_decimal,
+                predicate,
+            }),
+        (
+            prop::sample::subsequence(DISTINCT_ON_KEYS, 1..=DISTINCT_ON_KEYS.len()),
+            prop::sample::select(TIEBREAKERS),
+            any::<bool>(),
+            predicate(),
+        )
+            .prop_map(
+                |(keys, tiebreaker, descending, predicate)| Query::DistinctOn {
+                    keys,
+                    tiebreaker,
+                    descending,
+                    predicate,
+                }
+            ),
+    ]
+}
+
+/// Execute the query with `DataFusion` over an in-memory copy of the table
+fn datafusion_result(
+    sql: &str,
+    table: OwnedTable<Curve25519Scalar>,
+) -> OwnedTable<Curve25519Scalar> {
+    let batch = RecordBatch::try_from(table).unwrap();
+    let mem_table = MemTable::try_new(batch.schema(), vec![vec![batch]]).unwrap();
+    let ctx = SessionContext::new_with_config(SessionConfig::new().with_target_partitions(1));
+    ctx.register_table(TABLE_NAME, Arc::new(mem_table)).unwrap();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let result = runtime.block_on(async {
+        let data_frame = ctx.sql(sql).await.unwrap();
+        let schema = Arc::clone(data_frame.schema().inner());
+        let batches = data_frame.collect().await.unwrap();
+        concat_batches(&schema, &batches).unwrap()
+    });
+    OwnedTable::try_from(result).unwrap()
+}
+
+/// Plan, prove and verify the query over the table at `offset`
+fn proven_result(
+    sql: &str,
+    table: OwnedTable<Curve25519Scalar>,
+    offset: usize,
+) -> OwnedTable<Curve25519Scalar> {
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        TableRef::from_names(None, TABLE_NAME),
+        table,
+        offset,
+        (),
+    );
+    let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+    let plans = sql_to_proof_plans(&statements, &accessor, &ConfigOptions::default()).unwrap();
+    let [plan] = plans.as_slice() else {
+        panic!("a single query should have a single plan");
+    };
+    VerifiableQueryResult::<InnerProductProof>::new(plan, &accessor, &(), &[])
+        .unwrap()
+        .verify(plan, &accessor, &(), &[])
+        .unwrap()
+        .table
+}
+
+/// A value of a result, normalized so that values which only differ in representation are equal
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Value<S> {
+    Boolean(bool),
+    /// A number times `10^scale` for the largest scale of the column in either result
+    Number(S),
+    VarChar(String),
+    /// A timestamp in nanoseconds
+    Timestamp(i128),
+}
+
+/// The scale of a numeric column, or `None` if the column isn't numeric
+fn numeric_scale<S: Scalar>(column: &OwnedColumn<S>) -> Option<i8> {
+    match column {
+        OwnedColumn::Uint8(_)
+        | OwnedColumn::TinyInt(_)
+        | OwnedColumn::SmallInt(_)
+        | OwnedColumn::Int(_)
+        | OwnedColumn::BigInt(_)
+        | OwnedColumn::Int128(_)
+        | OwnedColumn::Scalar(_) => Some(0),
+        OwnedColumn::Decimal75(_, scale, _) => Some(*scale),
+        _ => None,
+    }
+}
+
+/// The values of a column, with any numbers brought to `scale`
+fn normalized_column<S: Scalar>(column: &OwnedColumn<S>, scale: i8) -> Vec<Value<S>> {
+    let factor =
+        (numeric_scale(column).unwrap_or(0)..scale).fold(S::ONE, |factor, _| factor * S::TEN);
+    let number = |value: S| Value::Number(value * factor);
+    match column {
+        OwnedColumn::Boolean(col) => col.iter().copied().map(Value::Boolean).collect(),
+        OwnedColumn::Uint8(col) => col.iter().map(|&value| number(value.into())).collect(),
+        OwnedColumn::TinyInt(col) => col.iter().map(|&value| number(value.into())).collect(),
+        OwnedColumn::SmallInt(col) => col.iter().map(|&value| number(value.into())).collect(),
+        OwnedColumn::Int(col) => col.iter().map(|&value| number(value.into())).collect(),
+        OwnedColumn::BigInt(col) => col.iter().map(|&value| number(value.into())).collect(),
+        OwnedColumn::Int128(col) => col.iter().map(|&value| number(value.into())).collect(),
+        OwnedColumn::Decimal75(_, _, col) | OwnedColumn::Scalar(col) => {
+            col.iter().copied().map(number).collect()
+        }
+        OwnedColumn::VarChar(col) => col.iter().cloned().map(Value::VarChar).collect(),
+        OwnedColumn::TimestampTZ(time_unit, _, col) => {
+            let nanos_per_unit = match time_unit {
+                PoSQLTimeUnit::Second => 1_000_000_000,
+                PoSQLTimeUnit::Millisecond => 1_000_000,
+                PoSQLTimeUnit::Microsecond => 1_000,
+                PoSQLTimeUnit::Nanosecond => 1,
+            };
+            col.iter()
+                .map(|&value| Value::Timestamp(i128::from(value) * nanos_per_unit))
+                .collect()
+        }
+        _ => panic!("unexpected result column {column:?}"),
+    }
+}
+
+/// Check that the results have the same columns and rows, ignoring the order of the rows
+/// unless `ordered`
+fn check_results_match<S: Scalar>(
+    proven: &OwnedTable<S>,
+    expected: &OwnedTable<S>,
+    ordered: bool,
+) -> Result<(), TestCaseError> {
+    prop_assert_eq!(
+        proven.column_names().collect::<Vec<_>>(),
+        expected.column_names().collect::<Vec<_>>()
+    );
+    let scales: Vec<i8> = proven
+        .inner_table()
+        .values()
+        .zip(expected.inner_table().values())
+        .map(|(proven_column, expected_column)| {
+            numeric_scale(proven_column)
+                .max(numeric_scale(expected_column))
+                .unwrap_or(0)
+        })
+        .collect();
+    let rows = |table: &OwnedTable<S>| {
+        let columns: Vec<Vec<Value<S>>> = table
+            .inner_table()
+            .values()
+            .zip(&scales)
+            .map(|(column, &scale)| normalized_column(column, scale))
+            .collect();
+        let mut rows: Vec<Vec<Value<S>>> = (0..table.num_rows())
+            .map(|i| columns.iter().map(|column| column[i].clone()).collect())
+            .collect();
+        if !ordered {
+            rows.sort();
+        }
+        rows
+    };
+    prop_assert_eq!(rows(proven), rows(expected));
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+    #[test]
+    fn we_can_prove_the_same_results_as_datafusion(
+        rows in prop::collection::vec(row(), 0..=8),
+        offset in 0_usize..3,
+        query in query(),
+    ) {
+        let sql = query.to_string();
+        let table = owned_table_of_rows(&rows);
+        let expected = datafusion_result(&sql, table.clone());
+        let proven = proven_result(&sql, table, offset);
+        check_results_match(&proven, &expected, query.is_ordered())?;
+    }
+}
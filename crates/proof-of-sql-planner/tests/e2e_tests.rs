@@ -15,12 +15,16 @@ use proof_of_sql::{
     proof_primitive::dory::{
         DoryScalar, DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
     },
-    sql::proof::VerifiableQueryResult,
+    sql::{proof::VerifiableQueryResult, ExpressionLimits, ExpressionLimitsError},
 };
 use proof_of_sql_planner::{
-    postprocessing::PostprocessingStep, sql_to_proof_plans, sql_to_proof_plans_with_postprocessing,
+    postprocessing::PostprocessingStep, sql_to_proof_plans, sql_to_proof_plans_with_limits,
+    sql_to_proof_plans_with_postprocessing, PlannerError, GROUPING_ID_COLUMN_NAME,
+};
+use sqlparser::{
+    dialect::{Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect},
+    parser::Parser,
 };
-use sqlparser::{dialect::GenericDialect, parser::Parser};
 
 /// Get a new `TableTestAccessor` with the provided tables
 fn new_test_accessor<'a, CP: CommitmentEvaluationProof>(
@@ -318,6 +322,46 @@ fn test_slicing_limit() {
     );
 }
 
+/// Test filters over derived tables with a LIMIT, which can not be pushed down into the scan
+#[test]
+fn test_nested_filters_over_limits() {
+    let alloc = Bump::new();
+    let sql = "SELECT * FROM (
+        SELECT * FROM (SELECT * FROM products WHERE price > 200 LIMIT 4) AS affordable
+        WHERE price < 1000 LIMIT 2
+    ) AS cheap WHERE id > 102;";
+
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "products") => table(
+            vec![
+                borrowed_int("id", [101, 102, 103, 104, 105], &alloc),
+                borrowed_varchar("name", ["Laptop", "Phone", "Tablet", "Monitor", "Keyboard"], &alloc),
+                borrowed_int("price", [1200, 800, 500, 300, 100], &alloc),
+            ]
+        )
+    };
+
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![owned_table([
+        int("id", [103]),
+        varchar("name", ["Tablet"]),
+        int("price", [500]),
+    ])];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
 /// Test GROUP BY queries
 #[test]
 fn test_group_by() {
@@ -405,6 +449,188 @@ fn test_group_by() {
     );
 }
 
+/// Test GROUP BY queries with distinct counts, with and without a filter
+#[test]
+fn test_group_by_with_distinct_counts() {
+    let alloc = Bump::new();
+    let sql = "select human, count(1) as num_cats, count(distinct age) as distinct_ages, count(distinct age) filter (where id > 3) as late_ages from cats group by human;
+    select human, count(1) as num_cats, count(distinct age) as late_ages from cats where id > 3 group by human;
+    select human, count(1) as num_cats, count(distinct age) filter (where age = 2) as ages_of_two from cats group by human;
+    select count(1) as num_cats, count(distinct age) filter (where id = 0) as no_ages from cats;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3, 4, 5], &alloc),
+                borrowed_varchar("human", ["Cassia", "Cassia", "Cassia", "Gretta", "Gretta"], &alloc),
+                borrowed_bigint("age", [2_i64, 3, 2, 3, 5], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            varchar("human", ["Cassia", "Gretta"]),
+            bigint("num_cats", [3_i64, 2]),
+            bigint("distinct_ages", [2_i64, 2]),
+            bigint("late_ages", [0_i64, 2]),
+        ]),
+        // The same as `late_ages` above, but without the groups that no row passes the filter in
+        owned_table([
+            varchar("human", ["Gretta"]),
+            bigint("num_cats", [2_i64]),
+            bigint("late_ages", [2_i64]),
+        ]),
+        owned_table([
+            varchar("human", ["Cassia", "Gretta"]),
+            bigint("num_cats", [3_i64, 2]),
+            bigint("ages_of_two", [1_i64, 0]),
+        ]),
+        owned_table([bigint("num_cats", [5_i64]), bigint("no_ages", [0_i64])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Distinct counts can be combined with sums and listed in any order, since the aggregation is
+/// reordered for `GroupByExec` and restored in postprocessing
+#[test]
+fn test_group_by_with_distinct_counts_and_sums() {
+    let alloc = Bump::new();
+    let sql = "select region, count(distinct user_id) as users, sum(amount) as total from sales group by region;
+    select region, count(distinct user_id) as users, sum(amount) as total from sales where amount > 5 group by region;
+    select count(distinct user_id) as users, count(1) as num_sales from sales;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "sales") => table(
+            vec![
+                borrowed_varchar("region", ["east", "west", "east", "north", "east", "west"], &alloc),
+                borrowed_bigint("user_id", [1_i64, 3, 1, 4, 2, 3], &alloc),
+                borrowed_bigint("amount", [10_i64, 7, 20, 1, 5, 8], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            varchar("region", ["east", "north", "west"]),
+            bigint("users", [2_i64, 1, 1]),
+            bigint("total", [35_i64, 1, 15]),
+        ]),
+        // `north` has no row passing the filter, so it has no group
+        owned_table([
+            varchar("region", ["east", "west"]),
+            bigint("users", [1_i64, 1]),
+            bigint("total", [30_i64, 15]),
+        ]),
+        owned_table([bigint("users", [4_i64]), bigint("num_sales", [6_i64])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    posql_end_to_end_test_with_postprocessing::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Distinct counts need to be of supported columns and can't be used with grouping sets
+#[test]
+fn we_cannot_plan_unsupported_distinct_counts() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3], &alloc),
+                borrowed_varchar("human", ["Cassia", "Cassia", "Gretta"], &alloc),
+            ]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    for sql in [
+        "select count(1) as num_cats, count(distinct human) as humans from cats",
+        "select human, count(1) as num_cats, count(distinct id) as ids from cats group by grouping sets ((human), ())",
+    ] {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert!(sql_to_proof_plans(&statements, &accessor, &config).is_err());
+    }
+}
+
+/// Grouping sets should give the union of the separate GROUP BYs, told apart by the grouping id
+#[test]
+fn test_grouping_sets() {
+    let alloc = Bump::new();
+    let sql = "select human, age, sum(weight) as total_weight, count(1) as num_cats from cats group by grouping sets ((human), (age), ());
+    select human, sum(weight) as total_weight, count(1) as num_cats from cats group by human;
+    select age, sum(weight) as total_weight, count(1) as num_cats from cats group by age;
+    select sum(weight) as total_weight, count(1) as num_cats from cats;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_int("id", [1, 2, 3, 4, 5], &alloc),
+                borrowed_varchar("human", ["Cassia", "Cassia", "Cassia", "Gretta", "Gretta"], &alloc),
+                borrowed_bigint("age", [2_i64, 3, 2, 3, 5], &alloc),
+                borrowed_decimal75("weight", 3, 1, [145, 75, 20, 45, 55], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            varchar("human", ["Cassia", "Gretta", "", "", "", ""]),
+            bigint("age", [0_i64, 0, 2, 3, 5, 0]),
+            decimal75("total_weight", 3, 1, [240, 100, 165, 120, 55, 340]),
+            bigint("num_cats", [3_i64, 2, 2, 2, 1, 5]),
+            bigint(GROUPING_ID_COLUMN_NAME, [1_i64, 1, 2, 2, 2, 3]),
+        ]),
+        owned_table([
+            varchar("human", ["Cassia", "Gretta"]),
+            decimal75("total_weight", 3, 1, [240, 100]),
+            bigint("num_cats", [3_i64, 2]),
+        ]),
+        owned_table([
+            bigint("age", [2_i64, 3, 5]),
+            decimal75("total_weight", 3, 1, [165, 120, 55]),
+            bigint("num_cats", [2_i64, 2, 1]),
+        ]),
+        owned_table([
+            decimal75("total_weight", 3, 1, [340]),
+            bigint("num_cats", [5_i64]),
+        ]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
 #[test]
 fn test_coin() {
     let alloc = Bump::new();
@@ -498,6 +724,175 @@ fn test_group_by_with_postprocessing() {
     );
 }
 
+/// Test GROUP BY queries with a computed group expression
+#[test]
+fn test_group_by_with_a_computed_group_expr() {
+    let alloc = Bump::new();
+    let sql = "select x % 10 as bucket, sum(y) as total, count(1) as num_readings from readings group by x % 10;
+    select x % 10 as bucket, count(1) as num_readings from readings where y > 6 group by x % 10;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "readings") => table(
+            vec![
+                borrowed_bigint("x", [1, 11, -3, 21, 2, 12, -13], &alloc),
+                borrowed_bigint("y", [10, 20, 9, 31, 5, 6, 7], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("bucket", [-3_i64, 1, 2]),
+            bigint("total", [16_i64, 61, 11]),
+            bigint("num_readings", [2_i64, 3, 2]),
+        ]),
+        owned_table([
+            bigint("bucket", [-3_i64, 1]),
+            bigint("num_readings", [2_i64, 3]),
+        ]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Test averages, which are proven as sums and counts and divided in postprocessing
+#[test]
+fn test_average() {
+    let alloc = Bump::new();
+    let sql = "select x % 10 as bucket, avg(y) from readings group by x % 10;
+    select x % 10 as bucket, sum(y) as total, count(1) as num_readings, avg(y) as mean from readings group by x % 10;
+    select avg(y) as mean, count(1) as num_readings from readings where x > 0;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "readings") => table(
+            vec![
+                borrowed_bigint("x", [1, 11, -3, 21, 2, 12, -13], &alloc),
+                borrowed_bigint("y", [10, 20, 9, 31, 5, 6, -7], &alloc),
+            ]
+        )
+    };
+    // Averages of integers are truncated towards zero
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("bucket", [-3_i64, 1, 2]),
+            bigint("AVG(readings.y)", [1_i64, 20, 5]),
+        ]),
+        owned_table([
+            bigint("bucket", [-3_i64, 1, 2]),
+            bigint("total", [2_i64, 61, 11]),
+            bigint("num_readings", [2_i64, 3, 2]),
+            bigint("mean", [1_i64, 20, 5]),
+        ]),
+        owned_table([bigint("mean", [14_i64]), bigint("num_readings", [5_i64])]),
+    ];
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    posql_end_to_end_test_with_postprocessing::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// The average of an empty selection is a division by zero, since there are no `NULL`s
+#[test]
+fn test_average_of_an_empty_selection() {
+    let alloc = Bump::new();
+    let sql = "select avg(y) as mean from readings where x > 100;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "readings") => table(
+            vec![
+                borrowed_bigint("x", [1, 11, -3], &alloc),
+                borrowed_bigint("y", [10, 20, 9], &alloc),
+            ]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let accessor: TableTestAccessor<DynamicDoryEvaluationProof> =
+        new_test_accessor(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+    let plans_with_postprocessing =
+        sql_to_proof_plans_with_postprocessing(&statements, &accessor, &config).unwrap();
+    let plan = plans_with_postprocessing[0].plan();
+    let res = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        plan,
+        &accessor,
+        &&prover_setup,
+        &[],
+    )
+    .unwrap();
+    let raw_table = res
+        .verify(plan, &accessor, &&verifier_setup, &[])
+        .unwrap()
+        .table;
+    assert!(plans_with_postprocessing[0]
+        .postprocessing()
+        .unwrap()
+        .apply(raw_table)
+        .is_err());
+}
+
+/// The planner takes statements parsed with any `sqlparser` dialect
+#[test]
+fn test_statements_parsed_with_other_dialects() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_int("id", [1, 2, 3, 4, 5], &alloc),
+                borrowed_varchar("human", ["Cassia", "Cassia", "Cassia", "Gretta", "Gretta"], &alloc),
+            ]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor: TableTestAccessor<DynamicDoryEvaluationProof> =
+        new_test_accessor(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    let plan = |dialect: &dyn Dialect, sql: &str| {
+        let statements = Parser::parse_sql(dialect, sql).unwrap();
+        sql_to_proof_plans(&statements, &accessor, &config).unwrap()
+    };
+    let expected_plans = plan(
+        &GenericDialect {},
+        "select cast(id as bigint) as id, human from cats where id > 2;",
+    );
+    assert_eq!(
+        plan(
+            &PostgreSqlDialect {},
+            "select id::bigint as id, human from cats where id > 2;"
+        ),
+        expected_plans
+    );
+    assert_eq!(
+        plan(
+            &MySqlDialect {},
+            "select cast(`id` as bigint) as `id`, `human` from `cats` where `id` > 2;"
+        ),
+        expected_plans
+    );
+    // Backticks don't quote identifiers in PostgreSQL
+    assert!(Parser::parse_sql(&PostgreSqlDialect {}, "select `id` from cats;").is_err());
+}
+
 #[test]
 fn test_join() {
     let alloc = Bump::new();
@@ -547,3 +942,689 @@ fn test_join() {
         &[],
     );
 }
+
+/// `NOT EXISTS` and `NOT IN` subqueries are both planned as provable anti-joins
+#[test]
+fn test_anti_join() {
+    let alloc = Bump::new();
+    let sql = "SELECT id, name FROM cats WHERE NOT EXISTS (SELECT 1 FROM adoptions WHERE adoptions.id = cats.id);
+    SELECT id, name FROM cats WHERE id NOT IN (SELECT id FROM adoptions);
+    SELECT id, name FROM cats WHERE id NOT IN (SELECT id FROM adoptions WHERE fee > 100);
+    SELECT id, name FROM cats WHERE id NOT IN (SELECT id FROM adoptions WHERE fee > 1000);";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3, 2, 5], &alloc),
+                borrowed_varchar("name", ["Chloe", "Margaret", "Katy", "Lucy", "Prudence"], &alloc),
+            ]
+        ),
+        TableRef::from_names(None, "adoptions") => table(
+            vec![
+                borrowed_bigint("id", [5_i64, 1, 7], &alloc),
+                borrowed_bigint("fee", [50_i64, 150, 200], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [2_i64, 3, 2]),
+            varchar("name", ["Margaret", "Katy", "Lucy"]),
+        ]),
+        owned_table([
+            bigint("id", [2_i64, 3, 2]),
+            varchar("name", ["Margaret", "Katy", "Lucy"]),
+        ]),
+        owned_table([
+            bigint("id", [2_i64, 3, 2, 5]),
+            varchar("name", ["Margaret", "Katy", "Lucy", "Prudence"]),
+        ]),
+        // Every row is kept when the subquery is empty
+        owned_table([
+            bigint("id", [1_i64, 2, 3, 2, 5]),
+            varchar("name", ["Chloe", "Margaret", "Katy", "Lucy", "Prudence"]),
+        ]),
+    ];
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// `DISTINCT ON` keeps the row with the extreme tiebreaker of each key, the earliest one on ties
+#[test]
+fn test_distinct_on() {
+    let alloc = Bump::new();
+    let sql = "select distinct on (sensor) sensor, ts, reading from readings order by sensor, ts desc;
+    select distinct on (sensor) sensor, reading, ts from readings where reading > 10 order by sensor, ts;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "readings") => table(
+            vec![
+                borrowed_varchar("sensor", ["a", "b", "a", "b", "a", "c"], &alloc),
+                borrowed_bigint("ts", [1_i64, 4, 3, 4, 3, 2], &alloc),
+                borrowed_bigint("reading", [10_i64, 20, 30, 40, 50, 60], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            varchar("sensor", ["a", "b", "c"]),
+            bigint("ts", [3_i64, 4, 2]),
+            bigint("reading", [30_i64, 20, 60]),
+        ]),
+        owned_table([
+            varchar("sensor", ["a", "b", "c"]),
+            bigint("reading", [30_i64, 20, 60]),
+            bigint("ts", [3_i64, 4, 2]),
+        ]),
+    ];
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+#[test]
+fn we_cannot_plan_distinct_on_without_a_selected_tiebreaker() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "readings") => table(
+            vec![
+                borrowed_varchar("sensor", ["a", "b", "a"], &alloc),
+                borrowed_bigint("ts", [1_i64, 4, 3], &alloc),
+                borrowed_bigint("reading", [10_i64, 20, 30], &alloc),
+            ]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    for sql in [
+        "select distinct on (sensor) sensor, reading from readings order by sensor, ts desc",
+        "select distinct on (sensor) sensor, ts from readings",
+        "select distinct on (sensor) sensor, ts, reading from readings order by sensor, ts, reading",
+        "select sensor, ts, reading from (select sensor, ts, reading, row_number() over (partition by sensor order by ts desc) as rn from readings) as r where rn = 1",
+    ] {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert!(sql_to_proof_plans(&statements, &accessor, &config).is_err());
+    }
+}
+
+/// Plans exceeding the expression limits are rejected by the planner
+#[test]
+fn we_cannot_plan_queries_exceeding_expression_limits() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_int("id", [1, 2, 3, 4, 5], &alloc),
+                borrowed_varchar("name", ["Chloe", "Margaret", "Katy", "Lucy", "Prudence"], &alloc),
+                borrowed_tinyint("age", [13_i8, 2, 0, 4, 4], &alloc),
+            ]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    let statements = Parser::parse_sql(
+        &GenericDialect {},
+        "select id, name from cats where age + 1 > 2 and id < 5",
+    )
+    .unwrap();
+
+    // The default limits are generous enough for ordinary queries
+    assert!(sql_to_proof_plans_with_limits(
+        &statements,
+        &accessor,
+        &config,
+        &ExpressionLimits::default()
+    )
+    .is_ok());
+
+    let limits = ExpressionLimits {
+        max_depth: 2,
+        ..ExpressionLimits::default()
+    };
+    assert!(matches!(
+        sql_to_proof_plans_with_limits(&statements, &accessor, &config, &limits),
+        Err(PlannerError::ExpressionLimitsError {
+            source: ExpressionLimitsError::DepthExceeded { max_depth: 2 }
+        })
+    ));
+
+    let limits = ExpressionLimits {
+        max_result_columns: 1,
+        ..ExpressionLimits::default()
+    };
+    assert!(matches!(
+        sql_to_proof_plans_with_limits(&statements, &accessor, &config, &limits),
+        Err(PlannerError::ExpressionLimitsError {
+            source: ExpressionLimitsError::ResultColumnsExceeded {
+                num_columns: 2,
+                max_result_columns: 1
+            }
+        })
+    ));
+}
+
+/// Test the conditional functions `IF` and `IIF`, taking both branches
+#[test]
+fn test_if() {
+    let alloc = Bump::new();
+    let sql = "select id, if(is_indoor, age, id) as res from cats;
+    select name from cats where iif(is_indoor, name, nickname) = 'Katy';";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3, 4], &alloc),
+                borrowed_varchar("name", ["Chloe", "Margaret", "Katy", "Lucy"], &alloc),
+                borrowed_varchar("nickname", ["Clo", "Katy", "Kit", "Lu"], &alloc),
+                borrowed_bigint("age", [13_i64, 2, 0, 4], &alloc),
+                borrowed_boolean("is_indoor", [true, false, true, false], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [1_i64, 2, 3, 4]),
+            bigint("res", [13_i64, 2, 0, 4]),
+        ]),
+        owned_table([varchar("name", ["Margaret", "Katy"])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Test substring search with `POSITION`, `STRPOS` and `INSTR` on literals
+#[test]
+fn test_position() {
+    let alloc = Bump::new();
+    let sql = "select id, position('ar' in 'Margaret') as found, strpos('cat', 'x') as missing, instr('cat', '') as empty from cats;
+    select id from cats where age = strpos('xaaaa', 'aa');";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3], &alloc),
+                borrowed_varchar("name", ["Chloe", "Margaret", "Katy"], &alloc),
+                borrowed_bigint("age", [13_i64, 2, 0], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [1_i64, 2, 3]),
+            int("found", [2; 3]),
+            int("missing", [0; 3]),
+            int("empty", [1; 3]),
+        ]),
+        owned_table([bigint("id", [2_i64])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Substring search on varchar columns can not be proven since strings are committed as hashes
+#[test]
+fn we_cannot_plan_position_on_a_column() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![borrowed_varchar("name", ["Chloe", "Margaret", "Katy"], &alloc)]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    for sql in [
+        "select position('ar' in name) as p from cats",
+        "select name from cats where strpos(name, 'a') = 2",
+    ] {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert!(matches!(
+            sql_to_proof_plans(&statements, &accessor, &config),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+}
+
+/// Test extracting delimited fields with `SPLIT_PART` on literals
+#[test]
+fn test_split_part() {
+    let alloc = Bump::new();
+    let sql = "select id, split_part('2024-05-17', '-', 2) as month, split_part('a.b', '.', 3) as beyond, split_part('abc', ',', 1) as whole, split_part('a::b::c', '::', -1) as last from cats;
+    select id from cats where name = split_part('Chloe,Katy', ',', 2);";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3], &alloc),
+                borrowed_varchar("name", ["Chloe", "Margaret", "Katy"], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [1_i64, 2, 3]),
+            varchar("month", ["05"; 3]),
+            varchar("beyond", [""; 3]),
+            varchar("whole", ["abc"; 3]),
+            varchar("last", ["c"; 3]),
+        ]),
+        owned_table([bigint("id", [3_i64])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// `SPLIT_PART` can not be proven on varchar columns since strings are committed as hashes, and
+/// requires a nonempty delimiter and a nonzero field position
+#[test]
+fn we_cannot_plan_split_part_on_a_column_or_with_invalid_arguments() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![borrowed_varchar("name", ["Chloe", "Margaret", "Katy"], &alloc)]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    for sql in [
+        "select split_part(name, 'a', 1) as p from cats",
+        "select name from cats where split_part(name, 'a', 2) = 'rg'",
+    ] {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert!(matches!(
+            sql_to_proof_plans(&statements, &accessor, &config),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+    for sql in [
+        "select split_part('a,b', '', 1) as p from cats",
+        "select split_part('a,b', ',', 0) as p from cats",
+    ] {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert!(sql_to_proof_plans(&statements, &accessor, &config).is_err());
+    }
+}
+
+/// Test arithmetic mixing integer and decimal operands without casts
+#[test]
+fn test_mixed_integer_and_decimal_arithmetic() {
+    let alloc = Bump::new();
+    let sql = "select id, amount * quantity as total, amount + discount as adjusted, discount - amount as negated from orders;
+    select id from orders where amount * quantity > 30;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "orders") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3], &alloc),
+                borrowed_decimal75("amount", 12, 2, [1999_i64, 250, 100], &alloc),
+                borrowed_bigint("quantity", [2_i64, 3, -1], &alloc),
+                borrowed_tinyint("discount", [0_i8, -1, 5], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [1_i64, 2, 3]),
+            decimal75("total", 33, 2, [3998_i64, 750, -100]),
+            decimal75("adjusted", 13, 2, [1999_i64, 150, 600]),
+            decimal75("negated", 13, 2, [-1999_i64, -350, 400]),
+        ]),
+        owned_table([bigint("id", [1_i64])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Test bucketing with `ROUND_TO`, rounding midpoints away from zero
+#[test]
+fn test_round_to() {
+    let alloc = Bump::new();
+    let sql = "select id, round_to(price, 10) as p10, round_to(price, 100) as p100, round_to(price, -3) as p3, round_to(149, 100) as folded from items;
+    select id from items where round_to(price, 100) = -200;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "items") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3, 4, 5], &alloc),
+                borrowed_bigint("price", [-150_i64, -149, 5, 150, 1234], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [1_i64, 2, 3, 4, 5]),
+            bigint("p10", [-150_i64, -150, 10, 150, 1230]),
+            bigint("p100", [-200_i64, -100, 0, 200, 1200]),
+            bigint("p3", [-150_i64, -150, 6, 150, 1233]),
+            bigint("folded", [100_i64; 5]),
+        ]),
+        owned_table([bigint("id", [1_i64])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// `ROUND_TO` can only be proven with a nonzero integer literal step
+#[test]
+fn we_cannot_plan_round_to_with_an_invalid_step() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "items") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3], &alloc),
+                borrowed_bigint("price", [10_i64, 20, 30], &alloc),
+            ]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    for sql in [
+        "select round_to(price, 0) as p from items",
+        "select round_to(price, id) as p from items",
+    ] {
+        let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+        assert!(sql_to_proof_plans(&statements, &accessor, &config).is_err());
+    }
+}
+
+/// Test integer square roots with `ISQRT`, including perfect squares and zero
+#[test]
+fn test_isqrt() {
+    let alloc = Bump::new();
+    let sql = "select id, isqrt(area) as side, isqrt(17) as folded from plots;
+    select id from plots where isqrt(area) = 3;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "plots") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3, 4, 5], &alloc),
+                borrowed_bigint("area", [0_i64, 8, 9, 15, 1_000_000], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [1_i64, 2, 3, 4, 5]),
+            bigint("side", [0_i64, 2, 3, 3, 1000]),
+            bigint("folded", [4_i64; 5]),
+        ]),
+        owned_table([bigint("id", [3_i64, 4])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Test modular powers with `POW_MOD`, including negative bases and a zero exponent
+#[test]
+fn test_pow_mod() {
+    let alloc = Bump::new();
+    let sql = "select id, pow_mod(token, 3, 11) as cube, pow_mod(token, 0, 11) as one, pow_mod(2, 10, 1000) as folded from keys;
+    select id from keys where pow_mod(token, 2, 7) = 2;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "keys") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3, 4, 5], &alloc),
+                borrowed_bigint("token", [-4_i64, 0, 3, 5, 10], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            bigint("id", [1_i64, 2, 3, 4, 5]),
+            bigint("cube", [2_i64, 0, 5, 4, 10]),
+            bigint("one", [1_i64; 5]),
+            bigint("folded", [24_i64; 5]),
+        ]),
+        owned_table([bigint("id", [1_i64, 3, 5])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[],
+    );
+}
+
+/// Test common table expressions, including one feeding a filter and one referenced twice
+#[test]
+fn test_cte() {
+    let alloc = Bump::new();
+    let sql = "with adults as (select id, name, age from cats where age > 1) select name from adults where id < 5;
+    with c(cat_id, cat_name) as (select id, name from cats) select cat_name from c where cat_id > 3;
+    with young as (select id, name from cats where age < 3) select name from young union all select name from young;
+    with recent as (select * from cats where id > $1::bigint) select count(1) as num_cats from recent;
+    with recent as (select * from cats where id > $1::bigint) select human, count(1) as num_cats from recent group by human;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_bigint("id", [1_i64, 2, 3, 4, 5], &alloc),
+                borrowed_varchar("name", ["Chloe", "Margaret", "Katy", "Lucy", "Prudence"], &alloc),
+                borrowed_varchar("human", ["Cassia", "Cassia", "Cassia", "Gretta", "Gretta"], &alloc),
+                borrowed_tinyint("age", [13_i8, 2, 0, 4, 4], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([varchar("name", ["Chloe", "Margaret", "Lucy"])]),
+        owned_table([varchar("cat_name", ["Lucy", "Prudence"])]),
+        owned_table([varchar("name", ["Margaret", "Katy", "Margaret", "Katy"])]),
+        owned_table([bigint("num_cats", [3_i64])]),
+        owned_table([
+            varchar("human", ["Cassia", "Gretta"]),
+            bigint("num_cats", [1_i64, 2]),
+        ]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+        sql,
+        &tables,
+        &expected_results,
+        &prover_setup,
+        &verifier_setup,
+        &[LiteralValue::BigInt(2)],
+    );
+}
+
+/// Recursive common table expressions are rejected by the planner
+#[test]
+fn we_cannot_plan_recursive_ctes() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![borrowed_bigint("id", [1_i64, 2, 3], &alloc)]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    let statements = Parser::parse_sql(
+        &GenericDialect {},
+        "with recursive ids as (select id from cats union all select id + 1 from ids where id < 10) select id from ids",
+    )
+    .unwrap();
+    assert!(matches!(
+        sql_to_proof_plans(&statements, &accessor, &config),
+        Err(PlannerError::RecursiveCteNotSupported)
+    ));
+}
+
+/// Aggregations over derived tables are flattened into the same plans as the equivalent flat queries
+#[test]
+fn test_aggregate_over_derived_table() {
+    let alloc = Bump::new();
+    let nested_sql = "select sum(w) as total_weight, count(1) as num_cats from (select weight as w from cats where human = 'Cassia');
+    select human, sum(w) as total_weight, count(1) as num_cats from (select human, weight as w from cats where id > 1) group by human;
+    select sum(double_weight) as total, count(1) as n from (select 2 * weight as double_weight from cats where id > 1) s;";
+    let flat_sql = "select sum(weight) as total_weight, count(1) as num_cats from cats where human = 'Cassia';
+    select human, sum(weight) as total_weight, count(1) as num_cats from cats where id > 1 group by human;
+    select sum(2 * weight) as total, count(1) as n from cats where id > 1;";
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![
+                borrowed_int("id", [1, 2, 3, 4, 5], &alloc),
+                borrowed_varchar("human", ["Cassia", "Cassia", "Cassia", "Gretta", "Gretta"], &alloc),
+                borrowed_decimal75("weight", 3, 1, [145, 75, 20, 45, 55], &alloc),
+            ]
+        )
+    };
+    let expected_results: Vec<OwnedTable<DoryScalar>> = vec![
+        owned_table([
+            decimal75("total_weight", 3, 1, [240]),
+            bigint("num_cats", [3_i64]),
+        ]),
+        owned_table([
+            varchar("human", ["Cassia", "Gretta"]),
+            decimal75("total_weight", 3, 1, [95, 100]),
+            bigint("num_cats", [2_i64, 2]),
+        ]),
+        owned_table([decimal75("total", 24, 1, [390]), bigint("n", [4_i64])]),
+    ];
+
+    // Create public parameters for DynamicDoryEvaluationProof
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    // The nested and flat queries have identical plans
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    let nested_statements = Parser::parse_sql(&GenericDialect {}, nested_sql).unwrap();
+    let flat_statements = Parser::parse_sql(&GenericDialect {}, flat_sql).unwrap();
+    assert_eq!(
+        sql_to_proof_plans(&nested_statements, &accessor, &config).unwrap(),
+        sql_to_proof_plans(&flat_statements, &accessor, &config).unwrap()
+    );
+
+    for sql in [nested_sql, flat_sql] {
+        posql_end_to_end_test::<DynamicDoryEvaluationProof>(
+            sql,
+            &tables,
+            &expected_results,
+            &prover_setup,
+            &verifier_setup,
+            &[],
+        );
+    }
+}
+
+/// Aggregations over derived tables with a LIMIT are not flattened
+#[test]
+fn we_cannot_flatten_aggregations_over_derived_tables_with_limits() {
+    let alloc = Bump::new();
+    let tables: IndexMap<TableRef, Table<DoryScalar>> = indexmap! {
+        TableRef::from_names(None, "cats") => table(
+            vec![borrowed_bigint("weight", [1_i64, 2, 3], &alloc)]
+        )
+    };
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let accessor = new_test_accessor::<DynamicDoryEvaluationProof>(&tables, &prover_setup);
+    let config = ConfigOptions::default();
+    let statements = Parser::parse_sql(
+        &GenericDialect {},
+        "select sum(w) as total, count(1) as n from (select weight as w from cats limit 2)",
+    )
+    .unwrap();
+    assert!(matches!(
+        sql_to_proof_plans(&statements, &accessor, &config),
+        Err(PlannerError::UnsupportedLogicalPlan { .. })
+    ));
+}
@@ -0,0 +1,35 @@
+//! Micro-benchmark of batch inversion of scalars using Montgomery's trick.
+//!
+//! ```bash
+//! cargo bench -p proof-of-sql --features "bench" --bench bench_batch_inversion
+//! ```
+#![expect(missing_docs, clippy::missing_docs_in_private_items)]
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+use proof_of_sql::{bench_util::batch_inversion, proof_primitive::dory::DoryScalar};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SIZES: [usize; 2] = [1_000, 1_000_000];
+
+fn bench_batch_inversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_inversion");
+    let mut rng = StdRng::seed_from_u64(0);
+    for size in SIZES {
+        let values: Vec<DoryScalar> = (0..size)
+            .map(|_| DoryScalar::from(rng.gen::<i64>()))
+            .collect();
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &values, |b, values| {
+            b.iter_batched_ref(
+                || values.clone(),
+                |values| batch_inversion(black_box(values)),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch_inversion);
+criterion_main!(benches);
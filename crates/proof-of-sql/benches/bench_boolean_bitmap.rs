@@ -0,0 +1,110 @@
+//! Micro-benchmark of committing to the boolean columns of the `BooleanFilter` and `Coin` queries,
+//! unpacked as `bool` slices and packed as [`BooleanBitmap`]s.
+//!
+//! `BooleanFilter` filters on the boolean table column `c`, and `Coin` casts the booleans
+//! `to_address = $1` and `from_address = $1` to integers. Before benchmarking, this checks that
+//! both representations give the same commitments, and prints how many bytes each holds.
+//!
+//! ```bash
+//! cargo bench -p proof-of-sql --no-default-features --features "bench" --bench bench_boolean_bitmap
+//! ```
+#![expect(missing_docs, clippy::missing_docs_in_private_items)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use proof_of_sql::{
+    base::{
+        commitment::{Commitment, CommittableColumn},
+        database::BooleanBitmap,
+    },
+    proof_primitive::dory::{
+        DoryCommitment, DoryProverPublicSetup, DynamicDoryCommitment, ProverSetup, PublicParameters,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const NUM_ROWS: [usize; 3] = [256, 1024, 4096];
+
+/// The boolean columns of each query, where `Coin` matches an address in about 1 of 100 rows
+fn generate_columns(rng: &mut StdRng, num_rows: usize) -> Vec<(&'static str, Vec<Vec<bool>>)> {
+    let mut column = |probability: f64| -> Vec<bool> {
+        (0..num_rows).map(|_| rng.gen_bool(probability)).collect()
+    };
+    vec![
+        ("boolean_filter", vec![column(0.5)]),
+        ("coin", vec![column(0.01), column(0.01)]),
+    ]
+}
+
+fn bench_boolean_bitmap(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let public_parameters = PublicParameters::test_rand(7, &mut rng);
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 6);
+    let mut group = c.benchmark_group("boolean_bitmap");
+    for num_rows in NUM_ROWS {
+        for (query, data) in generate_columns(&mut rng, num_rows) {
+            let bitmaps: Vec<BooleanBitmap> = data
+                .iter()
+                .map(|column| BooleanBitmap::from(column.as_slice()))
+                .collect();
+            let unpacked: Vec<CommittableColumn> = data
+                .iter()
+                .map(|column| CommittableColumn::Boolean(column.as_slice()))
+                .collect();
+            let packed: Vec<CommittableColumn> = bitmaps
+                .iter()
+                .map(CommittableColumn::BooleanBitmap)
+                .collect();
+
+            assert_eq!(
+                DoryCommitment::compute_commitments(&unpacked, 0, &dory_prover_setup),
+                DoryCommitment::compute_commitments(&packed, 0, &dory_prover_setup),
+            );
+            assert_eq!(
+                DynamicDoryCommitment::compute_commitments(&unpacked, 0, &&prover_setup),
+                DynamicDoryCommitment::compute_commitments(&packed, 0, &&prover_setup),
+            );
+            let unpacked_bytes: usize = data.iter().map(Vec::len).sum();
+            let packed_bytes: usize = bitmaps
+                .iter()
+                .map(|bitmap| size_of_val(bitmap.words()))
+                .sum();
+            println!(
+                "{query}/{num_rows}: {unpacked_bytes} bytes unpacked, {packed_bytes} bytes packed"
+            );
+
+            group.throughput(Throughput::Elements((data.len() * num_rows) as u64));
+            for (representation, columns) in [("unpacked", &unpacked), ("packed", &packed)] {
+                group.bench_with_input(
+                    BenchmarkId::new(format!("{query}/dory/{representation}"), num_rows),
+                    columns,
+                    |b, columns| {
+                        b.iter(|| {
+                            DoryCommitment::compute_commitments(
+                                black_box(columns),
+                                0,
+                                &dory_prover_setup,
+                            )
+                        });
+                    },
+                );
+                group.bench_with_input(
+                    BenchmarkId::new(format!("{query}/dynamic_dory/{representation}"), num_rows),
+                    columns,
+                    |b, columns| {
+                        b.iter(|| {
+                            DynamicDoryCommitment::compute_commitments(
+                                black_box(columns),
+                                0,
+                                &&prover_setup,
+                            )
+                        });
+                    },
+                );
+            }
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_boolean_bitmap);
+criterion_main!(benches);
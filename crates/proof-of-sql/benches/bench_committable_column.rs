@@ -0,0 +1,113 @@
+//! Micro-benchmark of converting owned columns of each type into committable columns.
+//!
+//! ```bash
+//! cargo bench -p proof-of-sql --features "bench" --bench bench_committable_column
+//! ```
+#![expect(missing_docs, clippy::missing_docs_in_private_items)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use proof_of_sql::{
+    base::{
+        commitment::CommittableColumn,
+        database::OwnedColumn,
+        math::decimal::Precision,
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    },
+    proof_primitive::dory::DoryScalar,
+};
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+
+const NUM_ROWS: usize = 1 << 16;
+
+fn random_scalars(rng: &mut StdRng) -> Vec<DoryScalar> {
+    (0..NUM_ROWS)
+        .map(|_| DoryScalar::from(rng.gen::<i64>()))
+        .collect()
+}
+
+/// Generate one random column of every type, labelled by the type name.
+fn generate_columns(rng: &mut StdRng) -> Vec<(&'static str, OwnedColumn<DoryScalar>)> {
+    vec![
+        (
+            "boolean",
+            OwnedColumn::Boolean((0..NUM_ROWS).map(|_| rng.gen()).collect()),
+        ),
+        (
+            "uint8",
+            OwnedColumn::Uint8((0..NUM_ROWS).map(|_| rng.gen()).collect()),
+        ),
+        (
+            "tinyint",
+            OwnedColumn::TinyInt((0..NUM_ROWS).map(|_| rng.gen()).collect()),
+        ),
+        (
+            "smallint",
+            OwnedColumn::SmallInt((0..NUM_ROWS).map(|_| rng.gen()).collect()),
+        ),
+        (
+            "int",
+            OwnedColumn::Int((0..NUM_ROWS).map(|_| rng.gen()).collect()),
+        ),
+        (
+            "bigint",
+            OwnedColumn::BigInt((0..NUM_ROWS).map(|_| rng.gen()).collect()),
+        ),
+        (
+            "int128",
+            OwnedColumn::Int128((0..NUM_ROWS).map(|_| rng.gen()).collect()),
+        ),
+        (
+            "decimal75",
+            OwnedColumn::Decimal75(Precision::new(75).unwrap(), 10, random_scalars(rng)),
+        ),
+        ("scalar", OwnedColumn::Scalar(random_scalars(rng))),
+        (
+            "varchar",
+            OwnedColumn::VarChar(
+                (0..NUM_ROWS)
+                    .map(|_| {
+                        let len = rng.gen_range(0..32);
+                        (&mut *rng)
+                            .sample_iter(&Alphanumeric)
+                            .take(len)
+                            .map(char::from)
+                            .collect()
+                    })
+                    .collect(),
+            ),
+        ),
+        (
+            "varbinary",
+            OwnedColumn::VarBinary(
+                (0..NUM_ROWS)
+                    .map(|_| {
+                        let len = rng.gen_range(0..32);
+                        (0..len).map(|_| rng.gen()).collect()
+                    })
+                    .collect(),
+            ),
+        ),
+        (
+            "timestamptz",
+            OwnedColumn::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+                (0..NUM_ROWS).map(|_| rng.gen()).collect(),
+            ),
+        ),
+    ]
+}
+
+fn bench_committable_column(c: &mut Criterion) {
+    let mut group = c.benchmark_group("committable_column_from_owned_column");
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    let mut rng = StdRng::seed_from_u64(0);
+    for (name, column) in generate_columns(&mut rng) {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &column, |b, column| {
+            b.iter(|| CommittableColumn::from(black_box(column)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_committable_column);
+criterion_main!(benches);
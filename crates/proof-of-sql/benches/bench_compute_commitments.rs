@@ -0,0 +1,64 @@
+//! Micro-benchmark of computing column commitments for each commitment scheme at small sizes.
+//!
+//! Without the `blitzar` feature this measures the CPU implementation, which is the
+//! intended use:
+//!
+//! ```bash
+//! cargo bench -p proof-of-sql --no-default-features --features "bench" --bench bench_compute_commitments
+//! ```
+#![expect(missing_docs, clippy::missing_docs_in_private_items)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use proof_of_sql::{
+    base::commitment::{Commitment, CommittableColumn},
+    proof_primitive::dory::{
+        DoryCommitment, DoryProverPublicSetup, DynamicDoryCommitment, ProverSetup, PublicParameters,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const NUM_COLUMNS: usize = 4;
+const NUM_ROWS: [usize; 3] = [16, 256, 1024];
+
+fn bench_compute_commitments(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let public_parameters = PublicParameters::test_rand(6, &mut rng);
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 5);
+    let mut group = c.benchmark_group("compute_commitments");
+    for num_rows in NUM_ROWS {
+        let data: Vec<Vec<i64>> = (0..NUM_COLUMNS)
+            .map(|_| (0..num_rows).map(|_| rng.gen()).collect())
+            .collect();
+        let columns: Vec<CommittableColumn> = data
+            .iter()
+            .map(|column| CommittableColumn::from(column.as_slice()))
+            .collect();
+        group.throughput(Throughput::Elements((NUM_COLUMNS * num_rows) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("dory", num_rows),
+            &columns,
+            |b, columns| {
+                b.iter(|| {
+                    DoryCommitment::compute_commitments(black_box(columns), 0, &dory_prover_setup)
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("dynamic_dory", num_rows),
+            &columns,
+            |b, columns| {
+                b.iter(|| {
+                    DynamicDoryCommitment::compute_commitments(
+                        black_box(columns),
+                        0,
+                        &&prover_setup,
+                    )
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_compute_commitments);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+//! Micro-benchmark of filtering columns by a selection vector and of gathering them by row indexes
+//! at varying selectivity.
+//!
+//! ```bash
+//! cargo bench -p proof-of-sql --features "bench" --bench bench_filter_columns
+//! ```
+#![expect(missing_docs, clippy::missing_docs_in_private_items)]
+use bumpalo::Bump;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use proof_of_sql::{
+    base::database::{
+        filter_util::{filter_columns, gather_columns},
+        Column,
+    },
+    proof_primitive::dory::DoryScalar,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const NUM_ROWS: usize = 1 << 16;
+/// Per mille of rows which are kept, i.e. 0.1%, 50% and 99%.
+const SELECTIVITIES: [u32; 3] = [1, 500, 990];
+
+fn bench_filter_columns(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let bigints: Vec<i64> = (0..NUM_ROWS).map(|_| rng.gen()).collect();
+    let int128s: Vec<i128> = (0..NUM_ROWS).map(|_| rng.gen()).collect();
+    let scalars: Vec<DoryScalar> = bigints.iter().copied().map(DoryScalar::from).collect();
+    let strings: Vec<String> = bigints.iter().map(ToString::to_string).collect();
+    let strs: Vec<&str> = strings.iter().map(String::as_str).collect();
+    let str_scalars: Vec<DoryScalar> = strs.iter().copied().map(DoryScalar::from).collect();
+    let columns = [
+        Column::BigInt(&bigints),
+        Column::Int128(&int128s),
+        Column::Scalar(&scalars),
+        Column::VarChar((&strs, &str_scalars)),
+    ];
+    let selections: Vec<(u32, Vec<bool>)> = SELECTIVITIES
+        .into_iter()
+        .map(|selectivity| {
+            let selection = (0..NUM_ROWS)
+                .map(|_| rng.gen_range(0..1000) < selectivity)
+                .collect();
+            (selectivity, selection)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("filter_columns");
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    for (selectivity, selection) in &selections {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}%", f64::from(*selectivity) / 10.0)),
+            selection,
+            |b, selection| {
+                let mut alloc = Bump::new();
+                b.iter(|| {
+                    black_box(filter_columns(&alloc, &columns, black_box(selection)));
+                    alloc.reset();
+                });
+            },
+        );
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("gather_columns");
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    for (selectivity, selection) in &selections {
+        let indexes: Vec<usize> = selection
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &selected)| selected.then_some(i))
+            .collect();
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}%", f64::from(*selectivity) / 10.0)),
+            &indexes,
+            |b, indexes| {
+                let mut alloc = Bump::new();
+                b.iter(|| {
+                    black_box(gather_columns(&alloc, &columns, black_box(indexes)));
+                    alloc.reset();
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_filter_columns);
+criterion_main!(benches);
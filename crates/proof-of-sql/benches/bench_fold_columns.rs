@@ -0,0 +1,44 @@
+//! Micro-benchmark of folding several columns into one with powers of a challenge.
+//!
+//! ```bash
+//! cargo bench -p proof-of-sql --features "bench" --bench bench_fold_columns
+//! ```
+#![expect(missing_docs, clippy::missing_docs_in_private_items)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use proof_of_sql::{
+    base::scalar::Scalar, bench_util::fold_columns, proof_primitive::dory::DoryScalar,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const NUM_ROWS: usize = 1 << 16;
+const NUM_COLUMNS: [usize; 3] = [1, 8, 32];
+
+fn bench_fold_columns(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fold_columns");
+    let mut rng = StdRng::seed_from_u64(0);
+    let mul = DoryScalar::from(rng.gen::<i64>());
+    let beta = DoryScalar::from(rng.gen::<i64>());
+    for num_columns in NUM_COLUMNS {
+        let columns: Vec<Vec<DoryScalar>> = (0..num_columns)
+            .map(|_| {
+                (0..NUM_ROWS)
+                    .map(|_| DoryScalar::from(rng.gen::<i64>()))
+                    .collect()
+            })
+            .collect();
+        let columns: Vec<&[DoryScalar]> = columns.iter().map(Vec::as_slice).collect();
+        group.throughput(Throughput::Elements((num_columns * NUM_ROWS) as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(num_columns),
+            &columns,
+            |b, columns| {
+                let mut res = vec![DoryScalar::ZERO; NUM_ROWS];
+                b.iter(|| fold_columns(&mut res, mul, beta, black_box(columns)));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_fold_columns);
+criterion_main!(benches);
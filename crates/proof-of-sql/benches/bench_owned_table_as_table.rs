@@ -0,0 +1,60 @@
+//! Micro-benchmark of borrowing an `OwnedTable` with varchar columns as a `Table`, comparing a
+//! rebuild of every column with the cached view of `OwnedTable::as_table`.
+//!
+//! ```bash
+//! cargo bench -p proof-of-sql --features "bench" --bench bench_owned_table_as_table
+//! ```
+#![expect(missing_docs, clippy::missing_docs_in_private_items)]
+use bumpalo::Bump;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use proof_of_sql::{
+    base::database::{
+        owned_table_utility::{bigint, owned_table, varchar},
+        Column, OwnedTable, Table,
+    },
+    proof_primitive::dory::DoryScalar,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const NUM_ROWS: usize = 1 << 16;
+
+fn bench_owned_table_as_table(c: &mut Criterion) {
+    let mut group = c.benchmark_group("owned_table_as_table");
+    group.throughput(Throughput::Elements(NUM_ROWS as u64));
+    let mut rng = StdRng::seed_from_u64(0);
+    let owned: OwnedTable<DoryScalar> = owned_table([
+        bigint(
+            "a",
+            (0..NUM_ROWS).map(|_| rng.gen::<i64>()).collect::<Vec<_>>(),
+        ),
+        varchar(
+            "b",
+            (0..NUM_ROWS)
+                .map(|_| format!("{:x}", rng.gen::<u64>()))
+                .collect::<Vec<_>>(),
+        ),
+    ]);
+    group.bench_function("from_owned_column", |b| {
+        let mut alloc = Bump::new();
+        b.iter(|| {
+            black_box(
+                Table::try_from_iter(owned.inner_table().iter().map(|(name, column)| {
+                    (name.clone(), Column::from_owned_column(column, &alloc))
+                }))
+                .unwrap(),
+            );
+            alloc.reset();
+        });
+    });
+    group.bench_function("as_table", |b| {
+        let mut alloc = Bump::new();
+        b.iter(|| {
+            black_box(owned.as_table(&alloc));
+            alloc.reset();
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_owned_table_as_table);
+criterion_main!(benches);
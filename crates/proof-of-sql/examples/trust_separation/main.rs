@@ -0,0 +1,106 @@
+#![doc = include_str!("README.md")]
+use ark_std::test_rng;
+use proof_of_sql::{
+    base::{
+        commitment::{QueryCommitments, TableCommitment},
+        database::{
+            owned_table_utility::{bigint, owned_table, varchar},
+            OwnedTable, OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+    },
+    proof_primitive::dory::{
+        DoryScalar, DynamicDoryCommitment, DynamicDoryEvaluationProof, ProverSetup,
+        PublicParameters, VerifierSetup,
+    },
+    sql::{
+        parse::QueryExpr,
+        proof::{check_data_matches_commitments, VerifiableQueryResult},
+    },
+};
+
+/// The table of the data owner, with `b` replaced by `b_values`
+fn table(b_values: [&str; 4]) -> OwnedTable<DoryScalar> {
+    owned_table([bigint("a", [1, 2, 3, 2]), varchar("b", b_values)])
+}
+
+/// # Panics
+///
+/// - Will panic if the query string cannot be parsed in `QueryExpr::try_new`.
+/// - Will panic if the query expression creation fails.
+/// - Will panic if the refreshed data of the prover doesn't match the published commitments.
+/// - Will panic if the proof cannot be created or verified.
+fn main() {
+    #[cfg(feature = "blitzar")]
+    proof_of_sql::base::commitment::init_backend();
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let table_ref = TableRef::new("sxt", "table");
+
+    // Data owner: commit to the table and publish the commitments
+    let owner_table = table(["hi", "hello", "there", "world"]);
+    let published_commitments: QueryCommitments<DynamicDoryCommitment> =
+        QueryCommitments::from_iter([(
+            table_ref.clone(),
+            TableCommitment::from_owned_table_with_offset(&owner_table, 0, &&prover_setup),
+        )]);
+    println!("Data owner: published commitments to {table_ref}");
+
+    // Prover: check its copy of the data against the published commitments before proving
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        table_ref.clone(),
+        table(["hi", "hello", "there", "stale"]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT b FROM table WHERE a = 2".parse().unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let report = check_data_matches_commitments(
+        query.proof_expr(),
+        &accessor,
+        &published_commitments,
+        &&prover_setup,
+    );
+    for check in report.mismatched_columns() {
+        println!(
+            "Prover: column {} doesn't match: {:?}",
+            check.column_ref.column_id(),
+            check.status
+        );
+    }
+
+    // Prover: refresh its copy of the data and prove the query
+    accessor.add_table(table_ref.clone(), owner_table, 0);
+    let report = check_data_matches_commitments(
+        query.proof_expr(),
+        &accessor,
+        &published_commitments,
+        &&prover_setup,
+    );
+    assert!(report.is_consistent());
+    println!("Prover: refreshed data matches the published commitments");
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+        &[],
+    )
+    .unwrap();
+
+    // Verifier: check the proof against the published commitments only
+    let result = verifiable_result
+        .verify(
+            query.proof_expr(),
+            &published_commitments,
+            &&verifier_setup,
+            &[],
+        )
+        .unwrap();
+    println!("Verifier: valid proof!");
+    println!("Query result: {:?}", result.table);
+}
@@ -61,6 +61,11 @@ impl<C: Commitment> TableCommitment<C> {
             }) => {
                 panic!("RecordBatches cannot have duplicate identifiers")
             }
+            Err(AppendTableCommitmentError::AppendColumnCommitments {
+                source: AppendColumnCommitmentsError::PolicyViolations { .. },
+            }) => {
+                panic!("rows are appended without validation")
+            }
             Err(AppendTableCommitmentError::AppendColumnCommitments {
                 source: AppendColumnCommitmentsError::Mismatch { source: e },
             }) => Err(e)?,
@@ -95,6 +100,8 @@ impl<C: Commitment> TableCommitment<C> {
             Err(TableCommitmentFromColumnsError::DuplicateIdents { .. }) => {
                 panic!("RecordBatches cannot have duplicate identifiers")
             }
+            Err(TableCommitmentFromColumnsError::InvalidIdent { source }) => Err(source)?,
+            Err(TableCommitmentFromColumnsError::UnsupportedColumnType { source }) => Err(source)?,
         }
     }
 }
@@ -1,5 +1,5 @@
 use super::arrow_array_to_column_conversion::ArrowArrayToColumnConversionError;
-use crate::base::commitment::ColumnCommitmentsMismatch;
+use crate::base::commitment::{ColumnCommitmentsMismatch, InvalidIdent, UnsupportedColumnType};
 use snafu::Snafu;
 
 /// Errors that can occur when trying to create or extend a [`TableCommitment`] from a record batch.
@@ -11,6 +11,18 @@ pub enum RecordBatchToColumnsError {
         /// The underlying source error
         source: ArrowArrayToColumnConversionError,
     },
+    /// The record batch has a field name that is not a valid ident
+    #[snafu(transparent)]
+    InvalidIdent {
+        /// The underlying source error
+        source: InvalidIdent,
+    },
+    /// The record batch has a column the commitment scheme does not support
+    #[snafu(transparent)]
+    UnsupportedColumnType {
+        /// The underlying source error
+        source: UnsupportedColumnType,
+    },
 }
 
 /// Errors that can occur when attempting to append a record batch to a [`TableCommitment`].
@@ -0,0 +1,422 @@
+//! Deserialization of untrusted bincode data that allocates in proportion to the input.
+//!
+//! Length prefixes are read before the data they describe, so derived `Deserialize`
+//! implementations may reserve memory for a declared length that the input does not back up.
+//! E.g. `IndexMap` reserves space for as many entries as a map declares, and bincode allocates
+//! a buffer of the declared length before reading the bytes of a string. A crafted length prefix
+//! of a few gigabytes therefore exhausts the memory before any validation happens.
+//!
+//! [`BoundedDeserializer`] caps the lengths it reports to visitors for preallocation, and reads
+//! strings and byte arrays one byte at a time, so memory only grows with the data actually read.
+//! Since bincode encodes strings and byte arrays exactly like sequences of bytes, this must only
+//! wrap bincode deserializers.
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+/// The largest number of elements a visitor may reserve space for before reading them.
+const MAX_PREALLOCATED_LENGTH: usize = 4096;
+
+/// Decode a `T` from `bytes` encoded with bincode's legacy configuration.
+///
+/// Unlike [`bincode::serde::decode_from_slice`], this allocates memory in proportion to the
+/// length of `bytes` rather than to the lengths declared in them.
+pub(crate) fn decode_bounded_from_slice<T: DeserializeOwned>(
+    bytes: &[u8],
+) -> Result<T, bincode::error::DecodeError> {
+    bincode::serde::decode_from_slice::<Bounded<T>, _>(bytes, bincode::config::legacy())
+        .map(|(Bounded(value), _)| value)
+}
+
+/// A `T` deserialized through a [`BoundedDeserializer`].
+struct Bounded<T>(T);
+
+impl<'de, T: de::Deserialize<'de>> de::Deserialize<'de> for Bounded<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(BoundedDeserializer {
+            inner: deserializer,
+        })
+        .map(Bounded)
+    }
+}
+
+/// A [`Deserializer`] which never lets visitors reserve memory for more than
+/// [`MAX_PREALLOCATED_LENGTH`] elements ahead of reading them.
+struct BoundedDeserializer<D> {
+    inner: D,
+}
+
+macro_rules! forward_deserialize_methods {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+                self.inner.$method($($arg,)* BoundedVisitor { inner: visitor })
+            }
+        )*
+    };
+}
+
+macro_rules! forward_deserialize_bytes_methods {
+    ($($method:ident($kind:ident)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.inner.deserialize_seq(BytesVisitor {
+                    inner: visitor,
+                    kind: BytesKind::$kind,
+                })
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for BoundedDeserializer<D> {
+    type Error = D::Error;
+
+    forward_deserialize_methods!(
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+
+    forward_deserialize_bytes_methods!(
+        deserialize_str(String),
+        deserialize_string(String),
+        deserialize_bytes(Bytes),
+        deserialize_byte_buf(Bytes),
+    );
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// What a [`BytesVisitor`] hands the bytes it reads to its visitor as
+enum BytesKind {
+    String,
+    Bytes,
+}
+
+/// A [`Visitor`] which reads a string or byte array as the sequence of bytes it is encoded as.
+struct BytesVisitor<V> {
+    inner: V,
+    kind: BytesKind,
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for BytesVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(preallocated_length(seq.size_hint()));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        match self.kind {
+            BytesKind::String => {
+                let string = String::from_utf8(bytes)
+                    .map_err(|_| de::Error::custom("invalid UTF-8 in string"))?;
+                self.inner.visit_string(string)
+            }
+            BytesKind::Bytes => self.inner.visit_byte_buf(bytes),
+        }
+    }
+}
+
+/// The number of elements to reserve space for given the length declared by the input
+fn preallocated_length(size_hint: Option<usize>) -> usize {
+    size_hint.unwrap_or(0).min(MAX_PREALLOCATED_LENGTH)
+}
+
+/// A [`Visitor`] which wraps any nested data in bounded wrappers.
+struct BoundedVisitor<V> {
+    inner: V,
+}
+
+macro_rules! forward_visit_methods {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for BoundedVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit_methods!(
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    );
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner.visit_some(BoundedDeserializer {
+            inner: deserializer,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.inner.visit_newtype_struct(BoundedDeserializer {
+            inner: deserializer,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_seq(BoundedAccess { inner: seq })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_map(BoundedAccess { inner: map })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_enum(BoundedAccess { inner: data })
+    }
+}
+
+/// A [`DeserializeSeed`] which wraps the [`Deserializer`] it is given in a [`BoundedDeserializer`].
+struct BoundedSeed<T> {
+    inner: T,
+}
+
+impl<'de, T: DeserializeSeed<'de>> DeserializeSeed<'de> for BoundedSeed<T> {
+    type Value = T::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner.deserialize(BoundedDeserializer {
+            inner: deserializer,
+        })
+    }
+}
+
+/// Wrapper for the [`SeqAccess`], [`MapAccess`], [`EnumAccess`] and [`VariantAccess`] handed to
+/// visitors, which caps the lengths they report.
+struct BoundedAccess<A> {
+    inner: A,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for BoundedAccess<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.inner.next_element_seed(BoundedSeed { inner: seed })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(preallocated_length(SeqAccess::size_hint(&self.inner)))
+    }
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for BoundedAccess<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        self.inner.next_key_seed(BoundedSeed { inner: seed })
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.next_value_seed(BoundedSeed { inner: seed })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(preallocated_length(MapAccess::size_hint(&self.inner)))
+    }
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for BoundedAccess<A> {
+    type Error = A::Error;
+    type Variant = BoundedAccess<A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        self.inner
+            .variant_seed(BoundedSeed { inner: seed })
+            .map(|(value, variant)| (value, BoundedAccess { inner: variant }))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for BoundedAccess<A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        self.inner.newtype_variant_seed(BoundedSeed { inner: seed })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .tuple_variant(len, BoundedVisitor { inner: visitor })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .struct_variant(fields, BoundedVisitor { inner: visitor })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{
+        database::{owned_table_utility::*, OwnedTable},
+        map::IndexMap,
+        scalar::test_scalar::TestScalar,
+    };
+    use alloc::{string::ToString, vec};
+    use proptest::prelude::*;
+
+    fn encode(value: &impl serde::Serialize) -> Vec<u8> {
+        bincode::serde::encode_to_vec(value, bincode::config::legacy()).unwrap()
+    }
+
+    fn sample_table() -> OwnedTable<TestScalar> {
+        owned_table([
+            bigint("a", [1_i64, -2, 3]),
+            varchar("b", ["x", "", "zzz"]),
+            boolean("c", [true, false, true]),
+        ])
+    }
+
+    #[test]
+    fn we_can_decode_what_bincode_encodes() {
+        let table = sample_table();
+        assert_eq!(
+            decode_bounded_from_slice::<OwnedTable<TestScalar>>(&encode(&table)).unwrap(),
+            table
+        );
+        let strings = vec!["abc".to_string(), String::new(), "ünïcödé".to_string()];
+        assert_eq!(
+            decode_bounded_from_slice::<Vec<String>>(&encode(&strings)).unwrap(),
+            strings
+        );
+        let map: IndexMap<String, u64> = [("a".to_string(), 1), ("b".to_string(), 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            decode_bounded_from_slice::<IndexMap<String, u64>>(&encode(&map)).unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn we_cannot_decode_a_length_prefix_the_input_does_not_back_up() {
+        let mut bytes = encode(&(u64::MAX >> 1));
+        bytes.extend_from_slice(b"abc");
+        assert!(decode_bounded_from_slice::<String>(&bytes).is_err());
+        assert!(decode_bounded_from_slice::<Vec<u8>>(&bytes).is_err());
+        assert!(decode_bounded_from_slice::<Vec<u64>>(&bytes).is_err());
+        assert!(decode_bounded_from_slice::<IndexMap<String, u64>>(&bytes).is_err());
+    }
+
+    #[test]
+    fn we_cannot_decode_a_string_with_invalid_utf8() {
+        let bytes = encode(&vec![0xff_u8, 0xfe]);
+        assert!(decode_bounded_from_slice::<String>(&bytes).is_err());
+        assert_eq!(
+            decode_bounded_from_slice::<Vec<u8>>(&bytes).unwrap(),
+            vec![0xff, 0xfe]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn we_can_decode_mutated_tables_without_panicking(
+            mutations in proptest::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+        ) {
+            let mut bytes = encode(&sample_table());
+            for (index, byte) in mutations {
+                *index.get_mut(&mut bytes) = byte;
+            }
+            let _ = decode_bounded_from_slice::<OwnedTable<TestScalar>>(&bytes);
+        }
+    }
+}
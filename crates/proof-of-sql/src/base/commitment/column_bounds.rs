@@ -1,4 +1,5 @@
 use super::committable_column::CommittableColumn;
+use crate::base::database::LiteralValue;
 use alloc::boxed::Box;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
@@ -147,6 +148,19 @@ where
             Bounds::Bounded(inner) | Bounds::Sharp(inner) => inner.surrounds(value),
         }
     }
+
+    /// Returns true if the bounds of another collection are within these bounds.
+    ///
+    /// Empty bounds are within any bounds.
+    /// A `true` result implies that every value of the other source collection is within these bounds.
+    pub fn surrounds_bounds(&self, other: &Bounds<T>) -> bool {
+        match other {
+            Bounds::Empty => true,
+            Bounds::Bounded(inner) | Bounds::Sharp(inner) => {
+                self.surrounds(inner.min()) && self.surrounds(inner.max())
+            }
+        }
+    }
 }
 
 impl<'a, T> FromIterator<&'a T> for Bounds<T>
@@ -184,6 +198,11 @@ where
     }
 }
 
+/// The bounds of a column of `num_rows` copies of `value`.
+fn constant_bounds<T: Ord + Copy>(value: T, num_rows: usize) -> Bounds<T> {
+    Bounds::from_iter(&[value][..num_rows.min(1)])
+}
+
 /// Columns with different [`ColumnBounds`] variants cannot operate with each other.
 #[derive(Debug, Snafu)]
 #[snafu(display(
@@ -231,11 +250,15 @@ impl ColumnBounds {
             CommittableColumn::SmallInt(ints) => ColumnBounds::SmallInt(Bounds::from_iter(*ints)),
             CommittableColumn::Int(ints) => ColumnBounds::Int(Bounds::from_iter(*ints)),
             CommittableColumn::BigInt(ints) => ColumnBounds::BigInt(Bounds::from_iter(*ints)),
+            CommittableColumn::DeltaEncoded(deltas) => {
+                ColumnBounds::BigInt(Bounds::from_iter(&deltas.to_vec()))
+            }
             CommittableColumn::Int128(ints) => ColumnBounds::Int128(Bounds::from_iter(*ints)),
             CommittableColumn::TimestampTZ(_, _, times) => {
                 ColumnBounds::TimestampTZ(Bounds::from_iter(*times))
             }
             CommittableColumn::Boolean(_)
+            | CommittableColumn::BooleanBitmap(_)
             | CommittableColumn::Decimal75(_, _, _)
             | CommittableColumn::Scalar(_)
             | CommittableColumn::VarBinary(_)
@@ -243,6 +266,34 @@ impl ColumnBounds {
         }
     }
 
+    /// Construct the [`ColumnBounds`] of a column of `num_rows` copies of `value`.
+    ///
+    /// If the column variant has order, the bounds are sharp at the value, or empty if there are no
+    /// rows.
+    #[must_use]
+    pub fn from_constant(value: &LiteralValue, num_rows: usize) -> ColumnBounds {
+        match value {
+            LiteralValue::Uint8(value) => ColumnBounds::Uint8(constant_bounds(*value, num_rows)),
+            LiteralValue::TinyInt(value) => {
+                ColumnBounds::TinyInt(constant_bounds(*value, num_rows))
+            }
+            LiteralValue::SmallInt(value) => {
+                ColumnBounds::SmallInt(constant_bounds(*value, num_rows))
+            }
+            LiteralValue::Int(value) => ColumnBounds::Int(constant_bounds(*value, num_rows)),
+            LiteralValue::BigInt(value) => ColumnBounds::BigInt(constant_bounds(*value, num_rows)),
+            LiteralValue::Int128(value) => ColumnBounds::Int128(constant_bounds(*value, num_rows)),
+            LiteralValue::TimeStampTZ(_, _, time) => {
+                ColumnBounds::TimestampTZ(constant_bounds(*time, num_rows))
+            }
+            LiteralValue::Boolean(_)
+            | LiteralValue::Decimal75(_, _, _)
+            | LiteralValue::Scalar(_)
+            | LiteralValue::VarBinary(_)
+            | LiteralValue::VarChar(_) => ColumnBounds::NoOrder,
+        }
+    }
+
     /// Combine two [`ColumnBounds`] as if their source collections are being unioned.
     ///
     /// Can error if the two values do not share the same [`ColumnBounds`] variant.
@@ -311,13 +362,47 @@ impl ColumnBounds {
             }),
         }
     }
+
+    /// Returns true if the bounds of another collection are within these bounds.
+    ///
+    /// Columns without order are always within each other's bounds.
+    ///
+    /// Can error if the two values do not share the same [`ColumnBounds`] variant.
+    pub fn try_surrounds(&self, other: &Self) -> Result<bool, ColumnBoundsMismatch> {
+        match (self, other) {
+            (ColumnBounds::NoOrder, ColumnBounds::NoOrder) => Ok(true),
+            (ColumnBounds::Uint8(bounds_a), ColumnBounds::Uint8(bounds_b)) => {
+                Ok(bounds_a.surrounds_bounds(bounds_b))
+            }
+            (ColumnBounds::TinyInt(bounds_a), ColumnBounds::TinyInt(bounds_b)) => {
+                Ok(bounds_a.surrounds_bounds(bounds_b))
+            }
+            (ColumnBounds::SmallInt(bounds_a), ColumnBounds::SmallInt(bounds_b)) => {
+                Ok(bounds_a.surrounds_bounds(bounds_b))
+            }
+            (ColumnBounds::Int(bounds_a), ColumnBounds::Int(bounds_b)) => {
+                Ok(bounds_a.surrounds_bounds(bounds_b))
+            }
+            (ColumnBounds::BigInt(bounds_a), ColumnBounds::BigInt(bounds_b))
+            | (ColumnBounds::TimestampTZ(bounds_a), ColumnBounds::TimestampTZ(bounds_b)) => {
+                Ok(bounds_a.surrounds_bounds(bounds_b))
+            }
+            (ColumnBounds::Int128(bounds_a), ColumnBounds::Int128(bounds_b)) => {
+                Ok(bounds_a.surrounds_bounds(bounds_b))
+            }
+            (_, _) => Err(ColumnBoundsMismatch {
+                bounds_a: Box::new(*self),
+                bounds_b: Box::new(*other),
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::base::{
-        database::OwnedColumn,
+        database::{DeltaEncodedColumn, OwnedColumn},
         math::decimal::Precision,
         posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
         scalar::test_scalar::TestScalar,
@@ -381,6 +466,20 @@ mod tests {
         assert!(!bounded.surrounds(&5));
     }
 
+    #[test]
+    fn we_can_determine_if_bounds_surround_other_bounds() {
+        let sharp = Bounds::Sharp(BoundsInner { min: 2, max: 4 });
+        assert!(sharp.surrounds_bounds(&Bounds::Empty));
+        assert!(sharp.surrounds_bounds(&Bounds::sharp(2, 4).unwrap()));
+        assert!(sharp.surrounds_bounds(&Bounds::bounded(3, 3).unwrap()));
+        assert!(!sharp.surrounds_bounds(&Bounds::sharp(1, 3).unwrap()));
+        assert!(!sharp.surrounds_bounds(&Bounds::sharp(3, 5).unwrap()));
+        assert!(!sharp.surrounds_bounds(&Bounds::bounded(0, 6).unwrap()));
+
+        assert!(Bounds::<i32>::Empty.surrounds_bounds(&Bounds::Empty));
+        assert!(!Bounds::Empty.surrounds_bounds(&Bounds::sharp(0, 0).unwrap()));
+    }
+
     #[test]
     fn we_can_union_sharp_bounds() {
         let bounds_a = Bounds::Sharp(BoundsInner { min: 3, max: 6 });
@@ -551,6 +650,13 @@ mod tests {
             ColumnBounds::BigInt(Bounds::Sharp(BoundsInner { min: 0, max: 3 }))
         );
 
+        // The bounds of a delta encoded column are those of its values, not of its deltas
+        let delta_encoded_column = DeltaEncodedColumn::try_from_values(&[1, 2, 3, 1, 0]).unwrap();
+        let committable_delta_encoded_column = CommittableColumn::from(&delta_encoded_column);
+        let delta_encoded_column_bounds =
+            ColumnBounds::from_column(&committable_delta_encoded_column);
+        assert_eq!(delta_encoded_column_bounds, bigint_column_bounds);
+
         let int128_column = OwnedColumn::<TestScalar>::Int128([1, 2, 3, 1, 0].to_vec());
         let committable_int128_column = CommittableColumn::from(&int128_column);
         let int128_column_bounds = ColumnBounds::from_column(&committable_int128_column);
@@ -709,6 +815,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_determine_if_column_bounds_surround_other_column_bounds() {
+        let enforced = ColumnBounds::BigInt(Bounds::bounded(0, 100).unwrap());
+        assert!(enforced
+            .try_surrounds(&ColumnBounds::BigInt(Bounds::sharp(0, 100).unwrap()))
+            .unwrap());
+        assert!(enforced
+            .try_surrounds(&ColumnBounds::BigInt(Bounds::Empty))
+            .unwrap());
+        assert!(!enforced
+            .try_surrounds(&ColumnBounds::BigInt(Bounds::sharp(-1, 50).unwrap()))
+            .unwrap());
+
+        let enforced = ColumnBounds::TimestampTZ(Bounds::bounded(0, 100).unwrap());
+        assert!(!enforced
+            .try_surrounds(&ColumnBounds::TimestampTZ(Bounds::sharp(50, 101).unwrap()))
+            .unwrap());
+
+        assert!(ColumnBounds::NoOrder
+            .try_surrounds(&ColumnBounds::NoOrder)
+            .unwrap());
+        assert!(enforced.try_surrounds(&ColumnBounds::NoOrder).is_err());
+        assert!(enforced
+            .try_surrounds(&ColumnBounds::BigInt(Bounds::Empty))
+            .is_err());
+    }
+
     #[test]
     fn we_cannot_difference_mismatched_column_bounds() {
         let no_order = ColumnBounds::NoOrder;
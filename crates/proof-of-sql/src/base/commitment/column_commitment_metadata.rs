@@ -1,6 +1,10 @@
 use super::{column_bounds::BoundsInner, committable_column::CommittableColumn, ColumnBounds};
 use crate::base::database::ColumnType;
-use core::fmt::Debug;
+use alloc::string::String;
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    ops::Range,
+};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 
@@ -25,13 +29,195 @@ pub struct ColumnCommitmentMetadataMismatch {
     datatype_b: ColumnType,
 }
 
+/// Rows violate the policies declared in a column's [`ColumnCommitmentMetadata`].
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The column has a different type than the committed column.
+    #[snafu(display("column of type {actual} cannot be appended to column of type {expected}"))]
+    TypeMismatch {
+        /// The type of the committed column
+        expected: ColumnType,
+        /// The type of the new column
+        actual: ColumnType,
+    },
+    /// The column has values outside of the enforced bounds.
+    #[snafu(display("column with bounds {actual:?} violates the enforced bounds {enforced:?}"))]
+    OutOfEnforcedBounds {
+        /// The bounds the column must stay within
+        enforced: ColumnBounds,
+        /// The bounds of the new column
+        actual: ColumnBounds,
+    },
+}
+
+/// Where a column commitment comes from: the commitment scheme, its setup and the committed rows.
+///
+/// Provenance isn't checked by the verifier.
+/// It exists to diagnose commitments that unexpectedly fail to combine or verify.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentProvenance {
+    scheme: String,
+    setup_digest: Option<[u8; 32]>,
+    row_range: Range<usize>,
+}
+
+impl CommitmentProvenance {
+    /// Construct a new [`CommitmentProvenance`].
+    #[must_use]
+    pub fn new(scheme: String, setup_digest: Option<[u8; 32]>, row_range: Range<usize>) -> Self {
+        Self {
+            scheme,
+            setup_digest,
+            row_range,
+        }
+    }
+
+    /// The identifier of the commitment scheme, see [`Commitment::scheme_identifier`].
+    ///
+    /// [`Commitment::scheme_identifier`]: super::Commitment::scheme_identifier
+    #[must_use]
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// The digest of the public setup, see [`Commitment::setup_digest`].
+    ///
+    /// [`Commitment::setup_digest`]: super::Commitment::setup_digest
+    #[must_use]
+    pub fn setup_digest(&self) -> Option<&[u8; 32]> {
+        self.setup_digest.as_ref()
+    }
+
+    /// The range of generator offsets of the committed rows.
+    #[must_use]
+    pub fn row_range(&self) -> &Range<usize> {
+        &self.row_range
+    }
+
+    /// Returns true if both commitments were computed with the same scheme and setup.
+    #[must_use]
+    pub fn has_same_setup(&self, other: &CommitmentProvenance) -> bool {
+        self.scheme == other.scheme && self.setup_digest == other.setup_digest
+    }
+
+    /// Describe the scheme and setup, without the row range.
+    pub(super) fn setup_description(&self) -> String {
+        match &self.setup_digest {
+            Some(digest) => alloc::format!("{} ({})", self.scheme, DigestHex(digest)),
+            None => self.scheme.clone(),
+        }
+    }
+
+    /// Combine two provenances of the same setup as if their rows are being unioned.
+    ///
+    /// The resulting row range spans both row ranges.
+    fn union(self, other: CommitmentProvenance) -> CommitmentProvenance {
+        let row_range = if self.row_range.is_empty() {
+            other.row_range
+        } else if other.row_range.is_empty() {
+            self.row_range
+        } else {
+            self.row_range.start.min(other.row_range.start)
+                ..self.row_range.end.max(other.row_range.end)
+        };
+        CommitmentProvenance { row_range, ..self }
+    }
+
+    /// Combine two provenances of the same setup as if their rows are being differenced.
+    ///
+    /// Removing a prefix or suffix shrinks the row range, anything else leaves it unchanged.
+    fn difference(self, other: CommitmentProvenance) -> CommitmentProvenance {
+        let row_range = if other.row_range.is_empty() {
+            self.row_range
+        } else if self.row_range.start == other.row_range.start {
+            other.row_range.end.min(self.row_range.end)..self.row_range.end
+        } else if self.row_range.end == other.row_range.end {
+            self.row_range.start..other.row_range.start.max(self.row_range.start)
+        } else {
+            self.row_range
+        };
+        CommitmentProvenance { row_range, ..self }
+    }
+}
+
+impl Display for CommitmentProvenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} rows {}..{}",
+            self.setup_description(),
+            self.row_range.start,
+            self.row_range.end
+        )
+    }
+}
+
+/// Lowercase hexadecimal formatting of a digest.
+struct DigestHex<'a>(&'a [u8; 32]);
+
+impl Display for DigestHex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// Combine two optional provenances, which is only meaningful if both are present and share a setup.
+fn combine_provenances(
+    provenance_a: Option<CommitmentProvenance>,
+    provenance_b: Option<CommitmentProvenance>,
+    combine: impl FnOnce(CommitmentProvenance, CommitmentProvenance) -> CommitmentProvenance,
+) -> Option<CommitmentProvenance> {
+    match (provenance_a, provenance_b) {
+        (Some(a), Some(b)) if a.has_same_setup(&b) => Some(combine(a, b)),
+        _ => None,
+    }
+}
+
 const EXPECT_BOUNDS_MATCH_MESSAGE: &str = "we've already checked the column types match, which is a stronger requirement (mapping of type variants to bounds variants is surjective)";
 
 /// Anonymous metadata associated with a column commitment.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Metadata serialized before provenance or enforced bounds were recorded has neither when
+/// deserialized with a self-describing format.
+///
+/// The bounds are observed from the committed rows, while the optional enforced bounds are a
+/// policy that new rows can be checked against with [`ColumnCommitmentMetadata::validate_column`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ColumnCommitmentMetadata {
     column_type: ColumnType,
     bounds: ColumnBounds,
+    #[serde(default)]
+    provenance: Option<CommitmentProvenance>,
+    #[serde(default)]
+    enforced_bounds: Option<ColumnBounds>,
+}
+
+/// Check that the bounds variant matches the column type.
+fn check_type_bounds(
+    column_type: ColumnType,
+    bounds: ColumnBounds,
+) -> Result<(), InvalidColumnCommitmentMetadata> {
+    match (column_type, bounds) {
+        (ColumnType::Uint8, ColumnBounds::Uint8(_))
+        | (ColumnType::TinyInt, ColumnBounds::TinyInt(_))
+        | (ColumnType::SmallInt, ColumnBounds::SmallInt(_))
+        | (ColumnType::Int, ColumnBounds::Int(_))
+        | (ColumnType::BigInt, ColumnBounds::BigInt(_))
+        | (ColumnType::Int128, ColumnBounds::Int128(_))
+        | (ColumnType::TimestampTZ(_, _), ColumnBounds::TimestampTZ(_))
+        | (
+            ColumnType::Boolean
+            | ColumnType::VarChar
+            | ColumnType::VarBinary
+            | ColumnType::Scalar
+            | ColumnType::Decimal75(..),
+            ColumnBounds::NoOrder,
+        ) => Ok(()),
+        _ => Err(InvalidColumnCommitmentMetadata::TypeBoundsMismatch {
+            column_type,
+            column_bounds: bounds,
+        }),
+    }
 }
 
 impl ColumnCommitmentMetadata {
@@ -43,30 +229,13 @@ impl ColumnCommitmentMetadata {
         column_type: ColumnType,
         bounds: ColumnBounds,
     ) -> Result<ColumnCommitmentMetadata, InvalidColumnCommitmentMetadata> {
-        match (column_type, bounds) {
-            (ColumnType::Uint8, ColumnBounds::Uint8(_))
-            | (ColumnType::TinyInt, ColumnBounds::TinyInt(_))
-            | (ColumnType::SmallInt, ColumnBounds::SmallInt(_))
-            | (ColumnType::Int, ColumnBounds::Int(_))
-            | (ColumnType::BigInt, ColumnBounds::BigInt(_))
-            | (ColumnType::Int128, ColumnBounds::Int128(_))
-            | (ColumnType::TimestampTZ(_, _), ColumnBounds::TimestampTZ(_))
-            | (
-                ColumnType::Boolean
-                | ColumnType::VarChar
-                | ColumnType::VarBinary
-                | ColumnType::Scalar
-                | ColumnType::Decimal75(..),
-                ColumnBounds::NoOrder,
-            ) => Ok(ColumnCommitmentMetadata {
-                column_type,
-                bounds,
-            }),
-            _ => Err(InvalidColumnCommitmentMetadata::TypeBoundsMismatch {
-                column_type,
-                column_bounds: bounds,
-            }),
-        }
+        check_type_bounds(column_type, bounds)?;
+        Ok(ColumnCommitmentMetadata {
+            column_type,
+            bounds,
+            provenance: None,
+            enforced_bounds: None,
+        })
     }
 
     #[expect(clippy::missing_panics_doc)]
@@ -116,17 +285,85 @@ impl ColumnCommitmentMetadata {
         &self.bounds
     }
 
+    /// Immutable reference to this column's provenance, if it was recorded.
+    #[must_use]
+    pub fn provenance(&self) -> Option<&CommitmentProvenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Record the provenance of this column's commitment.
+    #[must_use]
+    pub fn with_provenance(self, provenance: CommitmentProvenance) -> Self {
+        ColumnCommitmentMetadata {
+            provenance: Some(provenance),
+            ..self
+        }
+    }
+
+    /// Immutable reference to the bounds new rows of this column must stay within, if enforced.
+    #[must_use]
+    pub fn enforced_bounds(&self) -> Option<&ColumnBounds> {
+        self.enforced_bounds.as_ref()
+    }
+
+    /// Enforce that new rows of this column stay within the given bounds.
+    ///
+    /// Will error if the bounds variant and column type do not match.
+    pub fn try_with_enforced_bounds(
+        self,
+        enforced_bounds: ColumnBounds,
+    ) -> Result<Self, InvalidColumnCommitmentMetadata> {
+        check_type_bounds(self.column_type, enforced_bounds)?;
+        Ok(ColumnCommitmentMetadata {
+            enforced_bounds: Some(enforced_bounds),
+            ..self
+        })
+    }
+
     /// Construct a [`ColumnCommitmentMetadata`] by analyzing a column.
     #[must_use]
     pub fn from_column(column: &CommittableColumn) -> ColumnCommitmentMetadata {
         ColumnCommitmentMetadata {
             column_type: column.column_type(),
             bounds: ColumnBounds::from_column(column),
+            provenance: None,
+            enforced_bounds: None,
+        }
+    }
+
+    /// Check that a column can be appended to this column without violating its policies.
+    ///
+    /// The column must have the same type, and its values must be within the enforced bounds if
+    /// there are any.
+    #[expect(clippy::missing_panics_doc)]
+    pub fn validate_column(&self, column: &CommittableColumn) -> Result<(), ValidationError> {
+        let column_type = column.column_type();
+        if self.column_type != column_type {
+            return Err(ValidationError::TypeMismatch {
+                expected: self.column_type,
+                actual: column_type,
+            });
+        }
+        if let Some(enforced_bounds) = self.enforced_bounds {
+            let bounds = ColumnBounds::from_column(column);
+            if !enforced_bounds
+                .try_surrounds(&bounds)
+                .expect(EXPECT_BOUNDS_MATCH_MESSAGE)
+            {
+                return Err(ValidationError::OutOfEnforcedBounds {
+                    enforced: enforced_bounds,
+                    actual: bounds,
+                });
+            }
         }
+        Ok(())
     }
 
     /// Combine two [`ColumnCommitmentMetadata`] as if their source collections are being unioned.
     ///
+    /// Provenance is kept only if both metadatas have provenance with the same setup.
+    /// Enforced bounds are kept from self, or taken from other if self has none.
+    ///
     /// Can error if the two metadatas are mismatched.
     #[expect(clippy::missing_panics_doc)]
     pub fn try_union(
@@ -144,10 +381,17 @@ impl ColumnCommitmentMetadata {
             .bounds
             .try_union(other.bounds)
             .expect(EXPECT_BOUNDS_MATCH_MESSAGE);
+        let provenance = combine_provenances(
+            self.provenance,
+            other.provenance,
+            CommitmentProvenance::union,
+        );
 
         Ok(ColumnCommitmentMetadata {
             bounds,
             column_type: self.column_type,
+            provenance,
+            enforced_bounds: self.enforced_bounds.or(other.enforced_bounds),
         })
     }
 
@@ -155,6 +399,9 @@ impl ColumnCommitmentMetadata {
     ///
     /// This should be interpreted as the set difference of the two collections.
     /// The result would be the rows in self that are not also rows in other.
+    ///
+    /// Provenance is kept only if both metadatas have provenance with the same setup.
+    /// Enforced bounds are kept from self, or taken from other if self has none.
     #[expect(clippy::missing_panics_doc)]
     pub fn try_difference(
         self,
@@ -171,10 +418,17 @@ impl ColumnCommitmentMetadata {
             .bounds
             .try_difference(other.bounds)
             .expect(EXPECT_BOUNDS_MATCH_MESSAGE);
+        let provenance = combine_provenances(
+            self.provenance,
+            other.provenance,
+            CommitmentProvenance::difference,
+        );
 
         Ok(ColumnCommitmentMetadata {
             bounds,
             column_type: self.column_type,
+            provenance,
+            enforced_bounds: self.enforced_bounds.or(other.enforced_bounds),
         })
     }
 }
@@ -190,7 +444,7 @@ mod tests {
         posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
         scalar::test_scalar::TestScalar,
     };
-    use alloc::string::String;
+    use alloc::string::{String, ToString};
 
     #[test]
     fn we_can_construct_metadata() {
@@ -202,7 +456,9 @@ mod tests {
             .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::TinyInt,
-                bounds: ColumnBounds::TinyInt(Bounds::Empty)
+                bounds: ColumnBounds::TinyInt(Bounds::Empty),
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -214,7 +470,9 @@ mod tests {
             .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::SmallInt,
-                bounds: ColumnBounds::SmallInt(Bounds::Empty)
+                bounds: ColumnBounds::SmallInt(Bounds::Empty),
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -223,7 +481,9 @@ mod tests {
                 .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Int,
-                bounds: ColumnBounds::Int(Bounds::Empty)
+                bounds: ColumnBounds::Int(Bounds::Empty),
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -235,7 +495,9 @@ mod tests {
             .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::BigInt,
-                bounds: ColumnBounds::BigInt(Bounds::Empty)
+                bounds: ColumnBounds::BigInt(Bounds::Empty),
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -244,6 +506,8 @@ mod tests {
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Boolean,
                 bounds: ColumnBounds::NoOrder,
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -256,6 +520,8 @@ mod tests {
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Decimal75(Precision::new(10).unwrap(), 0),
                 bounds: ColumnBounds::NoOrder,
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -268,6 +534,8 @@ mod tests {
             ColumnCommitmentMetadata {
                 column_type: ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
                 bounds: ColumnBounds::TimestampTZ(Bounds::Empty),
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -279,7 +547,9 @@ mod tests {
             .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Int128,
-                bounds: ColumnBounds::Int128(Bounds::sharp(-5, 10).unwrap())
+                bounds: ColumnBounds::Int128(Bounds::sharp(-5, 10).unwrap()),
+                provenance: None,
+                enforced_bounds: None,
             }
         );
 
@@ -287,7 +557,9 @@ mod tests {
             ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::VarChar,
-                bounds: ColumnBounds::NoOrder
+                bounds: ColumnBounds::NoOrder,
+                provenance: None,
+                enforced_bounds: None,
             }
         );
     }
@@ -496,36 +768,56 @@ mod tests {
         let boolean_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Boolean,
             bounds: ColumnBounds::NoOrder,
+            provenance: None,
+            enforced_bounds: None,
         };
         assert_eq!(
-            boolean_metadata.try_union(boolean_metadata).unwrap(),
+            boolean_metadata
+                .clone()
+                .try_union(boolean_metadata.clone())
+                .unwrap(),
             boolean_metadata
         );
 
         let decimal_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Decimal75(Precision::new(12).unwrap(), 0),
             bounds: ColumnBounds::NoOrder,
+            provenance: None,
+            enforced_bounds: None,
         };
         assert_eq!(
-            decimal_metadata.try_union(decimal_metadata).unwrap(),
+            decimal_metadata
+                .clone()
+                .try_union(decimal_metadata.clone())
+                .unwrap(),
             decimal_metadata
         );
 
         let varchar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::VarChar,
             bounds: ColumnBounds::NoOrder,
+            provenance: None,
+            enforced_bounds: None,
         };
         assert_eq!(
-            varchar_metadata.try_union(varchar_metadata).unwrap(),
+            varchar_metadata
+                .clone()
+                .try_union(varchar_metadata.clone())
+                .unwrap(),
             varchar_metadata
         );
 
         let scalar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Scalar,
             bounds: ColumnBounds::NoOrder,
+            provenance: None,
+            enforced_bounds: None,
         };
         assert_eq!(
-            scalar_metadata.try_union(scalar_metadata).unwrap(),
+            scalar_metadata
+                .clone()
+                .try_union(scalar_metadata.clone())
+                .unwrap(),
             scalar_metadata
         );
 
@@ -622,6 +914,7 @@ mod tests {
         let timestamp_metadata_b = ColumnCommitmentMetadata::from_column(&timestamp_column_b);
 
         let b_difference_a = timestamp_metadata_b
+            .clone()
             .try_difference(timestamp_metadata_a)
             .unwrap();
         assert_eq!(
@@ -641,12 +934,14 @@ mod tests {
 
         assert_eq!(
             timestamp_metadata_b
-                .try_difference(timestamp_metadata_empty)
+                .clone()
+                .try_difference(timestamp_metadata_empty.clone())
                 .unwrap(),
             timestamp_metadata_b
         );
         assert_eq!(
             timestamp_metadata_empty
+                .clone()
                 .try_difference(timestamp_metadata_b)
                 .unwrap(),
             timestamp_metadata_empty
@@ -662,7 +957,10 @@ mod tests {
         let bigint_column_b = CommittableColumn::BigInt(&ints);
         let bigint_metadata_b = ColumnCommitmentMetadata::from_column(&bigint_column_b);
 
-        let b_difference_a = bigint_metadata_b.try_difference(bigint_metadata_a).unwrap();
+        let b_difference_a = bigint_metadata_b
+            .clone()
+            .try_difference(bigint_metadata_a)
+            .unwrap();
         assert_eq!(b_difference_a.column_type, ColumnType::BigInt);
         if let ColumnBounds::BigInt(Bounds::Bounded(bounds)) = b_difference_a.bounds() {
             assert_eq!(bounds.min(), &0);
@@ -676,12 +974,14 @@ mod tests {
 
         assert_eq!(
             bigint_metadata_b
-                .try_difference(bigint_metadata_empty)
+                .clone()
+                .try_difference(bigint_metadata_empty.clone())
                 .unwrap(),
             bigint_metadata_b
         );
         assert_eq!(
             bigint_metadata_empty
+                .clone()
                 .try_difference(bigint_metadata_b)
                 .unwrap(),
             bigint_metadata_empty
@@ -698,6 +998,7 @@ mod tests {
         let tinyint_metadata_b = ColumnCommitmentMetadata::from_column(&tinyint_column_b);
 
         let b_difference_a = tinyint_metadata_b
+            .clone()
             .try_difference(tinyint_metadata_a)
             .unwrap();
         assert_eq!(b_difference_a.column_type, ColumnType::TinyInt);
@@ -713,12 +1014,14 @@ mod tests {
 
         assert_eq!(
             tinyint_metadata_b
-                .try_difference(tinyint_metadata_empty)
+                .clone()
+                .try_difference(tinyint_metadata_empty.clone())
                 .unwrap(),
             tinyint_metadata_b
         );
         assert_eq!(
             tinyint_metadata_empty
+                .clone()
                 .try_difference(tinyint_metadata_b)
                 .unwrap(),
             tinyint_metadata_empty
@@ -735,6 +1038,7 @@ mod tests {
         let smallint_metadata_b = ColumnCommitmentMetadata::from_column(&smallint_column_b);
 
         let b_difference_a = smallint_metadata_b
+            .clone()
             .try_difference(smallint_metadata_a)
             .unwrap();
         assert_eq!(b_difference_a.column_type, ColumnType::SmallInt);
@@ -750,12 +1054,14 @@ mod tests {
 
         assert_eq!(
             smallint_metadata_b
-                .try_difference(smallint_metadata_empty)
+                .clone()
+                .try_difference(smallint_metadata_empty.clone())
                 .unwrap(),
             smallint_metadata_b
         );
         assert_eq!(
             smallint_metadata_empty
+                .clone()
                 .try_difference(smallint_metadata_b)
                 .unwrap(),
             smallint_metadata_empty
@@ -771,7 +1077,10 @@ mod tests {
         let int_column_b = CommittableColumn::Int(&ints);
         let int_metadata_b = ColumnCommitmentMetadata::from_column(&int_column_b);
 
-        let b_difference_a = int_metadata_b.try_difference(int_metadata_a).unwrap();
+        let b_difference_a = int_metadata_b
+            .clone()
+            .try_difference(int_metadata_a)
+            .unwrap();
         assert_eq!(b_difference_a.column_type, ColumnType::Int);
         if let ColumnBounds::Int(Bounds::Bounded(bounds)) = b_difference_a.bounds() {
             assert_eq!(bounds.min(), &0);
@@ -784,11 +1093,17 @@ mod tests {
         let int_metadata_empty = ColumnCommitmentMetadata::from_column(&int_column_empty);
 
         assert_eq!(
-            int_metadata_b.try_difference(int_metadata_empty).unwrap(),
+            int_metadata_b
+                .clone()
+                .try_difference(int_metadata_empty.clone())
+                .unwrap(),
             int_metadata_b
         );
         assert_eq!(
-            int_metadata_empty.try_difference(int_metadata_b).unwrap(),
+            int_metadata_empty
+                .clone()
+                .try_difference(int_metadata_b)
+                .unwrap(),
             int_metadata_empty
         );
     }
@@ -799,165 +1114,405 @@ mod tests {
         let boolean_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Boolean,
             bounds: ColumnBounds::NoOrder,
+            provenance: None,
+            enforced_bounds: None,
         };
         let varchar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::VarChar,
             bounds: ColumnBounds::NoOrder,
+            provenance: None,
+            enforced_bounds: None,
         };
         let scalar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Scalar,
             bounds: ColumnBounds::NoOrder,
+            provenance: None,
+            enforced_bounds: None,
         };
         let tinyint_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::TinyInt,
             bounds: ColumnBounds::TinyInt(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
         let smallint_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::SmallInt,
             bounds: ColumnBounds::SmallInt(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
         let int_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Int,
             bounds: ColumnBounds::Int(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
         let bigint_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::BigInt,
             bounds: ColumnBounds::BigInt(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
         let int128_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Int128,
             bounds: ColumnBounds::Int128(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
         let decimal75_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Decimal75(Precision::new(4).unwrap(), 8),
             bounds: ColumnBounds::Int128(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
 
-        assert!(tinyint_metadata.try_union(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_union(tinyint_metadata).is_err());
+        assert!(tinyint_metadata
+            .clone()
+            .try_union(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_union(tinyint_metadata.clone())
+            .is_err());
 
-        assert!(tinyint_metadata.try_union(decimal75_metadata).is_err());
-        assert!(decimal75_metadata.try_union(tinyint_metadata).is_err());
+        assert!(tinyint_metadata
+            .clone()
+            .try_union(decimal75_metadata.clone())
+            .is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_union(tinyint_metadata.clone())
+            .is_err());
 
-        assert!(tinyint_metadata.try_union(varchar_metadata).is_err());
-        assert!(varchar_metadata.try_union(tinyint_metadata).is_err());
+        assert!(tinyint_metadata
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_union(tinyint_metadata.clone())
+            .is_err());
 
-        assert!(tinyint_metadata.try_union(boolean_metadata).is_err());
-        assert!(boolean_metadata.try_union(tinyint_metadata).is_err());
+        assert!(tinyint_metadata
+            .clone()
+            .try_union(boolean_metadata.clone())
+            .is_err());
+        assert!(boolean_metadata
+            .clone()
+            .try_union(tinyint_metadata)
+            .is_err());
 
-        assert!(smallint_metadata.try_union(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_union(smallint_metadata).is_err());
+        assert!(smallint_metadata
+            .clone()
+            .try_union(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_union(smallint_metadata.clone())
+            .is_err());
 
-        assert!(smallint_metadata.try_union(decimal75_metadata).is_err());
-        assert!(decimal75_metadata.try_union(smallint_metadata).is_err());
+        assert!(smallint_metadata
+            .clone()
+            .try_union(decimal75_metadata.clone())
+            .is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_union(smallint_metadata.clone())
+            .is_err());
 
-        assert!(smallint_metadata.try_union(varchar_metadata).is_err());
-        assert!(varchar_metadata.try_union(smallint_metadata).is_err());
+        assert!(smallint_metadata
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_union(smallint_metadata.clone())
+            .is_err());
 
-        assert!(smallint_metadata.try_union(boolean_metadata).is_err());
-        assert!(boolean_metadata.try_union(smallint_metadata).is_err());
+        assert!(smallint_metadata
+            .clone()
+            .try_union(boolean_metadata.clone())
+            .is_err());
+        assert!(boolean_metadata
+            .clone()
+            .try_union(smallint_metadata)
+            .is_err());
 
-        assert!(int_metadata.try_union(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_union(int_metadata).is_err());
+        assert!(int_metadata
+            .clone()
+            .try_union(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_union(int_metadata.clone())
+            .is_err());
 
-        assert!(int_metadata.try_union(decimal75_metadata).is_err());
-        assert!(decimal75_metadata.try_union(int_metadata).is_err());
+        assert!(int_metadata
+            .clone()
+            .try_union(decimal75_metadata.clone())
+            .is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_union(int_metadata.clone())
+            .is_err());
 
-        assert!(int_metadata.try_union(varchar_metadata).is_err());
-        assert!(varchar_metadata.try_union(int_metadata).is_err());
+        assert!(int_metadata
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_union(int_metadata.clone())
+            .is_err());
 
-        assert!(int_metadata.try_union(boolean_metadata).is_err());
-        assert!(boolean_metadata.try_union(int_metadata).is_err());
+        assert!(int_metadata
+            .clone()
+            .try_union(boolean_metadata.clone())
+            .is_err());
+        assert!(boolean_metadata.clone().try_union(int_metadata).is_err());
 
-        assert!(varchar_metadata.try_union(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_union(varchar_metadata).is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_union(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
 
-        assert!(varchar_metadata.try_union(bigint_metadata).is_err());
-        assert!(bigint_metadata.try_union(varchar_metadata).is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_union(bigint_metadata.clone())
+            .is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
 
-        assert!(varchar_metadata.try_union(int128_metadata).is_err());
-        assert!(int128_metadata.try_union(varchar_metadata).is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_union(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_union(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_union(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_union(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_union(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_union(bigint_metadata).is_err());
-        assert!(bigint_metadata.try_union(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_union(bigint_metadata.clone())
+            .is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_union(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_union(varchar_metadata).is_err());
-        assert!(varchar_metadata.try_union(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_union(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_union(int128_metadata).is_err());
-        assert!(int128_metadata.try_union(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_union(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_union(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(scalar_metadata.try_union(bigint_metadata).is_err());
-        assert!(bigint_metadata.try_union(scalar_metadata).is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_union(bigint_metadata.clone())
+            .is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_union(scalar_metadata.clone())
+            .is_err());
 
-        assert!(scalar_metadata.try_union(int128_metadata).is_err());
-        assert!(int128_metadata.try_union(scalar_metadata).is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_union(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_union(scalar_metadata.clone())
+            .is_err());
 
-        assert!(bigint_metadata.try_union(int128_metadata).is_err());
-        assert!(int128_metadata.try_union(bigint_metadata).is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_union(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_union(bigint_metadata.clone())
+            .is_err());
 
-        assert!(varchar_metadata.try_difference(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_difference(varchar_metadata).is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_difference(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_difference(varchar_metadata.clone())
+            .is_err());
 
-        assert!(varchar_metadata.try_difference(bigint_metadata).is_err());
-        assert!(bigint_metadata.try_difference(varchar_metadata).is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_difference(bigint_metadata.clone())
+            .is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_difference(varchar_metadata.clone())
+            .is_err());
 
-        assert!(varchar_metadata.try_difference(int128_metadata).is_err());
-        assert!(int128_metadata.try_difference(varchar_metadata).is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_difference(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_difference(varchar_metadata.clone())
+            .is_err());
 
-        assert!(scalar_metadata.try_difference(bigint_metadata).is_err());
-        assert!(bigint_metadata.try_difference(scalar_metadata).is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_difference(bigint_metadata.clone())
+            .is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_difference(scalar_metadata.clone())
+            .is_err());
 
-        assert!(scalar_metadata.try_difference(int128_metadata).is_err());
-        assert!(int128_metadata.try_difference(scalar_metadata).is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_difference(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_difference(scalar_metadata.clone())
+            .is_err());
 
-        assert!(bigint_metadata.try_difference(int128_metadata).is_err());
-        assert!(int128_metadata.try_difference(bigint_metadata).is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_difference(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_difference(bigint_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_difference(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_difference(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_difference(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_difference(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_difference(bigint_metadata).is_err());
-        assert!(bigint_metadata.try_difference(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_difference(bigint_metadata.clone())
+            .is_err());
+        assert!(bigint_metadata
+            .clone()
+            .try_difference(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_difference(int128_metadata).is_err());
-        assert!(int128_metadata.try_difference(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_difference(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .clone()
+            .try_difference(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_difference(varchar_metadata).is_err());
-        assert!(varchar_metadata.try_difference(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_difference(varchar_metadata.clone())
+            .is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_difference(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(decimal75_metadata.try_difference(boolean_metadata).is_err());
-        assert!(boolean_metadata.try_difference(decimal75_metadata).is_err());
+        assert!(decimal75_metadata
+            .clone()
+            .try_difference(boolean_metadata.clone())
+            .is_err());
+        assert!(boolean_metadata
+            .clone()
+            .try_difference(decimal75_metadata.clone())
+            .is_err());
 
-        assert!(boolean_metadata.try_difference(bigint_metadata).is_err());
-        assert!(bigint_metadata.try_difference(boolean_metadata).is_err());
+        assert!(boolean_metadata
+            .clone()
+            .try_difference(bigint_metadata.clone())
+            .is_err());
+        assert!(bigint_metadata
+            .try_difference(boolean_metadata.clone())
+            .is_err());
 
-        assert!(boolean_metadata.try_difference(int128_metadata).is_err());
-        assert!(int128_metadata.try_difference(boolean_metadata).is_err());
+        assert!(boolean_metadata
+            .clone()
+            .try_difference(int128_metadata.clone())
+            .is_err());
+        assert!(int128_metadata
+            .try_difference(boolean_metadata.clone())
+            .is_err());
 
-        assert!(boolean_metadata.try_difference(varchar_metadata).is_err());
-        assert!(varchar_metadata.try_difference(boolean_metadata).is_err());
+        assert!(boolean_metadata
+            .clone()
+            .try_difference(varchar_metadata.clone())
+            .is_err());
+        assert!(varchar_metadata
+            .clone()
+            .try_difference(boolean_metadata.clone())
+            .is_err());
 
-        assert!(boolean_metadata.try_difference(scalar_metadata).is_err());
-        assert!(scalar_metadata.try_difference(boolean_metadata).is_err());
+        assert!(boolean_metadata
+            .clone()
+            .try_difference(scalar_metadata.clone())
+            .is_err());
+        assert!(scalar_metadata
+            .clone()
+            .try_difference(boolean_metadata)
+            .is_err());
 
         let different_decimal75_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Decimal75(Precision::new(75).unwrap(), 0),
             bounds: ColumnBounds::Int128(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
 
         assert!(decimal75_metadata
-            .try_difference(different_decimal75_metadata)
+            .clone()
+            .try_difference(different_decimal75_metadata.clone())
             .is_err());
         assert!(different_decimal75_metadata
-            .try_difference(decimal75_metadata)
+            .clone()
+            .try_difference(decimal75_metadata.clone())
             .is_err());
 
         assert!(decimal75_metadata
-            .try_union(different_decimal75_metadata)
+            .clone()
+            .try_union(different_decimal75_metadata.clone())
             .is_err());
         assert!(different_decimal75_metadata
             .try_union(decimal75_metadata)
@@ -966,39 +1521,260 @@ mod tests {
         let timestamp_tz_metadata_a = ColumnCommitmentMetadata {
             column_type: ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
             bounds: ColumnBounds::TimestampTZ(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
 
         let timestamp_tz_metadata_b = ColumnCommitmentMetadata {
             column_type: ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc()),
             bounds: ColumnBounds::TimestampTZ(Bounds::Empty),
+            provenance: None,
+            enforced_bounds: None,
         };
 
         // Tests for union operations
-        assert!(timestamp_tz_metadata_a.try_union(varchar_metadata).is_err());
-        assert!(varchar_metadata.try_union(timestamp_tz_metadata_a).is_err());
+        assert!(timestamp_tz_metadata_a
+            .clone()
+            .try_union(varchar_metadata.clone())
+            .is_err());
+        assert!(varchar_metadata
+            .try_union(timestamp_tz_metadata_a.clone())
+            .is_err());
 
         // Tests for difference operations
         assert!(timestamp_tz_metadata_a
-            .try_difference(scalar_metadata)
+            .clone()
+            .try_difference(scalar_metadata.clone())
             .is_err());
         assert!(scalar_metadata
-            .try_difference(timestamp_tz_metadata_a)
+            .try_difference(timestamp_tz_metadata_a.clone())
             .is_err());
 
         // Tests for different time units within the same type
         assert!(timestamp_tz_metadata_a
-            .try_union(timestamp_tz_metadata_b)
+            .clone()
+            .try_union(timestamp_tz_metadata_b.clone())
             .is_err());
         assert!(timestamp_tz_metadata_b
-            .try_union(timestamp_tz_metadata_a)
+            .clone()
+            .try_union(timestamp_tz_metadata_a.clone())
             .is_err());
 
         // Difference with different time units
         assert!(timestamp_tz_metadata_a
-            .try_difference(timestamp_tz_metadata_b)
+            .clone()
+            .try_difference(timestamp_tz_metadata_b.clone())
             .is_err());
         assert!(timestamp_tz_metadata_b
             .try_difference(timestamp_tz_metadata_a)
             .is_err());
     }
+
+    fn varchar_metadata_with_provenance(
+        setup_digest: Option<[u8; 32]>,
+        row_range: Range<usize>,
+    ) -> ColumnCommitmentMetadata {
+        ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder)
+            .unwrap()
+            .with_provenance(CommitmentProvenance::new(
+                "naive".to_string(),
+                setup_digest,
+                row_range,
+            ))
+    }
+
+    #[test]
+    fn we_can_union_and_difference_provenance() {
+        let metadata_a = varchar_metadata_with_provenance(None, 0..3);
+        let metadata_b = varchar_metadata_with_provenance(None, 3..5);
+        let union = metadata_a.clone().try_union(metadata_b.clone()).unwrap();
+        assert_eq!(union.provenance().unwrap().row_range(), &(0..5));
+        assert_eq!(union.provenance().unwrap().scheme(), "naive");
+
+        // Removing a prefix or a suffix shrinks the row range
+        let prefix_difference = union.clone().try_difference(metadata_a.clone()).unwrap();
+        assert_eq!(prefix_difference.provenance().unwrap().row_range(), &(3..5));
+        let suffix_difference = union.clone().try_difference(metadata_b).unwrap();
+        assert_eq!(suffix_difference.provenance().unwrap().row_range(), &(0..3));
+
+        // Removing rows in the middle leaves it unchanged
+        let middle = varchar_metadata_with_provenance(None, 1..2);
+        let middle_difference = union.try_difference(middle).unwrap();
+        assert_eq!(middle_difference.provenance().unwrap().row_range(), &(0..5));
+
+        // Empty row ranges act as the identity
+        let empty = varchar_metadata_with_provenance(None, 0..0);
+        let union_with_empty = empty.try_union(metadata_a.clone()).unwrap();
+        assert_eq!(union_with_empty.provenance().unwrap().row_range(), &(0..3));
+    }
+
+    #[test]
+    fn we_lose_provenance_when_combining_different_or_missing_setups() {
+        let metadata = varchar_metadata_with_provenance(None, 0..3);
+        let metadata_other_setup = varchar_metadata_with_provenance(Some([1; 32]), 3..5);
+        let metadata_without_provenance =
+            ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap();
+
+        assert!(metadata
+            .clone()
+            .try_union(metadata_other_setup.clone())
+            .unwrap()
+            .provenance()
+            .is_none());
+        assert!(metadata
+            .clone()
+            .try_difference(metadata_other_setup)
+            .unwrap()
+            .provenance()
+            .is_none());
+        assert!(metadata
+            .clone()
+            .try_union(metadata_without_provenance.clone())
+            .unwrap()
+            .provenance()
+            .is_none());
+        assert!(metadata_without_provenance
+            .try_difference(metadata)
+            .unwrap()
+            .provenance()
+            .is_none());
+    }
+
+    #[test]
+    fn we_can_display_provenance() {
+        let mut digest = [0; 32];
+        digest[0] = 0xab;
+        let provenance = CommitmentProvenance::new("dory".to_string(), Some(digest), 2..7);
+        assert_eq!(
+            provenance.to_string(),
+            alloc::format!("dory (ab{}) rows 2..7", "00".repeat(31))
+        );
+        let provenance = CommitmentProvenance::new("naive".to_string(), None, 0..4);
+        assert_eq!(provenance.to_string(), "naive rows 0..4");
+        assert!(provenance.has_same_setup(&CommitmentProvenance::new(
+            "naive".to_string(),
+            None,
+            5..6
+        )));
+    }
+
+    #[test]
+    fn we_can_deserialize_metadata_without_provenance() {
+        let metadata = varchar_metadata_with_provenance(Some([2; 32]), 0..4);
+        let serialized = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(
+            serde_json::from_value::<ColumnCommitmentMetadata>(serialized.clone()).unwrap(),
+            metadata
+        );
+
+        // Metadata serialized before provenance was recorded has no provenance
+        let mut legacy_serialized = serialized;
+        legacy_serialized
+            .as_object_mut()
+            .unwrap()
+            .remove("provenance")
+            .unwrap();
+        let deserialized =
+            serde_json::from_value::<ColumnCommitmentMetadata>(legacy_serialized).unwrap();
+        assert_eq!(
+            deserialized,
+            ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap()
+        );
+    }
+
+    #[test]
+    fn we_can_validate_a_column_against_metadata() {
+        let metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::Empty),
+        )
+        .unwrap();
+        assert_eq!(metadata.enforced_bounds(), None);
+        assert!(metadata
+            .validate_column(&CommittableColumn::BigInt(&[i64::MIN, i64::MAX]))
+            .is_ok());
+
+        let metadata = metadata
+            .try_with_enforced_bounds(ColumnBounds::BigInt(Bounds::bounded(0, 100).unwrap()))
+            .unwrap();
+        assert!(metadata
+            .validate_column(&CommittableColumn::BigInt(&[0, 50, 100]))
+            .is_ok());
+        assert!(metadata
+            .validate_column(&CommittableColumn::BigInt(&[]))
+            .is_ok());
+    }
+
+    #[test]
+    fn we_cannot_validate_a_column_of_a_different_type() {
+        let metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::Empty),
+        )
+        .unwrap();
+        assert_eq!(
+            metadata.validate_column(&CommittableColumn::Int(&[1, 2])),
+            Err(ValidationError::TypeMismatch {
+                expected: ColumnType::BigInt,
+                actual: ColumnType::Int,
+            })
+        );
+    }
+
+    #[test]
+    fn we_cannot_validate_a_column_outside_of_the_enforced_bounds() {
+        let enforced = ColumnBounds::BigInt(Bounds::bounded(0, 100).unwrap());
+        let metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::Empty),
+        )
+        .unwrap()
+        .try_with_enforced_bounds(enforced)
+        .unwrap();
+        assert_eq!(
+            metadata.validate_column(&CommittableColumn::BigInt(&[50, 101])),
+            Err(ValidationError::OutOfEnforcedBounds {
+                enforced,
+                actual: ColumnBounds::BigInt(Bounds::sharp(50, 101).unwrap()),
+            })
+        );
+        assert!(matches!(
+            metadata.validate_column(&CommittableColumn::BigInt(&[-1])),
+            Err(ValidationError::OutOfEnforcedBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_enforce_bounds_of_a_different_type() {
+        let metadata =
+            ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap();
+        assert!(matches!(
+            metadata.try_with_enforced_bounds(ColumnBounds::BigInt(Bounds::Empty)),
+            Err(InvalidColumnCommitmentMetadata::TypeBoundsMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn we_keep_enforced_bounds_when_combining_metadata() {
+        let enforced = ColumnBounds::Int(Bounds::bounded(-10, 10).unwrap());
+        let metadata_with_policy =
+            ColumnCommitmentMetadata::from_column(&CommittableColumn::Int(&[1, 2]))
+                .try_with_enforced_bounds(enforced)
+                .unwrap();
+        let metadata = ColumnCommitmentMetadata::from_column(&CommittableColumn::Int(&[3]));
+
+        let union = metadata_with_policy
+            .clone()
+            .try_union(metadata.clone())
+            .unwrap();
+        assert_eq!(union.enforced_bounds(), Some(&enforced));
+        assert_eq!(
+            union.bounds(),
+            &ColumnBounds::Int(Bounds::sharp(1, 3).unwrap())
+        );
+        let union = metadata.clone().try_union(metadata_with_policy).unwrap();
+        assert_eq!(union.enforced_bounds(), Some(&enforced));
+        let difference = union.try_difference(metadata).unwrap();
+        assert_eq!(difference.enforced_bounds(), Some(&enforced));
+    }
 }
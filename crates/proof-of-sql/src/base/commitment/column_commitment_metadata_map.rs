@@ -1,15 +1,33 @@
 use super::{
-    column_commitment_metadata::ColumnCommitmentMetadataMismatch, ColumnCommitmentMetadata,
-    CommittableColumn,
+    column_commitment_metadata::ColumnCommitmentMetadataMismatch,
+    column_commitments::check_for_invalid_idents, ColumnCommitmentMetadata, CommittableColumn,
 };
 use crate::base::{database::ColumnField, map::IndexMap};
 use alloc::string::{String, ToString};
+use proof_of_sql_parser::IdentifierLimits;
+use serde::{Deserialize, Deserializer};
 use snafu::Snafu;
 use sqlparser::ast::Ident;
 
 /// Mapping of column idents to column metadata used to associate metadata with commitments.
 pub type ColumnCommitmentMetadataMap = IndexMap<Ident, ColumnCommitmentMetadata>;
 
+/// Deserialize a [`ColumnCommitmentMetadataMap`], rejecting idents that violate the default
+/// [`IdentifierLimits`].
+///
+/// Artifacts stored before the limits were enforced are flagged on load rather than propagated.
+pub(super) fn deserialize_metadata_map_with_default_limits<'de, D>(
+    deserializer: D,
+) -> Result<ColumnCommitmentMetadataMap, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let metadata_map = ColumnCommitmentMetadataMap::deserialize(deserializer)?;
+    check_for_invalid_idents(metadata_map.keys(), &IdentifierLimits::default())
+        .map_err(serde::de::Error::custom)?;
+    Ok(metadata_map)
+}
+
 /// During commitment operation, metadata indicates that operand tables cannot be the same.
 #[derive(Debug, Snafu)]
 pub enum ColumnCommitmentsMismatch {
@@ -22,6 +40,16 @@ pub enum ColumnCommitmentsMismatch {
     /// Commitments with different column counts cannot operate with each other.
     #[snafu(display("commitments with different column counts cannot operate with each other"))]
     NumColumns,
+    /// Commitments computed with different commitment schemes or setups cannot operate with each other.
+    #[snafu(display(
+        "commitments computed with {setup_a} cannot operate with commitments computed with {setup_b}"
+    ))]
+    SetupMismatch {
+        /// The scheme and setup digest of the first commitments
+        setup_a: String,
+        /// The scheme and setup digest of the second commitments
+        setup_b: String,
+    },
     /// Columns with mismatched idents cannot operate with each other.
     ///
     /// Strings are used here instead of Idents to decrease the size of this variant
@@ -34,6 +62,22 @@ pub enum ColumnCommitmentsMismatch {
     },
 }
 
+/// Error if both metadatas have provenance, but their commitments were computed with different setups.
+fn check_for_setup_mismatch(
+    metadata_a: &ColumnCommitmentMetadata,
+    metadata_b: &ColumnCommitmentMetadata,
+) -> Result<(), ColumnCommitmentsMismatch> {
+    match (metadata_a.provenance(), metadata_b.provenance()) {
+        (Some(provenance_a), Some(provenance_b)) if !provenance_a.has_same_setup(provenance_b) => {
+            Err(ColumnCommitmentsMismatch::SetupMismatch {
+                setup_a: provenance_a.setup_description(),
+                setup_b: provenance_b.setup_description(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Extension trait intended for [`ColumnCommitmentMetadataMap`].
 pub trait ColumnCommitmentMetadataMapExt {
     /// Construct this mapping from a slice of column fields, with the bounds of each column set to
@@ -105,6 +149,7 @@ impl ColumnCommitmentMetadataMapExt for ColumnCommitmentMetadataMap {
                         id_b: identifier_b.to_string(),
                     })?;
                 }
+                check_for_setup_mismatch(&metadata_a, &metadata_b)?;
 
                 Ok((identifier_a, metadata_a.try_union(metadata_b)?))
             })
@@ -128,6 +173,7 @@ impl ColumnCommitmentMetadataMapExt for ColumnCommitmentMetadataMap {
                         id_b: identifier_b.to_string(),
                     })?;
                 }
+                check_for_setup_mismatch(&metadata_a, &metadata_b)?;
 
                 Ok((identifier_a, metadata_a.try_difference(metadata_b)?))
             })
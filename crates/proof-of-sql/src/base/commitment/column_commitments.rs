@@ -1,9 +1,15 @@
 use super::{
-    committable_column::CommittableColumn, ColumnCommitmentMetadata, ColumnCommitmentMetadataMap,
-    ColumnCommitmentMetadataMapExt, ColumnCommitmentsMismatch, Commitment, VecCommitmentExt,
+    check_column_type_supported_by,
+    column_commitment_metadata_map::deserialize_metadata_map_with_default_limits,
+    committable_column::{CommittableColumn, UnsupportedColumnType},
+    ColumnBounds, ColumnCommitmentMetadata, ColumnCommitmentMetadataMap,
+    ColumnCommitmentMetadataMapExt, ColumnCommitmentsMismatch, ColumnSegment, Commitment,
+    CommitmentProvenance, SegmentDeltaError, ValidationError, VecCommitmentExt,
 };
 use crate::base::{
-    database::{ColumnField, CommitmentAccessor, TableRef},
+    database::{
+        CoercionError, ColumnField, ColumnType, CommitmentAccessor, LiteralValue, TableRef,
+    },
     map::IndexSet,
 };
 use alloc::{
@@ -12,15 +18,158 @@ use alloc::{
     vec::Vec,
 };
 use core::{iter, slice};
+use proof_of_sql_parser::{IdentifierLimits, IdentifierValidationError};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use sqlparser::ast::Ident;
 
+/// The columns an ident collided with when creating or extending commitments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateIdentLocation {
+    /// The ident appears more than once among the new columns.
+    NewColumns,
+    /// The ident is already used by one of the existing columns.
+    ExistingColumns,
+}
+
+impl core::fmt::Display for DuplicateIdentLocation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NewColumns => write!(f, "another new column"),
+            Self::ExistingColumns => write!(f, "an existing column"),
+        }
+    }
+}
+
 /// Cannot create commitments with duplicate ident.
 #[derive(Debug, Snafu)]
-#[snafu(display("cannot create commitments with duplicate ident: {id}"))]
+#[snafu(display("cannot create commitments with duplicate ident: {id} collides with {location}"))]
 pub struct DuplicateIdents {
     id: String,
+    location: DuplicateIdentLocation,
+}
+
+impl DuplicateIdents {
+    /// The duplicated ident
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The columns the ident collided with
+    #[must_use]
+    pub fn location(&self) -> DuplicateIdentLocation {
+        self.location
+    }
+}
+
+/// Cannot create commitments with an ident that violates the [`IdentifierLimits`].
+#[derive(Debug, Snafu)]
+#[snafu(display("cannot create commitments with invalid ident: {source}"))]
+pub struct InvalidIdent {
+    source: IdentifierValidationError,
+}
+
+impl InvalidIdent {
+    /// The reason the ident is invalid
+    #[must_use]
+    pub fn reason(&self) -> &IdentifierValidationError {
+        &self.source
+    }
+}
+
+/// New rows violate the policies declared in the metadata of existing columns.
+///
+/// Every violating column is reported, not just the first.
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "new rows violate the policies of {} column(s): {}",
+    violations.len(),
+    describe_violations(violations)
+))]
+pub struct ColumnPolicyViolations {
+    violations: Vec<(String, ValidationError)>,
+}
+
+impl ColumnPolicyViolations {
+    /// The ident of every violating column with the reason it is invalid
+    #[must_use]
+    pub fn violations(&self) -> &[(String, ValidationError)] {
+        &self.violations
+    }
+}
+
+/// List the violations as `ident: reason`, separated by semicolons.
+fn describe_violations(violations: &[(String, ValidationError)]) -> String {
+    violations
+        .iter()
+        .map(|(id, error)| alloc::format!("{id}: {error}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Errors that can occur when creating or extending [`ColumnCommitments`] from columns.
+#[derive(Debug, Snafu)]
+pub enum ColumnCommitmentsFromColumnsError {
+    /// New columns have duplicate idents.
+    #[snafu(transparent)]
+    DuplicateIdents {
+        /// The underlying source error
+        source: DuplicateIdents,
+    },
+    /// New columns have idents violating the [`IdentifierLimits`].
+    #[snafu(transparent)]
+    InvalidIdent {
+        /// The underlying source error
+        source: InvalidIdent,
+    },
+    /// New columns have a type the commitment scheme cannot commit to.
+    #[snafu(transparent)]
+    UnsupportedColumnType {
+        /// The underlying source error
+        source: UnsupportedColumnType,
+    },
+}
+
+/// Errors that can occur when attempting to add a column filled with a default value to
+/// [`ColumnCommitments`].
+#[derive(Debug, Snafu)]
+pub enum ExtendColumnWithDefaultError {
+    /// The new column has the ident of an existing column.
+    #[snafu(transparent)]
+    DuplicateIdents {
+        /// The underlying source error
+        source: DuplicateIdents,
+    },
+    /// The new column has an ident violating the [`IdentifierLimits`].
+    #[snafu(transparent)]
+    InvalidIdent {
+        /// The underlying source error
+        source: InvalidIdent,
+    },
+    /// The new column has a type the commitment scheme cannot commit to.
+    #[snafu(transparent)]
+    UnsupportedColumnType {
+        /// The underlying source error
+        source: UnsupportedColumnType,
+    },
+    /// The default value cannot be coerced to the type of the new column.
+    #[snafu(transparent)]
+    DefaultValue {
+        /// The underlying source error
+        source: CoercionError,
+    },
+    /// The commitment scheme cannot compute the commitment to a constant column without
+    /// materializing it.
+    #[snafu(display(
+        "the {backend} commitment scheme cannot commit to constant {column_type} columns without materializing them"
+    ))]
+    NoConstantCommitment {
+        /// The type of the new column
+        column_type: ColumnType,
+        /// The identifier of the commitment scheme, see [`Commitment::scheme_identifier`]
+        backend: &'static str,
+    },
 }
 
 /// Errors that can occur when attempting to append rows to [`ColumnCommitments`].
@@ -38,6 +187,91 @@ pub enum AppendColumnCommitmentsError {
         /// The underlying source error
         source: DuplicateIdents,
     },
+    /// New rows violate the policies of existing columns.
+    #[snafu(transparent)]
+    PolicyViolations {
+        /// The underlying source error
+        source: ColumnPolicyViolations,
+    },
+}
+
+/// Errors that can occur when attempting to update rows of [`ColumnCommitments`].
+#[derive(Debug, Snafu)]
+pub enum UpdateColumnCommitmentsError {
+    /// Metadata between the updated and existing columns are mismatched.
+    #[snafu(transparent)]
+    Mismatch {
+        /// The underlying source error
+        source: ColumnCommitmentsMismatch,
+    },
+    /// The old and new rows cannot replace each other.
+    #[snafu(transparent)]
+    SegmentDelta {
+        /// The underlying source error
+        source: SegmentDeltaError,
+    },
+}
+
+/// Check that the `incoming` idents are unique among themselves and, if given, not already used by
+/// the `existing` columns.
+///
+/// This is the check used when creating, appending to and extending [`ColumnCommitments`], so it can
+/// be used to fail fast before computing any commitments.
+pub fn validate_unique_idents<'a>(
+    existing: Option<&ColumnCommitmentMetadataMap>,
+    incoming: impl IntoIterator<Item = &'a Ident>,
+) -> Result<(), DuplicateIdents> {
+    let mut unique_identifiers = IndexSet::default();
+    incoming.into_iter().try_for_each(|identifier| {
+        let location = if existing.is_some_and(|existing| existing.contains_key(identifier)) {
+            Some(DuplicateIdentLocation::ExistingColumns)
+        } else if !unique_identifiers.insert(identifier) {
+            Some(DuplicateIdentLocation::NewColumns)
+        } else {
+            None
+        };
+        location.map_or(Ok(()), |location| {
+            Err(DuplicateIdents {
+                id: identifier.to_string(),
+                location,
+            })
+        })
+    })
+}
+
+/// Check that each of the provided idents respects the given [`IdentifierLimits`].
+pub(super) fn check_for_invalid_idents<'a>(
+    identifiers: impl IntoIterator<Item = &'a Ident>,
+    limits: &IdentifierLimits,
+) -> Result<(), InvalidIdent> {
+    identifiers.into_iter().try_for_each(|identifier| {
+        limits
+            .validate_ident(identifier)
+            .map_err(|source| InvalidIdent { source })
+    })
+}
+
+/// Construct the metadata of columns being committed to, recording the provenance of their commitments.
+fn metadata_with_provenance<'a, C: Commitment>(
+    columns: impl IntoIterator<Item = (&'a Ident, &'a CommittableColumn<'a>)>,
+    offset: usize,
+    setup: &C::PublicSetup<'_>,
+) -> ColumnCommitmentMetadataMap {
+    let setup_digest = C::setup_digest(setup);
+    columns
+        .into_iter()
+        .map(|(identifier, column)| {
+            let provenance = CommitmentProvenance::new(
+                C::scheme_identifier().to_string(),
+                setup_digest,
+                offset..offset + column.len(),
+            );
+            (
+                identifier.clone(),
+                ColumnCommitmentMetadata::from_column(column).with_provenance(provenance),
+            )
+        })
+        .collect()
 }
 
 /// Commitments for a collection of columns with some metadata.
@@ -46,6 +280,7 @@ pub enum AppendColumnCommitmentsError {
 #[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ColumnCommitments<C> {
     commitments: Vec<C>,
+    #[serde(deserialize_with = "deserialize_metadata_map_with_default_limits")]
     column_metadata: ColumnCommitmentMetadataMap,
 }
 
@@ -117,28 +352,48 @@ impl<C: Commitment> ColumnCommitments<C> {
     }
 
     /// Returns [`ColumnCommitments`] to the provided columns using the given generator offset
+    ///
+    /// Idents must be unique and respect the default [`IdentifierLimits`], and the commitment
+    /// scheme must support the types of all columns.
     pub fn try_from_columns_with_offset<'a, COL>(
         columns: impl IntoIterator<Item = (&'a Ident, COL)>,
         offset: usize,
         setup: &C::PublicSetup<'_>,
-    ) -> Result<ColumnCommitments<C>, DuplicateIdents>
+    ) -> Result<ColumnCommitments<C>, ColumnCommitmentsFromColumnsError>
     where
         COL: Into<CommittableColumn<'a>>,
     {
-        // Check for duplicate idents
-        let mut unique_identifiers = IndexSet::default();
-        let unique_columns = columns
-            .into_iter()
-            .map(|(identifier, column)| {
-                if unique_identifiers.insert(identifier) {
-                    Ok((identifier, column))
-                } else {
-                    Err(DuplicateIdents {
-                        id: identifier.to_string(),
-                    })
-                }
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        Self::try_from_columns_with_offset_and_limits(
+            columns,
+            offset,
+            setup,
+            &IdentifierLimits::default(),
+        )
+    }
+
+    /// Returns [`ColumnCommitments`] to the provided columns using the given generator offset
+    ///
+    /// Idents must be unique and respect the provided [`IdentifierLimits`], and the commitment
+    /// scheme must support the types of all columns.
+    pub fn try_from_columns_with_offset_and_limits<'a, COL>(
+        columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+        limits: &IdentifierLimits,
+    ) -> Result<ColumnCommitments<C>, ColumnCommitmentsFromColumnsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        // Check for duplicate and invalid idents
+        let unique_columns: Vec<_> = columns.into_iter().collect();
+        validate_unique_idents(
+            None,
+            unique_columns.iter().map(|(identifier, _)| *identifier),
+        )?;
+        check_for_invalid_idents(
+            unique_columns.iter().map(|(identifier, _)| *identifier),
+            limits,
+        )?;
 
         let (identifiers, committable_columns): (Vec<&Ident>, Vec<CommittableColumn>) =
             unique_columns
@@ -148,9 +403,14 @@ impl<C: Commitment> ColumnCommitments<C> {
                     (identifier, committable_column)
                 })
                 .unzip();
+        committable_columns
+            .iter()
+            .try_for_each(CommittableColumn::check_supported_by::<C>)?;
 
-        let column_metadata = ColumnCommitmentMetadataMap::from_columns(
+        let column_metadata = metadata_with_provenance::<C>(
             identifiers.into_iter().zip(committable_columns.iter()),
+            offset,
+            setup,
         );
 
         let commitments = Vec::<C>::from_columns_with_offset(committable_columns, offset, setup);
@@ -168,30 +428,42 @@ impl<C: Commitment> ColumnCommitments<C> {
     ///
     /// Will error on a variety of mismatches.
     /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
-    #[expect(clippy::missing_panics_doc)]
     pub fn try_append_rows_with_offset<'a, COL>(
         &mut self,
         columns: impl IntoIterator<Item = (&'a Ident, COL)>,
         offset: usize,
         setup: &C::PublicSetup<'_>,
     ) -> Result<(), AppendColumnCommitmentsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        self.try_append_rows_with_offset_and_validation(columns, offset, setup, false)
+    }
+
+    /// Append rows of data from the provided columns to the existing commitments, optionally
+    /// validating them first.
+    ///
+    /// If `validate` is true, the rows are checked against the metadata of the existing columns
+    /// with [`Self::validate_append`] before any commitment is computed, and every violation is
+    /// reported as [`AppendColumnCommitmentsError::PolicyViolations`].
+    /// Otherwise, this is the same as [`Self::try_append_rows_with_offset`].
+    #[expect(clippy::missing_panics_doc)]
+    pub fn try_append_rows_with_offset_and_validation<'a, COL>(
+        &mut self,
+        columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+        validate: bool,
+    ) -> Result<(), AppendColumnCommitmentsError>
     where
         COL: Into<CommittableColumn<'a>>,
     {
         // Check for duplicate idents.
-        let mut unique_identifiers = IndexSet::default();
-        let unique_columns = columns
-            .into_iter()
-            .map(|(identifier, column)| {
-                if unique_identifiers.insert(identifier) {
-                    Ok((identifier, column))
-                } else {
-                    Err(DuplicateIdents {
-                        id: identifier.to_string(),
-                    })
-                }
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let unique_columns: Vec<_> = columns.into_iter().collect();
+        validate_unique_idents(
+            None,
+            unique_columns.iter().map(|(identifier, _)| *identifier),
+        )?;
 
         let (identifiers, committable_columns): (Vec<&Ident>, Vec<CommittableColumn>) =
             unique_columns
@@ -202,8 +474,16 @@ impl<C: Commitment> ColumnCommitments<C> {
                 })
                 .unzip();
 
-        let column_metadata = ColumnCommitmentMetadataMap::from_columns(
+        if validate {
+            self.validate_committable_columns(
+                identifiers.iter().copied().zip(committable_columns.iter()),
+            )?;
+        }
+
+        let column_metadata = metadata_with_provenance::<C>(
             identifiers.into_iter().zip(committable_columns.iter()),
+            offset,
+            setup,
         );
 
         self.column_metadata = self.column_metadata.clone().try_union(column_metadata)?;
@@ -215,37 +495,68 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(())
     }
 
+    /// Check that rows from the provided columns can be appended without violating the policies
+    /// in the metadata of the existing columns, see [`ColumnCommitmentMetadata::validate_column`].
+    ///
+    /// This doesn't compute any commitments, so it is cheap enough to reject rows before
+    /// committing to them. Every violating column is reported, not just the first.
+    /// Columns without existing metadata are skipped, since appending them fails with a mismatch
+    /// regardless.
+    pub fn validate_append<'a, COL>(
+        &self,
+        columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+    ) -> Result<(), ColumnPolicyViolations>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        let (identifiers, committable_columns): (Vec<&Ident>, Vec<CommittableColumn>) = columns
+            .into_iter()
+            .map(|(identifier, column)| (identifier, column.into()))
+            .unzip();
+        self.validate_committable_columns(identifiers.into_iter().zip(committable_columns.iter()))
+    }
+
+    /// Check already converted columns against the metadata of the existing columns.
+    fn validate_committable_columns<'a, 'b: 'a>(
+        &self,
+        columns: impl IntoIterator<Item = (&'a Ident, &'a CommittableColumn<'b>)>,
+    ) -> Result<(), ColumnPolicyViolations> {
+        let violations: Vec<_> = columns
+            .into_iter()
+            .filter_map(|(identifier, column)| {
+                let error = self
+                    .column_metadata
+                    .get(identifier)?
+                    .validate_column(column)
+                    .err()?;
+                Some((identifier.to_string(), error))
+            })
+            .collect();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ColumnPolicyViolations { violations })
+        }
+    }
+
     /// Add new columns to this [`ColumnCommitments`] using the given generator offset.
     pub fn try_extend_columns_with_offset<'a, COL>(
         &mut self,
         columns: impl IntoIterator<Item = (&'a Ident, COL)>,
         offset: usize,
         setup: &C::PublicSetup<'_>,
-    ) -> Result<(), DuplicateIdents>
+    ) -> Result<(), ColumnCommitmentsFromColumnsError>
     where
         COL: Into<CommittableColumn<'a>>,
     {
-        // Check for duplicates *between* the existing and new columns.
-        //
-        // The existing columns should not have any duplicates within themselves due to
-        // ColumnCommitments construction preventing it.
-        //
-        // If the new columns contain duplicates between each other, we'll catch this in the next
-        // step.
-        let unique_columns = columns
-            .into_iter()
-            .map(|(identifier, column)| {
-                if self.column_metadata.contains_key(identifier) {
-                    Err(DuplicateIdents {
-                        id: identifier.to_string(),
-                    })
-                } else {
-                    Ok((identifier, column))
-                }
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // this constructor will check for duplicates among the new columns
+        // Check for duplicates among the new columns and between the existing and new columns.
+        let unique_columns: Vec<_> = columns.into_iter().collect();
+        validate_unique_idents(
+            Some(&self.column_metadata),
+            unique_columns.iter().map(|(identifier, _)| *identifier),
+        )?;
+
+        // this constructor will check for invalid idents among the new columns
         let new_column_commitments =
             ColumnCommitments::<C>::try_from_columns_with_offset(unique_columns, offset, setup)?;
 
@@ -256,6 +567,112 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(())
     }
 
+    /// Add a new column holding `default` in each of `num_rows` rows to this [`ColumnCommitments`]
+    /// using the given generator offset.
+    ///
+    /// This is how a column added to a table with existing rows is committed to. The commitment is
+    /// computed from sums of generators, see [`Commitment::compute_constant_commitment`], so the
+    /// column is never materialized. The bounds of the column are sharp at the default value.
+    ///
+    /// `default` is coerced to `column_type`, see [`LiteralValue::try_coerce_to`].
+    /// Will error if the ident is taken or invalid, if the default value cannot be coerced, or if
+    /// the commitment scheme cannot commit to such a column without materializing it.
+    #[expect(clippy::missing_panics_doc)]
+    pub fn try_extend_columns_with_default(
+        &mut self,
+        ident: &Ident,
+        column_type: ColumnType,
+        default: &LiteralValue,
+        num_rows: usize,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), ExtendColumnWithDefaultError> {
+        validate_unique_idents(Some(&self.column_metadata), [ident])?;
+        check_for_invalid_idents([ident], &IdentifierLimits::default())?;
+        check_column_type_supported_by::<C>(column_type)?;
+        let default = default.try_coerce_to(&column_type)?;
+
+        let commitment =
+            C::compute_constant_commitment(default.to_scalar(), num_rows, offset, setup).ok_or(
+                ExtendColumnWithDefaultError::NoConstantCommitment {
+                    column_type,
+                    backend: C::scheme_identifier(),
+                },
+            )?;
+        let provenance = CommitmentProvenance::new(
+            C::scheme_identifier().to_string(),
+            C::setup_digest(setup),
+            offset..offset + num_rows,
+        );
+        let metadata = ColumnCommitmentMetadata::try_new(
+            column_type,
+            ColumnBounds::from_constant(&default, num_rows),
+        )
+        .expect("the bounds of a literal match its type")
+        .with_provenance(provenance);
+
+        self.commitments.push(commitment);
+        self.column_metadata.insert(ident.clone(), metadata);
+
+        Ok(())
+    }
+
+    /// Replace rows of the existing commitments, starting at the given generator offset.
+    ///
+    /// `old_columns` must be the data currently committed to in these rows and `new_columns` the
+    /// data replacing it, for every column in order.
+    /// Bounds are updated as if the old rows were subtracted and the new rows appended,
+    /// so they generally become [`Bounds::Bounded`](super::Bounds::Bounded).
+    ///
+    /// Will error on a variety of mismatches, or if the old and new columns have different lengths.
+    /// See [`ColumnCommitmentsMismatch`] and [`SegmentDeltaError`] for an enumeration of these errors.
+    pub fn try_update_rows_with_offset<'a, COL>(
+        &mut self,
+        old_columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        new_columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), UpdateColumnCommitmentsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        let (old_identifiers, old_segments): (Vec<&Ident>, Vec<ColumnSegment>) = old_columns
+            .into_iter()
+            .map(|(identifier, column)| (identifier, ColumnSegment::new(column, offset)))
+            .unzip();
+        let (new_identifiers, new_segments): (Vec<&Ident>, Vec<ColumnSegment>) = new_columns
+            .into_iter()
+            .map(|(identifier, column)| (identifier, ColumnSegment::new(column, offset)))
+            .unzip();
+
+        // The metadata is updated like the commitments, by removing the old rows and adding the new ones.
+        let old_metadata = metadata_with_provenance::<C>(
+            old_identifiers
+                .into_iter()
+                .zip(old_segments.iter().map(|segment| &segment.column)),
+            offset,
+            setup,
+        );
+        let new_metadata = metadata_with_provenance::<C>(
+            new_identifiers
+                .into_iter()
+                .zip(new_segments.iter().map(|segment| &segment.column)),
+            offset,
+            setup,
+        );
+        let column_metadata = self
+            .column_metadata
+            .clone()
+            .try_difference(old_metadata)?
+            .try_union(new_metadata)?;
+
+        self.commitments
+            .try_apply_segment_delta(&old_segments, &new_segments, setup)?;
+        self.column_metadata = column_metadata;
+
+        Ok(())
+    }
+
     /// Add two [`ColumnCommitments`] together.
     ///
     /// Will error on a variety of mismatches.
@@ -480,6 +897,161 @@ mod tests {
             column_commitments_from_columns
         );
     }
+    #[test]
+    fn we_cannot_construct_commitments_with_invalid_identifiers() {
+        let valid_identifier = "valid_identifier".into();
+        let quoted_identifier = Ident::with_quote('"', "quoted identifier");
+        let reserved_identifier = "timestamp".into();
+        let long_identifier = Ident::new("a".repeat(1 << 20));
+        let empty_column = OwnedColumn::<TestScalar>::BigInt(vec![]);
+
+        assert!(
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                [
+                    (&valid_identifier, &empty_column),
+                    (&quoted_identifier, &empty_column),
+                ],
+                0,
+                &(),
+            )
+            .is_ok()
+        );
+
+        let reserved_result = ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+            [
+                (&valid_identifier, &empty_column),
+                (&reserved_identifier, &empty_column),
+            ],
+            0,
+            &(),
+        );
+        match reserved_result {
+            Err(ColumnCommitmentsFromColumnsError::InvalidIdent { source }) => assert_eq!(
+                source.reason(),
+                &IdentifierValidationError::ReservedWord {
+                    word: "timestamp".to_string()
+                }
+            ),
+            _ => panic!("reserved words should be rejected"),
+        }
+
+        let long_result = ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+            [(&long_identifier, &empty_column)],
+            0,
+            &(),
+        );
+        match long_result {
+            Err(ColumnCommitmentsFromColumnsError::InvalidIdent { source }) => assert_eq!(
+                source.reason(),
+                &IdentifierValidationError::TooLong {
+                    length: 1 << 20,
+                    max_length: 128
+                }
+            ),
+            _ => panic!("long identifiers should be rejected"),
+        }
+
+        // The limits are configurable
+        let limits = IdentifierLimits::new(8);
+        assert!(matches!(
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset_and_limits(
+                [(&valid_identifier, &empty_column)],
+                0,
+                &(),
+                &limits,
+            ),
+            Err(ColumnCommitmentsFromColumnsError::InvalidIdent { .. })
+        ));
+
+        let mut existing_column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                [(&valid_identifier, &empty_column)],
+                0,
+                &(),
+            )
+            .unwrap();
+        assert!(matches!(
+            existing_column_commitments.try_extend_columns_with_offset(
+                [(&reserved_identifier, &empty_column)],
+                0,
+                &()
+            ),
+            Err(ColumnCommitmentsFromColumnsError::InvalidIdent { .. })
+        ));
+        assert_eq!(existing_column_commitments.len(), 1);
+    }
+
+    #[test]
+    fn we_cannot_deserialize_commitments_with_invalid_identifiers() {
+        let empty_column = OwnedColumn::<TestScalar>::BigInt(vec![]);
+        let valid_identifier = "valid_identifier".into();
+        let column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                [(&valid_identifier, &empty_column)],
+                0,
+                &(),
+            )
+            .unwrap();
+        let serialized = postcard::to_allocvec(&column_commitments).unwrap();
+        let deserialized: ColumnCommitments<NaiveCommitment> =
+            postcard::from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized, column_commitments);
+
+        // Simulate an artifact stored before identifiers were validated
+        let mut stale_column_commitments = column_commitments.clone();
+        let metadata = stale_column_commitments
+            .column_metadata_mut()
+            .shift_remove(&valid_identifier)
+            .unwrap();
+        stale_column_commitments
+            .column_metadata_mut()
+            .insert(Ident::new("a".repeat(1 << 20)), metadata);
+        let serialized = postcard::to_allocvec(&stale_column_commitments).unwrap();
+        let deserialized: Result<ColumnCommitments<NaiveCommitment>, _> =
+            postcard::from_bytes(&serialized);
+        assert!(deserialized.is_err());
+    }
+
+    #[cfg(feature = "blitzar")]
+    #[test]
+    fn we_cannot_construct_commitments_to_columns_the_scheme_does_not_support() {
+        use crate::proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar;
+        use curve25519_dalek::RistrettoPoint;
+
+        let owned_table = owned_table::<Curve25519Scalar>([
+            bigint("bigint_column", [1, 5, -5, 0]),
+            int128("int128_column", [100, 200, 300, 400]),
+        ]);
+        let result = ColumnCommitments::<RistrettoPoint>::try_from_columns_with_offset(
+            owned_table.inner_table(),
+            0,
+            &(),
+        );
+        match result {
+            Err(ColumnCommitmentsFromColumnsError::UnsupportedColumnType { source }) => {
+                assert_eq!(
+                    source,
+                    UnsupportedColumnType {
+                        column_type: ColumnType::Int128,
+                        backend: "inner_product",
+                    }
+                );
+            }
+            _ => panic!("columns the scheme does not support should be rejected"),
+        }
+
+        // other column types are still supported
+        let owned_table = owned_table::<Curve25519Scalar>([bigint("bigint_column", [1, 5, -5, 0])]);
+        assert!(
+            ColumnCommitments::<RistrettoPoint>::try_from_columns_with_offset(
+                owned_table.inner_table(),
+                0,
+                &(),
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn we_cannot_construct_commitments_with_duplicate_identifiers() {
         let duplicate_identifier_a = "duplicate_identifier_a".into();
@@ -498,7 +1070,12 @@ mod tests {
                 0,
                 &(),
             );
-        assert!(matches!(from_columns_result, Err(DuplicateIdents { .. })));
+        assert!(matches!(
+            from_columns_result,
+            Err(ColumnCommitmentsFromColumnsError::DuplicateIdents { source })
+                if source.id() == "duplicate_identifier_b"
+                    && source.location() == DuplicateIdentLocation::NewColumns
+        ));
 
         let mut existing_column_commitments =
             ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
@@ -515,7 +1092,9 @@ mod tests {
             .try_extend_columns_with_offset([(&duplicate_identifier_a, &empty_column)], 0, &());
         assert!(matches!(
             extend_with_existing_column_result,
-            Err(DuplicateIdents { .. })
+            Err(ColumnCommitmentsFromColumnsError::DuplicateIdents { source })
+                if source.id() == "duplicate_identifier_a"
+                    && source.location() == DuplicateIdentLocation::ExistingColumns
         ));
 
         let extend_with_duplicate_columns_result = existing_column_commitments
@@ -529,7 +1108,9 @@ mod tests {
             );
         assert!(matches!(
             extend_with_duplicate_columns_result,
-            Err(DuplicateIdents { .. })
+            Err(ColumnCommitmentsFromColumnsError::DuplicateIdents { source })
+                if source.id() == "duplicate_identifier_b"
+                    && source.location() == DuplicateIdentLocation::NewColumns
         ));
 
         let append_result = existing_column_commitments.try_append_rows_with_offset(
@@ -543,15 +1124,63 @@ mod tests {
         );
         assert!(matches!(
             append_result,
-            Err(AppendColumnCommitmentsError::DuplicateIdents { .. })
+            Err(AppendColumnCommitmentsError::DuplicateIdents { source })
+                if source.id() == "duplicate_identifier_a"
+                    && source.location() == DuplicateIdentLocation::NewColumns
         ));
     }
 
     #[test]
-    fn we_can_iterate_over_column_commitments() {
-        let bigint_id: Ident = "bigint_column".into();
-        let varchar_id: Ident = "varchar_column".into();
-        let scalar_id: Ident = "scalar_column".into();
+    fn we_can_validate_that_idents_are_unique() {
+        let a: Ident = "a".into();
+        let b: Ident = "b".into();
+        let c: Ident = "c".into();
+        let empty_column = OwnedColumn::<TestScalar>::BigInt(vec![]);
+        let existing_column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                [(&a, &empty_column)],
+                0,
+                &(),
+            )
+            .unwrap();
+        let existing = existing_column_commitments.column_metadata();
+
+        assert!(validate_unique_idents(None, []).is_ok());
+        assert!(validate_unique_idents(None, [&a, &b]).is_ok());
+        assert!(validate_unique_idents(Some(existing), [&b, &c]).is_ok());
+
+        let err = validate_unique_idents(None, [&a, &b, &a]).unwrap_err();
+        assert_eq!(err.id(), "a");
+        assert_eq!(err.location(), DuplicateIdentLocation::NewColumns);
+
+        let err = validate_unique_idents(Some(existing), [&b, &c, &b]).unwrap_err();
+        assert_eq!(err.id(), "b");
+        assert_eq!(err.location(), DuplicateIdentLocation::NewColumns);
+
+        let err = validate_unique_idents(Some(existing), [&b, &a]).unwrap_err();
+        assert_eq!(err.id(), "a");
+        assert_eq!(err.location(), DuplicateIdentLocation::ExistingColumns);
+
+        // A new column colliding with an existing one is reported as such even if repeated
+        let err = validate_unique_idents(Some(existing), [&a, &a]).unwrap_err();
+        assert_eq!(err.location(), DuplicateIdentLocation::ExistingColumns);
+    }
+
+    #[test]
+    fn we_can_display_duplicate_idents() {
+        let a: Ident = "a".into();
+        let err = validate_unique_idents(None, [&a, &a]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot create commitments with duplicate ident: a collides with another new column"
+        );
+    }
+
+    #[test]
+    fn we_can_iterate_over_column_commitments() {
+        let bigint_id: Ident = "bigint_column".into();
+        let varchar_id: Ident = "varchar_column".into();
+        let scalar_id: Ident = "scalar_column".into();
         let owned_table = owned_table::<TestScalar>([
             bigint(bigint_id.value.as_str(), [1, 5, -5, 0]),
             varchar(
@@ -592,6 +1221,132 @@ mod tests {
         assert_eq!(metadata.column_type(), &ColumnType::Scalar);
     }
 
+    #[test]
+    fn we_can_update_rows_of_column_commitments() {
+        let bigint_id: Ident = "bigint_column".into();
+        let varchar_id: Ident = "varchar_column".into();
+
+        let initial_columns: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [1, 5, -5, 0, 10]),
+            varchar(
+                varchar_id.value.as_str(),
+                ["Lorem", "ipsum", "dolor", "sit", "amet"],
+            ),
+        ]);
+        let mut column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                initial_columns.inner_table(),
+                0,
+                &(),
+            )
+            .unwrap();
+
+        let old_rows: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [5, -5]),
+            varchar(varchar_id.value.as_str(), ["ipsum", "dolor"]),
+        ]);
+        let new_rows: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [20, 3]),
+            varchar(varchar_id.value.as_str(), ["consectetur", "adipiscing"]),
+        ]);
+        column_commitments
+            .try_update_rows_with_offset(old_rows.inner_table(), new_rows.inner_table(), 1, &())
+            .unwrap();
+
+        let updated_columns: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [1, 20, 3, 0, 10]),
+            varchar(
+                varchar_id.value.as_str(),
+                ["Lorem", "consectetur", "adipiscing", "sit", "amet"],
+            ),
+        ]);
+        let expected_column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                updated_columns.inner_table(),
+                0,
+                &(),
+            )
+            .unwrap();
+        assert_eq!(
+            column_commitments.commitments(),
+            expected_column_commitments.commitments()
+        );
+
+        // the removed minimum can't be detected, so the bounds are no longer sharp
+        assert_eq!(
+            column_commitments
+                .get_metadata(&bigint_id)
+                .unwrap()
+                .bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-5, 20).unwrap())
+        );
+        assert_eq!(
+            column_commitments
+                .get_metadata(&bigint_id)
+                .unwrap()
+                .provenance()
+                .unwrap()
+                .row_range(),
+            &(0..5)
+        );
+    }
+
+    #[test]
+    fn we_cannot_update_rows_of_column_commitments_with_mismatched_columns() {
+        let initial_columns: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [1, 2, 3, 4]),
+            varchar("column_b", ["Lorem", "ipsum", "dolor", "sit"]),
+        ]);
+        let mut column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                initial_columns.inner_table(),
+                0,
+                &(),
+            )
+            .unwrap();
+        let original_column_commitments = column_commitments.clone();
+
+        let old_rows: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [2, 3]),
+            varchar("column_b", ["ipsum", "dolor"]),
+        ]);
+
+        let new_rows_diff_id: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [5, 6]),
+            varchar("column_c", ["ipsum", "dolor"]),
+        ]);
+        assert!(matches!(
+            column_commitments.try_update_rows_with_offset(
+                old_rows.inner_table(),
+                new_rows_diff_id.inner_table(),
+                1,
+                &()
+            ),
+            Err(UpdateColumnCommitmentsError::Mismatch {
+                source: ColumnCommitmentsMismatch::Ident { .. }
+            })
+        ));
+
+        let new_rows_diff_len: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [5, 6, 7]),
+            varchar("column_b", ["ipsum", "dolor", "sit"]),
+        ]);
+        assert!(matches!(
+            column_commitments.try_update_rows_with_offset(
+                old_rows.inner_table(),
+                new_rows_diff_len.inner_table(),
+                1,
+                &()
+            ),
+            Err(UpdateColumnCommitmentsError::SegmentDelta {
+                source: SegmentDeltaError::LengthMismatch { .. }
+            })
+        ));
+
+        // make sure the commitments weren't mutated
+        assert_eq!(column_commitments, original_column_commitments);
+    }
+
     #[test]
     fn we_can_append_rows_to_column_commitments() {
         let bigint_id: Ident = "bigint_column".into();
@@ -640,6 +1395,162 @@ mod tests {
         assert_eq!(column_commitments, expected_column_commitments);
     }
 
+    /// Commitments to a `BigInt` column `a` enforced to stay within `0..=100`, an `Int` column `b`
+    /// enforced to stay within `-10..=10`, and an unrestricted `VarChar` column `c`.
+    fn column_commitments_with_enforced_bounds() -> ColumnCommitments<NaiveCommitment> {
+        let table: OwnedTable<TestScalar> = owned_table([
+            bigint("a", [1, 2]),
+            int("b", [-1, 1]),
+            varchar("c", ["x", "y"]),
+        ]);
+        let mut column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                table.inner_table(),
+                0,
+                &(),
+            )
+            .unwrap();
+        for (id, enforced_bounds) in [
+            ("a", ColumnBounds::BigInt(Bounds::bounded(0, 100).unwrap())),
+            ("b", ColumnBounds::Int(Bounds::bounded(-10, 10).unwrap())),
+        ] {
+            let metadata = column_commitments
+                .column_metadata_mut()
+                .get_mut(&Ident::new(id))
+                .unwrap();
+            *metadata = metadata
+                .clone()
+                .try_with_enforced_bounds(enforced_bounds)
+                .unwrap();
+        }
+        column_commitments
+    }
+
+    #[test]
+    fn we_can_append_rows_that_satisfy_the_policies_of_the_columns() {
+        let mut column_commitments = column_commitments_with_enforced_bounds();
+        let append_columns: OwnedTable<TestScalar> = owned_table([
+            bigint("a", [0, 100]),
+            int("b", [-10, 10]),
+            varchar("c", ["anything", "goes"]),
+        ]);
+        assert!(column_commitments
+            .validate_append(append_columns.inner_table())
+            .is_ok());
+        column_commitments
+            .try_append_rows_with_offset_and_validation(append_columns.inner_table(), 2, &(), true)
+            .unwrap();
+        assert_eq!(
+            column_commitments
+                .get_metadata(&"a".into())
+                .unwrap()
+                .bounds(),
+            &ColumnBounds::BigInt(Bounds::sharp(0, 100).unwrap())
+        );
+        assert_eq!(
+            column_commitments
+                .get_metadata(&"a".into())
+                .unwrap()
+                .enforced_bounds(),
+            Some(&ColumnBounds::BigInt(Bounds::bounded(0, 100).unwrap()))
+        );
+    }
+
+    #[test]
+    fn we_can_report_every_policy_violation_of_appended_rows() {
+        let column_commitments = column_commitments_with_enforced_bounds();
+
+        // Out of the enforced bounds
+        let append_columns: OwnedTable<TestScalar> = owned_table([
+            bigint("a", [50, 101]),
+            int("b", [0, 0]),
+            varchar("c", ["x", "y"]),
+        ]);
+        let violations = column_commitments
+            .validate_append(append_columns.inner_table())
+            .unwrap_err();
+        assert_eq!(
+            violations.violations(),
+            &[(
+                "a".to_string(),
+                ValidationError::OutOfEnforcedBounds {
+                    enforced: ColumnBounds::BigInt(Bounds::bounded(0, 100).unwrap()),
+                    actual: ColumnBounds::BigInt(Bounds::sharp(50, 101).unwrap()),
+                }
+            )]
+        );
+
+        // Mismatched type
+        let append_columns: OwnedTable<TestScalar> = owned_table([
+            bigint("a", [50, 60]),
+            bigint("b", [0, 0]),
+            varchar("c", ["x", "y"]),
+        ]);
+        let violations = column_commitments
+            .validate_append(append_columns.inner_table())
+            .unwrap_err();
+        assert_eq!(
+            violations.violations(),
+            &[(
+                "b".to_string(),
+                ValidationError::TypeMismatch {
+                    expected: ColumnType::Int,
+                    actual: ColumnType::BigInt,
+                }
+            )]
+        );
+
+        // Every violation is reported
+        let append_columns: OwnedTable<TestScalar> =
+            owned_table([bigint("a", [-1, 0]), int("b", [0, 11]), int("c", [0, 0])]);
+        let violations = column_commitments
+            .validate_append(append_columns.inner_table())
+            .unwrap_err();
+        let violating_ids: Vec<_> = violations
+            .violations()
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(violating_ids, ["a", "b", "c"]);
+        assert!(matches!(
+            violations.violations()[2].1,
+            ValidationError::TypeMismatch { .. }
+        ));
+        assert!(violations
+            .to_string()
+            .starts_with("new rows violate the policies of 3 column(s): a: "));
+    }
+
+    #[test]
+    fn we_cannot_append_rows_that_violate_the_policies_of_the_columns_with_validation() {
+        let mut column_commitments = column_commitments_with_enforced_bounds();
+        let append_columns: OwnedTable<TestScalar> = owned_table([
+            bigint("a", [0, 1000]),
+            int("b", [-11, 0]),
+            varchar("c", ["x", "y"]),
+        ]);
+
+        let result = column_commitments
+            .clone()
+            .try_append_rows_with_offset_and_validation(append_columns.inner_table(), 2, &(), true);
+        assert!(matches!(
+            result,
+            Err(AppendColumnCommitmentsError::PolicyViolations { source }) if source.violations().len() == 2
+        ));
+
+        // Validation is opt-in, the observed bounds simply grow without it
+        column_commitments
+            .try_append_rows_with_offset(append_columns.inner_table(), 2, &())
+            .unwrap();
+        assert_eq!(
+            column_commitments
+                .get_metadata(&"a".into())
+                .unwrap()
+                .bounds(),
+            &ColumnBounds::BigInt(Bounds::sharp(0, 1000).unwrap())
+        );
+    }
+
     #[test]
     fn we_cannot_append_rows_to_mismatched_column_commitments() {
         let base_table: OwnedTable<TestScalar> = owned_table([
@@ -731,6 +1642,199 @@ mod tests {
         assert_eq!(column_commitments, expected_commitments);
     }
 
+    #[test]
+    fn we_can_compute_naive_commitments_to_constant_columns() {
+        crate::base::commitment::assert_constant_commitments_match_materialized::<NaiveCommitment>(
+            &(),
+            &[(0, 0), (0, 4), (1, 0), (5, 0), (5, 3), (100, 17)],
+        );
+    }
+
+    #[test]
+    fn we_can_extend_columns_with_default_values() {
+        let bigint_id: Ident = "bigint_column".into();
+        let default_id: Ident = "default_column".into();
+        for (num_rows, offset) in [(0, 0), (1, 0), (5, 0), (5, 3), (64, 10)] {
+            let initial_columns =
+                owned_table::<TestScalar>([bigint(bigint_id.value.as_str(), vec![3; num_rows])]);
+            let mut column_commitments =
+                ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                    initial_columns.inner_table(),
+                    offset,
+                    &(),
+                )
+                .unwrap();
+            // The default is coerced to the type of the column
+            column_commitments
+                .try_extend_columns_with_default(
+                    &default_id,
+                    ColumnType::BigInt,
+                    &LiteralValue::Int(-7),
+                    num_rows,
+                    offset,
+                    &(),
+                )
+                .unwrap();
+
+            let expected_columns = owned_table::<TestScalar>([
+                bigint(bigint_id.value.as_str(), vec![3; num_rows]),
+                bigint(default_id.value.as_str(), vec![-7; num_rows]),
+            ]);
+            let expected_commitments = ColumnCommitments::try_from_columns_with_offset(
+                expected_columns.inner_table(),
+                offset,
+                &(),
+            )
+            .unwrap();
+            assert_eq!(column_commitments, expected_commitments);
+
+            let expected_bounds = if num_rows == 0 {
+                Bounds::Empty
+            } else {
+                Bounds::sharp(-7, -7).unwrap()
+            };
+            assert_eq!(
+                column_commitments
+                    .get_metadata(&default_id)
+                    .unwrap()
+                    .bounds(),
+                &ColumnBounds::BigInt(expected_bounds)
+            );
+        }
+    }
+
+    #[test]
+    fn we_can_extend_columns_with_default_values_of_unordered_types() {
+        let default_id: Ident = "default_column".into();
+        let mut column_commitments = ColumnCommitments::<NaiveCommitment>::default();
+        column_commitments
+            .try_extend_columns_with_default(
+                &default_id,
+                ColumnType::VarChar,
+                &LiteralValue::VarChar("n/a".into()),
+                4,
+                2,
+                &(),
+            )
+            .unwrap();
+
+        let expected_columns =
+            owned_table::<TestScalar>([varchar(default_id.value.as_str(), ["n/a"; 4])]);
+        let expected_commitments =
+            ColumnCommitments::try_from_columns_with_offset(expected_columns.inner_table(), 2, &())
+                .unwrap();
+        assert_eq!(column_commitments, expected_commitments);
+        assert_eq!(
+            column_commitments
+                .get_metadata(&default_id)
+                .unwrap()
+                .bounds(),
+            &ColumnBounds::NoOrder
+        );
+    }
+
+    #[test]
+    fn we_cannot_extend_columns_with_invalid_default_values() {
+        let bigint_id: Ident = "bigint_column".into();
+        let initial_columns = owned_table::<TestScalar>([bigint(bigint_id.value.as_str(), [1])]);
+        let mut column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                initial_columns.inner_table(),
+                0,
+                &(),
+            )
+            .unwrap();
+        let expected_commitments = column_commitments.clone();
+
+        assert!(matches!(
+            column_commitments.try_extend_columns_with_default(
+                &bigint_id,
+                ColumnType::BigInt,
+                &LiteralValue::BigInt(0),
+                1,
+                0,
+                &()
+            ),
+            Err(ExtendColumnWithDefaultError::DuplicateIdents { .. })
+        ));
+        assert!(matches!(
+            column_commitments.try_extend_columns_with_default(
+                &Ident::new("a".repeat(1 << 20)),
+                ColumnType::BigInt,
+                &LiteralValue::BigInt(0),
+                1,
+                0,
+                &()
+            ),
+            Err(ExtendColumnWithDefaultError::InvalidIdent { .. })
+        ));
+        assert!(matches!(
+            column_commitments.try_extend_columns_with_default(
+                &"default_column".into(),
+                ColumnType::TinyInt,
+                &LiteralValue::BigInt(1000),
+                1,
+                0,
+                &()
+            ),
+            Err(ExtendColumnWithDefaultError::DefaultValue {
+                source: CoercionError::NotRepresentable { .. }
+            })
+        ));
+        assert!(matches!(
+            column_commitments.try_extend_columns_with_default(
+                &"default_column".into(),
+                ColumnType::Boolean,
+                &LiteralValue::BigInt(1),
+                1,
+                0,
+                &()
+            ),
+            Err(ExtendColumnWithDefaultError::DefaultValue {
+                source: CoercionError::IncompatibleTypes { .. }
+            })
+        ));
+        assert_eq!(column_commitments, expected_commitments);
+    }
+
+    #[test]
+    fn we_cannot_extend_columns_with_default_values_the_scheme_does_not_support() {
+        use crate::proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar;
+        use curve25519_dalek::RistrettoPoint;
+
+        let mut column_commitments = ColumnCommitments::<RistrettoPoint>::default();
+        assert!(matches!(
+            column_commitments.try_extend_columns_with_default(
+                &"int128_column".into(),
+                ColumnType::Int128,
+                &LiteralValue::Int128(1),
+                3,
+                0,
+                &()
+            ),
+            Err(ExtendColumnWithDefaultError::UnsupportedColumnType { .. })
+        ));
+        assert!(column_commitments.is_empty());
+
+        // Defaults of other types are committed to like materialized columns
+        column_commitments
+            .try_extend_columns_with_default(
+                &"bigint_column".into(),
+                ColumnType::BigInt,
+                &LiteralValue::BigInt(5),
+                3,
+                2,
+                &(),
+            )
+            .unwrap();
+        let expected_columns = owned_table::<Curve25519Scalar>([bigint("bigint_column", [5; 3])]);
+        assert_eq!(
+            column_commitments,
+            ColumnCommitments::try_from_columns_with_offset(expected_columns.inner_table(), 2, &())
+                .unwrap()
+        );
+    }
+
     #[test]
     fn we_can_add_column_commitments() {
         let bigint_id: Ident = "bigint_column".into();
@@ -952,4 +2056,115 @@ mod tests {
             Err(ColumnCommitmentsMismatch::NumColumns)
         ));
     }
+
+    #[test]
+    fn we_can_record_and_compose_the_provenance_of_column_commitments() {
+        let column_a: Ident = "column_a".into();
+        let table: OwnedTable<TestScalar> = owned_table([
+            bigint(column_a.value.as_str(), [1, 2, 3, 4, 5]),
+            varchar("column_b", ["Lorem", "ipsum", "dolor", "sit", "amet"]),
+        ]);
+        let expected_provenance =
+            |row_range| CommitmentProvenance::new("naive".to_string(), None, row_range);
+
+        let mut commitments = ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+            table.inner_table(),
+            3,
+            &(),
+        )
+        .unwrap();
+        assert_eq!(
+            commitments.get_metadata(&column_a).unwrap().provenance(),
+            Some(&expected_provenance(3..8))
+        );
+
+        // Appending rows composes the row ranges
+        commitments
+            .try_append_rows_with_offset(table.inner_table(), 8, &())
+            .unwrap();
+        assert_eq!(
+            commitments.get_metadata(&column_a).unwrap().provenance(),
+            Some(&expected_provenance(3..13))
+        );
+
+        // Subtracting a prefix shrinks them
+        let prefix =
+            ColumnCommitments::try_from_columns_with_offset(table.inner_table(), 3, &()).unwrap();
+        let commitments = commitments.try_sub(prefix).unwrap();
+        assert!(commitments
+            .column_metadata()
+            .values()
+            .all(|metadata| metadata.provenance() == Some(&expected_provenance(8..13))));
+
+        // Commitments to freshly added columns have their own row ranges
+        let mut commitments = commitments;
+        let new_columns: OwnedTable<TestScalar> = owned_table([bigint("column_c", [1, 2])]);
+        commitments
+            .try_extend_columns_with_offset(new_columns.inner_table(), 8, &())
+            .unwrap();
+        assert_eq!(
+            commitments
+                .get_metadata(&"column_c".into())
+                .unwrap()
+                .provenance(),
+            Some(&expected_provenance(8..10))
+        );
+    }
+
+    #[test]
+    fn we_cannot_operate_on_column_commitments_with_different_setups() {
+        let table: OwnedTable<TestScalar> = owned_table([bigint("column_a", [1, 2, 3, 4])]);
+        let commitments = ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+            table.inner_table(),
+            0,
+            &(),
+        )
+        .unwrap();
+        let mut commitments_other_setup = commitments.clone();
+        for metadata in commitments_other_setup.column_metadata_mut().values_mut() {
+            *metadata = metadata.clone().with_provenance(CommitmentProvenance::new(
+                "naive".to_string(),
+                Some([1; 32]),
+                0..4,
+            ));
+        }
+
+        let err = commitments
+            .clone()
+            .try_add(commitments_other_setup.clone())
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            ColumnCommitmentsMismatch::SetupMismatch { setup_a, setup_b }
+                if setup_a == "naive" && setup_b == &alloc::format!("naive ({})", "01".repeat(32))
+        ));
+        assert!(matches!(
+            commitments.clone().try_sub(commitments_other_setup.clone()),
+            Err(ColumnCommitmentsMismatch::SetupMismatch { .. })
+        ));
+        let mut appended_commitments = commitments_other_setup;
+        assert!(matches!(
+            appended_commitments.try_append_rows_with_offset(table.inner_table(), 4, &()),
+            Err(AppendColumnCommitmentsError::Mismatch {
+                source: ColumnCommitmentsMismatch::SetupMismatch { .. }
+            })
+        ));
+
+        // Commitments without provenance, e.g. ones deserialized from before it was recorded,
+        // can still be combined but lose their provenance
+        let mut commitments_without_provenance = commitments.clone();
+        for metadata in commitments_without_provenance
+            .column_metadata_mut()
+            .values_mut()
+        {
+            *metadata =
+                ColumnCommitmentMetadata::try_new(*metadata.column_type(), *metadata.bounds())
+                    .unwrap();
+        }
+        let sum = commitments.try_add(commitments_without_provenance).unwrap();
+        assert!(sum
+            .column_metadata()
+            .values()
+            .all(|metadata| metadata.provenance().is_none()));
+    }
 }
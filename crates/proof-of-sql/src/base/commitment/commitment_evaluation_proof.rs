@@ -30,6 +30,9 @@ pub trait CommitmentEvaluationProof {
         generators_offset: u64,
         setup: &Self::ProverPublicSetup<'_>,
     ) -> Self;
+    /// The number of bytes the proof takes up when serialized with bincode's legacy configuration,
+    /// computed from the number of elements in the proof without serializing it.
+    fn encoded_size_hint(&self) -> usize;
     /// Verify a proof.
     ///
     /// Note: `b_point` must have length `nu`, where `2^nu` is at least the length of `a`.
@@ -0,0 +1,273 @@
+use super::{
+    check_column_type_supported_by, column_commitments::check_for_invalid_idents,
+    validate_unique_idents, ColumnCommitments, ColumnCommitmentsFromColumnsError, Commitment,
+};
+use crate::base::database::OwnedColumn;
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+use proof_of_sql_parser::IdentifierLimits;
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// Errors that can occur when running a [`CommitmentJob`].
+#[derive(Debug, Snafu)]
+pub enum CommitmentJobError {
+    /// The job was cancelled before it completed.
+    #[snafu(display("commitment job was cancelled"))]
+    Cancelled,
+}
+
+/// The state shared between a [`CommitmentJob`] and the thread computing its commitments.
+struct JobState<C> {
+    result: Option<Result<ColumnCommitments<C>, CommitmentJobError>>,
+    waker: Option<Waker>,
+}
+
+struct SharedJobState<C> {
+    state: Mutex<JobState<C>>,
+    finished: Condvar,
+    cancelled: AtomicBool,
+}
+
+impl<C> SharedJobState<C> {
+    fn lock(&self) -> MutexGuard<'_, JobState<C>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    fn finish(&self, result: Result<ColumnCommitments<C>, CommitmentJobError>) {
+        let mut state = self.lock();
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        self.finished.notify_all();
+    }
+}
+
+/// A handle to [`ColumnCommitments`] being computed in the background.
+///
+/// The commitments are computed on the rayon thread pool, one column at a time, so that the
+/// submitting thread is never blocked. The handle can be polled with [`CommitmentJob::is_finished`],
+/// blocked on with [`CommitmentJob::wait`], or awaited.
+///
+/// The resulting [`ColumnCommitments`] are identical to those computed by
+/// [`ColumnCommitments::try_from_columns_with_offset`] for the same columns.
+pub struct CommitmentJob<C> {
+    shared: Arc<SharedJobState<C>>,
+}
+
+impl<C: Commitment + 'static> CommitmentJob<C>
+where
+    C::Scalar: 'static,
+    C::PublicSetup<'static>: Send,
+{
+    /// Submits the columns to be committed to in the background using the given generator offset.
+    ///
+    /// Idents and column types are validated before the job is submitted, so idents must be unique
+    /// and respect the default [`IdentifierLimits`] and the commitment scheme must support the
+    /// types of all columns.
+    pub fn try_spawn(
+        columns: Vec<(Ident, OwnedColumn<C::Scalar>)>,
+        offset: usize,
+        setup: C::PublicSetup<'static>,
+    ) -> Result<Self, ColumnCommitmentsFromColumnsError> {
+        validate_unique_idents(None, columns.iter().map(|(identifier, _)| identifier))?;
+        check_for_invalid_idents(
+            columns.iter().map(|(identifier, _)| identifier),
+            &IdentifierLimits::default(),
+        )?;
+        columns.iter().try_for_each(|(_, column)| {
+            check_column_type_supported_by::<C>(column.column_type())
+        })?;
+
+        let shared = Arc::new(SharedJobState {
+            state: Mutex::new(JobState {
+                result: None,
+                waker: None,
+            }),
+            finished: Condvar::new(),
+            cancelled: AtomicBool::new(false),
+        });
+        let job_shared = Arc::clone(&shared);
+        rayon::spawn(move || {
+            let result = commit_unless_cancelled(&columns, offset, &setup, &job_shared);
+            job_shared.finish(result);
+        });
+        Ok(Self { shared })
+    }
+}
+
+impl<C> CommitmentJob<C> {
+    /// Returns `true` once the job has stopped running and its result can be taken without blocking.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.shared.lock().result.is_some()
+    }
+
+    /// Cancels the job.
+    ///
+    /// Work in flight stops before the next column is committed to.
+    /// Once cancelled, the job always resolves to [`CommitmentJobError::Cancelled`], even if the
+    /// commitments happened to be completed.
+    pub fn cancel(&self) {
+        self.shared.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Blocks until the job completes and returns the commitments.
+    pub fn wait(self) -> Result<ColumnCommitments<C>, CommitmentJobError> {
+        let mut state = self.shared.lock();
+        loop {
+            if let Some(result) = state.result.take() {
+                return self.cancelled_or(result);
+            }
+            state = self
+                .shared
+                .finished
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    fn cancelled_or(
+        &self,
+        result: Result<ColumnCommitments<C>, CommitmentJobError>,
+    ) -> Result<ColumnCommitments<C>, CommitmentJobError> {
+        if self.shared.is_cancelled() {
+            Err(CommitmentJobError::Cancelled)
+        } else {
+            result
+        }
+    }
+}
+
+impl<C> Future for CommitmentJob<C> {
+    type Output = Result<ColumnCommitments<C>, CommitmentJobError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock();
+        if let Some(result) = state.result.take() {
+            drop(state);
+            Poll::Ready(self.cancelled_or(result))
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Commits to the columns one at a time, checking for cancellation between columns.
+fn commit_unless_cancelled<C: Commitment>(
+    columns: &[(Ident, OwnedColumn<C::Scalar>)],
+    offset: usize,
+    setup: &C::PublicSetup<'_>,
+    shared: &SharedJobState<C>,
+) -> Result<ColumnCommitments<C>, CommitmentJobError> {
+    let mut column_commitments = ColumnCommitments::<C>::default();
+    for (identifier, column) in columns {
+        if shared.is_cancelled() {
+            return Err(CommitmentJobError::Cancelled);
+        }
+        column_commitments
+            .try_extend_columns_with_offset([(identifier, column)], offset, setup)
+            .expect("idents were validated before the job was spawned");
+    }
+    Ok(column_commitments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{
+        commitment::naive_commitment::NaiveCommitment, database::owned_table_utility::*,
+        scalar::test_scalar::TestScalar,
+    };
+
+    fn sample_columns() -> Vec<(Ident, OwnedColumn<TestScalar>)> {
+        owned_table::<TestScalar>([
+            bigint("a", [1, 2, 3, 4]),
+            varchar("b", ["w", "x", "y", "z"]),
+            boolean("c", [true, false, true, true]),
+            decimal75("d", 12, 2, [100, 200, 300, 400]),
+        ])
+        .into_inner()
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn we_can_compute_commitments_in_the_background_identically_to_the_synchronous_path() {
+        let columns = sample_columns();
+        let expected = ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+            columns
+                .iter()
+                .map(|(identifier, column)| (identifier, column)),
+            2,
+            &(),
+        )
+        .unwrap();
+
+        let job = CommitmentJob::<NaiveCommitment>::try_spawn(columns, 2, ()).unwrap();
+        let column_commitments = job.wait().unwrap();
+        assert_eq!(column_commitments, expected);
+    }
+
+    #[test]
+    fn we_can_poll_a_commitment_job_until_it_finishes() {
+        let columns = sample_columns();
+        let expected = ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+            columns
+                .iter()
+                .map(|(identifier, column)| (identifier, column)),
+            0,
+            &(),
+        )
+        .unwrap();
+
+        let job = CommitmentJob::<NaiveCommitment>::try_spawn(columns, 0, ()).unwrap();
+        while !job.is_finished() {
+            std::thread::yield_now();
+        }
+        assert_eq!(job.wait().unwrap(), expected);
+    }
+
+    #[test]
+    fn we_can_commit_to_no_columns_in_the_background() {
+        let job = CommitmentJob::<NaiveCommitment>::try_spawn(Vec::new(), 0, ()).unwrap();
+        assert_eq!(job.wait().unwrap(), ColumnCommitments::default());
+    }
+
+    #[test]
+    fn we_cannot_get_commitments_from_a_cancelled_job() {
+        let job = CommitmentJob::<NaiveCommitment>::try_spawn(sample_columns(), 0, ()).unwrap();
+        job.cancel();
+        assert!(matches!(job.wait(), Err(CommitmentJobError::Cancelled)));
+
+        // Cancelling after the commitments are computed still discards them
+        let job = CommitmentJob::<NaiveCommitment>::try_spawn(sample_columns(), 0, ()).unwrap();
+        while !job.is_finished() {
+            std::thread::yield_now();
+        }
+        job.cancel();
+        assert!(matches!(job.wait(), Err(CommitmentJobError::Cancelled)));
+    }
+
+    #[test]
+    fn we_cannot_spawn_a_commitment_job_with_duplicate_idents() {
+        let mut columns = sample_columns();
+        columns.push(("a".into(), OwnedColumn::BigInt(vec![5, 6, 7, 8])));
+        assert!(matches!(
+            CommitmentJob::<NaiveCommitment>::try_spawn(columns, 0, ()),
+            Err(ColumnCommitmentsFromColumnsError::DuplicateIdents { .. })
+        ));
+    }
+}
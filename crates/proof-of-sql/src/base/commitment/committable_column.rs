@@ -1,5 +1,6 @@
+use super::Commitment;
 use crate::base::{
-    database::{Column, ColumnType, OwnedColumn},
+    database::{BooleanBitmap, Column, ColumnType, DeltaEncodedColumn, OwnedColumn},
     math::decimal::Precision,
     posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
     ref_into::RefInto,
@@ -8,6 +9,17 @@ use crate::base::{
 use alloc::vec::Vec;
 #[cfg(feature = "blitzar")]
 use blitzar::sequence::Sequence;
+use snafu::Snafu;
+
+/// The commitment scheme cannot commit to columns of this type.
+#[derive(Debug, Snafu, Clone, Copy, PartialEq, Eq)]
+#[snafu(display("the {backend} commitment scheme does not support {column_type} columns"))]
+pub struct UnsupportedColumnType {
+    /// The type of the rejected column
+    pub column_type: ColumnType,
+    /// The identifier of the commitment scheme, see [`Commitment::scheme_identifier`]
+    pub backend: &'static str,
+}
 
 /// Column data in "committable form".
 ///
@@ -25,6 +37,10 @@ use blitzar::sequence::Sequence;
 pub enum CommittableColumn<'a> {
     /// Borrowed Bool column, mapped to `bool`.
     Boolean(&'a [bool]),
+    /// Borrowed Bool column packed into a [`BooleanBitmap`], unpacked to `bool` while committing.
+    ///
+    /// `Column` and `OwnedColumn` are never converted to this variant, since they are not packed.
+    BooleanBitmap(&'a BooleanBitmap),
     /// Borrowed `Byte` column, mapped to `u8`.
     Uint8(&'a [u8]),
     /// Borrowed `TinyInt` column, mapped to `i8`.
@@ -35,6 +51,9 @@ pub enum CommittableColumn<'a> {
     Int(&'a [i32]),
     /// Borrowed `BigInt` column, mapped to `i64`.
     BigInt(&'a [i64]),
+    /// Borrowed `BigInt` column stored as a [`DeltaEncodedColumn`], decoded to its cumulative
+    /// values while committing.
+    DeltaEncoded(&'a DeltaEncodedColumn),
     /// Borrowed Int128 column, mapped to `i128`.
     Int128(&'a [i128]),
     /// Borrowed Decimal75(precision, scale, column), mapped to 'i256'
@@ -65,6 +84,8 @@ impl CommittableColumn<'_> {
             | CommittableColumn::VarChar(col)
             | CommittableColumn::VarBinary(col) => col.len(),
             CommittableColumn::Boolean(col) => col.len(),
+            CommittableColumn::BooleanBitmap(col) => col.len(),
+            CommittableColumn::DeltaEncoded(col) => col.len(),
         }
     }
 
@@ -74,11 +95,142 @@ impl CommittableColumn<'_> {
         self.len() == 0
     }
 
+    /// Returns the number of generators a setup must have to commit to the column with the given
+    /// generator offset, i.e. one more than the index of the generator of its last row.
+    ///
+    /// Compare this with [`Commitment::num_generators`](super::Commitment::num_generators) of a
+    /// setup to check whether the setup is large enough.
+    #[must_use]
+    pub fn generators_needed(&self, offset: usize) -> usize {
+        offset + self.len()
+    }
+
     /// Returns the type of the column.
     #[must_use]
     pub fn column_type(&self) -> ColumnType {
         self.into()
     }
+
+    /// Returns an error if the commitment scheme `C` cannot commit to columns of this type.
+    pub fn check_supported_by<C: Commitment>(&self) -> Result<(), UnsupportedColumnType> {
+        check_column_type_supported_by::<C>(self.column_type())
+    }
+}
+
+/// Returns an error if the commitment scheme `C` cannot commit to columns of `column_type`.
+pub(crate) fn check_column_type_supported_by<C: Commitment>(
+    column_type: ColumnType,
+) -> Result<(), UnsupportedColumnType> {
+    if C::supports_column_type(column_type) {
+        Ok(())
+    } else {
+        Err(UnsupportedColumnType {
+            column_type,
+            backend: C::scheme_identifier(),
+        })
+    }
+}
+
+/// One column type of every kind, to enumerate the types a commitment scheme supports in tests.
+#[cfg(test)]
+pub(crate) fn column_types_for_testing() -> Vec<ColumnType> {
+    alloc::vec![
+        ColumnType::Boolean,
+        ColumnType::Uint8,
+        ColumnType::TinyInt,
+        ColumnType::SmallInt,
+        ColumnType::Int,
+        ColumnType::BigInt,
+        ColumnType::Int128,
+        ColumnType::VarChar,
+        ColumnType::Decimal75(Precision::new(75).unwrap(), -2),
+        ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc()),
+        ColumnType::Scalar,
+        ColumnType::VarBinary,
+    ]
+}
+
+/// One literal of every column type, to compare constant commitments with materialized columns.
+#[cfg(test)]
+fn literal_values_for_testing() -> Vec<crate::base::database::LiteralValue> {
+    use crate::base::{database::LiteralValue, math::i256::I256};
+    alloc::vec![
+        LiteralValue::Boolean(true),
+        LiteralValue::Uint8(200),
+        LiteralValue::TinyInt(-3),
+        LiteralValue::SmallInt(-300),
+        LiteralValue::Int(70_000),
+        LiteralValue::BigInt(-7),
+        LiteralValue::Int128(1 << 100),
+        LiteralValue::VarChar("n/a".into()),
+        LiteralValue::Decimal75(Precision::new(10).unwrap(), 2, I256::from(-12_345)),
+        LiteralValue::TimeStampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), 1_700_000_000),
+        LiteralValue::Scalar([1, 2, 3, 4]),
+        LiteralValue::VarBinary(alloc::vec![0, 1, 255]),
+    ]
+}
+
+/// Asserts that [`Commitment::compute_constant_commitment`] computes the commitments to the
+/// materialized constant columns, for literals of every supported type and each of the
+/// `(num_rows, offset)` pairs.
+#[cfg(test)]
+pub(crate) fn assert_constant_commitments_match_materialized<C: Commitment>(
+    setup: &C::PublicSetup<'_>,
+    lengths_and_offsets: &[(usize, usize)],
+) {
+    let alloc = bumpalo::Bump::new();
+    for value in literal_values_for_testing()
+        .iter()
+        .filter(|value| C::supports_column_type(value.column_type()))
+    {
+        for &(num_rows, offset) in lengths_and_offsets {
+            let column = Column::<C::Scalar>::from_literal_with_length(value, num_rows, &alloc);
+            let expected = C::compute_commitments(&[(&column).into()], offset, setup);
+            assert_eq!(
+                C::compute_constant_commitment(value.to_scalar(), num_rows, offset, setup),
+                expected.into_iter().next(),
+                "{value:?} in {num_rows} rows at offset {offset}"
+            );
+        }
+    }
+}
+
+/// Asserts that the commitments to signed integer columns holding negative values are the
+/// commitments to the scalars that [`Column::to_scalar`] evaluates them to, at each of `offsets`.
+#[cfg(test)]
+pub(crate) fn assert_signed_commitments_match_scalars<C: Commitment>(
+    setup: &C::PublicSetup<'_>,
+    offsets: &[usize],
+) {
+    let columns: [Column<C::Scalar>; 6] = [
+        Column::TinyInt(&[-5, 0, i8::MIN, i8::MAX, -1]),
+        Column::SmallInt(&[-5, 0, i16::MIN, i16::MAX, -1]),
+        Column::Int(&[-5, 0, i32::MIN, i32::MAX, -1]),
+        Column::BigInt(&[-5, 0, i64::MIN, i64::MAX, -1]),
+        Column::Int128(&[-5, 0, i128::MIN, i128::MAX, -1]),
+        Column::TimestampTZ(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            &[-5, 0, i64::MIN, i64::MAX, -1],
+        ),
+    ];
+    for column in columns
+        .into_iter()
+        .filter(|column| C::supports_column_type(column.column_type()))
+    {
+        let column_type = column.column_type();
+        let scalars = column.to_scalar();
+        // Negative values are the negations of their magnitudes, not their two's complement bits
+        assert_eq!(scalars[0], -C::Scalar::from(5u8), "{column_type}");
+        let limbs: Vec<[u64; 4]> = scalars.iter().map(RefInto::ref_into).collect();
+        for &offset in offsets {
+            assert_eq!(
+                C::compute_commitments(&[(&column).into()], offset, setup),
+                C::compute_commitments(&[CommittableColumn::Scalar(limbs.clone())], offset, setup),
+                "{column_type} at offset {offset}"
+            );
+        }
+    }
 }
 
 impl<'a> From<&CommittableColumn<'a>> for ColumnType {
@@ -88,7 +240,7 @@ impl<'a> From<&CommittableColumn<'a>> for ColumnType {
             CommittableColumn::TinyInt(_) => ColumnType::TinyInt,
             CommittableColumn::SmallInt(_) => ColumnType::SmallInt,
             CommittableColumn::Int(_) => ColumnType::Int,
-            CommittableColumn::BigInt(_) => ColumnType::BigInt,
+            CommittableColumn::BigInt(_) | CommittableColumn::DeltaEncoded(_) => ColumnType::BigInt,
             CommittableColumn::Int128(_) => ColumnType::Int128,
             CommittableColumn::Decimal75(precision, scale, _) => {
                 ColumnType::Decimal75(*precision, *scale)
@@ -96,7 +248,9 @@ impl<'a> From<&CommittableColumn<'a>> for ColumnType {
             CommittableColumn::Scalar(_) => ColumnType::Scalar,
             CommittableColumn::VarChar(_) => ColumnType::VarChar,
             CommittableColumn::VarBinary(_) => ColumnType::VarBinary,
-            CommittableColumn::Boolean(_) => ColumnType::Boolean,
+            CommittableColumn::Boolean(_) | CommittableColumn::BooleanBitmap(_) => {
+                ColumnType::Boolean
+            }
             CommittableColumn::TimestampTZ(tu, tz, _) => ColumnType::TimestampTZ(*tu, *tz),
         }
     }
@@ -219,7 +373,22 @@ impl<'a> From<&'a [bool]> for CommittableColumn<'a> {
         CommittableColumn::Boolean(value)
     }
 }
+impl<'a> From<&'a BooleanBitmap> for CommittableColumn<'a> {
+    fn from(value: &'a BooleanBitmap) -> Self {
+        CommittableColumn::BooleanBitmap(value)
+    }
+}
+impl<'a> From<&'a DeltaEncodedColumn> for CommittableColumn<'a> {
+    fn from(value: &'a DeltaEncodedColumn) -> Self {
+        CommittableColumn::DeltaEncoded(value)
+    }
+}
 
+/// # Panics
+///
+/// Panics on [`CommittableColumn::BooleanBitmap`] and [`CommittableColumn::DeltaEncoded`],
+/// since blitzar can only borrow unpacked data.
+/// Use [`with_sequences`] to convert columns that may be packed or delta encoded.
 #[cfg(feature = "blitzar")]
 impl<'a, 'b> From<&'a CommittableColumn<'b>> for Sequence<'a> {
     fn from(value: &'a CommittableColumn<'b>) -> Self {
@@ -235,11 +404,52 @@ impl<'a, 'b> From<&'a CommittableColumn<'b>> for Sequence<'a> {
             | CommittableColumn::VarChar(limbs)
             | CommittableColumn::VarBinary(limbs) => Sequence::from(limbs),
             CommittableColumn::Boolean(bools) => Sequence::from(*bools),
+            CommittableColumn::BooleanBitmap(_) => {
+                panic!("packed boolean columns must be unpacked before converting to a Sequence")
+            }
+            CommittableColumn::DeltaEncoded(_) => {
+                panic!("delta encoded columns must be decoded before converting to a Sequence")
+            }
             CommittableColumn::TimestampTZ(_, _, times) => Sequence::from(*times),
         }
     }
 }
 
+/// Converts the columns to blitzar [`Sequence`]s and passes them to `f`.
+///
+/// Any [`CommittableColumn::BooleanBitmap`] is unpacked and any [`CommittableColumn::DeltaEncoded`]
+/// is decoded for the duration of the call, while all other columns are borrowed as they are.
+#[cfg(feature = "blitzar")]
+pub(crate) fn with_sequences<R>(
+    committable_columns: &[CommittableColumn],
+    f: impl FnOnce(&[Sequence]) -> R,
+) -> R {
+    let unpacked: Vec<Option<Vec<bool>>> = committable_columns
+        .iter()
+        .map(|column| match column {
+            CommittableColumn::BooleanBitmap(bitmap) => Some(bitmap.to_vec()),
+            _ => None,
+        })
+        .collect();
+    let decoded: Vec<Option<Vec<i64>>> = committable_columns
+        .iter()
+        .map(|column| match column {
+            CommittableColumn::DeltaEncoded(deltas) => Some(deltas.to_vec()),
+            _ => None,
+        })
+        .collect();
+    let sequences: Vec<Sequence> = committable_columns
+        .iter()
+        .zip(unpacked.iter().zip(&decoded))
+        .map(|(column, unpacked_or_decoded)| match unpacked_or_decoded {
+            (Some(bools), _) => Sequence::from(bools.as_slice()),
+            (_, Some(ints)) => Sequence::from(ints.as_slice()),
+            (None, None) => column.into(),
+        })
+        .collect();
+    f(&sequences)
+}
+
 #[cfg(all(test, feature = "blitzar"))]
 mod tests {
     use super::*;
@@ -515,6 +725,38 @@ mod tests {
         assert_eq!(bool_committable_column.column_type(), ColumnType::Boolean);
     }
 
+    #[test]
+    fn we_can_get_type_and_length_of_boolean_bitmap_column() {
+        // empty case
+        let bitmap = BooleanBitmap::new();
+        let bool_committable_column = CommittableColumn::from(&bitmap);
+        assert_eq!(bool_committable_column.len(), 0);
+        assert!(bool_committable_column.is_empty());
+        assert_eq!(bool_committable_column.column_type(), ColumnType::Boolean);
+
+        let bitmap = BooleanBitmap::from([true, false, true].as_slice());
+        let bool_committable_column = CommittableColumn::from(&bitmap);
+        assert_eq!(bool_committable_column.len(), 3);
+        assert!(!bool_committable_column.is_empty());
+        assert_eq!(bool_committable_column.column_type(), ColumnType::Boolean);
+    }
+
+    #[test]
+    fn we_can_get_type_and_length_of_delta_encoded_column() {
+        // empty case
+        let deltas = DeltaEncodedColumn::new();
+        let bigint_committable_column = CommittableColumn::from(&deltas);
+        assert_eq!(bigint_committable_column.len(), 0);
+        assert!(bigint_committable_column.is_empty());
+        assert_eq!(bigint_committable_column.column_type(), ColumnType::BigInt);
+
+        let deltas = DeltaEncodedColumn::try_from_values(&[5, 7, 6]).unwrap();
+        let bigint_committable_column = CommittableColumn::from(&deltas);
+        assert_eq!(bigint_committable_column.len(), 3);
+        assert!(!bigint_committable_column.is_empty());
+        assert_eq!(bigint_committable_column.column_type(), ColumnType::BigInt);
+    }
+
     #[test]
     fn we_can_get_length_of_uint8_column() {
         // empty case
@@ -527,6 +769,16 @@ mod tests {
         assert!(!bool_committable_column.is_empty());
     }
 
+    #[test]
+    fn we_can_get_the_generators_needed_by_a_column() {
+        let column = CommittableColumn::BigInt(&[1, 2, 3]);
+        assert_eq!(column.generators_needed(0), 3);
+        assert_eq!(column.generators_needed(5), 8);
+        let column = CommittableColumn::Boolean(&[]);
+        assert_eq!(column.generators_needed(0), 0);
+        assert_eq!(column.generators_needed(5), 5);
+    }
+
     #[test]
     fn we_can_convert_from_borrowing_timestamp_column() {
         // empty case
@@ -1062,6 +1314,67 @@ mod tests {
         assert_eq!(commitment_buffer[0], commitment_buffer[1]);
     }
 
+    #[test]
+    fn we_can_commit_to_boolean_bitmap_column_through_with_sequences() {
+        let values: Vec<bool> = (0..100).map(|i| i % 3 == 1).collect();
+        let bitmap = BooleanBitmap::from(values.as_slice());
+        let ints = [1_i64, -2, 3];
+        let committable_columns = [
+            CommittableColumn::BooleanBitmap(&bitmap),
+            CommittableColumn::BigInt(&ints),
+            CommittableColumn::Boolean(&values),
+        ];
+
+        let mut commitment_buffer = [CompressedRistretto::default(); 3];
+        with_sequences(&committable_columns, |sequences| {
+            compute_curve25519_commitments(&mut commitment_buffer, sequences, 0);
+        });
+
+        let mut expected_buffer = [CompressedRistretto::default(); 2];
+        compute_curve25519_commitments(
+            &mut expected_buffer,
+            &[
+                Sequence::from(values.as_slice()),
+                Sequence::from(ints.as_slice()),
+            ],
+            0,
+        );
+        assert_eq!(commitment_buffer[0], expected_buffer[0]);
+        assert_eq!(commitment_buffer[1], expected_buffer[1]);
+        assert_eq!(commitment_buffer[2], expected_buffer[0]);
+    }
+
+    #[test]
+    fn we_can_commit_to_delta_encoded_column_through_with_sequences() {
+        let values: Vec<i64> = (0..100).map(|i| 1_700_000_000_000 + 1_000 * i).collect();
+        let deltas = DeltaEncodedColumn::try_from_values(&values).unwrap();
+        let bools = [true, false, true];
+        let bitmap = BooleanBitmap::from(bools.as_slice());
+        let committable_columns = [
+            CommittableColumn::DeltaEncoded(&deltas),
+            CommittableColumn::BooleanBitmap(&bitmap),
+            CommittableColumn::BigInt(&values),
+        ];
+
+        let mut commitment_buffer = [CompressedRistretto::default(); 3];
+        with_sequences(&committable_columns, |sequences| {
+            compute_curve25519_commitments(&mut commitment_buffer, sequences, 0);
+        });
+
+        let mut expected_buffer = [CompressedRistretto::default(); 2];
+        compute_curve25519_commitments(
+            &mut expected_buffer,
+            &[
+                Sequence::from(values.as_slice()),
+                Sequence::from(bools.as_slice()),
+            ],
+            0,
+        );
+        assert_eq!(commitment_buffer[0], expected_buffer[0]);
+        assert_eq!(commitment_buffer[1], expected_buffer[1]);
+        assert_eq!(commitment_buffer[2], expected_buffer[0]);
+    }
+
     #[test]
     fn we_can_commit_to_boolean_column_through_committable_column() {
         // empty case
@@ -1,5 +1,5 @@
 //! Types for creation and utilization of cryptographic commitments to proof-of-sql data.
-use crate::base::scalar::Scalar;
+use crate::base::{database::ColumnType, scalar::Scalar};
 use alloc::vec::Vec;
 #[cfg(feature = "blitzar")]
 pub use blitzar::{
@@ -8,16 +8,28 @@ pub use blitzar::{
 };
 use core::ops::{AddAssign, SubAssign};
 mod committable_column;
-pub use committable_column::CommittableColumn;
+pub(crate) use committable_column::check_column_type_supported_by;
+#[cfg(feature = "blitzar")]
+pub(crate) use committable_column::with_sequences;
+#[cfg(test)]
+pub(crate) use committable_column::{
+    assert_constant_commitments_match_materialized, assert_signed_commitments_match_scalars,
+    column_types_for_testing,
+};
+pub use committable_column::{CommittableColumn, UnsupportedColumnType};
 
 mod vec_commitment_ext;
-pub use vec_commitment_ext::{NumColumnsMismatch, VecCommitmentExt};
+pub use vec_commitment_ext::{
+    ColumnSegment, NumColumnsMismatch, SegmentDeltaError, VecCommitmentExt,
+};
 
 mod column_bounds;
 pub use column_bounds::{Bounds, ColumnBounds, NegativeBounds};
 
 mod column_commitment_metadata;
-pub use column_commitment_metadata::ColumnCommitmentMetadata;
+pub use column_commitment_metadata::{
+    ColumnCommitmentMetadata, CommitmentProvenance, ValidationError,
+};
 
 mod column_commitment_metadata_map;
 pub use column_commitment_metadata_map::{
@@ -25,16 +37,28 @@ pub use column_commitment_metadata_map::{
 };
 
 mod column_commitments;
-pub use column_commitments::{AppendColumnCommitmentsError, ColumnCommitments, DuplicateIdents};
+pub use column_commitments::{
+    validate_unique_idents, AppendColumnCommitmentsError, ColumnCommitments,
+    ColumnCommitmentsFromColumnsError, ColumnPolicyViolations, DuplicateIdentLocation,
+    DuplicateIdents, ExtendColumnWithDefaultError, InvalidIdent, UpdateColumnCommitmentsError,
+};
+
+#[cfg(feature = "rayon")]
+mod commitment_job;
+#[cfg(feature = "rayon")]
+pub use commitment_job::{CommitmentJob, CommitmentJobError};
 
 mod table_commitment;
 pub use table_commitment::{
     AppendTableCommitmentError, MixedLengthColumns, NegativeRange, TableCommitment,
-    TableCommitmentArithmeticError, TableCommitmentFromColumnsError,
+    TableCommitmentArithmeticError, TableCommitmentFromColumnsError, UpdateTableCommitmentError,
 };
 
+mod table_equality;
+pub use table_equality::{prove_tables_equal, TableEqualityAttestation, TableEqualityError};
+
 mod query_commitments;
-pub use query_commitments::{QueryCommitments, QueryCommitmentsExt};
+pub use query_commitments::{QueryCommitments, QueryCommitmentsError, QueryCommitmentsExt};
 
 /// Module for providing a mock commitment.
 #[cfg(test)]
@@ -89,6 +113,56 @@ pub trait Commitment:
     ///
     /// This is also useful for serialization purposes.
     fn to_transcript_bytes(&self) -> Vec<u8>;
+
+    /// The number of bytes the commitment takes up when serialized with bincode's legacy
+    /// configuration, computed without serializing it.
+    fn encoded_size_hint(&self) -> usize;
+
+    /// An identifier of the commitment scheme, recorded in the provenance of column commitments.
+    fn scheme_identifier() -> &'static str {
+        core::any::type_name::<Self>()
+    }
+
+    /// A digest identifying the public setup, recorded in the provenance of column commitments.
+    ///
+    /// Commitments computed with setups with different digests cannot be combined.
+    /// Returns `None` for schemes without a setup to distinguish.
+    fn setup_digest(_setup: &Self::PublicSetup<'_>) -> Option<[u8; 32]> {
+        None
+    }
+
+    /// The number of generators `setup` provides, i.e. the largest number of generators, counting
+    /// the offset, that columns can be committed to and opened with.
+    ///
+    /// Compare this with [`CommittableColumn::generators_needed`] to check whether a setup is
+    /// large enough. Returns `None` for schemes whose generators are not limited by a setup.
+    fn num_generators(_setup: &Self::PublicSetup<'_>) -> Option<usize> {
+        None
+    }
+
+    /// Whether the commitment scheme can commit to columns of the given type.
+    ///
+    /// [`Self::compute_commitments`] may panic or produce meaningless commitments for columns of
+    /// unsupported types, so fallible constructors such as
+    /// [`ColumnCommitments::try_from_columns_with_offset`] reject them up front.
+    fn supports_column_type(_column_type: ColumnType) -> bool {
+        true
+    }
+
+    /// Compute the commitment to a column of `num_rows` copies of `value`, using the given
+    /// generator offset.
+    ///
+    /// This is the commitment [`Self::compute_commitments`] computes for such a column, but it is
+    /// derived from sums of the generators of the rows, without materializing the column.
+    /// Returns `None` for schemes that cannot compute it this way.
+    fn compute_constant_commitment(
+        _value: Self::Scalar,
+        _num_rows: usize,
+        _offset: usize,
+        _setup: &Self::PublicSetup<'_>,
+    ) -> Option<Self> {
+        None
+    }
 }
 
 mod commitment_evaluation_proof;
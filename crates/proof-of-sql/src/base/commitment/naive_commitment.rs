@@ -1,6 +1,7 @@
 use super::Commitment;
 use crate::base::{
     commitment::CommittableColumn,
+    encoded_size::{seq_size, SCALAR_SIZE},
     scalar::{test_scalar::TestScalar, Scalar},
 };
 use alloc::{vec, vec::Vec};
@@ -109,6 +110,10 @@ impl Commitment for NaiveCommitment {
     type Scalar = TestScalar;
     type PublicSetup<'a> = ();
 
+    fn scheme_identifier() -> &'static str {
+        "naive"
+    }
+
     fn compute_commitments(
         committable_columns: &[CommittableColumn],
         offset: usize,
@@ -122,6 +127,12 @@ impl Commitment for NaiveCommitment {
                     CommittableColumn::Boolean(bool_vec) => {
                         bool_vec.iter().map(core::convert::Into::into).collect()
                     }
+                    CommittableColumn::BooleanBitmap(bitmap) => {
+                        bitmap.iter().map(core::convert::Into::into).collect()
+                    }
+                    CommittableColumn::DeltaEncoded(deltas) => {
+                        deltas.iter().map(core::convert::Into::into).collect()
+                    }
                     CommittableColumn::Uint8(u8_vec) => {
                         u8_vec.iter().map(core::convert::Into::into).collect()
                     }
@@ -161,11 +172,26 @@ impl Commitment for NaiveCommitment {
             .collect()
     }
 
+    fn compute_constant_commitment(
+        value: TestScalar,
+        num_rows: usize,
+        offset: usize,
+        _setup: &Self::PublicSetup<'_>,
+    ) -> Option<Self> {
+        let mut vectors = vec![TestScalar::ZERO; offset];
+        vectors.resize(offset + num_rows, value);
+        Some(NaiveCommitment(vectors))
+    }
+
     fn to_transcript_bytes(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(self.0.compressed_size());
         self.0.serialize_compressed(&mut buf).unwrap();
         buf
     }
+
+    fn encoded_size_hint(&self) -> usize {
+        seq_size(self.0.len(), SCALAR_SIZE)
+    }
 }
 
 #[expect(clippy::similar_names)]
@@ -236,4 +262,14 @@ mod tests {
             commitment2.to_transcript_bytes()
         );
     }
+
+    #[test]
+    fn naive_commitments_support_every_column_type() {
+        for column_type in crate::base::commitment::column_types_for_testing() {
+            assert!(
+                NaiveCommitment::supports_column_type(column_type),
+                "{column_type}"
+            );
+        }
+    }
 }
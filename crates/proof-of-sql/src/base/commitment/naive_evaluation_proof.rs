@@ -1,5 +1,6 @@
 use super::{naive_commitment::NaiveCommitment, CommitmentEvaluationProof};
 use crate::base::{
+    encoded_size::{seq_size, SCALAR_SIZE},
     polynomial::compute_evaluation_vector,
     proof::Transcript,
     scalar::{test_scalar::TestScalar, Scalar},
@@ -51,6 +52,11 @@ impl CommitmentEvaluationProof for NaiveEvaluationProof {
         result
     }
 
+    /// The size the proof would have if it derived `Serialize`.
+    fn encoded_size_hint(&self) -> usize {
+        self.a.encoded_size_hint() + seq_size(self.b_point.len(), SCALAR_SIZE) + 32
+    }
+
     fn verify_batched_proof(
         &self,
         transcript: &mut impl Transcript,
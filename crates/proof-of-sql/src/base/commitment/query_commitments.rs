@@ -7,6 +7,7 @@ use crate::base::{
     map::IndexMap,
 };
 use alloc::vec::Vec;
+use snafu::Snafu;
 use sqlparser::ast::Ident;
 
 /// The commitments for all of the tables in a query.
@@ -17,6 +18,45 @@ use sqlparser::ast::Ident;
 /// - [`SchemaAccessor`]
 pub type QueryCommitments<C> = IndexMap<TableRef, TableCommitment<C>>;
 
+/// Errors that occur when [`QueryCommitments`] do not cover the tables and columns of a query.
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum QueryCommitmentsError {
+    /// A table of the query has no commitment.
+    #[snafu(display("no commitment for table {table_ref}"))]
+    MissingTable {
+        /// The table without a commitment
+        table_ref: TableRef,
+    },
+    /// A column of the query has no commitment.
+    #[snafu(display("no commitment for column {column_id} of table {table_ref}"))]
+    MissingColumn {
+        /// The table of the column
+        table_ref: TableRef,
+        /// The column without a commitment
+        column_id: Ident,
+    },
+    /// A column of the query has a commitment to a column of a different type.
+    #[snafu(display(
+        "column {column_id} of table {table_ref} is committed as {actual} but the query expects {expected}"
+    ))]
+    ColumnTypeMismatch {
+        /// The table of the column
+        table_ref: TableRef,
+        /// The column with the wrong type
+        column_id: Ident,
+        /// The type the query expects
+        expected: ColumnType,
+        /// The type of the committed column
+        actual: ColumnType,
+    },
+    /// The commitment of a table has a range that ends before it starts.
+    #[snafu(display("commitment for table {table_ref} has a negative range"))]
+    NegativeRange {
+        /// The table with the invalid commitment
+        table_ref: TableRef,
+    },
+}
+
 /// A trait for extending the functionality of the [`QueryCommitments`] alias.
 pub trait QueryCommitmentsExt<C>
 where
@@ -27,6 +67,16 @@ where
         columns: impl IntoIterator<Item = ColumnRef>,
         accessor: &(impl CommitmentAccessor<C> + SchemaAccessor),
     ) -> Self;
+
+    /// Check that there is a valid commitment for every given table and column.
+    ///
+    /// The accessor implementations of [`QueryCommitments`] panic on missing tables and columns,
+    /// so this should be called before using commitments from an untrusted source as an accessor.
+    fn try_validate_references(
+        &self,
+        table_refs: impl IntoIterator<Item = TableRef>,
+        column_refs: impl IntoIterator<Item = ColumnRef>,
+    ) -> Result<(), QueryCommitmentsError>;
 }
 
 impl<C: Commitment> QueryCommitmentsExt<C> for QueryCommitments<C> {
@@ -67,6 +117,47 @@ impl<C: Commitment> QueryCommitmentsExt<C> for QueryCommitments<C> {
             })
             .collect()
     }
+
+    fn try_validate_references(
+        &self,
+        table_refs: impl IntoIterator<Item = TableRef>,
+        column_refs: impl IntoIterator<Item = ColumnRef>,
+    ) -> Result<(), QueryCommitmentsError> {
+        for table_ref in table_refs {
+            let table_commitment =
+                self.get(&table_ref)
+                    .ok_or_else(|| QueryCommitmentsError::MissingTable {
+                        table_ref: table_ref.clone(),
+                    })?;
+            if table_commitment.range().start > table_commitment.range().end {
+                return Err(QueryCommitmentsError::NegativeRange { table_ref });
+            }
+        }
+        for column_ref in column_refs {
+            let table_ref = column_ref.table_ref();
+            let column_id = column_ref.column_id();
+            let metadata = self
+                .get(&table_ref)
+                .ok_or_else(|| QueryCommitmentsError::MissingTable {
+                    table_ref: table_ref.clone(),
+                })?
+                .column_commitments()
+                .get_metadata(&column_id)
+                .ok_or_else(|| QueryCommitmentsError::MissingColumn {
+                    table_ref: table_ref.clone(),
+                    column_id: column_id.clone(),
+                })?;
+            if metadata.column_type() != column_ref.column_type() {
+                return Err(QueryCommitmentsError::ColumnTypeMismatch {
+                    table_ref,
+                    column_id,
+                    expected: *column_ref.column_type(),
+                    actual: *metadata.column_type(),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<C: Commitment> MetadataAccessor for QueryCommitments<C> {
@@ -1,6 +1,9 @@
 use super::{
-    committable_column::CommittableColumn, AppendColumnCommitmentsError, ColumnCommitments,
-    ColumnCommitmentsMismatch, Commitment, DuplicateIdents,
+    column_commitments::{check_for_invalid_idents, validate_unique_idents},
+    committable_column::{CommittableColumn, UnsupportedColumnType},
+    AppendColumnCommitmentsError, ColumnCommitments, ColumnCommitmentsFromColumnsError,
+    ColumnCommitmentsMismatch, Commitment, DuplicateIdents, InvalidIdent,
+    UpdateColumnCommitmentsError,
 };
 use crate::base::{
     database::{ColumnField, CommitmentAccessor, OwnedTable, TableRef},
@@ -8,6 +11,7 @@ use crate::base::{
 };
 use alloc::vec::Vec;
 use core::ops::Range;
+use proof_of_sql_parser::IdentifierLimits;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use sqlparser::ast::Ident;
@@ -37,6 +41,28 @@ pub enum TableCommitmentFromColumnsError {
         /// The underlying source error
         source: DuplicateIdents,
     },
+    /// Cannot construct [`TableCommitment`] from columns with invalid idents.
+    #[snafu(transparent)]
+    InvalidIdent {
+        /// The underlying source error
+        source: InvalidIdent,
+    },
+    /// Cannot construct [`TableCommitment`] from columns the commitment scheme does not support.
+    #[snafu(transparent)]
+    UnsupportedColumnType {
+        /// The underlying source error
+        source: UnsupportedColumnType,
+    },
+}
+
+impl From<ColumnCommitmentsFromColumnsError> for TableCommitmentFromColumnsError {
+    fn from(error: ColumnCommitmentsFromColumnsError) -> Self {
+        match error {
+            ColumnCommitmentsFromColumnsError::DuplicateIdents { source } => source.into(),
+            ColumnCommitmentsFromColumnsError::InvalidIdent { source } => source.into(),
+            ColumnCommitmentsFromColumnsError::UnsupportedColumnType { source } => source.into(),
+        }
+    }
 }
 
 /// Errors that can occur when attempting to append rows to a [`TableCommitment`].
@@ -56,6 +82,35 @@ pub enum AppendTableCommitmentError {
     },
 }
 
+/// Errors that can occur when attempting to update rows of a [`TableCommitment`].
+#[derive(Debug, Snafu)]
+pub enum UpdateTableCommitmentError {
+    /// Cannot update rows with columns of mixed length.
+    #[snafu(transparent)]
+    MixedLengthColumns {
+        /// The underlying source error
+        source: MixedLengthColumns,
+    },
+    /// Cannot update rows outside of the [`TableCommitment`]'s range.
+    #[snafu(display(
+        "cannot update {num_rows} rows starting at row {start_row} of a TableCommitment with range {range:?}"
+    ))]
+    RowsOutOfRange {
+        /// The first row being updated
+        start_row: usize,
+        /// The number of rows being updated
+        num_rows: usize,
+        /// The range of the [`TableCommitment`]
+        range: Range<usize>,
+    },
+    /// Encountered error when updating internal [`ColumnCommitments`].
+    #[snafu(transparent)]
+    UpdateColumnCommitments {
+        /// The underlying source error
+        source: UpdateColumnCommitmentsError,
+    },
+}
+
 /// Errors that can occur when performing arithmetic on [`TableCommitment`]s.
 #[derive(Debug, Snafu)]
 pub enum TableCommitmentArithmeticError {
@@ -156,9 +211,20 @@ impl<C: Commitment> TableCommitment<C> {
         self.range.len()
     }
 
+    /// Returns the index of the generator of the last committed row, or `None` if no rows have
+    /// been committed to.
+    ///
+    /// Appending to or updating these commitments needs a setup with more generators than this,
+    /// see [`Commitment::num_generators`].
+    #[must_use]
+    pub fn max_generator_index(&self) -> Option<usize> {
+        self.range.clone().last()
+    }
+
     /// Returns a [`TableCommitment`] to the provided columns with the given row offset.
     ///
-    /// Provided columns must have the same length and no duplicate idents.
+    /// Provided columns must have the same length, no duplicate or invalid idents and types the
+    /// commitment scheme supports.
     pub fn try_from_columns_with_offset<'a, COL>(
         columns: impl IntoIterator<Item = (&'a Ident, COL)>,
         offset: usize,
@@ -186,11 +252,38 @@ impl<C: Commitment> TableCommitment<C> {
         })
     }
 
+    /// Runs the non-cryptographic checks of [`Self::try_from_columns_with_offset`] on the provided columns.
+    ///
+    /// This is cheap compared to computing commitments, so it can be used to fail fast on large batches.
+    /// Provided columns must have the same length, no duplicate or invalid idents and types the
+    /// commitment scheme supports.
+    pub fn validate_columns<'a, COL>(
+        columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+    ) -> Result<(), TableCommitmentFromColumnsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        let (identifiers, committable_columns): (Vec<&Ident>, Vec<CommittableColumn>) = columns
+            .into_iter()
+            .map(|(identifier, column)| (identifier, column.into()))
+            .unzip();
+
+        num_rows_of_columns(&committable_columns)?;
+        validate_unique_idents(None, identifiers.iter().copied())?;
+        check_for_invalid_idents(identifiers, &IdentifierLimits::default())?;
+        committable_columns
+            .iter()
+            .try_for_each(CommittableColumn::check_supported_by::<C>)?;
+
+        Ok(())
+    }
+
     /// Returns a [`TableCommitment`] to the provided table with the given row offset.
-    #[expect(
-        clippy::missing_panics_doc,
-        reason = "since OwnedTables cannot have columns of mixed length or duplicate idents"
-    )]
+    ///
+    /// # Panics
+    /// Panics if the table has idents that violate the default [`IdentifierLimits`] or columns
+    /// of a type the commitment scheme does not support.
+    /// `OwnedTable`s cannot have columns of mixed length or duplicate idents.
     pub fn from_owned_table_with_offset<S>(
         owned_table: &OwnedTable<S>,
         offset: usize,
@@ -200,7 +293,7 @@ impl<C: Commitment> TableCommitment<C> {
         S: Scalar,
     {
         Self::try_from_columns_with_offset(owned_table.inner_table(), offset, setup)
-            .expect("OwnedTables cannot have columns of mixed length or duplicate idents")
+            .expect("OwnedTables should have columns of equal length with unique, valid idents and supported types")
     }
 
     /// Append rows of data from the provided columns to the existing [`TableCommitment`].
@@ -233,6 +326,54 @@ impl<C: Commitment> TableCommitment<C> {
         Ok(())
     }
 
+    /// Replace rows of the existing [`TableCommitment`], starting at `start_row`.
+    ///
+    /// `old_rows` must be the data currently committed to in these rows and `new_rows` the data
+    /// replacing it, for every column in order. Only these rows are committed to, so correcting a
+    /// range in the middle of a large table does not require recommitting to the whole table.
+    ///
+    /// Will error on a variety of mismatches, if the provided columns have mixed length,
+    /// or if the rows are not within the [`TableCommitment`]'s range.
+    pub fn try_update_rows<'a, COL>(
+        &mut self,
+        old_rows: impl IntoIterator<Item = (&'a Ident, COL)>,
+        new_rows: impl IntoIterator<Item = (&'a Ident, COL)>,
+        start_row: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), UpdateTableCommitmentError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        let (old_identifiers, old_columns): (Vec<&Ident>, Vec<CommittableColumn>) = old_rows
+            .into_iter()
+            .map(|(identifier, column)| (identifier, column.into()))
+            .unzip();
+        let (new_identifiers, new_columns): (Vec<&Ident>, Vec<CommittableColumn>) = new_rows
+            .into_iter()
+            .map(|(identifier, column)| (identifier, column.into()))
+            .unzip();
+
+        let num_rows = num_rows_of_columns(&old_columns)?;
+        num_rows_of_columns(&new_columns)?;
+
+        if start_row < self.range.start || start_row + num_rows > self.range.end {
+            return Err(UpdateTableCommitmentError::RowsOutOfRange {
+                start_row,
+                num_rows,
+                range: self.range.clone(),
+            });
+        }
+
+        self.column_commitments.try_update_rows_with_offset(
+            old_identifiers.into_iter().zip(old_columns),
+            new_identifiers.into_iter().zip(new_columns),
+            start_row,
+            setup,
+        )?;
+
+        Ok(())
+    }
+
     /// Append data of the provided table to the exiting [`TableCommitment`].
     ///
     /// Will error on a variety of mismatches.
@@ -255,6 +396,9 @@ impl<C: Commitment> TableCommitment<C> {
                     AppendColumnCommitmentsError::DuplicateIdents { .. } => {
                         panic!("OwnedTables cannot have duplicate idents");
                     }
+                    AppendColumnCommitmentsError::PolicyViolations { .. } => {
+                        panic!("rows are appended without validation");
+                    }
                 },
                 AppendTableCommitmentError::MixedLengthColumns { .. } => {
                     panic!("OwnedTables cannot have columns of mixed length");
@@ -264,7 +408,7 @@ impl<C: Commitment> TableCommitment<C> {
 
     /// Add new columns to this [`TableCommitment`].
     ///
-    /// Columns must have the same length as the current commitment and no duplicate idents.
+    /// Columns must have the same length as the current commitment and no duplicate or invalid idents.
     pub fn try_extend_columns<'a, COL>(
         &mut self,
         columns: impl IntoIterator<Item = (&'a Ident, COL)>,
@@ -417,6 +561,7 @@ mod tests {
         assert_eq!(empty_table_commitment.range(), &(0..0));
         assert_eq!(empty_table_commitment.num_columns(), 0);
         assert_eq!(empty_table_commitment.num_rows(), 0);
+        assert_eq!(empty_table_commitment.max_generator_index(), None);
 
         // no-rows case
         empty_columns_iter.insert("column_a".into(), OwnedColumn::BigInt(vec![]));
@@ -434,6 +579,7 @@ mod tests {
         assert_eq!(empty_table_commitment.range(), &(1..1));
         assert_eq!(empty_table_commitment.num_columns(), 1);
         assert_eq!(empty_table_commitment.num_rows(), 0);
+        assert_eq!(empty_table_commitment.max_generator_index(), None);
 
         // nonempty case
         let owned_table = owned_table::<TestScalar>([
@@ -457,6 +603,7 @@ mod tests {
         assert_eq!(table_commitment.range(), &(2..6));
         assert_eq!(table_commitment.num_columns(), 3);
         assert_eq!(table_commitment.num_rows(), 4);
+        assert_eq!(table_commitment.max_generator_index(), Some(5));
 
         // matches from_owned_table constructor
         let table_commitment_from_owned_table =
@@ -577,6 +724,81 @@ mod tests {
         assert_eq!(table_commitment.column_commitments(), &column_commitments);
     }
 
+    #[test]
+    fn we_can_validate_columns_with_the_same_checks_as_construction() {
+        let column_id_a = "column_a".into();
+        let column_id_b = "column_b".into();
+
+        let one_row_column = OwnedColumn::<TestScalar>::BigInt(vec![1]);
+        let two_row_column = OwnedColumn::<TestScalar>::BigInt(vec![1, 2]);
+
+        let valid_columns = [
+            (&column_id_a, &one_row_column),
+            (&column_id_b, &one_row_column),
+        ];
+        assert!(TableCommitment::<NaiveCommitment>::validate_columns(valid_columns).is_ok());
+        assert!(
+            TableCommitment::<NaiveCommitment>::try_from_columns_with_offset(valid_columns, 0, &())
+                .is_ok()
+        );
+
+        let empty_columns: [(&Ident, &OwnedColumn<TestScalar>); 0] = [];
+        assert!(TableCommitment::<NaiveCommitment>::validate_columns(empty_columns).is_ok());
+
+        let mixed_length_columns = [
+            (&column_id_a, &one_row_column),
+            (&column_id_b, &two_row_column),
+        ];
+        assert!(matches!(
+            TableCommitment::<NaiveCommitment>::validate_columns(mixed_length_columns),
+            Err(TableCommitmentFromColumnsError::MixedLengthColumns { .. })
+        ));
+        assert!(matches!(
+            TableCommitment::<NaiveCommitment>::try_from_columns_with_offset(
+                mixed_length_columns,
+                0,
+                &()
+            ),
+            Err(TableCommitmentFromColumnsError::MixedLengthColumns { .. })
+        ));
+
+        let duplicate_columns = [
+            (&column_id_a, &one_row_column),
+            (&column_id_b, &one_row_column),
+            (&column_id_a, &one_row_column),
+        ];
+        assert!(matches!(
+            TableCommitment::<NaiveCommitment>::validate_columns(duplicate_columns),
+            Err(TableCommitmentFromColumnsError::DuplicateIdents { .. })
+        ));
+        assert!(matches!(
+            TableCommitment::<NaiveCommitment>::try_from_columns_with_offset(
+                duplicate_columns,
+                0,
+                &()
+            ),
+            Err(TableCommitmentFromColumnsError::DuplicateIdents { .. })
+        ));
+
+        let invalid_column_id = "column a".into();
+        let invalid_columns = [
+            (&column_id_a, &one_row_column),
+            (&invalid_column_id, &one_row_column),
+        ];
+        assert!(matches!(
+            TableCommitment::<NaiveCommitment>::validate_columns(invalid_columns),
+            Err(TableCommitmentFromColumnsError::InvalidIdent { .. })
+        ));
+        assert!(matches!(
+            TableCommitment::<NaiveCommitment>::try_from_columns_with_offset(
+                invalid_columns,
+                0,
+                &()
+            ),
+            Err(TableCommitmentFromColumnsError::InvalidIdent { .. })
+        ));
+    }
+
     #[test]
     fn we_can_append_rows_to_table_commitment() {
         let bigint_id: Ident = "bigint_column".into();
@@ -632,6 +854,117 @@ mod tests {
         assert_eq!(table_commitment, table_commitment_clone);
     }
 
+    #[test]
+    fn we_can_update_a_mid_table_range_of_table_commitment() {
+        let base_table: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [1, 2, 3, 4, 5, 6]),
+            varchar(
+                "column_b",
+                ["Lorem", "ipsum", "dolor", "sit", "amet", "elit"],
+            ),
+        ]);
+        let mut table_commitment =
+            TableCommitment::<NaiveCommitment>::try_from_columns_with_offset(
+                base_table.inner_table(),
+                2,
+                &(),
+            )
+            .unwrap();
+
+        // rows 4 and 5 are the third and fourth rows of the table
+        let old_rows: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [3, 4]),
+            varchar("column_b", ["dolor", "sit"]),
+        ]);
+        let new_rows: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [30, 40]),
+            varchar("column_b", ["consectetur", "adipiscing"]),
+        ]);
+        table_commitment
+            .try_update_rows(old_rows.inner_table(), new_rows.inner_table(), 4, &())
+            .unwrap();
+
+        let updated_table: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [1, 2, 30, 40, 5, 6]),
+            varchar(
+                "column_b",
+                [
+                    "Lorem",
+                    "ipsum",
+                    "consectetur",
+                    "adipiscing",
+                    "amet",
+                    "elit",
+                ],
+            ),
+        ]);
+        let expected_table_commitment =
+            TableCommitment::<NaiveCommitment>::try_from_columns_with_offset(
+                updated_table.inner_table(),
+                2,
+                &(),
+            )
+            .unwrap();
+        assert_eq!(table_commitment.range(), expected_table_commitment.range());
+        assert_eq!(
+            table_commitment.column_commitments().commitments(),
+            expected_table_commitment.column_commitments().commitments()
+        );
+    }
+
+    #[test]
+    fn we_cannot_update_rows_of_table_commitment_outside_its_range_or_with_mixed_lengths() {
+        let base_table: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [1, 2, 3, 4]),
+            varchar("column_b", ["Lorem", "ipsum", "dolor", "sit"]),
+        ]);
+        let mut table_commitment =
+            TableCommitment::<NaiveCommitment>::try_from_columns_with_offset(
+                base_table.inner_table(),
+                1,
+                &(),
+            )
+            .unwrap();
+        let original_table_commitment = table_commitment.clone();
+
+        let old_rows: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [3, 4]),
+            varchar("column_b", ["dolor", "sit"]),
+        ]);
+        let new_rows: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [30, 40]),
+            varchar("column_b", ["consectetur", "adipiscing"]),
+        ]);
+        for start_row in [0, 4] {
+            assert!(matches!(
+                table_commitment.try_update_rows(
+                    old_rows.inner_table(),
+                    new_rows.inner_table(),
+                    start_row,
+                    &()
+                ),
+                Err(UpdateTableCommitmentError::RowsOutOfRange { num_rows: 2, .. })
+            ));
+        }
+
+        let column_a_id: Ident = "column_a".into();
+        let column_b_id: Ident = "column_b".into();
+        let column_a = OwnedColumn::<TestScalar>::BigInt(vec![30, 40]);
+        let column_b = OwnedColumn::<TestScalar>::VarChar(vec!["consectetur".to_string()]);
+        assert!(matches!(
+            table_commitment.try_update_rows(
+                old_rows.inner_table(),
+                [(&column_a_id, &column_a), (&column_b_id, &column_b)],
+                3,
+                &()
+            ),
+            Err(UpdateTableCommitmentError::MixedLengthColumns { .. })
+        ));
+
+        // make sure the commitment wasn't mutated
+        assert_eq!(table_commitment, original_table_commitment);
+    }
+
     #[test]
     fn we_cannot_append_mismatched_columns_to_table_commitment() {
         let base_table: OwnedTable<TestScalar> = owned_table([
@@ -0,0 +1,230 @@
+use super::{ColumnCommitmentsMismatch, Commitment, TableCommitment};
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+/// Errors that can occur when checking that two [`TableCommitment`]s commit to equal tables.
+#[derive(Debug, Snafu)]
+pub enum TableEqualityError {
+    /// The tables span different rows, e.g. because they have different lengths.
+    #[snafu(display("cannot compare table commitments of rows {range_a:?} and {range_b:?}"))]
+    RangeMismatch {
+        /// The rows of the first table
+        range_a: Range<usize>,
+        /// The rows of the second table
+        range_b: Range<usize>,
+    },
+    /// The tables have different columns, or columns of different types.
+    #[snafu(transparent)]
+    ColumnMismatch {
+        /// The underlying source error
+        source: ColumnCommitmentsMismatch,
+    },
+    /// The tables have the same columns, but different values in some of them.
+    ///
+    /// Strings are used here instead of Idents to decrease the size of this variant
+    #[snafu(display("tables differ in the values of columns {}", column_ids.join(", ")))]
+    ValueMismatch {
+        /// The columns whose values differ
+        column_ids: Vec<String>,
+    },
+}
+
+/// An attestation that two [`TableCommitment`]s commit to identical tables, i.e. the same columns
+/// with the same values in the same order, as produced by [`prove_tables_equal`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableEqualityAttestation<C: Commitment> {
+    table_commitment: TableCommitment<C>,
+}
+
+impl<C: Commitment> TableEqualityAttestation<C> {
+    /// Returns the commitment to the table both commitments were found to commit to.
+    #[must_use]
+    pub fn table_commitment(&self) -> &TableCommitment<C> {
+        &self.table_commitment
+    }
+
+    /// Returns whether `table_commitment` commits to the attested table as well.
+    #[must_use]
+    pub fn attests_to(&self, table_commitment: &TableCommitment<C>) -> bool {
+        prove_tables_equal(&self.table_commitment, table_commitment).is_ok()
+    }
+}
+
+/// Check that two [`TableCommitment`]s commit to identical tables without access to their data.
+///
+/// Subtracting the commitments column by column yields commitments to the differences of the
+/// columns. Since commitments are binding, the difference of two columns commits to all zeros,
+/// which is the identity commitment, if and only if the columns are equal. This requires both
+/// tables to have been committed to with the same setup at the same rows.
+///
+/// This lets a replica show that its table equals a reference table by publishing its
+/// commitment, without revealing the contents of the table.
+///
+/// # Errors
+/// Returns [`TableEqualityError::RangeMismatch`] if the tables span different rows,
+/// [`TableEqualityError::ColumnMismatch`] if they have different columns, columns in a different
+/// order or commitments from different setups, and [`TableEqualityError::ValueMismatch`] if some
+/// of their columns differ.
+pub fn prove_tables_equal<C: Commitment>(
+    table_a: &TableCommitment<C>,
+    table_b: &TableCommitment<C>,
+) -> Result<TableEqualityAttestation<C>, TableEqualityError> {
+    if table_a.range() != table_b.range() {
+        return Err(TableEqualityError::RangeMismatch {
+            range_a: table_a.range().clone(),
+            range_b: table_b.range().clone(),
+        });
+    }
+    let differences = table_a
+        .column_commitments()
+        .clone()
+        .try_sub(table_b.column_commitments().clone())?;
+    let column_ids = differences
+        .into_iter()
+        .filter(|(_, _, difference)| *difference != C::default())
+        .map(|(column_id, _, _)| column_id.value)
+        .collect::<Vec<_>>();
+    if !column_ids.is_empty() {
+        return Err(TableEqualityError::ValueMismatch { column_ids });
+    }
+    Ok(TableEqualityAttestation {
+        table_commitment: table_a.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{
+        commitment::naive_commitment::NaiveCommitment,
+        database::{owned_table_utility::*, OwnedTable},
+        scalar::test_scalar::TestScalar,
+    };
+
+    fn commit(table: &OwnedTable<TestScalar>, offset: usize) -> TableCommitment<NaiveCommitment> {
+        TableCommitment::from_owned_table_with_offset(table, offset, &())
+    }
+
+    fn reference_table() -> OwnedTable<TestScalar> {
+        owned_table([
+            bigint("a", [1_i64, 2, 3, 4]),
+            varchar("b", ["x", "y", "z", "w"]),
+            boolean("c", [true, false, true, false]),
+        ])
+    }
+
+    #[test]
+    fn we_can_prove_that_equal_tables_are_equal() {
+        let reference = commit(&reference_table(), 2);
+        let replica = commit(&reference_table(), 2);
+        let attestation = prove_tables_equal(&replica, &reference).unwrap();
+        assert_eq!(attestation.table_commitment(), &replica);
+        assert!(attestation.attests_to(&reference));
+
+        let empty = commit(&owned_table([bigint("a", [0_i64; 0])]), 0);
+        assert!(prove_tables_equal(&empty, &empty).is_ok());
+    }
+
+    #[test]
+    fn we_cannot_prove_that_tables_differing_in_one_row_are_equal() {
+        let reference = commit(&reference_table(), 0);
+        let replica = commit(
+            &owned_table([
+                bigint("a", [1_i64, 2, 3, 4]),
+                varchar("b", ["x", "y", "Z", "w"]),
+                boolean("c", [true, false, false, false]),
+            ]),
+            0,
+        );
+        let Err(TableEqualityError::ValueMismatch { column_ids }) =
+            prove_tables_equal(&replica, &reference)
+        else {
+            panic!("tables differing in one row should not be equal");
+        };
+        assert_eq!(column_ids, ["b", "c"]);
+
+        let attestation = prove_tables_equal(&reference, &reference).unwrap();
+        assert!(!attestation.attests_to(&replica));
+    }
+
+    #[test]
+    fn we_cannot_prove_that_tables_with_rows_in_a_different_order_are_equal() {
+        let reference = commit(&owned_table([bigint("a", [1_i64, 2])]), 0);
+        let replica = commit(&owned_table([bigint("a", [2_i64, 1])]), 0);
+        assert!(matches!(
+            prove_tables_equal(&replica, &reference),
+            Err(TableEqualityError::ValueMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_prove_that_tables_of_different_rows_are_equal() {
+        let reference = commit(&reference_table(), 0);
+        let shorter = commit(
+            &owned_table([
+                bigint("a", [1_i64, 2, 3]),
+                varchar("b", ["x", "y", "z"]),
+                boolean("c", [true, false, true]),
+            ]),
+            0,
+        );
+        assert!(matches!(
+            prove_tables_equal(&shorter, &reference),
+            Err(TableEqualityError::RangeMismatch { range_a, range_b })
+                if range_a == (0..3) && range_b == (0..4)
+        ));
+        let shifted = commit(&reference_table(), 1);
+        assert!(matches!(
+            prove_tables_equal(&shifted, &reference),
+            Err(TableEqualityError::RangeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_prove_that_tables_with_different_columns_are_equal() {
+        let reference = commit(&reference_table(), 0);
+        let missing_column = commit(
+            &owned_table([
+                bigint("a", [1_i64, 2, 3, 4]),
+                varchar("b", ["x", "y", "z", "w"]),
+            ]),
+            0,
+        );
+        assert!(matches!(
+            prove_tables_equal(&missing_column, &reference),
+            Err(TableEqualityError::ColumnMismatch {
+                source: ColumnCommitmentsMismatch::NumColumns
+            })
+        ));
+        let renamed_column = commit(
+            &owned_table([
+                bigint("a", [1_i64, 2, 3, 4]),
+                varchar("b", ["x", "y", "z", "w"]),
+                boolean("d", [true, false, true, false]),
+            ]),
+            0,
+        );
+        assert!(matches!(
+            prove_tables_equal(&renamed_column, &reference),
+            Err(TableEqualityError::ColumnMismatch {
+                source: ColumnCommitmentsMismatch::Ident { .. }
+            })
+        ));
+        let retyped_column = commit(
+            &owned_table([
+                int("a", [1, 2, 3, 4]),
+                varchar("b", ["x", "y", "z", "w"]),
+                boolean("c", [true, false, true, false]),
+            ]),
+            0,
+        );
+        assert!(matches!(
+            prove_tables_equal(&retyped_column, &reference),
+            Err(TableEqualityError::ColumnMismatch {
+                source: ColumnCommitmentsMismatch::ColumnCommitmentMetadata { .. }
+            })
+        ));
+    }
+}
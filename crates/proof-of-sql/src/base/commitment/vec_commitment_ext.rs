@@ -1,6 +1,7 @@
 use super::Commitment;
 use crate::base::commitment::committable_column::CommittableColumn;
 use alloc::vec::Vec;
+use core::{ops::Range, slice};
 use snafu::Snafu;
 
 /// Cannot update commitment collections with different column counts
@@ -8,6 +9,77 @@ use snafu::Snafu;
 #[snafu(display("cannot update commitment collections with different column counts"))]
 pub struct NumColumnsMismatch;
 
+/// Errors that can occur when replacing segments of committed columns.
+#[derive(Snafu, Debug)]
+pub enum SegmentDeltaError {
+    /// The number of segments does not match the number of commitments.
+    #[snafu(transparent)]
+    NumColumnsMismatch {
+        /// The underlying source error
+        source: NumColumnsMismatch,
+    },
+    /// A segment would be replaced by a segment of a different length.
+    #[snafu(display(
+        "cannot replace a segment of length {old_len} with a segment of length {new_len}"
+    ))]
+    LengthMismatch {
+        /// The length of the segment being replaced
+        old_len: usize,
+        /// The length of the replacement segment
+        new_len: usize,
+    },
+    /// A segment would be replaced by a segment starting at a different row.
+    #[snafu(display(
+        "cannot replace a segment starting at row {old_start_row} with a segment starting at row {new_start_row}"
+    ))]
+    MisalignedSegments {
+        /// The first row of the segment being replaced
+        old_start_row: usize,
+        /// The first row of the replacement segment
+        new_start_row: usize,
+    },
+}
+
+/// A contiguous range of rows of a column, together with the row it starts at.
+///
+/// The start row is used as the generator offset when committing to the segment,
+/// so the commitment to a segment is exactly its contribution to the commitment of the whole column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnSegment<'a> {
+    /// The data of the rows in the segment
+    pub column: CommittableColumn<'a>,
+    /// The 0-indexed row number of the first row in the segment
+    pub start_row: usize,
+}
+
+impl<'a> ColumnSegment<'a> {
+    /// Create a new [`ColumnSegment`] of the given rows, starting at `start_row`.
+    pub fn new(column: impl Into<CommittableColumn<'a>>, start_row: usize) -> Self {
+        Self {
+            column: column.into(),
+            start_row,
+        }
+    }
+
+    /// Returns the number of rows in the segment.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.column.len()
+    }
+
+    /// Returns true if the segment has no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.column.is_empty()
+    }
+
+    /// Returns the range of rows the segment covers.
+    #[must_use]
+    pub fn rows(&self) -> Range<usize> {
+        self.start_row..self.start_row + self.len()
+    }
+}
+
 /// Extension trait intended for collections of commitments.
 ///
 /// Implemented for `Vec<CompressedRistretto>`.
@@ -56,6 +128,26 @@ pub trait VecCommitmentExt {
     ) where
         C: Into<CommittableColumn<'a>>;
 
+    /// Returns a collection of commitments to the provided segments.
+    ///
+    /// Each segment is committed to using its own start row as the generator offset.
+    fn from_segments(segments: &[ColumnSegment], setup: &Self::CommitmentPublicSetup<'_>) -> Self;
+
+    /// Replace segments of the committed columns with new data.
+    ///
+    /// The `i`-th old and new segments belong to the `i`-th committed column.
+    /// Since commitments are linear, this subtracts the commitments of the old segments and adds
+    /// those of the new ones, without recommitting to the rest of the columns.
+    ///
+    /// There must be one pair of segments for every commitment,
+    /// and the segments of each pair must have the same length and start row.
+    fn try_apply_segment_delta(
+        &mut self,
+        old_segments: &[ColumnSegment],
+        new_segments: &[ColumnSegment],
+        setup: &Self::CommitmentPublicSetup<'_>,
+    ) -> Result<(), SegmentDeltaError>;
+
     /// Add two collections of commitments if they have equal column counts.
     fn try_add(self, other: Self) -> Result<Self, NumColumnsMismatch>
     where
@@ -138,6 +230,45 @@ impl<C: Commitment> VecCommitmentExt for Vec<C> {
         self.extend(Self::from_columns_with_offset(columns, offset, setup));
     }
 
+    fn from_segments(segments: &[ColumnSegment], setup: &Self::CommitmentPublicSetup<'_>) -> Self {
+        segments
+            .iter()
+            .flat_map(|segment| {
+                C::compute_commitments(slice::from_ref(&segment.column), segment.start_row, setup)
+            })
+            .collect()
+    }
+
+    fn try_apply_segment_delta(
+        &mut self,
+        old_segments: &[ColumnSegment],
+        new_segments: &[ColumnSegment],
+        setup: &Self::CommitmentPublicSetup<'_>,
+    ) -> Result<(), SegmentDeltaError> {
+        if self.len() != old_segments.len() || self.len() != new_segments.len() {
+            return Err(NumColumnsMismatch.into());
+        }
+        for (old_segment, new_segment) in old_segments.iter().zip(new_segments) {
+            if old_segment.len() != new_segment.len() {
+                return Err(SegmentDeltaError::LengthMismatch {
+                    old_len: old_segment.len(),
+                    new_len: new_segment.len(),
+                });
+            }
+            if old_segment.start_row != new_segment.start_row {
+                return Err(SegmentDeltaError::MisalignedSegments {
+                    old_start_row: old_segment.start_row,
+                    new_start_row: new_segment.start_row,
+                });
+            }
+        }
+
+        unsafe_sub_assign(self, &Self::from_segments(old_segments, setup));
+        unsafe_add_assign(self, &Self::from_segments(new_segments, setup));
+
+        Ok(())
+    }
+
     fn try_add(self, other: Self) -> Result<Self, NumColumnsMismatch>
     where
         Self: Sized,
@@ -499,4 +630,114 @@ mod tests {
             Err(NumColumnsMismatch)
         ));
     }
+
+    #[test]
+    fn we_can_commit_to_segments_at_their_start_rows() {
+        let column_a = [12i64, 34, 56, 78, 90];
+        let column_b = [1i64, 2, 3, 4, 5];
+
+        let segments = [
+            ColumnSegment::new(&column_a[1..3], 1),
+            ColumnSegment::new(&column_b[3..], 3),
+        ];
+        assert_eq!(segments[0].rows(), 1..3);
+        assert_eq!(segments[1].len(), 2);
+        assert!(!segments[1].is_empty());
+
+        let commitments = Vec::<NaiveCommitment>::from_segments(&segments, &());
+
+        let expected_commitments = [
+            NaiveCommitment::compute_commitments(
+                &[CommittableColumn::BigInt(&column_a[1..3])],
+                1,
+                &(),
+            ),
+            NaiveCommitment::compute_commitments(
+                &[CommittableColumn::BigInt(&column_b[3..])],
+                3,
+                &(),
+            ),
+        ]
+        .concat();
+        assert_eq!(commitments, expected_commitments);
+    }
+
+    #[test]
+    fn we_can_patch_a_mid_column_range_with_a_segment_delta() {
+        let column_a = [12i64, 34, 56, 78, 90, 11];
+        let column_b = ["Lorem", "ipsum", "dolor", "sit", "amet", "elit"].map(String::from);
+        let patched_a = [12i64, 34, -1, -2, 90, 11];
+        let patched_b = ["Lorem", "ipsum", "X", "Y", "amet", "elit"].map(String::from);
+
+        let columns = vec![
+            OwnedColumn::<TestScalar>::BigInt(column_a.to_vec()),
+            OwnedColumn::VarChar(column_b.to_vec()),
+        ];
+        let mut commitments = Vec::<NaiveCommitment>::from_columns_with_offset(&columns, 0, &());
+
+        let old_b = OwnedColumn::<TestScalar>::VarChar(column_b[2..4].to_vec());
+        let new_b = OwnedColumn::<TestScalar>::VarChar(patched_b[2..4].to_vec());
+        let old_segments = [
+            ColumnSegment::new(&column_a[2..4], 2),
+            ColumnSegment::new(&old_b, 2),
+        ];
+        let new_segments = [
+            ColumnSegment::new(&patched_a[2..4], 2),
+            ColumnSegment::new(&new_b, 2),
+        ];
+        commitments
+            .try_apply_segment_delta(&old_segments, &new_segments, &())
+            .unwrap();
+
+        let patched_columns = vec![
+            OwnedColumn::<TestScalar>::BigInt(patched_a.to_vec()),
+            OwnedColumn::VarChar(patched_b.to_vec()),
+        ];
+        let expected_commitments =
+            Vec::<NaiveCommitment>::from_columns_with_offset(&patched_columns, 0, &());
+        assert_eq!(commitments, expected_commitments);
+    }
+
+    #[test]
+    fn we_cannot_apply_a_segment_delta_with_mismatched_segments() {
+        let column_a = [12i64, 34, 56, 78, 90];
+        let patched_a = [12i64, 34, -1, -2, 90];
+        let mut commitments =
+            Vec::<NaiveCommitment>::from_columns_with_offset([&column_a[..]], 0, &());
+        let original_commitments = commitments.clone();
+
+        assert!(matches!(
+            commitments.try_apply_segment_delta(
+                &[ColumnSegment::new(&column_a[2..4], 2)],
+                &[],
+                &()
+            ),
+            Err(SegmentDeltaError::NumColumnsMismatch { .. })
+        ));
+        assert!(matches!(
+            commitments.try_apply_segment_delta(
+                &[ColumnSegment::new(&column_a[2..4], 2)],
+                &[ColumnSegment::new(&patched_a[2..5], 2)],
+                &()
+            ),
+            Err(SegmentDeltaError::LengthMismatch {
+                old_len: 2,
+                new_len: 3
+            })
+        ));
+        assert!(matches!(
+            commitments.try_apply_segment_delta(
+                &[ColumnSegment::new(&column_a[2..4], 2)],
+                &[ColumnSegment::new(&patched_a[2..4], 1)],
+                &()
+            ),
+            Err(SegmentDeltaError::MisalignedSegments {
+                old_start_row: 2,
+                new_start_row: 1
+            })
+        ));
+
+        // make sure the commitments weren't mutated
+        assert_eq!(commitments, original_commitments);
+    }
 }
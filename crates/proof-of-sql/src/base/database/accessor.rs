@@ -22,6 +22,15 @@ pub trait MetadataAccessor {
     /// If the data span has its first row starting at the ith table row,
     /// this `get_offset` should then return `i`.
     fn get_offset(&self, table_ref: &TableRef) -> usize;
+
+    /// Return the generator offset of the commitment to a column in the table
+    ///
+    /// Columns of the same table may be committed starting at different offsets,
+    /// e.g. if they were appended to separately. The column's data span still consists of
+    /// the same rows as the table's. Defaults to the table's offset.
+    fn get_column_offset(&self, table_ref: &TableRef, _column_id: &Ident) -> usize {
+        self.get_offset(table_ref)
+    }
 }
 
 /// Access commitments of database columns.
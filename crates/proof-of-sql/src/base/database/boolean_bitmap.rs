@@ -0,0 +1,271 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Number of booleans stored in each word of a [`BooleanBitmap`]
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A boolean column packed into 64 bit words, one bit per row.
+///
+/// This takes an eighth of the memory of a `Vec<bool>`, which matters for wide tables of flags.
+/// Bit `i % 64` of word `i / 64` holds row `i`, and any bits past the length are always zero.
+///
+/// A bitmap can be committed to directly through
+/// [`CommittableColumn::BooleanBitmap`](crate::base::commitment::CommittableColumn::BooleanBitmap),
+/// producing the same commitment as the equivalent `&[bool]`.
+///
+/// Only committing uses bitmaps. [`Column::Boolean`](super::Column::Boolean) and
+/// [`OwnedColumn::Boolean`](super::OwnedColumn::Boolean) still hold one `bool` per row, because
+/// query evaluation and sumcheck index into them as slices. So accessors and provers do not save
+/// memory from this type; only callers that keep flags packed and commit to them do.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BooleanBitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BooleanBitmap {
+    /// Create an empty bitmap
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty bitmap with room for `capacity` booleans
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(capacity.div_ceil(WORD_BITS)),
+            len: 0,
+        }
+    }
+
+    /// Append a boolean to the end of the bitmap
+    pub fn push(&mut self, value: bool) {
+        let bit = self.len % WORD_BITS;
+        if bit == 0 {
+            self.words.push(0);
+        }
+        if value {
+            *self.words.last_mut().expect("a word was pushed above") |= 1 << bit;
+        }
+        self.len += 1;
+    }
+
+    /// Number of booleans in the bitmap
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the bitmap is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The boolean at `index`, or `None` if `index` is out of bounds
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.len).then(|| (self.words[index / WORD_BITS] >> (index % WORD_BITS)) & 1 == 1)
+    }
+
+    /// Number of `true` values in the bitmap
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The packed words backing the bitmap
+    #[must_use]
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Iterate over the booleans in the bitmap.
+    ///
+    /// Items are references so that code written against `&[bool]`, such as
+    /// `iter().filter(|b| **b)`, works unchanged.
+    #[must_use]
+    pub fn iter(&self) -> BooleanBitmapIter<'_> {
+        BooleanBitmapIter {
+            bitmap: self,
+            start: 0,
+            end: self.len,
+        }
+    }
+
+    /// Unpack the bitmap into one `bool` per row
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<bool> {
+        self.iter().copied().collect()
+    }
+}
+
+impl FromIterator<bool> for BooleanBitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut bitmap = Self::with_capacity(iter.size_hint().0);
+        iter.for_each(|value| bitmap.push(value));
+        bitmap
+    }
+}
+
+impl<'a> FromIterator<&'a bool> for BooleanBitmap {
+    fn from_iter<I: IntoIterator<Item = &'a bool>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl From<&[bool]> for BooleanBitmap {
+    fn from(value: &[bool]) -> Self {
+        value.iter().collect()
+    }
+}
+
+impl From<Vec<bool>> for BooleanBitmap {
+    fn from(value: Vec<bool>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl From<&BooleanBitmap> for Vec<bool> {
+    fn from(value: &BooleanBitmap) -> Self {
+        value.to_vec()
+    }
+}
+
+impl<'a> IntoIterator for &'a BooleanBitmap {
+    type Item = &'static bool;
+    type IntoIter = BooleanBitmapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the booleans of a [`BooleanBitmap`], unpacking them one at a time
+#[derive(Debug, Clone)]
+pub struct BooleanBitmapIter<'a> {
+    bitmap: &'a BooleanBitmap,
+    start: usize,
+    end: usize,
+}
+
+/// Reference to a static boolean, so that unpacked values can be handed out by reference
+fn static_ref(value: bool) -> &'static bool {
+    if value {
+        &true
+    } else {
+        &false
+    }
+}
+
+impl Iterator for BooleanBitmapIter<'_> {
+    type Item = &'static bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.start += 1;
+            static_ref(
+                self.bitmap
+                    .get(self.start - 1)
+                    .expect("index is within bounds"),
+            )
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for BooleanBitmapIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.end -= 1;
+            static_ref(self.bitmap.get(self.end).expect("index is within bounds"))
+        })
+    }
+}
+
+impl ExactSizeIterator for BooleanBitmapIter<'_> {}
+
+impl FusedIterator for BooleanBitmapIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn we_can_create_an_empty_bitmap() {
+        let bitmap = BooleanBitmap::new();
+        assert!(bitmap.is_empty());
+        assert_eq!(bitmap.len(), 0);
+        assert_eq!(bitmap.words(), &[] as &[u64]);
+        assert_eq!(bitmap.get(0), None);
+        assert_eq!(bitmap.count_ones(), 0);
+        assert_eq!(bitmap.to_vec(), Vec::<bool>::new());
+        assert_eq!(BooleanBitmap::from(&[] as &[bool]), bitmap);
+    }
+
+    #[test]
+    fn we_can_pack_and_unpack_booleans() {
+        let bools = vec![true, false, true, true, false];
+        let bitmap = BooleanBitmap::from(bools.as_slice());
+        assert_eq!(bitmap.len(), 5);
+        assert!(!bitmap.is_empty());
+        assert_eq!(bitmap.words(), &[0b01101]);
+        assert_eq!(bitmap.get(0), Some(true));
+        assert_eq!(bitmap.get(1), Some(false));
+        assert_eq!(bitmap.get(4), Some(false));
+        assert_eq!(bitmap.get(5), None);
+        assert_eq!(bitmap.count_ones(), 3);
+        assert_eq!(bitmap.to_vec(), bools);
+        assert_eq!(Vec::<bool>::from(&bitmap), bools);
+        assert_eq!(BooleanBitmap::from(bools), bitmap);
+    }
+
+    #[test]
+    fn we_can_pack_booleans_across_word_boundaries() {
+        let bools: Vec<bool> = (0..130).map(|i| i % 3 == 0).collect();
+        let bitmap: BooleanBitmap = bools.iter().collect();
+        assert_eq!(bitmap.len(), 130);
+        assert_eq!(bitmap.words().len(), 3);
+        assert_eq!(bitmap.words()[2], 0b10);
+        assert_eq!(bitmap.get(63), Some(true));
+        assert_eq!(bitmap.get(64), Some(false));
+        assert_eq!(bitmap.get(129), Some(true));
+        assert_eq!(bitmap.count_ones(), 44);
+        assert_eq!(bitmap.to_vec(), bools);
+
+        let mut pushed = BooleanBitmap::with_capacity(130);
+        for b in &bools {
+            pushed.push(*b);
+        }
+        assert_eq!(pushed, bitmap);
+    }
+
+    #[test]
+    fn we_can_iterate_over_a_bitmap_like_a_slice_of_booleans() {
+        let bools: Vec<bool> = (0..100).map(|i| i % 7 < 3).collect();
+        let bitmap = BooleanBitmap::from(bools.as_slice());
+        assert_eq!(
+            bitmap.iter().filter(|b| **b).count(),
+            bools.iter().filter(|b| **b).count()
+        );
+        assert_eq!(bitmap.iter().len(), 100);
+        assert!(bitmap.iter().eq(bools.iter()));
+        assert!(bitmap.iter().rev().eq(bools.iter().rev()));
+        assert!((&bitmap).into_iter().eq(bools.iter()));
+
+        let mut iter = bitmap.iter();
+        assert_eq!(iter.next(), Some(&true));
+        assert_eq!(iter.next_back(), Some(&true));
+        assert_eq!(iter.len(), 98);
+    }
+}
@@ -1,7 +1,7 @@
 use super::{ColumnOperationError, ColumnOperationResult};
 use crate::base::{
     database::ColumnType,
-    math::decimal::{DecimalError, Precision},
+    math::decimal::{DecimalError, Precision, MAX_SUPPORTED_PRECISION},
 };
 use alloc::string::ToString;
 // For decimal type manipulation please refer to
@@ -51,6 +51,9 @@ pub fn try_add_subtract_column_types(
 /// to add or subtract the two input types. Scaling is allowed. If the types are not compatible, return
 /// an error.
 ///
+/// Integers are treated as decimals with scale 0. The result has scale `max(s1, s2)` and precision
+/// `max(s1, s2) + max(p1 - s1, p2 - s2) + 1`, capped at 75.
+///
 /// # Panics
 ///
 /// - Panics if `lhs` or `rhs` does not have a precision or scale when they are expected to be numeric types.
@@ -93,6 +96,9 @@ pub fn try_add_subtract_column_types_with_scaling(
 /// to multiply the two input types. If the types are not compatible, return
 /// an error.
 ///
+/// Integers are treated as decimals with scale 0. The result has scale `s1 + s2` and precision
+/// `p1 + p2 + 1`, capped at 75. A scale above 75 is an error since no precision can hold it.
+///
 /// # Panics
 ///
 /// - Panics if `lhs` or `rhs` does not have a precision or scale when they are expected to be numeric types.
@@ -118,13 +124,14 @@ pub fn try_multiply_column_types(
             Precision::new(precision_value).expect("Precision value should be in range 0-75");
         let left_scale = lhs.scale().expect("Numeric types have scale");
         let right_scale = rhs.scale().expect("Numeric types have scale");
-        let scale = left_scale.checked_add(right_scale).ok_or(
-            ColumnOperationError::DecimalConversionError {
+        let scale = left_scale
+            .checked_add(right_scale)
+            .filter(|scale| i16::from(*scale) <= i16::from(MAX_SUPPORTED_PRECISION))
+            .ok_or(ColumnOperationError::DecimalConversionError {
                 source: DecimalError::InvalidScale {
                     scale: (i16::from(left_scale) + i16::from(right_scale)).to_string(),
                 },
-            },
-        )?;
+            })?;
         Ok(ColumnType::Decimal75(precision, scale))
     }
 }
@@ -935,6 +942,21 @@ mod test {
                 source: DecimalError::InvalidScale { .. }
             })
         ));
+
+        // A scale above the maximum precision
+        let lhs = ColumnType::Decimal75(Precision::new(40).unwrap(), 40_i8);
+        let rhs = ColumnType::Decimal75(Precision::new(36).unwrap(), 36_i8);
+        assert!(matches!(
+            try_multiply_column_types(lhs, rhs),
+            Err(ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidScale { .. }
+            })
+        ));
+        let rhs = ColumnType::Decimal75(Precision::new(35).unwrap(), 35_i8);
+        assert_eq!(
+            try_multiply_column_types(lhs, rhs).unwrap(),
+            ColumnType::Decimal75(Precision::new(75).unwrap(), 75)
+        );
     }
 
     #[test]
@@ -0,0 +1,248 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use snafu::Snafu;
+
+/// Errors from delta encoding or decoding a [`DeltaEncodedColumn`]
+#[derive(Snafu, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaEncodingError {
+    /// The cumulative sum of the deltas does not fit into an `i64`
+    #[snafu(display("the cumulative sum of the deltas overflows at row {index}"))]
+    CumulativeSumOverflow {
+        /// The first row whose value overflows
+        index: usize,
+    },
+    /// The difference between two adjacent values does not fit into an `i64`
+    #[snafu(display("the delta from the previous row to row {index} overflows"))]
+    DeltaOverflow {
+        /// The row whose delta to the previous row overflows
+        index: usize,
+    },
+}
+
+/// A `BigInt` column stored as the differences between adjacent rows.
+///
+/// The first delta is the absolute value of row 0, and every later delta is the difference to the
+/// previous row, so row `i` holds the cumulative sum `deltas[0] + ... + deltas[i]`. Slowly changing
+/// columns such as time series have small deltas, which compress much better than the values.
+///
+/// A delta encoded column can be committed to directly through
+/// [`CommittableColumn::DeltaEncoded`](crate::base::commitment::CommittableColumn::DeltaEncoded).
+/// The commitment is to the cumulative values, i.e. the commitment to the column at `offset` is
+/// ```text
+///     sum_i G_{offset + i} * (deltas[0] + ... + deltas[i])
+/// ```
+/// which is exactly the commitment to the equivalent `&[i64]`. Queries therefore see the logical
+/// values and are proven against the commitment as usual.
+///
+/// Every cumulative sum is checked to fit into an `i64` when the column is created, so decoding
+/// never overflows.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DeltaEncodedColumn {
+    deltas: Vec<i64>,
+}
+
+impl DeltaEncodedColumn {
+    /// Create an empty column
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a column from its deltas, where the first delta is the absolute value of row 0
+    ///
+    /// # Errors
+    /// Returns [`DeltaEncodingError::CumulativeSumOverflow`] if the value of some row does not
+    /// fit into an `i64`.
+    pub fn try_from_deltas(deltas: Vec<i64>) -> Result<Self, DeltaEncodingError> {
+        deltas
+            .iter()
+            .enumerate()
+            .try_fold(0_i64, |value, (index, delta)| {
+                value
+                    .checked_add(*delta)
+                    .ok_or(DeltaEncodingError::CumulativeSumOverflow { index })
+            })?;
+        Ok(Self { deltas })
+    }
+
+    /// Delta encode a column of values
+    ///
+    /// # Errors
+    /// Returns [`DeltaEncodingError::DeltaOverflow`] if the difference between two adjacent
+    /// values does not fit into an `i64`, e.g. from `i64::MIN` to `i64::MAX`.
+    pub fn try_from_values(values: &[i64]) -> Result<Self, DeltaEncodingError> {
+        let deltas = values
+            .first()
+            .copied()
+            .into_iter()
+            .map(Ok)
+            .chain(values.windows(2).enumerate().map(|(i, pair)| {
+                pair[1]
+                    .checked_sub(pair[0])
+                    .ok_or(DeltaEncodingError::DeltaOverflow { index: i + 1 })
+            }))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { deltas })
+    }
+
+    /// Number of rows in the column
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    /// Returns true if the column is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    /// The deltas backing the column, starting with the absolute value of row 0
+    #[must_use]
+    pub fn deltas(&self) -> &[i64] {
+        &self.deltas
+    }
+
+    /// Iterate over the cumulative values of the column
+    #[must_use]
+    pub fn iter(&self) -> DeltaEncodedColumnIter<'_> {
+        DeltaEncodedColumnIter {
+            deltas: self.deltas.iter(),
+            value: 0,
+        }
+    }
+
+    /// Decode the column into one value per row
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.iter().collect()
+    }
+}
+
+impl TryFrom<Vec<i64>> for DeltaEncodedColumn {
+    type Error = DeltaEncodingError;
+
+    fn try_from(deltas: Vec<i64>) -> Result<Self, Self::Error> {
+        Self::try_from_deltas(deltas)
+    }
+}
+
+impl From<&DeltaEncodedColumn> for Vec<i64> {
+    fn from(value: &DeltaEncodedColumn) -> Self {
+        value.to_vec()
+    }
+}
+
+impl<'a> IntoIterator for &'a DeltaEncodedColumn {
+    type Item = i64;
+    type IntoIter = DeltaEncodedColumnIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the values of a [`DeltaEncodedColumn`], summing up the deltas one at a time
+#[derive(Debug, Clone)]
+pub struct DeltaEncodedColumnIter<'a> {
+    deltas: core::slice::Iter<'a, i64>,
+    value: i64,
+}
+
+impl Iterator for DeltaEncodedColumnIter<'_> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deltas.next().map(|delta| {
+            self.value = self
+                .value
+                .checked_add(*delta)
+                .expect("cumulative sums are checked when the column is created");
+            self.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.deltas.size_hint()
+    }
+}
+
+impl ExactSizeIterator for DeltaEncodedColumnIter<'_> {}
+
+impl FusedIterator for DeltaEncodedColumnIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn we_can_create_an_empty_delta_encoded_column() {
+        let column = DeltaEncodedColumn::new();
+        assert!(column.is_empty());
+        assert_eq!(column.len(), 0);
+        assert_eq!(column.deltas(), &[] as &[i64]);
+        assert_eq!(column.to_vec(), Vec::<i64>::new());
+        assert_eq!(DeltaEncodedColumn::try_from_values(&[]).unwrap(), column);
+        assert_eq!(DeltaEncodedColumn::try_from_deltas(vec![]).unwrap(), column);
+    }
+
+    #[test]
+    fn we_can_encode_and_decode_values() {
+        let values = vec![1_000_i64, 1_010, 1_010, 1_005, -3];
+        let column = DeltaEncodedColumn::try_from_values(&values).unwrap();
+        assert_eq!(column.len(), 5);
+        assert!(!column.is_empty());
+        // The first delta is absolute
+        assert_eq!(column.deltas(), &[1_000, 10, 0, -5, -1_008]);
+        assert_eq!(column.to_vec(), values);
+        assert_eq!(Vec::<i64>::from(&column), values);
+        assert!((&column).into_iter().eq(values.iter().copied()));
+        assert_eq!(column.iter().len(), 5);
+        assert_eq!(
+            DeltaEncodedColumn::try_from(column.deltas().to_vec()).unwrap(),
+            column
+        );
+    }
+
+    #[test]
+    fn we_can_decode_values_at_the_limits_of_i64() {
+        let column = DeltaEncodedColumn::try_from_deltas(vec![i64::MAX, -1, 1]).unwrap();
+        assert_eq!(column.to_vec(), vec![i64::MAX, i64::MAX - 1, i64::MAX]);
+        let column = DeltaEncodedColumn::try_from_deltas(vec![i64::MIN, i64::MAX, 1]).unwrap();
+        assert_eq!(column.to_vec(), vec![i64::MIN, -1, 0]);
+        let values = [i64::MIN, -1, 0];
+        let column = DeltaEncodedColumn::try_from_values(&values).unwrap();
+        assert_eq!(column.deltas(), &[i64::MIN, i64::MAX, 1]);
+        assert_eq!(column.to_vec(), values);
+    }
+
+    #[test]
+    fn we_cannot_create_a_delta_encoded_column_whose_cumulative_sum_overflows() {
+        assert_eq!(
+            DeltaEncodedColumn::try_from_deltas(vec![i64::MAX, 1]),
+            Err(DeltaEncodingError::CumulativeSumOverflow { index: 1 })
+        );
+        assert_eq!(
+            DeltaEncodedColumn::try_from_deltas(vec![0, -1, i64::MIN, 5]),
+            Err(DeltaEncodingError::CumulativeSumOverflow { index: 2 })
+        );
+        // The final value would fit again, but an intermediate one does not
+        assert_eq!(
+            DeltaEncodedColumn::try_from_deltas(vec![i64::MAX, i64::MAX, i64::MIN]),
+            Err(DeltaEncodingError::CumulativeSumOverflow { index: 1 })
+        );
+    }
+
+    #[test]
+    fn we_cannot_delta_encode_values_whose_deltas_overflow() {
+        assert_eq!(
+            DeltaEncodedColumn::try_from_values(&[0, i64::MIN, i64::MAX]),
+            Err(DeltaEncodingError::DeltaOverflow { index: 2 })
+        );
+        assert_eq!(
+            DeltaEncodedColumn::try_from_values(&[1, i64::MIN]),
+            Err(DeltaEncodingError::DeltaOverflow { index: 1 })
+        );
+    }
+}
@@ -1,6 +1,12 @@
+//! Utilities for keeping a subset of the rows of columns, e.g. the rows selected by a filter or
+//! the rows matched by a join.
+//!
+//! Rows are copied in runs of consecutive rows, each run with a single slice copy. The
+//! `bench_filter_columns` benchmark measures the kernels at 0.1%, 50% and 99% selectivity.
 use crate::base::{database::Column, scalar::Scalar};
 use alloc::vec::Vec;
 use bumpalo::Bump;
+use core::ops::Range;
 
 /// This function takes a selection vector and a set of columns and returns a
 /// new set of columns that only contains the selected rows. The function
@@ -18,19 +24,32 @@ pub fn filter_columns<'a, S: Scalar>(
     for col in columns {
         assert_eq!(col.len(), selection.len());
     }
-    let indexes: Vec<_> = selection
-        .iter()
-        .enumerate()
-        .filter(|(_, &b)| b)
-        .map(|(i, _)| i)
-        .collect();
-    let result_length = indexes.len();
+    let runs = selected_runs(selection);
+    let result_length = runs.iter().map(ExactSizeIterator::len).sum();
     let filtered_result: Vec<_> = columns
         .iter()
-        .map(|column| filter_column_by_index(alloc, column, &indexes))
+        .map(|column| gather_column_runs(alloc, column, &runs, result_length))
         .collect();
     (filtered_result, result_length)
 }
+
+/// This function takes an index vector and a set of columns and returns a
+/// new set of columns that contains the rows at the given indexes, in the order
+/// of the indexes. Indexes may repeat.
+/// # Panics
+/// This function panics if any index is out of bounds of any of the columns.
+pub fn gather_columns<'a, S: Scalar>(
+    alloc: &'a Bump,
+    columns: &[Column<'a, S>],
+    indexes: &[usize],
+) -> Vec<Column<'a, S>> {
+    let runs = index_runs(indexes);
+    columns
+        .iter()
+        .map(|column| gather_column_runs(alloc, column, &runs, indexes.len()))
+        .collect()
+}
+
 /// This function takes an index vector and a `Column` and returns a
 /// new set of columns that only contains the selected indexes. It is assumed that
 /// the indexes are valid.
@@ -39,48 +58,119 @@ pub fn filter_column_by_index<'a, S: Scalar>(
     column: &Column<'a, S>,
     indexes: &[usize],
 ) -> Column<'a, S> {
-    match column {
-        Column::Boolean(col) => {
-            Column::Boolean(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
-        }
-        Column::Uint8(col) => {
-            Column::Uint8(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
-        }
-        Column::TinyInt(col) => {
-            Column::TinyInt(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
-        }
-        Column::SmallInt(col) => {
-            Column::SmallInt(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
-        }
-        Column::Int(col) => {
-            Column::Int(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
-        }
-        Column::BigInt(col) => {
-            Column::BigInt(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
+    gather_column_runs(alloc, column, &index_runs(indexes), indexes.len())
+}
+
+/// The runs of consecutive rows for which `selection` is `true`
+fn selected_runs(selection: &[bool]) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut run_start = None;
+    for (i, &selected) in selection.iter().enumerate() {
+        match (selected, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                runs.push(start..i);
+                run_start = None;
+            }
+            _ => {}
         }
-        Column::Int128(col) => {
-            Column::Int128(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
+    }
+    if let Some(start) = run_start {
+        runs.push(start..selection.len());
+    }
+    runs
+}
+
+/// The runs of consecutive rows in `indexes`, i.e. the maximal ranges of indexes increasing by one
+fn index_runs(indexes: &[usize]) -> Vec<Range<usize>> {
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for &i in indexes {
+        match runs.last_mut() {
+            Some(run) if run.end == i => run.end += 1,
+            _ => runs.push(i..i + 1),
         }
+    }
+    runs
+}
+
+/// Copy the `runs` of `values`, which have `len` rows in total, into a new slice
+fn gather_runs<'a, T: Copy + Default>(
+    alloc: &'a Bump,
+    values: &[T],
+    runs: &[Range<usize>],
+    len: usize,
+) -> &'a [T] {
+    let res = alloc.alloc_slice_fill_default(len);
+    let mut offset = 0;
+    for run in runs {
+        let next_offset = offset + run.len();
+        res[offset..next_offset].copy_from_slice(&values[run.clone()]);
+        offset = next_offset;
+    }
+    res
+}
+
+/// Copy the `runs` of `column`, which have `len` rows in total, into a new column
+///
+/// For strings and binary data only the references to the values are copied, along with their
+/// scalars, so the values themselves are never copied.
+fn gather_column_runs<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: &Column<'a, S>,
+    runs: &[Range<usize>],
+    len: usize,
+) -> Column<'a, S> {
+    match column {
+        Column::Boolean(col) => Column::Boolean(gather_runs(alloc, col, runs, len)),
+        Column::Uint8(col) => Column::Uint8(gather_runs(alloc, col, runs, len)),
+        Column::TinyInt(col) => Column::TinyInt(gather_runs(alloc, col, runs, len)),
+        Column::SmallInt(col) => Column::SmallInt(gather_runs(alloc, col, runs, len)),
+        Column::Int(col) => Column::Int(gather_runs(alloc, col, runs, len)),
+        Column::BigInt(col) => Column::BigInt(gather_runs(alloc, col, runs, len)),
+        Column::Int128(col) => Column::Int128(gather_runs(alloc, col, runs, len)),
         Column::VarChar((col, scals)) => Column::VarChar((
-            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])),
-            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| scals[i])),
+            gather_runs(alloc, col, runs, len),
+            gather_runs(alloc, scals, runs, len),
         )),
         Column::VarBinary((col, scals)) => Column::VarBinary((
-            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])),
-            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| scals[i])),
+            gather_runs(alloc, col, runs, len),
+            gather_runs(alloc, scals, runs, len),
         )),
-        Column::Scalar(col) => {
-            Column::Scalar(alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])))
+        Column::Scalar(col) => Column::Scalar(gather_runs(alloc, col, runs, len)),
+        Column::Decimal75(precision, scale, col) => {
+            Column::Decimal75(*precision, *scale, gather_runs(alloc, col, runs, len))
         }
-        Column::Decimal75(precision, scale, col) => Column::Decimal75(
-            *precision,
-            *scale,
-            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])),
-        ),
-        Column::TimestampTZ(tu, tz, col) => Column::TimestampTZ(
-            *tu,
-            *tz,
-            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])),
-        ),
+        Column::TimestampTZ(tu, tz, col) => {
+            Column::TimestampTZ(*tu, *tz, gather_runs(alloc, col, runs, len))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{index_runs, selected_runs};
+
+    #[test]
+    fn we_can_find_the_runs_of_a_selection() {
+        assert!(selected_runs(&[]).is_empty());
+        assert!(selected_runs(&[false, false]).is_empty());
+        assert_eq!(
+            selected_runs(&[true, true, true, false, true]),
+            vec![0..3, 4..5]
+        );
+        assert_eq!(
+            selected_runs(&[true, false, true, true, false, false, true]),
+            vec![0..1, 2..4, 6..7]
+        );
+    }
+
+    #[test]
+    fn we_can_find_the_runs_of_indexes() {
+        assert!(index_runs(&[]).is_empty());
+        assert_eq!(index_runs(&[3, 4, 5, 9]), vec![3..6, 9..10]);
+        assert_eq!(
+            index_runs(&[0, 1, 1, 2, 7, 6, 7, 0]),
+            vec![0..2, 1..3, 7..8, 6..8, 0..1]
+        );
     }
 }
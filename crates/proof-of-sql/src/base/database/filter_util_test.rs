@@ -1,9 +1,10 @@
 use crate::base::{
-    database::{filter_util::*, Column},
+    database::{filter_util::*, Column, OwnedColumn},
     math::decimal::Precision,
     scalar::test_scalar::TestScalar,
 };
 use bumpalo::Bump;
+use proptest::prelude::*;
 
 #[test]
 fn we_can_filter_columns() {
@@ -118,3 +119,122 @@ fn we_can_filter_columns_with_varbinary() {
         ]
     );
 }
+
+#[test]
+fn we_can_gather_columns() {
+    let str_scalars: [TestScalar; 5] = ["1".into(), "2".into(), "3".into(), "4".into(), "5".into()];
+    let columns = vec![
+        Column::BigInt(&[1, 2, 3, 4, 5]),
+        Column::VarChar((&["1", "2", "3", "4", "5"], &str_scalars)),
+        Column::Boolean(&[true, false, true, false, true]),
+    ];
+    let alloc = Bump::new();
+    let result = gather_columns(&alloc, &columns, &[3, 4, 0, 1, 1]);
+    assert_eq!(
+        result,
+        vec![
+            Column::BigInt(&[4, 5, 1, 2, 2]),
+            Column::VarChar((
+                &["4", "5", "1", "2", "2"],
+                &["4".into(), "5".into(), "1".into(), "2".into(), "2".into()]
+            )),
+            Column::Boolean(&[false, true, true, false, false]),
+        ]
+    );
+    assert_eq!(
+        gather_columns(&alloc, &columns, &[]),
+        vec![
+            Column::BigInt(&[]),
+            Column::VarChar((&[], &[])),
+            Column::Boolean(&[]),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn we_cannot_gather_columns_with_an_index_out_of_bounds() {
+    let alloc = Bump::new();
+    gather_columns(&alloc, &[Column::<TestScalar>::BigInt(&[1, 2, 3])], &[1, 3]);
+}
+
+/// Check row by row that `result` holds the rows of `column` at `indexes`
+fn assert_rows_at_indexes(
+    result: &Column<TestScalar>,
+    column: &Column<TestScalar>,
+    indexes: &[usize],
+) {
+    let owned_result = OwnedColumn::from(result);
+    let owned_column = OwnedColumn::from(column);
+    assert_eq!(owned_result.column_type(), owned_column.column_type());
+    assert_eq!(owned_result.len(), indexes.len());
+    for (row, &index) in indexes.iter().enumerate() {
+        assert_eq!(
+            owned_result.slice(row, row + 1),
+            owned_column.slice(index, index + 1)
+        );
+    }
+    // Strings and binary data are compared by their scalars as well
+    let scalars = column.to_scalar();
+    let expected_scalars: Vec<_> = indexes.iter().map(|&index| scalars[index]).collect();
+    assert_eq!(result.to_scalar(), expected_scalars);
+}
+
+/// An arbitrary column along with a selection vector of the same length
+fn column_and_selection() -> impl Strategy<Value = (OwnedColumn<TestScalar>, Vec<bool>)> {
+    any::<OwnedColumn<TestScalar>>().prop_flat_map(|column| {
+        let len = column.len();
+        (Just(column), prop::collection::vec(any::<bool>(), len))
+    })
+}
+
+/// An arbitrary nonempty column along with indexes of its rows, which are sorted half of the time
+/// so that there are runs of consecutive rows
+fn column_and_indexes() -> impl Strategy<Value = (OwnedColumn<TestScalar>, Vec<usize>)> {
+    any::<OwnedColumn<TestScalar>>()
+        .prop_filter("columns must be nonempty", |column| !column.is_empty())
+        .prop_flat_map(|column| {
+            let len = column.len();
+            let indexes = prop::collection::vec(0..len, 0..=2 * len);
+            (
+                Just(column),
+                prop_oneof![
+                    indexes.clone(),
+                    indexes.prop_map(|mut indexes| {
+                        indexes.sort_unstable();
+                        indexes
+                    })
+                ],
+            )
+        })
+}
+
+proptest! {
+    #[test]
+    fn filtering_matches_keeping_the_selected_rows_one_by_one(
+        (owned_column, selection) in column_and_selection()
+    ) {
+        let alloc = Bump::new();
+        let column = Column::from_owned_column(&owned_column, &alloc);
+        let (result, len) = filter_columns(&alloc, &[column], &selection);
+        let indexes: Vec<usize> = selection
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &selected)| selected.then_some(i))
+            .collect();
+        prop_assert_eq!(len, indexes.len());
+        assert_rows_at_indexes(&result[0], &column, &indexes);
+    }
+
+    #[test]
+    fn gathering_matches_copying_the_rows_one_by_one(
+        (owned_column, indexes) in column_and_indexes()
+    ) {
+        let alloc = Bump::new();
+        let column = Column::from_owned_column(&owned_column, &alloc);
+        let result = gather_columns(&alloc, &[column], &indexes);
+        prop_assert_eq!(result.len(), 1);
+        assert_rows_at_indexes(&result[0], &column, &indexes);
+        prop_assert_eq!(filter_column_by_index(&alloc, &column, &indexes), result[0]);
+    }
+}
@@ -5,13 +5,41 @@ use crate::base::{
     scalar::{Scalar, ScalarExt},
 };
 use alloc::{string::String, vec::Vec};
+use num_bigint::{BigInt, BigUint};
+use num_traits::Zero;
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+/// Errors that can occur when coercing a [`LiteralValue`] to a [`ColumnType`].
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum CoercionError {
+    /// There is no coercion between the types.
+    #[snafu(display("Literal of type {from} can not be coerced to {to}"))]
+    IncompatibleTypes {
+        /// The type of the literal.
+        from: ColumnType,
+        /// The type to which we are trying to coerce.
+        to: ColumnType,
+    },
+    /// The value of the literal can not be represented exactly in the target type.
+    #[snafu(display("Literal of type {from} is not representable as {to}"))]
+    NotRepresentable {
+        /// The type of the literal.
+        from: ColumnType,
+        /// The type to which we are trying to coerce.
+        to: ColumnType,
+    },
+}
 
 /// Represents a literal value.
 ///
 /// Note: The types here should correspond to native SQL database types.
 /// See `<https://ignite.apache.org/docs/latest/sql-reference/data-types>` for
 /// a description of the native types used by Apache Ignite.
+///
+/// Apart from [`LiteralValue::Scalar`], literals do not depend on the scalar field of a commitment
+/// scheme. They are only converted to scalars by [`LiteralValue::to_scalar`] when a plan is proven
+/// or verified, so a plan holding them can be used with any commitment scheme.
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum LiteralValue {
@@ -38,9 +66,11 @@ pub enum LiteralValue {
     /// i128 literals
     Int128(i128),
     /// Decimal literals with a max width of 252 bits
-    ///  - the backing store maps to the type [`crate::base::scalar::Curve25519Scalar`]
+    ///  - the backing store is the unscaled value, which fits in the scalar field of every
+    ///    supported commitment scheme
     Decimal75(Precision, i8, I256),
     /// Scalar literals. The underlying `[u64; 4]` is the limbs of the canonical form of the literal
+    ///  - the limbs are only meaningful in the field of the scheme they were produced for
     Scalar([u64; 4]),
     /// `TimeStamp` defined over a unit (s, ms, ns, etc) and timezone with backing store
     /// mapped to i64, which is time units since unix epoch
@@ -67,8 +97,77 @@ impl LiteralValue {
         }
     }
 
+    /// Coerces the literal to a literal of type `target` without changing its value.
+    ///
+    /// The supported coercions are
+    ///
+    /// | From | To |
+    /// |------|----|
+    /// | any type | the same type |
+    /// | integers and decimals | integers and decimals which represent the value exactly |
+    /// | timestamps | timestamps of any time zone with a unit which represents the instant exactly |
+    ///
+    /// Changing the time zone of a timestamp keeps the instant, since timestamps are stored as
+    /// time units since the unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoercionError::IncompatibleTypes`] if there is no coercion from the type of the
+    /// literal to `target` and [`CoercionError::NotRepresentable`] if the value is out of the range
+    /// of `target` or has more decimal places than `target`.
+    pub fn try_coerce_to(&self, target: &ColumnType) -> Result<LiteralValue, CoercionError> {
+        let from = self.column_type();
+        if from == *target {
+            return Ok(self.clone());
+        }
+        let incompatible_types = CoercionError::IncompatibleTypes { from, to: *target };
+        let not_representable = CoercionError::NotRepresentable { from, to: *target };
+        let is_exact_numeric = |column_type: ColumnType| {
+            column_type.is_integer() || matches!(column_type, ColumnType::Decimal75(_, _))
+        };
+        match (self, *target) {
+            (Self::TimeStampTZ(_, _, time), ColumnType::TimestampTZ(unit, timezone)) => {
+                try_rescale(BigInt::from(*time), from.scale(), target.scale())
+                    .and_then(|time| i64::try_from(time).ok())
+                    .map(|time| Self::TimeStampTZ(unit, timezone, time))
+                    .ok_or(not_representable)
+            }
+            (_, target) if is_exact_numeric(from) && is_exact_numeric(target) => {
+                let value = match self {
+                    Self::Uint8(i) => BigInt::from(*i),
+                    Self::TinyInt(i) => BigInt::from(*i),
+                    Self::SmallInt(i) => BigInt::from(*i),
+                    Self::Int(i) => BigInt::from(*i),
+                    Self::BigInt(i) => BigInt::from(*i),
+                    Self::Int128(i) => BigInt::from(*i),
+                    Self::Decimal75(_, _, i) => i.to_num_bigint(),
+                    _ => return Err(incompatible_types),
+                };
+                let value =
+                    try_rescale(value, from.scale(), target.scale()).ok_or(not_representable)?;
+                let coerced = match target {
+                    ColumnType::Uint8 => u8::try_from(value).ok().map(Self::Uint8),
+                    ColumnType::TinyInt => i8::try_from(value).ok().map(Self::TinyInt),
+                    ColumnType::SmallInt => i16::try_from(value).ok().map(Self::SmallInt),
+                    ColumnType::Int => i32::try_from(value).ok().map(Self::Int),
+                    ColumnType::BigInt => i64::try_from(value).ok().map(Self::BigInt),
+                    ColumnType::Int128 => i128::try_from(value).ok().map(Self::Int128),
+                    ColumnType::Decimal75(precision, scale) => (value.magnitude()
+                        < &BigUint::from(10_u8).pow(u32::from(precision.value())))
+                        .then(|| Self::Decimal75(precision, scale, I256::from_num_bigint(&value))),
+                    _ => return Err(incompatible_types),
+                };
+                coerced.ok_or(not_representable)
+            }
+            _ => Err(incompatible_types),
+        }
+    }
+
     /// Converts the literal to a scalar
-    pub(crate) fn to_scalar<S: Scalar>(&self) -> S {
+    ///
+    /// This is consistent with the scalars of a column containing the literal.
+    #[must_use]
+    pub fn to_scalar<S: Scalar>(&self) -> S {
         match self {
             Self::Boolean(b) => b.into(),
             Self::Uint8(i) => i.into(),
@@ -85,3 +184,15 @@ impl LiteralValue {
         }
     }
 }
+
+/// Rescales `value` from `from_scale` to `to_scale`, returning `None` if this would drop nonzero
+/// digits
+fn try_rescale(value: BigInt, from_scale: Option<i8>, to_scale: Option<i8>) -> Option<BigInt> {
+    let (from_scale, to_scale) = (from_scale?, to_scale?);
+    let factor = BigInt::from(10).pow(u32::from(from_scale.abs_diff(to_scale)));
+    if to_scale >= from_scale {
+        Some(value * factor)
+    } else {
+        (&value % &factor).is_zero().then(|| value / factor)
+    }
+}
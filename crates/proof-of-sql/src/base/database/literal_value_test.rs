@@ -0,0 +1,218 @@
+use super::{CoercionError, Column, ColumnType, LiteralValue};
+use crate::base::{
+    math::{decimal::Precision, i256::I256},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    scalar::test_scalar::TestScalar,
+};
+use bumpalo::Bump;
+
+/// One literal of every column type, along with a few decimals and timestamps
+fn sample_literals() -> Vec<LiteralValue> {
+    vec![
+        LiteralValue::Boolean(true),
+        LiteralValue::Uint8(200),
+        LiteralValue::TinyInt(-100),
+        LiteralValue::SmallInt(1_000),
+        LiteralValue::Int(-100_000),
+        LiteralValue::BigInt(10_000_000_000),
+        LiteralValue::Int128(-100_000_000_000_000_000_000),
+        LiteralValue::VarChar("proof".to_string()),
+        LiteralValue::VarBinary(vec![1, 2, 3]),
+        LiteralValue::Decimal75(Precision::new(10).unwrap(), 2, I256::from(12_345)),
+        LiteralValue::Scalar([1, 0, 0, 0]),
+        LiteralValue::TimeStampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), 1),
+        LiteralValue::TimeStampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::new(3600), 1_500),
+    ]
+}
+
+#[test]
+fn we_can_coerce_literals_according_to_the_coercion_matrix() {
+    // Rows are the literals of `sample_literals` and columns are their types, in the same order.
+    // `Y` is a successful coercion, `R` is a value which is not representable and `.` is a pair
+    // of incompatible types.
+    let matrix = [
+        "Y............",
+        ".YRYYYY..Y...",
+        ".RYYYYY..Y...",
+        ".RRYYYY..Y...",
+        ".RRRYYY..Y...",
+        ".RRRRYY..R...",
+        ".RRRRRY..R...",
+        ".......Y.....",
+        "........Y....",
+        ".RRRRRR..Y...",
+        "..........Y..",
+        "...........YY",
+        "...........RY",
+    ];
+    let literals = sample_literals();
+    for (literal, row) in literals.iter().zip(matrix) {
+        for (target, expected) in literals
+            .iter()
+            .map(LiteralValue::column_type)
+            .zip(row.chars())
+        {
+            let from = literal.column_type();
+            let res = literal.try_coerce_to(&target);
+            match expected {
+                'Y' => assert_eq!(res.unwrap().column_type(), target),
+                'R' => assert_eq!(
+                    res,
+                    Err(CoercionError::NotRepresentable { from, to: target })
+                ),
+                _ => assert_eq!(
+                    res,
+                    Err(CoercionError::IncompatibleTypes { from, to: target })
+                ),
+            }
+        }
+    }
+}
+
+#[test]
+fn we_can_coerce_integers_and_decimals_without_changing_their_values() {
+    assert_eq!(
+        LiteralValue::TinyInt(-100).try_coerce_to(&ColumnType::Int128),
+        Ok(LiteralValue::Int128(-100))
+    );
+    assert_eq!(
+        LiteralValue::BigInt(255).try_coerce_to(&ColumnType::Uint8),
+        Ok(LiteralValue::Uint8(255))
+    );
+    assert_eq!(
+        LiteralValue::Int(-123)
+            .try_coerce_to(&ColumnType::Decimal75(Precision::new(5).unwrap(), 2)),
+        Ok(LiteralValue::Decimal75(
+            Precision::new(5).unwrap(),
+            2,
+            I256::from(-12_300)
+        ))
+    );
+    // Negative scales drop trailing zeros
+    assert_eq!(
+        LiteralValue::Int(-12_300)
+            .try_coerce_to(&ColumnType::Decimal75(Precision::new(3).unwrap(), -2)),
+        Ok(LiteralValue::Decimal75(
+            Precision::new(3).unwrap(),
+            -2,
+            I256::from(-123)
+        ))
+    );
+    assert_eq!(
+        LiteralValue::Decimal75(Precision::new(10).unwrap(), 2, I256::from(-12_300))
+            .try_coerce_to(&ColumnType::SmallInt),
+        Ok(LiteralValue::SmallInt(-123))
+    );
+    assert_eq!(
+        LiteralValue::Decimal75(Precision::new(10).unwrap(), 2, I256::from(12_340))
+            .try_coerce_to(&ColumnType::Decimal75(Precision::new(4).unwrap(), 1)),
+        Ok(LiteralValue::Decimal75(
+            Precision::new(4).unwrap(),
+            1,
+            I256::from(1_234)
+        ))
+    );
+    assert_eq!(
+        LiteralValue::Int128(i128::MIN)
+            .try_coerce_to(&ColumnType::Decimal75(Precision::new(75).unwrap(), 30)),
+        Ok(LiteralValue::Decimal75(
+            Precision::new(75).unwrap(),
+            30,
+            I256::from_num_bigint(
+                &(num_bigint::BigInt::from(i128::MIN) * num_bigint::BigInt::from(10).pow(30))
+            )
+        ))
+    );
+}
+
+#[test]
+fn we_cannot_coerce_integers_and_decimals_to_types_which_can_not_represent_them() {
+    let decimal_type = ColumnType::Decimal75(Precision::new(4).unwrap(), 1);
+    for (literal, target) in [
+        (LiteralValue::SmallInt(-1), ColumnType::Uint8),
+        (LiteralValue::Int(128), ColumnType::TinyInt),
+        (LiteralValue::Int128(i128::MAX), ColumnType::BigInt),
+        // 1000.0 has five digits
+        (LiteralValue::SmallInt(1_000), decimal_type),
+        (LiteralValue::SmallInt(-1_000), decimal_type),
+        // 1.25 has two decimal places
+        (
+            LiteralValue::Decimal75(Precision::new(3).unwrap(), 2, I256::from(125)),
+            decimal_type,
+        ),
+        (
+            LiteralValue::Decimal75(Precision::new(3).unwrap(), 2, I256::from(-125)),
+            ColumnType::Int,
+        ),
+    ] {
+        assert_eq!(
+            literal.try_coerce_to(&target),
+            Err(CoercionError::NotRepresentable {
+                from: literal.column_type(),
+                to: target
+            })
+        );
+    }
+    assert_eq!(
+        LiteralValue::SmallInt(999).try_coerce_to(&decimal_type),
+        Ok(LiteralValue::Decimal75(
+            Precision::new(4).unwrap(),
+            1,
+            I256::from(9_990)
+        ))
+    );
+}
+
+#[test]
+fn we_can_coerce_timestamps_between_units_and_time_zones() {
+    let plus_two = PoSQLTimeZone::new(7200);
+    assert_eq!(
+        LiteralValue::TimeStampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), -2).try_coerce_to(
+            &ColumnType::TimestampTZ(PoSQLTimeUnit::Nanosecond, plus_two)
+        ),
+        Ok(LiteralValue::TimeStampTZ(
+            PoSQLTimeUnit::Nanosecond,
+            plus_two,
+            -2_000_000_000
+        ))
+    );
+    assert_eq!(
+        LiteralValue::TimeStampTZ(PoSQLTimeUnit::Microsecond, plus_two, 3_000).try_coerce_to(
+            &ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc())
+        ),
+        Ok(LiteralValue::TimeStampTZ(
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::utc(),
+            3
+        ))
+    );
+    // Overflow of the finer unit and a loss of precision of the coarser unit
+    for (literal, target) in [
+        (
+            LiteralValue::TimeStampTZ(PoSQLTimeUnit::Second, plus_two, i64::MAX),
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, plus_two),
+        ),
+        (
+            LiteralValue::TimeStampTZ(PoSQLTimeUnit::Nanosecond, plus_two, 1),
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Microsecond, plus_two),
+        ),
+    ] {
+        assert_eq!(
+            literal.try_coerce_to(&target),
+            Err(CoercionError::NotRepresentable {
+                from: literal.column_type(),
+                to: target
+            })
+        );
+    }
+}
+
+#[test]
+fn we_can_convert_literals_to_the_scalars_of_the_columns_containing_them() {
+    let alloc = Bump::new();
+    for literal in sample_literals() {
+        let column = Column::<TestScalar>::from_literal_with_length(&literal, 2, &alloc);
+        assert_eq!(column.column_type(), literal.column_type());
+        assert_eq!(column.scalar_at(1), Some(literal.to_scalar::<TestScalar>()));
+    }
+}
@@ -7,6 +7,12 @@ pub use accessor::{CommitmentAccessor, DataAccessor, MetadataAccessor, SchemaAcc
 mod column;
 pub use column::{Column, ColumnField, ColumnRef, ColumnType};
 
+mod boolean_bitmap;
+pub use boolean_bitmap::{BooleanBitmap, BooleanBitmapIter};
+
+mod delta_encoded_column;
+pub use delta_encoded_column::{DeltaEncodedColumn, DeltaEncodedColumnIter, DeltaEncodingError};
+
 #[cfg_attr(not(test), expect(dead_code))]
 pub(crate) mod slice_operation;
 
@@ -27,7 +33,7 @@ mod column_comparison_operation;
 pub(super) use column_comparison_operation::{ComparisonOp, EqualOp, GreaterThanOp, LessThanOp};
 
 mod column_index_operation;
-pub(super) use column_index_operation::apply_column_to_indexes;
+pub(crate) use column_index_operation::apply_column_to_indexes;
 
 mod column_repetition_operation;
 pub(super) use column_repetition_operation::{ColumnRepeatOp, ElementwiseRepeatOp, RepetitionOp};
@@ -42,7 +48,9 @@ mod columnar_value;
 pub use columnar_value::ColumnarValue;
 
 mod literal_value;
-pub use literal_value::LiteralValue;
+pub use literal_value::{CoercionError, LiteralValue};
+#[cfg(test)]
+mod literal_value_test;
 
 mod error;
 pub use error::ParseError;
@@ -76,6 +84,13 @@ pub use owned_table::{OwnedTable, OwnedTableError};
 mod owned_table_test;
 pub mod owned_table_utility;
 
+mod owned_table_diff;
+pub use owned_table_diff::{
+    CellMismatch, TableDiff, TableDiffOptions, UnmatchedRow, DEFAULT_MAX_TABLE_DIFF_MISMATCHES,
+};
+#[cfg(test)]
+mod owned_table_diff_test;
+
 mod table;
 #[cfg(test)]
 pub(crate) use table::TableError;
@@ -110,8 +125,16 @@ pub use table_test_accessor::TableTestAccessor;
 #[cfg(all(test, feature = "blitzar"))]
 mod table_test_accessor_test;
 
-/// TODO: add docs
-pub(crate) mod filter_util;
+#[cfg(feature = "std")]
+mod prefetching_accessor;
+#[cfg(feature = "std")]
+pub use prefetching_accessor::{
+    ColumnFetcher, PrefetchError, PrefetchOptions, PrefetchingAccessor,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod prefetching_accessor_test;
+
+pub mod filter_util;
 #[cfg(test)]
 mod filter_util_test;
 
@@ -4,6 +4,7 @@
 /// This is the analog of an arrow Array.
 use super::{Column, ColumnCoercionError, ColumnType, OwnedColumnError, OwnedColumnResult};
 use crate::base::{
+    encoded_size::{seq_size, LENGTH_SIZE, SCALAR_SIZE, VARIANT_SIZE},
     math::{
         decimal::Precision,
         permutation::{Permutation, PermutationError},
@@ -16,6 +17,7 @@ use alloc::{
     string::{String, ToString},
     vec::Vec,
 };
+use core::mem::size_of;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
@@ -91,6 +93,34 @@ impl<S: Scalar> OwnedColumn<S> {
         }
     }
 
+    /// Returns the number of bytes the column takes up when serialized with bincode's legacy
+    /// configuration, computed without serializing it.
+    pub(crate) fn encoded_size_hint(&self) -> usize {
+        let data_size = match self {
+            OwnedColumn::Boolean(col) => seq_size(col.len(), size_of::<bool>()),
+            OwnedColumn::TinyInt(col) => seq_size(col.len(), size_of::<i8>()),
+            OwnedColumn::Uint8(col) => seq_size(col.len(), size_of::<u8>()),
+            OwnedColumn::SmallInt(col) => seq_size(col.len(), size_of::<i16>()),
+            OwnedColumn::Int(col) => seq_size(col.len(), size_of::<i32>()),
+            OwnedColumn::BigInt(col) => seq_size(col.len(), size_of::<i64>()),
+            OwnedColumn::Int128(col) => seq_size(col.len(), size_of::<i128>()),
+            // The unit is an enum and the timezone an `i32` offset
+            OwnedColumn::TimestampTZ(_, _, col) => {
+                VARIANT_SIZE + size_of::<i32>() + seq_size(col.len(), size_of::<i64>())
+            }
+            OwnedColumn::VarChar(col) => {
+                LENGTH_SIZE + col.iter().map(|s| seq_size(s.len(), 1)).sum::<usize>()
+            }
+            OwnedColumn::VarBinary(col) => {
+                LENGTH_SIZE + col.iter().map(|b| seq_size(b.len(), 1)).sum::<usize>()
+            }
+            // The precision and the scale are one byte each
+            OwnedColumn::Decimal75(_, _, col) => 2 + seq_size(col.len(), SCALAR_SIZE),
+            OwnedColumn::Scalar(col) => seq_size(col.len(), SCALAR_SIZE),
+        };
+        VARIANT_SIZE + data_size
+    }
+
     /// Returns the column with its entries permutated
     pub fn try_permute(&self, permutation: &Permutation) -> Result<Self, PermutationError> {
         Ok(match self {
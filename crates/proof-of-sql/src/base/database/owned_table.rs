@@ -1,9 +1,14 @@
-use super::{ColumnField, OwnedColumn, Table};
+use super::{Column, ColumnField, OwnedColumn, Table, TableOptions};
 use crate::base::{
-    database::ColumnCoercionError, map::IndexMap, polynomial::compute_evaluation_vector,
-    scalar::Scalar,
+    database::ColumnCoercionError,
+    encoded_size::{seq_size, LENGTH_SIZE},
+    map::IndexMap,
+    polynomial::compute_evaluation_vector,
+    scalar::{Scalar, ScalarExt},
 };
-use alloc::{vec, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
+use bumpalo::Bump;
+use core::cell::OnceCell;
 use itertools::{EitherOrBoth, Itertools};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
@@ -38,18 +43,27 @@ pub(crate) enum TableCoercionError {
 #[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct OwnedTable<S: Scalar> {
     table: IndexMap<Ident, OwnedColumn<S>>,
+    /// The scalars of the varchar and varbinary columns, computed at most once
+    #[serde(skip)]
+    varlen_scalars: OnceCell<IndexMap<Ident, Vec<S>>>,
 }
 impl<S: Scalar> OwnedTable<S> {
     /// Creates a new [`OwnedTable`].
     pub fn try_new(table: IndexMap<Ident, OwnedColumn<S>>) -> Result<Self, OwnedTableError> {
         if table.is_empty() {
-            return Ok(Self { table });
+            return Ok(Self {
+                table,
+                varlen_scalars: OnceCell::new(),
+            });
         }
         let num_rows = table[0].len();
         if table.values().any(|column| column.len() != num_rows) {
             Err(OwnedTableError::ColumnLengthMismatch)
         } else {
-            Ok(Self { table })
+            Ok(Self {
+                table,
+                varlen_scalars: OnceCell::new(),
+            })
         }
     }
     /// Creates a new [`OwnedTable`].
@@ -126,6 +140,21 @@ impl<S: Scalar> OwnedTable<S> {
     pub fn inner_table(&self) -> &IndexMap<Ident, OwnedColumn<S>> {
         &self.table
     }
+    /// Returns the number of bytes the table takes up when serialized with bincode's legacy
+    /// configuration, computed without serializing it.
+    pub(crate) fn encoded_size_hint(&self) -> usize {
+        LENGTH_SIZE
+            + self
+                .table
+                .iter()
+                .map(|(name, column)| {
+                    seq_size(name.value.len(), 1)
+                        + 1
+                        + name.quote_style.map_or(0, char::len_utf8)
+                        + column.encoded_size_hint()
+                })
+                .sum::<usize>()
+    }
     /// Returns the columns of this table as an Iterator
     pub fn column_names(&self) -> impl Iterator<Item = &Ident> {
         self.table.keys()
@@ -136,6 +165,66 @@ impl<S: Scalar> OwnedTable<S> {
         self.table.get_index(index).map(|(_, v)| v)
     }
 
+    /// The scalars of the varchar and varbinary columns, which are computed on the first call
+    fn varlen_scalars(&self) -> &IndexMap<Ident, Vec<S>> {
+        self.varlen_scalars.get_or_init(|| {
+            self.table
+                .iter()
+                .filter_map(|(name, column)| match column {
+                    OwnedColumn::VarChar(col) => {
+                        Some((name.clone(), col.iter().map(S::from).collect()))
+                    }
+                    OwnedColumn::VarBinary(col) => Some((
+                        name.clone(),
+                        col.iter().map(|b| S::from_byte_slice_via_hash(b)).collect(),
+                    )),
+                    _ => None,
+                })
+                .collect()
+        })
+    }
+
+    /// Borrows the column with the given name as a [`Column`]
+    ///
+    /// Fixed-width columns are borrowed directly. The scalars of varchar and varbinary columns
+    /// are cached in this table, so only the slices of references are allocated in `alloc`.
+    pub(crate) fn column_view<'a>(
+        &'a self,
+        name: &Ident,
+        alloc: &'a Bump,
+    ) -> Option<Column<'a, S>> {
+        let column = self.table.get(name)?;
+        Some(match column {
+            OwnedColumn::VarChar(col) => Column::VarChar((
+                alloc.alloc_slice_fill_iter(col.iter().map(String::as_str)),
+                self.varlen_scalars()[name].as_slice(),
+            )),
+            OwnedColumn::VarBinary(col) => Column::VarBinary((
+                alloc.alloc_slice_fill_iter(col.iter().map(Vec::as_slice)),
+                self.varlen_scalars()[name].as_slice(),
+            )),
+            _ => Column::from_owned_column(column, alloc),
+        })
+    }
+
+    /// Borrows this table as a [`Table`] without copying its columns
+    ///
+    /// The scalars of varchar and varbinary columns are computed on the first call and cached in
+    /// this table, so repeated calls only allocate the slices of string and byte references.
+    #[must_use]
+    pub fn as_table<'a>(&'a self, alloc: &'a Bump) -> Table<'a, S> {
+        Table::try_from_iter_with_options(
+            self.table.keys().map(|name| {
+                let column = self
+                    .column_view(name, alloc)
+                    .expect("The column is in the table");
+                (name.clone(), column)
+            }),
+            TableOptions::new(Some(self.num_rows())),
+        )
+        .expect("Owned tables have columns of equal length")
+    }
+
     pub(crate) fn mle_evaluations(&self, evaluation_point: &[S]) -> Vec<S> {
         let mut evaluation_vector = vec![S::ZERO; self.num_rows()];
         compute_evaluation_vector(&mut evaluation_vector, evaluation_point);
@@ -196,8 +285,8 @@ mod tests {
     use super::OwnedTable;
     use crate::base::{
         database::{
-            owned_table_utility::*, table_utility::*, ColumnCoercionError, Table,
-            TableCoercionError, TableOptions,
+            owned_table_utility::*, table_utility::*, Column, ColumnCoercionError, OwnedColumn,
+            Table, TableCoercionError, TableOptions,
         },
         map::indexmap,
         posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
@@ -379,4 +468,66 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn we_can_view_an_owned_table_as_a_table() {
+        let alloc = Bump::new();
+        let owned = owned_table::<TestScalar>([
+            bigint("bigint", [0_i64, 1, i64::MIN]),
+            varchar("varchar", ["a", "b", "c"]),
+            varbinary("varbinary", [vec![1_u8], vec![], vec![2, 3]]),
+            boolean("boolean", [true, false, true]),
+        ]);
+        let view = owned.as_table(&alloc);
+        let expected = table::<TestScalar>([
+            borrowed_bigint("bigint", [0_i64, 1, i64::MIN], &alloc),
+            borrowed_varchar("varchar", ["a", "b", "c"], &alloc),
+            (
+                "varbinary".into(),
+                Column::from_owned_column(&owned.inner_table()[2], &alloc),
+            ),
+            borrowed_boolean("boolean", [true, false, true], &alloc),
+        ]);
+        assert_eq!(view, expected);
+        assert_eq!(OwnedTable::from(&view), owned);
+    }
+
+    #[test]
+    fn we_can_view_an_owned_table_repeatedly_without_recomputing_scalars() {
+        let alloc = Bump::new();
+        let owned = owned_table::<TestScalar>([
+            bigint("bigint", [0_i64, 1]),
+            varchar("varchar", ["a", "b"]),
+        ]);
+        let first = owned.as_table(&alloc);
+        let second = owned.as_table(&alloc);
+        assert_eq!(first, second);
+        // The scalars of the varchar column and the fixed-width column are shared
+        let (Some(Column::VarChar((_, first_scalars))), Some(Column::VarChar((_, second_scalars)))) =
+            (first.column(1), second.column(1))
+        else {
+            panic!("The second column is a varchar column")
+        };
+        assert!(core::ptr::eq(*first_scalars, *second_scalars));
+        let (Some(Column::BigInt(first_bigints)), OwnedColumn::BigInt(bigints)) =
+            (first.column(0), &owned.inner_table()[0])
+        else {
+            panic!("The first column is a bigint column")
+        };
+        assert!(core::ptr::eq(*first_bigints, bigints.as_slice()));
+    }
+
+    #[test]
+    fn we_can_view_owned_tables_without_rows_or_columns_as_tables() {
+        let alloc = Bump::new();
+        let no_rows = owned_table::<TestScalar>([varchar("varchar", [""; 0])]);
+        let view = no_rows.as_table(&alloc);
+        assert_eq!(view.num_rows(), 0);
+        assert_eq!(OwnedTable::from(&view), no_rows);
+
+        let no_columns = owned_table::<TestScalar>([]);
+        let view = no_columns.as_table(&alloc);
+        assert_eq!(view.num_rows(), 0);
+        assert_eq!(view.num_columns(), 0);
+    }
 }
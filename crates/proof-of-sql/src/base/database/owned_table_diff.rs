@@ -0,0 +1,460 @@
+use super::{ColumnType, OwnedColumn, OwnedTable};
+use crate::base::{
+    map::IndexMap,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    scalar::Scalar,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use chrono::{DateTime, FixedOffset};
+use core::fmt;
+use num_bigint::{BigInt, Sign};
+use sqlparser::ast::Ident;
+
+/// The number of mismatching cells or rows a [`TableDiff`] lists by default
+pub const DEFAULT_MAX_TABLE_DIFF_MISMATCHES: usize = 10;
+
+/// Options controlling how [`OwnedTable::diff_with_options`] compares two tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableDiffOptions {
+    max_mismatches: usize,
+    unordered: bool,
+}
+
+impl Default for TableDiffOptions {
+    fn default() -> Self {
+        Self {
+            max_mismatches: DEFAULT_MAX_TABLE_DIFF_MISMATCHES,
+            unordered: false,
+        }
+    }
+}
+
+impl TableDiffOptions {
+    /// Sets the number of mismatching cells or rows listed in the diff.
+    ///
+    /// Mismatches beyond this are only counted.
+    #[must_use]
+    pub fn with_max_mismatches(self, max_mismatches: usize) -> Self {
+        Self {
+            max_mismatches,
+            ..self
+        }
+    }
+
+    /// Returns the number of mismatching cells or rows listed in the diff
+    #[must_use]
+    pub fn max_mismatches(&self) -> usize {
+        self.max_mismatches
+    }
+
+    /// Compares the rows of the tables as multisets, ignoring their order
+    ///
+    /// This is needed for the results of plans which don't guarantee an order of their rows.
+    #[must_use]
+    pub fn unordered(self) -> Self {
+        Self {
+            unordered: true,
+            ..self
+        }
+    }
+
+    /// Returns whether the rows of the tables are compared as multisets
+    #[must_use]
+    pub fn is_unordered(&self) -> bool {
+        self.unordered
+    }
+}
+
+/// A cell whose value differs between the left and right table of a [`TableDiff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellMismatch {
+    /// The index of the row
+    pub row: usize,
+    /// The name of the column
+    pub column: Ident,
+    /// The value in the left table
+    pub left: String,
+    /// The value in the right table
+    pub right: String,
+}
+
+/// A row of one table of an unordered [`TableDiff`] without an equal row in the other table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmatchedRow {
+    /// The index of the row
+    pub row: usize,
+    /// The values of the row, in the order of the columns of the left table
+    pub values: Vec<String>,
+}
+
+/// The differences between two [`OwnedTable`]s, see [`OwnedTable::diff`].
+///
+/// Values are rendered as they would be written in SQL: decimals with their scale, timestamps in
+/// RFC 3339 with the offset of their timezone and strings quoted. Cells and rows are only compared
+/// for the columns with the same name and type in both tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableDiff {
+    /// The columns only in the left table
+    pub left_only_columns: Vec<Ident>,
+    /// The columns only in the right table
+    pub right_only_columns: Vec<Ident>,
+    /// The columns with another type in each table, with their left and right types
+    pub retyped_columns: Vec<(Ident, ColumnType, ColumnType)>,
+    /// The columns of the left and right table, if they have the same columns in another order
+    pub column_orders: Option<(Vec<Ident>, Vec<Ident>)>,
+    /// The numbers of rows of the left and right table, if they differ
+    pub row_counts: Option<(usize, usize)>,
+    /// The first mismatching cells of the rows both tables have, by row
+    pub cell_mismatches: Vec<CellMismatch>,
+    /// The number of mismatching cells, including those not listed
+    pub cell_mismatch_count: usize,
+    /// The first rows of the left table without an equal row in the right table
+    pub left_only_rows: Vec<UnmatchedRow>,
+    /// The number of rows of the left table without an equal row in the right table
+    pub left_only_row_count: usize,
+    /// The first rows of the right table without an equal row in the left table
+    pub right_only_rows: Vec<UnmatchedRow>,
+    /// The number of rows of the right table without an equal row in the left table
+    pub right_only_row_count: usize,
+}
+
+impl TableDiff {
+    /// Returns true if the tables are equal
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.left_only_columns.is_empty()
+            && self.right_only_columns.is_empty()
+            && self.retyped_columns.is_empty()
+            && self.column_orders.is_none()
+            && self.row_counts.is_none()
+            && self.cell_mismatch_count == 0
+            && self.left_only_row_count == 0
+            && self.right_only_row_count == 0
+    }
+}
+
+/// Writes a list of column names, e.g. `[a, b]`
+fn write_columns(f: &mut fmt::Formatter<'_>, columns: &[Ident]) -> fmt::Result {
+    write!(f, "[")?;
+    for (index, column) in columns.iter().enumerate() {
+        if index > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{column}")?;
+    }
+    write!(f, "]")
+}
+
+/// Writes unmatched rows followed by the number of rows left out
+fn write_unmatched_rows(
+    f: &mut fmt::Formatter<'_>,
+    rows: &[UnmatchedRow],
+    count: usize,
+    side: &str,
+) -> fmt::Result {
+    for row in rows {
+        writeln!(
+            f,
+            "row {} is only in the {side} table: ({})",
+            row.row,
+            row.values.join(", ")
+        )?;
+    }
+    if count > rows.len() {
+        writeln!(
+            f,
+            "... and {} more rows only in the {side} table",
+            count - rows.len()
+        )?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for TableDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "the tables are equal");
+        }
+        for column in &self.left_only_columns {
+            writeln!(f, "column {column} is only in the left table")?;
+        }
+        for column in &self.right_only_columns {
+            writeln!(f, "column {column} is only in the right table")?;
+        }
+        for (column, left_type, right_type) in &self.retyped_columns {
+            writeln!(
+                f,
+                "column {column} is {left_type} on the left but {right_type} on the right"
+            )?;
+        }
+        if let Some((left_columns, right_columns)) = &self.column_orders {
+            write!(f, "the columns are in another order: ")?;
+            write_columns(f, left_columns)?;
+            write!(f, " and ")?;
+            write_columns(f, right_columns)?;
+            writeln!(f)?;
+        }
+        if let Some((left_rows, right_rows)) = self.row_counts {
+            writeln!(
+                f,
+                "the left table has {left_rows} rows but the right table has {right_rows}"
+            )?;
+        }
+        for mismatch in &self.cell_mismatches {
+            writeln!(
+                f,
+                "row {}, column {}: {} != {}",
+                mismatch.row, mismatch.column, mismatch.left, mismatch.right
+            )?;
+        }
+        if self.cell_mismatch_count > self.cell_mismatches.len() {
+            writeln!(
+                f,
+                "... and {} more mismatching cells",
+                self.cell_mismatch_count - self.cell_mismatches.len()
+            )?;
+        }
+        write_unmatched_rows(f, &self.left_only_rows, self.left_only_row_count, "left")?;
+        write_unmatched_rows(f, &self.right_only_rows, self.right_only_row_count, "right")
+    }
+}
+
+/// Renders a decimal stored as an integer in units of its scale, e.g. `-1.50` for `-150` at scale 2
+fn format_decimal(value: &BigInt, scale: i8) -> String {
+    let sign = if value.sign() == Sign::Minus { "-" } else { "" };
+    let digits = value.magnitude().to_string();
+    match usize::try_from(scale) {
+        Ok(0) => value.to_string(),
+        Ok(scale) => {
+            let digits = format!("{digits:0>width$}", width = scale + 1);
+            let (integer, fraction) = digits.split_at(digits.len() - scale);
+            format!("{sign}{integer}.{fraction}")
+        }
+        Err(_) if value.sign() == Sign::NoSign => value.to_string(),
+        Err(_) => format!("{sign}{digits}{}", "0".repeat(scale.unsigned_abs().into())),
+    }
+}
+
+/// Renders a timestamp in RFC 3339 with the offset of its timezone, falling back to the raw value
+/// if it is out of range
+fn format_timestamp(value: i64, unit: PoSQLTimeUnit, timezone: PoSQLTimeZone) -> String {
+    let utc = match unit {
+        PoSQLTimeUnit::Second => DateTime::from_timestamp(value, 0),
+        PoSQLTimeUnit::Millisecond => DateTime::from_timestamp_millis(value),
+        PoSQLTimeUnit::Microsecond => DateTime::from_timestamp_micros(value),
+        PoSQLTimeUnit::Nanosecond => Some(DateTime::from_timestamp_nanos(value)),
+    };
+    match (utc, FixedOffset::east_opt(timezone.offset())) {
+        (Some(utc), Some(offset)) => {
+            let local = utc.with_timezone(&offset).naive_local();
+            format!("{}T{}{offset}", local.date(), local.time())
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Renders the value of a column at a row
+fn format_cell<S: Scalar>(column: &OwnedColumn<S>, row: usize) -> String {
+    match column {
+        OwnedColumn::Boolean(values) => values[row].to_string(),
+        OwnedColumn::Uint8(values) => values[row].to_string(),
+        OwnedColumn::TinyInt(values) => values[row].to_string(),
+        OwnedColumn::SmallInt(values) => values[row].to_string(),
+        OwnedColumn::Int(values) => values[row].to_string(),
+        OwnedColumn::BigInt(values) => values[row].to_string(),
+        OwnedColumn::Int128(values) => values[row].to_string(),
+        OwnedColumn::VarChar(values) => format!("{:?}", values[row]),
+        OwnedColumn::VarBinary(values) => values[row]
+            .iter()
+            .fold(String::from("0x"), |hex, byte| format!("{hex}{byte:02x}")),
+        OwnedColumn::Decimal75(_, scale, values) => format_decimal(&values[row].into(), *scale),
+        OwnedColumn::Scalar(values) => Into::<BigInt>::into(values[row]).to_string(),
+        OwnedColumn::TimestampTZ(unit, timezone, values) => {
+            format_timestamp(values[row], *unit, *timezone)
+        }
+    }
+}
+
+impl<S: Scalar> OwnedTable<S> {
+    /// Returns the differences between this table, on the left, and `other`, on the right.
+    ///
+    /// The diff is empty exactly if the tables are equal. Otherwise it lists the columns only in
+    /// one of the tables or with another type in each, the column orders if only they differ, the
+    /// row counts if they differ and the first [`DEFAULT_MAX_TABLE_DIFF_MISMATCHES`] mismatching
+    /// cells of the rows both tables have. See [`Self::diff_with_options`] to list more
+    /// mismatches or ignore the order of the rows.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> TableDiff {
+        self.diff_with_options(other, &TableDiffOptions::default())
+    }
+
+    /// Returns the differences between this table, on the left, and `other`, on the right.
+    ///
+    /// If the options are [`TableDiffOptions::unordered`], the rows are compared as multisets
+    /// instead of cell by cell, and the diff lists the rows of each table without an equal row in
+    /// the other. The diff is then empty exactly if the tables have the same columns, in the same
+    /// order, and the same rows in any order.
+    #[must_use]
+    pub fn diff_with_options(&self, other: &Self, options: &TableDiffOptions) -> TableDiff {
+        let left = self.inner_table();
+        let right = other.inner_table();
+        let mut diff = TableDiff {
+            left_only_columns: left
+                .keys()
+                .filter(|name| !right.contains_key(*name))
+                .cloned()
+                .collect(),
+            right_only_columns: right
+                .keys()
+                .filter(|name| !left.contains_key(*name))
+                .cloned()
+                .collect(),
+            ..TableDiff::default()
+        };
+        let mut common_columns = Vec::new();
+        for (name, left_column) in left {
+            if let Some(right_column) = right.get(name) {
+                let (left_type, right_type) =
+                    (left_column.column_type(), right_column.column_type());
+                if left_type == right_type {
+                    common_columns.push((name, left_column, right_column));
+                } else {
+                    diff.retyped_columns
+                        .push((name.clone(), left_type, right_type));
+                }
+            }
+        }
+        if diff.left_only_columns.is_empty()
+            && diff.right_only_columns.is_empty()
+            && left.keys().ne(right.keys())
+        {
+            diff.column_orders = Some((
+                left.keys().cloned().collect(),
+                right.keys().cloned().collect(),
+            ));
+        }
+        let (left_rows, right_rows) = (self.num_rows(), other.num_rows());
+        if left_rows != right_rows {
+            diff.row_counts = Some((left_rows, right_rows));
+        }
+
+        if options.is_unordered() {
+            diff_rows(&mut diff, &common_columns, left_rows, right_rows, options);
+        } else {
+            // Only the columns which differ as a whole need to be compared cell by cell
+            let differing_columns: Vec<_> = common_columns
+                .into_iter()
+                .filter(|(_, left_column, right_column)| left_column != right_column)
+                .collect();
+            for row in 0..left_rows.min(right_rows) {
+                for (name, left_column, right_column) in &differing_columns {
+                    let (left_value, right_value) = (
+                        format_cell(left_column, row),
+                        format_cell(right_column, row),
+                    );
+                    if left_value != right_value {
+                        diff.cell_mismatch_count += 1;
+                        if diff.cell_mismatches.len() < options.max_mismatches() {
+                            diff.cell_mismatches.push(CellMismatch {
+                                row,
+                                column: (*name).clone(),
+                                left: left_value,
+                                right: right_value,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        diff
+    }
+}
+
+/// Lists the rows of each table without an equal row in the other, comparing the common columns
+fn diff_rows<S: Scalar>(
+    diff: &mut TableDiff,
+    common_columns: &[(&Ident, &OwnedColumn<S>, &OwnedColumn<S>)],
+    left_rows: usize,
+    right_rows: usize,
+    options: &TableDiffOptions,
+) {
+    let format_row = |row: usize, is_left: bool| -> Vec<String> {
+        common_columns
+            .iter()
+            .map(|(_, left_column, right_column)| {
+                format_cell(if is_left { left_column } else { right_column }, row)
+            })
+            .collect()
+    };
+    // The rows of the right table not yet matched, by their values
+    let mut unmatched_right_rows: IndexMap<Vec<String>, Vec<usize>> = IndexMap::default();
+    for row in (0..right_rows).rev() {
+        unmatched_right_rows
+            .entry(format_row(row, false))
+            .or_default()
+            .push(row);
+    }
+    for row in 0..left_rows {
+        let values = format_row(row, true);
+        if unmatched_right_rows
+            .get_mut(&values)
+            .and_then(Vec::pop)
+            .is_none()
+        {
+            diff.left_only_row_count += 1;
+            if diff.left_only_rows.len() < options.max_mismatches() {
+                diff.left_only_rows.push(UnmatchedRow { row, values });
+            }
+        }
+    }
+    let mut right_only_rows: Vec<_> = unmatched_right_rows
+        .into_iter()
+        .flat_map(|(values, rows)| rows.into_iter().map(move |row| (row, values.clone())))
+        .collect();
+    right_only_rows.sort_unstable_by_key(|(row, _)| *row);
+    diff.right_only_row_count = right_only_rows.len();
+    diff.right_only_rows = right_only_rows
+        .into_iter()
+        .take(options.max_mismatches())
+        .map(|(row, values)| UnmatchedRow { row, values })
+        .collect();
+}
+
+/// Asserts that two [`OwnedTable`]s are equal, panicking with their
+/// [`TableDiff`](crate::base::database::TableDiff) otherwise.
+///
+/// Unlike `assert_eq!`, the panic message only lists what differs rather than both tables.
+/// [`TableDiffOptions`](crate::base::database::TableDiffOptions) may be passed as a third argument,
+/// e.g. to compare the rows as multisets.
+///
+/// ```
+/// # use proof_of_sql::{
+/// #     assert_tables_equal,
+/// #     base::{
+/// #         database::{owned_table_utility::*, TableDiffOptions},
+/// #         scalar::test_scalar::TestScalar,
+/// #     },
+/// # };
+/// let left = owned_table::<TestScalar>([bigint("a", [1, 2])]);
+/// let right = owned_table::<TestScalar>([bigint("a", [2, 1])]);
+/// assert_tables_equal!(left, right, TableDiffOptions::default().unordered());
+/// ```
+#[macro_export]
+macro_rules! assert_tables_equal {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_tables_equal!(
+            $left,
+            $right,
+            $crate::base::database::TableDiffOptions::default()
+        )
+    };
+    ($left:expr, $right:expr, $options:expr $(,)?) => {{
+        let diff =
+            $crate::base::database::OwnedTable::diff_with_options(&$left, &$right, &$options);
+        assert!(diff.is_empty(), "the tables are not equal:\n{diff}");
+    }};
+}
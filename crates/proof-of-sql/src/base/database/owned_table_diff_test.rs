@@ -0,0 +1,350 @@
+use crate::{
+    assert_tables_equal,
+    base::{
+        database::{
+            owned_table_utility::*, CellMismatch, ColumnType, OwnedTable, TableDiff,
+            TableDiffOptions, UnmatchedRow,
+        },
+        math::decimal::Precision,
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+        scalar::test_scalar::TestScalar,
+    },
+};
+use sqlparser::ast::Ident;
+
+fn mismatch(row: usize, column: &str, left: &str, right: &str) -> CellMismatch {
+    CellMismatch {
+        row,
+        column: column.into(),
+        left: left.into(),
+        right: right.into(),
+    }
+}
+
+fn unmatched_row(row: usize, values: &[&str]) -> UnmatchedRow {
+    UnmatchedRow {
+        row,
+        values: values.iter().map(ToString::to_string).collect(),
+    }
+}
+
+fn diff_unordered(left: &OwnedTable<TestScalar>, right: &OwnedTable<TestScalar>) -> TableDiff {
+    left.diff_with_options(right, &TableDiffOptions::default().unordered())
+}
+
+#[test]
+fn we_get_an_empty_diff_of_equal_tables() {
+    let table = owned_table::<TestScalar>([
+        bigint("a", [1, 2, 3]),
+        varchar("b", ["x", "y", "z"]),
+        boolean("c", [true, false, true]),
+    ]);
+    let diff = table.diff(&table.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff, TableDiff::default());
+    assert_eq!(diff.to_string(), "the tables are equal\n");
+    assert!(diff_unordered(&table, &table).is_empty());
+}
+
+#[test]
+fn we_can_diff_tables_with_missing_extra_and_retyped_columns() {
+    let left =
+        owned_table::<TestScalar>([bigint("a", [1, 2]), bigint("b", [3, 4]), int("c", [5, 6])]);
+    let right = owned_table::<TestScalar>([
+        bigint("a", [1, 2]),
+        bigint("c", [5, 6]),
+        varchar("d", ["x", "y"]),
+    ]);
+    let diff = left.diff(&right);
+    assert_eq!(
+        diff,
+        TableDiff {
+            left_only_columns: vec![Ident::new("b")],
+            right_only_columns: vec![Ident::new("d")],
+            retyped_columns: vec![(Ident::new("c"), ColumnType::Int, ColumnType::BigInt)],
+            ..TableDiff::default()
+        }
+    );
+    assert_eq!(
+        diff.to_string(),
+        "column b is only in the left table\n\
+         column d is only in the right table\n\
+         column c is INT on the left but BIGINT on the right\n"
+    );
+}
+
+#[test]
+fn we_can_diff_tables_with_reordered_columns() {
+    let left = owned_table::<TestScalar>([bigint("a", [1]), bigint("b", [2])]);
+    let right = owned_table::<TestScalar>([bigint("b", [2]), bigint("a", [1])]);
+    let diff = left.diff(&right);
+    assert_eq!(
+        diff.column_orders,
+        Some((
+            vec![Ident::new("a"), Ident::new("b")],
+            vec![Ident::new("b"), Ident::new("a")]
+        ))
+    );
+    assert_eq!(diff.cell_mismatch_count, 0);
+    assert!(!diff.is_empty());
+    assert_eq!(
+        diff.to_string(),
+        "the columns are in another order: [a, b] and [b, a]\n"
+    );
+}
+
+#[test]
+fn we_can_diff_tables_with_different_row_counts() {
+    let left = owned_table::<TestScalar>([bigint("a", [1, 2, 3])]);
+    let right = owned_table::<TestScalar>([bigint("a", [1, 5])]);
+    let diff = left.diff(&right);
+    assert_eq!(
+        diff,
+        TableDiff {
+            row_counts: Some((3, 2)),
+            cell_mismatches: vec![mismatch(1, "a", "2", "5")],
+            cell_mismatch_count: 1,
+            ..TableDiff::default()
+        }
+    );
+    assert_eq!(
+        diff.to_string(),
+        "the left table has 3 rows but the right table has 2\n\
+         row 1, column a: 2 != 5\n"
+    );
+}
+
+#[test]
+fn we_only_list_the_first_cell_mismatches() {
+    let left = owned_table::<TestScalar>([bigint("a", 0..20), bigint("b", 0..20)]);
+    let right = owned_table::<TestScalar>([bigint("a", 1..21), bigint("b", 0..20)]);
+    let diff = left.diff(&right);
+    assert_eq!(diff.cell_mismatch_count, 20);
+    assert_eq!(
+        diff.cell_mismatches,
+        (0..10)
+            .map(|row| mismatch(row, "a", &row.to_string(), &(row + 1).to_string()))
+            .collect::<Vec<_>>()
+    );
+    assert!(diff
+        .to_string()
+        .ends_with("row 9, column a: 9 != 10\n... and 10 more mismatching cells\n"));
+
+    let diff = left.diff_with_options(&right, &TableDiffOptions::default().with_max_mismatches(2));
+    assert_eq!(diff.cell_mismatch_count, 20);
+    assert_eq!(
+        diff.cell_mismatches,
+        vec![mismatch(0, "a", "0", "1"), mismatch(1, "a", "1", "2")]
+    );
+}
+
+#[test]
+fn we_list_cell_mismatches_by_row() {
+    let left = owned_table::<TestScalar>([bigint("a", [1, 2]), varchar("b", ["x", "y"])]);
+    let right = owned_table::<TestScalar>([bigint("a", [0, 0]), varchar("b", ["x\"", "z"])]);
+    assert_eq!(
+        left.diff(&right).cell_mismatches,
+        vec![
+            mismatch(0, "a", "1", "0"),
+            mismatch(0, "b", "\"x\"", "\"x\\\"\""),
+            mismatch(1, "a", "2", "0"),
+            mismatch(1, "b", "\"y\"", "\"z\""),
+        ]
+    );
+}
+
+#[test]
+fn we_render_decimals_with_their_scale() {
+    let left = owned_table::<TestScalar>([
+        decimal75("a", 10, 2, [-150, 5, 0]),
+        decimal75("b", 10, -2, [12, -3, 0]),
+        int128("c", [-1, 0, 1]),
+    ]);
+    let right = owned_table::<TestScalar>([
+        decimal75("a", 10, 2, [150, -5, 1]),
+        decimal75("b", 10, -2, [0, 0, 1]),
+        int128("c", [1, 1, 0]),
+    ]);
+    assert_eq!(
+        left.diff(&right).cell_mismatches,
+        vec![
+            mismatch(0, "a", "-1.50", "1.50"),
+            mismatch(0, "b", "1200", "0"),
+            mismatch(0, "c", "-1", "1"),
+            mismatch(1, "a", "0.05", "-0.05"),
+            mismatch(1, "b", "-300", "0"),
+            mismatch(1, "c", "0", "1"),
+            mismatch(2, "a", "0.00", "0.01"),
+            mismatch(2, "b", "0", "100"),
+            mismatch(2, "c", "1", "0"),
+        ]
+    );
+}
+
+#[test]
+fn we_report_decimals_with_another_precision_as_retyped() {
+    let left = owned_table::<TestScalar>([decimal75("a", 10, 2, [1])]);
+    let right = owned_table::<TestScalar>([decimal75("a", 11, 2, [1])]);
+    assert_eq!(
+        left.diff(&right).retyped_columns,
+        vec![(
+            Ident::new("a"),
+            ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+            ColumnType::Decimal75(Precision::new(11).unwrap(), 2)
+        )]
+    );
+}
+
+#[test]
+fn we_render_timestamps_in_rfc_3339_with_their_timezone() {
+    let left = owned_table::<TestScalar>([
+        timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [0]),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::new(3600),
+            [1_500],
+        ),
+        timestamptz("c", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [i64::MAX]),
+    ]);
+    let right = owned_table::<TestScalar>([
+        timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [86_400]),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::new(3600),
+            [-1_000],
+        ),
+        timestamptz("c", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1]),
+    ]);
+    assert_eq!(
+        left.diff(&right).cell_mismatches,
+        vec![
+            mismatch(
+                0,
+                "a",
+                "1970-01-01T00:00:00+00:00",
+                "1970-01-02T00:00:00+00:00"
+            ),
+            mismatch(
+                0,
+                "b",
+                "1970-01-01T01:00:01.500+01:00",
+                "1970-01-01T00:59:59+01:00"
+            ),
+            mismatch(0, "c", &i64::MAX.to_string(), "1970-01-01T00:00:01+00:00"),
+        ]
+    );
+}
+
+#[test]
+fn we_render_binaries_in_hex_and_scalars_as_integers() {
+    let left = owned_table::<TestScalar>([varbinary("a", [vec![0x00, 0xab]]), scalar("b", [-3])]);
+    let right = owned_table::<TestScalar>([varbinary("a", [vec![0xff]]), scalar("b", [3])]);
+    assert_eq!(
+        left.diff(&right).cell_mismatches,
+        vec![
+            mismatch(0, "a", "0x00ab", "0xff"),
+            mismatch(0, "b", "-3", "3")
+        ]
+    );
+}
+
+#[test]
+fn we_can_diff_tables_with_the_same_rows_in_another_order() {
+    let left = owned_table::<TestScalar>([
+        bigint("a", [1, 2, 2, 3]),
+        varchar("b", ["x", "y", "y", "z"]),
+    ]);
+    let right = owned_table::<TestScalar>([
+        bigint("a", [3, 2, 1, 2]),
+        varchar("b", ["z", "y", "x", "y"]),
+    ]);
+    assert_eq!(left.diff(&right).cell_mismatch_count, 6);
+    let diff = diff_unordered(&left, &right);
+    assert!(diff.is_empty());
+    assert_eq!(diff.cell_mismatch_count, 0);
+}
+
+#[test]
+fn we_can_diff_tables_with_different_multisets_of_rows() {
+    let left = owned_table::<TestScalar>([
+        bigint("a", [1, 2, 2, 4]),
+        varchar("b", ["x", "y", "y", "w"]),
+    ]);
+    let right = owned_table::<TestScalar>([bigint("a", [2, 1, 3]), varchar("b", ["y", "x", "z"])]);
+    let diff = diff_unordered(&left, &right);
+    assert_eq!(
+        diff,
+        TableDiff {
+            row_counts: Some((4, 3)),
+            left_only_rows: vec![
+                unmatched_row(2, &["2", "\"y\""]),
+                unmatched_row(3, &["4", "\"w\""])
+            ],
+            left_only_row_count: 2,
+            right_only_rows: vec![unmatched_row(2, &["3", "\"z\""])],
+            right_only_row_count: 1,
+            ..TableDiff::default()
+        }
+    );
+    assert_eq!(
+        diff.to_string(),
+        "the left table has 4 rows but the right table has 3\n\
+         row 2 is only in the left table: (2, \"y\")\n\
+         row 3 is only in the left table: (4, \"w\")\n\
+         row 2 is only in the right table: (3, \"z\")\n"
+    );
+}
+
+#[test]
+fn we_only_list_the_first_unmatched_rows() {
+    let left = owned_table::<TestScalar>([bigint("a", 0..5)]);
+    let right = owned_table::<TestScalar>([bigint("a", 10..15)]);
+    let diff = left.diff_with_options(
+        &right,
+        &TableDiffOptions::default()
+            .unordered()
+            .with_max_mismatches(1),
+    );
+    assert_eq!(diff.left_only_rows, vec![unmatched_row(0, &["0"])]);
+    assert_eq!(diff.left_only_row_count, 5);
+    assert_eq!(diff.right_only_rows, vec![unmatched_row(0, &["10"])]);
+    assert_eq!(diff.right_only_row_count, 5);
+    assert!(diff.to_string().ends_with(
+        "row 0 is only in the left table: (0)\n\
+         ... and 4 more rows only in the left table\n\
+         row 0 is only in the right table: (10)\n\
+         ... and 4 more rows only in the right table\n"
+    ));
+}
+
+#[test]
+fn we_can_assert_that_tables_are_equal() {
+    let left = owned_table::<TestScalar>([bigint("a", [1, 2])]);
+    assert_tables_equal!(left, owned_table::<TestScalar>([bigint("a", [1, 2])]));
+    assert_tables_equal!(
+        left,
+        owned_table::<TestScalar>([bigint("a", [2, 1])]),
+        TableDiffOptions::default().unordered()
+    );
+}
+
+#[test]
+#[should_panic(expected = "the tables are not equal:\nrow 1, column a: 2 != 3\n")]
+fn we_cannot_assert_that_different_tables_are_equal() {
+    assert_tables_equal!(
+        owned_table::<TestScalar>([bigint("a", [1, 2])]),
+        owned_table::<TestScalar>([bigint("a", [1, 3])])
+    );
+}
+
+#[test]
+#[should_panic(expected = "row 1 is only in the left table: (2)")]
+fn we_cannot_assert_that_tables_with_different_rows_are_equal_in_any_order() {
+    assert_tables_equal!(
+        owned_table::<TestScalar>([bigint("a", [1, 2])]),
+        owned_table::<TestScalar>([bigint("a", [3, 1])]),
+        TableDiffOptions::default().unordered()
+    );
+}
@@ -167,3 +167,35 @@ fn we_cannot_create_an_owned_table_with_differing_column_lengths() {
         Err(OwnedTableError::ColumnLengthMismatch)
     ));
 }
+#[test]
+fn we_can_compute_the_encoded_size_of_an_owned_table() {
+    let owned_table = owned_table::<TestScalar>([
+        uint8("uint8", [0_u8, 1, u8::MAX]),
+        tinyint("tinyint", [0_i8, 1, i8::MIN]),
+        smallint("smallint", [0_i16, 1, i16::MIN]),
+        int("int", [0, 1, i32::MIN]),
+        bigint("bigint", [0, 1, i64::MIN]),
+        int128("int128", [0, 1, i128::MIN]),
+        boolean("boolean", [true, false, true]),
+        varchar("varchar", ["", "a", "ünïcödé"]),
+        varbinary("varbinary", [vec![], vec![1_u8], vec![1_u8, 2, 3]]),
+        scalar("scalar", [0, 1, -1]),
+        decimal75("decimal", 12, 2, [0, 1, -1]),
+        timestamptz(
+            "timestamp",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::new(3600),
+            [0, 1, i64::MIN],
+        ),
+        (
+            Ident::with_quote('"', "quoted ñame"),
+            OwnedColumn::BigInt(vec![1, 2, 3]),
+        ),
+    ]);
+    let encoded = bincode::serde::encode_to_vec(&owned_table, bincode::config::legacy()).unwrap();
+    assert_eq!(owned_table.encoded_size_hint(), encoded.len());
+
+    let empty_table = OwnedTable::<TestScalar>::try_new(IndexMap::default()).unwrap();
+    let encoded = bincode::serde::encode_to_vec(&empty_table, bincode::config::legacy()).unwrap();
+    assert_eq!(empty_table.encoded_size_hint(), encoded.len());
+}
@@ -1,19 +1,22 @@
 use super::{
-    Column, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn,
-    OwnedTable, SchemaAccessor, TableRef, TestAccessor,
+    Column, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedTable,
+    SchemaAccessor, TableRef, TestAccessor,
 };
 use crate::base::{
-    commitment::{CommitmentEvaluationProof, VecCommitmentExt},
+    commitment::{
+        check_column_type_supported_by, CommitmentEvaluationProof, UnsupportedColumnType,
+        VecCommitmentExt,
+    },
     map::IndexMap,
-    scalar::ScalarExt,
 };
-use alloc::{string::String, vec::Vec};
+use alloc::vec::Vec;
 use bumpalo::Bump;
 use sqlparser::ast::Ident;
 /// A test accessor that uses [`OwnedTable`] as the underlying table type.
 /// Note: this is intended for testing and examples. It is not optimized for performance, so should not be used for benchmarks or production use-cases.
 pub struct OwnedTableTestAccessor<'a, CP: CommitmentEvaluationProof> {
     tables: IndexMap<TableRef, (OwnedTable<CP::Scalar>, usize)>,
+    column_offsets: IndexMap<TableRef, IndexMap<Ident, usize>>,
     alloc: Bump,
     setup: Option<CP::ProverPublicSetup<'a>>,
 }
@@ -22,6 +25,7 @@ impl<CP: CommitmentEvaluationProof> Default for OwnedTableTestAccessor<'_, CP> {
     fn default() -> Self {
         Self {
             tables: IndexMap::default(),
+            column_offsets: IndexMap::default(),
             alloc: Bump::new(),
             setup: None,
         }
@@ -32,6 +36,7 @@ impl<CP: CommitmentEvaluationProof> Clone for OwnedTableTestAccessor<'_, CP> {
     fn clone(&self) -> Self {
         Self {
             tables: self.tables.clone(),
+            column_offsets: self.column_offsets.clone(),
             setup: self.setup,
             ..Default::default()
         }
@@ -48,6 +53,7 @@ impl<CP: CommitmentEvaluationProof> TestAccessor<CP::Commitment>
     }
 
     fn add_table(&mut self, table_ref: TableRef, data: Self::Table, table_offset: usize) {
+        self.column_offsets.swap_remove(&table_ref);
         self.tables.insert(table_ref, (data, table_offset));
     }
     ///
@@ -82,52 +88,12 @@ impl<CP: CommitmentEvaluationProof> TestAccessor<CP::Commitment>
 /// indicating that an invalid column reference was provided.
 impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestAccessor<'_, CP> {
     fn get_column(&self, table_ref: &TableRef, column_id: &Ident) -> Column<CP::Scalar> {
-        match self
-            .tables
+        self.tables
             .get(table_ref)
             .unwrap()
             .0
-            .inner_table()
-            .get(column_id)
+            .column_view(column_id, &self.alloc)
             .unwrap()
-        {
-            OwnedColumn::Boolean(col) => Column::Boolean(col),
-            OwnedColumn::TinyInt(col) => Column::TinyInt(col),
-            OwnedColumn::Uint8(col) => Column::Uint8(col),
-            OwnedColumn::SmallInt(col) => Column::SmallInt(col),
-            OwnedColumn::Int(col) => Column::Int(col),
-            OwnedColumn::BigInt(col) => Column::BigInt(col),
-            OwnedColumn::Int128(col) => Column::Int128(col),
-            OwnedColumn::Decimal75(precision, scale, col) => {
-                Column::Decimal75(*precision, *scale, col)
-            }
-            OwnedColumn::Scalar(col) => Column::Scalar(col),
-            OwnedColumn::VarChar(col) => {
-                let col: &mut [&str] = self
-                    .alloc
-                    .alloc_slice_fill_iter(col.iter().map(String::as_str));
-                let scals: &mut [_] = self
-                    .alloc
-                    .alloc_slice_fill_iter(col.iter().map(|s| (*s).into()));
-                Column::VarChar((col, scals))
-            }
-            OwnedColumn::VarBinary(col) => {
-                // Convert each `Vec<u8>` to `&[u8]` for the `Column::VarBinary` "string-like" part.
-                let col_as_slices: &mut [&[u8]] = self
-                    .alloc
-                    .alloc_slice_fill_iter(col.iter().map(Vec::as_slice));
-
-                // Convert each `Vec<u8>` to a scalar by calling `from_le_bytes_mod_order`.
-                // That is the crucial step, because there's no direct `From<&[u8]>`.
-                let scals: &mut [CP::Scalar] = self.alloc.alloc_slice_fill_iter(
-                    col.iter()
-                        .map(|b| CP::Scalar::from_byte_slice_via_hash(b.as_slice())),
-                );
-
-                Column::VarBinary((col_as_slices, scals))
-            }
-            OwnedColumn::TimestampTZ(tu, tz, col) => Column::TimestampTZ(*tu, *tz, col),
-        }
     }
 }
 
@@ -139,11 +105,11 @@ impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
     for OwnedTableTestAccessor<'_, CP>
 {
     fn get_commitment(&self, table_ref: &TableRef, column_id: &Ident) -> CP::Commitment {
-        let (table, offset) = self.tables.get(table_ref).unwrap();
+        let (table, _) = self.tables.get(table_ref).unwrap();
         let owned_column = table.inner_table().get(column_id).unwrap();
         Vec::<CP::Commitment>::from_columns_with_offset(
             [owned_column],
-            *offset,
+            self.get_column_offset(table_ref, column_id),
             self.setup.as_ref().unwrap(),
         )[0]
         .clone()
@@ -164,6 +130,17 @@ impl<CP: CommitmentEvaluationProof> MetadataAccessor for OwnedTableTestAccessor<
     fn get_offset(&self, table_ref: &TableRef) -> usize {
         self.tables.get(&table_ref).unwrap().1
     }
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the `table_ref` is not found in `self.tables`, indicating that an invalid reference was provided.
+    fn get_column_offset(&self, table_ref: &TableRef, column_id: &Ident) -> usize {
+        self.column_offsets
+            .get(table_ref)
+            .and_then(|column_offsets| column_offsets.get(column_id))
+            .copied()
+            .unwrap_or_else(|| self.get_offset(table_ref))
+    }
 }
 impl<CP: CommitmentEvaluationProof> SchemaAccessor for OwnedTableTestAccessor<'_, CP> {
     fn lookup_column(&self, table_ref: &TableRef, column_id: &Ident) -> Option<ColumnType> {
@@ -211,4 +188,34 @@ impl<'a, CP: CommitmentEvaluationProof> OwnedTableTestAccessor<'a, CP> {
         res.add_table(table_ref, owned_table, offset);
         res
     }
+
+    /// Add a new table to the accessor, unless the commitment scheme cannot commit to one of its
+    /// columns.
+    pub fn try_add_table(
+        &mut self,
+        table_ref: TableRef,
+        owned_table: OwnedTable<CP::Scalar>,
+        offset: usize,
+    ) -> Result<(), UnsupportedColumnType> {
+        owned_table.inner_table().values().try_for_each(|column| {
+            check_column_type_supported_by::<CP::Commitment>(column.column_type())
+        })?;
+        self.add_table(table_ref, owned_table, offset);
+        Ok(())
+    }
+
+    /// Update the offset of the commitment to a single column, which otherwise has the table offset.
+    ///
+    /// The override is kept if the table offset is updated and dropped if the table is replaced.
+    pub fn update_column_offset(
+        &mut self,
+        table_ref: &TableRef,
+        column_id: &Ident,
+        new_offset: usize,
+    ) {
+        self.column_offsets
+            .entry(table_ref.clone())
+            .or_default()
+            .insert(column_id.clone(), new_offset);
+    }
 }
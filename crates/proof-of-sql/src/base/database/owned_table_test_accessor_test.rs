@@ -241,3 +241,59 @@ fn we_can_correctly_update_offsets() {
     assert_eq!(accessor1.get_offset(&table_ref), offset);
     assert_eq!(accessor2.get_offset(&table_ref), offset);
 }
+
+#[test]
+fn we_can_commit_to_columns_of_a_table_with_different_offsets() {
+    let mut accessor = OwnedTableTestAccessor::<NaiveEvaluationProof>::new_empty_with_setup(());
+    let table_ref = TableRef::new("sxt", "test");
+    let data = owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]);
+    accessor.add_table(table_ref.clone(), data, 2_usize);
+    accessor.update_column_offset(&table_ref, &"b".into(), 5);
+
+    assert_eq!(accessor.get_offset(&table_ref), 2);
+    assert_eq!(accessor.get_column_offset(&table_ref, &"a".into()), 2);
+    assert_eq!(accessor.get_column_offset(&table_ref, &"b".into()), 5);
+    assert_eq!(
+        accessor.get_commitment(&table_ref, &"a".into()),
+        NaiveCommitment::compute_commitments(&[CommittableColumn::BigInt(&[1, 2, 3])], 2, &())[0]
+    );
+    assert_eq!(
+        accessor.get_commitment(&table_ref, &"b".into()),
+        NaiveCommitment::compute_commitments(&[CommittableColumn::BigInt(&[4, 5, 6])], 5, &())[0]
+    );
+
+    // Column offsets are kept when the table offset is updated
+    accessor.update_offset(&table_ref, 3);
+    assert_eq!(accessor.get_column_offset(&table_ref, &"a".into()), 3);
+    assert_eq!(accessor.get_column_offset(&table_ref, &"b".into()), 5);
+
+    // but not when the table is replaced
+    let data = owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]);
+    accessor.add_table(table_ref.clone(), data, 0_usize);
+    assert_eq!(accessor.get_column_offset(&table_ref, &"b".into()), 0);
+}
+
+#[test]
+fn we_cannot_add_tables_with_columns_the_commitment_scheme_does_not_support() {
+    use crate::base::commitment::{InnerProductProof, UnsupportedColumnType};
+
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_ref = TableRef::new("sxt", "test");
+    let data = owned_table([bigint("a", [1, 2, 3]), int128("b", [4, 5, 6])]);
+    assert_eq!(
+        accessor.try_add_table(table_ref.clone(), data, 0),
+        Err(UnsupportedColumnType {
+            column_type: ColumnType::Int128,
+            backend: "inner_product",
+        })
+    );
+    assert!(accessor
+        .try_add_table(table_ref.clone(), owned_table([bigint("a", [1, 2, 3])]), 0)
+        .is_ok());
+    assert_eq!(accessor.get_length(&table_ref), 3);
+
+    let mut accessor = OwnedTableTestAccessor::<NaiveEvaluationProof>::new_empty_with_setup(());
+    let data = owned_table([bigint("a", [1, 2, 3]), int128("b", [4, 5, 6])]);
+    assert!(accessor.try_add_table(table_ref.clone(), data, 0).is_ok());
+    assert_eq!(accessor.get_length(&table_ref), 3);
+}
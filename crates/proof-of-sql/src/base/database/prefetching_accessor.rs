@@ -0,0 +1,321 @@
+use super::{Column, ColumnRef, ColumnType, DataAccessor, MetadataAccessor, OwnedColumn, TableRef};
+use crate::{
+    base::{
+        map::{IndexMap, IndexSet},
+        scalar::Scalar,
+    },
+    sql::proof::ProofPlan,
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use bumpalo::Bump;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+use std::{
+    thread::{self, Thread},
+    time::Instant,
+};
+
+/// A source of column data that may be expensive to reach, e.g. a remote storage service.
+///
+/// Metadata (lengths and offsets) is expected to be cheap to access and is read synchronously.
+/// Column data is fetched asynchronously so that all columns needed by a query can be
+/// requested concurrently by a [`PrefetchingAccessor`] before proving starts.
+pub trait ColumnFetcher<S: Scalar>: MetadataAccessor {
+    /// The error returned when a fetch fails
+    type Error: core::fmt::Display;
+
+    /// Fetch the data span of a column (not the full-table data)
+    fn fetch_column(
+        &self,
+        table_ref: &TableRef,
+        column_id: &Ident,
+    ) -> impl Future<Output = Result<OwnedColumn<S>, Self::Error>>;
+}
+
+/// Options controlling how a [`PrefetchingAccessor`] fetches columns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefetchOptions {
+    timeout: Option<Duration>,
+}
+
+impl PrefetchOptions {
+    /// Creates options with an optional timeout for fetching all columns
+    #[must_use]
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self { timeout }
+    }
+
+    /// Returns the timeout for fetching all columns, if any
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+/// Errors that can occur while prefetching columns.
+///
+/// These are raised before proving starts, so no proving work is wasted on a query whose
+/// data cannot be retrieved.
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum PrefetchError {
+    /// The fetcher failed to retrieve a column.
+    #[snafu(display("failed to fetch column {column_id} of table {table_ref}: {error}"))]
+    FetchFailed {
+        /// The table of the column that could not be fetched
+        table_ref: TableRef,
+        /// The column that could not be fetched
+        column_id: Ident,
+        /// The error reported by the fetcher
+        error: String,
+    },
+    /// Not all columns were fetched before the timeout elapsed.
+    #[snafu(display("timed out after {timeout:?} with {pending} column fetches pending"))]
+    TimedOut {
+        /// The timeout that elapsed
+        timeout: Duration,
+        /// The number of fetches that had not completed
+        pending: usize,
+    },
+    /// A fetched column does not have the length of its table.
+    #[snafu(display(
+        "column {column_id} of table {table_ref} has length {actual} but the table has length {expected}"
+    ))]
+    ColumnLengthMismatch {
+        /// The table of the column
+        table_ref: TableRef,
+        /// The column with the wrong length
+        column_id: Ident,
+        /// The length of the table
+        expected: usize,
+        /// The length of the fetched column
+        actual: usize,
+    },
+    /// A fetched column does not have the type the query expects.
+    #[snafu(display(
+        "column {column_id} of table {table_ref} has type {actual} but type {expected} was expected"
+    ))]
+    ColumnTypeMismatch {
+        /// The table of the column
+        table_ref: TableRef,
+        /// The column with the wrong type
+        column_id: Ident,
+        /// The type expected by the query
+        expected: ColumnType,
+        /// The type of the fetched column
+        actual: ColumnType,
+    },
+}
+
+/// A [`DataAccessor`] that fetches every column a query needs up front and then serves
+/// the synchronous accessor calls made during proving from memory.
+///
+/// Fetched columns are materialized in a bump arena so that they can be borrowed for the
+/// lifetime of the proof.
+pub struct PrefetchingAccessor<'a, S: Scalar, F: ColumnFetcher<S>> {
+    fetcher: F,
+    columns: IndexMap<(TableRef, Ident), Column<'a, S>>,
+}
+
+impl<'a, S: Scalar, F: ColumnFetcher<S>> PrefetchingAccessor<'a, S, F> {
+    /// Fetches all columns referenced by `plan` concurrently.
+    pub fn try_new_for_plan(
+        plan: &(impl ProofPlan + ?Sized),
+        fetcher: F,
+        alloc: &'a Bump,
+        options: &PrefetchOptions,
+    ) -> Result<Self, PrefetchError> {
+        Self::try_new(&plan.get_column_references(), fetcher, alloc, options)
+    }
+
+    /// Fetches all columns in `column_refs` concurrently.
+    ///
+    /// Each fetched column is checked against the type in its [`ColumnRef`] and the table
+    /// length reported by the fetcher.
+    pub fn try_new(
+        column_refs: &IndexSet<ColumnRef>,
+        fetcher: F,
+        alloc: &'a Bump,
+        options: &PrefetchOptions,
+    ) -> Result<Self, PrefetchError> {
+        let fetched = fetch_all(&fetcher, column_refs, options)?;
+        let columns = column_refs
+            .iter()
+            .zip(fetched)
+            .map(|(column_ref, owned_column)| {
+                let table_ref = column_ref.table_ref();
+                let column_id = column_ref.column_id();
+                let expected_type = *column_ref.column_type();
+                if owned_column.column_type() != expected_type {
+                    return Err(PrefetchError::ColumnTypeMismatch {
+                        table_ref,
+                        column_id,
+                        expected: expected_type,
+                        actual: owned_column.column_type(),
+                    });
+                }
+                let expected_length = fetcher.get_length(&table_ref);
+                if owned_column.len() != expected_length {
+                    return Err(PrefetchError::ColumnLengthMismatch {
+                        table_ref,
+                        column_id,
+                        expected: expected_length,
+                        actual: owned_column.len(),
+                    });
+                }
+                let column = materialize_in_arena(owned_column, alloc);
+                Ok(((table_ref, column_id), column))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { fetcher, columns })
+    }
+
+    /// Returns the fetcher the columns were prefetched from
+    #[must_use]
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// Returns the number of prefetched columns
+    #[must_use]
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+}
+
+impl<S: Scalar, F: ColumnFetcher<S>> MetadataAccessor for PrefetchingAccessor<'_, S, F> {
+    fn get_length(&self, table_ref: &TableRef) -> usize {
+        self.fetcher.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: &TableRef) -> usize {
+        self.fetcher.get_offset(table_ref)
+    }
+
+    fn get_column_offset(&self, table_ref: &TableRef, column_id: &Ident) -> usize {
+        self.fetcher.get_column_offset(table_ref, column_id)
+    }
+}
+
+///
+/// # Panics
+///
+/// Will panic if the column was not prefetched, i.e. it was not referenced by the plan
+/// or column references the accessor was created from.
+impl<'a, S: Scalar, F: ColumnFetcher<S>> DataAccessor<S> for PrefetchingAccessor<'a, S, F> {
+    fn get_column(&self, table_ref: &TableRef, column_id: &Ident) -> Column<'a, S> {
+        *self
+            .columns
+            .get(&(table_ref.clone(), column_id.clone()))
+            .expect("Column should have been prefetched")
+    }
+}
+
+/// Wakes the thread driving the fetches.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+type FetchFuture<'f, S, E> = Pin<Box<dyn Future<Output = Result<OwnedColumn<S>, E>> + 'f>>;
+
+/// Drives the fetches of all `column_refs` to completion on the current thread.
+///
+/// Fetches are polled together, so a fetcher whose futures are backed by non-blocking I/O
+/// has all requests in flight at once. The first failure cancels the remaining fetches.
+fn fetch_all<S: Scalar, F: ColumnFetcher<S>>(
+    fetcher: &F,
+    column_refs: &IndexSet<ColumnRef>,
+    options: &PrefetchOptions,
+) -> Result<Vec<OwnedColumn<S>>, PrefetchError> {
+    let deadline = options
+        .timeout
+        .map(|timeout| (timeout, Instant::now() + timeout));
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let keys: Vec<(TableRef, Ident)> = column_refs
+        .iter()
+        .map(|column_ref| (column_ref.table_ref(), column_ref.column_id()))
+        .collect();
+    let mut futures: Vec<Option<FetchFuture<'_, S, F::Error>>> = keys
+        .iter()
+        .map(|(table_ref, column_id)| {
+            Some(Box::pin(fetcher.fetch_column(table_ref, column_id))
+                as FetchFuture<'_, S, F::Error>)
+        })
+        .collect();
+    let mut results: Vec<Option<OwnedColumn<S>>> = keys.iter().map(|_| None).collect();
+    let mut pending = futures.len();
+    while pending > 0 {
+        for ((future_slot, result_slot), (table_ref, column_id)) in
+            futures.iter_mut().zip(&mut results).zip(&keys)
+        {
+            let Some(future) = future_slot else {
+                continue;
+            };
+            if let Poll::Ready(result) = future.as_mut().poll(&mut context) {
+                *future_slot = None;
+                pending -= 1;
+                *result_slot = Some(result.map_err(|error| PrefetchError::FetchFailed {
+                    table_ref: table_ref.clone(),
+                    column_id: column_id.clone(),
+                    error: error.to_string(),
+                })?);
+            }
+        }
+        if pending == 0 {
+            break;
+        }
+        if let Some((timeout, deadline)) = deadline {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(PrefetchError::TimedOut { timeout, pending });
+            }
+            thread::park_timeout(deadline - now);
+        } else {
+            thread::park();
+        }
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Copies an owned column into `alloc` so that it can be borrowed for the arena's lifetime.
+fn materialize_in_arena<S: Scalar>(owned_column: OwnedColumn<S>, alloc: &Bump) -> Column<'_, S> {
+    match owned_column {
+        OwnedColumn::Boolean(col) => Column::Boolean(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::Uint8(col) => Column::Uint8(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::TinyInt(col) => Column::TinyInt(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::SmallInt(col) => Column::SmallInt(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::Int(col) => Column::Int(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::BigInt(col) => Column::BigInt(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::Int128(col) => Column::Int128(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::Decimal75(precision, scale, col) => {
+            Column::Decimal75(precision, scale, alloc.alloc_slice_copy(&col))
+        }
+        OwnedColumn::Scalar(col) => Column::Scalar(alloc.alloc_slice_copy(&col)),
+        OwnedColumn::VarChar(col) => Column::VarChar((
+            alloc.alloc_slice_fill_iter(col.iter().map(|s| &*alloc.alloc_str(s))),
+            alloc.alloc_slice_fill_iter(col.iter().map(S::from)),
+        )),
+        OwnedColumn::VarBinary(col) => Column::VarBinary((
+            alloc.alloc_slice_fill_iter(col.iter().map(|b| &*alloc.alloc_slice_copy(b))),
+            alloc.alloc_slice_fill_iter(col.iter().map(|b| S::from_byte_slice_via_hash(b))),
+        )),
+        OwnedColumn::TimestampTZ(tu, tz, col) => {
+            Column::TimestampTZ(tu, tz, alloc.alloc_slice_copy(&col))
+        }
+    }
+}
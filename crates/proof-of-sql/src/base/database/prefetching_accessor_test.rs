@@ -0,0 +1,364 @@
+use super::{
+    Column, ColumnFetcher, ColumnRef, ColumnType, DataAccessor, MetadataAccessor, OwnedColumn,
+    PrefetchError, PrefetchOptions, PrefetchingAccessor, TableRef, TableTestAccessor, TestAccessor,
+};
+use crate::{
+    base::{
+        commitment::naive_evaluation_proof::NaiveEvaluationProof,
+        database::{owned_table_utility::*, table_utility::*},
+        map::{IndexMap, IndexSet},
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        proof::VerifiableQueryResult, proof_exprs::test_utility::*, proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use core::{
+    cell::Cell,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use sqlparser::ast::Ident;
+
+/// A fetcher backed by a [`TableTestAccessor`] that counts RPCs and tracks how many
+/// fetches are in flight at once.
+struct MockFetcher<'a, 'b> {
+    accessor: &'b TableTestAccessor<'a, NaiveEvaluationProof>,
+    failing_column: Option<Ident>,
+    stalled: bool,
+    rpcs: Cell<usize>,
+    in_flight: Cell<usize>,
+    max_in_flight: Cell<usize>,
+}
+
+impl<'a, 'b> MockFetcher<'a, 'b> {
+    fn new(accessor: &'b TableTestAccessor<'a, NaiveEvaluationProof>) -> Self {
+        Self {
+            accessor,
+            failing_column: None,
+            stalled: false,
+            rpcs: Cell::new(0),
+            in_flight: Cell::new(0),
+            max_in_flight: Cell::new(0),
+        }
+    }
+}
+
+/// A fetch that yields once before completing, or never completes if stalled.
+struct MockFetch<'f, 'a, 'b> {
+    fetcher: &'f MockFetcher<'a, 'b>,
+    result: Option<Result<OwnedColumn<TestScalar>, &'static str>>,
+    started: bool,
+    stalled: bool,
+}
+
+impl Future for MockFetch<'_, '_, '_> {
+    type Output = Result<OwnedColumn<TestScalar>, &'static str>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.stalled {
+            return Poll::Pending;
+        }
+        let fetcher = self.fetcher;
+        if !self.started {
+            self.started = true;
+            fetcher.in_flight.set(fetcher.in_flight.get() + 1);
+            fetcher
+                .max_in_flight
+                .set(fetcher.max_in_flight.get().max(fetcher.in_flight.get()));
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        fetcher.in_flight.set(fetcher.in_flight.get() - 1);
+        Poll::Ready(self.result.take().unwrap())
+    }
+}
+
+impl MetadataAccessor for MockFetcher<'_, '_> {
+    fn get_length(&self, table_ref: &TableRef) -> usize {
+        self.accessor.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: &TableRef) -> usize {
+        self.accessor.get_offset(table_ref)
+    }
+}
+
+impl ColumnFetcher<TestScalar> for MockFetcher<'_, '_> {
+    type Error = &'static str;
+
+    fn fetch_column(
+        &self,
+        table_ref: &TableRef,
+        column_id: &Ident,
+    ) -> impl Future<Output = Result<OwnedColumn<TestScalar>, Self::Error>> {
+        self.rpcs.set(self.rpcs.get() + 1);
+        let result = if self.failing_column.as_ref() == Some(column_id) {
+            Err("connection reset")
+        } else {
+            Ok(OwnedColumn::from(
+                &self.accessor.get_column(table_ref, column_id),
+            ))
+        };
+        MockFetch {
+            fetcher: self,
+            result: Some(result),
+            started: false,
+            stalled: self.stalled,
+        }
+    }
+}
+
+fn sample_accessor(alloc: &Bump) -> (TableTestAccessor<'_, NaiveEvaluationProof>, TableRef) {
+    let mut accessor = TableTestAccessor::<NaiveEvaluationProof>::new_empty_with_setup(());
+    let table_ref = TableRef::new("sxt", "t");
+    accessor.add_table(
+        table_ref.clone(),
+        table([
+            borrowed_bigint("a", [1, 5, 3, 5], alloc),
+            borrowed_varchar("b", ["x", "y", "z", "w"], alloc),
+            borrowed_int("c", [7, 8, 9, 10], alloc),
+        ]),
+        0,
+    );
+    (accessor, table_ref)
+}
+
+fn column_refs(table_ref: &TableRef, columns: &[(&str, ColumnType)]) -> IndexSet<ColumnRef> {
+    columns
+        .iter()
+        .map(|(name, column_type)| ColumnRef::new(table_ref.clone(), (*name).into(), *column_type))
+        .collect()
+}
+
+#[test]
+fn we_can_prefetch_columns_concurrently_with_one_rpc_per_column() {
+    let alloc = Bump::new();
+    let (accessor, table_ref) = sample_accessor(&alloc);
+    let fetcher = MockFetcher::new(&accessor);
+    let refs = column_refs(
+        &table_ref,
+        &[("a", ColumnType::BigInt), ("b", ColumnType::VarChar)],
+    );
+    let prefetching_accessor =
+        PrefetchingAccessor::try_new(&refs, fetcher, &alloc, &PrefetchOptions::default()).unwrap();
+    assert_eq!(prefetching_accessor.num_columns(), 2);
+    assert_eq!(prefetching_accessor.fetcher().rpcs.get(), 2);
+    assert_eq!(prefetching_accessor.fetcher().max_in_flight.get(), 2);
+    assert_eq!(prefetching_accessor.get_length(&table_ref), 4);
+    assert_eq!(prefetching_accessor.get_offset(&table_ref), 0);
+    assert_eq!(
+        prefetching_accessor.get_column(&table_ref, &"a".into()),
+        accessor.get_column(&table_ref, &"a".into())
+    );
+    assert_eq!(
+        prefetching_accessor.get_column(&table_ref, &"b".into()),
+        accessor.get_column(&table_ref, &"b".into())
+    );
+
+    // Serving columns does not make any more RPCs
+    assert_eq!(prefetching_accessor.fetcher().rpcs.get(), 2);
+}
+
+#[test]
+fn we_can_prefetch_no_columns() {
+    let alloc = Bump::new();
+    let (accessor, table_ref) = sample_accessor(&alloc);
+    let fetcher = MockFetcher::new(&accessor);
+    let prefetching_accessor = PrefetchingAccessor::try_new(
+        &IndexSet::default(),
+        fetcher,
+        &alloc,
+        &PrefetchOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(prefetching_accessor.num_columns(), 0);
+    assert_eq!(prefetching_accessor.fetcher().rpcs.get(), 0);
+    assert_eq!(prefetching_accessor.get_length(&table_ref), 4);
+}
+
+#[test]
+fn we_can_prove_a_query_using_only_prefetched_columns() {
+    let alloc = Bump::new();
+    let (accessor, table_ref) = sample_accessor(&alloc);
+    let plan = filter(
+        cols_expr_plan(&table_ref, &["a", "b"], &accessor),
+        tab(&table_ref),
+        equal(column(&table_ref, "a", &accessor), const_bigint(5)),
+    );
+    let fetcher = MockFetcher::new(&accessor);
+    let prefetching_accessor = PrefetchingAccessor::try_new_for_plan(
+        &plan,
+        fetcher,
+        &alloc,
+        &PrefetchOptions::new(Some(Duration::from_secs(10))),
+    )
+    .unwrap();
+    assert_eq!(prefetching_accessor.fetcher().rpcs.get(), 2);
+
+    let verifiable_res =
+        VerifiableQueryResult::<NaiveEvaluationProof>::new(&plan, &prefetching_accessor, &(), &[])
+            .unwrap();
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [5_i64, 5]), varchar("b", ["y", "w"])]);
+    assert_eq!(res, expected_res);
+    assert_eq!(prefetching_accessor.fetcher().rpcs.get(), 2);
+}
+
+#[test]
+fn we_cannot_prefetch_if_a_fetch_fails() {
+    let alloc = Bump::new();
+    let (accessor, table_ref) = sample_accessor(&alloc);
+    let mut fetcher = MockFetcher::new(&accessor);
+    fetcher.failing_column = Some("b".into());
+    let refs = column_refs(
+        &table_ref,
+        &[
+            ("a", ColumnType::BigInt),
+            ("b", ColumnType::VarChar),
+            ("c", ColumnType::Int),
+        ],
+    );
+    let err = PrefetchingAccessor::try_new(&refs, fetcher, &alloc, &PrefetchOptions::default())
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        PrefetchError::FetchFailed {
+            table_ref: table_ref.clone(),
+            column_id: "b".into(),
+            error: "connection reset".into(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "failed to fetch column b of table sxt.t: connection reset"
+    );
+}
+
+#[test]
+fn we_cannot_prefetch_if_fetches_do_not_complete_before_the_timeout() {
+    let alloc = Bump::new();
+    let (accessor, table_ref) = sample_accessor(&alloc);
+    let mut fetcher = MockFetcher::new(&accessor);
+    fetcher.stalled = true;
+    let refs = column_refs(
+        &table_ref,
+        &[("a", ColumnType::BigInt), ("c", ColumnType::Int)],
+    );
+    let timeout = Duration::from_millis(10);
+    let err =
+        PrefetchingAccessor::try_new(&refs, fetcher, &alloc, &PrefetchOptions::new(Some(timeout)))
+            .err()
+            .unwrap();
+    assert_eq!(
+        err,
+        PrefetchError::TimedOut {
+            timeout,
+            pending: 2
+        }
+    );
+}
+
+#[test]
+fn we_cannot_prefetch_a_column_whose_type_or_length_is_wrong() {
+    let alloc = Bump::new();
+    let (accessor, table_ref) = sample_accessor(&alloc);
+    let refs = column_refs(&table_ref, &[("c", ColumnType::BigInt)]);
+    let err = PrefetchingAccessor::try_new(
+        &refs,
+        MockFetcher::new(&accessor),
+        &alloc,
+        &PrefetchOptions::default(),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        err,
+        PrefetchError::ColumnTypeMismatch {
+            table_ref: table_ref.clone(),
+            column_id: "c".into(),
+            expected: ColumnType::BigInt,
+            actual: ColumnType::Int,
+        }
+    );
+
+    let mut short_accessor = accessor.clone();
+    short_accessor.add_table(
+        table_ref.clone(),
+        table([borrowed_int("c", [7, 8], &alloc)]),
+        0,
+    );
+    let mismatched_lengths = IndexMap::from_iter([(table_ref.clone(), 4)]);
+    let refs = column_refs(&table_ref, &[("c", ColumnType::Int)]);
+    let err = PrefetchingAccessor::try_new(
+        &refs,
+        LengthOverride {
+            fetcher: MockFetcher::new(&short_accessor),
+            lengths: mismatched_lengths,
+        },
+        &alloc,
+        &PrefetchOptions::default(),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        err,
+        PrefetchError::ColumnLengthMismatch {
+            table_ref,
+            column_id: "c".into(),
+            expected: 4,
+            actual: 2,
+        }
+    );
+}
+
+/// A fetcher that reports table lengths that disagree with the data it fetches.
+struct LengthOverride<'a, 'b> {
+    fetcher: MockFetcher<'a, 'b>,
+    lengths: IndexMap<TableRef, usize>,
+}
+
+impl MetadataAccessor for LengthOverride<'_, '_> {
+    fn get_length(&self, table_ref: &TableRef) -> usize {
+        self.lengths[table_ref]
+    }
+
+    fn get_offset(&self, table_ref: &TableRef) -> usize {
+        self.fetcher.get_offset(table_ref)
+    }
+}
+
+impl ColumnFetcher<TestScalar> for LengthOverride<'_, '_> {
+    type Error = &'static str;
+
+    fn fetch_column(
+        &self,
+        table_ref: &TableRef,
+        column_id: &Ident,
+    ) -> impl Future<Output = Result<OwnedColumn<TestScalar>, Self::Error>> {
+        self.fetcher.fetch_column(table_ref, column_id)
+    }
+}
+
+#[test]
+#[should_panic(expected = "Column should have been prefetched")]
+fn we_cannot_get_a_column_that_was_not_prefetched() {
+    let alloc = Bump::new();
+    let (accessor, table_ref) = sample_accessor(&alloc);
+    let refs = column_refs(&table_ref, &[("a", ColumnType::BigInt)]);
+    let prefetching_accessor = PrefetchingAccessor::try_new(
+        &refs,
+        MockFetcher::new(&accessor),
+        &alloc,
+        &PrefetchOptions::default(),
+    )
+    .unwrap();
+    let _: Column<TestScalar> = prefetching_accessor.get_column(&table_ref, &"c".into());
+}
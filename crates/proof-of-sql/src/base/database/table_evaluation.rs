@@ -2,6 +2,16 @@ use crate::base::scalar::Scalar;
 use alloc::vec::Vec;
 
 /// The result of evaluating a table
+///
+/// This is what [`ProofPlan::verifier_evaluate`](crate::sql::proof::ProofPlan::verifier_evaluate)
+/// returns for the output of a plan. It holds
+/// - exactly one evaluation per output column, in the order of
+///   [`ProofPlan::get_column_result_fields`](crate::sql::proof::ProofPlan::get_column_result_fields),
+/// - the chi evaluation of the length of the output, i.e. the evaluation of a column of ones with
+///   one row per output row.
+///
+/// For the plan at the root of a query, the column evaluations are compared with the evaluations
+/// of the result columns, so a plan has to produce an evaluation for every column it outputs.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct TableEvaluation<S: Scalar> {
     /// Evaluation of each column in the table
@@ -3,7 +3,10 @@ use super::{
     TableRef, TestAccessor,
 };
 use crate::base::{
-    commitment::{CommitmentEvaluationProof, VecCommitmentExt},
+    commitment::{
+        check_column_type_supported_by, CommitmentEvaluationProof, UnsupportedColumnType,
+        VecCommitmentExt,
+    },
     map::IndexMap,
 };
 use alloc::vec::Vec;
@@ -167,4 +170,19 @@ impl<'a, CP: CommitmentEvaluationProof> TableTestAccessor<'a, CP> {
         res.add_table(table_ref, table, offset);
         res
     }
+
+    /// Add a new table to the accessor, unless the commitment scheme cannot commit to one of its
+    /// columns.
+    pub fn try_add_table(
+        &mut self,
+        table_ref: TableRef,
+        table: Table<'a, CP::Scalar>,
+        offset: usize,
+    ) -> Result<(), UnsupportedColumnType> {
+        table.inner_table().values().try_for_each(|column| {
+            check_column_type_supported_by::<CP::Commitment>(column.column_type())
+        })?;
+        self.add_table(table_ref, table, offset);
+        Ok(())
+    }
 }
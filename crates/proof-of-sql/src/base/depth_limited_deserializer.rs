@@ -0,0 +1,325 @@
+//! A [`Deserializer`] wrapper that bounds how deeply nested the deserialized data may be.
+//!
+//! Derived `Deserialize` implementations of recursive types such as `DynProofExpr` recurse once
+//! per level of nesting, so untrusted input can overflow the stack.
+//! [`DepthLimitedDeserializer`] errors out once the nesting depth exceeds a fixed limit instead.
+use core::fmt;
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+
+/// Create the error returned when the maximum nesting depth is exceeded.
+fn depth_exceeded<E: de::Error>() -> E {
+    E::custom("maximum nesting depth exceeded during deserialization")
+}
+
+/// Deserialize a `T` from `deserializer`, erroring if the input is nested more than `max_depth` levels deep.
+pub(crate) fn deserialize_with_depth_limit<'de, T, D>(
+    deserializer: D,
+    max_depth: usize,
+) -> Result<T, D::Error>
+where
+    T: de::Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(DepthLimitedDeserializer {
+        inner: deserializer,
+        remaining_depth: max_depth,
+    })
+}
+
+/// A [`Deserializer`] which errors if the data it deserializes is nested too deeply.
+struct DepthLimitedDeserializer<D> {
+    inner: D,
+    remaining_depth: usize,
+}
+
+macro_rules! forward_deserialize_methods {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+                let remaining_depth = self
+                    .remaining_depth
+                    .checked_sub(1)
+                    .ok_or_else(depth_exceeded::<D::Error>)?;
+                self.inner.$method($($arg,)* DepthLimitedVisitor {
+                    inner: visitor,
+                    remaining_depth,
+                })
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for DepthLimitedDeserializer<D> {
+    type Error = D::Error;
+
+    forward_deserialize_methods!(
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// A [`Visitor`] which passes the remaining depth on to any nested data.
+struct DepthLimitedVisitor<V> {
+    inner: V,
+    remaining_depth: usize,
+}
+
+macro_rules! forward_visit_methods {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for DepthLimitedVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit_methods!(
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(alloc::string::String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(alloc::vec::Vec<u8>),
+    );
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner.visit_some(DepthLimitedDeserializer {
+            inner: deserializer,
+            remaining_depth: self.remaining_depth,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.inner.visit_newtype_struct(DepthLimitedDeserializer {
+            inner: deserializer,
+            remaining_depth: self.remaining_depth,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_seq(DepthLimitedAccess {
+            inner: seq,
+            remaining_depth: self.remaining_depth,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_map(DepthLimitedAccess {
+            inner: map,
+            remaining_depth: self.remaining_depth,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_enum(DepthLimitedAccess {
+            inner: data,
+            remaining_depth: self.remaining_depth,
+        })
+    }
+}
+
+/// A [`DeserializeSeed`] which wraps the [`Deserializer`] it is given in a [`DepthLimitedDeserializer`].
+struct DepthLimitedSeed<T> {
+    inner: T,
+    remaining_depth: usize,
+}
+
+impl<'de, T: DeserializeSeed<'de>> DeserializeSeed<'de> for DepthLimitedSeed<T> {
+    type Value = T::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner.deserialize(DepthLimitedDeserializer {
+            inner: deserializer,
+            remaining_depth: self.remaining_depth,
+        })
+    }
+}
+
+/// Wrapper for the [`SeqAccess`], [`MapAccess`], [`EnumAccess`] and [`VariantAccess`] handed to visitors.
+struct DepthLimitedAccess<A> {
+    inner: A,
+    remaining_depth: usize,
+}
+
+impl<A> DepthLimitedAccess<A> {
+    fn seed<T>(&self, seed: T) -> DepthLimitedSeed<T> {
+        DepthLimitedSeed {
+            inner: seed,
+            remaining_depth: self.remaining_depth,
+        }
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for DepthLimitedAccess<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let seed = self.seed(seed);
+        self.inner.next_element_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        SeqAccess::size_hint(&self.inner)
+    }
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for DepthLimitedAccess<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let seed = self.seed(seed);
+        self.inner.next_key_seed(seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let seed = self.seed(seed);
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        MapAccess::size_hint(&self.inner)
+    }
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for DepthLimitedAccess<A> {
+    type Error = A::Error;
+    type Variant = DepthLimitedAccess<A::Variant>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let remaining_depth = self.remaining_depth;
+        let seed = self.seed(seed);
+        self.inner.variant_seed(seed).map(|(value, variant)| {
+            (
+                value,
+                DepthLimitedAccess {
+                    inner: variant,
+                    remaining_depth,
+                },
+            )
+        })
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for DepthLimitedAccess<A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        let seed = self.seed(seed);
+        self.inner.newtype_variant_seed(seed)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.tuple_variant(
+            len,
+            DepthLimitedVisitor {
+                inner: visitor,
+                remaining_depth: self.remaining_depth,
+            },
+        )
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.struct_variant(
+            fields,
+            DepthLimitedVisitor {
+                inner: visitor,
+                remaining_depth: self.remaining_depth,
+            },
+        )
+    }
+}
@@ -0,0 +1,46 @@
+//! Helpers for computing the number of bytes a value takes up when serialized with bincode's
+//! legacy configuration (little endian, fixed size integers), without serializing it.
+//!
+//! Configurations using variable size integers never produce more bytes than the legacy
+//! configuration, so sizes computed this way are also upper bounds for those.
+
+/// Number of bytes used for a `u64`, a `usize` and the length prefix of a sequence, string or map
+pub(crate) const LENGTH_SIZE: usize = 8;
+
+/// Number of bytes used for the variant index of an enum
+pub(crate) const VARIANT_SIZE: usize = 4;
+
+/// Number of bytes used for a [`MontScalar`](crate::base::scalar::MontScalar), which is
+/// serialized as four `u64` limbs
+pub(crate) const SCALAR_SIZE: usize = 32;
+
+/// Number of bytes used for a sequence of `len` elements of `element_size` bytes each
+pub(crate) const fn seq_size(len: usize, element_size: usize) -> usize {
+    LENGTH_SIZE + len * element_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::scalar::test_scalar::TestScalar;
+    use alloc::{string::String, vec, vec::Vec};
+
+    fn legacy_size(value: &impl serde::Serialize) -> usize {
+        bincode::serde::encode_to_vec(value, bincode::config::legacy())
+            .unwrap()
+            .len()
+    }
+
+    #[test]
+    fn we_can_compute_the_encoded_size_of_primitives() {
+        assert_eq!(legacy_size(&0_usize), LENGTH_SIZE);
+        assert_eq!(legacy_size(&TestScalar::from(-1)), SCALAR_SIZE);
+        assert_eq!(legacy_size(&vec![1_i32, 2, 3]), seq_size(3, 4));
+        assert_eq!(
+            legacy_size(&Vec::<TestScalar>::new()),
+            seq_size(0, SCALAR_SIZE)
+        );
+        assert_eq!(legacy_size(&String::from("abc")), seq_size(3, 1));
+        assert_eq!(legacy_size(&Ok::<u8, u8>(1)), VARIANT_SIZE + 1);
+    }
+}
@@ -1,6 +1,8 @@
-pub(crate) type IndexMap<K, V> =
-    indexmap::IndexMap<K, V, core::hash::BuildHasherDefault<ahash::AHasher>>;
-pub(crate) type IndexSet<T> = indexmap::IndexSet<T, core::hash::BuildHasherDefault<ahash::AHasher>>;
+/// An [`indexmap::IndexMap`] with the hasher used throughout the crate, e.g. for the maps passed to
+/// [`ProofPlan`](crate::sql::proof::ProofPlan)s
+pub type IndexMap<K, V> = indexmap::IndexMap<K, V, core::hash::BuildHasherDefault<ahash::AHasher>>;
+/// An [`indexmap::IndexSet`] with the hasher used throughout the crate
+pub type IndexSet<T> = indexmap::IndexSet<T, core::hash::BuildHasherDefault<ahash::AHasher>>;
 
 /// Create an [`IndexMap`][self::IndexMap] from a list of key-value pairs
 macro_rules! indexmap {
@@ -1,4 +1,5 @@
 use crate::base::scalar::Scalar;
+use alloc::vec::Vec;
 use ark_ff::BigInteger;
 use core::ops::Neg;
 use serde::{Deserialize, Serialize};
@@ -55,6 +56,20 @@ impl I256 {
             num_bigint::Sign::Plus | num_bigint::Sign::NoSign => Self(limbs),
         }
     }
+
+    #[must_use]
+    /// Conversion into a [`num_bigint::BigInt`], interpreting the limbs as a two's complement signed integer.
+    ///
+    /// NOTE: this is not a particularly efficient method. Please either refactor or avoid when performance matters.
+    pub fn to_num_bigint(self) -> num_bigint::BigInt {
+        let bytes: Vec<u8> = self.0.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        let unsigned = num_bigint::BigInt::from_bytes_le(num_bigint::Sign::Plus, &bytes);
+        if self.0[3] & 0x8000_0000_0000_0000 == 0 {
+            unsigned
+        } else {
+            unsigned - (num_bigint::BigInt::from(1) << 256)
+        }
+    }
 }
 impl From<i32> for I256 {
     fn from(value: i32) -> Self {
@@ -260,6 +275,27 @@ mod tests {
         }
     }
     #[test]
+    fn we_can_convert_i256_to_num_bigint() {
+        assert_eq!(ZERO.to_num_bigint(), BigInt::from(0));
+        assert_eq!(ONE.to_num_bigint(), BigInt::from(1));
+        assert_eq!(NEG_ONE.to_num_bigint(), BigInt::from(-1));
+        assert_eq!(NEG_TWO.to_num_bigint(), BigInt::from(-2));
+        assert_eq!(A.to_num_bigint(), A_STR.parse().unwrap());
+        assert_eq!(NEG_A.to_num_bigint(), -A_STR.parse::<BigInt>().unwrap());
+        assert_eq!(B.to_num_bigint(), B_STR.parse().unwrap());
+        assert_eq!(NEG_B.to_num_bigint(), -B_STR.parse::<BigInt>().unwrap());
+        assert_eq!(
+            I256([0, 0, 0, 0x8000_0000_0000_0000]).to_num_bigint(),
+            -(BigInt::from(1) << 255)
+        );
+
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let x = I256([rng.gen(), rng.gen(), rng.gen(), rng.gen()]);
+            assert_eq!(I256::from_num_bigint(&x.to_num_bigint()), x);
+        }
+    }
+    #[test]
     fn we_can_convert_i256_from_i32() {
         assert_eq!(I256::from(0), ZERO);
         assert_eq!(I256::from(1), ONE);
@@ -4,23 +4,31 @@
 pub mod arrow;
 
 pub(crate) mod bit;
+pub(crate) mod bounded_deserializer;
 pub mod commitment;
 pub mod database;
+pub(crate) mod depth_limited_deserializer;
 /// TODO: add docs
 pub(crate) mod encode;
 pub mod math;
 /// TODO: add docs
 pub(crate) mod polynomial;
+pub use polynomial::MultilinearExtension;
 /// Module for Proof of SQL datetime types.
 pub mod posql_time;
 pub(crate) mod proof;
-pub use proof::{PlaceholderError, PlaceholderResult};
+pub use proof::{
+    Keccak256Transcript, PlaceholderError, PlaceholderResult, ProofError, ProofSizeMismatch,
+    Transcript,
+};
+pub(crate) mod encoded_size;
 pub(crate) mod ref_into;
 /// This module contains the `Scalar` trait as well as the main, generic, implementations of it.
 pub mod scalar;
 mod serialize;
 pub(crate) use serialize::{impl_serde_for_ark_serde_checked, impl_serde_for_ark_serde_unchecked};
 pub(crate) mod map;
+pub use map::{IndexMap, IndexSet};
 pub(crate) mod slice_ops;
 
 mod rayon_cfg;
@@ -1,15 +1,22 @@
 use crate::base::database::ColumnType;
 use snafu::Snafu;
+use sqlparser::ast::Ident;
 
 #[derive(Snafu, Debug)]
 /// These errors occur when a proof failed to verify.
 pub enum ProofError {
     #[snafu(display("Verification error: {error}"))]
     /// This error occurs when a proof failed to verify.
-    VerificationError { error: &'static str },
+    VerificationError {
+        /// The reason the proof failed to verify
+        error: &'static str,
+    },
     /// This error occurs when a query plan is not supported.
     #[snafu(display("Unsupported query plan: {error}"))]
-    UnsupportedQueryPlan { error: &'static str },
+    UnsupportedQueryPlan {
+        /// The reason the query plan is not supported
+        error: &'static str,
+    },
     /// This error occurs the type coercion of the result table failed.
     #[snafu(display("Result does not match query: type mismatch"))]
     InvalidTypeCoercion,
@@ -19,10 +26,52 @@ pub enum ProofError {
     /// This error occurs when the number of fields in the result table does not match the query.
     #[snafu(display("Result does not match query: field count mismatch"))]
     FieldCountMismatch,
+    /// This error occurs when a column of the result table does not match its proven evaluation.
+    #[snafu(display("Result does not match query: column {column} does not match the proof"))]
+    ResultColumnMismatch {
+        /// The name of the mismatching column
+        column: Ident,
+    },
+    /// This error occurs when a plan declares more output rows than its input has.
+    #[snafu(display(
+        "Declared output length {output_length} exceeds the input length {input_length}"
+    ))]
+    OutputLengthExceedsInput {
+        /// The output length declared by the prover
+        output_length: usize,
+        /// The length of the input table
+        input_length: usize,
+    },
+    /// This error occurs when a group by declares more groups than the verifier allows.
+    #[snafu(display("Declared group count {num_groups} exceeds the maximum of {max_groups}"))]
+    TooManyGroups {
+        /// The number of groups declared by the prover
+        num_groups: usize,
+        /// The maximum number of groups set in the verification options
+        max_groups: usize,
+    },
+    /// This error occurs when a chi evaluation has another length than the verifier expects.
+    #[snafu(display(
+        "Declared chi evaluation length {declared_length} does not match the expected length {expected_length}"
+    ))]
+    ChiLengthMismatch {
+        /// The length declared by the prover
+        declared_length: usize,
+        /// The length the verifier expects
+        expected_length: usize,
+    },
+    /// This error occurs when the proof does not have the size the plan expects.
     #[snafu(transparent)]
-    ProofSizeMismatch { source: ProofSizeMismatch },
+    ProofSizeMismatch {
+        /// The underlying proof size mismatch
+        source: ProofSizeMismatch,
+    },
+    /// This error occurs when a placeholder is invalid.
     #[snafu(transparent)]
-    PlaceholderError { source: PlaceholderError },
+    PlaceholderError {
+        /// The underlying placeholder error
+        source: PlaceholderError,
+    },
 }
 
 #[derive(Snafu, Debug)]
@@ -83,6 +132,19 @@ pub enum PlaceholderError {
         actual: ColumnType,
     },
 
+    #[snafu(display(
+        "Placeholder value is not representable: {id}, expected: {expected}, actual: {actual}"
+    ))]
+    /// Placeholder value can not be represented exactly in the type of the placeholder
+    PlaceholderValueNotRepresentable {
+        /// The placeholder id
+        id: usize,
+        /// The expected type
+        expected: ColumnType,
+        /// The actual type
+        actual: ColumnType,
+    },
+
     #[snafu(display("Placeholder id must be greater than 0"))]
     /// Placeholder id is zero
     ZeroPlaceholderId,
@@ -8,6 +8,7 @@ pub use error::{PlaceholderError, PlaceholderResult, ProofError, ProofSizeMismat
 mod merlin_transcript_core;
 
 mod transcript;
+pub(crate) use transcript::transcript_serialize;
 pub use transcript::Transcript;
 
 mod transcript_core;
@@ -2,6 +2,20 @@ use crate::base::scalar::Scalar;
 use alloc::vec::Vec;
 use zerocopy::{AsBytes, FromBytes};
 
+/// Serializes a message the way [`Transcript::extend_serialize_as_le`] appends it.
+///
+/// # Panics
+/// - Panics if the message fails to serialize.
+pub(crate) fn transcript_serialize(message: &(impl serde::Serialize + ?Sized)) -> Vec<u8> {
+    bincode::serde::encode_to_vec(
+        message,
+        bincode::config::legacy()
+            .with_fixed_int_encoding()
+            .with_big_endian(),
+    )
+    .unwrap()
+}
+
 /// A public-coin transcript.
 ///
 /// This trait contains several method for adding prover messages and computing verifier challenges.
@@ -41,14 +55,7 @@ pub trait Transcript {
     /// # Panics
     /// - Panics if `postcard::to_allocvec(message)` fails to serialize the message.
     fn extend_serialize_as_le(&mut self, message: &(impl serde::Serialize + ?Sized)) {
-        self.extend_as_le_from_refs([bincode::serde::encode_to_vec(
-            message,
-            bincode::config::legacy()
-                .with_fixed_int_encoding()
-                .with_big_endian(),
-        )
-        .unwrap()
-        .as_slice()]);
+        self.extend_as_le_from_refs([transcript_serialize(message).as_slice()]);
     }
     /// Appends a type that implements [`ark_serialize::CanonicalSerialize`] by appending the raw bytes (i.e. assuming the message is littleendian)
     ///
@@ -120,7 +120,10 @@ impl<T: MontConfig<4>> Ord for MontScalar<T> {
 // end replacement for #[derive(...)]
 // --------------------------------------------------------------------------------
 
-/// TODO: add docs
+/// Implement `From` for `MontScalar` for an integer type
+///
+/// This is the embedding of the integers into the field, so negative values are negated in the
+/// field. See the [`Scalar`](crate::base::scalar::Scalar) trait for why this matters.
 macro_rules! impl_from_for_mont_scalar_for_type_supported_by_from {
     ($tt:ty) => {
         impl<T: MontConfig<4>> From<$tt> for MontScalar<T> {
@@ -132,6 +135,13 @@ macro_rules! impl_from_for_mont_scalar_for_type_supported_by_from {
 }
 
 /// Implement `From<&[u8]>` for `MontScalar`
+///
+/// This is how `VarChar` values are encoded as scalars everywhere they are committed to or
+/// compared. The empty slice is zero, while any other slice is its blake3 hash with the top four
+/// bits cleared. That value is below `2^252`, and hence below the modulus of every scalar field
+/// this is used with, so it is never reduced. Distinct byte strings therefore only get the same
+/// scalar if their hashes collide, and a non-empty byte string such as `"\0"` only gets the scalar
+/// of the empty string if its masked hash is zero.
 impl<T: MontConfig<4>> From<&[u8]> for MontScalar<T> {
     fn from(x: &[u8]) -> Self {
         if x.is_empty() {
@@ -52,3 +52,25 @@ fn we_can_bound_modulus_using_max_bits() {
     assert!(modulus_of_i_max_bits <= modulus_of_test_scalar);
     assert!(modulus_of_i_max_bits_plus_1 > modulus_of_test_scalar);
 }
+
+#[test]
+fn we_encode_distinct_strings_as_distinct_scalars() {
+    // Strings which could plausibly be conflated with the empty string
+    let candidates = ["", "\0", "\0\0", " ", "0", "\0a", "a\0", "a"];
+    let scalars: Vec<TestScalar> = candidates.iter().map(|&s| TestScalar::from(s)).collect();
+    assert_eq!(scalars[0], TestScalar::ZERO);
+    for (i, scalar) in scalars.iter().enumerate() {
+        // Strings, string slices and their bytes are encoded alike
+        assert_eq!(*scalar, TestScalar::from(candidates[i].to_string()));
+        assert_eq!(*scalar, TestScalar::from(candidates[i].as_bytes()));
+        for other_scalar in &scalars[i + 1..] {
+            assert_ne!(scalar, other_scalar);
+        }
+    }
+}
+
+#[test]
+fn masked_string_hashes_are_not_reduced_by_the_modulus() {
+    let modulus_of_test_scalar = U256::from(TestMontConfig::MODULUS.0);
+    assert!(U256::ONE << 252 <= modulus_of_test_scalar);
+}
@@ -7,6 +7,15 @@ use core::ops::Sub;
 use num_bigint::BigInt;
 
 /// A trait for the scalar field used in Proof of SQL.
+///
+/// # Signed integers
+///
+/// A signed integer `x` is encoded as the field element `x`, so a negative `x` is `p - |x|`, or
+/// `-S::from(|x|)`. It is never encoded from its two's complement bits. The `From` conversions of
+/// the integer types are the single definition of this encoding. Columns are evaluated with them
+/// when proving and verifying, and every commitment scheme commits to the same values, either by
+/// converting with them or, for blitzar, by committing to signed sequences. Values above
+/// [`Scalar::MAX_SIGNED`] are read back as negative integers.
 pub trait Scalar:
     Clone
     + core::fmt::Debug
@@ -0,0 +1,14 @@
+//! Thin wrappers which expose crate-internal gadgets to the micro-benchmarks in `benches/`.
+//!
+//! These are only available with the `bench` feature and are not part of the stable API.
+use crate::base::scalar::Scalar;
+
+/// Invert every nonzero element of `v` in place using Montgomery's trick.
+pub fn batch_inversion<S: Scalar>(v: &mut [S]) {
+    crate::base::slice_ops::batch_inversion(v);
+}
+
+/// Add `mul * sum_i beta^(n-1-i) * columns[i]` to `res`, where `n` is the number of columns.
+pub fn fold_columns<S: Scalar>(res: &mut [S], mul: S, beta: S, columns: &[&[S]]) {
+    crate::sql::proof_plans::fold_columns(res, mul, beta, columns);
+}
@@ -9,3 +9,7 @@ pub mod proof_primitive;
 pub mod sql;
 /// Utilities for working with the library
 pub mod utils;
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench_util;
@@ -112,6 +112,13 @@ fn copy_column_data_to_slice(
         CommittableColumn::Boolean(column) => {
             scalar_row_slice[start..end].copy_from_slice(&column[index].offset_to_bytes());
         }
+        CommittableColumn::BooleanBitmap(bitmap) => {
+            let value = bitmap.get(index).expect("index is within the column");
+            scalar_row_slice[start..end].copy_from_slice(&value.offset_to_bytes());
+        }
+        CommittableColumn::DeltaEncoded(_) => {
+            unreachable!("delta encoded columns are decoded before copying their data")
+        }
         CommittableColumn::Uint8(column) => {
             scalar_row_slice[start..end].copy_from_slice(&column[index].offset_to_bytes());
         }
@@ -231,6 +238,19 @@ pub fn create_blitzar_metadata_tables(
         (num_of_bytes_in_committable_columns + ones_columns_lengths.len()) * offset_height;
     let mut blitzar_scalars = vec![0u8; num_scalar_rows * num_scalar_columns];
 
+    // Decode delta encoded columns up front, since their values cannot be accessed by index.
+    let decoded_values: Vec<Option<Vec<i64>>> = committable_columns
+        .iter()
+        .map(|column| match column {
+            CommittableColumn::DeltaEncoded(deltas) => Some(deltas.to_vec()),
+            _ => None,
+        })
+        .collect();
+    let decoded_columns: Vec<Option<CommittableColumn>> = decoded_values
+        .iter()
+        .map(|values| values.as_deref().map(CommittableColumn::BigInt))
+        .collect();
+
     // Populate the scalars array.
     let span = span!(Level::DEBUG, "pack_blitzar_scalars").entered();
     if !blitzar_scalars.is_empty() {
@@ -257,7 +277,7 @@ pub fn create_blitzar_metadata_tables(
                             + (single_entry_in_blitzar_output_bit_table[i] / BYTE_SIZE) as usize;
 
                         copy_column_data_to_slice(
-                            committable_column,
+                            decoded_columns[i].as_ref().unwrap_or(committable_column),
                             scalar_row_slice,
                             start,
                             end,
@@ -0,0 +1,118 @@
+use super::{
+    pairings, DoryCommitment, DoryProverPublicSetup, DoryScalar, DynamicDoryCommitment, G1Affine,
+    G1Projective, ProverSetup,
+};
+use crate::proof_primitive::dynamic_matrix_utils::matrix_structure::{
+    full_width_of_row, row_and_column_from_index, row_start_index,
+};
+use alloc::vec::Vec;
+use core::{iter::once, ops::Range};
+use num_traits::Zero;
+
+/// Returns the sums of the generators `Gamma_1[range]` for each of the `ranges`.
+///
+/// The sums are differences of prefix sums, so this takes time linear in the largest end of the
+/// ranges rather than in their total length.
+fn generator_sums(Gamma_1: &[G1Affine], ranges: &[Range<usize>]) -> Vec<G1Projective> {
+    let end = ranges.iter().map(|range| range.end).max().unwrap_or(0);
+    let prefix_sums: Vec<G1Projective> = once(G1Projective::zero())
+        .chain(
+            Gamma_1[..end]
+                .iter()
+                .scan(G1Projective::zero(), |sum, generator| {
+                    *sum += generator;
+                    Some(*sum)
+                }),
+        )
+        .collect();
+    ranges
+        .iter()
+        .map(|range| prefix_sums[range.end] - prefix_sums[range.start])
+        .collect()
+}
+
+/// Computes the Dory commitment to a column of `num_rows` copies of `value` starting at `offset`.
+///
+/// Each row commitment is `value` times the sum of the generators of the row, so the column is never
+/// materialized.
+///
+/// # Panics
+///
+/// Will panic if the setup is too small for the rows of the column.
+pub(super) fn compute_dory_constant_commitment(
+    value: DoryScalar,
+    num_rows: usize,
+    offset: usize,
+    setup: &DoryProverPublicSetup,
+) -> DoryCommitment {
+    if num_rows == 0 {
+        return DoryCommitment::default();
+    }
+    let Gamma_1 = setup.prover_setup().Gamma_1.last().unwrap();
+    let Gamma_2 = setup.prover_setup().Gamma_2.last().unwrap();
+    let num_columns = 1 << setup.sigma();
+    let last_index = offset + num_rows - 1;
+    let (first_row, first_col) = (offset / num_columns, offset % num_columns);
+    let (last_row, last_col) = (last_index / num_columns, last_index % num_columns);
+
+    let ranges: Vec<_> = (first_row..=last_row)
+        .map(|row| {
+            let start = if row == first_row { first_col } else { 0 };
+            let end = if row == last_row {
+                last_col + 1
+            } else {
+                num_columns
+            };
+            start..end
+        })
+        .collect();
+    let row_commits = generator_sums(Gamma_1, &ranges);
+
+    DoryCommitment(pairings::multi_pairing(row_commits, &Gamma_2[first_row..=last_row]) * value.0)
+}
+
+/// Computes the Dynamic Dory commitment to a column of `num_rows` copies of `value` starting at
+/// `offset`.
+///
+/// Each row commitment is `value` times the sum of the generators of the row, so the column is never
+/// materialized.
+///
+/// # Panics
+///
+/// Will panic if the setup is too small for the rows of the column.
+pub(super) fn compute_dynamic_dory_constant_commitment(
+    value: DoryScalar,
+    num_rows: usize,
+    offset: usize,
+    setup: &ProverSetup,
+) -> DynamicDoryCommitment {
+    if num_rows == 0 {
+        return DynamicDoryCommitment::default();
+    }
+    let Gamma_1 = setup.Gamma_1.last().unwrap();
+    let Gamma_2 = setup.Gamma_2.last().unwrap();
+    let (first_row, first_col) = row_and_column_from_index(offset);
+    let (last_row, last_col) = row_and_column_from_index(offset + num_rows - 1);
+
+    let ranges: Vec<_> = (first_row..=last_row)
+        .map(|row| {
+            // Not every row starts at the first column, e.g. the second row only has its second.
+            let start = if row == first_row {
+                first_col
+            } else {
+                row_and_column_from_index(row_start_index(row)).1
+            };
+            let end = if row == last_row {
+                last_col + 1
+            } else {
+                full_width_of_row(row)
+            };
+            start..end
+        })
+        .collect();
+    let row_commits = generator_sums(Gamma_1, &ranges);
+
+    DynamicDoryCommitment(
+        pairings::multi_pairing(row_commits, &Gamma_2[first_row..=last_row]) * value.0,
+    )
+}
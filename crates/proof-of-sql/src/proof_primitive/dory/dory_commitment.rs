@@ -25,6 +25,7 @@
 use super::{DoryProverPublicSetup, GT};
 use crate::base::{
     commitment::{Commitment, CommittableColumn},
+    encoded_size::seq_size,
     impl_serde_for_ark_serde_checked,
     scalar::MontScalar,
 };
@@ -87,11 +88,50 @@ impl Commitment for DoryCommitment {
         super::compute_dory_commitments(committable_columns, offset, setup)
     }
 
+    fn compute_constant_commitment(
+        value: DoryScalar,
+        num_rows: usize,
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) -> Option<Self> {
+        Some(super::compute_dory_constant_commitment(
+            value, num_rows, offset, setup,
+        ))
+    }
+
     fn to_transcript_bytes(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(self.0.compressed_size());
         self.0.serialize_compressed(&mut buf).unwrap();
         buf
     }
+
+    /// The commitment is serialized as a byte vector holding its compressed form, so this is exact.
+    fn encoded_size_hint(&self) -> usize {
+        seq_size(self.compressed_size(), 1)
+    }
+
+    fn scheme_identifier() -> &'static str {
+        "dory"
+    }
+
+    /// Commitments depend on `sigma` as well as on the prover setup.
+    fn setup_digest(setup: &Self::PublicSetup<'_>) -> Option<[u8; 32]> {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&setup.prover_setup().digest());
+        hasher.update(&(setup.sigma() as u64).to_le_bytes());
+        Some(*hasher.finalize().as_bytes())
+    }
+
+    /// The matrix of a setup has `2^max_nu` rows of `2^sigma` generators, and it can open MLEs
+    /// with up to `max_nu + sigma` variables, as long as `sigma <= max_nu`.
+    fn num_generators(setup: &Self::PublicSetup<'_>) -> Option<usize> {
+        let max_nu = setup.prover_setup().max_nu;
+        Some(if setup.sigma() <= max_nu {
+            1 << (max_nu + setup.sigma())
+        } else {
+            0
+        })
+    }
 }
 
 #[cfg(test)]
@@ -99,7 +139,9 @@ mod tests {
     use super::{DoryCommitment, DoryProverPublicSetup, DoryScalar, GT};
     use crate::{
         base::{
-            commitment::{Commitment, NumColumnsMismatch, VecCommitmentExt},
+            commitment::{
+                column_types_for_testing, Commitment, NumColumnsMismatch, VecCommitmentExt,
+            },
             database::{Column, OwnedColumn},
             scalar::test_scalar_constants,
         },
@@ -499,4 +541,14 @@ mod tests {
             commitment2.to_transcript_bytes()
         );
     }
+
+    #[test]
+    fn dory_commitments_support_every_column_type() {
+        for column_type in column_types_for_testing() {
+            assert!(
+                DoryCommitment::supports_column_type(column_type),
+                "{column_type}"
+            );
+        }
+    }
 }
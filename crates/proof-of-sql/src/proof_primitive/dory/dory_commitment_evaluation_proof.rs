@@ -6,9 +6,10 @@ use super::{
     DoryCommitment, DoryMessages, DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup, F,
 };
 use crate::{
-    base::{commitment::CommitmentEvaluationProof, proof::Transcript},
+    base::{commitment::CommitmentEvaluationProof, encoded_size::seq_size, proof::Transcript},
     utils::log,
 };
+use ark_serialize::CanonicalSerialize;
 use snafu::Snafu;
 
 /// The `CommitmentEvaluationProof` for the Dory PCS.
@@ -70,6 +71,11 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
         messages
     }
 
+    /// The messages are serialized as a byte vector holding their compressed form, so this is exact.
+    fn encoded_size_hint(&self) -> usize {
+        seq_size(self.compressed_size(), 1)
+    }
+
     #[tracing::instrument(
         name = "DoryEvaluationProof::verify_batched_proof",
         level = "debug",
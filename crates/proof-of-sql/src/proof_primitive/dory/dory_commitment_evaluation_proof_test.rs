@@ -1,8 +1,11 @@
 use super::{
-    test_rng, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup,
-    ProverSetup, PublicParameters, VerifierSetup,
+    test_rng, DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+    DoryVerifierPublicSetup, ProverSetup, PublicParameters, VerifierSetup,
+};
+use crate::base::{
+    commitment::{commitment_evaluation_proof_test::*, Commitment, CommitmentEvaluationProof},
+    math::log2_up,
 };
-use crate::base::commitment::{commitment_evaluation_proof_test::*, CommitmentEvaluationProof};
 use ark_std::UniformRand;
 use merlin::Transcript;
 
@@ -92,3 +95,37 @@ fn we_can_serialize_and_deserialize_dory_evaluation_proofs() {
     let decoded: DoryEvaluationProof = postcard::from_bytes(&encoded).unwrap();
     assert_eq!(decoded, proof);
 }
+
+#[test]
+fn we_can_open_mles_exactly_as_long_as_the_number_of_generators_of_the_setup() {
+    let public_parameters = PublicParameters::test_rand(3, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    for sigma in 1..=3 {
+        let prover_public_setup = DoryProverPublicSetup::new(&prover_setup, sigma);
+        let num_generators = DoryCommitment::num_generators(&prover_public_setup).unwrap();
+        assert_eq!(num_generators, 1 << (3 + sigma));
+        test_random_commitment_evaluation_proof::<DoryEvaluationProof>(
+            num_generators,
+            0,
+            &prover_public_setup,
+            &DoryVerifierPublicSetup::new(&verifier_setup, sigma),
+        );
+
+        // One more entry needs another variable, which the setup cannot open
+        let a = vec![DoryScalar::from(1u64); num_generators + 1];
+        let b_point = vec![DoryScalar::from(2u64); log2_up(num_generators + 1)];
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        assert_eq!(
+            DoryEvaluationProof::new(&mut transcript, &a, &b_point, 0, &prover_public_setup),
+            DoryEvaluationProof::default()
+        );
+    }
+
+    // A sigma larger than max_nu cannot be used at all
+    let prover_public_setup = DoryProverPublicSetup::new(&prover_setup, 4);
+    assert_eq!(
+        DoryCommitment::num_generators(&prover_public_setup),
+        Some(0)
+    );
+}
@@ -78,6 +78,12 @@ fn compute_dory_commitment(
         CommittableColumn::VarChar(column) => compute_dory_commitment_impl(column, offset, setup),
         CommittableColumn::VarBinary(column) => compute_dory_commitment_impl(column, offset, setup),
         CommittableColumn::Boolean(column) => compute_dory_commitment_impl(column, offset, setup),
+        CommittableColumn::BooleanBitmap(bitmap) => {
+            compute_dory_commitment_impl(&bitmap.to_vec(), offset, setup)
+        }
+        CommittableColumn::DeltaEncoded(deltas) => {
+            compute_dory_commitment_impl(&deltas.to_vec(), offset, setup)
+        }
         CommittableColumn::TimestampTZ(_, _, column) => {
             compute_dory_commitment_impl(column, offset, setup)
         }
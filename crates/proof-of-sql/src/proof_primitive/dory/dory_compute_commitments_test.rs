@@ -1,13 +1,19 @@
 use crate::{
     base::{
-        commitment::CommittableColumn,
+        commitment::{
+            assert_constant_commitments_match_materialized,
+            assert_signed_commitments_match_scalars, CommittableColumn,
+        },
+        database::{BooleanBitmap, DeltaEncodedColumn},
         math::decimal::Precision,
         posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
     },
     proof_primitive::dory::{
-        compute_dory_commitments, DoryProverPublicSetup, ProverSetup, PublicParameters, F, GT,
+        compute_dory_commitments, DoryCommitment, DoryProverPublicSetup, ProverSetup,
+        PublicParameters, F, GT,
     },
 };
+use alloc::vec::Vec;
 use ark_ec::pairing::Pairing;
 use ark_std::test_rng;
 use num_traits::Zero;
@@ -744,3 +750,98 @@ fn we_can_compute_a_dory_commitment_with_mixed_committable_columns_with_an_offse
         + Pairing::pairing(Gamma_1[3], Gamma_2[1]) * F::from(18);
     assert_eq!(res[9].0, expected);
 }
+
+#[test]
+fn we_can_compute_a_dory_commitment_with_packed_boolean_values() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let setup = DoryProverPublicSetup::new(&prover_setup, 2);
+    let values: Vec<bool> = (0..70).map(|i| i % 5 < 2).collect();
+    let bitmap = BooleanBitmap::from(values.as_slice());
+    for offset in [0, 3] {
+        let res = compute_dory_commitments(
+            &[
+                CommittableColumn::BooleanBitmap(&bitmap),
+                CommittableColumn::BigInt(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        let expected = compute_dory_commitments(
+            &[
+                CommittableColumn::Boolean(&values),
+                CommittableColumn::BigInt(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_compute_dory_commitments_to_negative_integers_as_their_scalars() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    assert_signed_commitments_match_scalars::<DoryCommitment>(
+        &DoryProverPublicSetup::new(&prover_setup, 2),
+        &[0, 1, 3, 13],
+    );
+}
+
+#[test]
+fn we_can_compute_dory_commitments_to_constant_columns() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    assert_constant_commitments_match_materialized::<DoryCommitment>(
+        &DoryProverPublicSetup::new(&prover_setup, 2),
+        &[
+            (0, 0),
+            (0, 5),
+            (1, 0),
+            (1, 1),
+            (2, 0),
+            (3, 1),
+            (4, 0),
+            (9, 2),
+            (17, 5),
+            (64, 0),
+            (70, 13),
+        ],
+    );
+}
+
+#[test]
+fn we_can_compute_a_dory_commitment_with_delta_encoded_values() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let setup = DoryProverPublicSetup::new(&prover_setup, 2);
+    // Smooth data with the first value far from zero, along with the extremes of i64
+    let values: Vec<i64> = (0..70)
+        .map(|i| 1_700_000_000_000 + 1_000 * i)
+        .chain([i64::MIN, -1, i64::MAX - 1, 0])
+        .collect();
+    let deltas = DeltaEncodedColumn::try_from_values(&values[..70]).unwrap();
+    let extremes = DeltaEncodedColumn::try_from_values(&values[70..]).unwrap();
+    for offset in [0, 3] {
+        let res = compute_dory_commitments(
+            &[
+                CommittableColumn::DeltaEncoded(&deltas),
+                CommittableColumn::DeltaEncoded(&extremes),
+                CommittableColumn::Int(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        let expected = compute_dory_commitments(
+            &[
+                CommittableColumn::BigInt(&values[..70]),
+                CommittableColumn::BigInt(&values[70..]),
+                CommittableColumn::Int(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        assert_eq!(res, expected);
+    }
+}
@@ -1,4 +1,4 @@
-use super::{ProverSetup, VerifierSetup};
+use super::{ProverSetup, PublicParameters, VerifierSetup};
 
 /// The public setup required for the Dory PCS by the prover and the commitment computation.
 #[derive(Clone, Copy)]
@@ -57,3 +57,55 @@ impl<'a> DoryVerifierPublicSetup<'a> {
         self.verifier_setup
     }
 }
+
+/// A prover and verifier setup shared between the Dory and Dynamic Dory PCS.
+///
+/// Both schemes use the same generators, so benchmarking or serving both only needs one copy of
+/// them. The Dory public setups borrow from this with a given `sigma`, while the Dynamic Dory
+/// public setups are the setups themselves.
+pub struct SharedDorySetup<'a> {
+    prover_setup: ProverSetup<'a>,
+    verifier_setup: VerifierSetup,
+}
+impl<'a> SharedDorySetup<'a> {
+    /// Create a new shared setup from the prover and verifier setups.
+    /// `prover_setup`: The prover's setup parameters for the Dory protocol.
+    /// `verifier_setup`: The verifier's setup parameters for the Dory protocol.
+    #[must_use]
+    pub fn new(prover_setup: ProverSetup<'a>, verifier_setup: VerifierSetup) -> Self {
+        Self {
+            prover_setup,
+            verifier_setup,
+        }
+    }
+    /// The public setup for the Dory PCS by the prover and the commitment computation.
+    /// `sigma`: A commitment with this setup is a matrix commitment with `1 << sigma` columns.
+    #[must_use]
+    pub fn dory_prover_setup(&self, sigma: usize) -> DoryProverPublicSetup<'_> {
+        DoryProverPublicSetup::new(&self.prover_setup, sigma)
+    }
+    /// The verifier's public setup for the Dory PCS.
+    /// `sigma`: A commitment with this setup is a matrix commitment with `1 << sigma` columns.
+    #[must_use]
+    pub fn dory_verifier_setup(&self, sigma: usize) -> DoryVerifierPublicSetup<'_> {
+        DoryVerifierPublicSetup::new(&self.verifier_setup, sigma)
+    }
+    /// The public setup for the Dynamic Dory PCS by the prover and the commitment computation.
+    #[must_use]
+    pub fn dynamic_dory_prover_setup(&self) -> &ProverSetup<'a> {
+        &self.prover_setup
+    }
+    /// The verifier's public setup for the Dynamic Dory PCS.
+    #[must_use]
+    pub fn dynamic_dory_verifier_setup(&self) -> &VerifierSetup {
+        &self.verifier_setup
+    }
+}
+impl<'a> From<&'a PublicParameters> for SharedDorySetup<'a> {
+    fn from(public_parameters: &'a PublicParameters) -> Self {
+        Self::new(
+            ProverSetup::from(public_parameters),
+            VerifierSetup::from(public_parameters),
+        )
+    }
+}
@@ -0,0 +1,78 @@
+use super::{
+    test_rng, DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
+    DynamicDoryCommitment, DynamicDoryEvaluationProof, ProverSetup, PublicParameters,
+    SharedDorySetup, VerifierSetup,
+};
+use crate::base::commitment::{
+    commitment_evaluation_proof_test::test_simple_commitment_evaluation_proof, Commitment,
+    CommittableColumn,
+};
+
+#[test]
+fn we_can_compute_the_same_commitments_with_a_shared_setup_as_with_separate_setups() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let shared_setup = SharedDorySetup::from(&public_parameters);
+    let dory_prover_setup = ProverSetup::from(&public_parameters);
+    let dynamic_dory_prover_setup = ProverSetup::from(&public_parameters);
+
+    let columns = [
+        CommittableColumn::BigInt(&[1, 2, 3, 4, 5]),
+        CommittableColumn::Boolean(&[true, false, true]),
+    ];
+
+    for offset in [0, 3] {
+        assert_eq!(
+            DoryCommitment::compute_commitments(
+                &columns,
+                offset,
+                &shared_setup.dory_prover_setup(2)
+            ),
+            DoryCommitment::compute_commitments(
+                &columns,
+                offset,
+                &DoryProverPublicSetup::new(&dory_prover_setup, 2)
+            )
+        );
+        assert_eq!(
+            DynamicDoryCommitment::compute_commitments(
+                &columns,
+                offset,
+                &shared_setup.dynamic_dory_prover_setup()
+            ),
+            DynamicDoryCommitment::compute_commitments(
+                &columns,
+                offset,
+                &&dynamic_dory_prover_setup
+            )
+        );
+    }
+}
+
+#[test]
+fn we_can_prove_and_verify_both_schemes_with_one_shared_setup() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let shared_setup = SharedDorySetup::from(&public_parameters);
+    test_simple_commitment_evaluation_proof::<DoryEvaluationProof>(
+        &shared_setup.dory_prover_setup(3),
+        &shared_setup.dory_verifier_setup(3),
+    );
+    test_simple_commitment_evaluation_proof::<DynamicDoryEvaluationProof>(
+        &shared_setup.dynamic_dory_prover_setup(),
+        &shared_setup.dynamic_dory_verifier_setup(),
+    );
+}
+
+#[test]
+fn we_can_create_a_shared_setup_from_existing_setups() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let shared_setup = SharedDorySetup::new(
+        ProverSetup::from(&public_parameters),
+        VerifierSetup::from(&public_parameters),
+    );
+    assert_eq!(shared_setup.dory_prover_setup(2).sigma(), 2);
+    assert_eq!(shared_setup.dory_verifier_setup(2).sigma(), 2);
+    test_simple_commitment_evaluation_proof::<DoryEvaluationProof>(
+        &shared_setup.dory_prover_setup(2),
+        &DoryVerifierPublicSetup::new(&VerifierSetup::from(&public_parameters), 2),
+    );
+}
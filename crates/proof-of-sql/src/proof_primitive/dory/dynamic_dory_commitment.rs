@@ -25,6 +25,7 @@
 use super::{DoryScalar, ProverSetup, GT};
 use crate::base::{
     commitment::{Commitment, CommittableColumn},
+    encoded_size::seq_size,
     impl_serde_for_ark_serde_checked,
 };
 use alloc::vec::Vec;
@@ -83,17 +84,50 @@ impl Commitment for DynamicDoryCommitment {
         super::compute_dynamic_dory_commitments(committable_columns, offset, setup)
     }
 
+    fn compute_constant_commitment(
+        value: DoryScalar,
+        num_rows: usize,
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) -> Option<Self> {
+        Some(super::compute_dynamic_dory_constant_commitment(
+            value, num_rows, offset, setup,
+        ))
+    }
+
     fn to_transcript_bytes(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(self.0.compressed_size());
         self.0.serialize_compressed(&mut buf).unwrap();
         buf
     }
+
+    /// The commitment is serialized as a byte vector holding its compressed form, so this is exact.
+    fn encoded_size_hint(&self) -> usize {
+        seq_size(self.compressed_size(), 1)
+    }
+
+    fn scheme_identifier() -> &'static str {
+        "dynamic_dory"
+    }
+
+    fn setup_digest(setup: &Self::PublicSetup<'_>) -> Option<[u8; 32]> {
+        Some(setup.digest())
+    }
+
+    /// A setup with `max_nu > 0` spans the first `2^max_nu` rows of the dynamic matrix, which hold
+    /// `2^(2 * max_nu - 1)` generators, and it can open MLEs with up to `2 * max_nu - 1` variables.
+    fn num_generators(setup: &Self::PublicSetup<'_>) -> Option<usize> {
+        Some(match setup.max_nu {
+            0 => 1,
+            max_nu => 1 << (2 * max_nu - 1),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{DynamicDoryCommitment, GT};
-    use crate::base::commitment::Commitment;
+    use crate::base::commitment::{column_types_for_testing, Commitment};
     use ark_ff::UniformRand;
     use rand::{rngs::StdRng, SeedableRng};
 
@@ -107,4 +141,14 @@ mod tests {
             commitment2.to_transcript_bytes()
         );
     }
+
+    #[test]
+    fn dynamic_dory_commitments_support_every_column_type() {
+        for column_type in column_types_for_testing() {
+            assert!(
+                DynamicDoryCommitment::supports_column_type(column_type),
+                "{column_type}"
+            );
+        }
+    }
 }
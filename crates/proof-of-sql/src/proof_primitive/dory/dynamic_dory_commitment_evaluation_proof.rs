@@ -6,9 +6,10 @@ use super::{
     DynamicDoryCommitment, ProverSetup, VerifierSetup, F,
 };
 use crate::{
-    base::{commitment::CommitmentEvaluationProof, proof::Transcript},
+    base::{commitment::CommitmentEvaluationProof, encoded_size::seq_size, proof::Transcript},
     utils::log,
 };
+use ark_serialize::CanonicalSerialize;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 
@@ -71,6 +72,11 @@ impl CommitmentEvaluationProof for DynamicDoryEvaluationProof {
         Self(messages)
     }
 
+    /// The messages are serialized as a byte vector holding their compressed form, so this is exact.
+    fn encoded_size_hint(&self) -> usize {
+        seq_size(self.0.compressed_size(), 1)
+    }
+
     #[tracing::instrument(
         name = "DoryEvaluationProof::verify_batched_proof",
         level = "debug",
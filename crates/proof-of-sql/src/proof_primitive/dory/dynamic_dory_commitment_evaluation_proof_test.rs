@@ -1,7 +1,11 @@
 use super::{
-    test_rng, DoryScalar, DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
+    test_rng, DoryScalar, DynamicDoryCommitment, DynamicDoryEvaluationProof, ProverSetup,
+    PublicParameters, VerifierSetup,
+};
+use crate::base::{
+    commitment::{commitment_evaluation_proof_test::*, Commitment, CommitmentEvaluationProof},
+    math::log2_up,
 };
-use crate::base::commitment::{commitment_evaluation_proof_test::*, CommitmentEvaluationProof};
 use ark_std::UniformRand;
 use merlin::Transcript;
 
@@ -75,3 +79,29 @@ fn we_can_serialize_and_deserialize_dory_evaluation_proofs() {
     let decoded: DynamicDoryEvaluationProof = postcard::from_bytes(&encoded).unwrap();
     assert_eq!(decoded, proof);
 }
+
+#[test]
+fn we_can_open_mles_exactly_as_long_as_the_number_of_generators_of_the_setup() {
+    for max_nu in 2..=4 {
+        let public_parameters = PublicParameters::test_rand(max_nu, &mut test_rng());
+        let prover_setup = ProverSetup::from(&public_parameters);
+        let verifier_setup = VerifierSetup::from(&public_parameters);
+        let num_generators = DynamicDoryCommitment::num_generators(&&prover_setup).unwrap();
+        assert_eq!(num_generators, 1 << (2 * max_nu - 1));
+        test_random_commitment_evaluation_proof::<DynamicDoryEvaluationProof>(
+            num_generators,
+            0,
+            &&prover_setup,
+            &&verifier_setup,
+        );
+
+        // One more entry needs another variable, which the setup cannot open
+        let a = vec![DoryScalar::from(1u64); num_generators + 1];
+        let b_point = vec![DoryScalar::from(2u64); log2_up(num_generators + 1)];
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        assert_eq!(
+            DynamicDoryEvaluationProof::new(&mut transcript, &a, &b_point, 0, &&prover_setup),
+            DynamicDoryEvaluationProof::default()
+        );
+    }
+}
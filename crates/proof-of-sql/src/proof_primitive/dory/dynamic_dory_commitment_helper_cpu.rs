@@ -87,6 +87,12 @@ fn compute_dory_commitment(
             compute_dory_commitment_impl(column, offset, setup)
         }
         CommittableColumn::Boolean(column) => compute_dory_commitment_impl(column, offset, setup),
+        CommittableColumn::BooleanBitmap(bitmap) => {
+            compute_dory_commitment_impl(&bitmap.to_vec(), offset, setup)
+        }
+        CommittableColumn::DeltaEncoded(deltas) => {
+            compute_dory_commitment_impl(&deltas.to_vec(), offset, setup)
+        }
         CommittableColumn::TimestampTZ(_, _, column) => {
             compute_dory_commitment_impl(column, offset, setup)
         }
@@ -1,13 +1,19 @@
 use crate::{
     base::{
-        commitment::CommittableColumn,
+        commitment::{
+            assert_constant_commitments_match_materialized,
+            assert_signed_commitments_match_scalars, CommittableColumn,
+        },
+        database::{BooleanBitmap, DeltaEncodedColumn},
         math::decimal::Precision,
         posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
     },
     proof_primitive::dory::{
-        compute_dynamic_dory_commitments, test_rng, ProverSetup, PublicParameters, F, GT,
+        compute_dynamic_dory_commitments, test_rng, DynamicDoryCommitment, ProverSetup,
+        PublicParameters, F, GT,
     },
 };
+use alloc::vec::Vec;
 use ark_ec::pairing::Pairing;
 use num_traits::Zero;
 
@@ -553,3 +559,96 @@ fn we_can_compute_a_dynamic_dory_commitment_with_mixed_committable_columns_with_
         + Pairing::pairing(Gamma_1[3], Gamma_2[3]) * F::from(18);
     assert_eq!(res[9].0, expected);
 }
+
+#[test]
+fn we_can_compute_a_dynamic_dory_commitment_with_packed_boolean_values() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let setup = ProverSetup::from(&public_parameters);
+    let values: Vec<bool> = (0..70).map(|i| i % 5 < 2).collect();
+    let bitmap = BooleanBitmap::from(values.as_slice());
+    for offset in [0, 3] {
+        let res = compute_dynamic_dory_commitments(
+            &[
+                CommittableColumn::BooleanBitmap(&bitmap),
+                CommittableColumn::BigInt(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        let expected = compute_dynamic_dory_commitments(
+            &[
+                CommittableColumn::Boolean(&values),
+                CommittableColumn::BigInt(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        assert_eq!(res, expected);
+    }
+}
+
+#[test]
+fn we_can_compute_dynamic_dory_commitments_to_negative_integers_as_their_scalars() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    assert_signed_commitments_match_scalars::<DynamicDoryCommitment>(
+        &&prover_setup,
+        &[0, 1, 3, 13],
+    );
+}
+
+#[test]
+fn we_can_compute_dynamic_dory_commitments_to_constant_columns() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    assert_constant_commitments_match_materialized::<DynamicDoryCommitment>(
+        &&prover_setup,
+        &[
+            (0, 0),
+            (0, 5),
+            (1, 0),
+            (1, 1),
+            (2, 0),
+            (3, 1),
+            (4, 0),
+            (9, 2),
+            (17, 5),
+            (64, 0),
+            (70, 13),
+        ],
+    );
+}
+
+#[test]
+fn we_can_compute_a_dynamic_dory_commitment_with_delta_encoded_values() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let setup = ProverSetup::from(&public_parameters);
+    // Smooth data with the first value far from zero, along with the extremes of i64
+    let values: Vec<i64> = (0..70)
+        .map(|i| 1_700_000_000_000 + 1_000 * i)
+        .chain([i64::MIN, -1, i64::MAX - 1, 0])
+        .collect();
+    let deltas = DeltaEncodedColumn::try_from_values(&values[..70]).unwrap();
+    let extremes = DeltaEncodedColumn::try_from_values(&values[70..]).unwrap();
+    for offset in [0, 3] {
+        let res = compute_dynamic_dory_commitments(
+            &[
+                CommittableColumn::DeltaEncoded(&deltas),
+                CommittableColumn::DeltaEncoded(&extremes),
+                CommittableColumn::Int(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        let expected = compute_dynamic_dory_commitments(
+            &[
+                CommittableColumn::BigInt(&values[..70]),
+                CommittableColumn::BigInt(&values[70..]),
+                CommittableColumn::Int(&[-1, 2, 3]),
+            ],
+            offset,
+            &setup,
+        );
+        assert_eq!(res, expected);
+    }
+}
@@ -88,6 +88,11 @@ mod extended_dory_inner_product_test;
 mod public_parameters;
 pub use public_parameters::PublicParameters;
 
+#[cfg(feature = "std")]
+mod prover_setup_file;
+#[cfg(feature = "std")]
+pub use prover_setup_file::ProverSetupFile;
+
 mod eval_vmv_re;
 pub(crate) use eval_vmv_re::{eval_vmv_re_prove, eval_vmv_re_verify};
 
@@ -103,7 +108,9 @@ pub(crate) use vmv_state::{VMVProverState, VMVVerifierState};
 mod vmv_state_test;
 
 mod dory_public_setup;
-pub use dory_public_setup::{DoryProverPublicSetup, DoryVerifierPublicSetup};
+pub use dory_public_setup::{DoryProverPublicSetup, DoryVerifierPublicSetup, SharedDorySetup};
+#[cfg(test)]
+mod dory_public_setup_test;
 
 mod dory_commitment;
 #[cfg(test)]
@@ -121,6 +128,11 @@ use dory_commitment_helper_gpu::compute_dory_commitments;
 #[cfg(test)]
 mod dory_compute_commitments_test;
 
+mod constant_commitment_helper;
+use constant_commitment_helper::{
+    compute_dory_constant_commitment, compute_dynamic_dory_constant_commitment,
+};
+
 mod dory_vmv_helper;
 use dory_vmv_helper::{
     compute_L_R_vec, compute_T_vec_prime, compute_l_r_tensors, compute_nu, compute_v_vec,
@@ -137,15 +137,15 @@ pub fn modify_commits(
 ///
 /// # Arguments
 ///
-/// * `column` - A reference to the committable column to be packed.
+/// * `column` - An iterator over the values of the committable column to be packed.
 /// * `packed_scalars` - A mutable reference to the array where the packed scalars will be stored.
 /// * `current_bit_table_sum` - The current sum of the bit table up to the current sub commit.
 /// * `offset` - The offset to the data.
 /// * `current_byte_size` - The current byte size of the column.
 /// * `bit_table_sum_in_bytes` - The full bit table size in bytes.
 /// * `num_columns` - The number of columns in a matrix commitment.
-fn pack_bit<const LEN: usize, T: OffsetToBytes<LEN>>(
-    column: &[T],
+fn pack_bit<'a, const LEN: usize, T: OffsetToBytes<LEN> + 'a>(
+    column: impl IntoIterator<Item = &'a T>,
     packed_scalars: &mut [u8],
     current_bit_table_sum: usize,
     offset: usize,
@@ -154,7 +154,7 @@ fn pack_bit<const LEN: usize, T: OffsetToBytes<LEN>>(
     num_columns: usize,
 ) {
     let byte_offset = current_bit_table_sum / BYTE_SIZE;
-    column.iter().enumerate().for_each(|(i, value)| {
+    column.into_iter().enumerate().for_each(|(i, value)| {
         let index = i + offset;
         let row_offset = (index % num_columns) * bit_table_sum_in_bytes;
         let col_offset = current_byte_size * (index / num_columns);
@@ -369,7 +369,7 @@ pub fn bit_table_and_scalars_for_packed_msm(
         .for_each(|(i, column)| match column {
             CommittableColumn::Uint8(column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
@@ -380,7 +380,7 @@ pub fn bit_table_and_scalars_for_packed_msm(
             }
             CommittableColumn::TinyInt(column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
@@ -391,7 +391,7 @@ pub fn bit_table_and_scalars_for_packed_msm(
             }
             CommittableColumn::SmallInt(column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
@@ -402,7 +402,7 @@ pub fn bit_table_and_scalars_for_packed_msm(
             }
             CommittableColumn::Int(column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
@@ -413,7 +413,7 @@ pub fn bit_table_and_scalars_for_packed_msm(
             }
             CommittableColumn::BigInt(column) | CommittableColumn::TimestampTZ(_, _, column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
@@ -424,7 +424,7 @@ pub fn bit_table_and_scalars_for_packed_msm(
             }
             CommittableColumn::Int128(column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
@@ -435,7 +435,29 @@ pub fn bit_table_and_scalars_for_packed_msm(
             }
             CommittableColumn::Boolean(column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
+                    &mut packed_scalars,
+                    cumulative_bit_sum_table[i],
+                    offset,
+                    committable_columns[i].column_type().byte_size(),
+                    bit_table_full_sum_in_bytes,
+                    num_matrix_commitment_columns,
+                );
+            }
+            CommittableColumn::DeltaEncoded(deltas) => {
+                pack_bit(
+                    deltas.to_vec().iter(),
+                    &mut packed_scalars,
+                    cumulative_bit_sum_table[i],
+                    offset,
+                    committable_columns[i].column_type().byte_size(),
+                    bit_table_full_sum_in_bytes,
+                    num_matrix_commitment_columns,
+                );
+            }
+            CommittableColumn::BooleanBitmap(bitmap) => {
+                pack_bit(
+                    bitmap.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
@@ -449,7 +471,7 @@ pub fn bit_table_and_scalars_for_packed_msm(
             | CommittableColumn::VarChar(column)
             | CommittableColumn::VarBinary(column) => {
                 pack_bit(
-                    column,
+                    column.iter(),
                     &mut packed_scalars,
                     cumulative_bit_sum_table[i],
                     offset,
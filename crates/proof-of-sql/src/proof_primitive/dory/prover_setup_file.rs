@@ -0,0 +1,706 @@
+//! A file format for the prover setup which can be memory-mapped instead of deserialized.
+//!
+//! Deserializing and validating [`PublicParameters`] dominates the startup time of the prover for
+//! large `max_nu`. A prover setup file instead stores `Gamma_1` and `Gamma_2` as fixed-stride
+//! records with the in-memory layout of [`G1Affine`] and [`G2Affine`], so the generators can be
+//! used directly from the mapped file.
+//!
+//! The file consists of
+//! - the magic bytes `PSQLDPS1` and a marker for the byte order of the writer,
+//! - `max_nu`,
+//! - the layouts of the `G1Affine` and `G2Affine` records,
+//! - the limbs of one in the base field, which identify the representation of field elements,
+//! - `H_1`, `H_2` and `Gamma_2_fin`, serialized uncompressed,
+//! - the `Gamma_1` records and the `Gamma_2` records, each starting at a multiple of 64 bytes.
+//!
+//! All integers are `u64`s in the byte order of the writer.
+//! Padding within and between records is zero.
+//!
+//! The points aren't validated when a file is opened, so a prover setup file must come from a
+//! trusted source, such as [`PublicParameters::save_to_prover_setup_file`] on validated
+//! public parameters.
+use super::{G1Affine, G2Affine, ProverSetup, PublicParameters, VerifierSetup};
+use alloc::vec::Vec;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use core::mem::{align_of, offset_of, size_of};
+use memmap2::Mmap;
+use std::{
+    fs::File,
+    io::{BufWriter, Error, ErrorKind, Read, Write},
+    path::Path,
+};
+
+/// The magic bytes at the start of a prover setup file
+const MAGIC: [u8; 8] = *b"PSQLDPS1";
+/// Written in the byte order of the writer, to detect files from platforms of another byte order
+const BYTE_ORDER_MARKER: u64 = 0x0102_0304_0506_0708;
+/// The alignment of the start of the `Gamma_1` and `Gamma_2` records within the file
+const RECORD_ALIGNMENT: usize = 64;
+
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn u64_to_usize(value: u64) -> std::io::Result<usize> {
+    usize::try_from(value).map_err(|_| invalid_data("value in prover setup file is too large"))
+}
+
+fn usize_to_u64(value: usize) -> u64 {
+    u64::try_from(value).expect("usize always fits in u64 on supported platforms")
+}
+
+fn round_up_to_record_alignment(offset: usize) -> usize {
+    offset.div_ceil(RECORD_ALIGNMENT) * RECORD_ALIGNMENT
+}
+
+/// The limbs of one in the base field, in the representation of field elements in memory
+fn base_field_one_limbs() -> [u64; 6] {
+    <ark_bls12_381::Fq as Field>::ONE.0 .0
+}
+
+/// Split the first `len` bytes off `bytes`
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> std::io::Result<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(invalid_data("prover setup file is truncated"));
+    }
+    let (taken, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(taken)
+}
+
+/// Split the first `n` words off `bytes`
+fn take_words(bytes: &mut &[u8], n: usize) -> std::io::Result<Vec<u64>> {
+    Ok(take(bytes, n * 8)?
+        .chunks_exact(8)
+        .map(|word| u64::from_ne_bytes(word.try_into().unwrap()))
+        .collect())
+}
+
+/// The layout of the records of the points of a curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RecordLayout {
+    /// The size of a record, i.e. the stride of the points
+    size: usize,
+    /// The alignment a record needs to be used in place
+    align: usize,
+    /// The size of a coordinate
+    coordinate_size: usize,
+    /// The offset of the `x` coordinate within a record
+    x: usize,
+    /// The offset of the `y` coordinate within a record
+    y: usize,
+    /// The offset of the `infinity` flag within a record
+    infinity: usize,
+}
+
+impl RecordLayout {
+    const NUM_WORDS: usize = 6;
+
+    /// The in-memory layout of `Affine<P>`
+    fn native<P: SWCurveConfig>() -> Self {
+        Self {
+            size: size_of::<Affine<P>>(),
+            align: align_of::<Affine<P>>(),
+            coordinate_size: size_of::<P::BaseField>(),
+            x: offset_of!(Affine<P>, x),
+            y: offset_of!(Affine<P>, y),
+            infinity: offset_of!(Affine<P>, infinity),
+        }
+    }
+
+    fn to_words(self) -> [u64; Self::NUM_WORDS] {
+        [
+            self.size,
+            self.align,
+            self.coordinate_size,
+            self.x,
+            self.y,
+            self.infinity,
+        ]
+        .map(usize_to_u64)
+    }
+
+    /// Read a layout and check that it describes records of coordinates of type `P::BaseField`
+    fn try_from_words<P: SWCurveConfig>(words: [u64; Self::NUM_WORDS]) -> std::io::Result<Self> {
+        let [size, align, coordinate_size, x, y, infinity] = words;
+        let layout = Self {
+            size: u64_to_usize(size)?,
+            align: u64_to_usize(align)?,
+            coordinate_size: u64_to_usize(coordinate_size)?,
+            x: u64_to_usize(x)?,
+            y: u64_to_usize(y)?,
+            infinity: u64_to_usize(infinity)?,
+        };
+        let fits = |offset: usize, len: usize| {
+            offset
+                .checked_add(len)
+                .is_some_and(|end| end <= layout.size)
+        };
+        if layout.coordinate_size != size_of::<P::BaseField>()
+            || !fits(layout.x, layout.coordinate_size)
+            || !fits(layout.y, layout.coordinate_size)
+            || !fits(layout.infinity, 1)
+        {
+            return Err(invalid_data("invalid record layout in prover setup file"));
+        }
+        Ok(layout)
+    }
+
+    /// Write the record of `point` into `record`, which must be zeroed
+    fn write_record<P: SWCurveConfig>(self, point: &Affine<P>, record: &mut [u8]) {
+        assert_eq!(record.len(), self.size);
+        assert_eq!(
+            size_of::<P::BaseField>(),
+            u64_to_usize(P::BaseField::extension_degree()).unwrap()
+                * size_of::<<P::BaseField as Field>::BasePrimeField>(),
+            "coordinates must not contain padding"
+        );
+        // SAFETY: the layout was checked to fit the coordinates and the flag into the record, and
+        // coordinates consist of limbs without padding, so every byte written is initialized.
+        unsafe {
+            let base = record.as_mut_ptr();
+            base.add(self.x)
+                .cast::<P::BaseField>()
+                .write_unaligned(point.x);
+            base.add(self.y)
+                .cast::<P::BaseField>()
+                .write_unaligned(point.y);
+        }
+        record[self.infinity] = u8::from(point.infinity);
+    }
+
+    /// Read the point of `record`, which must have been checked with `check_records`
+    fn read_record<P: SWCurveConfig>(self, record: &[u8]) -> Affine<P> {
+        assert_eq!(record.len(), self.size);
+        // SAFETY: the layout was checked to fit the coordinates into the record, and any bytes are
+        // a valid coordinate, since coordinates consist of integer limbs.
+        let (x, y) = unsafe {
+            let base = record.as_ptr();
+            (
+                base.add(self.x).cast::<P::BaseField>().read_unaligned(),
+                base.add(self.y).cast::<P::BaseField>().read_unaligned(),
+            )
+        };
+        Affine {
+            x,
+            y,
+            infinity: record[self.infinity] == 1,
+        }
+    }
+
+    /// Check that the `infinity` flags of all records are valid `bool`s
+    fn check_records(self, records: &[u8]) -> std::io::Result<()> {
+        if records
+            .chunks_exact(self.size)
+            .all(|record| record[self.infinity] <= 1)
+        {
+            Ok(())
+        } else {
+            Err(invalid_data("invalid point in prover setup file"))
+        }
+    }
+}
+
+/// The header of a prover setup file, i.e. everything except the records
+struct Header {
+    max_nu: usize,
+    g1_layout: RecordLayout,
+    g2_layout: RecordLayout,
+    H_1: G1Affine,
+    H_2: G2Affine,
+    Gamma_2_fin: G2Affine,
+}
+
+impl Header {
+    fn write(&self, bytes: &mut Vec<u8>) -> std::io::Result<()> {
+        bytes.extend_from_slice(&MAGIC);
+        let words = [BYTE_ORDER_MARKER, usize_to_u64(self.max_nu)]
+            .into_iter()
+            .chain(self.g1_layout.to_words())
+            .chain(self.g2_layout.to_words())
+            .chain(base_field_one_limbs());
+        for word in words {
+            bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+        let to_io_error = |e| Error::new(ErrorKind::Other, format!("{e}"));
+        self.H_1
+            .serialize_with_mode(&mut *bytes, Compress::No)
+            .map_err(to_io_error)?;
+        self.H_2
+            .serialize_with_mode(&mut *bytes, Compress::No)
+            .map_err(to_io_error)?;
+        self.Gamma_2_fin
+            .serialize_with_mode(&mut *bytes, Compress::No)
+            .map_err(to_io_error)?;
+        Ok(())
+    }
+
+    /// Read the header at the start of `bytes` and return it with the number of bytes read
+    fn read(bytes: &[u8]) -> std::io::Result<(Self, usize)> {
+        let mut remaining = bytes;
+        if take(&mut remaining, MAGIC.len())? != MAGIC {
+            return Err(invalid_data("not a prover setup file"));
+        }
+        if take_words(&mut remaining, 1)?[0] != BYTE_ORDER_MARKER {
+            return Err(invalid_data(
+                "prover setup file was written on a platform with another byte order",
+            ));
+        }
+        let max_nu = u64_to_usize(take_words(&mut remaining, 1)?[0])?;
+        if max_nu >= usize::BITS as usize {
+            return Err(invalid_data("max_nu in prover setup file is too large"));
+        }
+        let g1_layout = RecordLayout::try_from_words::<ark_bls12_381::g1::Config>(
+            take_words(&mut remaining, RecordLayout::NUM_WORDS)?
+                .try_into()
+                .unwrap(),
+        )?;
+        let g2_layout = RecordLayout::try_from_words::<ark_bls12_381::g2::Config>(
+            take_words(&mut remaining, RecordLayout::NUM_WORDS)?
+                .try_into()
+                .unwrap(),
+        )?;
+        if take_words(&mut remaining, 6)? != base_field_one_limbs() {
+            return Err(invalid_data(
+                "prover setup file was written with another representation of field elements",
+            ));
+        }
+        let to_io_error = |e| Error::new(ErrorKind::InvalidData, format!("{e}"));
+        let mut reader = remaining;
+        let H_1 = G1Affine::deserialize_with_mode(&mut reader, Compress::No, Validate::Yes)
+            .map_err(to_io_error)?;
+        let H_2 = G2Affine::deserialize_with_mode(&mut reader, Compress::No, Validate::Yes)
+            .map_err(to_io_error)?;
+        let Gamma_2_fin = G2Affine::deserialize_with_mode(&mut reader, Compress::No, Validate::Yes)
+            .map_err(to_io_error)?;
+        Ok((
+            Self {
+                max_nu,
+                g1_layout,
+                g2_layout,
+                H_1,
+                H_2,
+                Gamma_2_fin,
+            },
+            bytes.len() - reader.len(),
+        ))
+    }
+
+    /// The offsets of the `Gamma_1` records, the `Gamma_2` records and the end of the file
+    fn record_offsets(&self, header_len: usize) -> Option<(usize, usize, usize)> {
+        let num_points = 1_usize << self.max_nu;
+        let gamma_1_offset = round_up_to_record_alignment(header_len);
+        let gamma_2_offset = round_up_to_record_alignment(
+            gamma_1_offset.checked_add(num_points.checked_mul(self.g1_layout.size)?)?,
+        );
+        let end = gamma_2_offset.checked_add(num_points.checked_mul(self.g2_layout.size)?)?;
+        Some((gamma_1_offset, gamma_2_offset, end))
+    }
+}
+
+/// The storage of the `Gamma_1` and `Gamma_2` points of a [`ProverSetupFile`]
+enum Points {
+    /// The points are used in place from the mapped file.
+    Mapped {
+        mmap: Mmap,
+        gamma_1_offset: usize,
+        gamma_2_offset: usize,
+    },
+    /// The points were copied out of the file, because its layout or alignment didn't allow
+    /// using them in place.
+    Copied {
+        Gamma_1: Vec<G1Affine>,
+        Gamma_2: Vec<G2Affine>,
+    },
+}
+
+/// A prover setup file, opened with [`ProverSetupFile::open`]
+///
+/// The file is memory-mapped and its points are used in place whenever it was written on a
+/// platform with the same layout of points. Otherwise the points are copied into memory.
+pub struct ProverSetupFile {
+    points: Points,
+    H_1: G1Affine,
+    H_2: G2Affine,
+    Gamma_2_fin: G2Affine,
+    max_nu: usize,
+}
+
+impl ProverSetupFile {
+    /// Map the prover setup file at `path` read-only
+    ///
+    /// The file must not be modified while it is open.
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only and the file must not be modified while it is open.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let (header, header_len) = Header::read(&mmap)?;
+        let (gamma_1_offset, gamma_2_offset, end) = header
+            .record_offsets(header_len)
+            .ok_or_else(|| invalid_data("max_nu in prover setup file is too large"))?;
+        if mmap.len() != end {
+            return Err(invalid_data("prover setup file has the wrong length"));
+        }
+        let gamma_1_records = &mmap[gamma_1_offset..gamma_2_offset];
+        let gamma_2_records = &mmap[gamma_2_offset..];
+        header.g1_layout.check_records(gamma_1_records)?;
+        header.g2_layout.check_records(gamma_2_records)?;
+
+        let can_use_in_place = |layout: RecordLayout, native: RecordLayout, records: &[u8]| {
+            layout == native && records.as_ptr().align_offset(native.align) == 0
+        };
+        let points = if can_use_in_place(
+            header.g1_layout,
+            RecordLayout::native::<ark_bls12_381::g1::Config>(),
+            gamma_1_records,
+        ) && can_use_in_place(
+            header.g2_layout,
+            RecordLayout::native::<ark_bls12_381::g2::Config>(),
+            gamma_2_records,
+        ) {
+            Points::Mapped {
+                mmap,
+                gamma_1_offset,
+                gamma_2_offset,
+            }
+        } else {
+            let Gamma_1 = gamma_1_records
+                .chunks_exact(header.g1_layout.size)
+                .map(|record| header.g1_layout.read_record(record))
+                .collect();
+            let Gamma_2 = gamma_2_records
+                .chunks_exact(header.g2_layout.size)
+                .map(|record| header.g2_layout.read_record(record))
+                .collect();
+            Points::Copied { Gamma_1, Gamma_2 }
+        };
+        Ok(Self {
+            points,
+            H_1: header.H_1,
+            H_2: header.H_2,
+            Gamma_2_fin: header.Gamma_2_fin,
+            max_nu: header.max_nu,
+        })
+    }
+
+    /// Check whether the file at `path` starts with the magic bytes of a prover setup file
+    pub fn is_prover_setup_file(path: &Path) -> std::io::Result<bool> {
+        let mut magic = [0; MAGIC.len()];
+        match File::open(path)?.read_exact(&mut magic) {
+            Ok(()) => Ok(magic == MAGIC),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether the points are used in place from the mapped file rather than copied
+    #[must_use]
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.points, Points::Mapped { .. })
+    }
+
+    /// `Gamma_1` of the public parameters the file was written from
+    fn Gamma_1(&self) -> &[G1Affine] {
+        match &self.points {
+            Points::Mapped {
+                mmap,
+                gamma_1_offset,
+                ..
+            } => {
+                // SAFETY: `open` checked that the records have the layout and alignment of
+                // `G1Affine`, that they are in bounds and that their `infinity` flags are valid.
+                unsafe {
+                    core::slice::from_raw_parts(
+                        mmap.as_ptr().add(*gamma_1_offset).cast::<G1Affine>(),
+                        1 << self.max_nu,
+                    )
+                }
+            }
+            Points::Copied { Gamma_1, .. } => Gamma_1,
+        }
+    }
+
+    /// `Gamma_2` of the public parameters the file was written from
+    fn Gamma_2(&self) -> &[G2Affine] {
+        match &self.points {
+            Points::Mapped {
+                mmap,
+                gamma_2_offset,
+                ..
+            } => {
+                // SAFETY: `open` checked that the records have the layout and alignment of
+                // `G2Affine`, that they are in bounds and that their `infinity` flags are valid.
+                unsafe {
+                    core::slice::from_raw_parts(
+                        mmap.as_ptr().add(*gamma_2_offset).cast::<G2Affine>(),
+                        1 << self.max_nu,
+                    )
+                }
+            }
+            Points::Copied { Gamma_2, .. } => Gamma_2,
+        }
+    }
+
+    /// Create the prover setup of this file
+    #[must_use]
+    pub fn prover_setup(&self) -> ProverSetup<'_> {
+        ProverSetup::new(
+            self.Gamma_1(),
+            self.Gamma_2(),
+            self.H_1,
+            self.H_2,
+            self.Gamma_2_fin,
+            self.max_nu,
+        )
+    }
+
+    /// Create the prover setup of this file with a precomputed blitzar handle
+    #[must_use]
+    #[cfg(feature = "blitzar")]
+    pub fn prover_setup_with_blitzar_handle(
+        &self,
+        blitzar_handle: blitzar::compute::MsmHandle<
+            blitzar::compute::ElementP2<ark_bls12_381::g1::Config>,
+        >,
+    ) -> ProverSetup<'_> {
+        ProverSetup::new_with_blitzar_handle(
+            self.Gamma_1(),
+            self.Gamma_2(),
+            self.H_1,
+            self.H_2,
+            self.Gamma_2_fin,
+            self.max_nu,
+            blitzar_handle,
+        )
+    }
+
+    /// Create the verifier setup matching the prover setup of this file
+    #[must_use]
+    pub fn verifier_setup(&self) -> VerifierSetup {
+        VerifierSetup::new(
+            self.Gamma_1(),
+            self.Gamma_2(),
+            self.H_1,
+            self.H_2,
+            self.Gamma_2_fin,
+            self.max_nu,
+        )
+    }
+}
+
+impl ProverSetup<'static> {
+    /// Map the prover setup file at `path` read-only and create a prover setup from it
+    ///
+    /// The mapping is kept for the rest of the process, so this is meant for long-running provers
+    /// which load their setup once. Use [`ProverSetupFile::open`] to control the lifetime of the
+    /// mapping instead.
+    /// The file must not be modified while it is mapped.
+    pub fn open_mmap(path: &Path) -> std::io::Result<Self> {
+        let file: &'static ProverSetupFile = Box::leak(Box::new(ProverSetupFile::open(path)?));
+        Ok(file.prover_setup())
+    }
+}
+
+impl PublicParameters {
+    /// Save the public parameters as a prover setup file, which can be opened with
+    /// [`ProverSetupFile::open`] or [`ProverSetup::open_mmap`]
+    ///
+    /// # Panics
+    /// Panics if the size of the file would overflow `usize`, which can't happen for public
+    /// parameters which fit into memory.
+    pub fn save_to_prover_setup_file(&self, path: &Path) -> std::io::Result<()> {
+        let header = Header {
+            max_nu: self.max_nu,
+            g1_layout: RecordLayout::native::<ark_bls12_381::g1::Config>(),
+            g2_layout: RecordLayout::native::<ark_bls12_381::g2::Config>(),
+            H_1: self.H_1,
+            H_2: self.H_2,
+            Gamma_2_fin: self.Gamma_2_fin,
+        };
+        let mut header_bytes = Vec::new();
+        header.write(&mut header_bytes)?;
+        let (gamma_1_offset, gamma_2_offset, _) = header
+            .record_offsets(header_bytes.len())
+            .expect("the public parameters fit into memory");
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&header_bytes)?;
+        writer.write_all(&vec![0; gamma_1_offset - header_bytes.len()])?;
+        let mut written = gamma_1_offset;
+        let mut record = vec![0; header.g1_layout.size];
+        for point in &self.Gamma_1 {
+            record.fill(0);
+            header.g1_layout.write_record(point, &mut record);
+            writer.write_all(&record)?;
+            written += record.len();
+        }
+        writer.write_all(&vec![0; gamma_2_offset - written])?;
+        let mut record = vec![0; header.g2_layout.size];
+        for point in &self.Gamma_2 {
+            record.fill(0);
+            header.g2_layout.write_record(point, &mut record);
+            writer.write_all(&record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_std::rand::thread_rng;
+
+    fn assert_setups_are_equal(file: &ProverSetupFile, public_parameters: &PublicParameters) {
+        let expected = ProverSetup::from(public_parameters);
+        let actual = file.prover_setup();
+        assert_eq!(actual.Gamma_1, expected.Gamma_1);
+        assert_eq!(actual.Gamma_2, expected.Gamma_2);
+        assert_eq!(actual.H_1, expected.H_1);
+        assert_eq!(actual.H_2, expected.H_2);
+        assert_eq!(actual.Gamma_2_fin, expected.Gamma_2_fin);
+        assert_eq!(actual.max_nu, expected.max_nu);
+        assert_eq!(
+            file.verifier_setup(),
+            VerifierSetup::from(public_parameters)
+        );
+    }
+
+    #[test]
+    fn we_can_write_and_open_a_prover_setup_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        for max_nu in [0, 1, 3] {
+            let public_parameters = PublicParameters::test_rand(max_nu, &mut thread_rng());
+            let path = dir.path().join(format!("prover_setup_{max_nu}.bin"));
+            public_parameters.save_to_prover_setup_file(&path).unwrap();
+            assert!(ProverSetupFile::is_prover_setup_file(&path).unwrap());
+
+            let file = ProverSetupFile::open(&path).unwrap();
+            assert!(file.is_mapped());
+            assert_setups_are_equal(&file, &public_parameters);
+
+            let prover_setup = ProverSetup::open_mmap(&path).unwrap();
+            assert_eq!(
+                prover_setup.Gamma_1.last().unwrap(),
+                &&public_parameters.Gamma_1[..]
+            );
+        }
+    }
+
+    #[test]
+    fn we_copy_the_points_of_a_prover_setup_file_with_another_layout() {
+        let public_parameters = PublicParameters::test_rand(2, &mut thread_rng());
+        // Move the flag to the end of a record, which is one word longer
+        let native = RecordLayout::native::<ark_bls12_381::g1::Config>();
+        let g1_layout = RecordLayout {
+            size: native.size + 8,
+            infinity: native.size + 7,
+            ..native
+        };
+        let header = Header {
+            max_nu: 2,
+            g1_layout,
+            g2_layout: RecordLayout::native::<ark_bls12_381::g2::Config>(),
+            H_1: public_parameters.H_1,
+            H_2: public_parameters.H_2,
+            Gamma_2_fin: public_parameters.Gamma_2_fin,
+        };
+        let mut bytes = Vec::new();
+        header.write(&mut bytes).unwrap();
+        let (gamma_1_offset, gamma_2_offset, end) = header.record_offsets(bytes.len()).unwrap();
+        bytes.resize(end, 0);
+        for (record, point) in bytes[gamma_1_offset..gamma_2_offset]
+            .chunks_exact_mut(g1_layout.size)
+            .zip(&public_parameters.Gamma_1)
+        {
+            g1_layout.write_record(point, record);
+        }
+        for (record, point) in bytes[gamma_2_offset..]
+            .chunks_exact_mut(header.g2_layout.size)
+            .zip(&public_parameters.Gamma_2)
+        {
+            header.g2_layout.write_record(point, record);
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prover_setup.bin");
+        std::fs::write(&path, bytes).unwrap();
+
+        let file = ProverSetupFile::open(&path).unwrap();
+        assert!(!file.is_mapped());
+        assert_setups_are_equal(&file, &public_parameters);
+    }
+
+    #[test]
+    fn we_cannot_open_an_invalid_prover_setup_file() {
+        let public_parameters = PublicParameters::test_rand(2, &mut thread_rng());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prover_setup.bin");
+        public_parameters.save_to_prover_setup_file(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+
+        let public_parameters_path = dir.path().join("public_parameters.bin");
+        public_parameters
+            .save_to_file(&public_parameters_path)
+            .unwrap();
+        assert!(!ProverSetupFile::is_prover_setup_file(&public_parameters_path).unwrap());
+        assert!(ProverSetupFile::open(&public_parameters_path).is_err());
+
+        let mut truncated = bytes.clone();
+        truncated.pop();
+        std::fs::write(&path, truncated).unwrap();
+        assert!(ProverSetupFile::open(&path).is_err());
+
+        let mut invalid_flag = bytes.clone();
+        let last = invalid_flag.len() - RecordLayout::native::<ark_bls12_381::g2::Config>().size;
+        invalid_flag[last + RecordLayout::native::<ark_bls12_381::g2::Config>().infinity] = 2;
+        std::fs::write(&path, invalid_flag).unwrap();
+        assert!(ProverSetupFile::open(&path).is_err());
+
+        let mut other_byte_order = bytes;
+        other_byte_order[8..16].reverse();
+        std::fs::write(&path, other_byte_order).unwrap();
+        assert!(ProverSetupFile::open(&path).is_err());
+    }
+
+    /// Prints the time to load public parameters of `nu` and create a `ProverSetup` from them,
+    /// and the time to open a prover setup file of the same parameters
+    fn print_load_times(nu: usize) {
+        let dir = tempfile::tempdir().unwrap();
+        let public_parameters = PublicParameters::test_rand(nu, &mut thread_rng());
+        let public_parameters_path = dir.path().join("public_params.bin");
+        let prover_setup_path = dir.path().join("prover_setup.bin");
+        public_parameters
+            .save_to_file(&public_parameters_path)
+            .unwrap();
+        public_parameters
+            .save_to_prover_setup_file(&prover_setup_path)
+            .unwrap();
+
+        let start_time = std::time::Instant::now();
+        let loaded = PublicParameters::load_from_file(&public_parameters_path).unwrap();
+        let _prover_setup = ProverSetup::from(&loaded);
+        println!("load_from_file for nu = {nu}: {:?}", start_time.elapsed());
+
+        let start_time = std::time::Instant::now();
+        let _prover_setup = ProverSetup::open_mmap(&prover_setup_path).unwrap();
+        println!("open_mmap for nu = {nu}: {:?}", start_time.elapsed());
+    }
+
+    // Run with `--nocapture` to see the log.
+    #[test]
+    fn we_can_time_opening_small_prover_setup_files() {
+        for nu in [1, 2, 4] {
+            print_load_times(nu);
+        }
+    }
+
+    // The startup time at `nu = 14`, the size the memory-mapped format is for. Run with
+    // `--ignored --nocapture` to see the log.
+    #[test]
+    #[ignore = "This test generates and writes public parameters of nu = 14"]
+    fn we_can_time_opening_a_large_prover_setup_file() {
+        print_load_times(14);
+    }
+}
@@ -83,12 +83,32 @@ impl<'a> ProverSetup<'a> {
             blitzar::compute::ElementP2<ark_bls12_381::g1::Config>,
         >,
     ) -> Self {
-        let Gamma_1: &'a [G1Affine] = &public_parameters.Gamma_1;
-        let Gamma_2: &'a [G2Affine] = &public_parameters.Gamma_2;
-        let H_1 = public_parameters.H_1;
-        let H_2 = public_parameters.H_2;
-        let Gamma_2_fin = public_parameters.Gamma_2_fin;
-        let max_nu = public_parameters.max_nu;
+        Self::new_with_blitzar_handle(
+            &public_parameters.Gamma_1,
+            &public_parameters.Gamma_2,
+            public_parameters.H_1,
+            public_parameters.H_2,
+            public_parameters.Gamma_2_fin,
+            public_parameters.max_nu,
+            blitzar_handle,
+        )
+    }
+
+    /// Create a new `ProverSetup` from the public parameters and a blitzar handle for `Gamma_1`.
+    /// # Panics
+    /// Panics if the length of `Gamma_1` or `Gamma_2` is not equal to `2^max_nu`.
+    #[cfg(feature = "blitzar")]
+    pub(super) fn new_with_blitzar_handle(
+        Gamma_1: &'a [G1Affine],
+        Gamma_2: &'a [G2Affine],
+        H_1: G1Affine,
+        H_2: G2Affine,
+        Gamma_2_fin: G2Affine,
+        max_nu: usize,
+        blitzar_handle: blitzar::compute::MsmHandle<
+            blitzar::compute::ElementP2<ark_bls12_381::g1::Config>,
+        >,
+    ) -> Self {
         assert_eq!(Gamma_1.len(), 1 << max_nu);
         assert_eq!(Gamma_2.len(), 1 << max_nu);
 
@@ -102,11 +122,28 @@ impl<'a> ProverSetup<'a> {
             H_2,
             Gamma_2_fin,
             max_nu,
-            #[cfg(feature = "blitzar")]
             blitzar_handle,
         }
     }
 
+    /// A digest identifying the public parameters this setup was created from.
+    ///
+    /// `H_1`, `H_2` and `Gamma_2_fin` are sampled independently for every set of public
+    /// parameters, so they identify the setup without hashing all of the generators.
+    pub(super) fn digest(&self) -> [u8; 32] {
+        let mut buf = Vec::new();
+        self.H_1
+            .serialize_compressed(&mut buf)
+            .expect("serializing into a vector can't fail");
+        self.H_2
+            .serialize_compressed(&mut buf)
+            .expect("serializing into a vector can't fail");
+        self.Gamma_2_fin
+            .serialize_compressed(&mut buf)
+            .expect("serializing into a vector can't fail");
+        *blake3::hash(&buf).as_bytes()
+    }
+
     /// Gets the `MSMHandle` for this setup
     #[must_use]
     #[cfg(feature = "blitzar")]
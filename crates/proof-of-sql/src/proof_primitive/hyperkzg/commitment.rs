@@ -1,8 +1,9 @@
 use super::{BNScalar, HyperKZGPublicSetup};
-#[cfg(any(not(feature = "blitzar"), test))]
-use crate::base::if_rayon;
+#[cfg(feature = "blitzar")]
+use crate::base::commitment::with_sequences;
 use crate::base::{
     commitment::{Commitment, CommittableColumn},
+    if_rayon,
     scalar::Scalar,
     slice_ops,
 };
@@ -11,9 +12,10 @@ use ark_bn254::{G1Affine, G1Projective};
 use ark_ec::AffineRepr;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use core::ops::{AddAssign, Mul, Neg, Sub, SubAssign};
-#[cfg(all(feature = "rayon", any(not(feature = "blitzar"), test)))]
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use snafu::Snafu;
 
 /// This is the commitment type used in the hyperkzg proof system.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize, Default)]
@@ -108,7 +110,6 @@ impl Sub for HyperKZGCommitment {
     }
 }
 
-#[cfg(any(not(feature = "blitzar"), test))]
 #[tracing::instrument(
     name = "compute_commitment_generic_impl (cpu)",
     level = "debug",
@@ -129,7 +130,6 @@ fn compute_commitment_generic_impl<T: Into<BNScalar> + Clone + Sync>(
     }
 }
 
-#[cfg(any(not(feature = "blitzar"), test))]
 #[tracing::instrument(name = "compute_commitments_impl (cpu)", level = "debug", skip_all)]
 fn compute_commitments_impl(
     committable_columns: &[crate::base::commitment::CommittableColumn],
@@ -141,6 +141,12 @@ fn compute_commitments_impl(
             CommittableColumn::Boolean(vals) => {
                 compute_commitment_generic_impl(setup, offset, vals)
             }
+            CommittableColumn::BooleanBitmap(bitmap) => {
+                compute_commitment_generic_impl(setup, offset, &bitmap.to_vec())
+            }
+            CommittableColumn::DeltaEncoded(deltas) => {
+                compute_commitment_generic_impl(setup, offset, &deltas.to_vec())
+            }
             CommittableColumn::Uint8(vals) => compute_commitment_generic_impl(setup, offset, vals),
             CommittableColumn::TinyInt(vals) => {
                 compute_commitment_generic_impl(setup, offset, vals)
@@ -163,6 +169,117 @@ fn compute_commitments_impl(
         .collect()
 }
 
+/// The device blitzar computes on, i.e. the first visible device.
+///
+/// Blitzar selects its device when its backend is initialized, so this is the only device
+/// commitments can be pinned to.
+const BLITZAR_DEVICE_ID: u32 = 0;
+
+/// Errors from computing [`HyperKZGCommitment`]s with [`GpuOptions`].
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum GpuOptionsError {
+    /// This error occurs when a batch is assigned to a device commitments cannot be computed on.
+    #[snafu(display("commitments cannot be computed on device {device_id}"))]
+    UnsupportedDevice { device_id: u32 },
+}
+
+/// Options for computing [`HyperKZGCommitment`]s with
+/// [`HyperKZGCommitment::compute_commitments_with_options`].
+///
+/// Commitments can only be computed on the device blitzar selects, i.e. device `0`, and only with
+/// the `blitzar` feature. Blitzar does not let a computation be dispatched to any other device, so
+/// the batches are computed one after another rather than split across several devices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GpuOptions {
+    /// The devices the batches of columns are computed on, assigned in round-robin order.
+    ///
+    /// If this is empty, every batch is computed the same way as
+    /// [`Commitment::compute_commitments`]. Any device other than device `0` with the `blitzar`
+    /// feature is rejected with [`GpuOptionsError::UnsupportedDevice`].
+    pub device_ids: Vec<u32>,
+    /// The maximum number of bytes of column data in a batch.
+    ///
+    /// A column with more data than this is computed in a batch of its own.
+    pub max_batch_bytes: usize,
+}
+
+impl Default for GpuOptions {
+    fn default() -> Self {
+        Self {
+            device_ids: Vec::new(),
+            max_batch_bytes: usize::MAX,
+        }
+    }
+}
+
+/// Split `committable_columns` into consecutive batches of at most `max_batch_bytes` of data each,
+/// except for single columns with more data.
+fn split_into_batches<'a, 'b>(
+    committable_columns: &'a [CommittableColumn<'b>],
+    max_batch_bytes: usize,
+) -> Vec<&'a [CommittableColumn<'b>]> {
+    let mut batches = Vec::new();
+    let mut batch_start = 0;
+    let mut batch_bytes = 0_usize;
+    for (i, column) in committable_columns.iter().enumerate() {
+        let column_bytes = column
+            .len()
+            .saturating_mul(column.column_type().byte_size());
+        if i > batch_start && batch_bytes.saturating_add(column_bytes) > max_batch_bytes {
+            batches.push(&committable_columns[batch_start..i]);
+            batch_start = i;
+            batch_bytes = 0;
+        }
+        batch_bytes = batch_bytes.saturating_add(column_bytes);
+    }
+    if batch_start < committable_columns.len() {
+        batches.push(&committable_columns[batch_start..]);
+    }
+    batches
+}
+
+/// Whether commitments can be computed on the device `device_id`.
+fn is_supported_device(device_id: u32) -> bool {
+    cfg!(feature = "blitzar") && device_id == BLITZAR_DEVICE_ID
+}
+
+impl HyperKZGCommitment {
+    /// Compute the commitments of `committable_columns` like [`Commitment::compute_commitments`],
+    /// split into batches which are computed on the devices given by `options`.
+    ///
+    /// The commitments are returned in the order of the columns, so the result is the same as
+    /// computing all of them at once.
+    ///
+    /// # Errors
+    /// Fails with [`GpuOptionsError::UnsupportedDevice`] before computing anything if any of the
+    /// devices of `options` is not supported, see [`GpuOptions`].
+    #[tracing::instrument(
+        name = "HyperKZGCommitment::compute_commitments_with_options",
+        level = "debug",
+        skip_all
+    )]
+    pub fn compute_commitments_with_options(
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &HyperKZGPublicSetup<'_>,
+        options: &GpuOptions,
+    ) -> Result<Vec<Self>, GpuOptionsError> {
+        if let Some(&device_id) = options
+            .device_ids
+            .iter()
+            .find(|&&device_id| !is_supported_device(device_id))
+        {
+            return UnsupportedDeviceSnafu { device_id }.fail();
+        }
+        Ok(
+            split_into_batches(committable_columns, options.max_batch_bytes)
+                .into_iter()
+                .flat_map(|batch| Self::compute_commitments(batch, offset, setup))
+                .collect(),
+        )
+    }
+}
+
 impl Commitment for HyperKZGCommitment {
     type Scalar = BNScalar;
     type PublicSetup<'a> = HyperKZGPublicSetup<'a>;
@@ -197,27 +314,71 @@ impl Commitment for HyperKZGCommitment {
 
         let mut blitzar_commitments = vec![G1Affine::default(); committable_columns.len()];
 
-        blitzar::compute::compute_bn254_g1_uncompressed_commitments_with_generators(
-            &mut blitzar_commitments,
-            &slice_ops::slice_cast(committable_columns),
-            &setup[offset..offset + max_column_len],
-        );
+        with_sequences(committable_columns, |sequences| {
+            blitzar::compute::compute_bn254_g1_uncompressed_commitments_with_generators(
+                &mut blitzar_commitments,
+                sequences,
+                &setup[offset..offset + max_column_len],
+            );
+        });
 
         slice_ops::slice_cast(&blitzar_commitments)
     }
 
+    fn compute_constant_commitment(
+        value: BNScalar,
+        num_rows: usize,
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) -> Option<Self> {
+        assert!(offset + num_rows <= setup.len());
+        let generators: G1Projective = setup[offset..offset + num_rows].iter().sum();
+        Some(HyperKZGCommitment {
+            commitment: generators * value.0,
+        })
+    }
+
     fn to_transcript_bytes(&self) -> Vec<u8> {
         let mut writer = Vec::with_capacity(self.commitment.compressed_size());
         self.commitment.serialize_compressed(&mut writer).unwrap();
         writer
     }
+
+    /// The commitment is serialized as the 32 byte coordinates of the affine point, so this is exact.
+    fn encoded_size_hint(&self) -> usize {
+        64
+    }
+
+    fn scheme_identifier() -> &'static str {
+        "hyperkzg"
+    }
+
+    fn num_generators(setup: &Self::PublicSetup<'_>) -> Option<usize> {
+        Some(setup.len())
+    }
+
+    /// The first two generators, `g` and `tau * g`, identify the setup regardless of its length.
+    fn setup_digest(setup: &Self::PublicSetup<'_>) -> Option<[u8; 32]> {
+        let mut hasher = blake3::Hasher::new();
+        for generator in setup.iter().take(2) {
+            let mut buf = Vec::with_capacity(generator.compressed_size());
+            generator.serialize_compressed(&mut buf).unwrap();
+            hasher.update(&buf);
+        }
+        Some(*hasher.finalize().as_bytes())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     #[cfg(feature = "hyperkzg_proof")]
-    use crate::base::database::OwnedColumn;
+    use crate::base::{
+        commitment::{
+            assert_constant_commitments_match_materialized, assert_signed_commitments_match_scalars,
+        },
+        database::{BooleanBitmap, DeltaEncodedColumn, OwnedColumn},
+    };
     #[cfg(feature = "hyperkzg_proof")]
     use crate::proof_primitive::hyperkzg::nova_commitment_key_to_hyperkzg_public_setup;
     #[cfg(feature = "hyperkzg_proof")]
@@ -260,6 +421,147 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "hyperkzg_proof")]
+    #[test]
+    fn packed_and_unpacked_boolean_commitments_are_equal() {
+        let values: Vec<bool> = (0..70).map(|i| i % 5 < 2).collect();
+        let bitmap = BooleanBitmap::from(values.as_slice());
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 80);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+
+        let packed = [CommittableColumn::BooleanBitmap(&bitmap)];
+        let unpacked = [CommittableColumn::Boolean(&values)];
+        let expected = compute_commitments_impl(&unpacked, 3, &&public_setup[..]);
+        assert_eq!(
+            compute_commitments_impl(&packed, 3, &&public_setup[..]),
+            expected
+        );
+        assert_eq!(
+            HyperKZGCommitment::compute_commitments(&packed, 3, &&public_setup[..]),
+            expected
+        );
+    }
+
+    #[cfg(feature = "hyperkzg_proof")]
+    #[test]
+    fn delta_encoded_and_plain_bigint_commitments_are_equal() {
+        let values: Vec<i64> = (0..70).map(|i| 1_700_000_000_000 + 1_000 * i).collect();
+        let deltas = DeltaEncodedColumn::try_from_values(&values).unwrap();
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 80);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+
+        let delta_encoded = [CommittableColumn::DeltaEncoded(&deltas)];
+        let plain = [CommittableColumn::BigInt(&values)];
+        let expected = compute_commitments_impl(&plain, 3, &&public_setup[..]);
+        assert_eq!(
+            compute_commitments_impl(&delta_encoded, 3, &&public_setup[..]),
+            expected
+        );
+        assert_eq!(
+            HyperKZGCommitment::compute_commitments(&delta_encoded, 3, &&public_setup[..]),
+            expected
+        );
+    }
+
+    #[test]
+    fn we_can_split_columns_into_batches_of_bounded_size() {
+        let (a, b, c) = ([1_i64; 4], [1_i32; 4], [1_i64; 8]);
+        let columns = [
+            CommittableColumn::BigInt(&a),
+            CommittableColumn::Int(&b),
+            CommittableColumn::BigInt(&c),
+        ];
+        let batch_lens = |max_batch_bytes| {
+            split_into_batches(&columns, max_batch_bytes)
+                .iter()
+                .map(|batch| batch.len())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(batch_lens(usize::MAX), [3]);
+        assert_eq!(batch_lens(112), [3]);
+        assert_eq!(batch_lens(64), [2, 1]);
+        assert_eq!(batch_lens(48), [2, 1]);
+        assert_eq!(batch_lens(47), [1, 1, 1]);
+        assert_eq!(batch_lens(0), [1, 1, 1]);
+        assert!(split_into_batches(&[], 0).is_empty());
+    }
+
+    #[cfg(feature = "hyperkzg_proof")]
+    #[test]
+    fn commitments_computed_in_batches_equal_single_shot_commitments() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 16);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+        let bools: Vec<bool> = (0..9).map(|i| i % 3 == 0).collect();
+        let bitmap = BooleanBitmap::from(bools.as_slice());
+        let (a, b, c) = ([-3_i64, 5, 7], [2_i32; 11], [4_i128, -9]);
+        let columns = [
+            CommittableColumn::BigInt(&a),
+            CommittableColumn::Int(&b),
+            CommittableColumn::BooleanBitmap(&bitmap),
+            CommittableColumn::Int128(&c),
+            CommittableColumn::Boolean(&bools),
+        ];
+        let expected = HyperKZGCommitment::compute_commitments(&columns, 2, &&public_setup[..]);
+        let mut options = vec![
+            GpuOptions::default(),
+            GpuOptions {
+                device_ids: Vec::new(),
+                max_batch_bytes: 1,
+            },
+        ];
+        if cfg!(feature = "blitzar") {
+            options.push(GpuOptions {
+                device_ids: vec![0, 0],
+                max_batch_bytes: 40,
+            });
+        }
+        for options in options {
+            assert_eq!(
+                HyperKZGCommitment::compute_commitments_with_options(
+                    &columns,
+                    2,
+                    &&public_setup[..],
+                    &options
+                ),
+                Ok(expected.clone())
+            );
+        }
+        assert_eq!(
+            HyperKZGCommitment::compute_commitments_with_options(
+                &[],
+                0,
+                &&public_setup[..],
+                &GpuOptions::default()
+            ),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn we_cannot_compute_commitments_on_unsupported_devices() {
+        let a = [1_i64, 2, 3];
+        let columns = [CommittableColumn::BigInt(&a)];
+        let first_unsupported_device = if cfg!(feature = "blitzar") { 1 } else { 0 };
+        for device_ids in [vec![0, 1, 2], vec![7]] {
+            let device_id = *device_ids
+                .iter()
+                .find(|&&device_id| device_id >= first_unsupported_device)
+                .unwrap();
+            assert_eq!(
+                HyperKZGCommitment::compute_commitments_with_options(
+                    &columns,
+                    0,
+                    &&[][..],
+                    &GpuOptions {
+                        device_ids,
+                        max_batch_bytes: usize::MAX,
+                    }
+                ),
+                Err(GpuOptionsError::UnsupportedDevice { device_id })
+            );
+        }
+    }
+
     #[test]
     fn we_can_serialize_and_deserialize_hyperkzg_commitment_generator() {
         let bincode_config = bincode::config::legacy()
@@ -303,4 +605,69 @@ mod tests {
             assert_eq!(deserialized_commitment.commitment, commitment.commitment);
         }
     }
+
+    #[cfg(feature = "hyperkzg_proof")]
+    #[test]
+    fn we_can_compute_hyperkzg_commitments_to_constant_columns() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 128);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+        assert_constant_commitments_match_materialized::<HyperKZGCommitment>(
+            &&public_setup[..],
+            &[(0, 0), (0, 5), (1, 0), (4, 0), (7, 3), (100, 28)],
+        );
+    }
+
+    #[cfg(feature = "hyperkzg_proof")]
+    #[test]
+    fn we_can_compute_hyperkzg_commitments_to_negative_integers_as_their_scalars() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 128);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+        assert_signed_commitments_match_scalars::<HyperKZGCommitment>(
+            &&public_setup[..],
+            &[0, 1, 3, 28],
+        );
+    }
+
+    #[cfg(feature = "hyperkzg_proof")]
+    #[test]
+    fn we_can_commit_with_exactly_the_generators_needed() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 16);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+        let columns = [CommittableColumn::BigInt(&[1, 2, 3])];
+        let generators_needed = columns[0].generators_needed(5);
+        let setup = &public_setup[..generators_needed];
+        assert_eq!(
+            HyperKZGCommitment::num_generators(&setup),
+            Some(generators_needed)
+        );
+        assert_eq!(
+            HyperKZGCommitment::compute_commitments(&columns, 5, &setup),
+            compute_commitments_impl(&columns, 5, &&public_setup[..])
+        );
+    }
+
+    #[cfg(feature = "hyperkzg_proof")]
+    #[test]
+    #[should_panic]
+    fn we_cannot_commit_with_one_generator_less_than_needed() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 16);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+        let columns = [CommittableColumn::BigInt(&[1, 2, 3])];
+        let generators_needed = columns[0].generators_needed(5);
+        let _ = HyperKZGCommitment::compute_commitments(
+            &columns,
+            5,
+            &&public_setup[..generators_needed - 1],
+        );
+    }
+
+    #[test]
+    fn hyperkzg_commitments_support_every_column_type() {
+        for column_type in crate::base::commitment::column_types_for_testing() {
+            assert!(
+                HyperKZGCommitment::supports_column_type(column_type),
+                "{column_type}"
+            );
+        }
+    }
 }
@@ -1,6 +1,10 @@
 use super::{BNScalar, HyperKZGCommitment, HyperKZGEngine, HyperKZGPublicSetup};
 use crate::{
-    base::{commitment::CommitmentEvaluationProof, slice_ops},
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        encoded_size::{seq_size, SCALAR_SIZE},
+        slice_ops,
+    },
     proof_primitive::hyperkzg::nova_commitment::NovaCommitment,
 };
 use ark_bn254::{G1Affine, G1Projective};
@@ -93,6 +97,14 @@ impl CommitmentEvaluationProof for HyperKZGCommitmentEvaluationProof {
             .into()
     }
 
+    /// This is exact.
+    fn encoded_size_hint(&self) -> usize {
+        let commitment_size = HyperKZGCommitment::default().encoded_size_hint();
+        seq_size(self.com.len(), commitment_size)
+            + seq_size(self.v.len(), 3 * SCALAR_SIZE)
+            + 3 * commitment_size
+    }
+
     fn verify_batched_proof(
         &self,
         transcript: &mut impl crate::base::proof::Transcript,
@@ -20,7 +20,7 @@ pub use public_setup::{
 };
 
 mod commitment;
-pub use commitment::HyperKZGCommitment;
+pub use commitment::{GpuOptions, GpuOptionsError, HyperKZGCommitment};
 
 #[cfg(feature = "hyperkzg_proof")]
 mod nova_commitment;
@@ -28,7 +28,10 @@ mod nova_commitment;
 #[cfg(feature = "hyperkzg_proof")]
 mod nova_engine;
 #[cfg(feature = "hyperkzg_proof")]
-pub use nova_engine::{nova_commitment_key_to_hyperkzg_public_setup, HyperKZGEngine};
+pub use nova_engine::{
+    hyperkzg_public_setup_to_nova_commitment_key, hyperkzg_verifier_key_from_public_setup,
+    nova_commitment_key_to_hyperkzg_public_setup, HyperKZGEngine,
+};
 
 #[cfg(feature = "hyperkzg_proof")]
 mod commitment_evaluation_proof;
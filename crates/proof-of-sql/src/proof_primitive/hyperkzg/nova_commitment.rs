@@ -75,6 +75,20 @@ mod tests {
                 CommittableColumn::Boolean(vals) => {
                     expected.push(compute_commitment_with_hyperkzg_repo(ck, offset, vals));
                 }
+                CommittableColumn::BooleanBitmap(bitmap) => {
+                    expected.push(compute_commitment_with_hyperkzg_repo(
+                        ck,
+                        offset,
+                        &bitmap.to_vec(),
+                    ));
+                }
+                CommittableColumn::DeltaEncoded(deltas) => {
+                    expected.push(compute_commitment_with_hyperkzg_repo(
+                        ck,
+                        offset,
+                        &deltas.to_vec(),
+                    ));
+                }
                 CommittableColumn::Uint8(vals) => {
                     expected.push(compute_commitment_with_hyperkzg_repo(ck, offset, vals));
                 }
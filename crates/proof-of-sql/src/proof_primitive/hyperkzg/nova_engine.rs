@@ -1,12 +1,18 @@
-use super::{BNScalar, HyperKZGPublicSetupOwned};
+use super::{BNScalar, HyperKZGPublicSetup, HyperKZGPublicSetupOwned};
 use crate::base::{
     proof::{Keccak256Transcript, Transcript},
     slice_ops,
 };
+use halo2curves::bn256::G2Affine;
 use nova_snark::{
     errors::NovaError,
-    provider::{bn256_grumpkin::bn256::Scalar as NovaScalar, hyperkzg::CommitmentKey},
-    traits::{Engine, TranscriptEngineTrait, TranscriptReprTrait},
+    provider::{
+        bn256_grumpkin::bn256::{Affine, Scalar as NovaScalar},
+        hyperkzg::{CommitmentKey, EvaluationEngine, VerifierKey},
+    },
+    traits::{
+        evaluation::EvaluationEngineTrait, Engine, TranscriptEngineTrait, TranscriptReprTrait,
+    },
 };
 use serde::{Deserialize, Serialize};
 
@@ -54,3 +60,67 @@ pub fn nova_commitment_key_to_hyperkzg_public_setup(
 ) -> HyperKZGPublicSetupOwned {
     slice_ops::slice_cast_with(setup.ck(), blitzar::compute::convert_to_ark_bn254_g1_affine)
 }
+
+/// Utility converting a [`HyperKZGPublicSetup`] to a nova `CommitmentKey`.
+///
+/// This is the inverse of [`nova_commitment_key_to_hyperkzg_public_setup`].
+/// The public setup only holds the generators, so the remaining parts of the key are left as
+/// their defaults.
+#[must_use]
+pub fn hyperkzg_public_setup_to_nova_commitment_key(
+    setup: HyperKZGPublicSetup<'_>,
+) -> CommitmentKey<HyperKZGEngine> {
+    CommitmentKey::new(
+        slice_ops::slice_cast_with(setup, blitzar::compute::convert_to_halo2_bn256_g1_affine),
+        Affine::default(),
+        G2Affine::default(),
+    )
+}
+
+/// Utility deriving the nova `VerifierKey` from a [`HyperKZGPublicSetup`].
+#[must_use]
+pub fn hyperkzg_verifier_key_from_public_setup(
+    setup: HyperKZGPublicSetup<'_>,
+) -> VerifierKey<HyperKZGEngine> {
+    let (_, vk) = EvaluationEngine::setup(&hyperkzg_public_setup_to_nova_commitment_key(setup));
+    vk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof_primitive::hyperkzg::public_setup::load_small_setup_for_testing;
+    use nova_snark::{
+        provider::hyperkzg::CommitmentEngine, traits::commitment::CommitmentEngineTrait,
+    };
+
+    #[test]
+    fn we_can_round_trip_a_nova_commitment_key() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 32);
+        let public_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+        let round_tripped_ck = hyperkzg_public_setup_to_nova_commitment_key(&public_setup);
+        assert_eq!(round_tripped_ck.ck(), ck.ck());
+        assert_eq!(
+            nova_commitment_key_to_hyperkzg_public_setup(&round_tripped_ck),
+            public_setup
+        );
+    }
+
+    #[test]
+    fn we_can_round_trip_a_hyperkzg_public_setup() {
+        let (public_setup, _) = load_small_setup_for_testing();
+        let ck = hyperkzg_public_setup_to_nova_commitment_key(&public_setup);
+        assert_eq!(ck.ck().len(), public_setup.len());
+        assert_eq!(
+            nova_commitment_key_to_hyperkzg_public_setup(&ck),
+            public_setup
+        );
+    }
+
+    #[test]
+    fn we_can_round_trip_an_empty_hyperkzg_public_setup() {
+        let ck = hyperkzg_public_setup_to_nova_commitment_key(&[]);
+        assert!(ck.ck().is_empty());
+        assert!(nova_commitment_key_to_hyperkzg_public_setup(&ck).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::base::{
     commitment::CommitmentEvaluationProof,
+    encoded_size::{seq_size, SCALAR_SIZE},
     proof::Transcript,
     scalar::{MontScalar, Scalar},
     slice_ops,
@@ -40,6 +41,12 @@ impl CommitmentEvaluationProof for InnerProductProof {
         })
     }
 
+    /// The points and the scalar of the proof are serialized in their 32 byte compressed forms, so
+    /// this is exact.
+    fn encoded_size_hint(&self) -> usize {
+        seq_size(self.l_vector.len(), 32) + seq_size(self.r_vector.len(), 32) + SCALAR_SIZE
+    }
+
     fn verify_batched_proof(
         &self,
         transcript: &mut impl Transcript,
@@ -1,5 +1,8 @@
 use crate::{
-    base::commitment::{Commitment, CommittableColumn},
+    base::{
+        commitment::{Commitment, CommittableColumn},
+        database::ColumnType,
+    },
     proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
 };
 use alloc::vec::Vec;
@@ -8,22 +11,32 @@ use curve25519_dalek::RistrettoPoint;
 impl Commitment for RistrettoPoint {
     type Scalar = Curve25519Scalar;
     type PublicSetup<'a> = ();
+    fn scheme_identifier() -> &'static str {
+        "inner_product"
+    }
+    /// Commitments are computed with blitzar, which cannot commit to `Int128` columns yet.
+    /// Without blitzar no column type is supported.
+    fn supports_column_type(column_type: ColumnType) -> bool {
+        cfg!(feature = "blitzar") && column_type != ColumnType::Int128
+    }
     #[cfg(feature = "blitzar")]
     fn compute_commitments(
         committable_columns: &[CommittableColumn],
         offset: usize,
         _setup: &Self::PublicSetup<'_>,
     ) -> Vec<Self> {
+        use crate::base::commitment::with_sequences;
         use curve25519_dalek::ristretto::CompressedRistretto;
 
-        let sequences: Vec<_> = committable_columns.iter().map(Into::into).collect();
         let mut compressed_commitments =
             vec![CompressedRistretto::default(); committable_columns.len()];
-        blitzar::compute::compute_curve25519_commitments(
-            &mut compressed_commitments,
-            &sequences,
-            offset as u64,
-        );
+        with_sequences(committable_columns, |sequences| {
+            blitzar::compute::compute_curve25519_commitments(
+                &mut compressed_commitments,
+                sequences,
+                offset as u64,
+            );
+        });
         compressed_commitments
             .into_iter()
             .map(|cc| {
@@ -42,14 +55,34 @@ impl Commitment for RistrettoPoint {
         unimplemented!()
     }
 
+    /// The generators of the rows are summed as the differences of commitments to columns of ones.
+    #[cfg(feature = "blitzar")]
+    fn compute_constant_commitment(
+        value: Curve25519Scalar,
+        num_rows: usize,
+        offset: usize,
+        _setup: &Self::PublicSetup<'_>,
+    ) -> Option<Self> {
+        use blitzar::compute::get_one_curve25519_commit;
+
+        let generators = get_one_curve25519_commit((offset + num_rows) as u64)
+            - get_one_curve25519_commit(offset as u64);
+        Some(value * generators)
+    }
+
     fn to_transcript_bytes(&self) -> Vec<u8> {
         self.compress().as_bytes().to_vec()
     }
+
+    /// The commitment is serialized as its 32 byte compressed form, so this is exact.
+    fn encoded_size_hint(&self) -> usize {
+        32
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::base::commitment::*;
+    use crate::base::{commitment::*, database::ColumnType};
     use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint};
 
     #[test]
@@ -62,4 +95,30 @@ mod tests {
             commitment2.to_transcript_bytes()
         );
     }
+
+    #[cfg(feature = "blitzar")]
+    #[test]
+    fn we_can_compute_ristretto_point_commitments_to_constant_columns() {
+        assert_constant_commitments_match_materialized::<RistrettoPoint>(
+            &(),
+            &[(0, 0), (0, 5), (1, 0), (4, 0), (7, 3), (300, 1000)],
+        );
+    }
+
+    #[cfg(feature = "blitzar")]
+    #[test]
+    fn we_can_compute_ristretto_point_commitments_to_negative_integers_as_their_scalars() {
+        assert_signed_commitments_match_scalars::<RistrettoPoint>(&(), &[0, 1, 3, 1000]);
+    }
+
+    #[test]
+    fn ristretto_point_commitments_support_every_column_type_but_int128() {
+        for column_type in column_types_for_testing() {
+            assert_eq!(
+                RistrettoPoint::supports_column_type(column_type),
+                cfg!(feature = "blitzar") && column_type != ColumnType::Int128,
+                "{column_type}"
+            );
+        }
+    }
 }
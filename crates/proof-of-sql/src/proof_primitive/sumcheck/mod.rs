@@ -2,6 +2,7 @@ mod proof;
 #[cfg(test)]
 mod proof_test;
 pub use proof::SumcheckProof;
+pub(crate) use proof::{SumcheckCheckpoint, SumcheckCheckpointRef};
 
 mod prover_state;
 pub(crate) use prover_state::ProverState;
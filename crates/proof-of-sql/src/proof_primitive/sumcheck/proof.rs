@@ -1,5 +1,6 @@
 use crate::{
     base::{
+        encoded_size::{seq_size, SCALAR_SIZE},
         polynomial::interpolate_evaluations_to_reverse_coefficients,
         proof::{ProofError, Transcript},
         scalar::Scalar,
@@ -13,12 +14,32 @@ use crate::{
  * See third_party/license/arkworks.LICENSE
  */
 use alloc::vec::Vec;
+use core::convert::Infallible;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SumcheckProof<S: Scalar> {
     pub(super) coefficients: Vec<S>,
 }
+
+/// The state of the prover of a [`SumcheckProof`] after a round, from which the proof can be resumed
+#[derive(Serialize)]
+pub struct SumcheckCheckpointRef<'a, S: Scalar> {
+    /// The state of the prover
+    pub state: &'a ProverState<S>,
+    /// The coefficients of the completed rounds
+    pub coefficients: &'a [S],
+}
+
+/// An owned [`SumcheckCheckpointRef`]
+#[derive(Debug, Deserialize)]
+pub struct SumcheckCheckpoint<S: Scalar> {
+    /// The state of the prover
+    pub state: ProverState<S>,
+    /// The coefficients of the completed rounds
+    pub coefficients: Vec<S>,
+}
+
 pub struct Subclaim<S: Scalar> {
     pub evaluation_point: Vec<S>,
     pub expected_evaluation: S,
@@ -30,17 +51,45 @@ impl<S: Scalar> SumcheckProof<S> {
     pub fn create(
         transcript: &mut impl Transcript,
         evaluation_point: &mut [S],
-        mut state: ProverState<S>,
+        state: ProverState<S>,
     ) -> Self {
+        let coefficients = Vec::with_capacity(state.max_multiplicands * state.num_vars);
+        Self::create_with_checkpoints(transcript, evaluation_point, state, coefficients, |_| {
+            Ok::<_, Infallible>(())
+        })
+        .unwrap_or_else(|never| match never {})
+    }
+
+    /// Create a proof, starting from `state` after the rounds whose coefficients are `coefficients`.
+    ///
+    /// The challenges of the completed rounds are re-derived by absorbing their coefficients, so
+    /// the proof is identical to one created without interruption. `on_round` is called after
+    /// every round with the state from which the proof can be resumed.
+    pub fn create_with_checkpoints<E>(
+        transcript: &mut impl Transcript,
+        evaluation_point: &mut [S],
+        mut state: ProverState<S>,
+        mut coefficients: Vec<S>,
+        mut on_round: impl FnMut(SumcheckCheckpointRef<'_, S>) -> Result<(), E>,
+    ) -> Result<Self, E> {
         log::log_memory_usage("Start");
 
         assert_eq!(evaluation_point.len(), state.num_vars);
-        transcript.extend_as_be([((state.max_multiplicands + 1) * state.num_vars) as u64]);
+        let round_length = state.max_multiplicands + 1;
+        assert_eq!(coefficients.len(), state.round * round_length);
+        transcript.extend_as_be([(round_length * state.num_vars) as u64]);
         // This challenge is in order to keep transcript messages grouped. (This simplifies the Solidity implementation.)
         transcript.scalar_challenge_as_be::<S>();
         let mut r = None;
-        let mut coefficients = Vec::with_capacity(state.max_multiplicands * state.num_vars);
-        for scalar in evaluation_point.iter_mut().take(state.num_vars) {
+        for (scalar, round_coefficients) in evaluation_point
+            .iter_mut()
+            .zip(coefficients.chunks(round_length))
+        {
+            transcript.extend_scalars_as_be(round_coefficients);
+            *scalar = transcript.scalar_challenge_as_be();
+            r = Some(*scalar);
+        }
+        for scalar in evaluation_point.iter_mut().skip(state.round) {
             let round_evaluations = prove_round(&mut state, &r);
             let round_coefficients =
                 interpolate_evaluations_to_reverse_coefficients(&round_evaluations);
@@ -48,11 +97,20 @@ impl<S: Scalar> SumcheckProof<S> {
             coefficients.extend(round_coefficients);
             *scalar = transcript.scalar_challenge_as_be();
             r = Some(*scalar);
+            on_round(SumcheckCheckpointRef {
+                state: &state,
+                coefficients: &coefficients,
+            })?;
         }
 
         log::log_memory_usage("End");
 
-        SumcheckProof { coefficients }
+        Ok(SumcheckProof { coefficients })
+    }
+
+    /// The number of bytes the proof takes up when serialized with bincode's legacy configuration
+    pub fn encoded_size_hint(&self) -> usize {
+        seq_size(self.coefficients.len(), SCALAR_SIZE)
     }
 
     #[tracing::instrument(
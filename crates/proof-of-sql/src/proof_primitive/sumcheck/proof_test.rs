@@ -12,7 +12,7 @@ use crate::{
     },
     proof_primitive::{
         inner_product::curve_25519_scalar::Curve25519Scalar,
-        sumcheck::{ProverState, SumcheckProof},
+        sumcheck::{ProverState, SumcheckCheckpoint, SumcheckProof},
     },
 };
 use alloc::rc::Rc;
@@ -279,3 +279,52 @@ fn we_can_generate_and_verify_a_simple_sumcheck_proof() {
     //dbg!(subclaim.expected_evaluation);
     //dbg!(&evaluation_point);
 }
+
+#[test]
+fn we_can_resume_a_sumcheck_proof_from_any_round() {
+    let mut rng = ark_std::test_rng();
+
+    for test_case in sumcheck_test_cases::<TestScalar>(&mut rng) {
+        let mut transcript = Transcript::new(b"sumchecktest");
+        let mut evaluation_point = vec![MontScalar::default(); test_case.num_vars];
+        let mut checkpoints = Vec::new();
+        let proof = SumcheckProof::create_with_checkpoints(
+            &mut transcript,
+            &mut evaluation_point,
+            ProverState::create(&test_case.polynomial),
+            Vec::new(),
+            |checkpoint| {
+                checkpoints.push(
+                    bincode::serde::encode_to_vec(&checkpoint, bincode::config::legacy()).unwrap(),
+                );
+                Ok::<_, ()>(())
+            },
+        )
+        .unwrap();
+        assert_eq!(checkpoints.len(), test_case.num_vars);
+        let expected_transcript_state = transcript.challenge_as_le();
+
+        for checkpoint in checkpoints {
+            let (
+                SumcheckCheckpoint::<TestScalar> {
+                    state,
+                    coefficients,
+                },
+                _,
+            ) = bincode::serde::decode_from_slice(&checkpoint, bincode::config::legacy()).unwrap();
+            let mut transcript = Transcript::new(b"sumchecktest");
+            let mut resumed_evaluation_point = vec![MontScalar::default(); test_case.num_vars];
+            let resumed_proof = SumcheckProof::create_with_checkpoints(
+                &mut transcript,
+                &mut resumed_evaluation_point,
+                state,
+                coefficients,
+                |_| Ok::<_, ()>(()),
+            )
+            .unwrap();
+            assert_eq!(resumed_proof.coefficients, proof.coefficients);
+            assert_eq!(resumed_evaluation_point, evaluation_point);
+            assert_eq!(transcript.challenge_as_le(), expected_transcript_state);
+        }
+    }
+}
@@ -6,8 +6,9 @@ use crate::base::polynomial::CompositePolynomial;
  */
 use crate::{base::scalar::Scalar, utils::log};
 use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ProverState<S: Scalar> {
     /// Stores the list of products that is meant to be added together. Each multiplicand is represented by
     /// the index in `flattened_ml_extensions`
@@ -0,0 +1,63 @@
+use crate::sql::AnalyzeError;
+use alloc::string::{String, ToString};
+use snafu::Snafu;
+
+/// Errors that can occur when converting plans to and from canonical JSON.
+#[derive(Snafu, Debug, PartialEq)]
+pub enum CanonicalJsonError {
+    /// The plan contains a plan or expression which the canonical JSON format does not cover.
+    #[snafu(display("{kind} is not supported by the canonical JSON format"))]
+    NotSupported {
+        /// The kind of plan or expression
+        kind: &'static str,
+    },
+    /// The document is of a version of the format which is not supported.
+    #[snafu(display("canonical JSON version {version} is not supported, expected {expected}"))]
+    UnsupportedVersion {
+        /// The version of the document
+        version: u32,
+        /// The supported version
+        expected: u32,
+    },
+    /// The document is not valid JSON or does not follow the schema.
+    #[snafu(display("invalid canonical JSON: {message}"))]
+    InvalidJson {
+        /// The message of the underlying JSON error
+        message: String,
+    },
+    /// The value of a literal does not match its type.
+    #[snafu(display("invalid literal value for type {column_type}"))]
+    InvalidLiteral {
+        /// The type of the literal
+        column_type: String,
+    },
+    /// A decimal precision is out of range.
+    #[snafu(display("invalid decimal precision {precision}"))]
+    InvalidPrecision {
+        /// The precision
+        precision: u8,
+    },
+    /// The plan described by the document is invalid in a way its constructor does not report.
+    #[snafu(display("invalid plan: {reason}"))]
+    InvalidPlan {
+        /// Why the plan is invalid
+        reason: &'static str,
+    },
+    /// The plan or expression described by the document is invalid.
+    #[snafu(transparent)]
+    AnalyzeError {
+        /// The underlying source error
+        source: AnalyzeError,
+    },
+}
+
+impl From<serde_json::Error> for CanonicalJsonError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::InvalidJson {
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Result type for canonical JSON conversions.
+pub type CanonicalJsonResult<T> = core::result::Result<T, CanonicalJsonError>;
@@ -0,0 +1,342 @@
+use super::{
+    types::{CanonicalColumnRef, CanonicalColumnType, CanonicalLiteral},
+    CanonicalJsonError, CanonicalJsonResult,
+};
+use crate::{
+    base::database::LiteralValue,
+    sql::proof_exprs::{ColumnExpr, DynProofExpr, ProofExpr},
+};
+use alloc::{boxed::Box, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+/// An expression, tagged by `type`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub(super) enum CanonicalExpr {
+    Column {
+        column: CanonicalColumnRef,
+    },
+    Literal {
+        literal: CanonicalLiteral,
+    },
+    Placeholder {
+        id: usize,
+        data_type: CanonicalColumnType,
+    },
+    Equals {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    LessThan {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    GreaterThan {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    Add {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    Subtract {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    Multiply {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    And {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    Or {
+        lhs: Box<CanonicalExpr>,
+        rhs: Box<CanonicalExpr>,
+    },
+    Not {
+        expr: Box<CanonicalExpr>,
+    },
+    Cast {
+        expr: Box<CanonicalExpr>,
+        to_type: CanonicalColumnType,
+    },
+    ScalingCast {
+        expr: Box<CanonicalExpr>,
+        to_type: CanonicalColumnType,
+    },
+    If {
+        condition: Box<CanonicalExpr>,
+        then_expr: Box<CanonicalExpr>,
+        else_expr: Box<CanonicalExpr>,
+    },
+    RoundToMultiple {
+        expr: Box<CanonicalExpr>,
+        step: CanonicalLiteral,
+    },
+    Modulo {
+        expr: Box<CanonicalExpr>,
+        modulus: CanonicalLiteral,
+    },
+    PowMod {
+        base: Box<CanonicalExpr>,
+        exponent: u8,
+        modulus: CanonicalLiteral,
+    },
+    DecimalRescale {
+        expr: Box<CanonicalExpr>,
+        to_type: CanonicalColumnType,
+    },
+    RowHash {
+        columns: Vec<CanonicalColumnRef>,
+    },
+    Ln {
+        expr: Box<CanonicalExpr>,
+    },
+    #[serde(rename = "isqrt")]
+    ISqrt {
+        expr: Box<CanonicalExpr>,
+    },
+    DerivedKey {
+        namespace: [u8; 16],
+        columns: Vec<CanonicalColumnRef>,
+    },
+    In {
+        expr: Box<CanonicalExpr>,
+        values: Vec<CanonicalLiteral>,
+    },
+}
+
+/// Converts both sides of a binary expression.
+fn try_from_binary(
+    lhs: &DynProofExpr,
+    rhs: &DynProofExpr,
+) -> CanonicalJsonResult<(Box<CanonicalExpr>, Box<CanonicalExpr>)> {
+    Ok((
+        Box::new(CanonicalExpr::try_from_proof_expr(lhs)?),
+        Box::new(CanonicalExpr::try_from_proof_expr(rhs)?),
+    ))
+}
+
+/// Converts a hashed input of a row hash or a derived key, which is always a column.
+fn try_column_ref_from_proof_expr(expr: &DynProofExpr) -> CanonicalJsonResult<CanonicalColumnRef> {
+    match expr {
+        DynProofExpr::Column(column_expr) => Ok(column_expr.column_ref().into()),
+        _ => Err(CanonicalJsonError::NotSupported {
+            kind: "non-column hashed input",
+        }),
+    }
+}
+
+fn try_into_column_exprs(columns: &[CanonicalColumnRef]) -> CanonicalJsonResult<Vec<ColumnExpr>> {
+    columns
+        .iter()
+        .map(|column| Ok(ColumnExpr::new(column.try_into()?)))
+        .collect()
+}
+
+impl CanonicalExpr {
+    /// Try to create a `CanonicalExpr` from a `DynProofExpr`.
+    pub(super) fn try_from_proof_expr(expr: &DynProofExpr) -> CanonicalJsonResult<Self> {
+        Ok(match expr {
+            DynProofExpr::Column(column_expr) => Self::Column {
+                column: column_expr.column_ref().into(),
+            },
+            DynProofExpr::Literal(literal_expr) => Self::Literal {
+                literal: literal_expr.value().into(),
+            },
+            DynProofExpr::Placeholder(placeholder_expr) => Self::Placeholder {
+                id: placeholder_expr.id(),
+                data_type: placeholder_expr.column_type().into(),
+            },
+            DynProofExpr::Equals(equals_expr) => {
+                let (lhs, rhs) = try_from_binary(equals_expr.lhs(), equals_expr.rhs())?;
+                Self::Equals { lhs, rhs }
+            }
+            DynProofExpr::Inequality(inequality_expr) => {
+                let (lhs, rhs) = try_from_binary(inequality_expr.lhs(), inequality_expr.rhs())?;
+                if inequality_expr.is_lt() {
+                    Self::LessThan { lhs, rhs }
+                } else {
+                    Self::GreaterThan { lhs, rhs }
+                }
+            }
+            DynProofExpr::Add(add_expr) => {
+                let (lhs, rhs) = try_from_binary(add_expr.lhs(), add_expr.rhs())?;
+                Self::Add { lhs, rhs }
+            }
+            DynProofExpr::Subtract(subtract_expr) => {
+                let (lhs, rhs) = try_from_binary(subtract_expr.lhs(), subtract_expr.rhs())?;
+                Self::Subtract { lhs, rhs }
+            }
+            DynProofExpr::Multiply(multiply_expr) => {
+                let (lhs, rhs) = try_from_binary(multiply_expr.lhs(), multiply_expr.rhs())?;
+                Self::Multiply { lhs, rhs }
+            }
+            DynProofExpr::And(and_expr) => {
+                let (lhs, rhs) = try_from_binary(and_expr.lhs(), and_expr.rhs())?;
+                Self::And { lhs, rhs }
+            }
+            DynProofExpr::Or(or_expr) => {
+                let (lhs, rhs) = try_from_binary(or_expr.lhs(), or_expr.rhs())?;
+                Self::Or { lhs, rhs }
+            }
+            DynProofExpr::Not(not_expr) => Self::Not {
+                expr: Box::new(Self::try_from_proof_expr(not_expr.input())?),
+            },
+            DynProofExpr::Cast(cast_expr) => Self::Cast {
+                expr: Box::new(Self::try_from_proof_expr(cast_expr.from_expr())?),
+                to_type: cast_expr.data_type().into(),
+            },
+            DynProofExpr::ScalingCast(scaling_cast_expr) => Self::ScalingCast {
+                expr: Box::new(Self::try_from_proof_expr(scaling_cast_expr.from_expr())?),
+                to_type: scaling_cast_expr.data_type().into(),
+            },
+            DynProofExpr::If(if_expr) => Self::If {
+                condition: Box::new(Self::try_from_proof_expr(if_expr.condition())?),
+                then_expr: Box::new(Self::try_from_proof_expr(if_expr.then_expr())?),
+                else_expr: Box::new(Self::try_from_proof_expr(if_expr.else_expr())?),
+            },
+            DynProofExpr::RoundToMultiple(round_to_multiple_expr) => Self::RoundToMultiple {
+                expr: Box::new(Self::try_from_proof_expr(round_to_multiple_expr.expr())?),
+                step: (&LiteralValue::Int128(round_to_multiple_expr.step())).into(),
+            },
+            DynProofExpr::Modulo(modulo_expr) => Self::Modulo {
+                expr: Box::new(Self::try_from_proof_expr(modulo_expr.expr())?),
+                modulus: (&LiteralValue::Int128(modulo_expr.modulus())).into(),
+            },
+            DynProofExpr::PowMod(pow_mod_expr) => Self::PowMod {
+                base: Box::new(Self::try_from_proof_expr(pow_mod_expr.base())?),
+                exponent: pow_mod_expr.exponent(),
+                modulus: (&LiteralValue::Int128(pow_mod_expr.modulus())).into(),
+            },
+            DynProofExpr::DecimalRescale(decimal_rescale_expr) => Self::DecimalRescale {
+                expr: Box::new(Self::try_from_proof_expr(decimal_rescale_expr.from_expr())?),
+                to_type: decimal_rescale_expr.data_type().into(),
+            },
+            DynProofExpr::RowHash(row_hash_expr) => Self::RowHash {
+                columns: row_hash_expr
+                    .columns()
+                    .iter()
+                    .map(try_column_ref_from_proof_expr)
+                    .collect::<CanonicalJsonResult<_>>()?,
+            },
+            DynProofExpr::Ln(ln_expr) => Self::Ln {
+                expr: Box::new(Self::try_from_proof_expr(ln_expr.expr())?),
+            },
+            DynProofExpr::ISqrt(isqrt_expr) => Self::ISqrt {
+                expr: Box::new(Self::try_from_proof_expr(isqrt_expr.expr())?),
+            },
+            DynProofExpr::DerivedKey(derived_key_expr) => Self::DerivedKey {
+                namespace: *derived_key_expr.namespace(),
+                columns: derived_key_expr
+                    .columns()
+                    .iter()
+                    .map(try_column_ref_from_proof_expr)
+                    .collect::<CanonicalJsonResult<_>>()?,
+            },
+            DynProofExpr::In(in_expr) => Self::In {
+                expr: Box::new(Self::try_from_proof_expr(in_expr.expr())?),
+                values: in_expr.values().iter().map(Into::into).collect(),
+            },
+        })
+    }
+
+    /// Try to create a `DynProofExpr` from a `CanonicalExpr`.
+    ///
+    /// The expression is rebuilt with the checked constructors, so type errors are reported.
+    pub(super) fn try_into_proof_expr(&self) -> CanonicalJsonResult<DynProofExpr> {
+        Ok(match self {
+            Self::Column { column } => DynProofExpr::new_column(column.try_into()?),
+            Self::Literal { literal } => DynProofExpr::new_literal(literal.try_into()?),
+            Self::Placeholder { id, data_type } => {
+                DynProofExpr::try_new_placeholder(*id, (*data_type).try_into()?)?
+            }
+            Self::Equals { lhs, rhs } => DynProofExpr::try_new_equals(
+                lhs.try_into_proof_expr()?,
+                rhs.try_into_proof_expr()?,
+            )?,
+            Self::LessThan { lhs, rhs } => DynProofExpr::try_new_inequality(
+                lhs.try_into_proof_expr()?,
+                rhs.try_into_proof_expr()?,
+                true,
+            )?,
+            Self::GreaterThan { lhs, rhs } => DynProofExpr::try_new_inequality(
+                lhs.try_into_proof_expr()?,
+                rhs.try_into_proof_expr()?,
+                false,
+            )?,
+            Self::Add { lhs, rhs } => {
+                DynProofExpr::try_new_add(lhs.try_into_proof_expr()?, rhs.try_into_proof_expr()?)?
+            }
+            Self::Subtract { lhs, rhs } => DynProofExpr::try_new_subtract(
+                lhs.try_into_proof_expr()?,
+                rhs.try_into_proof_expr()?,
+            )?,
+            Self::Multiply { lhs, rhs } => DynProofExpr::try_new_multiply(
+                lhs.try_into_proof_expr()?,
+                rhs.try_into_proof_expr()?,
+            )?,
+            Self::And { lhs, rhs } => {
+                DynProofExpr::try_new_and(lhs.try_into_proof_expr()?, rhs.try_into_proof_expr()?)?
+            }
+            Self::Or { lhs, rhs } => {
+                DynProofExpr::try_new_or(lhs.try_into_proof_expr()?, rhs.try_into_proof_expr()?)?
+            }
+            Self::Not { expr } => DynProofExpr::try_new_not(expr.try_into_proof_expr()?)?,
+            Self::Cast { expr, to_type } => {
+                DynProofExpr::try_new_cast(expr.try_into_proof_expr()?, (*to_type).try_into()?)?
+            }
+            Self::ScalingCast { expr, to_type } => DynProofExpr::try_new_scaling_cast(
+                expr.try_into_proof_expr()?,
+                (*to_type).try_into()?,
+            )?,
+            Self::If {
+                condition,
+                then_expr,
+                else_expr,
+            } => DynProofExpr::try_new_if(
+                condition.try_into_proof_expr()?,
+                then_expr.try_into_proof_expr()?,
+                else_expr.try_into_proof_expr()?,
+            )?,
+            Self::RoundToMultiple { expr, step } => DynProofExpr::try_new_round_to_multiple(
+                expr.try_into_proof_expr()?,
+                &DynProofExpr::new_literal(step.try_into()?),
+            )?,
+            Self::Modulo { expr, modulus } => DynProofExpr::try_new_modulo(
+                expr.try_into_proof_expr()?,
+                &DynProofExpr::new_literal(modulus.try_into()?),
+            )?,
+            Self::PowMod {
+                base,
+                exponent,
+                modulus,
+            } => DynProofExpr::try_new_pow_mod(
+                base.try_into_proof_expr()?,
+                &DynProofExpr::new_literal(LiteralValue::Uint8(*exponent)),
+                &DynProofExpr::new_literal(modulus.try_into()?),
+            )?,
+            Self::DecimalRescale { expr, to_type } => DynProofExpr::try_new_decimal_rescale(
+                expr.try_into_proof_expr()?,
+                (*to_type).try_into()?,
+            )?,
+            Self::RowHash { columns } => {
+                DynProofExpr::try_new_row_hash(try_into_column_exprs(columns)?)?
+            }
+            Self::Ln { expr } => DynProofExpr::try_new_ln(expr.try_into_proof_expr()?)?,
+            Self::ISqrt { expr } => DynProofExpr::try_new_isqrt(expr.try_into_proof_expr()?)?,
+            Self::DerivedKey { namespace, columns } => {
+                DynProofExpr::try_new_derived_key(*namespace, try_into_column_exprs(columns)?)?
+            }
+            Self::In { expr, values } => DynProofExpr::try_new_in(
+                expr.try_into_proof_expr()?,
+                values
+                    .iter()
+                    .map(TryInto::try_into)
+                    .collect::<CanonicalJsonResult<_>>()?,
+            )?,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+//! A canonical, versioned JSON representation of [`DynProofPlan`] for tooling outside of Rust.
+//!
+//! Unlike the serde representation of [`DynProofPlan`], which mirrors the Rust types, the canonical
+//! form is tagged, uses `snake_case` names and encodes wide integers as decimal strings. It is
+//! described by the JSON schema in `canonical_json/v1/dyn_proof_plan.schema.json` at the root of
+//! this crate. Documents look like `{"version":1,"plan":{"type":"filter",...}}`.
+//!
+//! Every plan and expression is covered, except for group by plans whose keys are computed
+//! expressions rather than columns, which are rejected with [`CanonicalJsonError::NotSupported`].
+//!
+//! [`DynProofPlan`]: crate::sql::proof_plans::DynProofPlan
+mod error;
+pub use error::{CanonicalJsonError, CanonicalJsonResult};
+mod exprs;
+mod plans;
+#[cfg(test)]
+mod tests;
+mod types;
+
+use crate::sql::proof_plans::DynProofPlan;
+use alloc::string::String;
+use plans::CanonicalPlan;
+use serde::{Deserialize, Serialize};
+
+/// The version of the canonical JSON format produced by [`to_canonical_json`].
+pub const CANONICAL_JSON_VERSION: u32 = 1;
+
+/// A versioned document wrapping a plan.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CanonicalDocument<P> {
+    version: u32,
+    plan: P,
+}
+
+/// The version of a document, which is read before the rest of the document.
+#[derive(Deserialize)]
+struct CanonicalVersion {
+    version: u32,
+}
+
+/// Serializes a plan to canonical JSON.
+///
+/// # Errors
+/// Returns an error if the plan contains a plan or expression which the format does not cover.
+pub fn to_canonical_json(plan: &DynProofPlan) -> CanonicalJsonResult<String> {
+    let plan = CanonicalPlan::try_from_proof_plan(plan)?;
+    Ok(serde_json::to_string(&CanonicalDocument {
+        version: CANONICAL_JSON_VERSION,
+        plan,
+    })?)
+}
+
+/// Deserializes a plan from canonical JSON.
+///
+/// The version is checked before the plan is parsed, and the plan is rebuilt with the checked
+/// constructors, so ill-typed expressions are rejected.
+///
+/// # Errors
+/// Returns an error if the document is of another version, does not follow the schema or
+/// describes an invalid plan.
+pub fn from_canonical_json(json: &str) -> CanonicalJsonResult<DynProofPlan> {
+    let CanonicalVersion { version } = serde_json::from_str(json)?;
+    if version != CANONICAL_JSON_VERSION {
+        return Err(CanonicalJsonError::UnsupportedVersion {
+            version,
+            expected: CANONICAL_JSON_VERSION,
+        });
+    }
+    let CanonicalDocument::<CanonicalPlan> { plan, .. } = serde_json::from_str(json)?;
+    plan.try_into_proof_plan()
+}
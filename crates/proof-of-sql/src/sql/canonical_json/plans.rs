@@ -0,0 +1,702 @@
+use super::{
+    exprs::CanonicalExpr,
+    types::{CanonicalColumnField, CanonicalColumnRef, CanonicalLiteral},
+    CanonicalJsonError, CanonicalJsonResult,
+};
+use crate::{
+    base::database::{ColumnField, ColumnRef, ColumnType, TableRef},
+    sql::{
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, ColumnExpr, ProofExpr, TableExpr},
+        proof_plans::{
+            BucketCountExec, ContainmentCheckExec, DynProofPlan, GroupByExec, LeftAntiJoinExec,
+            MonotonicCheckExec, MultiCountExec, PermutationCheckExec, SortMergeJoinExec,
+        },
+    },
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// An expression with an alias.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct CanonicalAliasedExpr {
+    alias: String,
+    expr: CanonicalExpr,
+}
+
+impl CanonicalAliasedExpr {
+    fn try_from_proof_expr(aliased_expr: &AliasedDynProofExpr) -> CanonicalJsonResult<Self> {
+        Ok(Self {
+            alias: aliased_expr.alias.value.clone(),
+            expr: CanonicalExpr::try_from_proof_expr(&aliased_expr.expr)?,
+        })
+    }
+
+    fn try_into_proof_expr(&self) -> CanonicalJsonResult<AliasedDynProofExpr> {
+        Ok(AliasedDynProofExpr {
+            expr: self.expr.try_into_proof_expr()?,
+            alias: Ident::new(self.alias.as_str()),
+        })
+    }
+}
+
+/// A column whose distinct values are counted within each group, optionally only over the rows
+/// passing a filter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct CanonicalCountDistinct {
+    column: CanonicalColumnRef,
+    alias: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    filter: Option<CanonicalExpr>,
+}
+
+/// A plan, tagged by `type`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub(super) enum CanonicalPlan {
+    Empty,
+    Table {
+        table: TableRef,
+        schema: Vec<CanonicalColumnField>,
+    },
+    Projection {
+        results: Vec<CanonicalAliasedExpr>,
+        input: Box<CanonicalPlan>,
+    },
+    Filter {
+        results: Vec<CanonicalAliasedExpr>,
+        table: TableRef,
+        #[serde(rename = "where")]
+        where_clause: CanonicalExpr,
+    },
+    GroupBy {
+        group_by: Vec<CanonicalColumnRef>,
+        sums: Vec<CanonicalAliasedExpr>,
+        count_alias: String,
+        count_distinct: Vec<CanonicalCountDistinct>,
+        table: TableRef,
+        #[serde(rename = "where")]
+        where_clause: CanonicalExpr,
+    },
+    Slice {
+        input: Box<CanonicalPlan>,
+        skip: usize,
+        fetch: Option<usize>,
+    },
+    Union {
+        inputs: Vec<CanonicalPlan>,
+        schema: Vec<CanonicalColumnField>,
+    },
+    GroupingSets {
+        grouping_sets: Vec<Vec<CanonicalColumnRef>>,
+        sums: Vec<CanonicalAliasedExpr>,
+        count_alias: String,
+        grouping_id_alias: String,
+        table: TableRef,
+        #[serde(rename = "where")]
+        where_clause: CanonicalExpr,
+    },
+    GeneralizedFilter {
+        results: Vec<CanonicalAliasedExpr>,
+        input: Box<CanonicalPlan>,
+        #[serde(rename = "where")]
+        where_clause: CanonicalExpr,
+    },
+    SortMergeJoin {
+        left: Box<CanonicalPlan>,
+        right: Box<CanonicalPlan>,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+        result_names: Vec<String>,
+    },
+    MultiCount {
+        predicates: Vec<CanonicalAliasedExpr>,
+        table: TableRef,
+    },
+    BucketCount {
+        expr: CanonicalExpr,
+        boundaries: Vec<CanonicalLiteral>,
+        aliases: Vec<String>,
+        table: TableRef,
+    },
+    PercentageOfTotal {
+        results: Vec<CanonicalAliasedExpr>,
+        amount: CanonicalAliasedExpr,
+        scale: i8,
+        input: Box<CanonicalPlan>,
+    },
+    LeftAntiJoin {
+        left: Box<CanonicalPlan>,
+        right: Box<CanonicalPlan>,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+    },
+    ContainmentCheck {
+        child: Box<CanonicalPlan>,
+        parent: Box<CanonicalPlan>,
+        child_column_index: usize,
+        parent_column_index: usize,
+        alias: String,
+    },
+    MonotonicCheck {
+        input: Box<CanonicalPlan>,
+        column_index: usize,
+        alias: String,
+    },
+    PermutationCheck {
+        source: Box<CanonicalPlan>,
+        candidate: Box<CanonicalPlan>,
+        source_column_index: usize,
+        candidate_column_index: usize,
+        alias: String,
+    },
+    FirstPerGroup {
+        input: Box<CanonicalPlan>,
+        key_column_indexes: Vec<usize>,
+        tiebreaker_column_index: usize,
+        tiebreaker_descending: bool,
+    },
+}
+
+fn try_from_aliased_exprs(
+    aliased_exprs: &[AliasedDynProofExpr],
+) -> CanonicalJsonResult<Vec<CanonicalAliasedExpr>> {
+    aliased_exprs
+        .iter()
+        .map(CanonicalAliasedExpr::try_from_proof_expr)
+        .collect()
+}
+
+fn try_into_aliased_exprs(
+    aliased_exprs: &[CanonicalAliasedExpr],
+) -> CanonicalJsonResult<Vec<AliasedDynProofExpr>> {
+    aliased_exprs
+        .iter()
+        .map(CanonicalAliasedExpr::try_into_proof_expr)
+        .collect()
+}
+
+fn try_into_column_exprs(
+    column_refs: &[CanonicalColumnRef],
+) -> CanonicalJsonResult<Vec<ColumnExpr>> {
+    column_refs
+        .iter()
+        .map(|column_ref| Ok(ColumnExpr::new(column_ref.try_into()?)))
+        .collect()
+}
+
+fn names(idents: &[Ident]) -> Vec<String> {
+    idents.iter().map(|ident| ident.value.clone()).collect()
+}
+
+fn idents(names: &[String]) -> Vec<Ident> {
+    names.iter().map(|name| Ident::new(name.as_str())).collect()
+}
+
+/// Returns the types of the result columns of `plan` at `indexes`.
+///
+/// The constructors of the plans which refer to the columns of their inputs by index panic on
+/// out of bounds indexes, so these are rejected here first.
+fn try_result_column_types(
+    plan: &DynProofPlan,
+    indexes: &[usize],
+) -> CanonicalJsonResult<Vec<ColumnType>> {
+    let fields = plan.get_column_result_fields();
+    indexes
+        .iter()
+        .map(|&index| {
+            fields
+                .get(index)
+                .map(ColumnField::data_type)
+                .ok_or(CanonicalJsonError::InvalidPlan {
+                    reason: "column index out of bounds",
+                })
+        })
+        .collect()
+}
+
+/// Checks that the columns of `left` at `left_indexes` have the types of the columns of `right`
+/// at `right_indexes`, as joins and column comparisons require.
+fn check_matching_result_columns(
+    left: &DynProofPlan,
+    left_indexes: &[usize],
+    right: &DynProofPlan,
+    right_indexes: &[usize],
+) -> CanonicalJsonResult<()> {
+    if left_indexes.len() != right_indexes.len() {
+        return Err(CanonicalJsonError::InvalidPlan {
+            reason: "different numbers of columns on both sides",
+        });
+    }
+    if try_result_column_types(left, left_indexes)?
+        != try_result_column_types(right, right_indexes)?
+    {
+        return Err(CanonicalJsonError::InvalidPlan {
+            reason: "columns of different types on both sides",
+        });
+    }
+    Ok(())
+}
+
+fn try_into_column_fields(
+    schema: &[CanonicalColumnField],
+) -> CanonicalJsonResult<Vec<ColumnField>> {
+    schema.iter().map(TryInto::try_into).collect()
+}
+
+impl CanonicalPlan {
+    /// Try to create a `CanonicalPlan` from a `DynProofPlan`.
+    pub(super) fn try_from_proof_plan(plan: &DynProofPlan) -> CanonicalJsonResult<Self> {
+        Ok(match plan {
+            DynProofPlan::Empty(_) => Self::Empty,
+            DynProofPlan::Table(table_exec) => Self::Table {
+                table: table_exec.table_ref().clone(),
+                schema: table_exec.schema().iter().map(Into::into).collect(),
+            },
+            DynProofPlan::Projection(projection_exec) => Self::Projection {
+                results: try_from_aliased_exprs(projection_exec.aliased_results())?,
+                input: Box::new(Self::try_from_proof_plan(projection_exec.input())?),
+            },
+            DynProofPlan::Filter(filter_exec) => Self::Filter {
+                results: try_from_aliased_exprs(filter_exec.aliased_results())?,
+                table: filter_exec.table().table_ref.clone(),
+                where_clause: CanonicalExpr::try_from_proof_expr(filter_exec.where_clause())?,
+            },
+            DynProofPlan::GroupBy(group_by_exec) => Self::GroupBy {
+                group_by: group_by_exec
+                    .group_by_columns()
+                    .ok_or(CanonicalJsonError::NotSupported {
+                        kind: "GROUP BY expression",
+                    })?
+                    .iter()
+                    .map(|column_expr| column_expr.column_ref().into())
+                    .collect(),
+                sums: try_from_aliased_exprs(group_by_exec.sum_expr())?,
+                count_alias: group_by_exec.count_alias().value.clone(),
+                count_distinct: group_by_exec
+                    .count_distinct_exprs()
+                    .iter()
+                    .zip(group_by_exec.count_distinct_filters())
+                    .map(|((column_expr, alias), filter)| {
+                        Ok(CanonicalCountDistinct {
+                            column: column_expr.column_ref().into(),
+                            alias: alias.value.clone(),
+                            filter: filter
+                                .as_ref()
+                                .map(CanonicalExpr::try_from_proof_expr)
+                                .transpose()?,
+                        })
+                    })
+                    .collect::<CanonicalJsonResult<_>>()?,
+                table: group_by_exec.table().table_ref.clone(),
+                where_clause: CanonicalExpr::try_from_proof_expr(group_by_exec.where_clause())?,
+            },
+            DynProofPlan::Slice(slice_exec) => Self::Slice {
+                input: Box::new(Self::try_from_proof_plan(slice_exec.input())?),
+                skip: slice_exec.skip(),
+                fetch: slice_exec.fetch(),
+            },
+            DynProofPlan::Union(union_exec) => Self::Union {
+                inputs: union_exec
+                    .inputs()
+                    .iter()
+                    .map(Self::try_from_proof_plan)
+                    .collect::<CanonicalJsonResult<_>>()?,
+                schema: union_exec.schema().iter().map(Into::into).collect(),
+            },
+            DynProofPlan::GroupingSets(grouping_sets_exec) => Self::GroupingSets {
+                grouping_sets: grouping_sets_exec
+                    .grouping_sets()
+                    .iter()
+                    .map(|grouping_set| {
+                        grouping_set
+                            .iter()
+                            .map(|column_expr| column_expr.column_ref().into())
+                            .collect()
+                    })
+                    .collect(),
+                sums: try_from_aliased_exprs(grouping_sets_exec.sum_expr())?,
+                count_alias: grouping_sets_exec.count_alias().value.clone(),
+                grouping_id_alias: grouping_sets_exec.grouping_id_alias().value.clone(),
+                table: grouping_sets_exec.table().table_ref.clone(),
+                where_clause: CanonicalExpr::try_from_proof_expr(
+                    grouping_sets_exec.where_clause(),
+                )?,
+            },
+            DynProofPlan::GeneralizedFilter(generalized_filter_exec) => Self::GeneralizedFilter {
+                results: try_from_aliased_exprs(generalized_filter_exec.aliased_results())?,
+                input: Box::new(Self::try_from_proof_plan(generalized_filter_exec.input())?),
+                where_clause: CanonicalExpr::try_from_proof_expr(
+                    generalized_filter_exec.where_clause(),
+                )?,
+            },
+            DynProofPlan::SortMergeJoin(sort_merge_join_exec) => Self::SortMergeJoin {
+                left: Box::new(Self::try_from_proof_plan(sort_merge_join_exec.left())?),
+                right: Box::new(Self::try_from_proof_plan(sort_merge_join_exec.right())?),
+                left_join_column_indexes: sort_merge_join_exec.left_join_column_indexes().to_vec(),
+                right_join_column_indexes: sort_merge_join_exec
+                    .right_join_column_indexes()
+                    .to_vec(),
+                result_names: names(sort_merge_join_exec.result_idents()),
+            },
+            DynProofPlan::MultiCount(multi_count_exec) => Self::MultiCount {
+                predicates: try_from_aliased_exprs(multi_count_exec.predicates())?,
+                table: multi_count_exec.table().table_ref.clone(),
+            },
+            DynProofPlan::BucketCount(bucket_count_exec) => Self::BucketCount {
+                expr: CanonicalExpr::try_from_proof_expr(bucket_count_exec.expr())?,
+                boundaries: bucket_count_exec
+                    .boundaries()
+                    .iter()
+                    .map(Into::into)
+                    .collect(),
+                aliases: names(bucket_count_exec.aliases()),
+                table: bucket_count_exec.table().table_ref.clone(),
+            },
+            DynProofPlan::PercentageOfTotal(percentage_of_total_exec) => Self::PercentageOfTotal {
+                results: try_from_aliased_exprs(percentage_of_total_exec.aliased_results())?,
+                amount: CanonicalAliasedExpr::try_from_proof_expr(
+                    percentage_of_total_exec.amount(),
+                )?,
+                scale: percentage_of_total_exec.scale(),
+                input: Box::new(Self::try_from_proof_plan(percentage_of_total_exec.input())?),
+            },
+            DynProofPlan::LeftAntiJoin(left_anti_join_exec) => Self::LeftAntiJoin {
+                left: Box::new(Self::try_from_proof_plan(left_anti_join_exec.left())?),
+                right: Box::new(Self::try_from_proof_plan(left_anti_join_exec.right())?),
+                left_join_column_indexes: left_anti_join_exec.left_join_column_indexes().to_vec(),
+                right_join_column_indexes: left_anti_join_exec.right_join_column_indexes().to_vec(),
+            },
+            DynProofPlan::ContainmentCheck(containment_check_exec) => Self::ContainmentCheck {
+                child: Box::new(Self::try_from_proof_plan(containment_check_exec.child())?),
+                parent: Box::new(Self::try_from_proof_plan(containment_check_exec.parent())?),
+                child_column_index: containment_check_exec.child_column_index(),
+                parent_column_index: containment_check_exec.parent_column_index(),
+                alias: containment_check_exec.alias().value.clone(),
+            },
+            DynProofPlan::MonotonicCheck(monotonic_check_exec) => Self::MonotonicCheck {
+                input: Box::new(Self::try_from_proof_plan(monotonic_check_exec.input())?),
+                column_index: monotonic_check_exec.column_index(),
+                alias: monotonic_check_exec.alias().value.clone(),
+            },
+            DynProofPlan::PermutationCheck(permutation_check_exec) => Self::PermutationCheck {
+                source: Box::new(Self::try_from_proof_plan(permutation_check_exec.source())?),
+                candidate: Box::new(Self::try_from_proof_plan(
+                    permutation_check_exec.candidate(),
+                )?),
+                source_column_index: permutation_check_exec.source_column_index(),
+                candidate_column_index: permutation_check_exec.candidate_column_index(),
+                alias: permutation_check_exec.alias().value.clone(),
+            },
+            DynProofPlan::FirstPerGroup(first_per_group_exec) => Self::FirstPerGroup {
+                input: Box::new(Self::try_from_proof_plan(first_per_group_exec.input())?),
+                key_column_indexes: first_per_group_exec.key_column_indexes().to_vec(),
+                tiebreaker_column_index: first_per_group_exec.tiebreaker_column_index(),
+                tiebreaker_descending: first_per_group_exec.tiebreaker_descending(),
+            },
+        })
+    }
+
+    /// Try to create a `DynProofPlan` from a `CanonicalPlan`.
+    pub(super) fn try_into_proof_plan(&self) -> CanonicalJsonResult<DynProofPlan> {
+        Ok(match self {
+            Self::Empty => DynProofPlan::new_empty(),
+            Self::Table { table, schema } => {
+                DynProofPlan::new_table(table.clone(), try_into_column_fields(schema)?)
+            }
+            Self::Projection { results, input } => DynProofPlan::new_projection(
+                try_into_aliased_exprs(results)?,
+                input.try_into_proof_plan()?,
+            ),
+            Self::Filter {
+                results,
+                table,
+                where_clause,
+            } => DynProofPlan::new_filter(
+                try_into_aliased_exprs(results)?,
+                TableExpr {
+                    table_ref: table.clone(),
+                },
+                where_clause.try_into_proof_expr()?,
+            ),
+            Self::GroupBy {
+                group_by,
+                sums,
+                count_alias,
+                count_distinct,
+                table,
+                where_clause,
+            } => {
+                let count_distinct_exprs = count_distinct
+                    .iter()
+                    .map(|count_distinct| {
+                        let column_ref = ColumnRef::try_from(&count_distinct.column)?;
+                        // Other types would make the plan constructor panic
+                        if !matches!(
+                            column_ref.column_type(),
+                            ColumnType::Boolean
+                                | ColumnType::Uint8
+                                | ColumnType::TinyInt
+                                | ColumnType::SmallInt
+                                | ColumnType::Int
+                                | ColumnType::BigInt
+                                | ColumnType::TimestampTZ(_, _)
+                        ) {
+                            return Err(CanonicalJsonError::NotSupported {
+                                kind: "COUNT(DISTINCT) of this column type",
+                            });
+                        }
+                        let filter = count_distinct
+                            .filter
+                            .as_ref()
+                            .map(CanonicalExpr::try_into_proof_expr)
+                            .transpose()?;
+                        if filter
+                            .as_ref()
+                            .is_some_and(|filter| filter.data_type() != ColumnType::Boolean)
+                        {
+                            return Err(CanonicalJsonError::NotSupported {
+                                kind: "non-boolean COUNT(DISTINCT) filter",
+                            });
+                        }
+                        Ok((
+                            (
+                                ColumnExpr::new(column_ref),
+                                Ident::new(count_distinct.alias.as_str()),
+                            ),
+                            filter,
+                        ))
+                    })
+                    .collect::<CanonicalJsonResult<Vec<_>>>()?;
+                let (count_distinct_exprs, count_distinct_filters) =
+                    count_distinct_exprs.into_iter().unzip();
+                DynProofPlan::GroupBy(GroupByExec::new_with_filtered_count_distinct(
+                    group_by
+                        .iter()
+                        .map(|column_ref| Ok(ColumnExpr::new(column_ref.try_into()?)))
+                        .collect::<CanonicalJsonResult<_>>()?,
+                    try_into_aliased_exprs(sums)?,
+                    Ident::new(count_alias.as_str()),
+                    count_distinct_exprs,
+                    count_distinct_filters,
+                    TableExpr {
+                        table_ref: table.clone(),
+                    },
+                    where_clause.try_into_proof_expr()?,
+                ))
+            }
+            Self::Slice { input, skip, fetch } => {
+                DynProofPlan::new_slice(input.try_into_proof_plan()?, *skip, *fetch)
+            }
+            Self::Union { inputs, schema } => DynProofPlan::new_union(
+                inputs
+                    .iter()
+                    .map(Self::try_into_proof_plan)
+                    .collect::<CanonicalJsonResult<_>>()?,
+                try_into_column_fields(schema)?,
+            ),
+            Self::GroupingSets {
+                grouping_sets,
+                sums,
+                count_alias,
+                grouping_id_alias,
+                table,
+                where_clause,
+            } => DynProofPlan::try_new_grouping_sets(
+                grouping_sets
+                    .iter()
+                    .map(|grouping_set| try_into_column_exprs(grouping_set))
+                    .collect::<CanonicalJsonResult<_>>()?,
+                try_into_aliased_exprs(sums)?,
+                Ident::new(count_alias.as_str()),
+                TableExpr {
+                    table_ref: table.clone(),
+                },
+                where_clause.try_into_proof_expr()?,
+                Ident::new(grouping_id_alias.as_str()),
+            )?,
+            Self::GeneralizedFilter {
+                results,
+                input,
+                where_clause,
+            } => DynProofPlan::new_generalized_filter(
+                try_into_aliased_exprs(results)?,
+                input.try_into_proof_plan()?,
+                where_clause.try_into_proof_expr()?,
+            ),
+            Self::SortMergeJoin {
+                left,
+                right,
+                left_join_column_indexes,
+                right_join_column_indexes,
+                result_names,
+            } => {
+                let left = left.try_into_proof_plan()?;
+                let right = right.try_into_proof_plan()?;
+                // Checked here, since the plan constructor panics otherwise
+                if left_join_column_indexes.is_empty() {
+                    return Err(CanonicalJsonError::InvalidPlan {
+                        reason: "there are no join columns",
+                    });
+                }
+                try_result_column_types(&left, left_join_column_indexes)?;
+                try_result_column_types(&right, right_join_column_indexes)?;
+                if left_join_column_indexes.len() != right_join_column_indexes.len() {
+                    return Err(CanonicalJsonError::InvalidPlan {
+                        reason: "different numbers of columns on both sides",
+                    });
+                }
+                if left.get_column_result_fields().len() + right.get_column_result_fields().len()
+                    != result_names.len() + left_join_column_indexes.len()
+                {
+                    return Err(CanonicalJsonError::InvalidPlan {
+                        reason: "wrong number of join result names",
+                    });
+                }
+                DynProofPlan::SortMergeJoin(SortMergeJoinExec::new(
+                    Box::new(left),
+                    Box::new(right),
+                    left_join_column_indexes.clone(),
+                    right_join_column_indexes.clone(),
+                    idents(result_names),
+                ))
+            }
+            Self::MultiCount { predicates, table } => {
+                DynProofPlan::MultiCount(MultiCountExec::try_new(
+                    try_into_aliased_exprs(predicates)?,
+                    TableExpr {
+                        table_ref: table.clone(),
+                    },
+                )?)
+            }
+            Self::BucketCount {
+                expr,
+                boundaries,
+                aliases,
+                table,
+            } => DynProofPlan::BucketCount(BucketCountExec::try_new(
+                expr.try_into_proof_expr()?,
+                boundaries
+                    .iter()
+                    .map(TryInto::try_into)
+                    .collect::<CanonicalJsonResult<_>>()?,
+                idents(aliases),
+                TableExpr {
+                    table_ref: table.clone(),
+                },
+            )?),
+            Self::PercentageOfTotal {
+                results,
+                amount,
+                scale,
+                input,
+            } => DynProofPlan::try_new_percentage_of_total(
+                try_into_aliased_exprs(results)?,
+                amount.try_into_proof_expr()?,
+                *scale,
+                input.try_into_proof_plan()?,
+            )?,
+            Self::LeftAntiJoin {
+                left,
+                right,
+                left_join_column_indexes,
+                right_join_column_indexes,
+            } => {
+                let left = left.try_into_proof_plan()?;
+                let right = right.try_into_proof_plan()?;
+                check_matching_result_columns(
+                    &left,
+                    left_join_column_indexes,
+                    &right,
+                    right_join_column_indexes,
+                )?;
+                DynProofPlan::LeftAntiJoin(LeftAntiJoinExec::new(
+                    Box::new(left),
+                    Box::new(right),
+                    left_join_column_indexes.clone(),
+                    right_join_column_indexes.clone(),
+                ))
+            }
+            Self::ContainmentCheck {
+                child,
+                parent,
+                child_column_index,
+                parent_column_index,
+                alias,
+            } => {
+                let child = child.try_into_proof_plan()?;
+                let parent = parent.try_into_proof_plan()?;
+                check_matching_result_columns(
+                    &child,
+                    &[*child_column_index],
+                    &parent,
+                    &[*parent_column_index],
+                )?;
+                DynProofPlan::ContainmentCheck(ContainmentCheckExec::new(
+                    Box::new(child),
+                    Box::new(parent),
+                    *child_column_index,
+                    *parent_column_index,
+                    Ident::new(alias.as_str()),
+                ))
+            }
+            Self::MonotonicCheck {
+                input,
+                column_index,
+                alias,
+            } => {
+                let input = input.try_into_proof_plan()?;
+                let column_type = try_result_column_types(&input, &[*column_index])?[0];
+                if !(column_type.is_integer()
+                    || matches!(column_type, ColumnType::TimestampTZ(_, _)))
+                {
+                    return Err(CanonicalJsonError::InvalidPlan {
+                        reason: "monotonic column is not an integer or a timestamp",
+                    });
+                }
+                DynProofPlan::MonotonicCheck(MonotonicCheckExec::new(
+                    Box::new(input),
+                    *column_index,
+                    Ident::new(alias.as_str()),
+                ))
+            }
+            Self::PermutationCheck {
+                source,
+                candidate,
+                source_column_index,
+                candidate_column_index,
+                alias,
+            } => {
+                let source = source.try_into_proof_plan()?;
+                let candidate = candidate.try_into_proof_plan()?;
+                check_matching_result_columns(
+                    &source,
+                    &[*source_column_index],
+                    &candidate,
+                    &[*candidate_column_index],
+                )?;
+                DynProofPlan::PermutationCheck(PermutationCheckExec::new(
+                    Box::new(source),
+                    Box::new(candidate),
+                    *source_column_index,
+                    *candidate_column_index,
+                    Ident::new(alias.as_str()),
+                ))
+            }
+            Self::FirstPerGroup {
+                input,
+                key_column_indexes,
+                tiebreaker_column_index,
+                tiebreaker_descending,
+            } => DynProofPlan::try_new_first_per_group(
+                input.try_into_proof_plan()?,
+                key_column_indexes.clone(),
+                *tiebreaker_column_index,
+                *tiebreaker_descending,
+            )?,
+        })
+    }
+}
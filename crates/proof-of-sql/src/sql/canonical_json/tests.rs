@@ -0,0 +1,758 @@
+use super::{from_canonical_json, to_canonical_json, CanonicalJsonError};
+use crate::{
+    base::{
+        database::{ColumnField, ColumnRef, ColumnType, LiteralValue, TableRef},
+        math::{decimal::Precision, i256::I256},
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    },
+    sql::{
+        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
+        proof_plans::{BucketCountExec, DynProofPlan, GroupByExec, MultiCountExec},
+    },
+};
+use serde_json::Value;
+
+fn bench_table() -> TableRef {
+    TableRef::from_names(None, "bench_table")
+}
+
+fn column(name: &str, column_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::new_column(ColumnRef::new(bench_table(), name.into(), column_type))
+}
+
+fn column_expr(name: &str, column_type: ColumnType) -> ColumnExpr {
+    ColumnExpr::new(ColumnRef::new(bench_table(), name.into(), column_type))
+}
+
+fn placeholder(id: usize, column_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::try_new_placeholder(id, column_type).unwrap()
+}
+
+fn aliased(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr,
+        alias: alias.into(),
+    }
+}
+
+fn table_expr() -> TableExpr {
+    TableExpr {
+        table_ref: bench_table(),
+    }
+}
+
+/// `a <= b`, as planned from SQL
+fn lte(lhs: DynProofExpr, rhs: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_not(DynProofExpr::try_new_inequality(lhs, rhs, false).unwrap()).unwrap()
+}
+
+/// Checks that the plan serializes to the fixture and that the fixture deserializes to the plan.
+fn assert_matches_fixture(plan: &DynProofPlan, fixture: &str) {
+    let json = to_canonical_json(plan).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(&json).unwrap(),
+        serde_json::from_str::<Value>(fixture).unwrap()
+    );
+    assert_eq!(&from_canonical_json(fixture).unwrap(), plan);
+    assert_eq!(&from_canonical_json(&json).unwrap(), plan);
+}
+
+fn assert_round_trips(plan: &DynProofPlan) {
+    let json = to_canonical_json(plan).unwrap();
+    assert_eq!(&from_canonical_json(&json).unwrap(), plan);
+}
+
+/// `SELECT b FROM bench_table WHERE a = $1`
+#[test]
+fn we_can_serialize_the_single_column_filter_bench_query() {
+    let plan = DynProofPlan::new_filter(
+        vec![aliased(column("b", ColumnType::VarChar), "b")],
+        table_expr(),
+        DynProofExpr::try_new_equals(
+            column("a", ColumnType::BigInt),
+            placeholder(1, ColumnType::BigInt),
+        )
+        .unwrap(),
+    );
+    assert_matches_fixture(
+        &plan,
+        include_str!("../../../canonical_json/v1/fixtures/single_column_filter.json"),
+    );
+}
+
+/// `SELECT a + b AS r0, a * b - $1 AS r1, c FROM bench_table WHERE a <= b AND a >= $2`
+#[test]
+fn we_can_serialize_the_arithmetic_bench_query() {
+    let a = || column("a", ColumnType::BigInt);
+    let b = || column("b", ColumnType::TinyInt);
+    let plan = DynProofPlan::new_filter(
+        vec![
+            aliased(DynProofExpr::try_new_add(a(), b()).unwrap(), "r0"),
+            aliased(
+                DynProofExpr::try_new_subtract(
+                    DynProofExpr::try_new_multiply(a(), b()).unwrap(),
+                    placeholder(1, ColumnType::BigInt),
+                )
+                .unwrap(),
+                "r1",
+            ),
+            aliased(column("c", ColumnType::VarChar), "c"),
+        ],
+        table_expr(),
+        DynProofExpr::try_new_and(
+            lte(a(), b()),
+            DynProofExpr::try_new_not(
+                DynProofExpr::try_new_inequality(a(), placeholder(2, ColumnType::BigInt), true)
+                    .unwrap(),
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+    );
+    assert_matches_fixture(
+        &plan,
+        include_str!("../../../canonical_json/v1/fixtures/arithmetic.json"),
+    );
+}
+
+/// `SELECT a, COUNT(*) FROM bench_table WHERE (c = $1) and (a <= b) and (a > $2) GROUP BY a`
+#[test]
+fn we_can_serialize_the_group_by_bench_query() {
+    let a = || column("a", ColumnType::Int);
+    let plan = DynProofPlan::new_group_by(
+        vec![column_expr("a", ColumnType::Int)],
+        vec![],
+        "__count__".into(),
+        table_expr(),
+        DynProofExpr::try_new_and(
+            DynProofExpr::try_new_and(
+                DynProofExpr::try_new_equals(
+                    column("c", ColumnType::Boolean),
+                    placeholder(1, ColumnType::Boolean),
+                )
+                .unwrap(),
+                lte(a(), column("b", ColumnType::Int)),
+            )
+            .unwrap(),
+            DynProofExpr::try_new_inequality(a(), placeholder(2, ColumnType::Int), false).unwrap(),
+        )
+        .unwrap(),
+    );
+    assert_matches_fixture(
+        &plan,
+        include_str!("../../../canonical_json/v1/fixtures/group_by.json"),
+    );
+}
+
+/// `SELECT SUM(a) AS foo, COUNT(1) AS values FROM bench_table WHERE a = b OR c = $1`
+#[test]
+fn we_can_serialize_the_aggregate_bench_query() {
+    let plan = DynProofPlan::new_group_by(
+        vec![],
+        vec![aliased(column("a", ColumnType::BigInt), "foo")],
+        "values".into(),
+        table_expr(),
+        DynProofExpr::try_new_or(
+            DynProofExpr::try_new_equals(
+                column("a", ColumnType::BigInt),
+                column("b", ColumnType::BigInt),
+            )
+            .unwrap(),
+            DynProofExpr::try_new_equals(
+                column("c", ColumnType::VarChar),
+                placeholder(1, ColumnType::VarChar),
+            )
+            .unwrap(),
+        )
+        .unwrap(),
+    );
+    assert_matches_fixture(
+        &plan,
+        include_str!("../../../canonical_json/v1/fixtures/aggregate.json"),
+    );
+}
+
+#[test]
+fn we_can_round_trip_literals_of_every_type() {
+    let literals = [
+        LiteralValue::Boolean(false),
+        LiteralValue::Uint8(u8::MAX),
+        LiteralValue::TinyInt(i8::MIN),
+        LiteralValue::SmallInt(i16::MIN),
+        LiteralValue::Int(i32::MIN),
+        LiteralValue::BigInt(i64::MIN),
+        LiteralValue::Int128(i128::MAX),
+        LiteralValue::Decimal75(Precision::new(75).unwrap(), -2, I256::from(-12_345)),
+        LiteralValue::Scalar([u64::MAX, 1, 2, 3]),
+        LiteralValue::VarChar("caf\u{e9} \"quoted\"".to_string()),
+        LiteralValue::VarBinary(vec![0, 1, 255]),
+        LiteralValue::TimeStampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::new(3600), -1),
+    ];
+    let plan = DynProofPlan::new_filter(
+        literals
+            .iter()
+            .enumerate()
+            .map(|(i, literal)| {
+                aliased(
+                    DynProofExpr::new_literal(literal.clone()),
+                    &format!("literal_{i}"),
+                )
+            })
+            .collect(),
+        table_expr(),
+        DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+    );
+    assert_round_trips(&plan);
+
+    // Integers wider than 32 bits are strings so that no precision is lost
+    let json: Value = serde_json::from_str(&to_canonical_json(&plan).unwrap()).unwrap();
+    let value = |i: usize| json["plan"]["results"][i]["expr"]["literal"]["value"].clone();
+    assert_eq!(value(4), Value::from(i32::MIN));
+    assert_eq!(value(5), Value::from(i64::MIN.to_string()));
+    assert_eq!(value(7), Value::from("-12345"));
+    assert_eq!(value(10), Value::from(vec![0, 1, 255]));
+}
+
+#[test]
+fn we_can_round_trip_casts_and_nested_plans() {
+    let plan = DynProofPlan::new_slice(
+        DynProofPlan::new_union(
+            vec![
+                DynProofPlan::new_projection(
+                    vec![
+                        aliased(
+                            DynProofExpr::try_new_cast(
+                                column("a", ColumnType::Int),
+                                ColumnType::BigInt,
+                            )
+                            .unwrap(),
+                            "a",
+                        ),
+                        aliased(
+                            DynProofExpr::try_new_scaling_cast(
+                                column("b", ColumnType::BigInt),
+                                ColumnType::Decimal75(Precision::new(25).unwrap(), 2),
+                            )
+                            .unwrap(),
+                            "b",
+                        ),
+                    ],
+                    DynProofPlan::new_table(
+                        bench_table(),
+                        vec![
+                            ColumnField::new("a".into(), ColumnType::Int),
+                            ColumnField::new("b".into(), ColumnType::BigInt),
+                        ],
+                    ),
+                ),
+                DynProofPlan::new_empty(),
+            ],
+            vec![
+                ColumnField::new("a".into(), ColumnType::BigInt),
+                ColumnField::new(
+                    "b".into(),
+                    ColumnType::Decimal75(Precision::new(25).unwrap(), 2),
+                ),
+            ],
+        ),
+        1,
+        Some(2),
+    );
+    assert_round_trips(&plan);
+    assert_round_trips(&DynProofPlan::new_slice(DynProofPlan::new_empty(), 0, None));
+}
+
+#[test]
+fn we_can_round_trip_a_group_by_with_count_distinct() {
+    let plan = DynProofPlan::GroupBy(GroupByExec::new_with_count_distinct(
+        vec![column_expr("a", ColumnType::VarChar)],
+        vec![],
+        "__count__".into(),
+        vec![(
+            column_expr(
+                "t",
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            ),
+            "distinct_t".into(),
+        )],
+        table_expr(),
+        DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+    ));
+    assert_round_trips(&plan);
+}
+
+#[test]
+fn we_can_round_trip_a_group_by_with_filtered_count_distinct() {
+    let plan = DynProofPlan::GroupBy(GroupByExec::new_with_filtered_count_distinct(
+        vec![column_expr("a", ColumnType::VarChar)],
+        vec![],
+        "__count__".into(),
+        vec![
+            (column_expr("b", ColumnType::BigInt), "distinct_b".into()),
+            (
+                column_expr("b", ColumnType::BigInt),
+                "filtered_distinct_b".into(),
+            ),
+        ],
+        vec![
+            None,
+            Some(
+                DynProofExpr::try_new_equals(
+                    column("c", ColumnType::BigInt),
+                    placeholder(1, ColumnType::BigInt),
+                )
+                .unwrap(),
+            ),
+        ],
+        table_expr(),
+        DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+    ));
+    assert_round_trips(&plan);
+    // Distinct counts without a filter serialize as before
+    let json = to_canonical_json(&plan).unwrap();
+    assert!(json.contains(r#"{"column":{"table":"bench_table","column":"b","type":{"type":"bigint"}},"alias":"distinct_b"}"#));
+}
+
+fn true_literal() -> DynProofExpr {
+    DynProofExpr::new_literal(LiteralValue::Boolean(true))
+}
+
+fn bigint_literal(value: i64) -> DynProofExpr {
+    DynProofExpr::new_literal(LiteralValue::BigInt(value))
+}
+
+/// Checks that a filter with `expr` as its only result round trips.
+fn assert_expr_round_trips(expr: DynProofExpr) {
+    assert_round_trips(&DynProofPlan::new_filter(
+        vec![aliased(expr, "x")],
+        table_expr(),
+        true_literal(),
+    ));
+}
+
+/// A table plan over `table` with the columns `a BIGINT, b BIGINT, c VARCHAR`
+fn table_plan(table: &str) -> DynProofPlan {
+    DynProofPlan::new_table(
+        TableRef::from_names(None, table),
+        vec![
+            ColumnField::new("a".into(), ColumnType::BigInt),
+            ColumnField::new("b".into(), ColumnType::BigInt),
+            ColumnField::new("c".into(), ColumnType::VarChar),
+        ],
+    )
+}
+
+#[test]
+fn we_can_round_trip_an_if_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_if(
+            column("flag", ColumnType::Boolean),
+            column("a", ColumnType::BigInt),
+            column("b", ColumnType::BigInt),
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_a_round_to_multiple_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_round_to_multiple(
+            column("a", ColumnType::BigInt),
+            &bigint_literal(10),
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_a_modulo_expression() {
+    let expr =
+        DynProofExpr::try_new_modulo(column("a", ColumnType::BigInt), &bigint_literal(-7)).unwrap();
+    // The modulus is a typed literal, like every other literal
+    let json = to_canonical_json(&DynProofPlan::new_filter(
+        vec![aliased(expr.clone(), "x")],
+        table_expr(),
+        true_literal(),
+    ))
+    .unwrap();
+    assert!(json.contains(r#""modulus":{"type":{"type":"int128"},"value":"-7"}"#));
+    assert_expr_round_trips(expr);
+}
+
+#[test]
+fn we_can_round_trip_a_pow_mod_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_pow_mod(
+            column("a", ColumnType::BigInt),
+            &bigint_literal(3),
+            &bigint_literal(11),
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_a_decimal_rescale_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_decimal_rescale(
+            column("d", ColumnType::Decimal75(Precision::new(10).unwrap(), 4)),
+            ColumnType::Decimal75(Precision::new(9).unwrap(), 2),
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_a_row_hash_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_row_hash(vec![
+            column_expr("c", ColumnType::VarChar),
+            column_expr("a", ColumnType::BigInt),
+        ])
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_an_ln_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_ln(column(
+            "d",
+            ColumnType::Decimal75(Precision::new(10).unwrap(), 4),
+        ))
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_an_isqrt_expression() {
+    assert_expr_round_trips(DynProofExpr::try_new_isqrt(column("a", ColumnType::BigInt)).unwrap());
+}
+
+#[test]
+fn we_can_round_trip_a_derived_key_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_derived_key(
+            *b"customer-keys-v1",
+            vec![
+                column_expr("a", ColumnType::BigInt),
+                column_expr("c", ColumnType::VarChar),
+            ],
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_an_in_expression() {
+    assert_expr_round_trips(
+        DynProofExpr::try_new_in(
+            column("a", ColumnType::BigInt),
+            vec![LiteralValue::BigInt(1), LiteralValue::BigInt(-2)],
+        )
+        .unwrap(),
+    );
+    assert_expr_round_trips(
+        DynProofExpr::try_new_in(column("c", ColumnType::VarChar), vec![]).unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_a_grouping_sets_plan() {
+    assert_round_trips(
+        &DynProofPlan::try_new_grouping_sets(
+            vec![
+                vec![
+                    column_expr("a", ColumnType::BigInt),
+                    column_expr("c", ColumnType::VarChar),
+                ],
+                vec![column_expr("c", ColumnType::VarChar)],
+                vec![],
+            ],
+            vec![aliased(column("b", ColumnType::BigInt), "sum_b")],
+            "__count__".into(),
+            table_expr(),
+            true_literal(),
+            "grouping_id".into(),
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_a_generalized_filter_plan() {
+    assert_round_trips(&DynProofPlan::new_generalized_filter(
+        vec![aliased(column("c", ColumnType::VarChar), "c")],
+        table_plan("bench_table"),
+        DynProofExpr::try_new_equals(column("a", ColumnType::BigInt), bigint_literal(1)).unwrap(),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_sort_merge_join_plan() {
+    assert_round_trips(&DynProofPlan::SortMergeJoin(
+        crate::sql::proof_plans::SortMergeJoinExec::new(
+            Box::new(table_plan("left_table")),
+            Box::new(table_plan("right_table")),
+            vec![0, 2],
+            vec![0, 2],
+            vec!["a".into(), "c".into(), "left_b".into(), "right_b".into()],
+        ),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_multi_count_plan() {
+    assert_round_trips(&DynProofPlan::MultiCount(
+        MultiCountExec::try_new(
+            vec![
+                aliased(
+                    DynProofExpr::try_new_equals(
+                        column("a", ColumnType::BigInt),
+                        bigint_literal(1),
+                    )
+                    .unwrap(),
+                    "ones",
+                ),
+                aliased(true_literal(), "total"),
+            ],
+            table_expr(),
+        )
+        .unwrap(),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_bucket_count_plan() {
+    assert_round_trips(&DynProofPlan::BucketCount(
+        BucketCountExec::try_new(
+            column("a", ColumnType::BigInt),
+            vec![LiteralValue::BigInt(0), LiteralValue::BigInt(10)],
+            vec!["low".into(), "mid".into(), "high".into()],
+            table_expr(),
+        )
+        .unwrap(),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_percentage_of_total_plan() {
+    assert_round_trips(
+        &DynProofPlan::try_new_percentage_of_total(
+            vec![aliased(column("c", ColumnType::VarChar), "c")],
+            aliased(column("a", ColumnType::BigInt), "share"),
+            2,
+            table_plan("bench_table"),
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn we_can_round_trip_a_left_anti_join_plan() {
+    assert_round_trips(&DynProofPlan::LeftAntiJoin(
+        crate::sql::proof_plans::LeftAntiJoinExec::new(
+            Box::new(table_plan("left_table")),
+            Box::new(table_plan("right_table")),
+            vec![1],
+            vec![0],
+        ),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_containment_check_plan() {
+    assert_round_trips(&DynProofPlan::ContainmentCheck(
+        crate::sql::proof_plans::ContainmentCheckExec::new(
+            Box::new(table_plan("child_table")),
+            Box::new(table_plan("parent_table")),
+            2,
+            2,
+            "contained".into(),
+        ),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_monotonic_check_plan() {
+    assert_round_trips(&DynProofPlan::MonotonicCheck(
+        crate::sql::proof_plans::MonotonicCheckExec::new(
+            Box::new(table_plan("bench_table")),
+            1,
+            "sorted".into(),
+        ),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_permutation_check_plan() {
+    assert_round_trips(&DynProofPlan::PermutationCheck(
+        crate::sql::proof_plans::PermutationCheckExec::new(
+            Box::new(table_plan("source_table")),
+            Box::new(table_plan("candidate_table")),
+            0,
+            1,
+            "is_permutation".into(),
+        ),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_a_first_per_group_plan() {
+    assert_round_trips(
+        &DynProofPlan::try_new_first_per_group(table_plan("bench_table"), vec![2], 0, true)
+            .unwrap(),
+    );
+}
+
+/// A document of a plan checking the `index`-th column of `bench_table`, whose only column is
+/// `c VARCHAR`
+fn monotonic_check_of_column(index: usize) -> String {
+    format!(
+        r#"{{"version":1,"plan":{{"type":"monotonic_check","input":{{"type":"table","table":"bench_table","schema":[{{"name":"c","type":{{"type":"varchar"}}}}]}},"column_index":{index},"alias":"sorted"}}}}"#
+    )
+}
+
+#[test]
+fn we_cannot_deserialize_plans_whose_constructors_would_panic() {
+    assert_eq!(
+        from_canonical_json(&monotonic_check_of_column(1)),
+        Err(CanonicalJsonError::InvalidPlan {
+            reason: "column index out of bounds"
+        })
+    );
+    assert_eq!(
+        from_canonical_json(&monotonic_check_of_column(0)),
+        Err(CanonicalJsonError::InvalidPlan {
+            reason: "monotonic column is not an integer or a timestamp"
+        })
+    );
+    let table = r#"{"type":"table","table":"bench_table","schema":[{"name":"a","type":{"type":"bigint"}},{"name":"c","type":{"type":"varchar"}}]}"#;
+    assert_eq!(
+        from_canonical_json(&format!(
+            r#"{{"version":1,"plan":{{"type":"permutation_check","source":{table},"candidate":{table},"source_column_index":0,"candidate_column_index":1,"alias":"p"}}}}"#
+        )),
+        Err(CanonicalJsonError::InvalidPlan {
+            reason: "columns of different types on both sides"
+        })
+    );
+    assert_eq!(
+        from_canonical_json(&format!(
+            r#"{{"version":1,"plan":{{"type":"sort_merge_join","left":{table},"right":{table},"left_join_column_indexes":[0],"right_join_column_indexes":[0],"result_names":["a","c"]}}}}"#
+        )),
+        Err(CanonicalJsonError::InvalidPlan {
+            reason: "wrong number of join result names"
+        })
+    );
+}
+
+#[test]
+fn we_cannot_serialize_a_group_by_with_a_computed_key() {
+    let plan = DynProofPlan::GroupBy(GroupByExec::new_with_aliased_group_by_exprs(
+        vec![aliased(
+            DynProofExpr::try_new_modulo(
+                column("a", ColumnType::BigInt),
+                &DynProofExpr::new_literal(LiteralValue::BigInt(10)),
+            )
+            .unwrap(),
+            "bucket",
+        )],
+        vec![],
+        "__count__".into(),
+        vec![],
+        vec![],
+        table_expr(),
+        DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+    ));
+    assert_eq!(
+        to_canonical_json(&plan),
+        Err(CanonicalJsonError::NotSupported {
+            kind: "GROUP BY expression"
+        })
+    );
+}
+
+#[test]
+fn we_cannot_deserialize_other_versions() {
+    assert_eq!(
+        from_canonical_json(r#"{"version":2,"plan":{"type":"from_the_future"}}"#),
+        Err(CanonicalJsonError::UnsupportedVersion {
+            version: 2,
+            expected: 1
+        })
+    );
+    assert!(matches!(
+        from_canonical_json(r#"{"plan":{"type":"empty"}}"#),
+        Err(CanonicalJsonError::InvalidJson { .. })
+    ));
+}
+
+fn filter_with_result(expr: &str) -> String {
+    format!(
+        r#"{{"version":1,"plan":{{"type":"filter","results":[{{"alias":"x","expr":{expr}}}],"table":"bench_table","where":{{"type":"literal","literal":{{"type":{{"type":"boolean"}},"value":true}}}}}}}}"#
+    )
+}
+
+#[test]
+fn we_cannot_deserialize_invalid_documents() {
+    // A literal which is out of range for its type
+    assert_eq!(
+        from_canonical_json(&filter_with_result(
+            r#"{"type":"literal","literal":{"type":{"type":"tinyint"},"value":300}}"#
+        )),
+        Err(CanonicalJsonError::InvalidLiteral {
+            column_type: "TINYINT".to_string()
+        })
+    );
+    // A wide integer which is not a string
+    assert_eq!(
+        from_canonical_json(&filter_with_result(
+            r#"{"type":"literal","literal":{"type":{"type":"bigint"},"value":1}}"#
+        )),
+        Err(CanonicalJsonError::InvalidLiteral {
+            column_type: "BIGINT".to_string()
+        })
+    );
+    // A decimal precision which is out of range
+    assert_eq!(
+        from_canonical_json(&filter_with_result(
+            r#"{"type":"literal","literal":{"type":{"type":"decimal75","precision":80,"scale":0},"value":"1"}}"#
+        )),
+        Err(CanonicalJsonError::InvalidPrecision { precision: 80 })
+    );
+    // An ill-typed expression
+    assert!(matches!(
+        from_canonical_json(&filter_with_result(
+            r#"{"type":"equals","lhs":{"type":"literal","literal":{"type":{"type":"boolean"},"value":true}},"rhs":{"type":"literal","literal":{"type":{"type":"varchar"},"value":"a"}}}"#
+        )),
+        Err(CanonicalJsonError::AnalyzeError { .. })
+    ));
+    // An unknown field
+    assert!(matches!(
+        from_canonical_json(&filter_with_result(
+            r#"{"type":"not","expr":{"type":"literal","literal":{"type":{"type":"boolean"},"value":true}},"extra":1}"#
+        )),
+        Err(CanonicalJsonError::InvalidJson { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_deserialize_a_count_distinct_of_an_unsupported_type() {
+    let json = r#"{"version":1,"plan":{"type":"group_by","group_by":[],"sums":[],"count_alias":"__count__","count_distinct":[{"column":{"table":"bench_table","column":"a","type":{"type":"varchar"}},"alias":"distinct_a"}],"table":"bench_table","where":{"type":"literal","literal":{"type":{"type":"boolean"},"value":true}}}}"#;
+    assert!(matches!(
+        from_canonical_json(json),
+        Err(CanonicalJsonError::NotSupported { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_deserialize_a_count_distinct_with_a_non_boolean_filter() {
+    let json = r#"{"version":1,"plan":{"type":"group_by","group_by":[],"sums":[],"count_alias":"__count__","count_distinct":[{"column":{"table":"bench_table","column":"a","type":{"type":"bigint"}},"alias":"distinct_a","filter":{"type":"column","column":{"table":"bench_table","column":"a","type":{"type":"bigint"}}}}],"table":"bench_table","where":{"type":"literal","literal":{"type":{"type":"boolean"},"value":true}}}}"#;
+    assert_eq!(
+        from_canonical_json(json),
+        Err(CanonicalJsonError::NotSupported {
+            kind: "non-boolean COUNT(DISTINCT) filter"
+        })
+    );
+}
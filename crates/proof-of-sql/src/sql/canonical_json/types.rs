@@ -0,0 +1,299 @@
+use super::{CanonicalJsonError, CanonicalJsonResult};
+use crate::base::{
+    database::{ColumnField, ColumnRef, ColumnType, LiteralValue, TableRef},
+    math::{decimal::Precision, i256::I256},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use num_bigint::{BigInt, Sign};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlparser::ast::Ident;
+
+/// The unit of a timestamp type.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum CanonicalTimeUnit {
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+impl From<PoSQLTimeUnit> for CanonicalTimeUnit {
+    fn from(unit: PoSQLTimeUnit) -> Self {
+        match unit {
+            PoSQLTimeUnit::Second => Self::Second,
+            PoSQLTimeUnit::Millisecond => Self::Millisecond,
+            PoSQLTimeUnit::Microsecond => Self::Microsecond,
+            PoSQLTimeUnit::Nanosecond => Self::Nanosecond,
+        }
+    }
+}
+
+impl From<CanonicalTimeUnit> for PoSQLTimeUnit {
+    fn from(unit: CanonicalTimeUnit) -> Self {
+        match unit {
+            CanonicalTimeUnit::Second => Self::Second,
+            CanonicalTimeUnit::Millisecond => Self::Millisecond,
+            CanonicalTimeUnit::Microsecond => Self::Microsecond,
+            CanonicalTimeUnit::Nanosecond => Self::Nanosecond,
+        }
+    }
+}
+
+/// A column type, tagged by `type`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub(super) enum CanonicalColumnType {
+    Boolean,
+    Uint8,
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Int128,
+    VarChar,
+    VarBinary,
+    Scalar,
+    Decimal75 {
+        precision: u8,
+        scale: i8,
+    },
+    Timestamp {
+        unit: CanonicalTimeUnit,
+        offset_seconds: i32,
+    },
+}
+
+impl From<ColumnType> for CanonicalColumnType {
+    fn from(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::Boolean => Self::Boolean,
+            ColumnType::Uint8 => Self::Uint8,
+            ColumnType::TinyInt => Self::TinyInt,
+            ColumnType::SmallInt => Self::SmallInt,
+            ColumnType::Int => Self::Int,
+            ColumnType::BigInt => Self::BigInt,
+            ColumnType::Int128 => Self::Int128,
+            ColumnType::VarChar => Self::VarChar,
+            ColumnType::VarBinary => Self::VarBinary,
+            ColumnType::Scalar => Self::Scalar,
+            ColumnType::Decimal75(precision, scale) => Self::Decimal75 {
+                precision: precision.value(),
+                scale,
+            },
+            ColumnType::TimestampTZ(unit, timezone) => Self::Timestamp {
+                unit: unit.into(),
+                offset_seconds: timezone.offset(),
+            },
+        }
+    }
+}
+
+impl TryFrom<CanonicalColumnType> for ColumnType {
+    type Error = CanonicalJsonError;
+
+    fn try_from(column_type: CanonicalColumnType) -> CanonicalJsonResult<Self> {
+        Ok(match column_type {
+            CanonicalColumnType::Boolean => Self::Boolean,
+            CanonicalColumnType::Uint8 => Self::Uint8,
+            CanonicalColumnType::TinyInt => Self::TinyInt,
+            CanonicalColumnType::SmallInt => Self::SmallInt,
+            CanonicalColumnType::Int => Self::Int,
+            CanonicalColumnType::BigInt => Self::BigInt,
+            CanonicalColumnType::Int128 => Self::Int128,
+            CanonicalColumnType::VarChar => Self::VarChar,
+            CanonicalColumnType::VarBinary => Self::VarBinary,
+            CanonicalColumnType::Scalar => Self::Scalar,
+            CanonicalColumnType::Decimal75 { precision, scale } => Self::Decimal75(
+                Precision::new(precision)
+                    .map_err(|_| CanonicalJsonError::InvalidPrecision { precision })?,
+                scale,
+            ),
+            CanonicalColumnType::Timestamp {
+                unit,
+                offset_seconds,
+            } => Self::TimestampTZ(unit.into(), PoSQLTimeZone::new(offset_seconds)),
+        })
+    }
+}
+
+/// A named column of a schema.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct CanonicalColumnField {
+    name: String,
+    #[serde(rename = "type")]
+    column_type: CanonicalColumnType,
+}
+
+impl From<&ColumnField> for CanonicalColumnField {
+    fn from(field: &ColumnField) -> Self {
+        Self {
+            name: field.name().value,
+            column_type: field.data_type().into(),
+        }
+    }
+}
+
+impl TryFrom<&CanonicalColumnField> for ColumnField {
+    type Error = CanonicalJsonError;
+
+    fn try_from(field: &CanonicalColumnField) -> CanonicalJsonResult<Self> {
+        Ok(ColumnField::new(
+            Ident::new(field.name.as_str()),
+            field.column_type.try_into()?,
+        ))
+    }
+}
+
+/// A typed reference to a column of a table.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct CanonicalColumnRef {
+    table: TableRef,
+    column: String,
+    #[serde(rename = "type")]
+    column_type: CanonicalColumnType,
+}
+
+impl From<&ColumnRef> for CanonicalColumnRef {
+    fn from(column_ref: &ColumnRef) -> Self {
+        Self {
+            table: column_ref.table_ref(),
+            column: column_ref.column_id().value,
+            column_type: (*column_ref.column_type()).into(),
+        }
+    }
+}
+
+impl TryFrom<&CanonicalColumnRef> for ColumnRef {
+    type Error = CanonicalJsonError;
+
+    fn try_from(column_ref: &CanonicalColumnRef) -> CanonicalJsonResult<Self> {
+        Ok(ColumnRef::new(
+            column_ref.table.clone(),
+            Ident::new(column_ref.column.as_str()),
+            column_ref.column_type.try_into()?,
+        ))
+    }
+}
+
+/// A typed literal.
+///
+/// Integers of at most 32 bits are JSON numbers. Wider integers, the unscaled values of decimals,
+/// timestamps and scalars are decimal strings, so that no precision is lost by JSON parsers which
+/// represent numbers as doubles. Binary values are arrays of bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub(super) struct CanonicalLiteral {
+    #[serde(rename = "type")]
+    column_type: CanonicalColumnType,
+    value: Value,
+}
+
+impl From<&LiteralValue> for CanonicalLiteral {
+    fn from(literal: &LiteralValue) -> Self {
+        let value = match literal {
+            LiteralValue::Boolean(value) => Value::from(*value),
+            LiteralValue::Uint8(value) => Value::from(*value),
+            LiteralValue::TinyInt(value) => Value::from(*value),
+            LiteralValue::SmallInt(value) => Value::from(*value),
+            LiteralValue::Int(value) => Value::from(*value),
+            LiteralValue::BigInt(value) | LiteralValue::TimeStampTZ(_, _, value) => {
+                Value::from(value.to_string())
+            }
+            LiteralValue::Int128(value) => Value::from(value.to_string()),
+            LiteralValue::Decimal75(_, _, value) => Value::from(value.to_num_bigint().to_string()),
+            LiteralValue::Scalar(limbs) => {
+                let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+                Value::from(BigInt::from_bytes_le(Sign::Plus, &bytes).to_string())
+            }
+            LiteralValue::VarChar(value) => Value::from(value.as_str()),
+            LiteralValue::VarBinary(value) => Value::from(value.as_slice()),
+        };
+        Self {
+            column_type: literal.column_type().into(),
+            value,
+        }
+    }
+}
+
+impl TryFrom<&CanonicalLiteral> for LiteralValue {
+    type Error = CanonicalJsonError;
+
+    fn try_from(literal: &CanonicalLiteral) -> CanonicalJsonResult<Self> {
+        let column_type = ColumnType::try_from(literal.column_type)?;
+        let invalid = || CanonicalJsonError::InvalidLiteral {
+            column_type: column_type.to_string(),
+        };
+        let as_i64 = |value: &Value| value.as_i64().ok_or_else(invalid);
+        let parse_bigint = |value: &Value| {
+            value
+                .as_str()
+                .and_then(|value| value.parse::<BigInt>().ok())
+                .ok_or_else(invalid)
+        };
+        Ok(match (column_type, &literal.value) {
+            (ColumnType::Boolean, Value::Bool(value)) => LiteralValue::Boolean(*value),
+            (ColumnType::Uint8, value) => LiteralValue::Uint8(
+                value
+                    .as_u64()
+                    .and_then(|value| u8::try_from(value).ok())
+                    .ok_or_else(invalid)?,
+            ),
+            (ColumnType::TinyInt, value) => {
+                LiteralValue::TinyInt(i8::try_from(as_i64(value)?).map_err(|_| invalid())?)
+            }
+            (ColumnType::SmallInt, value) => {
+                LiteralValue::SmallInt(i16::try_from(as_i64(value)?).map_err(|_| invalid())?)
+            }
+            (ColumnType::Int, value) => {
+                LiteralValue::Int(i32::try_from(as_i64(value)?).map_err(|_| invalid())?)
+            }
+            (ColumnType::BigInt, Value::String(value)) => {
+                LiteralValue::BigInt(value.parse().map_err(|_| invalid())?)
+            }
+            (ColumnType::Int128, Value::String(value)) => {
+                LiteralValue::Int128(value.parse().map_err(|_| invalid())?)
+            }
+            (ColumnType::Decimal75(precision, scale), value) => {
+                let value = parse_bigint(value)?;
+                // Larger magnitudes would silently wrap when converted to an `I256`
+                if value.bits() > 255 {
+                    return Err(invalid());
+                }
+                LiteralValue::Decimal75(precision, scale, I256::from_num_bigint(&value))
+            }
+            (ColumnType::Scalar, value) => {
+                let (sign, digits) = parse_bigint(value)?.to_u64_digits();
+                if sign == Sign::Minus || digits.len() > 4 {
+                    return Err(invalid());
+                }
+                let mut limbs = [0u64; 4];
+                limbs[..digits.len()].copy_from_slice(&digits);
+                LiteralValue::Scalar(limbs)
+            }
+            (ColumnType::VarChar, Value::String(value)) => LiteralValue::VarChar(value.clone()),
+            (ColumnType::VarBinary, Value::Array(values)) => LiteralValue::VarBinary(
+                values
+                    .iter()
+                    .map(|value| {
+                        value
+                            .as_u64()
+                            .and_then(|value| u8::try_from(value).ok())
+                            .ok_or_else(invalid)
+                    })
+                    .collect::<CanonicalJsonResult<_>>()?,
+            ),
+            (ColumnType::TimestampTZ(unit, timezone), Value::String(value)) => {
+                LiteralValue::TimeStampTZ(unit, timezone, value.parse().map_err(|_| invalid())?)
+            }
+            _ => return Err(invalid()),
+        })
+    }
+}
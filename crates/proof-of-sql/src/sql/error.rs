@@ -27,6 +27,46 @@ pub enum AnalyzeError {
         right_type: String,
     },
 
+    #[snafu(display("Step of rounding must be a nonzero integer literal"))]
+    /// The step of a rounding to a multiple is not a nonzero integer literal
+    InvalidRoundingStep,
+
+    #[snafu(display("Modulus must be a nonzero integer literal"))]
+    /// The modulus of a modulo expression is not a nonzero integer literal
+    InvalidModulus,
+
+    #[snafu(display(
+        "Modulus of a modular exponentiation must be a nonzero integer literal of magnitude at \
+         most 2^63"
+    ))]
+    /// The modulus of a modular exponentiation is not a nonzero integer literal or too large
+    InvalidPowModModulus,
+
+    #[snafu(display("Exponent must be an integer literal between 0 and {max_exponent}"))]
+    /// The exponent of a modular exponentiation is not a small non-negative integer literal
+    InvalidExponent {
+        /// The largest supported exponent
+        max_exponent: u8,
+    },
+
+    #[snafu(display("An IN list may have at most {max_len} values"))]
+    /// The list of values of an `IN` expression is too long
+    InListTooLong {
+        /// The largest supported number of values
+        max_len: usize,
+    },
+
+    #[snafu(display(
+        "Percentage with scale {scale} of an expression of type {expr_type} is not supported"
+    ))]
+    /// The scale of a percentage of a total is negative or too large for the type of its amount
+    InvalidPercentageScale {
+        /// The scale of the percentage
+        scale: i8,
+        /// The type of the amount
+        expr_type: ColumnType,
+    },
+
     #[snafu(display("Columns have different lengths: {len_a} != {len_b}"))]
     /// Two columns do not have the same length
     DifferentColumnLength {
@@ -36,6 +76,51 @@ pub enum AnalyzeError {
         len_b: usize,
     },
 
+    #[snafu(display("Identifier '{ident}' is used by more than one column"))]
+    /// Two columns would have the same identifier
+    DuplicateIdent {
+        /// The duplicated identifier
+        ident: String,
+    },
+
+    #[snafu(display("A row hash needs at least one column"))]
+    /// A row hash has no columns to hash
+    EmptyRowHash,
+
+    #[snafu(display("Bucket boundaries must be strictly ascending"))]
+    /// The boundaries of buckets are not strictly ascending
+    UnsortedBucketBoundaries,
+
+    #[snafu(display("Expected {num_buckets} bucket aliases but got {num_aliases}"))]
+    /// The number of aliases does not match the number of buckets
+    BucketAliasCountMismatch {
+        /// The number of buckets, one more than the number of boundaries
+        num_buckets: usize,
+        /// The number of aliases
+        num_aliases: usize,
+    },
+
+    #[snafu(display("Invalid grouping sets: {reason}"))]
+    /// The grouping sets of a `GROUP BY GROUPING SETS` can't be proven
+    InvalidGroupingSets {
+        /// Why the grouping sets are invalid
+        reason: &'static str,
+    },
+
+    #[snafu(display("Invalid first row per group: {reason}"))]
+    /// The keys or the tiebreaker of a `DISTINCT ON` can't be proven
+    InvalidFirstPerGroup {
+        /// Why the first row per group is invalid
+        reason: &'static str,
+    },
+
+    #[snafu(display("Invalid table diff: {reason}"))]
+    /// The key of a diff between two versions of a table can't be proven
+    InvalidTableDiff {
+        /// Why the table diff is invalid
+        reason: &'static str,
+    },
+
     #[snafu(transparent)]
     /// Errors related to decimal operations
     DecimalConversionError {
@@ -319,9 +319,15 @@ impl EVMGroupByExec {
         table_refs: &IndexSet<TableRef>,
         column_refs: &IndexSet<ColumnRef>,
     ) -> EVMProofPlanResult<Self> {
-        // Map column expressions to their indices in column_refs
+        // Distinct counts are not supported by the EVM verifier
+        if !plan.count_distinct_exprs().is_empty() {
+            return Err(EVMProofPlanError::NotSupported);
+        }
+        // Map column expressions to their indices in column_refs. Computed group by expressions
+        // are not supported by the EVM verifier.
         let group_by_exprs = plan
-            .group_by_exprs()
+            .group_by_columns()
+            .ok_or(EVMProofPlanError::NotSupported)?
             .iter()
             .map(|col_expr| {
                 column_refs
@@ -771,10 +777,9 @@ mod tests {
         .unwrap();
 
         // Verify the roundtripped plan has the expected structure
-        assert_eq!(roundtripped_group_by_exec.group_by_exprs().len(), 1);
         assert_eq!(
-            roundtripped_group_by_exec.group_by_exprs()[0].get_column_reference(),
-            column_ref_a
+            roundtripped_group_by_exec.group_by_columns().unwrap(),
+            vec![ColumnExpr::new(column_ref_a)]
         );
         assert_eq!(roundtripped_group_by_exec.sum_expr().len(), 1);
         assert!(matches!(
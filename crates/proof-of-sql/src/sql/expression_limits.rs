@@ -0,0 +1,352 @@
+use crate::{
+    base::depth_limited_deserializer::deserialize_with_depth_limit,
+    sql::{proof::ProofPlan, proof_exprs::DynProofExpr, proof_plans::DynProofPlan},
+};
+use alloc::vec::Vec;
+use serde::{de, Deserializer};
+use snafu::Snafu;
+
+/// The number of serde nesting levels a single level of a plan or expression tree may take up.
+///
+/// For example, a projection plan is an enum variant containing a struct containing a sequence
+/// of aliased expressions, each of which is a struct containing an expression enum.
+const SERDE_NESTING_PER_LEVEL: usize = 4;
+
+/// Additional serde nesting allowed for the leaves of a plan, e.g. column references and types.
+const SERDE_NESTING_SLACK: usize = 16;
+
+/// Errors returned when a plan or expression exceeds its [`ExpressionLimits`].
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum ExpressionLimitsError {
+    #[snafu(display("Expression depth exceeds the maximum of {max_depth}"))]
+    /// The plan or expression tree is nested too deeply
+    DepthExceeded {
+        /// The maximum allowed depth
+        max_depth: usize,
+    },
+
+    #[snafu(display("Number of expression nodes exceeds the maximum of {max_nodes}"))]
+    /// The plan contains too many expression nodes
+    NodeCountExceeded {
+        /// The maximum allowed number of expression nodes
+        max_nodes: usize,
+    },
+
+    #[snafu(display(
+        "Number of result columns {num_columns} exceeds the maximum of {max_result_columns}"
+    ))]
+    /// The plan produces too many result columns
+    ResultColumnsExceeded {
+        /// The number of result columns of the plan
+        num_columns: usize,
+        /// The maximum allowed number of result columns
+        max_result_columns: usize,
+    },
+}
+
+/// Limits on the size of plans and expressions.
+///
+/// These prevent pathological queries from exhausting the stack or memory while planning,
+/// deserializing, proving or verifying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpressionLimits {
+    /// The maximum depth of a plan, counting both nested plans and nested expressions
+    pub max_depth: usize,
+    /// The maximum total number of expression nodes in a plan
+    pub max_nodes: usize,
+    /// The maximum number of columns a plan may return
+    pub max_result_columns: usize,
+}
+
+impl Default for ExpressionLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 64,
+            max_nodes: 65_536,
+            max_result_columns: 1024,
+        }
+    }
+}
+
+/// A node of a plan tree
+enum Node<'a> {
+    Plan(&'a DynProofPlan),
+    Expr(&'a DynProofExpr),
+}
+
+impl ExpressionLimits {
+    /// Check that a plan is within these limits.
+    ///
+    /// The plan is walked with an explicit stack, so this does not recurse regardless of the plan's depth.
+    pub fn check_plan(&self, plan: &DynProofPlan) -> Result<(), ExpressionLimitsError> {
+        self.check_nodes(Node::Plan(plan))?;
+        let num_columns = plan.get_column_result_fields().len();
+        if num_columns > self.max_result_columns {
+            return Err(ExpressionLimitsError::ResultColumnsExceeded {
+                num_columns,
+                max_result_columns: self.max_result_columns,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that an expression is within these limits.
+    ///
+    /// The expression is walked with an explicit stack, so this does not recurse regardless of the expression's depth.
+    pub fn check_expr(&self, expr: &DynProofExpr) -> Result<(), ExpressionLimitsError> {
+        self.check_nodes(Node::Expr(expr))
+    }
+
+    /// Check the depth and the number of expression nodes of the tree rooted at `root`.
+    fn check_nodes(&self, root: Node<'_>) -> Result<(), ExpressionLimitsError> {
+        let mut num_nodes = 0;
+        let mut stack: Vec<(Node<'_>, usize)> = Vec::from([(root, 1)]);
+        while let Some((node, depth)) = stack.pop() {
+            if depth > self.max_depth {
+                return Err(ExpressionLimitsError::DepthExceeded {
+                    max_depth: self.max_depth,
+                });
+            }
+            match node {
+                Node::Plan(plan) => {
+                    stack.extend(
+                        plan.inputs()
+                            .into_iter()
+                            .map(|p| (Node::Plan(p), depth + 1)),
+                    );
+                    stack.extend(plan.exprs().into_iter().map(|e| (Node::Expr(e), depth + 1)));
+                }
+                Node::Expr(expr) => {
+                    num_nodes += 1;
+                    if num_nodes > self.max_nodes {
+                        return Err(ExpressionLimitsError::NodeCountExceeded {
+                            max_nodes: self.max_nodes,
+                        });
+                    }
+                    stack.extend(
+                        expr.children()
+                            .into_iter()
+                            .map(|e| (Node::Expr(e), depth + 1)),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The maximum serde nesting depth accepted when deserializing under these limits.
+    fn max_serde_depth(&self) -> usize {
+        self.max_depth
+            .saturating_mul(SERDE_NESTING_PER_LEVEL)
+            .saturating_add(SERDE_NESTING_SLACK)
+    }
+
+    /// Deserialize a [`DynProofPlan`] and check that it is within these limits.
+    ///
+    /// Unlike the derived `Deserialize` implementation, this errors instead of overflowing the stack on deeply nested input.
+    pub fn deserialize_plan<'de, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<DynProofPlan, D::Error> {
+        let plan: DynProofPlan =
+            deserialize_with_depth_limit(deserializer, self.max_serde_depth())?;
+        self.check_plan(&plan).map_err(de::Error::custom)?;
+        Ok(plan)
+    }
+
+    /// Deserialize a [`DynProofExpr`] and check that it is within these limits.
+    ///
+    /// Unlike the derived `Deserialize` implementation, this errors instead of overflowing the stack on deeply nested input.
+    pub fn deserialize_expr<'de, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<DynProofExpr, D::Error> {
+        let expr: DynProofExpr =
+            deserialize_with_depth_limit(deserializer, self.max_serde_depth())?;
+        self.check_expr(&expr).map_err(de::Error::custom)?;
+        Ok(expr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::database::{ColumnRef, ColumnType, LiteralValue, TableRef},
+        sql::proof_exprs::AliasedDynProofExpr,
+    };
+    use alloc::{vec, vec::Vec};
+
+    fn bool_column() -> DynProofExpr {
+        DynProofExpr::new_column(ColumnRef::new(
+            TableRef::new("sxt", "t"),
+            "a".into(),
+            ColumnType::Boolean,
+        ))
+    }
+
+    /// A balanced tree of `AND`s with `2^height` leaves
+    fn balanced_and_tree(height: usize) -> DynProofExpr {
+        if height == 0 {
+            bool_column()
+        } else {
+            DynProofExpr::try_new_and(balanced_and_tree(height - 1), balanced_and_tree(height - 1))
+                .unwrap()
+        }
+    }
+
+    /// A chain of `depth - 1` `NOT`s around a boolean literal
+    fn not_chain(depth: usize) -> DynProofExpr {
+        (1..depth).fold(
+            DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+            |expr, _| DynProofExpr::try_new_not(expr).unwrap(),
+        )
+    }
+
+    #[test]
+    fn we_can_check_expressions_within_limits() {
+        let limits = ExpressionLimits::default();
+        assert_eq!(limits.check_expr(&balanced_and_tree(4)), Ok(()));
+        assert_eq!(limits.check_expr(&not_chain(64)), Ok(()));
+    }
+
+    #[test]
+    fn we_cannot_check_expressions_that_are_too_deep() {
+        let limits = ExpressionLimits::default();
+        assert_eq!(
+            limits.check_expr(&not_chain(65)),
+            Err(ExpressionLimitsError::DepthExceeded { max_depth: 64 })
+        );
+    }
+
+    #[test]
+    fn we_cannot_check_expressions_with_too_many_nodes() {
+        // 2^17 - 1 = 131_071 nodes
+        let expr = balanced_and_tree(16);
+        let limits = ExpressionLimits::default();
+        assert_eq!(
+            limits.check_expr(&expr),
+            Err(ExpressionLimitsError::NodeCountExceeded { max_nodes: 65_536 })
+        );
+        let limits = ExpressionLimits {
+            max_nodes: 131_071,
+            ..ExpressionLimits::default()
+        };
+        assert_eq!(limits.check_expr(&expr), Ok(()));
+    }
+
+    #[test]
+    fn we_can_check_plans_against_limits() {
+        let table_ref = TableRef::new("sxt", "t");
+        let table = DynProofPlan::new_table(table_ref, vec![]);
+        let plan = DynProofPlan::new_projection(
+            vec![
+                AliasedDynProofExpr {
+                    expr: not_chain(3),
+                    alias: "a".into(),
+                },
+                AliasedDynProofExpr {
+                    expr: bool_column(),
+                    alias: "b".into(),
+                },
+            ],
+            table,
+        );
+        let limits = ExpressionLimits::default();
+        assert_eq!(limits.check_plan(&plan), Ok(()));
+
+        // The projection and its expressions make for a depth of 4
+        let limits = ExpressionLimits {
+            max_depth: 3,
+            ..ExpressionLimits::default()
+        };
+        assert_eq!(
+            limits.check_plan(&plan),
+            Err(ExpressionLimitsError::DepthExceeded { max_depth: 3 })
+        );
+
+        let limits = ExpressionLimits {
+            max_nodes: 3,
+            ..ExpressionLimits::default()
+        };
+        assert_eq!(
+            limits.check_plan(&plan),
+            Err(ExpressionLimitsError::NodeCountExceeded { max_nodes: 3 })
+        );
+
+        let limits = ExpressionLimits {
+            max_result_columns: 1,
+            ..ExpressionLimits::default()
+        };
+        assert_eq!(
+            limits.check_plan(&plan),
+            Err(ExpressionLimitsError::ResultColumnsExceeded {
+                num_columns: 2,
+                max_result_columns: 1
+            })
+        );
+    }
+
+    #[test]
+    fn we_can_deserialize_plans_and_expressions_within_limits() {
+        let limits = ExpressionLimits::default();
+        let expr = balanced_and_tree(3);
+        let bytes = postcard::to_allocvec(&expr).unwrap();
+        let deserialized = limits
+            .deserialize_expr(&mut postcard::Deserializer::from_bytes(&bytes))
+            .unwrap();
+        assert_eq!(deserialized, expr);
+
+        let plan = DynProofPlan::new_projection(
+            vec![AliasedDynProofExpr {
+                expr,
+                alias: "a".into(),
+            }],
+            DynProofPlan::new_table(TableRef::new("sxt", "t"), vec![]),
+        );
+        let bytes = postcard::to_allocvec(&plan).unwrap();
+        let deserialized = limits
+            .deserialize_plan(&mut postcard::Deserializer::from_bytes(&bytes))
+            .unwrap();
+        assert_eq!(deserialized, plan);
+    }
+
+    #[test]
+    fn we_cannot_deserialize_a_100k_deep_expression() {
+        // Hand-encode `NOT NOT ... NOT true` with 100_000 `NOT`s in postcard's format so that the
+        // test itself never builds or drops the deeply nested expression.
+        let not_variant = 3;
+        let literal_variant = 4;
+        let boolean_variant = 0;
+        let mut bytes: Vec<u8> = vec![not_variant; 100_000];
+        bytes.extend([literal_variant, boolean_variant, 1]);
+
+        let limits = ExpressionLimits::default();
+        assert!(limits
+            .deserialize_expr(&mut postcard::Deserializer::from_bytes(&bytes))
+            .is_err());
+
+        // The same encoding is accepted when it is shallow enough
+        let mut bytes: Vec<u8> = vec![not_variant; 10];
+        bytes.extend([literal_variant, boolean_variant, 1]);
+        assert_eq!(
+            limits
+                .deserialize_expr(&mut postcard::Deserializer::from_bytes(&bytes))
+                .unwrap(),
+            not_chain(11)
+        );
+    }
+
+    #[test]
+    fn we_cannot_deserialize_expressions_with_too_many_nodes() {
+        let expr = balanced_and_tree(4);
+        let bytes = postcard::to_allocvec(&expr).unwrap();
+        let limits = ExpressionLimits {
+            max_nodes: 30,
+            ..ExpressionLimits::default()
+        };
+        assert!(limits
+            .deserialize_expr(&mut postcard::Deserializer::from_bytes(&bytes))
+            .is_err());
+    }
+}
@@ -1,15 +1,22 @@
 //! This module contains the main logic for Proof of SQL.
 
+pub mod canonical_json;
 mod error;
 /// This module holds the [`EVMProofPlan`] struct and its implementation, which allows for EVM compatible serialization.
 pub mod evm_proof_plan;
 pub mod parse;
 /// [`AnalyzeError`] temporarily exists until we switch to using Datafusion Analyzer to handle type checking.
 pub use error::{AnalyzeError, AnalyzeResult};
+mod expression_limits;
+pub use expression_limits::{ExpressionLimits, ExpressionLimitsError};
 pub mod postprocessing;
 pub mod proof;
 pub mod proof_exprs;
 pub mod proof_gadgets;
 pub mod proof_plans;
+mod prove_only_columns;
+pub use prove_only_columns::{ProveOnlyColumns, ProveOnlyColumnsError};
+#[cfg(all(test, feature = "blitzar"))]
+mod prove_only_columns_test;
 mod scale;
 pub use scale::scale_cast_binary_op;
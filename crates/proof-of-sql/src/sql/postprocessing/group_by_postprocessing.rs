@@ -235,12 +235,11 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
             .group_by_identifiers
             .iter()
             .map(|id| {
-                let column = owned_table.inner_table().get(id).ok_or(
-                    PostprocessingError::ColumnNotFound {
+                owned_table
+                    .column_view(id, &alloc)
+                    .ok_or(PostprocessingError::ColumnNotFound {
                         column: id.to_string(),
-                    },
-                )?;
-                Ok(Column::<S>::from_owned_column(column, &alloc))
+                    })
             })
             .collect::<PostprocessingResult<Vec<_>>>()?;
         // TODO: Allow a filter
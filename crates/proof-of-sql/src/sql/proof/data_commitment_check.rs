@@ -0,0 +1,156 @@
+use super::ProofPlan;
+use crate::base::{
+    commitment::{Commitment, CommittableColumn},
+    database::{ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor, TableRef},
+};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The rows a table spans in the data of a prover and in the published commitments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRangeCheck {
+    /// The table
+    pub table_ref: TableRef,
+    /// The rows of the table in the data of the prover
+    pub data_range: Range<usize>,
+    /// The rows of the table in the published commitments
+    pub committed_range: Range<usize>,
+}
+
+impl TableRangeCheck {
+    /// Whether the data of the prover spans the same rows as the published commitments
+    #[must_use]
+    pub fn matches(&self) -> bool {
+        self.data_range == self.committed_range
+    }
+}
+
+/// The outcome of comparing the data of a column with its published commitment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnCommitmentStatus {
+    /// The data of the column commits to the published commitment
+    Matches,
+    /// The data of the column is committed at different rows than the published commitment
+    RangeMismatch {
+        /// The rows of the column in the data of the prover
+        data_range: Range<usize>,
+        /// The rows of the column in the published commitment
+        committed_range: Range<usize>,
+    },
+    /// The data of the column spans the same rows but commits to a different commitment, e.g.
+    /// because the prover holds stale data
+    CommitmentMismatch,
+}
+
+/// The outcome of comparing the data of a column referenced by a plan with its published commitment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnCommitmentCheck {
+    /// The column
+    pub column_ref: ColumnRef,
+    /// The outcome of the comparison
+    pub status: ColumnCommitmentStatus,
+}
+
+/// A report of whether the data of a prover matches the published commitments to the tables and
+/// columns referenced by a plan, as produced by [`check_data_matches_commitments`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataCommitmentReport {
+    /// The checks of the referenced tables
+    pub tables: Vec<TableRangeCheck>,
+    /// The checks of the referenced columns
+    pub columns: Vec<ColumnCommitmentCheck>,
+}
+
+impl DataCommitmentReport {
+    /// Whether all referenced tables and columns match their published commitments
+    ///
+    /// Proofs created from data which doesn't match fail verification against the published
+    /// commitments.
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.tables.iter().all(TableRangeCheck::matches)
+            && self
+                .columns
+                .iter()
+                .all(|check| check.status == ColumnCommitmentStatus::Matches)
+    }
+
+    /// The checks of the referenced columns which don't match their published commitments
+    pub fn mismatched_columns(&self) -> impl Iterator<Item = &ColumnCommitmentCheck> {
+        self.columns
+            .iter()
+            .filter(|check| check.status != ColumnCommitmentStatus::Matches)
+    }
+}
+
+/// Check that the data a prover holds matches the commitments published by the data owner before
+/// proving `plan`.
+///
+/// A prover which doesn't commit to the data itself can't tell whether its copy of the data is the
+/// one the verifier's commitments were computed from, and a proof created from stale data only
+/// fails verification. This recommits the columns referenced by `plan`, and only those, with the
+/// data of `data_accessor`, and compares them to the commitments of `commitment_accessor`.
+///
+/// # Panics
+/// Panics if either accessor lacks a table or column referenced by `plan`. Commitments from an
+/// untrusted source can be checked with
+/// [`QueryCommitmentsExt::try_validate_references`](crate::base::commitment::QueryCommitmentsExt::try_validate_references)
+/// first.
+pub fn check_data_matches_commitments<C: Commitment>(
+    plan: &impl ProofPlan,
+    data_accessor: &impl DataAccessor<C::Scalar>,
+    commitment_accessor: &impl CommitmentAccessor<C>,
+    setup: &C::PublicSetup<'_>,
+) -> DataCommitmentReport {
+    let range = |accessor: &dyn MetadataAccessor, table_ref: &TableRef, offset: usize| {
+        offset..offset + accessor.get_length(table_ref)
+    };
+    let tables = plan
+        .get_table_references()
+        .into_iter()
+        .map(|table_ref| TableRangeCheck {
+            data_range: range(
+                data_accessor,
+                &table_ref,
+                data_accessor.get_offset(&table_ref),
+            ),
+            committed_range: range(
+                commitment_accessor,
+                &table_ref,
+                commitment_accessor.get_offset(&table_ref),
+            ),
+            table_ref,
+        })
+        .collect();
+    let columns = plan
+        .get_column_references()
+        .into_iter()
+        .map(|column_ref| {
+            let table_ref = column_ref.table_ref();
+            let column_id = column_ref.column_id();
+            let data_offset = data_accessor.get_column_offset(&table_ref, &column_id);
+            let committed_offset = commitment_accessor.get_column_offset(&table_ref, &column_id);
+            let data_range = range(data_accessor, &table_ref, data_offset);
+            let committed_range = range(commitment_accessor, &table_ref, committed_offset);
+            let status = if data_range != committed_range {
+                ColumnCommitmentStatus::RangeMismatch {
+                    data_range,
+                    committed_range,
+                }
+            } else {
+                let column = data_accessor.get_column(&table_ref, &column_id);
+                let commitment =
+                    C::compute_commitments(&[CommittableColumn::from(&column)], data_offset, setup)
+                        .pop()
+                        .expect("There is one commitment per column");
+                if commitment == commitment_accessor.get_commitment(&table_ref, &column_id) {
+                    ColumnCommitmentStatus::Matches
+                } else {
+                    ColumnCommitmentStatus::CommitmentMismatch
+                }
+            };
+            ColumnCommitmentCheck { column_ref, status }
+        })
+        .collect();
+    DataCommitmentReport { tables, columns }
+}
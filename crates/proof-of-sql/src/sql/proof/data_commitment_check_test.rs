@@ -0,0 +1,158 @@
+use super::{
+    check_data_matches_commitments, ColumnCommitmentStatus, DataCommitmentReport,
+    VerifiableQueryResult,
+};
+use crate::{
+    base::{
+        commitment::{InnerProductProof, QueryCommitments, TableCommitment},
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+use sqlparser::ast::Ident;
+
+/// The table of the data owner, which the published commitments are computed from
+fn owner_table() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("a", [1_i64, 2, 3, 2]),
+        varchar("b", ["x", "y", "z", "w"]),
+        bigint("c", [10_i64, 20, 30, 40]),
+    ])
+}
+
+/// `select a, b from sxt.t where a = 2`, which doesn't reference `c`
+fn plan(t: &TableRef, accessor: &OwnedTableTestAccessor<InnerProductProof>) -> DynProofPlan {
+    filter(
+        cols_expr_plan(t, &["a", "b"], accessor),
+        tab(t),
+        equal(column(t, "a", accessor), const_bigint(2)),
+    )
+}
+
+/// Check the table of the prover against the commitments published for the table of the owner
+fn check(
+    prover_table: OwnedTable<Curve25519Scalar>,
+    prover_offset: usize,
+) -> (
+    DataCommitmentReport,
+    DynProofPlan,
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+    QueryCommitments<RistrettoPoint>,
+) {
+    let t = TableRef::new("sxt", "t");
+    let published_commitments = QueryCommitments::from_iter([(
+        t.clone(),
+        TableCommitment::from_owned_table_with_offset(&owner_table(), 2, &()),
+    )]);
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        prover_table,
+        prover_offset,
+        (),
+    );
+    let plan = plan(&t, &accessor);
+    let report = check_data_matches_commitments(&plan, &accessor, &published_commitments, &());
+    (report, plan, accessor, published_commitments)
+}
+
+#[test]
+fn we_can_check_that_the_data_of_a_prover_matches_the_published_commitments() {
+    let (report, plan, accessor, published_commitments) = check(owner_table(), 2);
+    assert!(report.is_consistent());
+    assert_eq!(report.tables.len(), 1);
+    assert_eq!(report.tables[0].data_range, 2..6);
+    assert_eq!(report.tables[0].committed_range, 2..6);
+    assert_eq!(report.columns.len(), 2);
+    assert!(report
+        .columns
+        .iter()
+        .all(|check| check.status == ColumnCommitmentStatus::Matches));
+    assert_eq!(report.mismatched_columns().count(), 0);
+
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    let res = verifiable_res
+        .verify(&plan, &published_commitments, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [2_i64, 2]), varchar("b", ["y", "w"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_detect_stale_data_in_one_column_before_proving() {
+    let stale_table = owned_table([
+        bigint("a", [1_i64, 2, 3, 2]),
+        varchar("b", ["x", "y", "stale", "w"]),
+        bigint("c", [10_i64, 20, 30, 40]),
+    ]);
+    let (report, plan, accessor, published_commitments) = check(stale_table, 2);
+    assert!(!report.is_consistent());
+    assert!(report.tables[0].matches());
+    let mismatches: Vec<_> = report.mismatched_columns().collect();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].column_ref.column_id(), Ident::new("b"));
+    assert_eq!(
+        mismatches[0].status,
+        ColumnCommitmentStatus::CommitmentMismatch
+    );
+    let a_check = report
+        .columns
+        .iter()
+        .find(|check| check.column_ref.column_id() == Ident::new("a"))
+        .unwrap();
+    assert_eq!(a_check.status, ColumnCommitmentStatus::Matches);
+
+    // A proof from the stale data fails verification against the published commitments
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    assert!(verifiable_res
+        .verify(&plan, &published_commitments, &(), &[])
+        .is_err());
+}
+
+#[test]
+fn we_ignore_stale_data_in_columns_which_are_not_referenced() {
+    let stale_table = owned_table([
+        bigint("a", [1_i64, 2, 3, 2]),
+        varchar("b", ["x", "y", "z", "w"]),
+        bigint("c", [10_i64, 20, 0, 40]),
+    ]);
+    let (report, ..) = check(stale_table, 2);
+    assert!(report.is_consistent());
+}
+
+#[test]
+fn we_can_detect_data_with_a_different_number_of_rows() {
+    let longer_table = owned_table([
+        bigint("a", [1_i64, 2, 3, 2, 5]),
+        varchar("b", ["x", "y", "z", "w", "v"]),
+        bigint("c", [10_i64, 20, 30, 40, 50]),
+    ]);
+    let (report, ..) = check(longer_table, 2);
+    assert!(!report.is_consistent());
+    assert!(!report.tables[0].matches());
+    assert_eq!(report.tables[0].data_range, 2..7);
+    assert_eq!(report.tables[0].committed_range, 2..6);
+    assert_eq!(report.mismatched_columns().count(), 2);
+    assert!(report.columns.iter().all(|check| check.status
+        == ColumnCommitmentStatus::RangeMismatch {
+            data_range: 2..7,
+            committed_range: 2..6,
+        }));
+}
+
+#[test]
+fn we_can_detect_data_at_a_different_offset() {
+    let (report, ..) = check(owner_table(), 0);
+    assert!(!report.is_consistent());
+    assert_eq!(report.tables[0].data_range, 0..4);
+    assert_eq!(report.tables[0].committed_range, 2..6);
+    assert!(report
+        .columns
+        .iter()
+        .all(|check| matches!(check.status, ColumnCommitmentStatus::RangeMismatch { .. })));
+}
@@ -11,6 +11,12 @@ use crate::{
 use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 
 /// Track components used to form a query's proof
+///
+/// A [`ProofPlan`](super::ProofPlan) uses it in
+/// [`ProverEvaluate::final_round_evaluate`](super::ProverEvaluate::final_round_evaluate) to commit
+/// to the columns it computes and to produce the constraints on them. The verifier has to consume
+/// the MLE evaluations and produce the constraint evaluations in the same order in
+/// [`ProofPlan::verifier_evaluate`](super::ProofPlan::verifier_evaluate).
 pub struct FinalRoundBuilder<'a, S: Scalar> {
     num_sumcheck_variables: usize,
     bit_distributions: Vec<BitDistribution>,
@@ -28,7 +34,7 @@ pub struct FinalRoundBuilder<'a, S: Scalar> {
 }
 
 impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
-    pub fn new(num_sumcheck_variables: usize, post_result_challenges: VecDeque<S>) -> Self {
+    pub(crate) fn new(num_sumcheck_variables: usize, post_result_challenges: VecDeque<S>) -> Self {
         Self {
             num_sumcheck_variables,
             bit_distributions: Vec::new(),
@@ -39,21 +45,21 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         }
     }
 
-    pub fn num_sumcheck_variables(&self) -> usize {
+    pub(crate) fn num_sumcheck_variables(&self) -> usize {
         self.num_sumcheck_variables
     }
 
-    pub fn num_sumcheck_subpolynomials(&self) -> usize {
+    pub(crate) fn num_sumcheck_subpolynomials(&self) -> usize {
         self.sumcheck_subpolynomials.len()
     }
 
-    pub fn pcs_proof_mles(&self) -> &[Box<dyn MultilinearExtension<S> + 'a>] {
+    pub(crate) fn pcs_proof_mles(&self) -> &[Box<dyn MultilinearExtension<S> + 'a>] {
         &self.pcs_proof_mles
     }
 
     /// Produce a bit distribution that describes which bits are constant
     /// and which bits varying in a column of data
-    pub fn produce_bit_distribution(&mut self, dist: BitDistribution) {
+    pub(crate) fn produce_bit_distribution(&mut self, dist: BitDistribution) {
         self.bit_distributions.push(dist);
     }
 
@@ -76,8 +82,13 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         self.produce_anchored_mle(data);
     }
 
-    /// Produce a subpolynomial to be aggegated into sumcheck where the sum across binary
-    /// values of the variables is zero.
+    /// Produce a subpolynomial to be aggegated into sumcheck.
+    ///
+    /// An [`Identity`](SumcheckSubpolynomialType::Identity) subpolynomial has to be zero at every
+    /// row, while a [`ZeroSum`](SumcheckSubpolynomialType::ZeroSum) subpolynomial has to sum to
+    /// zero across all rows. Each term is a constant times a product of MLEs. The verifier has to
+    /// produce the evaluation of the subpolynomial along with its degree, i.e. the largest number
+    /// of MLEs in a term.
     pub fn produce_sumcheck_subpolynomial(
         &mut self,
         subpolynomial_type: SumcheckSubpolynomialType,
@@ -93,7 +104,7 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         level = "debug",
         skip_all
     )]
-    pub fn commit_intermediate_mles<C: Commitment>(
+    pub(crate) fn commit_intermediate_mles<C: Commitment>(
         &self,
         offset_generators: usize,
         setup: &C::PublicSetup<'_>,
@@ -113,7 +124,7 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
 
     /// Produce a subpolynomial to be aggegated into sumcheck where the sum across binary
     /// values of the variables is zero.
-    pub fn sumcheck_subpolynomials(&self) -> &[SumcheckSubpolynomial<'a, S>] {
+    pub(crate) fn sumcheck_subpolynomials(&self) -> &[SumcheckSubpolynomial<'a, S>] {
         &self.sumcheck_subpolynomials
     }
 
@@ -124,7 +135,7 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         level = "debug",
         skip_all
     )]
-    pub fn evaluate_pcs_proof_mles(&self, evaluation_vec: &[S]) -> Vec<S> {
+    pub(crate) fn evaluate_pcs_proof_mles(&self, evaluation_vec: &[S]) -> Vec<S> {
         log::log_memory_usage("Start");
 
         let mut res = Vec::with_capacity(self.pcs_proof_mles.len());
@@ -137,7 +148,7 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         res
     }
 
-    pub fn bit_distributions(&self) -> &[BitDistribution] {
+    pub(crate) fn bit_distributions(&self) -> &[BitDistribution] {
         &self.bit_distributions
     }
 
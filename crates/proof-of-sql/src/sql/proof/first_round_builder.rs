@@ -8,6 +8,11 @@ use crate::{
 };
 use alloc::{boxed::Box, vec::Vec};
 /// Track the result created by a query
+///
+/// A [`ProofPlan`](super::ProofPlan) uses it in
+/// [`ProverEvaluate::first_round_evaluate`](super::ProverEvaluate::first_round_evaluate) to
+/// declare the lengths of its chi and rho evaluations, e.g. the length of its output, to commit to
+/// the columns it computes before any challenge is drawn and to request post-result challenges.
 pub struct FirstRoundBuilder<'a, S> {
     commitment_descriptor: Vec<CommittableColumn<'a>>,
     pcs_proof_mles: Vec<Box<dyn MultilinearExtension<S> + 'a>>,
@@ -25,7 +30,7 @@ pub struct FirstRoundBuilder<'a, S> {
 }
 
 impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
-    pub fn new(initial_range_length: usize) -> Self {
+    pub(crate) fn new(initial_range_length: usize) -> Self {
         Self {
             commitment_descriptor: Vec::new(),
             pcs_proof_mles: Vec::new(),
@@ -48,7 +53,7 @@ impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
         }
     }
 
-    pub fn pcs_proof_mles(&self) -> &[Box<dyn MultilinearExtension<S> + 'a>] {
+    pub(crate) fn pcs_proof_mles(&self) -> &[Box<dyn MultilinearExtension<S> + 'a>] {
         &self.pcs_proof_mles
     }
 
@@ -58,7 +63,11 @@ impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
     }
 
     /// Append the length to the list of chi evaluation lengths.
-    pub(crate) fn produce_chi_evaluation_length(&mut self, length: usize) {
+    ///
+    /// The chi evaluation of length `n` is the evaluation of a column of `n` ones. The verifier
+    /// consumes the chi evaluations in the order they are produced, e.g. with
+    /// [`VerificationBuilder::try_consume_chi_evaluation_of_length`](super::VerificationBuilder::try_consume_chi_evaluation_of_length).
+    pub fn produce_chi_evaluation_length(&mut self, length: usize) {
         self.update_range_length(length);
         self.chi_evaluation_lengths.push(length);
     }
@@ -69,7 +78,10 @@ impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
     }
 
     /// Append the length to the list of rho evaluation lengths.
-    pub(crate) fn produce_rho_evaluation_length(&mut self, length: usize) {
+    ///
+    /// The rho evaluation of length `n` is the evaluation of the column `0, 1, ..., n - 1`. The
+    /// verifier consumes the rho evaluations in the order they are produced.
+    pub fn produce_rho_evaluation_length(&mut self, length: usize) {
         self.rho_evaluation_lengths.push(length);
     }
 
@@ -91,7 +103,7 @@ impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
         level = "debug",
         skip_all
     )]
-    pub fn commit_intermediate_mles<C: Commitment>(
+    pub(crate) fn commit_intermediate_mles<C: Commitment>(
         &self,
         offset_generators: usize,
         setup: &C::PublicSetup<'_>,
@@ -110,7 +122,7 @@ impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
         level = "debug",
         skip_all
     )]
-    pub fn evaluate_pcs_proof_mles(&self, evaluation_vec: &[S]) -> Vec<S> {
+    pub(crate) fn evaluate_pcs_proof_mles(&self, evaluation_vec: &[S]) -> Vec<S> {
         log::log_memory_usage("Start");
 
         let mut res = Vec::with_capacity(self.pcs_proof_mles.len());
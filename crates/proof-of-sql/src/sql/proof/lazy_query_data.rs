@@ -0,0 +1,135 @@
+use super::{query_proof::ResultEvaluations, QueryData, QueryError, QueryResult};
+use crate::base::{
+    database::{ColumnField, OwnedColumn, TableCoercionError},
+    polynomial::compute_evaluation_vector,
+    proof::ProofError,
+    scalar::Scalar,
+};
+use alloc::{vec, vec::Vec};
+use sqlparser::ast::Ident;
+
+/// The result of a query whose proof has been verified, but whose columns are only checked against
+/// the proof when they are accessed.
+///
+/// Verifying a proof ends with checking that every result column evaluates to what the proof
+/// implies. For a wide result of which only a few columns are needed, most of that work can be
+/// skipped: the proof, including its sumcheck and evaluation proofs, has already been verified,
+/// and every column was absorbed into the transcript before any challenge was drawn. Plans such
+/// as filters and group bys skip their own checks of the result columns as well, while still
+/// checking the structure of the result, e.g. the order of the groups. So a column
+/// that is returned by [`LazyQueryData::column`] is exactly as verified as one returned by
+/// [`VerifiableQueryResult::verify`](super::VerifiableQueryResult::verify).
+///
+/// Columns that were never accessed are not verified, which is why the verification hash is only
+/// available from [`LazyQueryData::into_query_data`], once every column has been checked.
+pub struct LazyQueryData<S: Scalar> {
+    /// The query data of the result, whose columns are not coerced to the result fields yet
+    query_data: QueryData<S>,
+    /// The fields of the result columns
+    fields: Vec<ColumnField>,
+    /// The evaluation vector of the point the result columns are evaluated at
+    evaluation_vector: Vec<S>,
+    /// The evaluation of each result column implied by the proof
+    column_evals: Vec<S>,
+    /// Whether each result column has already been checked against its evaluation
+    checked: Vec<bool>,
+}
+
+impl<S: Scalar> LazyQueryData<S> {
+    /// Creates the lazily checked query data of a result whose proof has been verified except for
+    /// the checks of the result columns against `result_evaluations`.
+    pub(super) fn try_new(
+        query_data: QueryData<S>,
+        fields: Vec<ColumnField>,
+        result_evaluations: ResultEvaluations<S>,
+    ) -> Result<Self, QueryError> {
+        let table = &query_data.table;
+        if table.num_columns() != fields.len() {
+            Err(TableCoercionError::ColumnCountMismatch)?;
+        }
+        if table
+            .column_names()
+            .zip(&fields)
+            .any(|(name, field)| *name != field.name())
+        {
+            Err(TableCoercionError::NameMismatch)?;
+        }
+        if result_evaluations.column_evals.len() != fields.len() {
+            Err(ProofError::VerificationError {
+                error: "result evaluation check failed",
+            })?;
+        }
+        let mut evaluation_vector = vec![S::ZERO; table.num_rows()];
+        compute_evaluation_vector(&mut evaluation_vector, &result_evaluations.evaluation_point);
+        Ok(Self {
+            checked: vec![false; fields.len()],
+            query_data,
+            fields,
+            evaluation_vector,
+            column_evals: result_evaluations.column_evals,
+        })
+    }
+
+    /// The fields of the result columns.
+    #[must_use]
+    pub fn column_fields(&self) -> &[ColumnField] {
+        &self.fields
+    }
+
+    /// Returns the result column `name`, or `None` if there is no such column.
+    ///
+    /// The column is checked against the proof the first time it is accessed, and fails with
+    /// [`ProofError::VerificationError`] if it does not match.
+    pub fn column(&mut self, name: &Ident) -> Result<Option<OwnedColumn<S>>, QueryError> {
+        let Some(index) = self.fields.iter().position(|field| field.name() == *name) else {
+            return Ok(None);
+        };
+        self.check_column(index)?;
+        let column = self
+            .query_data
+            .table
+            .column_by_index(index)
+            .expect("the table has a column per field")
+            .clone();
+        Ok(Some(
+            column
+                .try_coerce_scalar_to_numeric(self.fields[index].data_type())
+                .map_err(TableCoercionError::from)?,
+        ))
+    }
+
+    /// Whether each result column has already been checked against its evaluation
+    #[cfg(test)]
+    pub(super) fn checked_columns(&self) -> &[bool] {
+        &self.checked
+    }
+
+    /// Checks every result column that was not accessed yet and returns the query data of the
+    /// verified result, as [`VerifiableQueryResult::verify`](super::VerifiableQueryResult::verify)
+    /// would have.
+    pub fn into_query_data(mut self) -> QueryResult<S> {
+        (0..self.fields.len()).try_for_each(|index| self.check_column(index))?;
+        Ok(QueryData {
+            table: self.query_data.table.try_coerce_with_fields(self.fields)?,
+            ..self.query_data
+        })
+    }
+
+    /// Checks the result column at `index` against its evaluation, unless it has been already.
+    fn check_column(&mut self, index: usize) -> Result<(), ProofError> {
+        if !self.checked[index] {
+            let column = self
+                .query_data
+                .table
+                .column_by_index(index)
+                .expect("the table has a column per field");
+            if column.inner_product(&self.evaluation_vector) != self.column_evals[index] {
+                Err(ProofError::VerificationError {
+                    error: "result evaluation check failed",
+                })?;
+            }
+            self.checked[index] = true;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use super::{
+    query_proof::ResultEvaluations, LazyQueryData, ProofPlan, QueryData, VerifiableQueryResult,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, LiteralValue, OwnedTable,
+            OwnedTableTestAccessor, TableRef,
+        },
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use sqlparser::ast::Ident;
+
+/// `select a, b from sxt.t where a = $1`
+fn plan_and_accessor() -> (
+    DynProofPlan,
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+) {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 2, 5]),
+        varchar("b", ["x", "y", "z", "w", "v"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let plan = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(
+            column(&t, "a", &accessor),
+            DynProofExpr::try_new_placeholder(1, ColumnType::BigInt).unwrap(),
+        ),
+    );
+    (plan, accessor)
+}
+
+/// The lazily checked query data of `table`, whose columns are expected to evaluate to
+/// `column_evals`
+fn lazy_query_data(
+    table: OwnedTable<Curve25519Scalar>,
+    column_evals: Vec<Curve25519Scalar>,
+) -> LazyQueryData<Curve25519Scalar> {
+    let fields = vec![
+        ColumnField::new("a".into(), ColumnType::BigInt),
+        ColumnField::new("b".into(), ColumnType::VarChar),
+    ];
+    LazyQueryData::try_new(
+        QueryData::new(table, [0; 32]),
+        fields,
+        ResultEvaluations {
+            evaluation_point: evaluation_point(),
+            column_evals,
+        },
+    )
+    .unwrap()
+}
+
+fn evaluation_point() -> Vec<Curve25519Scalar> {
+    vec![3.into(), 5.into(), 7.into()]
+}
+
+#[test]
+fn we_can_access_lazily_verified_columns_in_any_order() {
+    let (plan, accessor) = plan_and_accessor();
+    let params = [LiteralValue::BigInt(2)];
+    let verifiable_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &params).unwrap();
+    let expected = verifiable_res
+        .clone()
+        .verify(&plan, &accessor, &(), &params)
+        .unwrap();
+
+    let mut lazy = verifiable_res
+        .verify_lazily(&plan, &accessor, &(), &params)
+        .unwrap();
+    assert_eq!(lazy.column_fields(), plan.get_column_result_fields());
+    assert_eq!(
+        lazy.column(&"b".into()).unwrap(),
+        expected.table.inner_table().get(&Ident::new("b")).cloned()
+    );
+    assert_eq!(lazy.column(&"c".into()).unwrap(), None);
+    // accessing a column again checks it only once and returns the same column
+    assert_eq!(
+        lazy.column(&"b".into()).unwrap(),
+        expected.table.inner_table().get(&Ident::new("b")).cloned()
+    );
+    let query_data = lazy.into_query_data().unwrap();
+    assert_eq!(query_data.table, expected.table);
+    assert_eq!(query_data.verification_hash, expected.verification_hash);
+}
+
+#[test]
+fn we_do_not_check_columns_that_are_never_accessed() {
+    let (plan, accessor) = plan_and_accessor();
+    let params = [LiteralValue::BigInt(2)];
+    let mut lazy = VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &params)
+        .unwrap()
+        .verify_lazily(&plan, &accessor, &(), &params)
+        .unwrap();
+    assert_eq!(lazy.checked_columns(), [false, false]);
+    lazy.column(&"b".into()).unwrap();
+    assert_eq!(lazy.checked_columns(), [false, true]);
+}
+
+#[test]
+fn we_cannot_lazily_verify_invalid_proofs() {
+    let (plan, accessor) = plan_and_accessor();
+    let params = [LiteralValue::BigInt(2)];
+    let verifiable_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &params).unwrap();
+
+    // the result is absorbed into the transcript, so a tampered result fails before any column
+    // is accessed
+    let mut tampered = verifiable_res.clone();
+    tampered.result = owned_table([bigint("a", [2_i64]), varchar("b", ["y"])]);
+    assert!(tampered
+        .verify_lazily(&plan, &accessor, &(), &params)
+        .is_err());
+
+    let other_params = [LiteralValue::BigInt(3)];
+    assert!(verifiable_res
+        .verify_lazily(&plan, &accessor, &(), &other_params)
+        .is_err());
+}
+
+#[test]
+fn we_can_only_detect_a_mismatching_column_once_it_is_accessed() {
+    let table = owned_table([
+        bigint("a", [1_i64, 2, 3, 2, 5]),
+        varchar("b", ["x", "y", "z", "w", "v"]),
+    ]);
+    let mut column_evals = table.mle_evaluations(&evaluation_point());
+    column_evals[1] += Curve25519Scalar::from(1);
+
+    let mut lazy = lazy_query_data(table.clone(), column_evals.clone());
+    assert_eq!(
+        lazy.column(&"a".into()).unwrap(),
+        table.inner_table().get(&Ident::new("a")).cloned()
+    );
+    assert!(lazy.column(&"b".into()).is_err());
+
+    let lazy = lazy_query_data(table, column_evals);
+    assert!(lazy.into_query_data().is_err());
+}
+
+#[test]
+fn we_can_lazily_check_matching_columns() {
+    let table = owned_table([
+        bigint("a", [1_i64, 2, 3, 2, 5]),
+        varchar("b", ["x", "y", "z", "w", "v"]),
+    ]);
+    let column_evals = table.mle_evaluations(&evaluation_point());
+    let query_data = lazy_query_data(table.clone(), column_evals)
+        .into_query_data()
+        .unwrap();
+    assert_eq!(query_data.table, table);
+}
+
+#[test]
+fn we_cannot_create_lazy_query_data_whose_columns_do_not_match_the_fields() {
+    let table = owned_table([bigint("a", [1_i64, 2]), varchar("c", ["x", "y"])]);
+    let column_evals = table.mle_evaluations(&evaluation_point());
+    let fields = vec![
+        ColumnField::new("a".into(), ColumnType::BigInt),
+        ColumnField::new("b".into(), ColumnType::VarChar),
+    ];
+    let result_evaluations = ResultEvaluations {
+        evaluation_point: evaluation_point(),
+        column_evals: column_evals.clone(),
+    };
+    assert!(LazyQueryData::try_new(
+        QueryData::new(table.clone(), [0; 32]),
+        fields[..1].to_vec(),
+        result_evaluations.clone(),
+    )
+    .is_err());
+    assert!(LazyQueryData::try_new(
+        QueryData::new(table.clone(), [0; 32]),
+        fields,
+        result_evaluations,
+    )
+    .is_err());
+    assert!(LazyQueryData::try_new(
+        QueryData::new(table, [0; 32]),
+        vec![
+            ColumnField::new("a".into(), ColumnType::BigInt),
+            ColumnField::new("c".into(), ColumnType::VarChar),
+        ],
+        ResultEvaluations {
+            evaluation_point: evaluation_point(),
+            column_evals: column_evals[..1].to_vec(),
+        },
+    )
+    .is_err());
+}
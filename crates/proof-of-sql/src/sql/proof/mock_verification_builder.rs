@@ -1,6 +1,10 @@
-use super::{FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder};
+use super::{
+    FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    VerificationOptions,
+};
 use crate::base::{
     bit::BitDistribution,
+    database::TableRef,
     polynomial::MultilinearExtension,
     proof::ProofSizeMismatch,
     scalar::{test_scalar::TestScalar, Scalar},
@@ -30,17 +34,23 @@ pub struct MockVerificationBuilder<S: Scalar> {
 
 impl<S: Scalar> VerificationBuilder<S> for MockVerificationBuilder<S> {
     fn try_consume_chi_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        self.try_consume_chi_evaluation_with_length()
+            .map(|(chi_eval, _)| chi_eval)
+    }
+
+    fn try_consume_chi_evaluation_with_length(&mut self) -> Result<(S, usize), ProofSizeMismatch> {
         let length = self
             .chi_evaluation_length_queue
             .get(self.consumed_chi_evaluations)
             .copied()
             .ok_or(ProofSizeMismatch::TooFewChiLengths)?;
         self.consumed_chi_evaluations += 1;
-        Ok(if self.evaluation_row_index < length {
+        let chi_eval = if self.evaluation_row_index < length {
             S::ONE
         } else {
             S::ZERO
-        })
+        };
+        Ok((chi_eval, length))
     }
 
     fn try_produce_sumcheck_subpolynomial_evaluation(
@@ -136,6 +146,18 @@ impl<S: Scalar> VerificationBuilder<S> for MockVerificationBuilder<S> {
         unimplemented!("No tests currently use this function")
     }
 
+    fn evaluation_point(&self) -> &[S] {
+        unimplemented!("No tests currently use this function")
+    }
+
+    fn table_length(&self, _table_ref: &TableRef) -> Option<usize> {
+        None
+    }
+
+    fn verification_options(&self) -> VerificationOptions {
+        VerificationOptions::default()
+    }
+
     fn try_consume_final_round_mle_evaluations(
         &mut self,
         count: usize,
@@ -3,7 +3,7 @@
 mod final_round_builder;
 #[cfg(test)]
 pub(crate) mod mock_verification_builder;
-pub(crate) use final_round_builder::FinalRoundBuilder;
+pub use final_round_builder::FinalRoundBuilder;
 #[cfg(all(test, feature = "blitzar"))]
 mod final_round_builder_test;
 
@@ -13,7 +13,8 @@ pub(crate) use composite_polynomial_builder::CompositePolynomialBuilder;
 mod composite_polynomial_builder_test;
 
 mod verification_builder;
-pub(crate) use verification_builder::{VerificationBuilder, VerificationBuilderImpl};
+pub use verification_builder::VerificationBuilder;
+pub(crate) use verification_builder::VerificationBuilderImpl;
 #[cfg(test)]
 mod verification_builder_test;
 
@@ -32,27 +33,59 @@ mod sumcheck_random_scalars;
 pub(crate) use sumcheck_random_scalars::SumcheckRandomScalars;
 
 mod proof_plan;
-pub use proof_plan::ProofPlan;
-pub(crate) use proof_plan::{HonestProver, ProverEvaluate, ProverHonestyMarker};
+pub(crate) use proof_plan::{HonestProver, ProverHonestyMarker};
+pub use proof_plan::{ProofPlan, ProverEvaluate};
 
 mod query_proof;
-pub use query_proof::QueryProof;
+pub use query_proof::{required_num_generators, QueryProof};
 #[cfg(all(test, feature = "blitzar"))]
 mod query_proof_test;
 
+mod prover_checkpoint;
+#[cfg(feature = "std")]
+pub use prover_checkpoint::CheckpointError;
+
 mod query_result;
-pub use query_result::{QueryData, QueryError, QueryResult};
+pub use query_result::{
+    verification_hash_version, QueryData, QueryError, QueryResult, VerificationHashInputs,
+    VERIFICATION_HASH_VERSION,
+};
 
 mod sumcheck_subpolynomial;
-pub(crate) use sumcheck_subpolynomial::{
-    SumcheckSubpolynomial, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
+pub(crate) use sumcheck_subpolynomial::SumcheckSubpolynomial;
+pub use sumcheck_subpolynomial::{SumcheckSubpolynomialTerm, SumcheckSubpolynomialType};
+
+mod verification_options;
+pub use verification_options::VerificationOptions;
+
+mod prepared_plan_verifier;
+pub use prepared_plan_verifier::PreparedPlanVerifier;
+#[cfg(all(test, feature = "blitzar"))]
+mod prepared_plan_verifier_test;
+
+mod data_commitment_check;
+pub use data_commitment_check::{
+    check_data_matches_commitments, ColumnCommitmentCheck, ColumnCommitmentStatus,
+    DataCommitmentReport, TableRangeCheck,
 };
+#[cfg(all(test, feature = "blitzar"))]
+mod data_commitment_check_test;
+
+mod lazy_query_data;
+pub use lazy_query_data::LazyQueryData;
+#[cfg(all(test, feature = "blitzar"))]
+mod lazy_query_data_test;
 
 mod verifiable_query_result;
-pub use verifiable_query_result::VerifiableQueryResult;
+pub use verifiable_query_result::{EncodedSizes, VerifiableQueryResult};
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test;
 
+mod verifiable_query_result_bundle;
+pub use verifiable_query_result_bundle::VerifiableQueryResultBundle;
+#[cfg(all(test, feature = "blitzar"))]
+mod verifiable_query_result_bundle_test;
+
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test_utility;
 #[cfg(all(test, feature = "blitzar"))]
@@ -63,8 +96,18 @@ pub(crate) use result_element_serialization::{
     decode_and_convert, decode_multiple_elements, ProvableResultElement,
 };
 
+#[cfg(any(test, feature = "test"))]
+mod round_consistency;
+#[cfg(any(test, feature = "test"))]
+pub use round_consistency::{
+    assert_plan_round_consistency, check_plan_round_consistency, RoundConsistencyError,
+    RoundStepKind,
+};
+#[cfg(test)]
+mod round_consistency_test;
+
 mod first_round_builder;
-pub(crate) use first_round_builder::FirstRoundBuilder;
+pub use first_round_builder::FirstRoundBuilder;
 #[cfg(all(test, feature = "blitzar"))]
 mod first_round_builder_test;
 
@@ -0,0 +1,105 @@
+use super::{
+    query_proof::PlanVerificationData, ProofPlan, QueryData, QueryResult, VerifiableQueryResult,
+    VerificationOptions,
+};
+use crate::base::{
+    commitment::CommitmentEvaluationProof,
+    database::{ColumnField, CommitmentAccessor, LiteralValue},
+    proof::Transcript,
+};
+use alloc::vec::Vec;
+use serde::Serialize;
+
+/// A verifier for the proofs of a single plan, e.g. a
+/// [`DynProofPlan`](crate::sql::proof_plans::DynProofPlan), which caches the data of the plan that
+/// does not depend on the proof.
+///
+/// Verifying many proofs of the same plan with [`VerifiableQueryResult::verify`] serializes the
+/// plan for the transcript and collects its table references, column references and result fields
+/// for every proof. A `PreparedPlanVerifier` does this once, when it is created, and reuses the
+/// data for every proof it verifies. Verification is otherwise identical, including every
+/// cryptographic check, so both accept and reject exactly the same proofs. How much of the time of
+/// a verification the cached data accounts for depends on the plan and has not been measured.
+///
+/// A `PreparedPlanVerifier` is `Send` and `Sync` whenever the plan and the verifier setup are, so
+/// it can be shared by the threads of a verification pool.
+///
+/// ```ignore
+/// let prepared = PreparedPlanVerifier::<DynamicDoryEvaluationProof, _>::new(plan, &verifier_setup);
+/// for verifiable_result in verifiable_results {
+///     let QueryData { table, .. } = prepared.verify(verifiable_result, &accessor, &[])?;
+/// }
+/// ```
+pub struct PreparedPlanVerifier<'a, CP: CommitmentEvaluationProof, P> {
+    plan: P,
+    plan_data: PlanVerificationData,
+    result_fields: Vec<ColumnField>,
+    setup: CP::VerifierPublicSetup<'a>,
+    options: VerificationOptions,
+}
+
+impl<'a, CP: CommitmentEvaluationProof, P: ProofPlan + Serialize> PreparedPlanVerifier<'a, CP, P> {
+    /// Prepare the verification of the proofs of `plan` with `setup` and the default
+    /// [`VerificationOptions`]
+    pub fn new(plan: P, setup: CP::VerifierPublicSetup<'a>) -> Self {
+        let plan_data = PlanVerificationData::new(core::slice::from_ref(&plan));
+        let result_fields = plan.get_column_result_fields();
+        Self {
+            plan,
+            plan_data,
+            result_fields,
+            setup,
+            options: VerificationOptions::default(),
+        }
+    }
+
+    /// Verify with the limits set in `options` instead of the default ones
+    #[must_use]
+    pub fn with_options(self, options: VerificationOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    /// Get a reference to the plan
+    pub fn plan(&self) -> &P {
+        &self.plan
+    }
+
+    /// Get a reference to the verification options
+    pub fn options(&self) -> &VerificationOptions {
+        &self.options
+    }
+
+    /// Verify a `VerifiableQueryResult` of the plan. Upon success, this function returns the
+    /// finalized form of the query result.
+    ///
+    /// This is equivalent to [`VerifiableQueryResult::verify_with_options`] with the plan, setup
+    /// and options of this verifier.
+    ///
+    /// Note: This does NOT transform the result!
+    #[tracing::instrument(name = "PreparedPlanVerifier::verify", level = "info", skip_all)]
+    pub fn verify(
+        &self,
+        verifiable_result: VerifiableQueryResult<CP>,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        params: &[LiteralValue],
+    ) -> QueryResult<CP::Scalar> {
+        self.options
+            .check_proof_bytes(verifiable_result.proof.encoded_size_hint())?;
+        let query_data = verifiable_result.proof.verify_with_plan_data(
+            &self.plan,
+            &self.plan_data,
+            accessor,
+            verifiable_result.result,
+            &self.setup,
+            params,
+            Transcript::new(),
+            &self.options,
+        )?;
+        Ok(QueryData {
+            table: query_data
+                .table
+                .try_coerce_with_fields(self.result_fields.clone())?,
+            ..query_data
+        })
+    }
+}
@@ -0,0 +1,189 @@
+use super::{
+    PreparedPlanVerifier, ProofPlan, QueryResult, VerifiableQueryResult, VerificationOptions,
+};
+use crate::{
+    base::{
+        commitment::{InnerProductProof, QueryCommitments, QueryCommitmentsExt},
+        database::{
+            owned_table_utility::*, ColumnType, LiteralValue, OwnedTableTestAccessor, TableRef,
+        },
+    },
+    proof_primitive::{
+        dory::DynamicDoryEvaluationProof, inner_product::curve_25519_scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+
+/// `select a, b from sxt.t where a = $1`
+fn plan_and_accessor() -> (
+    DynProofPlan,
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+) {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 2, 5]),
+        varchar("b", ["x", "y", "z", "w", "v"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let plan = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(
+            column(&t, "a", &accessor),
+            DynProofExpr::try_new_placeholder(1, ColumnType::BigInt).unwrap(),
+        ),
+    );
+    (plan, accessor)
+}
+
+/// Assert that two verification results are both successes with the same data or both failures
+/// with the same error
+fn assert_same_outcome(
+    prepared: QueryResult<Curve25519Scalar>,
+    unprepared: QueryResult<Curve25519Scalar>,
+) {
+    match (prepared, unprepared) {
+        (Ok(prepared), Ok(unprepared)) => {
+            assert_eq!(prepared.table, unprepared.table);
+            assert_eq!(prepared.verification_hash, unprepared.verification_hash);
+        }
+        (Err(prepared), Err(unprepared)) => {
+            assert_eq!(prepared.to_string(), unprepared.to_string());
+        }
+        (prepared, unprepared) => panic!(
+            "outcomes differ: prepared is ok: {}, unprepared is ok: {}",
+            prepared.is_ok(),
+            unprepared.is_ok()
+        ),
+    }
+}
+
+#[test]
+fn prepared_and_unprepared_verification_agree_on_valid_proofs() {
+    let (plan, accessor) = plan_and_accessor();
+    let prepared = PreparedPlanVerifier::<InnerProductProof, _>::new(plan.clone(), ());
+    for value in [2_i64, 5, 7] {
+        let params = [LiteralValue::BigInt(value)];
+        let verifiable_res =
+            VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &params)
+                .unwrap();
+        let prepared_res = prepared.verify(verifiable_res.clone(), &accessor, &params);
+        assert!(prepared_res.is_ok());
+        assert_same_outcome(
+            prepared_res,
+            verifiable_res.verify(&plan, &accessor, &(), &params),
+        );
+    }
+    let res = prepared
+        .verify(
+            VerifiableQueryResult::new(&plan, &accessor, &(), &[LiteralValue::BigInt(2)]).unwrap(),
+            &accessor,
+            &[LiteralValue::BigInt(2)],
+        )
+        .unwrap()
+        .table;
+    assert_eq!(
+        res,
+        owned_table([bigint("a", [2_i64, 2]), varchar("b", ["y", "w"])])
+    );
+}
+
+#[test]
+fn prepared_and_unprepared_verification_agree_on_invalid_proofs() {
+    let (plan, accessor) = plan_and_accessor();
+    let prepared = PreparedPlanVerifier::<InnerProductProof, _>::new(plan.clone(), ());
+    let params = [LiteralValue::BigInt(2)];
+    let verifiable_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &params).unwrap();
+
+    // A tampered result
+    let mut tampered = verifiable_res.clone();
+    tampered.result = owned_table([bigint("a", [2_i64]), varchar("b", ["y"])]);
+    let prepared_res = prepared.verify(tampered.clone(), &accessor, &params);
+    assert!(prepared_res.is_err());
+    assert_same_outcome(
+        prepared_res,
+        tampered.verify(&plan, &accessor, &(), &params),
+    );
+
+    // Different parameters than the proof was created with
+    let other_params = [LiteralValue::BigInt(3)];
+    let prepared_res = prepared.verify(verifiable_res.clone(), &accessor, &other_params);
+    assert!(prepared_res.is_err());
+    assert_same_outcome(
+        prepared_res,
+        verifiable_res
+            .clone()
+            .verify(&plan, &accessor, &(), &other_params),
+    );
+
+    // A proof of another plan
+    let t = TableRef::new("sxt", "t");
+    let other_plan = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(2)),
+    );
+    let other_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&other_plan, &accessor, &(), &params)
+            .unwrap();
+    let prepared_res = prepared.verify(other_res.clone(), &accessor, &params);
+    assert!(prepared_res.is_err());
+    assert_same_outcome(
+        prepared_res,
+        other_res.verify(&plan, &accessor, &(), &params),
+    );
+}
+
+#[test]
+fn prepared_verification_respects_its_options() {
+    let (plan, accessor) = plan_and_accessor();
+    let params = [LiteralValue::BigInt(2)];
+    let verifiable_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &params).unwrap();
+    let options = VerificationOptions::default().with_max_proof_bytes(Some(1));
+    let prepared =
+        PreparedPlanVerifier::<InnerProductProof, _>::new(plan.clone(), ()).with_options(options);
+    assert_eq!(prepared.options(), &options);
+    assert_eq!(prepared.plan(), &plan);
+    assert_same_outcome(
+        prepared.verify(verifiable_res.clone(), &accessor, &params),
+        verifiable_res.verify_with_options(&plan, &accessor, &(), &params, &options),
+    );
+}
+
+#[test]
+fn prepared_plan_verifiers_can_be_shared_across_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<PreparedPlanVerifier<'static, InnerProductProof, DynProofPlan>>();
+    assert_send_sync::<PreparedPlanVerifier<'static, DynamicDoryEvaluationProof, DynProofPlan>>();
+
+    let (plan, accessor) = plan_and_accessor();
+    let prepared = PreparedPlanVerifier::<InnerProductProof, _>::new(plan.clone(), ());
+    // Unlike the accessor, the commitments can be shared across threads
+    let commitments =
+        QueryCommitments::from_accessor_with_max_bounds(plan.get_column_references(), &accessor);
+    let verifiable_results: Vec<_> = [1_i64, 2, 3, 4]
+        .map(|value| {
+            let params = [LiteralValue::BigInt(value)];
+            let verifiable_res =
+                VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &params)
+                    .unwrap();
+            (verifiable_res, params)
+        })
+        .into();
+    std::thread::scope(|scope| {
+        for (verifiable_res, params) in verifiable_results {
+            let (prepared, commitments) = (&prepared, &commitments);
+            scope.spawn(move || {
+                assert!(prepared
+                    .verify(verifiable_res, commitments, &params)
+                    .is_ok());
+            });
+        }
+    });
+}
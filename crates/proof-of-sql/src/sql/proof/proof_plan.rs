@@ -16,6 +16,14 @@ use sqlparser::ast::Ident;
 #[enum_dispatch::enum_dispatch(DynProofPlan)]
 pub trait ProofPlan: Debug + Send + Sync + ProverEvaluate {
     /// Form components needed to verify and proof store into `VerificationBuilder`
+    ///
+    /// The evaluations and constraints have to be consumed and produced in the order the prover
+    /// produced them in [`ProverEvaluate`]. The returned [`TableEvaluation`] holds one evaluation
+    /// per column of [`get_column_result_fields`](Self::get_column_result_fields) along with the
+    /// chi evaluation of the output length.
+    ///
+    /// With the `test` feature, `assert_plan_round_consistency` checks that this mirrors the
+    /// prover and names the first step at which it doesn't.
     fn verifier_evaluate<S: Scalar>(
         &self,
         builder: &mut impl VerificationBuilder<S>,
@@ -35,6 +43,10 @@ pub trait ProofPlan: Debug + Send + Sync + ProverEvaluate {
     fn get_table_references(&self) -> IndexSet<TableRef>;
 }
 
+/// The prover side of a [`ProofPlan`], evaluated in two rounds
+///
+/// Both rounds return the output of the plan. The first round runs before any challenge is
+/// drawn and the final round runs after the result has been committed to.
 #[enum_dispatch::enum_dispatch(DynProofPlan)]
 pub trait ProverEvaluate {
     /// Evaluate the query, modify `FirstRoundBuilder` and return the result.
@@ -0,0 +1,245 @@
+use super::ProofPlan;
+#[cfg(feature = "std")]
+use crate::base::proof::{Keccak256Transcript, Transcript};
+use crate::{
+    base::{
+        commitment::CommitmentEvaluationProof,
+        database::{DataAccessor, LiteralValue},
+        proof::PlaceholderError,
+        scalar::Scalar,
+    },
+    proof_primitive::sumcheck::{SumcheckCheckpoint, SumcheckCheckpointRef},
+};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use snafu::Snafu;
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+/// The work done by the prover of a [`super::QueryProof`] so far
+///
+/// The query is always re-evaluated upon resumption since that is cheap compared to the
+/// commitments and the sumcheck.
+#[derive(Serialize)]
+pub(super) struct ProverCheckpointRef<'a, C, S: Scalar> {
+    /// The commitments to the columns referenced in the query
+    pub column_commitments: &'a [C],
+    /// The commitments to the intermediate MLEs of the first round
+    pub first_round_commitments: &'a [C],
+    /// The commitments to the intermediate MLEs of the final round, once computed
+    pub final_round_commitments: Option<&'a [C]>,
+    /// The state of the sumcheck prover after its last completed round
+    pub sumcheck: Option<SumcheckCheckpointRef<'a, S>>,
+}
+
+/// An owned [`ProverCheckpointRef`]
+#[derive(Deserialize)]
+pub(super) struct ProverCheckpoint<C, S: Scalar> {
+    pub column_commitments: Vec<C>,
+    pub first_round_commitments: Vec<C>,
+    pub final_round_commitments: Option<Vec<C>>,
+    pub sumcheck: Option<SumcheckCheckpoint<S>>,
+}
+
+/// Where the prover of a [`super::QueryProof`] loads and saves its checkpoints
+pub(super) trait ProverCheckpoints<CP: CommitmentEvaluationProof> {
+    /// The error of loading or saving a checkpoint
+    type Error: From<PlaceholderError>;
+
+    /// Load the checkpoint to resume proving `expr` from, if any
+    fn load(
+        &mut self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        params: &[LiteralValue],
+    ) -> Result<Option<ProverCheckpoint<CP::Commitment, CP::Scalar>>, Self::Error>;
+
+    /// Save a checkpoint of the work done so far
+    fn save(
+        &mut self,
+        checkpoint: &ProverCheckpointRef<'_, CP::Commitment, CP::Scalar>,
+    ) -> Result<(), Self::Error>;
+}
+
+/// No checkpoints at all
+impl<CP: CommitmentEvaluationProof> ProverCheckpoints<CP> for () {
+    type Error = PlaceholderError;
+
+    fn load(
+        &mut self,
+        _expr: &(impl ProofPlan + Serialize),
+        _accessor: &impl DataAccessor<CP::Scalar>,
+        _params: &[LiteralValue],
+    ) -> Result<Option<ProverCheckpoint<CP::Commitment, CP::Scalar>>, Self::Error> {
+        Ok(None)
+    }
+
+    fn save(
+        &mut self,
+        _checkpoint: &ProverCheckpointRef<'_, CP::Commitment, CP::Scalar>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Errors that can occur when proving with checkpoints.
+#[cfg(feature = "std")]
+#[derive(Debug, Snafu)]
+pub enum CheckpointError {
+    /// The query could not be evaluated.
+    #[snafu(transparent)]
+    Placeholder {
+        /// The underlying source error
+        source: PlaceholderError,
+    },
+    /// The checkpoint could not be read or written.
+    #[snafu(context(false), display("checkpoint io error: {source}"))]
+    Io {
+        /// The underlying source error
+        source: std::io::Error,
+    },
+    /// The checkpoint could not be encoded.
+    #[snafu(context(false), display("failed to encode checkpoint: {source}"))]
+    Encode {
+        /// The underlying source error
+        source: bincode::error::EncodeError,
+    },
+    /// The checkpoint could not be decoded.
+    #[snafu(context(false), display("failed to decode checkpoint: {source}"))]
+    Decode {
+        /// The underlying source error
+        source: bincode::error::DecodeError,
+    },
+    /// The checkpoint was saved while proving a different plan or different parameters.
+    #[snafu(display("checkpoint belongs to a different plan or different parameters"))]
+    PlanMismatch,
+    /// The checkpoint was saved while proving against different data.
+    #[snafu(display("checkpoint belongs to different data"))]
+    DataMismatch,
+    /// Proving was deliberately interrupted after saving a checkpoint.
+    #[cfg(test)]
+    #[snafu(display("proving was interrupted after checkpoint {checkpoint}"))]
+    Interrupted {
+        /// The number of checkpoints saved before the interruption
+        checkpoint: usize,
+    },
+}
+
+/// The name of the checkpoint file within the checkpoint directory
+#[cfg(feature = "std")]
+const CHECKPOINT_FILE: &str = "checkpoint.bin";
+
+/// Checkpoints kept in a directory, where each one replaces the previous one
+#[cfg(feature = "std")]
+pub(super) struct CheckpointDirectory<'a> {
+    path: &'a Path,
+    resume: bool,
+    fingerprints: ([u8; 32], [u8; 32]),
+    /// The number of checkpoints saved so far
+    #[cfg(test)]
+    pub(super) saved: usize,
+    /// Fail with [`CheckpointError::Interrupted`] after saving this many checkpoints
+    #[cfg(test)]
+    pub(super) interrupt_after: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> CheckpointDirectory<'a> {
+    /// Checkpoints in `path`, resuming from the existing checkpoint if `resume` is set
+    pub(super) fn new(path: &'a Path, resume: bool) -> Self {
+        Self {
+            path,
+            resume,
+            fingerprints: ([0; 32], [0; 32]),
+            #[cfg(test)]
+            saved: 0,
+            #[cfg(test)]
+            interrupt_after: None,
+        }
+    }
+}
+
+/// Fingerprints of the plan with its parameters and of the data the plan references
+#[cfg(feature = "std")]
+fn fingerprints<S: Scalar>(
+    expr: &(impl ProofPlan + Serialize),
+    accessor: &impl DataAccessor<S>,
+    params: &[LiteralValue],
+) -> ([u8; 32], [u8; 32]) {
+    let mut plan_transcript = Keccak256Transcript::new();
+    plan_transcript.extend_serialize_as_le(expr);
+    plan_transcript.extend_serialize_as_le(params);
+
+    let mut data_transcript = Keccak256Transcript::new();
+    for table_ref in expr.get_table_references() {
+        data_transcript.extend_as_le([
+            accessor.get_length(&table_ref) as u64,
+            accessor.get_offset(&table_ref) as u64,
+        ]);
+    }
+    for col_ref in expr.get_column_references() {
+        let (table_ref, column_id) = (col_ref.table_ref(), col_ref.column_id());
+        data_transcript.extend_as_le([accessor.get_column_offset(&table_ref, &column_id) as u64]);
+        data_transcript
+            .extend_scalars_as_be(&accessor.get_column(&table_ref, &column_id).to_scalar());
+    }
+    (
+        plan_transcript.challenge_as_le(),
+        data_transcript.challenge_as_le(),
+    )
+}
+
+#[cfg(feature = "std")]
+impl<CP: CommitmentEvaluationProof> ProverCheckpoints<CP> for CheckpointDirectory<'_> {
+    type Error = CheckpointError;
+
+    fn load(
+        &mut self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        params: &[LiteralValue],
+    ) -> Result<Option<ProverCheckpoint<CP::Commitment, CP::Scalar>>, Self::Error> {
+        self.fingerprints = fingerprints(expr, accessor, params);
+        if !self.resume {
+            return Ok(None);
+        }
+        let (plan_fingerprint, data_fingerprint, checkpoint): ([u8; 32], [u8; 32], _) =
+            bincode::serde::decode_from_slice(
+                &fs::read(self.path.join(CHECKPOINT_FILE))?,
+                bincode::config::legacy(),
+            )?
+            .0;
+        if plan_fingerprint != self.fingerprints.0 {
+            Err(CheckpointError::PlanMismatch)
+        } else if data_fingerprint != self.fingerprints.1 {
+            Err(CheckpointError::DataMismatch)
+        } else {
+            Ok(Some(checkpoint))
+        }
+    }
+
+    fn save(
+        &mut self,
+        checkpoint: &ProverCheckpointRef<'_, CP::Commitment, CP::Scalar>,
+    ) -> Result<(), Self::Error> {
+        let bytes = bincode::serde::encode_to_vec(
+            (self.fingerprints.0, self.fingerprints.1, checkpoint),
+            bincode::config::legacy(),
+        )?;
+        // Write to a temporary file first so that an interruption never leaves a partial checkpoint
+        let tmp_path = self.path.join(format!("{CHECKPOINT_FILE}.tmp"));
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(tmp_path, self.path.join(CHECKPOINT_FILE))?;
+        #[cfg(test)]
+        {
+            self.saved += 1;
+            if self.interrupt_after == Some(self.saved) {
+                return Err(CheckpointError::Interrupted {
+                    checkpoint: self.saved,
+                });
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use super::{
-    make_sumcheck_state::make_sumcheck_prover_state, FinalRoundBuilder, FirstRoundBuilder,
-    ProofPlan, QueryData, QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars,
-    VerificationBuilderImpl,
+    make_sumcheck_state::make_sumcheck_prover_state,
+    prover_checkpoint::{ProverCheckpoint, ProverCheckpointRef, ProverCheckpoints},
+    FinalRoundBuilder, FirstRoundBuilder, LazyQueryData, ProofPlan, QueryData, QueryError,
+    QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilderImpl,
+    VerificationOptions,
 };
 use crate::{
     base::{
@@ -11,17 +13,21 @@ use crate::{
             ColumnRef, CommitmentAccessor, DataAccessor, LiteralValue, MetadataAccessor,
             OwnedTable, Table, TableRef,
         },
+        encoded_size::{seq_size, LENGTH_SIZE, SCALAR_SIZE},
         map::{IndexMap, IndexSet},
         math::log2_up,
         polynomial::{compute_evaluation_vector, MultilinearExtension},
-        proof::{Keccak256Transcript, PlaceholderResult, ProofError, Transcript},
+        proof::{
+            transcript_serialize, Keccak256Transcript, PlaceholderResult, ProofError, Transcript,
+        },
+        scalar::Scalar,
     },
-    proof_primitive::sumcheck::SumcheckProof,
+    proof_primitive::sumcheck::{SumcheckCheckpoint, SumcheckProof},
     utils::log,
 };
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
 use bumpalo::Bump;
-use core::cmp;
+use core::{cmp, mem::size_of};
 use itertools::Itertools;
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
@@ -36,7 +42,7 @@ const SETUP_HASH: [u8; 32] = [
 ///
 /// Basically we are looking for the smallest offset and the largest offset + length
 /// so that we have an index range of the table rows that the query is referencing.
-fn get_index_range<'a>(
+pub(super) fn get_index_range<'a>(
     accessor: &dyn MetadataAccessor,
     table_refs: impl IntoIterator<Item = &'a TableRef>,
 ) -> (usize, usize) {
@@ -52,6 +58,141 @@ fn get_index_range<'a>(
         .unwrap_or((0, 1))
 }
 
+/// Compute the commitments to `columns`, each with the generator offset of its own commitment
+fn compute_commitments_with_offsets<C: Commitment>(
+    columns: &[CommittableColumn],
+    offsets: &[usize],
+    setup: &C::PublicSetup<'_>,
+) -> Vec<C> {
+    let mut commitments = vec![C::default(); columns.len()];
+    for offset in offsets.iter().copied().sorted_unstable().dedup() {
+        let (indexes, offset_columns): (Vec<_>, Vec<_>) = columns
+            .iter()
+            .zip(offsets)
+            .enumerate()
+            .filter(|(_, (_, &column_offset))| column_offset == offset)
+            .map(|(index, (column, _))| (index, column.clone()))
+            .unzip();
+        for (index, commitment) in
+            indexes
+                .into_iter()
+                .zip(C::compute_commitments(&offset_columns, offset, setup))
+        {
+            commitments[index] = commitment;
+        }
+    }
+    commitments
+}
+
+/// Group the indexes of the column references by the generator offsets of their commitments,
+/// leaving out the ones committed with `main_offset`
+///
+/// The groups are sorted by offset so that the prover and verifier agree on their order.
+fn group_columns_by_offset(
+    column_offsets: &[usize],
+    main_offset: usize,
+) -> BTreeMap<usize, Vec<usize>> {
+    let mut groups = BTreeMap::<usize, Vec<usize>>::new();
+    for (index, &offset) in column_offsets.iter().enumerate() {
+        if offset != main_offset {
+            groups.entry(offset).or_default().push(index);
+        }
+    }
+    groups
+}
+
+/// Keep the entries of `column_values` which belong to the columns committed with `main_offset`
+fn retain_main_offset_columns<'a, T: Clone>(
+    column_values: &'a [T],
+    column_offsets: &'a [usize],
+    main_offset: usize,
+) -> impl Iterator<Item = T> + 'a {
+    column_values
+        .iter()
+        .zip(column_offsets)
+        .filter(move |(_, &offset)| offset == main_offset)
+        .map(|(value, _)| value.clone())
+}
+
+/// Get the columns of `column_refs` from `accessor`, grouped by the tables in `table_refs`
+pub(super) fn get_table_map<'a, S: Scalar>(
+    accessor: &'a impl DataAccessor<S>,
+    table_refs: &IndexSet<TableRef>,
+    column_refs: &IndexSet<ColumnRef>,
+) -> IndexMap<TableRef, Table<'a, S>> {
+    table_refs
+        .iter()
+        .map(|table_ref| {
+            let idents: IndexSet<Ident> = column_refs
+                .iter()
+                .filter(|col_ref| col_ref.table_ref() == *table_ref)
+                .map(ColumnRef::column_id)
+                .collect();
+            (table_ref.clone(), accessor.get_table(table_ref, &idents))
+        })
+        .collect()
+}
+
+/// Returns the number of generators a setup must have to prove `exprs` over the data in
+/// `accessor`, i.e. one more than the largest generator index the proof uses.
+///
+/// This accounts for
+/// - the commitments to the referenced columns, each with its own generator offset,
+/// - the commitments to intermediate MLEs, which are committed with the smallest offset of the
+///   referenced tables and are no longer than the range of the proof,
+/// - the evaluation proofs, which open MLEs padded to `2^num_sumcheck_variables` entries.
+///
+/// Compare the result with [`Commitment::num_generators`] of a setup. Only the first round of
+/// `exprs` is evaluated, which is enough to determine the range of the proof.
+///
+/// # Errors
+/// Returns an error if the first round evaluation of some plan fails, e.g. because of a missing
+/// placeholder parameter.
+pub fn required_num_generators<S: Scalar>(
+    exprs: &[impl ProofPlan],
+    accessor: &impl DataAccessor<S>,
+    params: &[LiteralValue],
+) -> PlaceholderResult<usize> {
+    let table_refs: IndexSet<TableRef> = exprs
+        .iter()
+        .flat_map(ProofPlan::get_table_references)
+        .collect();
+    let (min_row_num, max_row_num) = get_index_range(accessor, &table_refs);
+    let column_refs: IndexSet<ColumnRef> = exprs
+        .iter()
+        .flat_map(ProofPlan::get_column_references)
+        .collect();
+    let column_offsets: Vec<usize> = column_refs
+        .iter()
+        .map(|col_ref| accessor.get_column_offset(&col_ref.table_ref(), &col_ref.column_id()))
+        .collect();
+    let table_map = get_table_map(accessor, &table_refs, &column_refs);
+
+    let alloc = Bump::new();
+    let mut first_round_builder = FirstRoundBuilder::new((max_row_num - min_row_num).max(1));
+    for expr in exprs {
+        expr.first_round_evaluate(&mut first_round_builder, &alloc, &table_map, params)?;
+    }
+    let num_sumcheck_variables = cmp::max(log2_up(first_round_builder.range_length()), 1);
+
+    let column_generators = column_refs
+        .iter()
+        .zip(&column_offsets)
+        .map(|(col_ref, &offset)| {
+            CommittableColumn::from(accessor.get_column(&col_ref.table_ref(), &col_ref.column_id()))
+                .generators_needed(offset)
+        });
+    // one evaluation proof is made at the smallest offset and one at every other column offset
+    let proof_generators = column_offsets
+        .iter()
+        .chain([&min_row_num])
+        .map(|offset| offset + (1 << num_sumcheck_variables));
+    Ok(column_generators
+        .chain(proof_generators)
+        .max()
+        .unwrap_or_default())
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct FirstRoundMessage<C> {
     /// Length of the range of generators we use
@@ -97,6 +238,9 @@ pub struct QueryProof<CP: CommitmentEvaluationProof> {
     pub(super) pcs_proof_evaluations: QueryProofPCSProofEvaluations<CP::Scalar>,
     /// Inner product proof of the MLEs' evaluations
     pub(super) evaluation_proof: CP,
+    /// Inner product proofs of the evaluations of the columns committed with a generator offset
+    /// other than the one of `evaluation_proof`, one per offset in increasing order
+    pub(super) offset_evaluation_proofs: Vec<CP>,
 }
 
 impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
@@ -108,32 +252,114 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         setup: &CP::ProverPublicSetup<'_>,
         params: &[LiteralValue],
     ) -> PlaceholderResult<(Self, OwnedTable<CP::Scalar>)> {
+        Self::new_with_proof_transcript(expr, accessor, setup, params, Transcript::new())
+    }
+
+    /// Create a new `QueryProof` using `transcript`, which may already contain messages, as the
+    /// transcript of the proof.
+    pub(super) fn new_with_proof_transcript(
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+        transcript: Keccak256Transcript,
+    ) -> PlaceholderResult<(Self, OwnedTable<CP::Scalar>)> {
+        Self::new_with_checkpoints(expr, accessor, setup, params, transcript, &mut ())
+    }
+
+    /// Create a new `QueryProof` using `transcript` as the transcript of the proof, resuming from
+    /// the checkpoint loaded from `checkpoints`, if any, and saving a checkpoint after each phase.
+    ///
+    /// The phases are the commitments of the first round, the commitments of the final round and
+    /// every round of the sumcheck. The query is evaluated again upon resumption, and every
+    /// message is absorbed into `transcript` again, so the proof is identical to one created
+    /// without interruption.
+    pub(super) fn new_with_checkpoints<K: ProverCheckpoints<CP>>(
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+        transcript: Keccak256Transcript,
+        checkpoints: &mut K,
+    ) -> Result<(Self, OwnedTable<CP::Scalar>), K::Error> {
+        let checkpoint = checkpoints.load(expr, accessor, params)?;
+        let (proof, mut results) = Self::new_with_plans(
+            core::slice::from_ref(expr),
+            accessor,
+            setup,
+            params,
+            transcript,
+            checkpoint,
+            checkpoints,
+        )?;
+        Ok((proof, results.pop().expect("there is one result per plan")))
+    }
+
+    /// Create a single `QueryProof` of all of `exprs`, returning the result of each of them.
+    ///
+    /// The plans are evaluated one after the other on the same builders, so they share the
+    /// transcript, the sumcheck and the evaluation proofs, and every column referenced by any of
+    /// them is committed and opened once. All plans and all results are absorbed into the
+    /// transcript before any challenge the proof depends on is drawn. With a single plan, the
+    /// proof is the one of that plan alone.
+    ///
+    /// Proving resumes from `checkpoint`, if any, and saves a checkpoint to `checkpoints` after
+    /// each phase as in [`Self::new_with_checkpoints`].
+    pub(super) fn new_with_plans<K: ProverCheckpoints<CP>>(
+        exprs: &[impl ProofPlan + Serialize],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+        mut transcript: Keccak256Transcript,
+        checkpoint: Option<ProverCheckpoint<CP::Commitment, CP::Scalar>>,
+        checkpoints: &mut K,
+    ) -> Result<(Self, Vec<OwnedTable<CP::Scalar>>), K::Error> {
         log::log_memory_usage("Start");
 
-        let (min_row_num, max_row_num) = get_index_range(accessor, &expr.get_table_references());
+        let (column_commitments, first_round_commitments, final_round_commitments, sumcheck) =
+            match checkpoint {
+                Some(ProverCheckpoint {
+                    column_commitments,
+                    first_round_commitments,
+                    final_round_commitments,
+                    sumcheck,
+                }) => (
+                    Some(column_commitments),
+                    Some(first_round_commitments),
+                    final_round_commitments,
+                    sumcheck,
+                ),
+                None => (None, None, None, None),
+            };
+
+        let table_refs: IndexSet<TableRef> = exprs
+            .iter()
+            .flat_map(ProofPlan::get_table_references)
+            .collect();
+        let (min_row_num, max_row_num) = get_index_range(accessor, &table_refs);
         let initial_range_length = (max_row_num - min_row_num).max(1);
         let alloc = Bump::new();
 
-        let total_col_refs = expr.get_column_references();
-        let table_map: IndexMap<TableRef, Table<CP::Scalar>> = expr
-            .get_table_references()
-            .into_iter()
-            .map(|table_ref| {
-                let idents: IndexSet<Ident> = total_col_refs
-                    .iter()
-                    .filter(|col_ref| col_ref.table_ref() == table_ref)
-                    .map(ColumnRef::column_id)
-                    .collect();
-                (table_ref.clone(), accessor.get_table(&table_ref, &idents))
-            })
+        let total_col_refs: IndexSet<ColumnRef> = exprs
+            .iter()
+            .flat_map(ProofPlan::get_column_references)
             .collect();
+        // Columns of the same table may be committed with different generator offsets
+        let column_offsets: Vec<usize> = total_col_refs
+            .iter()
+            .map(|col_ref| accessor.get_column_offset(&col_ref.table_ref(), &col_ref.column_id()))
+            .collect();
+        let table_map = get_table_map(accessor, &table_refs, &total_col_refs);
 
-        // Prover First Round: Evaluate the query && get the right number of post result challenges
+        // Prover First Round: Evaluate the queries && get the right number of post result challenges
         let mut first_round_builder = FirstRoundBuilder::new(initial_range_length);
-        let query_result =
-            expr.first_round_evaluate(&mut first_round_builder, &alloc, &table_map, params)?;
-        let owned_table_result = OwnedTable::from(&query_result);
-        let provable_result = query_result.into();
+        let results = exprs
+            .iter()
+            .map(|expr| {
+                expr.first_round_evaluate(&mut first_round_builder, &alloc, &table_map, params)
+                    .map(|query_result| OwnedTable::from(&query_result))
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
         let chi_evaluation_lengths = first_round_builder.chi_evaluation_lengths();
         let rho_evaluation_lengths = first_round_builder.rho_evaluation_lengths();
 
@@ -143,42 +369,59 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         let post_result_challenge_count = first_round_builder.num_post_result_challenges();
 
         // commit to any intermediate MLEs
-        let first_round_commitments =
-            first_round_builder.commit_intermediate_mles(min_row_num, setup);
+        let is_first_round_resumed = first_round_commitments.is_some();
+        let first_round_commitments = first_round_commitments
+            .unwrap_or_else(|| first_round_builder.commit_intermediate_mles(min_row_num, setup));
 
         // construct a transcript for the proof
-        let mut transcript: Keccak256Transcript = Transcript::new();
         transcript.extend_as_le([SETUP_HASH]);
         transcript.challenge_as_le();
-        transcript.extend_serialize_as_le(expr);
+        for expr in exprs {
+            transcript.extend_serialize_as_le(expr);
+        }
         transcript.challenge_as_le();
-        transcript.extend_serialize_as_le(&owned_table_result);
+        for result in &results {
+            transcript.extend_serialize_as_le(result);
+        }
         transcript.challenge_as_le();
 
-        for table in expr.get_table_references() {
-            let length = accessor.get_length(&table);
+        for table in &table_refs {
+            let length = accessor.get_length(table);
             transcript.extend_serialize_as_le(&[0, 0, 0, length]);
         }
         transcript.challenge_as_le();
 
-        for commitment in CP::Commitment::compute_commitments(
-            &expr
-                .get_column_references()
-                .into_iter()
-                .map(|col| {
-                    CommittableColumn::from(accessor.get_column(&col.table_ref(), &col.column_id()))
-                })
-                .collect_vec(),
-            min_row_num,
-            setup,
-        ) {
-            transcript.extend_serialize_as_le(&commitment);
+        let column_commitments = column_commitments.unwrap_or_else(|| {
+            compute_commitments_with_offsets::<CP::Commitment>(
+                &total_col_refs
+                    .iter()
+                    .map(|col| {
+                        CommittableColumn::from(
+                            accessor.get_column(&col.table_ref(), &col.column_id()),
+                        )
+                    })
+                    .collect_vec(),
+                &column_offsets,
+                setup,
+            )
+        });
+        for commitment in &column_commitments {
+            transcript.extend_serialize_as_le(commitment);
         }
         transcript.challenge_as_le();
 
         transcript.extend_serialize_as_le(&min_row_num);
         transcript.challenge_as_le();
 
+        if !is_first_round_resumed {
+            checkpoints.save(&ProverCheckpointRef {
+                column_commitments: &column_commitments,
+                first_round_commitments: &first_round_commitments,
+                final_round_commitments: None,
+                sumcheck: None,
+            })?;
+        }
+
         let first_round_message = FirstRoundMessage {
             range_length,
             chi_evaluation_lengths: chi_evaluation_lengths.to_vec(),
@@ -201,13 +444,24 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         let mut final_round_builder =
             FinalRoundBuilder::new(num_sumcheck_variables, post_result_challenges);
 
-        expr.final_round_evaluate(&mut final_round_builder, &alloc, &table_map, params)?;
+        for expr in exprs {
+            expr.final_round_evaluate(&mut final_round_builder, &alloc, &table_map, params)?;
+        }
 
         let num_sumcheck_variables = final_round_builder.num_sumcheck_variables();
 
         // commit to any intermediate MLEs
-        let final_round_commitments =
-            final_round_builder.commit_intermediate_mles(min_row_num, setup);
+        let is_final_round_resumed = final_round_commitments.is_some();
+        let final_round_commitments = final_round_commitments
+            .unwrap_or_else(|| final_round_builder.commit_intermediate_mles(min_row_num, setup));
+        if !is_final_round_resumed {
+            checkpoints.save(&ProverCheckpointRef {
+                column_commitments: &column_commitments,
+                first_round_commitments: &first_round_message.round_commitments,
+                final_round_commitments: Some(&final_round_commitments),
+                sumcheck: None,
+            })?;
+        }
 
         let final_round_message = FinalRoundMessage {
             subpolynomial_constraint_count: final_round_builder.num_sumcheck_subpolynomials(),
@@ -226,16 +480,42 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
                 .take(num_random_scalars)
                 .collect();
-        let state = make_sumcheck_prover_state(
-            final_round_builder.sumcheck_subpolynomials(),
-            num_sumcheck_variables,
-            &SumcheckRandomScalars::new(&random_scalars, range_length, num_sumcheck_variables),
-        );
+        let (state, coefficients) = match sumcheck {
+            Some(SumcheckCheckpoint {
+                state,
+                coefficients,
+            }) => (state, coefficients),
+            None => (
+                make_sumcheck_prover_state(
+                    final_round_builder.sumcheck_subpolynomials(),
+                    num_sumcheck_variables,
+                    &SumcheckRandomScalars::new(
+                        &random_scalars,
+                        range_length,
+                        num_sumcheck_variables,
+                    ),
+                ),
+                Vec::new(),
+            ),
+        };
         transcript.challenge_as_le();
 
         // create the sumcheck proof -- this is the main part of proving a query
         let mut evaluation_point = vec![Zero::zero(); state.num_vars];
-        let sumcheck_proof = SumcheckProof::create(&mut transcript, &mut evaluation_point, state);
+        let sumcheck_proof = SumcheckProof::create_with_checkpoints(
+            &mut transcript,
+            &mut evaluation_point,
+            state,
+            coefficients,
+            |sumcheck| {
+                checkpoints.save(&ProverCheckpointRef {
+                    column_commitments: &column_commitments,
+                    first_round_commitments: &first_round_message.round_commitments,
+                    final_round_commitments: Some(&final_round_message.round_commitments),
+                    sumcheck: Some(sumcheck),
+                })
+            },
+        )?;
 
         // evaluate the MLEs used in sumcheck except for the result columns
         let mut evaluation_vec = vec![Zero::zero(); range_length];
@@ -280,14 +560,22 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                     as Box<dyn MultilinearExtension<_>>
             })
             .collect();
+        // the columns committed with other generator offsets are left out and opened separately
+        let column_ref_mles_at_min_row_num = column_ref_mles
+            .iter()
+            .zip(&column_offsets)
+            .map(|(mle, &offset)| (offset == min_row_num).then_some(mle));
         for (multiplier, evaluator) in random_scalars.iter().zip(
             first_round_builder
                 .pcs_proof_mles()
                 .iter()
-                .chain(&column_ref_mles)
-                .chain(final_round_builder.pcs_proof_mles().iter()),
+                .map(Some)
+                .chain(column_ref_mles_at_min_row_num)
+                .chain(final_round_builder.pcs_proof_mles().iter().map(Some)),
         ) {
-            evaluator.mul_add(&mut folded_mle, multiplier);
+            if let Some(evaluator) = evaluator {
+                evaluator.mul_add(&mut folded_mle, multiplier);
+            }
         }
 
         // finally, form the inner product proof of the MLEs' evaluations
@@ -299,17 +587,107 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             setup,
         );
 
+        // form one inner product proof for the columns committed with each other offset
+        let offset_evaluation_proofs = group_columns_by_offset(&column_offsets, min_row_num)
+            .into_iter()
+            .map(|(offset, column_indexes)| {
+                transcript.extend_serialize_as_le(&offset);
+                let random_scalars: Vec<_> =
+                    core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                        .take(column_indexes.len())
+                        .collect();
+                let mut folded_mle = vec![Zero::zero(); range_length];
+                for (multiplier, &index) in random_scalars.iter().zip(&column_indexes) {
+                    column_ref_mles[index].mul_add(&mut folded_mle, multiplier);
+                }
+                CP::new(
+                    &mut transcript,
+                    &folded_mle,
+                    &evaluation_point,
+                    offset as u64,
+                    setup,
+                )
+            })
+            .collect();
+
         let proof = Self {
             first_round_message,
             final_round_message,
             sumcheck_proof,
             pcs_proof_evaluations,
             evaluation_proof,
+            offset_evaluation_proofs,
         };
 
         log::log_memory_usage("End");
 
-        Ok((proof, provable_result))
+        Ok((proof, results))
+    }
+
+    /// The lengths of the sequences in the proof along with the counts declared by the prover
+    fn sequence_lengths(&self) -> impl Iterator<Item = usize> {
+        [
+            self.first_round_message.post_result_challenge_count,
+            self.first_round_message.chi_evaluation_lengths.len(),
+            self.first_round_message.rho_evaluation_lengths.len(),
+            self.first_round_message.round_commitments.len(),
+            self.final_round_message.subpolynomial_constraint_count,
+            self.final_round_message.round_commitments.len(),
+            self.final_round_message.bit_distributions.len(),
+            self.pcs_proof_evaluations.first_round.len(),
+            self.pcs_proof_evaluations.column_ref.len(),
+            self.pcs_proof_evaluations.final_round.len(),
+            self.offset_evaluation_proofs.len(),
+        ]
+        .into_iter()
+    }
+
+    /// The number of bytes the proof takes up when serialized with bincode's legacy configuration,
+    /// computed from the number of elements in the proof without serializing it.
+    ///
+    /// This is exact as long as [`CommitmentEvaluationProof::encoded_size_hint`] and
+    /// [`Commitment::encoded_size_hint`] are exact for `CP`.
+    #[must_use]
+    pub fn encoded_size_hint(&self) -> usize {
+        let commitments_size = |commitments: &[CP::Commitment]| {
+            LENGTH_SIZE
+                + commitments
+                    .iter()
+                    .map(Commitment::encoded_size_hint)
+                    .sum::<usize>()
+        };
+        let first_round_message_size = 2 * LENGTH_SIZE
+            + seq_size(
+                self.first_round_message.chi_evaluation_lengths.len(),
+                LENGTH_SIZE,
+            )
+            + seq_size(
+                self.first_round_message.rho_evaluation_lengths.len(),
+                LENGTH_SIZE,
+            )
+            + commitments_size(&self.first_round_message.round_commitments);
+        // Each bit distribution consists of two `[u64; 4]` masks
+        let final_round_message_size = LENGTH_SIZE
+            + commitments_size(&self.final_round_message.round_commitments)
+            + seq_size(
+                self.final_round_message.bit_distributions.len(),
+                2 * 4 * size_of::<u64>(),
+            );
+        let pcs_proof_evaluations_size =
+            seq_size(self.pcs_proof_evaluations.first_round.len(), SCALAR_SIZE)
+                + seq_size(self.pcs_proof_evaluations.column_ref.len(), SCALAR_SIZE)
+                + seq_size(self.pcs_proof_evaluations.final_round.len(), SCALAR_SIZE);
+        first_round_message_size
+            + final_round_message_size
+            + self.sumcheck_proof.encoded_size_hint()
+            + pcs_proof_evaluations_size
+            + self.evaluation_proof.encoded_size_hint()
+            + LENGTH_SIZE
+            + self
+                .offset_evaluation_proofs
+                .iter()
+                .map(CP::encoded_size_hint)
+                .sum::<usize>()
     }
 
     #[tracing::instrument(name = "QueryProof::verify", level = "debug", skip_all, err)]
@@ -322,13 +700,204 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         setup: &CP::VerifierPublicSetup<'_>,
         params: &[LiteralValue],
     ) -> QueryResult<CP::Scalar> {
+        self.verify_with_options(
+            expr,
+            accessor,
+            result,
+            setup,
+            params,
+            &VerificationOptions::default(),
+        )
+    }
+
+    /// Verify a `QueryProof` with the limits set in `options`. Note: This does NOT transform the result!
+    pub fn verify_with_options(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: OwnedTable<CP::Scalar>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        options: &VerificationOptions,
+    ) -> QueryResult<CP::Scalar> {
+        self.verify_with_proof_transcript(
+            expr,
+            accessor,
+            result,
+            setup,
+            params,
+            Transcript::new(),
+            options,
+        )
+    }
+
+    /// Verify a `QueryProof` using `transcript`, which must contain the same messages as the one
+    /// the proof was created with, as the transcript of the proof.
+    #[expect(clippy::too_many_arguments)]
+    pub(super) fn verify_with_proof_transcript(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: OwnedTable<CP::Scalar>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        transcript: Keccak256Transcript,
+        options: &VerificationOptions,
+    ) -> QueryResult<CP::Scalar> {
+        self.verify_with_plan_data(
+            expr,
+            &PlanVerificationData::new(core::slice::from_ref(expr)),
+            accessor,
+            result,
+            setup,
+            params,
+            transcript,
+            options,
+        )
+    }
+
+    /// Verify a `QueryProof` using `transcript` as in [`Self::verify_with_proof_transcript`], with
+    /// the data of `expr` that does not depend on the proof precomputed in `plan_data`.
+    #[expect(clippy::too_many_arguments)]
+    pub(super) fn verify_with_plan_data(
+        self,
+        expr: &impl ProofPlan,
+        plan_data: &PlanVerificationData,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: OwnedTable<CP::Scalar>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        transcript: Keccak256Transcript,
+        options: &VerificationOptions,
+    ) -> QueryResult<CP::Scalar> {
+        let mut query_data = self.verify_with_plans(
+            core::slice::from_ref(expr),
+            plan_data,
+            accessor,
+            vec![result],
+            setup,
+            params,
+            transcript,
+            options,
+        )?;
+        Ok(query_data.pop().expect("there is one result per plan"))
+    }
+
+    /// Verify a `QueryProof` as in [`Self::verify_with_options`], except that the result columns
+    /// are only checked against the proof when they are accessed through the returned
+    /// [`LazyQueryData`].
+    pub(super) fn verify_lazily_with_options(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: OwnedTable<CP::Scalar>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        options: &VerificationOptions,
+    ) -> Result<LazyQueryData<CP::Scalar>, QueryError> {
+        let (query_data, result_evaluations) = self
+            .verify_with_plans_and_result_evaluations(
+                core::slice::from_ref(expr),
+                &PlanVerificationData::new(core::slice::from_ref(expr)),
+                accessor,
+                vec![result],
+                setup,
+                params,
+                Transcript::new(),
+                options,
+                true,
+            )?
+            .pop()
+            .expect("there is one result per plan");
+        LazyQueryData::try_new(
+            query_data,
+            expr.get_column_result_fields(),
+            result_evaluations,
+        )
+    }
+
+    /// Verify a `QueryProof` of all of `exprs` created by [`Self::new_with_plans`], where
+    /// `results` holds the result of each plan and `plan_data` is the data of all of `exprs`.
+    ///
+    /// Upon success, this returns the query data of each plan, which share the final challenge
+    /// of the transcript and hence the verification hash.
+    #[expect(clippy::too_many_arguments)]
+    pub(super) fn verify_with_plans(
+        self,
+        exprs: &[impl ProofPlan],
+        plan_data: &PlanVerificationData,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: Vec<OwnedTable<CP::Scalar>>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        transcript: Keccak256Transcript,
+        options: &VerificationOptions,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, QueryError> {
+        Ok(self
+            .verify_with_plans_and_result_evaluations(
+                exprs, plan_data, accessor, results, setup, params, transcript, options, false,
+            )?
+            .into_iter()
+            .map(|(query_data, _)| query_data)
+            .collect())
+    }
+
+    /// Verify a `QueryProof` as in [`Self::verify_with_plans`], returning the evaluations each
+    /// result column has to match along with the query data of each plan.
+    ///
+    /// If `defer_result_checks` is set, the result columns are not checked against these
+    /// evaluations, which is left to the caller. Everything else, including the sumcheck and the
+    /// evaluation proofs, is checked either way. Since every result is absorbed into the
+    /// transcript before any challenge is drawn, the evaluations are bound to the results.
+    #[expect(clippy::too_many_arguments)]
+    fn verify_with_plans_and_result_evaluations(
+        self,
+        exprs: &[impl ProofPlan],
+        plan_data: &PlanVerificationData,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: Vec<OwnedTable<CP::Scalar>>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        mut transcript: Keccak256Transcript,
+        options: &VerificationOptions,
+        defer_result_checks: bool,
+    ) -> Result<Vec<(QueryData<CP::Scalar>, ResultEvaluations<CP::Scalar>)>, QueryError> {
         log::log_memory_usage("Start");
+        let deadline = options.start_deadline();
+
+        if results.len() != exprs.len() {
+            Err(ProofError::VerificationError {
+                error: "invalid number of results",
+            })?;
+        }
+
+        // reject oversized proofs and results before anything is allocated for them
+        for length in self.sequence_lengths().chain(
+            results
+                .iter()
+                .flat_map(|result| [result.num_columns(), result.num_rows()]),
+        ) {
+            options.check_sequence_length(length)?;
+        }
 
-        let table_refs = expr.get_table_references();
-        let (min_row_num, _) = get_index_range(accessor, &table_refs);
+        let table_refs = &plan_data.table_refs;
+        let (min_row_num, _) = get_index_range(accessor, table_refs);
         let num_sumcheck_variables = cmp::max(log2_up(self.first_round_message.range_length), 1);
         assert!(num_sumcheck_variables > 0);
 
+        // reject declared lengths outside the range before anything is allocated for them
+        if self
+            .first_round_message
+            .chi_evaluation_lengths
+            .iter()
+            .chain(&self.first_round_message.rho_evaluation_lengths)
+            .any(|&length| length > self.first_round_message.range_length)
+        {
+            Err(ProofError::VerificationError {
+                error: "declared length exceeds the range length",
+            })?;
+        }
+
         // validate bit decompositions
         for dist in &self.final_round_message.bit_distributions {
             if !dist.is_valid() {
@@ -342,29 +911,49 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             }
         }
 
-        let column_references = expr.get_column_references();
+        let column_references = &plan_data.column_references;
+        if self.pcs_proof_evaluations.column_ref.len() != column_references.len() {
+            Err(ProofError::VerificationError {
+                error: "invalid number of column evaluations",
+            })?;
+        }
+        // every MLE the prover committed to has exactly one evaluation
+        if self.pcs_proof_evaluations.first_round.len()
+            != self.first_round_message.round_commitments.len()
+            || self.pcs_proof_evaluations.final_round.len()
+                != self.final_round_message.round_commitments.len()
+        {
+            Err(ProofError::VerificationError {
+                error: "invalid number of MLE evaluations",
+            })?;
+        }
+        let column_commitments: Vec<_> = column_references
+            .iter()
+            .map(|col| accessor.get_commitment(&col.table_ref(), &col.column_id()))
+            .collect();
+        let column_offsets: Vec<usize> = column_references
+            .iter()
+            .map(|col| accessor.get_column_offset(&col.table_ref(), &col.column_id()))
+            .collect();
 
         // construct a transcript for the proof
-        let mut transcript: Keccak256Transcript = Transcript::new();
         transcript.extend_as_le([SETUP_HASH]);
         transcript.challenge_as_le();
-        transcript.extend_serialize_as_le(expr);
+        transcript.extend_as_le_from_refs([plan_data.serialized_plans.as_slice()]);
         transcript.challenge_as_le();
-        transcript.extend_serialize_as_le(&result);
+        for result in &results {
+            transcript.extend_serialize_as_le(result);
+        }
         transcript.challenge_as_le();
 
-        for table in expr.get_table_references() {
-            let length = accessor.get_length(&table);
+        for table in table_refs {
+            let length = accessor.get_length(table);
             transcript.extend_serialize_as_le(&[0, 0, 0, length]);
         }
         transcript.challenge_as_le();
 
-        for commitment in expr
-            .get_column_references()
-            .into_iter()
-            .map(|col| accessor.get_commitment(&col.table_ref(), &col.column_id()))
-        {
-            transcript.extend_serialize_as_le(&commitment);
+        for commitment in &column_commitments {
+            transcript.extend_serialize_as_le(commitment);
         }
         transcript.challenge_as_le();
 
@@ -402,6 +991,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         transcript.challenge_as_le();
 
         // verify sumcheck up to the evaluation check
+        deadline.check()?;
         let subclaim = self.sumcheck_proof.verify_without_evaluation(
             &mut transcript,
             num_sumcheck_variables,
@@ -424,10 +1014,10 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         // Always prepend input lengths to the chi evaluation lengths
         let table_length_map = table_refs
-            .into_iter()
+            .iter()
             .map(|table_ref| {
-                let len = accessor.get_length(&table_ref);
-                (table_ref, len)
+                let len = accessor.get_length(table_ref);
+                (table_ref.clone(), len)
             })
             .collect::<IndexMap<TableRef, usize>>();
 
@@ -447,8 +1037,13 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &self.pcs_proof_evaluations.final_round,
         );
         let chi_eval_map: IndexMap<TableRef, CP::Scalar> = table_length_map
-            .into_iter()
-            .map(|(table_ref, length)| (table_ref, sumcheck_evaluations.chi_evaluations[&length]))
+            .iter()
+            .map(|(table_ref, length)| {
+                (
+                    table_ref.clone(),
+                    sumcheck_evaluations.chi_evaluations[length],
+                )
+            })
             .collect();
         let mut builder = VerificationBuilderImpl::new(
             sumcheck_evaluations,
@@ -458,45 +1053,51 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             self.first_round_message.chi_evaluation_lengths.clone(),
             self.first_round_message.rho_evaluation_lengths.clone(),
             subclaim.max_multiplicands,
-        );
+            table_length_map,
+            *options,
+        )
+        .with_deadline(deadline)
+        .with_result_checks_deferred(defer_result_checks);
 
-        let pcs_proof_commitments: Vec<_> = self
-            .first_round_message
-            .round_commitments
+        // Key the evaluations strictly by table and column ident, so that resolution does not
+        // depend on column references of the same table being adjacent
+        let mut evaluation_accessor: IndexMap<TableRef, IndexMap<Ident, CP::Scalar>> =
+            IndexMap::default();
+        for (column_ref, eval) in column_references
             .iter()
-            .cloned()
-            .chain(
-                column_references
-                    .iter()
-                    .map(|col| accessor.get_commitment(&col.table_ref(), &col.column_id())),
-            )
-            .chain(self.final_round_message.round_commitments.iter().cloned())
-            .collect();
-        let evaluation_accessor: IndexMap<_, _> = column_references
-            .into_iter()
             .zip(self.pcs_proof_evaluations.column_ref.iter().copied())
-            .chunk_by(|(r, _)| r.table_ref())
-            .into_iter()
-            .map(|(tr, g)| {
-                let im: IndexMap<_, _> = g.map(|(cr, eval)| (cr.column_id(), eval)).collect();
-                (tr, im)
-            })
-            .collect();
+        {
+            evaluation_accessor
+                .entry(column_ref.table_ref())
+                .or_default()
+                .insert(column_ref.column_id(), eval);
+        }
 
-        let verifier_evaluations = expr.verifier_evaluate(
-            &mut builder,
-            &evaluation_accessor,
-            Some(&result),
-            &chi_eval_map,
-            params,
-        )?;
-        // compute the evaluation of the result MLEs
-        let result_evaluations = result.mle_evaluations(&subclaim.evaluation_point);
-        // check the evaluation of the result MLEs
-        if verifier_evaluations.column_evals() != result_evaluations {
-            Err(ProofError::VerificationError {
-                error: "result evaluation check failed",
-            })?;
+        // the plans consume the builder in the order they were proven in
+        let mut result_column_evals = Vec::with_capacity(exprs.len());
+        for (expr, result) in exprs.iter().zip(&results) {
+            let verifier_evaluations = expr.verifier_evaluate(
+                &mut builder,
+                &evaluation_accessor,
+                Some(result),
+                &chi_eval_map,
+                params,
+            );
+            if builder.timed_out() {
+                Err(QueryError::Timeout)?;
+            }
+            let verifier_evaluations = verifier_evaluations?;
+            if !defer_result_checks {
+                // compute the evaluation of the result MLEs
+                let result_evaluations = result.mle_evaluations(&subclaim.evaluation_point);
+                // check the evaluation of the result MLEs
+                if verifier_evaluations.column_evals() != result_evaluations {
+                    Err(ProofError::VerificationError {
+                        error: "result evaluation check failed",
+                    })?;
+                }
+            }
+            result_column_evals.push(verifier_evaluations.column_evals().to_vec());
         }
 
         // perform the evaluation check of the sumcheck polynomial
@@ -506,21 +1107,53 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             })?;
         }
 
+        // the columns committed with other generator offsets are checked separately below
+        let (first_round_random_scalars, remaining_random_scalars) =
+            evaluation_random_scalars.split_at(self.pcs_proof_evaluations.first_round.len());
+        let (column_ref_random_scalars, final_round_random_scalars) =
+            remaining_random_scalars.split_at(column_offsets.len());
+        let pcs_proof_commitments: Vec<_> = self
+            .first_round_message
+            .round_commitments
+            .iter()
+            .cloned()
+            .chain(retain_main_offset_columns(
+                &column_commitments,
+                &column_offsets,
+                min_row_num,
+            ))
+            .chain(self.final_round_message.round_commitments.iter().cloned())
+            .collect();
+        let pcs_proof_random_scalars: Vec<_> = first_round_random_scalars
+            .iter()
+            .copied()
+            .chain(retain_main_offset_columns(
+                column_ref_random_scalars,
+                &column_offsets,
+                min_row_num,
+            ))
+            .chain(final_round_random_scalars.iter().copied())
+            .collect();
         let pcs_proof_evaluations: Vec<_> = self
             .pcs_proof_evaluations
             .first_round
             .iter()
-            .chain(self.pcs_proof_evaluations.column_ref.iter())
-            .chain(self.pcs_proof_evaluations.final_round.iter())
             .copied()
+            .chain(retain_main_offset_columns(
+                &self.pcs_proof_evaluations.column_ref,
+                &column_offsets,
+                min_row_num,
+            ))
+            .chain(self.pcs_proof_evaluations.final_round.iter().copied())
             .collect();
 
         // finally, check the MLE evaluations with the inner product proof
+        deadline.check()?;
         self.evaluation_proof
             .verify_batched_proof(
                 &mut transcript,
                 &pcs_proof_commitments,
-                &evaluation_random_scalars,
+                &pcs_proof_random_scalars,
                 &pcs_proof_evaluations,
                 &subclaim.evaluation_point,
                 min_row_num as u64,
@@ -531,13 +1164,103 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 error: "Inner product proof of MLE evaluations failed",
             })?;
 
-        let verification_hash = transcript.challenge_as_le();
+        // check the evaluations of the columns committed with each other offset
+        let offset_groups = group_columns_by_offset(&column_offsets, min_row_num);
+        if self.offset_evaluation_proofs.len() != offset_groups.len() {
+            Err(ProofError::VerificationError {
+                error: "invalid number of inner product proofs for column offsets",
+            })?;
+        }
+        for ((offset, column_indexes), offset_evaluation_proof) in offset_groups
+            .into_iter()
+            .zip(&self.offset_evaluation_proofs)
+        {
+            deadline.check()?;
+            transcript.extend_serialize_as_le(&offset);
+            let random_scalars: Vec<_> =
+                core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                    .take(column_indexes.len())
+                    .collect();
+            let commitments: Vec<_> = column_indexes
+                .iter()
+                .map(|&index| column_commitments[index].clone())
+                .collect();
+            let evaluations: Vec<_> = column_indexes
+                .iter()
+                .map(|&index| self.pcs_proof_evaluations.column_ref[index])
+                .collect();
+            offset_evaluation_proof
+                .verify_batched_proof(
+                    &mut transcript,
+                    &commitments,
+                    &random_scalars,
+                    &evaluations,
+                    &subclaim.evaluation_point,
+                    offset as u64,
+                    self.first_round_message.range_length,
+                    setup,
+                )
+                .map_err(|_e| ProofError::VerificationError {
+                    error: "Inner product proof of column evaluations at another offset failed",
+                })?;
+        }
+
+        let transcript_challenge = transcript.challenge_as_le();
 
         log::log_memory_usage("End");
 
-        Ok(QueryData {
-            table: result,
-            verification_hash,
-        })
+        Ok(results
+            .into_iter()
+            .zip(result_column_evals)
+            .map(|(result, column_evals)| {
+                (
+                    QueryData::new(result, transcript_challenge),
+                    ResultEvaluations {
+                        evaluation_point: subclaim.evaluation_point.clone(),
+                        column_evals,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+/// The evaluations the result columns of a plan have to match for its proof to hold
+#[derive(Debug, Clone)]
+pub(super) struct ResultEvaluations<S> {
+    /// The point the MLEs of the result columns are evaluated at
+    pub(super) evaluation_point: Vec<S>,
+    /// The evaluation of each result column implied by the proof
+    pub(super) column_evals: Vec<S>,
+}
+
+/// The data a [`QueryProof`] of one or more plans is verified with that depends only on the plans
+///
+/// This is computed anew for every verification unless it is cached by a
+/// [`PreparedPlanVerifier`](super::PreparedPlanVerifier).
+#[derive(Debug, Clone)]
+pub(super) struct PlanVerificationData {
+    /// The plans as they are absorbed into the transcript, one after the other
+    pub(super) serialized_plans: Vec<u8>,
+    /// The union of the [`ProofPlan::get_table_references`] of the plans
+    pub(super) table_refs: IndexSet<TableRef>,
+    /// The union of the [`ProofPlan::get_column_references`] of the plans
+    pub(super) column_references: IndexSet<ColumnRef>,
+}
+
+impl PlanVerificationData {
+    /// Compute the verification data of `exprs`
+    pub(super) fn new(exprs: &[impl ProofPlan + Serialize]) -> Self {
+        Self {
+            serialized_plans: exprs.iter().flat_map(transcript_serialize).collect(),
+            table_refs: exprs
+                .iter()
+                .flat_map(ProofPlan::get_table_references)
+                .collect(),
+            column_references: exprs
+                .iter()
+                .flat_map(ProofPlan::get_column_references)
+                .collect(),
+        }
     }
 }
@@ -1,4 +1,7 @@
-use super::{FinalRoundBuilder, ProofPlan, ProverEvaluate, QueryProof, VerificationBuilder};
+use super::{
+    required_num_generators, FinalRoundBuilder, ProofPlan, ProverEvaluate, QueryError, QueryProof,
+    VerificationBuilder, VerificationOptions,
+};
 use crate::{
     base::{
         bit::BitDistribution,
@@ -14,7 +17,10 @@ use crate::{
         scalar::Scalar,
     },
     proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
-    sql::proof::{FirstRoundBuilder, QueryData, SumcheckSubpolynomialType},
+    sql::{
+        proof::{FirstRoundBuilder, QueryData, SumcheckSubpolynomialType},
+        proof_plans::DynProofPlan,
+    },
 };
 use bumpalo::Bump;
 use serde::Serialize;
@@ -137,6 +143,7 @@ fn verify_a_trivial_query_proof_with_given_offset(n: usize, offset_generators: u
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof
         .clone()
         .verify(&expr, &accessor, result.clone(), &(), &[])
@@ -213,6 +220,83 @@ fn verify_fails_if_counts_dont_match() {
     assert!(proof.verify(&expr, &accessor, result, &(), &[]).is_err());
 }
 
+#[test]
+fn verify_fails_early_if_a_declared_length_exceeds_the_range_length() {
+    let expr = TrivialTestProofPlan::default();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        TableRef::new("sxt", "test"),
+        owned_table([bigint("a1", [0_i64; 2])]),
+        0,
+        (),
+    );
+    let (mut proof, result) =
+        QueryProof::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    proof
+        .first_round_message
+        .rho_evaluation_lengths
+        .push(usize::MAX);
+    assert!(matches!(
+        proof.verify(&expr, &accessor, result.clone(), &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "declared length exceeds the range length"
+            }
+        })
+    ));
+
+    let (mut proof, _) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    proof.first_round_message.chi_evaluation_lengths[0] =
+        proof.first_round_message.range_length + 1;
+    assert!(matches!(
+        proof.verify(&expr, &accessor, result, &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "declared length exceeds the range length"
+            }
+        })
+    ));
+}
+
+#[test]
+fn verify_fails_early_if_a_sequence_exceeds_the_length_limit() {
+    let expr = TrivialTestProofPlan::default();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        TableRef::new("sxt", "test"),
+        owned_table([bigint("a1", [0_i64; 2])]),
+        0,
+        (),
+    );
+    let options = VerificationOptions::default().with_max_sequence_length(Some(2));
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    assert!(proof
+        .clone()
+        .verify_with_options(&expr, &accessor, result.clone(), &(), &[], &options)
+        .is_ok());
+
+    // A declared count beyond the limit is rejected before anything is drawn for it
+    let mut tampered_proof = proof.clone();
+    tampered_proof
+        .final_round_message
+        .subpolynomial_constraint_count = usize::MAX;
+    assert!(matches!(
+        tampered_proof.verify_with_options(&expr, &accessor, result.clone(), &(), &[], &options),
+        Err(QueryError::SequenceTooLong {
+            length: usize::MAX,
+            max_sequence_length: 2
+        })
+    ));
+
+    // So is a result with too many rows
+    let long_result = owned_table([bigint("a1", [0_i64; 3])]);
+    assert!(matches!(
+        proof.verify_with_options(&expr, &accessor, long_result, &(), &[], &options),
+        Err(QueryError::SequenceTooLong {
+            length: 3,
+            max_sequence_length: 2
+        })
+    ));
+}
+
 #[test]
 fn verify_fails_if_the_number_of_bit_distributions_is_not_enough() {
     let expr = TrivialTestProofPlan {
@@ -359,6 +443,7 @@ fn verify_a_proof_with_an_anchored_commitment_and_given_offset(offset_generators
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof
         .clone()
         .verify(&expr, &accessor, result.clone(), &(), &[])
@@ -562,6 +647,7 @@ fn verify_a_proof_with_an_intermediate_commitment_and_given_offset(offset_genera
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof
         .clone()
         .verify(&expr, &accessor, result.clone(), &(), &[])
@@ -612,6 +698,45 @@ fn verify_fails_if_an_intermediate_commitment_doesnt_match() {
     assert!(proof.verify(&expr, &accessor, result, &(), &[]).is_err());
 }
 
+#[test]
+fn verify_fails_early_if_the_number_of_mle_evaluations_doesnt_match_the_commitments() {
+    let expr = DoubleSquareTestProofPlan {
+        ..Default::default()
+    };
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        TableRef::new("sxt", "test"),
+        owned_table([bigint("x", [3, 5])]),
+        0,
+        (),
+    );
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+
+    let mut tampered_proof = proof.clone();
+    tampered_proof
+        .pcs_proof_evaluations
+        .final_round
+        .push(Curve25519Scalar::from(2u64));
+    assert!(matches!(
+        tampered_proof.verify(&expr, &accessor, result.clone(), &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "invalid number of MLE evaluations"
+            }
+        })
+    ));
+
+    let mut tampered_proof = proof;
+    tampered_proof.final_round_message.round_commitments.pop();
+    assert!(matches!(
+        tampered_proof.verify(&expr, &accessor, result, &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "invalid number of MLE evaluations"
+            }
+        })
+    ));
+}
+
 #[test]
 fn verify_fails_if_an_intermediate_equation_isnt_satified() {
     // attempt to prove and verify an artificial query where
@@ -755,6 +880,7 @@ fn verify_a_proof_with_a_post_result_challenge_and_given_offset(offset_generator
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof
         .clone()
         .verify(&expr, &accessor, result.clone(), &(), &[])
@@ -898,6 +1024,7 @@ fn verify_a_proof_with_a_commitment_and_given_offset(offset_generators: usize) {
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof
         .clone()
         .verify(&expr, &accessor, result.clone(), &(), &[])
@@ -965,3 +1092,25 @@ fn verify_fails_if_the_commitment_doesnt_match() {
     let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
     assert!(proof.verify(&expr, &accessor, result, &(), &[]).is_err());
 }
+
+#[test]
+fn we_can_compute_the_number_of_generators_needed_to_prove_plans() {
+    let t = TableRef::new("sxt", "t");
+    let plan = DynProofPlan::new_table(
+        t.clone(),
+        vec![ColumnField::new("a".into(), ColumnType::BigInt)],
+    );
+    let data = owned_table([bigint("a", [1, 2, 3, 4, 5])]);
+
+    // 5 rows need 3 sumcheck variables, so the evaluation proof opens 8 generators
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    assert_eq!(
+        required_num_generators(&[plan.clone()], &accessor, &[]).unwrap(),
+        8
+    );
+
+    // The data and the evaluation proof are shifted by the offset of the table
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 3, ());
+    assert_eq!(plan.required_setup_size(&accessor, &[]).unwrap(), 11);
+}
@@ -1,9 +1,14 @@
 use crate::base::{
-    database::{ColumnCoercionError, OwnedTable, OwnedTableError, TableCoercionError},
+    commitment::QueryCommitmentsError,
+    database::{ColumnCoercionError, OwnedTable, OwnedTableError, TableCoercionError, TableRef},
     proof::ProofError,
     scalar::Scalar,
 };
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
 use snafu::Snafu;
+use sqlparser::ast::Ident;
+use tiny_keccak::{Hasher, Keccak};
 
 /// Verifiable query errors
 #[derive(Snafu, Debug)]
@@ -37,6 +42,46 @@ pub enum QueryError {
     /// The number of columns in the table was invalid.
     #[snafu(display("Invalid number of columns"))]
     InvalidColumnCount,
+    /// The commitments do not cover the tables and columns of the query.
+    #[snafu(transparent)]
+    InvalidCommitments {
+        /// The underlying source error
+        source: QueryCommitmentsError,
+    },
+    /// The caller's commitment validation rejected the commitment of a column of the query.
+    #[snafu(display(
+        "commitment of column {column_id} of table {table_ref} was rejected: {reason}"
+    ))]
+    RejectedCommitment {
+        /// The table of the column
+        table_ref: TableRef,
+        /// The column whose commitment was rejected
+        column_id: Ident,
+        /// The reason given by the validation
+        reason: String,
+    },
+    /// The verification did not finish within the timeout set in the verification options.
+    #[snafu(display("Verification timed out"))]
+    Timeout,
+    /// The serialized proof exceeds the byte limit set in the verification options.
+    #[snafu(display("proof of {proof_bytes} bytes exceeds the maximum of {max_proof_bytes}"))]
+    ProofTooLarge {
+        /// The number of bytes of the serialized proof
+        proof_bytes: usize,
+        /// The maximum number of bytes set in the verification options
+        max_proof_bytes: usize,
+    },
+    /// A sequence in the proof or its result exceeds the length limit set in the verification
+    /// options.
+    #[snafu(display(
+        "sequence of {length} elements exceeds the maximum of {max_sequence_length}"
+    ))]
+    SequenceTooLong {
+        /// The number of elements of the sequence
+        length: usize,
+        /// The maximum length set in the verification options
+        max_sequence_length: usize,
+    },
 }
 
 impl From<TableCoercionError> for QueryError {
@@ -54,6 +99,67 @@ impl From<TableCoercionError> for QueryError {
     }
 }
 
+/// The version of the derivation of [`QueryData::verification_hash`] from its
+/// [`VerificationHashInputs`].
+///
+/// This is bumped whenever the derivation, or what the proof transcript commits to, changes, so
+/// that hashes produced by different versions of this crate never collide.
+pub const VERIFICATION_HASH_VERSION: u32 = 1;
+
+/// Returns [`VERIFICATION_HASH_VERSION`], the version of the verification hash derivation of this
+/// version of the crate.
+#[must_use]
+pub const fn verification_hash_version() -> u32 {
+    VERIFICATION_HASH_VERSION
+}
+
+/// The inputs from which a verification hash is derived.
+///
+/// The verification hash is `keccak256(version || transcript_challenge)`, where `version` is
+/// serialized as 4 little-endian bytes. See [`VerificationHashInputs::to_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationHashInputs {
+    /// The [`VERIFICATION_HASH_VERSION`] of the derivation
+    pub version: u32,
+    /// The final challenge of the proof transcript once verification has completed.
+    ///
+    /// The transcript absorbs, in order, the setup hash, the serialized query plans, the results, the
+    /// lengths of the queried tables, the commitments of the queried columns, the minimum row
+    /// number, the first and final round messages, the sumcheck proof, the MLE evaluations, and
+    /// the evaluation proofs, so this challenge commits to all of them.
+    pub transcript_challenge: [u8; 32],
+}
+
+impl VerificationHashInputs {
+    /// Creates the inputs of the current [`VERIFICATION_HASH_VERSION`]
+    #[must_use]
+    pub fn new(transcript_challenge: [u8; 32]) -> Self {
+        Self {
+            version: VERIFICATION_HASH_VERSION,
+            transcript_challenge,
+        }
+    }
+
+    /// Returns the stable serialized form of the inputs, i.e. the preimage of the verification hash
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; 36] {
+        let mut bytes = [0; 36];
+        bytes[..4].copy_from_slice(&self.version.to_le_bytes());
+        bytes[4..].copy_from_slice(&self.transcript_challenge);
+        bytes
+    }
+
+    /// Returns the verification hash derived from the inputs
+    #[must_use]
+    pub fn verification_hash(&self) -> [u8; 32] {
+        let mut hasher = Keccak::v256();
+        hasher.update(&self.to_bytes());
+        let mut hash = [0; 32];
+        hasher.finalize(&mut hash);
+        hash
+    }
+}
+
 /// The verified results of a query along with metadata produced by verification
 pub struct QueryData<S: Scalar> {
     /// We use Apache Arrow's [`RecordBatch`] to represent a table
@@ -64,8 +170,98 @@ pub struct QueryData<S: Scalar> {
     pub table: OwnedTable<S>,
     /// Additionally, there is a 32-byte verification hash that is included with this table.
     /// This hash provides evidence that the verification has been run.
+    ///
+    /// It is derived from [`QueryData::verification_hash_inputs`], so hashes produced by versions
+    /// of this crate with different [`VERIFICATION_HASH_VERSION`]s are never equal.
     pub verification_hash: [u8; 32],
+    pub(super) transcript_challenge: [u8; 32],
+}
+
+impl<S: Scalar> QueryData<S> {
+    /// Creates the query data of a verified `table` from the final challenge of the proof transcript
+    pub(super) fn new(table: OwnedTable<S>, transcript_challenge: [u8; 32]) -> Self {
+        Self {
+            table,
+            verification_hash: VerificationHashInputs::new(transcript_challenge)
+                .verification_hash(),
+            transcript_challenge,
+        }
+    }
+
+    /// Returns the inputs the verification hash was derived from
+    ///
+    /// Comparing these with stored inputs tells a change of [`VERIFICATION_HASH_VERSION`] apart from a
+    /// change of the verified proof.
+    #[must_use]
+    pub fn verification_hash_inputs(&self) -> VerificationHashInputs {
+        VerificationHashInputs::new(self.transcript_challenge)
+    }
 }
 
 /// The result of a query -- either an error or a table.
 pub type QueryResult<S> = Result<QueryData<S>, QueryError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_serialize_verification_hash_inputs() {
+        let inputs = VerificationHashInputs::new([7; 32]);
+        assert_eq!(inputs.version, verification_hash_version());
+        let bytes = inputs.to_bytes();
+        assert_eq!(bytes[..4], VERIFICATION_HASH_VERSION.to_le_bytes());
+        assert_eq!(bytes[4..], [7; 32]);
+    }
+
+    // If these fixtures change, stored verification hashes will no longer match and
+    // `VERIFICATION_HASH_VERSION` must be bumped.
+    #[test]
+    fn we_derive_the_verification_hash_of_the_current_version_stably() {
+        assert_eq!(VERIFICATION_HASH_VERSION, 1);
+        let mut transcript_challenge = [0; 32];
+        for (byte, value) in transcript_challenge.iter_mut().zip(0u8..) {
+            *byte = value;
+        }
+        assert_eq!(
+            VerificationHashInputs::new(transcript_challenge).verification_hash(),
+            [
+                0x89, 0xa4, 0xff, 0x2e, 0x08, 0x88, 0x68, 0x68, 0x92, 0xf9, 0x98, 0x7f, 0x89, 0x97,
+                0x88, 0xac, 0xc3, 0xb7, 0xc1, 0x43, 0x77, 0xe2, 0x79, 0x98, 0x30, 0x7a, 0x4a, 0x61,
+                0xb5, 0x9e, 0x39, 0xb0
+            ]
+        );
+        assert_eq!(
+            VerificationHashInputs::new([0; 32]).verification_hash(),
+            [
+                0x78, 0xa4, 0xe8, 0xc4, 0x4c, 0x28, 0xfe, 0x9e, 0xd4, 0x73, 0x8b, 0xf6, 0x97, 0x80,
+                0x19, 0xb4, 0x9f, 0x59, 0x39, 0xca, 0x62, 0x2a, 0x9d, 0xb7, 0xb7, 0x39, 0x1c, 0x51,
+                0x90, 0xee, 0xa9, 0x49
+            ]
+        );
+    }
+
+    #[test]
+    fn verification_hashes_of_different_versions_differ() {
+        let mut transcript_challenge = [0; 32];
+        for (byte, value) in transcript_challenge.iter_mut().zip(0u8..) {
+            *byte = value;
+        }
+        let other_version = VerificationHashInputs {
+            version: 2,
+            transcript_challenge,
+        };
+        assert_eq!(
+            other_version.verification_hash(),
+            [
+                0xfe, 0xe8, 0x91, 0x66, 0xfd, 0x5c, 0x5c, 0x6b, 0x72, 0x0f, 0x67, 0x97, 0x5b, 0x2b,
+                0xa2, 0x27, 0xb3, 0xfb, 0xcd, 0xb3, 0x04, 0x56, 0x7d, 0x0c, 0x58, 0x99, 0x5c, 0xf2,
+                0x52, 0xd7, 0xef, 0xb6
+            ]
+        );
+        assert_ne!(
+            other_version.verification_hash(),
+            VerificationHashInputs::new(transcript_challenge).verification_hash()
+        );
+    }
+}
@@ -109,7 +109,8 @@ pub fn decode_multiple_elements<'a, T: ProvableResultElement<'a>>(
     data: &'a [u8],
     n: usize,
 ) -> Result<(Vec<T>, usize), QueryError> {
-    let mut res = Vec::with_capacity(n);
+    // every element takes up at least one byte, so a declared count beyond the data is invalid
+    let mut res = Vec::with_capacity(n.min(data.len()));
     let mut cnt = 0;
     for _ in 0..n {
         let (val, num_read) = <T>::decode(&data[cnt..])?;
@@ -0,0 +1,610 @@
+use super::{
+    query_proof::{get_index_range, get_table_map},
+    FinalRoundBuilder, FirstRoundBuilder, ProofPlan, SumcheckMleEvaluations, SumcheckRandomScalars,
+    SumcheckSubpolynomial, SumcheckSubpolynomialType, VerificationBuilder, VerificationOptions,
+};
+use crate::base::{
+    bit::BitDistribution,
+    database::{DataAccessor, LiteralValue, OwnedTable, TableRef},
+    map::IndexMap,
+    math::log2_up,
+    polynomial::{compute_evaluation_vector, MultilinearExtension},
+    proof::{PlaceholderError, ProofError, ProofSizeMismatch},
+    scalar::Scalar,
+};
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use bumpalo::Bump;
+use core::{cmp, fmt};
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+
+/// A kind of step of the round contract between the prover and the verifier of a [`ProofPlan`]
+///
+/// The steps of each kind form a queue of their own: the verifier has to consume them in the
+/// order the prover produced them, but steps of different kinds may interleave freely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundStepKind {
+    /// A challenge requested in the first round and consumed in the final round
+    PostResultChallenge,
+    /// A chi evaluation, whose length is declared in the first round
+    ChiEvaluation,
+    /// A rho evaluation, whose length is declared in the first round
+    RhoEvaluation,
+    /// An MLE committed to in the first round
+    FirstRoundMle,
+    /// An MLE committed to or anchored in the final round
+    FinalRoundMle,
+    /// A bit distribution produced in the final round
+    BitDistribution,
+    /// A sumcheck subpolynomial, i.e. a constraint, produced in the final round
+    SumcheckSubpolynomial,
+}
+
+impl RoundStepKind {
+    const ALL: [Self; 7] = [
+        Self::PostResultChallenge,
+        Self::ChiEvaluation,
+        Self::RhoEvaluation,
+        Self::FirstRoundMle,
+        Self::FinalRoundMle,
+        Self::BitDistribution,
+        Self::SumcheckSubpolynomial,
+    ];
+}
+
+impl fmt::Display for RoundStepKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::PostResultChallenge => "post-result challenge",
+            Self::ChiEvaluation => "chi evaluation",
+            Self::RhoEvaluation => "rho evaluation",
+            Self::FirstRoundMle => "first round MLE",
+            Self::FinalRoundMle => "final round MLE",
+            Self::BitDistribution => "bit distribution",
+            Self::SumcheckSubpolynomial => "sumcheck subpolynomial",
+        })
+    }
+}
+
+/// Ways in which the verifier of a [`ProofPlan`] fails to mirror its prover
+///
+/// Steps are numbered from zero in the order the verifier takes them, while the index of a step
+/// is its position in the queue of its [`RoundStepKind`].
+#[derive(Debug, Snafu)]
+pub enum RoundConsistencyError {
+    /// The prover failed to evaluate the plan.
+    #[snafu(transparent)]
+    ProverFailed {
+        /// The underlying source error
+        source: PlaceholderError,
+    },
+    /// The verifier consumed a step the prover never produced.
+    #[snafu(display(
+        "step {step} of the verifier consumes {kind} {index}, but the prover only produced \
+         {produced}"
+    ))]
+    Unproduced {
+        /// The step of the verifier
+        step: usize,
+        /// The kind of the step
+        kind: RoundStepKind,
+        /// The index of the step among the steps of its kind
+        index: usize,
+        /// The number of steps of this kind the prover produced
+        produced: usize,
+    },
+    /// The verifier expects a chi evaluation of another length than the prover declared.
+    #[snafu(display(
+        "step {step} of the verifier consumes chi evaluation {index} of length {expected}, but \
+         the prover declared length {declared}"
+    ))]
+    ChiLengthMismatch {
+        /// The step of the verifier
+        step: usize,
+        /// The index of the chi evaluation
+        index: usize,
+        /// The length the prover declared
+        declared: usize,
+        /// The length the verifier expects
+        expected: usize,
+    },
+    /// The verifier produces a subpolynomial of another type or degree than the prover.
+    #[snafu(display(
+        "step {step} of the verifier produces sumcheck subpolynomial {index} as \
+         {verifier_type:?} of degree {verifier_degree}, but the prover produced \
+         {prover_type:?} of degree {prover_degree}"
+    ))]
+    SubpolynomialMismatch {
+        /// The step of the verifier
+        step: usize,
+        /// The index of the subpolynomial
+        index: usize,
+        /// The type of the subpolynomial of the prover
+        prover_type: SumcheckSubpolynomialType,
+        /// The degree of the subpolynomial of the prover, i.e. the most MLEs in a term
+        prover_degree: usize,
+        /// The type of the subpolynomial of the verifier
+        verifier_type: SumcheckSubpolynomialType,
+        /// The degree the verifier declared
+        verifier_degree: usize,
+    },
+    /// The evaluation of a subpolynomial of the verifier differs from the one of the prover.
+    #[snafu(display(
+        "step {step} of the verifier produces sumcheck subpolynomial {index} with another \
+         evaluation than the prover's, e.g. because MLEs or challenges are consumed in another \
+         order than they were produced"
+    ))]
+    SubpolynomialEvaluationMismatch {
+        /// The step of the verifier
+        step: usize,
+        /// The index of the subpolynomial
+        index: usize,
+    },
+    /// The verifier failed for a reason other than a missing step.
+    #[snafu(display("the verifier fails after {steps} steps: {source}"))]
+    VerifierFailed {
+        /// The number of steps the verifier took
+        steps: usize,
+        /// The underlying source error
+        source: ProofError,
+    },
+    /// The verifier left steps the prover produced unconsumed.
+    #[snafu(display(
+        "the prover produced {produced} {kind}s, but the verifier consumed {consumed}"
+    ))]
+    Unconsumed {
+        /// The kind of the steps
+        kind: RoundStepKind,
+        /// The number of steps of this kind the prover produced
+        produced: usize,
+        /// The number of steps of this kind the verifier consumed
+        consumed: usize,
+    },
+    /// The evaluations of the result the verifier returns are not the ones of the prover's result.
+    #[snafu(display("the verifier evaluates the result differently than the prover computed it"))]
+    ResultEvaluationMismatch,
+}
+
+/// A step taken by the verifier, as recorded by [`RecordingVerificationBuilder`]
+enum VerifierStep<S> {
+    PostResultChallenge,
+    ChiEvaluation {
+        expected_length: Option<usize>,
+    },
+    RhoEvaluation,
+    FirstRoundMle,
+    FinalRoundMle,
+    BitDistribution,
+    SumcheckSubpolynomial {
+        subpolynomial_type: SumcheckSubpolynomialType,
+        eval: S,
+        degree: usize,
+    },
+}
+
+impl<S> VerifierStep<S> {
+    fn kind(&self) -> RoundStepKind {
+        match self {
+            Self::PostResultChallenge => RoundStepKind::PostResultChallenge,
+            Self::ChiEvaluation { .. } => RoundStepKind::ChiEvaluation,
+            Self::RhoEvaluation => RoundStepKind::RhoEvaluation,
+            Self::FirstRoundMle => RoundStepKind::FirstRoundMle,
+            Self::FinalRoundMle => RoundStepKind::FinalRoundMle,
+            Self::BitDistribution => RoundStepKind::BitDistribution,
+            Self::SumcheckSubpolynomial { .. } => RoundStepKind::SumcheckSubpolynomial,
+        }
+    }
+}
+
+/// The steps the prover produced, along with the evaluations of its MLEs and subpolynomials
+struct ProverSteps<'a, S: Scalar> {
+    post_result_challenges: &'a [S],
+    chi_evaluation_lengths: &'a [usize],
+    rho_evaluation_lengths: &'a [usize],
+    first_round_mle_evaluations: Vec<S>,
+    final_round_mle_evaluations: Vec<S>,
+    bit_distributions: &'a [BitDistribution],
+    /// The type, degree and evaluation of each subpolynomial
+    subpolynomials: Vec<(SumcheckSubpolynomialType, usize, S)>,
+}
+
+impl<S: Scalar> ProverSteps<'_, S> {
+    fn num_produced(&self, kind: RoundStepKind) -> usize {
+        match kind {
+            RoundStepKind::PostResultChallenge => self.post_result_challenges.len(),
+            RoundStepKind::ChiEvaluation => self.chi_evaluation_lengths.len(),
+            RoundStepKind::RhoEvaluation => self.rho_evaluation_lengths.len(),
+            RoundStepKind::FirstRoundMle => self.first_round_mle_evaluations.len(),
+            RoundStepKind::FinalRoundMle => self.final_round_mle_evaluations.len(),
+            RoundStepKind::BitDistribution => self.bit_distributions.len(),
+            RoundStepKind::SumcheckSubpolynomial => self.subpolynomials.len(),
+        }
+    }
+}
+
+/// Evaluate `subpolynomial` by evaluating every MLE of its terms, returning the evaluation along
+/// with the degree of the subpolynomial
+fn evaluate_subpolynomial<S: Scalar>(
+    subpolynomial: &SumcheckSubpolynomial<'_, S>,
+    evaluation_vec: &[S],
+) -> (S, usize) {
+    subpolynomial.iter_mul_by(S::ONE).fold(
+        (S::ZERO, 0),
+        |(eval, degree), (_, coefficient, mles)| {
+            (
+                eval + coefficient
+                    * mles
+                        .iter()
+                        .map(|mle| mle.inner_product(evaluation_vec))
+                        .product::<S>(),
+                degree.max(mles.len()),
+            )
+        },
+    )
+}
+
+/// A [`VerificationBuilder`] that serves the honest evaluations of everything the prover produced
+/// and records every step the verifier takes
+struct RecordingVerificationBuilder<'a, S: Scalar> {
+    prover_steps: &'a ProverSteps<'a, S>,
+    mle_evaluations: SumcheckMleEvaluations<'a, S>,
+    table_lengths: IndexMap<TableRef, usize>,
+    steps: Vec<VerifierStep<S>>,
+}
+
+impl<S: Scalar> RecordingVerificationBuilder<'_, S> {
+    /// Record `step`, returning its index among the steps of its kind
+    fn record(&mut self, step: VerifierStep<S>) -> usize {
+        let index = self
+            .steps
+            .iter()
+            .filter(|recorded| recorded.kind() == step.kind())
+            .count();
+        self.steps.push(step);
+        index
+    }
+
+    fn consume_chi_evaluation(
+        &mut self,
+        expected_length: Option<usize>,
+    ) -> Result<(S, usize), ProofSizeMismatch> {
+        let index = self.record(VerifierStep::ChiEvaluation { expected_length });
+        let length = self
+            .prover_steps
+            .chi_evaluation_lengths
+            .get(index)
+            .copied()
+            .ok_or(ProofSizeMismatch::TooFewChiLengths)?;
+        Ok((self.mle_evaluations.chi_evaluations[&length], length))
+    }
+}
+
+impl<S: Scalar> VerificationBuilder<S> for RecordingVerificationBuilder<'_, S> {
+    fn try_consume_chi_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        self.try_consume_chi_evaluation_with_length()
+            .map(|(chi_eval, _)| chi_eval)
+    }
+
+    fn try_consume_chi_evaluation_with_length(&mut self) -> Result<(S, usize), ProofSizeMismatch> {
+        self.consume_chi_evaluation(None)
+    }
+
+    /// Records the expected length instead of checking it, so that a mismatch is reported as
+    /// the step it happens at
+    fn try_consume_chi_evaluation_of_length(&mut self, length: usize) -> Result<S, ProofError> {
+        Ok(self.consume_chi_evaluation(Some(length))?.0)
+    }
+
+    fn try_consume_rho_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        let index = self.record(VerifierStep::RhoEvaluation);
+        let length = self
+            .prover_steps
+            .rho_evaluation_lengths
+            .get(index)
+            .copied()
+            .ok_or(ProofSizeMismatch::TooFewRhoLengths)?;
+        Ok(self.mle_evaluations.rho_evaluations[&length])
+    }
+
+    fn try_consume_first_round_mle_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        let index = self.record(VerifierStep::FirstRoundMle);
+        self.prover_steps
+            .first_round_mle_evaluations
+            .get(index)
+            .copied()
+            .ok_or(ProofSizeMismatch::TooFewMLEEvaluations)
+    }
+
+    fn try_consume_final_round_mle_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        let index = self.record(VerifierStep::FinalRoundMle);
+        self.prover_steps
+            .final_round_mle_evaluations
+            .get(index)
+            .copied()
+            .ok_or(ProofSizeMismatch::TooFewMLEEvaluations)
+    }
+
+    fn try_consume_final_round_mle_evaluations(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<S>, ProofSizeMismatch> {
+        core::iter::repeat_with(|| self.try_consume_final_round_mle_evaluation())
+            .take(count)
+            .collect()
+    }
+
+    fn try_consume_bit_distribution(&mut self) -> Result<BitDistribution, ProofSizeMismatch> {
+        let index = self.record(VerifierStep::BitDistribution);
+        self.prover_steps
+            .bit_distributions
+            .get(index)
+            .cloned()
+            .ok_or(ProofSizeMismatch::TooFewBitDistributions)
+    }
+
+    fn try_produce_sumcheck_subpolynomial_evaluation(
+        &mut self,
+        subpolynomial_type: SumcheckSubpolynomialType,
+        eval: S,
+        degree: usize,
+    ) -> Result<(), ProofSizeMismatch> {
+        self.record(VerifierStep::SumcheckSubpolynomial {
+            subpolynomial_type,
+            eval,
+            degree,
+        });
+        Ok(())
+    }
+
+    fn try_consume_post_result_challenge(&mut self) -> Result<S, ProofSizeMismatch> {
+        let index = self.record(VerifierStep::PostResultChallenge);
+        self.prover_steps
+            .post_result_challenges
+            .get(index)
+            .copied()
+            .ok_or(ProofSizeMismatch::PostResultCountMismatch)
+    }
+
+    fn singleton_chi_evaluation(&self) -> S {
+        self.mle_evaluations.singleton_chi_evaluation
+    }
+
+    fn rho_256_evaluation(&self) -> Option<S> {
+        self.mle_evaluations.rho_256_evaluation
+    }
+
+    fn evaluation_point(&self) -> &[S] {
+        self.mle_evaluations.evaluation_point
+    }
+
+    fn table_length(&self, table_ref: &TableRef) -> Option<usize> {
+        self.table_lengths.get(table_ref).copied()
+    }
+
+    fn verification_options(&self) -> VerificationOptions {
+        VerificationOptions::default()
+    }
+}
+
+/// Find the first step of the verifier which does not mirror the prover
+fn check_verifier_steps<S: Scalar>(
+    prover_steps: &ProverSteps<'_, S>,
+    verifier_steps: &[VerifierStep<S>],
+) -> Result<(), RoundConsistencyError> {
+    let mut consumed = [0; RoundStepKind::ALL.len()];
+    for (step, verifier_step) in verifier_steps.iter().enumerate() {
+        let kind = verifier_step.kind();
+        let index = consumed[kind as usize];
+        consumed[kind as usize] += 1;
+        let produced = prover_steps.num_produced(kind);
+        if index >= produced {
+            return Err(RoundConsistencyError::Unproduced {
+                step,
+                kind,
+                index,
+                produced,
+            });
+        }
+        match *verifier_step {
+            VerifierStep::ChiEvaluation {
+                expected_length: Some(expected),
+            } => {
+                let declared = prover_steps.chi_evaluation_lengths[index];
+                if declared != expected {
+                    return Err(RoundConsistencyError::ChiLengthMismatch {
+                        step,
+                        index,
+                        declared,
+                        expected,
+                    });
+                }
+            }
+            VerifierStep::SumcheckSubpolynomial {
+                subpolynomial_type: verifier_type,
+                eval,
+                degree: verifier_degree,
+            } => {
+                let (prover_type, prover_degree, prover_eval) = prover_steps.subpolynomials[index];
+                if (prover_type, prover_degree) != (verifier_type, verifier_degree) {
+                    return Err(RoundConsistencyError::SubpolynomialMismatch {
+                        step,
+                        index,
+                        prover_type,
+                        prover_degree,
+                        verifier_type,
+                        verifier_degree,
+                    });
+                }
+                if prover_eval != eval {
+                    return Err(RoundConsistencyError::SubpolynomialEvaluationMismatch {
+                        step,
+                        index,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Check that the verifier of `plan` mirrors its prover over the data in `accessor`.
+///
+/// The prover rounds are run as they are when proving, and the verifier is run on the honest
+/// evaluations, at a fixed point, of everything the prover produced. Every step the verifier
+/// takes is recorded and compared with the ones the prover produced: the verifier has to consume
+/// the post-result challenges, chi and rho evaluations, MLE evaluations and bit distributions in
+/// the order the prover produced them, declare the same chi lengths and produce the same
+/// subpolynomials, with the same types, degrees and evaluations. Finally, every step has to be
+/// consumed and the verifier has to evaluate the result the prover computed.
+///
+/// Such mistakes otherwise surface as a failed sumcheck or evaluation check when verifying a
+/// proof, which doesn't tell where the prover and the verifier diverge.
+///
+/// # Errors
+/// Returns the first divergence between the prover and the verifier as a
+/// [`RoundConsistencyError`], naming the step of the verifier it happens at.
+pub fn check_plan_round_consistency<S: Scalar>(
+    plan: &impl ProofPlan,
+    accessor: &impl DataAccessor<S>,
+    params: &[LiteralValue],
+) -> Result<(), RoundConsistencyError> {
+    let table_refs = plan.get_table_references();
+    let (min_row_num, max_row_num) = get_index_range(accessor, &table_refs);
+    let table_map = get_table_map(accessor, &table_refs, &plan.get_column_references());
+    let alloc = Bump::new();
+
+    let mut first_round_builder = FirstRoundBuilder::new((max_row_num - min_row_num).max(1));
+    let result = OwnedTable::from(&plan.first_round_evaluate(
+        &mut first_round_builder,
+        &alloc,
+        &table_map,
+        params,
+    )?);
+    let range_length = first_round_builder.range_length();
+    let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
+    // Any point works, as long as the prover and the verifier agree on it
+    let evaluation_point: Vec<S> = (0..num_sumcheck_variables)
+        .map(|i| S::from(i as u64 + 2))
+        .collect();
+    let post_result_challenges: Vec<S> = (0..first_round_builder.num_post_result_challenges())
+        .map(|i| S::from(i as u64 + 3))
+        .collect();
+
+    let mut final_round_builder = FinalRoundBuilder::new(
+        num_sumcheck_variables,
+        post_result_challenges
+            .iter()
+            .copied()
+            .collect::<VecDeque<_>>(),
+    );
+    plan.final_round_evaluate(&mut final_round_builder, &alloc, &table_map, params)?;
+
+    let mut evaluation_vec = vec![S::ZERO; range_length];
+    compute_evaluation_vector(&mut evaluation_vec, &evaluation_point);
+    let prover_steps = ProverSteps {
+        post_result_challenges: &post_result_challenges,
+        chi_evaluation_lengths: first_round_builder.chi_evaluation_lengths(),
+        rho_evaluation_lengths: first_round_builder.rho_evaluation_lengths(),
+        first_round_mle_evaluations: first_round_builder.evaluate_pcs_proof_mles(&evaluation_vec),
+        final_round_mle_evaluations: final_round_builder.evaluate_pcs_proof_mles(&evaluation_vec),
+        bit_distributions: final_round_builder.bit_distributions(),
+        subpolynomials: final_round_builder
+            .sumcheck_subpolynomials()
+            .iter()
+            .map(|subpolynomial| {
+                let (eval, degree) = evaluate_subpolynomial(subpolynomial, &evaluation_vec);
+                (subpolynomial.subpolynomial_type(), degree, eval)
+            })
+            .collect(),
+    };
+
+    let table_lengths: IndexMap<TableRef, usize> = table_refs
+        .iter()
+        .map(|table_ref| (table_ref.clone(), accessor.get_length(table_ref)))
+        .collect();
+    let mle_evaluations = SumcheckMleEvaluations::new(
+        range_length,
+        table_lengths
+            .values()
+            .chain(prover_steps.chi_evaluation_lengths)
+            .copied(),
+        prover_steps.rho_evaluation_lengths.iter().copied(),
+        &evaluation_point,
+        &SumcheckRandomScalars::new(&evaluation_point, range_length, num_sumcheck_variables),
+        &[],
+        &[],
+    );
+    let chi_eval_map: IndexMap<TableRef, S> = table_lengths
+        .iter()
+        .map(|(table_ref, length)| (table_ref.clone(), mle_evaluations.chi_evaluations[length]))
+        .collect();
+    let evaluation_accessor: IndexMap<TableRef, IndexMap<Ident, S>> = table_map
+        .iter()
+        .map(|(table_ref, table)| {
+            (
+                table_ref.clone(),
+                table
+                    .inner_table()
+                    .iter()
+                    .map(|(ident, column)| (ident.clone(), column.inner_product(&evaluation_vec)))
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let mut builder = RecordingVerificationBuilder {
+        prover_steps: &prover_steps,
+        mle_evaluations,
+        table_lengths,
+        steps: Vec::new(),
+    };
+    let verifier_evaluations = plan.verifier_evaluate(
+        &mut builder,
+        &evaluation_accessor,
+        Some(&result),
+        &chi_eval_map,
+        params,
+    );
+    check_verifier_steps(&prover_steps, &builder.steps)?;
+    let verifier_evaluations =
+        verifier_evaluations.map_err(|source| RoundConsistencyError::VerifierFailed {
+            steps: builder.steps.len(),
+            source,
+        })?;
+    for kind in RoundStepKind::ALL {
+        let produced = prover_steps.num_produced(kind);
+        let consumed = builder
+            .steps
+            .iter()
+            .filter(|step| step.kind() == kind)
+            .count();
+        if consumed < produced {
+            return Err(RoundConsistencyError::Unconsumed {
+                kind,
+                produced,
+                consumed,
+            });
+        }
+    }
+    if verifier_evaluations.column_evals() != result.mle_evaluations(&evaluation_point) {
+        return Err(RoundConsistencyError::ResultEvaluationMismatch);
+    }
+    Ok(())
+}
+
+/// Assert that the verifier of `plan` mirrors its prover over the data in `accessor`.
+///
+/// This is meant for tests of custom [`ProofPlan`]s. See [`check_plan_round_consistency`] for
+/// what is checked.
+///
+/// # Panics
+/// Panics with the step at which the prover and the verifier diverge if they do.
+pub fn assert_plan_round_consistency<S: Scalar>(
+    plan: &impl ProofPlan,
+    accessor: &impl DataAccessor<S>,
+) {
+    if let Err(error) = check_plan_round_consistency(plan, accessor, &[]) {
+        panic!("the verifier of {plan:?} does not mirror its prover: {error}");
+    }
+}
@@ -0,0 +1,272 @@
+use super::{
+    assert_plan_round_consistency, check_plan_round_consistency, FinalRoundBuilder,
+    FirstRoundBuilder, ProofPlan, ProverEvaluate, RoundConsistencyError, RoundStepKind,
+    SumcheckSubpolynomialType, VerificationBuilder,
+};
+use crate::{
+    base::{
+        commitment::naive_evaluation_proof::NaiveEvaluationProof,
+        database::{
+            table_utility::*, ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, Table,
+            TableEvaluation, TableRef, TableTestAccessor, TestAccessor,
+        },
+        map::{indexset, IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{proof_exprs::test_utility::*, proof_plans::test_utility::*},
+};
+use bumpalo::Bump;
+use sqlparser::ast::Ident;
+
+/// A way in which the verifier of [`SquarePlan`] fails to mirror its prover
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fault {
+    WrongChiLength,
+    SwappedMles,
+    ExtraMle,
+    WrongDegree,
+    MissingConstraint,
+    WrongResult,
+}
+
+/// A plan which proves that `a_squared = a * a` and returns `a`, with an optionally faulty verifier
+#[derive(Debug)]
+struct SquarePlan {
+    table_ref: TableRef,
+    fault: Option<Fault>,
+}
+
+impl SquarePlan {
+    fn column<'a, S: Scalar>(&self, table_map: &IndexMap<TableRef, Table<'a, S>>) -> &'a [i64] {
+        table_map[&self.table_ref].inner_table()[&Ident::new("a")]
+            .as_bigint()
+            .unwrap()
+    }
+}
+
+impl ProverEvaluate for SquarePlan {
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        _params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        builder.produce_chi_evaluation_length(self.column(table_map).len());
+        builder.request_post_result_challenges(1);
+        Ok(table_map[&self.table_ref].clone())
+    }
+
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        _params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let a = self.column(table_map);
+        let a_squared: &[_] = alloc.alloc_slice_fill_iter(a.iter().map(|x| x * x));
+        let alpha = builder.consume_post_result_challenge();
+        builder.produce_intermediate_mle(a);
+        builder.produce_intermediate_mle(a_squared);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (alpha, vec![Box::new(a_squared)]),
+                (-alpha, vec![Box::new(a), Box::new(a)]),
+            ],
+        );
+        Ok(table_map[&self.table_ref].clone())
+    }
+}
+
+impl ProofPlan for SquarePlan {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        _chi_eval_map: &IndexMap<TableRef, S>,
+        _params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let length = builder.table_length(&self.table_ref).unwrap();
+        let chi_eval = builder.try_consume_chi_evaluation_of_length(
+            length + usize::from(self.fault == Some(Fault::WrongChiLength)),
+        )?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let mut mle_evals = builder.try_consume_final_round_mle_evaluations(2)?;
+        if self.fault == Some(Fault::SwappedMles) {
+            mle_evals.reverse();
+        }
+        if self.fault == Some(Fault::ExtraMle) {
+            builder.try_consume_final_round_mle_evaluation()?;
+        }
+        let (a_eval, a_squared_eval) = (mle_evals[0], mle_evals[1]);
+        if self.fault != Some(Fault::MissingConstraint) {
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                alpha * (a_squared_eval - a_eval * a_eval),
+                if self.fault == Some(Fault::WrongDegree) {
+                    3
+                } else {
+                    2
+                },
+            )?;
+        }
+        let column_eval = accessor[&self.table_ref][&Ident::new("a")];
+        Ok(TableEvaluation::new(
+            vec![if self.fault == Some(Fault::WrongResult) {
+                a_squared_eval
+            } else {
+                column_eval
+            }],
+            chi_eval,
+        ))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        vec![ColumnField::new("a".into(), ColumnType::BigInt)]
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        indexset! {ColumnRef::new(self.table_ref.clone(), "a".into(), ColumnType::BigInt)}
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        indexset! {self.table_ref.clone()}
+    }
+}
+
+fn check_square_plan(fault: Option<Fault>) -> Result<(), RoundConsistencyError> {
+    let alloc = Bump::new();
+    let table_ref = TableRef::new("sxt", "t");
+    let accessor = TableTestAccessor::<NaiveEvaluationProof>::new_from_table(
+        table_ref.clone(),
+        table([borrowed_bigint("a", [1, -2, 3, 5, 8], &alloc)]),
+        0,
+        (),
+    );
+    check_plan_round_consistency(&SquarePlan { table_ref, fault }, &accessor, &[])
+}
+
+#[test]
+fn we_can_check_a_plan_whose_verifier_mirrors_its_prover() {
+    assert!(check_square_plan(None).is_ok());
+
+    let alloc = Bump::new();
+    let t = TableRef::new("sxt", "t");
+    let accessor = TableTestAccessor::<NaiveEvaluationProof>::new_from_table(
+        t.clone(),
+        table([
+            borrowed_bigint("a", [1, 2, 3, 4, 5], &alloc),
+            borrowed_bigint("b", [0, 1, 0, 2, 0], &alloc),
+        ]),
+        0,
+        (),
+    );
+    let plan = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_bigint(0)),
+    );
+    assert_plan_round_consistency(&plan, &accessor);
+}
+
+#[test]
+fn we_can_find_the_step_at_which_a_verifier_consumes_a_chi_evaluation_of_another_length() {
+    assert!(matches!(
+        check_square_plan(Some(Fault::WrongChiLength)),
+        Err(RoundConsistencyError::ChiLengthMismatch {
+            step: 0,
+            index: 0,
+            declared: 5,
+            expected: 6,
+        })
+    ));
+}
+
+#[test]
+fn we_can_find_the_step_at_which_a_verifier_consumes_mles_out_of_order() {
+    assert!(matches!(
+        check_square_plan(Some(Fault::SwappedMles)),
+        Err(RoundConsistencyError::SubpolynomialEvaluationMismatch { step: 4, index: 0 })
+    ));
+}
+
+#[test]
+fn we_can_find_the_step_at_which_a_verifier_consumes_an_mle_that_was_never_produced() {
+    assert!(matches!(
+        check_square_plan(Some(Fault::ExtraMle)),
+        Err(RoundConsistencyError::Unproduced {
+            step: 4,
+            kind: RoundStepKind::FinalRoundMle,
+            index: 2,
+            produced: 2,
+        })
+    ));
+}
+
+#[test]
+fn we_can_find_the_step_at_which_a_verifier_declares_another_degree() {
+    assert!(matches!(
+        check_square_plan(Some(Fault::WrongDegree)),
+        Err(RoundConsistencyError::SubpolynomialMismatch {
+            step: 4,
+            index: 0,
+            prover_degree: 2,
+            verifier_degree: 3,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn we_can_find_the_steps_a_verifier_leaves_unconsumed() {
+    assert!(matches!(
+        check_square_plan(Some(Fault::MissingConstraint)),
+        Err(RoundConsistencyError::Unconsumed {
+            kind: RoundStepKind::SumcheckSubpolynomial,
+            produced: 1,
+            consumed: 0,
+        })
+    ));
+}
+
+#[test]
+fn we_can_find_a_verifier_that_evaluates_another_result() {
+    assert!(matches!(
+        check_square_plan(Some(Fault::WrongResult)),
+        Err(RoundConsistencyError::ResultEvaluationMismatch)
+    ));
+}
+
+#[test]
+fn we_can_describe_the_step_at_which_a_verifier_diverges() {
+    assert_eq!(
+        check_square_plan(Some(Fault::ExtraMle))
+            .unwrap_err()
+            .to_string(),
+        "step 4 of the verifier consumes final round MLE 2, but the prover only produced 2"
+    );
+}
+
+#[test]
+#[should_panic(expected = "does not mirror its prover: step 0 of the verifier consumes chi")]
+fn we_cannot_assert_the_round_consistency_of_a_verifier_that_diverges() {
+    let alloc = Bump::new();
+    let table_ref = TableRef::new("sxt", "t");
+    let accessor = TableTestAccessor::<NaiveEvaluationProof>::new_from_table(
+        table_ref.clone(),
+        table([borrowed_bigint("a", [1, 2, 3], &alloc)]),
+        0,
+        (),
+    );
+    assert_plan_round_consistency(
+        &SquarePlan {
+            table_ref,
+            fault: Some(Fault::WrongChiLength),
+        },
+        &accessor,
+    );
+}
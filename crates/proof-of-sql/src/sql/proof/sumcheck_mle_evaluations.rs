@@ -12,6 +12,8 @@ use core::iter::IntoIterator;
 /// Evaluations for different MLEs at the random point chosen for sumcheck
 #[derive(Default)]
 pub struct SumcheckMleEvaluations<'a, S: Scalar> {
+    /// The random point generated by sumcheck
+    pub evaluation_point: &'a [S],
     /// The evaluation (at the random point generated by sumcheck) of an MLE `{x_i}` where
     ///     `x_i = 1` if `i < length;`
     ///         = 0, otherwise
@@ -54,7 +56,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
         range_length: usize,
         chi_evaluation_lengths: impl IntoIterator<Item = usize>,
         rho_evaluation_lengths: impl IntoIterator<Item = usize>,
-        evaluation_point: &[S],
+        evaluation_point: &'a [S],
         sumcheck_random_scalars: &SumcheckRandomScalars<S>,
         first_round_pcs_proof_evaluations: &'a [S],
         final_round_pcs_proof_evaluations: &'a [S],
@@ -102,6 +104,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
             .collect();
         let singleton_chi_evaluation = compute_truncated_lagrange_basis_sum(1, evaluation_point);
         Self {
+            evaluation_point,
             chi_evaluations,
             rho_evaluations,
             singleton_chi_evaluation,
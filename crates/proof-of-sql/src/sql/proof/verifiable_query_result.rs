@@ -1,13 +1,36 @@
-use super::{ProofPlan, QueryData, QueryProof, QueryResult};
+#[cfg(feature = "std")]
+use super::prover_checkpoint::{CheckpointDirectory, CheckpointError};
+use super::{
+    LazyQueryData, ProofPlan, QueryData, QueryError, QueryProof, QueryResult, VerificationOptions,
+};
 use crate::{
     base::{
-        commitment::CommitmentEvaluationProof,
-        database::{CommitmentAccessor, DataAccessor, LiteralValue, OwnedTable},
-        proof::PlaceholderResult,
+        bounded_deserializer::decode_bounded_from_slice,
+        commitment::{CommitmentEvaluationProof, QueryCommitments, QueryCommitmentsExt},
+        database::{CommitmentAccessor, DataAccessor, LiteralValue, OwnedTable, TableRef},
+        proof::{Keccak256Transcript, PlaceholderResult, Transcript},
     },
     utils::log,
 };
-use serde::{Deserialize, Serialize};
+use alloc::string::ToString;
+use core::fmt::Display;
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+use sqlparser::ast::Ident;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// The number of bytes the parts of a [`VerifiableQueryResult`] take up when serialized.
+///
+/// See [`VerifiableQueryResult::encoded_size_hint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodedSizes {
+    /// The size of the proof
+    pub proof_bytes: usize,
+    /// The size of the result table
+    pub result_bytes: usize,
+    /// The size of the whole [`VerifiableQueryResult`]
+    pub total: usize,
+}
 
 /// The result of an sql query along with a proof that the query is valid. The
 /// result and proof can be verified using commitments to database columns.
@@ -78,6 +101,11 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
     ///
     /// This function both computes the result of a query and constructs a proof of the results
     /// validity.
+    ///
+    /// Proving is reproducible: the same plan, data, setup and parameters always give a
+    /// byte-identical serialized result. The prover draws no randomness, since every challenge
+    /// comes from the transcript, and parallel reductions only reorder exact additions of field
+    /// and group elements, which are serialized in canonical form.
     #[tracing::instrument(name = "VerifiableQueryResult::new", level = "info", skip_all)]
     pub fn new(
         expr: &(impl ProofPlan + Serialize),
@@ -91,6 +119,127 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
         Ok(Self { result: res, proof })
     }
 
+    /// Form a `VerifiableQueryResult` from a query expression, binding the proof to `transcript`.
+    ///
+    /// This allows the proof to be composed into a larger protocol which shares its Fiat-Shamir
+    /// transcript with the proof. The ordering contract is
+    /// 1. Every message absorbed into `transcript` before this call binds the proof, since the
+    ///    proof is created from a challenge of `transcript`.
+    /// 2. Upon success, `transcript` has absorbed the result and the proof, so every challenge
+    ///    drawn from it afterwards depends on them.
+    ///
+    /// The verifier must call [`Self::verify_with_transcript`] with a transcript which has
+    /// absorbed the same messages in the same order. Afterwards, both transcripts are in the same
+    /// state. Upon failure, `transcript` is left in an unspecified state.
+    ///
+    /// ```ignore
+    /// let mut transcript: Keccak256Transcript = Transcript::new();
+    /// transcript.extend_serialize_as_le(&external_commitment);
+    /// let verifiable_result =
+    ///     VerifiableQueryResult::new_with_transcript(&plan, &accessor, &setup, &[], &mut transcript)?;
+    /// let challenge: DoryScalar = transcript.scalar_challenge_as_be();
+    /// ```
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::new_with_transcript",
+        level = "info",
+        skip_all
+    )]
+    pub fn new_with_transcript(
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+        transcript: &mut impl Transcript,
+    ) -> PlaceholderResult<Self> {
+        log::log_memory_usage("Start");
+        let (proof, res) = QueryProof::new_with_proof_transcript(
+            expr,
+            accessor,
+            setup,
+            params,
+            proof_transcript(transcript),
+        )?;
+        let verifiable_result = Self { result: res, proof };
+        transcript.extend_serialize_as_le(&verifiable_result);
+        log::log_memory_usage("End");
+        Ok(verifiable_result)
+    }
+
+    /// Form a `VerifiableQueryResult` from a query expression, saving checkpoints to
+    /// `checkpoint_dir` as proving progresses.
+    ///
+    /// A checkpoint is saved once the commitments of each round are computed and after every
+    /// round of the sumcheck, replacing the previous one. If proving is interrupted, it can be
+    /// continued with [`Self::resume`]. The resulting proof is identical to the one created by
+    /// [`Self::new`]. `checkpoint_dir` must exist and any checkpoint already in it is overwritten.
+    #[cfg(feature = "std")]
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::new_with_checkpoints",
+        level = "info",
+        skip_all
+    )]
+    pub fn new_with_checkpoints(
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+        checkpoint_dir: &Path,
+    ) -> Result<Self, CheckpointError> {
+        Self::prove_with_checkpoints(
+            expr,
+            accessor,
+            setup,
+            params,
+            &mut CheckpointDirectory::new(checkpoint_dir, false),
+        )
+    }
+
+    /// Continue forming a `VerifiableQueryResult` from the last checkpoint saved to
+    /// `checkpoint_dir` by [`Self::new_with_checkpoints`] or a previous call to this function.
+    ///
+    /// The checkpoint is rejected with [`CheckpointError::PlanMismatch`] if it was saved for a
+    /// different plan or different parameters, and with [`CheckpointError::DataMismatch`] if the
+    /// data referenced by `expr` has changed since. Proving continues to save checkpoints to
+    /// `checkpoint_dir`.
+    #[cfg(feature = "std")]
+    #[tracing::instrument(name = "VerifiableQueryResult::resume", level = "info", skip_all)]
+    pub fn resume(
+        checkpoint_dir: &Path,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+    ) -> Result<Self, CheckpointError> {
+        Self::prove_with_checkpoints(
+            expr,
+            accessor,
+            setup,
+            params,
+            &mut CheckpointDirectory::new(checkpoint_dir, true),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    pub(super) fn prove_with_checkpoints(
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+        checkpoints: &mut CheckpointDirectory<'_>,
+    ) -> Result<Self, CheckpointError> {
+        log::log_memory_usage("Start");
+        let (proof, res) = QueryProof::new_with_checkpoints(
+            expr,
+            accessor,
+            setup,
+            params,
+            Transcript::new(),
+            checkpoints,
+        )?;
+        log::log_memory_usage("End");
+        Ok(Self { result: res, proof })
+    }
+
     /// Verify a `VerifiableQueryResult`. Upon success, this function returns the finalized form of
     /// the query result.
     ///
@@ -105,17 +254,319 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
         accessor: &impl CommitmentAccessor<CP::Commitment>,
         setup: &CP::VerifierPublicSetup<'_>,
         params: &[LiteralValue],
+    ) -> QueryResult<CP::Scalar> {
+        self.verify_with_options(
+            expr,
+            accessor,
+            setup,
+            params,
+            &VerificationOptions::default(),
+        )
+    }
+
+    /// Verify a `VerifiableQueryResult` with the limits set in `options`. Upon success, this
+    /// function returns the finalized form of the query result.
+    ///
+    /// The limits are checked against the lengths declared by the prover before the proof is
+    /// checked, so e.g. a result declaring more groups than
+    /// [`VerificationOptions::max_groups`] fails with
+    /// [`ProofError::TooManyGroups`](crate::base::proof::ProofError::TooManyGroups).
+    ///
+    /// A proof larger than [`VerificationOptions::max_proof_bytes`] fails with
+    /// [`QueryError::ProofTooLarge`], and a verification that does not finish within the timeout
+    /// fails with [`QueryError::Timeout`]. The deadline is checked between the steps of the
+    /// verification and between batches of constraints, so no threads are involved.
+    ///
+    /// Note: This does NOT transform the result!
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::verify_with_options",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_with_options(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        options: &VerificationOptions,
+    ) -> QueryResult<CP::Scalar> {
+        log::log_memory_usage("Start");
+        options.check_proof_bytes(self.proof.encoded_size_hint())?;
+        let query_data =
+            self.proof
+                .verify_with_options(expr, accessor, self.result, setup, params, options)?;
+        Ok(QueryData {
+            table: query_data
+                .table
+                .try_coerce_with_fields(expr.get_column_result_fields())?,
+            ..query_data
+        })
+    }
+
+    /// Verify a `VerifiableQueryResult`, except that the result columns are only checked against
+    /// the proof when they are accessed through the returned [`LazyQueryData`].
+    ///
+    /// This is cheaper than [`Self::verify`] for wide results of which only some columns are
+    /// needed. A column that is accessed is verified just as well as with [`Self::verify`].
+    pub fn verify_lazily(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+    ) -> Result<LazyQueryData<CP::Scalar>, QueryError> {
+        self.verify_lazily_with_options(
+            expr,
+            accessor,
+            setup,
+            params,
+            &VerificationOptions::default(),
+        )
+    }
+
+    /// Verify a `VerifiableQueryResult` as in [`Self::verify_lazily`], with the limits set in
+    /// `options` as in [`Self::verify_with_options`].
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::verify_lazily_with_options",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_lazily_with_options(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        options: &VerificationOptions,
+    ) -> Result<LazyQueryData<CP::Scalar>, QueryError> {
+        options.check_proof_bytes(self.proof.encoded_size_hint())?;
+        self.proof
+            .verify_lazily_with_options(expr, accessor, self.result, setup, params, options)
+    }
+
+    /// Deserialize a `VerifiableQueryResult` serialized with bincode's legacy configuration and
+    /// verify it with the limits set in `options`. Upon success, this function returns the
+    /// finalized form of the query result.
+    ///
+    /// [`VerificationOptions::max_proof_bytes`] is checked against the length of `bytes`, which
+    /// include the result, before anything is deserialized, so an oversized proof fails with [`QueryError::ProofTooLarge`]
+    /// without allocating for it. The lengths declared in `bytes` are not trusted for allocation,
+    /// so decoding allocates in proportion to the length of `bytes` even if they are crafted.
+    /// Bytes that do not decode fail with [`QueryError::MiscellaneousDecodingError`].
+    ///
+    /// Every sequence decoded is then checked against
+    /// [`VerificationOptions::max_sequence_length`] before the proof is verified.
+    ///
+    /// Note: This does NOT transform the result!
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::verify_serialized_with_options",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_serialized_with_options(
+        bytes: &[u8],
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        options: &VerificationOptions,
+    ) -> QueryResult<CP::Scalar>
+    where
+        Self: DeserializeOwned,
+    {
+        options.check_proof_bytes(bytes.len())?;
+        let verifiable_result: Self =
+            decode_bounded_from_slice(bytes).map_err(|_| QueryError::MiscellaneousDecodingError)?;
+        verifiable_result.verify_with_options(expr, accessor, setup, params, options)
+    }
+
+    /// Verify a `VerifiableQueryResult` created with [`Self::new_with_transcript`]. Upon success,
+    /// this function returns the finalized form of the query result.
+    ///
+    /// `transcript` must have absorbed the same messages in the same order as the transcript of
+    /// the prover before it created the result. Upon success, `transcript` has absorbed the
+    /// result and the proof and is in the same state as the transcript of the prover. Upon
+    /// failure, `transcript` is left in an unspecified state.
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::verify_with_transcript",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_with_transcript(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        transcript: &mut impl Transcript,
     ) -> QueryResult<CP::Scalar> {
         log::log_memory_usage("Start");
-        let QueryData {
-            table,
-            verification_hash,
-        } = self
-            .proof
-            .verify(expr, accessor, self.result, setup, params)?;
+        let proof_transcript = proof_transcript(transcript);
+        transcript.extend_serialize_as_le(&self);
+        let query_data = self.proof.verify_with_proof_transcript(
+            expr,
+            accessor,
+            self.result,
+            setup,
+            params,
+            proof_transcript,
+            &VerificationOptions::default(),
+        )?;
         Ok(QueryData {
-            table: table.try_coerce_with_fields(expr.get_column_result_fields())?,
-            verification_hash,
+            table: query_data
+                .table
+                .try_coerce_with_fields(expr.get_column_result_fields())?,
+            ..query_data
         })
     }
+
+    /// Verify a `VerifiableQueryResult` against the commitments of the queried tables, e.g. ones
+    /// deserialized from storage. Upon success, this function returns the finalized form of the
+    /// query result.
+    ///
+    /// Before verifying, this checks that `commitments` contain every table and column of `expr`
+    /// with the expected types and valid ranges, returning
+    /// [`QueryError::InvalidCommitments`](super::QueryError::InvalidCommitments)
+    /// naming the first missing or mismatched table or column otherwise.
+    ///
+    /// ```ignore
+    /// let commitments: QueryCommitments<DynamicDoryCommitment> =
+    ///     bincode::serde::decode_from_slice(&std::fs::read(path)?, bincode::config::legacy())?.0;
+    /// let verifiable_result: VerifiableQueryResult<DynamicDoryEvaluationProof> = receive_result();
+    /// let QueryData { table, .. } = verifiable_result.verify_with_commitments(
+    ///     &plan,
+    ///     &commitments,
+    ///     &&verifier_setup,
+    ///     &[],
+    /// )?;
+    /// ```
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::verify_with_commitments",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_with_commitments(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        commitments: &QueryCommitments<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+    ) -> QueryResult<CP::Scalar> {
+        commitments
+            .try_validate_references(expr.get_table_references(), expr.get_column_references())?;
+        self.verify(expr, commitments, setup, params)
+    }
+
+    /// Verify a `VerifiableQueryResult` after checking every commitment it will be verified
+    /// against with `validate_commitment`. Upon success, this function returns the finalized form
+    /// of the query result.
+    ///
+    /// `validate_commitment` is called with the table, column and commitment of every column of
+    /// `expr`, in the order of [`ProofPlan::get_column_references`], before any of the proof is
+    /// checked. This allows a verifier to confirm that the commitments provided by `accessor`
+    /// match a source of truth, e.g. ones recorded on a blockchain. The first error it returns
+    /// aborts verification with [`QueryError::RejectedCommitment`].
+    ///
+    /// ```ignore
+    /// let QueryData { table, .. } = verifiable_result.verify_with_commitment_validation(
+    ///     &plan,
+    ///     &accessor,
+    ///     &&verifier_setup,
+    ///     &[],
+    ///     |table_ref, column_id, commitment| {
+    ///         if on_chain_commitment(table_ref, column_id)? == *commitment {
+    ///             Ok(())
+    ///         } else {
+    ///             Err("commitment does not match the chain")
+    ///         }
+    ///     },
+    /// )?;
+    /// ```
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::verify_with_commitment_validation",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_with_commitment_validation<E: Display>(
+        self,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+        mut validate_commitment: impl FnMut(&TableRef, &Ident, &CP::Commitment) -> Result<(), E>,
+    ) -> QueryResult<CP::Scalar> {
+        for column_ref in expr.get_column_references() {
+            let table_ref = column_ref.table_ref();
+            let column_id = column_ref.column_id();
+            let commitment = accessor.get_commitment(&table_ref, &column_id);
+            validate_commitment(&table_ref, &column_id, &commitment).map_err(|error| {
+                QueryError::RejectedCommitment {
+                    table_ref,
+                    column_id,
+                    reason: error.to_string(),
+                }
+            })?;
+        }
+        self.verify(expr, accessor, setup, params)
+    }
+
+    /// Compute the number of bytes the proof and the result take up when serialized, without
+    /// serializing them.
+    ///
+    /// The sizes are computed from the number of commitments, MLE evaluations and proof elements
+    /// and from the byte lengths of the result columns. They are exact for bincode's legacy
+    /// configuration for all commitment schemes in this crate: Dory and dynamic Dory commitments and
+    /// proofs are sized through their compressed arkworks encoding, while `HyperKZG` and inner
+    /// product commitments and proofs have fixed size elements. Configurations using variable size
+    /// integers, such as bincode's standard configuration, produce fewer bytes, so the sizes are
+    /// upper bounds for those.
+    #[must_use]
+    pub fn encoded_size_hint(&self) -> EncodedSizes {
+        let proof_bytes = self.proof.encoded_size_hint();
+        let result_bytes = self.result.encoded_size_hint();
+        EncodedSizes {
+            proof_bytes,
+            result_bytes,
+            total: proof_bytes + result_bytes,
+        }
+    }
+
+    /// Serialize only the proof, leaving out the result table.
+    ///
+    /// This is useful when the verifier already has the result from another source (e.g. a
+    /// database) and only the proof needs to be transmitted. The proof can be deserialized as a
+    /// [`QueryProof`] and checked against the result with [`Self::verify_with_result`].
+    pub fn serialize_proof_only<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.proof.serialize(serializer)
+    }
+
+    /// Verify a proof against an externally supplied query result. Upon success, this function
+    /// returns the finalized form of the query result.
+    ///
+    /// The supplied result is bound to the proof in exactly the same way as the result of a
+    /// [`VerifiableQueryResult`], so verification fails if it differs from the result the proof
+    /// was created for.
+    #[tracing::instrument(
+        name = "VerifiableQueryResult::verify_with_result",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_with_result(
+        proof: QueryProof<CP>,
+        result: OwnedTable<CP::Scalar>,
+        expr: &(impl ProofPlan + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+    ) -> QueryResult<CP::Scalar> {
+        Self { result, proof }.verify(expr, accessor, setup, params)
+    }
+}
+
+/// The transcript of a proof which is bound to `transcript` by a challenge of it
+fn proof_transcript(transcript: &mut impl Transcript) -> Keccak256Transcript {
+    let mut proof_transcript: Keccak256Transcript = Transcript::new();
+    proof_transcript.extend_as_le([transcript.challenge_as_le()]);
+    proof_transcript
 }
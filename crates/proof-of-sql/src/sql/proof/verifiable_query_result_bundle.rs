@@ -0,0 +1,149 @@
+use super::{
+    query_proof::PlanVerificationData, EncodedSizes, ProofPlan, QueryData, QueryError, QueryProof,
+    QueryResult, VerificationOptions,
+};
+use crate::{
+    base::{
+        commitment::CommitmentEvaluationProof,
+        database::{CommitmentAccessor, DataAccessor, LiteralValue, OwnedTable},
+        encoded_size::LENGTH_SIZE,
+        proof::{PlaceholderResult, Transcript},
+    },
+    utils::log,
+};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The results of several sql queries over the same data along with a single proof that all of
+/// them are valid.
+///
+/// Proving each query with its own [`VerifiableQueryResult`](super::VerifiableQueryResult)
+/// commits to and opens the columns referenced by several queries once per query, and repeats the
+/// sumcheck and the evaluation proofs for each of them. A bundle evaluates all of the plans under
+/// one transcript instead, so there is a single sumcheck, every referenced column is opened once
+/// and the evaluations of all plans are checked by the same batched evaluation proofs. This makes
+/// the bundle smaller and faster to verify than the separate proofs of its plans, e.g. for a
+/// dashboard issuing many queries over the same table snapshot.
+///
+/// All plans and all results are absorbed into the transcript before any challenge is drawn, so
+/// the proof is bound to every plan and every result. Tampering with any of the results, or
+/// verifying against other plans or plans in another order, fails verification as a whole.
+///
+/// ```ignore
+/// let bundle = VerifiableQueryResultBundle::<DynamicDoryEvaluationProof>::new(
+///     &plans,
+///     &accessor,
+///     &&prover_setup,
+///     &[],
+/// )?;
+/// for query_result in bundle.verify_bundle(&plans, &accessor, &&verifier_setup, &[])? {
+///     let QueryData { table, .. } = query_result?;
+/// }
+/// ```
+///
+/// Note: Because the class is deserialized from untrusted data, it
+/// cannot maintain any invariant on its data members; hence, they are
+/// all public so as to allow for easy manipulation for testing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VerifiableQueryResultBundle<CP: CommitmentEvaluationProof> {
+    /// The result of each query in intermediate form, in the order of the plans.
+    pub results: Vec<OwnedTable<CP::Scalar>>,
+    /// The proof that all of the query results are valid.
+    pub proof: QueryProof<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> VerifiableQueryResultBundle<CP> {
+    /// Form a `VerifiableQueryResultBundle` from several query expressions over the same data.
+    ///
+    /// This function both computes the result of each query and constructs a single proof of the
+    /// validity of all of them. A bundle of a single plan has the same proof as a
+    /// [`VerifiableQueryResult`](super::VerifiableQueryResult) of that plan.
+    #[tracing::instrument(name = "VerifiableQueryResultBundle::new", level = "info", skip_all)]
+    pub fn new(
+        plans: &[impl ProofPlan + Serialize],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Self> {
+        log::log_memory_usage("Start");
+        let (proof, results) = QueryProof::new_with_plans(
+            plans,
+            accessor,
+            setup,
+            params,
+            Transcript::new(),
+            None,
+            &mut (),
+        )?;
+        log::log_memory_usage("End");
+        Ok(Self { results, proof })
+    }
+
+    /// Verify a `VerifiableQueryResultBundle` of `plans`, which must be the plans the bundle was
+    /// formed from in the same order.
+    ///
+    /// If the proof fails to verify, this function fails as a whole since the results of all plans
+    /// are proven together. Otherwise, it returns the finalized form of the result of each plan,
+    /// which is an error if that result alone cannot be finalized, e.g. because it overflows. The
+    /// query data of all plans share the same verification hash.
+    ///
+    /// Note: This does NOT transform the results!
+    #[tracing::instrument(
+        name = "VerifiableQueryResultBundle::verify_bundle",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify_bundle(
+        self,
+        plans: &[impl ProofPlan + Serialize],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        params: &[LiteralValue],
+    ) -> Result<Vec<QueryResult<CP::Scalar>>, QueryError> {
+        log::log_memory_usage("Start");
+        let query_data = self.proof.verify_with_plans(
+            plans,
+            &PlanVerificationData::new(plans),
+            accessor,
+            self.results,
+            setup,
+            params,
+            Transcript::new(),
+            &VerificationOptions::default(),
+        )?;
+        let query_results = query_data
+            .into_iter()
+            .zip(plans)
+            .map(|(query_data, plan)| {
+                Ok(QueryData {
+                    table: query_data
+                        .table
+                        .try_coerce_with_fields(plan.get_column_result_fields())?,
+                    ..query_data
+                })
+            })
+            .collect();
+        log::log_memory_usage("End");
+        Ok(query_results)
+    }
+
+    /// Compute the number of bytes the proof and the results take up when serialized, without
+    /// serializing them.
+    ///
+    /// See [`VerifiableQueryResult::encoded_size_hint`](super::VerifiableQueryResult::encoded_size_hint).
+    #[must_use]
+    pub fn encoded_size_hint(&self) -> EncodedSizes {
+        let proof_bytes = self.proof.encoded_size_hint();
+        let result_bytes = LENGTH_SIZE
+            + self
+                .results
+                .iter()
+                .map(OwnedTable::encoded_size_hint)
+                .sum::<usize>();
+        EncodedSizes {
+            proof_bytes,
+            result_bytes,
+            total: proof_bytes + result_bytes,
+        }
+    }
+}
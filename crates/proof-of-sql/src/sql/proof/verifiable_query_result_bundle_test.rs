@@ -0,0 +1,236 @@
+use super::{QueryData, VerifiableQueryResult, VerifiableQueryResultBundle};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::{bigint, owned_table},
+            ColumnType, OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+    },
+    sql::{
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+
+fn bundle_test_accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        TableRef::new("sxt", "t"),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5, 1, 5]),
+            bigint("b", [10_i64, 20, 30, 40, 50, 60]),
+        ]),
+        0,
+        (),
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "u"),
+        owned_table([bigint("c", [7_i64, 8, 9])]),
+        0,
+    );
+    accessor
+}
+
+fn bundle_test_plans(
+    accessor: &OwnedTableTestAccessor<'_, InnerProductProof>,
+) -> Vec<DynProofPlan> {
+    let t = TableRef::new("sxt", "t");
+    let u = TableRef::new("sxt", "u");
+    vec![
+        filter(
+            cols_expr_plan(&t, &["a", "b"], accessor),
+            tab(&t),
+            equal(column(&t, "a", accessor), const_bigint(5)),
+        ),
+        group_by(
+            cols_expr(&t, &["a"], accessor),
+            vec![sum_expr(column(&t, "b", accessor), "sum_b")],
+            "__count__",
+            tab(&t),
+            const_bool(true),
+        ),
+        projection(
+            vec![aliased_plan(
+                add(column(&t, "a", accessor), column(&t, "b", accessor)),
+                "a_plus_b",
+            )],
+            table_exec(
+                t.clone(),
+                vec![
+                    column_field("a", ColumnType::BigInt),
+                    column_field("b", ColumnType::BigInt),
+                ],
+            ),
+        ),
+        filter(
+            cols_expr_plan(&u, &["c"], accessor),
+            tab(&u),
+            equal(column(&u, "c", accessor), const_bigint(8)),
+        ),
+    ]
+}
+
+#[test]
+fn we_can_verify_a_bundle_of_plans_over_the_same_data() {
+    let accessor = bundle_test_accessor();
+    let plans = bundle_test_plans(&accessor);
+    let bundle =
+        VerifiableQueryResultBundle::<InnerProductProof>::new(&plans, &accessor, &(), &[]).unwrap();
+    assert_eq!(bundle.results.len(), plans.len());
+    let query_results = bundle.verify_bundle(&plans, &accessor, &(), &[]).unwrap();
+    assert_eq!(query_results.len(), plans.len());
+
+    let mut verification_hashes = Vec::new();
+    for (query_result, plan) in query_results.into_iter().zip(&plans) {
+        let QueryData {
+            table,
+            verification_hash,
+            ..
+        } = query_result.unwrap();
+        // Every result matches the one of a separate proof of the plan
+        let expected_table =
+            VerifiableQueryResult::<InnerProductProof>::new(plan, &accessor, &(), &[])
+                .unwrap()
+                .verify(plan, &accessor, &(), &[])
+                .unwrap()
+                .table;
+        assert_eq!(table, expected_table);
+        verification_hashes.push(verification_hash);
+    }
+    assert!(verification_hashes
+        .iter()
+        .all(|verification_hash| *verification_hash == verification_hashes[0]));
+}
+
+#[test]
+fn we_can_verify_a_bundle_of_a_single_plan_like_a_verifiable_query_result() {
+    let accessor = bundle_test_accessor();
+    let plans = bundle_test_plans(&accessor)[..1].to_vec();
+    let bundle =
+        VerifiableQueryResultBundle::<InnerProductProof>::new(&plans, &accessor, &(), &[]).unwrap();
+    let res =
+        VerifiableQueryResult::<InnerProductProof>::new(&plans[0], &accessor, &(), &[]).unwrap();
+    let legacy = bincode::config::legacy();
+    assert_eq!(
+        bincode::serde::encode_to_vec(&bundle.proof, legacy).unwrap(),
+        bincode::serde::encode_to_vec(&res.proof, legacy).unwrap()
+    );
+    assert_eq!(bundle.results, vec![res.result.clone()]);
+
+    // The proofs are interchangeable
+    let QueryData {
+        table,
+        verification_hash,
+        ..
+    } = VerifiableQueryResult {
+        result: bundle.results[0].clone(),
+        proof: bundle.proof,
+    }
+    .verify(&plans[0], &accessor, &(), &[])
+    .unwrap();
+    let expected = res.verify(&plans[0], &accessor, &(), &[]).unwrap();
+    assert_eq!(table, expected.table);
+    assert_eq!(verification_hash, expected.verification_hash);
+}
+
+#[test]
+fn a_bundle_is_smaller_than_separate_proofs_of_its_plans() {
+    let accessor = bundle_test_accessor();
+    let plans = bundle_test_plans(&accessor);
+    let bundle =
+        VerifiableQueryResultBundle::<InnerProductProof>::new(&plans, &accessor, &(), &[]).unwrap();
+    let separate_proof_bytes: usize = plans
+        .iter()
+        .map(|plan| {
+            VerifiableQueryResult::<InnerProductProof>::new(plan, &accessor, &(), &[])
+                .unwrap()
+                .encoded_size_hint()
+                .proof_bytes
+        })
+        .sum();
+    let hint = bundle.encoded_size_hint();
+    assert!(hint.proof_bytes < separate_proof_bytes);
+
+    let legacy = bincode::config::legacy();
+    assert_eq!(
+        hint.proof_bytes,
+        bincode::serde::encode_to_vec(&bundle.proof, legacy)
+            .unwrap()
+            .len()
+    );
+    assert_eq!(
+        hint.result_bytes,
+        bincode::serde::encode_to_vec(&bundle.results, legacy)
+            .unwrap()
+            .len()
+    );
+    assert_eq!(
+        hint.total,
+        bincode::serde::encode_to_vec(&bundle, legacy)
+            .unwrap()
+            .len()
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_bundle_with_a_tampered_result() {
+    let accessor = bundle_test_accessor();
+    let plans = bundle_test_plans(&accessor);
+    let bundle =
+        VerifiableQueryResultBundle::<InnerProductProof>::new(&plans, &accessor, &(), &[]).unwrap();
+
+    // A changed value in the result of the last plan
+    let mut tampered_bundle = bundle.clone();
+    tampered_bundle.results[3] = owned_table([bigint("c", [9_i64])]);
+    assert!(tampered_bundle
+        .verify_bundle(&plans, &accessor, &(), &[])
+        .is_err());
+
+    // The results of two plans swapped
+    let mut tampered_bundle = bundle.clone();
+    tampered_bundle.results.swap(0, 3);
+    assert!(tampered_bundle
+        .verify_bundle(&plans, &accessor, &(), &[])
+        .is_err());
+
+    // A missing result
+    let mut tampered_bundle = bundle;
+    tampered_bundle.results.pop();
+    assert!(tampered_bundle
+        .verify_bundle(&plans, &accessor, &(), &[])
+        .is_err());
+}
+
+#[test]
+fn we_cannot_verify_a_bundle_against_other_plans() {
+    let accessor = bundle_test_accessor();
+    let plans = bundle_test_plans(&accessor);
+    let bundle =
+        VerifiableQueryResultBundle::<InnerProductProof>::new(&plans, &accessor, &(), &[]).unwrap();
+
+    // The plans in another order
+    let mut reordered_plans = plans.clone();
+    reordered_plans.swap(0, 3);
+    assert!(bundle
+        .clone()
+        .verify_bundle(&reordered_plans, &accessor, &(), &[])
+        .is_err());
+
+    // A missing plan
+    assert!(bundle
+        .clone()
+        .verify_bundle(&plans[..3], &accessor, &(), &[])
+        .is_err());
+
+    // A different plan
+    let mut other_plans = plans;
+    let t = TableRef::new("sxt", "t");
+    other_plans[0] = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(1)),
+    );
+    assert!(bundle
+        .verify_bundle(&other_plans, &accessor, &(), &[])
+        .is_err());
+}
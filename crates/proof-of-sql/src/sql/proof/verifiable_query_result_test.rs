@@ -1,22 +1,32 @@
 use super::{
-    FinalRoundBuilder, ProofPlan, ProverEvaluate, VerifiableQueryResult, VerificationBuilder,
+    exercise_verification,
+    prover_checkpoint::{CheckpointDirectory, CheckpointError},
+    FinalRoundBuilder, ProofPlan, ProverEvaluate, QueryError, QueryProof, VerifiableQueryResult,
+    VerificationBuilder, VerificationOptions,
 };
 use crate::{
     base::{
-        commitment::InnerProductProof,
+        commitment::{
+            InnerProductProof, QueryCommitments, QueryCommitmentsError, QueryCommitmentsExt,
+        },
         database::{
-            owned_table_utility::{bigint, owned_table},
+            owned_table_utility::{bigint, int, owned_table},
             table_utility::*,
-            ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, OwnedTableTestAccessor,
-            Table, TableEvaluation, TableRef,
+            ColumnField, ColumnRef, ColumnType, CommitmentAccessor, LiteralValue, OwnedTable,
+            OwnedTableTestAccessor, Table, TableEvaluation, TableRef,
         },
         map::{indexset, IndexMap, IndexSet},
-        proof::{PlaceholderResult, ProofError},
+        proof::{Keccak256Transcript, PlaceholderResult, ProofError, Transcript},
         scalar::Scalar,
     },
-    sql::proof::{FirstRoundBuilder, QueryData},
+    sql::{
+        proof::{verification_hash_version, FirstRoundBuilder, QueryData},
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
 };
 use bumpalo::Bump;
+use core::time::Duration;
 use serde::Serialize;
 use sqlparser::ast::Ident;
 
@@ -111,7 +121,879 @@ fn we_can_verify_queries_on_an_empty_table() {
     let QueryData {
         verification_hash: _,
         table,
+        ..
     } = res.verify(&expr, &accessor, &(), &[]).unwrap();
     let expected_res = owned_table([bigint("a1", [0; 0])]);
     assert_eq!(table, expected_res);
 }
+
+#[test]
+fn we_can_verify_a_proof_serialized_without_the_result_against_an_external_result() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+
+    let mut serializer = flexbuffers::FlexbufferSerializer::new();
+    res.serialize_proof_only(&mut serializer).unwrap();
+    let proof_only_size = serializer.view().len();
+    assert!(proof_only_size < flexbuffers::to_vec(&res).unwrap().len());
+    let proof: QueryProof<InnerProductProof> = flexbuffers::from_slice(serializer.view()).unwrap();
+
+    // The result is stored separately, e.g. in a database
+    let external_result = res.result.clone();
+    let QueryData {
+        verification_hash: _,
+        table,
+        ..
+    } = VerifiableQueryResult::verify_with_result(
+        proof,
+        external_result,
+        &expr,
+        &accessor,
+        &(),
+        &[],
+    )
+    .unwrap();
+    let expected_res = owned_table([bigint("a", [5_i64, 5]), bigint("b", [20_i64, 40])]);
+    assert_eq!(table, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_proof_against_a_tampered_external_result() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+
+    // A changed value
+    let tampered_result = owned_table([bigint("a", [5_i64, 5]), bigint("b", [20_i64, 41])]);
+    assert!(VerifiableQueryResult::verify_with_result(
+        res.proof.clone(),
+        tampered_result,
+        &expr,
+        &accessor,
+        &(),
+        &[],
+    )
+    .is_err());
+
+    // A missing row
+    let tampered_result = owned_table([bigint("a", [5_i64]), bigint("b", [20_i64])]);
+    assert!(VerifiableQueryResult::verify_with_result(
+        res.proof.clone(),
+        tampered_result,
+        &expr,
+        &accessor,
+        &(),
+        &[],
+    )
+    .is_err());
+
+    // An extra row
+    let tampered_result = owned_table([bigint("a", [5_i64, 5, 5]), bigint("b", [20_i64, 40, 50])]);
+    assert!(VerifiableQueryResult::verify_with_result(
+        res.proof,
+        tampered_result,
+        &expr,
+        &accessor,
+        &(),
+        &[],
+    )
+    .is_err());
+}
+
+#[test]
+fn we_can_hint_the_encoded_sizes_of_a_verifiable_query_result() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    let hint = res.encoded_size_hint();
+
+    let legacy = bincode::config::legacy();
+    assert_eq!(
+        hint.proof_bytes,
+        bincode::serde::encode_to_vec(&res.proof, legacy)
+            .unwrap()
+            .len()
+    );
+    assert_eq!(
+        hint.result_bytes,
+        bincode::serde::encode_to_vec(&res.result, legacy)
+            .unwrap()
+            .len()
+    );
+    assert_eq!(
+        hint.total,
+        bincode::serde::encode_to_vec(&res, legacy).unwrap().len()
+    );
+
+    // Variable size integers only make the encoding smaller
+    let standard = bincode::config::standard();
+    assert!(hint.total >= bincode::serde::encode_to_vec(&res, standard).unwrap().len());
+}
+
+#[test]
+fn we_can_verify_a_result_against_commitments_read_from_disk() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let path = std::env::temp_dir().join("we_can_verify_a_result_against_commitments.bin");
+    let commitments =
+        QueryCommitments::from_accessor_with_max_bounds(expr.get_column_references(), &accessor);
+    std::fs::write(
+        &path,
+        bincode::serde::encode_to_vec(&commitments, bincode::config::legacy()).unwrap(),
+    )
+    .unwrap();
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+
+    // The example from the documentation of `verify_with_commitments`
+    let commitments: QueryCommitments<_> = bincode::serde::decode_from_slice(
+        &std::fs::read(&path).unwrap(),
+        bincode::config::legacy(),
+    )
+    .unwrap()
+    .0;
+    let QueryData { table, .. } = res
+        .verify_with_commitments(&expr, &commitments, &(), &[])
+        .unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+    let expected_res = owned_table([bigint("a", [5_i64, 5]), bigint("b", [20_i64, 40])]);
+    assert_eq!(table, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_result_against_commitments_missing_a_table_or_column() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+
+    // Missing table
+    let commitments = QueryCommitments::from_accessor_with_max_bounds(
+        [ColumnRef::new(
+            TableRef::new("sxt", "other"),
+            "a".into(),
+            ColumnType::BigInt,
+        )],
+        &OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            TableRef::new("sxt", "other"),
+            owned_table([bigint("a", [1_i64])]),
+            0,
+            (),
+        ),
+    );
+    assert!(matches!(
+        res.clone()
+            .verify_with_commitments(&expr, &commitments, &(), &[]),
+        Err(QueryError::InvalidCommitments {
+            source: QueryCommitmentsError::MissingTable { table_ref }
+        }) if table_ref == t
+    ));
+
+    // Missing column
+    let commitments = QueryCommitments::from_accessor_with_max_bounds(
+        [ColumnRef::new(t.clone(), "a".into(), ColumnType::BigInt)],
+        &accessor,
+    );
+    assert!(matches!(
+        res.clone()
+            .verify_with_commitments(&expr, &commitments, &(), &[]),
+        Err(QueryError::InvalidCommitments {
+            source: QueryCommitmentsError::MissingColumn { table_ref, column_id }
+        }) if table_ref == t && column_id.value == "b"
+    ));
+
+    // Column committed with a different type
+    let commitments = QueryCommitments::from_accessor_with_max_bounds(
+        [
+            ColumnRef::new(t.clone(), "a".into(), ColumnType::BigInt),
+            ColumnRef::new(t.clone(), "b".into(), ColumnType::Int),
+        ],
+        &OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            owned_table([bigint("a", [1_i64, 5, 3, 5]), int("b", [10, 20, 30, 40])]),
+            0,
+            (),
+        ),
+    );
+    assert!(matches!(
+        res.verify_with_commitments(&expr, &commitments, &(), &[]),
+        Err(QueryError::InvalidCommitments {
+            source: QueryCommitmentsError::ColumnTypeMismatch {
+                expected: ColumnType::BigInt,
+                actual: ColumnType::Int,
+                ..
+            }
+        })
+    ));
+}
+
+#[test]
+fn we_can_verify_a_result_after_validating_commitments_against_a_source_of_truth() {
+    let t = TableRef::new("sxt", "t");
+    let data = owned_table([
+        bigint("a", [1_i64, 5, 3, 5]),
+        bigint("b", [10_i64, 20, 30, 40]),
+    ]);
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let source_of_truth =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    let mut validated_columns = Vec::new();
+    let QueryData { table, .. } = res
+        .verify_with_commitment_validation(
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            |table_ref, column_id, commitment| {
+                validated_columns.push((table_ref.clone(), column_id.value.clone()));
+                if source_of_truth.get_commitment(table_ref, column_id) == *commitment {
+                    Ok(())
+                } else {
+                    Err("commitment does not match the source of truth")
+                }
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        validated_columns,
+        vec![(t.clone(), "a".to_string()), (t, "b".to_string())]
+    );
+    let expected_res = owned_table([bigint("a", [5_i64, 5]), bigint("b", [20_i64, 40])]);
+    assert_eq!(table, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_result_if_the_commitment_validation_returns_an_error() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let source_of_truth = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 41]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    assert!(matches!(
+        res.verify_with_commitment_validation(
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            |table_ref, column_id, commitment| {
+                if source_of_truth.get_commitment(table_ref, column_id) == *commitment {
+                    Ok(())
+                } else {
+                    Err("commitment does not match the source of truth")
+                }
+            },
+        ),
+        Err(QueryError::RejectedCommitment { table_ref, column_id, reason })
+            if table_ref == t
+                && column_id.value == "b"
+                && reason == "commitment does not match the source of truth"
+    ));
+}
+
+#[test]
+fn we_validate_commitments_before_checking_the_proof() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let mut res =
+        VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    res.result = owned_table([bigint("a", [5_i64, 5]), bigint("b", [20_i64, 41])]);
+
+    // The tampered result fails verification once the commitments are accepted
+    assert!(matches!(
+        res.clone()
+            .verify_with_commitment_validation(
+                &expr,
+                &accessor,
+                &(),
+                &[],
+                |_, _, _| Ok::<_, &str>(()),
+            ),
+        Err(QueryError::ProofError { .. })
+    ));
+
+    // A rejected commitment aborts verification before the proof is checked
+    let mut num_calls = 0;
+    assert!(matches!(
+        res.verify_with_commitment_validation(&expr, &accessor, &(), &[], |_, _, _| {
+            num_calls += 1;
+            Err("rejected")
+        }),
+        Err(QueryError::RejectedCommitment { column_id, .. }) if column_id.value == "a"
+    ));
+    assert_eq!(num_calls, 1);
+}
+
+/// A transcript which has absorbed the messages of a larger protocol
+fn external_transcript(message: &[u8]) -> Keccak256Transcript {
+    let mut transcript: Keccak256Transcript = Transcript::new();
+    transcript.extend_as_le_from_refs([message]);
+    transcript
+}
+
+#[test]
+fn we_can_verify_a_result_bound_to_a_transcript_with_matching_external_messages() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1_i64, 5, 3, 5])]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let mut prover_transcript = external_transcript(b"external commitment");
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_transcript(
+        &expr,
+        &accessor,
+        &(),
+        &[],
+        &mut prover_transcript,
+    )
+    .unwrap();
+    let mut verifier_transcript = external_transcript(b"external commitment");
+    let QueryData { table, .. } = res
+        .verify_with_transcript(&expr, &accessor, &(), &[], &mut verifier_transcript)
+        .unwrap();
+    assert_eq!(table, owned_table([bigint("a", [5_i64, 5])]));
+
+    // Both transcripts continue from the same state
+    prover_transcript.extend_as_le_from_refs([b"after the proof".as_slice()]);
+    verifier_transcript.extend_as_le_from_refs([b"after the proof".as_slice()]);
+    assert_eq!(
+        prover_transcript.challenge_as_le(),
+        verifier_transcript.challenge_as_le()
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_result_bound_to_a_transcript_with_different_external_messages() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1_i64, 5, 3, 5])]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_transcript(
+        &expr,
+        &accessor,
+        &(),
+        &[],
+        &mut external_transcript(b"external commitment"),
+    )
+    .unwrap();
+    assert!(res
+        .clone()
+        .verify_with_transcript(
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            &mut external_transcript(b"another commitment")
+        )
+        .is_err());
+    // The proof is bound to the transcript, so it does not verify on its own
+    assert!(res.verify(&expr, &accessor, &(), &[]).is_err());
+}
+
+fn checkpoint_test_accessor(
+    t: &TableRef,
+    factor: i64,
+) -> OwnedTableTestAccessor<InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5, 2, 5, 5, 0, 1, 5, 3, 5, 2, 5, 5, 0]),
+            bigint("b", (0..16).map(|i| i * factor)),
+        ]),
+        0,
+        (),
+    )
+}
+
+#[test]
+fn we_can_resume_an_interrupted_proof_from_any_checkpoint() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = checkpoint_test_accessor(&t, 1);
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let expected =
+        VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    let expected_bytes =
+        bincode::serde::encode_to_vec(&expected, bincode::config::legacy()).unwrap();
+    let dir = std::env::temp_dir().join("we_can_resume_an_interrupted_proof_from_any_checkpoint");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut checkpoints = CheckpointDirectory::new(&dir, false);
+    let res = VerifiableQueryResult::<InnerProductProof>::prove_with_checkpoints(
+        &expr,
+        &accessor,
+        &(),
+        &[],
+        &mut checkpoints,
+    )
+    .unwrap();
+    assert_eq!(
+        bincode::serde::encode_to_vec(&res, bincode::config::legacy()).unwrap(),
+        expected_bytes
+    );
+    // One checkpoint for each round of commitments and one for each sumcheck round
+    let checkpoint_count = checkpoints.saved;
+    assert!(checkpoint_count > 2);
+    for interrupt_after in 1..=checkpoint_count {
+        let mut checkpoints = CheckpointDirectory::new(&dir, false);
+        checkpoints.interrupt_after = Some(interrupt_after);
+        assert!(matches!(
+            VerifiableQueryResult::<InnerProductProof>::prove_with_checkpoints(
+                &expr,
+                &accessor,
+                &(),
+                &[],
+                &mut checkpoints
+            ),
+            Err(CheckpointError::Interrupted { checkpoint }) if checkpoint == interrupt_after
+        ));
+        let res =
+            VerifiableQueryResult::<InnerProductProof>::resume(&dir, &expr, &accessor, &(), &[])
+                .unwrap();
+        assert_eq!(
+            bincode::serde::encode_to_vec(&res, bincode::config::legacy()).unwrap(),
+            expected_bytes
+        );
+    }
+    // Resuming after the last checkpoint only finishes the proof, and so does resuming twice
+    let res = VerifiableQueryResult::<InnerProductProof>::resume(&dir, &expr, &accessor, &(), &[])
+        .unwrap();
+    assert_eq!(
+        bincode::serde::encode_to_vec(&res, bincode::config::legacy()).unwrap(),
+        expected_bytes
+    );
+
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_checkpoints(
+        &expr,
+        &accessor,
+        &(),
+        &[],
+        &dir,
+    )
+    .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        bincode::serde::encode_to_vec(&res, bincode::config::legacy()).unwrap(),
+        expected_bytes
+    );
+    let QueryData { table, .. } = res.verify(&expr, &accessor, &(), &[]).unwrap();
+    assert_eq!(table.num_rows(), 8);
+}
+
+#[test]
+fn we_cannot_resume_a_proof_of_a_different_plan_or_from_different_data() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = checkpoint_test_accessor(&t, 1);
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let dir = std::env::temp_dir()
+        .join("we_cannot_resume_a_proof_of_a_different_plan_or_from_different_data");
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut checkpoints = CheckpointDirectory::new(&dir, false);
+    checkpoints.interrupt_after = Some(3);
+    assert!(
+        VerifiableQueryResult::<InnerProductProof>::prove_with_checkpoints(
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            &mut checkpoints
+        )
+        .is_err()
+    );
+
+    let other_expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(3)),
+    );
+    assert!(matches!(
+        VerifiableQueryResult::<InnerProductProof>::resume(&dir, &other_expr, &accessor, &(), &[]),
+        Err(CheckpointError::PlanMismatch)
+    ));
+    let other_accessor = checkpoint_test_accessor(&t, 2);
+    assert!(matches!(
+        VerifiableQueryResult::<InnerProductProof>::resume(&dir, &expr, &other_accessor, &(), &[]),
+        Err(CheckpointError::DataMismatch)
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(
+        VerifiableQueryResult::<InnerProductProof>::resume(&dir, &expr, &accessor, &(), &[]),
+        Err(CheckpointError::Io { .. })
+    ));
+}
+
+/// Padding rows beyond the length of a table are excluded by its chi evaluation, so they can
+/// neither satisfy a filter nor be counted in a group, even though every column is zero there.
+#[test]
+fn we_can_verify_queries_over_tables_whose_lengths_are_not_powers_of_two() {
+    for num_rows in [1, 1000, 1023] {
+        let t = TableRef::new("sxt", "t");
+        let a = (0_i64..).take(num_rows);
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            owned_table([
+                bigint("a", a.clone()),
+                bigint("b", a.clone().map(|x| x % 3)),
+            ]),
+            0,
+            (),
+        );
+
+        // Padding rows would satisfy `b = 0`
+        let expr = filter(
+            cols_expr_plan(&t, &["a"], &accessor),
+            tab(&t),
+            equal(column(&t, "b", &accessor), const_bigint(0)),
+        );
+        let res =
+            VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+        exercise_verification(&res, &expr, &accessor, &t);
+        let table = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+        assert_eq!(
+            table,
+            owned_table([bigint("a", a.clone().filter(|x| x % 3 == 0))])
+        );
+
+        // Padding rows would be counted in the group `b = 0`
+        let expr = group_by(
+            cols_expr(&t, &["b"], &accessor),
+            vec![sum_expr(column(&t, "a", &accessor), "sum_a")],
+            "__count__",
+            tab(&t),
+            const_bool(true),
+        );
+        let res =
+            VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+        let table = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+        let groups: Vec<i64> = (0..3).filter(|b| a.clone().any(|x| x % 3 == *b)).collect();
+        let group_rows = |b: i64| a.clone().filter(move |x| x % 3 == b);
+        assert_eq!(
+            table,
+            owned_table([
+                bigint("b", groups.clone()),
+                bigint("sum_a", groups.iter().map(|&b| group_rows(b).sum::<i64>())),
+                bigint(
+                    "__count__",
+                    groups
+                        .iter()
+                        .map(|&b| i64::try_from(group_rows(b).count()).unwrap()),
+                ),
+            ])
+        );
+    }
+}
+
+#[test]
+fn we_get_the_same_verification_hash_when_reverifying_a_stored_proof() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5]),
+            bigint("b", [10_i64, 20, 30, 40]),
+        ]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    let stored = bincode::serde::encode_to_vec(&res, bincode::config::legacy()).unwrap();
+    let query_data = res.verify(&expr, &accessor, &(), &[]).unwrap();
+    let inputs = query_data.verification_hash_inputs();
+    assert_eq!(inputs.version, verification_hash_version());
+    assert_eq!(query_data.verification_hash, inputs.verification_hash());
+
+    let stored_res: VerifiableQueryResult<InnerProductProof> =
+        bincode::serde::decode_from_slice(&stored, bincode::config::legacy())
+            .unwrap()
+            .0;
+    let reverified = stored_res.verify(&expr, &accessor, &(), &[]).unwrap();
+    assert_eq!(reverified.verification_hash_inputs(), inputs);
+    assert_eq!(reverified.verification_hash, query_data.verification_hash);
+}
+
+fn large_filter_result() -> (
+    VerifiableQueryResult<InnerProductProof>,
+    DynProofPlan,
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+) {
+    let t = TableRef::new("sxt", "t");
+    let a = (0_i64..).take(4096);
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", a.clone()), bigint("b", a.map(|x| x % 7))]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_bigint(3)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    (res, expr, accessor)
+}
+
+#[test]
+fn we_cannot_verify_an_honest_proof_that_takes_longer_than_the_timeout() {
+    let (res, expr, accessor) = large_filter_result();
+    let options = VerificationOptions::default().with_timeout(Some(Duration::ZERO));
+    assert!(matches!(
+        res.clone()
+            .verify_with_options(&expr, &accessor, &(), &[], &options),
+        Err(QueryError::Timeout)
+    ));
+
+    // The same proof verifies with enough time
+    let options = VerificationOptions::default().with_timeout(Some(Duration::from_secs(3600)));
+    let table = res
+        .verify_with_options(&expr, &accessor, &(), &[], &options)
+        .unwrap()
+        .table;
+    assert_eq!(table.num_rows(), 585);
+}
+
+#[test]
+fn we_cannot_verify_a_proof_larger_than_the_byte_limit() {
+    let (res, expr, accessor) = large_filter_result();
+    let proof_bytes = res.encoded_size_hint().proof_bytes;
+    let options = VerificationOptions::default().with_max_proof_bytes(Some(proof_bytes - 1));
+    assert!(matches!(
+        res.clone()
+            .verify_with_options(&expr, &accessor, &(), &[], &options),
+        Err(QueryError::ProofTooLarge { proof_bytes: actual, max_proof_bytes })
+            if actual == proof_bytes && max_proof_bytes == proof_bytes - 1
+    ));
+
+    let options = VerificationOptions::default().with_max_proof_bytes(Some(proof_bytes));
+    assert!(res
+        .verify_with_options(&expr, &accessor, &(), &[], &options)
+        .is_ok());
+}
+
+#[test]
+fn we_check_the_byte_limit_before_deserializing_a_proof() {
+    let (res, expr, accessor) = large_filter_result();
+    let bytes = bincode::serde::encode_to_vec(&res, bincode::config::legacy()).unwrap();
+
+    let options = VerificationOptions::default().with_max_proof_bytes(Some(bytes.len()));
+    let table = VerifiableQueryResult::<InnerProductProof>::verify_serialized_with_options(
+        &bytes,
+        &expr,
+        &accessor,
+        &(),
+        &[],
+        &options,
+    )
+    .unwrap()
+    .table;
+    assert_eq!(table.num_rows(), 585);
+
+    // Bytes exceeding the limit are rejected without being decoded
+    let garbage = vec![0; bytes.len() + 1];
+    assert!(matches!(
+        VerifiableQueryResult::<InnerProductProof>::verify_serialized_with_options(
+            &garbage,
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            &options,
+        ),
+        Err(QueryError::ProofTooLarge { .. })
+    ));
+    assert!(matches!(
+        VerifiableQueryResult::<InnerProductProof>::verify_serialized_with_options(
+            &bytes[..bytes.len() / 2],
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            &options,
+        ),
+        Err(QueryError::MiscellaneousDecodingError)
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_result_longer_than_the_sequence_limit() {
+    let (res, expr, accessor) = large_filter_result();
+    let options = VerificationOptions::default().with_max_sequence_length(Some(584));
+    assert!(matches!(
+        res.clone()
+            .verify_with_options(&expr, &accessor, &(), &[], &options),
+        Err(QueryError::SequenceTooLong {
+            length: 585,
+            max_sequence_length: 584
+        })
+    ));
+
+    let options = VerificationOptions::default().with_max_sequence_length(Some(585));
+    assert!(res
+        .verify_with_options(&expr, &accessor, &(), &[], &options)
+        .is_ok());
+}
+
+#[test]
+fn we_can_decode_mutated_proofs_without_panicking() {
+    use crate::base::bounded_deserializer::decode_bounded_from_slice;
+    use proptest::{collection::vec as prop_vec, prelude::*, test_runner::TestRunner};
+
+    let (res, _, _) = large_filter_result();
+    let bytes = bincode::serde::encode_to_vec(&res, bincode::config::legacy()).unwrap();
+    let decoded: VerifiableQueryResult<InnerProductProof> =
+        decode_bounded_from_slice(&bytes).unwrap();
+    assert_eq!(decoded.result, res.result);
+
+    let mut runner = TestRunner::default();
+    runner
+        .run(
+            &(
+                prop_vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+                any::<prop::sample::Index>(),
+            ),
+            |(mutations, truncation)| {
+                let mut mutated = bytes.clone();
+                for (index, byte) in mutations {
+                    *index.get_mut(&mut mutated) = byte;
+                }
+                let _ =
+                    decode_bounded_from_slice::<VerifiableQueryResult<InnerProductProof>>(&mutated);
+                let _ = decode_bounded_from_slice::<VerifiableQueryResult<InnerProductProof>>(
+                    &mutated[..truncation.index(mutated.len())],
+                );
+                Ok(())
+            },
+        )
+        .unwrap();
+}
@@ -1,4 +1,4 @@
-use super::{ProofPlan, VerifiableQueryResult};
+use super::{assert_plan_round_consistency, ProofPlan, VerifiableQueryResult};
 use crate::{
     base::{
         commitment::{Commitment, CommittableColumn},
@@ -15,11 +15,13 @@ use serde::Serialize;
 /// This function takes a valid `verifiable_result`, copies it, tweaks it, and checks that
 /// verification fails.
 ///
-/// It's useful as a tool for testing proof code.
+/// It's useful as a tool for testing proof code. It also checks that the verifier of `expr` mirrors
+/// its prover with [`assert_plan_round_consistency`].
 ///
 /// # Panics
 ///
 /// Will panic if:
+/// - The verifier of `expr` does not mirror its prover.
 /// - The verification of `res` does not succeed, causing the assertion `assert!(res.verify(...).is_ok())` to fail.
 /// - `fake_accessor.update_offset` fails, causing a panic if it is designed to do so in the implementation.
 pub fn exercise_verification(
@@ -28,6 +30,8 @@ pub fn exercise_verification(
     accessor: &impl TestAccessor<RistrettoPoint>,
     table_ref: &TableRef,
 ) {
+    assert_plan_round_consistency::<Curve25519Scalar>(expr, accessor);
+
     res.clone()
         .verify(expr, accessor, &(), &[])
         .expect("Verification failed");
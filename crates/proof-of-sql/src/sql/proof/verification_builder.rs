@@ -1,12 +1,55 @@
-use super::{SumcheckMleEvaluations, SumcheckSubpolynomialType};
-use crate::base::{bit::BitDistribution, proof::ProofSizeMismatch, scalar::Scalar};
+use super::{
+    verification_options::VerificationDeadline, SumcheckMleEvaluations, SumcheckSubpolynomialType,
+    VerificationOptions,
+};
+use crate::base::{
+    bit::BitDistribution,
+    database::TableRef,
+    map::IndexMap,
+    proof::{ProofError, ProofSizeMismatch},
+    scalar::Scalar,
+};
 use alloc::{collections::VecDeque, vec::Vec};
 use core::iter;
 
+/// The number of constraints produced between two checks of the verification deadline
+const DEADLINE_CHECK_INTERVAL: usize = 64;
+
+/// Track components used to verify a query's proof
+///
+/// A [`ProofPlan`](super::ProofPlan) uses it in
+/// [`ProofPlan::verifier_evaluate`](super::ProofPlan::verifier_evaluate) to consume the
+/// evaluations the prover produced with the [`FirstRoundBuilder`](super::FirstRoundBuilder) and the
+/// [`FinalRoundBuilder`](super::FinalRoundBuilder), in the same order, and to produce the
+/// evaluations of the constraints the prover produced.
 pub trait VerificationBuilder<S: Scalar> {
     /// Consume the evaluation of a chi evaluation
     fn try_consume_chi_evaluation(&mut self) -> Result<S, ProofSizeMismatch>;
 
+    /// Consume the evaluation of a chi evaluation together with the length declared for it
+    fn try_consume_chi_evaluation_with_length(&mut self) -> Result<(S, usize), ProofSizeMismatch>;
+
+    /// Consume the evaluation of a chi evaluation whose declared length has to be `length`
+    ///
+    /// This is the counterpart of
+    /// [`FirstRoundBuilder::produce_chi_evaluation_length`](super::FirstRoundBuilder::produce_chi_evaluation_length)
+    /// for a length the verifier knows, e.g. the length of an input table.
+    ///
+    /// # Errors
+    /// Returns [`ProofError::ChiLengthMismatch`] if the prover declared another length, or a
+    /// [`ProofSizeMismatch`] if there are no chi evaluations left.
+    fn try_consume_chi_evaluation_of_length(&mut self, length: usize) -> Result<S, ProofError> {
+        let (chi_eval, declared_length) = self.try_consume_chi_evaluation_with_length()?;
+        if declared_length == length {
+            Ok(chi_eval)
+        } else {
+            Err(ProofError::ChiLengthMismatch {
+                declared_length,
+                expected_length: length,
+            })
+        }
+    }
+
     /// Consume the evaluation of a rho evaluation
     fn try_consume_rho_evaluation(&mut self) -> Result<S, ProofSizeMismatch>;
 
@@ -47,6 +90,21 @@ pub trait VerificationBuilder<S: Scalar> {
 
     /// Retrieves the `rho_256_evaluation` from the `mle_evaluations`
     fn rho_256_evaluation(&self) -> Option<S>;
+
+    /// Retrieves the random point generated by sumcheck, at which all MLEs are evaluated
+    fn evaluation_point(&self) -> &[S];
+
+    /// Retrieves the length of an input table of the query, if it is known
+    fn table_length(&self, table_ref: &TableRef) -> Option<usize>;
+
+    /// Retrieves the options the proof is verified with
+    fn verification_options(&self) -> VerificationOptions;
+
+    /// Whether plans skip checking their result columns against the evaluations consumed from the
+    /// proof, because the caller checks them lazily instead
+    fn result_checks_deferred(&self) -> bool {
+        false
+    }
 }
 
 /// Track components used to verify a query's proof
@@ -71,9 +129,15 @@ pub struct VerificationBuilderImpl<'a, S: Scalar> {
     chi_evaluation_length_queue: Vec<usize>,
     rho_evaluation_length_queue: Vec<usize>,
     subpolynomial_max_multiplicands: usize,
+    table_lengths: IndexMap<TableRef, usize>,
+    verification_options: VerificationOptions,
+    deadline: VerificationDeadline,
+    timed_out: bool,
+    result_checks_deferred: bool,
 }
 
 impl<'a, S: Scalar> VerificationBuilderImpl<'a, S> {
+    #[expect(clippy::too_many_arguments)]
     pub fn new(
         mle_evaluations: SumcheckMleEvaluations<'a, S>,
         bit_distributions: &'a [BitDistribution],
@@ -82,6 +146,8 @@ impl<'a, S: Scalar> VerificationBuilderImpl<'a, S> {
         chi_evaluation_length_queue: Vec<usize>,
         rho_evaluation_length_queue: Vec<usize>,
         subpolynomial_max_multiplicands: usize,
+        table_lengths: IndexMap<TableRef, usize>,
+        verification_options: VerificationOptions,
     ) -> Self {
         Self {
             mle_evaluations,
@@ -97,9 +163,33 @@ impl<'a, S: Scalar> VerificationBuilderImpl<'a, S> {
             chi_evaluation_length_queue,
             rho_evaluation_length_queue,
             subpolynomial_max_multiplicands,
+            table_lengths,
+            verification_options,
+            deadline: VerificationDeadline::default(),
+            timed_out: false,
+            result_checks_deferred: false,
         }
     }
 
+    /// Sets the deadline checked between batches of constraints
+    pub fn with_deadline(self, deadline: VerificationDeadline) -> Self {
+        Self { deadline, ..self }
+    }
+
+    /// Sets whether plans skip checking their result columns, see
+    /// [`VerificationBuilder::result_checks_deferred`]
+    pub fn with_result_checks_deferred(self, result_checks_deferred: bool) -> Self {
+        Self {
+            result_checks_deferred,
+            ..self
+        }
+    }
+
+    /// Returns whether the builder stopped producing constraints because the deadline passed
+    pub fn timed_out(&self) -> bool {
+        self.timed_out
+    }
+
     #[expect(
         clippy::missing_panics_doc,
         reason = "The panic condition is clear due to the assertion that checks if the computation is completed."
@@ -124,6 +214,11 @@ impl<'a, S: Scalar> VerificationBuilderImpl<'a, S> {
 
 impl<S: Scalar> VerificationBuilder<S> for VerificationBuilderImpl<'_, S> {
     fn try_consume_chi_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
+        self.try_consume_chi_evaluation_with_length()
+            .map(|(chi_eval, _)| chi_eval)
+    }
+
+    fn try_consume_chi_evaluation_with_length(&mut self) -> Result<(S, usize), ProofSizeMismatch> {
         let index = self.consumed_chi_evaluations;
         let length = self
             .chi_evaluation_length_queue
@@ -131,11 +226,12 @@ impl<S: Scalar> VerificationBuilder<S> for VerificationBuilderImpl<'_, S> {
             .copied()
             .ok_or(ProofSizeMismatch::TooFewChiLengths)?;
         self.consumed_chi_evaluations += 1;
-        Ok(*self
+        let chi_eval = *self
             .mle_evaluations
             .chi_evaluations
             .get(&length)
-            .ok_or(ProofSizeMismatch::ChiLengthNotFound)?)
+            .ok_or(ProofSizeMismatch::ChiLengthNotFound)?;
+        Ok((chi_eval, length))
     }
 
     fn try_consume_rho_evaluation(&mut self) -> Result<S, ProofSizeMismatch> {
@@ -198,6 +294,13 @@ impl<S: Scalar> VerificationBuilder<S> for VerificationBuilderImpl<'_, S> {
         eval: S,
         degree: usize,
     ) -> Result<(), ProofSizeMismatch> {
+        // The error only stops the plan from producing further constraints. The caller reports
+        // a timeout instead once it sees that the builder timed out.
+        if self.produced_subpolynomials % DEADLINE_CHECK_INTERVAL == 0 && self.deadline.is_expired()
+        {
+            self.timed_out = true;
+            Err(ProofSizeMismatch::ConstraintCountMismatch)?;
+        }
         self.sumcheck_evaluation += self
             .subpolynomial_multipliers
             .get(self.produced_subpolynomials)
@@ -240,4 +343,20 @@ impl<S: Scalar> VerificationBuilder<S> for VerificationBuilderImpl<'_, S> {
     fn rho_256_evaluation(&self) -> Option<S> {
         self.mle_evaluations.rho_256_evaluation
     }
+
+    fn evaluation_point(&self) -> &[S] {
+        self.mle_evaluations.evaluation_point
+    }
+
+    fn table_length(&self, table_ref: &TableRef) -> Option<usize> {
+        self.table_lengths.get(table_ref).copied()
+    }
+
+    fn verification_options(&self) -> VerificationOptions {
+        self.verification_options
+    }
+
+    fn result_checks_deferred(&self) -> bool {
+        self.result_checks_deferred
+    }
 }
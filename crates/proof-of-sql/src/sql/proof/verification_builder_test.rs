@@ -1,9 +1,13 @@
 use super::{SumcheckMleEvaluations, VerificationBuilderImpl};
 use crate::{
+    base::{
+        map::{indexmap, IndexMap},
+        proof::{ProofError, ProofSizeMismatch},
+    },
     proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
-    sql::proof::{SumcheckSubpolynomialType, VerificationBuilder},
+    sql::proof::{SumcheckSubpolynomialType, VerificationBuilder, VerificationOptions},
 };
-use alloc::collections::VecDeque;
+use alloc::{collections::VecDeque, vec};
 use num_traits::Zero;
 
 #[test]
@@ -19,6 +23,8 @@ fn an_empty_sumcheck_polynomial_evaluates_to_zero() {
         Vec::new(),
         Vec::new(),
         0,
+        IndexMap::default(),
+        VerificationOptions::default(),
     );
     assert_eq!(builder.sumcheck_evaluation(), Curve25519Scalar::zero());
 }
@@ -40,6 +46,8 @@ fn we_build_up_a_sumcheck_polynomial_evaluation_from_subpolynomial_evaluations()
         Vec::new(),
         Vec::new(),
         1,
+        IndexMap::default(),
+        VerificationOptions::default(),
     );
     builder
         .try_produce_sumcheck_subpolynomial_evaluation(
@@ -75,6 +83,8 @@ fn we_can_consume_post_result_challenges_in_verification_builder() {
         Vec::new(),
         Vec::new(),
         0,
+        IndexMap::default(),
+        VerificationOptions::default(),
     );
     assert_eq!(
         Curve25519Scalar::from(123),
@@ -89,3 +99,75 @@ fn we_can_consume_post_result_challenges_in_verification_builder() {
         builder.try_consume_post_result_challenge().unwrap()
     );
 }
+
+#[test]
+fn we_can_consume_chi_evaluations_of_expected_lengths() {
+    let mut builder = VerificationBuilderImpl::new(
+        SumcheckMleEvaluations {
+            chi_evaluations: indexmap! {
+                3 => Curve25519Scalar::from(30u64),
+                5 => Curve25519Scalar::from(50u64),
+            },
+            ..Default::default()
+        },
+        &[][..],
+        &[][..],
+        VecDeque::new(),
+        vec![5, 3, 3],
+        Vec::new(),
+        0,
+        IndexMap::default(),
+        VerificationOptions::default(),
+    );
+    assert_eq!(
+        builder.try_consume_chi_evaluation_of_length(5).unwrap(),
+        Curve25519Scalar::from(50u64)
+    );
+    assert_eq!(
+        builder.try_consume_chi_evaluation_of_length(3).unwrap(),
+        Curve25519Scalar::from(30u64)
+    );
+    assert!(matches!(
+        builder.try_consume_chi_evaluation_of_length(5),
+        Err(ProofError::ChiLengthMismatch {
+            declared_length: 3,
+            expected_length: 5
+        })
+    ));
+    assert!(matches!(
+        builder.try_consume_chi_evaluation_of_length(3),
+        Err(ProofError::ProofSizeMismatch {
+            source: ProofSizeMismatch::TooFewChiLengths
+        })
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn we_stop_producing_subpolynomials_once_the_deadline_has_passed() {
+    let subpolynomial_multipliers = [Curve25519Scalar::from(10u64)];
+    let deadline = VerificationOptions::default()
+        .with_timeout(Some(core::time::Duration::ZERO))
+        .start_deadline();
+    let mut builder = VerificationBuilderImpl::new(
+        SumcheckMleEvaluations::default(),
+        &[][..],
+        &subpolynomial_multipliers,
+        VecDeque::new(),
+        Vec::new(),
+        Vec::new(),
+        1,
+        IndexMap::default(),
+        VerificationOptions::default(),
+    )
+    .with_deadline(deadline);
+    assert!(!builder.timed_out());
+    assert!(builder
+        .try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::ZeroSum,
+            Curve25519Scalar::from(2u64),
+            1,
+        )
+        .is_err());
+    assert!(builder.timed_out());
+}
@@ -0,0 +1,154 @@
+use super::QueryError;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+/// Options controlling how a query proof is verified.
+///
+/// The limits are checked against the lengths the prover declares in the first round, before any
+/// cryptographic check, so that an oversized proof is rejected early.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerificationOptions {
+    max_groups: Option<usize>,
+    max_proof_bytes: Option<usize>,
+    max_sequence_length: Option<usize>,
+    #[cfg(feature = "std")]
+    timeout: Option<Duration>,
+}
+
+impl VerificationOptions {
+    /// Creates options with an optional maximum number of groups a group by may output
+    #[must_use]
+    pub fn new(max_groups: Option<usize>) -> Self {
+        Self {
+            max_groups,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the maximum number of groups a group by may output, if any
+    #[must_use]
+    pub fn max_groups(&self) -> Option<usize> {
+        self.max_groups
+    }
+
+    /// Sets the maximum number of bytes the serialized proof may take up
+    #[must_use]
+    pub fn with_max_proof_bytes(self, max_proof_bytes: Option<usize>) -> Self {
+        Self {
+            max_proof_bytes,
+            ..self
+        }
+    }
+
+    /// Returns the maximum number of bytes the serialized proof may take up, if any
+    #[must_use]
+    pub fn max_proof_bytes(&self) -> Option<usize> {
+        self.max_proof_bytes
+    }
+
+    /// Sets the maximum length of any sequence in a proof or its result
+    ///
+    /// This bounds the number of rows of the result, the numbers of commitments, MLE evaluations
+    /// and bit distributions in the proof, and the numbers of challenges, constraints and chi and
+    /// rho evaluations the prover declares.
+    #[must_use]
+    pub fn with_max_sequence_length(self, max_sequence_length: Option<usize>) -> Self {
+        Self {
+            max_sequence_length,
+            ..self
+        }
+    }
+
+    /// Returns the maximum length of any sequence in a proof or its result, if any
+    #[must_use]
+    pub fn max_sequence_length(&self) -> Option<usize> {
+        self.max_sequence_length
+    }
+
+    /// Sets the wall-clock budget of a verification
+    ///
+    /// The deadline is checked cooperatively between the steps of the verification, so a
+    /// verification may overrun it by the time a single step takes.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Returns the wall-clock budget of a verification, if any
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Starts the clock of a verification, returning the deadline it has to finish by
+    pub(crate) fn start_deadline(&self) -> VerificationDeadline {
+        VerificationDeadline {
+            #[cfg(feature = "std")]
+            deadline: self
+                .timeout
+                .and_then(|timeout| Instant::now().checked_add(timeout)),
+        }
+    }
+
+    /// Checks that a serialized proof of `proof_bytes` bytes does not exceed the byte limit
+    pub(crate) fn check_proof_bytes(&self, proof_bytes: usize) -> Result<(), QueryError> {
+        match self.max_proof_bytes {
+            Some(max_proof_bytes) if proof_bytes > max_proof_bytes => {
+                Err(QueryError::ProofTooLarge {
+                    proof_bytes,
+                    max_proof_bytes,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that a sequence of `length` elements in a proof or its result does not exceed the
+    /// length limit
+    pub(crate) fn check_sequence_length(&self, length: usize) -> Result<(), QueryError> {
+        match self.max_sequence_length {
+            Some(max_sequence_length) if length > max_sequence_length => {
+                Err(QueryError::SequenceTooLong {
+                    length,
+                    max_sequence_length,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The point in time by which a verification has to finish
+///
+/// Without the `std` feature there is no clock, so the deadline never expires.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct VerificationDeadline {
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+}
+
+impl VerificationDeadline {
+    /// Returns whether the deadline has passed
+    pub(crate) fn is_expired(&self) -> bool {
+        #[cfg(feature = "std")]
+        {
+            self.deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            false
+        }
+    }
+
+    /// Returns [`QueryError::Timeout`] if the deadline has passed
+    pub(crate) fn check(&self) -> Result<(), QueryError> {
+        if self.is_expired() {
+            Err(QueryError::Timeout)
+        } else {
+            Ok(())
+        }
+    }
+}
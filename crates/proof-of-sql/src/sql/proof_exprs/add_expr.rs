@@ -1,8 +1,11 @@
-use super::{add_subtract_columns, DecimalProofExpr, DynProofExpr, ProofExpr};
+use super::{
+    add_subtract_columns, get_column_references_of_exprs, DecimalProofExpr, DynProofExpr, ProofExpr,
+};
 use crate::{
     base::{
         database::{
-            try_add_subtract_column_types, Column, ColumnRef, ColumnType, LiteralValue, Table,
+            try_add_subtract_column_types, try_add_subtract_column_types_with_scaling, Column,
+            ColumnRef, ColumnType, LiteralValue, Table,
         },
         map::{IndexMap, IndexSet},
         proof::{PlaceholderResult, ProofError},
@@ -10,7 +13,7 @@ use crate::{
     },
     sql::{
         proof::{FinalRoundBuilder, VerificationBuilder},
-        AnalyzeError, AnalyzeResult,
+        scale_cast_binary_op, AnalyzeError, AnalyzeResult,
     },
     utils::log,
 };
@@ -28,15 +31,27 @@ pub struct AddExpr {
 
 impl AddExpr {
     /// Create numerical `+` expression
+    ///
+    /// Operands of different scales, including integers which have scale 0, are supported.
+    /// Unless one of them is a scalar, the operand with the smaller scale is scaled to the larger
+    /// scale, which fails if that would need a precision above 75.
     pub fn try_new(lhs: Box<DynProofExpr>, rhs: Box<DynProofExpr>) -> AnalyzeResult<Self> {
         let left_datatype = lhs.data_type();
         let right_datatype = rhs.data_type();
-        try_add_subtract_column_types(left_datatype, right_datatype)
-            .map(|_| Self { lhs, rhs })
-            .map_err(|_| AnalyzeError::DataTypeMismatch {
+        try_add_subtract_column_types_with_scaling(left_datatype, right_datatype).map_err(
+            |_| AnalyzeError::DataTypeMismatch {
                 left_type: left_datatype.to_string(),
                 right_type: right_datatype.to_string(),
-            })
+            },
+        )?;
+        if left_datatype == ColumnType::Scalar || right_datatype == ColumnType::Scalar {
+            return Ok(Self { lhs, rhs });
+        }
+        let (lhs, rhs) = scale_cast_binary_op(*lhs, *rhs)?;
+        Ok(Self {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        })
     }
 
     /// Get the left-hand side expression
@@ -111,8 +126,7 @@ impl ProofExpr for AddExpr {
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.lhs.get_column_references(columns);
-        self.rhs.get_column_references(columns);
+        get_column_references_of_exprs([&*self.lhs, &*self.rhs], columns);
     }
 }
 
@@ -6,7 +6,7 @@ use crate::{
             owned_table_utility::*, table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef,
             TableTestAccessor,
         },
-        math::decimal::Precision,
+        math::decimal::{DecimalError, Precision},
     },
     proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
     sql::{
@@ -126,6 +126,72 @@ fn we_can_prove_a_typical_add_subtract_query_with_decimals() {
     assert_eq!(res, expected_res);
 }
 
+// select a + b as s, b - a as d from sxt.t where c - b = 0.5
+#[test]
+fn we_can_prove_an_add_subtract_query_with_mixed_integer_and_decimal_operands() {
+    let data = owned_table([
+        tinyint("a", [1_i8, -2, 3]),
+        decimal75("b", 5, 2, [150_i64, 25, -1]),
+        bigint("c", [2_i64, 1, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let sum = add(column(&t, "a", &accessor), column(&t, "b", &accessor));
+    let difference = subtract(column(&t, "b", &accessor), column(&t, "a", &accessor));
+    assert_eq!(
+        sum.data_type(),
+        ColumnType::Decimal75(Precision::new(6).unwrap(), 2)
+    );
+    assert_eq!(
+        difference.data_type(),
+        ColumnType::Decimal75(Precision::new(6).unwrap(), 2)
+    );
+    let ast = filter(
+        vec![aliased_plan(sum, "s"), aliased_plan(difference, "d")],
+        tab(&t),
+        equal(
+            subtract(column(&t, "c", &accessor), column(&t, "b", &accessor)),
+            const_decimal75(3, 2, 50),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        decimal75("s", 6, 2, [250_i64]),
+        decimal75("d", 6, 2, [50_i64]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_add_subtract_operands_whose_scaling_exceeds_the_maximum_precision() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_decimal75("a", 75, 0, [1_i64, 2], &alloc),
+        borrowed_decimal75("b", 10, 5, [3_i64, 4], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor = TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let lhs = column(&t, "a", &accessor);
+    let rhs = column(&t, "b", &accessor);
+    for res in [
+        DynProofExpr::try_new_add(lhs.clone(), rhs.clone()),
+        DynProofExpr::try_new_subtract(rhs, lhs),
+    ] {
+        assert!(matches!(
+            res,
+            Err(AnalyzeError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision { .. }
+            })
+        ));
+    }
+}
+
 fn test_random_tables_with_given_offset(offset: usize) {
     let dist = Uniform::new(-3, 4);
     let mut rng = StdRng::from_seed([0u8; 32]);
@@ -1,4 +1,4 @@
-use super::{numerical_util::cast_column, DynProofExpr, ProofExpr};
+use super::{get_column_references_of_exprs, numerical_util::cast_column, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{try_cast_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
@@ -34,6 +34,11 @@ impl CastExpr {
                 right_type: to_type.to_string(),
             })
     }
+
+    /// Get the expression being cast
+    pub fn from_expr(&self) -> &DynProofExpr {
+        &self.from_expr
+    }
 }
 
 impl ProofExpr for CastExpr {
@@ -86,6 +91,6 @@ impl ProofExpr for CastExpr {
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.from_expr.get_column_references(columns);
+        get_column_references_of_exprs([&*self.from_expr], columns);
     }
 }
@@ -0,0 +1,333 @@
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_sign, verifier_evaluate_sign},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use bumpalo::Bump;
+use num_bigint::{BigInt, BigUint};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expression converting a numeric expression to a decimal with a different scale
+///
+/// Increasing the scale is exact. Decreasing the scale by `d` divides by `10^d` and rounds
+/// half to even (banker's rounding), so that `2.5` becomes `2`, `3.5` becomes `4`,
+/// `-2.5` becomes `-2` and `-3.5` becomes `-4`. Unlike rounding half away from zero, this
+/// doesn't bias sums of rounded values.
+///
+/// The prover commits to the quotients `q = 2h + b` through the halves `h`, which are range
+/// checked, and the parity bits `b`. With `step = 10^d` the remainder `r = expr - step * q` is
+/// shown to satisfy `-step + b <= 2r <= step - b`. For odd quotients the bounds are strict, so
+/// the midpoints `2r = ±step` can only be reached by the even neighbour.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecimalRescaleExpr {
+    from_expr: Box<DynProofExpr>,
+    to_type: ColumnType,
+}
+
+impl DecimalRescaleExpr {
+    /// Create an expression converting `from_expr` to the decimal type `to_type`
+    ///
+    /// `from_expr` must be an integer or decimal expression and `to_type` a decimal type whose
+    /// precision fits every rescaled value, including a carry caused by rounding.
+    pub fn try_new(from_expr: Box<DynProofExpr>, to_type: ColumnType) -> AnalyzeResult<Self> {
+        let from_type = from_expr.data_type();
+        let mismatch = || AnalyzeError::DataTypeMismatch {
+            left_type: from_type.to_string(),
+            right_type: to_type.to_string(),
+        };
+        let (Some(from_precision), Some(from_scale), ColumnType::Decimal75(to_precision, to_scale)) =
+            (from_type.precision_value(), from_type.scale(), to_type)
+        else {
+            return Err(mismatch());
+        };
+        if !(from_type.is_integer() || matches!(from_type, ColumnType::Decimal75(_, _))) {
+            return Err(mismatch());
+        }
+        let from_precision = i16::from(from_precision);
+        let to_precision = i16::from(to_precision.value());
+        let scale_diff = i16::from(to_scale) - i16::from(from_scale);
+        let required_precision = if scale_diff >= 0 {
+            from_precision + scale_diff
+        } else {
+            from_precision + scale_diff + 1
+        };
+        if to_precision < required_precision {
+            return Err(mismatch());
+        }
+        Ok(Self { from_expr, to_type })
+    }
+
+    /// Create an expression converting `from_expr` to the decimal type `to_type` without
+    /// checking that the precision of `to_type` fits every rescaled value
+    ///
+    /// This is for expressions whose values are bounded more tightly than their types tell,
+    /// such as the intermediate values of [`LnExpr`](super::LnExpr).
+    pub(super) fn new_bounded(from_expr: Box<DynProofExpr>, to_type: ColumnType) -> Self {
+        debug_assert!(matches!(to_type, ColumnType::Decimal75(_, _)));
+        Self { from_expr, to_type }
+    }
+
+    /// Get the expression being rescaled
+    pub fn from_expr(&self) -> &DynProofExpr {
+        &self.from_expr
+    }
+
+    /// The difference between the scale of the result and the scale of the input
+    fn scale_diff(&self) -> i16 {
+        let from_scale = self.from_expr.data_type().scale().unwrap_or(0);
+        let to_scale = self.to_type.scale().unwrap_or(0);
+        i16::from(to_scale) - i16::from(from_scale)
+    }
+
+    /// The number of bits the halves of the quotients are range checked to
+    ///
+    /// Values with `p` digits fit in `ceil(3.322 * p)` bits and a step of `10^d` is at least
+    /// `2^floor(3.321 * d)`. As for `RoundToMultipleExpr`, two bits of slack bound `|step * q|`
+    /// far below the modulus of the scalar field.
+    fn half_quotient_bits(&self) -> u8 {
+        let precision = u32::from(self.from_expr.data_type().precision_value().unwrap_or(0));
+        let value_bits = (precision * 3322).div_ceil(1000);
+        let step_log2 = u32::from(self.scale_diff().unsigned_abs()) * 3321 / 1000;
+        u8::try_from((value_bits + 2).saturating_sub(step_log2).max(2))
+            .expect("Decimals have at most 75 digits")
+    }
+}
+
+/// Divide `value` by `step`, rounding half to even
+pub(crate) fn divide_rounding_half_to_even(value: &BigInt, step: &BigUint) -> BigInt {
+    let magnitude = value.magnitude();
+    let quotient = magnitude / step;
+    let doubled_remainder = (magnitude - &quotient * step) * 2u8;
+    let rounds_up = doubled_remainder > *step || (doubled_remainder == *step && quotient.bit(0));
+    let rounded = if rounds_up { quotient + 1u8 } else { quotient };
+    BigInt::from_biguint(value.sign(), rounded)
+}
+
+/// Rescale scalars by `10^scale_diff`, rounding half to even when the scale decreases
+fn rescale_scalars<S: Scalar>(values: &[S], scale_diff: i16) -> Vec<S> {
+    let factor = BigUint::from(10u8).pow(u32::from(scale_diff.unsigned_abs()));
+    if scale_diff >= 0 {
+        let factor = S::try_from(BigInt::from(factor)).expect("10^d fits in a scalar for d <= 75");
+        values.iter().map(|&value| value * factor).collect()
+    } else {
+        values
+            .iter()
+            .map(|&value| {
+                S::try_from(divide_rounding_half_to_even(&value.into(), &factor))
+                    .expect("A rounded quotient is smaller than its dividend")
+            })
+            .collect()
+    }
+}
+
+/// Split a quotient `q` into its half `h` and its parity bit `b`, so that `q = 2h + b`
+fn split_parity<S: Scalar>(quotient: S) -> (S, bool) {
+    let quotient: BigInt = quotient.into();
+    let parity = quotient.magnitude().bit(0);
+    let half = (quotient - u8::from(parity)) / 2u8;
+    (
+        S::try_from(half).expect("A half is smaller than its quotient"),
+        parity,
+    )
+}
+
+impl ProofExpr for DecimalRescaleExpr {
+    fn data_type(&self) -> ColumnType {
+        self.to_type
+    }
+
+    #[tracing::instrument(
+        name = "DecimalRescaleExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self.from_expr.first_round_evaluate(alloc, table, params)?;
+        let values: Vec<S> = column.to_scalar();
+        let rescaled = alloc.alloc_slice_fill_iter(rescale_scalars(&values, self.scale_diff()));
+        let ColumnType::Decimal75(precision, scale) = self.to_type else {
+            unreachable!("The target type is checked to be a decimal")
+        };
+
+        log::log_memory_usage("End");
+
+        Ok(Column::Decimal75(precision, scale, rescaled))
+    }
+
+    #[tracing::instrument(
+        name = "DecimalRescaleExpr::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self
+            .from_expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+        let values: &'a [S] = alloc.alloc_slice_fill_iter(column.to_scalar());
+        let scale_diff = self.scale_diff();
+        let quotients: &'a [S] = alloc.alloc_slice_fill_iter(rescale_scalars(values, scale_diff));
+        let ColumnType::Decimal75(precision, scale) = self.to_type else {
+            unreachable!("The target type is checked to be a decimal")
+        };
+
+        if scale_diff < 0 {
+            // q = 2h + b
+            let (halves, parities): (Vec<S>, Vec<bool>) = quotients
+                .iter()
+                .map(|&quotient| split_parity(quotient))
+                .unzip();
+            let halves: &'a [S] = alloc.alloc_slice_copy(&halves);
+            let parities: &'a [bool] = alloc.alloc_slice_copy(&parities);
+            builder.produce_intermediate_mle(halves);
+            builder.produce_intermediate_mle(parities);
+
+            // b * b - b = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (
+                        S::one(),
+                        vec![Box::new(parities as &[_]), Box::new(parities as &[_])],
+                    ),
+                    (-S::one(), vec![Box::new(parities as &[_])]),
+                ],
+            );
+            // range check of the halves
+            final_round_evaluate_sign(builder, alloc, halves);
+
+            // The bounds on the remainders, which are all non-negative
+            let step = S::try_from(BigInt::from(10u8).pow(u32::from(scale_diff.unsigned_abs())))
+                .expect("10^d fits in a scalar for d <= 75");
+            let doubled_remainders = values
+                .iter()
+                .zip(quotients)
+                .map(|(&value, &quotient)| (value - step * quotient) * S::TWO);
+            let lower_bounds: &'a [S] =
+                alloc.alloc_slice_fill_iter(doubled_remainders.clone().zip(parities).map(
+                    |(doubled_remainder, &parity)| doubled_remainder + step - S::from(parity),
+                ));
+            let upper_bounds: &'a [S] =
+                alloc.alloc_slice_fill_iter(doubled_remainders.zip(parities).map(
+                    |(doubled_remainder, &parity)| step - S::from(parity) - doubled_remainder,
+                ));
+            final_round_evaluate_sign(builder, alloc, lower_bounds);
+            final_round_evaluate_sign(builder, alloc, upper_bounds);
+        }
+
+        log::log_memory_usage("End");
+
+        Ok(Column::Decimal75(precision, scale, quotients))
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let value_eval = self
+            .from_expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        let scale_diff = self.scale_diff();
+        let factor = S::try_from(BigInt::from(10u8).pow(u32::from(scale_diff.unsigned_abs())))
+            .expect("10^d fits in a scalar for d <= 75");
+        if scale_diff >= 0 {
+            return Ok(value_eval * factor);
+        }
+
+        let half_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let parity_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // b * b - b = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            parity_eval * parity_eval - parity_eval,
+            2,
+        )?;
+        // range check of the halves
+        verifier_evaluate_sign(
+            builder,
+            half_eval,
+            chi_eval,
+            Some(self.half_quotient_bits()),
+        )?;
+
+        let quotient_eval = S::TWO * half_eval + parity_eval;
+        let doubled_remainder_eval = (value_eval - factor * quotient_eval) * S::TWO;
+        let lower_bound_eval = doubled_remainder_eval + factor * chi_eval - parity_eval;
+        let upper_bound_eval = factor * chi_eval - parity_eval - doubled_remainder_eval;
+        for bound_eval in [lower_bound_eval, upper_bound_eval] {
+            if verifier_evaluate_sign(builder, bound_eval, chi_eval, None)? != S::ZERO {
+                return Err(ProofError::VerificationError {
+                    error: "rescaled value is not rounded half to even",
+                });
+            }
+        }
+
+        Ok(quotient_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs([&*self.from_expr], columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::divide_rounding_half_to_even;
+    use num_bigint::{BigInt, BigUint};
+
+    fn divide(value: i64, step: u64) -> i64 {
+        i64::try_from(divide_rounding_half_to_even(
+            &BigInt::from(value),
+            &BigUint::from(step),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn we_can_divide_rounding_half_to_even() {
+        assert_eq!(divide(0, 10), 0);
+        assert_eq!(divide(5, 10), 0);
+        assert_eq!(divide(15, 10), 2);
+        assert_eq!(divide(25, 10), 2);
+        assert_eq!(divide(35, 10), 4);
+        assert_eq!(divide(-5, 10), 0);
+        assert_eq!(divide(-15, 10), -2);
+        assert_eq!(divide(-25, 10), -2);
+        assert_eq!(divide(-35, 10), -4);
+        assert_eq!(divide(249, 100), 2);
+        assert_eq!(divide(251, 100), 3);
+        assert_eq!(divide(-251, 100), -3);
+        assert_eq!(divide(-249, 100), -2);
+        assert_eq!(divide(1234, 1), 1234);
+    }
+}
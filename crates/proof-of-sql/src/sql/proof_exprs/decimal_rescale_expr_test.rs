@@ -0,0 +1,263 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor, TestAccessor,
+        },
+        math::decimal::Precision,
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{
+            decimal_rescale_expr::DecimalRescaleExpr, test_utility::*, DynProofExpr, ProofExpr,
+        },
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+
+fn decimal_type(precision: u8, scale: i8) -> ColumnType {
+    ColumnType::Decimal75(Precision::new(precision).unwrap(), scale)
+}
+
+// select rescale(a, decimal(3, 0)) as r from sxt.t
+#[test]
+fn we_can_prove_a_rescale_which_rounds_midpoints_half_to_even() {
+    let data = owned_table([decimal75(
+        "a",
+        3,
+        1,
+        [5, 15, 25, 35, 45, -5, -15, -25, -35, -45, 0, 10, -10],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            decimal_rescale(column(&t, "a", &accessor), decimal_type(3, 0)),
+            "r",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([decimal75(
+        "r",
+        3,
+        0,
+        [0, 2, 2, 4, 4, 0, -2, -2, -4, -4, 0, 1, -1],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+// select rescale(a, decimal(6, 1)) as r1, rescale(a, decimal(5, 0)) as r0,
+//   rescale(a, decimal(9, 5)) as r5 from sxt.t
+#[test]
+fn we_can_prove_rescales_of_a_decimal_in_both_directions() {
+    let data = owned_table([decimal75(
+        "a",
+        6,
+        2,
+        [
+            1249, 1250, 1251, 1349, 1350, -1250, -1350, -1251, 999_999, -999_999,
+        ],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                decimal_rescale(column(&t, "a", &accessor), decimal_type(6, 1)),
+                "r1",
+            ),
+            aliased_plan(
+                decimal_rescale(column(&t, "a", &accessor), decimal_type(5, 0)),
+                "r0",
+            ),
+            aliased_plan(
+                decimal_rescale(column(&t, "a", &accessor), decimal_type(9, 5)),
+                "r5",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        decimal75(
+            "r1",
+            6,
+            1,
+            [125, 125, 125, 135, 135, -125, -135, -125, 100_000, -100_000],
+        ),
+        decimal75(
+            "r0",
+            5,
+            0,
+            [12, 12, 13, 13, 14, -12, -14, -13, 10_000, -10_000],
+        ),
+        decimal75(
+            "r5",
+            9,
+            5,
+            [
+                1_249_000,
+                1_250_000,
+                1_251_000,
+                1_349_000,
+                1_350_000,
+                -1_250_000,
+                -1_350_000,
+                -1_251_000,
+                999_999_000,
+                -999_999_000,
+            ],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select rescale(a, decimal(18, -2)) as r from sxt.t
+#[test]
+fn we_can_prove_a_rescale_of_an_integer_to_a_negative_scale() {
+    let data = owned_table([bigint(
+        "a",
+        [50_i64, 150, 250, -250, 251, i64::MAX, i64::MIN],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            decimal_rescale(column(&t, "a", &accessor), decimal_type(18, -2)),
+            "r",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([decimal75(
+        "r",
+        18,
+        -2,
+        [
+            0_i64,
+            2,
+            2,
+            -2,
+            3,
+            92_233_720_368_547_758,
+            -92_233_720_368_547_758,
+        ],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where rescale(a, decimal(2, 0)) = 2
+#[test]
+fn we_can_prove_a_filter_on_a_rescaled_column() {
+    let data = owned_table([decimal75("a", 2, 1, [15, 25, 35, 20, 26, -25])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            decimal_rescale(column(&t, "a", &accessor), decimal_type(2, 0)),
+            const_decimal75(2, 0, 2),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([decimal75("a", 2, 1, [15, 25, 20])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_decimal_rescale_expr_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_decimal75(
+        "a",
+        4,
+        2,
+        [-150, -250, 250, 251],
+        &alloc,
+    )]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let expr: DynProofExpr = decimal_rescale(column(&t, "a", &accessor), decimal_type(4, 1));
+    let res = expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(
+        res,
+        Column::Decimal75(
+            Precision::new(4).unwrap(),
+            1,
+            &[-15, -25, 25, 25].map(Curve25519Scalar::from)
+        )
+    );
+    assert_eq!(expr.data_type(), decimal_type(4, 1));
+}
+
+#[test]
+fn we_cannot_rescale_to_a_type_which_is_too_small_or_not_a_decimal() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_decimal75("a", 4, 2, [1, 2], &alloc),
+        borrowed_varchar("b", ["x", "y"], &alloc),
+        borrowed_boolean("c", [true, false], &alloc),
+        borrowed_int("d", [1, 2], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for (column_id, to_type) in [
+        // A decrease needs room for the carry
+        ("a", decimal_type(2, 1)),
+        // An increase needs room for the additional digits
+        ("a", decimal_type(5, 4)),
+        ("a", ColumnType::BigInt),
+        ("b", decimal_type(10, 0)),
+        ("c", decimal_type(10, 0)),
+        ("d", decimal_type(9, 0)),
+        ("d", ColumnType::Int128),
+    ] {
+        let res = DecimalRescaleExpr::try_new(Box::new(column(&t, column_id, &accessor)), to_type);
+        assert!(matches!(res, Err(AnalyzeError::DataTypeMismatch { .. })));
+    }
+    for (column_id, to_type) in [
+        ("a", decimal_type(4, 1)),
+        ("a", decimal_type(6, 4)),
+        ("d", decimal_type(10, 0)),
+        ("d", decimal_type(9, -2)),
+    ] {
+        assert!(
+            DecimalRescaleExpr::try_new(Box::new(column(&t, column_id, &accessor)), to_type)
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use super::{
+    get_column_references_of_exprs, row_hash_expr::canonical_hash_columns, ColumnExpr,
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_row_hash, row_hash, verifier_evaluate_row_hash},
+        AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expression deriving a surrogate key from the values of several columns in each row
+///
+/// Like a UUID v5, the key is a hash of a 16 byte namespace followed by the natural key, so equal
+/// natural keys get equal surrogate keys within a namespace while keys of different namespaces are
+/// unrelated. The hash is the row hash of `RowHashExpr`, with the scalar
+/// derived from the namespace as the first value, so the columns are hashed in the canonical order
+/// of ascending column identifiers and each value is hashed as its scalar.
+///
+/// The result has type [`ColumnType::Scalar`], i.e. the key is a field element with a fixed size
+/// of 32 bytes. It is not truncated to 16 bytes, because proving a truncation would need a range
+/// check of the canonical decomposition of the whole hash.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivedKeyExpr {
+    namespace: [u8; 16],
+    columns: Vec<DynProofExpr>,
+}
+
+impl DerivedKeyExpr {
+    /// Create an expression deriving keys in `namespace` from the values of `columns` in each row
+    ///
+    /// There must be at least one column and no column may be listed twice.
+    pub fn try_new(namespace: [u8; 16], columns: Vec<ColumnExpr>) -> AnalyzeResult<Self> {
+        Ok(Self {
+            namespace,
+            columns: canonical_hash_columns(columns)?,
+        })
+    }
+
+    /// Get the namespace of the keys
+    pub fn namespace(&self) -> &[u8; 16] {
+        &self.namespace
+    }
+
+    /// Get the columns of the natural key, in their canonical order
+    pub fn columns(&self) -> &[DynProofExpr] {
+        &self.columns
+    }
+}
+
+/// The scalar a namespace is hashed as
+fn namespace_scalar<S: Scalar>(namespace: &[u8; 16]) -> S {
+    S::from_byte_slice_via_hash(&[b"proof-of-sql derived key".as_slice(), namespace].concat())
+}
+
+/// Compute the key derived in `namespace` from the values of a natural key, in canonical order
+pub(crate) fn derived_key<S: Scalar>(namespace: &[u8; 16], values: &[S]) -> S {
+    row_hash(
+        &core::iter::once(namespace_scalar(namespace))
+            .chain(values.iter().copied())
+            .collect::<Vec<_>>(),
+    )
+}
+
+impl ProofExpr for DerivedKeyExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Scalar
+    }
+
+    #[tracing::instrument(
+        name = "DerivedKeyExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                Ok(column
+                    .first_round_evaluate(alloc, table, params)?
+                    .to_scalar())
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let keys = alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            derived_key(
+                &self.namespace,
+                &columns.iter().map(|column| column[i]).collect::<Vec<_>>(),
+            )
+        });
+
+        log::log_memory_usage("End");
+
+        Ok(Column::Scalar(keys))
+    }
+
+    #[tracing::instrument(
+        name = "DerivedKeyExpr::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let namespace: &'a [S] =
+            alloc.alloc_slice_fill_copy(table.num_rows(), namespace_scalar(&self.namespace));
+        let columns = core::iter::once(Ok(namespace))
+            .chain(self.columns.iter().map(|column| {
+                let values = column.final_round_evaluate(builder, alloc, table, params)?;
+                Ok(&*alloc.alloc_slice_fill_iter(values.to_scalar()))
+            }))
+            .collect::<PlaceholderResult<Vec<&'a [S]>>>()?;
+        let keys = final_round_evaluate_row_hash(builder, alloc, &columns, table.num_rows());
+
+        log::log_memory_usage("End");
+
+        Ok(Column::Scalar(keys))
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let column_evals = core::iter::once(Ok(namespace_scalar::<S>(&self.namespace) * chi_eval))
+            .chain(
+                self.columns
+                    .iter()
+                    .map(|column| column.verifier_evaluate(builder, accessor, chi_eval, params)),
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+        verifier_evaluate_row_hash(builder, &column_evals, chi_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs(&self.columns, columns);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, ColumnRef, ColumnType, OwnedTableTestAccessor, TableRef,
+        },
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{
+            derived_key_expr::{derived_key as derive_key, DerivedKeyExpr},
+            test_utility::*,
+            ColumnExpr, DynProofExpr,
+        },
+        proof_gadgets::row_hash as hash_values,
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+
+const CUSTOMERS: [u8; 16] = *b"sxt.customers v1";
+const ORDERS: [u8; 16] = *b"sxt.orders    v1";
+
+// select a, derived_key(b, a) as k from sxt.t
+#[test]
+fn we_can_prove_keys_derived_from_natural_keys() {
+    let a = [1_i64, -2, 1, 4, 1];
+    let b = ["x", "y", "x", "", "z"];
+    let data = owned_table([bigint("a", a), varchar("b", b)]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            col_expr_plan(&t, "a", &accessor),
+            aliased_plan(derived_key(CUSTOMERS, &t, &["b", "a"], &accessor), "k"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let keys: Vec<_> = a
+        .iter()
+        .zip(b)
+        .map(|(&a, b)| {
+            derive_key(
+                &CUSTOMERS,
+                &[Curve25519Scalar::from(a), Curve25519Scalar::from(b)],
+            )
+        })
+        .collect();
+    let expected_res = owned_table([bigint("a", a), scalar("k", keys.clone())]);
+    assert_eq!(res, expected_res);
+
+    // Equal natural keys get equal keys, different ones different keys
+    assert_eq!(keys[0], keys[2]);
+    assert_ne!(keys[0], keys[1]);
+    assert_ne!(keys[0], keys[4]);
+}
+
+// select a from sxt.t where derived_key(a, b) = k
+#[test]
+fn we_can_prove_a_filter_comparing_derived_keys_with_stored_keys() {
+    let a = [10_i64, 20, 30];
+    let b = [true, false, true];
+    let keys: Vec<_> = a
+        .iter()
+        .zip(b)
+        .map(|(&a, b)| {
+            derive_key(
+                &ORDERS,
+                &[Curve25519Scalar::from(a), Curve25519Scalar::from(b)],
+            )
+        })
+        .collect();
+    let data = owned_table([
+        bigint("a", [10_i64, 21, 30]),
+        boolean("b", b),
+        scalar("k", keys),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            derived_key(ORDERS, &t, &["a", "b"], &accessor),
+            column(&t, "k", &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [10_i64, 30])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_derive_the_same_keys_and_proofs_across_prover_runs() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3]), int("b", [7_i32, 8, 9])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            derived_key(CUSTOMERS, &t, &["a", "b"], &accessor),
+            "k",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let first = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &(), &[]).unwrap();
+    let second =
+        VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &(), &[]).unwrap();
+    assert_eq!(
+        bincode::serde::encode_to_vec(&first, bincode::config::legacy()).unwrap(),
+        bincode::serde::encode_to_vec(&second, bincode::config::legacy()).unwrap()
+    );
+    assert_eq!(
+        first.verify(&ast, &accessor, &(), &[]).unwrap().table,
+        second.verify(&ast, &accessor, &(), &[]).unwrap().table
+    );
+}
+
+#[test]
+fn keys_of_different_namespaces_and_row_hashes_differ() {
+    let values = [Curve25519Scalar::from(1), Curve25519Scalar::from(2)];
+    let key = derive_key(&CUSTOMERS, &values);
+    assert_eq!(key, derive_key(&CUSTOMERS, &values));
+    assert_ne!(key, derive_key(&ORDERS, &values));
+    assert_ne!(key, hash_values(&values));
+}
+
+// select derived_key(a) as k from sxt.t
+#[test]
+fn we_can_prove_derived_keys_of_an_empty_table() {
+    let data = owned_table([bigint("a", [0_i64; 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            derived_key(CUSTOMERS, &t, &["a"], &accessor),
+            "k",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([scalar::<Curve25519Scalar>("k", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn the_columns_of_a_derived_key_are_in_ascending_order_of_their_identifiers() {
+    let t = TableRef::new("sxt", "t");
+    let column =
+        |name: &str| ColumnExpr::new(ColumnRef::new(t.clone(), name.into(), ColumnType::BigInt));
+    let expr = DerivedKeyExpr::try_new(CUSTOMERS, vec![column("c"), column("a")]).unwrap();
+    assert_eq!(
+        expr,
+        DerivedKeyExpr::try_new(CUSTOMERS, vec![column("a"), column("c")]).unwrap()
+    );
+    assert_eq!(
+        expr.columns(),
+        [column("a"), column("c")].map(DynProofExpr::Column)
+    );
+    assert_eq!(expr.namespace(), &CUSTOMERS);
+}
+
+#[test]
+fn we_cannot_derive_keys_from_no_columns_or_a_column_twice() {
+    let t = TableRef::new("sxt", "t");
+    let column =
+        |name: &str| ColumnExpr::new(ColumnRef::new(t.clone(), name.into(), ColumnType::BigInt));
+    assert!(matches!(
+        DerivedKeyExpr::try_new(CUSTOMERS, vec![]),
+        Err(AnalyzeError::EmptyRowHash)
+    ));
+    assert!(matches!(
+        DerivedKeyExpr::try_new(CUSTOMERS, vec![column("a"), column("a")]),
+        Err(AnalyzeError::DuplicateIdent { ident }) if ident == "a"
+    ));
+}
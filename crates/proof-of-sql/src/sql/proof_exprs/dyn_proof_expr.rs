@@ -1,6 +1,8 @@
 use super::{
-    AddExpr, AndExpr, CastExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr, MultiplyExpr,
-    NotExpr, OrExpr, PlaceholderExpr, ProofExpr, ScalingCastExpr, SubtractExpr,
+    AddExpr, AndExpr, CastExpr, ColumnExpr, DecimalRescaleExpr, DerivedKeyExpr, EqualsExpr,
+    ISqrtExpr, IfExpr, InExpr, InequalityExpr, LiteralExpr, LnExpr, ModuloExpr, MultiplyExpr,
+    NotExpr, OrExpr, PlaceholderExpr, PowModExpr, ProofExpr, RoundToMultipleExpr, RowHashExpr,
+    ScalingCastExpr, SubtractExpr,
 };
 use crate::{
     base::{
@@ -14,7 +16,7 @@ use crate::{
         AnalyzeResult,
     },
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
 use core::fmt::Debug;
 use serde::{Deserialize, Serialize};
@@ -50,6 +52,26 @@ pub enum DynProofExpr {
     Cast(CastExpr),
     /// Provable expression for casting numeric expressions to decimal expressions
     ScalingCast(ScalingCastExpr),
+    /// Provable conditional expression
+    If(IfExpr),
+    /// Provable expression rounding to the nearest multiple of a constant step
+    RoundToMultiple(RoundToMultipleExpr),
+    /// Provable numeric `%` expression with a constant modulus
+    Modulo(ModuloExpr),
+    /// Provable modular exponentiation with a small constant exponent and a constant modulus
+    PowMod(PowModExpr),
+    /// Provable expression changing the scale of a decimal, rounding half to even
+    DecimalRescale(DecimalRescaleExpr),
+    /// Provable expression hashing the values of several columns in each row
+    RowHash(RowHashExpr),
+    /// Provable approximation of the natural logarithm over a bounded domain
+    Ln(LnExpr),
+    /// Provable integer square root
+    ISqrt(ISqrtExpr),
+    /// Provable expression deriving a surrogate key from several columns in each row
+    DerivedKey(DerivedKeyExpr),
+    /// Provable expression for whether a value is in a list of literals
+    In(InExpr),
 }
 impl DynProofExpr {
     /// Create column expression
@@ -121,4 +143,130 @@ impl DynProofExpr {
     ) -> AnalyzeResult<Self> {
         ScalingCastExpr::try_new(Box::new(from_expr), to_datatype).map(DynProofExpr::ScalingCast)
     }
+
+    /// Create a new conditional expression
+    pub fn try_new_if(
+        condition: DynProofExpr,
+        then_expr: DynProofExpr,
+        else_expr: DynProofExpr,
+    ) -> AnalyzeResult<Self> {
+        IfExpr::try_new(
+            Box::new(condition),
+            Box::new(then_expr),
+            Box::new(else_expr),
+        )
+        .map(DynProofExpr::If)
+    }
+
+    /// Create a new expression rounding `expr` to the nearest multiple of `step`
+    pub fn try_new_round_to_multiple(
+        expr: DynProofExpr,
+        step: &DynProofExpr,
+    ) -> AnalyzeResult<Self> {
+        RoundToMultipleExpr::try_new(Box::new(expr), step).map(DynProofExpr::RoundToMultiple)
+    }
+
+    /// Create a new expression for the remainder of `expr` divided by `modulus`
+    pub fn try_new_modulo(expr: DynProofExpr, modulus: &DynProofExpr) -> AnalyzeResult<Self> {
+        ModuloExpr::try_new(Box::new(expr), modulus).map(DynProofExpr::Modulo)
+    }
+
+    /// Create a new expression raising `base` to `exponent` modulo `modulus`
+    pub fn try_new_pow_mod(
+        base: DynProofExpr,
+        exponent: &DynProofExpr,
+        modulus: &DynProofExpr,
+    ) -> AnalyzeResult<Self> {
+        PowModExpr::try_new(Box::new(base), exponent, modulus).map(DynProofExpr::PowMod)
+    }
+
+    /// Create a new expression converting `from_expr` to the decimal type `to_datatype`,
+    /// rounding half to even
+    pub fn try_new_decimal_rescale(
+        from_expr: DynProofExpr,
+        to_datatype: ColumnType,
+    ) -> AnalyzeResult<Self> {
+        DecimalRescaleExpr::try_new(Box::new(from_expr), to_datatype)
+            .map(DynProofExpr::DecimalRescale)
+    }
+
+    /// Create a new expression approximating the natural logarithm of `expr`
+    pub fn try_new_ln(expr: DynProofExpr) -> AnalyzeResult<Self> {
+        LnExpr::try_new(Box::new(expr)).map(DynProofExpr::Ln)
+    }
+
+    /// Create a new expression taking the integer square root of `expr`
+    pub fn try_new_isqrt(expr: DynProofExpr) -> AnalyzeResult<Self> {
+        ISqrtExpr::try_new(Box::new(expr)).map(DynProofExpr::ISqrt)
+    }
+
+    /// Create a new expression hashing the values of `columns` in each row
+    pub fn try_new_row_hash(columns: Vec<ColumnExpr>) -> AnalyzeResult<Self> {
+        RowHashExpr::try_new(columns).map(DynProofExpr::RowHash)
+    }
+
+    /// Create a new expression deriving keys in `namespace` from the values of `columns` in each
+    /// row
+    pub fn try_new_derived_key(
+        namespace: [u8; 16],
+        columns: Vec<ColumnExpr>,
+    ) -> AnalyzeResult<Self> {
+        DerivedKeyExpr::try_new(namespace, columns).map(DynProofExpr::DerivedKey)
+    }
+
+    /// Create a new expression for whether the value of `expr` is one of `values`, i.e.
+    /// `expr IN (values)`
+    ///
+    /// The values must all have the same type, and an empty list is false for every row.
+    pub fn try_new_in(expr: DynProofExpr, values: Vec<LiteralValue>) -> AnalyzeResult<Self> {
+        InExpr::try_new(Box::new(expr), values).map(DynProofExpr::In)
+    }
+
+    /// Get the direct sub-expressions of this expression
+    #[must_use]
+    pub fn children(&self) -> Vec<&DynProofExpr> {
+        match self {
+            DynProofExpr::Column(_) | DynProofExpr::Literal(_) | DynProofExpr::Placeholder(_) => {
+                vec![]
+            }
+            DynProofExpr::And(expr) => vec![expr.lhs(), expr.rhs()],
+            DynProofExpr::Or(expr) => vec![expr.lhs(), expr.rhs()],
+            DynProofExpr::Not(expr) => vec![expr.input()],
+            DynProofExpr::Equals(expr) => vec![expr.lhs(), expr.rhs()],
+            DynProofExpr::Inequality(expr) => vec![expr.lhs(), expr.rhs()],
+            DynProofExpr::Add(expr) => vec![expr.lhs(), expr.rhs()],
+            DynProofExpr::Subtract(expr) => vec![expr.lhs(), expr.rhs()],
+            DynProofExpr::Multiply(expr) => vec![expr.lhs(), expr.rhs()],
+            DynProofExpr::Cast(expr) => vec![expr.from_expr()],
+            DynProofExpr::ScalingCast(expr) => vec![expr.from_expr()],
+            DynProofExpr::If(expr) => vec![expr.condition(), expr.then_expr(), expr.else_expr()],
+            DynProofExpr::RoundToMultiple(expr) => vec![expr.expr()],
+            DynProofExpr::Modulo(expr) => vec![expr.expr()],
+            DynProofExpr::PowMod(expr) => vec![expr.base()],
+            DynProofExpr::DecimalRescale(expr) => vec![expr.from_expr()],
+            DynProofExpr::RowHash(expr) => expr.columns().iter().collect(),
+            DynProofExpr::Ln(expr) => vec![expr.expr()],
+            DynProofExpr::ISqrt(expr) => vec![expr.expr()],
+            DynProofExpr::DerivedKey(expr) => expr.columns().iter().collect(),
+            DynProofExpr::In(expr) => vec![expr.expr()],
+        }
+    }
+}
+
+/// Insert the column references of `exprs` into `columns`.
+///
+/// Columns are inserted in the same order as a recursive left-to-right walk would insert them,
+/// but an explicit stack is used so that deeply nested expressions cannot overflow the call stack.
+pub(crate) fn get_column_references_of_exprs<'a>(
+    exprs: impl IntoIterator<Item = &'a DynProofExpr>,
+    columns: &mut IndexSet<ColumnRef>,
+) {
+    let mut stack: Vec<&DynProofExpr> = exprs.into_iter().collect();
+    stack.reverse();
+    while let Some(expr) = stack.pop() {
+        if let DynProofExpr::Column(column_expr) = expr {
+            column_expr.get_column_references(columns);
+        }
+        stack.extend(expr.children().into_iter().rev());
+    }
 }
@@ -1,4 +1,4 @@
-use super::{add_subtract_columns, DynProofExpr, ProofExpr};
+use super::{add_subtract_columns, get_column_references_of_exprs, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{try_equals_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
@@ -123,8 +123,7 @@ impl ProofExpr for EqualsExpr {
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.lhs.get_column_references(columns);
-        self.rhs.get_column_references(columns);
+        get_column_references_of_exprs([&*self.lhs, &*self.rhs], columns);
     }
 }
 
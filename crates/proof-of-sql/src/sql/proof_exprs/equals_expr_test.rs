@@ -358,6 +358,103 @@ fn we_can_prove_an_equality_query_with_a_string_comparison() {
     assert_eq!(res, expected_res);
 }
 
+/// Strings which could plausibly share a scalar encoding with the empty string
+const EMPTY_STRING_COLLISION_CANDIDATES: [&str; 5] = ["\0", "\0\0", " ", "0", "\0a"];
+
+fn empty_string_collision_accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut strings = vec![""];
+    strings.extend(EMPTY_STRING_COLLISION_CANDIDATES);
+    strings.push("");
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        TableRef::new("sxt", "t"),
+        owned_table([
+            bigint("a", 0..i64::try_from(strings.len()).unwrap()),
+            varchar("c", strings),
+        ]),
+        0,
+        (),
+    )
+}
+
+#[test]
+fn we_can_tell_the_empty_string_apart_from_candidate_collisions() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = empty_string_collision_accessor();
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "c"], &accessor),
+        tab(&t),
+        equal(column(&t, "c", &accessor), const_varchar("")),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [0_i64, 6]), varchar("c", ["", ""])]);
+    assert_eq!(res, expected_res);
+
+    // Conversely, no candidate selects the empty string
+    for (a, candidate) in (1_i64..).zip(EMPTY_STRING_COLLISION_CANDIDATES) {
+        let ast = filter(
+            cols_expr_plan(&t, &["a", "c"], &accessor),
+            tab(&t),
+            equal(column(&t, "c", &accessor), const_varchar(candidate)),
+        );
+        let res = VerifiableQueryResult::new(&ast, &accessor, &(), &[])
+            .unwrap()
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+        let expected_res = owned_table([bigint("a", [a]), varchar("c", [candidate])]);
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_cannot_prove_that_the_empty_string_equals_a_candidate_collision() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = empty_string_collision_accessor();
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "c"], &accessor),
+        tab(&t),
+        equal(column(&t, "c", &accessor), const_varchar("")),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    for (a, candidate) in (1_i64..).zip(EMPTY_STRING_COLLISION_CANDIDATES) {
+        // The row of the candidate claimed to equal the empty string
+        let tampered_result = owned_table([
+            bigint("a", [0_i64, a, 6]),
+            varchar("c", ["", candidate, ""]),
+        ]);
+        assert!(VerifiableQueryResult {
+            result: tampered_result,
+            proof: verifiable_res.proof.clone(),
+        }
+        .verify(&ast, &accessor, &(), &[])
+        .is_err());
+
+        // The candidate passed off as the empty string
+        let tampered_result = owned_table([bigint("a", [0_i64, a, 6]), varchar("c", ["", "", ""])]);
+        assert!(VerifiableQueryResult {
+            result: tampered_result,
+            proof: verifiable_res.proof.clone(),
+        }
+        .verify(&ast, &accessor, &(), &[])
+        .is_err());
+
+        // The empty string passed off as the candidate
+        let tampered_result = owned_table([bigint("a", [0_i64, 6]), varchar("c", ["", candidate])]);
+        assert!(VerifiableQueryResult {
+            result: tampered_result,
+            proof: verifiable_res.proof.clone(),
+        }
+        .verify(&ast, &accessor, &(), &[])
+        .is_err());
+    }
+}
+
 fn test_random_tables_with_given_offset(offset: usize) {
     let dist = Uniform::new(-3, 4);
     let mut rng = StdRng::from_seed([0u8; 32]);
@@ -0,0 +1,259 @@
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, string::ToString, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable conditional expression, i.e. `IF(condition, then_expr, else_expr)`
+///
+/// For each row this selects the value of `then_expr` if `condition` is true
+/// and the value of `else_expr` otherwise.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IfExpr {
+    condition: Box<DynProofExpr>,
+    then_expr: Box<DynProofExpr>,
+    else_expr: Box<DynProofExpr>,
+}
+
+impl IfExpr {
+    /// Create a conditional expression
+    ///
+    /// The condition must be boolean and both branches must have the same type.
+    pub fn try_new(
+        condition: Box<DynProofExpr>,
+        then_expr: Box<DynProofExpr>,
+        else_expr: Box<DynProofExpr>,
+    ) -> AnalyzeResult<Self> {
+        let condition_type = condition.data_type();
+        if condition_type != ColumnType::Boolean {
+            return Err(AnalyzeError::InvalidDataType {
+                expr_type: condition_type,
+            });
+        }
+        let then_type = then_expr.data_type();
+        let else_type = else_expr.data_type();
+        if then_type != else_type {
+            return Err(AnalyzeError::DataTypeMismatch {
+                left_type: then_type.to_string(),
+                right_type: else_type.to_string(),
+            });
+        }
+        Ok(Self {
+            condition,
+            then_expr,
+            else_expr,
+        })
+    }
+
+    /// Get the condition expression
+    pub fn condition(&self) -> &DynProofExpr {
+        &self.condition
+    }
+
+    /// Get the expression selected when the condition is true
+    pub fn then_expr(&self) -> &DynProofExpr {
+        &self.then_expr
+    }
+
+    /// Get the expression selected when the condition is false
+    pub fn else_expr(&self) -> &DynProofExpr {
+        &self.else_expr
+    }
+}
+
+/// Select `then_values[i]` where `condition[i]` is true and `else_values[i]` otherwise
+fn select_slice<'a, T: Copy>(
+    alloc: &'a Bump,
+    condition: &[bool],
+    then_values: &[T],
+    else_values: &[T],
+) -> &'a [T] {
+    alloc.alloc_slice_fill_with(condition.len(), |i| {
+        if condition[i] {
+            then_values[i]
+        } else {
+            else_values[i]
+        }
+    })
+}
+
+/// Select the rows of `then_column` where `condition` is true and the rows of `else_column` otherwise
+///
+/// # Panics
+/// Panics if the two columns are not of the same type.
+fn select_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    condition: &[bool],
+    then_column: Column<'a, S>,
+    else_column: Column<'a, S>,
+) -> Column<'a, S> {
+    match (then_column, else_column) {
+        (Column::Boolean(a), Column::Boolean(b)) => {
+            Column::Boolean(select_slice(alloc, condition, a, b))
+        }
+        (Column::Uint8(a), Column::Uint8(b)) => Column::Uint8(select_slice(alloc, condition, a, b)),
+        (Column::TinyInt(a), Column::TinyInt(b)) => {
+            Column::TinyInt(select_slice(alloc, condition, a, b))
+        }
+        (Column::SmallInt(a), Column::SmallInt(b)) => {
+            Column::SmallInt(select_slice(alloc, condition, a, b))
+        }
+        (Column::Int(a), Column::Int(b)) => Column::Int(select_slice(alloc, condition, a, b)),
+        (Column::BigInt(a), Column::BigInt(b)) => {
+            Column::BigInt(select_slice(alloc, condition, a, b))
+        }
+        (Column::Int128(a), Column::Int128(b)) => {
+            Column::Int128(select_slice(alloc, condition, a, b))
+        }
+        (Column::Scalar(a), Column::Scalar(b)) => {
+            Column::Scalar(select_slice(alloc, condition, a, b))
+        }
+        (Column::Decimal75(precision, scale, a), Column::Decimal75(_, _, b)) => {
+            Column::Decimal75(precision, scale, select_slice(alloc, condition, a, b))
+        }
+        (Column::VarChar((a, a_scalars)), Column::VarChar((b, b_scalars))) => Column::VarChar((
+            select_slice(alloc, condition, a, b),
+            select_slice(alloc, condition, a_scalars, b_scalars),
+        )),
+        (Column::VarBinary((a, a_scalars)), Column::VarBinary((b, b_scalars))) => {
+            Column::VarBinary((
+                select_slice(alloc, condition, a, b),
+                select_slice(alloc, condition, a_scalars, b_scalars),
+            ))
+        }
+        (Column::TimestampTZ(unit, timezone, a), Column::TimestampTZ(_, _, b)) => {
+            Column::TimestampTZ(unit, timezone, select_slice(alloc, condition, a, b))
+        }
+        _ => panic!("Branches of an IF expression should have the same type"),
+    }
+}
+
+impl ProofExpr for IfExpr {
+    fn data_type(&self) -> ColumnType {
+        self.then_expr.data_type()
+    }
+
+    #[tracing::instrument(name = "IfExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let condition_column: Column<'a, S> =
+            self.condition.first_round_evaluate(alloc, table, params)?;
+        let condition = condition_column
+            .as_boolean()
+            .expect("condition is not boolean");
+        let then_column: Column<'a, S> =
+            self.then_expr.first_round_evaluate(alloc, table, params)?;
+        let else_column: Column<'a, S> =
+            self.else_expr.first_round_evaluate(alloc, table, params)?;
+        let res = select_column(alloc, condition, then_column, else_column);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(name = "IfExpr::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let condition_column: Column<'a, S> = self
+            .condition
+            .final_round_evaluate(builder, alloc, table, params)?;
+        let condition = condition_column
+            .as_boolean()
+            .expect("condition is not boolean");
+        let then_column: Column<'a, S> = self
+            .then_expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+        let else_column: Column<'a, S> = self
+            .else_expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+
+        // res = condition * then + (1 - condition) * else
+        let res = select_column(alloc, condition, then_column, else_column);
+        builder.produce_intermediate_mle(res);
+
+        // subpolynomial: res - else - condition * then + condition * else
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(res)]),
+                (-S::one(), vec![Box::new(else_column)]),
+                (
+                    -S::one(),
+                    vec![Box::new(condition_column), Box::new(then_column)],
+                ),
+                (
+                    S::one(),
+                    vec![Box::new(condition_column), Box::new(else_column)],
+                ),
+            ],
+        );
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let condition = self
+            .condition
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        let then_eval = self
+            .then_expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        let else_eval = self
+            .else_expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+
+        // res
+        let res = builder.try_consume_final_round_mle_evaluation()?;
+
+        // subpolynomial: res - else - condition * (then - else)
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            res - else_eval - condition * (then_eval - else_eval),
+            2,
+        )?;
+
+        Ok(res)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs(
+            [&*self.condition, &*self.then_expr, &*self.else_expr],
+            columns,
+        );
+    }
+}
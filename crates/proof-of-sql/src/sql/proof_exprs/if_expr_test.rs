@@ -0,0 +1,229 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor, TestAccessor,
+        },
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{if_expr::IfExpr, test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+use itertools::multizip;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+// select if(c, a, b) as r, if(c, d, e) as s from sxt.t
+#[test]
+fn we_can_prove_an_if_query_taking_both_branches() {
+    let data = owned_table([
+        boolean("c", [true, false, true, false]),
+        bigint("a", [1_i64, 2, 3, 4]),
+        bigint("b", [10_i64, 20, 30, 40]),
+        varchar("d", ["a", "b", "c", "d"]),
+        varchar("e", ["w", "x", "y", "z"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                if_expr(
+                    column(&t, "c", &accessor),
+                    column(&t, "a", &accessor),
+                    column(&t, "b", &accessor),
+                ),
+                "r",
+            ),
+            aliased_plan(
+                if_expr(
+                    column(&t, "c", &accessor),
+                    column(&t, "d", &accessor),
+                    column(&t, "e", &accessor),
+                ),
+                "s",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("r", [1_i64, 20, 3, 40]),
+        varchar("s", ["a", "x", "c", "z"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a, b from sxt.t where if(c, a, b) = 3
+#[test]
+fn we_can_prove_a_filter_on_an_if_expression() {
+    let data = owned_table([
+        boolean("c", [true, false, true, false]),
+        bigint("a", [3_i64, 3, 1, 4]),
+        bigint("b", [0_i64, 0, 3, 3]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(
+            if_expr(
+                column(&t, "c", &accessor),
+                column(&t, "a", &accessor),
+                column(&t, "b", &accessor),
+            ),
+            const_bigint(3),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [3_i64, 4]), bigint("b", [0_i64, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-3, 4);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            boolean("c", dist.sample_iter(&mut rng).take(n).map(|v| v > 0)),
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            bigint("b", dist.sample_iter(&mut rng).take(n)),
+        ]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![aliased_plan(
+                if_expr(
+                    column(&t, "c", &accessor),
+                    column(&t, "a", &accessor),
+                    column(&t, "b", &accessor),
+                ),
+                "r",
+            )],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+
+        // Calculate/compare expected result
+        let expected_r: Vec<_> = multizip((
+            data["c"].bool_iter(),
+            data["a"].i64_iter(),
+            data["b"].i64_iter(),
+        ))
+        .map(|(c, a, b)| if *c { *a } else { *b })
+        .collect();
+        let expected_result = owned_table([bigint("r", expected_r)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_if_expr_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_boolean("c", [true, false, false], &alloc),
+        borrowed_bigint("a", [1, 2, 3], &alloc),
+        borrowed_bigint("b", [4, 5, 6], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let expr: DynProofExpr = if_expr(
+        column(&t, "c", &accessor),
+        column(&t, "a", &accessor),
+        column(&t, "b", &accessor),
+    );
+    let res = expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(res, Column::BigInt(&[1, 5, 6]));
+    assert_eq!(expr.data_type(), ColumnType::BigInt);
+}
+
+#[test]
+fn we_cannot_create_an_if_expr_with_mismatched_branch_types() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_boolean("c", [true, false], &alloc),
+        borrowed_bigint("a", [1, 2], &alloc),
+        borrowed_varchar("b", ["x", "y"], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    let res = IfExpr::try_new(
+        Box::new(column(&t, "c", &accessor)),
+        Box::new(column(&t, "a", &accessor)),
+        Box::new(column(&t, "b", &accessor)),
+    );
+    assert!(matches!(res, Err(AnalyzeError::DataTypeMismatch { .. })));
+}
+
+#[test]
+fn we_cannot_create_an_if_expr_with_a_non_boolean_condition() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [1, 2], &alloc),
+        borrowed_bigint("b", [3, 4], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    let res = IfExpr::try_new(
+        Box::new(column(&t, "a", &accessor)),
+        Box::new(column(&t, "a", &accessor)),
+        Box::new(column(&t, "b", &accessor)),
+    );
+    assert!(matches!(
+        res,
+        Err(AnalyzeError::InvalidDataType {
+            expr_type: ColumnType::BigInt
+        })
+    ));
+}
@@ -0,0 +1,269 @@
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{try_equals_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        polynomial::MultilinearExtension,
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
+            VerificationBuilder,
+        },
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use bumpalo::Bump;
+use core::{fmt::Debug, iter};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// The largest number of values in the list of an [`InExpr`]
+///
+/// The identities of the proof have degree `2` more than the number of values, and the degree of
+/// the whole sumcheck is the largest degree of any identity.
+const MAX_IN_LIST_LEN: usize = 64;
+
+/// Provable AST expression for `expr IN (v_1, ..., v_k)` with a list of literals
+///
+/// With `p(x) = (x - v_1) * ... * (x - v_k)`, a value is in the list exactly when `p` is zero at
+/// it, so membership is proven like an equality with zero: the prover commits to the result
+/// `selection` and to `pseudo_inv`, the inverse of `p(expr)` where it is nonzero, and
+/// `selection * p(expr) = 0` and `(1 - selection) - p(expr) * pseudo_inv = 0` are identities. The
+/// polynomial is folded into its coefficients, so the proof has two columns and two identities
+/// whatever the length of the list, rather than an equality for each value. An empty list is
+/// false for every row and needs no proof.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InExpr {
+    expr: Box<DynProofExpr>,
+    values: Vec<LiteralValue>,
+}
+
+impl InExpr {
+    /// Create an expression for whether `expr` is one of `values`
+    ///
+    /// The values must all have the same type, which must be comparable with the type of `expr`,
+    /// and there may be at most [`MAX_IN_LIST_LEN`] of them.
+    pub fn try_new(expr: Box<DynProofExpr>, values: Vec<LiteralValue>) -> AnalyzeResult<Self> {
+        if values.len() > MAX_IN_LIST_LEN {
+            return Err(AnalyzeError::InListTooLong {
+                max_len: MAX_IN_LIST_LEN,
+            });
+        }
+        if let Some((first, rest)) = values.split_first() {
+            let values_type = first.column_type();
+            if let Some(other) = rest.iter().find(|value| value.column_type() != values_type) {
+                return Err(AnalyzeError::DataTypeMismatch {
+                    left_type: values_type.to_string(),
+                    right_type: other.column_type().to_string(),
+                });
+            }
+            let expr_type = expr.data_type();
+            try_equals_types(expr_type, values_type).map_err(|_| {
+                AnalyzeError::DataTypeMismatch {
+                    left_type: expr_type.to_string(),
+                    right_type: values_type.to_string(),
+                }
+            })?;
+        }
+        Ok(Self { expr, values })
+    }
+
+    /// Get the expression whose values are looked up in the list
+    pub fn expr(&self) -> &DynProofExpr {
+        &self.expr
+    }
+
+    /// Get the list of values
+    pub fn values(&self) -> &[LiteralValue] {
+        &self.values
+    }
+
+    /// The coefficients of `p(x) = (x - v_1) * ... * (x - v_k)`, from the constant one up
+    fn coefficients<S: Scalar>(&self) -> Vec<S> {
+        self.values
+            .iter()
+            .fold(vec![S::one()], |coefficients, value| {
+                // Multiply by `x - value`
+                let value: S = value.to_scalar();
+                iter::once(S::zero())
+                    .chain(coefficients.iter().copied())
+                    .zip(coefficients.iter().map(|&c| -c * value).chain([S::zero()]))
+                    .map(|(shifted, scaled)| shifted + scaled)
+                    .collect()
+            })
+    }
+
+    /// Evaluates `p` at each of `values`
+    fn evaluate_polynomial<'a, S: Scalar>(&self, alloc: &'a Bump, values: &[S]) -> &'a [S] {
+        let roots: Vec<S> = self.values.iter().map(LiteralValue::to_scalar).collect();
+        alloc.alloc_slice_fill_with(values.len(), |i| {
+            roots
+                .iter()
+                .fold(S::one(), |product, &root| product * (values[i] - root))
+        })
+    }
+}
+
+/// The terms `sign * coefficient * factor * values^i` of `sign * factor * p(values)`, given the
+/// coefficients of `p`
+fn polynomial_terms<'a, S: Scalar, T: Sync + Debug>(
+    coefficients: &'a [S],
+    sign: S,
+    factor: &'a [T],
+    values: &'a [S],
+) -> impl Iterator<Item = SumcheckSubpolynomialTerm<'a, S>>
+where
+    &'a T: Into<S>,
+{
+    coefficients
+        .iter()
+        .enumerate()
+        .map(move |(degree, &coefficient)| {
+            let powers =
+                iter::repeat_with(|| Box::new(values) as Box<dyn MultilinearExtension<S> + 'a>)
+                    .take(degree);
+            let multiplicands =
+                iter::once(Box::new(factor) as Box<dyn MultilinearExtension<S> + 'a>)
+                    .chain(powers)
+                    .collect();
+            (sign * coefficient, multiplicands)
+        })
+}
+
+impl ProofExpr for InExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "InExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self.expr.first_round_evaluate(alloc, table, params)?;
+        let polynomial = self.evaluate_polynomial(alloc, &column.to_scalar());
+        let res = Column::Boolean(
+            alloc.alloc_slice_fill_with(table.num_rows(), |i| polynomial[i] == S::zero()),
+        );
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(name = "InExpr::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self
+            .expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+        if self.values.is_empty() {
+            return Ok(Column::Boolean(
+                alloc.alloc_slice_fill_copy(table.num_rows(), false),
+            ));
+        }
+        let values: &'a [S] = alloc.alloc_slice_copy(&column.to_scalar());
+        let polynomial = self.evaluate_polynomial(alloc, values);
+
+        // pseudo_inv
+        let pseudo_inv: &'a [S] = {
+            let pseudo_inv = alloc.alloc_slice_copy(polynomial);
+            slice_ops::batch_inversion(pseudo_inv);
+            pseudo_inv
+        };
+        builder.produce_intermediate_mle(pseudo_inv);
+
+        // selection
+        let selection: &'a [bool] =
+            alloc.alloc_slice_fill_with(table.num_rows(), |i| polynomial[i] == S::zero());
+        builder.produce_intermediate_mle(selection);
+        let selection_not: &'a [bool] =
+            alloc.alloc_slice_fill_with(table.num_rows(), |i| !selection[i]);
+
+        let coefficients: &'a [S] = alloc.alloc_slice_copy(&self.coefficients());
+
+        // subpolynomial: selection * p(values)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            polynomial_terms(coefficients, S::one(), selection, values).collect(),
+        );
+
+        // subpolynomial: selection_not - p(values) * pseudo_inv
+        let mut terms: Vec<SumcheckSubpolynomialTerm<'a, S>> =
+            vec![(S::one(), vec![Box::new(selection_not)])];
+        terms.extend(polynomial_terms(
+            coefficients,
+            -S::one(),
+            pseudo_inv,
+            values,
+        ));
+        builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+
+        log::log_memory_usage("End");
+
+        Ok(Column::Boolean(selection))
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let eval = self
+            .expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        if self.values.is_empty() {
+            return Ok(S::zero());
+        }
+        let polynomial_eval = self
+            .coefficients::<S>()
+            .into_iter()
+            .rev()
+            .fold(S::zero(), |acc, coefficient| acc * eval + coefficient);
+        let degree = self.values.len() + 1;
+
+        // consume mle evaluations
+        let pseudo_inv_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let selection_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let selection_not_eval = chi_eval - selection_eval;
+
+        // subpolynomial: selection * p(values)
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            selection_eval * polynomial_eval,
+            degree,
+        )?;
+
+        // subpolynomial: selection_not - p(values) * pseudo_inv
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            selection_not_eval - polynomial_eval * pseudo_inv_eval,
+            degree,
+        )?;
+
+        Ok(selection_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs([&*self.expr], columns);
+    }
+}
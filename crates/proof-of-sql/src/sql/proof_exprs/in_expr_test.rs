@@ -0,0 +1,366 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnRef, ColumnType, LiteralValue,
+            OwnedTable, OwnedTableTestAccessor, Table, TableRef,
+        },
+        map::indexmap,
+        math::{decimal::Precision, i256::I256},
+        polynomial::MultilinearExtension,
+        scalar::test_scalar::TestScalar,
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{
+            exercise_verification, mock_verification_builder::MockVerificationBuilder,
+            FinalRoundBuilder, VerifiableQueryResult,
+        },
+        proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr, InExpr, ProofExpr},
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+use sqlparser::ast::Ident;
+use std::collections::VecDeque;
+
+fn bigints(values: impl IntoIterator<Item = i64>) -> Vec<LiteralValue> {
+    values.into_iter().map(LiteralValue::BigInt).collect()
+}
+
+fn varchars<'a>(values: impl IntoIterator<Item = &'a str>) -> Vec<LiteralValue> {
+    values
+        .into_iter()
+        .map(|value| LiteralValue::VarChar(value.into()))
+        .collect()
+}
+
+fn decimals(precision: u8, scale: i8, values: impl IntoIterator<Item = i32>) -> Vec<LiteralValue> {
+    values
+        .into_iter()
+        .map(|value| {
+            LiteralValue::Decimal75(Precision::new(precision).unwrap(), scale, I256::from(value))
+        })
+        .collect()
+}
+
+/// Proves `select a from sxt.t where a in (values)`, returning the verified result
+fn prove_and_verify_filter(
+    data: OwnedTable<Curve25519Scalar>,
+    values: Vec<LiteralValue>,
+) -> OwnedTable<Curve25519Scalar> {
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        in_list(column(&t, "a", &accessor), values),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table
+}
+
+// select a from sxt.t where a in (1, 3, 5)
+#[test]
+fn we_can_prove_a_bigint_in_list() {
+    let res = prove_and_verify_filter(
+        owned_table([bigint("a", [1_i64, 2, 3, 4, 5, 6, 1])]),
+        bigints([1, 3, 5]),
+    );
+    assert_eq!(res, owned_table([bigint("a", [1_i64, 3, 5, 1])]));
+}
+
+// select a from sxt.t where a in ('x', 'zz', '')
+#[test]
+fn we_can_prove_a_varchar_in_list() {
+    let res = prove_and_verify_filter(
+        owned_table([varchar("a", ["x", "y", "zz", "", "xx", "x"])]),
+        varchars(["x", "zz", ""]),
+    );
+    assert_eq!(res, owned_table([varchar("a", ["x", "zz", "", "x"])]));
+}
+
+// select a from sxt.t where a in (1.50, -0.25, 10.00)
+#[test]
+fn we_can_prove_a_decimal_in_list() {
+    let res = prove_and_verify_filter(
+        owned_table([decimal75("a", 10, 2, [150, 25, -25, 1000, 0])]),
+        decimals(5, 2, [150, -25, 1000]),
+    );
+    assert_eq!(res, owned_table([decimal75("a", 10, 2, [150, -25, 1000])]));
+}
+
+// select a from sxt.t where a in (7, 7, 7)
+#[test]
+fn we_can_prove_an_in_list_with_repeated_values() {
+    let res = prove_and_verify_filter(
+        owned_table([bigint("a", [7_i64, 8, 7])]),
+        bigints([7, 7, 7]),
+    );
+    assert_eq!(res, owned_table([bigint("a", [7_i64, 7])]));
+}
+
+// select a from sxt.t where a in (1, 2)
+#[test]
+fn we_can_prove_an_in_list_on_an_empty_table() {
+    let res = prove_and_verify_filter(owned_table([bigint("a", [0_i64; 0])]), bigints([1, 2]));
+    assert_eq!(res, owned_table([bigint("a", [0_i64; 0])]));
+}
+
+// select a in () as is_in from sxt.t
+#[test]
+fn we_can_prove_an_empty_in_list_is_false_for_every_row() {
+    let data = owned_table([bigint("a", [0_i64, 1, 2])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            in_list(column(&t, "a", &accessor), []),
+            "is_in",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    assert_eq!(res, owned_table([boolean("is_in", [false; 3])]));
+}
+
+// select a in (2, 4) as is_in from sxt.t
+#[test]
+fn we_cannot_verify_a_result_with_a_flipped_membership_bit() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3, 4])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            in_list(column(&t, "a", &accessor), bigints([2, 4])),
+            "is_in",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    assert_eq!(
+        verifiable_res
+            .clone()
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table,
+        owned_table([boolean("is_in", [false, true, false, true])])
+    );
+    for flipped in [[true, true, false, true], [false, false, false, true]] {
+        assert!(VerifiableQueryResult {
+            result: owned_table([boolean("is_in", flipped)]),
+            proof: verifiable_res.proof.clone(),
+        }
+        .verify(&ast, &accessor, &(), &[])
+        .is_err());
+    }
+}
+
+/// Proves `a in (2, 4)` for `a = [1, 2, 3, 4]` honestly, then verifies each row with the
+/// committed membership bit of `flipped_row` flipped, returning the identity results of each row
+fn identity_results_with_a_flipped_membership_bit(flipped_row: usize) -> Vec<Vec<bool>> {
+    let alloc = Bump::new();
+    let a = [1_i64, 2, 3, 4];
+    let table = table([borrowed_bigint("a", a, &alloc)]);
+    let column_ref = ColumnRef::new(
+        "sxt.t".parse().unwrap(),
+        Ident::new("a"),
+        ColumnType::BigInt,
+    );
+    let in_expr = InExpr::try_new(
+        Box::new(DynProofExpr::Column(ColumnExpr::new(column_ref.clone()))),
+        bigints([2, 4]),
+    )
+    .unwrap();
+
+    let mut final_round_builder: FinalRoundBuilder<'_, TestScalar> =
+        FinalRoundBuilder::new(2, VecDeque::new());
+    let res = in_expr
+        .final_round_evaluate(&mut final_round_builder, &alloc, &table, &[])
+        .unwrap();
+    assert_eq!(res, Column::Boolean(&[false, true, false, true]));
+
+    let evaluation_points: Vec<Vec<TestScalar>> = (0..a.len())
+        .map(|i| {
+            (0..a.len())
+                .map(|j| TestScalar::from(u64::from(i == j)))
+                .collect()
+        })
+        .collect();
+    // The committed columns are the pseudo-inverse and the membership bits
+    let final_round_mles = evaluation_points
+        .iter()
+        .enumerate()
+        .map(|(row, evaluation_point)| {
+            let mut mles = final_round_builder.evaluate_pcs_proof_mles(evaluation_point);
+            if row == flipped_row {
+                mles[1] = TestScalar::from(1) - mles[1];
+            }
+            mles
+        })
+        .collect();
+    let mut verification_builder = MockVerificationBuilder::new(
+        Vec::new(),
+        4,
+        Vec::new(),
+        final_round_mles,
+        Vec::new(),
+        Vec::new(),
+    );
+    for evaluation_point in &evaluation_points {
+        let chi_eval = (&[1, 1, 1, 1]).inner_product(evaluation_point);
+        let accessor = indexmap! {
+            column_ref.column_id() => (&a).inner_product(evaluation_point)
+        };
+        in_expr
+            .verifier_evaluate(&mut verification_builder, &accessor, chi_eval, &[])
+            .unwrap();
+        verification_builder.increment_row_index();
+    }
+    verification_builder.get_identity_results()
+}
+
+#[test]
+fn we_can_verify_honest_membership_bits_row_by_row() {
+    let results = identity_results_with_a_flipped_membership_bit(usize::MAX);
+    assert_eq!(results, vec![vec![true, true]; 4]);
+}
+
+#[test]
+fn we_cannot_verify_a_dishonest_prover_flipping_one_membership_bit() {
+    // a value in the list claimed not to be, and a value not in the list claimed to be
+    for flipped_row in [1, 2] {
+        let results = identity_results_with_a_flipped_membership_bit(flipped_row);
+        for (row, result) in results.iter().enumerate() {
+            assert_eq!(result.iter().all(|&holds| holds), row != flipped_row);
+        }
+    }
+}
+
+#[test]
+fn we_can_prove_an_in_list_with_a_smaller_proof_than_a_chain_of_equalities() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3, 4, 5, 6, 7, 8])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let values = [2_i64, 3, 5, 7, 11, 13];
+    let proof_bytes = |where_clause: DynProofExpr| {
+        let ast = filter(cols_expr_plan(&t, &["a"], &accessor), tab(&t), where_clause);
+        let verifiable_res =
+            VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &(), &[]).unwrap();
+        let proof_bytes = verifiable_res.encoded_size_hint().proof_bytes;
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+        assert_eq!(res, owned_table([bigint("a", [2_i64, 3, 5, 7])]));
+        proof_bytes
+    };
+    let in_list_bytes = proof_bytes(in_list(column(&t, "a", &accessor), bigints(values)));
+    let equalities_bytes = proof_bytes(
+        values
+            .into_iter()
+            .map(|value| equal(column(&t, "a", &accessor), const_bigint(value)))
+            .reduce(or)
+            .unwrap(),
+    );
+    assert!(in_list_bytes < equalities_bytes);
+}
+
+#[test]
+fn we_can_compute_an_in_list_without_a_proof() {
+    let alloc = Bump::new();
+    let data: Table<'_, TestScalar> = table([
+        borrowed_bigint("a", [1_i64, 2, 3], &alloc),
+        borrowed_varchar("b", ["x", "y", "z"], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [0_i64; 0]), varchar("b", [""; 0])]),
+        0,
+        (),
+    );
+    let in_expr = in_list(column(&t, "b", &accessor), varchars(["z", "x"]));
+    let res = in_expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(res, Column::Boolean(&[true, false, true]));
+    let empty_expr = in_list(column(&t, "a", &accessor), []);
+    let res = empty_expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(res, Column::Boolean(&[false, false, false]));
+}
+
+#[test]
+fn we_cannot_create_an_in_list_with_mixed_value_types() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [0_i64; 0])]),
+        0,
+        (),
+    );
+    let values = vec![LiteralValue::BigInt(1), LiteralValue::VarChar("1".into())];
+    assert_eq!(
+        DynProofExpr::try_new_in(column(&t, "a", &accessor), values),
+        Err(AnalyzeError::DataTypeMismatch {
+            left_type: "BIGINT".to_string(),
+            right_type: "VARCHAR".to_string(),
+        })
+    );
+    let values = vec![LiteralValue::BigInt(1), LiteralValue::Int(2)];
+    assert!(matches!(
+        DynProofExpr::try_new_in(column(&t, "a", &accessor), values),
+        Err(AnalyzeError::DataTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_create_an_in_list_of_values_not_comparable_with_the_expression() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [0_i64; 0]), decimal75("d", 10, 2, [0; 0])]),
+        0,
+        (),
+    );
+    assert!(matches!(
+        DynProofExpr::try_new_in(column(&t, "a", &accessor), varchars(["1"])),
+        Err(AnalyzeError::DataTypeMismatch { .. })
+    ));
+    // Decimals are only compared at the same scale
+    assert!(matches!(
+        DynProofExpr::try_new_in(column(&t, "d", &accessor), decimals(10, 1, [15])),
+        Err(AnalyzeError::DataTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_create_an_in_list_with_too_many_values() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [0_i64; 0])]),
+        0,
+        (),
+    );
+    assert!(DynProofExpr::try_new_in(column(&t, "a", &accessor), bigints(0..64)).is_ok());
+    assert_eq!(
+        DynProofExpr::try_new_in(column(&t, "a", &accessor), bigints(0..65)),
+        Err(AnalyzeError::InListTooLong { max_len: 64 })
+    );
+}
@@ -1,4 +1,4 @@
-use super::{add_subtract_columns, DynProofExpr, ProofExpr};
+use super::{add_subtract_columns, get_column_references_of_exprs, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{try_inequality_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
@@ -154,7 +154,6 @@ impl ProofExpr for InequalityExpr {
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.lhs.get_column_references(columns);
-        self.rhs.get_column_references(columns);
+        get_column_references_of_exprs([&*self.lhs, &*self.rhs], columns);
     }
 }
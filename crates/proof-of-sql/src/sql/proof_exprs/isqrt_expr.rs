@@ -0,0 +1,231 @@
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_sign, verifier_evaluate_sign},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable integer square root of an integer expression, i.e. `ISQRT(x) = FLOOR(SQRT(x))`
+///
+/// The result has the type of `expr`. Rows with negative values can't be proven.
+///
+/// The prover commits to the roots `out` and their squares `out_sq`. The roots are range checked
+/// so that `out * out` can't wrap around, and `out_sq - out * out = 0` is an identity. Then
+/// `x - out_sq >= 0` and `out_sq + 2 * out - x >= 0` show `out^2 <= x < (out + 1)^2`, which only
+/// has a solution with `out = FLOOR(SQRT(x))` when `x` is non-negative.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ISqrtExpr {
+    expr: Box<DynProofExpr>,
+}
+
+impl ISqrtExpr {
+    /// Create an expression taking the integer square root of `expr`
+    ///
+    /// `expr` must be an integer expression.
+    pub fn try_new(expr: Box<DynProofExpr>) -> AnalyzeResult<Self> {
+        let expr_type = expr.data_type();
+        if !expr_type.is_integer() {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        Ok(Self { expr })
+    }
+
+    /// Get the expression whose integer square root is taken
+    pub fn expr(&self) -> &DynProofExpr {
+        &self.expr
+    }
+
+    /// The number of bits the roots are range checked to
+    ///
+    /// With the values fitting in `n` bits, the roots are below `2^ceil(n/2)`. Allowing two more
+    /// bits bounds `out^2` by `2^(n+3)`, far below the modulus of the scalar field.
+    fn root_bits(&self) -> u8 {
+        let value_bits = self.expr.data_type().bit_size();
+        u8::try_from(value_bits.div_ceil(2) + 2).expect("Integer types have at most 128 bits")
+    }
+}
+
+/// The integer square root of `value`, or `None` if `value` is negative
+fn isqrt(value: i128) -> Option<i128> {
+    u128::try_from(value)
+        .ok()
+        .map(|value| i128::try_from(value.isqrt()).expect("Square roots of i128 fit in i128"))
+}
+
+/// Take the integer square roots of `values`, with zero for negative values
+fn isqrt_slice<'a, T: Copy + Into<i128>>(
+    alloc: &'a Bump,
+    values: &[T],
+    from_i128: impl Fn(i128) -> T,
+) -> &'a [T] {
+    alloc.alloc_slice_fill_iter(
+        values
+            .iter()
+            .map(|&value| from_i128(isqrt(value.into()).unwrap_or(0))),
+    )
+}
+
+/// Take the integer square roots of the values of an integer column
+///
+/// Negative values get a root of zero, which fails the checks of the proof.
+///
+/// # Panics
+/// Panics if the column is not an integer column.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn isqrt_column<'a, S: Scalar>(alloc: &'a Bump, column: Column<'a, S>) -> Column<'a, S> {
+    match column {
+        Column::Uint8(values) => Column::Uint8(isqrt_slice(alloc, values, |v| v as u8)),
+        Column::TinyInt(values) => Column::TinyInt(isqrt_slice(alloc, values, |v| v as i8)),
+        Column::SmallInt(values) => Column::SmallInt(isqrt_slice(alloc, values, |v| v as i16)),
+        Column::Int(values) => Column::Int(isqrt_slice(alloc, values, |v| v as i32)),
+        Column::BigInt(values) => Column::BigInt(isqrt_slice(alloc, values, |v| v as i64)),
+        Column::Int128(values) => Column::Int128(isqrt_slice(alloc, values, |v| v)),
+        _ => panic!("Only integer columns have integer square roots"),
+    }
+}
+
+impl ProofExpr for ISqrtExpr {
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[tracing::instrument(name = "ISqrtExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self.expr.first_round_evaluate(alloc, table, params)?;
+        let res = isqrt_column(alloc, column);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(name = "ISqrtExpr::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self
+            .expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+        let res = isqrt_column(alloc, column);
+        let values: &'a [S] = alloc.alloc_slice_fill_iter(column.to_scalar());
+        let roots: &'a [S] = alloc.alloc_slice_fill_iter(res.to_scalar());
+        let squares: &'a [S] = alloc.alloc_slice_fill_iter(roots.iter().map(|&root| root * root));
+        builder.produce_intermediate_mle(roots);
+        builder.produce_intermediate_mle(squares);
+
+        // out_sq - out * out = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(squares)]),
+                (-S::one(), vec![Box::new(roots), Box::new(roots)]),
+            ],
+        );
+        // range check of the roots
+        final_round_evaluate_sign(builder, alloc, roots);
+
+        // x - out_sq >= 0 and out_sq + 2 * out - x >= 0
+        let lower_bounds: &'a [S] = alloc.alloc_slice_fill_iter(
+            values
+                .iter()
+                .zip(squares)
+                .map(|(&value, &square)| value - square),
+        );
+        let upper_bounds: &'a [S] = alloc.alloc_slice_fill_iter(
+            values
+                .iter()
+                .zip(roots.iter().zip(squares))
+                .map(|(&value, (&root, &square))| square + root * S::TWO - value),
+        );
+        final_round_evaluate_sign(builder, alloc, lower_bounds);
+        final_round_evaluate_sign(builder, alloc, upper_bounds);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let value_eval = self
+            .expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        let root_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let square_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // out_sq - out * out = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            square_eval - root_eval * root_eval,
+            2,
+        )?;
+        // range check of the roots
+        verifier_evaluate_sign(builder, root_eval, chi_eval, Some(self.root_bits()))?;
+
+        let lower_bound_eval = value_eval - square_eval;
+        let upper_bound_eval = square_eval + root_eval * S::TWO - value_eval;
+        for bound_eval in [lower_bound_eval, upper_bound_eval] {
+            if verifier_evaluate_sign(builder, bound_eval, chi_eval, None)? != S::ZERO {
+                return Err(ProofError::VerificationError {
+                    error: "root is not the integer square root of a non-negative value",
+                });
+            }
+        }
+
+        Ok(root_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs([&*self.expr], columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::isqrt;
+
+    #[test]
+    fn we_can_compute_integer_square_roots() {
+        assert_eq!(isqrt(0), Some(0));
+        assert_eq!(isqrt(1), Some(1));
+        assert_eq!(isqrt(3), Some(1));
+        assert_eq!(isqrt(4), Some(2));
+        assert_eq!(isqrt(99), Some(9));
+        assert_eq!(isqrt(100), Some(10));
+        assert_eq!(isqrt(i128::from(i64::MAX)), Some(3_037_000_499));
+        assert_eq!(isqrt(i128::MAX), Some(13_043_817_825_332_782_212));
+        assert_eq!(isqrt(-1), None);
+        assert_eq!(isqrt(i128::MIN), None);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor, TestAccessor,
+        },
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{isqrt_expr::ISqrtExpr, test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+/// The largest `r` with `r * r <= value`, found by counting up
+fn reference_isqrt(value: i64) -> i64 {
+    (0..)
+        .take_while(|root| root * root <= value)
+        .last()
+        .unwrap()
+}
+
+// select isqrt(a) as r from sxt.t
+#[test]
+fn we_can_prove_the_integer_square_roots_of_a_column() {
+    let data = owned_table([bigint("a", [0_i64, 1, 2, 3, 4, 8, 9, 10, 99, 100, 101])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(isqrt(column(&t, "a", &accessor)), "r")],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("r", [0_i64, 1, 1, 1, 2, 2, 3, 3, 9, 10, 10])]);
+    assert_eq!(res, expected_res);
+}
+
+// select isqrt(a) as a, isqrt(b) as b, isqrt(c) as c, isqrt(d) as d, isqrt(e) as e,
+//   isqrt(f) as f from sxt.t
+#[test]
+fn we_can_prove_the_integer_square_roots_of_columns_of_every_integer_type() {
+    let data = owned_table([
+        tinyint("a", [0_i8, 15, 16, i8::MAX]),
+        uint8("b", [0_u8, 24, 25, u8::MAX]),
+        smallint("c", [0_i16, 1, 10_000, i16::MAX]),
+        int("d", [0_i32, 2, 1_000_000, i32::MAX]),
+        bigint("e", [0_i64, 3, 1_000_000_000_000, i64::MAX]),
+        int128("f", [0_i128, 4, 1 << 100, i128::MAX]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        ["a", "b", "c", "d", "e", "f"]
+            .into_iter()
+            .map(|id| aliased_plan(isqrt(column(&t, id, &accessor)), id))
+            .collect(),
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        tinyint("a", [0_i8, 3, 4, 11]),
+        uint8("b", [0_u8, 4, 5, 15]),
+        smallint("c", [0_i16, 1, 100, 181]),
+        int("d", [0_i32, 1, 1000, 46_340]),
+        bigint("e", [0_i64, 1, 1_000_000, 3_037_000_499]),
+        int128("f", [0_i128, 2, 1 << 50, 13_043_817_825_332_782_212]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where isqrt(a) = 3
+#[test]
+fn we_can_prove_a_filter_on_an_integer_square_root() {
+    let data = owned_table([bigint("a", [8_i64, 9, 12, 15, 16, 3])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(isqrt(column(&t, "a", &accessor)), const_bigint(3)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [9_i64, 12, 15])]);
+    assert_eq!(res, expected_res);
+}
+
+// select isqrt(a) as r from sxt.t where a has a negative value
+#[test]
+fn we_cannot_verify_the_integer_square_root_of_a_negative_value() {
+    for value in [-1_i64, -4, i64::MIN] {
+        let data = owned_table([bigint("a", [4_i64, value, 0])]);
+        let t = TableRef::new("sxt", "t");
+        let accessor =
+            OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+        let ast = filter(
+            vec![aliased_plan(isqrt(column(&t, "a", &accessor)), "r")],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        assert!(verifiable_res.verify(&ast, &accessor, &(), &[]).is_err());
+    }
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(0, 10_000);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([bigint("a", dist.sample_iter(&mut rng).take(n))]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![aliased_plan(isqrt(column(&t, "a", &accessor)), "r")],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+
+        // Calculate/compare expected result
+        let expected_r: Vec<_> = data["a"].i64_iter().map(|&a| reference_isqrt(a)).collect();
+        let expected_result = owned_table([bigint("r", expected_r)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_isqrt_expr_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_int("a", [0, 24, 25, 26], &alloc)]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let expr: DynProofExpr = isqrt(column(&t, "a", &accessor));
+    let res = expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(res, Column::Int(&[0, 4, 5, 5]));
+    assert_eq!(expr.data_type(), ColumnType::Int);
+}
+
+#[test]
+fn we_cannot_take_the_integer_square_root_of_an_expression_which_is_not_an_integer() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_varchar("a", ["x", "y"], &alloc),
+        borrowed_decimal75("b", 5, 1, [10, 20], &alloc),
+        borrowed_boolean("c", [true, false], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for column_id in ["a", "b", "c"] {
+        let res = ISqrtExpr::try_new(Box::new(column(&t, column_id, &accessor)));
+        assert!(matches!(res, Err(AnalyzeError::InvalidDataType { .. })));
+    }
+}
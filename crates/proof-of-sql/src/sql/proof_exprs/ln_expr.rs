@@ -0,0 +1,514 @@
+use super::{
+    get_column_references_of_exprs, ColumnExpr, DecimalRescaleExpr, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table, TableOptions, TableRef},
+        map::{IndexMap, IndexSet},
+        math::decimal::Precision,
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_sign, verifier_evaluate_sign},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// The number of bits of the exponent `e = k + MIN_EXPONENT`
+const EXPONENT_BITS: usize = 6;
+/// The negated smallest power of two `k` in the domain
+const MIN_EXPONENT: u8 = 20;
+/// The scale of the reduced argument and of the intermediate values of the polynomial
+const WORKING_SCALE: i8 = 18;
+/// The precision of the intermediate values of the polynomial
+const WORKING_PRECISION: u8 = 19;
+/// The precision of the result
+const RESULT_PRECISION: u8 = 8;
+/// The scale of the result
+const RESULT_SCALE: i8 = 6;
+/// The largest number of integer digits of the argument, i.e. precision minus scale
+///
+/// This keeps `x * 2^20 * 10^18` below half the modulus of the scalar field, so that the
+/// reduction can't wrap around.
+const MAX_INTEGER_DIGITS: i16 = 51;
+/// `ln(2)` rounded to 18 decimal places
+const LN_2: i64 = 693_147_180_559_945_309;
+/// The Taylor coefficients of `ln` around `1.5` rounded to 18 decimal places, i.e. `ln(1.5)`
+/// followed by `(-1)^(i + 1) / (i * 1.5^i)` for `i` from 1 to 12
+const TAYLOR_COEFFICIENTS: [i64; 13] = [
+    405_465_108_108_164_382,
+    666_666_666_666_666_667,
+    -222_222_222_222_222_222,
+    98_765_432_098_765_432,
+    -49_382_716_049_382_716,
+    26_337_448_559_670_782,
+    -14_631_915_866_483_768,
+    8_361_094_780_847_867,
+    -4_877_305_288_827_923,
+    2_890_254_985_972_102,
+    -1_734_152_991_583_261,
+    1_051_001_813_080_764,
+    -642_278_885_771_578,
+];
+
+/// Provable natural logarithm of a numeric expression, approximated over a bounded domain
+///
+/// The domain is `2^-20 <= x < 2^44`, i.e. roughly `9.5e-7 <= x < 1.7e13`. Rows outside of it,
+/// including every `x <= 0`, can't be proven. The result is a `DECIMAL(8, 6)` which differs from
+/// `ln(x)` by less than `6e-7`.
+///
+/// The argument is reduced to `x = v * 2^k` with `1 <= v < 2`. The prover commits to the bits of
+/// `e = k + 20`, the power `2^e` through its partial products, the mantissa `V = v * 10^18` and
+/// the remainder `r` of `x * 2^20 * 10^18 = V * 2^e + r`. Range checks show `0 <= r < 2^e` and
+/// `10^18 <= V < 2 * 10^18`, which only have a solution when `x` is in the domain.
+///
+/// Then `ln(x) = k * ln(2) + ln(v)`, where `ln(v)` is the Taylor polynomial of degree 12 around
+/// `1.5` in `t = v - 1.5`. It is evaluated with Horner's scheme from provable expressions,
+/// rounding to 18 decimal places after each step. Since `|t| <= 0.5`, the truncation error is
+/// below `(1/3)^13 / (13 * 2/3) < 7.3e-8`, the rounding of the coefficients, of `ln(2)` and of
+/// the intermediate values adds less than `1e-15`, and the rounding of the result less than
+/// `5e-7`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LnExpr {
+    expr: Box<DynProofExpr>,
+}
+
+impl LnExpr {
+    /// Create an expression approximating the natural logarithm of `expr`
+    ///
+    /// `expr` must be an integer or decimal expression with a scale of at most 18 and at most
+    /// 51 digits before the decimal point.
+    pub fn try_new(expr: Box<DynProofExpr>) -> AnalyzeResult<Self> {
+        let expr_type = expr.data_type();
+        let (Some(precision), Some(scale)) = (expr_type.precision_value(), expr_type.scale())
+        else {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        };
+        if !(expr_type.is_integer() || matches!(expr_type, ColumnType::Decimal75(_, _)))
+            || scale > WORKING_SCALE
+            || i16::from(precision) - i16::from(scale) > MAX_INTEGER_DIGITS
+        {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        Ok(Self { expr })
+    }
+
+    /// Get the expression whose logarithm is taken
+    pub fn expr(&self) -> &DynProofExpr {
+        &self.expr
+    }
+
+    /// The factor `2^20 * 10^(18 - scale)` turning the argument into `x * 2^20 * 10^18`
+    fn reduction_factor(&self) -> BigInt {
+        let scale = self.expr.data_type().scale().unwrap_or(0);
+        let scale_diff = u32::try_from(i16::from(WORKING_SCALE) - i16::from(scale))
+            .expect("The scale is checked to be at most the working scale");
+        (BigInt::from(1u8) << MIN_EXPONENT) * BigInt::from(10u8).pow(scale_diff)
+    }
+
+    /// The polynomial evaluating `k * ln(2) + ln(v)` from the reduced columns `t` and `k`
+    fn approximation() -> DynProofExpr {
+        let working_type = decimal_type(WORKING_PRECISION, WORKING_SCALE);
+        let t = reduced_column(
+            "t",
+            decimal_type(WORKING_SCALE.unsigned_abs(), WORKING_SCALE),
+        );
+        let k = reduced_column("k", decimal_type(2, 0));
+        let (constant, coefficients) = TAYLOR_COEFFICIENTS
+            .split_first()
+            .expect("There are Taylor coefficients");
+        let polynomial = coefficients
+            .iter()
+            .rev()
+            .map(|&coefficient| working_literal(coefficient))
+            .reduce(|accumulator, coefficient| {
+                bounded_rescale(
+                    add(multiply(accumulator, t.clone()), coefficient),
+                    working_type,
+                )
+            })
+            .expect("There are Taylor coefficients");
+        let ln_v = bounded_rescale(
+            add(multiply(polynomial, t), working_literal(*constant)),
+            working_type,
+        );
+        bounded_rescale(
+            add(multiply(k, working_literal(LN_2)), ln_v),
+            decimal_type(RESULT_PRECISION, RESULT_SCALE),
+        )
+    }
+}
+
+fn decimal_type(precision: u8, scale: i8) -> ColumnType {
+    ColumnType::Decimal75(
+        Precision::new(precision).expect("The precisions used are valid"),
+        scale,
+    )
+}
+
+fn working_literal(value: i64) -> DynProofExpr {
+    DynProofExpr::new_literal(LiteralValue::Decimal75(
+        Precision::new(WORKING_PRECISION).expect("The working precision is valid"),
+        WORKING_SCALE,
+        i128::from(value).into(),
+    ))
+}
+
+fn reduced_column(name: &str, column_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+        TableRef::from_names(None, "ln"),
+        name.into(),
+        column_type,
+    )))
+}
+
+fn add(lhs: DynProofExpr, rhs: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_add(lhs, rhs).expect("Decimals can be added")
+}
+
+fn multiply(lhs: DynProofExpr, rhs: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_multiply(lhs, rhs).expect("Decimals can be multiplied")
+}
+
+fn bounded_rescale(expr: DynProofExpr, to_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::DecimalRescale(DecimalRescaleExpr::new_bounded(
+        Box::new(expr as &[_]),
+        to_type,
+    ))
+}
+
+/// The witness of the range reduction `x * 2^20 * 10^18 = V * 2^e + r` of one column
+struct Reduction<'a, S: Scalar> {
+    /// The bits of `e`
+    bits: Vec<&'a [bool]>,
+    /// The partial products `prod_(j <= i) 2^(2^j * b_j)` for `i` from 1, the last being `2^e`
+    powers: Vec<&'a [S]>,
+    mantissas: &'a [S],
+    remainders: &'a [S],
+    /// `x * 2^20 * 10^18`
+    scaled_values: &'a [S],
+    /// The reduced columns `t = v - 1.5` and `k`
+    table: Table<'a, S>,
+}
+
+/// Reduce every value of `column`, multiplied by `factor`, to `V * 2^e + r` with
+/// `10^18 <= V < 2 * 10^18` and `0 <= r < 2^e`
+///
+/// Values outside of the domain get a witness which fails the range checks.
+fn reduce<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: &Column<'a, S>,
+    factor: &BigInt,
+) -> Reduction<'a, S> {
+    let one = BigInt::from(10u8).pow(u32::from(WORKING_SCALE.unsigned_abs()));
+    let two = &one * 2u8;
+    let zero = BigInt::from(0u8);
+    let scaled_values: Vec<BigInt> = column
+        .to_scalar()
+        .into_iter()
+        .map(|value| Into::<BigInt>::into(value) * factor)
+        .collect();
+    let (exponents, mantissas): (Vec<usize>, Vec<BigInt>) = scaled_values
+        .iter()
+        .map(|value| {
+            let value = value.max(&zero);
+            let exponent = (0..1 << EXPONENT_BITS)
+                .find(|&exponent| (value >> exponent) < two)
+                .unwrap_or((1 << EXPONENT_BITS) - 1);
+            (exponent, value >> exponent)
+        })
+        .unzip();
+    let to_scalar =
+        |value: BigInt| S::try_from(value).expect("Reduced values are bounded by the input type");
+    let remainders: &'a [S] = alloc.alloc_slice_fill_iter(
+        scaled_values
+            .iter()
+            .zip(&exponents)
+            .zip(&mantissas)
+            .map(|((value, &exponent), mantissa)| to_scalar(value - (mantissa << exponent))),
+    );
+    let bits = (0..EXPONENT_BITS)
+        .map(|j| -> &'a [bool] {
+            alloc.alloc_slice_fill_iter(exponents.iter().map(|e| (e >> j) & 1 == 1))
+        })
+        .collect();
+    let powers = (1..EXPONENT_BITS)
+        .map(|i| -> &'a [S] {
+            alloc.alloc_slice_fill_iter(
+                exponents
+                    .iter()
+                    .map(|&e| to_scalar(BigInt::from(1u8) << (e & ((2 << i) - 1)))),
+            )
+        })
+        .collect();
+    let t_values: &'a [S] = alloc.alloc_slice_fill_iter(
+        mantissas
+            .iter()
+            .map(|mantissa| to_scalar(mantissa - &one - &one / 2u8)),
+    );
+    let k_values: &'a [S] = alloc.alloc_slice_fill_iter(
+        exponents
+            .iter()
+            .map(|&e| to_scalar(BigInt::from(e) - MIN_EXPONENT)),
+    );
+    let table = Table::try_from_iter_with_options(
+        [
+            (
+                Ident::new("t"),
+                Column::Decimal75(
+                    Precision::new(WORKING_SCALE.unsigned_abs()).expect("The precision is valid"),
+                    WORKING_SCALE,
+                    t_values,
+                ),
+            ),
+            (
+                Ident::new("k"),
+                Column::Decimal75(
+                    Precision::new(2).expect("The precision is valid"),
+                    0,
+                    k_values,
+                ),
+            ),
+        ],
+        TableOptions::new(Some(column.len())),
+    )
+    .expect("The reduced columns have the length of the input");
+    Reduction {
+        bits,
+        powers,
+        mantissas: alloc.alloc_slice_fill_iter(mantissas.into_iter().map(to_scalar)),
+        remainders,
+        scaled_values: alloc.alloc_slice_fill_iter(scaled_values.into_iter().map(to_scalar)),
+        table,
+    }
+}
+
+impl ProofExpr for LnExpr {
+    fn data_type(&self) -> ColumnType {
+        decimal_type(RESULT_PRECISION, RESULT_SCALE)
+    }
+
+    #[tracing::instrument(name = "LnExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self.expr.first_round_evaluate(alloc, table, params)?;
+        let reduction = reduce(alloc, &column, &self.reduction_factor());
+        let res = Self::approximation().first_round_evaluate(alloc, &reduction.table, params)?;
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(name = "LnExpr::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self
+            .expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+        let reduction = reduce(alloc, &column, &self.reduction_factor());
+        for &bits in &reduction.bits {
+            builder.produce_intermediate_mle(bits);
+        }
+        for &power in &reduction.powers {
+            builder.produce_intermediate_mle(power);
+        }
+        builder.produce_intermediate_mle(reduction.mantissas);
+        builder.produce_intermediate_mle(reduction.remainders);
+
+        // b * b - b = 0
+        for &bits in &reduction.bits {
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (
+                        S::one(),
+                        vec![Box::new(bits as &[_]), Box::new(bits as &[_])],
+                    ),
+                    (-S::one(), vec![Box::new(bits as &[_])]),
+                ],
+            );
+        }
+        // p_i - p_(i - 1) - (2^(2^i) - 1) * p_(i - 1) * b_i = 0 with p_0 = 1 + b_0
+        let first_power: &'a [S] = alloc
+            .alloc_slice_fill_iter(reduction.bits[0].iter().map(|bit| S::one() + S::from(bit)));
+        for (i, (&power, &previous_power)) in reduction
+            .powers
+            .iter()
+            .zip(core::iter::once(&first_power).chain(&reduction.powers))
+            .enumerate()
+        {
+            let bits = reduction.bits[i + 1];
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(power as &[_])]),
+                    (-S::one(), vec![Box::new(previous_power as &[_])]),
+                    (
+                        -power_step::<S>(i + 1),
+                        vec![Box::new(previous_power as &[_]), Box::new(bits as &[_])],
+                    ),
+                ],
+            );
+        }
+        // x * 2^20 * 10^18 - V * 2^e - r = 0
+        let exponent_power = *reduction.powers.last().expect("There are powers");
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(reduction.scaled_values as &[_])]),
+                (
+                    -S::one(),
+                    vec![
+                        Box::new(reduction.mantissas as &[_]),
+                        Box::new(exponent_power as &[_]),
+                    ],
+                ),
+                (-S::one(), vec![Box::new(reduction.remainders as &[_])]),
+            ],
+        );
+        // 0 <= r < 2^e and 10^18 <= V < 2 * 10^18
+        let one = working_one::<S>();
+        let bounds: [&'a [S]; 4] = [
+            reduction.remainders,
+            alloc.alloc_slice_fill_iter(
+                exponent_power
+                    .iter()
+                    .zip(reduction.remainders)
+                    .map(|(&power, &remainder)| power - S::one() - remainder),
+            ),
+            alloc.alloc_slice_fill_iter(reduction.mantissas.iter().map(|&mantissa| mantissa - one)),
+            alloc.alloc_slice_fill_iter(
+                reduction
+                    .mantissas
+                    .iter()
+                    .map(|&mantissa| one + one - S::one() - mantissa),
+            ),
+        ];
+        for bound in bounds {
+            final_round_evaluate_sign(builder, alloc, bound);
+        }
+
+        let res =
+            Self::approximation().final_round_evaluate(builder, alloc, &reduction.table, params)?;
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let value_eval = self
+            .expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        let bit_evals = builder.try_consume_final_round_mle_evaluations(EXPONENT_BITS)?;
+        let power_evals = builder.try_consume_final_round_mle_evaluations(EXPONENT_BITS - 1)?;
+        let mantissa_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let remainder_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // b * b - b = 0
+        for &bit_eval in &bit_evals {
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                bit_eval * bit_eval - bit_eval,
+                2,
+            )?;
+        }
+        // p_i - p_(i - 1) - (2^(2^i) - 1) * p_(i - 1) * b_i = 0 with p_0 = 1 + b_0
+        let first_power_eval = chi_eval + bit_evals[0];
+        for (i, (&power_eval, &previous_power_eval)) in power_evals
+            .iter()
+            .zip(core::iter::once(&first_power_eval).chain(&power_evals))
+            .enumerate()
+        {
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                power_eval
+                    - previous_power_eval
+                    - power_step::<S>(i + 1) * previous_power_eval * bit_evals[i + 1],
+                2,
+            )?;
+        }
+        // x * 2^20 * 10^18 - V * 2^e - r = 0
+        let factor =
+            S::try_from(self.reduction_factor()).expect("The reduction factor fits in a scalar");
+        let exponent_power_eval = *power_evals.last().expect("There are powers");
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            factor * value_eval - mantissa_eval * exponent_power_eval - remainder_eval,
+            2,
+        )?;
+        // 0 <= r < 2^e and 10^18 <= V < 2 * 10^18
+        let one = working_one::<S>();
+        for bound_eval in [
+            remainder_eval,
+            exponent_power_eval - chi_eval - remainder_eval,
+            mantissa_eval - one * chi_eval,
+            (one + one - S::one()) * chi_eval - mantissa_eval,
+        ] {
+            if verifier_evaluate_sign(builder, bound_eval, chi_eval, None)? != S::ZERO {
+                return Err(ProofError::VerificationError {
+                    error: "ln argument is outside of the domain",
+                });
+            }
+        }
+
+        let exponent_eval = bit_evals
+            .iter()
+            .rev()
+            .fold(S::ZERO, |acc, &bit_eval| acc * S::TWO + bit_eval);
+        let one_and_a_half = one
+            + S::try_from(BigInt::from(5u8) * BigInt::from(10u8).pow(17))
+                .expect("Half of the working one fits in a scalar");
+        let reduced_accessor = IndexMap::from_iter([
+            (Ident::new("t"), mantissa_eval - one_and_a_half * chi_eval),
+            (
+                Ident::new("k"),
+                exponent_eval - S::from(MIN_EXPONENT) * chi_eval,
+            ),
+        ]);
+        Self::approximation().verifier_evaluate(builder, &reduced_accessor, chi_eval, params)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs([&*self.expr], columns);
+    }
+}
+
+/// `10^18`, the value of one at the working scale
+fn working_one<S: Scalar>() -> S {
+    S::try_from(BigInt::from(10u8).pow(u32::from(WORKING_SCALE.unsigned_abs())))
+        .expect("10^18 fits in a scalar")
+}
+
+/// `2^(2^i) - 1`, so that `p_i = p_(i - 1) * (1 + (2^(2^i) - 1) * b_i)`
+fn power_step<S: Scalar>(i: usize) -> S {
+    S::try_from((BigInt::from(1u8) << (1usize << i)) - 1u8).expect("2^(2^i) fits in a scalar")
+}
@@ -0,0 +1,173 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedColumn, OwnedTable,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor, TestAccessor,
+        },
+        math::decimal::Precision,
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{ln_expr::LnExpr, test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+
+/// The result values of `ln(x)` in `res`, converted to `f64`
+fn ln_results(res: &OwnedTable<Curve25519Scalar>) -> Vec<f64> {
+    let Some(OwnedColumn::Decimal75(precision, 6, values)) = res.column_by_index(0) else {
+        panic!("The result is not a DECIMAL(8, 6)");
+    };
+    assert_eq!(precision.value(), 8);
+    values
+        .iter()
+        .map(|&value| f64::from(i32::try_from(value).unwrap()) / 1e6)
+        .collect()
+}
+
+fn prove_and_verify_ln(data: OwnedTable<Curve25519Scalar>) -> Vec<f64> {
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(ln(column(&t, "x", &accessor)), "y")],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    ln_results(&res)
+}
+
+// select ln(x) as y from sxt.t
+#[test]
+fn we_can_prove_ln_of_a_decimal_column_within_the_error_bound() {
+    let values: [(i128, f64); 9] = [
+        (100, 0.000_001),
+        (50_000_000, 0.5),
+        (100_000_000, 1.0),
+        (150_000_000, 1.5),
+        (200_000_000, 2.0),
+        (271_828_183, 2.718_281_83),
+        (1_000_000_000, 10.0),
+        (1_234_567_890_000, 12_345.678_9),
+        (100_000_000_000_000_000_000, 1e12),
+    ];
+    let results = prove_and_verify_ln(owned_table([decimal75(
+        "x",
+        21,
+        8,
+        values.map(|(value, _)| value),
+    )]));
+    for ((_, x), result) in values.iter().zip(results) {
+        assert!((result - x.ln()).abs() < 6e-7, "ln({x}) = {result}");
+    }
+}
+
+// select ln(x) as y from sxt.t
+#[test]
+fn we_can_prove_ln_of_an_integer_column_within_the_error_bound() {
+    let values: [(i64, f64); 6] = [
+        (1, 1.0),
+        (2, 2.0),
+        (3, 3.0),
+        (1000, 1000.0),
+        (1 << 40, 1_099_511_627_776.0),
+        ((1 << 44) - 1, 17_592_186_044_415.0),
+    ];
+    let results = prove_and_verify_ln(owned_table([bigint("x", values.map(|(value, _)| value))]));
+    for ((_, x), result) in values.iter().zip(results) {
+        assert!((result - x.ln()).abs() < 6e-7, "ln({x}) = {result}");
+    }
+}
+
+#[test]
+fn we_cannot_prove_ln_of_values_outside_of_the_domain() {
+    let t = TableRef::new("sxt", "t");
+    // 0.00000095 is just below 2^-20 and 2^44 is the first value above the domain
+    for data in [
+        owned_table([decimal75("x", 21, 8, [100_000_000_i64, 0])]),
+        owned_table([decimal75("x", 21, 8, [100_000_000_i64, -100_000_000])]),
+        owned_table([decimal75("x", 21, 8, [100_000_000_i64, 95])]),
+        owned_table([bigint("x", [1_i64, 1 << 44])]),
+        owned_table([bigint("x", [1_i64, i64::MIN])]),
+    ] {
+        let accessor =
+            OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+        let ast = filter(
+            vec![aliased_plan(ln(column(&t, "x", &accessor)), "y")],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        assert!(verifiable_res.verify(&ast, &accessor, &(), &[]).is_err());
+    }
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_ln_expr_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_decimal75(
+        "x",
+        10,
+        8,
+        [
+            50_000_000,
+            100_000_000,
+            200_000_000,
+            271_828_183,
+            1_000_000_000,
+        ],
+        &alloc,
+    )]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let expr: DynProofExpr = ln(column(&t, "x", &accessor));
+    let res = expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(
+        res,
+        Column::Decimal75(
+            Precision::new(8).unwrap(),
+            6,
+            &[-693_147, 0, 693_147, 1_000_000, 2_302_585].map(Curve25519Scalar::from)
+        )
+    );
+    assert_eq!(
+        expr.data_type(),
+        ColumnType::Decimal75(Precision::new(8).unwrap(), 6)
+    );
+}
+
+#[test]
+fn we_cannot_take_ln_of_non_numeric_or_too_wide_expressions() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_varchar("a", ["x", "y"], &alloc),
+        borrowed_boolean("b", [true, false], &alloc),
+        borrowed_decimal75("c", 20, 19, [1, 2], &alloc),
+        borrowed_decimal75("d", 75, 0, [1, 2], &alloc),
+        borrowed_decimal75("e", 63, 10, [1, 2], &alloc),
+        borrowed_bigint("f", [1, 2], &alloc),
+        borrowed_int128("g", [1, 2], &alloc),
+        borrowed_decimal75("h", 69, 18, [1, 2], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for column_id in ["a", "b", "c", "d", "e"] {
+        let res = LnExpr::try_new(Box::new(column(&t, column_id, &accessor)));
+        assert!(matches!(res, Err(AnalyzeError::InvalidDataType { .. })));
+    }
+    for column_id in ["f", "g", "h"] {
+        assert!(LnExpr::try_new(Box::new(column(&t, column_id, &accessor))).is_ok());
+    }
+}
@@ -21,6 +21,7 @@ pub(crate) use multiply_expr::MultiplyExpr;
 mod multiply_expr_test;
 
 mod dyn_proof_expr;
+pub(crate) use dyn_proof_expr::get_column_references_of_exprs;
 pub use dyn_proof_expr::DynProofExpr;
 
 mod literal_expr;
@@ -63,6 +64,11 @@ pub(crate) use equals_expr::EqualsExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod equals_expr_test;
 
+mod in_expr;
+pub(crate) use in_expr::InExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod in_expr_test;
+
 mod table_expr;
 pub use table_expr::TableExpr;
 
@@ -83,3 +89,48 @@ mod scaling_cast_expr;
 pub(crate) use scaling_cast_expr::ScalingCastExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod scaling_cast_expr_test;
+
+mod if_expr;
+pub(crate) use if_expr::IfExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod if_expr_test;
+
+mod round_to_multiple_expr;
+pub(crate) use round_to_multiple_expr::{nearest_multiple_quotient, RoundToMultipleExpr};
+#[cfg(all(test, feature = "blitzar"))]
+mod round_to_multiple_expr_test;
+
+mod modulo_expr;
+pub(crate) use modulo_expr::ModuloExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod modulo_expr_test;
+
+mod pow_mod_expr;
+pub(crate) use pow_mod_expr::PowModExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod pow_mod_expr_test;
+
+mod decimal_rescale_expr;
+pub(crate) use decimal_rescale_expr::DecimalRescaleExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod decimal_rescale_expr_test;
+
+mod ln_expr;
+pub(crate) use ln_expr::LnExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod ln_expr_test;
+
+mod isqrt_expr;
+pub(crate) use isqrt_expr::ISqrtExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod isqrt_expr_test;
+
+mod row_hash_expr;
+pub(crate) use row_hash_expr::RowHashExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod row_hash_expr_test;
+
+mod derived_key_expr;
+pub(crate) use derived_key_expr::DerivedKeyExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod derived_key_expr_test;
@@ -0,0 +1,312 @@
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_sign, verifier_evaluate_sign},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expression for the remainder of an integer expression divided by a constant modulus,
+/// i.e. `expr % modulus`
+///
+/// As in SQL, the division truncates towards zero, so the remainder has the sign of `expr`:
+/// `7 % 3 = 1`, `-7 % 3 = -1` and `7 % -3 = 1`. The sign of the modulus doesn't matter.
+///
+/// The result has the type of `expr`. Since the remainder is never larger in magnitude than
+/// `expr`, it is always in range.
+///
+/// The prover commits to the truncated quotients `q = TRUNC(expr / modulus)`, which are range
+/// checked so that `modulus * q` can't wrap around. The remainder `r = expr - modulus * q` is then
+/// shown to satisfy `0 <= r < |modulus|` for non-negative and `-|modulus| < r <= 0` for negative
+/// values, which determines `q` uniquely.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuloExpr {
+    expr: Box<DynProofExpr>,
+    modulus: i128,
+}
+
+impl ModuloExpr {
+    /// Create an expression for the remainder of `expr` divided by `modulus`
+    ///
+    /// `expr` must be an integer expression and `modulus` a nonzero integer literal.
+    pub fn try_new(expr: Box<DynProofExpr>, modulus: &DynProofExpr) -> AnalyzeResult<Self> {
+        let expr_type = expr.data_type();
+        if !expr_type.is_integer() {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        let modulus = integer_literal_value(modulus)
+            .filter(|&modulus| modulus != 0)
+            .ok_or(AnalyzeError::InvalidModulus)?;
+        Ok(Self { expr, modulus })
+    }
+
+    /// Get the expression being divided
+    pub fn expr(&self) -> &DynProofExpr {
+        &self.expr
+    }
+
+    /// Get the modulus the expression is divided by
+    pub fn modulus(&self) -> i128 {
+        self.modulus
+    }
+
+    /// The number of bits the quotients are range checked to
+    ///
+    /// As for [`RoundToMultipleExpr`](super::RoundToMultipleExpr), with
+    /// `2^k <= |modulus| < 2^(k+1)` and the values fitting in `n` bits, allowing `n - k + 2` bits
+    /// bounds `|modulus * q|` by `2^(n+2)`, far below the modulus of the scalar field.
+    fn quotient_bits(&self) -> u8 {
+        let value_bits = self.expr.data_type().bit_size();
+        let modulus_log2 = 127 - self.modulus.unsigned_abs().leading_zeros();
+        u8::try_from((value_bits + 2).saturating_sub(modulus_log2).max(2))
+            .expect("Integer types have at most 128 bits")
+    }
+
+    /// The absolute value of the modulus minus one as a scalar, i.e. the largest magnitude of a
+    /// remainder
+    fn max_abs_remainder<S: Scalar>(&self) -> S {
+        if self.modulus < 0 {
+            -S::from(self.modulus) - S::ONE
+        } else {
+            S::from(self.modulus) - S::ONE
+        }
+    }
+}
+
+/// The value of `expr` if it is an integer literal
+pub(super) fn integer_literal_value(expr: &DynProofExpr) -> Option<i128> {
+    match expr {
+        DynProofExpr::Literal(literal) => match *literal.value() {
+            LiteralValue::Uint8(value) => Some(i128::from(value)),
+            LiteralValue::TinyInt(value) => Some(i128::from(value)),
+            LiteralValue::SmallInt(value) => Some(i128::from(value)),
+            LiteralValue::Int(value) => Some(i128::from(value)),
+            LiteralValue::BigInt(value) => Some(i128::from(value)),
+            LiteralValue::Int128(value) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The quotient of `value` divided by `modulus`, truncated towards zero
+///
+/// Unlike `i128` division this doesn't overflow, since `i128::MIN / -1` is representable as a
+/// scalar.
+#[expect(clippy::cast_possible_truncation)]
+fn truncated_quotient<S: Scalar>(value: i128, modulus: i128) -> S {
+    let abs_quotient = value.unsigned_abs() / modulus.unsigned_abs();
+    let quotient = S::from([abs_quotient as u64, (abs_quotient >> 64) as u64, 0, 0]);
+    if (value < 0) == (modulus < 0) {
+        quotient
+    } else {
+        -quotient
+    }
+}
+
+/// The remainders of `values` divided by `modulus`, with the sign of the values
+fn remainder_slice<'a, T: Copy + Into<i128>>(
+    alloc: &'a Bump,
+    values: &[T],
+    modulus: i128,
+    from_remainder: impl Fn(i128) -> T,
+) -> &'a [T] {
+    // The remainder is at most as large as the value, so it fits into `T`
+    alloc.alloc_slice_fill_iter(
+        values
+            .iter()
+            .map(|&value| from_remainder(value.into().wrapping_rem(modulus))),
+    )
+}
+
+/// The remainders of the values of an integer column divided by `modulus`
+///
+/// # Panics
+/// Panics if the column is not an integer column.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn remainder_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: Column<'a, S>,
+    modulus: i128,
+) -> Column<'a, S> {
+    match column {
+        Column::Uint8(values) => {
+            Column::Uint8(remainder_slice(alloc, values, modulus, |r| r as u8))
+        }
+        Column::TinyInt(values) => {
+            Column::TinyInt(remainder_slice(alloc, values, modulus, |r| r as i8))
+        }
+        Column::SmallInt(values) => {
+            Column::SmallInt(remainder_slice(alloc, values, modulus, |r| r as i16))
+        }
+        Column::Int(values) => Column::Int(remainder_slice(alloc, values, modulus, |r| r as i32)),
+        Column::BigInt(values) => {
+            Column::BigInt(remainder_slice(alloc, values, modulus, |r| r as i64))
+        }
+        Column::Int128(values) => Column::Int128(remainder_slice(alloc, values, modulus, |r| r)),
+        _ => panic!("Only integer columns have a remainder"),
+    }
+}
+
+/// The truncated quotients of the values of an integer column divided by `modulus`
+///
+/// # Panics
+/// Panics if the column is not an integer column.
+fn quotient_column<S: Scalar>(column: Column<'_, S>, modulus: i128) -> Vec<S> {
+    fn quotients<S: Scalar, T: Copy + Into<i128>>(values: &[T], modulus: i128) -> Vec<S> {
+        values
+            .iter()
+            .map(|&value| truncated_quotient(value.into(), modulus))
+            .collect()
+    }
+    match column {
+        Column::Uint8(values) => quotients(values, modulus),
+        Column::TinyInt(values) => quotients(values, modulus),
+        Column::SmallInt(values) => quotients(values, modulus),
+        Column::Int(values) => quotients(values, modulus),
+        Column::BigInt(values) => quotients(values, modulus),
+        Column::Int128(values) => quotients(values, modulus),
+        _ => panic!("Only integer columns have a quotient"),
+    }
+}
+
+impl ProofExpr for ModuloExpr {
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[tracing::instrument(name = "ModuloExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self.expr.first_round_evaluate(alloc, table, params)?;
+        let res = remainder_column(alloc, column, self.modulus);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(name = "ModuloExpr::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self
+            .expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+        let res = remainder_column(alloc, column, self.modulus);
+        let values: &'a [S] = alloc.alloc_slice_fill_iter(column.to_scalar());
+        let quotients: &'a [S] = alloc.alloc_slice_fill_iter(quotient_column(column, self.modulus));
+        builder.produce_intermediate_mle(quotients);
+
+        // sign(value) == -1, which decides the sign of the remainder
+        let is_negative = final_round_evaluate_sign(builder, alloc, values);
+        // range check of the quotients
+        final_round_evaluate_sign(builder, alloc, quotients);
+
+        // The bounds on the remainders, which are all non-negative
+        let modulus = S::from(self.modulus);
+        let max_abs_remainder = self.max_abs_remainder::<S>();
+        let remainders = values
+            .iter()
+            .zip(quotients)
+            .map(|(&value, &quotient)| value - modulus * quotient);
+        let lower_bounds: &'a [S] =
+            alloc.alloc_slice_fill_iter(remainders.clone().zip(is_negative).map(
+                |(remainder, &is_negative)| remainder + max_abs_remainder * S::from(is_negative),
+            ));
+        let upper_bounds: &'a [S] = alloc.alloc_slice_fill_iter(remainders.zip(is_negative).map(
+            |(remainder, &is_negative)| max_abs_remainder * S::from(!is_negative) - remainder,
+        ));
+        final_round_evaluate_sign(builder, alloc, lower_bounds);
+        final_round_evaluate_sign(builder, alloc, upper_bounds);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let value_eval = self
+            .expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        let quotient_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // sign(value) == -1
+        let is_negative_eval = verifier_evaluate_sign(builder, value_eval, chi_eval, None)?;
+        // range check of the quotients
+        verifier_evaluate_sign(builder, quotient_eval, chi_eval, Some(self.quotient_bits()))?;
+
+        let remainder_eval = value_eval - S::from(self.modulus) * quotient_eval;
+        let max_abs_remainder = self.max_abs_remainder::<S>();
+        let lower_bound_eval = remainder_eval + max_abs_remainder * is_negative_eval;
+        let upper_bound_eval = max_abs_remainder * (chi_eval - is_negative_eval) - remainder_eval;
+        for bound_eval in [lower_bound_eval, upper_bound_eval] {
+            if verifier_evaluate_sign(builder, bound_eval, chi_eval, None)? != S::ZERO {
+                return Err(ProofError::VerificationError {
+                    error: "remainder is not within the modulus or has the wrong sign",
+                });
+            }
+        }
+
+        Ok(remainder_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs([&*self.expr], columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncated_quotient;
+    use crate::base::scalar::test_scalar::TestScalar;
+
+    #[test]
+    fn we_can_compute_the_truncated_quotient() {
+        let quotient = |value, modulus| truncated_quotient::<TestScalar>(value, modulus);
+        assert_eq!(quotient(0, 10), TestScalar::from(0));
+        assert_eq!(quotient(7, 3), TestScalar::from(2));
+        assert_eq!(quotient(-7, 3), TestScalar::from(-2));
+        assert_eq!(quotient(7, -3), TestScalar::from(-2));
+        assert_eq!(quotient(-7, -3), TestScalar::from(2));
+        assert_eq!(quotient(9, 10), TestScalar::from(0));
+        assert_eq!(quotient(-9, 10), TestScalar::from(0));
+        assert_eq!(quotient(i128::MAX, 1), TestScalar::from(i128::MAX));
+        assert_eq!(quotient(i128::MIN, 1), TestScalar::from(i128::MIN));
+        // The quotient which doesn't fit into an `i128`
+        assert_eq!(
+            quotient(i128::MIN, -1),
+            TestScalar::from(i128::MAX) + TestScalar::from(1)
+        );
+    }
+}
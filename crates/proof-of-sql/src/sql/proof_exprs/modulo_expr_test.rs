@@ -0,0 +1,252 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, LiteralValue,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor, TestAccessor,
+        },
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{modulo_expr::ModuloExpr, test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+// select a % 10 as m10, a % 3 as m3, a % -7 as mn7 from sxt.t
+#[test]
+fn we_can_prove_the_remainders_of_a_column_with_several_moduli() {
+    let data = owned_table([bigint(
+        "a",
+        [-151_i64, -150, -149, -5, 0, 4, 5, 149, 150, 151, 1234],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(modulo(column(&t, "a", &accessor), &const_bigint(10)), "m10"),
+            aliased_plan(modulo(column(&t, "a", &accessor), &const_smallint(3)), "m3"),
+            aliased_plan(modulo(column(&t, "a", &accessor), &const_int(-7)), "mn7"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("m10", [-1_i64, 0, -9, -5, 0, 4, 5, 9, 0, 1, 4]),
+        bigint("m3", [-1_i64, 0, -2, -2, 0, 1, 2, 2, 0, 1, 1]),
+        bigint("mn7", [-4_i64, -3, -2, -5, 0, 4, 5, 2, 3, 4, 2]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a % 10 as a, b % 10 as b, c % 50 as c, d % 100 as d, e % 10^20 as e, f % -1 as f
+//   from sxt.t
+#[test]
+fn we_can_prove_the_remainders_of_columns_of_every_integer_type() {
+    let data = owned_table([
+        tinyint("a", [i8::MIN, -15, 14, i8::MAX]),
+        uint8("b", [0_u8, 5, 14, u8::MAX]),
+        smallint("c", [i16::MIN, 25, -25, 74]),
+        int("d", [i32::MIN, -7, 50, -50]),
+        int128("e", [i128::MIN, -5, i128::MAX, 123]),
+        int128("f", [i128::MIN, -5, i128::MAX, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(modulo(column(&t, "a", &accessor), &const_bigint(10)), "a"),
+            aliased_plan(modulo(column(&t, "b", &accessor), &const_bigint(10)), "b"),
+            aliased_plan(modulo(column(&t, "c", &accessor), &const_bigint(50)), "c"),
+            aliased_plan(modulo(column(&t, "d", &accessor), &const_bigint(100)), "d"),
+            aliased_plan(
+                modulo(
+                    column(&t, "e", &accessor),
+                    &const_int128(100_000_000_000_000_000_000),
+                ),
+                "e",
+            ),
+            aliased_plan(modulo(column(&t, "f", &accessor), &const_bigint(-1)), "f"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        tinyint("a", [-8_i8, -5, 4, 7]),
+        uint8("b", [0_u8, 5, 4, 5]),
+        smallint("c", [-18_i16, 25, -25, 24]),
+        int("d", [-48_i32, -7, 0, -50]),
+        int128(
+            "e",
+            [
+                -31_687_303_715_884_105_728_i128,
+                -5,
+                31_687_303_715_884_105_727,
+                123,
+            ],
+        ),
+        int128("f", [0_i128, 0, 0, 0]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where a % 10 = 4
+#[test]
+fn we_can_prove_a_filter_on_a_remainder() {
+    let data = owned_table([bigint("a", [14_i64, 15, 24, -4, -14, 4, 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            modulo(column(&t, "a", &accessor), &const_bigint(10)),
+            const_bigint(4),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [14_i64, 24, 4])]);
+    assert_eq!(res, expected_res);
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-50, 51);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([bigint("a", dist.sample_iter(&mut rng).take(n))]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![aliased_plan(
+                modulo(column(&t, "a", &accessor), &const_bigint(8)),
+                "r",
+            )],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+
+        // Calculate/compare expected result
+        let expected_r: Vec<_> = data["a"].i64_iter().map(|a| a % 8).collect();
+        let expected_result = owned_table([bigint("r", expected_r)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_modulo_expr_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_int("a", [-25, -24, 24, 25], &alloc)]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let expr: DynProofExpr = modulo(column(&t, "a", &accessor), &const_int(10));
+    let res = expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(res, Column::Int(&[-5, -4, 4, 5]));
+    assert_eq!(expr.data_type(), ColumnType::Int);
+}
+
+#[test]
+fn we_cannot_take_a_modulus_which_is_not_a_nonzero_integer_literal() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [1, 2], &alloc),
+        borrowed_bigint("b", [3, 4], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for modulus in [
+        const_bigint(0),
+        const_int128(0),
+        const_bool(true),
+        const_varchar("10"),
+        const_decimal75(3, 1, 100),
+        column(&t, "b", &accessor),
+        DynProofExpr::try_new_placeholder(1, ColumnType::BigInt).unwrap(),
+    ] {
+        let res = ModuloExpr::try_new(Box::new(column(&t, "a", &accessor)), &modulus);
+        assert!(matches!(res, Err(AnalyzeError::InvalidModulus)));
+    }
+    assert_eq!(
+        ModuloExpr::try_new(
+            Box::new(column(&t, "a", &accessor)),
+            &DynProofExpr::new_literal(LiteralValue::Uint8(5)),
+        )
+        .unwrap()
+        .modulus(),
+        5
+    );
+}
+
+#[test]
+fn we_cannot_take_the_remainder_of_an_expression_which_is_not_an_integer() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_varchar("a", ["x", "y"], &alloc),
+        borrowed_decimal75("b", 5, 1, [10, 20], &alloc),
+        borrowed_boolean("c", [true, false], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for column_id in ["a", "b", "c"] {
+        let res = ModuloExpr::try_new(
+            Box::new(column(&t, column_id, &accessor)),
+            &const_bigint(10),
+        );
+        assert!(matches!(res, Err(AnalyzeError::InvalidDataType { .. })));
+    }
+}
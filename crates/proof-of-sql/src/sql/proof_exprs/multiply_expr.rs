@@ -1,7 +1,10 @@
-use super::{DecimalProofExpr, DynProofExpr, ProofExpr};
+use super::{get_column_references_of_exprs, DecimalProofExpr, DynProofExpr, ProofExpr};
 use crate::{
     base::{
-        database::{try_multiply_column_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
+        database::{
+            try_multiply_column_types, Column, ColumnOperationError, ColumnRef, ColumnType,
+            LiteralValue, Table,
+        },
         map::{IndexMap, IndexSet},
         proof::{PlaceholderResult, ProofError},
         scalar::Scalar,
@@ -27,14 +30,22 @@ pub struct MultiplyExpr {
 
 impl MultiplyExpr {
     /// Create numerical `*` expression
+    ///
+    /// Integer operands are treated as decimals with scale 0, so that the result has the sum of
+    /// the scales of the operands. See [`try_multiply_column_types`] for the precision.
     pub fn try_new(lhs: Box<DynProofExpr>, rhs: Box<DynProofExpr>) -> AnalyzeResult<Self> {
         let left_datatype = lhs.data_type();
         let right_datatype = rhs.data_type();
         try_multiply_column_types(left_datatype, right_datatype)
             .map(|_| Self { lhs, rhs })
-            .map_err(|_| AnalyzeError::DataTypeMismatch {
-                left_type: left_datatype.to_string(),
-                right_type: right_datatype.to_string(),
+            .map_err(|err| match err {
+                ColumnOperationError::DecimalConversionError { source } => {
+                    AnalyzeError::DecimalConversionError { source }
+                }
+                _ => AnalyzeError::DataTypeMismatch {
+                    left_type: left_datatype.to_string(),
+                    right_type: right_datatype.to_string(),
+                },
             })
     }
 
@@ -136,8 +147,7 @@ impl ProofExpr for MultiplyExpr {
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.lhs.get_column_references(columns);
-        self.rhs.get_column_references(columns);
+        get_column_references_of_exprs([&*self.lhs, &*self.rhs], columns);
     }
 }
 
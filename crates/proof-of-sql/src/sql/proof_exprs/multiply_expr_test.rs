@@ -5,7 +5,7 @@ use crate::{
             owned_table_utility::*, table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef,
             TableTestAccessor,
         },
-        math::decimal::Precision,
+        math::decimal::{DecimalError, Precision},
     },
     proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
     sql::{
@@ -139,6 +139,54 @@ fn where_clause_can_wrap_around() {
     assert_eq!(res, expected_res);
 }
 
+// select amount * quantity as total, amount * discount as reduction from sxt.t
+#[test]
+fn we_can_prove_a_multiply_query_with_mixed_integer_and_decimal_operands() {
+    let data = owned_table([
+        decimal75("amount", 12, 2, [1999_i64, 250, 100]),
+        bigint("quantity", [2_i64, 3, -1]),
+        tinyint("discount", [0_i8, 5, -10]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let total = multiply(
+        column(&t, "amount", &accessor),
+        column(&t, "quantity", &accessor),
+    );
+    let reduction = multiply(
+        column(&t, "discount", &accessor),
+        column(&t, "amount", &accessor),
+    );
+    assert_eq!(
+        total.data_type(),
+        ColumnType::Decimal75(Precision::new(32).unwrap(), 2)
+    );
+    assert_eq!(
+        reduction.data_type(),
+        ColumnType::Decimal75(Precision::new(16).unwrap(), 2)
+    );
+    let ast = filter(
+        vec![
+            aliased_plan(total, "total"),
+            aliased_plan(reduction, "reduction"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        decimal75("total", 32, 2, [3998_i64, 750, -100]),
+        decimal75("reduction", 16, 2, [0_i64, 1250, -1000]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
 fn test_random_tables_with_given_offset(offset: usize) {
     let dist = Uniform::new(-3, 4);
     let mut rng = StdRng::from_seed([0u8; 32]);
@@ -283,3 +331,25 @@ fn we_cannot_multiply_mismatching_types() {
         }
     ));
 }
+
+#[test]
+fn we_cannot_multiply_decimals_whose_product_has_a_scale_above_the_maximum_precision() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_decimal75("a", 40, 40, [1_i64, 2], &alloc),
+        borrowed_decimal75("b", 36, 36, [3_i64, 4], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor = TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let multiply_err = MultiplyExpr::try_new(
+        Box::new(column(&t, "a", &accessor)),
+        Box::new(column(&t, "b", &accessor)),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        multiply_err,
+        AnalyzeError::DecimalConversionError {
+            source: DecimalError::InvalidScale { .. }
+        }
+    ));
+}
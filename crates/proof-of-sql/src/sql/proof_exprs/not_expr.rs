@@ -1,4 +1,4 @@
-use super::{DynProofExpr, ProofExpr};
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{can_not_type, Column, ColumnRef, ColumnType, LiteralValue, Table},
@@ -96,6 +96,6 @@ impl ProofExpr for NotExpr {
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.expr.get_column_references(columns);
+        get_column_references_of_exprs([&*self.expr], columns);
     }
 }
@@ -1,4 +1,4 @@
-use super::{DynProofExpr, ProofExpr};
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{can_and_or_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
@@ -116,8 +116,7 @@ impl ProofExpr for OrExpr {
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.lhs.get_column_references(columns);
-        self.rhs.get_column_references(columns);
+        get_column_references_of_exprs([&*self.lhs, &*self.rhs], columns);
     }
 }
 
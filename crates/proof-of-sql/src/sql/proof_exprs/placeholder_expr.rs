@@ -1,7 +1,7 @@
 use super::ProofExpr;
 use crate::{
     base::{
-        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        database::{CoercionError, Column, ColumnRef, ColumnType, LiteralValue, Table},
         map::{IndexMap, IndexSet},
         proof::{PlaceholderError, PlaceholderResult, ProofError},
         scalar::Scalar,
@@ -44,14 +44,13 @@ impl PlaceholderExpr {
     /// Replace the placeholder with the correct value in `params`.
     ///
     /// Following `PostgreSQL` convention id starts from 1, so the first placeholder has id 1.
+    /// The value is coerced to the type of the placeholder with [`LiteralValue::try_coerce_to`].
     ///
     /// Note that this function will return an error if
     /// 1. The placeholder id is out of bounds
-    /// 2. The placeholder type does not match the type of the value in `params`
-    fn interpolate<'a>(
-        &self,
-        params: &'a [LiteralValue],
-    ) -> Result<&'a LiteralValue, PlaceholderError> {
+    /// 2. The type of the value in `params` can not be coerced to the placeholder type
+    /// 3. The value in `params` is not representable in the placeholder type
+    fn interpolate(&self, params: &[LiteralValue]) -> Result<LiteralValue, PlaceholderError> {
         let pos = self.id - 1;
         let param_value = params
             .get(pos)
@@ -59,14 +58,24 @@ impl PlaceholderExpr {
                 id: self.id,
                 num_params: params.len(),
             })?;
-        if param_value.column_type() != self.column_type {
-            return Err(PlaceholderError::InvalidPlaceholderType {
-                id: self.id,
-                expected: self.column_type,
-                actual: param_value.column_type(),
-            });
-        }
-        Ok(param_value)
+        param_value
+            .try_coerce_to(&self.column_type)
+            .map_err(|err| match err {
+                CoercionError::IncompatibleTypes { from, to } => {
+                    PlaceholderError::InvalidPlaceholderType {
+                        id: self.id,
+                        expected: to,
+                        actual: from,
+                    }
+                }
+                CoercionError::NotRepresentable { from, to } => {
+                    PlaceholderError::PlaceholderValueNotRepresentable {
+                        id: self.id,
+                        expected: to,
+                        actual: from,
+                    }
+                }
+            })
     }
 }
 
@@ -89,7 +98,7 @@ impl ProofExpr for PlaceholderExpr {
         log::log_memory_usage("Start");
 
         let param_value = self.interpolate(params)?;
-        let res = Column::from_literal_with_length(param_value, table.num_rows(), alloc);
+        let res = Column::from_literal_with_length(&param_value, table.num_rows(), alloc);
 
         log::log_memory_usage("End");
 
@@ -111,7 +120,7 @@ impl ProofExpr for PlaceholderExpr {
         log::log_memory_usage("Start");
 
         let param_value = self.interpolate(params)?;
-        let res = Column::from_literal_with_length(param_value, table.num_rows(), alloc);
+        let res = Column::from_literal_with_length(&param_value, table.num_rows(), alloc);
 
         log::log_memory_usage("End");
 
@@ -180,6 +189,25 @@ mod tests {
         let placeholder_expr = PlaceholderExpr::try_new(1, ColumnType::Boolean).unwrap();
         let params = vec![LiteralValue::Boolean(true)];
         let res = placeholder_expr.interpolate(&params);
-        assert_eq!(res.unwrap(), &LiteralValue::Boolean(true));
+        assert_eq!(res.unwrap(), LiteralValue::Boolean(true));
+    }
+
+    #[test]
+    fn we_can_interpolate_placeholder_if_the_value_can_be_coerced_to_its_type() {
+        let placeholder_expr = PlaceholderExpr::try_new(1, ColumnType::BigInt).unwrap();
+        let params = vec![LiteralValue::Int(123)];
+        let res = placeholder_expr.interpolate(&params);
+        assert_eq!(res.unwrap(), LiteralValue::BigInt(123));
+    }
+
+    #[test]
+    fn we_cannot_interpolate_placeholder_if_the_value_is_not_representable_in_its_type() {
+        let placeholder_expr = PlaceholderExpr::try_new(1, ColumnType::TinyInt).unwrap();
+        let params = vec![LiteralValue::BigInt(1_000)];
+        let res = placeholder_expr.interpolate(&params);
+        assert!(matches!(
+            res,
+            Err(PlaceholderError::PlaceholderValueNotRepresentable { .. })
+        ));
     }
 }
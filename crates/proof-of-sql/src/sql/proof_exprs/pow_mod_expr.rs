@@ -0,0 +1,348 @@
+use super::{
+    get_column_references_of_exprs, modulo_expr::integer_literal_value, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        polynomial::MultilinearExtension,
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_sign, verifier_evaluate_sign},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// The largest exponent of a [`PowModExpr`]
+///
+/// Every multiplication of the power chain commits to two more columns, so only small constant
+/// exponents are supported.
+const MAX_EXPONENT: u8 = 16;
+
+/// Provable modular exponentiation of an integer expression with a constant exponent and
+/// modulus, i.e. `POW_MOD(base, exponent, modulus) = base^exponent mod modulus`
+///
+/// The result is the least non-negative residue, i.e. it is in `[0, |modulus|)` whatever the
+/// signs of `base` and `modulus`. Note that this differs from `%`, which keeps the sign of its
+/// left side: `POW_MOD(-7, 1, 3) = 2` while `-7 % 3 = -1`. An exponent of zero gives
+/// `1 mod |modulus|`, which is 0 for a modulus of 1 and 1 otherwise. The result is a `BIGINT`.
+///
+/// The power is computed as a chain of reductions `r_1 = base mod m` and
+/// `r_i = r_(i-1) * r_1 mod m`. For each step the prover commits to the remainder `r_i` and the
+/// floored quotient `q_i`, and `dividend - m * q_i - r_i = 0` is an identity. The quotients are
+/// range checked so that `m * q_i` can't wrap around, and `0 <= r_i <= m - 1` then determines
+/// `q_i` and `r_i` uniquely.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowModExpr {
+    base: Box<DynProofExpr>,
+    exponent: u8,
+    modulus: i128,
+}
+
+impl PowModExpr {
+    /// Create an expression for `base` raised to `exponent` modulo `modulus`
+    ///
+    /// `base` must be an integer expression, `exponent` an integer literal between 0 and 16 and
+    /// `modulus` a nonzero integer literal of magnitude at most `2^63`.
+    pub fn try_new(
+        base: Box<DynProofExpr>,
+        exponent: &DynProofExpr,
+        modulus: &DynProofExpr,
+    ) -> AnalyzeResult<Self> {
+        let expr_type = base.data_type();
+        if !expr_type.is_integer() {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        let exponent = integer_literal_value(exponent)
+            .and_then(|exponent| u8::try_from(exponent).ok())
+            .filter(|&exponent| exponent <= MAX_EXPONENT)
+            .ok_or(AnalyzeError::InvalidExponent {
+                max_exponent: MAX_EXPONENT,
+            })?;
+        let modulus = integer_literal_value(modulus)
+            .filter(|&modulus| modulus != 0 && modulus.unsigned_abs() <= 1 << 63)
+            .ok_or(AnalyzeError::InvalidPowModModulus)?;
+        Ok(Self {
+            base,
+            exponent,
+            modulus,
+        })
+    }
+
+    /// Get the expression being raised to a power
+    pub fn base(&self) -> &DynProofExpr {
+        &self.base
+    }
+
+    /// Get the exponent the base is raised to
+    pub fn exponent(&self) -> u8 {
+        self.exponent
+    }
+
+    /// Get the modulus the power is reduced by
+    pub fn modulus(&self) -> i128 {
+        self.modulus
+    }
+
+    /// The absolute value of the modulus, which is what the power is reduced by
+    fn abs_modulus(&self) -> i128 {
+        self.modulus.abs()
+    }
+
+    /// The number of bits the quotients of a dividend of `dividend_bits` bits are range checked to
+    ///
+    /// As for [`ModuloExpr`](super::ModuloExpr), with `2^k <= |modulus| < 2^(k+1)`, allowing
+    /// `n - k + 2` bits for a dividend of `n` bits bounds `|modulus * q|` by `2^(n+3)`, far below
+    /// the modulus of the scalar field.
+    fn quotient_bits(&self, dividend_bits: u32) -> u8 {
+        let modulus_log2 = 127 - self.abs_modulus().leading_zeros();
+        u8::try_from((dividend_bits + 2).saturating_sub(modulus_log2).max(2))
+            .expect("Dividends have at most 128 bits")
+    }
+
+    /// The number of bits of a product of two remainders, which are below `2^(k+1)`
+    fn product_bits(&self) -> u32 {
+        2 * (128 - self.abs_modulus().leading_zeros())
+    }
+
+    /// The result for an exponent of zero, i.e. `1 mod |modulus|`
+    fn one_mod_modulus(&self) -> i64 {
+        i64::from(self.abs_modulus() != 1)
+    }
+}
+
+/// The least non-negative residue of `value^exponent` modulo the positive `modulus`, computed
+/// with the same chain of reductions the proof uses
+///
+/// Every intermediate product is below `modulus^2 <= 2^126`, so nothing overflows.
+pub(super) fn pow_mod(value: i128, exponent: u8, modulus: i128) -> i128 {
+    let first = value.rem_euclid(modulus);
+    (1..exponent).fold(
+        if exponent == 0 { 1 % modulus } else { first },
+        |remainder, _| (remainder * first) % modulus,
+    )
+}
+
+/// The values of an integer column as `i128`s
+///
+/// # Panics
+/// Panics if the column is not an integer column.
+fn integer_values<S: Scalar>(column: Column<'_, S>) -> Vec<i128> {
+    fn widen<T: Copy + Into<i128>>(values: &[T]) -> Vec<i128> {
+        values.iter().map(|&value| value.into()).collect()
+    }
+    match column {
+        Column::Uint8(values) => widen(values),
+        Column::TinyInt(values) => widen(values),
+        Column::SmallInt(values) => widen(values),
+        Column::Int(values) => widen(values),
+        Column::BigInt(values) => widen(values),
+        Column::Int128(values) => widen(values),
+        _ => panic!("Only integer columns have a modular power"),
+    }
+}
+
+/// Convert residues, which are below `2^63`, to a `BIGINT` column
+fn residue_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    residues: impl ExactSizeIterator<Item = i128>,
+) -> Column<'a, S> {
+    Column::BigInt(alloc.alloc_slice_fill_iter(
+        residues.map(|residue| i64::try_from(residue).expect("Residues are below 2^63")),
+    ))
+}
+
+impl ProofExpr for PowModExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::BigInt
+    }
+
+    #[tracing::instrument(name = "PowModExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self.base.first_round_evaluate(alloc, table, params)?;
+        let res = residue_column(
+            alloc,
+            integer_values(column)
+                .into_iter()
+                .map(|value| pow_mod(value, self.exponent, self.abs_modulus())),
+        );
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(name = "PowModExpr::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self
+            .base
+            .final_round_evaluate(builder, alloc, table, params)?;
+        if self.exponent == 0 {
+            return Ok(Column::BigInt(
+                alloc.alloc_slice_fill_copy(column.len(), self.one_mod_modulus()),
+            ));
+        }
+        let modulus = self.abs_modulus();
+        let max_remainder = S::from(modulus - 1);
+        let values: &'a [S] = alloc.alloc_slice_fill_iter(column.to_scalar());
+
+        let mut dividends = integer_values(column);
+        let mut dividend_factors: Vec<Box<dyn MultilinearExtension<S> + 'a>> =
+            vec![Box::new(values)];
+        let mut first_remainders: Option<(Vec<i128>, &'a [S])> = None;
+        let mut residues = Vec::new();
+        for _ in 0..self.exponent {
+            let (remainder_values, quotient_values): (Vec<i128>, Vec<i128>) = dividends
+                .iter()
+                .map(|dividend| (dividend.rem_euclid(modulus), dividend.div_euclid(modulus)))
+                .unzip();
+            let remainders: &'a [S] =
+                alloc.alloc_slice_fill_iter(remainder_values.iter().map(|&r| S::from(r)));
+            let quotients: &'a [S] =
+                alloc.alloc_slice_fill_iter(quotient_values.iter().map(|&q| S::from(q)));
+            builder.produce_intermediate_mle(remainders);
+            builder.produce_intermediate_mle(quotients);
+
+            // dividend - modulus * q - r = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), dividend_factors),
+                    (-S::from(modulus), vec![Box::new(quotients)]),
+                    (-S::one(), vec![Box::new(remainders)]),
+                ],
+            );
+            // range check of the quotients
+            final_round_evaluate_sign(builder, alloc, quotients);
+
+            // r >= 0 and modulus - 1 - r >= 0
+            let upper_bounds: &'a [S] = alloc.alloc_slice_fill_iter(
+                remainders
+                    .iter()
+                    .map(|&remainder| max_remainder - remainder),
+            );
+            final_round_evaluate_sign(builder, alloc, remainders);
+            final_round_evaluate_sign(builder, alloc, upper_bounds);
+
+            let (first_values, first_scalars) =
+                first_remainders.get_or_insert_with(|| (remainder_values.clone(), remainders));
+            dividends = remainder_values
+                .iter()
+                .zip(first_values.iter())
+                .map(|(&remainder, &first)| remainder * first)
+                .collect();
+            dividend_factors = vec![Box::new(remainders), Box::new(*first_scalars)];
+            residues = remainder_values;
+        }
+        let res = residue_column(alloc, residues.into_iter());
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let value_eval = self
+            .base
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        if self.exponent == 0 {
+            return Ok(chi_eval * S::from(self.one_mod_modulus()));
+        }
+        let modulus = S::from(self.abs_modulus());
+        let max_remainder = modulus - S::ONE;
+
+        let mut dividend_eval = value_eval;
+        let mut dividend_degree = 1;
+        let mut dividend_bits = self.base.data_type().bit_size();
+        let mut first_remainder_eval = None;
+        let mut remainder_eval = S::ZERO;
+        for _ in 0..self.exponent {
+            remainder_eval = builder.try_consume_final_round_mle_evaluation()?;
+            let quotient_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+            // dividend - modulus * q - r = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                dividend_eval - modulus * quotient_eval - remainder_eval,
+                dividend_degree,
+            )?;
+            // range check of the quotients
+            verifier_evaluate_sign(
+                builder,
+                quotient_eval,
+                chi_eval,
+                Some(self.quotient_bits(dividend_bits)),
+            )?;
+
+            let upper_bound_eval = max_remainder * chi_eval - remainder_eval;
+            for bound_eval in [remainder_eval, upper_bound_eval] {
+                if verifier_evaluate_sign(builder, bound_eval, chi_eval, None)? != S::ZERO {
+                    return Err(ProofError::VerificationError {
+                        error: "remainder of a modular power is not within the modulus",
+                    });
+                }
+            }
+
+            let first_eval = *first_remainder_eval.get_or_insert(remainder_eval);
+            dividend_eval = remainder_eval * first_eval;
+            dividend_degree = 2;
+            dividend_bits = self.product_bits();
+        }
+
+        Ok(remainder_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs([&*self.base], columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pow_mod;
+
+    #[test]
+    fn we_can_compute_a_modular_power() {
+        assert_eq!(pow_mod(3, 4, 7), 4);
+        assert_eq!(pow_mod(-3, 3, 7), 1);
+        assert_eq!(pow_mod(10, 0, 7), 1);
+        assert_eq!(pow_mod(10, 0, 1), 0);
+        assert_eq!(pow_mod(-10, 1, 7), 4);
+        assert_eq!(pow_mod(0, 5, 13), 0);
+        assert_eq!(pow_mod(i128::MIN, 2, 1 << 63), 0);
+        assert_eq!(pow_mod(i128::MAX, 15, 1 << 63), (1 << 63) - 1);
+        assert_eq!(
+            pow_mod(i128::from(i64::MAX), 2, i128::from(i64::MAX) - 1),
+            1
+        );
+    }
+}
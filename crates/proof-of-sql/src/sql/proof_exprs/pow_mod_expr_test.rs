@@ -0,0 +1,451 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, LiteralValue,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor, TestAccessor,
+        },
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{pow_mod_expr::PowModExpr, test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+/// `base^exponent mod |modulus|` by repeated multiplication, as a reference for the proof
+fn reference_pow_mod(base: i128, exponent: u8, modulus: i128) -> i64 {
+    let modulus = modulus.abs();
+    let base = base.rem_euclid(modulus);
+    let res = (0..exponent).fold(1 % modulus, |power, _| (power * base).rem_euclid(modulus));
+    i64::try_from(res).unwrap()
+}
+
+// select pow_mod(a, 3, 7) as p7, pow_mod(a, 5, 13) as p13, pow_mod(a, 2, -10) as pn10,
+//   pow_mod(a, 16, 2^61 - 1) as pl from sxt.t
+#[test]
+fn we_can_prove_modular_powers_of_a_column_with_several_moduli() {
+    let values = [
+        -151_i64,
+        -8,
+        -1,
+        0,
+        1,
+        2,
+        6,
+        12,
+        149,
+        1234,
+        i64::MIN,
+        i64::MAX,
+    ];
+    let data = owned_table([bigint("a", values)]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let large_modulus = (1_i64 << 61) - 1;
+    let ast = filter(
+        vec![
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(3),
+                    &const_bigint(7),
+                ),
+                "p7",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_int(5),
+                    &const_smallint(13),
+                ),
+                "p13",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(2),
+                    &const_int(-10),
+                ),
+                "pn10",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(16),
+                    &const_bigint(large_modulus),
+                ),
+                "pl",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected = |exponent, modulus| {
+        values
+            .iter()
+            .map(|&value| reference_pow_mod(value.into(), exponent, modulus))
+            .collect::<Vec<_>>()
+    };
+    let expected_res = owned_table([
+        bigint("p7", expected(3, 7)),
+        bigint("p13", expected(5, 13)),
+        bigint("pn10", expected(2, -10)),
+        bigint("pl", expected(16, large_modulus.into())),
+    ]);
+    assert_eq!(res, expected_res);
+    assert_eq!(expected(2, -10), [1_i64, 4, 1, 0, 1, 4, 6, 4, 1, 6, 4, 9]);
+}
+
+// select pow_mod(a, 0, 7) as e0, pow_mod(a, 1, 7) as e1, pow_mod(a, 0, -1) as e0m1,
+//   pow_mod(a, 4, 1) as m1 from sxt.t
+#[test]
+fn we_can_prove_modular_powers_with_trivial_exponents_and_moduli() {
+    let data = owned_table([smallint("a", [-9_i16, -7, 0, 3, 8])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(0),
+                    &const_bigint(7),
+                ),
+                "e0",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(1),
+                    &const_bigint(7),
+                ),
+                "e1",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(0),
+                    &const_bigint(-1),
+                ),
+                "e0m1",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(4),
+                    &const_bigint(1),
+                ),
+                "m1",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("e0", [1_i64; 5]),
+        bigint("e1", [5_i64, 0, 0, 3, 1]),
+        bigint("e0m1", [0_i64; 5]),
+        bigint("m1", [0_i64; 5]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select pow_mod(a, 3, 100) as a, pow_mod(b, 3, 100) as b, pow_mod(c, 2, 2^63) as c,
+//   pow_mod(d, 7, -(2^63)) as d, pow_mod(e, 2, 97) as e, pow_mod(f, 2, 97) as f from sxt.t
+#[test]
+fn we_can_prove_modular_powers_of_columns_of_every_integer_type() {
+    let data = owned_table([
+        tinyint("a", [i8::MIN, -15, 14, i8::MAX]),
+        uint8("b", [0_u8, 5, 14, u8::MAX]),
+        int128("c", [i128::MIN, -5, i128::MAX, 3_037_000_499]),
+        int128("d", [i128::MIN, -5, i128::MAX, 123]),
+        smallint("e", [i16::MIN, 25, -25, i16::MAX]),
+        int("f", [i32::MIN, -7, 50, i32::MAX]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(3),
+                    &const_bigint(100),
+                ),
+                "a",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "b", &accessor),
+                    &const_bigint(3),
+                    &const_bigint(100),
+                ),
+                "b",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "c", &accessor),
+                    &const_bigint(2),
+                    &const_int128(1 << 63),
+                ),
+                "c",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "d", &accessor),
+                    &const_bigint(7),
+                    &const_bigint(i64::MIN),
+                ),
+                "d",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "e", &accessor),
+                    &const_bigint(2),
+                    &const_bigint(97),
+                ),
+                "e",
+            ),
+            aliased_plan(
+                pow_mod(
+                    column(&t, "f", &accessor),
+                    &const_bigint(2),
+                    &const_bigint(97),
+                ),
+                "f",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let c_mod = 1_i128 << 63;
+    let expected_res = owned_table([
+        bigint(
+            "a",
+            [i8::MIN, -15, 14, i8::MAX].map(|v| reference_pow_mod(v.into(), 3, 100)),
+        ),
+        bigint(
+            "b",
+            [0_u8, 5, 14, u8::MAX].map(|v| reference_pow_mod(v.into(), 3, 100)),
+        ),
+        bigint(
+            "c",
+            [i128::MIN, -5, i128::MAX, 3_037_000_499].map(|v| reference_pow_mod(v, 2, c_mod)),
+        ),
+        bigint(
+            "d",
+            [i128::MIN, -5, i128::MAX, 123].map(|v| reference_pow_mod(v, 7, c_mod)),
+        ),
+        bigint(
+            "e",
+            [i16::MIN, 25, -25, i16::MAX].map(|v| reference_pow_mod(v.into(), 2, 97)),
+        ),
+        bigint(
+            "f",
+            [i32::MIN, -7, 50, i32::MAX].map(|v| reference_pow_mod(v.into(), 2, 97)),
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where pow_mod(a, 2, 7) = 2
+#[test]
+fn we_can_prove_a_filter_on_a_modular_power() {
+    let data = owned_table([bigint("a", [3_i64, 4, 5, -4, 10, 0, 7])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            pow_mod(
+                column(&t, "a", &accessor),
+                &const_bigint(2),
+                &const_bigint(7),
+            ),
+            const_bigint(2),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [3_i64, 4, -4, 10])]);
+    assert_eq!(res, expected_res);
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-50, 51);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([bigint("a", dist.sample_iter(&mut rng).take(n))]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![aliased_plan(
+                pow_mod(
+                    column(&t, "a", &accessor),
+                    &const_bigint(5),
+                    &const_bigint(11),
+                ),
+                "p",
+            )],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+
+        // Calculate/compare expected result
+        let expected_p: Vec<_> = data["a"]
+            .i64_iter()
+            .map(|&a| reference_pow_mod(a.into(), 5, 11))
+            .collect();
+        let expected_result = owned_table([bigint("p", expected_p)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_pow_mod_expr_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_int("a", [-3, -2, 2, 3], &alloc)]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let expr: DynProofExpr = pow_mod(column(&t, "a", &accessor), &const_int(3), &const_int(5));
+    let res = expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(res, Column::BigInt(&[3, 2, 3, 2]));
+    assert_eq!(expr.data_type(), ColumnType::BigInt);
+}
+
+#[test]
+fn we_cannot_take_a_modular_power_with_an_invalid_exponent_or_modulus() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [1, 2], &alloc),
+        borrowed_bigint("b", [3, 4], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for exponent in [
+        const_bigint(-1),
+        const_bigint(17),
+        const_int128(256),
+        const_bool(true),
+        const_decimal75(3, 1, 20),
+        column(&t, "b", &accessor),
+        DynProofExpr::try_new_placeholder(1, ColumnType::BigInt).unwrap(),
+    ] {
+        let res = PowModExpr::try_new(
+            Box::new(column(&t, "a", &accessor)),
+            &exponent,
+            &const_bigint(7),
+        );
+        assert!(matches!(
+            res,
+            Err(AnalyzeError::InvalidExponent { max_exponent: 16 })
+        ));
+    }
+    for modulus in [
+        const_bigint(0),
+        const_int128(0),
+        const_int128((1 << 63) + 1),
+        const_int128(-(1 << 63) - 1),
+        const_varchar("10"),
+        column(&t, "b", &accessor),
+    ] {
+        let res = PowModExpr::try_new(
+            Box::new(column(&t, "a", &accessor)),
+            &const_bigint(2),
+            &modulus,
+        );
+        assert!(matches!(res, Err(AnalyzeError::InvalidPowModModulus)));
+    }
+    let expr = PowModExpr::try_new(
+        Box::new(column(&t, "a", &accessor)),
+        &DynProofExpr::new_literal(LiteralValue::Uint8(16)),
+        &const_bigint(i64::MIN),
+    )
+    .unwrap();
+    assert_eq!(expr.exponent(), 16);
+    assert_eq!(expr.modulus(), i128::from(i64::MIN));
+}
+
+#[test]
+fn we_cannot_take_a_modular_power_of_an_expression_which_is_not_an_integer() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_varchar("a", ["x", "y"], &alloc),
+        borrowed_decimal75("b", 5, 1, [10, 20], &alloc),
+        borrowed_boolean("c", [true, false], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for column_id in ["a", "b", "c"] {
+        let res = PowModExpr::try_new(
+            Box::new(column(&t, column_id, &accessor)),
+            &const_bigint(2),
+            &const_bigint(10),
+        );
+        assert!(matches!(res, Err(AnalyzeError::InvalidDataType { .. })));
+    }
+}
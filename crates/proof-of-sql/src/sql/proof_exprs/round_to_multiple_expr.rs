@@ -0,0 +1,321 @@
+use super::{get_column_references_of_exprs, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_sign, verifier_evaluate_sign},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expression rounding an integer expression to the nearest multiple of a constant step,
+/// i.e. `ROUND_TO(expr, step) = step * ROUND(expr / step)`
+///
+/// Exact midpoints are rounded half away from zero, like `ROUND` in SQL, so that
+/// `ROUND_TO(150, 100) = 200` and `ROUND_TO(-150, 100) = -200`.
+/// Since the multiples of `step` and `-step` are the same, the sign of the step doesn't matter.
+///
+/// The result has the type of `expr`. Rows whose nearest multiple is out of range of that type
+/// can't be proven.
+///
+/// The prover commits to the quotients `q = ROUND(expr / step)`, which are range checked so that
+/// `step * q` can't wrap around. The remainder `r = expr - step * q` is then shown to satisfy
+/// `-|step| <= 2r < |step|` for non-negative and `-|step| < 2r <= |step|` for negative values,
+/// i.e. `step * q` is the nearest multiple with midpoints rounded away from zero.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundToMultipleExpr {
+    expr: Box<DynProofExpr>,
+    step: i128,
+}
+
+impl RoundToMultipleExpr {
+    /// Create an expression rounding `expr` to the nearest multiple of `step`
+    ///
+    /// `expr` must be an integer expression and `step` a nonzero integer literal.
+    pub fn try_new(expr: Box<DynProofExpr>, step: &DynProofExpr) -> AnalyzeResult<Self> {
+        let expr_type = expr.data_type();
+        if !expr_type.is_integer() {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        let step = match step {
+            DynProofExpr::Literal(literal) => match *literal.value() {
+                LiteralValue::Uint8(step) => i128::from(step),
+                LiteralValue::TinyInt(step) => i128::from(step),
+                LiteralValue::SmallInt(step) => i128::from(step),
+                LiteralValue::Int(step) => i128::from(step),
+                LiteralValue::BigInt(step) => i128::from(step),
+                LiteralValue::Int128(step) => step,
+                _ => return Err(AnalyzeError::InvalidRoundingStep),
+            },
+            _ => return Err(AnalyzeError::InvalidRoundingStep),
+        };
+        if step == 0 {
+            return Err(AnalyzeError::InvalidRoundingStep);
+        }
+        Ok(Self { expr, step })
+    }
+
+    /// Get the expression being rounded
+    pub fn expr(&self) -> &DynProofExpr {
+        &self.expr
+    }
+
+    /// Get the step whose multiples the expression is rounded to
+    pub fn step(&self) -> i128 {
+        self.step
+    }
+
+    /// The number of bits the quotients are range checked to
+    ///
+    /// With `2^k <= |step| < 2^(k+1)` and the values fitting in `n` bits, the quotients have
+    /// magnitude at most `2^(n-k)`. Allowing `n - k + 2` bits bounds `|step * q|` by `2^(n+2)`,
+    /// far below the modulus of the scalar field.
+    fn quotient_bits(&self) -> u8 {
+        let value_bits = self.expr.data_type().bit_size();
+        let step_log2 = 127 - self.step.unsigned_abs().leading_zeros();
+        u8::try_from((value_bits + 2).saturating_sub(step_log2).max(2))
+            .expect("Integer types have at most 128 bits")
+    }
+
+    /// The absolute value of the step as a scalar
+    fn abs_step<S: Scalar>(&self) -> S {
+        if self.step < 0 {
+            -S::from(self.step)
+        } else {
+            S::from(self.step)
+        }
+    }
+}
+
+/// The quotient by `step` of the multiple of `step` nearest to `value`, rounding half away from zero
+pub(crate) fn nearest_multiple_quotient(value: i128, step: i128) -> i128 {
+    let abs_step = step.unsigned_abs();
+    let abs_value = value.unsigned_abs();
+    let remainder = abs_value % abs_step;
+    let abs_quotient = abs_value / abs_step + u128::from(remainder >= abs_step - remainder);
+    // This only wraps if the rounded value is out of range of `i128`
+    #[expect(clippy::cast_possible_wrap)]
+    let quotient = abs_quotient as i128;
+    if (value < 0) == (step < 0) {
+        quotient
+    } else {
+        quotient.wrapping_neg()
+    }
+}
+
+/// Round `values` to the nearest multiples of `step`, returning the rounded values and their
+/// quotients by `step`
+///
+/// Rounded values out of range of `T` wrap around.
+fn round_slice<'a, T: Copy + Into<i128>>(
+    alloc: &'a Bump,
+    values: &[T],
+    step: i128,
+    wrapping_from_i128: impl Fn(i128) -> T,
+) -> (&'a [T], Vec<i128>) {
+    let quotients: Vec<i128> = values
+        .iter()
+        .map(|&value| nearest_multiple_quotient(value.into(), step))
+        .collect();
+    let rounded = alloc.alloc_slice_fill_iter(
+        quotients
+            .iter()
+            .map(|&quotient| wrapping_from_i128(quotient.wrapping_mul(step))),
+    );
+    (rounded, quotients)
+}
+
+/// Round the values of an integer column to the nearest multiples of `step`, returning the
+/// rounded column and the quotients by `step`
+///
+/// # Panics
+/// Panics if the column is not an integer column.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn round_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: Column<'a, S>,
+    step: i128,
+) -> (Column<'a, S>, Vec<i128>) {
+    match column {
+        Column::Uint8(values) => {
+            let (rounded, quotients) = round_slice(alloc, values, step, |v| v as u8);
+            (Column::Uint8(rounded), quotients)
+        }
+        Column::TinyInt(values) => {
+            let (rounded, quotients) = round_slice(alloc, values, step, |v| v as i8);
+            (Column::TinyInt(rounded), quotients)
+        }
+        Column::SmallInt(values) => {
+            let (rounded, quotients) = round_slice(alloc, values, step, |v| v as i16);
+            (Column::SmallInt(rounded), quotients)
+        }
+        Column::Int(values) => {
+            let (rounded, quotients) = round_slice(alloc, values, step, |v| v as i32);
+            (Column::Int(rounded), quotients)
+        }
+        Column::BigInt(values) => {
+            let (rounded, quotients) = round_slice(alloc, values, step, |v| v as i64);
+            (Column::BigInt(rounded), quotients)
+        }
+        Column::Int128(values) => {
+            let (rounded, quotients) = round_slice(alloc, values, step, |v| v);
+            (Column::Int128(rounded), quotients)
+        }
+        _ => panic!("Only integer columns can be rounded to a multiple"),
+    }
+}
+
+impl ProofExpr for RoundToMultipleExpr {
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[tracing::instrument(
+        name = "RoundToMultipleExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self.expr.first_round_evaluate(alloc, table, params)?;
+        let (res, _) = round_column(alloc, column, self.step);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(
+        name = "RoundToMultipleExpr::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let column = self
+            .expr
+            .final_round_evaluate(builder, alloc, table, params)?;
+        let (res, quotients) = round_column(alloc, column, self.step);
+        let values: &'a [S] = alloc.alloc_slice_fill_iter(column.to_scalar());
+        let quotients: &'a [S] = alloc.alloc_slice_fill_iter(quotients.into_iter().map(S::from));
+        builder.produce_intermediate_mle(quotients);
+
+        // sign(value) == -1, which decides the rounding direction of midpoints
+        let is_negative = final_round_evaluate_sign(builder, alloc, values);
+        // range check of the quotients
+        final_round_evaluate_sign(builder, alloc, quotients);
+
+        // The bounds on the remainders, which are all non-negative
+        let step = S::from(self.step);
+        let abs_step = self.abs_step::<S>();
+        let doubled_remainders = values
+            .iter()
+            .zip(quotients)
+            .map(|(&value, &quotient)| (value - step * quotient) * S::TWO);
+        let lower_bounds: &'a [S] =
+            alloc.alloc_slice_fill_iter(doubled_remainders.clone().zip(is_negative).map(
+                |(doubled_remainder, &is_negative)| {
+                    doubled_remainder + abs_step - S::from(is_negative)
+                },
+            ));
+        let upper_bounds: &'a [S] =
+            alloc.alloc_slice_fill_iter(doubled_remainders.zip(is_negative).map(
+                |(doubled_remainder, &is_negative)| {
+                    abs_step - S::ONE + S::from(is_negative) - doubled_remainder
+                },
+            ));
+        final_round_evaluate_sign(builder, alloc, lower_bounds);
+        final_round_evaluate_sign(builder, alloc, upper_bounds);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let value_eval = self
+            .expr
+            .verifier_evaluate(builder, accessor, chi_eval, params)?;
+        let quotient_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // sign(value) == -1
+        let is_negative_eval = verifier_evaluate_sign(builder, value_eval, chi_eval, None)?;
+        // range check of the quotients
+        verifier_evaluate_sign(builder, quotient_eval, chi_eval, Some(self.quotient_bits()))?;
+
+        let rounded_eval = S::from(self.step) * quotient_eval;
+        let doubled_remainder_eval = (value_eval - rounded_eval) * S::TWO;
+        let abs_step = self.abs_step::<S>();
+        let lower_bound_eval = doubled_remainder_eval + abs_step * chi_eval - is_negative_eval;
+        let upper_bound_eval =
+            (abs_step - S::ONE) * chi_eval + is_negative_eval - doubled_remainder_eval;
+        for bound_eval in [lower_bound_eval, upper_bound_eval] {
+            if verifier_evaluate_sign(builder, bound_eval, chi_eval, None)? != S::ZERO {
+                return Err(ProofError::VerificationError {
+                    error: "rounded value is not the nearest multiple of the step",
+                });
+            }
+        }
+
+        Ok(rounded_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs([&*self.expr], columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::nearest_multiple_quotient;
+
+    #[test]
+    fn we_can_compute_the_quotient_of_the_nearest_multiple() {
+        assert_eq!(nearest_multiple_quotient(0, 100), 0);
+        assert_eq!(nearest_multiple_quotient(149, 100), 1);
+        assert_eq!(nearest_multiple_quotient(150, 100), 2);
+        assert_eq!(nearest_multiple_quotient(-149, 100), -1);
+        assert_eq!(nearest_multiple_quotient(-150, 100), -2);
+        assert_eq!(nearest_multiple_quotient(-151, 100), -2);
+        assert_eq!(nearest_multiple_quotient(150, -100), -2);
+        assert_eq!(nearest_multiple_quotient(-150, -100), 2);
+        assert_eq!(nearest_multiple_quotient(4, 3), 1);
+        assert_eq!(nearest_multiple_quotient(5, 3), 2);
+        assert_eq!(nearest_multiple_quotient(-5, 3), -2);
+        assert_eq!(nearest_multiple_quotient(7, 1), 7);
+        assert_eq!(nearest_multiple_quotient(i128::MAX, 1), i128::MAX);
+        assert_eq!(nearest_multiple_quotient(i128::MIN, 1), i128::MIN);
+        assert_eq!(nearest_multiple_quotient(i128::MIN, i128::MIN), 1);
+        assert_eq!(nearest_multiple_quotient(i128::MAX, i128::MIN), -1);
+        assert_eq!(nearest_multiple_quotient(1000, i128::MAX), 0);
+    }
+}
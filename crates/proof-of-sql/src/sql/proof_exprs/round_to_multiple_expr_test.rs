@@ -0,0 +1,320 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, LiteralValue,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor, TestAccessor,
+        },
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{
+            round_to_multiple_expr::RoundToMultipleExpr, test_utility::*, DynProofExpr, ProofExpr,
+        },
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+// select round_to(a, 10) as r10, round_to(a, 100) as r100, round_to(a, 3) as r3,
+//   round_to(a, -100) as rn100 from sxt.t
+#[test]
+fn we_can_prove_a_bucketing_of_a_column_with_several_steps() {
+    let data = owned_table([bigint(
+        "a",
+        [-151_i64, -150, -149, -5, 0, 4, 5, 149, 150, 151, 1234],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                round_to_multiple(column(&t, "a", &accessor), &const_bigint(10)),
+                "r10",
+            ),
+            aliased_plan(
+                round_to_multiple(column(&t, "a", &accessor), &const_int(100)),
+                "r100",
+            ),
+            aliased_plan(
+                round_to_multiple(column(&t, "a", &accessor), &const_smallint(3)),
+                "r3",
+            ),
+            aliased_plan(
+                round_to_multiple(column(&t, "a", &accessor), &const_bigint(-100)),
+                "rn100",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint(
+            "r10",
+            [-150_i64, -150, -150, -10, 0, 0, 10, 150, 150, 150, 1230],
+        ),
+        bigint(
+            "r100",
+            [-200_i64, -200, -100, 0, 0, 0, 0, 100, 200, 200, 1200],
+        ),
+        bigint(
+            "r3",
+            [-150_i64, -150, -150, -6, 0, 3, 6, 150, 150, 150, 1233],
+        ),
+        bigint(
+            "rn100",
+            [-200_i64, -200, -100, 0, 0, 0, 0, 100, 200, 200, 1200],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select round_to(a, 10) as a, round_to(b, 10) as b, round_to(c, 50) as c,
+//   round_to(d, 100) as d, round_to(e, 10^20) as e from sxt.t
+#[test]
+fn we_can_prove_a_rounding_of_columns_of_every_integer_type() {
+    let data = owned_table([
+        tinyint("a", [-124_i8, -15, 14, 124]),
+        uint8("b", [0_u8, 5, 14, 250]),
+        smallint("c", [-32000_i16, 25, -25, 74]),
+        int("d", [1_000_000_149_i32, -7, 50, -50]),
+        int128(
+            "e",
+            [
+                123_456_789_012_345_678_901_234_567_i128,
+                -5,
+                i128::MAX,
+                -150_000_000_000_000_000_000,
+            ],
+        ),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                round_to_multiple(column(&t, "a", &accessor), &const_bigint(10)),
+                "a",
+            ),
+            aliased_plan(
+                round_to_multiple(column(&t, "b", &accessor), &const_bigint(10)),
+                "b",
+            ),
+            aliased_plan(
+                round_to_multiple(column(&t, "c", &accessor), &const_bigint(50)),
+                "c",
+            ),
+            aliased_plan(
+                round_to_multiple(column(&t, "d", &accessor), &const_bigint(100)),
+                "d",
+            ),
+            aliased_plan(
+                round_to_multiple(
+                    column(&t, "e", &accessor),
+                    &const_int128(100_000_000_000_000_000_000),
+                ),
+                "e",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        tinyint("a", [-120_i8, -20, 10, 120]),
+        uint8("b", [0_u8, 10, 10, 250]),
+        smallint("c", [-32000_i16, 50, -50, 50]),
+        int("d", [1_000_000_100_i32, 0, 100, -100]),
+        int128(
+            "e",
+            [
+                123_456_800_000_000_000_000_000_000_i128,
+                0,
+                170_141_183_460_469_231_700_000_000_000_000_000_000,
+                -200_000_000_000_000_000_000,
+            ],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where round_to(a, 10) = 20
+#[test]
+fn we_can_prove_a_filter_on_a_rounded_column() {
+    let data = owned_table([bigint("a", [14_i64, 15, 24, 25, -20, 20])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            round_to_multiple(column(&t, "a", &accessor), &const_bigint(10)),
+            const_bigint(20),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [15_i64, 24, 20])]);
+    assert_eq!(res, expected_res);
+}
+
+// select round_to(a, 10) as r from sxt.t where the rounding of 125 overflows a TINYINT
+#[test]
+fn we_cannot_verify_a_rounding_which_overflows_the_data_type() {
+    let data = owned_table([tinyint("a", [125_i8, 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            round_to_multiple(column(&t, "a", &accessor), &const_bigint(10)),
+            "r",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    assert!(verifiable_res.verify(&ast, &accessor, &(), &[]).is_err());
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-50, 51);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([bigint("a", dist.sample_iter(&mut rng).take(n))]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![aliased_plan(
+                round_to_multiple(column(&t, "a", &accessor), &const_bigint(8)),
+                "r",
+            )],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+
+        // Calculate/compare expected result
+        let expected_r: Vec<_> = data["a"]
+            .i64_iter()
+            .map(|a| a.signum() * (a.abs() + 4) / 8 * 8)
+            .collect();
+        let expected_result = owned_table([bigint("r", expected_r)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_round_to_multiple_expr_using_first_round_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_int("a", [-25, -24, 24, 25], &alloc)]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let expr: DynProofExpr = round_to_multiple(column(&t, "a", &accessor), &const_int(10));
+    let res = expr.first_round_evaluate(&alloc, &data, &[]).unwrap();
+    assert_eq!(res, Column::Int(&[-30, -20, 20, 30]));
+    assert_eq!(expr.data_type(), ColumnType::Int);
+}
+
+#[test]
+fn we_cannot_round_to_a_step_which_is_not_a_nonzero_integer_literal() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [1, 2], &alloc),
+        borrowed_bigint("b", [3, 4], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for step in [
+        const_bigint(0),
+        const_int128(0),
+        const_bool(true),
+        const_varchar("10"),
+        const_decimal75(3, 1, 100),
+        column(&t, "b", &accessor),
+        DynProofExpr::try_new_placeholder(1, ColumnType::BigInt).unwrap(),
+    ] {
+        let res = RoundToMultipleExpr::try_new(Box::new(column(&t, "a", &accessor)), &step);
+        assert!(matches!(res, Err(AnalyzeError::InvalidRoundingStep)));
+    }
+    assert_eq!(
+        RoundToMultipleExpr::try_new(
+            Box::new(column(&t, "a", &accessor)),
+            &DynProofExpr::new_literal(LiteralValue::Uint8(5)),
+        )
+        .unwrap()
+        .step(),
+        5
+    );
+}
+
+#[test]
+fn we_cannot_round_an_expression_which_is_not_an_integer() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_varchar("a", ["x", "y"], &alloc),
+        borrowed_decimal75("b", 5, 1, [10, 20], &alloc),
+        borrowed_boolean("c", [true, false], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data, 0);
+    for column_id in ["a", "b", "c"] {
+        let res = RoundToMultipleExpr::try_new(
+            Box::new(column(&t, column_id, &accessor)),
+            &const_bigint(10),
+        );
+        assert!(matches!(res, Err(AnalyzeError::InvalidDataType { .. })));
+    }
+}
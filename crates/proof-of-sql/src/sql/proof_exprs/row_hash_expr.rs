@@ -0,0 +1,147 @@
+use super::{get_column_references_of_exprs, ColumnExpr, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof_gadgets::{final_round_evaluate_row_hash, row_hash, verifier_evaluate_row_hash},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{string::ToString, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expression hashing the values of several columns in each row
+///
+/// The result has type [`ColumnType::Scalar`], so it can be compared with a committed column of
+/// stored hashes to detect rows which were tampered with.
+///
+/// The columns are hashed in the canonical order of ascending column identifiers, compared by
+/// their values, so the hash doesn't depend on the order in which the columns are listed.
+/// Each value is hashed as its scalar, i.e. booleans as `0` or `1`, numbers as their value
+/// without scale and strings and bytes as the hashes used for commitments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowHashExpr {
+    columns: Vec<DynProofExpr>,
+}
+
+impl RowHashExpr {
+    /// Create an expression hashing the values of `columns` in each row
+    ///
+    /// There must be at least one column and no column may be listed twice.
+    pub fn try_new(columns: Vec<ColumnExpr>) -> AnalyzeResult<Self> {
+        Ok(Self {
+            columns: canonical_hash_columns(columns)?,
+        })
+    }
+
+    /// Get the hashed columns, in their canonical order
+    pub fn columns(&self) -> &[DynProofExpr] {
+        &self.columns
+    }
+}
+
+/// Sort the columns of a row hash into their canonical order of ascending column identifiers
+///
+/// There must be at least one column and no column may be listed twice.
+pub(super) fn canonical_hash_columns(
+    mut columns: Vec<ColumnExpr>,
+) -> AnalyzeResult<Vec<DynProofExpr>> {
+    if columns.is_empty() {
+        return Err(AnalyzeError::EmptyRowHash);
+    }
+    columns.sort_by(|a, b| a.column_id().value.cmp(&b.column_id().value));
+    if let Some(pair) = columns
+        .windows(2)
+        .find(|pair| pair[0].column_id().value == pair[1].column_id().value)
+    {
+        return Err(AnalyzeError::DuplicateIdent {
+            ident: pair[0].column_id().to_string(),
+        });
+    }
+    Ok(columns.into_iter().map(DynProofExpr::Column).collect())
+}
+
+impl ProofExpr for RowHashExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Scalar
+    }
+
+    #[tracing::instrument(name = "RowHashExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                Ok(column
+                    .first_round_evaluate(alloc, table, params)?
+                    .to_scalar())
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let hashes = alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            row_hash(&columns.iter().map(|column| column[i]).collect::<Vec<_>>())
+        });
+
+        log::log_memory_usage("End");
+
+        Ok(Column::Scalar(hashes))
+    }
+
+    #[tracing::instrument(name = "RowHashExpr::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Column<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| {
+                let values = column.final_round_evaluate(builder, alloc, table, params)?;
+                Ok(&*alloc.alloc_slice_fill_iter(values.to_scalar()))
+            })
+            .collect::<PlaceholderResult<Vec<&'a [S]>>>()?;
+        let hashes = final_round_evaluate_row_hash(builder, alloc, &columns, table.num_rows());
+
+        log::log_memory_usage("End");
+
+        Ok(Column::Scalar(hashes))
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<Ident, S>,
+        chi_eval: S,
+        params: &[LiteralValue],
+    ) -> Result<S, ProofError> {
+        let column_evals = self
+            .columns
+            .iter()
+            .map(|column| column.verifier_evaluate(builder, accessor, chi_eval, params))
+            .collect::<Result<Vec<_>, _>>()?;
+        verifier_evaluate_row_hash(builder, &column_evals, chi_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        get_column_references_of_exprs(&self.columns, columns);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, ColumnRef, ColumnType, OwnedTableTestAccessor, TableRef,
+        },
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{row_hash_expr::RowHashExpr, test_utility::*, ColumnExpr, DynProofExpr},
+        proof_gadgets::row_hash as hash_values,
+        proof_plans::test_utility::*,
+        AnalyzeError,
+    },
+};
+
+/// The hashes of rows with the values `a`, `b` and `c`, in this order
+fn stored_hashes(a: &[i64], b: &[&str], c: &[bool]) -> Vec<Curve25519Scalar> {
+    a.iter()
+        .zip(b)
+        .zip(c)
+        .map(|((&a, &b), &c)| {
+            hash_values(&[
+                Curve25519Scalar::from(a),
+                Curve25519Scalar::from(b),
+                Curve25519Scalar::from(c),
+            ])
+        })
+        .collect()
+}
+
+// select a, row_hash(c, a, b) = h as matches from sxt.t
+#[test]
+fn we_can_prove_which_rows_match_their_stored_hashes() {
+    let a = [1_i64, -2, 3, 4, 0];
+    let b = ["x", "y", "z", "", "w"];
+    let c = [true, false, true, false, true];
+    let hashes = stored_hashes(&a, &b, &c);
+    // Tamper with the second and fourth rows after hashing
+    let data = owned_table([
+        bigint("a", [1_i64, -2, 3, 5, 0]),
+        varchar("b", ["x", "Y", "z", "", "w"]),
+        boolean("c", c),
+        scalar("h", hashes),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            col_expr_plan(&t, "a", &accessor),
+            aliased_plan(
+                equal(
+                    row_hash(&t, &["c", "a", "b"], &accessor),
+                    column(&t, "h", &accessor),
+                ),
+                "matches",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, -2, 3, 5, 0]),
+        boolean("matches", [true, false, true, false, true]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a, b from sxt.t where row_hash(b, a) <> h
+#[test]
+fn we_can_prove_a_filter_of_tampered_rows() {
+    let a = [10_i64, 20, 30];
+    let b = ["p", "q", "r"];
+    let hashes: Vec<_> = a
+        .iter()
+        .zip(b)
+        .map(|(&a, b)| hash_values(&[Curve25519Scalar::from(a), Curve25519Scalar::from(b)]))
+        .collect();
+    let data = owned_table([
+        bigint("a", [10_i64, 21, 30]),
+        varchar("b", b),
+        scalar("h", hashes),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        not(equal(
+            row_hash(&t, &["b", "a"], &accessor),
+            column(&t, "h", &accessor),
+        )),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [21_i64]), varchar("b", ["q"])]);
+    assert_eq!(res, expected_res);
+}
+
+// select row_hash(a, b) as h from sxt.t
+#[test]
+fn we_can_prove_row_hashes_of_an_empty_table() {
+    let data = owned_table([bigint("a", [0_i64; 0]), int("b", [0_i32; 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(row_hash(&t, &["a", "b"], &accessor), "h")],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([scalar::<Curve25519Scalar>("h", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn the_columns_of_a_row_hash_are_in_ascending_order_of_their_identifiers() {
+    let t = TableRef::new("sxt", "t");
+    let column =
+        |name: &str| ColumnExpr::new(ColumnRef::new(t.clone(), name.into(), ColumnType::BigInt));
+    let expr = RowHashExpr::try_new(vec![column("c"), column("a"), column("b")]).unwrap();
+    assert_eq!(
+        expr,
+        RowHashExpr::try_new(vec![column("a"), column("b"), column("c")]).unwrap()
+    );
+    assert_eq!(
+        expr.columns(),
+        [column("a"), column("b"), column("c")].map(DynProofExpr::Column)
+    );
+}
+
+#[test]
+fn we_cannot_hash_no_columns_or_a_column_twice() {
+    let t = TableRef::new("sxt", "t");
+    let column =
+        |name: &str| ColumnExpr::new(ColumnRef::new(t.clone(), name.into(), ColumnType::BigInt));
+    assert!(matches!(
+        RowHashExpr::try_new(vec![]),
+        Err(AnalyzeError::EmptyRowHash)
+    ));
+    assert!(matches!(
+        RowHashExpr::try_new(vec![column("b"), column("a"), column("b")]),
+        Err(AnalyzeError::DuplicateIdent { ident }) if ident == "b"
+    ));
+}
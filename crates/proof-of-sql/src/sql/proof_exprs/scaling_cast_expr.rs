@@ -1,4 +1,5 @@
 use super::{
+    get_column_references_of_exprs,
     numerical_util::{cast_column_with_scaling, try_get_scaling_factor_with_precision_and_scale},
     DynProofExpr, ProofExpr,
 };
@@ -7,7 +8,7 @@ use crate::{
         database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
         map::{IndexMap, IndexSet},
         proof::{PlaceholderResult, ProofError},
-        scalar::Scalar,
+        scalar::{Scalar, ScalarExt},
     },
     sql::{
         proof::{FinalRoundBuilder, VerificationBuilder},
@@ -19,11 +20,14 @@ use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
+/// Provable expression for a cast that multiplies by a power of ten to change the scale
+///
+/// Only the types are stored. The scaling factor is converted to a scalar when the expression is
+/// proven or verified, so the same expression can be used with any commitment scheme.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct ScalingCastExpr {
     from_expr: Box<DynProofExpr>,
     to_type: ColumnType,
-    scaling_factor: [u64; 4],
 }
 
 impl ScalingCastExpr {
@@ -31,16 +35,17 @@ impl ScalingCastExpr {
     pub fn try_new(from_expr: Box<DynProofExpr>, to_type: ColumnType) -> AnalyzeResult<Self> {
         let from_datatype = from_expr.data_type();
         try_get_scaling_factor_with_precision_and_scale(from_datatype, to_type)
-            .map(|(scaling_factor, _, _)| Self {
-                from_expr,
-                to_type,
-                scaling_factor: scaling_factor.into(),
-            })
+            .map(|_| Self { from_expr, to_type })
             .map_err(|_| AnalyzeError::DataTypeMismatch {
                 left_type: from_datatype.to_string(),
                 right_type: to_type.to_string(),
             })
     }
+
+    /// Get the expression being cast
+    pub fn from_expr(&self) -> &DynProofExpr {
+        &self.from_expr
+    }
 }
 
 impl ProofExpr for ScalingCastExpr {
@@ -86,12 +91,19 @@ impl ProofExpr for ScalingCastExpr {
         chi_eval: S,
         params: &[LiteralValue],
     ) -> Result<S, ProofError> {
+        let (scaling_factor, _, _) = try_get_scaling_factor_with_precision_and_scale(
+            self.from_expr.data_type(),
+            self.to_type,
+        )
+        .map_err(|_| ProofError::UnsupportedQueryPlan {
+            error: "ScalingCastExpr has types that can not be scale cast",
+        })?;
         self.from_expr
             .verifier_evaluate(builder, accessor, chi_eval, params)
-            .map(|unscaled_eval| S::from(self.scaling_factor) * unscaled_eval)
+            .map(|unscaled_eval| S::from_wrapping(scaling_factor) * unscaled_eval)
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
-        self.from_expr.get_column_references(columns);
+        get_column_references_of_exprs([&*self.from_expr], columns);
     }
 }
@@ -92,6 +92,104 @@ pub fn scaling_cast(left: DynProofExpr, right: ColumnType) -> DynProofExpr {
     DynProofExpr::try_new_scaling_cast(left, right).unwrap()
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_if()` returns an error.
+pub fn if_expr(
+    condition: DynProofExpr,
+    then_expr: DynProofExpr,
+    else_expr: DynProofExpr,
+) -> DynProofExpr {
+    DynProofExpr::try_new_if(condition, then_expr, else_expr).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_round_to_multiple()` returns an error.
+pub fn round_to_multiple(expr: DynProofExpr, step: &DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_round_to_multiple(expr, step).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_modulo()` returns an error.
+pub fn modulo(expr: DynProofExpr, modulus: &DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_modulo(expr, modulus).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_pow_mod()` returns an error.
+pub fn pow_mod(
+    base: DynProofExpr,
+    exponent: &DynProofExpr,
+    modulus: &DynProofExpr,
+) -> DynProofExpr {
+    DynProofExpr::try_new_pow_mod(base, exponent, modulus).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_decimal_rescale()` returns an error.
+pub fn decimal_rescale(expr: DynProofExpr, to_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::try_new_decimal_rescale(expr, to_type).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_ln()` returns an error.
+pub fn ln(expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_ln(expr).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_isqrt()` returns an error.
+pub fn isqrt(expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_isqrt(expr).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_in()` returns an error.
+pub fn in_list(expr: DynProofExpr, values: impl IntoIterator<Item = LiteralValue>) -> DynProofExpr {
+    DynProofExpr::try_new_in(expr, values.into_iter().collect()).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `accessor.lookup_column()` returns `None`, indicating a column is not found.
+/// - `DynProofExpr::try_new_row_hash()` returns an error.
+pub fn row_hash(tab: &TableRef, names: &[&str], accessor: &impl SchemaAccessor) -> DynProofExpr {
+    DynProofExpr::try_new_row_hash(
+        names
+            .iter()
+            .map(|name| ColumnExpr::new(col_ref(tab, name, accessor)))
+            .collect(),
+    )
+    .unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `accessor.lookup_column()` returns `None`, indicating a column is not found.
+/// - `DynProofExpr::try_new_derived_key()` returns an error.
+pub fn derived_key(
+    namespace: [u8; 16],
+    tab: &TableRef,
+    names: &[&str],
+    accessor: &impl SchemaAccessor,
+) -> DynProofExpr {
+    DynProofExpr::try_new_derived_key(
+        namespace,
+        names
+            .iter()
+            .map(|name| ColumnExpr::new(col_ref(tab, name, accessor)))
+            .collect(),
+    )
+    .unwrap()
+}
+
 pub fn const_bool(val: bool) -> DynProofExpr {
     DynProofExpr::new_literal(LiteralValue::Boolean(val))
 }
@@ -1,3 +1,12 @@
+//! Prove that every row of some candidate columns occurs in some source columns.
+//!
+//! Each row of the candidate and source columns is looked up once, so the cost is linear in the
+//! number of candidate rows plus the number of source rows. There is no bloom filter variant for
+//! huge source columns, because it would cost more than this exact check. The `k` filter positions
+//! of an integer candidate could be proven with `ModuloExpr`, but each position then needs its own
+//! lookup into the set positions of the filter. That is `k` lookups per candidate row instead of
+//! one, on top of the quotient and sign checks of `k` remainders. The set positions of `n` source
+//! values are up to `k * n` rows, compared with `n` rows here.
 use crate::{
     base::{
         database::{join_util::get_multiplicities, Column},
@@ -14,11 +14,16 @@ pub(crate) use membership_check::{
 mod membership_check_test;
 #[expect(unused_imports)]
 use permutation_check::{final_round_evaluate_permutation_check, verify_permutation_check};
+pub(crate) use permutation_check::{
+    final_round_evaluate_permutation_difference, verify_permutation_difference,
+};
 #[cfg(test)]
 mod permutation_check_test;
 use shift::{final_round_evaluate_shift, first_round_evaluate_shift, verify_shift};
+mod row_hash;
 #[cfg(test)]
 mod shift_test;
+pub(crate) use row_hash::{final_round_evaluate_row_hash, row_hash, verifier_evaluate_row_hash};
 mod sign_expr;
 pub(crate) use sign_expr::{
     final_round_evaluate_sign, first_round_evaluate_sign, verifier_evaluate_sign,
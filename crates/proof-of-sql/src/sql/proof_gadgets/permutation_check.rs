@@ -128,9 +128,175 @@ pub(crate) fn verify_permutation_check<S: Scalar>(
     Ok(())
 }
 
+/// Perform final round evaluation of the difference between the sides of a permutation check.
+///
+/// Unlike [`final_round_evaluate_permutation_check`], the columns may have different lengths and
+/// need not be permutations of each other. The one-row MLE `difference` is produced, with
+/// `sum c_star - d_star - difference = 0`, and its value is returned. It is zero exactly when the
+/// rows of `candidates` are a permutation of the rows of `columns`, with overwhelming probability.
+/// `difference - difference * singleton_chi = 0` keeps it zero after its first row, so the sum
+/// can not be spread over several rows.
+///
+/// # Panics
+/// Panics if the number of source and candidate columns are not equal
+/// or if the number of columns is zero.
+#[expect(clippy::too_many_arguments)]
+pub(crate) fn final_round_evaluate_permutation_difference<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    chi_n: &'a [bool],
+    chi_m: &'a [bool],
+    columns: &[Column<'a, S>],
+    candidates: &[Column<'a, S>],
+) -> S {
+    assert_eq!(
+        columns.len(),
+        candidates.len(),
+        "The number of source and candidate columns should be equal"
+    );
+    assert!(
+        !columns.is_empty(),
+        "The number of source columns should be greater than 0"
+    );
+    // Fold the columns
+    let c_fold = alloc.alloc_slice_fill_copy(chi_n.len(), Zero::zero());
+    fold_columns(c_fold, alpha, beta, columns);
+    let d_fold = alloc.alloc_slice_fill_copy(chi_m.len(), Zero::zero());
+    fold_columns(d_fold, alpha, beta, candidates);
+
+    let c_star = alloc.alloc_slice_copy(c_fold);
+    slice_ops::add_const::<S, S>(c_star, One::one());
+    slice_ops::batch_inversion(c_star);
+
+    let d_star = alloc.alloc_slice_copy(d_fold);
+    slice_ops::add_const::<S, S>(d_star, One::one());
+    slice_ops::batch_inversion(d_star);
+
+    let difference = c_star.iter().copied().sum::<S>() - d_star.iter().copied().sum::<S>();
+    let alloc_difference = alloc.alloc_slice_copy(&[difference]);
+
+    builder.produce_intermediate_mle(c_star as &[_]);
+    builder.produce_intermediate_mle(d_star as &[_]);
+    builder.produce_intermediate_mle(alloc_difference as &[_]);
+
+    // difference - difference * singleton_chi = 0
+    let singleton_chi: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(alloc_difference as &[_])]),
+            (
+                -S::one(),
+                vec![
+                    Box::new(alloc_difference as &[_]),
+                    Box::new(singleton_chi as &[_]),
+                ],
+            ),
+        ],
+    );
+
+    // sum c_star - d_star - difference = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(c_star as &[_])]),
+            (-S::one(), vec![Box::new(d_star as &[_])]),
+            (-S::one(), vec![Box::new(alloc_difference as &[_])]),
+        ],
+    );
+
+    // c_star + c_fold * c_star - chi_n = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(c_star as &[_])]),
+            (
+                S::one(),
+                vec![Box::new(c_star as &[_]), Box::new(c_fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(chi_n as &[_])]),
+        ],
+    );
+
+    // d_star + d_fold * d_star - chi_m = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(d_star as &[_])]),
+            (
+                S::one(),
+                vec![Box::new(d_star as &[_]), Box::new(d_fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(chi_m as &[_])]),
+        ],
+    );
+    difference
+}
+
+/// Verify the difference between the sides of a permutation check, returning the evaluation of
+/// the one-row `difference` MLE.
+#[expect(clippy::similar_names, clippy::too_many_arguments)]
+pub(crate) fn verify_permutation_difference<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    chi_n_eval: S,
+    chi_m_eval: S,
+    singleton_chi_eval: S,
+    column_evals: &[S],
+    candidate_evals: &[S],
+) -> Result<S, ProofError> {
+    // Check that the source and candidate columns have the same amount of columns
+    if column_evals.len() != candidate_evals.len() {
+        return Err(ProofError::VerificationError {
+            error: "The number of source and candidate columns should be equal",
+        });
+    }
+    let c_fold_eval = fold_vals(beta, column_evals);
+    let d_fold_eval = fold_vals(beta, candidate_evals);
+    let c_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let d_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let difference_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // difference - difference * singleton_chi = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        difference_eval - difference_eval * singleton_chi_eval,
+        2,
+    )?;
+
+    // sum c_star - d_star - difference = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        c_star_eval - d_star_eval - difference_eval,
+        1,
+    )?;
+
+    // c_star + c_fold * c_star - chi_n = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        (S::ONE + alpha * c_fold_eval) * c_star_eval - chi_n_eval,
+        2,
+    )?;
+
+    // d_star + d_fold * d_star - chi_m = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        (S::ONE + alpha * d_fold_eval) * d_star_eval - chi_m_eval,
+        2,
+    )?;
+
+    Ok(difference_eval)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{final_round_evaluate_permutation_check, verify_permutation_check};
+    use super::{
+        final_round_evaluate_permutation_check, final_round_evaluate_permutation_difference,
+        verify_permutation_check, verify_permutation_difference,
+    };
     use crate::{
         base::{
             database::table_utility::borrowed_bigint,
@@ -188,4 +354,55 @@ mod tests {
             .iter()
             .all(|v| *v));
     }
+
+    #[test]
+    fn we_can_prove_the_difference_of_columns_that_are_not_permutations() {
+        let alloc = Bump::new();
+        let column = borrowed_bigint::<TestScalar>("a", [1, 2, 3], &alloc).1;
+        let candidate_table = borrowed_bigint::<TestScalar>("c", [3, 2], &alloc).1;
+        let chi_n: &[bool] = &[true, true, true];
+        let chi_m: &[bool] = &[true, true];
+        let first_round_builder: FirstRoundBuilder<'_, _> = FirstRoundBuilder::new(3);
+        let mut final_round_builder: FinalRoundBuilder<TestScalar> =
+            FinalRoundBuilder::new(3, VecDeque::new());
+        let difference = final_round_evaluate_permutation_difference(
+            &mut final_round_builder,
+            &alloc,
+            TestScalar::TWO,
+            TestScalar::TEN,
+            chi_n,
+            chi_m,
+            &[column],
+            &[candidate_table],
+        );
+        // Only `1` is unmatched, and `1 / (1 + 2 * 1) = 1 / 3`
+        assert_eq!(difference * TestScalar::from(3u8), TestScalar::ONE);
+        let verification_builder = run_verify_for_each_row(
+            3,
+            &first_round_builder,
+            &final_round_builder,
+            3,
+            |verification_builder, chi_eval, evaluation_point| {
+                verify_permutation_difference(
+                    verification_builder,
+                    TestScalar::TWO,
+                    TestScalar::TEN,
+                    chi_eval,
+                    chi_m.inner_product(evaluation_point),
+                    [true].inner_product(evaluation_point),
+                    &[column.inner_product(evaluation_point)],
+                    &[candidate_table.inner_product(evaluation_point)],
+                )
+                .unwrap();
+            },
+        );
+        assert!(verification_builder
+            .get_identity_results()
+            .iter()
+            .all(|v| v.iter().all(|val| *val)));
+        assert!(verification_builder
+            .get_zero_sum_results()
+            .iter()
+            .all(|v| *v));
+    }
 }
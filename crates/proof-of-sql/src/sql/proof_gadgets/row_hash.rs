@@ -0,0 +1,147 @@
+//! A hash of the values of each row of several columns, which can be proven with sumcheck.
+//!
+//! The hash is built from the `MiMC-7` block cipher `E_k(x)`, which has 91 rounds
+//! `x <- (x + k + c_i)^7` followed by a final `x + k`. Since `gcd(7, p - 1) = 1` for the scalar
+//! fields of all supported commitment schemes, every round is a permutation.
+//! The values `v_1, ..., v_m` of a row are compressed in the Miyaguchi-Preneel mode, i.e.
+//! `h_0 = 0` and `h_j = E_{h_(j-1)}(v_j) + h_(j-1) + v_j`, and the hash of the row is `h_m`.
+//!
+//! Each round is proven with the two intermediate columns `a = s^3` and `x' = a^2 * s`, where
+//! `s = x + k + c_i`, so every constraint has degree 3.
+use crate::{
+    base::{
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+
+/// The number of rounds of the cipher, `ceil(log_7(2^253))`
+const NUM_ROUNDS: u32 = 91;
+
+/// The round constants of the cipher
+///
+/// The first constant is zero and the others are hashes of their index.
+fn round_constants<S: Scalar>() -> Vec<S> {
+    (0..NUM_ROUNDS)
+        .map(|i| {
+            if i == 0 {
+                S::ZERO
+            } else {
+                S::from_byte_slice_via_hash(
+                    &[b"proof-of-sql row hash".as_slice(), &i.to_le_bytes()].concat(),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Compute the hash of the values of a row, in the order given
+pub(crate) fn row_hash<S: Scalar>(values: &[S]) -> S {
+    let constants = round_constants::<S>();
+    values.iter().fold(S::ZERO, |key, &value| {
+        let x = constants.iter().fold(value, |x, &constant| {
+            let s = x + key + constant;
+            let a = s * s * s;
+            a * a * s
+        });
+        x + key + key + value
+    })
+}
+
+/// Prove the hashes of the rows of `columns`, in the order given, and return them
+///
+/// All columns must have `num_rows` rows.
+pub(crate) fn final_round_evaluate_row_hash<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    columns: &[&'a [S]],
+    num_rows: usize,
+) -> &'a [S] {
+    let constants = round_constants::<S>();
+    let mut keys: &'a [S] = alloc.alloc_slice_fill_copy(num_rows, S::ZERO);
+    for &values in columns {
+        let mut xs = values;
+        for &constant in &constants {
+            let s: &'a [S] = alloc.alloc_slice_fill_with(num_rows, |i| xs[i] + keys[i] + constant);
+            let a: &'a [S] = alloc.alloc_slice_fill_with(num_rows, |i| s[i] * s[i] * s[i]);
+            let next_xs: &'a [S] = alloc.alloc_slice_fill_with(num_rows, |i| a[i] * a[i] * s[i]);
+            builder.produce_intermediate_mle(a);
+            builder.produce_intermediate_mle(next_xs);
+
+            // a - s^3 = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(a)]),
+                    (-S::one(), vec![Box::new(s), Box::new(s), Box::new(s)]),
+                ],
+            );
+            // x' - a^2 * s = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(next_xs)]),
+                    (-S::one(), vec![Box::new(a), Box::new(a), Box::new(s)]),
+                ],
+            );
+            xs = next_xs;
+        }
+        keys = alloc.alloc_slice_fill_with(num_rows, |i| xs[i] + keys[i] + keys[i] + values[i]);
+    }
+    keys
+}
+
+/// Verify the hashes of the rows of columns with the evaluations `column_evals`, in the order
+/// given, and return the evaluation of the hashes
+pub(crate) fn verifier_evaluate_row_hash<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    column_evals: &[S],
+    chi_eval: S,
+) -> Result<S, ProofError> {
+    let constants = round_constants::<S>();
+    let mut key_eval = S::ZERO;
+    for &value_eval in column_evals {
+        let mut x_eval = value_eval;
+        for &constant in &constants {
+            let s_eval = x_eval + key_eval + constant * chi_eval;
+            let a_eval = builder.try_consume_final_round_mle_evaluation()?;
+            let next_x_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+            // a - s^3 = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                a_eval - s_eval * s_eval * s_eval,
+                3,
+            )?;
+            // x' - a^2 * s = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                next_x_eval - a_eval * a_eval * s_eval,
+                3,
+            )?;
+            x_eval = next_x_eval;
+        }
+        key_eval = x_eval + key_eval + key_eval + value_eval;
+    }
+    Ok(key_eval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::row_hash;
+    use crate::base::scalar::test_scalar::TestScalar;
+
+    #[test]
+    fn we_can_hash_rows_depending_on_the_order_of_their_values() {
+        let hash = row_hash(&[TestScalar::from(1), TestScalar::from(2)]);
+        assert_eq!(hash, row_hash(&[TestScalar::from(1), TestScalar::from(2)]));
+        assert_ne!(hash, row_hash(&[TestScalar::from(2), TestScalar::from(1)]));
+        assert_ne!(hash, row_hash(&[TestScalar::from(1), TestScalar::from(3)]));
+        assert_ne!(hash, row_hash(&[TestScalar::from(1)]));
+        assert_ne!(row_hash(&[TestScalar::from(0)]), TestScalar::from(0));
+        assert_eq!(row_hash::<TestScalar>(&[]), TestScalar::from(0));
+    }
+}
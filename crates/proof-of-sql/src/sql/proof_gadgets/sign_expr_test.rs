@@ -2,11 +2,13 @@ use super::{final_round_evaluate_sign, first_round_evaluate_sign, verifier_evalu
 use crate::{
     base::{
         bit::BitDistribution,
+        map::IndexMap,
         polynomial::MultilinearExtension,
         scalar::{test_scalar::TestScalar, Scalar},
     },
     sql::proof::{
         FinalRoundBuilder, SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilderImpl,
+        VerificationOptions,
     },
 };
 use alloc::collections::VecDeque;
@@ -64,6 +66,8 @@ fn we_can_verify_a_constant_decomposition() {
         Vec::new(),
         Vec::new(),
         3,
+        IndexMap::default(),
+        VerificationOptions::default(),
     );
     let data_eval = (&data).evaluate_at_point(&evaluation_point);
     let eval = verifier_evaluate_sign(&mut builder, data_eval, *chi_eval, Some(8)).unwrap();
@@ -98,6 +102,8 @@ fn verification_of_constant_data_fails_if_the_commitment_doesnt_match_the_bit_di
         Vec::new(),
         Vec::new(),
         3,
+        IndexMap::default(),
+        VerificationOptions::default(),
     );
     let data_eval = TestScalar::from(2) * (&data).evaluate_at_point(&evaluation_point);
     assert!(verifier_evaluate_sign(&mut builder, data_eval, *chi_eval, None).is_err());
@@ -0,0 +1,357 @@
+use crate::{
+    base::{
+        database::{
+            Column, ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, Table,
+            TableEvaluation, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialTerm, SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::{DynProofExpr, ProofExpr, TableExpr},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use bumpalo::Bump;
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT COUNT(*) FILTER (WHERE <expr> < <boundary1>) as <alias0>,
+///         COUNT(*) FILTER (WHERE <boundary1> <= <expr> AND <expr> < <boundary2>) as <alias1>, ...,
+///         COUNT(*) FILTER (WHERE <boundaryN> <= <expr>) as <aliasN>
+///     FROM <table>
+/// ```
+///
+/// The `N` strictly ascending boundaries split the values of `expr` into `N + 1` buckets, the
+/// first and last of which catch the values below the first and at or above the last boundary.
+/// The result is a single row holding one count per bucket.
+///
+/// Only one inequality per boundary is proven. The indicator of a bucket is the difference of
+/// the indicators of the values being below its upper and its lower boundary, so every row is
+/// provably counted in exactly one bucket and the counts sum to the number of rows. Every count
+/// column is kept zero after its first row, so that a count can not be spread over several rows.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct BucketCountExec {
+    pub(super) expr: DynProofExpr,
+    pub(super) boundaries: Vec<LiteralValue>,
+    pub(super) aliases: Vec<Ident>,
+    pub(super) table: TableExpr,
+}
+
+/// The value of an integer, decimal or timestamp literal in units of the scale of its type
+fn scaled_value(literal: &LiteralValue) -> Option<BigInt> {
+    match literal {
+        LiteralValue::Uint8(i) => Some(BigInt::from(*i)),
+        LiteralValue::TinyInt(i) => Some(BigInt::from(*i)),
+        LiteralValue::SmallInt(i) => Some(BigInt::from(*i)),
+        LiteralValue::Int(i) => Some(BigInt::from(*i)),
+        LiteralValue::BigInt(i) | LiteralValue::TimeStampTZ(_, _, i) => Some(BigInt::from(*i)),
+        LiteralValue::Int128(i) => Some(BigInt::from(*i)),
+        LiteralValue::Decimal75(_, _, i) => Some(i.to_num_bigint()),
+        _ => None,
+    }
+}
+
+impl BucketCountExec {
+    /// Creates a new bucket count plan.
+    ///
+    /// The boundaries are coerced to the type of `expr` and there must be one alias per bucket,
+    /// i.e. one more than there are boundaries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnalyzeError::InvalidDataType`] if `expr` is not an integer, decimal or timestamp,
+    /// [`AnalyzeError::BucketAliasCountMismatch`] if the number of aliases is wrong,
+    /// [`AnalyzeError::DataTypeMismatch`] if a boundary cannot be coerced to the type of `expr` and
+    /// [`AnalyzeError::UnsortedBucketBoundaries`] if the boundaries are not strictly ascending.
+    pub fn try_new(
+        expr: DynProofExpr,
+        boundaries: Vec<LiteralValue>,
+        aliases: Vec<Ident>,
+        table: TableExpr,
+    ) -> AnalyzeResult<Self> {
+        let expr_type = expr.data_type();
+        if !(expr_type.is_integer()
+            || matches!(
+                expr_type,
+                ColumnType::Decimal75(_, _) | ColumnType::TimestampTZ(_, _)
+            ))
+        {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        if aliases.len() != boundaries.len() + 1 {
+            return Err(AnalyzeError::BucketAliasCountMismatch {
+                num_buckets: boundaries.len() + 1,
+                num_aliases: aliases.len(),
+            });
+        }
+        let boundaries = boundaries
+            .into_iter()
+            .map(|boundary| {
+                boundary
+                    .try_coerce_to(&expr_type)
+                    .map_err(|_| AnalyzeError::DataTypeMismatch {
+                        left_type: expr_type.to_string(),
+                        right_type: boundary.column_type().to_string(),
+                    })
+            })
+            .collect::<AnalyzeResult<Vec<_>>>()?;
+        // The coerced boundaries all have the scale of `expr`, so their scaled values compare.
+        let values: Vec<_> = boundaries.iter().map(scaled_value).collect();
+        if values.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(AnalyzeError::UnsortedBucketBoundaries);
+        }
+        for boundary in &boundaries {
+            less_than(&expr, boundary)?;
+        }
+        Ok(Self {
+            expr,
+            boundaries,
+            aliases,
+            table,
+        })
+    }
+
+    /// Get a reference to the bucketed expression
+    pub fn expr(&self) -> &DynProofExpr {
+        &self.expr
+    }
+
+    /// Get a reference to the boundaries of the buckets, coerced to the type of the expression
+    pub fn boundaries(&self) -> &[LiteralValue] {
+        &self.boundaries
+    }
+
+    /// Get a reference to the aliases of the counts of the buckets
+    pub fn aliases(&self) -> &[Ident] {
+        &self.aliases
+    }
+
+    /// Get a reference to the table expression
+    pub fn table(&self) -> &TableExpr {
+        &self.table
+    }
+
+    /// The predicates `<expr> < <boundary>`, one per boundary
+    fn below_boundaries(&self) -> Vec<DynProofExpr> {
+        self.boundaries
+            .iter()
+            .map(|boundary| {
+                less_than(&self.expr, boundary).expect("boundaries are checked on construction")
+            })
+            .collect()
+    }
+
+    /// Count the rows in each bucket, returning the single row result table
+    fn count<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        below_columns: &[&'a [bool]],
+        num_rows: usize,
+    ) -> (Table<'a, S>, Vec<&'a [i64]>) {
+        let below_counts: Vec<usize> = below_columns
+            .iter()
+            .map(|below| below.iter().filter(|b| **b).count())
+            .chain([num_rows])
+            .collect();
+        let count_columns: Vec<&'a [i64]> =
+            below_counts
+                .iter()
+                .scan(0, |lower_count, &count| {
+                    let bucket_count = count - *lower_count;
+                    *lower_count = count;
+                    Some(&*alloc.alloc_slice_copy(&[
+                        i64::try_from(bucket_count).expect("count fits in i64"),
+                    ]))
+                })
+                .collect();
+        let res = Table::<'a, S>::try_from_iter(
+            self.aliases
+                .iter()
+                .cloned()
+                .zip(count_columns.iter().map(|count| Column::BigInt(*count))),
+        )
+        .expect("Failed to create table from iterator");
+        (res, count_columns)
+    }
+}
+
+/// The predicate `<expr> < <boundary>`
+fn less_than(expr: &DynProofExpr, boundary: &LiteralValue) -> AnalyzeResult<DynProofExpr> {
+    DynProofExpr::try_new_inequality(
+        expr.clone(),
+        DynProofExpr::new_literal(boundary.clone()),
+        true,
+    )
+}
+
+impl ProofPlan for BucketCountExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_chi_eval = *chi_eval_map
+            .get(&self.table.table_ref)
+            .expect("Chi eval not found");
+        let no_column_evals = IndexMap::default();
+        let accessor = accessor
+            .get(&self.table.table_ref)
+            .unwrap_or(&no_column_evals);
+        // 1. below boundaries
+        let below_evals = self
+            .below_boundaries()
+            .iter()
+            .map(|below| below.verifier_evaluate(builder, accessor, input_chi_eval, params))
+            .collect::<Result<Vec<_>, _>>()?;
+        // 2. counts
+        let count_evals = builder.try_consume_final_round_mle_evaluations(self.aliases.len())?;
+        let output_chi_eval = builder.try_consume_chi_evaluation_of_length(1)?;
+        let singleton_chi_eval = builder.singleton_chi_evaluation();
+
+        let upper_evals = below_evals.iter().copied().chain([input_chi_eval]);
+        let lower_evals = core::iter::once(S::zero()).chain(below_evals.iter().copied());
+        for ((upper_eval, lower_eval), count_eval) in
+            upper_evals.zip(lower_evals).zip(count_evals.iter())
+        {
+            // count - count * singleton_chi = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                *count_eval - *count_eval * singleton_chi_eval,
+                2,
+            )?;
+            // sum (below upper boundary - below lower boundary) - count = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::ZeroSum,
+                upper_eval - lower_eval - *count_eval,
+                1,
+            )?;
+        }
+        Ok(TableEvaluation::new(count_evals, output_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.aliases
+            .iter()
+            .map(|alias| ColumnField::new(alias.clone(), ColumnType::BigInt))
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut columns = IndexSet::default();
+        self.expr.get_column_references(&mut columns);
+        columns
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        IndexSet::from_iter([self.table.table_ref.clone()])
+    }
+}
+
+impl ProverEvaluate for BucketCountExec {
+    #[tracing::instrument(
+        name = "BucketCountExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let table = table_map
+            .get(&self.table.table_ref)
+            .expect("Table not found");
+        let below_columns = self
+            .below_boundaries()
+            .iter()
+            .map(|below| {
+                let column = below.first_round_evaluate(alloc, table, params)?;
+                Ok(column.as_boolean().expect("inequality is boolean"))
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let (res, _) = self.count(alloc, &below_columns, table.num_rows());
+        builder.produce_chi_evaluation_length(1);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(
+        name = "BucketCountExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let table = table_map
+            .get(&self.table.table_ref)
+            .expect("Table not found");
+        // 1. below boundaries
+        let below_columns = self
+            .below_boundaries()
+            .iter()
+            .map(|below| {
+                let column = below.final_round_evaluate(builder, alloc, table, params)?;
+                Ok(column.as_boolean().expect("inequality is boolean"))
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        // 2. counts
+        let (res, count_columns) = self.count(alloc, &below_columns, table.num_rows());
+        for count in &count_columns {
+            builder.produce_intermediate_mle(*count);
+        }
+
+        let chi_n: &'a [bool] = alloc.alloc_slice_fill_copy(table.num_rows(), true);
+        let singleton_chi: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        let uppers = below_columns.iter().copied().chain([chi_n]);
+        let lowers = core::iter::once(None).chain(below_columns.iter().copied().map(Some));
+        for ((upper, lower), count) in uppers.zip(lowers).zip(count_columns) {
+            // count - count * singleton_chi = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(count)]),
+                    (-S::one(), vec![Box::new(count), Box::new(singleton_chi)]),
+                ],
+            );
+            // sum (below upper boundary - below lower boundary) - count = 0
+            let mut terms: Vec<SumcheckSubpolynomialTerm<'a, S>> =
+                vec![(S::one(), vec![Box::new(upper)])];
+            if let Some(lower) = lower {
+                terms.push((-S::one(), vec![Box::new(lower)]));
+            }
+            terms.push((-S::one(), vec![Box::new(count)]));
+            builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::ZeroSum, terms);
+        }
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+}
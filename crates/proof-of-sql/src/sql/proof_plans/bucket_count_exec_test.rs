@@ -0,0 +1,275 @@
+use super::{test_utility::*, BucketCountExec};
+use crate::{
+    assert_tables_equal,
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, ColumnType, LiteralValue, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+        math::{decimal::Precision, i256::I256},
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        AnalyzeError,
+    },
+};
+use sqlparser::ast::Ident;
+
+fn bucket_aliases(num_buckets: usize) -> Vec<Ident> {
+    (0..num_buckets)
+        .map(|i| Ident::new(format!("bucket_{i}")))
+        .collect()
+}
+
+/// Proves the counts of the buckets of column `a` of `data`, returning the verified result
+fn verify_bucket_counts(
+    data: OwnedTable<Curve25519Scalar>,
+    offset: usize,
+    boundaries: Vec<LiteralValue>,
+) -> OwnedTable<Curve25519Scalar> {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, offset);
+    let aliases = bucket_aliases(boundaries.len() + 1);
+    let aliases: Vec<&str> = aliases.iter().map(|alias| alias.value.as_str()).collect();
+    let plan = bucket_count(column(&t, "a", &accessor), boundaries, &aliases, tab(&t));
+    let res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &plan, &accessor, &t);
+    res.verify(&plan, &accessor, &(), &[]).unwrap().table
+}
+
+/// The sum of the counts of all buckets of a verified result
+fn total_count(res: &OwnedTable<Curve25519Scalar>) -> i64 {
+    res.inner_table()
+        .values()
+        .map(|column| column.i64_iter().sum::<i64>())
+        .sum()
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_references() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2]), bigint("b", [3, 4])]),
+        0,
+    );
+    let plan = BucketCountExec::try_new(
+        column(&t, "a", &accessor),
+        vec![LiteralValue::BigInt(0), LiteralValue::BigInt(10)],
+        bucket_aliases(3),
+        tab(&t),
+    )
+    .unwrap();
+    assert_eq!(
+        plan.get_column_result_fields(),
+        vec![
+            column_field("bucket_0", ColumnType::BigInt),
+            column_field("bucket_1", ColumnType::BigInt),
+            column_field("bucket_2", ColumnType::BigInt),
+        ]
+    );
+    assert_eq!(
+        plan.get_column_references(),
+        [col_ref(&t, "a", &accessor)].into_iter().collect()
+    );
+    assert_eq!(plan.get_table_references(), [t].into_iter().collect());
+}
+
+/// `select count(*) filter (where a < 0), count(*) filter (where 0 <= a and a < 10),
+/// count(*) filter (where 10 <= a and a < 100), count(*) filter (where 100 <= a) from sxt.t`
+#[test]
+fn we_can_prove_bucket_counts_with_underflow_and_overflow_buckets() {
+    let res = verify_bucket_counts(
+        owned_table([bigint("a", [-5, 0, 9, 10, 55, 99, 100, 1000, -1, 3])]),
+        0,
+        vec![
+            LiteralValue::BigInt(0),
+            LiteralValue::BigInt(10),
+            LiteralValue::BigInt(100),
+        ],
+    );
+    let expected = owned_table([
+        bigint("bucket_0", [2]),
+        bigint("bucket_1", [3]),
+        bigint("bucket_2", [3]),
+        bigint("bucket_3", [2]),
+    ]);
+    assert_tables_equal!(res, expected);
+    assert_eq!(total_count(&res), 10);
+}
+
+#[test]
+fn we_can_prove_bucket_counts_with_all_rows_in_one_bucket() {
+    let res = verify_bucket_counts(
+        owned_table([bigint("a", [5, 6, 7])]),
+        0,
+        vec![LiteralValue::BigInt(10), LiteralValue::BigInt(20)],
+    );
+    let expected = owned_table([
+        bigint("bucket_0", [3]),
+        bigint("bucket_1", [0]),
+        bigint("bucket_2", [0]),
+    ]);
+    assert_tables_equal!(res, expected);
+
+    // Without boundaries there is a single bucket holding every row
+    let res = verify_bucket_counts(owned_table([bigint("a", [5, 6, 7])]), 0, vec![]);
+    assert_tables_equal!(res, owned_table([bigint("bucket_0", [3])]));
+}
+
+#[test]
+fn we_can_prove_bucket_counts_on_an_empty_table() {
+    let res = verify_bucket_counts(
+        owned_table([bigint("a", [0; 0])]),
+        0,
+        vec![LiteralValue::BigInt(0), LiteralValue::BigInt(10)],
+    );
+    let expected = owned_table([
+        bigint("bucket_0", [0]),
+        bigint("bucket_1", [0]),
+        bigint("bucket_2", [0]),
+    ]);
+    assert_tables_equal!(res, expected);
+    assert_eq!(total_count(&res), 0);
+}
+
+#[test]
+fn we_can_prove_bucket_counts_on_a_table_with_an_offset() {
+    let res = verify_bucket_counts(
+        owned_table([int("a", [1, 2, 3, 4, 5])]),
+        3,
+        vec![LiteralValue::Int(2), LiteralValue::Int(4)],
+    );
+    let expected = owned_table([
+        bigint("bucket_0", [1]),
+        bigint("bucket_1", [2]),
+        bigint("bucket_2", [2]),
+    ]);
+    assert_tables_equal!(res, expected);
+    assert_eq!(total_count(&res), 5);
+}
+
+#[test]
+fn we_can_prove_bucket_counts_of_decimals_with_coerced_boundaries() {
+    // 1.50, -0.25, 3.00, 0.99
+    let res = verify_bucket_counts(
+        owned_table([decimal75("a", 10, 2, [150, -25, 300, 99])]),
+        0,
+        vec![
+            LiteralValue::BigInt(0),
+            LiteralValue::BigInt(1),
+            LiteralValue::Decimal75(Precision::new(3).unwrap(), 1, I256::from(30)),
+        ],
+    );
+    let expected = owned_table([
+        bigint("bucket_0", [1]),
+        bigint("bucket_1", [1]),
+        bigint("bucket_2", [1]),
+        bigint("bucket_3", [1]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_can_prove_bucket_counts_matching_separate_count_queries() {
+    let data = owned_table([bigint("a", [7, -3, 12, 0, 5, 5, 20, 11, -8])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let plan = bucket_count(
+        column(&t, "a", &accessor),
+        vec![LiteralValue::BigInt(0), LiteralValue::BigInt(10)],
+        &["low", "mid", "high"],
+        tab(&t),
+    );
+    let res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &plan, &accessor, &t);
+    let res = res.verify(&plan, &accessor, &(), &[]).unwrap().table;
+
+    let a = || column(&t, "a", &accessor);
+    let lt = |lhs, rhs| DynProofExpr::try_new_inequality(lhs, rhs, true).unwrap();
+    let predicates = [
+        ("low", lt(a(), const_bigint(0))),
+        (
+            "mid",
+            and(gte(a(), const_bigint(0)), lt(a(), const_bigint(10))),
+        ),
+        ("high", gte(a(), const_bigint(10))),
+    ];
+    let naive_plan = multi_count(
+        predicates
+            .into_iter()
+            .map(|(alias, predicate)| aliased_plan(predicate, alias))
+            .collect(),
+        tab(&t),
+    );
+    let naive_res = VerifiableQueryResult::new(&naive_plan, &accessor, &(), &[])
+        .unwrap()
+        .verify(&naive_plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    assert_tables_equal!(res, naive_res);
+    assert_eq!(total_count(&res), 9);
+}
+
+#[test]
+fn we_cannot_count_buckets_with_invalid_boundaries_or_aliases() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2]), varchar("b", ["x", "y"])]),
+        0,
+    );
+    let try_new = |expr: DynProofExpr, boundaries: Vec<LiteralValue>, num_aliases: usize| {
+        BucketCountExec::try_new(expr, boundaries, bucket_aliases(num_aliases), tab(&t))
+    };
+
+    assert_eq!(
+        try_new(
+            column(&t, "a", &accessor),
+            vec![LiteralValue::BigInt(10), LiteralValue::BigInt(0)],
+            3
+        ),
+        Err(AnalyzeError::UnsortedBucketBoundaries)
+    );
+    assert_eq!(
+        try_new(
+            column(&t, "a", &accessor),
+            vec![LiteralValue::BigInt(5), LiteralValue::Int(5)],
+            3
+        ),
+        Err(AnalyzeError::UnsortedBucketBoundaries)
+    );
+    assert_eq!(
+        try_new(column(&t, "a", &accessor), vec![LiteralValue::BigInt(0)], 3),
+        Err(AnalyzeError::BucketAliasCountMismatch {
+            num_buckets: 2,
+            num_aliases: 3
+        })
+    );
+    assert_eq!(
+        try_new(column(&t, "b", &accessor), vec![], 1),
+        Err(AnalyzeError::InvalidDataType {
+            expr_type: ColumnType::VarChar
+        })
+    );
+    // 1.5 has no exact representation as a BIGINT
+    assert!(matches!(
+        try_new(
+            column(&t, "a", &accessor),
+            vec![LiteralValue::Decimal75(
+                Precision::new(2).unwrap(),
+                1,
+                I256::from(15)
+            )],
+            2
+        ),
+        Err(AnalyzeError::DataTypeMismatch { .. })
+    ));
+}
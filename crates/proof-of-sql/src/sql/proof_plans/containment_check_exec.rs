@@ -0,0 +1,348 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        database::{
+            join_util::{get_columns_of_table, get_multiplicities, ordered_set_union},
+            Column, ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, Table,
+            TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_membership_check, final_round_evaluate_monotonic,
+            first_round_evaluate_membership_check, first_round_evaluate_monotonic,
+            verify_membership_check, verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// `ProofPlan` attesting referential integrity, i.e. that every value of a column of the
+/// `child` input occurs in a column of the `parent` input, as in
+/// ```ignore
+///     SELECT NOT EXISTS (
+///         SELECT 1 FROM <ProofPlan> AS <child>
+///         WHERE <child>.col1 NOT IN (SELECT <parent>.col2 FROM <ProofPlan> AS <parent>)
+///     ) AS <alias>
+/// ```
+///
+/// The result is a single row with a single boolean column that is always `true`.
+/// If some child value is missing from the parent column, no valid proof exists and verification fails.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainmentCheckExec {
+    pub(super) child: Box<DynProofPlan>,
+    pub(super) parent: Box<DynProofPlan>,
+    pub(super) child_column_index: usize,
+    pub(super) parent_column_index: usize,
+    pub(super) alias: Ident,
+}
+
+impl ContainmentCheckExec {
+    /// Create a new `ContainmentCheckExec` checking that the values of the `child_column_index`-th
+    /// column of `child` are contained in the values of the `parent_column_index`-th column of `parent`
+    ///
+    /// # Panics
+    /// Panics if one of the following conditions is met:
+    /// - A column index is out of bounds
+    /// - The types of the columns are different
+    #[must_use]
+    pub fn new(
+        child: Box<DynProofPlan>,
+        parent: Box<DynProofPlan>,
+        child_column_index: usize,
+        parent_column_index: usize,
+        alias: Ident,
+    ) -> Self {
+        let (Some(child_field), Some(parent_field)) = (
+            child
+                .get_column_result_fields()
+                .get(child_column_index)
+                .cloned(),
+            parent
+                .get_column_result_fields()
+                .get(parent_column_index)
+                .cloned(),
+        ) else {
+            panic!("Containment column index out of bounds");
+        };
+        assert!(
+            (child_field.data_type() == parent_field.data_type()),
+            "Containment columns should have the same types"
+        );
+        Self {
+            child,
+            parent,
+            child_column_index,
+            parent_column_index,
+            alias,
+        }
+    }
+
+    /// Get the child plan
+    pub fn child(&self) -> &DynProofPlan {
+        &self.child
+    }
+
+    /// Get the parent plan
+    pub fn parent(&self) -> &DynProofPlan {
+        &self.parent
+    }
+
+    /// Get the index of the checked column of the child plan
+    pub fn child_column_index(&self) -> usize {
+        self.child_column_index
+    }
+
+    /// Get the index of the checked column of the parent plan
+    pub fn parent_column_index(&self) -> usize {
+        self.parent_column_index
+    }
+
+    /// Get the alias of the result
+    pub fn alias(&self) -> &Ident {
+        &self.alias
+    }
+}
+
+/// Compute the witnesses of the containment check from the parent column
+///
+/// These are
+/// 1. `U`, the distinct values of the parent column in increasing order
+/// 2. `inv_u`, the inverses of the multiplicities of the rows of `U` in the parent column
+fn get_containment_witnesses<'a, S: Scalar>(
+    c_p: &[Column<'a, S>],
+    alloc: &'a Bump,
+) -> (Vec<Column<'a, S>>, &'a [S]) {
+    let u = ordered_set_union(c_p, c_p, alloc).expect("Columns should have the same types");
+    let w_p = get_multiplicities::<S>(c_p, &u, alloc);
+    let inv_u = alloc.alloc_slice_fill_iter(w_p.iter().map(S::from));
+    slice_ops::batch_inversion(inv_u);
+    (u, inv_u)
+}
+
+impl ContainmentCheckExec {
+    /// The single-row result attesting that the check passed
+    fn result_table<'a, S: Scalar>(&self, alloc: &'a Bump) -> Table<'a, S> {
+        Table::<'a, S>::try_from_iter_with_options(
+            [(
+                self.alias.clone(),
+                Column::Boolean(alloc.alloc_slice_copy(&[true])),
+            )],
+            TableOptions::new(Some(1)),
+        )
+        .expect("Failed to create table from iterator")
+    }
+}
+
+impl ProofPlan for ContainmentCheckExec
+where
+    ContainmentCheckExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let child_eval =
+            self.child
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let parent_eval =
+            self.parent
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let child_column_eval = *child_eval
+            .column_evals()
+            .get(self.child_column_index)
+            .expect("Index can not be out of bounds");
+        let parent_column_eval = *parent_eval
+            .column_evals()
+            .get(self.parent_column_index)
+            .expect("Index can not be out of bounds");
+        // 2. Chi evals
+        let u_chi_eval = builder.try_consume_chi_evaluation()?;
+        let res_chi_eval = builder.try_consume_chi_evaluation_of_length(1)?;
+        // 3. alpha, beta
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 4. First round MLE evaluations: `U` and `inv_u`
+        let u_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let inv_u_eval = builder.try_consume_first_round_mle_evaluation()?;
+        // 5. `w_p` is the multiplicity of each row of `U` in the parent column
+        let w_p_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            parent_eval.chi_eval(),
+            &[u_eval],
+            &[parent_column_eval],
+        )?;
+        // 6. Every row of `U` occurs in the parent column
+        // chi_u - w_p * inv_u = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            u_chi_eval - w_p_eval * inv_u_eval,
+            2,
+        )?;
+        // 7. Every child value occurs in `U`
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            child_eval.chi_eval(),
+            &[u_eval],
+            &[child_column_eval],
+        )?;
+        // 8. `U` is strictly increasing, so its rows are distinct
+        verify_monotonic::<S, true, true>(builder, alpha, beta, u_eval, u_chi_eval)?;
+        // 9. The result is a single `true`
+        Ok(TableEvaluation::new(vec![res_chi_eval], res_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        vec![ColumnField::new(self.alias.clone(), ColumnType::Boolean)]
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.child
+            .get_column_references()
+            .into_iter()
+            .chain(self.parent.get_column_references())
+            .collect()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.child
+            .get_table_references()
+            .into_iter()
+            .chain(self.parent.get_table_references())
+            .collect()
+    }
+}
+
+impl ProverEvaluate for ContainmentCheckExec {
+    #[tracing::instrument(
+        name = "ContainmentCheckExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let child = self
+            .child
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let parent = self
+            .parent
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let c_c = get_columns_of_table(&child, &[self.child_column_index])
+            .expect("Index can not be out of bounds");
+        let c_p = get_columns_of_table(&parent, &[self.parent_column_index])
+            .expect("Index can not be out of bounds");
+        // 2. Witnesses
+        let (u, inv_u) = get_containment_witnesses(&c_p, alloc);
+        let num_rows_u = u[0].len();
+        // 3. Chi evals
+        builder.produce_chi_evaluation_length(num_rows_u);
+        builder.produce_chi_evaluation_length(1);
+        // 4. First round MLEs
+        let alloc_u_0 = alloc.alloc_slice_copy(u[0].to_scalar().as_slice());
+        builder.produce_intermediate_mle(alloc_u_0 as &[_]);
+        builder.produce_intermediate_mle(inv_u as &[_]);
+        // 5. Membership checks
+        first_round_evaluate_membership_check(builder, alloc, &u, &c_p);
+        first_round_evaluate_membership_check(builder, alloc, &u, &c_c);
+        // 6. Monotonicity check
+        first_round_evaluate_monotonic(builder, num_rows_u);
+        // 7. Request post-result challenges
+        builder.request_post_result_challenges(2);
+
+        log::log_memory_usage("End");
+
+        Ok(self.result_table(alloc))
+    }
+
+    #[tracing::instrument(
+        name = "ContainmentCheckExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let child = self
+            .child
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let parent = self
+            .parent
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let c_c = get_columns_of_table(&child, &[self.child_column_index])
+            .expect("Index can not be out of bounds");
+        let c_p = get_columns_of_table(&parent, &[self.parent_column_index])
+            .expect("Index can not be out of bounds");
+        let chi_c = alloc.alloc_slice_fill_copy(child.num_rows(), true);
+        let chi_p = alloc.alloc_slice_fill_copy(parent.num_rows(), true);
+        // 2. Witnesses
+        let (u, inv_u) = get_containment_witnesses(&c_p, alloc);
+        let num_rows_u = u[0].len();
+        let chi_u = alloc.alloc_slice_fill_copy(num_rows_u, true);
+        let alloc_u_0 = alloc.alloc_slice_copy(u[0].to_scalar().as_slice());
+        // 3. Get post-result challenges
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 4. `w_p` is the multiplicity of each row of `U` in the parent column
+        let w_p = final_round_evaluate_membership_check(
+            builder, alloc, alpha, beta, chi_u, chi_p, &u, &c_p,
+        );
+        // 5. Every row of `U` occurs in the parent column
+        // chi_u - w_p * inv_u = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(chi_u as &[_])]),
+                (
+                    -S::one(),
+                    vec![Box::new(w_p as &[_]), Box::new(inv_u as &[_])],
+                ),
+            ],
+        );
+        // 6. Every child value occurs in `U`
+        final_round_evaluate_membership_check(builder, alloc, alpha, beta, chi_u, chi_c, &u, &c_c);
+        // 7. `U` is strictly increasing
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_u_0);
+
+        log::log_memory_usage("End");
+
+        Ok(self.result_table(alloc))
+    }
+}
@@ -0,0 +1,196 @@
+use super::{test_utility::*, ContainmentCheckExec, DynProofPlan};
+use crate::{
+    assert_tables_equal,
+    base::database::{
+        owned_table_utility::*, table_utility::*, ColumnField, ColumnType, TableRef,
+        TableTestAccessor, TestAccessor,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+
+fn orders_and_customers_accessor<'a>(
+    alloc: &'a Bump,
+    customer_ids_of_orders: &[i64],
+    customer_ids: &[i64],
+) -> (TableTestAccessor<'a, InnerProductProof>, TableRef, TableRef) {
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let orders: TableRef = "sxt.orders".parse().unwrap();
+    let customers: TableRef = "sxt.customers".parse().unwrap();
+    accessor.add_table(
+        orders.clone(),
+        table([
+            borrowed_bigint(
+                "order_id",
+                (0..customer_ids_of_orders.len()).map(|i| i64::try_from(i).unwrap() + 100),
+                alloc,
+            ),
+            borrowed_bigint("customer_id", customer_ids_of_orders.iter().copied(), alloc),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        customers.clone(),
+        table([borrowed_bigint("id", customer_ids.iter().copied(), alloc)]),
+        0,
+    );
+    (accessor, orders, customers)
+}
+
+fn orders_reference_customers(orders: &TableRef, customers: &TableRef) -> DynProofPlan {
+    containment_check(
+        table_exec(
+            orders.clone(),
+            vec![
+                column_field("order_id", ColumnType::BigInt),
+                column_field("customer_id", ColumnType::BigInt),
+            ],
+        ),
+        table_exec(
+            customers.clone(),
+            vec![column_field("id", ColumnType::BigInt)],
+        ),
+        1,
+        0,
+        "contained",
+    )
+}
+
+#[test]
+fn we_can_prove_that_a_column_is_contained_in_another() {
+    let alloc = Bump::new();
+    for (customer_ids_of_orders, customer_ids) in [
+        (&[1_i64, 2, 2, 3, 1][..], &[3_i64, 1, 2, 4][..]),
+        // Duplicate referenced values
+        (&[3_i64, 1, 3][..], &[1_i64, 1, 2, 3, 3, 3][..]),
+        // No referencing rows
+        (&[][..], &[1_i64, 2][..]),
+        (&[][..], &[][..]),
+        // Negative values
+        (&[-5_i64, 0, -5][..], &[0_i64, -5, 7][..]),
+    ] {
+        let (accessor, orders, customers) =
+            orders_and_customers_accessor(&alloc, customer_ids_of_orders, customer_ids);
+        let ast = orders_reference_customers(&orders, &customers);
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &ast, &accessor, &orders);
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+        let expected_res = owned_table([boolean("contained", [true])]);
+        assert_tables_equal!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_cannot_prove_that_a_column_is_contained_in_another_when_it_is_not() {
+    let alloc = Bump::new();
+    for (customer_ids_of_orders, customer_ids) in [
+        (&[1_i64, 2, 5, 3][..], &[3_i64, 1, 2, 4][..]),
+        // All values missing
+        (&[7_i64, 8][..], &[1_i64, 2, 3][..]),
+        // Empty referenced set
+        (&[1_i64][..], &[][..]),
+        // A missing value repeated
+        (&[2_i64, 9, 9][..], &[2_i64, 2][..]),
+    ] {
+        let (accessor, orders, customers) =
+            orders_and_customers_accessor(&alloc, customer_ids_of_orders, customer_ids);
+        let ast = orders_reference_customers(&orders, &customers);
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        assert!(verifiable_res.verify(&ast, &accessor, &(), &[]).is_err());
+    }
+}
+
+#[test]
+fn we_can_prove_containment_of_filtered_columns() {
+    let alloc = Bump::new();
+    let (accessor, orders, customers) =
+        orders_and_customers_accessor(&alloc, &[1, 2, 9, 3], &[1, 2, 3, 4]);
+    // The order referencing the missing customer is filtered out
+    let ast = containment_check(
+        filter(
+            cols_expr_plan(&orders, &["customer_id"], &accessor),
+            tab(&orders),
+            lte(column(&orders, "customer_id", &accessor), const_bigint(3)),
+        ),
+        table_exec(
+            customers.clone(),
+            vec![column_field("id", ColumnType::BigInt)],
+        ),
+        0,
+        0,
+        "contained",
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &orders);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([boolean("contained", [true])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_get_the_result_fields_and_references_of_a_containment_check() {
+    let orders: TableRef = "sxt.orders".parse().unwrap();
+    let customers: TableRef = "sxt.customers".parse().unwrap();
+    let ast = orders_reference_customers(&orders, &customers);
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![ColumnField::new("contained".into(), ColumnType::Boolean)]
+    );
+    assert_eq!(
+        ast.get_table_references(),
+        [orders, customers].into_iter().collect()
+    );
+}
+
+#[test]
+#[should_panic(expected = "Containment column index out of bounds")]
+fn we_cannot_create_a_containment_check_with_out_of_bounds_indexes() {
+    let orders: TableRef = "sxt.orders".parse().unwrap();
+    let customers: TableRef = "sxt.customers".parse().unwrap();
+    let _ = ContainmentCheckExec::new(
+        Box::new(table_exec(
+            orders,
+            vec![column_field("customer_id", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            customers,
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        0,
+        1,
+        "contained".into(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Containment columns should have the same types")]
+fn we_cannot_create_a_containment_check_on_columns_of_different_types() {
+    let orders: TableRef = "sxt.orders".parse().unwrap();
+    let customers: TableRef = "sxt.customers".parse().unwrap();
+    let _ = ContainmentCheckExec::new(
+        Box::new(table_exec(
+            orders,
+            vec![column_field("customer_id", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            customers,
+            vec![column_field("name", ColumnType::VarChar)],
+        )),
+        0,
+        0,
+        "contained".into(),
+    );
+}
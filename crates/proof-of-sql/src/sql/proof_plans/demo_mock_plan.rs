@@ -87,7 +87,7 @@ mod tests {
             owned_table_utility::{bigint, owned_table},
             ColumnRef, ColumnType, OwnedTableTestAccessor, TableRef,
         },
-        sql::proof::VerifiableQueryResult,
+        sql::proof::{assert_plan_round_consistency, VerifiableQueryResult},
     };
     #[cfg(feature = "blitzar")]
     use blitzar::proof::InnerProductProof;
@@ -114,4 +114,20 @@ mod tests {
             .table;
         assert_eq!(res, table);
     }
+
+    #[test]
+    #[cfg(feature = "blitzar")]
+    fn we_can_check_that_the_verifier_of_a_demo_mock_plan_mirrors_its_prover() {
+        let table_ref = "namespace.table_name".parse::<TableRef>().unwrap();
+        let column_ref =
+            ColumnRef::new(table_ref.clone(), "column_name".into(), ColumnType::BigInt);
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            table_ref,
+            owned_table([bigint("column_name", [0, 1, 2, 3])]),
+            0_usize,
+            (),
+        );
+        // place the data you want to check the prover and verifier with here
+        assert_plan_round_consistency(&DemoMockPlan { column: column_ref }, &accessor);
+    }
 }
@@ -1,11 +1,14 @@
 use super::{
-    EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, SortMergeJoinExec, TableExec,
-    UnionExec,
+    BucketCountExec, ContainmentCheckExec, EmptyExec, FilterExec, FirstPerGroupExec,
+    GeneralizedFilterExec, GroupByExec, GroupingSetsExec, LeftAntiJoinExec, MonotonicCheckExec,
+    MultiCountExec, PercentageOfTotalExec, PermutationCheckExec, ProjectionExec, SliceExec,
+    SortMergeJoinExec, TableExec, UnionExec,
 };
 use crate::{
     base::{
         database::{
-            ColumnField, ColumnRef, LiteralValue, OwnedTable, Table, TableEvaluation, TableRef,
+            ColumnField, ColumnRef, ColumnType, DataAccessor, LiteralValue, OwnedTable, Table,
+            TableEvaluation, TableRef,
         },
         map::{IndexMap, IndexSet},
         proof::{PlaceholderResult, ProofError},
@@ -13,12 +16,14 @@ use crate::{
     },
     sql::{
         proof::{
-            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+            required_num_generators, FinalRoundBuilder, FirstRoundBuilder, ProofPlan,
+            ProverEvaluate, VerificationBuilder,
         },
         proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
+        AnalyzeResult,
     },
 };
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
@@ -46,11 +51,27 @@ pub enum DynProofPlan {
     ///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
     /// ```
     GroupBy(GroupByExec),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <group_column1>, ..., <group_columnK>,
+    ///         SUM(<sum_expr1>.0) as <sum_expr1>.1, ..., SUM(<sum_exprN>.0) as <sum_exprN>.1,
+    ///         COUNT(*) as count_alias,
+    ///         GROUPING(<group_column1>, ..., <group_columnK>) as grouping_id_alias
+    ///     FROM <table>
+    ///     WHERE <where_clause>
+    ///     GROUP BY GROUPING SETS ((<grouping_set1>), ..., (<grouping_setM>))
+    /// ```
+    GroupingSets(GroupingSetsExec),
     /// Provable expressions for queries of the form, where the result is sent in a dense form
     /// ```ignore
     ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
     /// ```
     Filter(FilterExec),
+    /// Provable expressions for queries of the form, where the result is sent in a dense form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <ProofPlan> WHERE <where_clause>
+    /// ```
+    GeneralizedFilter(GeneralizedFilterExec),
     /// `ProofPlan` for queries of the form
     /// ```ignore
     ///     <ProofPlan> LIMIT <fetch> [OFFSET <skip>]
@@ -72,6 +93,64 @@ pub enum DynProofPlan {
     ///     ON col1 = col2
     /// ```
     SortMergeJoin(SortMergeJoinExec),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT COUNT(*) FILTER (WHERE <predicate1>) as <alias1>, ...,
+    ///         COUNT(*) FILTER (WHERE <predicateN>) as <aliasN>
+    ///     FROM <table>
+    /// ```
+    MultiCount(MultiCountExec),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT COUNT(*) FILTER (WHERE <expr> < <boundary1>) as <alias0>, ...,
+    ///         COUNT(*) FILTER (WHERE <boundaryN> <= <expr>) as <aliasN>
+    ///     FROM <table>
+    /// ```
+    BucketCount(BucketCountExec),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN>,
+    ///         <amount> * 100 / SUM(<amount>) OVER () as <alias>
+    ///     FROM <input>
+    /// ```
+    PercentageOfTotal(PercentageOfTotalExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     SELECT <left>.* FROM <ProofPlan> AS <left>
+    ///     WHERE NOT EXISTS (SELECT 1 FROM <ProofPlan> AS <right> WHERE <right>.col2 = <left>.col1)
+    /// ```
+    LeftAntiJoin(LeftAntiJoinExec),
+    /// `ProofPlan` attesting that every value of a column of `<child>` occurs in a column of `<parent>`
+    /// ```ignore
+    ///     SELECT NOT EXISTS (
+    ///         SELECT 1 FROM <ProofPlan> AS <child>
+    ///         WHERE <child>.col1 NOT IN (SELECT <parent>.col2 FROM <ProofPlan> AS <parent>)
+    ///     ) AS <alias>
+    /// ```
+    ContainmentCheck(ContainmentCheckExec),
+    /// `ProofPlan` attesting that a column of `<ProofPlan>` is non-decreasing in row order
+    /// ```ignore
+    ///     SELECT NOT EXISTS (
+    ///         SELECT 1 FROM (SELECT <col>, LEAD(<col>) OVER () AS next FROM <ProofPlan>)
+    ///         WHERE <col> > next
+    ///     ) AS <alias>
+    /// ```
+    MonotonicCheck(MonotonicCheckExec),
+    /// `ProofPlan` attesting whether a column of `<ProofPlan>` is a permutation of another
+    /// ```ignore
+    ///     SELECT NOT EXISTS (
+    ///         (SELECT <col1> FROM <ProofPlan> EXCEPT ALL SELECT <col2> FROM <ProofPlan>)
+    ///         UNION ALL
+    ///         (SELECT <col2> FROM <ProofPlan> EXCEPT ALL SELECT <col1> FROM <ProofPlan>)
+    ///     ) AS <alias>
+    /// ```
+    PermutationCheck(PermutationCheckExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     SELECT DISTINCT ON (<key1>, ..., <keyK>) * FROM <ProofPlan>
+    ///     ORDER BY <key1>, ..., <keyK>, <tiebreaker> [ASC | DESC]
+    /// ```
+    FirstPerGroup(FirstPerGroupExec),
 }
 
 impl DynProofPlan {
@@ -103,6 +182,20 @@ impl DynProofPlan {
         Self::Filter(FilterExec::new(aliased_results, input, filter_expr))
     }
 
+    /// Creates a new filter plan over the output of another plan.
+    #[must_use]
+    pub fn new_generalized_filter(
+        aliased_results: Vec<AliasedDynProofExpr>,
+        input: DynProofPlan,
+        filter_expr: DynProofExpr,
+    ) -> Self {
+        Self::GeneralizedFilter(GeneralizedFilterExec::new(
+            aliased_results,
+            Box::new(input),
+            filter_expr,
+        ))
+    }
+
     /// Creates a new group by plan.
     #[must_use]
     pub fn new_group_by(
@@ -121,6 +214,29 @@ impl DynProofPlan {
         ))
     }
 
+    /// Creates a new grouping sets plan.
+    ///
+    /// # Errors
+    /// Returns an error if the grouping sets are invalid, see [`GroupingSetsExec::try_new`].
+    pub fn try_new_grouping_sets(
+        grouping_sets: Vec<Vec<ColumnExpr>>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+        grouping_id_alias: Ident,
+    ) -> AnalyzeResult<Self> {
+        GroupingSetsExec::try_new(
+            grouping_sets,
+            sum_expr,
+            count_alias,
+            table,
+            where_clause,
+            grouping_id_alias,
+        )
+        .map(Self::GroupingSets)
+    }
+
     /// Creates a new slice plan.
     #[must_use]
     pub fn new_slice(input: DynProofPlan, skip: usize, fetch: Option<usize>) -> Self {
@@ -132,4 +248,218 @@ impl DynProofPlan {
     pub fn new_union(inputs: Vec<DynProofPlan>, schema: Vec<ColumnField>) -> Self {
         Self::Union(UnionExec::new(inputs, schema))
     }
+
+    /// Creates a new percentage of total plan.
+    ///
+    /// # Errors
+    /// Returns an error if `amount` is not an integer or a decimal expression, if `scale` is
+    /// not supported for it or if its alias is also the alias of a result.
+    pub fn try_new_percentage_of_total(
+        aliased_results: Vec<AliasedDynProofExpr>,
+        amount: AliasedDynProofExpr,
+        scale: i8,
+        input: DynProofPlan,
+    ) -> AnalyzeResult<Self> {
+        PercentageOfTotalExec::try_new(aliased_results, amount, scale, Box::new(input))
+            .map(Self::PercentageOfTotal)
+    }
+
+    /// Creates a new plan keeping the first row of each key of `input`.
+    ///
+    /// # Errors
+    /// Returns an error if the keys or the tiebreaker are invalid, see
+    /// [`FirstPerGroupExec::try_new`].
+    pub fn try_new_first_per_group(
+        input: DynProofPlan,
+        key_column_indexes: Vec<usize>,
+        tiebreaker_column_index: usize,
+        tiebreaker_descending: bool,
+    ) -> AnalyzeResult<Self> {
+        FirstPerGroupExec::try_new(
+            Box::new(input),
+            key_column_indexes,
+            tiebreaker_column_index,
+            tiebreaker_descending,
+        )
+        .map(Self::FirstPerGroup)
+    }
+
+    /// Creates a plan asserting that `expr` equals `value` on every row of `table`.
+    ///
+    /// This is a template for queries of the form
+    /// ```ignore
+    ///     SELECT COUNT(*) FILTER (WHERE <expr> = <value>) = COUNT(*) as <alias> FROM <table>
+    /// ```
+    /// whose result is a single boolean, vacuously true for an empty table.
+    /// Both counts come from a single [`MultiCountExec`], which is much cheaper than
+    /// proving `COUNT(DISTINCT <expr>) = 1`.
+    ///
+    /// # Errors
+    /// Returns an error if `expr` cannot be compared with `value`.
+    pub fn try_new_is_constant(
+        table: TableExpr,
+        expr: DynProofExpr,
+        value: DynProofExpr,
+        alias: Ident,
+    ) -> AnalyzeResult<Self> {
+        let matching_count = Ident::new("__matching_count__");
+        let row_count = Ident::new("__row_count__");
+        let count_column = |column_id: &Ident| {
+            DynProofExpr::new_column(ColumnRef::new(
+                table.table_ref.clone(),
+                column_id.clone(),
+                ColumnType::BigInt,
+            ))
+        };
+        let is_constant =
+            DynProofExpr::try_new_equals(count_column(&matching_count), count_column(&row_count))?;
+        let counts = MultiCountExec::try_new(
+            vec![
+                AliasedDynProofExpr {
+                    expr: DynProofExpr::try_new_equals(expr, value)?,
+                    alias: matching_count,
+                },
+                AliasedDynProofExpr {
+                    expr: DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+                    alias: row_count,
+                },
+            ],
+            table,
+        )?;
+        Ok(Self::new_projection(
+            vec![AliasedDynProofExpr {
+                expr: is_constant,
+                alias,
+            }],
+            Self::MultiCount(counts),
+        ))
+    }
+
+    /// Get the input plans of this plan
+    #[must_use]
+    pub fn inputs(&self) -> Vec<&DynProofPlan> {
+        match self {
+            DynProofPlan::Empty(_)
+            | DynProofPlan::Table(_)
+            | DynProofPlan::Filter(_)
+            | DynProofPlan::GroupBy(_)
+            | DynProofPlan::GroupingSets(_)
+            | DynProofPlan::MultiCount(_)
+            | DynProofPlan::BucketCount(_) => vec![],
+            DynProofPlan::Projection(projection_exec) => vec![projection_exec.input()],
+            DynProofPlan::PercentageOfTotal(percentage_of_total_exec) => {
+                vec![percentage_of_total_exec.input()]
+            }
+            DynProofPlan::GeneralizedFilter(generalized_filter_exec) => {
+                vec![generalized_filter_exec.input()]
+            }
+            DynProofPlan::Slice(slice_exec) => vec![slice_exec.input()],
+            DynProofPlan::FirstPerGroup(first_per_group_exec) => {
+                vec![first_per_group_exec.input()]
+            }
+            DynProofPlan::Union(union_exec) => union_exec.inputs.iter().collect(),
+            DynProofPlan::SortMergeJoin(sort_merge_join_exec) => {
+                vec![&*sort_merge_join_exec.left, &*sort_merge_join_exec.right]
+            }
+            DynProofPlan::LeftAntiJoin(left_anti_join_exec) => {
+                vec![&*left_anti_join_exec.left, &*left_anti_join_exec.right]
+            }
+            DynProofPlan::ContainmentCheck(containment_check_exec) => {
+                vec![
+                    &*containment_check_exec.child,
+                    &*containment_check_exec.parent,
+                ]
+            }
+            DynProofPlan::MonotonicCheck(monotonic_check_exec) => {
+                vec![monotonic_check_exec.input()]
+            }
+            DynProofPlan::PermutationCheck(permutation_check_exec) => {
+                vec![
+                    &*permutation_check_exec.source,
+                    &*permutation_check_exec.candidate,
+                ]
+            }
+        }
+    }
+
+    /// Get the expressions directly owned by this plan, excluding those of its inputs
+    ///
+    /// Group by columns of a `GroupingSetsExec` are plain column references and are not included.
+    #[must_use]
+    pub fn exprs(&self) -> Vec<&DynProofExpr> {
+        match self {
+            DynProofPlan::Empty(_)
+            | DynProofPlan::Table(_)
+            | DynProofPlan::Slice(_)
+            | DynProofPlan::Union(_)
+            | DynProofPlan::SortMergeJoin(_)
+            | DynProofPlan::LeftAntiJoin(_)
+            | DynProofPlan::ContainmentCheck(_)
+            | DynProofPlan::MonotonicCheck(_)
+            | DynProofPlan::PermutationCheck(_)
+            | DynProofPlan::FirstPerGroup(_) => vec![],
+            DynProofPlan::Projection(projection_exec) => projection_exec
+                .aliased_results()
+                .iter()
+                .map(|aliased_expr| &aliased_expr.expr)
+                .collect(),
+            DynProofPlan::Filter(filter_exec) => filter_exec
+                .aliased_results()
+                .iter()
+                .map(|aliased_expr| &aliased_expr.expr)
+                .chain([filter_exec.where_clause()])
+                .collect(),
+            DynProofPlan::GeneralizedFilter(generalized_filter_exec) => generalized_filter_exec
+                .aliased_results()
+                .iter()
+                .map(|aliased_expr| &aliased_expr.expr)
+                .chain([generalized_filter_exec.where_clause()])
+                .collect(),
+            DynProofPlan::GroupBy(group_by_exec) => group_by_exec
+                .group_by_exprs()
+                .iter()
+                .chain(group_by_exec.sum_expr())
+                .map(|aliased_expr| &aliased_expr.expr)
+                .chain(group_by_exec.count_distinct_filters().iter().flatten())
+                .chain([group_by_exec.where_clause()])
+                .collect(),
+            DynProofPlan::GroupingSets(grouping_sets_exec) => grouping_sets_exec
+                .sum_expr()
+                .iter()
+                .map(|aliased_expr| &aliased_expr.expr)
+                .chain([grouping_sets_exec.where_clause()])
+                .collect(),
+            DynProofPlan::MultiCount(multi_count_exec) => multi_count_exec
+                .predicates()
+                .iter()
+                .map(|aliased_expr| &aliased_expr.expr)
+                .collect(),
+            DynProofPlan::BucketCount(bucket_count_exec) => vec![bucket_count_exec.expr()],
+            DynProofPlan::PercentageOfTotal(percentage_of_total_exec) => percentage_of_total_exec
+                .aliased_results()
+                .iter()
+                .chain([percentage_of_total_exec.amount()])
+                .map(|aliased_expr| &aliased_expr.expr)
+                .collect(),
+        }
+    }
+
+    /// Get the number of generators a setup must have to prove this plan over the data in
+    /// `accessor`, covering both the data columns and the intermediate MLEs the proof commits to
+    ///
+    /// Compare the result with
+    /// [`Commitment::num_generators`](crate::base::commitment::Commitment::num_generators) of the
+    /// setup of a scheme.
+    /// See [`required_num_generators`] for details.
+    ///
+    /// # Errors
+    /// Returns an error if the first round evaluation of the plan fails, e.g. because of a
+    /// missing placeholder parameter.
+    pub fn required_setup_size<S: Scalar>(
+        &self,
+        accessor: &impl DataAccessor<S>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<usize> {
+        required_num_generators(core::slice::from_ref(self), accessor, params)
+    }
 }
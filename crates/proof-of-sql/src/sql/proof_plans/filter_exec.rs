@@ -80,21 +80,22 @@ where
         &self,
         builder: &mut impl VerificationBuilder<S>,
         accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
-        _result: Option<&OwnedTable<S>>,
+        result: Option<&OwnedTable<S>>,
         chi_eval_map: &IndexMap<TableRef, S>,
         params: &[LiteralValue],
     ) -> Result<TableEvaluation<S>, ProofError> {
         let input_chi_eval = *chi_eval_map
             .get(&self.table.table_ref)
             .expect("Chi eval not found");
+        // Borrow the evaluations of the table, which are only missing if no column is referenced
+        let no_column_evals = IndexMap::default();
         let accessor = accessor
             .get(&self.table.table_ref)
-            .cloned()
-            .unwrap_or_else(|| [].into_iter().collect());
+            .unwrap_or(&no_column_evals);
         // 1. selection
         let selection_eval =
             self.where_clause
-                .verifier_evaluate(builder, &accessor, input_chi_eval, params)?;
+                .verifier_evaluate(builder, accessor, input_chi_eval, params)?;
         // 2. columns
         let columns_evals = Vec::from_iter(
             self.aliased_results
@@ -102,7 +103,7 @@ where
                 .map(|aliased_expr| {
                     aliased_expr
                         .expr
-                        .verifier_evaluate(builder, &accessor, input_chi_eval, params)
+                        .verifier_evaluate(builder, accessor, input_chi_eval, params)
                 })
                 .collect::<Result<Vec<_>, _>>()?,
         );
@@ -114,7 +115,8 @@ where
         let alpha = builder.try_consume_post_result_challenge()?;
         let beta = builder.try_consume_post_result_challenge()?;
 
-        let output_chi_eval = builder.try_consume_chi_evaluation()?;
+        let (output_chi_eval, output_length) = builder.try_consume_chi_evaluation_with_length()?;
+        verify_output_length(builder, &self.table.table_ref, output_length)?;
 
         verify_filter(
             builder,
@@ -126,6 +128,9 @@ where
             selection_eval,
             &filtered_columns_evals,
         )?;
+        if let Some(result) = result {
+            verify_result_column_evals(builder, result, &filtered_columns_evals)?;
+        }
         Ok(TableEvaluation::new(
             filtered_columns_evals,
             output_chi_eval,
@@ -326,6 +331,52 @@ pub(super) fn verify_filter<S: Scalar>(
     Ok(())
 }
 
+/// Check that each column of the decoded result evaluates, at the point chosen by sumcheck,
+/// to the evaluation consumed from the proof
+///
+/// Only the number of columns is checked if the builder defers the result checks, since the
+/// caller then checks each column when it is accessed.
+pub(super) fn verify_result_column_evals<S: Scalar>(
+    builder: &impl VerificationBuilder<S>,
+    result: &OwnedTable<S>,
+    column_evals: &[S],
+) -> Result<(), ProofError> {
+    if result.num_columns() != column_evals.len() {
+        Err(ProofError::FieldCountMismatch)?;
+    }
+    if builder.result_checks_deferred() {
+        return Ok(());
+    }
+    result
+        .column_names()
+        .zip(result.mle_evaluations(builder.evaluation_point()))
+        .zip(column_evals)
+        .find(|((_, result_eval), column_eval)| result_eval != *column_eval)
+        .map_or(Ok(()), |((column, _), _)| {
+            Err(ProofError::ResultColumnMismatch {
+                column: column.clone(),
+            })
+        })
+}
+
+/// Check that the output length declared by the prover does not exceed the length of the input
+/// table, when the verifier knows it
+pub(super) fn verify_output_length<S: Scalar>(
+    builder: &impl VerificationBuilder<S>,
+    input_table_ref: &TableRef,
+    output_length: usize,
+) -> Result<(), ProofError> {
+    match builder.table_length(input_table_ref) {
+        Some(input_length) if output_length > input_length => {
+            Err(ProofError::OutputLengthExceedsInput {
+                output_length,
+                input_length,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 #[expect(clippy::too_many_arguments, clippy::many_single_char_names)]
 pub(super) fn prove_filter<'a, S: Scalar + 'a>(
     builder: &mut FinalRoundBuilder<'a, S>,
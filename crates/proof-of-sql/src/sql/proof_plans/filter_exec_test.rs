@@ -1,5 +1,6 @@
 use super::{test_utility::*, FilterExec};
 use crate::{
+    assert_tables_equal,
     base::{
         database::{
             owned_table_utility::*, table_utility::*, ColumnField, ColumnRef, ColumnType,
@@ -8,12 +9,15 @@ use crate::{
         },
         map::{indexmap, IndexMap, IndexSet},
         math::decimal::Precision,
+        proof::ProofError,
+        scalar::Scalar,
     },
     proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
     sql::{
         proof::{
             exercise_verification, FirstRoundBuilder, ProofPlan, ProvableQueryResult,
-            ProverEvaluate, VerifiableQueryResult,
+            ProverEvaluate, QueryError, SumcheckMleEvaluations, VerifiableQueryResult,
+            VerificationBuilderImpl, VerificationOptions,
         },
         proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr, LiteralExpr, TableExpr},
     },
@@ -170,7 +174,81 @@ fn we_can_prove_and_get_the_correct_result_from_a_basic_filter() {
         .unwrap()
         .table;
     let expected_res = owned_table([bigint("b", [3_i64, 5])]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_filter_over_a_table_whose_columns_are_committed_with_different_offsets() {
+    let data = owned_table([
+        bigint("a", [1_i64, 4_i64, 5_i64, 2_i64, 5_i64]),
+        bigint("b", [1_i64, 2, 3, 4, 5]),
+        varchar("c", ["t", "u", "v", "w", "x"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 2, ());
+    accessor.update_column_offset(&t, &"b".into(), 5);
+    accessor.update_column_offset(&t, &"c".into(), 0);
+    let where_clause = equal(column(&t, "a", &accessor), const_int128(5_i128));
+    let ast = filter(
+        cols_expr_plan(&t, &["b", "c"], &accessor),
+        tab(&t),
+        where_clause,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .clone()
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("b", [3_i64, 5]), varchar("c", ["v", "x"])]);
+    assert_tables_equal!(res, expected_res);
+
+    // The proof is bound to the offset of each column
+    let mut shifted_accessor = accessor.clone();
+    shifted_accessor.update_column_offset(&t, &"b".into(), 6);
+    assert!(verifiable_res
+        .clone()
+        .verify(&ast, &shifted_accessor, &(), &[])
+        .is_err());
+    let mut aligned_accessor = accessor;
+    aligned_accessor.update_column_offset(&t, &"b".into(), 2);
+    aligned_accessor.update_column_offset(&t, &"c".into(), 2);
+    assert!(verifiable_res
+        .verify(&ast, &aligned_accessor, &(), &[])
+        .is_err());
+}
+
+#[test]
+fn we_can_prove_a_filter_on_negative_bigints() {
+    let data = owned_table([
+        bigint("a", [-5_i64, 5, i64::MIN, -1, 0, -5, i64::MAX]),
+        bigint("b", [1_i64, 2, 3, 4, 5, 6, 7]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 3, ());
+    let where_clause = or(
+        equal(column(&t, "a", &accessor), const_bigint(-5)),
+        lte(column(&t, "a", &accessor), const_bigint(-6)),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        where_clause,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("a", [-5_i64, i64::MIN, -5]),
+        bigint("b", [1_i64, 3, 6]),
+    ]);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -219,7 +297,7 @@ fn we_can_get_an_empty_result_from_a_basic_filter_on_an_empty_table_using_first_
         decimal75("e", 75, 0, [0; 0]),
     ]);
 
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -268,7 +346,7 @@ fn we_can_get_an_empty_result_from_a_basic_filter_using_first_round_evaluate() {
         decimal75("e", 1, 0, [0; 0]),
     ]);
 
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -299,7 +377,7 @@ fn we_can_get_no_columns_from_a_basic_filter_with_no_selected_columns_using_firs
     .to_owned_table(fields)
     .unwrap();
     let expected = OwnedTable::try_new(IndexMap::default()).unwrap();
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -347,7 +425,7 @@ fn we_can_get_the_correct_result_from_a_basic_filter_using_first_round_evaluate(
         varchar("d", ["3", "5"]),
         decimal75("e", 1, 0, [3, 5]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -375,7 +453,7 @@ fn we_can_prove_a_filter_on_an_empty_table() {
         varchar("d", ["3"; 0]),
         scalar("e", [3; 0]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -404,7 +482,7 @@ fn we_can_prove_a_filter_with_empty_results() {
         varchar("d", ["3"; 0]),
         scalar("e", [3; 0]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -445,5 +523,130 @@ fn we_can_prove_a_filter() {
         int128("const", [105, 105]),
         boolean("bool", [true, false]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_a_filter_whose_result_was_tampered_with_after_proving() {
+    let data = owned_table([bigint("a", [5_i64, 5]), bigint("b", [20_i64, 40])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
+    let tampered_result = owned_table([bigint("a", [5_i64, 6]), bigint("b", [20_i64, 40])]);
+    assert!(matches!(
+        VerifiableQueryResult::verify_with_result(
+            res.proof,
+            tampered_result,
+            &expr,
+            &accessor,
+            &(),
+            &[],
+        ),
+        Err(QueryError::ProofError {
+            source: ProofError::ResultColumnMismatch { column }
+        }) if column == Ident::new("a")
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_filter_declaring_more_output_rows_than_input_rows() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2, 3])]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        const_bool(true),
+    );
+    let final_round_evaluations = [Curve25519Scalar::ZERO];
+    let mle_evaluations = SumcheckMleEvaluations {
+        chi_evaluations: indexmap! {4 => Curve25519Scalar::ONE},
+        final_round_pcs_proof_evaluations: &final_round_evaluations,
+        ..Default::default()
+    };
+    let mut builder = VerificationBuilderImpl::new(
+        mle_evaluations,
+        &[],
+        &[],
+        [Curve25519Scalar::ONE, Curve25519Scalar::ONE].into(),
+        vec![4],
+        Vec::new(),
+        3,
+        indexmap! {t.clone() => 3},
+        VerificationOptions::default(),
+    );
+    let column_evals = indexmap! {t.clone() => indexmap! {"a".into() => Curve25519Scalar::ZERO}};
+    let chi_eval_map = indexmap! {t => Curve25519Scalar::ONE};
+    assert!(matches!(
+        expr.verifier_evaluate(&mut builder, &column_evals, None, &chi_eval_map, &[]),
+        Err(ProofError::OutputLengthExceedsInput {
+            output_length: 4,
+            input_length: 3
+        })
+    ));
+}
+
+#[test]
+fn we_do_not_evaluate_the_result_columns_of_a_filter_if_the_result_checks_are_deferred() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2, 3])]),
+        0,
+        (),
+    );
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        const_bool(true),
+    );
+    // The result does not evaluate to the filtered column evaluation of zero
+    let result = owned_table([bigint("a", [1, 2, 3])]);
+    let evaluation_point = [Curve25519Scalar::from(2), Curve25519Scalar::from(3)];
+    let final_round_evaluations = [Curve25519Scalar::ZERO; 3];
+    let subpolynomial_multipliers = [Curve25519Scalar::ONE; 4];
+    let column_evals = indexmap! {t.clone() => indexmap! {"a".into() => Curve25519Scalar::ZERO}};
+    let chi_eval_map = indexmap! {t.clone() => Curve25519Scalar::ONE};
+    let verify = |result_checks_deferred: bool| {
+        let mle_evaluations = SumcheckMleEvaluations {
+            evaluation_point: &evaluation_point,
+            chi_evaluations: indexmap! {3 => Curve25519Scalar::ONE},
+            final_round_pcs_proof_evaluations: &final_round_evaluations,
+            ..Default::default()
+        };
+        let mut builder = VerificationBuilderImpl::new(
+            mle_evaluations,
+            &[],
+            &subpolynomial_multipliers,
+            [Curve25519Scalar::ONE, Curve25519Scalar::ONE].into(),
+            vec![3],
+            Vec::new(),
+            3,
+            indexmap! {t.clone() => 3},
+            VerificationOptions::default(),
+        )
+        .with_result_checks_deferred(result_checks_deferred);
+        expr.verifier_evaluate(
+            &mut builder,
+            &column_evals,
+            Some(&result),
+            &chi_eval_map,
+            &[],
+        )
+    };
+    assert!(matches!(
+        verify(false),
+        Err(ProofError::ResultColumnMismatch { column }) if column == Ident::new("a")
+    ));
+    assert!(verify(true).is_ok());
 }
@@ -0,0 +1,469 @@
+use super::{filter_exec::verify_result_column_evals, DynProofPlan};
+use crate::{
+    base::{
+        database::{
+            apply_column_to_indexes,
+            order_by_util::{compare_indexes_by_columns, compare_indexes_by_owned_columns},
+            Column, ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, Table,
+            TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_membership_check, final_round_evaluate_sign,
+            first_round_evaluate_membership_check, verifier_evaluate_sign, verify_membership_check,
+        },
+        AnalyzeError, AnalyzeResult,
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::cmp::Ordering;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     SELECT DISTINCT ON (<key1>, ..., <keyK>) * FROM <ProofPlan>
+///     ORDER BY <key1>, ..., <keyK>, <tiebreaker> [ASC | DESC]
+/// ```
+///
+/// The result has one row per distinct key, the row of the input with the maximum tiebreaker
+/// within the key if `tiebreaker_descending` and the minimum otherwise, ordered by the key.
+/// Rows with the same tiebreaker are told apart by their index in the input, the earliest one
+/// being kept.
+///
+/// Both rules are captured by the order value `v(i) = ±tiebreaker(i) * 2^64 - i` of input row `i`,
+/// whose unique maximum within a key is the kept row. The prover commits to `M`, the order value
+/// of the kept row of the key of each input row, and shows that
+/// 1. every result row, along with its index `r`, is a row of the input along with its index,
+/// 2. every `(key, M)` of the input is the `(key, v(r))` of a result row and
+/// 3. `M - v` is nonnegative.
+///
+/// Since the keys of the result are distinct, which the verifier checks by its order, the result
+/// row of a key is its kept row.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct FirstPerGroupExec {
+    pub(super) input: Box<DynProofPlan>,
+    pub(super) key_column_indexes: Vec<usize>,
+    pub(super) tiebreaker_column_index: usize,
+    pub(super) tiebreaker_descending: bool,
+}
+
+/// The columns of the proof of a [`FirstPerGroupExec`]
+struct FirstPerGroupColumns<'a, S: Scalar> {
+    /// The result
+    res: Table<'a, S>,
+    /// The input columns followed by the row indexes of the input
+    input_hat_columns: Vec<Column<'a, S>>,
+    /// The result columns followed by the row indexes of the result rows in the input
+    res_hat_columns: Vec<Column<'a, S>>,
+    /// The keys of the input followed by `M`
+    input_order_columns: Vec<Column<'a, S>>,
+    /// The keys of the result followed by the order values of the result rows
+    res_order_columns: Vec<Column<'a, S>>,
+    /// `M`
+    max_order_values: &'a [S],
+    /// `M - v` over the input
+    slack: &'a [S],
+}
+
+impl FirstPerGroupExec {
+    /// Creates a new first per group plan.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnalyzeError::InvalidFirstPerGroup`] if there are no key columns or a column
+    /// index is out of bounds, and [`AnalyzeError::InvalidDataType`] if the tiebreaker is not an
+    /// integer or a timestamp.
+    pub fn try_new(
+        input: Box<DynProofPlan>,
+        key_column_indexes: Vec<usize>,
+        tiebreaker_column_index: usize,
+        tiebreaker_descending: bool,
+    ) -> AnalyzeResult<Self> {
+        if key_column_indexes.is_empty() {
+            return Err(AnalyzeError::InvalidFirstPerGroup {
+                reason: "there are no key columns",
+            });
+        }
+        let fields = input.get_column_result_fields();
+        let tiebreaker_field = key_column_indexes
+            .iter()
+            .all(|&index| index < fields.len())
+            .then(|| fields.get(tiebreaker_column_index))
+            .flatten()
+            .ok_or(AnalyzeError::InvalidFirstPerGroup {
+                reason: "a column index is out of bounds",
+            })?;
+        let expr_type = tiebreaker_field.data_type();
+        if !expr_type.is_integer() && !matches!(expr_type, ColumnType::TimestampTZ(_, _)) {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        Ok(Self {
+            input,
+            key_column_indexes,
+            tiebreaker_column_index,
+            tiebreaker_descending,
+        })
+    }
+
+    /// Get a reference to the input plan
+    pub fn input(&self) -> &DynProofPlan {
+        &self.input
+    }
+
+    /// Get the indexes of the key columns in the input
+    pub fn key_column_indexes(&self) -> &[usize] {
+        &self.key_column_indexes
+    }
+
+    /// Get the index of the tiebreaker column in the input
+    pub fn tiebreaker_column_index(&self) -> usize {
+        self.tiebreaker_column_index
+    }
+
+    /// Whether the row with the maximum rather than the minimum tiebreaker is kept
+    pub fn tiebreaker_descending(&self) -> bool {
+        self.tiebreaker_descending
+    }
+
+    /// The order value `±tiebreaker * 2^64 - row_index` of a row, or of evaluations since it is
+    /// linear
+    fn order_value<S: Scalar>(&self, tiebreaker: S, row_index: S) -> S {
+        let tiebreaker = if self.tiebreaker_descending {
+            tiebreaker
+        } else {
+            -tiebreaker
+        };
+        tiebreaker * S::TWO_POW_64 - row_index
+    }
+
+    /// The indexes of the kept rows of `input` ordered by key, along with the index of the kept
+    /// row of the key of each row
+    fn kept_rows<S: Scalar>(&self, input: &Table<'_, S>) -> (Vec<usize>, Vec<usize>) {
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        let keys = self
+            .key_column_indexes
+            .iter()
+            .map(|&index| columns[index])
+            .collect::<Vec<_>>();
+        let tiebreaker = [columns[self.tiebreaker_column_index]];
+        // The sort is stable, so rows with the same key and tiebreaker stay in input order
+        let sorted_rows = (0..input.num_rows())
+            .sorted_by(|&i, &j| {
+                compare_indexes_by_columns(&keys, i, j).then_with(|| {
+                    let ordering = compare_indexes_by_columns(&tiebreaker, i, j);
+                    if self.tiebreaker_descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut kept_rows = Vec::new();
+        let mut kept_row_of_key = vec![0; input.num_rows()];
+        for (position, &row) in sorted_rows.iter().enumerate() {
+            if position == 0
+                || compare_indexes_by_columns(&keys, sorted_rows[position - 1], row)
+                    != Ordering::Equal
+            {
+                kept_rows.push(row);
+            }
+            kept_row_of_key[row] = *kept_rows.last().expect("A row was just kept");
+        }
+        (kept_rows, kept_row_of_key)
+    }
+
+    /// Compute the result and the columns of the proof from the input
+    fn first_per_group_columns<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        input: &Table<'a, S>,
+    ) -> FirstPerGroupColumns<'a, S> {
+        let num_rows = input.num_rows();
+        let (kept_rows, kept_row_of_key) = self.kept_rows(input);
+        let input_columns = input.columns().copied().collect::<Vec<_>>();
+        let res_columns = input_columns
+            .iter()
+            .map(|column| apply_column_to_indexes(column, alloc, &kept_rows))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Indexes can not be out of bounds");
+        let res = Table::try_from_iter_with_options(
+            input
+                .column_names()
+                .cloned()
+                .zip_eq(res_columns.iter().copied()),
+            TableOptions::new(Some(kept_rows.len())),
+        )
+        .expect("Columns of the input have the same length");
+        let kept_row_indexes =
+            alloc.alloc_slice_fill_iter(kept_rows.iter().map(|&row| row as i128));
+        let order_values = input_columns[self.tiebreaker_column_index]
+            .to_scalar()
+            .into_iter()
+            .enumerate()
+            .map(|(row, tiebreaker)| self.order_value(tiebreaker, S::from(row as u64)))
+            .collect::<Vec<_>>();
+        let max_order_values = alloc.alloc_slice_fill_iter(
+            kept_row_of_key
+                .iter()
+                .map(|&kept_row| order_values[kept_row]),
+        );
+        let kept_order_values =
+            alloc.alloc_slice_fill_iter(kept_rows.iter().map(|&row| order_values[row]));
+        let slack = alloc.alloc_slice_fill_iter(
+            max_order_values
+                .iter()
+                .zip_eq(&order_values)
+                .map(|(&max_order_value, &order_value)| max_order_value - order_value),
+        );
+        let key_columns = |columns: &[Column<'a, S>]| {
+            self.key_column_indexes
+                .iter()
+                .map(|&index| columns[index])
+                .collect::<Vec<_>>()
+        };
+        let input_order_columns = key_columns(&input_columns)
+            .into_iter()
+            .chain([Column::Scalar(max_order_values as &[_])])
+            .collect();
+        let res_order_columns = key_columns(&res_columns)
+            .into_iter()
+            .chain([Column::Scalar(kept_order_values as &[_])])
+            .collect();
+        let input_hat_columns = input_columns
+            .into_iter()
+            .chain([Column::rho(num_rows, alloc)])
+            .collect();
+        let res_hat_columns = res_columns
+            .into_iter()
+            .chain([Column::Int128(kept_row_indexes as &[_])])
+            .collect();
+        FirstPerGroupColumns {
+            res,
+            input_hat_columns,
+            res_hat_columns,
+            input_order_columns,
+            res_order_columns,
+            max_order_values,
+            slack,
+        }
+    }
+}
+
+impl ProofPlan for FirstPerGroupExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let input_chi_eval = input_eval.chi_eval();
+        let res_chi_eval = builder.try_consume_chi_evaluation()?;
+        let rho_eval = builder.try_consume_rho_evaluation()?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        let input_column_evals = input_eval.column_evals();
+        let num_columns = input_column_evals.len();
+        let res_hat_column_evals =
+            builder.try_consume_final_round_mle_evaluations(num_columns + 1)?;
+        let max_order_value_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let (res_column_evals, kept_row_index_eval) = (
+            &res_hat_column_evals[..num_columns],
+            res_hat_column_evals[num_columns],
+        );
+        // 1. The result rows are rows of the input
+        let input_hat_column_evals = input_column_evals
+            .iter()
+            .copied()
+            .chain([rho_eval])
+            .collect::<Vec<_>>();
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            input_chi_eval,
+            res_chi_eval,
+            &input_hat_column_evals,
+            &res_hat_column_evals,
+        )?;
+        // 2. `M` is the order value of the result row of the key of each input row
+        let key_evals = |column_evals: &[S]| {
+            self.key_column_indexes
+                .iter()
+                .map(|&index| column_evals[index])
+                .collect::<Vec<_>>()
+        };
+        let res_order_evals = key_evals(res_column_evals)
+            .into_iter()
+            .chain([self.order_value(
+                res_column_evals[self.tiebreaker_column_index],
+                kept_row_index_eval,
+            )])
+            .collect::<Vec<_>>();
+        let input_order_evals = key_evals(input_column_evals)
+            .into_iter()
+            .chain([max_order_value_eval])
+            .collect::<Vec<_>>();
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            res_chi_eval,
+            input_chi_eval,
+            &res_order_evals,
+            &input_order_evals,
+        )?;
+        // 3. `M` is the maximum order value of the key of each input row
+        let slack_eval = max_order_value_eval
+            - self.order_value(input_column_evals[self.tiebreaker_column_index], rho_eval);
+        if verifier_evaluate_sign(builder, slack_eval, input_chi_eval, None)? != S::ZERO {
+            return Err(ProofError::VerificationError {
+                error: "Result of first per group is not the first row of a group.",
+            });
+        }
+        match result {
+            Some(table) => {
+                let columns = table.inner_table().values().collect::<Vec<_>>();
+                let keys = self
+                    .key_column_indexes
+                    .iter()
+                    .map(|&index| columns.get(index).copied())
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(ProofError::VerificationError {
+                        error: "Result does not have all key columns.",
+                    })?;
+                if (1..table.num_rows())
+                    .any(|i| compare_indexes_by_owned_columns(&keys, i - 1, i).is_ge())
+                {
+                    return Err(ProofError::VerificationError {
+                        error: "Result of first per group not ordered as expected.",
+                    });
+                }
+                verify_result_column_evals(builder, table, res_column_evals)?;
+            }
+            None => {
+                return Err(ProofError::UnsupportedQueryPlan {
+                    error: "FirstPerGroupExec currently only supported at top level of query plan.",
+                });
+            }
+        }
+        Ok(TableEvaluation::new(
+            res_column_evals.to_vec(),
+            res_chi_eval,
+        ))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for FirstPerGroupExec {
+    #[tracing::instrument(
+        name = "FirstPerGroupExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let input = self
+            .input
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let columns = self.first_per_group_columns(alloc, &input);
+        builder.produce_intermediate_mle(columns.max_order_values);
+        builder.produce_chi_evaluation_length(columns.res.num_rows());
+        builder.produce_rho_evaluation_length(input.num_rows());
+        first_round_evaluate_membership_check(
+            builder,
+            alloc,
+            &columns.input_hat_columns,
+            &columns.res_hat_columns,
+        );
+        first_round_evaluate_membership_check(
+            builder,
+            alloc,
+            &columns.res_order_columns,
+            &columns.input_order_columns,
+        );
+        builder.request_post_result_challenges(2);
+        Ok(columns.res)
+    }
+
+    #[tracing::instrument(
+        name = "FirstPerGroupExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let input = self
+            .input
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let columns = self.first_per_group_columns(alloc, &input);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        for column in &columns.res_hat_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        let chi_n = alloc.alloc_slice_fill_copy(input.num_rows(), true);
+        let chi_m = alloc.alloc_slice_fill_copy(columns.res.num_rows(), true);
+        // 1. The result rows are rows of the input
+        final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_n,
+            chi_m,
+            &columns.input_hat_columns,
+            &columns.res_hat_columns,
+        );
+        // 2. `M` is the order value of the result row of the key of each input row
+        final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_m,
+            chi_n,
+            &columns.res_order_columns,
+            &columns.input_order_columns,
+        );
+        // 3. `M` is the maximum order value of the key of each input row
+        final_round_evaluate_sign(builder, alloc, columns.slack);
+        Ok(columns.res)
+    }
+}
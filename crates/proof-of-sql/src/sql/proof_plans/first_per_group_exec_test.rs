@@ -0,0 +1,232 @@
+use super::{test_utility::*, FirstPerGroupExec};
+use crate::{
+    assert_tables_equal,
+    base::{
+        database::{
+            owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+        proof::ProofError,
+    },
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+        AnalyzeError,
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+/// The rows of the keys `a` and `b` have the same extreme tiebreakers
+fn accessor_with_duplicate_tiebreakers(t: &TableRef) -> OwnedTableTestAccessor<InnerProductProof> {
+    let data = owned_table([
+        varchar("k", ["b", "a", "b", "a", "b", "c", "a"]),
+        bigint("ts", [3, 5, 7, 5, 7, 1, 2]),
+        bigint("v", [1, 2, 3, 4, 5, 6, 7]),
+        bigint("d", [0, 0, 0, 0, 0, 0, 1]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    accessor
+}
+
+/// `select distinct on (k) k, ts, v from sxt.t where d = 0 order by k, ts desc`
+#[test]
+fn we_can_prove_the_rows_with_the_maximum_tiebreaker_of_each_key() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_duplicate_tiebreakers(&t);
+    let ast = first_per_group(
+        filter(
+            cols_expr_plan(&t, &["k", "ts", "v"], &accessor),
+            tab(&t),
+            equal(column(&t, "d", &accessor), const_bigint(0)),
+        ),
+        vec![0],
+        1,
+        true,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    // The earliest of the rows with the same maximum tiebreaker is kept
+    let expected_res = owned_table([
+        varchar("k", ["a", "b", "c"]),
+        bigint("ts", [5, 7, 1]),
+        bigint("v", [2, 3, 6]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+/// `select distinct on (k) k, ts, v from sxt.t where d = 0 order by k, ts`
+#[test]
+fn we_can_prove_the_rows_with_the_minimum_tiebreaker_of_each_key() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_duplicate_tiebreakers(&t);
+    let ast = first_per_group(
+        filter(
+            cols_expr_plan(&t, &["v", "ts", "k"], &accessor),
+            tab(&t),
+            equal(column(&t, "d", &accessor), const_bigint(0)),
+        ),
+        vec![2],
+        1,
+        false,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("v", [2, 1, 6]),
+        bigint("ts", [5, 3, 1]),
+        varchar("k", ["a", "b", "c"]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+/// `select distinct on (a, b) * from sxt.t order by a, b, ts desc` with a table at an offset
+#[test]
+fn we_can_prove_the_first_rows_of_multiple_keys_with_a_timestamp_tiebreaker() {
+    let data = owned_table([
+        bigint("a", [1, 1, 2, 1, 1, 2]),
+        varchar("b", ["x", "y", "x", "x", "y", "x"]),
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [-10, 20, 30, 40, -50, 30],
+        ),
+        int128("v", [1, 2, 3, 4, 5, 6]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 3);
+    let ast = first_per_group(
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("a", ColumnType::BigInt),
+                column_field("b", ColumnType::VarChar),
+                column_field(
+                    "ts",
+                    ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+                ),
+                column_field("v", ColumnType::Int128),
+            ],
+        ),
+        vec![0, 1],
+        2,
+        true,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("a", [1, 1, 2]),
+        varchar("b", ["x", "y", "x"]),
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [40, 20, 30],
+        ),
+        int128("v", [4, 2, 3]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_the_first_rows_of_an_empty_input() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_duplicate_tiebreakers(&t);
+    let ast = first_per_group(
+        filter(
+            cols_expr_plan(&t, &["k", "ts"], &accessor),
+            tab(&t),
+            equal(column(&t, "d", &accessor), const_bigint(2)),
+        ),
+        vec![0],
+        1,
+        true,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([varchar("k", [""; 0]), bigint("ts", [0_i64; 0])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_prove_the_first_rows_which_are_not_at_the_top_level_for_now() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_duplicate_tiebreakers(&t);
+    let ast = slice_exec(
+        first_per_group(
+            filter(
+                cols_expr_plan(&t, &["k", "ts"], &accessor),
+                tab(&t),
+                const_bool(true),
+            ),
+            vec![0],
+            1,
+            true,
+        ),
+        1,
+        None,
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::UnsupportedQueryPlan { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_create_first_rows_with_invalid_keys_or_tiebreakers() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_duplicate_tiebreakers(&t);
+    let try_new = |key_column_indexes: Vec<usize>, tiebreaker_column_index: usize| {
+        FirstPerGroupExec::try_new(
+            Box::new(filter(
+                cols_expr_plan(&t, &["k", "ts"], &accessor),
+                tab(&t),
+                const_bool(true),
+            )),
+            key_column_indexes,
+            tiebreaker_column_index,
+            true,
+        )
+    };
+    assert!(try_new(vec![0], 1).is_ok());
+    assert!(matches!(
+        try_new(vec![], 1),
+        Err(AnalyzeError::InvalidFirstPerGroup { .. })
+    ));
+    assert!(matches!(
+        try_new(vec![2], 1),
+        Err(AnalyzeError::InvalidFirstPerGroup { .. })
+    ));
+    assert!(matches!(
+        try_new(vec![0], 2),
+        Err(AnalyzeError::InvalidFirstPerGroup { .. })
+    ));
+    assert_eq!(
+        try_new(vec![1], 0).unwrap_err(),
+        AnalyzeError::InvalidDataType {
+            expr_type: ColumnType::VarChar
+        }
+    );
+}
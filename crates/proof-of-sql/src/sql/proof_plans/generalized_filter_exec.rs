@@ -0,0 +1,272 @@
+use super::{
+    filter_exec::{prove_filter, verify_filter, verify_result_column_evals},
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            filter_util::filter_columns, Column, ColumnField, ColumnRef, LiteralValue, OwnedTable,
+            Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <result_expr1>, ..., <result_exprN> FROM <input> WHERE <where_clause>
+/// ```
+///
+/// This differs from the [`FilterExec`](super::FilterExec) in that the input is the result of
+/// another plan rather than a table, so that subqueries in `FROM` can be filtered. The input is
+/// proven along with the filter, sharing its transcript, and the expressions refer to the columns
+/// of the input by their names.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct GeneralizedFilterExec {
+    pub(super) aliased_results: Vec<AliasedDynProofExpr>,
+    pub(super) input: Box<DynProofPlan>,
+    pub(super) where_clause: DynProofExpr,
+}
+
+impl GeneralizedFilterExec {
+    /// Creates a new generalized filter plan.
+    pub fn new(
+        aliased_results: Vec<AliasedDynProofExpr>,
+        input: Box<DynProofPlan>,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        Self {
+            aliased_results,
+            input,
+            where_clause,
+        }
+    }
+
+    /// Get a reference to the input plan
+    pub fn input(&self) -> &DynProofPlan {
+        &self.input
+    }
+
+    /// Get the aliased results
+    pub fn aliased_results(&self) -> &[AliasedDynProofExpr] {
+        &self.aliased_results
+    }
+
+    /// Get the where clause expression
+    pub fn where_clause(&self) -> &DynProofExpr {
+        &self.where_clause
+    }
+
+    /// Evaluate the selection and the result expressions over `input` with `evaluate` and
+    /// filter the results
+    fn filter<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        mut evaluate: impl FnMut(&DynProofExpr) -> PlaceholderResult<Column<'a, S>>,
+    ) -> PlaceholderResult<(&'a [bool], Vec<Column<'a, S>>, Vec<Column<'a, S>>, usize)> {
+        // 1. selection
+        let selection = evaluate(&self.where_clause)?
+            .as_boolean()
+            .expect("selection is not boolean");
+        // 2. columns
+        let columns = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| evaluate(&aliased_expr.expr))
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let (filtered_columns, output_length) = filter_columns(alloc, &columns, selection);
+        Ok((selection, columns, filtered_columns, output_length))
+    }
+
+    fn result_table<'a, S: Scalar>(
+        &self,
+        filtered_columns: Vec<Column<'a, S>>,
+        output_length: usize,
+    ) -> Table<'a, S> {
+        Table::<'a, S>::try_from_iter_with_options(
+            self.aliased_results
+                .iter()
+                .map(|expr| expr.alias.clone())
+                .zip(filtered_columns),
+            TableOptions::new(Some(output_length)),
+        )
+        .expect("Failed to create table from iterator")
+    }
+}
+
+impl ProofPlan for GeneralizedFilterExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let input_chi_eval = input_eval.chi_eval();
+        let input_accessor = self
+            .input
+            .get_column_result_fields()
+            .iter()
+            .zip(input_eval.column_evals())
+            .map(|(field, eval)| (field.name().clone(), *eval))
+            .collect::<IndexMap<_, _>>();
+        // 1. selection
+        let selection_eval = self.where_clause.verifier_evaluate(
+            builder,
+            &input_accessor,
+            input_chi_eval,
+            params,
+        )?;
+        // 2. columns
+        let columns_evals = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr.expr.verifier_evaluate(
+                    builder,
+                    &input_accessor,
+                    input_chi_eval,
+                    params,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // 3. filtered_columns
+        let filtered_columns_evals =
+            builder.try_consume_final_round_mle_evaluations(self.aliased_results.len())?;
+
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+
+        let output_chi_eval = builder.try_consume_chi_evaluation()?;
+
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            input_chi_eval,
+            output_chi_eval,
+            &columns_evals,
+            selection_eval,
+            &filtered_columns_evals,
+        )?;
+        if let Some(result) = result {
+            verify_result_column_evals(builder, result, &filtered_columns_evals)?;
+        }
+        Ok(TableEvaluation::new(
+            filtered_columns_evals,
+            output_chi_eval,
+        ))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
+            })
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        // Every column the expressions refer to is a column of the input
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for GeneralizedFilterExec {
+    #[tracing::instrument(
+        name = "GeneralizedFilterExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let input = self
+            .input
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let (_, _, filtered_columns, output_length) = self.filter(alloc, |expr| {
+            expr.first_round_evaluate(alloc, &input, params)
+        })?;
+        let res = self.result_table(filtered_columns, output_length);
+        builder.request_post_result_challenges(2);
+        builder.produce_chi_evaluation_length(output_length);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(
+        name = "GeneralizedFilterExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let input = self
+            .input
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let (selection, columns, filtered_columns, output_length) = self.filter(alloc, |expr| {
+            expr.final_round_evaluate(builder, alloc, &input, params)
+        })?;
+        // 3. Produce MLEs
+        filtered_columns.iter().copied().for_each(|column| {
+            builder.produce_intermediate_mle(column);
+        });
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        prove_filter::<S>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &columns,
+            selection,
+            &filtered_columns,
+            input.num_rows(),
+            output_length,
+        );
+        let res = self.result_table(filtered_columns, output_length);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,174 @@
+use super::test_utility::*;
+use crate::{
+    assert_tables_equal,
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTableTestAccessor, TableRef,
+            TestAccessor,
+        },
+        map::IndexSet,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+fn schema() -> Vec<ColumnField> {
+    vec![
+        ColumnField::new("a".into(), ColumnType::BigInt),
+        ColumnField::new("b".into(), ColumnType::BigInt),
+        ColumnField::new("c".into(), ColumnType::VarChar),
+    ]
+}
+
+fn accessor_with_table(t: &TableRef) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let data = owned_table([
+        bigint("a", [1_i64, 4, 5, 2, 5, 7, 3]),
+        bigint("b", [1_i64, 2, 3, 4, 5, 6, 7]),
+        varchar("c", ["t", "u", "v", "w", "x", "y", "z"]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    accessor
+}
+
+#[test]
+fn we_can_correctly_fetch_the_result_schema_and_references_of_a_generalized_filter() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_table(&t);
+    let plan = generalized_filter(
+        vec![
+            col_expr_plan(&t, "c", &accessor),
+            aliased_plan(
+                add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "sum",
+            ),
+        ],
+        table_exec(t.clone(), schema()),
+        gte(column(&t, "a", &accessor), const_bigint(2)),
+    );
+    assert_eq!(
+        plan.get_column_result_fields(),
+        vec![
+            ColumnField::new("c".into(), ColumnType::VarChar),
+            ColumnField::new(
+                "sum".into(),
+                ColumnType::Decimal75(20.try_into().unwrap(), 0)
+            ),
+        ]
+    );
+    assert_eq!(
+        plan.get_table_references(),
+        IndexSet::from_iter([t.clone()])
+    );
+    assert_eq!(
+        plan.get_column_references(),
+        table_exec(t.clone(), schema()).get_column_references()
+    );
+    assert_eq!(plan.inputs(), vec![&table_exec(t.clone(), schema())]);
+    assert_eq!(plan.exprs().len(), 3);
+}
+
+#[test]
+fn we_can_prove_a_generalized_filter_on_a_table() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_table(&t);
+    let plan = generalized_filter(
+        vec![
+            col_expr_plan(&t, "c", &accessor),
+            aliased_plan(
+                add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "sum",
+            ),
+        ],
+        table_exec(t.clone(), schema()),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &plan, &accessor, &t);
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected = owned_table([
+        varchar("c", ["v", "x"]),
+        decimal75("sum", 20, 0, [8_i64, 10]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_two_level_nested_filter() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_table(&t);
+    // SELECT c, b FROM (SELECT a, b, c FROM (SELECT * FROM t) WHERE a >= 3) WHERE b <= 5
+    let plan = generalized_filter(
+        cols_expr_plan(&t, &["c", "b"], &accessor),
+        generalized_filter(
+            cols_expr_plan(&t, &["a", "b", "c"], &accessor),
+            table_exec(t.clone(), schema()),
+            gte(column(&t, "a", &accessor), const_bigint(3)),
+        ),
+        lte(column(&t, "b", &accessor), const_bigint(5)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &plan, &accessor, &t);
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected = owned_table([varchar("c", ["u", "v", "x"]), bigint("b", [2_i64, 3, 5])]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_generalized_filter_over_a_filter_and_a_slice() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_table(&t);
+    // SELECT a, c FROM (SELECT a, b, c FROM t WHERE b >= 2 LIMIT 4) WHERE a = 5
+    let plan = generalized_filter(
+        cols_expr_plan(&t, &["a", "c"], &accessor),
+        slice_exec(
+            filter(
+                cols_expr_plan(&t, &["a", "b", "c"], &accessor),
+                tab(&t),
+                gte(column(&t, "b", &accessor), const_bigint(2)),
+            ),
+            0,
+            Some(4),
+        ),
+        equal(column(&t, "a", &accessor), const_bigint(5)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &plan, &accessor, &t);
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected = owned_table([bigint("a", [5_i64, 5]), varchar("c", ["v", "x"])]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_generalized_filter_with_an_empty_result() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = accessor_with_table(&t);
+    let plan = generalized_filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        generalized_filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            table_exec(t.clone(), schema()),
+            gte(column(&t, "a", &accessor), const_bigint(6)),
+        ),
+        lte(column(&t, "b", &accessor), const_bigint(5)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected = owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]);
+    assert_tables_equal!(res, expected);
+}
@@ -1,4 +1,7 @@
-use super::{fold_columns, fold_vals};
+use super::{
+    filter_exec::{verify_output_length, verify_result_column_evals},
+    fold_columns, fold_vals,
+};
 use crate::{
     base::{
         database::{
@@ -8,6 +11,7 @@ use crate::{
             TableEvaluation, TableRef,
         },
         map::{IndexMap, IndexSet},
+        polynomial::MultilinearExtension,
         proof::{PlaceholderResult, ProofError},
         scalar::Scalar,
         slice_ops,
@@ -18,6 +22,9 @@ use crate::{
             SumcheckSubpolynomialType, VerificationBuilder,
         },
         proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, verify_monotonic,
+        },
     },
     utils::log,
 };
@@ -32,18 +39,43 @@ use sqlparser::ast::Ident;
 /// ```ignore
 ///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
 ///         SUM(<sum_expr1>.expr) as <sum_expr1>.alias, ..., SUM(<sum_exprN>.expr) as <sum_exprN>.alias,
-///         COUNT(*) as count_alias
+///         COUNT(*) as count_alias,
+///         COUNT(DISTINCT <count_distinct_expr1>.0) FILTER (WHERE <count_distinct_filter1>)
+///             as <count_distinct_expr1>.1, ...
 ///     FROM <table>
 ///     WHERE <where_clause>
 ///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
 /// ```
 ///
+/// Each group by expression is either a plain column or a computed key such as `x % 10`, which is
+/// output under its alias.
+///
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+/// As in SQL, the result then always has exactly one row, even if no rows are selected. Over an
+/// empty selection `COUNT(*)` and `COUNT(DISTINCT ..)` are 0 in that row. SQL would make the sums
+/// `NULL`, but since columns are not nullable they are 0 as well.
+///
+/// # Distinct counts
+/// For each `COUNT(DISTINCT ..)` the prover commits to `D`, the distinct (group, value) pairs of the
+/// selected rows sorted by group and then by value, along with the number of selected rows of each pair
+/// and the index of the output group of each pair. Then
+/// 1. A logup over the selected rows shows that every selected pair occurs in `D` and, since every
+///    multiplicity is nonzero, that every row of `D` is a selected pair.
+/// 2. A logup of the group index and group columns of `D` against the row index and group columns of
+///    the output shows that the distinct count of each group is the number of rows of `D` in it.
+/// 3. `2^64 * group_index + value` is strictly increasing on `D`, so the rows of `D` are distinct.
+///
+/// A distinct count with a `FILTER (WHERE ..)` only counts the selected rows passing its filter, so its
+/// output groups may have no pairs in `D` and a distinct count of 0. The filter shares the scan of the
+/// aggregation: the selection is multiplied by the filter in the first logup instead of being
+/// committed to separately.
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct GroupByExec {
-    pub(super) group_by_exprs: Vec<ColumnExpr>,
+    pub(super) group_by_exprs: Vec<AliasedDynProofExpr>,
     pub(super) sum_expr: Vec<AliasedDynProofExpr>,
     pub(super) count_alias: Ident,
+    pub(super) count_distinct_exprs: Vec<(ColumnExpr, Ident)>,
+    pub(super) count_distinct_filters: Vec<Option<DynProofExpr>>,
     pub(super) table: TableExpr,
     pub(super) where_clause: DynProofExpr,
 }
@@ -57,10 +89,119 @@ impl GroupByExec {
         table: TableExpr,
         where_clause: DynProofExpr,
     ) -> Self {
+        Self::new_with_count_distinct(
+            group_by_exprs,
+            sum_expr,
+            count_alias,
+            Vec::new(),
+            table,
+            where_clause,
+        )
+    }
+
+    /// Creates a new `group_by` expression that also counts the distinct values of some columns.
+    ///
+    /// # Panics
+    /// Panics if a distinct column is not an integer of at most 64 bits, a boolean or a timestamp.
+    pub fn new_with_count_distinct(
+        group_by_exprs: Vec<ColumnExpr>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        count_distinct_exprs: Vec<(ColumnExpr, Ident)>,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        let count_distinct_filters = vec![None; count_distinct_exprs.len()];
+        Self::new_with_filtered_count_distinct(
+            group_by_exprs,
+            sum_expr,
+            count_alias,
+            count_distinct_exprs,
+            count_distinct_filters,
+            table,
+            where_clause,
+        )
+    }
+
+    /// Creates a new `group_by` expression that also counts the distinct values of some columns,
+    /// each only over the selected rows passing its optional filter.
+    ///
+    /// # Panics
+    /// Panics if one of the following conditions is met:
+    /// - A distinct column is not an integer of at most 64 bits, a boolean or a timestamp
+    /// - The number of filters differs from the number of distinct columns
+    /// - A filter is not boolean
+    pub fn new_with_filtered_count_distinct(
+        group_by_exprs: Vec<ColumnExpr>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        count_distinct_exprs: Vec<(ColumnExpr, Ident)>,
+        count_distinct_filters: Vec<Option<DynProofExpr>>,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        Self::new_with_aliased_group_by_exprs(
+            group_by_exprs
+                .into_iter()
+                .map(|column| AliasedDynProofExpr {
+                    alias: column.column_id(),
+                    expr: DynProofExpr::Column(column),
+                })
+                .collect(),
+            sum_expr,
+            count_alias,
+            count_distinct_exprs,
+            count_distinct_filters,
+            table,
+            where_clause,
+        )
+    }
+
+    /// Creates a new `group_by` expression whose groups are the distinct values of arbitrary
+    /// expressions, each output under its alias, e.g. `GROUP BY x % 10` with the alias `bucket`.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`Self::new_with_filtered_count_distinct`].
+    pub fn new_with_aliased_group_by_exprs(
+        group_by_exprs: Vec<AliasedDynProofExpr>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        count_distinct_exprs: Vec<(ColumnExpr, Ident)>,
+        count_distinct_filters: Vec<Option<DynProofExpr>>,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        assert!(
+            count_distinct_exprs.iter().all(|(expr, _)| matches!(
+                expr.data_type(),
+                ColumnType::Boolean
+                    | ColumnType::Uint8
+                    | ColumnType::TinyInt
+                    | ColumnType::SmallInt
+                    | ColumnType::Int
+                    | ColumnType::BigInt
+                    | ColumnType::TimestampTZ(_, _)
+            )),
+            "Count distinct columns should be integers of at most 64 bits, booleans or timestamps"
+        );
+        assert_eq!(
+            count_distinct_filters.len(),
+            count_distinct_exprs.len(),
+            "Every count distinct column should have exactly one optional filter"
+        );
+        assert!(
+            count_distinct_filters
+                .iter()
+                .flatten()
+                .all(|filter| filter.data_type() == ColumnType::Boolean),
+            "Count distinct filters should be boolean"
+        );
         Self {
             group_by_exprs,
             sum_expr,
             count_alias,
+            count_distinct_exprs,
+            count_distinct_filters,
             table,
             where_clause,
         }
@@ -76,11 +217,25 @@ impl GroupByExec {
         &self.where_clause
     }
 
-    /// Get a reference to the group by expressions
-    pub fn group_by_exprs(&self) -> &[ColumnExpr] {
+    /// Get a reference to the group by expressions with their aliases
+    pub fn group_by_exprs(&self) -> &[AliasedDynProofExpr] {
         &self.group_by_exprs
     }
 
+    /// Get the group by columns if every group by expression is a plain column output under its
+    /// own name, as for the expressions created by [`Self::new_with_filtered_count_distinct`]
+    pub fn group_by_columns(&self) -> Option<Vec<ColumnExpr>> {
+        self.group_by_exprs
+            .iter()
+            .map(|aliased_expr| match &aliased_expr.expr {
+                DynProofExpr::Column(column) if column.column_id() == aliased_expr.alias => {
+                    Some(column.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Get a reference to the sum expressions
     pub fn sum_expr(&self) -> &[AliasedDynProofExpr] {
         &self.sum_expr
@@ -90,33 +245,50 @@ impl GroupByExec {
     pub fn count_alias(&self) -> &Ident {
         &self.count_alias
     }
-}
 
-impl ProofPlan for GroupByExec {
-    fn verifier_evaluate<S: Scalar>(
+    /// Get a reference to the columns whose distinct values are counted, with their aliases
+    pub fn count_distinct_exprs(&self) -> &[(ColumnExpr, Ident)] {
+        &self.count_distinct_exprs
+    }
+
+    /// Get a reference to the optional filter of each distinct count, in the order of
+    /// [`Self::count_distinct_exprs`]
+    pub fn count_distinct_filters(&self) -> &[Option<DynProofExpr>] {
+        &self.count_distinct_filters
+    }
+
+    /// Verify the aggregation without checking that the groups of the output are distinct
+    ///
+    /// [`ProofPlan::verifier_evaluate`] shows that the groups are distinct by checking that the
+    /// result is ordered by them. A plan which evaluates this one as an input has to show that on
+    /// its own.
+    pub(super) fn verifier_evaluate_without_distinct_groups<S: Scalar>(
         &self,
         builder: &mut impl VerificationBuilder<S>,
         accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
-        result: Option<&OwnedTable<S>>,
         chi_eval_map: &IndexMap<TableRef, S>,
         params: &[LiteralValue],
     ) -> Result<TableEvaluation<S>, ProofError> {
         let input_chi_eval = *chi_eval_map
             .get(&self.table.table_ref)
             .expect("Chi eval not found");
+        let no_column_evals = IndexMap::default();
         let accessor = accessor
             .get(&self.table.table_ref)
-            .cloned()
-            .unwrap_or_else(|| [].into_iter().collect());
+            .unwrap_or(&no_column_evals);
         // 1. selection
         let where_eval =
             self.where_clause
-                .verifier_evaluate(builder, &accessor, input_chi_eval, params)?;
+                .verifier_evaluate(builder, accessor, input_chi_eval, params)?;
         // 2. columns
         let group_by_evals = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.verifier_evaluate(builder, &accessor, input_chi_eval, params))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, accessor, input_chi_eval, params)
+            })
             .collect::<Result<Vec<_>, _>>()?;
         let aggregate_evals = self
             .sum_expr
@@ -124,7 +296,24 @@ impl ProofPlan for GroupByExec {
             .map(|aliased_expr| {
                 aliased_expr
                     .expr
-                    .verifier_evaluate(builder, &accessor, input_chi_eval, params)
+                    .verifier_evaluate(builder, accessor, input_chi_eval, params)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let count_distinct_evals = self
+            .count_distinct_exprs
+            .iter()
+            .map(|(expr, _)| expr.verifier_evaluate(builder, accessor, input_chi_eval, params))
+            .collect::<Result<Vec<_>, _>>()?;
+        let count_distinct_filter_evals = self
+            .count_distinct_filters
+            .iter()
+            .map(|filter| {
+                filter
+                    .as_ref()
+                    .map(|filter| {
+                        filter.verifier_evaluate(builder, accessor, input_chi_eval, params)
+                    })
+                    .transpose()
             })
             .collect::<Result<Vec<_>, _>>()?;
         // 3. filtered_columns
@@ -133,10 +322,29 @@ impl ProofPlan for GroupByExec {
         let sum_result_columns_evals =
             builder.try_consume_final_round_mle_evaluations(self.sum_expr.len())?;
         let count_column_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let count_distinct_result_columns_evals =
+            builder.try_consume_final_round_mle_evaluations(self.count_distinct_exprs.len())?;
 
         let alpha = builder.try_consume_post_result_challenge()?;
         let beta = builder.try_consume_post_result_challenge()?;
-        let output_chi_eval = builder.try_consume_chi_evaluation()?;
+        let (output_chi_eval, num_groups) = builder.try_consume_chi_evaluation_with_length()?;
+        if self.group_by_exprs.is_empty() {
+            if num_groups != 1 {
+                Err(ProofError::VerificationError {
+                    error: "aggregation without group by columns must have exactly one row",
+                })?;
+            }
+        } else {
+            verify_output_length(builder, &self.table.table_ref, num_groups)?;
+        }
+        if let Some(max_groups) = builder.verification_options().max_groups() {
+            if num_groups > max_groups {
+                Err(ProofError::TooManyGroups {
+                    num_groups,
+                    max_groups,
+                })?;
+            }
+        }
 
         verify_group_by(
             builder,
@@ -144,25 +352,64 @@ impl ProofPlan for GroupByExec {
             beta,
             input_chi_eval,
             output_chi_eval,
-            (group_by_evals, aggregate_evals, where_eval),
+            (group_by_evals.clone(), aggregate_evals, where_eval),
             (
                 group_by_result_columns_evals.clone(),
                 sum_result_columns_evals.clone(),
                 count_column_eval,
             ),
         )?;
+        for ((&value_eval, &filter_eval), &distinct_count_eval) in count_distinct_evals
+            .iter()
+            .zip(&count_distinct_filter_evals)
+            .zip(&count_distinct_result_columns_evals)
+        {
+            verify_count_distinct(
+                builder,
+                alpha,
+                beta,
+                (input_chi_eval, output_chi_eval),
+                (&group_by_evals, value_eval, where_eval, filter_eval),
+                (&group_by_result_columns_evals, distinct_count_eval),
+            )?;
+        }
+        let column_evals = group_by_result_columns_evals
+            .into_iter()
+            .chain(sum_result_columns_evals)
+            .chain(iter::once(count_column_eval))
+            .chain(count_distinct_result_columns_evals)
+            .collect::<Vec<_>>();
+        Ok(TableEvaluation::new(column_evals, output_chi_eval))
+    }
+}
+
+impl ProofPlan for GroupByExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let table_eval = self.verifier_evaluate_without_distinct_groups(
+            builder,
+            accessor,
+            chi_eval_map,
+            params,
+        )?;
         match result {
             Some(table) => {
                 let cols = self
                     .group_by_exprs
                     .iter()
-                    .map(|col| table.inner_table().get(&col.column_id()))
+                    .map(|aliased_expr| table.inner_table().get(&aliased_expr.alias))
                     .collect::<Option<Vec<_>>>()
                     .ok_or(ProofError::VerificationError {
                         error: "Result does not all correct group by columns.",
                     })?;
-                if (0..table.num_rows() - 1)
-                    .any(|i| compare_indexes_by_owned_columns(&cols, i, i + 1).is_ge())
+                if (1..table.num_rows())
+                    .any(|i| compare_indexes_by_owned_columns(&cols, i - 1, i).is_ge())
                 {
                     Err(ProofError::VerificationError {
                         error: "Result of group by not ordered as expected.",
@@ -176,38 +423,43 @@ impl ProofPlan for GroupByExec {
             }
         }
 
-        let column_evals = group_by_result_columns_evals
-            .into_iter()
-            .chain(sum_result_columns_evals)
-            .chain(iter::once(count_column_eval))
-            .collect::<Vec<_>>();
-        Ok(TableEvaluation::new(column_evals, output_chi_eval))
+        if let Some(table) = result {
+            verify_result_column_evals(builder, table, table_eval.column_evals())?;
+        }
+        Ok(table_eval)
     }
 
-    #[expect(clippy::redundant_closure_for_method_calls)]
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.group_by_exprs
             .iter()
-            .map(|col| col.get_column_field())
-            .chain(self.sum_expr.iter().map(|aliased_expr| {
+            .chain(&self.sum_expr)
+            .map(|aliased_expr| {
                 ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
-            }))
+            })
             .chain(iter::once(ColumnField::new(
                 self.count_alias.clone(),
                 ColumnType::BigInt,
             )))
+            .chain(
+                self.count_distinct_exprs
+                    .iter()
+                    .map(|(_, alias)| ColumnField::new(alias.clone(), ColumnType::BigInt)),
+            )
             .collect()
     }
 
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
         let mut columns = IndexSet::default();
 
-        for col in &self.group_by_exprs {
-            columns.insert(col.get_column_reference());
-        }
-        for aliased_expr in &self.sum_expr {
+        for aliased_expr in self.group_by_exprs.iter().chain(&self.sum_expr) {
             aliased_expr.expr.get_column_references(&mut columns);
         }
+        for (expr, _) in &self.count_distinct_exprs {
+            columns.insert(expr.get_column_reference());
+        }
+        for filter in self.count_distinct_filters.iter().flatten() {
+            filter.get_column_references(&mut columns);
+        }
 
         self.where_clause.get_column_references(&mut columns);
 
@@ -246,8 +498,8 @@ impl ProverEvaluate for GroupByExec {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| -> PlaceholderResult<Column<'a, S>> {
-                expr.first_round_evaluate(alloc, table, params)
+            .map(|aliased_expr| -> PlaceholderResult<Column<'a, S>> {
+                aliased_expr.expr.first_round_evaluate(alloc, table, params)
             })
             .collect::<PlaceholderResult<Vec<_>>>()?;
         let sum_columns = self
@@ -257,15 +509,49 @@ impl ProverEvaluate for GroupByExec {
                 aliased_expr.expr.first_round_evaluate(alloc, table, params)
             })
             .collect::<PlaceholderResult<Vec<_>>>()?;
+        let count_distinct_columns = self
+            .count_distinct_exprs
+            .iter()
+            .map(|(expr, _)| expr.first_round_evaluate(alloc, table, params))
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let count_distinct_filters = self
+            .count_distinct_filters
+            .iter()
+            .map(|filter| {
+                filter
+                    .as_ref()
+                    .map(|filter| filter.first_round_evaluate(alloc, table, params))
+                    .transpose()
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
         // Compute filtered_columns
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
             sum_columns: sum_result_columns,
             count_column,
             ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
+        } = aggregate_columns_with_single_row_without_groups(
+            alloc,
+            &group_by_columns,
+            &sum_columns,
+            selection,
+        );
+        let count_distinct_witnesses = count_distinct_columns
+            .iter()
+            .zip(&count_distinct_filters)
+            .map(|(&values, &filter)| {
+                count_distinct_witness(
+                    alloc,
+                    (&group_by_columns, values, selection, filter_slice(filter)),
+                    &group_by_result_columns,
+                    count_column.len(),
+                )
+            })
+            .collect::<Vec<_>>();
         let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
+        let count_distinct_result_columns_iter = count_distinct_witnesses
+            .iter()
+            .map(|witness| Column::BigInt(witness.distinct_counts));
         let res = Table::<'a, S>::try_from_iter(
             self.get_column_result_fields()
                 .into_iter()
@@ -274,12 +560,16 @@ impl ProverEvaluate for GroupByExec {
                     group_by_result_columns
                         .into_iter()
                         .chain(sum_result_columns_iter)
-                        .chain(iter::once(Column::BigInt(count_column))),
+                        .chain(iter::once(Column::BigInt(count_column)))
+                        .chain(count_distinct_result_columns_iter),
                 ),
         )
         .expect("Failed to create table from column references");
         builder.request_post_result_challenges(2);
         builder.produce_chi_evaluation_length(count_column.len());
+        for witness in &count_distinct_witnesses {
+            first_round_evaluate_count_distinct(builder, witness);
+        }
 
         log::log_memory_usage("End");
 
@@ -311,8 +601,10 @@ impl ProverEvaluate for GroupByExec {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| -> PlaceholderResult<Column<'a, S>> {
-                expr.final_round_evaluate(builder, alloc, table, params)
+            .map(|aliased_expr| -> PlaceholderResult<Column<'a, S>> {
+                aliased_expr
+                    .expr
+                    .final_round_evaluate(builder, alloc, table, params)
             })
             .collect::<PlaceholderResult<Vec<_>>>()?;
         let sum_columns = self
@@ -324,14 +616,45 @@ impl ProverEvaluate for GroupByExec {
                     .final_round_evaluate(builder, alloc, table, params)
             })
             .collect::<PlaceholderResult<Vec<_>>>()?;
+        let count_distinct_columns = self
+            .count_distinct_exprs
+            .iter()
+            .map(|(expr, _)| expr.final_round_evaluate(builder, alloc, table, params))
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let count_distinct_filters = self
+            .count_distinct_filters
+            .iter()
+            .map(|filter| {
+                filter
+                    .as_ref()
+                    .map(|filter| filter.final_round_evaluate(builder, alloc, table, params))
+                    .transpose()
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
         // 3. Compute filtered_columns
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
             sum_columns: sum_result_columns,
             count_column,
             ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
+        } = aggregate_columns_with_single_row_without_groups(
+            alloc,
+            &group_by_columns,
+            &sum_columns,
+            selection,
+        );
+        let count_distinct_witnesses = count_distinct_columns
+            .iter()
+            .zip(&count_distinct_filters)
+            .map(|(&values, &filter)| {
+                count_distinct_witness(
+                    alloc,
+                    (&group_by_columns, values, selection, filter_slice(filter)),
+                    &group_by_result_columns,
+                    count_column.len(),
+                )
+            })
+            .collect::<Vec<_>>();
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
@@ -342,7 +665,12 @@ impl ProverEvaluate for GroupByExec {
             .clone()
             .into_iter()
             .chain(sum_result_columns_iter)
-            .chain(iter::once(Column::BigInt(count_column)));
+            .chain(iter::once(Column::BigInt(count_column)))
+            .chain(
+                count_distinct_witnesses
+                    .iter()
+                    .map(|witness| Column::BigInt(witness.distinct_counts)),
+            );
         let res = Table::<'a, S>::try_from_iter(
             self.get_column_result_fields()
                 .into_iter()
@@ -364,6 +692,22 @@ impl ProverEvaluate for GroupByExec {
             (&group_by_result_columns, &sum_result_columns, count_column),
             table.num_rows(),
         );
+        // 7. Prove distinct counts
+        for ((&values, &filter), witness) in count_distinct_columns
+            .iter()
+            .zip(&count_distinct_filters)
+            .zip(&count_distinct_witnesses)
+        {
+            prove_count_distinct(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                (&group_by_columns, values, selection, filter_slice(filter)),
+                &group_by_result_columns,
+                witness,
+            );
+        }
 
         log::log_memory_usage("End");
 
@@ -371,6 +715,28 @@ impl ProverEvaluate for GroupByExec {
     }
 }
 
+/// Aggregates the selected rows like [`aggregate_columns`], except that without group by columns
+/// the result always has a single row, which is all zeros if no rows are selected.
+///
+/// A zero row adds nothing to either side of the group by logup, so it is proven like any other.
+fn aggregate_columns_with_single_row_without_groups<'a, S: Scalar>(
+    alloc: &'a Bump,
+    group_by_columns: &[Column<'a, S>],
+    sum_columns: &[Column<S>],
+    selection: &[bool],
+) -> AggregatedColumns<'a, S> {
+    let mut aggregated =
+        aggregate_columns(alloc, group_by_columns, sum_columns, &[], &[], selection)
+            .expect("columns should be aggregatable");
+    if group_by_columns.is_empty() && aggregated.count_column.is_empty() {
+        for sum_column in &mut aggregated.sum_columns {
+            *sum_column = alloc.alloc_slice_fill_copy(1, S::zero());
+        }
+        aggregated.count_column = alloc.alloc_slice_fill_copy(1, 0);
+    }
+    aggregated
+}
+
 fn verify_group_by<S: Scalar>(
     builder: &mut impl VerificationBuilder<S>,
     alpha: S,
@@ -504,3 +870,374 @@ pub fn prove_group_by<'a, S: Scalar>(
         ],
     );
 }
+
+/// The witnesses of a `COUNT(DISTINCT ..)` aggregate.
+///
+/// `D` is the table of the distinct (group, value) pairs of the selected rows passing the filter, sorted by
+/// group and then by value.
+struct CountDistinctWitness<'a, S: Scalar> {
+    /// The group by columns of `D`
+    group_by_columns: Vec<Column<'a, S>>,
+    /// The values of `D`
+    values: Column<'a, S>,
+    /// The number of selected rows passing the filter with each pair of `D`
+    multiplicities: &'a [i64],
+    /// The inverses of `multiplicities`
+    inv_multiplicities: &'a [S],
+    /// The index of the output group of each pair of `D`
+    group_indexes: &'a [S],
+    /// The number of pairs of `D` in each output group, i.e. the distinct counts
+    distinct_counts: &'a [i64],
+}
+
+/// The boolean slice of an evaluated count distinct filter
+fn filter_slice<'a, S: Scalar>(filter: Option<Column<'a, S>>) -> Option<&'a [bool]> {
+    filter.map(|filter| {
+        filter
+            .as_boolean()
+            .expect("count distinct filter is not boolean")
+    })
+}
+
+fn count_distinct_witness<'a, S: Scalar>(
+    alloc: &'a Bump,
+    (group_by_columns, values, selection, filter): (
+        &[Column<'a, S>],
+        Column<'a, S>,
+        &[bool],
+        Option<&[bool]>,
+    ),
+    g_out: &[Column<'a, S>],
+    num_groups: usize,
+) -> CountDistinctWitness<'a, S> {
+    let selection: &[bool] = match filter {
+        Some(filter) => alloc.alloc_slice_fill_iter(
+            selection
+                .iter()
+                .zip(filter)
+                .map(|(&selected, &passes)| selected && passes),
+        ),
+        None => selection,
+    };
+    // Deduplicating the selected pairs is a group by over both the group by columns and the values
+    let pair_columns = group_by_columns
+        .iter()
+        .copied()
+        .chain(iter::once(values))
+        .collect::<Vec<_>>();
+    let AggregatedColumns {
+        group_by_columns: mut pair_group_by_columns,
+        count_column: multiplicities,
+        ..
+    } = aggregate_columns(alloc, &pair_columns, &[], &[], &[], selection)
+        .expect("columns should be aggregatable");
+    let values = pair_group_by_columns
+        .pop()
+        .expect("the values should be the last column");
+    let num_pairs = multiplicities.len();
+
+    let inv_multiplicities = alloc.alloc_slice_fill_iter(multiplicities.iter().map(S::from));
+    slice_ops::batch_inversion(inv_multiplicities);
+
+    // The pairs and the output groups are both sorted by group, and every group of a pair is an
+    // output group. A filter may leave output groups without pairs, so the group index skips
+    // ahead to the output group of each pair.
+    let mut group_index = 0;
+    let distinct_counts = alloc.alloc_slice_fill_copy(num_groups, 0_i64);
+    let group_indexes = alloc.alloc_slice_fill_with(num_pairs, |j| {
+        while !g_out
+            .iter()
+            .zip(&pair_group_by_columns)
+            .all(|(out, pair)| out.scalar_at(group_index) == pair.scalar_at(j))
+        {
+            group_index += 1;
+        }
+        distinct_counts[group_index] += 1;
+        S::from(group_index as u64)
+    });
+
+    CountDistinctWitness {
+        group_by_columns: pair_group_by_columns,
+        values,
+        multiplicities,
+        inv_multiplicities,
+        group_indexes,
+        distinct_counts,
+    }
+}
+
+/// `2^64 * group_index + value`, which is strictly increasing on the sorted pairs
+/// since the values are integers of at most 64 bits
+fn count_distinct_keys<'a, S: Scalar>(
+    alloc: &'a Bump,
+    group_indexes: &[S],
+    values: Column<'a, S>,
+) -> &'a [S] {
+    let two_pow_64: S = iter::repeat_n(S::TWO, 64).product();
+    alloc.alloc_slice_fill_iter(
+        group_indexes
+            .iter()
+            .zip(values.to_scalar())
+            .map(|(&group_index, value)| two_pow_64 * group_index + value),
+    )
+}
+
+fn first_round_evaluate_count_distinct<'a, S: Scalar>(
+    builder: &mut FirstRoundBuilder<'a, S>,
+    witness: &CountDistinctWitness<'a, S>,
+) {
+    let num_pairs = witness.multiplicities.len();
+    builder.produce_chi_evaluation_length(num_pairs);
+    builder.produce_rho_evaluation_length(witness.distinct_counts.len());
+    for column in &witness.group_by_columns {
+        builder.produce_intermediate_mle(*column);
+    }
+    builder.produce_intermediate_mle(witness.values);
+    builder.produce_intermediate_mle(witness.multiplicities);
+    builder.produce_intermediate_mle(witness.inv_multiplicities as &[_]);
+    builder.produce_intermediate_mle(witness.group_indexes as &[_]);
+    first_round_evaluate_monotonic(builder, num_pairs);
+}
+
+/// Returns `(fold, star)` where `fold = alpha * sum beta^j * columns[j]` and `star = (1 + fold)^(-1)`
+fn fold_and_invert<'a, S: Scalar>(
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    columns: &[Column<'a, S>],
+    num_rows: usize,
+) -> (&'a [S], &'a [S]) {
+    let fold = alloc.alloc_slice_fill_copy(num_rows, Zero::zero());
+    fold_columns(fold, alpha, beta, columns);
+    let star = alloc.alloc_slice_copy(fold);
+    slice_ops::add_const::<S, S>(star, One::one());
+    slice_ops::batch_inversion(star);
+    (fold, star)
+}
+
+/// Produce `star + star * fold - chi = 0`
+fn produce_star_identity<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    star: &'a [S],
+    fold: &'a [S],
+    chi: &'a [bool],
+) {
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(star)]),
+            (S::one(), vec![Box::new(star), Box::new(fold)]),
+            (-S::one(), vec![Box::new(chi)]),
+        ],
+    );
+}
+
+fn prove_count_distinct<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    (g_in, values_in, sel_in, filter_in): (
+        &[Column<'a, S>],
+        Column<'a, S>,
+        &'a [bool],
+        Option<&'a [bool]>,
+    ),
+    g_out: &[Column<'a, S>],
+    witness: &CountDistinctWitness<'a, S>,
+) {
+    let n = sel_in.len();
+    let k = witness.multiplicities.len();
+    let m = witness.distinct_counts.len();
+    let chi_n: &[bool] = alloc.alloc_slice_fill_copy(n, true);
+    let chi_k: &[bool] = alloc.alloc_slice_fill_copy(k, true);
+    let chi_m: &[bool] = alloc.alloc_slice_fill_copy(m, true);
+    let rho_m = alloc.alloc_slice_fill_with(m, |i| S::from(i as u64));
+
+    // 1. Every selected pair is in `D` and every pair of `D` is selected
+    let in_columns = g_in
+        .iter()
+        .copied()
+        .chain(iter::once(values_in))
+        .collect::<Vec<_>>();
+    let pair_columns = witness
+        .group_by_columns
+        .iter()
+        .copied()
+        .chain(iter::once(witness.values))
+        .collect::<Vec<_>>();
+    let (in_fold, in_star) = fold_and_invert(alloc, alpha, beta, &in_columns, n);
+    let (pair_fold, pair_star) = fold_and_invert(alloc, alpha, beta, &pair_columns, k);
+    builder.produce_intermediate_mle(in_star as &[_]);
+    builder.produce_intermediate_mle(pair_star as &[_]);
+
+    // sum in_star * sel_in * filter_in - pair_star * multiplicities = 0
+    let mut in_term: Vec<Box<dyn MultilinearExtension<S> + 'a>> =
+        vec![Box::new(in_star as &[_]), Box::new(sel_in)];
+    if let Some(filter_in) = filter_in {
+        in_term.push(Box::new(filter_in));
+    }
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), in_term),
+            (
+                -S::one(),
+                vec![
+                    Box::new(pair_star as &[_]),
+                    Box::new(witness.multiplicities),
+                ],
+            ),
+        ],
+    );
+    produce_star_identity(builder, in_star, in_fold, chi_n);
+    produce_star_identity(builder, pair_star, pair_fold, chi_k);
+
+    // multiplicities * inv_multiplicities - chi_k = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![
+                    Box::new(witness.multiplicities),
+                    Box::new(witness.inv_multiplicities),
+                ],
+            ),
+            (-S::one(), vec![Box::new(chi_k)]),
+        ],
+    );
+
+    // 2. The distinct count of each group is the number of pairs of `D` in it
+    let index_columns = iter::once(Column::Scalar(witness.group_indexes))
+        .chain(witness.group_by_columns.iter().copied())
+        .collect::<Vec<_>>();
+    let out_columns = iter::once(Column::Scalar(rho_m))
+        .chain(g_out.iter().copied())
+        .collect::<Vec<_>>();
+    let (index_fold, index_star) = fold_and_invert(alloc, alpha, beta, &index_columns, k);
+    let (out_fold, out_star) = fold_and_invert(alloc, alpha, beta, &out_columns, m);
+    builder.produce_intermediate_mle(index_star as &[_]);
+    builder.produce_intermediate_mle(out_star as &[_]);
+
+    // sum index_star - out_star * distinct_counts = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(index_star as &[_])]),
+            (
+                -S::one(),
+                vec![
+                    Box::new(out_star as &[_]),
+                    Box::new(witness.distinct_counts),
+                ],
+            ),
+        ],
+    );
+    produce_star_identity(builder, index_star, index_fold, chi_k);
+    produce_star_identity(builder, out_star, out_fold, chi_m);
+
+    // 3. The pairs of `D` are distinct
+    let keys = count_distinct_keys(alloc, witness.group_indexes, witness.values);
+    final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, keys);
+}
+
+fn verify_count_distinct<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    (input_chi_eval, output_chi_eval): (S, S),
+    (g_in_evals, value_in_eval, sel_in_eval, filter_in_eval): (&[S], S, S, Option<S>),
+    (g_out_evals, distinct_count_out_eval): (&[S], S),
+) -> Result<(), ProofError> {
+    let pair_chi_eval = builder.try_consume_chi_evaluation()?;
+    let rho_out_eval = builder.try_consume_rho_evaluation()?;
+    let g_pair_evals = iter::repeat_with(|| builder.try_consume_first_round_mle_evaluation())
+        .take(g_in_evals.len())
+        .collect::<Result<Vec<_>, _>>()?;
+    let value_pair_eval = builder.try_consume_first_round_mle_evaluation()?;
+    let multiplicity_eval = builder.try_consume_first_round_mle_evaluation()?;
+    let inv_multiplicity_eval = builder.try_consume_first_round_mle_evaluation()?;
+    let group_index_eval = builder.try_consume_first_round_mle_evaluation()?;
+
+    // 1. Every selected pair is in `D` and every pair of `D` is selected
+    let in_evals = g_in_evals
+        .iter()
+        .copied()
+        .chain(iter::once(value_in_eval))
+        .collect::<Vec<_>>();
+    let pair_evals = g_pair_evals
+        .iter()
+        .copied()
+        .chain(iter::once(value_pair_eval))
+        .collect::<Vec<_>>();
+    let in_fold_eval = alpha * fold_vals(beta, &in_evals);
+    let pair_fold_eval = alpha * fold_vals(beta, &pair_evals);
+    let in_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let pair_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // sum in_star * sel_in * filter_in - pair_star * multiplicities = 0
+    let (in_eval, in_degree) = match filter_in_eval {
+        Some(filter_in_eval) => (in_star_eval * sel_in_eval * filter_in_eval, 3),
+        None => (in_star_eval * sel_in_eval, 2),
+    };
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        in_eval - pair_star_eval * multiplicity_eval,
+        in_degree,
+    )?;
+    // in_star + in_star * in_fold - chi_n = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        in_star_eval + in_star_eval * in_fold_eval - input_chi_eval,
+        2,
+    )?;
+    // pair_star + pair_star * pair_fold - chi_k = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        pair_star_eval + pair_star_eval * pair_fold_eval - pair_chi_eval,
+        2,
+    )?;
+    // multiplicities * inv_multiplicities - chi_k = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        multiplicity_eval * inv_multiplicity_eval - pair_chi_eval,
+        2,
+    )?;
+
+    // 2. The distinct count of each group is the number of pairs of `D` in it
+    let index_evals = iter::once(group_index_eval)
+        .chain(g_pair_evals)
+        .collect::<Vec<_>>();
+    let out_evals = iter::once(rho_out_eval)
+        .chain(g_out_evals.iter().copied())
+        .collect::<Vec<_>>();
+    let index_fold_eval = alpha * fold_vals(beta, &index_evals);
+    let out_fold_eval = alpha * fold_vals(beta, &out_evals);
+    let index_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let out_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // sum index_star - out_star * distinct_counts = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        index_star_eval - out_star_eval * distinct_count_out_eval,
+        2,
+    )?;
+    // index_star + index_star * index_fold - chi_k = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        index_star_eval + index_star_eval * index_fold_eval - pair_chi_eval,
+        2,
+    )?;
+    // out_star + out_star * out_fold - chi_m = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        out_star_eval + out_star_eval * out_fold_eval - output_chi_eval,
+        2,
+    )?;
+
+    // 3. The pairs of `D` are distinct
+    let two_pow_64: S = iter::repeat_n(S::TWO, 64).product();
+    let key_eval = two_pow_64 * group_index_eval + value_pair_eval;
+    verify_monotonic::<S, true, true>(builder, alpha, beta, key_eval, pair_chi_eval)
+}
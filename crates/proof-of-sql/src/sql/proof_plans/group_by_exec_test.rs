@@ -1,15 +1,27 @@
 use super::test_utility::*;
 use crate::{
+    assert_tables_equal,
     base::{
         commitment::InnerProductProof,
-        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef, TestAccessor},
+        database::{
+            owned_table_utility::*, OwnedColumn, OwnedTable, OwnedTableTestAccessor, TableRef,
+            TestAccessor,
+        },
+        map::indexmap,
+        proof::ProofError,
+        scalar::Scalar,
     },
     proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
     sql::{
-        proof::{exercise_verification, VerifiableQueryResult},
+        proof::{
+            exercise_verification, ProofPlan, QueryError, SumcheckMleEvaluations,
+            VerifiableQueryResult, VerificationBuilderImpl, VerificationOptions,
+        },
         proof_exprs::test_utility::*,
+        proof_plans::{DynProofPlan, GroupByExec},
     },
 };
+use alloc::collections::{BTreeMap, BTreeSet};
 
 /// `select sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99`
 #[test]
@@ -36,7 +48,7 @@ fn we_can_prove_aggregation_without_group_by() {
         bigint("sum_c", [101 + 104 + 102 + 103]),
         bigint("__count__", [4]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 /// `select a, sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a`
@@ -65,7 +77,7 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
         bigint("sum_c", [101 + 104, 102 + 103]),
         bigint("__count__", [2, 2]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 /// `select a, sum(c * 2 + 1) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a`
@@ -100,7 +112,40 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
         decimal75("sum_c", 40, 0, [(101 + 104) * 2 + 2, (102 + 103) * 2 + 2]),
         bigint("__count__", [2, 2]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select a % 10 as bucket, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a % 10`
+#[test]
+fn we_can_prove_a_group_by_with_a_computed_group_expr() {
+    let data = owned_table([
+        bigint("a", [1, 11, -3, 21, 2, 12, -13]),
+        bigint("c", [10, 20, 9, 31, 5, 6, 7]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = DynProofPlan::GroupBy(GroupByExec::new_with_aliased_group_by_exprs(
+        vec![aliased_plan(
+            modulo(column(&t, "a", &accessor), &const_bigint(10)),
+            "bucket",
+        )],
+        vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+        "__count__".into(),
+        vec![],
+        vec![],
+        tab(&t),
+        const_bool(true),
+    ));
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("bucket", [-3, 1, 2]),
+        bigint("sum_c", [9 + 7, 10 + 20 + 31, 5 + 6]),
+        bigint("__count__", [2, 3, 2]),
+    ]);
+    assert_tables_equal!(res, expected);
 }
 
 #[expect(clippy::too_many_lines)]
@@ -226,7 +271,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
         scalar("sum_scal", [1116, 1033, 375]),
         bigint("__count__", [3, 2, 1]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 
     // SELECT sum(bigint_sum) as sum_int, sum(int128_sum * 4) as sum_128, sum(scalar_sum) as sum_scal, count(*) as __count__
     //  FROM sxt.t WHERE int128_filter = 1020 AND varchar_filter = 'f2'
@@ -256,5 +301,580 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
         scalar("sum_scal", [1116 + 1033 + 375]),
         bigint("__count__", [3 + 2 + 1]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select region, sum(amount) as sum_amount, count(*) as __count__, count(distinct user_id) as distinct_users from sxt.t where b = 99 group by region`
+#[test]
+fn we_can_prove_a_group_by_with_a_distinct_count() {
+    let data = owned_table([
+        bigint("region", [1, 1, 1, 2, 2, 2, 3]),
+        bigint("user_id", [10, 10, 11, 20, 20, 20, 30]),
+        bigint("amount", [1, 2, 3, 4, 5, 6, 7]),
+        bigint("b", [99, 99, 99, 99, 99, 99, 99]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_count_distinct(
+        cols_expr(&t, &["region"], &accessor),
+        vec![sum_expr(column(&t, "amount", &accessor), "sum_amount")],
+        "__count__",
+        vec![(col_expr(&t, "user_id", &accessor), "distinct_users")],
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("region", [1, 2, 3]),
+        bigint("sum_amount", [1 + 2 + 3, 4 + 5 + 6, 7]),
+        bigint("__count__", [3, 3, 1]),
+        bigint("distinct_users", [2, 1, 1]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select region, count(*) as __count__, count(distinct user_id) as distinct_users from sxt.t where b = 99 group by region`
+#[test]
+fn we_can_prove_a_distinct_count_when_the_filter_removes_values_and_groups() {
+    let data = owned_table([
+        bigint("region", [1, 1, 1, 2, 2, 2, 3]),
+        bigint("user_id", [10, 10, 11, 20, 21, 20, 30]),
+        bigint("b", [99, 99, 0, 99, 99, 99, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_count_distinct(
+        cols_expr(&t, &["region"], &accessor),
+        vec![],
+        "__count__",
+        vec![(col_expr(&t, "user_id", &accessor), "distinct_users")],
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("region", [1, 2]),
+        bigint("__count__", [2, 3]),
+        bigint("distinct_users", [1, 2]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select region, count(*) as __count__, count(distinct user_id) as distinct_users from sxt.t where b = 1000 group by region`
+#[test]
+fn we_can_prove_a_distinct_count_with_an_empty_selection() {
+    let data = owned_table([
+        bigint("region", [1, 1, 2]),
+        bigint("user_id", [10, 11, 20]),
+        bigint("b", [99, 99, 99]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_count_distinct(
+        cols_expr(&t, &["region"], &accessor),
+        vec![],
+        "__count__",
+        vec![(col_expr(&t, "user_id", &accessor), "distinct_users")],
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(1000)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("region", [0_i64; 0]),
+        bigint("__count__", [0_i64; 0]),
+        bigint("distinct_users", [0_i64; 0]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select count(*) as __count__, count(distinct c) as distinct_c, count(distinct d) as distinct_d from sxt.t where b = 99`
+#[test]
+fn we_can_prove_distinct_counts_of_signed_and_boolean_columns_without_group_by() {
+    let data = owned_table([
+        tinyint("c", [-1_i8, 3, -1, 0, 3, 5, -128]),
+        boolean("d", [true, true, true, true, true, true, false]),
+        bigint("b", [99, 99, 99, 99, 99, 99, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_count_distinct(
+        vec![],
+        vec![],
+        "__count__",
+        vec![
+            (col_expr(&t, "c", &accessor), "distinct_c"),
+            (col_expr(&t, "d", &accessor), "distinct_d"),
+        ],
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("__count__", [6]),
+        bigint("distinct_c", [4]),
+        bigint("distinct_d", [1]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_can_prove_distinct_counts_matching_a_reference_computation() {
+    let a: Vec<i64> = (0..40).map(|i| (i * 7) % 3).collect();
+    let b: Vec<i32> = (0..40).map(|i| (i * 5) % 2).collect();
+    let c: Vec<i64> = (0..40).map(|i| (i * 13) % 11 - 5).collect();
+    let filter: Vec<i64> = (0..40).map(|i| i % 4).collect();
+    let mut reference = BTreeMap::<(i64, i32), (i64, BTreeSet<i64>)>::new();
+    for i in (0..40).filter(|&i| filter[i] != 0) {
+        let (count, values) = reference.entry((a[i], b[i])).or_default();
+        *count += 1;
+        values.insert(c[i]);
+    }
+    let data = owned_table([
+        bigint("a", a),
+        int("b", b),
+        bigint("c", c),
+        bigint("filter", filter),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_count_distinct(
+        cols_expr(&t, &["a", "b"], &accessor),
+        vec![],
+        "__count__",
+        vec![(col_expr(&t, "c", &accessor), "distinct_c")],
+        tab(&t),
+        not(equal(column(&t, "filter", &accessor), const_bigint(0))),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("a", reference.keys().map(|&(a, _)| a)),
+        int("b", reference.keys().map(|&(_, b)| b)),
+        bigint("__count__", reference.values().map(|&(count, _)| count)),
+        bigint(
+            "distinct_c",
+            reference
+                .values()
+                .map(|(_, values)| i64::try_from(values.len()).unwrap()),
+        ),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+#[should_panic(
+    expected = "Count distinct columns should be integers of at most 64 bits, booleans or timestamps"
+)]
+fn we_cannot_create_a_distinct_count_of_a_varchar_column() {
+    let data = owned_table([bigint("a", [1, 2]), varchar("c", ["x", "y"])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let _ = group_by_with_count_distinct(
+        cols_expr(&t, &["a"], &accessor),
+        vec![],
+        "__count__",
+        vec![(col_expr(&t, "c", &accessor), "distinct_c")],
+        tab(&t),
+        const_bool(true),
+    );
+}
+
+/// `select region, count(*) as __count__, count(distinct user_id) as distinct_users,
+/// count(distinct user_id) filter (where amount >= 5) as big_spenders from sxt.t where b = 99 group by region`
+#[test]
+fn we_can_prove_a_filtered_distinct_count() {
+    let data = owned_table([
+        bigint("region", [1, 1, 1, 2, 2, 2, 3, 3]),
+        bigint("user_id", [10, 11, 10, 20, 20, 20, 30, 31]),
+        bigint("amount", [5, 1, 6, 7, 8, 9, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 99, 99, 99, 99]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_filtered_count_distinct(
+        cols_expr(&t, &["region"], &accessor),
+        vec![],
+        "__count__",
+        vec![
+            (col_expr(&t, "user_id", &accessor), "distinct_users", None),
+            (
+                col_expr(&t, "user_id", &accessor),
+                "big_spenders",
+                Some(gte(column(&t, "amount", &accessor), const_bigint(5))),
+            ),
+        ],
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    // Region 1 has a single value passing the filter and region 3 none at all
+    let expected = owned_table([
+        bigint("region", [1, 2, 3]),
+        bigint("__count__", [3, 3, 2]),
+        bigint("distinct_users", [2, 1, 2]),
+        bigint("big_spenders", [1, 1, 0]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select region, count(*) as __count__, count(distinct user_id) filter (where amount = 1000) as distinct_users
+/// from sxt.t group by region`
+#[test]
+fn we_can_prove_a_filtered_distinct_count_whose_filter_is_always_false() {
+    let data = owned_table([
+        bigint("region", [1, 1, 2]),
+        bigint("user_id", [10, 11, 20]),
+        bigint("amount", [1, 2, 3]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let never = || Some(equal(column(&t, "amount", &accessor), const_bigint(1000)));
+
+    let expr = group_by_with_filtered_count_distinct(
+        cols_expr(&t, &["region"], &accessor),
+        vec![],
+        "__count__",
+        vec![(
+            col_expr(&t, "user_id", &accessor),
+            "distinct_users",
+            never(),
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("region", [1, 2]),
+        bigint("__count__", [2, 1]),
+        bigint("distinct_users", [0, 0]),
+    ]);
+    assert_tables_equal!(res, expected);
+
+    let expr = group_by_with_filtered_count_distinct(
+        vec![],
+        vec![],
+        "__count__",
+        vec![(
+            col_expr(&t, "user_id", &accessor),
+            "distinct_users",
+            never(),
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([bigint("__count__", [3]), bigint("distinct_users", [0])]);
+    assert_tables_equal!(res, expected);
+}
+
+/// A filtered distinct count agrees with the distinct count of a query with the filter added to
+/// the where clause, in which the groups without any row passing the filter are missing.
+#[test]
+fn we_can_prove_filtered_distinct_counts_matching_the_composed_query() {
+    let a: Vec<i64> = (0..40).map(|i| (i * 7) % 5).collect();
+    let c: Vec<i64> = (0..40).map(|i| (i * 13) % 11 - 5).collect();
+    let d: Vec<i64> = (0..40).map(|i| i % 10).collect();
+    let filter: Vec<i64> = (0..40).map(|i| i % 4).collect();
+    let data = owned_table([
+        bigint("a", a),
+        bigint("c", c),
+        bigint("d", d),
+        bigint("filter", filter),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let where_clause = || not(equal(column(&t, "filter", &accessor), const_bigint(0)));
+    let cond = || lte(column(&t, "d", &accessor), const_bigint(1));
+
+    let expr = group_by_with_filtered_count_distinct(
+        cols_expr(&t, &["a"], &accessor),
+        vec![],
+        "__count__",
+        vec![(col_expr(&t, "c", &accessor), "distinct_c", Some(cond()))],
+        tab(&t),
+        where_clause(),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+
+    let composed_expr = group_by_with_count_distinct(
+        cols_expr(&t, &["a"], &accessor),
+        vec![],
+        "__count__",
+        vec![(col_expr(&t, "c", &accessor), "distinct_c")],
+        tab(&t),
+        and(where_clause(), cond()),
+    );
+    let composed_res = VerifiableQueryResult::new(&composed_expr, &accessor, &(), &[])
+        .unwrap()
+        .verify(&composed_expr, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let bigint_values =
+        |table: &OwnedTable<Curve25519Scalar>, name: &str| match &table.inner_table()[name] {
+            OwnedColumn::BigInt(values) => values.clone(),
+            _ => panic!("Expected a bigint column"),
+        };
+    let composed_counts: BTreeMap<i64, i64> = bigint_values(&composed_res, "a")
+        .into_iter()
+        .zip(bigint_values(&composed_res, "distinct_c"))
+        .collect();
+    let groups = bigint_values(&res, "a");
+    assert!(groups.len() > composed_counts.len());
+    let expected_counts: Vec<i64> = groups
+        .iter()
+        .map(|group| composed_counts.get(group).copied().unwrap_or(0))
+        .collect();
+    assert_eq!(bigint_values(&res, "distinct_c"), expected_counts);
+}
+
+#[test]
+#[should_panic(expected = "Count distinct filters should be boolean")]
+fn we_cannot_create_a_distinct_count_with_a_non_boolean_filter() {
+    let data = owned_table([bigint("a", [1, 2]), bigint("c", [3, 4])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let _ = group_by_with_filtered_count_distinct(
+        cols_expr(&t, &["a"], &accessor),
+        vec![],
+        "__count__",
+        vec![(
+            col_expr(&t, "c", &accessor),
+            "distinct_c",
+            Some(column(&t, "c", &accessor)),
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+}
+
+/// `select a, count(*) as __count__ from sxt.t group by a`
+#[test]
+fn we_can_limit_the_number_of_groups_when_verifying() {
+    let data = owned_table([bigint("a", [1, 2, 2, 3])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        cols_expr(&t, &["a"], &accessor),
+        vec![],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    assert!(matches!(
+        res.clone().verify_with_options(
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            &VerificationOptions::new(Some(2)),
+        ),
+        Err(QueryError::ProofError {
+            source: ProofError::TooManyGroups {
+                num_groups: 3,
+                max_groups: 2
+            }
+        })
+    ));
+    let res = res
+        .verify_with_options(
+            &expr,
+            &accessor,
+            &(),
+            &[],
+            &VerificationOptions::new(Some(3)),
+        )
+        .unwrap()
+        .table;
+    let expected = owned_table([bigint("a", [1, 2, 3]), bigint("__count__", [1, 2, 1])]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_a_group_by_declaring_more_groups_than_input_rows() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2, 2])]),
+        0,
+        (),
+    );
+    let expr = group_by(
+        cols_expr(&t, &["a"], &accessor),
+        vec![],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    // The group by and count columns of the result
+    let final_round_evaluations = [Curve25519Scalar::ZERO; 2];
+    let mle_evaluations = SumcheckMleEvaluations {
+        chi_evaluations: indexmap! {4 => Curve25519Scalar::ONE},
+        final_round_pcs_proof_evaluations: &final_round_evaluations,
+        ..Default::default()
+    };
+    let mut builder = VerificationBuilderImpl::new(
+        mle_evaluations,
+        &[],
+        &[],
+        [Curve25519Scalar::ONE, Curve25519Scalar::ONE].into(),
+        vec![4],
+        Vec::new(),
+        3,
+        indexmap! {t.clone() => 3},
+        VerificationOptions::default(),
+    );
+    let column_evals = indexmap! {t.clone() => indexmap! {"a".into() => Curve25519Scalar::ZERO}};
+    let chi_eval_map = indexmap! {t => Curve25519Scalar::ONE};
+    assert!(matches!(
+        expr.verifier_evaluate(&mut builder, &column_evals, None, &chi_eval_map, &[]),
+        Err(ProofError::OutputLengthExceedsInput {
+            output_length: 4,
+            input_length: 3
+        })
+    ));
+}
+
+/// `select sum(c) as sum_c, count(*) as __count__, count(distinct a) as distinct_a from sxt.t where b = 1000`
+#[test]
+fn we_can_prove_aggregation_without_group_by_over_an_empty_selection() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_count_distinct(
+        vec![],
+        vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+        "__count__",
+        vec![(col_expr(&t, "a", &accessor), "distinct_a")],
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(1000)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("sum_c", [0]),
+        bigint("__count__", [0]),
+        bigint("distinct_a", [0]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select sum(a) as sum_a, count(*) as __count__ from sxt.t`
+#[test]
+fn we_can_prove_aggregation_without_group_by_over_an_empty_table() {
+    let data = owned_table([bigint("a", [0_i64; 0])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        vec![],
+        vec![sum_expr(column(&t, "a", &accessor), "sum_a")],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([bigint("sum_a", [0]), bigint("__count__", [0])]);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select a, sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 1000 group by a`
+#[test]
+fn we_can_prove_a_group_by_over_an_empty_selection() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        cols_expr(&t, &["a"], &accessor),
+        vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(1000)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [0_i64; 0]),
+        bigint("sum_c", [0_i64; 0]),
+        bigint("__count__", [0_i64; 0]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_cannot_verify_an_aggregation_without_group_by_declaring_no_rows() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2, 2])]),
+        0,
+        (),
+    );
+    let expr = group_by(vec![], vec![], "__count__", tab(&t), const_bool(true));
+    // The count column of the result
+    let final_round_evaluations = [Curve25519Scalar::ZERO; 1];
+    let mle_evaluations = SumcheckMleEvaluations {
+        chi_evaluations: indexmap! {0 => Curve25519Scalar::ZERO},
+        final_round_pcs_proof_evaluations: &final_round_evaluations,
+        ..Default::default()
+    };
+    let mut builder = VerificationBuilderImpl::new(
+        mle_evaluations,
+        &[],
+        &[],
+        [Curve25519Scalar::ONE, Curve25519Scalar::ONE].into(),
+        vec![0],
+        Vec::new(),
+        3,
+        indexmap! {t.clone() => 3},
+        VerificationOptions::default(),
+    );
+    let column_evals = indexmap! {t.clone() => indexmap! {"a".into() => Curve25519Scalar::ZERO}};
+    let chi_eval_map = indexmap! {t => Curve25519Scalar::ONE};
+    assert!(matches!(
+        expr.verifier_evaluate(&mut builder, &column_evals, None, &chi_eval_map, &[]),
+        Err(ProofError::VerificationError { .. })
+    ));
 }
@@ -0,0 +1,521 @@
+use super::{
+    filter_exec::verify_result_column_evals,
+    union_exec::{prove_union, verify_union},
+    GroupByExec,
+};
+use crate::{
+    base::{
+        database::{
+            order_by_util::compare_indexes_by_owned_columns, union_util::table_union, Column,
+            ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedColumn, OwnedTable, Table,
+            TableEvaluation, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        math::i256::I256,
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, ProofExpr, TableExpr},
+        AnalyzeError, AnalyzeResult,
+    },
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use bumpalo::Bump;
+use core::{cmp::Ordering, iter};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// The maximum number of distinct group columns of a [`GroupingSetsExec`], one per bit of the
+/// grouping id
+pub const MAX_GROUPING_COLUMNS: usize = 63;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <group_column1>, ..., <group_columnK>,
+///         SUM(<sum_expr1>.expr) as <sum_expr1>.alias, ..., SUM(<sum_exprN>.expr) as <sum_exprN>.alias,
+///         COUNT(*) as count_alias,
+///         GROUPING(<group_column1>, ..., <group_columnK>) as grouping_id_alias
+///     FROM <table>
+///     WHERE <where_clause>
+///     GROUP BY GROUPING SETS ((<grouping_set1>), ..., (<grouping_setM>))
+/// ```
+/// where `<group_column1>, ..., <group_columnK>` are the distinct columns of the grouping sets in
+/// order of their first appearance.
+///
+/// Each grouping set is aggregated as by a [`GroupByExec`] and the results are combined as by a
+/// [`UnionExec`](super::UnionExec). In the rows of a set the group columns which aren't in it take
+/// the value of [`grouping_default_literal`], and as in SQL bit `i` of the grouping id is set if
+/// `<group_column(K-i)>` isn't in the set.
+///
+/// A [`GroupByExec`] shows that its groups are distinct by checking that the result is ordered by
+/// them. The rows of the sets are mixed in the result, so instead this checks that the groups of
+/// the rows with the same grouping id are distinct.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct GroupingSetsExec {
+    pub(super) grouping_sets: Vec<Vec<ColumnExpr>>,
+    pub(super) sum_expr: Vec<AliasedDynProofExpr>,
+    pub(super) count_alias: Ident,
+    pub(super) table: TableExpr,
+    pub(super) where_clause: DynProofExpr,
+    pub(super) grouping_id_alias: Ident,
+}
+
+impl GroupingSetsExec {
+    /// Creates a new grouping sets expression.
+    ///
+    /// # Errors
+    /// Returns an error if there are no grouping sets, if a grouping set has the same column twice,
+    /// if two of them have the same columns, if they have more than [`MAX_GROUPING_COLUMNS`]
+    /// distinct columns or if two result columns have the same alias.
+    pub fn try_new(
+        grouping_sets: Vec<Vec<ColumnExpr>>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+        grouping_id_alias: Ident,
+    ) -> AnalyzeResult<Self> {
+        if grouping_sets.is_empty() {
+            return Err(AnalyzeError::InvalidGroupingSets {
+                reason: "there are no grouping sets",
+            });
+        }
+        let distinct_sets = grouping_sets
+            .iter()
+            .map(|grouping_set| {
+                let mut column_ids = grouping_set
+                    .iter()
+                    .map(ColumnExpr::column_id)
+                    .collect::<Vec<_>>();
+                column_ids.sort();
+                column_ids.dedup();
+                (column_ids.len() == grouping_set.len())
+                    .then_some(column_ids)
+                    .ok_or(AnalyzeError::InvalidGroupingSets {
+                        reason: "a grouping set has the same column twice",
+                    })
+            })
+            .collect::<AnalyzeResult<IndexSet<_>>>()?;
+        if distinct_sets.len() != grouping_sets.len() {
+            return Err(AnalyzeError::InvalidGroupingSets {
+                reason: "two grouping sets have the same columns",
+            });
+        }
+        let exec = Self {
+            grouping_sets,
+            sum_expr,
+            count_alias,
+            table,
+            where_clause,
+            grouping_id_alias,
+        };
+        if exec.group_fields().len() > MAX_GROUPING_COLUMNS {
+            return Err(AnalyzeError::InvalidGroupingSets {
+                reason: "the grouping sets have too many distinct columns",
+            });
+        }
+        let mut aliases = IndexSet::default();
+        if let Some(field) = exec
+            .get_column_result_fields()
+            .into_iter()
+            .find(|field| !aliases.insert(field.name()))
+        {
+            return Err(AnalyzeError::DuplicateIdent {
+                ident: field.name().to_string(),
+            });
+        }
+        Ok(exec)
+    }
+
+    /// Get a reference to the grouping sets
+    pub fn grouping_sets(&self) -> &[Vec<ColumnExpr>] {
+        &self.grouping_sets
+    }
+
+    /// Get a reference to the sum expressions
+    pub fn sum_expr(&self) -> &[AliasedDynProofExpr] {
+        &self.sum_expr
+    }
+
+    /// Get a reference to the count alias
+    pub fn count_alias(&self) -> &Ident {
+        &self.count_alias
+    }
+
+    /// Get a reference to the table expression
+    pub fn table(&self) -> &TableExpr {
+        &self.table
+    }
+
+    /// Get a reference to the where clause
+    pub fn where_clause(&self) -> &DynProofExpr {
+        &self.where_clause
+    }
+
+    /// Get a reference to the alias of the grouping id
+    pub fn grouping_id_alias(&self) -> &Ident {
+        &self.grouping_id_alias
+    }
+
+    /// The distinct group columns of all grouping sets in order of their first appearance
+    fn group_fields(&self) -> Vec<ColumnField> {
+        self.grouping_sets
+            .iter()
+            .flatten()
+            .map(|column| (column.column_id(), column.get_column_field()))
+            .collect::<IndexMap<_, _>>()
+            .into_values()
+            .collect()
+    }
+
+    /// The aggregation of a single grouping set
+    fn group_by(&self, grouping_set: &[ColumnExpr]) -> GroupByExec {
+        GroupByExec::new(
+            grouping_set.to_vec(),
+            self.sum_expr.clone(),
+            self.count_alias.clone(),
+            self.table.clone(),
+            self.where_clause.clone(),
+        )
+    }
+
+    /// For each of `group_fields` the index of the column of `grouping_set` grouping by it, if any,
+    /// along with the grouping id of the set
+    fn set_columns(
+        group_fields: &[ColumnField],
+        grouping_set: &[ColumnExpr],
+    ) -> (Vec<Option<usize>>, i64) {
+        let set_columns = group_fields
+            .iter()
+            .map(|field| {
+                grouping_set
+                    .iter()
+                    .position(|column| column.column_id() == field.name())
+            })
+            .collect::<Vec<_>>();
+        let grouping_id = set_columns.iter().fold(0, |grouping_id, index| {
+            (grouping_id << 1) | i64::from(index.is_none())
+        });
+        (set_columns, grouping_id)
+    }
+
+    /// Extend the result of the aggregation of a grouping set to the schema of the result
+    fn extend_table<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        group_fields: &[ColumnField],
+        grouping_set: &[ColumnExpr],
+        table: &Table<'a, S>,
+    ) -> Table<'a, S> {
+        let (set_columns, grouping_id) = Self::set_columns(group_fields, grouping_set);
+        let num_rows = table.num_rows();
+        let columns = table.columns().copied().collect::<Vec<_>>();
+        let literal_column =
+            |value: &LiteralValue| Column::from_literal_with_length(value, num_rows, alloc);
+        Table::try_from_iter(
+            group_fields
+                .iter()
+                .zip(&set_columns)
+                .map(|(field, index)| {
+                    let column = index.map_or_else(
+                        || literal_column(&grouping_default_literal(field.data_type())),
+                        |index| columns[index],
+                    );
+                    (field.name(), column)
+                })
+                .chain(
+                    self.get_column_result_fields()
+                        .into_iter()
+                        .skip(group_fields.len())
+                        .zip(
+                            columns[grouping_set.len()..]
+                                .iter()
+                                .copied()
+                                .chain([literal_column(&LiteralValue::BigInt(grouping_id))]),
+                        )
+                        .map(|(field, column)| (field.name(), column)),
+                ),
+        )
+        .expect("Columns of the aggregation have the same length")
+    }
+
+    /// Aggregate each grouping set with `evaluate` and combine the results
+    fn union_group_bys<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        mut evaluate: impl FnMut(&GroupByExec) -> PlaceholderResult<Table<'a, S>>,
+    ) -> PlaceholderResult<(Vec<Table<'a, S>>, Table<'a, S>)> {
+        let group_fields = self.group_fields();
+        let inputs = self
+            .grouping_sets
+            .iter()
+            .map(|grouping_set| {
+                let table = evaluate(&self.group_by(grouping_set))?;
+                Ok(self.extend_table(alloc, &group_fields, grouping_set, &table))
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        // The sums of the aggregation are scalar columns, so the result has the schema of the
+        // extended tables rather than the result fields
+        let res = table_union(&inputs, alloc, inputs[0].schema()).expect("Failed to union tables");
+        Ok((inputs, res))
+    }
+
+    /// Check that the groups of the rows of each grouping set are distinct
+    pub(super) fn verify_distinct_groups<S: Scalar>(
+        &self,
+        table: &OwnedTable<S>,
+    ) -> Result<(), ProofError> {
+        let Some(OwnedColumn::BigInt(grouping_ids)) =
+            table.inner_table().get(&self.grouping_id_alias)
+        else {
+            return Err(ProofError::VerificationError {
+                error: "Result does not have the grouping id column.",
+            });
+        };
+        let group_fields = self.group_fields();
+        let group_columns = group_fields
+            .iter()
+            .map(|field| table.inner_table().get(&field.name()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ProofError::VerificationError {
+                error: "Result does not have all group columns.",
+            })?;
+        let mut set_rows: IndexMap<i64, (Vec<&OwnedColumn<S>>, Vec<usize>)> = self
+            .grouping_sets
+            .iter()
+            .map(|grouping_set| {
+                let (set_columns, grouping_id) = Self::set_columns(&group_fields, grouping_set);
+                let columns = group_columns
+                    .iter()
+                    .zip(&set_columns)
+                    .filter_map(|(&column, index)| index.map(|_| column))
+                    .collect();
+                (grouping_id, (columns, Vec::new()))
+            })
+            .collect();
+        for (row, grouping_id) in grouping_ids.iter().enumerate() {
+            set_rows
+                .get_mut(grouping_id)
+                .ok_or(ProofError::VerificationError {
+                    error: "Result has a row of an unknown grouping set.",
+                })?
+                .1
+                .push(row);
+        }
+        for (columns, rows) in set_rows.values_mut() {
+            rows.sort_unstable_by(|&i, &j| compare_indexes_by_owned_columns(columns, i, j));
+            if rows.windows(2).any(|pair| {
+                compare_indexes_by_owned_columns(columns, pair[0], pair[1]) == Ordering::Equal
+            }) {
+                Err(ProofError::VerificationError {
+                    error: "Result of grouping sets has duplicate groups.",
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The value of a group column in the rows of the grouping sets which don't group by it
+///
+/// SQL makes these `NULL`, but since columns are not nullable they are the zero of their type,
+/// the empty string or the empty byte string.
+#[must_use]
+pub fn grouping_default_literal(data_type: ColumnType) -> LiteralValue {
+    match data_type {
+        ColumnType::Boolean => LiteralValue::Boolean(false),
+        ColumnType::Uint8 => LiteralValue::Uint8(0),
+        ColumnType::TinyInt => LiteralValue::TinyInt(0),
+        ColumnType::SmallInt => LiteralValue::SmallInt(0),
+        ColumnType::Int => LiteralValue::Int(0),
+        ColumnType::BigInt => LiteralValue::BigInt(0),
+        ColumnType::Int128 => LiteralValue::Int128(0),
+        ColumnType::VarChar => LiteralValue::VarChar(String::new()),
+        ColumnType::VarBinary => LiteralValue::VarBinary(Vec::new()),
+        ColumnType::Decimal75(precision, scale) => {
+            LiteralValue::Decimal75(precision, scale, I256::new([0; 4]))
+        }
+        ColumnType::Scalar => LiteralValue::Scalar([0; 4]),
+        ColumnType::TimestampTZ(unit, timezone) => LiteralValue::TimeStampTZ(unit, timezone, 0),
+    }
+}
+
+impl ProofPlan for GroupingSetsExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let group_fields = self.group_fields();
+        let input_table_evals = self
+            .grouping_sets
+            .iter()
+            .map(|grouping_set| {
+                let table_eval = self
+                    .group_by(grouping_set)
+                    .verifier_evaluate_without_distinct_groups(
+                        builder,
+                        accessor,
+                        chi_eval_map,
+                        params,
+                    )?;
+                let (set_columns, grouping_id) = Self::set_columns(&group_fields, grouping_set);
+                let column_evals = table_eval.column_evals();
+                let chi_eval = table_eval.chi_eval();
+                let extended_column_evals = group_fields
+                    .iter()
+                    .zip(&set_columns)
+                    .map(|(field, index)| {
+                        index.map_or_else(
+                            || {
+                                chi_eval
+                                    * grouping_default_literal(field.data_type()).to_scalar::<S>()
+                            },
+                            |index| column_evals[index],
+                        )
+                    })
+                    .chain(column_evals[grouping_set.len()..].iter().copied())
+                    .chain(iter::once(chi_eval * S::from(grouping_id)))
+                    .collect();
+                Ok(TableEvaluation::new(extended_column_evals, chi_eval))
+            })
+            .collect::<Result<Vec<_>, ProofError>>()?;
+        let input_column_evals = input_table_evals
+            .iter()
+            .map(TableEvaluation::column_evals)
+            .collect::<Vec<_>>();
+        let output_column_evals = builder
+            .try_consume_final_round_mle_evaluations(self.get_column_result_fields().len())?;
+        let chi_n_evals = input_table_evals
+            .iter()
+            .map(TableEvaluation::chi_eval)
+            .collect::<Vec<_>>();
+        let chi_m_eval = builder.try_consume_chi_evaluation()?;
+        let gamma = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        verify_union(
+            builder,
+            gamma,
+            beta,
+            &input_column_evals,
+            &output_column_evals,
+            &chi_n_evals,
+            chi_m_eval,
+        )?;
+        match result {
+            Some(table) => {
+                self.verify_distinct_groups(table)?;
+                verify_result_column_evals(builder, table, &output_column_evals)?;
+            }
+            None => {
+                Err(ProofError::UnsupportedQueryPlan {
+                    error: "GroupingSetsExec currently only supported at top level of query plan.",
+                })?;
+            }
+        }
+        Ok(TableEvaluation::new(output_column_evals, chi_m_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.group_fields()
+            .into_iter()
+            .chain(self.sum_expr.iter().map(|aliased_expr| {
+                ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
+            }))
+            .chain([
+                ColumnField::new(self.count_alias.clone(), ColumnType::BigInt),
+                ColumnField::new(self.grouping_id_alias.clone(), ColumnType::BigInt),
+            ])
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut columns = IndexSet::default();
+
+        for col in self.grouping_sets.iter().flatten() {
+            columns.insert(col.get_column_reference());
+        }
+        for aliased_expr in &self.sum_expr {
+            aliased_expr.expr.get_column_references(&mut columns);
+        }
+
+        self.where_clause.get_column_references(&mut columns);
+
+        columns
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        IndexSet::from_iter([self.table.table_ref.clone()])
+    }
+}
+
+impl ProverEvaluate for GroupingSetsExec {
+    #[tracing::instrument(
+        name = "GroupingSetsExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let (_, res) = self.union_group_bys(alloc, |group_by| {
+            group_by.first_round_evaluate(builder, alloc, table_map, params)
+        })?;
+        builder.request_post_result_challenges(2);
+        builder.produce_chi_evaluation_length(res.num_rows());
+        Ok(res)
+    }
+
+    #[tracing::instrument(
+        name = "GroupingSetsExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let (inputs, res) = self.union_group_bys(alloc, |group_by| {
+            group_by.final_round_evaluate(builder, alloc, table_map, params)
+        })?;
+        let input_lengths = inputs.iter().map(Table::num_rows).collect::<Vec<_>>();
+        let gamma = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let input_columns: Vec<Vec<Column<'a, S>>> = inputs
+            .iter()
+            .map(|table| table.columns().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let output_columns: Vec<Column<'a, S>> = res.columns().copied().collect::<Vec<_>>();
+        output_columns.iter().copied().for_each(|column| {
+            builder.produce_intermediate_mle(column);
+        });
+        prove_union(
+            builder,
+            alloc,
+            gamma,
+            beta,
+            &input_columns,
+            &output_columns,
+            &input_lengths,
+            res.num_rows(),
+        );
+        Ok(res)
+    }
+}
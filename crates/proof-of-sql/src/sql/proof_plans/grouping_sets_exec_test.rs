@@ -0,0 +1,208 @@
+use super::{test_utility::*, GroupingSetsExec};
+use crate::{
+    assert_tables_equal,
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef, TestAccessor},
+        proof::ProofError,
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+        AnalyzeError,
+    },
+};
+
+/// `select a, b, sum(c) as sum_c, count(*) as __count__, grouping(a, b) as __grouping_id
+/// from sxt.t where d = 99 group by grouping sets ((a), (b), (a, b), ())`
+#[test]
+fn we_can_prove_grouping_sets() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 3]),
+        varchar("b", ["x", "y", "x", "x", "y", "z"]),
+        bigint("c", [101, 102, 103, 104, 105, 106]),
+        bigint("d", [99, 99, 99, 99, 0, 99]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = grouping_sets(
+        vec![
+            cols_expr(&t, &["a"], &accessor),
+            cols_expr(&t, &["b"], &accessor),
+            cols_expr(&t, &["a", "b"], &accessor),
+            vec![],
+        ],
+        vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(&t),
+        equal(column(&t, "d", &accessor), const_int128(99)),
+        "__grouping_id",
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3, 0, 0, 0, 1, 2, 2, 3, 0]),
+        varchar("b", ["", "", "", "x", "y", "z", "x", "x", "y", "z", ""]),
+        bigint(
+            "sum_c",
+            [205, 205, 106, 308, 102, 106, 205, 103, 102, 106, 516],
+        ),
+        bigint("__count__", [2, 2, 1, 3, 1, 1, 2, 1, 1, 1, 5]),
+        bigint("__grouping_id", [1, 1, 1, 2, 2, 2, 0, 0, 0, 0, 3]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+/// `select a, sum(c) as sum_c, count(*) as __count__, grouping(a) as __grouping_id
+/// from sxt.t where d = 1000 group by grouping sets ((a), ())`
+#[test]
+fn we_can_prove_grouping_sets_over_an_empty_selection_of_a_table_with_an_offset() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2]),
+        bigint("c", [101, 102, 103]),
+        bigint("d", [99, 99, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 3);
+    let expr = grouping_sets(
+        vec![cols_expr(&t, &["a"], &accessor), vec![]],
+        vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(&t),
+        equal(column(&t, "d", &accessor), const_int128(1000)),
+        "__grouping_id",
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [0]),
+        bigint("sum_c", [0]),
+        bigint("__count__", [0]),
+        bigint("__grouping_id", [1]),
+    ]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_cannot_prove_grouping_sets_which_are_not_at_the_top_level_for_now() {
+    let data = owned_table([bigint("a", [1, 2, 2]), bigint("c", [101, 102, 103])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = slice_exec(
+        grouping_sets(
+            vec![cols_expr(&t, &["a"], &accessor), vec![]],
+            vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+            "__count__",
+            tab(&t),
+            const_bool(true),
+            "__grouping_id",
+        ),
+        1,
+        None,
+    );
+    let res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    assert!(matches!(
+        res.verify(&expr, &accessor, &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::UnsupportedQueryPlan { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_create_invalid_grouping_sets() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2]), bigint("b", [3, 4])]),
+        0,
+        (),
+    );
+    let try_new = |sets: Vec<Vec<&str>>, grouping_id_alias: &str| {
+        GroupingSetsExec::try_new(
+            sets.iter()
+                .map(|set| cols_expr(&t, set, &accessor))
+                .collect(),
+            vec![sum_expr(column(&t, "b", &accessor), "sum_b")],
+            "__count__".into(),
+            tab(&t),
+            const_bool(true),
+            grouping_id_alias.into(),
+        )
+    };
+    assert!(try_new(vec![vec!["a"], vec!["a", "b"], vec![]], "__grouping_id").is_ok());
+    assert!(matches!(
+        try_new(vec![], "__grouping_id"),
+        Err(AnalyzeError::InvalidGroupingSets { .. })
+    ));
+    assert!(matches!(
+        try_new(vec![vec!["a", "b"], vec!["b", "a"]], "__grouping_id"),
+        Err(AnalyzeError::InvalidGroupingSets { .. })
+    ));
+    assert!(matches!(
+        try_new(vec![vec!["a", "a"]], "__grouping_id"),
+        Err(AnalyzeError::InvalidGroupingSets { .. })
+    ));
+    assert_eq!(
+        try_new(vec![vec!["a"], vec![]], "sum_b").unwrap_err(),
+        AnalyzeError::DuplicateIdent {
+            ident: "sum_b".into()
+        }
+    );
+}
+
+#[test]
+fn we_can_detect_duplicate_groups_of_a_grouping_set_in_a_result() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2]), varchar("b", ["x", "y"])]),
+        0,
+        (),
+    );
+    // The grouping ids of `(a)`, `(b)` and `()` are 1, 2 and 3
+    let exec = GroupingSetsExec::try_new(
+        vec![
+            cols_expr(&t, &["a"], &accessor),
+            cols_expr(&t, &["b"], &accessor),
+            vec![],
+        ],
+        vec![],
+        "__count__".into(),
+        tab(&t),
+        const_bool(true),
+        "__grouping_id".into(),
+    )
+    .unwrap();
+    let result = |a: [i64; 4], b: [&str; 4], grouping_ids: [i64; 4]| {
+        owned_table::<Curve25519Scalar>([
+            bigint("a", a),
+            varchar("b", b),
+            bigint("__count__", [1; 4]),
+            bigint("__grouping_id", grouping_ids),
+        ])
+    };
+    // The same values in columns which aren't in the grouping set of the row are fine
+    assert!(exec
+        .verify_distinct_groups(&result([1, 1, 0, 0], ["", "", "x", "y"], [1, 2, 2, 3]))
+        .is_ok());
+    assert!(exec
+        .verify_distinct_groups(&result([1, 1, 0, 0], ["", "", "x", "y"], [1, 1, 2, 3]))
+        .is_err());
+    assert!(exec
+        .verify_distinct_groups(&result([1, 2, 0, 0], ["", "", "x", "x"], [1, 1, 2, 2]))
+        .is_err());
+    assert!(exec
+        .verify_distinct_groups(&result([1, 2, 0, 0], ["", "", "", ""], [1, 1, 3, 3]))
+        .is_err());
+    assert!(exec
+        .verify_distinct_groups(&result([1, 2, 0, 0], ["", "", "x", ""], [1, 1, 2, 0]))
+        .is_err());
+}
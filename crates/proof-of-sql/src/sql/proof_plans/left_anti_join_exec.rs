@@ -0,0 +1,436 @@
+use super::{
+    filter_exec::{prove_filter, verify_filter},
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            filter_util::filter_columns,
+            join_util::{get_columns_of_table, get_multiplicities, ordered_set_union},
+            slice_operation::apply_slice_to_indexes,
+            Column, ColumnField, ColumnRef, LiteralValue, OwnedTable, Table, TableEvaluation,
+            TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_membership_check, final_round_evaluate_monotonic,
+            first_round_evaluate_membership_check, first_round_evaluate_monotonic,
+            verify_membership_check, verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, collections::BTreeSet, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     SELECT <left>.* FROM <ProofPlan> AS <left>
+///     WHERE NOT EXISTS (SELECT 1 FROM <ProofPlan> AS <right> WHERE <right>.col2 = <left>.col1)
+/// ```
+///
+/// The result consists of the rows of the left input, in their original order, whose join key
+/// does not occur among the join keys of the right input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeftAntiJoinExec {
+    pub(super) left: Box<DynProofPlan>,
+    pub(super) right: Box<DynProofPlan>,
+    pub(super) left_join_column_indexes: Vec<usize>,
+    pub(super) right_join_column_indexes: Vec<usize>,
+}
+
+impl LeftAntiJoinExec {
+    /// Create a new `LeftAntiJoinExec` with the given left and right plans
+    ///
+    /// # Panics
+    /// Panics if one of the following conditions is met:
+    /// - The join column index is out of bounds
+    /// - The number of join columns is different
+    /// - The types of the join columns are different
+    #[must_use]
+    pub fn new(
+        left: Box<DynProofPlan>,
+        right: Box<DynProofPlan>,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+    ) -> Self {
+        let left_fields = left.get_column_result_fields();
+        let right_fields = right.get_column_result_fields();
+        assert!(
+            (left_join_column_indexes.len() == right_join_column_indexes.len()),
+            "Join columns should have the same number of columns"
+        );
+        for (&left_index, &right_index) in left_join_column_indexes
+            .iter()
+            .zip(&right_join_column_indexes)
+        {
+            let (Some(left_field), Some(right_field)) =
+                (left_fields.get(left_index), right_fields.get(right_index))
+            else {
+                panic!("Join column index out of bounds");
+            };
+            assert!(
+                (left_field.data_type() == right_field.data_type()),
+                "Join columns should have the same types"
+            );
+        }
+        Self {
+            left,
+            right,
+            left_join_column_indexes,
+            right_join_column_indexes,
+        }
+    }
+
+    /// Get the left input plan
+    pub fn left(&self) -> &DynProofPlan {
+        &self.left
+    }
+
+    /// Get the right input plan
+    pub fn right(&self) -> &DynProofPlan {
+        &self.right
+    }
+
+    /// Get the indexes of the join columns of the left input
+    pub fn left_join_column_indexes(&self) -> &[usize] {
+        &self.left_join_column_indexes
+    }
+
+    /// Get the indexes of the join columns of the right input
+    pub fn right_join_column_indexes(&self) -> &[usize] {
+        &self.right_join_column_indexes
+    }
+}
+
+/// Compute the witnesses of the anti-join from the join columns of both sides
+///
+/// These are
+/// 1. `U`, the ordered set union of the join columns
+/// 2. `keep_u`, the indicator of the rows of `U` absent from the right join columns
+/// 3. `inv_u`, the inverses of the multiplicities of the rows of `U` in the right join columns,
+///    or zero where they are absent
+/// 4. `keep_l`, the indicator of the left rows absent from the right join columns
+fn get_anti_join_witnesses<'a, S: Scalar>(
+    c_l: &[Column<'a, S>],
+    c_r: &[Column<'a, S>],
+    alloc: &'a Bump,
+) -> (Vec<Column<'a, S>>, &'a [bool], &'a [S], &'a [bool]) {
+    let u = ordered_set_union(c_l, c_r, alloc).expect("Join columns should have the same types");
+    assert!((u.len() == 1), "Join on multiple columns not supported yet");
+    let w_r = get_multiplicities::<S>(c_r, &u, alloc);
+    let keep_u = alloc.alloc_slice_fill_iter(w_r.iter().map(|w| *w == 0));
+    let inv_u = alloc.alloc_slice_fill_iter(w_r.iter().map(S::from));
+    slice_ops::batch_inversion(inv_u);
+    let right_keys: BTreeSet<S> = c_r[0].to_scalar().into_iter().collect();
+    let keep_l = alloc.alloc_slice_fill_iter(
+        c_l[0]
+            .to_scalar()
+            .iter()
+            .map(|key| !right_keys.contains(key)),
+    );
+    (u, keep_u, inv_u, keep_l)
+}
+
+impl ProofPlan for LeftAntiJoinExec
+where
+    LeftAntiJoinExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let left_eval =
+            self.left
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let right_eval =
+            self.right
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let left_chi_eval = left_eval.chi_eval();
+        let right_chi_eval = right_eval.chi_eval();
+        let left_join_column_evals =
+            apply_slice_to_indexes(left_eval.column_evals(), &self.left_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let right_join_column_evals =
+            apply_slice_to_indexes(right_eval.column_evals(), &self.right_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        //TODO: Relax to allow multiple columns
+        let ([left_join_column_eval], [right_join_column_eval]) =
+            (&left_join_column_evals[..], &right_join_column_evals[..])
+        else {
+            return Err(ProofError::VerificationError {
+                error: "Left and right join columns should have exactly one column",
+            });
+        };
+        // 2. Chi evals
+        let u_chi_eval = builder.try_consume_chi_evaluation()?;
+        let res_chi_eval = builder.try_consume_chi_evaluation()?;
+        // 3. alpha, beta
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 4. First round MLE evaluations: `U`, `keep_u`, `inv_u` and `keep_l`
+        let u_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let keep_u_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let inv_u_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let keep_l_eval = builder.try_consume_first_round_mle_evaluation()?;
+        // 5. Result columns
+        let res_column_evals =
+            builder.try_consume_final_round_mle_evaluations(left_eval.column_evals().len())?;
+        // 6. `w_r` is the multiplicity of each row of `U` in the right join column
+        let w_r_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            right_chi_eval,
+            &[u_eval],
+            &[*right_join_column_eval],
+        )?;
+        // 7. `keep_u` is one exactly where `w_r` is zero
+        // w_r * keep_u = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            w_r_eval * keep_u_eval,
+            2,
+        )?;
+        // chi_u - keep_u - w_r * inv_u = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            u_chi_eval - keep_u_eval - w_r_eval * inv_u_eval,
+            2,
+        )?;
+        // 8. `keep_l` agrees with `keep_u` on each left join key
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            left_chi_eval,
+            &[u_eval, keep_u_eval],
+            &[*left_join_column_eval, keep_l_eval],
+        )?;
+        // 9. `U` is strictly increasing, so each key has a single `keep_u`
+        verify_monotonic::<S, true, true>(builder, alpha, beta, u_eval, u_chi_eval)?;
+        // 10. The result is the left input filtered by `keep_l`
+        verify_filter(
+            builder,
+            alpha,
+            beta,
+            left_chi_eval,
+            res_chi_eval,
+            left_eval.column_evals(),
+            keep_l_eval,
+            &res_column_evals,
+        )?;
+        Ok(TableEvaluation::new(res_column_evals, res_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.left.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.left
+            .get_column_references()
+            .into_iter()
+            .chain(self.right.get_column_references())
+            .collect()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.left
+            .get_table_references()
+            .into_iter()
+            .chain(self.right.get_table_references())
+            .collect()
+    }
+}
+
+impl ProverEvaluate for LeftAntiJoinExec {
+    #[tracing::instrument(
+        name = "LeftAntiJoinExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let left = self
+            .left
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let right = self
+            .right
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let columns = left.columns().copied().collect::<Vec<_>>();
+        let c_l = get_columns_of_table(&left, &self.left_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let c_r = get_columns_of_table(&right, &self.right_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        // 2. Witnesses
+        let (u, keep_u, inv_u, keep_l) = get_anti_join_witnesses(&c_l, &c_r, alloc);
+        let num_rows_u = u[0].len();
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, keep_l);
+        // 3. Chi evals
+        builder.produce_chi_evaluation_length(num_rows_u);
+        builder.produce_chi_evaluation_length(result_len);
+        // 4. First round MLEs
+        let alloc_u_0 = alloc.alloc_slice_copy(u[0].to_scalar().as_slice());
+        builder.produce_intermediate_mle(alloc_u_0 as &[_]);
+        builder.produce_intermediate_mle(keep_u);
+        builder.produce_intermediate_mle(inv_u as &[_]);
+        builder.produce_intermediate_mle(keep_l);
+        // 5. Membership checks
+        first_round_evaluate_membership_check(builder, alloc, &u, &c_r);
+        first_round_evaluate_membership_check(
+            builder,
+            alloc,
+            &[u[0], Column::Boolean(keep_u)],
+            &[c_l[0], Column::Boolean(keep_l)],
+        );
+        // 6. Monotonicity check
+        first_round_evaluate_monotonic(builder, num_rows_u);
+        // 7. Request post-result challenges
+        builder.request_post_result_challenges(2);
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(filtered_columns),
+            TableOptions::new(Some(result_len)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(
+        name = "LeftAntiJoinExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let left = self
+            .left
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let right = self
+            .right
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let num_rows_left = left.num_rows();
+        let columns = left.columns().copied().collect::<Vec<_>>();
+        let c_l = get_columns_of_table(&left, &self.left_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let c_r = get_columns_of_table(&right, &self.right_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let chi_l = alloc.alloc_slice_fill_copy(num_rows_left, true);
+        let chi_r = alloc.alloc_slice_fill_copy(right.num_rows(), true);
+        // 2. Witnesses
+        let (u, keep_u, inv_u, keep_l) = get_anti_join_witnesses(&c_l, &c_r, alloc);
+        let num_rows_u = u[0].len();
+        let chi_u = alloc.alloc_slice_fill_copy(num_rows_u, true);
+        let alloc_u_0 = alloc.alloc_slice_copy(u[0].to_scalar().as_slice());
+        let (filtered_columns, result_len) = filter_columns(alloc, &columns, keep_l);
+        // 3. Get post-result challenges
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 4. Result columns
+        filtered_columns.iter().copied().for_each(|column| {
+            builder.produce_intermediate_mle(column);
+        });
+        // 5. `w_r` is the multiplicity of each row of `U` in the right join column
+        let w_r = final_round_evaluate_membership_check(
+            builder, alloc, alpha, beta, chi_u, chi_r, &u, &c_r,
+        );
+        // 6. `keep_u` is one exactly where `w_r` is zero
+        // w_r * keep_u = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(
+                S::one(),
+                vec![Box::new(w_r as &[_]), Box::new(keep_u as &[_])],
+            )],
+        );
+        // chi_u - keep_u - w_r * inv_u = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(chi_u as &[_])]),
+                (-S::one(), vec![Box::new(keep_u as &[_])]),
+                (
+                    -S::one(),
+                    vec![Box::new(w_r as &[_]), Box::new(inv_u as &[_])],
+                ),
+            ],
+        );
+        // 7. `keep_l` agrees with `keep_u` on each left join key
+        final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_u,
+            chi_l,
+            &[u[0], Column::Boolean(keep_u)],
+            &[c_l[0], Column::Boolean(keep_l)],
+        );
+        // 8. `U` is strictly increasing
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_u_0);
+        // 9. The result is the left input filtered by `keep_l`
+        prove_filter::<S>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &columns,
+            keep_l,
+            &filtered_columns,
+            num_rows_left,
+            result_len,
+        );
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(filtered_columns),
+            TableOptions::new(Some(result_len)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,219 @@
+use super::{test_utility::*, DynProofPlan, LeftAntiJoinExec};
+use crate::{
+    assert_tables_equal,
+    base::database::{
+        owned_table_utility::*, table_utility::*, ColumnType, TableRef, TableTestAccessor,
+        TestAccessor,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+
+fn cats_and_details_accessor<'a>(
+    alloc: &'a Bump,
+    cat_ids: &[i64],
+    detail_ids: &[i64],
+) -> (TableTestAccessor<'a, InnerProductProof>, TableRef, TableRef) {
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(
+        table_left.clone(),
+        table([
+            borrowed_bigint("id", cat_ids.iter().copied(), alloc),
+            borrowed_bigint("age", cat_ids.iter().map(|id| id + 10), alloc),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        table_right.clone(),
+        table([
+            borrowed_bigint("id", detail_ids.iter().copied(), alloc),
+            borrowed_bigint("weight", detail_ids.iter().map(|id| id * 2), alloc),
+        ]),
+        0,
+    );
+    (accessor, table_left, table_right)
+}
+
+fn cats_anti_join_details(table_left: &TableRef, table_right: &TableRef) -> DynProofPlan {
+    left_anti_join(
+        table_exec(
+            table_left.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("age", ColumnType::BigInt),
+            ],
+        ),
+        table_exec(
+            table_right.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("weight", ColumnType::BigInt),
+            ],
+        ),
+        vec![0],
+        vec![0],
+    )
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_left_anti_join() {
+    let alloc = Bump::new();
+    let (accessor, table_left, table_right) =
+        cats_and_details_accessor(&alloc, &[1, 2, 3, 4, 5], &[1, 2, 98, 4, 1, 2, 7]);
+    let ast = cats_anti_join_details(&table_left, &table_right);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("id", [3_i64, 5]), bigint("age", [13_i64, 15])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_left_anti_join_with_duplicate_left_keys() {
+    let alloc = Bump::new();
+    let (accessor, table_left, table_right) =
+        cats_and_details_accessor(&alloc, &[3, 1, 3, 2, 1, 5, 3], &[1, 7]);
+    let ast = cats_anti_join_details(&table_left, &table_right);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("id", [3_i64, 3, 2, 5, 3]),
+        bigint("age", [13_i64, 13, 12, 15, 13]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_left_anti_join_against_an_empty_right_table() {
+    let alloc = Bump::new();
+    let (accessor, table_left, table_right) = cats_and_details_accessor(&alloc, &[1, 2, 2, 4], &[]);
+    let ast = cats_anti_join_details(&table_left, &table_right);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 2, 2, 4]),
+        bigint("age", [11_i64, 12, 12, 14]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_left_anti_join() {
+    let alloc = Bump::new();
+    for (cat_ids, detail_ids) in [
+        (&[1_i64, 2, 2][..], &[2_i64, 1, 3][..]),
+        (&[][..], &[2_i64, 1][..]),
+        (&[][..], &[][..]),
+    ] {
+        let (accessor, table_left, table_right) =
+            cats_and_details_accessor(&alloc, cat_ids, detail_ids);
+        let ast = cats_anti_join_details(&table_left, &table_right);
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+        let expected_res = owned_table([bigint("id", [0_i64; 0]), bigint("age", [0_i64; 0])]);
+        assert_tables_equal!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_can_prove_a_left_anti_join_of_filtered_tables() {
+    let alloc = Bump::new();
+    let (accessor, table_left, table_right) =
+        cats_and_details_accessor(&alloc, &[1, 2, 3, 4, 5, 6], &[1, 2, 3, 4, 5, 6]);
+    let ast = left_anti_join(
+        filter(
+            cols_expr_plan(&table_left, &["id", "age"], &accessor),
+            tab(&table_left),
+            lte(column(&table_left, "id", &accessor), const_bigint(4)),
+        ),
+        filter(
+            cols_expr_plan(&table_right, &["id"], &accessor),
+            tab(&table_right),
+            gte(column(&table_right, "weight", &accessor), const_bigint(6)),
+        ),
+        vec![0],
+        vec![0],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("id", [1_i64, 2]), bigint("age", [11_i64, 12])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_get_the_table_references_of_both_sides_of_a_left_anti_join() {
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    let ast = cats_anti_join_details(&table_left, &table_right);
+    assert_eq!(
+        ast.get_table_references(),
+        [table_left, table_right].into_iter().collect()
+    );
+}
+
+#[test]
+#[should_panic(expected = "Join column index out of bounds")]
+fn we_cannot_create_a_left_anti_join_with_out_of_bounds_indexes() {
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    let _ = LeftAntiJoinExec::new(
+        Box::new(table_exec(
+            table_left,
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            table_right,
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        vec![0],
+        vec![1],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Join columns should have the same types")]
+fn we_cannot_create_a_left_anti_join_on_columns_of_different_types() {
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    let _ = LeftAntiJoinExec::new(
+        Box::new(table_exec(
+            table_left,
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            table_right,
+            vec![column_field("human", ColumnType::VarChar)],
+        )),
+        vec![0],
+        vec![0],
+    );
+}
@@ -24,6 +24,11 @@ mod filter_exec_test;
 #[cfg(all(test, feature = "blitzar"))]
 mod filter_exec_test_dishonest_prover;
 
+mod generalized_filter_exec;
+pub use generalized_filter_exec::GeneralizedFilterExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod generalized_filter_exec_test;
+
 mod fold_util;
 pub(crate) use fold_util::{fold_columns, fold_vals};
 #[cfg(test)]
@@ -35,6 +40,16 @@ pub(crate) use group_by_exec::GroupByExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod group_by_exec_test;
 
+mod grouping_sets_exec;
+pub use grouping_sets_exec::{grouping_default_literal, GroupingSetsExec, MAX_GROUPING_COLUMNS};
+#[cfg(all(test, feature = "blitzar"))]
+mod grouping_sets_exec_test;
+
+mod first_per_group_exec;
+pub use first_per_group_exec::FirstPerGroupExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod first_per_group_exec_test;
+
 mod slice_exec;
 pub(crate) use slice_exec::SliceExec;
 #[cfg(all(test, feature = "blitzar"))]
@@ -50,8 +65,51 @@ pub use sort_merge_join_exec::SortMergeJoinExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod sort_merge_join_exec_test;
 
+mod left_anti_join_exec;
+pub use left_anti_join_exec::LeftAntiJoinExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod left_anti_join_exec_test;
+
+mod containment_check_exec;
+pub use containment_check_exec::ContainmentCheckExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod containment_check_exec_test;
+
+mod monotonic_check_exec;
+pub use monotonic_check_exec::MonotonicCheckExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod monotonic_check_exec_test;
+
+mod permutation_check_exec;
+pub use permutation_check_exec::PermutationCheckExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod permutation_check_exec_test;
+
+mod multi_count_exec;
+pub use multi_count_exec::MultiCountExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod multi_count_exec_test;
+
+mod bucket_count_exec;
+pub use bucket_count_exec::BucketCountExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod bucket_count_exec_test;
+
+mod percentage_of_total_exec;
+pub use percentage_of_total_exec::{PercentageOfTotalExec, MAX_PERCENTAGE_PRECISION};
+#[cfg(all(test, feature = "blitzar"))]
+mod percentage_of_total_exec_test;
+
 mod dyn_proof_plan;
 pub use dyn_proof_plan::DynProofPlan;
 
+mod table_diff;
+pub use table_diff::{ChangeType, CHANGE_TYPE_COLUMN};
+#[cfg(all(test, feature = "blitzar"))]
+mod table_diff_test;
+
 #[cfg(test)]
 mod demo_mock_plan;
+
+#[cfg(test)]
+mod verifier_allocation_test;
@@ -0,0 +1,219 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        database::{
+            join_util::get_columns_of_table, Column, ColumnField, ColumnRef, ColumnType,
+            LiteralValue, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// `ProofPlan` attesting that a column of the `input` is non-decreasing in row order, i.e. that
+/// `col[i] <= col[i + 1]` for all adjacent rows, as in
+/// ```ignore
+///     SELECT NOT EXISTS (
+///         SELECT 1 FROM (SELECT <col>, LEAD(<col>) OVER () AS next FROM <ProofPlan>)
+///         WHERE <col> > next
+///     ) AS <alias>
+/// ```
+///
+/// The result is a single row with a single boolean column that is always `true`.
+/// Equal adjacent values are allowed, and an input with at most one row is trivially ordered.
+/// If some value is greater than the next one, no valid proof exists and verification fails.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonotonicCheckExec {
+    pub(super) input: Box<DynProofPlan>,
+    pub(super) column_index: usize,
+    pub(super) alias: Ident,
+}
+
+impl MonotonicCheckExec {
+    /// Create a new `MonotonicCheckExec` checking that the `column_index`-th column of `input`
+    /// is non-decreasing
+    ///
+    /// # Panics
+    /// Panics if one of the following conditions is met:
+    /// - The column index is out of bounds
+    /// - The column is not an integer or a timestamp
+    #[must_use]
+    pub fn new(input: Box<DynProofPlan>, column_index: usize, alias: Ident) -> Self {
+        let Some(field) = input.get_column_result_fields().get(column_index).cloned() else {
+            panic!("Monotonic column index out of bounds");
+        };
+        assert!(
+            matches!(
+                field.data_type(),
+                ColumnType::Uint8
+                    | ColumnType::TinyInt
+                    | ColumnType::SmallInt
+                    | ColumnType::Int
+                    | ColumnType::BigInt
+                    | ColumnType::Int128
+                    | ColumnType::TimestampTZ(_, _)
+            ),
+            "Monotonic column should be an integer or a timestamp"
+        );
+        Self {
+            input,
+            column_index,
+            alias,
+        }
+    }
+
+    /// Get the input plan
+    pub fn input(&self) -> &DynProofPlan {
+        &self.input
+    }
+
+    /// Get the index of the checked column of the input plan
+    pub fn column_index(&self) -> usize {
+        self.column_index
+    }
+
+    /// Get the alias of the result
+    pub fn alias(&self) -> &Ident {
+        &self.alias
+    }
+
+    /// The single-row result attesting that the check passed
+    fn result_table<'a, S: Scalar>(&self, alloc: &'a Bump) -> Table<'a, S> {
+        Table::<'a, S>::try_from_iter_with_options(
+            [(
+                self.alias.clone(),
+                Column::Boolean(alloc.alloc_slice_copy(&[true])),
+            )],
+            TableOptions::new(Some(1)),
+        )
+        .expect("Failed to create table from iterator")
+    }
+}
+
+impl ProofPlan for MonotonicCheckExec
+where
+    MonotonicCheckExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let input_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let column_eval = *input_eval
+            .column_evals()
+            .get(self.column_index)
+            .expect("Index can not be out of bounds");
+        // 2. Chi evals
+        let res_chi_eval = builder.try_consume_chi_evaluation_of_length(1)?;
+        // 3. alpha, beta
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 4. The column is non-decreasing
+        verify_monotonic::<S, false, true>(
+            builder,
+            alpha,
+            beta,
+            column_eval,
+            input_eval.chi_eval(),
+        )?;
+        // 5. The result is a single `true`
+        Ok(TableEvaluation::new(vec![res_chi_eval], res_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        vec![ColumnField::new(self.alias.clone(), ColumnType::Boolean)]
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for MonotonicCheckExec {
+    #[tracing::instrument(
+        name = "MonotonicCheckExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self
+            .input
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        // 2. Chi evals
+        builder.produce_chi_evaluation_length(1);
+        // 3. Monotonicity check
+        first_round_evaluate_monotonic(builder, input.num_rows());
+        // 4. Request post-result challenges
+        builder.request_post_result_challenges(2);
+
+        log::log_memory_usage("End");
+
+        Ok(self.result_table(alloc))
+    }
+
+    #[tracing::instrument(
+        name = "MonotonicCheckExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self
+            .input
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let column = get_columns_of_table(&input, &[self.column_index])
+            .expect("Index can not be out of bounds");
+        let alloc_column = alloc.alloc_slice_copy(column[0].to_scalar().as_slice());
+        // 2. Get post-result challenges
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 3. The column is non-decreasing
+        final_round_evaluate_monotonic::<S, false, true>(builder, alloc, alpha, beta, alloc_column);
+
+        log::log_memory_usage("End");
+
+        Ok(self.result_table(alloc))
+    }
+}
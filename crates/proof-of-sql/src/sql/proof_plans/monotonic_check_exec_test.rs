@@ -0,0 +1,171 @@
+use super::{test_utility::*, DynProofPlan, MonotonicCheckExec};
+use crate::{
+    assert_tables_equal,
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTableTestAccessor, TableRef,
+        },
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+fn events_accessor(
+    events: &TableRef,
+    timestamps: &[i64],
+) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        events.clone(),
+        owned_table([
+            bigint(
+                "event_id",
+                (0..timestamps.len()).map(|i| i64::try_from(i).unwrap()),
+            ),
+            timestamptz(
+                "ts",
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::utc(),
+                timestamps.to_vec(),
+            ),
+        ]),
+        0,
+        (),
+    )
+}
+
+fn events_are_ordered(events: &TableRef) -> DynProofPlan {
+    monotonic_check(
+        table_exec(
+            events.clone(),
+            vec![
+                column_field("event_id", ColumnType::BigInt),
+                column_field(
+                    "ts",
+                    ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc()),
+                ),
+            ],
+        ),
+        1,
+        "ordered",
+    )
+}
+
+#[test]
+fn we_can_prove_that_timestamps_are_non_decreasing() {
+    let events: TableRef = "sxt.events".parse().unwrap();
+    for timestamps in [
+        &[1_000_i64, 2_000, 3_500, 10_000][..],
+        // Equal adjacent timestamps
+        &[1_000_i64, 1_000, 2_000, 2_000, 2_000][..],
+        &[7_i64, 7, 7][..],
+        // A single row
+        &[1_625_072_400_000_i64][..],
+        // No rows
+        &[][..],
+        // Negative timestamps
+        &[-5_000_i64, -5_000, 0, 3][..],
+    ] {
+        let accessor = events_accessor(&events, timestamps);
+        let ast = events_are_ordered(&events);
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &ast, &accessor, &events);
+        let res = verifiable_res
+            .verify(&ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+        let expected_res = owned_table([boolean("ordered", [true])]);
+        assert_tables_equal!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_cannot_prove_that_unordered_timestamps_are_non_decreasing() {
+    let events: TableRef = "sxt.events".parse().unwrap();
+    for timestamps in [
+        &[2_000_i64, 1_000][..],
+        // A single out of order pair among equal values
+        &[1_000_i64, 1_000, 999, 1_000][..],
+        // Decreasing
+        &[3_i64, 2, 1, 0, -1][..],
+        // Out of order only at the end
+        &[1_i64, 2, 3, 4, 0][..],
+    ] {
+        let accessor = events_accessor(&events, timestamps);
+        let ast = events_are_ordered(&events);
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        assert!(verifiable_res.verify(&ast, &accessor, &(), &[]).is_err());
+    }
+}
+
+#[test]
+fn we_can_prove_that_a_filtered_column_is_non_decreasing() {
+    let events: TableRef = "sxt.events".parse().unwrap();
+    let accessor = events_accessor(&events, &[1_000, 5_000, 2_000, 3_000]);
+    // The out of order event is filtered out
+    let ast = monotonic_check(
+        filter(
+            cols_expr_plan(&events, &["ts"], &accessor),
+            tab(&events),
+            not(equal(
+                column(&events, "event_id", &accessor),
+                const_bigint(1),
+            )),
+        ),
+        0,
+        "ordered",
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &events);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([boolean("ordered", [true])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_get_the_result_fields_and_references_of_a_monotonic_check() {
+    let events: TableRef = "sxt.events".parse().unwrap();
+    let ast = events_are_ordered(&events);
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![ColumnField::new("ordered".into(), ColumnType::Boolean)]
+    );
+    assert_eq!(ast.get_table_references(), [events].into_iter().collect());
+}
+
+#[test]
+#[should_panic(expected = "Monotonic column index out of bounds")]
+fn we_cannot_create_a_monotonic_check_with_an_out_of_bounds_index() {
+    let events: TableRef = "sxt.events".parse().unwrap();
+    let _ = MonotonicCheckExec::new(
+        Box::new(table_exec(
+            events,
+            vec![column_field("event_id", ColumnType::BigInt)],
+        )),
+        1,
+        "ordered".into(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Monotonic column should be an integer or a timestamp")]
+fn we_cannot_create_a_monotonic_check_on_a_varchar_column() {
+    let events: TableRef = "sxt.events".parse().unwrap();
+    let _ = MonotonicCheckExec::new(
+        Box::new(table_exec(
+            events,
+            vec![column_field("name", ColumnType::VarChar)],
+        )),
+        0,
+        "ordered".into(),
+    );
+}
@@ -0,0 +1,255 @@
+use crate::{
+    base::{
+        database::{
+            Column, ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, Table,
+            TableEvaluation, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::{AliasedDynProofExpr, ProofExpr, TableExpr},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT COUNT(*) FILTER (WHERE <predicate1>) as <alias1>, ...,
+///         COUNT(*) FILTER (WHERE <predicateN>) as <aliasN>
+///     FROM <table>
+/// ```
+///
+/// Every predicate is evaluated over the same scan of the table and the result is a single row
+/// holding one count per predicate. Each count is proven with a degree one sumcheck constraint
+/// summing its predicate and a degree two identity keeping the count column zero after its first
+/// row, which is far cheaper than proving `N` separate filtered counts.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct MultiCountExec {
+    pub(super) predicates: Vec<AliasedDynProofExpr>,
+    pub(super) table: TableExpr,
+}
+
+impl MultiCountExec {
+    /// Creates a new multi-predicate count plan.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnalyzeError::InvalidDataType`] if any predicate is not boolean.
+    pub fn try_new(predicates: Vec<AliasedDynProofExpr>, table: TableExpr) -> AnalyzeResult<Self> {
+        if let Some(predicate) = predicates
+            .iter()
+            .find(|predicate| predicate.expr.data_type() != ColumnType::Boolean)
+        {
+            return Err(AnalyzeError::InvalidDataType {
+                expr_type: predicate.expr.data_type(),
+            });
+        }
+        Ok(Self { predicates, table })
+    }
+
+    /// Get a reference to the predicates, each aliased by the name of its count
+    pub fn predicates(&self) -> &[AliasedDynProofExpr] {
+        &self.predicates
+    }
+
+    /// Get a reference to the table expression
+    pub fn table(&self) -> &TableExpr {
+        &self.table
+    }
+}
+
+impl ProofPlan for MultiCountExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_chi_eval = *chi_eval_map
+            .get(&self.table.table_ref)
+            .expect("Chi eval not found");
+        let no_column_evals = IndexMap::default();
+        let accessor = accessor
+            .get(&self.table.table_ref)
+            .unwrap_or(&no_column_evals);
+        // 1. predicates
+        let predicate_evals = self
+            .predicates
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, accessor, input_chi_eval, params)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // 2. counts
+        let count_evals = builder.try_consume_final_round_mle_evaluations(self.predicates.len())?;
+        let output_chi_eval = builder.try_consume_chi_evaluation_of_length(1)?;
+        let singleton_chi_eval = builder.singleton_chi_evaluation();
+
+        for (predicate_eval, count_eval) in predicate_evals.into_iter().zip(count_evals.iter()) {
+            // count - count * singleton_chi = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                *count_eval - *count_eval * singleton_chi_eval,
+                2,
+            )?;
+            // sum predicate - count = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::ZeroSum,
+                predicate_eval - *count_eval,
+                1,
+            )?;
+        }
+        Ok(TableEvaluation::new(count_evals, output_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.predicates
+            .iter()
+            .map(|aliased_expr| ColumnField::new(aliased_expr.alias.clone(), ColumnType::BigInt))
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut columns = IndexSet::default();
+        for aliased_expr in &self.predicates {
+            aliased_expr.expr.get_column_references(&mut columns);
+        }
+        columns
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        IndexSet::from_iter([self.table.table_ref.clone()])
+    }
+}
+
+impl MultiCountExec {
+    /// Count the rows satisfying each predicate, returning the single row result table
+    fn count<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        predicate_columns: &[Column<'a, S>],
+    ) -> (Table<'a, S>, Vec<&'a [i64]>) {
+        let count_columns: Vec<&'a [i64]> = predicate_columns
+            .iter()
+            .map(|column| {
+                let selection = column.as_boolean().expect("predicate is not boolean");
+                let count = selection.iter().filter(|b| **b).count();
+                &*alloc.alloc_slice_copy(&[i64::try_from(count).expect("count fits in i64")])
+            })
+            .collect();
+        let res = Table::<'a, S>::try_from_iter(
+            self.predicates
+                .iter()
+                .map(|aliased_expr| aliased_expr.alias.clone())
+                .zip(count_columns.iter().map(|count| Column::BigInt(*count))),
+        )
+        .expect("Failed to create table from iterator");
+        (res, count_columns)
+    }
+}
+
+impl ProverEvaluate for MultiCountExec {
+    #[tracing::instrument(
+        name = "MultiCountExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let table = table_map
+            .get(&self.table.table_ref)
+            .expect("Table not found");
+        let predicate_columns = self
+            .predicates
+            .iter()
+            .map(|aliased_expr| aliased_expr.expr.first_round_evaluate(alloc, table, params))
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let (res, _) = self.count(alloc, &predicate_columns);
+        builder.produce_chi_evaluation_length(1);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(
+        name = "MultiCountExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let table = table_map
+            .get(&self.table.table_ref)
+            .expect("Table not found");
+        // 1. predicates
+        let predicate_columns = self
+            .predicates
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .final_round_evaluate(builder, alloc, table, params)
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        // 2. counts
+        let (res, count_columns) = self.count(alloc, &predicate_columns);
+        for count in &count_columns {
+            builder.produce_intermediate_mle(*count);
+        }
+
+        let singleton_chi: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        for (predicate, count) in predicate_columns.iter().zip(count_columns) {
+            let selection = predicate.as_boolean().expect("predicate is not boolean");
+            // count - count * singleton_chi = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(count)]),
+                    (-S::one(), vec![Box::new(count), Box::new(singleton_chi)]),
+                ],
+            );
+            // sum predicate - count = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (S::one(), vec![Box::new(selection)]),
+                    (-S::one(), vec![Box::new(count)]),
+                ],
+            );
+        }
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+}
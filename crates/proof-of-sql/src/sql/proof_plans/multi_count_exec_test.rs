@@ -0,0 +1,417 @@
+use super::{test_utility::*, DynProofPlan, MultiCountExec};
+use crate::{
+    assert_tables_equal,
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, ColumnField, ColumnRef, ColumnType,
+            LiteralValue, OwnedTable, OwnedTableTestAccessor, Table, TableEvaluation, TableRef,
+            TestAccessor,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{
+            exercise_verification, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            QueryError, SumcheckSubpolynomialType, VerifiableQueryResult, VerificationBuilder,
+        },
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        AnalyzeError,
+    },
+};
+use bumpalo::Bump;
+use serde::Serialize;
+use sqlparser::ast::Ident;
+
+/// A plan built by [`DynProofPlan::try_new_is_constant`] whose prover claims that the expression
+/// is constant by committing to `[R, m - R]` as the count of the `m` matching rows of a table with
+/// `R` rows, so that the first row of the counts is equal
+#[derive(Debug, Serialize)]
+struct CheatingIsConstant(DynProofPlan);
+
+impl CheatingIsConstant {
+    fn counts(&self) -> &MultiCountExec {
+        let DynProofPlan::Projection(projection) = &self.0 else {
+            panic!("Expected a projection");
+        };
+        let DynProofPlan::MultiCount(counts) = projection.input() else {
+            panic!("Expected a multi count");
+        };
+        counts
+    }
+}
+
+impl ProverEvaluate for CheatingIsConstant {
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        self.0
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        Ok(table([borrowed_boolean("is_constant", [true], alloc)]))
+    }
+
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let counts = self.counts();
+        let table = &table_map[&counts.table.table_ref];
+        let predicates = counts
+            .predicates
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .final_round_evaluate(builder, alloc, table, params)
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let num_rows = S::from(table.num_rows() as u64);
+        let num_matching = S::from(
+            predicates[0]
+                .as_boolean()
+                .unwrap()
+                .iter()
+                .filter(|b| **b)
+                .count() as u64,
+        );
+        // The counts of the matching rows and of all rows
+        let matching_count: &'a [S] = alloc.alloc_slice_copy(&[num_rows, num_matching - num_rows]);
+        let row_count: &'a [S] = alloc.alloc_slice_copy(&[num_rows]);
+        builder.produce_intermediate_mle(matching_count);
+        builder.produce_intermediate_mle(row_count);
+        let singleton_chi: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        for (predicate, count) in predicates.iter().zip([matching_count, row_count]) {
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(count)]),
+                    (-S::one(), vec![Box::new(count), Box::new(singleton_chi)]),
+                ],
+            );
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::ZeroSum,
+                vec![
+                    (S::one(), vec![Box::new(predicate.as_boolean().unwrap())]),
+                    (-S::one(), vec![Box::new(count)]),
+                ],
+            );
+        }
+        // `matching_count = row_count`, which holds on the first row, with a pseudo inverse that
+        // is zero on the second row too
+        let difference: &'a [S] = alloc.alloc_slice_copy(&[S::ZERO, num_matching - num_rows]);
+        let pseudo_inv: &'a [S] = alloc.alloc_slice_copy(&[S::ZERO, S::ZERO]);
+        builder.produce_intermediate_mle(pseudo_inv);
+        let selection: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        builder.produce_intermediate_mle(selection);
+        let selection_not: &'a [bool] = alloc.alloc_slice_copy(&[false]);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(S::one(), vec![Box::new(difference), Box::new(selection)])],
+        );
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(selection_not)]),
+                (-S::one(), vec![Box::new(difference), Box::new(pseudo_inv)]),
+            ],
+        );
+        Ok(table([borrowed_boolean("is_constant", [true], alloc)]))
+    }
+}
+
+impl ProofPlan for CheatingIsConstant {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        self.0
+            .verifier_evaluate(builder, accessor, result, chi_eval_map, params)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.0.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.0.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.0.get_table_references()
+    }
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_references() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2]), boolean("b", [true, false])]),
+        0,
+    );
+    let plan = MultiCountExec::try_new(
+        vec![
+            aliased_plan(equal(column(&t, "a", &accessor), const_bigint(1)), "a_is_1"),
+            aliased_plan(column(&t, "b", &accessor), "b_is_set"),
+        ],
+        tab(&t),
+    )
+    .unwrap();
+    assert_eq!(
+        plan.get_column_result_fields(),
+        vec![
+            column_field("a_is_1", ColumnType::BigInt),
+            column_field("b_is_set", ColumnType::BigInt),
+        ]
+    );
+    assert_eq!(
+        plan.get_column_references(),
+        [col_ref(&t, "a", &accessor), col_ref(&t, "b", &accessor)]
+            .into_iter()
+            .collect()
+    );
+    assert_eq!(plan.get_table_references(), [t].into_iter().collect());
+}
+
+#[test]
+fn we_cannot_count_with_a_non_boolean_predicate() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), owned_table([bigint("a", [1, 2])]), 0);
+    let err = MultiCountExec::try_new(vec![aliased_plan(column(&t, "a", &accessor), "a")], tab(&t))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        AnalyzeError::InvalidDataType {
+            expr_type: ColumnType::BigInt
+        }
+    ));
+}
+
+/// `select count(*) filter (where a <= 3), count(*) filter (where a >= 2),
+/// count(*) filter (where b = 99) from sxt.t`
+///
+/// The first two predicates overlap and the last one is never true. The counts must match those
+/// of three separate `select count(*) from sxt.t where <predicate>` queries.
+#[test]
+fn we_can_prove_several_counts_matching_separate_count_queries() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 4, 5, 2]),
+        bigint("b", [0, 1, 0, 1, 0, 1]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let predicates = [
+        ("a_lte_3", lte(column(&t, "a", &accessor), const_bigint(3))),
+        ("a_gte_2", gte(column(&t, "a", &accessor), const_bigint(2))),
+        (
+            "b_is_99",
+            equal(column(&t, "b", &accessor), const_bigint(99)),
+        ),
+    ];
+
+    let expr = multi_count(
+        predicates
+            .iter()
+            .map(|(alias, predicate)| aliased_plan(predicate.clone(), alias))
+            .collect(),
+        tab(&t),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([
+        bigint("a_lte_3", [4]),
+        bigint("a_gte_2", [5]),
+        bigint("b_is_99", [0]),
+    ]);
+    assert_tables_equal!(res, expected);
+
+    for (alias, predicate) in predicates {
+        let naive_expr = group_by(vec![], vec![], alias, tab(&t), predicate);
+        let naive_res = VerifiableQueryResult::new(&naive_expr, &accessor, &(), &[])
+            .unwrap()
+            .verify(&naive_expr, &accessor, &(), &[])
+            .unwrap()
+            .table;
+        assert_eq!(
+            naive_res.inner_table()[&Ident::new(alias)],
+            res.inner_table()[&Ident::new(alias)]
+        );
+    }
+}
+
+#[test]
+fn we_can_prove_counts_on_an_empty_table() {
+    let data = owned_table([bigint("a", [0; 0])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = multi_count(
+        vec![
+            aliased_plan(equal(column(&t, "a", &accessor), const_bigint(1)), "a_is_1"),
+            aliased_plan(const_bool(true), "all"),
+        ],
+        tab(&t),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([bigint("a_is_1", [0]), bigint("all", [0])]);
+    assert_tables_equal!(res, expected);
+}
+
+#[test]
+fn we_can_prove_counts_on_a_table_with_an_offset() {
+    let data = owned_table([bigint("a", [1, 2, 3, 4, 5])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 3);
+    let expr = multi_count(
+        vec![
+            aliased_plan(gte(column(&t, "a", &accessor), const_bigint(2)), "a_gte_2"),
+            aliased_plan(const_bool(true), "all"),
+        ],
+        tab(&t),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
+    let expected = owned_table([bigint("a_gte_2", [4]), bigint("all", [5])]);
+    assert_tables_equal!(res, expected);
+}
+
+fn verify_is_constant(
+    data: OwnedTable<Curve25519Scalar>,
+    expr: impl Fn(&TableRef, &OwnedTableTestAccessor<InnerProductProof>) -> DynProofExpr,
+    value: DynProofExpr,
+) -> OwnedTable<Curve25519Scalar> {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let plan = DynProofPlan::try_new_is_constant(
+        tab(&t),
+        expr(&t, &accessor),
+        value,
+        Ident::new("is_constant"),
+    )
+    .unwrap();
+    let res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &plan, &accessor, &t);
+    res.verify(&plan, &accessor, &(), &[]).unwrap().table
+}
+
+#[test]
+fn we_can_prove_a_column_is_constant() {
+    let res = verify_is_constant(
+        owned_table([bigint("a", [7, 7, 7, 7]), bigint("b", [1, 2, 3, 4])]),
+        |t, accessor| column(t, "a", accessor),
+        const_bigint(7),
+    );
+    assert_tables_equal!(res, owned_table([boolean("is_constant", [true])]));
+
+    let res = verify_is_constant(
+        owned_table([varchar("a", ["x", "x", "x"])]),
+        |t, accessor| column(t, "a", accessor),
+        const_varchar("x"),
+    );
+    assert_tables_equal!(res, owned_table([boolean("is_constant", [true])]));
+}
+
+#[test]
+fn we_can_prove_a_column_is_not_constant() {
+    let res = verify_is_constant(
+        owned_table([bigint("a", [7, 7, 8, 7])]),
+        |t, accessor| column(t, "a", accessor),
+        const_bigint(7),
+    );
+    assert_tables_equal!(res, owned_table([boolean("is_constant", [false])]));
+
+    // Constant, but not equal to the given value
+    let res = verify_is_constant(
+        owned_table([bigint("a", [7, 7, 7])]),
+        |t, accessor| column(t, "a", accessor),
+        const_bigint(8),
+    );
+    assert_tables_equal!(res, owned_table([boolean("is_constant", [false])]));
+}
+
+#[test]
+fn we_can_prove_an_expression_is_constant() {
+    let res = verify_is_constant(
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 3, 2])]),
+        |t, accessor| add(column(t, "a", accessor), column(t, "b", accessor)),
+        const_bigint(5),
+    );
+    assert_tables_equal!(res, owned_table([boolean("is_constant", [true])]));
+}
+
+#[test]
+fn we_can_prove_empty_and_single_row_columns_are_constant() {
+    let res = verify_is_constant(
+        owned_table([bigint("a", [0; 0])]),
+        |t, accessor| column(t, "a", accessor),
+        const_bigint(7),
+    );
+    assert_tables_equal!(res, owned_table([boolean("is_constant", [true])]));
+
+    let res = verify_is_constant(
+        owned_table([bigint("a", [7])]),
+        |t, accessor| column(t, "a", accessor),
+        const_bigint(7),
+    );
+    assert_tables_equal!(res, owned_table([boolean("is_constant", [true])]));
+}
+
+#[test]
+fn we_cannot_verify_a_column_is_constant_with_counts_spread_over_several_rows() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), owned_table([bigint("a", [7, 7, 8, 7])]), 0);
+    let plan = CheatingIsConstant(
+        DynProofPlan::try_new_is_constant(
+            tab(&t),
+            column(&t, "a", &accessor),
+            const_bigint(7),
+            Ident::new("is_constant"),
+        )
+        .unwrap(),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &[]).unwrap();
+    assert!(matches!(
+        res.verify(&plan, &accessor, &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_check_a_column_is_constant_with_an_incomparable_value() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), owned_table([bigint("a", [1, 2])]), 0);
+    assert!(DynProofPlan::try_new_is_constant(
+        tab(&t),
+        column(&t, "a", &accessor),
+        const_varchar("x"),
+        Ident::new("is_constant"),
+    )
+    .is_err());
+}
@@ -0,0 +1,471 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        database::{
+            Column, ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable, Table,
+            TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        math::decimal::Precision,
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::{nearest_multiple_quotient, AliasedDynProofExpr, ProofExpr},
+        proof_gadgets::{final_round_evaluate_sign, verifier_evaluate_sign},
+        AnalyzeError, AnalyzeResult,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
+use bumpalo::Bump;
+use num_traits::Inv;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// The maximum precision of a percentage of a total.
+///
+/// This keeps the percentages, and the amounts multiplied by `100 * 10^scale`, within an `i128`.
+pub const MAX_PERCENTAGE_PRECISION: u8 = 38;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <result_expr1>, ..., <result_exprN>,
+///         <amount> * 100 / SUM(<amount>) OVER () as <alias>
+///     FROM <input>
+/// ```
+///
+/// The percentage is a decimal with the given scale, rounded half away from zero, so that the
+/// percentages of a nonnegative amount sum to 100 up to the rounding of each row.
+/// Percentages of an input with a total of zero are undefined and fail verification, unless the
+/// input is empty.
+///
+/// The proof has two passes over the input. The first pass commits to the total `T` as a column
+/// which is zero after its first row and proves that it is the sum of the amounts. The verifier
+/// recovers `T` from the evaluation of that column, which makes `T` a constant of the second pass.
+/// With `T` known, the prover commits to the percentages `p` and shows that
+/// `r = 100 * 10^scale * amount - T * p` satisfies the same bounds as the remainder of
+/// [`RoundToMultipleExpr`](crate::sql::proof_exprs::RoundToMultipleExpr), while `p` is range
+/// checked so that `T * p` can't wrap around.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct PercentageOfTotalExec {
+    pub(super) aliased_results: Vec<AliasedDynProofExpr>,
+    pub(super) amount: AliasedDynProofExpr,
+    pub(super) scale: i8,
+    pub(super) input: Box<DynProofPlan>,
+}
+
+impl PercentageOfTotalExec {
+    /// Creates a new percentage of total plan.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnalyzeError::InvalidDataType`] if the amount is not an integer or a decimal,
+    /// [`AnalyzeError::InvalidPercentageScale`] if `scale` is negative or the percentage would
+    /// need a precision above [`MAX_PERCENTAGE_PRECISION`] and [`AnalyzeError::DuplicateIdent`] if
+    /// the alias of the percentage is also the alias of a result.
+    pub fn try_new(
+        aliased_results: Vec<AliasedDynProofExpr>,
+        amount: AliasedDynProofExpr,
+        scale: i8,
+        input: Box<DynProofPlan>,
+    ) -> AnalyzeResult<Self> {
+        let expr_type = amount.expr.data_type();
+        if !expr_type.is_integer() && !matches!(expr_type, ColumnType::Decimal75(_, _)) {
+            return Err(AnalyzeError::InvalidDataType { expr_type });
+        }
+        let amount_precision = expr_type
+            .precision_value()
+            .expect("Numeric types have a precision");
+        if scale < 0
+            || i16::from(amount_precision) + 2 + i16::from(scale)
+                > i16::from(MAX_PERCENTAGE_PRECISION)
+        {
+            return Err(AnalyzeError::InvalidPercentageScale { scale, expr_type });
+        }
+        if aliased_results
+            .iter()
+            .any(|aliased_expr| aliased_expr.alias == amount.alias)
+        {
+            return Err(AnalyzeError::DuplicateIdent {
+                ident: amount.alias.to_string(),
+            });
+        }
+        Ok(Self {
+            aliased_results,
+            amount,
+            scale,
+            input,
+        })
+    }
+
+    /// Get a reference to the input plan
+    pub fn input(&self) -> &DynProofPlan {
+        &self.input
+    }
+
+    /// Get a reference to the aliased results
+    pub fn aliased_results(&self) -> &[AliasedDynProofExpr] {
+        &self.aliased_results
+    }
+
+    /// Get a reference to the amount, aliased by the name of its percentage
+    pub fn amount(&self) -> &AliasedDynProofExpr {
+        &self.amount
+    }
+
+    /// Get the scale of the percentage
+    pub fn scale(&self) -> i8 {
+        self.scale
+    }
+
+    /// The precision of the percentage
+    ///
+    /// Since a nonzero total is at least one in absolute value, the magnitude of a percentage is
+    /// at most `100 * 10^scale` times that of its amount.
+    fn precision(&self) -> u8 {
+        let amount_precision = self
+            .amount
+            .expr
+            .data_type()
+            .precision_value()
+            .expect("Numeric types have a precision");
+        amount_precision + 2 + self.scale.unsigned_abs()
+    }
+
+    /// The factor `100 * 10^scale` the amounts are multiplied by
+    fn factor(&self) -> i128 {
+        10_i128.pow(u32::from(self.scale.unsigned_abs()) + 2)
+    }
+
+    /// The number of bits the percentages are range checked to
+    ///
+    /// With `2^k <= |total|` and the percentages bounded by `10^precision < 2^m` for a nonzero
+    /// total, the percentages have magnitude at most `2^(m-k)`. Allowing `m - k + 2` bits, but at
+    /// least two, bounds `|total * percentage|` by `2^129`, far below the modulus of the scalar
+    /// field.
+    fn percentage_bits(&self, total: i128) -> u8 {
+        let max_percentage = 10_u128.pow(u32::from(self.precision()));
+        let percentage_bits = 128 - max_percentage.leading_zeros();
+        let total_log2 = 127 - total.unsigned_abs().max(1).leading_zeros();
+        u8::try_from((percentage_bits + 2).saturating_sub(total_log2).max(2))
+            .expect("Percentages have at most 128 bits")
+    }
+
+    /// Compute the total of `amounts` and the percentages of it, which are all zero if the total
+    /// is zero
+    fn percentages<S: Scalar>(&self, amounts: &[S]) -> (i128, Vec<i128>) {
+        let amounts: Vec<i128> = amounts
+            .iter()
+            .map(|&amount| {
+                let Ok(amount) = amount.try_into() else {
+                    panic!("Amounts have at most 38 digits");
+                };
+                amount
+            })
+            .collect();
+        let total: i128 = amounts.iter().sum();
+        let factor = self.factor();
+        let percentages = amounts
+            .iter()
+            .map(|&amount| {
+                if total == 0 {
+                    0
+                } else {
+                    nearest_multiple_quotient(factor * amount, total)
+                }
+            })
+            .collect();
+        (total, percentages)
+    }
+
+    /// Evaluate the results and the percentages of the input, returning the result table along
+    /// with the amounts, the total and the percentages
+    fn result_table<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        input: &Table<'a, S>,
+        result_columns: Vec<Column<'a, S>>,
+        amount_column: Column<'a, S>,
+    ) -> (Table<'a, S>, &'a [S], i128, &'a [S]) {
+        let amounts: &'a [S] = alloc.alloc_slice_fill_iter(amount_column.to_scalar());
+        let (total, percentages) = self.percentages(amounts);
+        let percentages: &'a [S] =
+            alloc.alloc_slice_fill_iter(percentages.into_iter().map(S::from));
+        let percentage_column = Column::Decimal75(
+            Precision::new(self.precision()).expect("Precision is at most 38"),
+            self.scale,
+            percentages,
+        );
+        let res = Table::<'a, S>::try_new_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(result_columns.into_iter().chain([percentage_column]))
+                .collect(),
+            TableOptions::new(Some(input.num_rows())),
+        )
+        .expect("Failed to create table from iterator");
+        (res, amounts, total, percentages)
+    }
+}
+
+impl ProofPlan for PercentageOfTotalExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // The input and the output have the same length and hence the same chi eval
+        let input_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let chi_eval = input_eval.chi_eval();
+        let input_schema = self.input.get_column_result_fields();
+        let current_accessor = input_schema
+            .iter()
+            .zip(input_eval.column_evals())
+            .map(|(field, eval)| (field.name().clone(), *eval))
+            .collect::<IndexMap<_, _>>();
+        // 1. results and amounts
+        let mut output_column_evals = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, &current_accessor, chi_eval, params)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let amount_eval =
+            self.amount
+                .expr
+                .verifier_evaluate(builder, &current_accessor, chi_eval, params)?;
+        let total_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let percentage_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // 2. total
+        // total - total * singleton_chi = 0, i.e. the total column is zero after its first row
+        let singleton_chi_eval = builder.singleton_chi_evaluation();
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            total_eval - total_eval * singleton_chi_eval,
+            2,
+        )?;
+        // sum amount - total = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::ZeroSum,
+            amount_eval - total_eval,
+            1,
+        )?;
+        let total: i128 = singleton_chi_eval
+            .inv()
+            .map(|inverse| total_eval * inverse)
+            .and_then(|total| total.try_into().ok())
+            .ok_or(ProofError::VerificationError {
+                error: "total of percentages is out of range",
+            })?;
+        if total == 0 && chi_eval != S::ZERO {
+            return Err(ProofError::VerificationError {
+                error: "percentages of a total of zero are undefined",
+            });
+        }
+
+        // 3. percentages
+        // The amounts are flipped for a negative total so that the percentages are the rounded
+        // quotients of them by the absolute value of the total
+        let sign = if total < 0 { -S::ONE } else { S::ONE };
+        let abs_total = S::from(total) * sign;
+        let signed_amount_eval = amount_eval * sign;
+        // sign(signed_amount) == -1, which decides the rounding direction of midpoints
+        let is_negative_eval = verifier_evaluate_sign(builder, signed_amount_eval, chi_eval, None)?;
+        // range check of the percentages
+        verifier_evaluate_sign(
+            builder,
+            percentage_eval,
+            chi_eval,
+            Some(self.percentage_bits(total)),
+        )?;
+        let doubled_remainder_eval =
+            (S::from(self.factor()) * signed_amount_eval - abs_total * percentage_eval) * S::TWO;
+        let lower_bound_eval = doubled_remainder_eval + abs_total * chi_eval - is_negative_eval;
+        let upper_bound_eval =
+            (abs_total - S::ONE) * chi_eval + is_negative_eval - doubled_remainder_eval;
+        for bound_eval in [lower_bound_eval, upper_bound_eval] {
+            if verifier_evaluate_sign(builder, bound_eval, chi_eval, None)? != S::ZERO {
+                return Err(ProofError::VerificationError {
+                    error: "percentage is not the rounded share of the total",
+                });
+            }
+        }
+
+        output_column_evals.push(percentage_eval);
+        Ok(TableEvaluation::new(output_column_evals, chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
+            })
+            .chain([ColumnField::new(
+                self.amount.alias.clone(),
+                ColumnType::Decimal75(
+                    Precision::new(self.precision()).expect("Precision is at most 38"),
+                    self.scale,
+                ),
+            )])
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        // Any output column reference is a reference to an input column
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for PercentageOfTotalExec {
+    #[tracing::instrument(
+        name = "PercentageOfTotalExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let input = self
+            .input
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let result_columns = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .first_round_evaluate(alloc, &input, params)
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let amount_column = self
+            .amount
+            .expr
+            .first_round_evaluate(alloc, &input, params)?;
+        let (res, ..) = self.result_table(alloc, &input, result_columns, amount_column);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+
+    #[tracing::instrument(
+        name = "PercentageOfTotalExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        let input = self
+            .input
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        // 1. results and amounts
+        let result_columns = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .final_round_evaluate(builder, alloc, &input, params)
+            })
+            .collect::<PlaceholderResult<Vec<_>>>()?;
+        let amount_column = self
+            .amount
+            .expr
+            .final_round_evaluate(builder, alloc, &input, params)?;
+        let (res, amounts, total, percentages) =
+            self.result_table(alloc, &input, result_columns, amount_column);
+        let total_column: &'a [S] = alloc.alloc_slice_copy(&[S::from(total)]);
+        builder.produce_intermediate_mle(total_column);
+        builder.produce_intermediate_mle(percentages);
+
+        // 2. total
+        // total - total * singleton_chi = 0
+        let singleton_chi: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(total_column)]),
+                (
+                    -S::one(),
+                    vec![Box::new(total_column), Box::new(singleton_chi)],
+                ),
+            ],
+        );
+        // sum amount - total = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (S::one(), vec![Box::new(amounts)]),
+                (-S::one(), vec![Box::new(total_column)]),
+            ],
+        );
+
+        // 3. percentages
+        let sign = if total < 0 { -S::ONE } else { S::ONE };
+        let abs_total = S::from(total) * sign;
+        let signed_amounts: &'a [S] =
+            alloc.alloc_slice_fill_iter(amounts.iter().map(|&amount| amount * sign));
+        // sign(signed_amount) == -1, which decides the rounding direction of midpoints
+        let is_negative = final_round_evaluate_sign(builder, alloc, signed_amounts);
+        // range check of the percentages
+        final_round_evaluate_sign(builder, alloc, percentages);
+
+        // The bounds on the remainders, which are all non-negative
+        let factor = S::from(self.factor());
+        let doubled_remainders = signed_amounts
+            .iter()
+            .zip(percentages)
+            .map(|(&amount, &percentage)| (factor * amount - abs_total * percentage) * S::TWO);
+        let lower_bounds: &'a [S] =
+            alloc.alloc_slice_fill_iter(doubled_remainders.clone().zip(is_negative).map(
+                |(doubled_remainder, &is_negative)| {
+                    doubled_remainder + abs_total - S::from(is_negative)
+                },
+            ));
+        let upper_bounds: &'a [S] =
+            alloc.alloc_slice_fill_iter(doubled_remainders.zip(is_negative).map(
+                |(doubled_remainder, &is_negative)| {
+                    abs_total - S::ONE + S::from(is_negative) - doubled_remainder
+                },
+            ));
+        final_round_evaluate_sign(builder, alloc, lower_bounds);
+        final_round_evaluate_sign(builder, alloc, upper_bounds);
+
+        log::log_memory_usage("End");
+
+        Ok(res)
+    }
+}
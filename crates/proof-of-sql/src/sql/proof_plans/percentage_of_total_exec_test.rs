@@ -0,0 +1,407 @@
+use super::{test_utility::*, PercentageOfTotalExec};
+use crate::{
+    assert_tables_equal,
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, ColumnType, OwnedColumn, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+        math::decimal::Precision,
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        AnalyzeError,
+    },
+};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+/// The values of a decimal result column
+fn decimal_values(table: &OwnedTable<Curve25519Scalar>, name: &str) -> Vec<i128> {
+    match &table.inner_table()[name] {
+        OwnedColumn::Decimal75(_, _, values) => values
+            .iter()
+            .map(|&value| i128::try_from(value).unwrap())
+            .collect(),
+        _ => panic!("Expected a decimal column"),
+    }
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_references() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([
+            varchar("region", ["north", "south"]),
+            int("amount", [1, 2]),
+            decimal75("price", 10, 2, [150, 250]),
+        ]),
+        0,
+    );
+    let plan = PercentageOfTotalExec::try_new(
+        vec![aliased_plan(column(&t, "region", &accessor), "region")],
+        aliased_plan(column(&t, "amount", &accessor), "share"),
+        2,
+        Box::new(table_exec(
+            t.clone(),
+            vec![
+                column_field("region", ColumnType::VarChar),
+                column_field("amount", ColumnType::Int),
+            ],
+        )),
+    )
+    .unwrap();
+    assert_eq!(
+        plan.get_column_result_fields(),
+        vec![
+            column_field("region", ColumnType::VarChar),
+            column_field(
+                "share",
+                ColumnType::Decimal75(Precision::new(14).unwrap(), 2)
+            ),
+        ]
+    );
+    assert_eq!(
+        plan.get_column_references(),
+        [
+            col_ref(&t, "region", &accessor),
+            col_ref(&t, "amount", &accessor)
+        ]
+        .into_iter()
+        .collect()
+    );
+    assert_eq!(
+        plan.get_table_references(),
+        [t.clone()].into_iter().collect()
+    );
+
+    // A decimal amount with precision 10 and a scale 1 percentage
+    let plan = PercentageOfTotalExec::try_new(
+        vec![],
+        aliased_plan(column(&t, "price", &accessor), "share"),
+        1,
+        Box::new(table_exec(
+            t,
+            vec![column_field(
+                "price",
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+            )],
+        )),
+    )
+    .unwrap();
+    assert_eq!(
+        plan.get_column_result_fields(),
+        vec![column_field(
+            "share",
+            ColumnType::Decimal75(Precision::new(13).unwrap(), 1)
+        )]
+    );
+}
+
+#[test]
+fn we_cannot_create_a_percentage_of_total_with_an_invalid_amount_or_scale() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([
+            varchar("region", ["north"]),
+            bigint("amount", [1]),
+            int128("big_amount", [1]),
+        ]),
+        0,
+    );
+    let input = || {
+        Box::new(table_exec(
+            t.clone(),
+            vec![
+                column_field("region", ColumnType::VarChar),
+                column_field("amount", ColumnType::BigInt),
+                column_field("big_amount", ColumnType::Int128),
+            ],
+        ))
+    };
+    assert!(matches!(
+        PercentageOfTotalExec::try_new(
+            vec![],
+            aliased_plan(column(&t, "region", &accessor), "share"),
+            2,
+            input()
+        ),
+        Err(AnalyzeError::InvalidDataType {
+            expr_type: ColumnType::VarChar
+        })
+    ));
+    // 19 digits of a BIGINT, 2 digits of the percent and the scale exceed 38 digits
+    for scale in [-1, 18] {
+        assert!(matches!(
+            PercentageOfTotalExec::try_new(
+                vec![],
+                aliased_plan(column(&t, "amount", &accessor), "share"),
+                scale,
+                input()
+            ),
+            Err(AnalyzeError::InvalidPercentageScale {
+                expr_type: ColumnType::BigInt,
+                ..
+            })
+        ));
+    }
+    assert!(PercentageOfTotalExec::try_new(
+        vec![],
+        aliased_plan(column(&t, "amount", &accessor), "share"),
+        17,
+        input()
+    )
+    .is_ok());
+    assert!(matches!(
+        PercentageOfTotalExec::try_new(
+            vec![],
+            aliased_plan(column(&t, "big_amount", &accessor), "share"),
+            0,
+            input()
+        ),
+        Err(AnalyzeError::InvalidPercentageScale {
+            scale: 0,
+            expr_type: ColumnType::Int128
+        })
+    ));
+    assert!(matches!(
+        PercentageOfTotalExec::try_new(
+            vec![aliased_plan(column(&t, "region", &accessor), "share")],
+            aliased_plan(column(&t, "amount", &accessor), "share"),
+            2,
+            input()
+        ),
+        Err(AnalyzeError::DuplicateIdent { ident }) if ident == "share"
+    ));
+}
+
+/// `select region, amount * 100 / sum(amount) over () as share from sxt.t`
+#[test]
+fn we_can_prove_the_percentages_of_a_total() {
+    let data = owned_table([
+        varchar("region", ["north", "south", "east", "west"]),
+        bigint("amount", [10_i64, 20, 30, 40]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let plan = percentage_of_total(
+        vec![aliased_plan(column(&t, "region", &accessor), "region")],
+        aliased_plan(column(&t, "amount", &accessor), "share"),
+        2,
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("region", ColumnType::VarChar),
+                column_field("amount", ColumnType::BigInt),
+            ],
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &plan, &accessor, &t);
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        varchar("region", ["north", "south", "east", "west"]),
+        decimal75("share", 23, 2, [1000, 2000, 3000, 4000]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+/// `select amount * 100 / sum(amount) over () as share from sxt.t`
+///
+/// Midpoints are rounded half away from zero, and a negative total flips the signs.
+#[test]
+fn we_can_prove_rounded_percentages_of_negative_amounts_and_totals() {
+    let t = TableRef::new("sxt", "t");
+    for (amounts, scale, expected_shares) in [
+        // 100/3 and 200/3 percent
+        (vec![1_i32, 2], 2, vec![3333_i64, 6667]),
+        // 12.5 and 62.5 percent
+        (vec![1, 1, 1, 5], 0, vec![13, 13, 13, 63]),
+        // -12.5 and 112.5 percent
+        (vec![-1, 9], 0, vec![-13, 113]),
+        // A negative total
+        (vec![-30, -10], 1, vec![750, 250]),
+        (vec![1, -3], 0, vec![-50, 150]),
+    ] {
+        let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+        accessor.add_table(t.clone(), owned_table([int("amount", amounts)]), 0);
+        let plan = percentage_of_total(
+            vec![],
+            aliased_plan(column(&t, "amount", &accessor), "share"),
+            scale,
+            table_exec(t.clone(), vec![column_field("amount", ColumnType::Int)]),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &plan, &accessor, &t);
+        let res = verifiable_res
+            .verify(&plan, &accessor, &(), &[])
+            .unwrap()
+            .table;
+        let expected_res = owned_table([decimal75(
+            "share",
+            12 + scale.unsigned_abs(),
+            scale,
+            expected_shares,
+        )]);
+        assert_tables_equal!(res, expected_res);
+    }
+}
+
+/// `select amount * 100 / sum(amount) over () as share from sxt.t where amount > 1.00`
+#[test]
+fn we_can_prove_the_percentages_of_a_decimal_total_of_a_filtered_input() {
+    let data = owned_table([decimal75("amount", 10, 2, [150, 250, 100, 0])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let input = filter(
+        cols_expr_plan(&t, &["amount"], &accessor),
+        tab(&t),
+        gt(column(&t, "amount", &accessor), const_decimal75(10, 2, 100)),
+    );
+    let plan = percentage_of_total(
+        vec![aliased_plan(
+            DynProofExpr::new_column(col_ref(&t, "amount", &accessor)),
+            "amount",
+        )],
+        aliased_plan(
+            DynProofExpr::new_column(col_ref(&t, "amount", &accessor)),
+            "share",
+        ),
+        1,
+        input,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &plan, &accessor, &t);
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        decimal75("amount", 10, 2, [150, 250]),
+        decimal75("share", 13, 1, [375, 625]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+/// `select amount * 100 / sum(amount) over () as share from sxt.t where amount > 100`
+#[test]
+fn we_can_prove_the_percentages_of_an_empty_input() {
+    let data = owned_table([bigint("amount", [10_i64, 20])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let input = filter(
+        cols_expr_plan(&t, &["amount"], &accessor),
+        tab(&t),
+        gt(column(&t, "amount", &accessor), const_bigint(100)),
+    );
+    let plan = percentage_of_total(
+        vec![],
+        aliased_plan(
+            DynProofExpr::new_column(col_ref(&t, "amount", &accessor)),
+            "share",
+        ),
+        2,
+        input,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &plan, &accessor, &t);
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([decimal75("share", 23, 2, Vec::<i64>::new())]);
+    assert_tables_equal!(res, expected_res);
+}
+
+/// `select amount * 100 / sum(amount) over () as share from sxt.t` with a total of zero
+#[test]
+fn we_cannot_verify_the_percentages_of_a_total_of_zero() {
+    let data = owned_table([bigint("amount", [5_i64, -5, 0])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let plan = percentage_of_total(
+        vec![],
+        aliased_plan(column(&t, "amount", &accessor), "share"),
+        2,
+        table_exec(t.clone(), vec![column_field("amount", ColumnType::BigInt)]),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    assert!(verifiable_res.verify(&plan, &accessor, &(), &[]).is_err());
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(0, 1000);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table with a positive total
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let amounts: Vec<i64> = core::iter::once(1)
+            .chain(dist.sample_iter(&mut rng).take(n - 1))
+            .collect();
+        let data = owned_table([bigint("amount", amounts.clone())]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data,
+            offset,
+            (),
+        );
+        let plan = percentage_of_total(
+            vec![],
+            aliased_plan(column(&t, "amount", &accessor), "share"),
+            2,
+            table_exec(t.clone(), vec![column_field("amount", ColumnType::BigInt)]),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+        exercise_verification(&verifiable_res, &plan, &accessor, &t);
+        let res = verifiable_res
+            .verify(&plan, &accessor, &(), &[])
+            .unwrap()
+            .table;
+
+        // Calculate/compare expected result, rounding half up
+        let total: i64 = amounts.iter().sum();
+        let expected_shares: Vec<i64> = amounts
+            .iter()
+            .map(|amount| (2 * 10_000 * amount + total) / (2 * total))
+            .collect();
+        assert_tables_equal!(
+            res,
+            owned_table([decimal75("share", 23, 2, expected_shares)])
+        );
+
+        // The percentages sum to 100 up to half a unit of rounding per row
+        let shares = decimal_values(&res, "share");
+        let deviation = (shares.iter().sum::<i128>() - 10_000).unsigned_abs();
+        assert!(2 * deviation <= u128::try_from(n).unwrap());
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_a_non_zero_offset() {
+    test_random_tables_with_given_offset(75);
+}
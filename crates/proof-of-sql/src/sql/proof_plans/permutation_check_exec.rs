@@ -0,0 +1,329 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        database::{
+            join_util::get_columns_of_table, Column, ColumnField, ColumnRef, ColumnType,
+            LiteralValue, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_permutation_difference, verify_permutation_difference,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use num_traits::Inv;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// `ProofPlan` attesting whether a column of the `candidate` input is a permutation of a column
+/// of the `source` input, i.e. whether both hold the same multiset of values, as in
+/// ```ignore
+///     SELECT NOT EXISTS (
+///         (SELECT <col1> FROM <ProofPlan> EXCEPT ALL SELECT <col2> FROM <ProofPlan>)
+///         UNION ALL
+///         (SELECT <col2> FROM <ProofPlan> EXCEPT ALL SELECT <col1> FROM <ProofPlan>)
+///     ) AS <alias>
+/// ```
+///
+/// The result is a single row with a single boolean column. Duplicates have to occur equally often
+/// on both sides, so columns of different lengths are never permutations of each other, while two
+/// empty columns are.
+///
+/// The proof compares `sum 1 / (1 + alpha * source)` with `sum 1 / (1 + alpha * candidate)` for a
+/// random `alpha`, which are equal exactly when the multisets are, with overwhelming probability.
+/// Their difference is proven as a column which is zero after its first row, and the result is
+/// whether its first row is zero.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermutationCheckExec {
+    pub(super) source: Box<DynProofPlan>,
+    pub(super) candidate: Box<DynProofPlan>,
+    pub(super) source_column_index: usize,
+    pub(super) candidate_column_index: usize,
+    pub(super) alias: Ident,
+}
+
+impl PermutationCheckExec {
+    /// Create a new `PermutationCheckExec` checking whether the values of the
+    /// `candidate_column_index`-th column of `candidate` are a permutation of the values of the
+    /// `source_column_index`-th column of `source`
+    ///
+    /// # Panics
+    /// Panics if one of the following conditions is met:
+    /// - A column index is out of bounds
+    /// - The types of the columns are different
+    #[must_use]
+    pub fn new(
+        source: Box<DynProofPlan>,
+        candidate: Box<DynProofPlan>,
+        source_column_index: usize,
+        candidate_column_index: usize,
+        alias: Ident,
+    ) -> Self {
+        let (Some(source_field), Some(candidate_field)) = (
+            source
+                .get_column_result_fields()
+                .get(source_column_index)
+                .cloned(),
+            candidate
+                .get_column_result_fields()
+                .get(candidate_column_index)
+                .cloned(),
+        ) else {
+            panic!("Permutation column index out of bounds");
+        };
+        assert!(
+            (source_field.data_type() == candidate_field.data_type()),
+            "Permutation columns should have the same types"
+        );
+        Self {
+            source,
+            candidate,
+            source_column_index,
+            candidate_column_index,
+            alias,
+        }
+    }
+
+    /// Get the source plan
+    pub fn source(&self) -> &DynProofPlan {
+        &self.source
+    }
+
+    /// Get the candidate plan
+    pub fn candidate(&self) -> &DynProofPlan {
+        &self.candidate
+    }
+
+    /// Get the index of the compared column of the source plan
+    pub fn source_column_index(&self) -> usize {
+        self.source_column_index
+    }
+
+    /// Get the index of the compared column of the candidate plan
+    pub fn candidate_column_index(&self) -> usize {
+        self.candidate_column_index
+    }
+
+    /// Get the alias of the result
+    pub fn alias(&self) -> &Ident {
+        &self.alias
+    }
+
+    /// The single-row result holding whether the columns are permutations of each other
+    fn result_table<'a, S: Scalar>(&self, alloc: &'a Bump, is_permutation: bool) -> Table<'a, S> {
+        Table::<'a, S>::try_from_iter_with_options(
+            [(
+                self.alias.clone(),
+                Column::Boolean(alloc.alloc_slice_copy(&[is_permutation])),
+            )],
+            TableOptions::new(Some(1)),
+        )
+        .expect("Failed to create table from iterator")
+    }
+}
+
+/// Whether the two columns hold the same multiset of values
+fn is_permutation<S: Scalar>(source: Column<'_, S>, candidate: Column<'_, S>) -> bool {
+    let mut source = source.to_scalar();
+    let mut candidate = candidate.to_scalar();
+    source.sort_unstable();
+    candidate.sort_unstable();
+    source == candidate
+}
+
+impl ProofPlan for PermutationCheckExec
+where
+    PermutationCheckExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let source_eval =
+            self.source
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let candidate_eval =
+            self.candidate
+                .verifier_evaluate(builder, accessor, None, chi_eval_map, params)?;
+        let source_column_eval = *source_eval
+            .column_evals()
+            .get(self.source_column_index)
+            .expect("Index can not be out of bounds");
+        let candidate_column_eval = *candidate_eval
+            .column_evals()
+            .get(self.candidate_column_index)
+            .expect("Index can not be out of bounds");
+        // 2. Chi evals
+        let res_chi_eval = builder.try_consume_chi_evaluation_of_length(1)?;
+        // 3. alpha, beta
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 4. `difference` is the difference between the sums of the two sides
+        let difference_eval = verify_permutation_difference(
+            builder,
+            alpha,
+            beta,
+            source_eval.chi_eval(),
+            candidate_eval.chi_eval(),
+            builder.singleton_chi_evaluation(),
+            &[source_column_eval],
+            &[candidate_column_eval],
+        )?;
+        // 5. The result is whether `difference` is zero
+        let pseudo_inv_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let selection_eval = builder.try_consume_final_round_mle_evaluation()?;
+        // selection * difference = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            selection_eval * difference_eval,
+            2,
+        )?;
+        // chi_res - selection - difference * pseudo_inv = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            res_chi_eval - selection_eval - difference_eval * pseudo_inv_eval,
+            2,
+        )?;
+        Ok(TableEvaluation::new(vec![selection_eval], res_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        vec![ColumnField::new(self.alias.clone(), ColumnType::Boolean)]
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.source
+            .get_column_references()
+            .into_iter()
+            .chain(self.candidate.get_column_references())
+            .collect()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.source
+            .get_table_references()
+            .into_iter()
+            .chain(self.candidate.get_table_references())
+            .collect()
+    }
+}
+
+impl ProverEvaluate for PermutationCheckExec {
+    #[tracing::instrument(
+        name = "PermutationCheckExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let source = self
+            .source
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let candidate = self
+            .candidate
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        let c_s = get_columns_of_table(&source, &[self.source_column_index])
+            .expect("Index can not be out of bounds");
+        let c_c = get_columns_of_table(&candidate, &[self.candidate_column_index])
+            .expect("Index can not be out of bounds");
+        // 2. Chi evals
+        builder.produce_chi_evaluation_length(1);
+        // 3. Request post-result challenges
+        builder.request_post_result_challenges(2);
+
+        log::log_memory_usage("End");
+
+        Ok(self.result_table(alloc, is_permutation(c_s[0], c_c[0])))
+    }
+
+    #[tracing::instrument(
+        name = "PermutationCheckExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let source = self
+            .source
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let candidate = self
+            .candidate
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let c_s = get_columns_of_table(&source, &[self.source_column_index])
+            .expect("Index can not be out of bounds");
+        let c_c = get_columns_of_table(&candidate, &[self.candidate_column_index])
+            .expect("Index can not be out of bounds");
+        let chi_s = alloc.alloc_slice_fill_copy(source.num_rows(), true);
+        let chi_c = alloc.alloc_slice_fill_copy(candidate.num_rows(), true);
+        let chi_res: &'a [bool] = alloc.alloc_slice_fill_copy(1, true);
+        // 2. Get post-result challenges
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 3. `difference` is the difference between the sums of the two sides
+        let difference = final_round_evaluate_permutation_difference(
+            builder, alloc, alpha, beta, chi_s, chi_c, &c_s, &c_c,
+        );
+        let alloc_difference: &'a [S] = alloc.alloc_slice_copy(&[difference]);
+        // 4. The result is whether `difference` is zero
+        let pseudo_inv: &'a [S] = alloc.alloc_slice_copy(&[difference.inv().unwrap_or(S::ZERO)]);
+        builder.produce_intermediate_mle(pseudo_inv);
+        let selection: &'a [bool] = alloc.alloc_slice_copy(&[difference == S::ZERO]);
+        builder.produce_intermediate_mle(selection);
+        // selection * difference = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(
+                S::one(),
+                vec![Box::new(selection), Box::new(alloc_difference)],
+            )],
+        );
+        // chi_res - selection - difference * pseudo_inv = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(chi_res)]),
+                (-S::one(), vec![Box::new(selection)]),
+                (
+                    -S::one(),
+                    vec![Box::new(alloc_difference), Box::new(pseudo_inv)],
+                ),
+            ],
+        );
+
+        log::log_memory_usage("End");
+
+        Ok(self.result_table(alloc, selection[0]))
+    }
+}
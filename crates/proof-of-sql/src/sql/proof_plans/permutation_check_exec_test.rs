@@ -0,0 +1,397 @@
+use super::{fold_columns, test_utility::*, DynProofPlan, PermutationCheckExec};
+use crate::{
+    assert_tables_equal,
+    base::{
+        database::{
+            join_util::get_columns_of_table, owned_table_utility::*, table_utility::*, ColumnField,
+            ColumnRef, ColumnType, LiteralValue, OwnedTable, Table, TableEvaluation, TableRef,
+            TableTestAccessor, TestAccessor,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{PlaceholderResult, ProofError},
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            exercise_verification, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            QueryError, SumcheckSubpolynomialType, VerifiableQueryResult, VerificationBuilder,
+        },
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+use serde::Serialize;
+use sqlparser::ast::Ident;
+
+/// A `PermutationCheckExec` whose prover claims that the columns are permutations of each other
+/// by spreading the difference of the sums over two rows, of which only the first is checked to
+/// be zero by the result identities
+#[derive(Debug, Serialize)]
+struct CheatingPermutationCheckExec(PermutationCheckExec);
+
+impl ProverEvaluate for CheatingPermutationCheckExec {
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        self.0
+            .source
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        self.0
+            .candidate
+            .first_round_evaluate(builder, alloc, table_map, params)?;
+        builder.produce_chi_evaluation_length(1);
+        builder.request_post_result_challenges(2);
+        Ok(table([borrowed_boolean("is_permutation", [true], alloc)]))
+    }
+
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let source = self
+            .0
+            .source
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let candidate = self
+            .0
+            .candidate
+            .final_round_evaluate(builder, alloc, table_map, params)?;
+        let c_s = get_columns_of_table(&source, &[self.0.source_column_index]).unwrap();
+        let c_c = get_columns_of_table(&candidate, &[self.0.candidate_column_index]).unwrap();
+        let chi_s: &'a [bool] = alloc.alloc_slice_fill_copy(source.num_rows(), true);
+        let chi_c: &'a [bool] = alloc.alloc_slice_fill_copy(candidate.num_rows(), true);
+        let chi_res: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        let singleton_chi: &'a [bool] = alloc.alloc_slice_copy(&[true]);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let c_fold: &'a mut [S] = alloc.alloc_slice_fill_copy(chi_s.len(), S::ZERO);
+        fold_columns(c_fold, alpha, beta, &c_s);
+        let d_fold: &'a mut [S] = alloc.alloc_slice_fill_copy(chi_c.len(), S::ZERO);
+        fold_columns(d_fold, alpha, beta, &c_c);
+        let c_star: &'a mut [S] = alloc.alloc_slice_copy(c_fold);
+        slice_ops::add_const::<S, S>(c_star, S::ONE);
+        slice_ops::batch_inversion(c_star);
+        let d_star: &'a mut [S] = alloc.alloc_slice_copy(d_fold);
+        slice_ops::add_const::<S, S>(d_star, S::ONE);
+        slice_ops::batch_inversion(d_star);
+        let (c_fold, d_fold, c_star, d_star): (&'a [S], &'a [S], &'a [S], &'a [S]) =
+            (c_fold, d_fold, c_star, d_star);
+        // The whole difference is moved to the second row
+        let total = c_star.iter().copied().sum::<S>() - d_star.iter().copied().sum::<S>();
+        let difference: &'a [S] = alloc.alloc_slice_copy(&[S::ZERO, total]);
+        builder.produce_intermediate_mle(c_star);
+        builder.produce_intermediate_mle(d_star);
+        builder.produce_intermediate_mle(difference);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(difference)]),
+                (
+                    -S::one(),
+                    vec![Box::new(difference), Box::new(singleton_chi)],
+                ),
+            ],
+        );
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (S::one(), vec![Box::new(c_star)]),
+                (-S::one(), vec![Box::new(d_star)]),
+                (-S::one(), vec![Box::new(difference)]),
+            ],
+        );
+        for (star, fold, chi) in [(c_star, c_fold, chi_s), (d_star, d_fold, chi_c)] {
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(star)]),
+                    (S::one(), vec![Box::new(star), Box::new(fold)]),
+                    (-S::one(), vec![Box::new(chi)]),
+                ],
+            );
+        }
+        // Both result identities hold on every row with these
+        let pseudo_inv: &'a [S] = alloc.alloc_slice_copy(&[S::ZERO, S::ZERO]);
+        builder.produce_intermediate_mle(pseudo_inv);
+        let selection: &'a [bool] = alloc.alloc_slice_copy(&[true, false]);
+        builder.produce_intermediate_mle(selection);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(S::one(), vec![Box::new(selection), Box::new(difference)])],
+        );
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(chi_res)]),
+                (-S::one(), vec![Box::new(selection)]),
+                (-S::one(), vec![Box::new(difference), Box::new(pseudo_inv)]),
+            ],
+        );
+        Ok(table([borrowed_boolean("is_permutation", [true], alloc)]))
+    }
+}
+
+impl ProofPlan for CheatingPermutationCheckExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+        params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        self.0
+            .verifier_evaluate(builder, accessor, result, chi_eval_map, params)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.0.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.0.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.0.get_table_references()
+    }
+}
+
+fn shuffled_and_original_accessor<'a>(
+    alloc: &'a Bump,
+    original: &[i64],
+    shuffled: &[i64],
+) -> (TableTestAccessor<'a, InnerProductProof>, TableRef, TableRef) {
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let originals: TableRef = "sxt.originals".parse().unwrap();
+    let shuffles: TableRef = "sxt.shuffles".parse().unwrap();
+    accessor.add_table(
+        originals.clone(),
+        table([borrowed_bigint("a", original.iter().copied(), alloc)]),
+        0,
+    );
+    accessor.add_table(
+        shuffles.clone(),
+        table([
+            borrowed_bigint(
+                "position",
+                (0..shuffled.len()).map(|i| i64::try_from(i).unwrap()),
+                alloc,
+            ),
+            borrowed_bigint("b", shuffled.iter().copied(), alloc),
+        ]),
+        0,
+    );
+    (accessor, originals, shuffles)
+}
+
+fn shuffle_is_permutation(originals: &TableRef, shuffles: &TableRef) -> DynProofPlan {
+    permutation_check(
+        table_exec(
+            originals.clone(),
+            vec![column_field("a", ColumnType::BigInt)],
+        ),
+        table_exec(
+            shuffles.clone(),
+            vec![
+                column_field("position", ColumnType::BigInt),
+                column_field("b", ColumnType::BigInt),
+            ],
+        ),
+        0,
+        1,
+        "is_permutation",
+    )
+}
+
+fn assert_permutation_check_is_proven(original: &[i64], shuffled: &[i64], expected: bool) {
+    let alloc = Bump::new();
+    let (accessor, originals, shuffles) =
+        shuffled_and_original_accessor(&alloc, original, shuffled);
+    let ast = shuffle_is_permutation(&originals, &shuffles);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &originals);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([boolean("is_permutation", [expected])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_that_a_column_is_a_permutation_of_another() {
+    for (original, shuffled) in [
+        (&[1_i64, 2, 3, 4][..], &[3_i64, 1, 4, 2][..]),
+        (&[1_i64, 2, 3][..], &[1_i64, 2, 3][..]),
+        // Duplicates occurring equally often
+        (&[5_i64, 1, 5, 5, 2][..], &[5_i64, 5, 2, 1, 5][..]),
+        // Negative values
+        (&[-5_i64, 0, i64::MIN, 7][..], &[7_i64, i64::MIN, -5, 0][..]),
+        (&[42_i64][..], &[42_i64][..]),
+        (&[][..], &[][..]),
+    ] {
+        assert_permutation_check_is_proven(original, shuffled, true);
+    }
+}
+
+#[test]
+fn we_can_prove_that_a_column_is_not_a_permutation_of_another() {
+    for (original, shuffled) in [
+        (&[1_i64, 2, 3, 4][..], &[3_i64, 1, 4, 5][..]),
+        // Different lengths
+        (&[1_i64, 2, 3][..], &[1_i64, 2][..]),
+        (&[1_i64, 2][..], &[1_i64, 2, 2][..]),
+        (&[][..], &[1_i64][..]),
+        (&[1_i64][..], &[][..]),
+        // Duplicates occurring with other multiplicities
+        (&[1_i64, 1, 2][..], &[1_i64, 2, 2][..]),
+        (&[3_i64, 3, 3][..], &[3_i64, 3, 4][..]),
+        // Negated values
+        (&[-1_i64, 2][..], &[1_i64, 2][..]),
+    ] {
+        assert_permutation_check_is_proven(original, shuffled, false);
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_permutation_check_whose_result_was_flipped() {
+    let alloc = Bump::new();
+    for (original, shuffled, is_permutation) in [
+        (&[1_i64, 2, 2][..], &[2_i64, 1, 2][..], true),
+        (&[1_i64, 2, 2][..], &[2_i64, 1, 1][..], false),
+        (&[1_i64, 2][..], &[1_i64, 2, 3][..], false),
+    ] {
+        let (accessor, originals, shuffles) =
+            shuffled_and_original_accessor(&alloc, original, shuffled);
+        let ast = shuffle_is_permutation(&originals, &shuffles);
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+        assert!(VerifiableQueryResult {
+            result: owned_table([boolean("is_permutation", [!is_permutation])]),
+            proof: verifiable_res.proof,
+        }
+        .verify(&ast, &accessor, &(), &[])
+        .is_err());
+    }
+}
+
+#[test]
+fn we_can_prove_that_filtered_columns_are_permutations_of_each_other() {
+    let alloc = Bump::new();
+    let (accessor, originals, shuffles) =
+        shuffled_and_original_accessor(&alloc, &[4, 1, 3], &[3, 9, 1, 4]);
+    // The extra value of the shuffle is filtered out
+    let ast = permutation_check(
+        table_exec(
+            originals.clone(),
+            vec![column_field("a", ColumnType::BigInt)],
+        ),
+        filter(
+            cols_expr_plan(&shuffles, &["b"], &accessor),
+            tab(&shuffles),
+            lte(column(&shuffles, "b", &accessor), const_bigint(4)),
+        ),
+        0,
+        0,
+        "is_permutation",
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &originals);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([boolean("is_permutation", [true])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_get_the_result_fields_and_references_of_a_permutation_check() {
+    let originals: TableRef = "sxt.originals".parse().unwrap();
+    let shuffles: TableRef = "sxt.shuffles".parse().unwrap();
+    let ast = shuffle_is_permutation(&originals, &shuffles);
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![ColumnField::new(
+            "is_permutation".into(),
+            ColumnType::Boolean
+        )]
+    );
+    assert_eq!(
+        ast.get_table_references(),
+        [originals, shuffles].into_iter().collect()
+    );
+}
+
+#[test]
+#[should_panic(expected = "Permutation column index out of bounds")]
+fn we_cannot_create_a_permutation_check_with_out_of_bounds_indexes() {
+    let originals: TableRef = "sxt.originals".parse().unwrap();
+    let shuffles: TableRef = "sxt.shuffles".parse().unwrap();
+    let _ = PermutationCheckExec::new(
+        Box::new(table_exec(
+            originals,
+            vec![column_field("a", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            shuffles,
+            vec![column_field("b", ColumnType::BigInt)],
+        )),
+        0,
+        1,
+        "is_permutation".into(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Permutation columns should have the same types")]
+fn we_cannot_create_a_permutation_check_on_columns_of_different_types() {
+    let originals: TableRef = "sxt.originals".parse().unwrap();
+    let shuffles: TableRef = "sxt.shuffles".parse().unwrap();
+    let _ = PermutationCheckExec::new(
+        Box::new(table_exec(
+            originals,
+            vec![column_field("a", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            shuffles,
+            vec![column_field("b", ColumnType::VarChar)],
+        )),
+        0,
+        0,
+        "is_permutation".into(),
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_permutation_check_whose_difference_is_spread_over_several_rows() {
+    let alloc = Bump::new();
+    let (accessor, originals, shuffles) =
+        shuffled_and_original_accessor(&alloc, &[1, 2, 3], &[3, 2, 2]);
+    let DynProofPlan::PermutationCheck(honest_plan) = shuffle_is_permutation(&originals, &shuffles)
+    else {
+        panic!("Expected a permutation check");
+    };
+    let plan = CheatingPermutationCheckExec(honest_plan);
+    let verifiable_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&plan, &accessor, &(), &[]).unwrap();
+    assert!(matches!(
+        verifiable_res.verify(&plan, &accessor, &(), &[]),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError { .. }
+        })
+    ));
+}
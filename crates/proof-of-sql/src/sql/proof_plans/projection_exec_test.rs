@@ -1,5 +1,6 @@
 use super::{test_utility::*, DynProofPlan, ProjectionExec};
 use crate::{
+    assert_tables_equal,
     base::{
         database::{
             owned_table_utility::*, table_utility::*, ColumnField, ColumnRef, ColumnType,
@@ -138,7 +139,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_basic_projection() {
         .unwrap()
         .table;
     let expected = owned_table([bigint("b", [1_i64, 2, 3, 4, 5, 1, 2, 3, 4, 5])]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -176,7 +177,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_nontrivial_projection() {
         decimal75("b", 20, 0, [2_i64, 3, 4, 5, 6]),
         decimal75("prod", 39, 0, [1_i64, 8, 15, 8, 25]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -232,7 +233,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_composed_projection() {
         decimal75("b", 21, 0, [5_i64, 7]),
         decimal75("prod", 41, 0, [32_i64, 60]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -289,7 +290,7 @@ fn we_can_get_an_empty_result_from_a_basic_projection_on_an_empty_table_using_fi
         decimal75("e", 75, 0, [0; 0]),
     ]);
 
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -332,7 +333,7 @@ fn we_can_get_no_columns_from_a_basic_projection_with_no_selected_columns_using_
     .to_owned_table(fields)
     .unwrap();
     let expected = OwnedTable::try_new(IndexMap::default()).unwrap();
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -395,7 +396,7 @@ fn we_can_get_the_correct_result_from_a_basic_projection_using_first_round_evalu
         varchar("d", ["1", "2", "3", "4", "5"]),
         decimal75("e", 1, 0, [3; 5]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -439,7 +440,7 @@ fn we_can_prove_a_projection_on_an_empty_table() {
         varchar("d", ["3"; 0]),
         decimal75("e", 1, 0, [3; 0]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -488,5 +489,5 @@ fn we_can_prove_a_projection() {
         int128("const", [105; 5]),
         boolean("bool", [true, false, true, true, false]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
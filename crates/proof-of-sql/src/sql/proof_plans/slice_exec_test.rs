@@ -1,5 +1,6 @@
 use super::test_utility::*;
 use crate::{
+    assert_tables_equal,
     base::{
         database::{
             owned_table_utility::*, table_utility::*, ColumnField, ColumnType, OwnedTable,
@@ -51,7 +52,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_slice_exec() {
         .unwrap()
         .table;
     let expected_res = owned_table([bigint("a", [2_i64, 3]), varchar("b", ["2", "3"])]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -80,7 +81,7 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_slice_exec() {
         .unwrap()
         .table;
     let expected_res = owned_table([bigint("a", [0_i64; 0]), varchar("b", [""; 0])]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -134,7 +135,7 @@ fn we_can_get_an_empty_result_from_a_slice_on_an_empty_table_using_first_round_e
         decimal75("e", 75, 0, [0; 0]),
     ]);
 
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -188,7 +189,7 @@ fn we_can_get_an_empty_result_from_a_slice_using_first_round_evaluate() {
         decimal75("e", 1, 0, [0; 0]),
     ]);
 
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -223,7 +224,7 @@ fn we_can_get_no_columns_from_a_slice_with_empty_input_using_first_round_evaluat
     .to_owned_table(fields)
     .unwrap();
     let expected = OwnedTable::try_new(IndexMap::default()).unwrap();
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -275,7 +276,7 @@ fn we_can_get_the_correct_result_from_a_slice_using_first_round_evaluate() {
         varchar("d", ["5"]),
         decimal75("e", 1, 0, [5]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -320,7 +321,7 @@ fn we_can_prove_a_slice_exec() {
         int128("const", [105]),
         boolean("bool", [true]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -369,7 +370,7 @@ fn we_can_prove_a_nested_slice_exec() {
         int128("const", [105]),
         boolean("bool", [true]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -418,7 +419,7 @@ fn we_can_prove_a_nested_slice_exec_with_no_rows() {
         int128("const", [0; 0]),
         boolean("bool", [true; 0]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -467,7 +468,7 @@ fn we_can_prove_another_nested_slice_exec_with_no_rows() {
         int128("const", [0; 0]),
         boolean("bool", [true; 0]),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -523,7 +524,7 @@ fn we_can_create_and_prove_a_slice_exec_on_top_of_a_table_exec() {
             ["espacio y tiempo", "espaço e tempo", "espace et temps"],
         ),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -533,7 +534,7 @@ fn we_can_create_and_prove_a_slice_exec_on_top_of_an_empty_exec() {
     let expr = slice_exec(empty_exec(), 3, Some(2));
     let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &(), &[]).unwrap();
     let res = res.verify(&expr, &accessor, &(), &[]).unwrap().table;
-    assert_eq!(res, empty_table);
+    assert_tables_equal!(res, empty_table);
 }
 
 #[test]
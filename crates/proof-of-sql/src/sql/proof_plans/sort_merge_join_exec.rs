@@ -1,4 +1,4 @@
-use super::DynProofPlan;
+use super::{DynProofPlan, ProjectionExec};
 use crate::{
     base::{
         database::{
@@ -19,14 +19,16 @@ use crate::{
             FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
             SumcheckSubpolynomialType, VerificationBuilder,
         },
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr},
         proof_gadgets::{
             final_round_evaluate_membership_check, final_round_evaluate_monotonic,
             first_round_evaluate_membership_check, first_round_evaluate_monotonic,
             verify_membership_check, verify_monotonic,
         },
+        AnalyzeError, AnalyzeResult,
     },
 };
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
 use bumpalo::{
     collections::{CollectIn, Vec as BumpVec},
     Bump,
@@ -93,6 +95,141 @@ impl SortMergeJoinExec {
             result_idents,
         }
     }
+
+    /// Get the left input plan
+    pub fn left(&self) -> &DynProofPlan {
+        &self.left
+    }
+
+    /// Get the right input plan
+    pub fn right(&self) -> &DynProofPlan {
+        &self.right
+    }
+
+    /// Get the indexes of the join columns of the left input
+    pub fn left_join_column_indexes(&self) -> &[usize] {
+        &self.left_join_column_indexes
+    }
+
+    /// Get the indexes of the join columns of the right input
+    pub fn right_join_column_indexes(&self) -> &[usize] {
+        &self.right_join_column_indexes
+    }
+
+    /// Get the names of the result columns
+    pub fn result_idents(&self) -> &[Ident] {
+        &self.result_idents
+    }
+
+    /// Create a new `SortMergeJoinExec` for queries of the form
+    /// ```ignore
+    ///     <ProofPlan> INNER JOIN <ProofPlan>
+    ///     ON expr1 = expr2
+    /// ```
+    ///
+    /// Key expressions that are columns of their input are joined on directly. Otherwise the
+    /// input is wrapped in a [`ProjectionExec`] which evaluates the keys ahead of the input
+    /// columns, naming each key after its entry in `result_idents`. As with
+    /// [`SortMergeJoinExec::new`], `result_idents` names the join keys followed by the
+    /// remaining left and right columns.
+    ///
+    /// # Errors
+    /// - [`AnalyzeError::DataTypeMismatch`] if the two sides of a key have different types
+    /// - [`AnalyzeError::DuplicateIdent`] if a computed key has the same name as a column of
+    ///   its input
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`SortMergeJoinExec::new`].
+    pub fn try_new_with_key_exprs(
+        left: Box<DynProofPlan>,
+        right: Box<DynProofPlan>,
+        key_exprs: Vec<(DynProofExpr, DynProofExpr)>,
+        result_idents: Vec<Ident>,
+    ) -> AnalyzeResult<Self> {
+        for (left_key, right_key) in &key_exprs {
+            if left_key.data_type() != right_key.data_type() {
+                return Err(AnalyzeError::DataTypeMismatch {
+                    left_type: left_key.data_type().to_string(),
+                    right_type: right_key.data_type().to_string(),
+                });
+            }
+        }
+        let (left_keys, right_keys): (Vec<_>, Vec<_>) = key_exprs.into_iter().unzip();
+        let key_idents = &result_idents[..left_keys.len().min(result_idents.len())];
+        let (left, left_join_column_indexes) = with_key_columns(left, left_keys, key_idents)?;
+        let (right, right_join_column_indexes) = with_key_columns(right, right_keys, key_idents)?;
+        Ok(Self::new(
+            left,
+            right,
+            left_join_column_indexes,
+            right_join_column_indexes,
+            result_idents,
+        ))
+    }
+}
+
+/// Returns a plan whose output contains the given join keys along with their indexes
+///
+/// If every key is a column of `input` then `input` is returned unchanged. Otherwise it is
+/// wrapped in a projection that outputs the keys, named by `key_idents`, followed by the
+/// columns of `input`.
+fn with_key_columns(
+    input: Box<DynProofPlan>,
+    key_exprs: Vec<DynProofExpr>,
+    key_idents: &[Ident],
+) -> AnalyzeResult<(Box<DynProofPlan>, Vec<usize>)> {
+    let fields = input.get_column_result_fields();
+    let column_indexes = key_exprs
+        .iter()
+        .map(|key_expr| match key_expr {
+            DynProofExpr::Column(column_expr) => fields
+                .iter()
+                .position(|field| field.name() == column_expr.column_id()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    if let Some(column_indexes) = column_indexes {
+        return Ok((input, column_indexes));
+    }
+    if let Some(ident) = key_idents
+        .iter()
+        .find(|ident| fields.iter().any(|field| field.name() == **ident))
+    {
+        return Err(AnalyzeError::DuplicateIdent {
+            ident: ident.value.clone(),
+        });
+    }
+    // `ProjectionExec` looks columns up by name, so the table of the pass-through columns
+    // is only informational
+    let table_ref = input
+        .get_table_references()
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| TableRef::from_names(None, ""));
+    let key_indexes = (0..key_exprs.len()).collect();
+    let aliased_results = key_exprs
+        .into_iter()
+        .zip(key_idents)
+        .map(|(expr, alias)| AliasedDynProofExpr {
+            expr,
+            alias: alias.clone(),
+        })
+        .chain(fields.into_iter().map(|field| AliasedDynProofExpr {
+            expr: DynProofExpr::new_column(ColumnRef::new(
+                table_ref.clone(),
+                field.name(),
+                field.data_type(),
+            )),
+            alias: field.name(),
+        }))
+        .collect();
+    Ok((
+        Box::new(DynProofPlan::Projection(ProjectionExec::new(
+            aliased_results,
+            input,
+        ))),
+        key_indexes,
+    ))
 }
 
 impl ProofPlan for SortMergeJoinExec
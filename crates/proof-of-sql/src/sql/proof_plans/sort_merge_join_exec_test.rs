@@ -1,5 +1,6 @@
-use super::test_utility::*;
+use super::{test_utility::*, SortMergeJoinExec};
 use crate::{
+    assert_tables_equal,
     base::database::{
         owned_table_utility::*, table_utility::*, ColumnType, TableRef, TableTestAccessor,
         TestAccessor,
@@ -7,6 +8,7 @@ use crate::{
     sql::{
         proof::{exercise_verification, VerifiableQueryResult},
         proof_exprs::test_utility::*,
+        AnalyzeError,
     },
 };
 use blitzar::proof::InnerProductProof;
@@ -68,7 +70,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_join() {
         varchar("name", ["Chloe", "Chloe", "Margaret", "Margaret", "Lucy"]),
         varchar("human", ["Cassia", "Ian", "Cassia", "Ian", "Gretta"]),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -134,7 +136,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_complex_query_involving_sort_m
         varchar("name", ["Margaret", "Margaret"]),
         varchar("human", ["Cassia", "Ian"]),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -259,7 +261,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_complex_query_involving_two_so
             ],
         ),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -313,7 +315,7 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_sort_merge_join() {
         varchar("name", [""; 0]),
         varchar("human", [""; 0]),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[expect(clippy::too_many_lines)]
@@ -366,7 +368,7 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_sort_merge_join_if_one_o
         varchar("name", [""; 0]),
         varchar("human", [""; 0]),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 
     // Right table has no rows but left table has rows
     let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
@@ -417,7 +419,7 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_sort_merge_join_if_one_o
         varchar("name", [""; 0]),
         varchar("human", [""; 0]),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 
     // Both tables have no rows
     let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
@@ -463,5 +465,229 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_sort_merge_join_if_one_o
         varchar("name", [""; 0]),
         varchar("human", [""; 0]),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_sort_merge_join_on_an_expression_key() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [1_i64, 2, 3, 4, 5], &alloc),
+        borrowed_varchar(
+            "name",
+            ["Chloe", "Margaret", "Prudence", "Lucy", "Pepper"],
+            &alloc,
+        ),
+    ]);
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let right = table([
+        borrowed_bigint("id", [2_i64, 3, 99, 5, 2, 8], &alloc),
+        borrowed_varchar(
+            "human",
+            ["Cassia", "Cassia", "Gretta", "Gretta", "Ian", "Erik"],
+            &alloc,
+        ),
+    ]);
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    // The same table with `id - 1` precomputed
+    let precomputed_right = table([
+        borrowed_bigint("id", [2_i64, 3, 99, 5, 2, 8], &alloc),
+        borrowed_bigint("id_minus_one", [1_i64, 2, 98, 4, 1, 7], &alloc),
+        borrowed_varchar(
+            "human",
+            ["Cassia", "Cassia", "Gretta", "Gretta", "Ian", "Erik"],
+            &alloc,
+        ),
+    ]);
+    let table_precomputed_right: TableRef = "sxt.precomputed_cat_details".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    accessor.add_table(table_precomputed_right.clone(), precomputed_right, 0);
+    let left_plan = table_exec(
+        table_left.clone(),
+        vec![
+            column_field("id", ColumnType::BigInt),
+            column_field("name", ColumnType::VarChar),
+        ],
+    );
+    let result_idents = vec![
+        Ident::new("key"),
+        Ident::new("name"),
+        Ident::new("id"),
+        Ident::new("human"),
+    ];
+
+    // a.id = b.id - 1
+    let ast = sort_merge_join_on_exprs(
+        left_plan.clone(),
+        table_exec(
+            table_right.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("human", ColumnType::VarChar),
+            ],
+        ),
+        vec![(
+            column(&table_left, "id", &accessor),
+            subtract(column(&table_right, "id", &accessor), const_bigint(1)),
+        )],
+        result_idents.clone(),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+
+    // a.id = b.id_minus_one
+    let precomputed_ast = sort_merge_join(
+        left_plan,
+        table_exec(
+            table_precomputed_right.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("id_minus_one", ColumnType::BigInt),
+                column_field("human", ColumnType::VarChar),
+            ],
+        ),
+        vec![0],
+        vec![1],
+        result_idents,
+    );
+    let precomputed_res =
+        VerifiableQueryResult::<InnerProductProof>::new(&precomputed_ast, &accessor, &(), &[])
+            .unwrap()
+            .verify(&precomputed_ast, &accessor, &(), &[])
+            .unwrap()
+            .table;
+
+    let expected_res = owned_table([
+        bigint("key", [1_i64, 1, 2, 4]),
+        varchar("name", ["Chloe", "Chloe", "Margaret", "Lucy"]),
+        bigint("id", [2_i64, 2, 3, 5]),
+        varchar("human", ["Cassia", "Ian", "Cassia", "Gretta"]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+    assert_tables_equal!(res, precomputed_res);
+}
+
+#[test]
+fn we_can_create_a_sort_merge_join_on_column_keys_without_projections() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(
+        table_left.clone(),
+        table([
+            borrowed_varchar("name", ["Chloe"], &alloc),
+            borrowed_bigint("id", [1_i64], &alloc),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        table_right.clone(),
+        table([borrowed_bigint("id", [1_i64], &alloc)]),
+        0,
+    );
+    let left_plan = table_exec(
+        table_left.clone(),
+        vec![
+            column_field("name", ColumnType::VarChar),
+            column_field("id", ColumnType::BigInt),
+        ],
+    );
+    let right_plan = table_exec(
+        table_right.clone(),
+        vec![column_field("id", ColumnType::BigInt)],
+    );
+    let result_idents = vec![Ident::new("id"), Ident::new("name")];
+    assert_eq!(
+        sort_merge_join_on_exprs(
+            left_plan.clone(),
+            right_plan.clone(),
+            vec![(
+                column(&table_left, "id", &accessor),
+                column(&table_right, "id", &accessor),
+            )],
+            result_idents.clone(),
+        ),
+        sort_merge_join(left_plan, right_plan, vec![1], vec![0], result_idents)
+    );
+}
+
+#[test]
+fn we_cannot_create_a_sort_merge_join_on_expression_keys_of_different_types() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(
+        table_left.clone(),
+        table([borrowed_bigint("id", [1_i64], &alloc)]),
+        0,
+    );
+    accessor.add_table(
+        table_right.clone(),
+        table([borrowed_varchar("human", ["Cassia"], &alloc)]),
+        0,
+    );
+    let res = SortMergeJoinExec::try_new_with_key_exprs(
+        Box::new(table_exec(
+            table_left.clone(),
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            table_right.clone(),
+            vec![column_field("human", ColumnType::VarChar)],
+        )),
+        vec![(
+            add(column(&table_left, "id", &accessor), const_bigint(1)),
+            column(&table_right, "human", &accessor),
+        )],
+        vec![Ident::new("key")],
+    );
+    assert!(matches!(res, Err(AnalyzeError::DataTypeMismatch { .. })));
+}
+
+#[test]
+fn we_cannot_create_a_sort_merge_join_whose_computed_key_shadows_an_input_column() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(
+        table_left.clone(),
+        table([borrowed_bigint("id", [1_i64], &alloc)]),
+        0,
+    );
+    accessor.add_table(
+        table_right.clone(),
+        table([borrowed_bigint("id", [2_i64], &alloc)]),
+        0,
+    );
+    let res = SortMergeJoinExec::try_new_with_key_exprs(
+        Box::new(table_exec(
+            table_left.clone(),
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        Box::new(table_exec(
+            table_right.clone(),
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        vec![(
+            column(&table_left, "id", &accessor),
+            subtract(column(&table_right, "id", &accessor), const_bigint(1)),
+        )],
+        vec![Ident::new("id"), Ident::new("id")],
+    );
+    assert_eq!(
+        res,
+        Err(AnalyzeError::DuplicateIdent {
+            ident: "id".to_string()
+        })
+    );
 }
@@ -0,0 +1,188 @@
+use super::{DynProofPlan, LeftAntiJoinExec, SortMergeJoinExec};
+use crate::{
+    base::database::{ColumnField, ColumnRef, ColumnType, LiteralValue, TableRef},
+    sql::{
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr},
+        AnalyzeError, AnalyzeResult,
+    },
+};
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use core::iter;
+use sqlparser::ast::Ident;
+
+/// Name of the column labelling each row of a table diff with its [`ChangeType`]
+pub const CHANGE_TYPE_COLUMN: &str = "change_type";
+
+/// The change of a key between two versions of a table, as labelled by a table diff
+///
+/// See [`DynProofPlan::try_new_table_diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeType {
+    /// The key only occurs in the later version
+    Inserted,
+    /// The key only occurs in the earlier version
+    Deleted,
+    /// The key occurs in both versions, with different values in some other column
+    Updated,
+}
+
+impl ChangeType {
+    /// The label of the change in the [`CHANGE_TYPE_COLUMN`] of a table diff
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            ChangeType::Inserted => "inserted",
+            ChangeType::Deleted => "deleted",
+            ChangeType::Updated => "updated",
+        }
+    }
+}
+
+impl DynProofPlan {
+    /// Creates a plan for the keys whose rows changed between two versions of a table.
+    ///
+    /// This is a template for change-data-capture queries of the form
+    /// ```ignore
+    ///     SELECT <key>, 'inserted' AS change_type FROM <after>
+    ///         WHERE NOT EXISTS (SELECT 1 FROM <before> WHERE <before>.<key> = <after>.<key>)
+    ///     UNION ALL
+    ///     SELECT <key>, 'deleted' AS change_type FROM <before>
+    ///         WHERE NOT EXISTS (SELECT 1 FROM <after> WHERE <after>.<key> = <before>.<key>)
+    ///     UNION ALL
+    ///     SELECT <key>, 'updated' AS change_type
+    ///         FROM <before> JOIN <after> ON <before>.<key> = <after>.<key>
+    ///         WHERE <before>.<col1> <> <after>.<col1> OR ... OR <before>.<colN> <> <after>.<colN>
+    /// ```
+    /// where both versions are committed tables with the columns of `schema`, keyed by the
+    /// column at `key_column_index`.
+    ///
+    /// The result has the key column, under its own name, followed by a `VARCHAR` column named
+    /// [`CHANGE_TYPE_COLUMN`] with the [`ChangeType::label`] of each changed key. Inserted keys
+    /// come first, in the order of `after`, then deleted keys in the order of `before` and finally
+    /// updated keys. Keys whose rows are equal in both versions have no row.
+    ///
+    /// The inserted and deleted keys are proven with anti-joins, which check the membership of
+    /// the keys of one version in the other, and the updated keys with an inner join on the key
+    /// and an equality check of every other column. Keys are expected to be unique in each
+    /// version. Otherwise each pair of rows with the same key is compared.
+    ///
+    /// # Errors
+    /// Returns [`AnalyzeError::InvalidTableDiff`] if `key_column_index` is out of bounds,
+    /// [`AnalyzeError::DuplicateIdent`] if the key column is named [`CHANGE_TYPE_COLUMN`] and
+    /// an error if the values of some column can't be compared.
+    pub fn try_new_table_diff(
+        before: TableRef,
+        after: TableRef,
+        schema: Vec<ColumnField>,
+        key_column_index: usize,
+    ) -> AnalyzeResult<Self> {
+        let key_field =
+            schema
+                .get(key_column_index)
+                .cloned()
+                .ok_or(AnalyzeError::InvalidTableDiff {
+                    reason: "the key column index is out of bounds",
+                })?;
+        let change_type_ident = Ident::new(CHANGE_TYPE_COLUMN);
+        if key_field.name() == change_type_ident {
+            return Err(AnalyzeError::DuplicateIdent {
+                ident: change_type_ident.value,
+            });
+        }
+        // The plans over the joins look columns up by name, so the table is only informational
+        let column = |column_id: Ident, column_type: ColumnType| {
+            DynProofExpr::new_column(ColumnRef::new(before.clone(), column_id, column_type))
+        };
+        let labelled_keys = |change_type: ChangeType| {
+            vec![
+                AliasedDynProofExpr {
+                    expr: column(key_field.name(), key_field.data_type()),
+                    alias: key_field.name(),
+                },
+                AliasedDynProofExpr {
+                    expr: DynProofExpr::new_literal(LiteralValue::VarChar(
+                        change_type.label().to_string(),
+                    )),
+                    alias: change_type_ident.clone(),
+                },
+            ]
+        };
+        let before_table = Self::new_table(before.clone(), schema.clone());
+        let after_table = Self::new_table(after, schema.clone());
+
+        // Keys of one version missing from the other
+        let inserted = Self::new_projection(
+            labelled_keys(ChangeType::Inserted),
+            Self::LeftAntiJoin(LeftAntiJoinExec::new(
+                Box::new(after_table.clone()),
+                Box::new(before_table.clone()),
+                vec![key_column_index],
+                vec![key_column_index],
+            )),
+        );
+        let deleted = Self::new_projection(
+            labelled_keys(ChangeType::Deleted),
+            Self::LeftAntiJoin(LeftAntiJoinExec::new(
+                Box::new(before_table.clone()),
+                Box::new(after_table.clone()),
+                vec![key_column_index],
+                vec![key_column_index],
+            )),
+        );
+
+        // Keys of both versions with a differing value
+        let value_fields = schema
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| index != key_column_index)
+            .map(|(_, field)| field)
+            .collect::<Vec<_>>();
+        let versioned_ident = |version: &str, field: &ColumnField| {
+            Ident::new(format!("__{version}_{}__", field.name().value))
+        };
+        let joined = SortMergeJoinExec::new(
+            Box::new(before_table),
+            Box::new(after_table),
+            vec![key_column_index],
+            vec![key_column_index],
+            iter::once(key_field.name())
+                .chain(
+                    value_fields
+                        .iter()
+                        .map(|field| versioned_ident("before", field)),
+                )
+                .chain(
+                    value_fields
+                        .iter()
+                        .map(|field| versioned_ident("after", field)),
+                )
+                .collect(),
+        );
+        let is_changed = value_fields
+            .iter()
+            .try_fold(None, |is_changed, field| {
+                let differs = DynProofExpr::try_new_not(DynProofExpr::try_new_equals(
+                    column(versioned_ident("before", field), field.data_type()),
+                    column(versioned_ident("after", field), field.data_type()),
+                )?)?;
+                Ok::<_, AnalyzeError>(Some(match is_changed {
+                    Some(is_changed) => DynProofExpr::try_new_or(is_changed, differs)?,
+                    None => differs,
+                }))
+            })?
+            .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(false)));
+        let updated = Self::new_generalized_filter(
+            labelled_keys(ChangeType::Updated),
+            Self::SortMergeJoin(joined),
+            is_changed,
+        );
+
+        Ok(Self::new_union(
+            vec![inserted, deleted, updated],
+            vec![
+                key_field,
+                ColumnField::new(change_type_ident, ColumnType::VarChar),
+            ],
+        ))
+    }
+}
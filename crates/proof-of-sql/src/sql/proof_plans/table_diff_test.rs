@@ -0,0 +1,200 @@
+use super::{ChangeType, DynProofPlan, CHANGE_TYPE_COLUMN};
+use crate::{
+    assert_tables_equal,
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+    },
+    proof_primitive::inner_product::curve_25519_scalar::Curve25519Scalar,
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        AnalyzeError,
+    },
+};
+
+fn accounts_schema() -> Vec<ColumnField> {
+    vec![
+        ColumnField::new("id".into(), ColumnType::BigInt),
+        ColumnField::new("balance".into(), ColumnType::BigInt),
+        ColumnField::new("owner".into(), ColumnType::VarChar),
+    ]
+}
+
+/// Proves the diff between the `before` and `after` versions of a table, returning the verified
+/// result
+fn verify_table_diff(
+    before: OwnedTable<Curve25519Scalar>,
+    after: OwnedTable<Curve25519Scalar>,
+    schema: Vec<ColumnField>,
+    key_column_index: usize,
+) -> OwnedTable<Curve25519Scalar> {
+    let before_ref = TableRef::new("sxt", "accounts_v1");
+    let after_ref = TableRef::new("sxt", "accounts_v2");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(before_ref.clone(), before, 0);
+    accessor.add_table(after_ref.clone(), after, 3);
+    let plan =
+        DynProofPlan::try_new_table_diff(before_ref.clone(), after_ref, schema, key_column_index)
+            .unwrap();
+    let res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&res, &plan, &accessor, &before_ref);
+    res.verify(&plan, &accessor, &(), &[]).unwrap().table
+}
+
+#[test]
+fn we_can_prove_the_rows_changed_between_two_versions_of_a_table() {
+    let before = owned_table([
+        bigint("id", [1_i64, 2, 3, 4]),
+        bigint("balance", [100_i64, 200, 300, 400]),
+        varchar("owner", ["Ann", "Bob", "Cat", "Dan"]),
+    ]);
+    // 1 is deleted, 2 is unchanged, 3 and 4 are updated and 5 is inserted
+    let after = owned_table([
+        bigint("id", [2_i64, 3, 5, 4]),
+        bigint("balance", [200_i64, 350, 500, 400]),
+        varchar("owner", ["Bob", "Cat", "Eve", "Dave"]),
+    ]);
+    let res = verify_table_diff(before, after, accounts_schema(), 0);
+    let expected_res = owned_table([
+        bigint("id", [5_i64, 1, 3, 4]),
+        varchar(
+            CHANGE_TYPE_COLUMN,
+            ["inserted", "deleted", "updated", "updated"],
+        ),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_the_diff_of_a_table_keyed_by_a_column_other_than_the_first() {
+    let schema = vec![
+        ColumnField::new("balance".into(), ColumnType::BigInt),
+        ColumnField::new("owner".into(), ColumnType::VarChar),
+        ColumnField::new("id".into(), ColumnType::BigInt),
+    ];
+    let before = owned_table([
+        bigint("balance", [100_i64, 200, 300]),
+        varchar("owner", ["Ann", "Bob", "Cat"]),
+        bigint("id", [10_i64, 20, 30]),
+    ]);
+    let after = owned_table([
+        bigint("balance", [300_i64, 100, 250]),
+        varchar("owner", ["Cat", "Ann", "Bob"]),
+        bigint("id", [30_i64, 10, 20]),
+    ]);
+    let res = verify_table_diff(before, after, schema, 2);
+    let expected_res = owned_table([
+        bigint("id", [20_i64]),
+        varchar(CHANGE_TYPE_COLUMN, [ChangeType::Updated.label()]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_that_equal_versions_of_a_table_have_no_diff() {
+    let table = owned_table([
+        bigint("id", [1_i64, 2, 3]),
+        bigint("balance", [100_i64, 200, 300]),
+        varchar("owner", ["Ann", "Bob", "Cat"]),
+    ]);
+    // Rows in a different order are not a change
+    let reordered = owned_table([
+        bigint("id", [3_i64, 1, 2]),
+        bigint("balance", [300_i64, 100, 200]),
+        varchar("owner", ["Cat", "Ann", "Bob"]),
+    ]);
+    let res = verify_table_diff(table, reordered, accounts_schema(), 0);
+    let expected_res = owned_table([
+        bigint("id", [0_i64; 0]),
+        varchar(CHANGE_TYPE_COLUMN, [""; 0]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_the_diff_against_an_empty_version_of_a_table() {
+    let table = owned_table([
+        bigint("id", [1_i64, 2]),
+        bigint("balance", [100_i64, 200]),
+        varchar("owner", ["Ann", "Bob"]),
+    ]);
+    let empty = owned_table([
+        bigint("id", [0_i64; 0]),
+        bigint("balance", [0_i64; 0]),
+        varchar("owner", [""; 0]),
+    ]);
+    let res = verify_table_diff(empty.clone(), table.clone(), accounts_schema(), 0);
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 2]),
+        varchar(CHANGE_TYPE_COLUMN, ["inserted", "inserted"]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+    let res = verify_table_diff(table, empty, accounts_schema(), 0);
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 2]),
+        varchar(CHANGE_TYPE_COLUMN, ["deleted", "deleted"]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_the_diff_of_a_table_with_only_a_key_column() {
+    let schema = vec![ColumnField::new("id".into(), ColumnType::BigInt)];
+    let before = owned_table([bigint("id", [1_i64, 2, 3])]);
+    let after = owned_table([bigint("id", [2_i64, 4, 3])]);
+    let res = verify_table_diff(before, after, schema, 0);
+    let expected_res = owned_table([
+        bigint("id", [4_i64, 1]),
+        varchar(CHANGE_TYPE_COLUMN, ["inserted", "deleted"]),
+    ]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_get_the_result_fields_of_a_table_diff() {
+    let before_ref = TableRef::new("sxt", "accounts_v1");
+    let after_ref = TableRef::new("sxt", "accounts_v2");
+    let plan = DynProofPlan::try_new_table_diff(
+        before_ref.clone(),
+        after_ref.clone(),
+        accounts_schema(),
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        plan.get_column_result_fields(),
+        vec![
+            ColumnField::new("id".into(), ColumnType::BigInt),
+            ColumnField::new(CHANGE_TYPE_COLUMN.into(), ColumnType::VarChar),
+        ]
+    );
+    assert_eq!(plan.get_table_references().len(), 2);
+    assert!(plan.get_table_references().contains(&before_ref));
+    assert!(plan.get_table_references().contains(&after_ref));
+}
+
+#[test]
+fn we_cannot_diff_a_table_with_an_invalid_key_column() {
+    let before_ref = TableRef::new("sxt", "accounts_v1");
+    let after_ref = TableRef::new("sxt", "accounts_v2");
+    assert!(matches!(
+        DynProofPlan::try_new_table_diff(
+            before_ref.clone(),
+            after_ref.clone(),
+            accounts_schema(),
+            3
+        ),
+        Err(AnalyzeError::InvalidTableDiff { .. })
+    ));
+    let schema = vec![
+        ColumnField::new(CHANGE_TYPE_COLUMN.into(), ColumnType::BigInt),
+        ColumnField::new("balance".into(), ColumnType::BigInt),
+    ];
+    assert!(matches!(
+        DynProofPlan::try_new_table_diff(before_ref, after_ref, schema, 0),
+        Err(AnalyzeError::DuplicateIdent { ident }) if ident == CHANGE_TYPE_COLUMN
+    ));
+}
@@ -1,5 +1,6 @@
 use super::test_utility::*;
 use crate::{
+    assert_tables_equal,
     base::database::{
         owned_table_utility::*, table_utility::*, ColumnField, ColumnType, TableRef,
         TableTestAccessor,
@@ -30,7 +31,7 @@ fn we_can_create_and_prove_an_empty_table_exec() {
         .unwrap()
         .table;
     let expected = owned_table([bigint("a", [0_i64; 0])]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
 
 #[test]
@@ -90,5 +91,5 @@ fn we_can_create_and_prove_a_table_exec() {
             ],
         ),
     ]);
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
 }
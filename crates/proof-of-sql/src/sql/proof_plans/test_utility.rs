@@ -1,9 +1,11 @@
 use super::{
-    DynProofPlan, EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, SortMergeJoinExec,
-    TableExec, UnionExec,
+    BucketCountExec, ContainmentCheckExec, DynProofPlan, EmptyExec, FilterExec, FirstPerGroupExec,
+    GeneralizedFilterExec, GroupByExec, GroupingSetsExec, LeftAntiJoinExec, MonotonicCheckExec,
+    MultiCountExec, PercentageOfTotalExec, PermutationCheckExec, ProjectionExec, SliceExec,
+    SortMergeJoinExec, TableExec, UnionExec,
 };
 use crate::{
-    base::database::{ColumnField, ColumnType, TableRef},
+    base::database::{ColumnField, ColumnType, LiteralValue, TableRef},
     sql::proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
 };
 use sqlparser::ast::Ident;
@@ -32,6 +34,18 @@ pub fn filter(
     DynProofPlan::Filter(FilterExec::new(results, table, where_clause))
 }
 
+pub fn generalized_filter(
+    results: Vec<AliasedDynProofExpr>,
+    input: DynProofPlan,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    DynProofPlan::GeneralizedFilter(GeneralizedFilterExec::new(
+        results,
+        Box::new(input),
+        where_clause,
+    ))
+}
+
 /// # Panics
 ///
 /// Will panic if `count_alias` cannot be parsed as a valid identifier.
@@ -51,6 +65,71 @@ pub fn group_by(
     ))
 }
 
+pub fn group_by_with_count_distinct(
+    group_by_exprs: Vec<ColumnExpr>,
+    sum_expr: Vec<AliasedDynProofExpr>,
+    count_alias: &str,
+    count_distinct_exprs: Vec<(ColumnExpr, &str)>,
+    table: TableExpr,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    DynProofPlan::GroupBy(GroupByExec::new_with_count_distinct(
+        group_by_exprs,
+        sum_expr,
+        count_alias.into(),
+        count_distinct_exprs
+            .into_iter()
+            .map(|(expr, alias)| (expr, alias.into()))
+            .collect(),
+        table,
+        where_clause,
+    ))
+}
+
+pub fn group_by_with_filtered_count_distinct(
+    group_by_exprs: Vec<ColumnExpr>,
+    sum_expr: Vec<AliasedDynProofExpr>,
+    count_alias: &str,
+    count_distinct_exprs: Vec<(ColumnExpr, &str, Option<DynProofExpr>)>,
+    table: TableExpr,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    let (count_distinct_exprs, count_distinct_filters) = count_distinct_exprs
+        .into_iter()
+        .map(|(expr, alias, filter)| ((expr, alias.into()), filter))
+        .unzip();
+    DynProofPlan::GroupBy(GroupByExec::new_with_filtered_count_distinct(
+        group_by_exprs,
+        sum_expr,
+        count_alias.into(),
+        count_distinct_exprs,
+        count_distinct_filters,
+        table,
+        where_clause,
+    ))
+}
+
+pub fn grouping_sets(
+    grouping_sets: Vec<Vec<ColumnExpr>>,
+    sum_expr: Vec<AliasedDynProofExpr>,
+    count_alias: &str,
+    table: TableExpr,
+    where_clause: DynProofExpr,
+    grouping_id_alias: &str,
+) -> DynProofPlan {
+    DynProofPlan::GroupingSets(
+        GroupingSetsExec::try_new(
+            grouping_sets,
+            sum_expr,
+            count_alias.into(),
+            table,
+            where_clause,
+            grouping_id_alias.into(),
+        )
+        .unwrap(),
+    )
+}
+
 pub fn slice_exec(input: DynProofPlan, skip: usize, fetch: Option<usize>) -> DynProofPlan {
     DynProofPlan::Slice(SliceExec::new(Box::new(input), skip, fetch))
 }
@@ -74,3 +153,123 @@ pub fn sort_merge_join(
         result_idents,
     ))
 }
+
+pub fn sort_merge_join_on_exprs(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    key_exprs: Vec<(DynProofExpr, DynProofExpr)>,
+    result_idents: Vec<Ident>,
+) -> DynProofPlan {
+    DynProofPlan::SortMergeJoin(
+        SortMergeJoinExec::try_new_with_key_exprs(
+            Box::new(left),
+            Box::new(right),
+            key_exprs,
+            result_idents,
+        )
+        .unwrap(),
+    )
+}
+
+pub fn left_anti_join(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    left_join_column_indexes: Vec<usize>,
+    right_join_column_indexes: Vec<usize>,
+) -> DynProofPlan {
+    DynProofPlan::LeftAntiJoin(LeftAntiJoinExec::new(
+        Box::new(left),
+        Box::new(right),
+        left_join_column_indexes,
+        right_join_column_indexes,
+    ))
+}
+
+pub fn containment_check(
+    child: DynProofPlan,
+    parent: DynProofPlan,
+    child_column_index: usize,
+    parent_column_index: usize,
+    alias: &str,
+) -> DynProofPlan {
+    DynProofPlan::ContainmentCheck(ContainmentCheckExec::new(
+        Box::new(child),
+        Box::new(parent),
+        child_column_index,
+        parent_column_index,
+        alias.into(),
+    ))
+}
+
+pub fn monotonic_check(input: DynProofPlan, column_index: usize, alias: &str) -> DynProofPlan {
+    DynProofPlan::MonotonicCheck(MonotonicCheckExec::new(
+        Box::new(input),
+        column_index,
+        alias.into(),
+    ))
+}
+
+pub fn permutation_check(
+    source: DynProofPlan,
+    candidate: DynProofPlan,
+    source_column_index: usize,
+    candidate_column_index: usize,
+    alias: &str,
+) -> DynProofPlan {
+    DynProofPlan::PermutationCheck(PermutationCheckExec::new(
+        Box::new(source),
+        Box::new(candidate),
+        source_column_index,
+        candidate_column_index,
+        alias.into(),
+    ))
+}
+
+pub fn multi_count(predicates: Vec<AliasedDynProofExpr>, table: TableExpr) -> DynProofPlan {
+    DynProofPlan::MultiCount(MultiCountExec::try_new(predicates, table).unwrap())
+}
+
+pub fn bucket_count(
+    expr: DynProofExpr,
+    boundaries: Vec<LiteralValue>,
+    aliases: &[&str],
+    table: TableExpr,
+) -> DynProofPlan {
+    DynProofPlan::BucketCount(
+        BucketCountExec::try_new(
+            expr,
+            boundaries,
+            aliases.iter().map(|alias| (*alias).into()).collect(),
+            table,
+        )
+        .unwrap(),
+    )
+}
+
+pub fn percentage_of_total(
+    results: Vec<AliasedDynProofExpr>,
+    amount: AliasedDynProofExpr,
+    scale: i8,
+    input: DynProofPlan,
+) -> DynProofPlan {
+    DynProofPlan::PercentageOfTotal(
+        PercentageOfTotalExec::try_new(results, amount, scale, Box::new(input)).unwrap(),
+    )
+}
+
+pub fn first_per_group(
+    input: DynProofPlan,
+    key_column_indexes: Vec<usize>,
+    tiebreaker_column_index: usize,
+    tiebreaker_descending: bool,
+) -> DynProofPlan {
+    DynProofPlan::FirstPerGroup(
+        FirstPerGroupExec::try_new(
+            Box::new(input),
+            key_column_indexes,
+            tiebreaker_column_index,
+            tiebreaker_descending,
+        )
+        .unwrap(),
+    )
+}
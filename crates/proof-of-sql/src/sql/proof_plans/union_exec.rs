@@ -42,6 +42,16 @@ impl UnionExec {
     pub fn new(inputs: Vec<DynProofPlan>, schema: Vec<ColumnField>) -> Self {
         Self { inputs, schema }
     }
+
+    /// Get the input plans
+    pub fn inputs(&self) -> &[DynProofPlan] {
+        &self.inputs
+    }
+
+    /// Get the schema of the union
+    pub fn schema(&self) -> &[ColumnField] {
+        &self.schema
+    }
 }
 
 impl ProofPlan for UnionExec
@@ -176,7 +186,7 @@ impl ProverEvaluate for UnionExec {
 ///
 /// # Panics
 /// Should never panic if the code is correct.
-fn verify_union<S: Scalar>(
+pub(super) fn verify_union<S: Scalar>(
     builder: &mut impl VerificationBuilder<S>,
     gamma: S,
     beta: S,
@@ -230,7 +240,7 @@ fn verify_union<S: Scalar>(
 /// # Panics
 /// Should never panic if the code is correct.
 #[expect(clippy::too_many_arguments)]
-fn prove_union<'a, S: Scalar + 'a>(
+pub(super) fn prove_union<'a, S: Scalar + 'a>(
     builder: &mut FinalRoundBuilder<'a, S>,
     alloc: &'a Bump,
     gamma: S,
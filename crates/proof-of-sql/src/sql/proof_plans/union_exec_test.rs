@@ -1,5 +1,6 @@
-use super::test_utility::*;
+use super::{test_utility::*, DynProofPlan};
 use crate::{
+    assert_tables_equal,
     base::{
         database::{
             owned_table_utility::*, table_utility::*, ColumnType, OwnedTable,
@@ -36,7 +37,7 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_union_with_no_tables() {
         .unwrap()
         .table;
     let expected_res = owned_table([bigint("a", [0_i64; 0]), varchar("b", [""; 0])]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -63,7 +64,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_union_with_one_table() {
         .unwrap()
         .table;
     let expected_res = owned_table([bigint("a", [2_i64, 3, 4])]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -95,7 +96,7 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_union_exec() {
         .unwrap()
         .table;
     let expected_res = owned_table([bigint("a", [0_i64; 0])]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -149,7 +150,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_union_exec() {
         bigint("a", [1_i64, 2, 3, 4, 5, 2, 3, 4, 5, 6]),
         varchar("b", ["1", "2", "3", "4", "5", "2", "3", "4", "5", "6"]),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[expect(clippy::too_many_lines)]
@@ -295,7 +296,7 @@ fn we_can_prove_and_get_the_correct_result_from_a_more_complex_union_exec() {
             ],
         ),
     ]);
-    assert_eq!(res, expected_res);
+    assert_tables_equal!(res, expected_res);
 }
 
 #[test]
@@ -367,5 +368,73 @@ fn we_can_get_result_from_union_using_first_round_evaluate() {
         varchar("b", ["1", "2", "3", "4", "5", "2", "3", "4", "5", "6"]),
     ]);
 
-    assert_eq!(res, expected);
+    assert_tables_equal!(res, expected);
+}
+
+/// The column references of this plan are `t0.a0, t1.a1, t0.b0`, so the references to `t0` are not adjacent.
+fn union_with_interleaved_column_references(t0: &TableRef, t1: &TableRef) -> DynProofPlan {
+    union_exec(
+        vec![
+            table_exec(t0.clone(), vec![column_field("a0", ColumnType::BigInt)]),
+            table_exec(t1.clone(), vec![column_field("a1", ColumnType::BigInt)]),
+            table_exec(t0.clone(), vec![column_field("b0", ColumnType::BigInt)]),
+        ],
+        vec![column_field("a", ColumnType::BigInt)],
+    )
+}
+
+#[test]
+fn we_can_prove_a_union_whose_column_references_interleave_tables() {
+    let t0 = TableRef::new("sxt", "t0");
+    let t1 = TableRef::new("sxt", "t1");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t0.clone(),
+        owned_table([bigint("a0", [1_i64, 2]), bigint("b0", [5_i64, 6])]),
+        0,
+    );
+    accessor.add_table(t1.clone(), owned_table([bigint("a1", [3_i64, 4, 7])]), 0);
+    let ast = union_with_interleaved_column_references(&t0, &t1);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &ast, &accessor, &t0);
+    let res = verifiable_res
+        .verify(&ast, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [1_i64, 2, 3, 4, 7, 5, 6])]);
+    assert_tables_equal!(res, expected_res);
+}
+
+#[test]
+fn we_can_verify_a_union_with_an_accessor_built_in_a_different_order() {
+    let t0 = TableRef::new("sxt", "t0");
+    let t1 = TableRef::new("sxt", "t1");
+    let mut prover_accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    prover_accessor.add_table(
+        t0.clone(),
+        owned_table([bigint("a0", [1_i64, 2]), bigint("b0", [5_i64, 6])]),
+        0,
+    );
+    prover_accessor.add_table(t1.clone(), owned_table([bigint("a1", [3_i64, 4, 7])]), 0);
+
+    // Same tables, inserted in the opposite order and with their columns shuffled
+    let mut verifier_accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    verifier_accessor.add_table(t1.clone(), owned_table([bigint("a1", [3_i64, 4, 7])]), 0);
+    verifier_accessor.add_table(
+        t0.clone(),
+        owned_table([bigint("b0", [5_i64, 6]), bigint("a0", [1_i64, 2])]),
+        0,
+    );
+
+    let ast = union_with_interleaved_column_references(&t0, &t1);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &prover_accessor, &(), &[]).unwrap();
+    let res = verifiable_res
+        .verify(&ast, &verifier_accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([bigint("a", [1_i64, 2, 3, 4, 7, 5, 6])]);
+    assert_tables_equal!(res, expected_res);
 }
@@ -0,0 +1,147 @@
+use crate::{
+    base::{
+        commitment::naive_evaluation_proof::NaiveEvaluationProof,
+        database::{Column, LiteralValue, Table, TableRef, TableTestAccessor},
+        map::{indexmap, IndexMap},
+        scalar::{test_scalar::TestScalar, Scalar},
+    },
+    sql::{
+        proof::{ProofPlan, SumcheckMleEvaluations, VerificationBuilderImpl, VerificationOptions},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use core::cell::Cell;
+use sqlparser::ast::Ident;
+use std::alloc::{GlobalAlloc, Layout, System};
+
+/// The system allocator, counting the bytes allocated by each thread
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation(size: usize) {
+    // The counter is unavailable while the thread is torn down, when nothing is measured anyway
+    let _ = ALLOCATED_BYTES.try_with(|bytes| bytes.set(bytes.get() + size));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f`, returning its result and the number of bytes it allocated on this thread
+fn allocated_bytes<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED_BYTES.with(Cell::get);
+    let result = f();
+    (result, ALLOCATED_BYTES.with(Cell::get) - before)
+}
+
+/// Returns the evaluations of `column_count` columns of `table_ref`, named `c0`, `c1`, ...
+fn column_evals(
+    table_ref: &TableRef,
+    column_count: usize,
+) -> IndexMap<TableRef, IndexMap<Ident, TestScalar>> {
+    indexmap! {
+        table_ref.clone() => (0..column_count)
+            .map(|i| (Ident::new(format!("c{i}")), TestScalar::ONE))
+            .collect()
+    }
+}
+
+/// Returns the bytes allocated by the verifier of `plan` over the table `table_ref`, given the
+/// column evaluations in `accessor`
+fn verifier_allocated_bytes(
+    plan: &DynProofPlan,
+    table_ref: &TableRef,
+    accessor: &IndexMap<TableRef, IndexMap<Ident, TestScalar>>,
+) -> usize {
+    let ones = [TestScalar::ONE; 16];
+    let mut builder = VerificationBuilderImpl::new(
+        SumcheckMleEvaluations {
+            chi_evaluations: indexmap! {3 => TestScalar::ONE},
+            final_round_pcs_proof_evaluations: &ones,
+            ..Default::default()
+        },
+        &[],
+        &ones,
+        [TestScalar::ONE, TestScalar::ONE].into(),
+        vec![3],
+        Vec::new(),
+        4,
+        indexmap! {table_ref.clone() => 4},
+        VerificationOptions::default(),
+    );
+    let chi_eval_map = indexmap! {table_ref.clone() => TestScalar::ONE};
+    let (result, bytes) = allocated_bytes(|| {
+        plan.verifier_evaluate(&mut builder, accessor, None, &chi_eval_map, &[])
+    });
+    result.unwrap();
+    bytes
+}
+
+#[test]
+fn we_can_verify_a_filter_without_allocating_per_accessor_column() {
+    const COLUMN_VALUES: [i64; 4] = [1, 2, 3, 4];
+    let t = TableRef::new("sxt", "t");
+    let accessor = TableTestAccessor::<NaiveEvaluationProof>::new_from_table(
+        t.clone(),
+        Table::<TestScalar>::try_from_iter(
+            (0..50).map(|i| (Ident::new(format!("c{i}")), Column::BigInt(&COLUMN_VALUES))),
+        )
+        .unwrap(),
+        0,
+        (),
+    );
+    let plan = filter(
+        cols_expr_plan(&t, &["c0", "c1"], &accessor),
+        tab(&t),
+        equal(column(&t, "c1", &accessor), const_bigint(2)),
+    );
+    let wide_evals = column_evals(&t, 50);
+
+    // Cloning the evaluations of a 50-column table allocates, but borrowing them doesn't
+    assert!(allocated_bytes(|| wide_evals[&t].clone()).1 > 0);
+    assert_eq!(
+        verifier_allocated_bytes(&plan, &t, &wide_evals),
+        verifier_allocated_bytes(&plan, &t, &column_evals(&t, 2))
+    );
+}
+
+#[test]
+fn we_can_verify_a_filter_over_a_table_without_evaluations_without_allocating_them() {
+    let t = TableRef::new("sxt", "t");
+    let plan = filter(
+        vec![aliased_plan(
+            DynProofExpr::new_literal(LiteralValue::BigInt(1)),
+            "one",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    assert_eq!(
+        verifier_allocated_bytes(&plan, &t, &IndexMap::default()),
+        verifier_allocated_bytes(&plan, &t, &column_evals(&t, 50))
+    );
+}
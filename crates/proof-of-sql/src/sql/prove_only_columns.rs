@@ -0,0 +1,135 @@
+use crate::{
+    base::{
+        database::{ColumnRef, TableRef},
+        map::IndexSet,
+    },
+    sql::{
+        proof_exprs::{ColumnExpr, DynProofExpr, ProofExpr},
+        proof_plans::DynProofPlan,
+    },
+};
+use alloc::vec::Vec;
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+
+/// Errors returned when a plan could reveal a prove-only column.
+#[derive(Snafu, Debug, PartialEq, Eq)]
+pub enum ProveOnlyColumnsError {
+    #[snafu(display(
+        "Prove-only column {column_id} of {table_ref} could be revealed by the plan"
+    ))]
+    /// The plan selects, filters or groups on a prove-only column
+    ColumnRevealed {
+        /// The table of the column
+        table_ref: TableRef,
+        /// The prove-only column
+        column_id: Ident,
+    },
+}
+
+/// Columns which may only be used inside the sums of an aggregation.
+///
+/// A prove-only column is committed to and contributes to the proven sums, but its values never
+/// appear in the result, so they are never materialized in the `QueryData` sent to the verifier.
+/// Selecting, filtering or grouping on a prove-only column is rejected, as is any table scan
+/// which outputs it, even if a later plan drops the column.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProveOnlyColumns {
+    columns: IndexSet<(TableRef, Ident)>,
+}
+
+impl ProveOnlyColumns {
+    /// Creates the set of prove-only columns from pairs of tables and columns
+    #[must_use]
+    pub fn new(columns: impl IntoIterator<Item = (TableRef, Ident)>) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+        }
+    }
+
+    /// Whether the column of the table is prove-only
+    #[must_use]
+    pub fn contains(&self, table_ref: &TableRef, column_id: &Ident) -> bool {
+        self.columns
+            .contains(&(table_ref.clone(), column_id.clone()))
+    }
+
+    /// Check that a plan uses prove-only columns only inside the sums of an aggregation.
+    ///
+    /// The plan is walked with an explicit stack, so this does not recurse regardless of the plan's depth.
+    pub fn check_plan(&self, plan: &DynProofPlan) -> Result<(), ProveOnlyColumnsError> {
+        let mut stack = Vec::from([plan]);
+        while let Some(plan) = stack.pop() {
+            stack.extend(plan.inputs());
+            match plan {
+                DynProofPlan::Table(table_exec) => {
+                    for field in table_exec.schema() {
+                        self.check_column(table_exec.table_ref(), &field.name())?;
+                    }
+                }
+                // The sums are the only expressions which are allowed to use prove-only columns
+                DynProofPlan::GroupBy(group_by_exec) => {
+                    self.check_column_exprs(
+                        group_by_exec
+                            .count_distinct_exprs()
+                            .iter()
+                            .map(|(column_expr, _)| column_expr),
+                    )?;
+                    for aliased_expr in group_by_exec.group_by_exprs() {
+                        self.check_expr(&aliased_expr.expr)?;
+                    }
+                    for filter in group_by_exec.count_distinct_filters().iter().flatten() {
+                        self.check_expr(filter)?;
+                    }
+                    self.check_expr(group_by_exec.where_clause())?;
+                }
+                DynProofPlan::GroupingSets(grouping_sets_exec) => {
+                    self.check_column_exprs(grouping_sets_exec.grouping_sets().iter().flatten())?;
+                    self.check_expr(grouping_sets_exec.where_clause())?;
+                }
+                _ => {
+                    for expr in plan.exprs() {
+                        self.check_expr(expr)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_column(
+        &self,
+        table_ref: &TableRef,
+        column_id: &Ident,
+    ) -> Result<(), ProveOnlyColumnsError> {
+        if self.contains(table_ref, column_id) {
+            Err(ProveOnlyColumnsError::ColumnRevealed {
+                table_ref: table_ref.clone(),
+                column_id: column_id.clone(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_column_ref(&self, column_ref: &ColumnRef) -> Result<(), ProveOnlyColumnsError> {
+        self.check_column(&column_ref.table_ref(), &column_ref.column_id())
+    }
+
+    fn check_column_exprs<'a>(
+        &self,
+        column_exprs: impl IntoIterator<Item = &'a ColumnExpr>,
+    ) -> Result<(), ProveOnlyColumnsError> {
+        column_exprs
+            .into_iter()
+            .try_for_each(|column_expr| self.check_column_ref(column_expr.column_ref()))
+    }
+
+    fn check_expr(&self, expr: &DynProofExpr) -> Result<(), ProveOnlyColumnsError> {
+        let mut columns = IndexSet::default();
+        expr.get_column_references(&mut columns);
+        columns
+            .iter()
+            .try_for_each(|column_ref| self.check_column_ref(column_ref))
+    }
+}
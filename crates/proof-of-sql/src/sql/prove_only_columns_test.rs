@@ -0,0 +1,149 @@
+use super::{ProveOnlyColumns, ProveOnlyColumnsError};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef},
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+        proof_plans::test_utility::*,
+    },
+};
+use sqlparser::ast::Ident;
+
+fn accessor(t: &TableRef) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            varchar("dept", ["eng", "ops", "eng", "eng", "ops"]),
+            bigint("salary", [100, 80, 120, 90, 70]),
+            bigint("level", [2, 1, 3, 2, 1]),
+        ]),
+        0,
+        (),
+    )
+}
+
+fn prove_only_salary(t: &TableRef) -> ProveOnlyColumns {
+    ProveOnlyColumns::new([(t.clone(), Ident::new("salary"))])
+}
+
+/// `select dept, sum(salary) as total, count(*) as __count__ from sxt.employees where level >= 2 group by dept`
+#[test]
+fn we_can_prove_a_sum_of_a_prove_only_column_without_revealing_it() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = accessor(&t);
+    let plan = group_by(
+        cols_expr(&t, &["dept"], &accessor),
+        vec![sum_expr(column(&t, "salary", &accessor), "total")],
+        "__count__",
+        tab(&t),
+        gte(column(&t, "level", &accessor), const_bigint(2)),
+    );
+    assert_eq!(prove_only_salary(&t).check_plan(&plan), Ok(()));
+
+    let verifiable_res = VerifiableQueryResult::new(&plan, &accessor, &(), &[]).unwrap();
+    exercise_verification(&verifiable_res, &plan, &accessor, &t);
+    // Only the aggregates are materialized in what is sent to the verifier
+    let sent_columns: Vec<_> = verifiable_res.result.column_names().cloned().collect();
+    assert_eq!(
+        sent_columns,
+        [
+            Ident::new("dept"),
+            Ident::new("total"),
+            Ident::new("__count__")
+        ]
+    );
+    let res = verifiable_res
+        .verify(&plan, &accessor, &(), &[])
+        .unwrap()
+        .table;
+    let expected_res = owned_table([
+        varchar("dept", ["eng"]),
+        bigint("total", [310]),
+        bigint("__count__", [3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_check_plans_which_do_not_reference_a_prove_only_column() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = accessor(&t);
+    let plan = filter(
+        cols_expr_plan(&t, &["dept", "level"], &accessor),
+        tab(&t),
+        gte(column(&t, "level", &accessor), const_bigint(2)),
+    );
+    assert_eq!(prove_only_salary(&t).check_plan(&plan), Ok(()));
+    // Prove-only columns of other tables don't matter
+    let other = TableRef::new("sxt", "other");
+    let plan = filter(
+        cols_expr_plan(&t, &["salary"], &accessor),
+        tab(&t),
+        const_bool(true),
+    );
+    assert_eq!(prove_only_salary(&other).check_plan(&plan), Ok(()));
+}
+
+#[test]
+fn we_cannot_reveal_a_prove_only_column() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = accessor(&t);
+    let revealed = Err(ProveOnlyColumnsError::ColumnRevealed {
+        table_ref: t.clone(),
+        column_id: Ident::new("salary"),
+    });
+    let prove_only_columns = prove_only_salary(&t);
+
+    // Selecting the column, even within an expression
+    let plan = filter(
+        vec![aliased_plan(
+            multiply(column(&t, "salary", &accessor), const_bigint(2)),
+            "double_salary",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    assert_eq!(prove_only_columns.check_plan(&plan), revealed);
+
+    // Filtering on the column
+    let plan = filter(
+        cols_expr_plan(&t, &["dept"], &accessor),
+        tab(&t),
+        gte(column(&t, "salary", &accessor), const_bigint(85)),
+    );
+    assert_eq!(prove_only_columns.check_plan(&plan), revealed);
+
+    // Grouping on the column or filtering the rows of an aggregation on it
+    let plan = group_by(
+        cols_expr(&t, &["salary"], &accessor),
+        vec![],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    assert_eq!(prove_only_columns.check_plan(&plan), revealed);
+    let plan = group_by(
+        cols_expr(&t, &["dept"], &accessor),
+        vec![sum_expr(column(&t, "level", &accessor), "total_level")],
+        "__count__",
+        tab(&t),
+        gte(column(&t, "salary", &accessor), const_bigint(85)),
+    );
+    assert_eq!(prove_only_columns.check_plan(&plan), revealed);
+
+    // Scanning the column, even if it is later dropped
+    let plan = projection(
+        cols_expr_plan(&t, &["dept"], &accessor),
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("dept", ColumnType::VarChar),
+                column_field("salary", ColumnType::BigInt),
+            ],
+        ),
+    );
+    assert_eq!(prove_only_columns.check_plan(&plan), revealed);
+}
@@ -10,7 +10,9 @@ use core::cmp::Ordering;
 
 /// Add a layer of decimal scaling cast to the expression
 /// so that we can do binary operations on it
-#[expect(clippy::missing_panics_doc, reason = "Precision can not be invalid")]
+///
+/// Integer expressions are treated as decimals with scale 0. Returns an error if the
+/// scaled expression would need a precision above 75.
 fn decimal_scale_cast_expr(
     from_proof_expr: DynProofExpr,
     from_scale: i8,
@@ -23,16 +25,12 @@ fn decimal_scale_cast_expr(
         });
     }
     let from_precision_value = from_proof_expr.data_type().precision_value().unwrap_or(0);
-    let to_precision_value = u8::try_from(
-        i16::from(from_precision_value) + i16::from(to_scale - from_scale).min(75_i16),
-    )
-    .expect("Precision is definitely valid");
+    let to_precision_value =
+        i16::from(from_precision_value) + i16::from(to_scale) - i16::from(from_scale);
+    let to_precision = Precision::new(u8::try_from(to_precision_value).unwrap_or(u8::MAX))?;
     DynProofExpr::try_new_scaling_cast(
         from_proof_expr,
-        ColumnType::Decimal75(
-            Precision::new(to_precision_value).expect("Precision is definitely valid"),
-            to_scale,
-        ),
+        ColumnType::Decimal75(to_precision, to_scale),
     )
 }
 
@@ -40,6 +38,10 @@ fn decimal_scale_cast_expr(
 ///
 /// We use this function so that binary ops for numeric types no longer
 /// need to keep track of scale
+///
+/// # Errors
+/// Returns an error if the side with the smaller scale would need a precision above 75
+/// once scaled.
 pub fn scale_cast_binary_op(
     left_proof_expr: DynProofExpr,
     right_proof_expr: DynProofExpr,
@@ -73,7 +75,10 @@ pub fn scale_cast_binary_op(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::base::database::{ColumnRef, TableRef};
+    use crate::base::{
+        database::{ColumnRef, TableRef},
+        math::decimal::DecimalError,
+    };
 
     #[expect(non_snake_case)]
     fn COLUMN1_BOOLEAN() -> DynProofExpr {
@@ -173,6 +178,17 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn we_cannot_convert_decimal_scale_cast_expr_beyond_the_maximum_precision() {
+        let proof_expr = decimal_scale_cast_expr(COLUMN3_DECIMAL_75_10(), 10, 11);
+        assert!(matches!(
+            proof_expr,
+            Err(AnalyzeError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision { .. }
+            })
+        ));
+    }
+
     // scale_cast_binary_op
     #[test]
     fn we_can_convert_scale_cast_binary_op_upcasting_left() {
@@ -235,4 +251,25 @@ mod tests {
         let proof_exprs = scale_cast_binary_op(left.clone(), right.clone()).unwrap();
         assert_eq!(proof_exprs, (left, right));
     }
+
+    #[test]
+    fn we_cannot_convert_scale_cast_binary_op_if_the_scaled_side_overflows() {
+        let left = COLUMN3_DECIMAL_75_10();
+        let right = DynProofExpr::new_column(ColumnRef::new(
+            TableRef::from_names(Some("namespace"), "table_name"),
+            "column2".into(),
+            ColumnType::Decimal75(
+                Precision::new(5).expect("Precision is definitely valid"),
+                12,
+            ),
+        ));
+        for (left, right) in [(left.clone(), right.clone()), (right, left)] {
+            assert!(matches!(
+                scale_cast_binary_op(left, right),
+                Err(AnalyzeError::DecimalConversionError {
+                    source: DecimalError::InvalidPrecision { .. }
+                })
+            ));
+        }
+    }
 }
@@ -0,0 +1,550 @@
+use crate::base::{
+    commitment::{
+        AppendColumnCommitmentsError, ColumnCommitments, ColumnCommitmentsFromColumnsError,
+        Commitment,
+    },
+    database::{ColumnField, ColumnType, OwnedColumn},
+    math::{
+        decimal::{try_convert_intermediate_decimal_to_scalar, Precision, MAX_SUPPORTED_PRECISION},
+        BigDecimalExt,
+    },
+    scalar::Scalar,
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use bigdecimal::BigDecimal;
+use csv::{ReaderBuilder, StringRecord};
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+use std::io::Read;
+
+/// The default number of rows [`commit_csv`] keeps in memory at a time
+pub const DEFAULT_CSV_BATCH_SIZE: usize = 1 << 16;
+
+/// Errors that can occur when committing to a CSV
+#[derive(Debug, Snafu)]
+pub enum CsvCommitmentError {
+    /// The CSV could not be read.
+    #[snafu(display("failed to read the csv: {source}"))]
+    Read {
+        /// The underlying source error
+        source: csv::Error,
+    },
+    /// The header of the CSV doesn't name the columns of the schema.
+    #[snafu(display("the csv has columns {header:?}, but the schema has columns {schema:?}"))]
+    HeaderMismatch {
+        /// The column names in the header of the CSV
+        header: Vec<String>,
+        /// The column names of the schema
+        schema: Vec<String>,
+    },
+    /// A row of the CSV has another number of fields than the header.
+    #[snafu(display("line {line} of the csv has {actual} fields, but {expected} were expected"))]
+    FieldCountMismatch {
+        /// The 1-indexed line of the row
+        line: u64,
+        /// The number of columns of the schema
+        expected: usize,
+        /// The number of fields of the row
+        actual: usize,
+    },
+    /// A field of the CSV can't be parsed as a value of its column.
+    #[snafu(display(
+        "line {line} of the csv has {value:?} in column {column}, which is not a valid {column_type}"
+    ))]
+    InvalidValue {
+        /// The 1-indexed line of the row
+        line: u64,
+        /// The name of the column
+        column: String,
+        /// The unparsable field
+        value: String,
+        /// The type of the column
+        column_type: ColumnType,
+    },
+    /// A column of the schema has a type that can't be read from a CSV.
+    #[snafu(display("{column_type} columns can't be read from a csv"))]
+    UnsupportedCsvColumnType {
+        /// The type of the column
+        column_type: ColumnType,
+    },
+    /// The commitments to the columns could not be created.
+    #[snafu(transparent)]
+    FromColumns {
+        /// The underlying source error
+        source: ColumnCommitmentsFromColumnsError,
+    },
+    /// A batch of rows could not be appended to the commitments.
+    #[snafu(transparent)]
+    Append {
+        /// The underlying source error
+        source: AppendColumnCommitmentsError,
+    },
+}
+
+/// The types a column of a CSV may still have while its schema is inferred
+struct TypeCandidates {
+    boolean: bool,
+    bigint: bool,
+    /// The number of integer digits and the scale needed by the values so far, if they are all
+    /// decimals
+    decimal: Option<(u64, u64)>,
+}
+
+impl TypeCandidates {
+    fn new() -> Self {
+        Self {
+            boolean: true,
+            bigint: true,
+            decimal: Some((0, 0)),
+        }
+    }
+
+    fn observe(&mut self, value: &str) {
+        self.boolean &= parse_bool(value).is_some();
+        self.bigint &= value.parse::<i64>().is_ok();
+        self.decimal = self.decimal.and_then(|(integer_digits, scale)| {
+            let decimal = value.parse::<BigDecimal>().ok()?;
+            let value_scale = decimal.scale();
+            let value_integer_digits = i64::try_from(decimal.precision())
+                .ok()?
+                .saturating_sub(value_scale);
+            Some((
+                integer_digits.max(u64::try_from(value_integer_digits).unwrap_or(0)),
+                scale.max(u64::try_from(value_scale).unwrap_or(0)),
+            ))
+        });
+    }
+
+    fn column_type(&self, has_values: bool) -> ColumnType {
+        match self.decimal {
+            _ if !has_values => ColumnType::VarChar,
+            _ if self.boolean => ColumnType::Boolean,
+            _ if self.bigint => ColumnType::BigInt,
+            Some((integer_digits, scale))
+                if integer_digits + scale <= u64::from(MAX_SUPPORTED_PRECISION) =>
+            {
+                let precision = u8::try_from((integer_digits + scale).max(1))
+                    .expect("the precision is at most the maximum supported precision");
+                let scale = i8::try_from(scale).expect("the scale is at most the precision");
+                ColumnType::Decimal75(
+                    Precision::new(precision).expect("the precision is supported"),
+                    scale,
+                )
+            }
+            _ => ColumnType::VarChar,
+        }
+    }
+}
+
+/// Parses `true` or `false`, ignoring case
+fn parse_bool(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Returns the 1-indexed line of a record
+fn line_of(record: &StringRecord) -> u64 {
+    record.position().map_or(0, csv::Position::line)
+}
+
+/// Creates a reader of a CSV, along with the column names in its header row
+fn csv_reader<R: Read>(reader: R) -> Result<(csv::Reader<R>, Vec<String>), CsvCommitmentError> {
+    let mut reader = ReaderBuilder::new().flexible(true).from_reader(reader);
+    let header = reader
+        .headers()
+        .map_err(|source| CsvCommitmentError::Read { source })?
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    Ok((reader, header))
+}
+
+/// Reads the next row of a CSV into `record`, returning `false` at the end of the CSV
+fn read_row<R: Read>(
+    reader: &mut csv::Reader<R>,
+    record: &mut StringRecord,
+    expected: usize,
+) -> Result<bool, CsvCommitmentError> {
+    if !reader
+        .read_record(record)
+        .map_err(|source| CsvCommitmentError::Read { source })?
+    {
+        return Ok(false);
+    }
+    if record.len() != expected {
+        return Err(CsvCommitmentError::FieldCountMismatch {
+            line: line_of(record),
+            expected,
+            actual: record.len(),
+        });
+    }
+    Ok(true)
+}
+
+/// Infers the schema of a CSV with a header row, streaming through its rows without keeping them.
+///
+/// Each column is inferred as the first of `BOOLEAN`, `BIGINT` and `DECIMAL` which all of its
+/// values can be parsed as, with the smallest precision and scale fitting every value.
+/// Columns whose values are not all numbers, need a precision above 75 or have no values at all
+/// are inferred as `VARCHAR`.
+///
+/// Since the CSV is consumed, committing to it with [`commit_csv`] needs another reader, e.g. a
+/// file reopened from the start.
+///
+/// # Errors
+/// Returns [`CsvCommitmentError::FieldCountMismatch`] with the line of the first row with another
+/// number of fields than the header, or [`CsvCommitmentError::Read`] if the CSV can't be read.
+pub fn infer_csv_schema(reader: impl Read) -> Result<Vec<ColumnField>, CsvCommitmentError> {
+    let (mut reader, header) = csv_reader(reader)?;
+    let mut candidates: Vec<_> = header.iter().map(|_| TypeCandidates::new()).collect();
+    let mut record = StringRecord::new();
+    let mut has_values = false;
+    while read_row(&mut reader, &mut record, header.len())? {
+        has_values = true;
+        for (candidate, value) in candidates.iter_mut().zip(record.iter()) {
+            candidate.observe(value);
+        }
+    }
+    Ok(header
+        .into_iter()
+        .zip(candidates)
+        .map(|(name, candidate)| {
+            ColumnField::new(Ident::new(name), candidate.column_type(has_values))
+        })
+        .collect())
+}
+
+/// Returns an empty column of the given type
+fn empty_column<S: Scalar>(column_type: ColumnType) -> Result<OwnedColumn<S>, CsvCommitmentError> {
+    Ok(match column_type {
+        ColumnType::Boolean => OwnedColumn::Boolean(Vec::new()),
+        ColumnType::Uint8 => OwnedColumn::Uint8(Vec::new()),
+        ColumnType::TinyInt => OwnedColumn::TinyInt(Vec::new()),
+        ColumnType::SmallInt => OwnedColumn::SmallInt(Vec::new()),
+        ColumnType::Int => OwnedColumn::Int(Vec::new()),
+        ColumnType::BigInt => OwnedColumn::BigInt(Vec::new()),
+        ColumnType::Int128 => OwnedColumn::Int128(Vec::new()),
+        ColumnType::VarChar => OwnedColumn::VarChar(Vec::new()),
+        ColumnType::Decimal75(precision, scale) => {
+            OwnedColumn::Decimal75(precision, scale, Vec::new())
+        }
+        ColumnType::TimestampTZ(unit, timezone) => {
+            OwnedColumn::TimestampTZ(unit, timezone, Vec::new())
+        }
+        ColumnType::VarBinary | ColumnType::Scalar => {
+            return Err(CsvCommitmentError::UnsupportedCsvColumnType { column_type })
+        }
+    })
+}
+
+/// Parses a field and pushes it onto a column, returning `None` if it isn't a valid value
+fn push_value<S: Scalar>(column: &mut OwnedColumn<S>, value: &str) -> Option<()> {
+    match column {
+        OwnedColumn::Boolean(values) => values.push(parse_bool(value)?),
+        OwnedColumn::Uint8(values) => values.push(value.parse().ok()?),
+        OwnedColumn::TinyInt(values) => values.push(value.parse().ok()?),
+        OwnedColumn::SmallInt(values) => values.push(value.parse().ok()?),
+        OwnedColumn::Int(values) => values.push(value.parse().ok()?),
+        OwnedColumn::BigInt(values) | OwnedColumn::TimestampTZ(_, _, values) => {
+            values.push(value.parse().ok()?);
+        }
+        OwnedColumn::Int128(values) => values.push(value.parse().ok()?),
+        OwnedColumn::VarChar(values) => values.push(value.to_string()),
+        OwnedColumn::Decimal75(precision, scale, values) => values.push(
+            try_convert_intermediate_decimal_to_scalar(&value.parse().ok()?, *precision, *scale)
+                .ok()?,
+        ),
+        OwnedColumn::VarBinary(_) | OwnedColumn::Scalar(_) => return None,
+    }
+    Some(())
+}
+
+/// Computes the [`ColumnCommitments`] to a CSV with a header row, streaming through it in batches
+/// of `batch_size` rows so that the whole CSV is never held in memory.
+///
+/// The header must name the columns of `schema`, in order, which can be given or inferred with
+/// [`infer_csv_schema`]. The first row is committed to at generator `offset`, and every batch is
+/// appended with [`ColumnCommitments::try_append_rows_with_offset`] at the offset of its first
+/// row, so the result is the same as committing to the fully loaded columns with
+/// [`ColumnCommitments::try_from_columns_with_offset`].
+///
+/// `BOOLEAN` values are `true` or `false`, ignoring case, and `TIMESTAMP` values are integers in
+/// the time unit of their column.
+///
+/// # Errors
+/// Returns [`CsvCommitmentError::FieldCountMismatch`] or [`CsvCommitmentError::InvalidValue`]
+/// with the line of the first malformed row, [`CsvCommitmentError::HeaderMismatch`] if the header
+/// doesn't match the schema and an error if the CSV can't be read or the columns can't be
+/// committed to.
+///
+/// # Panics
+/// Panics if `batch_size` is zero.
+pub fn commit_csv<C: Commitment>(
+    reader: impl Read,
+    schema: &[ColumnField],
+    offset: usize,
+    batch_size: usize,
+    setup: &C::PublicSetup<'_>,
+) -> Result<ColumnCommitments<C>, CsvCommitmentError> {
+    assert!(batch_size > 0, "the batch size must be positive");
+    let (mut reader, header) = csv_reader(reader)?;
+    let idents: Vec<Ident> = schema.iter().map(ColumnField::name).collect();
+    if header.len() != idents.len()
+        || header
+            .iter()
+            .zip(&idents)
+            .any(|(name, ident)| Ident::new(name.as_str()) != *ident)
+    {
+        return Err(CsvCommitmentError::HeaderMismatch {
+            header,
+            schema: idents.into_iter().map(|ident| ident.value).collect(),
+        });
+    }
+    let empty_batch = schema
+        .iter()
+        .map(|field| empty_column::<C::Scalar>(field.data_type()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut commitments = ColumnCommitments::<C>::try_from_columns_with_offset(
+        idents.iter().zip(&empty_batch),
+        offset,
+        setup,
+    )?;
+    let mut record = StringRecord::new();
+    let mut rows_committed = 0;
+    let mut is_finished = false;
+    while !is_finished {
+        let mut batch = empty_batch.clone();
+        let mut batch_length = 0;
+        while batch_length < batch_size {
+            if !read_row(&mut reader, &mut record, idents.len())? {
+                is_finished = true;
+                break;
+            }
+            for ((column, value), field) in batch.iter_mut().zip(record.iter()).zip(schema) {
+                push_value(column, value).ok_or_else(|| CsvCommitmentError::InvalidValue {
+                    line: line_of(&record),
+                    column: field.name().value,
+                    value: value.to_string(),
+                    column_type: field.data_type(),
+                })?;
+            }
+            batch_length += 1;
+        }
+        if batch_length > 0 {
+            commitments.try_append_rows_with_offset(
+                idents.iter().zip(&batch),
+                offset + rows_committed,
+                setup,
+            )?;
+            rows_committed += batch_length;
+        }
+    }
+    Ok(commitments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{
+        commitment::naive_commitment::NaiveCommitment,
+        database::{owned_table_utility::*, OwnedTable},
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+        scalar::test_scalar::TestScalar,
+    };
+
+    const ACCOUNTS_CSV: &str = "\
+id,balance,active,owner
+1,100.5,true,Ann
+2,-20,false,Bob
+3,0.25,TRUE,Cat
+4,7,false,Dan
+5,1000,true,Eve
+";
+
+    fn accounts_table() -> OwnedTable<TestScalar> {
+        owned_table([
+            bigint("id", [1_i64, 2, 3, 4, 5]),
+            decimal75("balance", 6, 2, [10050_i64, -2000, 25, 700, 100_000]),
+            boolean("active", [true, false, true, false, true]),
+            varchar("owner", ["Ann", "Bob", "Cat", "Dan", "Eve"]),
+        ])
+    }
+
+    fn commit_table(
+        table: &OwnedTable<TestScalar>,
+        offset: usize,
+    ) -> ColumnCommitments<NaiveCommitment> {
+        ColumnCommitments::try_from_columns_with_offset(table.inner_table(), offset, &()).unwrap()
+    }
+
+    #[test]
+    fn we_can_infer_the_schema_of_a_csv() {
+        assert_eq!(
+            infer_csv_schema(ACCOUNTS_CSV.as_bytes()).unwrap(),
+            vec![
+                ColumnField::new("id".into(), ColumnType::BigInt),
+                ColumnField::new(
+                    "balance".into(),
+                    ColumnType::Decimal75(Precision::new(6).unwrap(), 2)
+                ),
+                ColumnField::new("active".into(), ColumnType::Boolean),
+                ColumnField::new("owner".into(), ColumnType::VarChar),
+            ]
+        );
+        let too_precise = format!("a\n1.{}\n", "1".repeat(75));
+        assert_eq!(
+            infer_csv_schema(too_precise.as_bytes()).unwrap(),
+            vec![ColumnField::new("a".into(), ColumnType::VarChar)]
+        );
+        assert_eq!(
+            infer_csv_schema("a,b\n".as_bytes()).unwrap(),
+            vec![
+                ColumnField::new("a".into(), ColumnType::VarChar),
+                ColumnField::new("b".into(), ColumnType::VarChar),
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_commit_to_a_csv_in_batches() {
+        let schema = infer_csv_schema(ACCOUNTS_CSV.as_bytes()).unwrap();
+        let expected = commit_table(&accounts_table(), 3);
+        for batch_size in [1, 2, 5, DEFAULT_CSV_BATCH_SIZE] {
+            let commitments =
+                commit_csv::<NaiveCommitment>(ACCOUNTS_CSV.as_bytes(), &schema, 3, batch_size, &())
+                    .unwrap();
+            assert_eq!(commitments, expected);
+        }
+    }
+
+    #[test]
+    fn we_can_commit_to_a_csv_with_a_given_schema() {
+        let schema = vec![
+            ColumnField::new("id".into(), ColumnType::TinyInt),
+            ColumnField::new(
+                "time".into(),
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            ),
+        ];
+        let commitments = commit_csv::<NaiveCommitment>(
+            "id,time\n1,1700000000\n-2,0\n".as_bytes(),
+            &schema,
+            0,
+            1,
+            &(),
+        )
+        .unwrap();
+        let expected = commit_table(
+            &owned_table([
+                tinyint("id", [1_i8, -2]),
+                timestamptz(
+                    "time",
+                    PoSQLTimeUnit::Second,
+                    PoSQLTimeZone::utc(),
+                    [1_700_000_000_i64, 0],
+                ),
+            ]),
+            0,
+        );
+        assert_eq!(commitments, expected);
+    }
+
+    #[test]
+    fn we_can_commit_to_an_empty_csv() {
+        let schema = infer_csv_schema(ACCOUNTS_CSV.as_bytes()).unwrap();
+        let commitments = commit_csv::<NaiveCommitment>(
+            "id,balance,active,owner\n".as_bytes(),
+            &schema,
+            0,
+            2,
+            &(),
+        )
+        .unwrap();
+        assert_eq!(commitments.len(), 4);
+        let empty_table = owned_table([
+            bigint("id", [0_i64; 0]),
+            decimal75("balance", 6, 2, [0_i64; 0]),
+            boolean("active", [false; 0]),
+            varchar("owner", [""; 0]),
+        ]);
+        assert_eq!(commitments, commit_table(&empty_table, 0));
+    }
+
+    #[test]
+    fn we_cannot_commit_to_a_csv_with_a_malformed_row() {
+        let schema = infer_csv_schema(ACCOUNTS_CSV.as_bytes()).unwrap();
+        let missing_field = "id,balance,active,owner\n1,1.5,true,Ann\n2,3,false\n";
+        assert!(matches!(
+            infer_csv_schema(missing_field.as_bytes()),
+            Err(CsvCommitmentError::FieldCountMismatch {
+                line: 3,
+                expected: 4,
+                actual: 3
+            })
+        ));
+        assert!(matches!(
+            commit_csv::<NaiveCommitment>(missing_field.as_bytes(), &schema, 0, 1, &()),
+            Err(CsvCommitmentError::FieldCountMismatch {
+                line: 3,
+                expected: 4,
+                actual: 3
+            })
+        ));
+        let invalid_value = "id,balance,active,owner\n1,1.5,true,Ann\n2,3,false,Bob\n3,4,yes,Cat\n";
+        let error = commit_csv::<NaiveCommitment>(invalid_value.as_bytes(), &schema, 0, 2, &())
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            CsvCommitmentError::InvalidValue {
+                line: 4,
+                column,
+                value,
+                column_type: ColumnType::Boolean,
+            } if column == "active" && value == "yes"
+        ));
+        assert_eq!(
+            error.to_string(),
+            r#"line 4 of the csv has "yes" in column active, which is not a valid BOOLEAN"#
+        );
+        let too_precise = "id,balance,active,owner\n1,1.125,true,Ann\n";
+        assert!(matches!(
+            commit_csv::<NaiveCommitment>(too_precise.as_bytes(), &schema, 0, 1, &()),
+            Err(CsvCommitmentError::InvalidValue { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_commit_to_a_csv_that_does_not_match_the_schema() {
+        let schema = infer_csv_schema(ACCOUNTS_CSV.as_bytes()).unwrap();
+        assert!(matches!(
+            commit_csv::<NaiveCommitment>(
+                "id,balance,owner,active\n".as_bytes(),
+                &schema,
+                0,
+                1,
+                &()
+            ),
+            Err(CsvCommitmentError::HeaderMismatch { .. })
+        ));
+        assert!(matches!(
+            commit_csv::<NaiveCommitment>("id,balance\n".as_bytes(), &schema, 0, 1, &()),
+            Err(CsvCommitmentError::HeaderMismatch { .. })
+        ));
+        let schema = vec![ColumnField::new("a".into(), ColumnType::VarBinary)];
+        assert!(matches!(
+            commit_csv::<NaiveCommitment>("a\n".as_bytes(), &schema, 0, 1, &()),
+            Err(CsvCommitmentError::UnsupportedCsvColumnType {
+                column_type: ColumnType::VarBinary
+            })
+        ));
+    }
+}
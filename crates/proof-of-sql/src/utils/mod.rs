@@ -4,3 +4,7 @@ pub mod parse;
 
 /// This module provides logging utilities for the library, including functions to log system memory usage.
 pub mod log;
+
+/// Streaming commitments to CSV files, with schema inference.
+#[cfg(feature = "csv")]
+pub mod csv_commitment;
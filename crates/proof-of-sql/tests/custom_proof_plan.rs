@@ -0,0 +1,202 @@
+//! An example of writing a custom [`ProofPlan`] outside of the crate.
+//!
+//! `PassThroughExec` outputs the columns of a table unchanged. Rather than reusing the evaluations
+//! of the input columns, as `TableExec` does, it commits to a copy of each column and proves that
+//! the copy equals the input, so it touches every part of the public plan API:
+//! 1. the first round declares the length of the output,
+//! 2. the final round commits to the copies and produces one constraint per column,
+//! 3. the verifier consumes the chi evaluation of the output length and the evaluations of the
+//!    copies in the same order, checks the constraints and returns one evaluation per output
+//!    column.
+#![cfg(feature = "test")]
+use ark_std::test_rng;
+use bumpalo::Bump;
+use proof_of_sql::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnRef, ColumnType, LiteralValue, OwnedTable,
+            OwnedTableTestAccessor, Table, TableEvaluation, TableRef, TestAccessor,
+        },
+        scalar::Scalar,
+        IndexMap, IndexSet, MultilinearExtension, PlaceholderResult, ProofError,
+    },
+    proof_primitive::dory::{
+        DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
+    },
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, SumcheckSubpolynomialType,
+        VerifiableQueryResult, VerificationBuilder,
+    },
+};
+use serde::Serialize;
+use sqlparser::ast::Ident;
+
+/// Outputs the columns in `schema` of the table `table_ref` unchanged
+#[derive(Debug, Serialize)]
+struct PassThroughExec {
+    table_ref: TableRef,
+    schema: Vec<ColumnField>,
+}
+
+impl ProofPlan for PassThroughExec {
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<TableRef, IndexMap<Ident, S>>,
+        _result: Option<&OwnedTable<S>>,
+        _chi_eval_map: &IndexMap<TableRef, S>,
+        _params: &[LiteralValue],
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let input_evals = &accessor[&self.table_ref];
+        // The output has as many rows as the input, so the prover must not declare another length
+        let length =
+            builder
+                .table_length(&self.table_ref)
+                .ok_or(ProofError::VerificationError {
+                    error: "the length of the input table is unknown",
+                })?;
+        let chi_eval = builder.try_consume_chi_evaluation_of_length(length)?;
+        let copy_evals = builder.try_consume_final_round_mle_evaluations(self.schema.len())?;
+        for (field, copy_eval) in self.schema.iter().zip(&copy_evals) {
+            // copy - input = 0
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                *copy_eval - input_evals[&field.name()],
+                1,
+            )?;
+        }
+        Ok(TableEvaluation::new(copy_evals, chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.schema.clone()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.schema
+            .iter()
+            .map(|field| ColumnRef::new(self.table_ref.clone(), field.name(), field.data_type()))
+            .collect()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        IndexSet::from_iter([self.table_ref.clone()])
+    }
+}
+
+impl PassThroughExec {
+    /// The input columns in the order of the schema
+    fn output_table<'a, S: Scalar>(
+        &self,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        let table = &table_map[&self.table_ref];
+        Table::try_from_iter(
+            self.schema
+                .iter()
+                .map(|field| (field.name(), table.inner_table()[&field.name()])),
+        )
+        .unwrap()
+    }
+}
+
+impl ProverEvaluate for PassThroughExec {
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        _params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let output = self.output_table(table_map);
+        builder.produce_chi_evaluation_length(output.num_rows());
+        Ok(output)
+    }
+
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+        _params: &[LiteralValue],
+    ) -> PlaceholderResult<Table<'a, S>> {
+        let output = self.output_table(table_map);
+        for column in output.columns().copied() {
+            builder.produce_intermediate_mle(column);
+        }
+        for column in output.columns().copied() {
+            let copy: Box<dyn MultilinearExtension<S> + 'a> = Box::new(column);
+            let input: Box<dyn MultilinearExtension<S> + 'a> = Box::new(column);
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![(S::ONE, vec![copy]), (-S::ONE, vec![input])],
+            );
+        }
+        Ok(output)
+    }
+}
+
+#[test]
+fn we_can_prove_and_verify_a_custom_pass_through_plan() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let table_ref = TableRef::new("sxt", "table");
+    let data = owned_table([
+        bigint("a", [1_i64, -2, 3, 4, 5]),
+        varchar("b", ["x", "y", "z", "", "w"]),
+        boolean("c", [true, false, true, true, false]),
+    ]);
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(table_ref.clone(), data.clone(), 0);
+    let plan = PassThroughExec {
+        table_ref,
+        schema: vec![
+            ColumnField::new("a".into(), ColumnType::BigInt),
+            ColumnField::new("b".into(), ColumnType::VarChar),
+            ColumnField::new("c".into(), ColumnType::Boolean),
+        ],
+    };
+
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        &plan,
+        &accessor,
+        &&prover_setup,
+        &[],
+    )
+    .unwrap();
+    let table = verifiable_result
+        .verify(&plan, &accessor, &&verifier_setup, &[])
+        .unwrap()
+        .table;
+    assert_eq!(table, data);
+}
+
+#[test]
+fn we_cannot_verify_a_custom_pass_through_plan_with_a_tampered_result() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let table_ref = TableRef::new("sxt", "table");
+    let data = owned_table([bigint("a", [1_i64, 2, 3])]);
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(table_ref.clone(), data, 0);
+    let plan = PassThroughExec {
+        table_ref,
+        schema: vec![ColumnField::new("a".into(), ColumnType::BigInt)],
+    };
+
+    let mut verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        &plan,
+        &accessor,
+        &&prover_setup,
+        &[],
+    )
+    .unwrap();
+    verifiable_result.result = owned_table([bigint("a", [1_i64, 2, 4])]);
+    assert!(verifiable_result
+        .verify(&plan, &accessor, &&verifier_setup, &[])
+        .is_err());
+}
@@ -9,13 +9,17 @@ use proof_of_sql::proof_primitive::hyperkzg::{
     nova_commitment_key_to_hyperkzg_public_setup, HyperKZGCommitmentEvaluationProof,
 };
 use proof_of_sql::{
-    base::database::{
-        owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef, TestAccessor,
+    base::{
+        commitment::Commitment,
+        database::{
+            owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
     },
     proof_primitive::{
         dory::{
             DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
-            DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
+            DynamicDoryCommitment, DynamicDoryEvaluationProof, ProverSetup, PublicParameters,
+            VerifierSetup,
         },
         inner_product::curve_25519_scalar::Curve25519Scalar,
     },
@@ -120,6 +124,59 @@ fn we_can_prove_a_minimal_filter_query_with_dynamic_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_a_query_with_the_smallest_dynamic_dory_setup_that_is_large_enough() {
+    let table = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6, 7, 8]),
+        boolean("b", [true, false, true, true, false, false, true, false]),
+    ]);
+    let sql = "SELECT a FROM table WHERE b";
+
+    // Plan the query against a setup that is certainly large enough
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(TableRef::new("sxt", "table"), table.clone(), 0);
+    let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+    // 8 rows need 3 sumcheck variables, so the proof opens exactly 8 generators
+    let required = query
+        .proof_expr()
+        .required_setup_size(&accessor, &[])
+        .unwrap();
+    assert_eq!(required, 8);
+
+    // max_nu = 2 is the smallest setup with enough generators
+    let setup_size = |max_nu| {
+        let prover_setup = ProverSetup::from(&PublicParameters::test_rand(max_nu, &mut test_rng()));
+        DynamicDoryCommitment::num_generators(&&prover_setup).unwrap()
+    };
+    assert!(setup_size(1) < required);
+    assert!(setup_size(2) >= required);
+
+    let public_parameters = PublicParameters::test_rand(2, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(TableRef::new("sxt", "table"), table, 0);
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&prover_setup,
+        &[],
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&verifier_setup, &[])
+        .unwrap()
+        .table;
+    assert_eq!(
+        owned_table_result,
+        owned_table([bigint("a", [1_i64, 3, 4, 7])])
+    );
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_prove_a_basic_equality_query_with_curve25519() {
@@ -219,6 +276,64 @@ fn we_can_prove_a_basic_equality_query_with_hyperkzg() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+#[cfg(feature = "hyperkzg_proof")]
+fn we_can_prove_one_serialized_plan_with_both_dory_and_hyperkzg() {
+    use nova_snark::{
+        provider::hyperkzg::{CommitmentEngine, CommitmentKey, EvaluationEngine},
+        traits::{commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait},
+    };
+    use proof_of_sql::sql::proof_plans::DynProofPlan;
+    type CP = HyperKZGCommitmentEvaluationProof;
+
+    let table = owned_table([
+        int128("a", [-i128::MAX, 5, 7, i128::MAX]),
+        bigint("b", [1_i64, 2, -3, 0]),
+    ]);
+    // The negative literal is a different scalar in each field, and `b` is scale cast
+    let sql = "SELECT * FROM table WHERE a = -170141183460469231731687303715884105727 OR b > 1.5";
+    let expected_result = owned_table([int128("a", [-i128::MAX, 5]), bigint("b", [1_i64, 2])]);
+
+    // Dory
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(TableRef::new("sxt", "table"), table.clone(), 0);
+    let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+    let serialized_plan = postcard::to_allocvec(query.proof_expr()).unwrap();
+    let plan: DynProofPlan = postcard::from_bytes(&serialized_plan).unwrap();
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        &plan,
+        &accessor,
+        &&prover_setup,
+        &[],
+    )
+    .unwrap();
+    let dory_result = verifiable_result
+        .verify(&plan, &accessor, &&verifier_setup, &[])
+        .unwrap()
+        .table;
+
+    // HyperKZG, with the same serialized plan
+    let ck: CommitmentKey<_> = CommitmentEngine::setup(b"test", 32);
+    let (_, vk) = EvaluationEngine::setup(&ck);
+    let ark_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+    let mut accessor = OwnedTableTestAccessor::<CP>::new_empty_with_setup(&ark_setup[..]);
+    accessor.add_table(TableRef::new("sxt", "table"), table, 0);
+    let plan: DynProofPlan = postcard::from_bytes(&serialized_plan).unwrap();
+    let verifiable_result =
+        VerifiableQueryResult::<CP>::new(&plan, &accessor, &&ark_setup[..], &[]).unwrap();
+    let hyperkzg_result = verifiable_result
+        .verify(&plan, &accessor, &&vk, &[])
+        .unwrap()
+        .table;
+
+    assert_eq!(dory_result, expected_result);
+    assert_eq!(hyperkzg_result, expected_result);
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_prove_a_basic_inequality_query_with_curve25519() {
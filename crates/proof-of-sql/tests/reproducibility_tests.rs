@@ -0,0 +1,91 @@
+//! Tests that proofs are reproducible, i.e. that proving the same query over the same data twice
+//! results in byte-identical serialized proofs.
+#![cfg(feature = "test")]
+#![cfg_attr(test, expect(clippy::missing_panics_doc))]
+use ark_std::test_rng;
+#[cfg(feature = "blitzar")]
+use proof_of_sql::base::commitment::InnerProductProof;
+#[cfg(feature = "hyperkzg_proof")]
+use proof_of_sql::proof_primitive::hyperkzg::{
+    nova_commitment_key_to_hyperkzg_public_setup, HyperKZGCommitmentEvaluationProof,
+};
+use proof_of_sql::{
+    base::{
+        commitment::CommitmentEvaluationProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef, TestAccessor},
+    },
+    proof_primitive::dory::{
+        DoryEvaluationProof, DoryProverPublicSetup, DynamicDoryEvaluationProof, ProverSetup,
+        PublicParameters,
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+
+/// Prove a query with a filter, a group by and arithmetic twice and return both serialized
+/// results
+fn prove_twice<CP: CommitmentEvaluationProof>(
+    setup: CP::ProverPublicSetup<'_>,
+) -> (Vec<u8>, Vec<u8>) {
+    let mut accessor = OwnedTableTestAccessor::<CP>::new_empty_with_setup(setup);
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7, 8]),
+            bigint("b", [1, 0, 1, 1, 0, 1, 0, 1]),
+            varchar("c", ["x", "y", "x", "z", "y", "x", "z", "x"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT c, SUM(a) AS s, COUNT(*) AS n FROM table WHERE a - b > 1 GROUP BY c"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let [first, second] = [(); 2].map(|()| {
+        let verifiable_result =
+            VerifiableQueryResult::<CP>::new(query.proof_expr(), &accessor, &setup, &[]).unwrap();
+        bincode::serde::encode_to_vec(&verifiable_result, bincode::config::legacy()).unwrap()
+    });
+    (first, second)
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn proofs_with_curve25519_are_reproducible() {
+    let (first, second) = prove_twice::<InnerProductProof>(());
+    assert_eq!(first, second);
+}
+
+#[test]
+fn proofs_with_dory_are_reproducible() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let (first, second) =
+        prove_twice::<DoryEvaluationProof>(DoryProverPublicSetup::new(&prover_setup, 3));
+    assert_eq!(first, second);
+}
+
+#[test]
+fn proofs_with_dynamic_dory_are_reproducible() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let (first, second) = prove_twice::<DynamicDoryEvaluationProof>(&prover_setup);
+    assert_eq!(first, second);
+}
+
+#[test]
+#[cfg(feature = "hyperkzg_proof")]
+fn proofs_with_hyperkzg_are_reproducible() {
+    use nova_snark::{
+        provider::hyperkzg::{CommitmentEngine, CommitmentKey},
+        traits::commitment::CommitmentEngineTrait,
+    };
+
+    let ck: CommitmentKey<_> = CommitmentEngine::setup(b"test", 32);
+    let ark_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+    let (first, second) = prove_twice::<HyperKZGCommitmentEvaluationProof>(&ark_setup[..]);
+    assert_eq!(first, second);
+}
@@ -0,0 +1,458 @@
+//! The golden proof fixtures.
+//!
+//! This module is used by the `generate-proof-fixtures` binary, which writes the fixtures and
+//! checks that they verify. The `verify_*_fixture` functions are also meant for the integration
+//! test that will check the committed corpus. See `tests/fixtures/README.md` for the layout of a
+//! fixture and the policy on regenerating them.
+use ark_std::test_rng;
+#[cfg(feature = "blitzar")]
+use proof_of_sql::base::commitment::InnerProductProof;
+#[cfg(feature = "hyperkzg_proof")]
+use proof_of_sql::proof_primitive::hyperkzg::{
+    nova_commitment_key_to_hyperkzg_public_setup, HyperKZGCommitmentEvaluationProof, HyperKZGEngine,
+};
+use proof_of_sql::{
+    base::{
+        commitment::{
+            CommitmentEvaluationProof, QueryCommitments, QueryCommitmentsExt, TableCommitment,
+        },
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef, TestAccessor},
+    },
+    proof_primitive::dory::{
+        DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
+        DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
+    },
+    sql::{
+        parse::QueryExpr,
+        proof::{ProofPlan, VerifiableQueryResult},
+        proof_plans::DynProofPlan,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use snafu::Snafu;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The version of the fixture format.
+///
+/// Fixtures are only ever regenerated together with a bump of this version, see
+/// `tests/fixtures/README.md`.
+pub const FIXTURE_FORMAT_VERSION: u32 = 1;
+
+/// The schema of the fixture table.
+const SCHEMA: &str = "sxt";
+/// The name of the fixture table.
+const TABLE: &str = "table";
+/// The query proven by every fixture.
+const QUERY: &str = "SELECT c, SUM(a) AS s, COUNT(*) AS n FROM table WHERE a - b > 1 GROUP BY c";
+
+const PARAMS_FILE: &str = "params.json";
+const PLAN_FILE: &str = "plan.bin";
+const COMMITMENTS_FILE: &str = "commitments.bin";
+const VERIFIER_SETUP_FILE: &str = "verifier_setup.bin";
+const PROOF_FILE: &str = "proof.bin";
+const RESULT_HASH_FILE: &str = "result_hash.txt";
+
+/// The directory of the fixtures of the inner product proof.
+#[cfg(feature = "blitzar")]
+const INNER_PRODUCT_PROOF: &str = "inner_product_proof";
+/// The directory of the fixtures of the Dory PCS.
+const DORY: &str = "dory";
+/// The directory of the fixtures of the Dynamic Dory PCS.
+const DYNAMIC_DORY: &str = "dynamic_dory";
+/// The directory of the fixtures of the `HyperKZG` PCS.
+#[cfg(feature = "hyperkzg_proof")]
+const HYPERKZG: &str = "hyperkzg";
+
+/// `nu` of the public parameters of the Dory fixture.
+const DORY_NU: usize = 4;
+/// `sigma` of the Dory fixture.
+const DORY_SIGMA: usize = 3;
+/// `nu` of the public parameters of the Dynamic Dory fixture.
+const DYNAMIC_DORY_NU: usize = 5;
+/// The number of generators of the `HyperKZG` fixture.
+#[cfg(feature = "hyperkzg_proof")]
+const HYPERKZG_SETUP_SIZE: usize = 32;
+
+/// Errors from writing or verifying a fixture.
+#[derive(Debug, Snafu)]
+pub enum FixtureError {
+    /// The fixture doesn't exist.
+    #[snafu(display(
+        "fixture {path:?} is missing; generate it with \
+         `cargo run --bin generate-proof-fixtures --features test,utils,hyperkzg_proof`"
+    ))]
+    Missing {
+        /// The missing file.
+        path: PathBuf,
+    },
+    /// A fixture of the current format version already exists.
+    #[snafu(display(
+        "fixture {path:?} already exists for format version {version}; bump \
+         FIXTURE_FORMAT_VERSION to regenerate it deliberately"
+    ))]
+    AlreadyExists {
+        /// The existing fixture.
+        path: PathBuf,
+        /// The format version of the existing fixture.
+        version: u32,
+    },
+    /// A fixture file could not be read or written.
+    #[snafu(display("failed to access {path:?}: {source}"))]
+    Io {
+        /// The file.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+    /// A fixture file could not be serialized or deserialized.
+    #[snafu(display("failed to (de)serialize {path:?}: {message}"))]
+    Serialization {
+        /// The file.
+        path: PathBuf,
+        /// The underlying error.
+        message: String,
+    },
+    /// The fixture query could not be planned or proven.
+    #[snafu(display("failed to prove the fixture query: {message}"))]
+    Prove {
+        /// The underlying error.
+        message: String,
+    },
+    /// The fixture was written with a different format version.
+    #[snafu(display("fixture has format version {found}, expected {expected}"))]
+    FormatVersion {
+        /// The current format version.
+        expected: u32,
+        /// The format version of the fixture.
+        found: u32,
+    },
+    /// The stored proof doesn't verify.
+    #[snafu(display("fixture proof doesn't verify: {message}"))]
+    Verification {
+        /// The underlying error.
+        message: String,
+    },
+    /// The verified result doesn't match the stored result hash.
+    #[snafu(display("fixture result hash is {found}, expected {expected}"))]
+    ResultHash {
+        /// The stored result hash.
+        expected: String,
+        /// The hash of the verified result.
+        found: String,
+    },
+}
+
+/// Result type for fixture operations.
+pub type FixtureResult<T> = Result<T, FixtureError>;
+
+/// The human-readable parameters of a fixture.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureParams {
+    /// The fixture format version, see [`FIXTURE_FORMAT_VERSION`].
+    pub format_version: u32,
+    /// The version of the crate that generated the fixture.
+    pub crate_version: String,
+    /// The commitment scheme.
+    pub scheme: String,
+    /// How the setup was generated.
+    pub setup: String,
+    /// The schema of the table.
+    pub schema: String,
+    /// The name of the table.
+    pub table: String,
+    /// The proven query.
+    pub query: String,
+}
+
+/// Returns the directory of the checked-in fixture corpus.
+#[must_use]
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+}
+
+fn read(path: &Path) -> FixtureResult<Vec<u8>> {
+    if !path.exists() {
+        return MissingSnafu { path }.fail();
+    }
+    fs::read(path).map_err(|source| FixtureError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write(path: &Path, bytes: &[u8]) -> FixtureResult<()> {
+    fs::write(path, bytes).map_err(|source| FixtureError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn serialization_error(path: &Path, error: &impl ToString) -> FixtureError {
+    FixtureError::Serialization {
+        path: path.to_path_buf(),
+        message: error.to_string(),
+    }
+}
+
+/// Plans and proofs are stored in the legacy bincode encoding.
+fn write_bincode(path: &Path, value: &impl Serialize) -> FixtureResult<()> {
+    let bytes = bincode::serde::encode_to_vec(value, bincode::config::legacy())
+        .map_err(|error| serialization_error(path, &error))?;
+    write(path, &bytes)
+}
+
+fn read_bincode<T: DeserializeOwned>(path: &Path) -> FixtureResult<T> {
+    let (value, _) = bincode::serde::decode_from_slice(&read(path)?, bincode::config::legacy())
+        .map_err(|error| serialization_error(path, &error))?;
+    Ok(value)
+}
+
+/// Commitments and setups are stored with postcard, which is how they are stored elsewhere.
+fn write_postcard(path: &Path, value: &impl Serialize) -> FixtureResult<()> {
+    let bytes = postcard::to_allocvec(value).map_err(|error| serialization_error(path, &error))?;
+    write(path, &bytes)
+}
+
+fn read_postcard<T: DeserializeOwned>(path: &Path) -> FixtureResult<T> {
+    postcard::from_bytes(&read(path)?).map_err(|error| serialization_error(path, &error))
+}
+
+/// Returns the hash of the serialized result table.
+fn result_hash(table: &impl Serialize) -> String {
+    let bytes = bincode::serde::encode_to_vec(table, bincode::config::legacy())
+        .expect("tables are always serializable");
+    blake3::hash(&bytes).to_hex().to_string()
+}
+
+/// Proves the fixture query and writes the fixture to `dir`.
+///
+/// The proof is verified before anything is written. Fails if `dir` already holds a fixture of the
+/// current format version.
+fn write_fixture<CP: CommitmentEvaluationProof>(
+    dir: &Path,
+    scheme: &str,
+    setup: String,
+    prover_setup: CP::ProverPublicSetup<'_>,
+    verifier_setup: CP::VerifierPublicSetup<'_>,
+    serializable_verifier_setup: &impl Serialize,
+) -> FixtureResult<()> {
+    let params_path = dir.join(PARAMS_FILE);
+    if params_path.exists() {
+        let params: FixtureParams = serde_json::from_slice(&read(&params_path)?)
+            .map_err(|error| serialization_error(&params_path, &error))?;
+        if params.format_version == FIXTURE_FORMAT_VERSION {
+            return AlreadyExistsSnafu {
+                path: dir,
+                version: params.format_version,
+            }
+            .fail();
+        }
+    }
+
+    let table_ref = TableRef::new(SCHEMA, TABLE);
+    let mut accessor = OwnedTableTestAccessor::<CP>::new_empty_with_setup(prover_setup);
+    accessor.add_table(
+        table_ref.clone(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7, 8]),
+            bigint("b", [1, 0, 1, 1, 0, 1, 0, 1]),
+            varchar("c", ["x", "y", "x", "z", "y", "x", "z", "x"]),
+        ]),
+        0,
+    );
+    let prove_error = |error: &dyn ToString| FixtureError::Prove {
+        message: error.to_string(),
+    };
+    let query = QueryExpr::try_new(
+        QUERY.parse().map_err(|error| prove_error(&error))?,
+        SCHEMA.into(),
+        &accessor,
+    )
+    .map_err(|error| prove_error(&error))?;
+    let plan = query.proof_expr();
+    let verifiable_result = VerifiableQueryResult::<CP>::new(plan, &accessor, &prover_setup, &[])
+        .map_err(|error| prove_error(&error))?;
+    let commitments =
+        QueryCommitments::from_accessor_with_max_bounds(plan.get_column_references(), &accessor);
+    let result = verifiable_result
+        .clone()
+        .verify_with_commitments(plan, &commitments, &verifier_setup, &[])
+        .map_err(|error| FixtureError::Verification {
+            message: error.to_string(),
+        })?;
+
+    fs::create_dir_all(dir).map_err(|source| FixtureError::Io {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    let params = FixtureParams {
+        format_version: FIXTURE_FORMAT_VERSION,
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        scheme: scheme.to_string(),
+        setup,
+        schema: SCHEMA.to_string(),
+        table: TABLE.to_string(),
+        query: QUERY.to_string(),
+    };
+    let params_json = serde_json::to_vec_pretty(&params)
+        .map_err(|error| serialization_error(&params_path, &error))?;
+    write(&params_path, &params_json)?;
+    write_bincode(&dir.join(PLAN_FILE), plan)?;
+    write_postcard(&dir.join(COMMITMENTS_FILE), &commitments[&table_ref])?;
+    write_postcard(&dir.join(VERIFIER_SETUP_FILE), serializable_verifier_setup)?;
+    write_bincode(&dir.join(PROOF_FILE), &verifiable_result)?;
+    write(
+        &dir.join(RESULT_HASH_FILE),
+        result_hash(&result.table).as_bytes(),
+    )
+}
+
+/// Loads the fixture in `dir` and checks that its proof verifies to the stored result.
+fn verify_fixture<CP: CommitmentEvaluationProof>(
+    dir: &Path,
+    verifier_setup: CP::VerifierPublicSetup<'_>,
+) -> FixtureResult<()> {
+    let params_path = dir.join(PARAMS_FILE);
+    let params: FixtureParams = serde_json::from_slice(&read(&params_path)?)
+        .map_err(|error| serialization_error(&params_path, &error))?;
+    if params.format_version != FIXTURE_FORMAT_VERSION {
+        return FormatVersionSnafu {
+            expected: FIXTURE_FORMAT_VERSION,
+            found: params.format_version,
+        }
+        .fail();
+    }
+    let plan: DynProofPlan = read_bincode(&dir.join(PLAN_FILE))?;
+    let commitment: TableCommitment<CP::Commitment> = read_postcard(&dir.join(COMMITMENTS_FILE))?;
+    let verifiable_result: VerifiableQueryResult<CP> = read_bincode(&dir.join(PROOF_FILE))?;
+    let expected_hash = String::from_utf8_lossy(&read(&dir.join(RESULT_HASH_FILE))?)
+        .trim()
+        .to_string();
+
+    let commitments: QueryCommitments<CP::Commitment> =
+        [(TableRef::new(&params.schema, &params.table), commitment)]
+            .into_iter()
+            .collect();
+    let result = verifiable_result
+        .verify_with_commitments(&plan, &commitments, &verifier_setup, &[])
+        .map_err(|error| FixtureError::Verification {
+            message: error.to_string(),
+        })?;
+    let found_hash = result_hash(&result.table);
+    if found_hash != expected_hash {
+        return ResultHashSnafu {
+            expected: expected_hash,
+            found: found_hash,
+        }
+        .fail();
+    }
+    Ok(())
+}
+
+/// Writes the fixture of the inner product proof, which has no setup.
+#[cfg(feature = "blitzar")]
+pub fn write_inner_product_proof_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    write_fixture::<InnerProductProof>(
+        &fixtures_dir.join(INNER_PRODUCT_PROOF),
+        INNER_PRODUCT_PROOF,
+        "none".to_string(),
+        (),
+        (),
+        &(),
+    )
+}
+
+/// Verifies the fixture of the inner product proof.
+#[cfg(feature = "blitzar")]
+pub fn verify_inner_product_proof_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    verify_fixture::<InnerProductProof>(&fixtures_dir.join(INNER_PRODUCT_PROOF), ())
+}
+
+/// Writes the fixture of the Dory PCS.
+///
+/// The stored verifier setup is the verifier setup together with sigma.
+pub fn write_dory_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    let public_parameters = PublicParameters::test_rand(DORY_NU, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    write_fixture::<DoryEvaluationProof>(
+        &fixtures_dir.join(DORY),
+        DORY,
+        format!("PublicParameters::test_rand({DORY_NU}, test_rng()), sigma = {DORY_SIGMA}"),
+        DoryProverPublicSetup::new(&prover_setup, DORY_SIGMA),
+        DoryVerifierPublicSetup::new(&verifier_setup, DORY_SIGMA),
+        &(&verifier_setup, DORY_SIGMA),
+    )
+}
+
+/// Verifies the fixture of the Dory PCS against its stored verifier setup.
+pub fn verify_dory_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    let dir = fixtures_dir.join(DORY);
+    let (verifier_setup, sigma): (VerifierSetup, usize) =
+        read_postcard(&dir.join(VERIFIER_SETUP_FILE))?;
+    verify_fixture::<DoryEvaluationProof>(
+        &dir,
+        DoryVerifierPublicSetup::new(&verifier_setup, sigma),
+    )
+}
+
+/// Writes the fixture of the Dynamic Dory PCS.
+pub fn write_dynamic_dory_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    let public_parameters = PublicParameters::test_rand(DYNAMIC_DORY_NU, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    write_fixture::<DynamicDoryEvaluationProof>(
+        &fixtures_dir.join(DYNAMIC_DORY),
+        DYNAMIC_DORY,
+        format!("PublicParameters::test_rand({DYNAMIC_DORY_NU}, test_rng())"),
+        &prover_setup,
+        &verifier_setup,
+        &verifier_setup,
+    )
+}
+
+/// Verifies the fixture of the Dynamic Dory PCS against its stored verifier setup.
+pub fn verify_dynamic_dory_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    let dir = fixtures_dir.join(DYNAMIC_DORY);
+    let verifier_setup: VerifierSetup = read_postcard(&dir.join(VERIFIER_SETUP_FILE))?;
+    verify_fixture::<DynamicDoryEvaluationProof>(&dir, &verifier_setup)
+}
+
+/// Writes the fixture of the `HyperKZG` PCS.
+///
+/// The setup is random, which is fine because only its verifier key is needed to verify.
+#[cfg(feature = "hyperkzg_proof")]
+pub fn write_hyperkzg_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    use nova_snark::{
+        provider::hyperkzg::{CommitmentEngine, CommitmentKey, EvaluationEngine},
+        traits::{commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait},
+    };
+
+    let ck: CommitmentKey<HyperKZGEngine> =
+        CommitmentEngine::setup(b"fixture", HYPERKZG_SETUP_SIZE);
+    let (_, vk) = EvaluationEngine::setup(&ck);
+    let prover_setup = nova_commitment_key_to_hyperkzg_public_setup(&ck);
+    write_fixture::<HyperKZGCommitmentEvaluationProof>(
+        &fixtures_dir.join(HYPERKZG),
+        HYPERKZG,
+        format!("CommitmentEngine::setup(b\"fixture\", {HYPERKZG_SETUP_SIZE})"),
+        &prover_setup[..],
+        &vk,
+        &vk,
+    )
+}
+
+/// Verifies the fixture of the `HyperKZG` PCS against its stored verifier key.
+#[cfg(feature = "hyperkzg_proof")]
+pub fn verify_hyperkzg_fixture(fixtures_dir: &Path) -> FixtureResult<()> {
+    use nova_snark::provider::hyperkzg::VerifierKey;
+
+    let dir = fixtures_dir.join(HYPERKZG);
+    let vk: VerifierKey<HyperKZGEngine> = read_postcard(&dir.join(VERIFIER_SETUP_FILE))?;
+    verify_fixture::<HyperKZGCommitmentEvaluationProof>(&dir, &vk)
+}
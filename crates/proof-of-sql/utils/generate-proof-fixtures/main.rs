@@ -0,0 +1,49 @@
+//! Utility to regenerate the golden proof fixtures in `tests/fixtures`.
+//!
+//! Fixtures are only regenerated deliberately, when a format break is intended. The utility refuses
+//! to overwrite fixtures of the current `FIXTURE_FORMAT_VERSION`, so that version has to be bumped
+//! first. See `tests/fixtures/README.md` for the full policy.
+//!
+//! ```bash
+//! cargo run --bin generate-proof-fixtures --features test,utils,hyperkzg_proof
+//! ```
+mod fixture;
+
+use clap::Parser;
+use fixture::{
+    fixtures_dir, verify_dory_fixture, verify_dynamic_dory_fixture, verify_hyperkzg_fixture,
+    verify_inner_product_proof_fixture, write_dory_fixture, write_dynamic_dory_fixture,
+    write_hyperkzg_fixture, write_inner_product_proof_fixture, FixtureResult,
+    FIXTURE_FORMAT_VERSION,
+};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// The directory to write the fixtures to (defaults to the checked-in corpus)
+    #[arg(short, long)]
+    target: Option<PathBuf>,
+}
+
+fn main() -> FixtureResult<()> {
+    let cli = Cli::parse();
+    let target = cli.target.unwrap_or_else(fixtures_dir);
+
+    write_inner_product_proof_fixture(&target)?;
+    write_dory_fixture(&target)?;
+    write_dynamic_dory_fixture(&target)?;
+    write_hyperkzg_fixture(&target)?;
+
+    // Check that the written fixtures load and verify.
+    verify_inner_product_proof_fixture(&target)?;
+    verify_dory_fixture(&target)?;
+    verify_dynamic_dory_fixture(&target)?;
+    verify_hyperkzg_fixture(&target)?;
+
+    println!(
+        "Wrote fixtures of format version {FIXTURE_FORMAT_VERSION} to {}",
+        target.display()
+    );
+    Ok(())
+}